@@ -20,6 +20,54 @@ mod m20251230_150000_add_llm_info_to_generation_logs;
 // Fixture files: src/fixtures/*.yaml
 
 mod m20260102_094239_add_timeout_secs_to_llm_configs;
+mod m20261016_100000_add_sections_to_company_rules;
+mod m20261016_110000_add_usage_stats_to_knowledge_bases;
+mod m20261016_120000_project_output_settings;
+mod m20261016_130000_naming_profiles;
+mod m20261016_140000_saved_intents;
+mod m20261016_150000_race_results;
+mod m20261016_160000_add_quality_score_to_generation_logs;
+mod m20261016_170000_knowledge_base_revisions;
+mod m20261016_180000_add_knowledge_revisions_to_generation_logs;
+mod m20261016_190000_add_company_to_tenant_tables;
+mod m20261016_200000_add_job_lease_to_generation_logs;
+mod m20261016_210000_add_stage_timestamps_to_generation_logs;
+mod m20261016_220000_checklist_items;
+mod m20261016_230000_add_project_to_generation_logs;
+mod m20261017_000000_add_quarantine_to_kb_and_rules;
+mod m20261017_010000_pipeline_profiles;
+mod m20261017_010100_add_pipeline_profile_to_generation_logs;
+mod m20261017_020000_add_replay_of_to_generation_logs;
+mod m20261017_030000_distillation_examples;
+mod m20261017_030100_distillation_settings;
+mod m20261017_040000_add_file_headers_to_project_output_settings;
+mod m20261017_050000_add_git_integration_to_project_output_settings;
+mod m20261017_060000_add_issue_key_to_generation_logs;
+mod m20261017_060100_add_issue_tracker_to_project_output_settings;
+mod m20261017_070000_notification_settings;
+mod m20261017_080000_validation_plugins;
+mod m20261017_090000_admin_audit_logs;
+mod m20261017_100000_template_activations;
+mod m20261017_110000_canary_rollouts;
+mod m20261017_110100_add_canary_to_generation_logs;
+mod m20261017_120000_batch_schedules;
+mod m20261017_120100_calendar_overrides;
+mod m20261017_130000_schema_snapshots;
+mod m20261017_140000_add_lineage_to_generation_logs;
+mod m20261017_150000_retention_policies;
+mod m20261017_150100_add_role_to_users;
+mod m20261017_150200_add_artifact_expiry_to_generation_logs;
+mod m20261017_160000_screen_registrations;
+mod m20261017_170000_add_error_code_to_generation_logs;
+mod m20261017_180000_queue_settings;
+mod m20261017_190000_add_token_usage_to_generation_logs;
+mod m20261017_200000_add_fallback_priority_to_llm_configs;
+mod m20261017_210000_column_archetypes;
+mod m20261018_090000_error_code_catalogs;
+mod m20261018_100000_add_company_to_project_output_settings;
+mod m20261018_110000_add_company_to_pipeline_profiles;
+mod m20261018_120000_add_company_to_naming_profiles;
+mod m20261018_130000_add_company_to_saved_intents;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -38,6 +86,54 @@ impl MigratorTrait for Migrator {
             Box::new(m20251230_140000_remove_company_id_from_company_rules::Migration),
             Box::new(m20251230_150000_add_llm_info_to_generation_logs::Migration),
             Box::new(m20260102_094239_add_timeout_secs_to_llm_configs::Migration),
+            Box::new(m20261016_100000_add_sections_to_company_rules::Migration),
+            Box::new(m20261016_110000_add_usage_stats_to_knowledge_bases::Migration),
+            Box::new(m20261016_120000_project_output_settings::Migration),
+            Box::new(m20261016_130000_naming_profiles::Migration),
+            Box::new(m20261016_140000_saved_intents::Migration),
+            Box::new(m20261016_150000_race_results::Migration),
+            Box::new(m20261016_160000_add_quality_score_to_generation_logs::Migration),
+            Box::new(m20261016_170000_knowledge_base_revisions::Migration),
+            Box::new(m20261016_180000_add_knowledge_revisions_to_generation_logs::Migration),
+            Box::new(m20261016_190000_add_company_to_tenant_tables::Migration),
+            Box::new(m20261016_200000_add_job_lease_to_generation_logs::Migration),
+            Box::new(m20261016_210000_add_stage_timestamps_to_generation_logs::Migration),
+            Box::new(m20261016_220000_checklist_items::Migration),
+            Box::new(m20261016_230000_add_project_to_generation_logs::Migration),
+            Box::new(m20261017_000000_add_quarantine_to_kb_and_rules::Migration),
+            Box::new(m20261017_010000_pipeline_profiles::Migration),
+            Box::new(m20261017_010100_add_pipeline_profile_to_generation_logs::Migration),
+            Box::new(m20261017_020000_add_replay_of_to_generation_logs::Migration),
+            Box::new(m20261017_030000_distillation_examples::Migration),
+            Box::new(m20261017_030100_distillation_settings::Migration),
+            Box::new(m20261017_040000_add_file_headers_to_project_output_settings::Migration),
+            Box::new(m20261017_050000_add_git_integration_to_project_output_settings::Migration),
+            Box::new(m20261017_060000_add_issue_key_to_generation_logs::Migration),
+            Box::new(m20261017_060100_add_issue_tracker_to_project_output_settings::Migration),
+            Box::new(m20261017_070000_notification_settings::Migration),
+            Box::new(m20261017_080000_validation_plugins::Migration),
+            Box::new(m20261017_090000_admin_audit_logs::Migration),
+            Box::new(m20261017_100000_template_activations::Migration),
+            Box::new(m20261017_110000_canary_rollouts::Migration),
+            Box::new(m20261017_110100_add_canary_to_generation_logs::Migration),
+            Box::new(m20261017_120000_batch_schedules::Migration),
+            Box::new(m20261017_120100_calendar_overrides::Migration),
+            Box::new(m20261017_130000_schema_snapshots::Migration),
+            Box::new(m20261017_140000_add_lineage_to_generation_logs::Migration),
+            Box::new(m20261017_150000_retention_policies::Migration),
+            Box::new(m20261017_150100_add_role_to_users::Migration),
+            Box::new(m20261017_150200_add_artifact_expiry_to_generation_logs::Migration),
+            Box::new(m20261017_160000_screen_registrations::Migration),
+            Box::new(m20261017_170000_add_error_code_to_generation_logs::Migration),
+            Box::new(m20261017_180000_queue_settings::Migration),
+            Box::new(m20261017_190000_add_token_usage_to_generation_logs::Migration),
+            Box::new(m20261017_200000_add_fallback_priority_to_llm_configs::Migration),
+            Box::new(m20261017_210000_column_archetypes::Migration),
+            Box::new(m20261018_090000_error_code_catalogs::Migration),
+            Box::new(m20261018_100000_add_company_to_project_output_settings::Migration),
+            Box::new(m20261018_110000_add_company_to_pipeline_profiles::Migration),
+            Box::new(m20261018_120000_add_company_to_naming_profiles::Migration),
+            Box::new(m20261018_130000_add_company_to_saved_intents::Migration),
             // inject-above (do not remove this comment)
         ]
     }
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Structured rule sections (naming, layout, security, comments, forbidden_apis)
+        // NULL means only the legacy free-text columns are used
+        m.alter_table(
+            Table::alter()
+                .table(CompanyRules::Table)
+                .add_column(
+                    ColumnDef::new(CompanyRules::Sections)
+                        .json()
+                        .null()
+                )
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(CompanyRules::Table)
+                .drop_column(CompanyRules::Sections)
+                .to_owned(),
+        )
+        .await
+    }
+}
+
+#[derive(Iden)]
+enum CompanyRules {
+    Table,
+    Sections,
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Prompt usage statistics for ranking boosts:
+        // how often an entry was included in a generation prompt, how many of those
+        // generations passed validation cleanly, and the total findings they produced
+        m.alter_table(
+            Table::alter()
+                .table(KnowledgeBases::Table)
+                .add_column(
+                    ColumnDef::new(KnowledgeBases::UsageCount)
+                        .integer()
+                        .not_null()
+                        .default(0)
+                )
+                .add_column(
+                    ColumnDef::new(KnowledgeBases::CleanCount)
+                        .integer()
+                        .not_null()
+                        .default(0)
+                )
+                .add_column(
+                    ColumnDef::new(KnowledgeBases::FindingCount)
+                        .integer()
+                        .not_null()
+                        .default(0)
+                )
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(KnowledgeBases::Table)
+                .drop_column(KnowledgeBases::UsageCount)
+                .drop_column(KnowledgeBases::CleanCount)
+                .drop_column(KnowledgeBases::FindingCount)
+                .to_owned(),
+        )
+        .await
+    }
+}
+
+#[derive(Iden)]
+enum KnowledgeBases {
+    Table,
+    UsageCount,
+    CleanCount,
+    FindingCount,
+}
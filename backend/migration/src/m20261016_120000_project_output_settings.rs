@@ -0,0 +1,26 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "project_output_settings",
+            &[
+            ("id", ColType::PkAuto),
+            ("project", ColType::StringUniq),
+            ("encoding", ColType::String),
+            ("bom", ColType::Boolean),
+            ("line_ending", ColType::String),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "project_output_settings").await
+    }
+}
@@ -0,0 +1,27 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "naming_profiles",
+            &[
+            ("id", ColType::PkAuto),
+            ("name", ColType::StringUniq),
+            ("xml_template", ColType::StringNull),
+            ("js_template", ColType::StringNull),
+            ("java_template", ColType::StringNull),
+            ("mapper_xml_template", ColType::StringNull),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "naming_profiles").await
+    }
+}
@@ -0,0 +1,25 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "saved_intents",
+            &[
+            ("id", ColType::PkAuto),
+            ("name", ColType::StringUniq),
+            ("tags", ColType::JsonNull),
+            ("intent", ColType::Json),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "saved_intents").await
+    }
+}
@@ -0,0 +1,29 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "race_results",
+            &[
+            ("id", ColType::PkAuto),
+            ("product", ColType::String),
+            ("winner_provider", ColType::String),
+            ("winner_model", ColType::String),
+            ("loser_provider", ColType::String),
+            ("loser_model", ColType::String),
+            ("loser_outcome", ColType::String),
+            ("winner_ms", ColType::Integer),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "race_results").await
+    }
+}
@@ -0,0 +1,40 @@
+//! Add quality_score to generation_logs table
+//!
+//! Deterministic 0-100 score of the generated artifacts (see `ArtifactScorer`).
+//! Null for failed generations and for rows logged before scoring existed.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(ColumnDef::new(GenerationLogs::QualityScore).integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::QualityScore)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    QualityScore,
+}
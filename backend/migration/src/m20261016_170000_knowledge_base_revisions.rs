@@ -0,0 +1,54 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "knowledge_base_revisions",
+            &[
+            ("id", ColType::PkAuto),
+            ("revision", ColType::Integer),
+            ("name", ColType::String),
+            ("category", ColType::String),
+            ("component", ColType::StringNull),
+            ("section", ColType::StringNull),
+            ("content", ColType::Text),
+            ("relevance_tags", ColType::JsonNull),
+            ("priority", ColType::StringNull),
+            ],
+            &[
+            ("knowledge_base", "knowledge_base_id"),
+            ]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-knowledge_base_revisions-knowledge_base_id-revision")
+                .table(Alias::new("knowledge_base_revisions"))
+                .col(Alias::new("knowledge_base_id"))
+                .col(Alias::new("revision"))
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        // Existing entries start their history at their current version
+        m.get_connection()
+            .execute_unprepared(
+                "INSERT INTO knowledge_base_revisions \
+                 (created_at, updated_at, knowledge_base_id, revision, name, category, component, section, content, relevance_tags, priority) \
+                 SELECT updated_at, updated_at, id, COALESCE(version, 1), name, category, component, section, content, relevance_tags, priority \
+                 FROM knowledge_bases",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "knowledge_base_revisions").await
+    }
+}
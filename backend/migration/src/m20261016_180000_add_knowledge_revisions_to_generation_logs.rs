@@ -0,0 +1,41 @@
+//! Add knowledge_revisions to generation_logs table
+//!
+//! JSON array of the knowledge base entry revisions included in the prompt
+//! (`[{"id": 12, "revision": 3}, ...]`, in prompt order), so the exact
+//! knowledge context of a past generation can be rebuilt.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(ColumnDef::new(GenerationLogs::KnowledgeRevisions).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::KnowledgeRevisions)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    KnowledgeRevisions,
+}
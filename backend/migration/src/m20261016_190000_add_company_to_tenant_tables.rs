@@ -0,0 +1,68 @@
+//! Add company (tenant) column to tenant-scoped tables
+//!
+//! Multi-company deployments tag rows with a company code. NULL means the row
+//! is shared (templates, rules, knowledge, LLM configs) or belongs to the
+//! platform itself (users, generation logs); existing rows keep NULL, so a
+//! single-company install behaves as before.
+
+use sea_orm_migration::prelude::*;
+
+const TENANT_TABLES: [&str; 6] = [
+    "users",
+    "prompt_templates",
+    "company_rules",
+    "knowledge_bases",
+    "generation_logs",
+    "llm_configs",
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        for table in TENANT_TABLES {
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .add_column(ColumnDef::new(Alias::new("company")).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+            m.create_index(
+                Index::create()
+                    .name(format!("idx_{}_company", table))
+                    .table(Alias::new(table))
+                    .col(Alias::new("company"))
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        for table in TENANT_TABLES {
+            m.drop_index(
+                Index::drop()
+                    .name(format!("idx_{}_company", table))
+                    .table(Alias::new(table))
+                    .to_owned(),
+            )
+            .await?;
+
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .drop_column(Alias::new("company"))
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
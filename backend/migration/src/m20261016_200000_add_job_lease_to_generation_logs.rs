@@ -0,0 +1,54 @@
+//! Add job lease columns to generation_logs table
+//!
+//! A worker that claims a job holds it until `lease_expires_at` and renews
+//! the lease while it runs. Jobs whose lease ran out (the worker crashed or
+//! lost its connection) are claimed again by another instance; `attempts`
+//! counts the claims so a job that keeps killing workers is eventually failed.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(
+                        ColumnDef::new(GenerationLogs::LeaseExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(GenerationLogs::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::LeaseExpiresAt)
+                    .drop_column(GenerationLogs::Attempts)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    LeaseExpiresAt,
+    Attempts,
+}
@@ -0,0 +1,59 @@
+//! Add stage timestamps to generation_logs table
+//!
+//! `queued_at`, `started_at` and `completed_at` already bound a job; these
+//! split the run into preparation, LLM call and post-processing so SLA
+//! breaches can be traced to the stage that caused them.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(
+                        ColumnDef::new(GenerationLogs::LlmStartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(GenerationLogs::LlmFinishedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(GenerationLogs::PipelineFinishedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::LlmStartedAt)
+                    .drop_column(GenerationLogs::LlmFinishedAt)
+                    .drop_column(GenerationLogs::PipelineFinishedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    LlmStartedAt,
+    LlmFinishedAt,
+    PipelineFinishedAt,
+}
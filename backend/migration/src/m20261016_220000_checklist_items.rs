@@ -0,0 +1,29 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "checklist_items",
+            &[
+            ("id", ColType::PkAuto),
+            ("product", ColType::String),
+            ("screen_type", ColType::StringNull),
+            ("label", ColType::String),
+            ("target", ColType::String),
+            ("pattern", ColType::String),
+            ("is_active", ColType::BooleanNull),
+            ("company", ColType::StringNull),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "checklist_items").await
+    }
+}
@@ -0,0 +1,62 @@
+//! Add project column to generation_logs table
+//!
+//! The project a screen was generated for (`context.project`; the package base
+//! for Spring), so earlier generations of the same screen in a project can be
+//! found before generating it again.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(ColumnDef::new(GenerationLogs::Project).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_generation_logs_product_project")
+                    .table(GenerationLogs::Table)
+                    .col(GenerationLogs::Product)
+                    .col(GenerationLogs::Project)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_generation_logs_product_project")
+                    .table(GenerationLogs::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::Project)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    Product,
+    Project,
+}
@@ -0,0 +1,71 @@
+//! Add quarantine columns to knowledge_bases and company_rules
+//!
+//! Content flagged by the content guard (prompt-injection phrasing, oversized
+//! entries) is quarantined: kept out of prompts until an administrator other
+//! than the one who saved it approves it.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(KnowledgeBases::Table)
+                    .add_column(ColumnDef::new(KnowledgeBases::QuarantineReason).text().null())
+                    .add_column(ColumnDef::new(KnowledgeBases::QuarantinedBy).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CompanyRules::Table)
+                    .add_column(ColumnDef::new(CompanyRules::QuarantineReason).text().null())
+                    .add_column(ColumnDef::new(CompanyRules::QuarantinedBy).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CompanyRules::Table)
+                    .drop_column(CompanyRules::QuarantineReason)
+                    .drop_column(CompanyRules::QuarantinedBy)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(KnowledgeBases::Table)
+                    .drop_column(KnowledgeBases::QuarantineReason)
+                    .drop_column(KnowledgeBases::QuarantinedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum KnowledgeBases {
+    Table,
+    QuarantineReason,
+    QuarantinedBy,
+}
+
+#[derive(DeriveIden)]
+enum CompanyRules {
+    Table,
+    QuarantineReason,
+    QuarantinedBy,
+}
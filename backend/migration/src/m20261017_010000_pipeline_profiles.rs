@@ -0,0 +1,26 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "pipeline_profiles",
+            &[
+            ("id", ColType::PkAuto),
+            ("name", ColType::StringUniq),
+            ("description", ColType::StringNull),
+            ("default_for_product", ColType::StringNull),
+            ("passes", ColType::Json),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "pipeline_profiles").await
+    }
+}
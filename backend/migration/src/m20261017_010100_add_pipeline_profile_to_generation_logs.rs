@@ -0,0 +1,43 @@
+//! Add pipeline profile columns to generation_logs table
+//!
+//! Name of the pipeline profile a generation ran with and the per-pass
+//! overrides it applied (copied, so later profile edits don't rewrite history).
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(ColumnDef::new(GenerationLogs::PipelineProfile).string().null())
+                    .add_column(ColumnDef::new(GenerationLogs::PipelineOverrides).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::PipelineProfile)
+                    .drop_column(GenerationLogs::PipelineOverrides)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    PipelineProfile,
+    PipelineOverrides,
+}
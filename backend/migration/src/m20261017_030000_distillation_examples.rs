@@ -0,0 +1,35 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "distillation_examples",
+            &[
+            ("id", ColType::PkAuto),
+            ("product", ColType::String),
+            ("screen_type", ColType::String),
+            ("features", ColType::Text),
+            ("ui_intent", ColType::Text),
+            ("xml", ColType::Text),
+            ("javascript", ColType::Text),
+            ("source_provider", ColType::String),
+            ("source_model", ColType::String),
+            ("quality_score", ColType::IntegerNull),
+            ("company", ColType::StringNull),
+            ("pinned", ColType::Boolean),
+            ("use_count", ColType::Integer),
+            ("last_used_at", ColType::TimestampWithTimeZoneNull),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "distillation_examples").await
+    }
+}
@@ -0,0 +1,26 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "distillation_settings",
+            &[
+            ("id", ColType::PkAuto),
+            ("enabled", ColType::Boolean),
+            ("max_examples", ColType::Integer),
+            ("eviction", ColType::String),
+            ("min_quality", ColType::Integer),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "distillation_settings").await
+    }
+}
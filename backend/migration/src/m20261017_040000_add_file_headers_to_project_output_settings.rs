@@ -0,0 +1,39 @@
+//! Add file_headers column to project_output_settings table
+//!
+//! Header comment templates stamped on packaged files, per artifact type.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .add_column(ColumnDef::new(ProjectOutputSettings::FileHeaders).json().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .drop_column(ProjectOutputSettings::FileHeaders)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectOutputSettings {
+    Table,
+    FileHeaders,
+}
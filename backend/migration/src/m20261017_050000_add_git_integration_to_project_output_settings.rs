@@ -0,0 +1,39 @@
+//! Add git_integration column to project_output_settings table
+//!
+//! Git remote the generated files of a project are pushed to (branch per generation).
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .add_column(ColumnDef::new(ProjectOutputSettings::GitIntegration).json().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .drop_column(ProjectOutputSettings::GitIntegration)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectOutputSettings {
+    Table,
+    GitIntegration,
+}
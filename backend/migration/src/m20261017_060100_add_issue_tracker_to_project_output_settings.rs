@@ -0,0 +1,39 @@
+//! Add issue_tracker column to project_output_settings table
+//!
+//! Jira/Redmine instance that gets a comment for every generation linked to an issue.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .add_column(ColumnDef::new(ProjectOutputSettings::IssueTracker).json().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .drop_column(ProjectOutputSettings::IssueTracker)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectOutputSettings {
+    Table,
+    IssueTracker,
+}
@@ -0,0 +1,31 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "notification_settings",
+            &[
+            ("id", ColType::PkAuto),
+            ("provider", ColType::String),
+            ("webhook_url", ColType::StringNull),
+            ("channel", ColType::StringNull),
+            ("admin_base_url", ColType::StringNull),
+            ("notify_completed", ColType::Boolean),
+            ("notify_failed", ColType::Boolean),
+            ("notify_failure_spike", ColType::Boolean),
+            ("spike_threshold", ColType::Integer),
+            ("spike_window_minutes", ColType::Integer),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "notification_settings").await
+    }
+}
@@ -0,0 +1,29 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "validation_plugins",
+            &[
+            ("id", ColType::PkAuto),
+            ("name", ColType::StringUniq),
+            ("description", ColType::TextNull),
+            ("wasm", ColType::Blob),
+            ("sha256", ColType::String),
+            ("size_bytes", ColType::Integer),
+            ("enabled", ColType::Boolean),
+            ("uploaded_by", ColType::StringNull),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "validation_plugins").await
+    }
+}
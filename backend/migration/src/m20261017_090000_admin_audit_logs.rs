@@ -0,0 +1,40 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "admin_audit_logs",
+            &[
+            ("id", ColType::PkAuto),
+            ("entity_type", ColType::String),
+            ("entity_id", ColType::Integer),
+            ("entity_name", ColType::String),
+            ("action", ColType::String),
+            ("actor", ColType::String),
+            ("company", ColType::StringNull),
+            ("before", ColType::JsonNull),
+            ("after", ColType::JsonNull),
+            ],
+            &[
+            ]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-admin_audit_logs-entity_type-entity_id")
+                .table(Alias::new("admin_audit_logs"))
+                .col(Alias::new("entity_type"))
+                .col(Alias::new("entity_id"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "admin_audit_logs").await
+    }
+}
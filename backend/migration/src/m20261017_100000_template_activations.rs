@@ -0,0 +1,46 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "template_activations",
+            &[
+            ("id", ColType::PkAuto),
+            ("status", ColType::String),
+            ("company", ColType::StringNull),
+            ("proposed_by", ColType::String),
+            ("note", ColType::TextNull),
+            ("reviewed_by", ColType::StringNull),
+            ("review_note", ColType::TextNull),
+            ("reviewed_at", ColType::TimestampWithTimeZoneNull),
+            ("window_start", ColType::TimestampWithTimeZoneNull),
+            ("window_end", ColType::TimestampWithTimeZoneNull),
+            ("previous_template_id", ColType::IntegerNull),
+            ("activated_at", ColType::TimestampWithTimeZoneNull),
+            ("baseline_warning_rate", ColType::FloatNull),
+            ("rolled_back_at", ColType::TimestampWithTimeZoneNull),
+            ("rollback_reason", ColType::TextNull),
+            ],
+            &[
+            ("prompt_template", ""),
+            ]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-template_activations-status")
+                .table(Alias::new("template_activations"))
+                .col(Alias::new("status"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "template_activations").await
+    }
+}
@@ -0,0 +1,51 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "canary_rollouts",
+            &[
+            ("id", ColType::PkAuto),
+            ("kind", ColType::String),
+            ("status", ColType::String),
+            ("product", ColType::StringNull),
+            ("screen_type", ColType::StringNull),
+            ("company", ColType::StringNull),
+            ("template_activation_id", ColType::IntegerNull),
+            ("candidate_template_id", ColType::IntegerNull),
+            ("stable_template_id", ColType::IntegerNull),
+            ("candidate_llm_config_id", ColType::IntegerNull),
+            ("stable_llm_config_id", ColType::IntegerNull),
+            ("percent", ColType::Integer),
+            ("min_samples", ColType::Integer),
+            ("max_score_drop", ColType::Float),
+            ("max_error_increase", ColType::Float),
+            ("started_by", ColType::String),
+            ("decided_at", ColType::TimestampWithTimeZoneNull),
+            ("outcome", ColType::TextNull),
+            ("stable_samples", ColType::IntegerNull),
+            ("stable_score", ColType::FloatNull),
+            ("canary_samples", ColType::IntegerNull),
+            ("canary_score", ColType::FloatNull),
+            ],
+            &[]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-canary_rollouts-status")
+                .table(Alias::new("canary_rollouts"))
+                .col(Alias::new("status"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "canary_rollouts").await
+    }
+}
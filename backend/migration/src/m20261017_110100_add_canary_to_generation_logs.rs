@@ -0,0 +1,62 @@
+//! Add canary columns to generation_logs table
+//!
+//! Generations routed while a canary rollout runs record the rollout and the
+//! arm (`canary` or `stable`) they took, so both arms can be compared.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(ColumnDef::new(GenerationLogs::CanaryId).integer().null())
+                    .add_column(ColumnDef::new(GenerationLogs::CanaryArm).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_generation_logs_canary_id")
+                    .table(GenerationLogs::Table)
+                    .col(GenerationLogs::CanaryId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_generation_logs_canary_id")
+                    .table(GenerationLogs::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::CanaryId)
+                    .drop_column(GenerationLogs::CanaryArm)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    CanaryId,
+    CanaryArm,
+}
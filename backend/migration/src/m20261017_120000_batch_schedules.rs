@@ -0,0 +1,26 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "batch_schedules",
+            &[
+            ("id", ColType::PkAuto),
+            ("job", ColType::StringUniq),
+            ("enabled", ColType::Boolean),
+            ("run_at", ColType::String),
+            ("business_days_only", ColType::Boolean),
+            ("updated_by", ColType::StringNull),
+            ],
+            &[]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "batch_schedules").await
+    }
+}
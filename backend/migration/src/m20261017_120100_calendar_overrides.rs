@@ -0,0 +1,38 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "calendar_overrides",
+            &[
+            ("id", ColType::PkAuto),
+            ("kind", ColType::String),
+            ("date", ColType::DateNull),
+            ("weekday", ColType::IntegerNull),
+            ("start_time", ColType::StringNull),
+            ("end_time", ColType::StringNull),
+            ("company", ColType::StringNull),
+            ("name", ColType::String),
+            ("created_by", ColType::String),
+            ],
+            &[]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-calendar_overrides-date")
+                .table(Alias::new("calendar_overrides"))
+                .col(Alias::new("date"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "calendar_overrides").await
+    }
+}
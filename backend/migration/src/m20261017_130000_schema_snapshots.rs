@@ -0,0 +1,34 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "schema_snapshots",
+            &[
+            ("id", ColType::PkAuto),
+            ("company", ColType::StringNull),
+            ("table_name", ColType::String),
+            ("schema_json", ColType::Text),
+            ("source", ColType::String),
+            ],
+            &[]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-schema_snapshots-table_name")
+                .table(Alias::new("schema_snapshots"))
+                .col(Alias::new("table_name"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "schema_snapshots").await
+    }
+}
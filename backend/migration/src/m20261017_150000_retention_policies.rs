@@ -0,0 +1,25 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "retention_policies",
+            &[
+            ("id", ColType::PkAuto),
+            ("role", ColType::StringNull),
+            ("product", ColType::StringNull),
+            ("retention_days", ColType::Integer),
+            ("updated_by", ColType::StringNull),
+            ],
+            &[]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "retention_policies").await
+    }
+}
@@ -0,0 +1,38 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "screen_registrations",
+            &[
+            ("id", ColType::PkAuto),
+            ("company", ColType::StringNull),
+            ("project", ColType::String),
+            ("screen_id", ColType::String),
+            ("xml_filename", ColType::StringNull),
+            ("js_filename", ColType::StringNull),
+            ("generation_id", ColType::IntegerNull),
+            ("user_id", ColType::Integer),
+            ],
+            &[]
+        ).await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-screen_registrations-project")
+                .table(Alias::new("screen_registrations"))
+                .col(Alias::new("project"))
+                .col(Alias::new("screen_id"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "screen_registrations").await
+    }
+}
@@ -0,0 +1,46 @@
+//! Add token usage to generation_logs table
+//!
+//! Prompt and completion tokens plus the time spent in LLM calls, summed
+//! over the retries of a generation, for GPU capacity planning.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .add_column(ColumnDef::new(GenerationLogs::PromptTokens).integer().null())
+                    .add_column(ColumnDef::new(GenerationLogs::CompletionTokens).integer().null())
+                    .add_column(ColumnDef::new(GenerationLogs::LlmLatencyMs).integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GenerationLogs::Table)
+                    .drop_column(GenerationLogs::PromptTokens)
+                    .drop_column(GenerationLogs::CompletionTokens)
+                    .drop_column(GenerationLogs::LlmLatencyMs)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GenerationLogs {
+    Table,
+    PromptTokens,
+    CompletionTokens,
+    LlmLatencyMs,
+}
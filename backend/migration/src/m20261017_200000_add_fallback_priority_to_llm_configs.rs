@@ -0,0 +1,41 @@
+//! Add fallback_priority to llm_configs table
+//!
+//! Configs with a priority back up the active one: when a call to it fails,
+//! generation moves on to them in ascending priority order. NULL = not a
+//! fallback candidate.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LlmConfigs::Table)
+                    .add_column(ColumnDef::new(LlmConfigs::FallbackPriority).integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LlmConfigs::Table)
+                    .drop_column(LlmConfigs::FallbackPriority)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LlmConfigs {
+    Table,
+    FallbackPriority,
+}
@@ -0,0 +1,33 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "column_archetypes",
+            &[
+            ("id", ColType::PkAuto),
+            ("name", ColType::String),
+            ("name_patterns", ColType::StringNull),
+            ("comment_keywords", ColType::StringNull),
+            ("label", ColType::StringNull),
+            ("ui_type", ColType::StringNull),
+            ("data_type", ColType::StringNull),
+            ("max_length", ColType::IntegerNull),
+            ("validation", ColType::StringNull),
+            ("format", ColType::StringNull),
+            ("is_active", ColType::BooleanNull),
+            ("company", ColType::StringNull),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "column_archetypes").await
+    }
+}
@@ -0,0 +1,28 @@
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        create_table(m, "error_code_catalogs",
+            &[
+            ("id", ColType::PkAuto),
+            ("name", ColType::String),
+            ("codes", ColType::Text),
+            ("code_count", ColType::Integer),
+            ("enabled", ColType::Boolean),
+            ("company", ColType::StringNull),
+            ("uploaded_by", ColType::StringNull),
+            ],
+            &[
+            ]
+        ).await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        drop_table(m, "error_code_catalogs").await
+    }
+}
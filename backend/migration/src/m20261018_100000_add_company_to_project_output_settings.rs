@@ -0,0 +1,59 @@
+//! Add company (tenant) column to project_output_settings table
+//!
+//! Output settings belong to the company that saved them; NULL rows are
+//! shared and apply to every tenant's projects of that name.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .add_column(ColumnDef::new(ProjectOutputSettings::Company).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_project_output_settings_company")
+                    .table(ProjectOutputSettings::Table)
+                    .col(ProjectOutputSettings::Company)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_project_output_settings_company")
+                    .table(ProjectOutputSettings::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProjectOutputSettings::Table)
+                    .drop_column(ProjectOutputSettings::Company)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectOutputSettings {
+    Table,
+    Company,
+}
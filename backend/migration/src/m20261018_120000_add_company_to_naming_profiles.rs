@@ -0,0 +1,59 @@
+//! Add company (tenant) column to naming_profiles table
+//!
+//! Profiles belong to the company that saved them; NULL rows are shared
+//! platform profiles every tenant may select.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamingProfiles::Table)
+                    .add_column(ColumnDef::new(NamingProfiles::Company).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_naming_profiles_company")
+                    .table(NamingProfiles::Table)
+                    .col(NamingProfiles::Company)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_naming_profiles_company")
+                    .table(NamingProfiles::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(NamingProfiles::Table)
+                    .drop_column(NamingProfiles::Company)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NamingProfiles {
+    Table,
+    Company,
+}
@@ -0,0 +1,59 @@
+//! Add company (tenant) column to saved_intents table
+//!
+//! Library entries belong to the company that saved them; NULL rows are
+//! shared with every tenant.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SavedIntents::Table)
+                    .add_column(ColumnDef::new(SavedIntents::Company).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_saved_intents_company")
+                    .table(SavedIntents::Table)
+                    .col(SavedIntents::Company)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_saved_intents_company")
+                    .table(SavedIntents::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SavedIntents::Table)
+                    .drop_column(SavedIntents::Company)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SavedIntents {
+    Table,
+    Company,
+}
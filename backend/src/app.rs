@@ -16,7 +16,7 @@ use std::path::Path;
 #[allow(unused_imports)]
 use crate::{
     controllers, initializers,
-    models::_entities::{users, knowledge_bases, llm_configs, prompt_templates},
+    models::_entities::{users, knowledge_bases, llm_configs, prompt_templates, checklist_items, column_archetypes},
     services, tasks,
     workers::downloader::DownloadWorker,
 };
@@ -47,14 +47,26 @@ impl Hooks for App {
     }
 
     async fn initializers(_ctx: &AppContext) -> Result<Vec<Box<dyn Initializer>>> {
-        Ok(vec![Box::new(
-            initializers::view_engine::ViewEngineInitializer,
-        )])
+        Ok(vec![
+            Box::new(initializers::view_engine::ViewEngineInitializer),
+            // Keep provider and model details out of every API response
+            Box::new(initializers::redaction::RedactionInitializer),
+        ])
     }
 
     async fn after_context(ctx: AppContext) -> Result<AppContext> {
         // Start the metrics collector for dashboard graphs
         services::metrics_history::start_metrics_collector();
+        // Push traces/metrics to an OTLP collector when configured
+        services::telemetry::start_exporter();
+        // Cache templates, LLM configs and knowledge lookups
+        services::lookup_cache::init(&ctx.environment);
+        // Share the generation hook registry; extension crates register on it
+        services::generation_hooks::install(&ctx);
+        // Activate approved template proposals and watch them for warning spikes
+        services::template_rollout::start_scheduler(ctx.db.clone());
+        // Compare running canaries against the stable path and decide them
+        services::canary::start_evaluator(ctx.db.clone());
         Ok(ctx)
     }
 
@@ -65,13 +77,24 @@ impl Hooks for App {
             .add_route(controllers::home::routes())
             // API routes
             .add_route(controllers::generate::routes())
+            .add_route(controllers::spring_module::routes())
             .add_route(controllers::review::routes())
+            .add_route(controllers::refactor::routes())
+            .add_route(controllers::screen_registry::routes())
+            .add_route(controllers::intent::routes())
             .add_route(controllers::qa::routes())
             .add_route(controllers::jobs::routes())
             .add_route(controllers::llm_config::routes())
             .add_route(controllers::generation_log::routes())
+            .add_route(controllers::my_generation::routes())
+            .add_route(controllers::schema_change::routes())
             .add_route(controllers::company_rule::routes())
+            .add_route(controllers::project_output_setting::routes())
+            .add_route(controllers::naming_profile::routes())
+            .add_route(controllers::pipeline_profile::routes())
+            .add_route(controllers::saved_intent::routes())
             .add_route(controllers::prompt_template::routes())
+            .add_route(controllers::api_docs::routes())
             .add_route(controllers::auth::routes())
             // Admin panel (HTMX views)
             .add_route(controllers::admin::routes())
@@ -84,6 +107,9 @@ impl Hooks for App {
     #[allow(unused_variables)]
     fn register_tasks(tasks: &mut Tasks) {
         tasks.register(tasks::QueueProcessorTask);
+        tasks.register(tasks::ClientSdkTask);
+        tasks.register(tasks::BatchCheckTask);
+        tasks.register(tasks::RetentionCleanupTask);
         // tasks-inject (do not remove)
     }
     async fn truncate(ctx: &AppContext) -> Result<()> {
@@ -91,6 +117,8 @@ impl Hooks for App {
         truncate_table(&ctx.db, knowledge_bases::Entity).await?;
         truncate_table(&ctx.db, llm_configs::Entity).await?;
         truncate_table(&ctx.db, prompt_templates::Entity).await?;
+        truncate_table(&ctx.db, checklist_items::Entity).await?;
+        truncate_table(&ctx.db, column_archetypes::Entity).await?;
         Ok(())
     }
 
@@ -103,6 +131,10 @@ impl Hooks for App {
             .await?;
         db::seed::<prompt_templates::ActiveModel>(&ctx.db, &base.join("prompt_templates.yaml").display().to_string())
             .await?;
+        db::seed::<checklist_items::ActiveModel>(&ctx.db, &base.join("checklist_items.yaml").display().to_string())
+            .await?;
+        db::seed::<column_archetypes::ActiveModel>(&ctx.db, &base.join("column_archetypes.yaml").display().to_string())
+            .await?;
         Ok(())
     }
 }
\ No newline at end of file
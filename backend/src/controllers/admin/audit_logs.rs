@@ -0,0 +1,132 @@
+//! Admin Audit Logs Controller
+//!
+//! HTMX-based view-only history of admin entity changes, with CSV export.
+//! Thin controller - delegates to AuditLogService.
+
+use axum::http::{header, HeaderMap, StatusCode};
+use loco_rs::prelude::*;
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Redirect response for non-HTMX requests to modal endpoints
+fn redirect_to_main_page() -> Result<Response> {
+    Ok(Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header(header::LOCATION, "/admin/audit-logs")
+        .body(axum::body::Body::empty())?
+        .into_response())
+}
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::audit_log::{AuditLogService, QueryParams};
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let params = QueryParams::default();
+    let scope = TenantScope::for_user(&auth_user);
+    let response = AuditLogService::search(&ctx.db, &scope, &params).await?;
+    let (entity_types, actions) = AuditLogService::filter_options();
+
+    let template = if is_htmx_request(&headers) {
+        "admin/audit_log/main.html"
+    } else {
+        "admin/audit_log/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "audit_logs",
+            "user": auth_user,
+            "entity_types": entity_types,
+            "actions": actions,
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<QueryParams>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let response = AuditLogService::search(&ctx.db, &scope, &params).await?;
+
+    format::render().view(
+        &v,
+        "admin/audit_log/list.html",
+        data!({
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+        }),
+    )
+}
+
+/// Show a single change with its field diff
+#[debug_handler]
+pub async fn show(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    // Redirect to main page if not an HTMX request (direct URL access)
+    if !is_htmx_request(&headers) {
+        return redirect_to_main_page();
+    }
+
+    let scope = TenantScope::for_user(&auth_user);
+    let detail = AuditLogService::find_by_id(&ctx.db, &scope, id).await?;
+
+    format::render().view(
+        &v,
+        "admin/audit_log/show.html",
+        data!({
+            "item": detail.item,
+            "changes": detail.changes,
+        }),
+    )
+}
+
+/// Export the filtered records as CSV
+#[debug_handler]
+pub async fn export(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    Query(params): Query<QueryParams>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let csv = AuditLogService::export_csv(&ctx.db, &scope, &params).await?;
+    let filename = format!("admin-audit-{}.csv", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+
+    let response = Response::builder()
+        .header("Content-Type", "text/csv; charset=utf-8")
+        .header("Content-Disposition", format!("attachment; filename=\"{}\"", filename))
+        .body(csv.into())
+        .map_err(|e| Error::string(&format!("Failed to build response: {}", e)))?;
+
+    Ok(response)
+}
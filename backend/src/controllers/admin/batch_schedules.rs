@@ -0,0 +1,102 @@
+//! Admin Batch Schedules Controller
+//!
+//! HTMX view of the nightly batch job schedules, the upcoming Korean public
+//! holidays they skip and the day-off / maintenance overrides.
+//! Thin controller - delegates to BatchScheduleAdminService.
+
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+use tracing::debug;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::batch_schedule::{BatchScheduleAdminService, OverrideParams, ScheduleParams};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let jobs = BatchScheduleAdminService::jobs(&ctx.db, &scope).await?;
+    let overrides = BatchScheduleAdminService::overrides(&ctx.db, &scope).await?;
+    let days_off = BatchScheduleAdminService::upcoming_days_off(&ctx.db, &scope).await?;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/batch_schedule/main.html"
+    } else {
+        "admin/batch_schedule/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "batch_schedules",
+            "user": auth_user,
+            "jobs": jobs,
+            "overrides": overrides,
+            "days_off": days_off,
+            "calendar_outdated": BatchScheduleAdminService::calendar_outdated(),
+            "can_configure": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// Save a job's schedule (platform administrators only)
+#[debug_handler]
+pub async fn update_schedule(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(job): Path<String>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ScheduleParams>,
+) -> Result<Response> {
+    debug!("batch_schedules::update_schedule - job: {}, params: {:?}", job, params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let item = BatchScheduleAdminService::update_schedule(&ctx.db, &scope, &auth_user.email, &job, params).await?;
+
+    format::render().view(
+        &v,
+        "admin/batch_schedule/job_row.html",
+        data!({ "item": item, "can_configure": true, "saved": true }),
+    )
+}
+
+/// Add a day off, worked day or maintenance window
+#[debug_handler]
+pub async fn create_override(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<OverrideParams>,
+) -> Result<Response> {
+    debug!("batch_schedules::create_override - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let item = BatchScheduleAdminService::create_override(&ctx.db, &scope, &auth_user.email, params).await?;
+
+    format::render().view(&v, "admin/batch_schedule/override_row.html", data!({ "item": item }))
+}
+
+/// Remove an override
+#[debug_handler]
+pub async fn delete_override(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    BatchScheduleAdminService::delete_override(&ctx.db, &scope, id).await?;
+
+    format::html("")
+}
@@ -0,0 +1,149 @@
+//! Admin Canaries Controller
+//!
+//! HTMX views of canary rollouts: start one from a pending template
+//! activation or an inactive LLM config, then promote or roll it back before
+//! the evaluator decides. The traffic share and roll-back reason arrive in
+//! the `HX-Prompt` header.
+//! Thin controller - delegates to CanaryAdminService.
+
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+use tracing::{debug, error};
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::CanaryAdminService;
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Text the administrator typed into the `hx-prompt` dialog
+fn prompt_text(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("HX-Prompt")
+        .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+}
+
+/// Send the browser to the canaries page once a canary started
+fn redirect_to_canaries() -> Result<Response> {
+    format::render()
+        .header("HX-Redirect", "/admin/canaries")
+        .text("Redirecting...")
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = CanaryAdminService::list(&ctx.db, &scope).await?;
+    let defaults = CanaryAdminService::defaults();
+
+    let template = if is_htmx_request(&headers) {
+        "admin/canary/main.html"
+    } else {
+        "admin/canary/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "canaries",
+            "user": auth_user,
+            "items": items,
+            "max_error_increase_points": (defaults.max_error_increase * 100.0).round() as i64,
+            "defaults": defaults,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = CanaryAdminService::list(&ctx.db, &scope).await?;
+
+    format::render().view(&v, "admin/canary/list.html", data!({ "items": items }))
+}
+
+/// Start a canary from a pending template activation
+#[debug_handler]
+pub async fn start_template(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    Path(activation_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    debug!("canaries::start_template - activation_id: {}", activation_id);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let percent = prompt_text(&headers);
+    if let Err(e) =
+        CanaryAdminService::start_template(&ctx.db, &scope, &auth_user.email, activation_id, percent.as_deref()).await
+    {
+        error!("canaries::start_template - failed: {:?}", e);
+        return Err(e);
+    }
+    redirect_to_canaries()
+}
+
+/// Start a canary for an inactive LLM config
+#[debug_handler]
+pub async fn start_llm_config(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    Path(config_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    debug!("canaries::start_llm_config - config_id: {}", config_id);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let percent = prompt_text(&headers);
+    if let Err(e) =
+        CanaryAdminService::start_llm_config(&ctx.db, &scope, &auth_user.email, config_id, percent.as_deref()).await
+    {
+        error!("canaries::start_llm_config - failed: {:?}", e);
+        return Err(e);
+    }
+    redirect_to_canaries()
+}
+
+/// Promote a running canary
+#[debug_handler]
+pub async fn promote(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = CanaryAdminService::promote(&ctx.db, &scope, &auth_user.email, id).await?;
+
+    format::render().view(&v, "admin/canary/row.html", data!({ "item": item }))
+}
+
+/// Roll a running canary back
+#[debug_handler]
+pub async fn rollback(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = CanaryAdminService::roll_back(&ctx.db, &scope, &auth_user.email, id, prompt_text(&headers)).await?;
+
+    format::render().view(&v, "admin/canary/row.html", data!({ "item": item }))
+}
@@ -0,0 +1,168 @@
+//! Admin Checklists Controller
+//!
+//! HTMX-based CRUD for generation checklist items.
+//! Thin controller - delegates to ChecklistItemService.
+
+use axum::http::{header, HeaderMap, StatusCode};
+use loco_rs::prelude::*;
+use tracing::debug;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::checklist_item::{
+    ChecklistItemService, CreateParams, QueryParams, UpdateParams,
+};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Redirect response for non-HTMX requests to modal endpoints
+fn redirect_to_main_page() -> Result<Response> {
+    Ok(Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header(header::LOCATION, "/admin/checklists")
+        .body(axum::body::Body::empty())?
+        .into_response())
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let response = ChecklistItemService::search(&ctx.db, &scope, &QueryParams::default()).await?;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/checklist/main.html"
+    } else {
+        "admin/checklist/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "checklists",
+            "user": auth_user,
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<QueryParams>,
+) -> Result<Response> {
+    debug!("checklists::list - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let response = ChecklistItemService::search(&ctx.db, &scope, &params).await?;
+
+    format::render().view(
+        &v,
+        "admin/checklist/list.html",
+        data!({
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+        }),
+    )
+}
+
+/// New form
+#[debug_handler]
+pub async fn new_form(
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+) -> Result<Response> {
+    // Redirect to main page if not an HTMX request (direct URL access)
+    if !is_htmx_request(&headers) {
+        return redirect_to_main_page();
+    }
+
+    format::render().view(&v, "admin/checklist/create.html", data!({}))
+}
+
+/// Edit form
+#[debug_handler]
+pub async fn edit_form(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    // Redirect to main page if not an HTMX request (direct URL access)
+    if !is_htmx_request(&headers) {
+        return redirect_to_main_page();
+    }
+
+    let scope = TenantScope::for_user(&auth_user);
+    let item = ChecklistItemService::find_by_id(&ctx.db, &scope, id).await?;
+
+    format::render().view(&v, "admin/checklist/edit.html", data!({ "item": item }))
+}
+
+/// Create new item
+#[debug_handler]
+pub async fn create(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateParams>,
+) -> Result<Response> {
+    debug!("checklists::create - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let item = ChecklistItemService::create(&ctx.db, &scope, params).await?;
+
+    // Return just the row to insert at the beginning of tbody
+    format::render().view(&v, "admin/checklist/row.html", data!({ "item": item }))
+}
+
+/// Update existing item
+#[debug_handler]
+pub async fn update(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<UpdateParams>,
+) -> Result<Response> {
+    debug!("checklists::update - id: {}, params: {:?}", id, params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let item = ChecklistItemService::update(&ctx.db, &scope, id, params).await?;
+
+    // Return just the updated row to replace the specific row
+    format::render().view(&v, "admin/checklist/row.html", data!({ "item": item }))
+}
+
+/// Delete item
+#[debug_handler]
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    ChecklistItemService::delete(&ctx.db, &scope, id).await?;
+
+    format::html("")
+}
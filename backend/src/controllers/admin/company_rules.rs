@@ -22,6 +22,7 @@ fn redirect_to_main_page() -> Result<Response> {
 }
 
 use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
 use crate::services::admin::company_rule::{
     CompanyRuleService, CreateParams, QueryParams, UpdateParams,
 };
@@ -38,7 +39,8 @@ pub async fn main(
     debug!("company_rules::main - entering");
     let params = QueryParams::default();
 
-    let response = match CompanyRuleService::search(&ctx.db, &params).await {
+    let scope = TenantScope::for_user(&auth_user);
+    let response = match CompanyRuleService::search(&ctx.db, &scope, &params).await {
         Ok(r) => {
             debug!("company_rules::main - search returned {} items", r.items.len());
             r
@@ -76,13 +78,15 @@ pub async fn main(
 /// List view - for HTMX partial updates
 #[debug_handler]
 pub async fn list(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Query(params): Query<QueryParams>,
 ) -> Result<Response> {
     debug!("company_rules::list - params: {:?}", params);
 
-    let response = match CompanyRuleService::search(&ctx.db, &params).await {
+    let scope = TenantScope::for_user(&auth_user);
+    let response = match CompanyRuleService::search(&ctx.db, &scope, &params).await {
         Ok(r) => {
             debug!("company_rules::list - search returned {} items", r.items.len());
             r
@@ -109,6 +113,7 @@ pub async fn list(
 /// Show single item
 #[debug_handler]
 pub async fn show(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -121,7 +126,8 @@ pub async fn show(
 
     debug!("company_rules::show - id: {}", id);
 
-    let item = match CompanyRuleService::find_by_id(&ctx.db, id).await {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = match CompanyRuleService::find_by_id(&ctx.db, &scope, id).await {
         Ok(i) => {
             debug!("company_rules::show - found item: {:?}", i.name);
             i
@@ -159,6 +165,7 @@ pub async fn new_form(
 /// Edit form
 #[debug_handler]
 pub async fn edit_form(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -171,7 +178,8 @@ pub async fn edit_form(
 
     debug!("company_rules::edit_form - id: {}", id);
 
-    let item = match CompanyRuleService::find_by_id(&ctx.db, id).await {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = match CompanyRuleService::find_by_id(&ctx.db, &scope, id).await {
         Ok(i) => {
             debug!("company_rules::edit_form - found item: {:?}", i.name);
             i
@@ -194,13 +202,15 @@ pub async fn edit_form(
 /// Create new item
 #[debug_handler]
 pub async fn create(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Json(params): Json<CreateParams>,
 ) -> Result<Response> {
     debug!("company_rules::create - params: {:?}", params);
 
-    let item = match CompanyRuleService::create(&ctx.db, params).await {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = match CompanyRuleService::create(&ctx.db, &scope, &auth_user.email, params).await {
         Ok(i) => {
             debug!("company_rules::create - created item id: {}", i.id);
             i
@@ -224,6 +234,7 @@ pub async fn create(
 /// Update existing item
 #[debug_handler]
 pub async fn update(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
@@ -231,7 +242,8 @@ pub async fn update(
 ) -> Result<Response> {
     debug!("company_rules::update - id: {}, params: {:?}", id, params);
 
-    let item = match CompanyRuleService::update(&ctx.db, id, params).await {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = match CompanyRuleService::update(&ctx.db, &scope, &auth_user.email, id, params).await {
         Ok(i) => {
             debug!("company_rules::update - updated item id: {}", i.id);
             i
@@ -252,12 +264,45 @@ pub async fn update(
     )
 }
 
+/// Approve a quarantined rule set (must be another administrator than the one who saved it)
+#[debug_handler]
+pub async fn approve(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    debug!("company_rules::approve - id: {}", id);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let item = match CompanyRuleService::approve(&ctx.db, &scope, &auth_user.email, id).await {
+        Ok(i) => i,
+        Err(e) => {
+            error!("company_rules::approve - failed: {:?}", e);
+            return Err(e);
+        }
+    };
+
+    format::render().view(
+        &v,
+        "admin/company_rule/row.html",
+        data!({
+            "item": item,
+        }),
+    )
+}
+
 /// Delete item
 #[debug_handler]
-pub async fn delete(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
     debug!("company_rules::delete - id: {}", id);
 
-    match CompanyRuleService::delete(&ctx.db, id).await {
+    let scope = TenantScope::for_user(&auth_user);
+    match CompanyRuleService::delete(&ctx.db, &scope, &auth_user.email, id).await {
         Ok(_) => debug!("company_rules::delete - deleted id: {}", id),
         Err(e) => {
             error!("company_rules::delete - failed: {:?}", e);
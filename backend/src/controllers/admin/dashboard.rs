@@ -3,11 +3,12 @@
 //! Provides system monitoring and analytics for the admin dashboard.
 
 use loco_rs::prelude::*;
-use sea_orm::{EntityTrait, PaginatorTrait};
+use sea_orm::{EntityTrait, PaginatorTrait, QueryFilter};
 
 use crate::middleware::cookie_auth::AuthUser;
 use crate::models::_entities::{company_rules, llm_configs, prompt_templates, users};
 use crate::services::analytics::AnalyticsService;
+use crate::services::TenantScope;
 use crate::services::metrics_history::get_metrics_store;
 use crate::services::system_monitor::{format_bytes, format_uptime, SystemMonitor};
 
@@ -18,7 +19,7 @@ pub async fn index(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    let data = get_dashboard_data(&ctx).await?;
+    let data = get_dashboard_data(&ctx, &TenantScope::for_user(&auth_user)).await?;
 
     format::render().view(
         &v,
@@ -36,10 +37,11 @@ pub async fn index(
 /// Dashboard main content - for HTMX partial updates
 #[debug_handler]
 pub async fn main(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    let data = get_dashboard_data(&ctx).await?;
+    let data = get_dashboard_data(&ctx, &TenantScope::for_user(&auth_user)).await?;
 
     format::render().view(
         &v,
@@ -73,10 +75,12 @@ pub async fn system_metrics(ViewEngine(v): ViewEngine<TeraView>) -> Result<Respo
 /// Analytics endpoint for real-time updates
 #[debug_handler]
 pub async fn analytics(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    let analytics = AnalyticsService::get_dashboard_analytics(&ctx.db).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let analytics = AnalyticsService::get_dashboard_analytics(&ctx.db, &scope).await?;
 
     format::render().view(
         &v,
@@ -87,6 +91,35 @@ pub async fn analytics(
     )
 }
 
+/// Query parameters for the warning heatmap
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct HeatmapQuery {
+    pub product: Option<String>,
+    pub days: Option<i64>,
+}
+
+/// Warning taxonomy heatmap per template version
+#[debug_handler]
+pub async fn warning_heatmap(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<HeatmapQuery>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let product = params.product.unwrap_or_else(|| "xframe5".to_string());
+    let days = params.days.unwrap_or(90).clamp(1, 365);
+    let heatmap = AnalyticsService::get_warning_heatmap(&ctx.db, &scope, &product, days).await?;
+
+    format::render().view(
+        &v,
+        "admin/dashboard/partials/warning_heatmap.html",
+        data!({
+            "heatmap": heatmap,
+        }),
+    )
+}
+
 /// Historical metrics endpoint for graphs (JSON)
 #[debug_handler]
 pub async fn metrics_history() -> Result<Response> {
@@ -137,24 +170,31 @@ struct FormattedDisk {
     fs_type: String,
 }
 
-async fn get_dashboard_data(ctx: &AppContext) -> Result<DashboardData> {
-    // Get configuration counts
+async fn get_dashboard_data(ctx: &AppContext, scope: &TenantScope) -> Result<DashboardData> {
+    // Get configuration counts (rows visible to the tenant)
     let prompt_templates_count = prompt_templates::Entity::find()
+        .filter(scope.readable(prompt_templates::Column::Company))
         .count(&ctx.db)
         .await
         .unwrap_or(0);
 
     let company_rules_count = company_rules::Entity::find()
+        .filter(scope.readable(company_rules::Column::Company))
         .count(&ctx.db)
         .await
         .unwrap_or(0);
 
     let llm_configs_count = llm_configs::Entity::find()
+        .filter(scope.owned(llm_configs::Column::Company))
         .count(&ctx.db)
         .await
         .unwrap_or(0);
 
-    let users_count = users::Entity::find().count(&ctx.db).await.unwrap_or(0);
+    let users_count = users::Entity::find()
+        .filter(scope.owned(users::Column::Company))
+        .count(&ctx.db)
+        .await
+        .unwrap_or(0);
 
     let config_stats = ConfigStats {
         prompt_templates_count,
@@ -170,7 +210,7 @@ async fn get_dashboard_data(ctx: &AppContext) -> Result<DashboardData> {
     let system_metrics = format_system_metrics(&metrics);
 
     // Get analytics
-    let analytics = AnalyticsService::get_dashboard_analytics(&ctx.db).await?;
+    let analytics = AnalyticsService::get_dashboard_analytics(&ctx.db, scope).await?;
 
     Ok(DashboardData {
         config_stats,
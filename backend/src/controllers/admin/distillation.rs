@@ -0,0 +1,158 @@
+//! Admin Distillation Controller
+//!
+//! HTMX views of the distillation corpus: capture settings, the examples of
+//! the tenant, pinning and eviction.
+//! Thin controller - delegates to DistillationAdminService.
+
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+use tracing::debug;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::distillation_settings::EVICTION_POLICIES;
+use crate::services::TenantScope;
+use crate::services::admin::distillation::{DistillationAdminService, QueryParams, SettingsParams};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let response = DistillationAdminService::search(&ctx.db, &scope, &QueryParams::default()).await?;
+    let settings = DistillationAdminService::settings(&ctx.db).await;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/distillation/main.html"
+    } else {
+        "admin/distillation/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "distillation",
+            "user": auth_user,
+            "settings": settings,
+            "policies": EVICTION_POLICIES,
+            "can_configure": scope == TenantScope::Platform,
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<QueryParams>,
+) -> Result<Response> {
+    debug!("distillation::list - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let response = DistillationAdminService::search(&ctx.db, &scope, &params).await?;
+
+    format::render().view(
+        &v,
+        "admin/distillation/list.html",
+        data!({
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+        }),
+    )
+}
+
+/// Save the capture settings (platform administrators only)
+#[debug_handler]
+pub async fn update_settings(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<SettingsParams>,
+) -> Result<Response> {
+    debug!("distillation::update_settings - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let settings = DistillationAdminService::update_settings(&ctx.db, &scope, params).await?;
+
+    format::render().view(
+        &v,
+        "admin/distillation/settings.html",
+        data!({
+            "settings": settings,
+            "policies": EVICTION_POLICIES,
+            "can_configure": true,
+            "saved": true,
+        }),
+    )
+}
+
+/// Apply the corpus size now, then show the remaining examples
+#[debug_handler]
+pub async fn evict(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let removed = DistillationAdminService::evict(&ctx.db, &scope).await?;
+    let response = DistillationAdminService::search(&ctx.db, &scope, &QueryParams::default()).await?;
+
+    format::render().view(
+        &v,
+        "admin/distillation/list.html",
+        data!({
+            "items": response.items,
+            "page": response.page,
+            "page_size": response.page_size,
+            "total_pages": response.total_pages,
+            "total_items": response.total_items,
+            "evicted": removed,
+        }),
+    )
+}
+
+/// Pin or unpin an example
+#[debug_handler]
+pub async fn toggle_pin(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = DistillationAdminService::toggle_pin(&ctx.db, &scope, id).await?;
+
+    format::render().view(&v, "admin/distillation/row.html", data!({ "item": item }))
+}
+
+/// Delete an example
+#[debug_handler]
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    DistillationAdminService::delete(&ctx.db, &scope, id).await?;
+
+    format::html("")
+}
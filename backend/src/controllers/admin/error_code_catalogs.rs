@@ -0,0 +1,186 @@
+//! Admin Error-Code Catalogs Controller
+//!
+//! HTMX views of the projects' error-code catalogs: upload, enable/disable, delete.
+//! Thin controller - delegates to ErrorCodeCatalogService.
+
+use axum::extract::Multipart;
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::error_code_catalog::{ErrorCodeCatalogService, UploadParams, MAX_CATALOG_BYTES};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = ErrorCodeCatalogService::list(&ctx.db, &scope).await?;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/error_code_catalog/main.html"
+    } else {
+        "admin/error_code_catalog/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "error_code_catalogs",
+            "user": auth_user,
+            "items": items,
+            "is_platform": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = ErrorCodeCatalogService::list(&ctx.db, &scope).await?;
+
+    format::render().view(
+        &v,
+        "admin/error_code_catalog/list.html",
+        data!({
+            "items": items,
+            "is_platform": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// Upload form modal
+#[debug_handler]
+pub async fn upload_form(ViewEngine(v): ViewEngine<TeraView>) -> Result<Response> {
+    format::render().view(
+        &v,
+        "admin/error_code_catalog/upload.html",
+        data!({ "max_kb": MAX_CATALOG_BYTES / 1024 }),
+    )
+}
+
+/// Upload a catalog file (.csv or .json)
+#[debug_handler]
+pub async fn upload(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    mut multipart: Multipart,
+) -> Result<Response> {
+    let mut params = UploadParams {
+        name: String::new(),
+        filename: String::new(),
+        content: String::new(),
+        replace: false,
+    };
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        Error::string(&format!("Failed to read multipart field: {}", e))
+    })? {
+        let name = field.name().unwrap_or("").to_string();
+        let read_error = |e: axum::extract::multipart::MultipartError| {
+            Error::string(&format!("Failed to read field: {}", e))
+        };
+
+        match name.as_str() {
+            "file" => {
+                params.filename = field.file_name().unwrap_or("").to_string();
+                params.content = field.text().await.map_err(read_error)?;
+                if params.name.is_empty() {
+                    params.name = params
+                        .filename
+                        .rsplit_once('.')
+                        .map_or(params.filename.as_str(), |(stem, _)| stem)
+                        .to_string();
+                }
+            }
+            "name" => {
+                let value = field.text().await.map_err(read_error)?;
+                if !value.trim().is_empty() {
+                    params.name = value;
+                }
+            }
+            "replace" => {
+                let value = field.text().await.map_err(read_error)?;
+                params.replace = value == "true" || value == "on";
+            }
+            _ => {}
+        }
+    }
+
+    let scope = TenantScope::for_user(&auth_user);
+    match ErrorCodeCatalogService::upload(&ctx.db, &scope, params, &auth_user.email).await {
+        Ok(catalog) => format::html(&format!(
+            r#"<div class="p-4 rounded-lg bg-green-500/10 border border-green-500/20">
+                    <p class="text-sm text-green-700 font-medium">Catalog '{}' uploaded</p>
+                    <p class="text-xs text-green-600 mt-1">{} codes</p>
+                </div>
+                <script>
+                    setTimeout(() => {{
+                        document.getElementById('modal-container').innerHTML = '';
+                        htmx.ajax('GET', '/admin/error-code-catalogs/list', {{target: '#catalog-list', swap: 'outerHTML'}});
+                    }}, 2000);
+                </script>"#,
+            html_escape(&catalog.name),
+            catalog.code_count
+        )),
+        Err(e) => format::html(&format!(
+            r#"<div class="p-4 rounded-lg bg-red-500/10 border border-red-500/20">
+                    <p class="text-sm text-red-700 font-medium">Upload Failed</p>
+                    <p class="text-xs text-red-600 mt-1">{}</p>
+                </div>"#,
+            html_escape(&e.to_string())
+        )),
+    }
+}
+
+/// Enable or disable a catalog
+#[debug_handler]
+pub async fn toggle(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = ErrorCodeCatalogService::toggle(&ctx.db, &scope, id).await?;
+
+    format::render().view(
+        &v,
+        "admin/error_code_catalog/row.html",
+        data!({ "item": item, "is_platform": scope == TenantScope::Platform }),
+    )
+}
+
+/// Delete a catalog
+#[debug_handler]
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    ErrorCodeCatalogService::delete(&ctx.db, &scope, id).await?;
+
+    format::html("")
+}
+
+/// Parse errors and catalog names quote the uploaded file
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,35 @@
+//! Admin Fault Injection Controller (`fault-injection` feature only)
+//!
+//! Reads and changes the chaos settings at runtime. Platform administrators only.
+
+use axum::debug_handler;
+use loco_rs::prelude::*;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::fault_injection::{FaultConfig, FaultInjector};
+use crate::services::TenantScope;
+
+fn ensure_platform(auth_user: &AuthUser) -> Result<()> {
+    if TenantScope::for_user(auth_user) == TenantScope::Platform {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized(
+            "Fault injection can only be changed by platform administrators".to_string(),
+        ))
+    }
+}
+
+/// Current settings
+#[debug_handler(state = AppContext)]
+pub async fn show(auth_user: AuthUser) -> Result<Response> {
+    ensure_platform(&auth_user)?;
+    format::json(FaultInjector::config())
+}
+
+/// Replace the settings (missing fields reset to off/0)
+#[debug_handler(state = AppContext)]
+pub async fn update(auth_user: AuthUser, Json(config): Json<FaultConfig>) -> Result<Response> {
+    ensure_platform(&auth_user)?;
+    tracing::warn!("Fault injection settings changed by {}", auth_user.email);
+    format::json(FaultInjector::set_config(config))
+}
@@ -21,6 +21,7 @@ fn redirect_to_main_page() -> Result<Response> {
 }
 
 use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
 use crate::services::admin::generation_log::{GenerationLogService, QueryParams};
 
 /// Main page - renders full layout for direct access, partial for HTMX
@@ -32,7 +33,8 @@ pub async fn main(
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
     let params = QueryParams::default();
-    let response = GenerationLogService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = GenerationLogService::search(&ctx.db, &scope, &params).await?;
 
     // Check if this is an HTMX request
     let is_htmx = headers.get("HX-Request").is_some();
@@ -60,11 +62,13 @@ pub async fn main(
 /// List view - for HTMX partial updates
 #[debug_handler]
 pub async fn list(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Query(params): Query<QueryParams>,
 ) -> Result<Response> {
-    let response = GenerationLogService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = GenerationLogService::search(&ctx.db, &scope, &params).await?;
 
     format::render().view(
         &v,
@@ -82,6 +86,7 @@ pub async fn list(
 /// Show single log entry
 #[debug_handler]
 pub async fn show(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -92,13 +97,62 @@ pub async fn show(
         return redirect_to_main_page();
     }
 
-    let item = GenerationLogService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = GenerationLogService::find_by_id(&ctx.db, &scope, id).await?;
+    let replay_targets = GenerationLogService::replay_targets(&ctx.db, &scope).await?;
+    let lineage = GenerationLogService::lineage(&ctx.db, &scope, id).await?;
+
+    format::render().view(
+        &v,
+        "admin/generation_log/show.html",
+        data!({
+            "item": item,
+            "replay_targets": replay_targets,
+            "lineage": lineage,
+        }),
+    )
+}
+
+/// Replay request (empty = the recorded LLM config)
+#[derive(Debug, serde::Deserialize)]
+pub struct ReplayParams {
+    #[serde(default)]
+    pub llm_config_id: Option<String>,
+}
+
+/// Replay a generation and show the new, linked log
+#[debug_handler]
+pub async fn replay(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ReplayParams>,
+) -> Result<Response> {
+    let llm_config_id = match params.llm_config_id.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(value) => Some(
+            value
+                .parse::<i32>()
+                .map_err(|_| Error::BadRequest(format!("Invalid LLM config id: {}", value)))?,
+        ),
+    };
+
+    let scope = TenantScope::for_user(&auth_user);
+    let outcome = GenerationLogService::replay(&ctx.db, &scope, id, llm_config_id).await?;
+    let log_id = outcome
+        .log_id
+        .ok_or_else(|| Error::string("Replay finished but its generation log could not be written"))?;
+    let item = GenerationLogService::find_by_id(&ctx.db, &scope, log_id).await?;
+    let replay_targets = GenerationLogService::replay_targets(&ctx.db, &scope).await?;
 
     format::render().view(
         &v,
         "admin/generation_log/show.html",
         data!({
             "item": item,
+            "replay_targets": replay_targets,
+            "replay_notes": outcome.notes,
         }),
     )
 }
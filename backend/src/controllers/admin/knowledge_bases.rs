@@ -21,8 +21,9 @@ fn redirect_to_main_page() -> Result<Response> {
 }
 
 use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
 use crate::services::admin::{
-    AdminKnowledgeBaseService,
+    AdminKnowledgeBaseService, KnowledgeRevisionService,
 };
 use crate::services::admin::knowledge_base::{
     CreateParams, QueryParams, UpdateParams,
@@ -37,7 +38,8 @@ pub async fn main(
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
     let params = QueryParams::default();
-    let response = AdminKnowledgeBaseService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = AdminKnowledgeBaseService::search(&ctx.db, &scope, &params).await?;
 
     // Check if this is an HTMX request
     let is_htmx = headers.get("HX-Request").is_some();
@@ -65,11 +67,13 @@ pub async fn main(
 /// List view - for HTMX partial updates
 #[debug_handler]
 pub async fn list(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Query(params): Query<QueryParams>,
 ) -> Result<Response> {
-    let response = AdminKnowledgeBaseService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = AdminKnowledgeBaseService::search(&ctx.db, &scope, &params).await?;
 
     format::render().view(
         &v,
@@ -87,6 +91,7 @@ pub async fn list(
 /// Show single item
 #[debug_handler]
 pub async fn show(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -97,7 +102,8 @@ pub async fn show(
         return redirect_to_main_page();
     }
 
-    let item = AdminKnowledgeBaseService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = AdminKnowledgeBaseService::find_by_id(&ctx.db, &scope, id).await?;
 
     format::render().view(
         &v,
@@ -108,6 +114,34 @@ pub async fn show(
     )
 }
 
+/// Revision history with diffs (loaded into the show modal)
+#[debug_handler]
+pub async fn revisions(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    // Redirect to main page if not an HTMX request (direct URL access)
+    if !is_htmx_request(&headers) {
+        return redirect_to_main_page();
+    }
+
+    // Only entries visible to the tenant have a visible history
+    let scope = TenantScope::for_user(&auth_user);
+    AdminKnowledgeBaseService::find_by_id(&ctx.db, &scope, id).await?;
+    let revisions = KnowledgeRevisionService::history(&ctx.db, id).await?;
+
+    format::render().view(
+        &v,
+        "admin/knowledge_base/revisions.html",
+        data!({
+            "revisions": revisions,
+        }),
+    )
+}
+
 /// New form
 #[debug_handler]
 pub async fn new_form(
@@ -125,6 +159,7 @@ pub async fn new_form(
 /// Edit form
 #[debug_handler]
 pub async fn edit_form(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -135,7 +170,8 @@ pub async fn edit_form(
         return redirect_to_main_page();
     }
 
-    let item = AdminKnowledgeBaseService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = AdminKnowledgeBaseService::find_by_id(&ctx.db, &scope, id).await?;
 
     format::render().view(
         &v,
@@ -149,15 +185,17 @@ pub async fn edit_form(
 /// Create new item
 #[debug_handler]
 pub async fn create(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Json(params): Json<CreateParams>,
 ) -> Result<Response> {
-    AdminKnowledgeBaseService::create(&ctx.db, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    AdminKnowledgeBaseService::create(&ctx.db, &scope, &auth_user.email, params).await?;
 
     // Return updated list
     let query_params = QueryParams::default();
-    let response = AdminKnowledgeBaseService::search(&ctx.db, &query_params).await?;
+    let response = AdminKnowledgeBaseService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -175,16 +213,18 @@ pub async fn create(
 /// Update existing item
 #[debug_handler]
 pub async fn update(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<UpdateParams>,
 ) -> Result<Response> {
-    AdminKnowledgeBaseService::update(&ctx.db, id, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    AdminKnowledgeBaseService::update(&ctx.db, &scope, &auth_user.email, id, params).await?;
 
     // Return updated list
     let query_params = QueryParams::default();
-    let response = AdminKnowledgeBaseService::search(&ctx.db, &query_params).await?;
+    let response = AdminKnowledgeBaseService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -199,18 +239,40 @@ pub async fn update(
     )
 }
 
+/// Approve a quarantined entry (must be another administrator than the one who saved it)
+#[debug_handler]
+pub async fn approve(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = AdminKnowledgeBaseService::approve(&ctx.db, &scope, &auth_user.email, id).await?;
+
+    format::render().view(
+        &v,
+        "admin/knowledge_base/row.html",
+        data!({
+            "item": item,
+        }),
+    )
+}
+
 /// Delete item
 #[debug_handler]
 pub async fn delete(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    AdminKnowledgeBaseService::delete(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    AdminKnowledgeBaseService::delete(&ctx.db, &scope, &auth_user.email, id).await?;
 
     // Return updated list
     let query_params = QueryParams::default();
-    let response = AdminKnowledgeBaseService::search(&ctx.db, &query_params).await?;
+    let response = AdminKnowledgeBaseService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
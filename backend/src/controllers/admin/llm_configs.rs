@@ -10,17 +10,28 @@ use tracing::debug;
 
 use crate::llm::OllamaBackend;
 use crate::middleware::cookie_auth::AuthUser;
+use crate::services::{AvailableModel, ModelCatalog, TenantScope};
 use crate::services::admin::llm_config::{
-    CreateParams, LlmConfigService, QueryParams, UpdateParams,
+    CreateParams, LlmConfigService, ModelAdviceParams, QueryParams, UpdateParams,
 };
 
 /// Query parameters for fetching models from a remote endpoint
 #[derive(Debug, Deserialize)]
 pub struct FetchModelsParams {
+    /// `ollama` (default) or `vllm`
+    pub provider: Option<String>,
     pub endpoint_url: Option<String>,
+    pub api_key: Option<String>,
     pub current_model: Option<String>,
 }
 
+/// Model to pull onto an Ollama server
+#[derive(Debug, Deserialize)]
+pub struct PullModelParams {
+    pub endpoint_url: Option<String>,
+    pub model_name: Option<String>,
+}
+
 /// Helper to check if request is from HTMX
 fn is_htmx_request(headers: &HeaderMap) -> bool {
     headers.get("HX-Request").is_some()
@@ -35,6 +46,18 @@ fn redirect_to_main_page() -> Result<Response> {
         .into_response())
 }
 
+/// Models of a server for pre-filling a form (empty if unreachable or unsupported)
+async fn list_models_quietly(provider: &str, endpoint: &str, api_key: Option<String>) -> Vec<AvailableModel> {
+    tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        ModelCatalog::list(provider, endpoint, api_key),
+    )
+    .await
+    .ok()
+    .and_then(|r| r.ok())
+    .unwrap_or_default()
+}
+
 /// Main page - renders full layout for direct access, partial for HTMX
 #[debug_handler]
 pub async fn main(
@@ -44,7 +67,8 @@ pub async fn main(
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
     let params = QueryParams::default();
-    let response = LlmConfigService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = LlmConfigService::search(&ctx.db, &scope, &params).await?;
 
     // Check if this is an HTMX request
     let is_htmx = headers.get("HX-Request").is_some();
@@ -72,11 +96,13 @@ pub async fn main(
 /// List view - for HTMX partial updates
 #[debug_handler]
 pub async fn list(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Query(params): Query<QueryParams>,
 ) -> Result<Response> {
-    let response = LlmConfigService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = LlmConfigService::search(&ctx.db, &scope, &params).await?;
 
     format::render().view(
         &v,
@@ -105,14 +131,7 @@ pub async fn new_form(
     // Try to fetch available models from Ollama with a short timeout
     // If server is not reachable, show empty list (user can still enter manually)
     let ollama = OllamaBackend::from_env();
-    let available_models = tokio::time::timeout(
-        std::time::Duration::from_secs(2),
-        ollama.list_models(),
-    )
-    .await
-    .ok()
-    .and_then(|r| r.ok())
-    .unwrap_or_default();
+    let available_models = list_models_quietly("ollama", ollama.endpoint(), None).await;
 
     format::render().view(
         &v,
@@ -126,6 +145,7 @@ pub async fn new_form(
 /// Show item details
 #[debug_handler]
 pub async fn show(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -136,7 +156,8 @@ pub async fn show(
         return redirect_to_main_page();
     }
 
-    let item = LlmConfigService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = LlmConfigService::find_by_id(&ctx.db, &scope, id).await?;
 
     format::render().view(
         &v,
@@ -150,6 +171,7 @@ pub async fn show(
 /// Edit form
 #[debug_handler]
 pub async fn edit_form(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -160,18 +182,16 @@ pub async fn edit_form(
         return redirect_to_main_page();
     }
 
-    let item = LlmConfigService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = LlmConfigService::find_by_id(&ctx.db, &scope, id).await?;
 
-    // Try to fetch available models from Ollama with a short timeout
-    let ollama = OllamaBackend::from_env();
-    let available_models = tokio::time::timeout(
-        std::time::Duration::from_secs(2),
-        ollama.list_models(),
-    )
-    .await
-    .ok()
-    .and_then(|r| r.ok())
-    .unwrap_or_default();
+    // Try to fetch the models of the configured server with a short timeout
+    let available_models = match item.endpoint_url.as_deref() {
+        Some(endpoint) if !endpoint.is_empty() => {
+            list_models_quietly(&item.provider, endpoint, item.api_key.clone()).await
+        }
+        _ => Vec::new(),
+    };
 
     format::render().view(
         &v,
@@ -186,11 +206,13 @@ pub async fn edit_form(
 /// Create new item
 #[debug_handler]
 pub async fn create(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Json(params): Json<CreateParams>,
 ) -> Result<Response> {
-    let item = LlmConfigService::create(&ctx.db, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = LlmConfigService::create(&ctx.db, &scope, &auth_user.email, params).await?;
 
     // Return just the row to insert at the beginning of tbody
     format::render().view(
@@ -205,12 +227,14 @@ pub async fn create(
 /// Update existing item
 #[debug_handler]
 pub async fn update(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<UpdateParams>,
 ) -> Result<Response> {
-    let item = LlmConfigService::update(&ctx.db, id, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = LlmConfigService::update(&ctx.db, &scope, &auth_user.email, id, params).await?;
 
     // Return just the updated row to replace the specific row
     format::render().view(
@@ -224,23 +248,30 @@ pub async fn update(
 
 /// Delete item
 #[debug_handler]
-pub async fn delete(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    LlmConfigService::delete(&ctx.db, id).await?;
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    LlmConfigService::delete(&ctx.db, &scope, &auth_user.email, id).await?;
     format::html("")
 }
 
 /// Activate item (deactivates all others)
 #[debug_handler]
 pub async fn activate(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    let _item = LlmConfigService::activate(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let _item = LlmConfigService::activate(&ctx.db, &scope, &auth_user.email, id).await?;
 
     // Return the full list to replace #search-result
     let query_params = QueryParams::default();
-    let response = LlmConfigService::search(&ctx.db, &query_params).await?;
+    let response = LlmConfigService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -255,15 +286,19 @@ pub async fn activate(
     )
 }
 
-/// Fetch available models from a remote Ollama endpoint
+/// Fetch available models from a remote Ollama or vLLM endpoint
 /// Returns HTML options for the model dropdown
 #[debug_handler]
 pub async fn fetch_models(
     ViewEngine(v): ViewEngine<TeraView>,
     Query(params): Query<FetchModelsParams>,
 ) -> Result<Response> {
-    debug!("llm_configs::fetch_models - params: {:?}", params);
+    debug!("llm_configs::fetch_models - provider: {:?}, endpoint: {:?}", params.provider, params.endpoint_url);
 
+    let provider = params
+        .provider
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| "ollama".to_string());
     let endpoint_url = params.endpoint_url.unwrap_or_default();
 
     if endpoint_url.is_empty() {
@@ -272,20 +307,19 @@ pub async fn fetch_models(
             &v,
             "admin/llm_config/model_options.html",
             data!({
-                "available_models": Vec::<String>::new(),
+                "available_models": Vec::<AvailableModel>::new(),
                 "current_model": params.current_model,
                 "error": null,
             }),
         );
     }
 
-    // Create an OllamaBackend with the specified endpoint
-    let ollama = OllamaBackend::new(endpoint_url.clone(), String::new(), 10);
+    let api_key = params.api_key.filter(|k| !k.is_empty());
 
     // Try to fetch models with a short timeout
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(3),
-        ollama.list_models(),
+        ModelCatalog::list(&provider, &endpoint_url, api_key),
     )
     .await;
 
@@ -296,7 +330,7 @@ pub async fn fetch_models(
         }
         Ok(Err(e)) => {
             debug!("llm_configs::fetch_models - error fetching models: {:?}", e);
-            (Vec::new(), Some(format!("Failed to connect: {}", e)))
+            (Vec::new(), Some(format!("Failed to fetch models: {}", e)))
         }
         Err(_) => {
             debug!("llm_configs::fetch_models - timeout fetching models");
@@ -314,3 +348,68 @@ pub async fn fetch_models(
         }),
     )
 }
+
+/// Start pulling a model onto an Ollama server
+/// Returns the progress partial, which polls until the pull finishes
+#[debug_handler(state = AppContext)]
+pub async fn pull_model(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Json(params): Json<PullModelParams>,
+) -> Result<Response> {
+    let endpoint_url = params.endpoint_url.unwrap_or_default();
+    let model_name = params.model_name.unwrap_or_default();
+
+    let progress = ModelCatalog::start_pull(&endpoint_url, &model_name)
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    tracing::info!("{} started pulling model {} onto {}", auth_user.email, progress.model, progress.endpoint);
+
+    format::render().view(
+        &v,
+        "admin/llm_config/pull_progress.html",
+        data!({
+            "progress": progress,
+        }),
+    )
+}
+
+/// Progress of a model pull (polled by the progress partial)
+#[debug_handler]
+pub async fn pull_status(
+    ViewEngine(v): ViewEngine<TeraView>,
+    Query(params): Query<PullModelParams>,
+) -> Result<Response> {
+    let progress = ModelCatalog::progress(
+        params.endpoint_url.as_deref().unwrap_or_default(),
+        params.model_name.as_deref().unwrap_or_default(),
+    )
+    .ok_or_else(|| Error::NotFound)?;
+
+    format::render().view(
+        &v,
+        "admin/llm_config/pull_progress.html",
+        data!({
+            "progress": progress,
+        }),
+    )
+}
+
+/// Read GGUF metadata for the model path and show recommendations
+/// Returns an HTML partial for the local LLM settings
+#[debug_handler]
+pub async fn model_advice(
+    ViewEngine(v): ViewEngine<TeraView>,
+    Query(params): Query<ModelAdviceParams>,
+) -> Result<Response> {
+    let advice = tokio::task::spawn_blocking(move || LlmConfigService::model_advice(&params))
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+
+    format::render().view(
+        &v,
+        "admin/llm_config/model_advice.html",
+        data!({
+            "advice": advice,
+        }),
+    )
+}
@@ -2,18 +2,44 @@
 //!
 //! HTMX-based admin panel for managing:
 //! - Prompt Templates
+//! - Template Activations (two-person approval)
+//! - Canary Rollouts
 //! - Company Rules
+//! - Checklists
+//! - Column Archetypes
+//! - Error-Code Catalogs
+//! - Distillation Corpus
+//! - Chat Notifications
+//! - Job Queue
+//! - Batch Schedules
+//! - Retention Policies
+//! - Validation Plugins
 //! - LLM Configurations
 //! - Generation Logs (view only)
+//! - Audit Logs (view only)
 //! - Users
 
 pub mod dashboard;
 pub mod prompt_templates;
+pub mod template_activations;
+pub mod canaries;
 pub mod company_rules;
 pub mod generation_logs;
+pub mod audit_logs;
 pub mod llm_configs;
 pub mod users;
 pub mod knowledge_bases;
+pub mod checklists;
+pub mod column_archetypes;
+pub mod error_code_catalogs;
+pub mod distillation;
+pub mod notifications;
+pub mod queue;
+pub mod batch_schedules;
+pub mod retention_policies;
+pub mod validation_plugins;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 
 use loco_rs::prelude::*;
 
@@ -25,7 +51,7 @@ pub async fn empty() -> Result<Response> {
 
 /// Combine all admin routes
 pub fn routes() -> Routes {
-    Routes::new()
+    let routes = Routes::new()
         .prefix("admin/")
         .add("/", get(dashboard::index))
         .add("empty", get(empty))
@@ -33,6 +59,7 @@ pub fn routes() -> Routes {
         .add("dashboard", get(dashboard::main))
         .add("dashboard/system-metrics", get(dashboard::system_metrics))
         .add("dashboard/analytics", get(dashboard::analytics))
+        .add("dashboard/warning-heatmap", get(dashboard::warning_heatmap))
         .add("dashboard/metrics-history", get(dashboard::metrics_history))
         // Prompt Templates (static routes BEFORE {id} routes)
         .add("prompt-templates", get(prompt_templates::main))
@@ -43,9 +70,25 @@ pub fn routes() -> Routes {
         .add("prompt-templates", post(prompt_templates::create))
         .add("prompt-templates/{id}/edit", get(prompt_templates::edit_form))
         .add("prompt-templates/{id}/export", get(prompt_templates::export))
+        .add("prompt-templates/{id}/playground", post(prompt_templates::playground))
         .add("prompt-templates/{id}", get(prompt_templates::show))
         .add("prompt-templates/{id}", patch(prompt_templates::update))
         .add("prompt-templates/{id}", delete(prompt_templates::delete))
+        // Template Activations
+        .add("template-activations", get(template_activations::main))
+        .add("template-activations/list", get(template_activations::list))
+        .add("template-activations/new/{template_id}", get(template_activations::new_form))
+        .add("template-activations/new/{template_id}", post(template_activations::create))
+        .add("template-activations/{id}/approve", post(template_activations::approve))
+        .add("template-activations/{id}/reject", post(template_activations::reject))
+        .add("template-activations/{id}/rollback", post(template_activations::rollback))
+        // Canary Rollouts
+        .add("canaries", get(canaries::main))
+        .add("canaries/list", get(canaries::list))
+        .add("canaries/template/{activation_id}", post(canaries::start_template))
+        .add("canaries/llm-config/{config_id}", post(canaries::start_llm_config))
+        .add("canaries/{id}/promote", post(canaries::promote))
+        .add("canaries/{id}/rollback", post(canaries::rollback))
         // Company Rules
         .add("company-rules", get(company_rules::main))
         .add("company-rules/list", get(company_rules::list))
@@ -54,12 +97,69 @@ pub fn routes() -> Routes {
         .add("company-rules/{id}", get(company_rules::show))
         .add("company-rules/{id}/edit", get(company_rules::edit_form))
         .add("company-rules/{id}", patch(company_rules::update))
+        .add("company-rules/{id}/approve", post(company_rules::approve))
         .add("company-rules/{id}", delete(company_rules::delete))
+        // Checklists
+        .add("checklists", get(checklists::main))
+        .add("checklists/list", get(checklists::list))
+        .add("checklists/new", get(checklists::new_form))
+        .add("checklists", post(checklists::create))
+        .add("checklists/{id}/edit", get(checklists::edit_form))
+        .add("checklists/{id}", patch(checklists::update))
+        .add("checklists/{id}", delete(checklists::delete))
+        // Column Archetypes
+        .add("column-archetypes", get(column_archetypes::main))
+        .add("column-archetypes/list", get(column_archetypes::list))
+        .add("column-archetypes/new", get(column_archetypes::new_form))
+        .add("column-archetypes", post(column_archetypes::create))
+        .add("column-archetypes/{id}/edit", get(column_archetypes::edit_form))
+        .add("column-archetypes/{id}", patch(column_archetypes::update))
+        .add("column-archetypes/{id}", delete(column_archetypes::delete))
+        // Error-Code Catalogs
+        .add("error-code-catalogs", get(error_code_catalogs::main))
+        .add("error-code-catalogs/list", get(error_code_catalogs::list))
+        .add("error-code-catalogs/upload", get(error_code_catalogs::upload_form))
+        .add("error-code-catalogs/upload", post(error_code_catalogs::upload))
+        .add("error-code-catalogs/{id}/toggle", post(error_code_catalogs::toggle))
+        .add("error-code-catalogs/{id}", delete(error_code_catalogs::delete))
+        // Distillation Corpus
+        .add("distillation", get(distillation::main))
+        .add("distillation/list", get(distillation::list))
+        .add("distillation/settings", put(distillation::update_settings))
+        .add("distillation/evict", post(distillation::evict))
+        .add("distillation/{id}/pin", post(distillation::toggle_pin))
+        .add("distillation/{id}", delete(distillation::delete))
+        // Chat Notifications
+        .add("notifications", get(notifications::main))
+        .add("notifications/settings", put(notifications::update_settings))
+        .add("notifications/test", post(notifications::send_test))
+        // Job Queue
+        .add("queue", get(queue::main))
+        .add("queue/settings", put(queue::update_settings))
+        // Batch Schedules
+        .add("batch-schedules", get(batch_schedules::main))
+        .add("batch-schedules/overrides", post(batch_schedules::create_override))
+        .add("batch-schedules/overrides/{id}", delete(batch_schedules::delete_override))
+        .add("batch-schedules/{job}", put(batch_schedules::update_schedule))
+        // Retention Policies
+        .add("retention-policies", get(retention_policies::main))
+        .add("retention-policies", post(retention_policies::save))
+        .add("retention-policies/{id}", delete(retention_policies::delete))
+        // Validation Plugins
+        .add("validation-plugins", get(validation_plugins::main))
+        .add("validation-plugins/list", get(validation_plugins::list))
+        .add("validation-plugins/upload", get(validation_plugins::upload_form))
+        .add("validation-plugins/upload", post(validation_plugins::upload))
+        .add("validation-plugins/{id}/toggle", post(validation_plugins::toggle))
+        .add("validation-plugins/{id}", delete(validation_plugins::delete))
         // LLM Configs
         .add("llm-configs", get(llm_configs::main))
         .add("llm-configs/list", get(llm_configs::list))
         .add("llm-configs/new", get(llm_configs::new_form))
         .add("llm-configs/models", get(llm_configs::fetch_models))
+        .add("llm-configs/models/pull", post(llm_configs::pull_model))
+        .add("llm-configs/models/pull", get(llm_configs::pull_status))
+        .add("llm-configs/model-advice", get(llm_configs::model_advice))
         .add("llm-configs", post(llm_configs::create))
         .add("llm-configs/{id}", get(llm_configs::show))
         .add("llm-configs/{id}/edit", get(llm_configs::edit_form))
@@ -70,6 +170,12 @@ pub fn routes() -> Routes {
         .add("generation-logs", get(generation_logs::main))
         .add("generation-logs/list", get(generation_logs::list))
         .add("generation-logs/{id}", get(generation_logs::show))
+        .add("generation-logs/{id}/replay", post(generation_logs::replay))
+        // Audit Logs (read only)
+        .add("audit-logs", get(audit_logs::main))
+        .add("audit-logs/list", get(audit_logs::list))
+        .add("audit-logs/export", get(audit_logs::export))
+        .add("audit-logs/{id}", get(audit_logs::show))
         // Users
         .add("users", get(users::main))
         .add("users/list", get(users::list))
@@ -86,6 +192,16 @@ pub fn routes() -> Routes {
         .add("knowledge-bases", post(knowledge_bases::create))
         .add("knowledge-bases/{id}", get(knowledge_bases::show))
         .add("knowledge-bases/{id}/edit", get(knowledge_bases::edit_form))
+        .add("knowledge-bases/{id}/revisions", get(knowledge_bases::revisions))
+        .add("knowledge-bases/{id}/approve", post(knowledge_bases::approve))
         .add("knowledge-bases/{id}", patch(knowledge_bases::update))
-        .add("knowledge-bases/{id}", delete(knowledge_bases::delete))
+        .add("knowledge-bases/{id}", delete(knowledge_bases::delete));
+
+    // Chaos settings (resilience testing builds only)
+    #[cfg(feature = "fault-injection")]
+    let routes = routes
+        .add("fault-injection", get(fault_injection::show))
+        .add("fault-injection", put(fault_injection::update));
+
+    routes
 }
@@ -0,0 +1,83 @@
+//! Admin Notifications Controller
+//!
+//! HTMX view of the Slack/Mattermost notification settings: channel, webhook
+//! and which job events are posted.
+//! Thin controller - delegates to NotificationAdminService.
+
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+use tracing::debug;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::notification_settings::NOTIFICATION_PROVIDERS;
+use crate::services::TenantScope;
+use crate::services::admin::notification::{NotificationAdminService, SettingsParams};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let settings = NotificationAdminService::settings(&ctx.db).await;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/notifications/main.html"
+    } else {
+        "admin/notifications/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "notifications",
+            "user": auth_user,
+            "settings": settings,
+            "providers": NOTIFICATION_PROVIDERS,
+            "can_configure": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// Save the settings (platform administrators only)
+#[debug_handler]
+pub async fn update_settings(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<SettingsParams>,
+) -> Result<Response> {
+    debug!("notifications::update_settings - provider: {:?}", params.provider);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let settings = NotificationAdminService::update_settings(&ctx.db, &scope, params).await?;
+
+    format::render().view(
+        &v,
+        "admin/notifications/settings.html",
+        data!({
+            "settings": settings,
+            "providers": NOTIFICATION_PROVIDERS,
+            "can_configure": true,
+            "saved": true,
+        }),
+    )
+}
+
+/// Post a test message to the channel
+#[debug_handler]
+pub async fn send_test(auth_user: AuthUser, State(ctx): State<AppContext>) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    NotificationAdminService::send_test(&ctx.db, &scope).await?;
+
+    format::html(r#"<span class="text-xs text-green-600">Test message sent</span>"#)
+}
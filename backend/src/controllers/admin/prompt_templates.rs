@@ -22,10 +22,13 @@ fn redirect_to_main_page() -> Result<Response> {
 }
 
 use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::{AuditLogService, TemplateActivationService};
+use crate::services::admin::template_activation::ProposeParams;
 use crate::services::admin::prompt_template::{
-    CreateParams, PromptTemplateService, QueryParams, UpdateParams,
+    CreateParams, PlaygroundParams, PromptTemplateService, QueryParams, UpdateParams, PLAYGROUND_SAMPLE_INPUT,
 };
-use crate::services::{ImportOptions, TemplateImporter};
+use crate::services::{ImportOptions, TemplateImporter, TemplateVariables};
 
 /// Main page - renders full layout for direct access, partial for HTMX
 #[debug_handler]
@@ -36,7 +39,8 @@ pub async fn main(
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
     let params = QueryParams::default();
-    let response = PromptTemplateService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = PromptTemplateService::search(&ctx.db, &scope, &params).await?;
 
     // Check if this is an HTMX request
     let is_htmx = headers.get("HX-Request").is_some();
@@ -64,11 +68,13 @@ pub async fn main(
 /// List view - for HTMX partial updates
 #[debug_handler]
 pub async fn list(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Query(params): Query<QueryParams>,
 ) -> Result<Response> {
-    let response = PromptTemplateService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = PromptTemplateService::search(&ctx.db, &scope, &params).await?;
 
     format::render().view(
         &v,
@@ -86,6 +92,7 @@ pub async fn list(
 /// Show single item
 #[debug_handler]
 pub async fn show(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -96,17 +103,42 @@ pub async fn show(
         return redirect_to_main_page();
     }
 
-    let item = PromptTemplateService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = PromptTemplateService::find_by_id(&ctx.db, &scope, id).await?;
+    let check = TemplateVariables::check(
+        &item.product,
+        item.screen_type.as_deref(),
+        &item.system_prompt,
+        &item.user_prompt_template,
+    );
 
     format::render().view(
         &v,
         "admin/prompt_template/show.html",
         data!({
             "item": item,
+            "variable_notes": check.notes(),
+            "unknown_variables": check.unknown,
+            "sample_input": PLAYGROUND_SAMPLE_INPUT,
         }),
     )
 }
 
+/// Template playground: compiled variable values for a sample input
+#[debug_handler]
+pub async fn playground(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<PlaygroundParams>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let result = PromptTemplateService::playground(&ctx.db, &scope, id, params).await?;
+
+    format::render().view(&v, "admin/prompt_template/playground.html", data!({ "result": result }))
+}
+
 /// New form
 #[debug_handler]
 pub async fn new_form(
@@ -124,6 +156,7 @@ pub async fn new_form(
 /// Edit form
 #[debug_handler]
 pub async fn edit_form(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -134,7 +167,8 @@ pub async fn edit_form(
         return redirect_to_main_page();
     }
 
-    let item = PromptTemplateService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = PromptTemplateService::find_by_id(&ctx.db, &scope, id).await?;
 
     format::render().view(
         &v,
@@ -148,15 +182,17 @@ pub async fn edit_form(
 /// Create new item
 #[debug_handler]
 pub async fn create(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Json(params): Json<CreateParams>,
 ) -> Result<Response> {
-    PromptTemplateService::create(&ctx.db, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    PromptTemplateService::create(&ctx.db, &scope, &auth_user.email, params).await?;
 
     // Return the full list to replace #search-result
     let query_params = QueryParams::default();
-    let response = PromptTemplateService::search(&ctx.db, &query_params).await?;
+    let response = PromptTemplateService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -174,16 +210,18 @@ pub async fn create(
 /// Update existing item
 #[debug_handler]
 pub async fn update(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<UpdateParams>,
 ) -> Result<Response> {
-    PromptTemplateService::update(&ctx.db, id, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    PromptTemplateService::update(&ctx.db, &scope, &auth_user.email, id, params).await?;
 
     // Return the full list to replace #search-result
     let query_params = QueryParams::default();
-    let response = PromptTemplateService::search(&ctx.db, &query_params).await?;
+    let response = PromptTemplateService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -200,8 +238,13 @@ pub async fn update(
 
 /// Delete item
 #[debug_handler]
-pub async fn delete(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    PromptTemplateService::delete(&ctx.db, id).await?;
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    PromptTemplateService::delete(&ctx.db, &scope, &auth_user.email, id).await?;
     format::html("")
 }
 
@@ -225,13 +268,13 @@ pub async fn import_form(
 #[debug_handler]
 pub async fn import(
     State(ctx): State<AppContext>,
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     mut multipart: Multipart,
 ) -> Result<Response> {
     let mut file_content: Option<String> = None;
     let mut file_type: Option<String> = None;
-    let mut deactivate_old = true;
-    let mut set_active = true;
+    // Unchecked checkboxes are not submitted
+    let mut propose_activation = false;
 
     // Parse multipart form data
     while let Some(field) = multipart.next_field().await.map_err(|e| {
@@ -259,17 +302,11 @@ pub async fn import(
                     return Err(Error::string("Unsupported file type. Please upload .yaml, .yml, or .json"));
                 }
             }
-            "deactivate_old" => {
-                let value = field.text().await.map_err(|e| {
-                    Error::string(&format!("Failed to read field: {}", e))
-                })?;
-                deactivate_old = value == "true" || value == "on";
-            }
-            "set_active" => {
+            "propose_activation" => {
                 let value = field.text().await.map_err(|e| {
                     Error::string(&format!("Failed to read field: {}", e))
                 })?;
-                set_active = value == "true" || value == "on";
+                propose_activation = value == "true" || value == "on";
             }
             _ => {}
         }
@@ -279,11 +316,13 @@ pub async fn import(
     let content = file_content.ok_or_else(|| Error::string("No file uploaded"))?;
     let ftype = file_type.ok_or_else(|| Error::string("Could not determine file type"))?;
 
-    // Import options
+    // Imported versions start inactive; activation goes through an approved proposal
+    let scope = TenantScope::for_user(&auth_user);
     let options = ImportOptions {
-        deactivate_old,
+        deactivate_old: false,
         force_version: None,
-        set_active,
+        set_active: false,
+        company: scope.company().map(str::to_string),
     };
 
     // Import template
@@ -295,6 +334,20 @@ pub async fn import(
 
     match result {
         Ok(import_result) => {
+            if let Some(template_id) = import_result.template_id {
+                AuditLogService::record_import(&ctx.db, &auth_user.email, template_id).await?;
+                if propose_activation {
+                    TemplateActivationService::propose(
+                        &ctx.db,
+                        &scope,
+                        &auth_user.email,
+                        template_id,
+                        ProposeParams::default(),
+                    )
+                    .await?;
+                }
+            }
+
             // Return success message
             format::html(&format!(
                 r#"<div class="p-4 rounded-lg bg-green-500/10 border border-green-500/20">
@@ -330,13 +383,14 @@ pub async fn import(
 pub async fn export(
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
 ) -> Result<Response> {
+    // Get template info for filename (also hides other tenants' templates)
+    let scope = TenantScope::for_user(&auth_user);
+    let template = PromptTemplateService::find_by_id(&ctx.db, &scope, id).await?;
+
     let yaml_content = TemplateImporter::export_to_yaml(&ctx.db, id).await
         .map_err(|e| Error::string(&format!("Export failed: {}", e)))?;
-
-    // Get template info for filename
-    let template = PromptTemplateService::find_by_id(&ctx.db, id).await?;
     let filename = format!("{}-v{}.yaml", template.name, template.version);
 
     // Return as downloadable file
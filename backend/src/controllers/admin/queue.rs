@@ -0,0 +1,76 @@
+//! Admin Job Queue Controller
+//!
+//! HTMX view of the job queue: jobs per status and the fairness settings
+//! (users or projects taking turns, running jobs per owner).
+//! Thin controller - delegates to QueueAdminService.
+
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+use tracing::debug;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::queue_settings::QUEUE_FAIRNESS;
+use crate::services::TenantScope;
+use crate::services::admin::queue::{QueueAdminService, SettingsParams};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let settings = QueueAdminService::settings(&ctx.db).await;
+    let stats = QueueAdminService::stats(&ctx.db).await?;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/queue/main.html"
+    } else {
+        "admin/queue/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "queue",
+            "user": auth_user,
+            "settings": settings,
+            "stats": stats,
+            "policies": QUEUE_FAIRNESS,
+            "can_configure": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// Save the settings (platform administrators only)
+#[debug_handler]
+pub async fn update_settings(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<SettingsParams>,
+) -> Result<Response> {
+    debug!("queue::update_settings - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let settings = QueueAdminService::update_settings(&ctx.db, &scope, params).await?;
+
+    format::render().view(
+        &v,
+        "admin/queue/settings.html",
+        data!({
+            "settings": settings,
+            "policies": QUEUE_FAIRNESS,
+            "can_configure": true,
+            "saved": true,
+        }),
+    )
+}
@@ -0,0 +1,84 @@
+//! Admin Retention Policies Controller
+//!
+//! HTMX view of how long generation artifacts are kept per user role and
+//! product.
+//! Thin controller - delegates to RetentionPolicyAdminService.
+
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+use tracing::debug;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::admin::retention_policy::{PolicyParams, RetentionPolicyAdminService};
+use crate::services::{RetentionService, TenantScope};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let policies = RetentionPolicyAdminService::policies(&ctx.db).await?;
+    let roles = RetentionPolicyAdminService::roles(&ctx.db).await?;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/retention_policy/main.html"
+    } else {
+        "admin/retention_policy/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "retention_policies",
+            "user": auth_user,
+            "policies": policies,
+            "roles": roles,
+            "default_days": RetentionService::default_days(),
+            "can_configure": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// Set the retention of a role / product pair (platform administrators only)
+#[debug_handler]
+pub async fn save(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<PolicyParams>,
+) -> Result<Response> {
+    debug!("retention_policies::save - params: {:?}", params);
+
+    let scope = TenantScope::for_user(&auth_user);
+    RetentionPolicyAdminService::save(&ctx.db, &scope, &auth_user.email, params).await?;
+    let policies = RetentionPolicyAdminService::policies(&ctx.db).await?;
+
+    format::render().view(
+        &v,
+        "admin/retention_policy/rows.html",
+        data!({ "policies": policies, "can_configure": true }),
+    )
+}
+
+/// Remove a policy
+#[debug_handler]
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    RetentionPolicyAdminService::delete(&ctx.db, &scope, id).await?;
+
+    format::html("")
+}
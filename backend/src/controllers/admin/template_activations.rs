@@ -0,0 +1,212 @@
+//! Admin Template Activations Controller
+//!
+//! HTMX views of prompt template activation proposals: propose, approve,
+//! reject and roll back. Review notes arrive in the `HX-Prompt` header.
+//! Thin controller - delegates to TemplateActivationService.
+
+use axum::http::{header, HeaderMap, StatusCode};
+use loco_rs::prelude::*;
+use serde::Deserialize;
+use tracing::{debug, error};
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::prompt_templates;
+use crate::services::TenantScope;
+use crate::services::admin::template_activation::{ProposeParams, TemplateActivationService};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Redirect response for non-HTMX requests to modal endpoints
+fn redirect_to_main_page() -> Result<Response> {
+    Ok(Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header(header::LOCATION, "/admin/template-activations")
+        .body(axum::body::Body::empty())?
+        .into_response())
+}
+
+/// Text the administrator typed into the `hx-prompt` dialog
+fn prompt_text(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("HX-Prompt")
+        .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+}
+
+/// List filter
+#[derive(Debug, Deserialize, Default)]
+pub struct ListParams {
+    pub status: Option<String>,
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = TemplateActivationService::list(&ctx.db, &scope, None).await?;
+    let config = TemplateActivationService::config();
+
+    let template = if is_htmx_request(&headers) {
+        "admin/template_activation/main.html"
+    } else {
+        "admin/template_activation/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "template_activations",
+            "user": auth_user,
+            "items": items,
+            "spike_margin_points": (config.spike_margin * 100.0).round() as i64,
+            "config": config,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<ListParams>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = TemplateActivationService::list(&ctx.db, &scope, params.status.as_deref()).await?;
+
+    format::render().view(
+        &v,
+        "admin/template_activation/list.html",
+        data!({
+            "user": auth_user,
+            "items": items,
+        }),
+    )
+}
+
+/// Proposal form for an inactive template
+#[debug_handler]
+pub async fn new_form(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(template_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    // Redirect to main page if not an HTMX request (direct URL access)
+    if !is_htmx_request(&headers) {
+        return redirect_to_main_page();
+    }
+
+    let scope = TenantScope::for_user(&auth_user);
+    let template = prompt_templates::Entity::find_by_id(template_id)
+        .filter(scope.readable(prompt_templates::Column::Company))
+        .one(&ctx.db)
+        .await?
+        .ok_or_else(|| Error::NotFound)?;
+
+    format::render().view(
+        &v,
+        "admin/template_activation/form.html",
+        data!({
+            "template": template,
+        }),
+    )
+}
+
+/// File a proposal
+#[debug_handler]
+pub async fn create(
+    auth_user: AuthUser,
+    Path(template_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ProposeParams>,
+) -> Result<Response> {
+    debug!("template_activations::create - template_id: {}", template_id);
+
+    let scope = TenantScope::for_user(&auth_user);
+    let row = match TemplateActivationService::propose(&ctx.db, &scope, &auth_user.email, template_id, params).await {
+        Ok(row) => row,
+        Err(e) => {
+            error!("template_activations::create - failed: {:?}", e);
+            return Err(e);
+        }
+    };
+
+    format::html(&format!(
+        r#"<div class="p-4 rounded-lg bg-green-500/10 border border-green-500/20">
+                <p class="text-sm text-green-700 font-medium">Activation of v{} proposed</p>
+                <p class="text-xs text-green-600 mt-1">Another administrator approves it under Template Activations.</p>
+            </div>
+            <script>
+                setTimeout(() => {{ document.getElementById('modal-container').innerHTML = ''; }}, 2000);
+            </script>"#,
+        row.version
+    ))
+}
+
+/// Approve a pending proposal
+#[debug_handler]
+pub async fn approve(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = TemplateActivationService::approve(&ctx.db, &scope, &auth_user.email, id, prompt_text(&headers)).await?;
+
+    format::render().view(
+        &v,
+        "admin/template_activation/row.html",
+        data!({ "item": item, "user": auth_user }),
+    )
+}
+
+/// Reject (or, by the proposer, withdraw) a proposal
+#[debug_handler]
+pub async fn reject(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = TemplateActivationService::reject(&ctx.db, &scope, &auth_user.email, id, prompt_text(&headers)).await?;
+
+    format::render().view(
+        &v,
+        "admin/template_activation/row.html",
+        data!({ "item": item, "user": auth_user }),
+    )
+}
+
+/// Roll an active activation back to the previous version
+#[debug_handler]
+pub async fn rollback(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = TemplateActivationService::rollback(&ctx.db, &scope, &auth_user.email, id, prompt_text(&headers)).await?;
+
+    format::render().view(
+        &v,
+        "admin/template_activation/row.html",
+        data!({ "item": item, "user": auth_user }),
+    )
+}
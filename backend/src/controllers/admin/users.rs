@@ -7,6 +7,7 @@ use axum::http::{header, HeaderMap, StatusCode};
 use loco_rs::prelude::*;
 
 use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
 use crate::services::admin::user::{
     CreateParams, QueryParams, UpdateParams, UserService,
 };
@@ -34,7 +35,8 @@ pub async fn main(
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
     let params = QueryParams::default();
-    let response = UserService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = UserService::search(&ctx.db, &scope, &params).await?;
 
     // Check if this is an HTMX request
     let is_htmx = headers.get("HX-Request").is_some();
@@ -62,11 +64,13 @@ pub async fn main(
 /// List view - for HTMX partial updates
 #[debug_handler]
 pub async fn list(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Query(params): Query<QueryParams>,
 ) -> Result<Response> {
-    let response = UserService::search(&ctx.db, &params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let response = UserService::search(&ctx.db, &scope, &params).await?;
 
     format::render().view(
         &v,
@@ -84,6 +88,7 @@ pub async fn list(
 /// Show user details
 #[debug_handler]
 pub async fn show(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -94,7 +99,8 @@ pub async fn show(
         return redirect_to_users_page();
     }
 
-    let item = UserService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = UserService::find_by_id(&ctx.db, &scope, id).await?;
 
     format::render().view(
         &v,
@@ -106,8 +112,9 @@ pub async fn show(
 }
 
 /// New form
-#[debug_handler]
+#[debug_handler(state = AppContext)]
 pub async fn new_form(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
 ) -> Result<Response> {
@@ -116,12 +123,22 @@ pub async fn new_form(
         return redirect_to_users_page();
     }
 
-    format::render().view(&v, "admin/user/create.html", data!({}))
+    // Only platform administrators assign users to a company
+    let is_platform = TenantScope::for_user(&auth_user) == TenantScope::Platform;
+
+    format::render().view(
+        &v,
+        "admin/user/create.html",
+        data!({
+            "is_platform": is_platform,
+        }),
+    )
 }
 
 /// Edit form
 #[debug_handler]
 pub async fn edit_form(
+    auth_user: AuthUser,
     headers: HeaderMap,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
@@ -132,13 +149,15 @@ pub async fn edit_form(
         return redirect_to_users_page();
     }
 
-    let item = UserService::find_by_id(&ctx.db, id).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    let item = UserService::find_by_id(&ctx.db, &scope, id).await?;
 
     format::render().view(
         &v,
         "admin/user/edit.html",
         data!({
             "item": item,
+            "is_platform": scope == TenantScope::Platform,
         }),
     )
 }
@@ -146,15 +165,17 @@ pub async fn edit_form(
 /// Create new user
 #[debug_handler]
 pub async fn create(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Json(params): Json<CreateParams>,
 ) -> Result<Response> {
-    UserService::create(&ctx.db, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    UserService::create(&ctx.db, &scope, params).await?;
 
     // Return the full list to replace #search-result
     let query_params = QueryParams::default();
-    let response = UserService::search(&ctx.db, &query_params).await?;
+    let response = UserService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -172,16 +193,18 @@ pub async fn create(
 /// Update existing user
 #[debug_handler]
 pub async fn update(
+    auth_user: AuthUser,
     ViewEngine(v): ViewEngine<TeraView>,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<UpdateParams>,
 ) -> Result<Response> {
-    UserService::update(&ctx.db, id, params).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    UserService::update(&ctx.db, &scope, id, params).await?;
 
     // Return the full list to replace #search-result
     let query_params = QueryParams::default();
-    let response = UserService::search(&ctx.db, &query_params).await?;
+    let response = UserService::search(&ctx.db, &scope, &query_params).await?;
 
     format::render().view(
         &v,
@@ -203,6 +226,7 @@ pub async fn delete(
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    UserService::delete(&ctx.db, id, &auth_user.pid).await?;
+    let scope = TenantScope::for_user(&auth_user);
+    UserService::delete(&ctx.db, &scope, id, &auth_user.pid).await?;
     format::html("")
 }
@@ -0,0 +1,185 @@
+//! Admin Validation Plugins Controller
+//!
+//! HTMX views of the customer WASM checks: upload, enable/disable, delete.
+//! Thin controller - delegates to ValidationPluginService.
+
+use axum::extract::Multipart;
+use axum::http::HeaderMap;
+use loco_rs::prelude::*;
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::services::TenantScope;
+use crate::services::admin::validation_plugin::{UploadParams, ValidationPluginService, MAX_PLUGIN_BYTES};
+
+/// Helper to check if request is from HTMX
+fn is_htmx_request(headers: &HeaderMap) -> bool {
+    headers.get("HX-Request").is_some()
+}
+
+/// Main page - renders full layout for direct access, partial for HTMX
+#[debug_handler]
+pub async fn main(
+    auth_user: AuthUser,
+    headers: HeaderMap,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = ValidationPluginService::list(&ctx.db).await?;
+
+    let template = if is_htmx_request(&headers) {
+        "admin/validation_plugins/main.html"
+    } else {
+        "admin/validation_plugins/index.html"
+    };
+
+    format::render().view(
+        &v,
+        template,
+        data!({
+            "current_page": "validation_plugins",
+            "user": auth_user,
+            "items": items,
+            "can_configure": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// List view - for HTMX partial updates
+#[debug_handler]
+pub async fn list(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let items = ValidationPluginService::list(&ctx.db).await?;
+
+    format::render().view(
+        &v,
+        "admin/validation_plugins/list.html",
+        data!({
+            "items": items,
+            "can_configure": scope == TenantScope::Platform,
+        }),
+    )
+}
+
+/// Upload form modal
+#[debug_handler]
+pub async fn upload_form(ViewEngine(v): ViewEngine<TeraView>) -> Result<Response> {
+    format::render().view(
+        &v,
+        "admin/validation_plugins/upload.html",
+        data!({ "max_mb": MAX_PLUGIN_BYTES / 1024 / 1024 }),
+    )
+}
+
+/// Upload a module (platform administrators only)
+#[debug_handler]
+pub async fn upload(
+    auth_user: AuthUser,
+    State(ctx): State<AppContext>,
+    mut multipart: Multipart,
+) -> Result<Response> {
+    let mut params = UploadParams {
+        name: String::new(),
+        description: None,
+        wasm: Vec::new(),
+        replace: false,
+    };
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        Error::string(&format!("Failed to read multipart field: {}", e))
+    })? {
+        let name = field.name().unwrap_or("").to_string();
+        let read_error = |e: axum::extract::multipart::MultipartError| {
+            Error::string(&format!("Failed to read field: {}", e))
+        };
+
+        match name.as_str() {
+            "file" => {
+                let filename = field.file_name().unwrap_or("").to_string();
+                if !filename.ends_with(".wasm") {
+                    return Err(Error::string("Unsupported file type. Please upload a .wasm module"));
+                }
+                params.wasm = field.bytes().await.map_err(read_error)?.to_vec();
+                if params.name.is_empty() {
+                    params.name = filename.trim_end_matches(".wasm").to_string();
+                }
+            }
+            "name" => {
+                let value = field.text().await.map_err(read_error)?;
+                if !value.trim().is_empty() {
+                    params.name = value;
+                }
+            }
+            "description" => params.description = Some(field.text().await.map_err(read_error)?),
+            "replace" => {
+                let value = field.text().await.map_err(read_error)?;
+                params.replace = value == "true" || value == "on";
+            }
+            _ => {}
+        }
+    }
+
+    let scope = TenantScope::for_user(&auth_user);
+    match ValidationPluginService::upload(&ctx.db, &scope, params, &auth_user.email).await {
+        Ok(plugin) => format::html(&format!(
+            r#"<div class="p-4 rounded-lg bg-green-500/10 border border-green-500/20">
+                    <p class="text-sm text-green-700 font-medium">Plugin '{}' uploaded</p>
+                    <p class="text-xs text-green-600 mt-1">SHA-256: {}… | {} bytes</p>
+                </div>
+                <script>
+                    setTimeout(() => {{
+                        document.getElementById('modal-container').innerHTML = '';
+                        htmx.ajax('GET', '/admin/validation-plugins/list', {{target: '#plugin-list', swap: 'outerHTML'}});
+                    }}, 2000);
+                </script>"#,
+            plugin.name, plugin.sha256_short, plugin.size_bytes
+        )),
+        Err(e) => format::html(&format!(
+            r#"<div class="p-4 rounded-lg bg-red-500/10 border border-red-500/20">
+                    <p class="text-sm text-red-700 font-medium">Upload Failed</p>
+                    <p class="text-xs text-red-600 mt-1">{}</p>
+                </div>"#,
+            html_escape(&e.to_string())
+        )),
+    }
+}
+
+/// Enable or disable a plugin
+#[debug_handler]
+pub async fn toggle(
+    auth_user: AuthUser,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    let item = ValidationPluginService::toggle(&ctx.db, &scope, id).await?;
+
+    format::render().view(
+        &v,
+        "admin/validation_plugins/row.html",
+        data!({ "item": item, "can_configure": true }),
+    )
+}
+
+/// Delete a plugin
+#[debug_handler]
+pub async fn delete(
+    auth_user: AuthUser,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let scope = TenantScope::for_user(&auth_user);
+    ValidationPluginService::delete(&ctx.db, &scope, id).await?;
+
+    format::html("")
+}
+
+/// Compile errors quote the module's export and import names
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
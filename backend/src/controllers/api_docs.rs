@@ -0,0 +1,261 @@
+//! API Documentation Controller
+//!
+//! Serves the OpenAPI document of the public REST endpoints and a Swagger UI
+//! page for plugin developers. The examples below are checked against the
+//! request/response structs of each endpoint on every request.
+
+use axum::debug_handler;
+use loco_rs::app::Hooks;
+use loco_rs::prelude::*;
+use serde_json::{json, Value};
+
+use crate::app::App;
+use crate::controllers::generate::{GenerateApiRequest, HealthResponse};
+use crate::controllers::jobs::QueueStatsResponse;
+use crate::controllers::qa::QAApiRequest;
+use crate::controllers::refactor::{DuplicatesRequest, RenameRequest};
+use crate::controllers::review::{ApplyPatchRequest, ApplyPatchResponse, ReviewApiRequest, ReviewGateApiRequest};
+use crate::controllers::spring_module::SpringModuleApiRequest;
+use crate::domain::{GenerateResponse, QAResponse, ReviewGateResponse, ReviewResponse};
+use crate::services::job_status::JobStatusResponse;
+use crate::services::openapi::{ApiOperation, OpenApi};
+use crate::services::{ScalingConfig, ScalingHints};
+
+const TIMESTAMP: &str = "2026-10-16T09:00:00Z";
+
+fn schema_input() -> Value {
+    json!({
+        "type": "db_schema",
+        "table": "member",
+        "columns": [
+            { "name": "member_id", "column_type": "INTEGER", "nullable": false, "pk": true },
+            { "name": "member_name", "column_type": "VARCHAR(100)", "nullable": false, "pk": false, "comment": "회원명" }
+        ],
+        "primary_keys": ["member_id"]
+    })
+}
+
+fn review_request() -> Value {
+    json!({
+        "product": "xframe5-ui",
+        "input": { "code": "<screen id=\"member_list\">...</screen>", "file_type": "xml" },
+        "context": { "project": "erp", "file_name": "member_list.xml" }
+    })
+}
+
+fn review_result() -> Value {
+    json!({
+        "summary": "Grid binding is correct; the search function lacks input validation.",
+        "issues": [{
+            "severity": "warning",
+            "category": "pattern",
+            "line": 12,
+            "message": "fn_search does not validate the search conditions",
+            "suggestion": "Check required conditions before the transaction"
+        }],
+        "score": { "overall": 82, "categories": { "syntax": 95, "patterns": 75 } },
+        "improvements": []
+    })
+}
+
+/// Documented operations (examples round-tripped through their types)
+pub fn operations() -> std::result::Result<Vec<ApiOperation>, serde_json::Error> {
+    let job = json!({
+        "job_id": "6f1c2e1a-8d4b-4c55-9a0e-3f5b2d7c9e10",
+        "status": "queued",
+        "queue_position": 2,
+        "estimated_wait_secs": 60,
+        "product": "xframe5-ui",
+        "timestamps": { "queued_at": TIMESTAMP }
+    });
+    let scaling = ScalingHints::compute(&ScalingConfig::default(), chrono::Utc::now(), &[], &[4.5], Default::default());
+
+    Ok(vec![
+        ApiOperation::new("post", "/agent/generate", "generate", "Generate a screen or Spring backend")
+            .query("mode", "`async` queues the request and returns a job ID")
+            .query("force", "`true` generates even if the project already has the screen")
+            .request(OpenApi::example::<GenerateApiRequest>(json!({
+                "product": "xframe5-ui",
+                "input": schema_input(),
+                "context": { "project": "erp", "issue_key": "ERP-123" }
+            }))?)
+            .response(OpenApi::example::<GenerateResponse>(json!({
+                "status": "success",
+                "artifacts": {
+                    "xml": "<screen id=\"member_list\">...</screen>",
+                    "javascript": "this.fn_search = function() { ... };",
+                    "xml_filename": "member_list.xml",
+                    "js_filename": "member_list.js"
+                },
+                "warnings": ["Grid column member_name has no header text; used the column name"],
+                "error": null,
+                "meta": { "generator": "xframe5-ui-v1", "timestamp": TIMESTAMP, "generation_time_ms": 5200 }
+            }))?),
+        ApiOperation::new("post", "/agent/generate/spring-module", "generate", "Generate a Spring module for several entities")
+            .request(OpenApi::example::<SpringModuleApiRequest>(json!({
+                "entities": [schema_input()],
+                "context": { "project": "com.company.erp" }
+            }))?),
+        ApiOperation::new("get", "/agent/health", "generate", "Server health")
+            .response(serde_json::to_value(HealthResponse {
+                status: "healthy".to_string(),
+                llm_available: true,
+                message: None,
+            })?),
+        ApiOperation::new("get", "/agent/products", "generate", "Products and their input/output types"),
+        ApiOperation::new("get", "/agent/jobs", "jobs", "Status of several jobs")
+            .query("ids", "Comma-separated job IDs (up to 100)")
+            .response(json!({ "jobs": [OpenApi::example::<JobStatusResponse>(job.clone())?], "missing": [] })),
+        ApiOperation::new("get", "/agent/jobs/{job_id}", "jobs", "Status of a job (ETag / long-polling)")
+            .query("wait", "Long-poll up to this long for a change (`30s`, max 60s)")
+            .response(OpenApi::example::<JobStatusResponse>(job)?),
+        ApiOperation::new("delete", "/agent/jobs/{job_id}", "jobs", "Cancel a queued job"),
+        ApiOperation::new("get", "/agent/jobs/{job_id}/download", "jobs", "Download the artifacts of a finished job as a ZIP"),
+        ApiOperation::new("post", "/agent/jobs/{job_id}/push", "jobs", "Push the artifacts of a finished job to the project's Git remote"),
+        ApiOperation::new("get", "/agent/queue/stats", "jobs", "Queue statistics")
+            .response(OpenApi::example::<QueueStatsResponse>(json!({
+                "stats": { "queued": 2, "processing": 1, "completed": 120, "failed": 3 },
+                "avg_processing_time_ms": 48000
+            }))?),
+        ApiOperation::new("get", "/agent/queue/scaling", "jobs", "Autoscaling hints")
+            .query("format", "`prometheus` for the Prometheus text format")
+            .response(serde_json::to_value(scaling)?),
+        ApiOperation::new("post", "/agent/review", "review", "Review code")
+            .request(OpenApi::example::<ReviewApiRequest>(review_request())?)
+            .response(OpenApi::example::<ReviewResponse>(json!({
+                "status": "success",
+                "review": review_result(),
+                "error": null,
+                "meta": { "generator": "xframe5-ui-review-v1", "timestamp": TIMESTAMP, "review_time_ms": 3100 }
+            }))?),
+        ApiOperation::new("post", "/agent/review/gate", "review", "Review code against a pass/fail policy (CI)")
+            .request(OpenApi::example::<ReviewGateApiRequest>({
+                let mut request = review_request();
+                request["policy"] = json!({ "max_errors": 0, "max_warnings": 5, "min_score": 70 });
+                request
+            })?)
+            .response(OpenApi::example::<ReviewGateResponse>(json!({
+                "status": "success",
+                "exit_code": 0,
+                "gate": {
+                    "passed": true,
+                    "summary": {
+                        "total": { "errors": 0, "warnings": 1, "infos": 0, "suggestions": 0 },
+                        "by_category": { "pattern": { "errors": 0, "warnings": 1, "infos": 0, "suggestions": 0 } },
+                        "score": 82
+                    },
+                    "violations": []
+                },
+                "review": review_result(),
+                "error": null,
+                "meta": { "generator": "xframe5-ui-review-v1", "timestamp": TIMESTAMP, "review_time_ms": 3100 }
+            }))?),
+        ApiOperation::new("post", "/agent/review/apply", "review", "Apply review patches")
+            .request(OpenApi::example::<ApplyPatchRequest>(json!({
+                "code": "<screen id=\"member_list\">...</screen>",
+                "patches": ["@@ -12 +12 @@\n-old\n+new\n"]
+            }))?)
+            .response(OpenApi::example::<ApplyPatchResponse>(json!({
+                "status": "success",
+                "code": "<screen id=\"member_list\">...</screen>",
+                "applied": 1
+            }))?),
+        ApiOperation::new("post", "/agent/review/project", "review", "Review every file of a project ZIP (multipart)")
+            .query("format", "`json` (default), `sarif` or `html` report"),
+        ApiOperation::new("post", "/agent/qa", "qa", "Answer a question from the knowledge base")
+            .request(OpenApi::example::<QAApiRequest>(json!({
+                "product": "xframe5-ui",
+                "input": { "question": "How do I bind a Dataset to a grid?" }
+            }))?)
+            .response(OpenApi::example::<QAResponse>(json!({
+                "status": "success",
+                "answer": {
+                    "text": "Set the grid's `link_data` to the Dataset ID.",
+                    "code_examples": [{ "language": "xml", "code": "<grid link_data=\"ds_list\"/>", "description": null }],
+                    "related_topics": ["Dataset"]
+                },
+                "references": [{ "knowledge_id": 3, "name": "grid", "category": "component", "section": null, "relevance": 0.5 }],
+                "error": null,
+                "meta": { "generator": "xframe5-qa-v1", "timestamp": TIMESTAMP, "answer_time_ms": 2100 }
+            }))?),
+        ApiOperation::new("post", "/agent/refactor/rename", "refactor", "Rename a symbol or entity across generated files")
+            .request(OpenApi::example::<RenameRequest>(json!({
+                "files": [
+                    { "name": "task_list.xml", "content": "<screen ...>" },
+                    { "name": "task_list.js", "content": "this.fn_search = ..." }
+                ],
+                "from": "fn_search",
+                "to": "fn_query"
+            }))?),
+        ApiOperation::new("post", "/agent/refactor/duplicates", "refactor", "Find copy-paste duplicates and pattern drift across screens")
+            .request(OpenApi::example::<DuplicatesRequest>(json!({
+                "project": "erp",
+                "files": [{ "name": "member_popup.js", "content": "this.fn_search = ..." }]
+            }))?),
+        ApiOperation::new("get", "/agent/screens", "screens", "Screens delivered in a project and their owners")
+            .query("project", "Project name"),
+        ApiOperation::new("get", "/agent/screens/check", "screens", "Whether a screen id or file name is free in a project")
+            .query("project", "Project name")
+            .query("screen_id", "Screen id (`SCREEN_MEMBER_LIST`)")
+            .query("file", "XML or JS file name"),
+        ApiOperation::new("get", "/api/my/generations", "my_generations", "The caller's generations")
+            .query("entity", "Screen or entity name")
+            .query("product", "Product identifier")
+            .query("date_from", "First day (YYYY-MM-DD)")
+            .query("date_to", "Last day (YYYY-MM-DD)")
+            .query("page", "Page number")
+            .query("page_size", "Rows per page"),
+        ApiOperation::new("get", "/api/my/generations/{id}/download", "my_generations", "Download one of the caller's generations"),
+        ApiOperation::new("get", "/api/my/generations/{id}/todos", "my_generations", "TODO placeholders left in a generation"),
+        ApiOperation::new("get", "/api/my/generations/{id}/lineage", "my_generations", "Where each source column ended up")
+            .query("column", "Source column")
+            .query("file", "Generated file")
+            .query("line", "Line in the file"),
+        ApiOperation::new("post", "/api/my/generations/{id}/todos/resolve", "my_generations", "Fill in TODO placeholders")
+            .request(json!({ "resolutions": [{ "id": "todo-1", "value": "TB_MEMBER" }] })),
+        ApiOperation::new("get", "/api/my/schema-changes", "schema_changes", "The caller's generations whose tables changed"),
+        ApiOperation::new("post", "/api/my/schema-changes/snapshots", "schema_changes", "Record introspected tables")
+            .request(json!({
+                "tables": [{
+                    "table": "TB_MEMBER",
+                    "columns": [{ "name": "EMAIL", "column_type": "VARCHAR2(200)", "nullable": true, "pk": false }]
+                }]
+            })),
+        ApiOperation::new("post", "/api/my/schema-changes/{id}/regenerate", "schema_changes", "Regenerate with the schema diff"),
+    ])
+}
+
+/// OpenAPI document of the application's routes (also used by the `client_sdk` task)
+pub fn document(ctx: &AppContext) -> Result<Value> {
+    let routes: Vec<(String, Vec<String>)> = App::routes(ctx)
+        .collect()
+        .into_iter()
+        .map(|route| (route.uri, route.actions.iter().map(ToString::to_string).collect()))
+        .collect();
+    let operations = operations().map_err(|e| Error::string(&format!("Invalid API example: {}", e)))?;
+
+    Ok(OpenApi::document(&routes, &operations))
+}
+
+/// OpenAPI document
+///
+/// GET /api/openapi.json
+#[debug_handler]
+pub async fn openapi(State(ctx): State<AppContext>) -> Result<Response> {
+    format::json(document(&ctx)?)
+}
+
+/// Swagger UI page
+///
+/// GET /api/docs
+#[debug_handler]
+pub async fn docs(ViewEngine(v): ViewEngine<TeraView>) -> Result<Response> {
+    format::render().view(&v, "api_docs/index.html", data!({ "spec_url": "/api/openapi.json" }))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/")
+        .add("openapi.json", get(openapi))
+        .add("docs", get(docs))
+}
@@ -2,67 +2,91 @@
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
 use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
 use serde::{Deserialize, Serialize};
 
-use crate::models::_entities::company_rules::{ActiveModel, Entity, Model};
+use crate::domain::CompanyRuleSections;
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::company_rules::{Column, Entity};
+use crate::services::admin::company_rule::{CompanyRuleService, CreateParams, UpdateParams};
+use crate::services::TenantScope;
+use crate::utils::OptionalField;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Params {
     pub name: String,
     pub naming_convention: Option<String>,
     pub additional_rules: Option<String>,
+    #[serde(default)]
+    pub sections: Option<CompanyRuleSections>,
 }
 
 impl Params {
-    fn update(&self, item: &mut ActiveModel) {
-        item.name = Set(self.name.clone());
-        item.naming_convention = Set(self.naming_convention.clone());
-        item.additional_rules = Set(self.additional_rules.clone());
+    /// Sections as the JSON text the service parses
+    fn sections_json(&self) -> Result<Option<String>> {
+        Ok(self.sections.as_ref().map(serde_json::to_string).transpose()?)
+    }
+
+    fn into_create(self) -> Result<CreateParams> {
+        Ok(CreateParams {
+            sections: self.sections_json()?,
+            name: self.name,
+            naming_convention: self.naming_convention,
+            additional_rules: self.additional_rules,
+            company: None,
+        })
     }
-}
 
-async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
-    let item = Entity::find_by_id(id).one(&ctx.db).await?;
-    item.ok_or_else(|| Error::NotFound)
+    fn into_update(self) -> Result<UpdateParams> {
+        Ok(UpdateParams {
+            sections: OptionalField::Present(self.sections_json()?),
+            name: Some(self.name),
+            naming_convention: OptionalField::Present(self.naming_convention),
+            additional_rules: OptionalField::Present(self.additional_rules),
+        })
+    }
 }
 
 #[debug_handler]
-pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(Entity::find().all(&ctx.db).await?)
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?)
 }
 
 #[debug_handler]
-pub async fn add(State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
-    let mut item = ActiveModel {
-        ..Default::default()
-    };
-    params.update(&mut item);
-    let item = item.insert(&ctx.db).await?;
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let item = CompanyRuleService::create(&ctx.db, &scope, &user.email, params.into_create()?).await?;
     format::json(item)
 }
 
 #[debug_handler]
 pub async fn update(
+    auth: auth::JWT,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<Params>,
 ) -> Result<Response> {
-    let item = load_item(&ctx, id).await?;
-    let mut item = item.into_active_model();
-    params.update(&mut item);
-    let item = item.update(&ctx.db).await?;
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let item = CompanyRuleService::update(&ctx.db, &scope, &user.email, id, params.into_update()?).await?;
     format::json(item)
 }
 
 #[debug_handler]
-pub async fn remove(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    load_item(&ctx, id).await?.delete(&ctx.db).await?;
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    CompanyRuleService::delete(&ctx.db, &scope, &user.email, id).await?;
     format::empty()
 }
 
 #[debug_handler]
-pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(load_item(&ctx, id).await?)
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(CompanyRuleService::find_by_id(&ctx.db, &TenantScope::for_user(&user), id).await?)
 }
 
 pub fn routes() -> Routes {
@@ -2,8 +2,12 @@
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
 
+use std::net::SocketAddr;
+
 use axum::debug_handler;
-use axum::extract::Query;
+use axum::extract::{ConnectInfo, Query};
+use axum::http::StatusCode;
+use axum::Extension;
 use loco_rs::prelude::*;
 use sea_orm::{ActiveModelTrait, Set};
 use serde::{Deserialize, Serialize};
@@ -12,9 +16,15 @@ use uuid::Uuid;
 use crate::domain::{
     GenerateInput, GenerateOptions, GenerateResponse, GenerateStatus, RequestContext,
 };
+use crate::middleware::api_auth::ApiCaller;
 use crate::models::_entities::generation_logs;
-use crate::services::{GenerationService, SpringGenerationService};
+use crate::services::telemetry::{self, Span, SpanContext, SpanKind};
+use crate::services::{
+    DemoConfig, DemoService, DuplicatePolicy, DuplicateScreen, DuplicateScreenDetector, GenerationService,
+    SpringGenerationService,
+};
 use crate::workers::generation::GenerateJobRequest;
+use crate::workers::job_queue;
 
 /// API request for code generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +58,9 @@ pub struct GenerateQuery {
     /// Processing mode: "async" for queue-based, omit for sync
     #[serde(default)]
     pub mode: Option<String>,
+    /// Generate even if the screen was generated before in the project
+    #[serde(default)]
+    pub force: bool,
 }
 
 impl GenerateQuery {
@@ -67,6 +80,16 @@ pub struct AsyncGenerateResponse {
     pub status_url: String,
     /// Message
     pub message: String,
+    /// Notices about the request (e.g. a duplicate screen)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Response when a duplicate screen blocks the request
+#[derive(Debug, Serialize)]
+pub struct DuplicateScreenResponse {
+    pub error: String,
+    pub duplicate: DuplicateScreen,
 }
 
 /// Health check response
@@ -81,6 +104,7 @@ pub struct HealthResponse {
 ///
 /// POST /agent/generate
 /// POST /agent/generate?mode=async (async mode)
+/// POST /agent/generate?force=true (generate even if the screen already exists)
 ///
 /// Request:
 /// ```json
@@ -97,7 +121,8 @@ pub struct HealthResponse {
 ///   },
 ///   "context": {
 ///     "project": "xframe5",
-///     "output": ["xml", "javascript"]
+///     "output": ["xml", "javascript"],
+///     "issue_key": "ERP-123"
 ///   },
 ///   "priority": 3
 /// }
@@ -124,8 +149,11 @@ pub struct HealthResponse {
 /// ```
 #[debug_handler]
 pub async fn generate(
+    caller: ApiCaller,
     State(ctx): State<AppContext>,
     Query(query): Query<GenerateQuery>,
+    peer: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: axum::http::HeaderMap,
     Json(req): Json<GenerateApiRequest>,
 ) -> Result<Response> {
     // Validate product
@@ -135,6 +163,7 @@ pub async fn generate(
             artifacts: None,
             warnings: vec![],
             error: Some("Product is required".to_string()),
+            error_code: None,
             meta: crate::domain::ResponseMeta {
                 generator: "unknown".to_string(),
                 timestamp: chrono::Utc::now(),
@@ -143,19 +172,120 @@ pub async fn generate(
         });
     }
 
-    // TODO: Extract user ID from JWT token when auth is integrated
-    let user_id: i32 = 1; // Default to system user for now
+    if let Err(e) = req.context.validate() {
+        return format::json(GenerateResponse {
+            status: GenerateStatus::Error,
+            artifacts: None,
+            warnings: vec![],
+            error: Some(e),
+            error_code: None,
+            meta: crate::domain::ResponseMeta {
+                generator: "unknown".to_string(),
+                timestamp: chrono::Utc::now(),
+                generation_time_ms: 0,
+            },
+        });
+    }
+
+    // Public demo: curated samples, rate limited, nothing stored
+    let demo = DemoService::config();
+    if demo.enabled {
+        return generate_demo(req, peer.map(|Extension(ConnectInfo(addr))| addr), &headers, demo).await;
+    }
+
+    // Join the caller's trace when a W3C traceparent header is sent
+    let parent = headers
+        .get("traceparent")
+        .and_then(|v| v.to_str().ok())
+        .and_then(SpanContext::from_traceparent);
+    let mut span = Span::start("http.request", SpanKind::Server, parent.as_ref())
+        .with_attr("http.route", "/agent/generate")
+        .with_attr("product", req.product.as_str())
+        .with_attr("async", query.is_async());
+
+    // Look for an earlier generation of the same screen in the project
+    let mut notices = Vec::new();
+    let policy = DuplicatePolicy::from_env();
+    if policy != DuplicatePolicy::Off && !query.force {
+        match DuplicateScreenDetector::check(&ctx.db, &req.product, &req.input, &req.context, caller.user_id).await {
+            Ok(Some(duplicate)) if policy == DuplicatePolicy::Block => {
+                span.end();
+                return format::render().status(StatusCode::CONFLICT).json(DuplicateScreenResponse {
+                    error: format!("{}. Resend with ?force=true to generate it anyway.", duplicate.message()),
+                    duplicate,
+                });
+            }
+            Ok(Some(duplicate)) => notices.push(duplicate.message()),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Duplicate screen check failed: {}", e),
+        }
+    }
 
     // Check if async mode is requested
     tracing::debug!("Query params: {:?}, is_async: {}", query, query.is_async());
-    if query.is_async() {
+    let result = if query.is_async() {
         tracing::info!("Async mode requested, enqueueing job");
-        return enqueue_job(&ctx, &req, user_id).await;
+        span.in_scope(enqueue_job(&ctx, &req, caller.stored_user_id(), notices)).await
+    } else {
+        // Synchronous processing (legacy mode)
+        tracing::info!("Sync mode, processing immediately");
+        span.in_scope(process_sync(&ctx, req, caller.user_id, notices)).await
+    };
+
+    if let Err(e) = &result {
+        span.set_error(e);
     }
+    span.end();
+    result
+}
 
-    // Synchronous processing (legacy mode)
-    tracing::info!("Sync mode, processing immediately");
-    process_sync(&ctx, req, user_id).await
+/// Demo generation (see `DemoService`); async mode is ignored, as a job row
+/// would store the input
+async fn generate_demo(
+    req: GenerateApiRequest,
+    peer: Option<SocketAddr>,
+    headers: &axum::http::HeaderMap,
+    demo: &DemoConfig,
+) -> Result<Response> {
+    let limit = demo.rate_limit;
+    let client = demo.client_address(peer, headers);
+    if let Err(retry_after) = DemoService::limiter().check(&client, limit, std::time::Instant::now()) {
+        return format::render()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header("Retry-After", retry_after.to_string())
+            .json(GenerateResponse {
+                status: GenerateStatus::Error,
+                artifacts: None,
+                warnings: vec![],
+                error: Some(format!(
+                    "Demo limit of {} generations per hour reached. Try again in {} minutes.",
+                    limit,
+                    retry_after.div_ceil(60)
+                )),
+                error_code: None,
+                meta: crate::domain::ResponseMeta {
+                    generator: format!("{}-demo", req.product),
+                    timestamp: chrono::Utc::now(),
+                    generation_time_ms: 0,
+                },
+            });
+    }
+
+    match DemoService::generate(req.input, &req.product).await {
+        Ok(resp) => format::json(resp),
+        Err(e) => format::json(GenerateResponse {
+            status: GenerateStatus::Error,
+            artifacts: None,
+            warnings: vec![],
+            error: Some(format!("Generation failed: {}", e)),
+            error_code: None,
+            meta: crate::domain::ResponseMeta {
+                generator: format!("{}-demo", req.product),
+                timestamp: chrono::Utc::now(),
+                generation_time_ms: 0,
+            },
+        }),
+    }
 }
 
 /// Enqueue a job for async processing
@@ -163,6 +293,7 @@ async fn enqueue_job(
     ctx: &AppContext,
     req: &GenerateApiRequest,
     user_id: i32,
+    notices: Vec<String>,
 ) -> Result<Response> {
     let job_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
@@ -172,6 +303,10 @@ async fn enqueue_job(
         GenerateInput::DbSchema(_) => "db_schema",
         GenerateInput::QuerySample(_) => "query_sample",
         GenerateInput::NaturalLanguage(_) => "natural_language",
+        GenerateInput::Dashboard(_) => "dashboard",
+        GenerateInput::Intent(_) => "intent",
+        GenerateInput::SavedIntent(_) => "saved_intent",
+        GenerateInput::Dsl(_) => "dsl",
     };
 
     // Create job payload
@@ -180,6 +315,7 @@ async fn enqueue_job(
         input: req.input.clone(),
         options: req.options.clone(),
         context: req.context.clone(),
+        trace_parent: telemetry::current_context().map(|c| c.to_traceparent()),
     };
 
     let payload_json = serde_json::to_string(&payload)
@@ -197,10 +333,16 @@ async fn enqueue_job(
         queued_at: Set(Some(now.into())),
         priority: Set(req.priority.clamp(1, 5)),
         user_id: Set(user_id),
+        project: Set(req.context.project.clone()),
+        issue_key: Set(req.context.issue_key.clone()),
         ..Default::default()
     };
 
-    new_job.insert(&ctx.db).await?;
+    let new_job = new_job.insert(&ctx.db).await?;
+    // A missed push is picked up again from the stored row
+    if let Err(e) = job_queue().push(&new_job).await {
+        tracing::warn!("Could not push job {} to the queue: {}", job_id, e);
+    }
 
     tracing::info!("Job {} queued for {} generation", job_id, req.product);
 
@@ -209,6 +351,7 @@ async fn enqueue_job(
         status: "queued".to_string(),
         status_url: format!("/agent/jobs/{}", job_id),
         message: "Job queued for processing. Poll status_url for updates.".to_string(),
+        warnings: notices,
     })
 }
 
@@ -216,7 +359,8 @@ async fn enqueue_job(
 async fn process_sync(
     ctx: &AppContext,
     req: GenerateApiRequest,
-    user_id: i32,
+    user_id: Option<i32>,
+    notices: Vec<String>,
 ) -> Result<Response> {
     // Route based on product type
     match req.product.as_str() {
@@ -227,12 +371,15 @@ async fn process_sync(
                 req.input,
                 &req.options,
                 &req.context,
-                Some(user_id),
+                user_id,
             )
             .await;
 
             match response {
-                Ok(resp) => format::json(resp),
+                Ok(mut resp) => {
+                    resp.warnings.splice(0..0, notices);
+                    format::json(resp)
+                }
                 Err(e) => {
                     tracing::error!("Spring generation failed: {}", e);
                     format::json(crate::services::SpringGenerateResponse {
@@ -240,6 +387,7 @@ async fn process_sync(
                         artifacts: None,
                         warnings: vec![],
                         error: Some(format!("Generation failed: {}", e)),
+                        error_code: None,
                         meta: crate::domain::ResponseMeta {
                             generator: "spring-backend-v1".to_string(),
                             timestamp: chrono::Utc::now(),
@@ -257,12 +405,15 @@ async fn process_sync(
                 &req.product,
                 &req.options,
                 &req.context,
-                Some(user_id),
+                user_id,
             )
             .await;
 
             match response {
-                Ok(resp) => format::json(resp),
+                Ok(mut resp) => {
+                    resp.warnings.splice(0..0, notices);
+                    format::json(resp)
+                }
                 Err(e) => {
                     tracing::error!("Generation failed: {}", e);
                     format::json(GenerateResponse {
@@ -270,6 +421,7 @@ async fn process_sync(
                         artifacts: None,
                         warnings: vec![],
                         error: Some(format!("Generation failed: {}", e)),
+                        error_code: None,
                         meta: crate::domain::ResponseMeta {
                             generator: format!("{}-v1", req.product),
                             timestamp: chrono::Utc::now(),
@@ -287,8 +439,17 @@ async fn process_sync(
 /// GET /agent/health
 #[debug_handler]
 pub async fn health(State(ctx): State<AppContext>) -> Result<Response> {
+    // Demo mode needs no model server
+    if DemoService::config().enabled {
+        return format::json(HealthResponse {
+            status: "healthy".to_string(),
+            llm_available: true,
+            message: Some("Demo mode: responses are curated samples".to_string()),
+        });
+    }
+
     // Check LLM availability (DB config takes priority, falls back to env)
-    let llm = crate::llm::create_backend_from_db_or_env(&ctx.db).await;
+    let llm = crate::llm::create_backend_from_db_or_env(&ctx.db, None).await;
     let llm_check = llm.health_check().await;
 
     let (llm_available, message) = match llm_check {
@@ -315,7 +476,7 @@ pub async fn list_products(State(_ctx): State<AppContext>) -> Result<Response> {
                 "name": "xFrame5 UI Generator",
                 "description": "Generate xFrame5 XML views and JavaScript handlers",
                 "status": "available",
-                "input_types": ["db_schema", "query_sample", "natural_language"],
+                "input_types": ["db_schema", "query_sample", "natural_language", "intent", "saved_intent", "dsl"],
                 "output_types": ["xml", "javascript"]
             },
             {
@@ -323,8 +484,9 @@ pub async fn list_products(State(_ctx): State<AppContext>) -> Result<Response> {
                 "name": "Spring Framework Generator",
                 "description": "Generate Spring Controller, Service, DTO, and MyBatis Mapper",
                 "status": "available",
-                "input_types": ["db_schema", "query_sample", "natural_language"],
-                "output_types": ["controller", "service", "service_impl", "dto", "mapper", "mapper_xml"]
+                "input_types": ["db_schema", "query_sample", "natural_language", "intent", "saved_intent", "dsl"],
+                "output_types": ["controller", "service", "service_impl", "dto", "mapper", "mapper_xml"],
+                "module_endpoint": "/agent/generate/spring-module"
             }
         ]
     }))
@@ -5,6 +5,7 @@ use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::generation_logs::{ActiveModel, Entity, Model};
+use crate::services::{KnowledgeBaseService, KnowledgeRevision};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Params {
@@ -79,12 +80,38 @@ pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Resu
     format::json(load_item(&ctx, id).await?)
 }
 
+#[derive(Debug, Serialize)]
+pub struct KnowledgeResponse {
+    pub generation_log_id: i32,
+    pub revisions: Vec<KnowledgeRevision>,
+    /// Knowledge section of the system prompt as the generation saw it
+    pub content: String,
+}
+
+/// Rebuild the knowledge context a past generation saw
+#[debug_handler]
+pub async fn knowledge(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let item = load_item(&ctx, id).await?;
+    let revisions: Vec<KnowledgeRevision> = match item.knowledge_revisions.as_deref() {
+        Some(json) => serde_json::from_str(json).map_err(|e| Error::string(&e.to_string()))?,
+        None => Vec::new(),
+    };
+    let content = KnowledgeBaseService::content_at(&ctx.db, &revisions).await?;
+
+    format::json(KnowledgeResponse {
+        generation_log_id: item.id,
+        revisions,
+        content,
+    })
+}
+
 pub fn routes() -> Routes {
     Routes::new()
         .prefix("api/generation_logs/")
         .add("/", get(list))
         .add("/", post(add))
         .add("{id}", get(get_one))
+        .add("{id}/knowledge", get(knowledge))
         .add("{id}", delete(remove))
         .add("{id}", put(update))
         .add("{id}", patch(update))
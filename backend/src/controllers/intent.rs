@@ -0,0 +1,116 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+
+use axum::debug_handler;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::GenerateInput;
+use crate::middleware::api_auth::ApiCaller;
+use crate::services::{ComplexityReport, IntentComplexity, IntentMerger, ScreenSplit};
+
+/// API request for an intent merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequest {
+    /// Base input (schema, query, DSL, saved intent, ...) normalized to an intent
+    pub base: GenerateInput,
+
+    /// Refinements, one per line or separated by `,` / `;`
+    pub instructions: String,
+}
+
+/// Apply refinement instructions onto the intent of a base input
+///
+/// POST /agent/intent/merge
+///
+/// Request:
+/// ```json
+/// {
+///   "base": { "type": "db_schema", "table": "TB_MEMBER", "columns": [...] },
+///   "instructions": "status column should be a combobox with code group CM001, hide email"
+/// }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "intent": { "screen_name": "member_list", ... },
+///   "applied": [{ "instruction": "hide email", "refinements": [{ "command": "hide", "column": "EMAIL_ADDR" }] }],
+///   "skipped": [{ "instruction": "make it pretty", "reason": "..." }],
+///   "changes": [{ "path": "datasets[ds_member].columns[STATUS_CD].code_group", "after": "CM001" }]
+/// }
+/// ```
+#[debug_handler]
+pub async fn merge(caller: ApiCaller, State(ctx): State<AppContext>, Json(req): Json<MergeRequest>) -> Result<Response> {
+    let base = IntentMerger::base_intent(&ctx.db, &req.base, &caller.tenant())
+        .await
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    let result = IntentMerger::merge(&base, &req.instructions).map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(result)
+}
+
+/// API request for an intent complexity estimate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityRequest {
+    /// Input (schema, query, DSL, saved intent, ...) normalized to an intent
+    pub base: GenerateInput,
+
+    /// Also build the list and tabbed detail intents of a split
+    #[serde(default)]
+    pub split: bool,
+}
+
+/// Complexity estimate, with the split intents when asked for
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplexityResponse {
+    #[serde(flatten)]
+    pub report: ComplexityReport,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split: Option<ScreenSplit>,
+}
+
+/// Estimate how wide the screen of an input is and how to split it
+///
+/// POST /agent/intent/complexity
+///
+/// Request:
+/// ```json
+/// { "base": { "type": "db_schema", "table": "TB_CUSTOMER", "columns": [...] }, "split": true }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "level": "too_wide",
+///   "field_count": 92,
+///   "grid_column_count": 92,
+///   "widest_dataset": "ds_customer",
+///   "estimated_prompt_tokens": 6100,
+///   "estimated_output_tokens": 16560,
+///   "suggestions": ["Suggestion: show only the key columns in the grid (8 of 92) ...", "..."],
+///   "column_groups": [{ "id": "basic", "title": "기본", "columns": ["CUST_ID", "CUST_NM"] }, { "id": "addr", "title": "ADDR", "columns": [...] }],
+///   "split": { "list": { "screen_name": "customer_list", ... }, "detail": { "screen_name": "customer_detail", "tabs": { "id": "detail", "tabs": [...] }, ... } }
+/// }
+/// ```
+#[debug_handler]
+pub async fn complexity(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Json(req): Json<ComplexityRequest>,
+) -> Result<Response> {
+    let intent = IntentMerger::base_intent(&ctx.db, &req.base, &caller.tenant())
+        .await
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    let report = IntentComplexity::estimate(&intent);
+    let split = if req.split { IntentComplexity::split(&intent) } else { None };
+    format::json(ComplexityResponse { report, split })
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("agent/")
+        .add("intent/merge", post(merge))
+        .add("intent/complexity", post(complexity))
+}
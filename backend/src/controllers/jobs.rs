@@ -7,56 +7,20 @@
 #![allow(clippy::unused_async)]
 
 use axum::debug_handler;
-use axum::extract::Path;
+use axum::extract::{Path, Query};
+use axum::http::{header, HeaderMap, StatusCode};
 use loco_rs::prelude::*;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::HeaderVars;
+use crate::middleware::api_auth::ApiCaller;
 use crate::models::_entities::generation_logs;
+use crate::services::job_status::{JobStatusResponse, JobStatusService, MAX_BATCH};
+use crate::services::retention::ARTIFACTS_EXPIRE_HEADER;
+use crate::services::{ArtifactPackager, GenerationHistoryService, GitPublisher, RetentionService, ScalingConfig, ScalingHints};
 use crate::workers::{JobQueueProcessor, QueueStats};
 
-/// Job status response
-#[derive(Debug, Serialize, Deserialize)]
-pub struct JobStatusResponse {
-    /// Job ID
-    pub job_id: String,
-    /// Current status: queued, processing, completed, failed
-    pub status: String,
-    /// Position in queue (if queued)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub queue_position: Option<i64>,
-    /// Estimated wait time in seconds (rough estimate)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub estimated_wait_secs: Option<i64>,
-    /// Generated artifacts (if completed)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub artifacts: Option<serde_json::Value>,
-    /// Warnings from generation
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub warnings: Option<Vec<String>>,
-    /// Error message (if failed)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub error: Option<String>,
-    /// Generation time in milliseconds (if completed)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub generation_time_ms: Option<i32>,
-    /// Product type
-    pub product: String,
-    /// Timestamps
-    pub timestamps: JobTimestamps,
-}
-
-/// Job timestamps
-#[derive(Debug, Serialize, Deserialize)]
-pub struct JobTimestamps {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub queued_at: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub started_at: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub completed_at: Option<String>,
-}
-
 /// Queue stats response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueueStatsResponse {
@@ -65,64 +29,133 @@ pub struct QueueStatsResponse {
     pub avg_processing_time_ms: Option<i64>,
 }
 
+/// Query parameters for autoscaling hints
+#[derive(Debug, Deserialize)]
+pub struct ScalingQuery {
+    /// `prometheus` for the text exposition format (default JSON)
+    pub format: Option<String>,
+}
+
+/// Query parameters for job status
+#[derive(Debug, Deserialize)]
+pub struct JobStatusQuery {
+    /// Long-poll: hold the request up to this long ("30", "30s") for a change
+    pub wait: Option<String>,
+}
+
+/// Query parameters for batch job status
+#[derive(Debug, Deserialize)]
+pub struct BatchStatusQuery {
+    /// Comma-separated job IDs
+    pub ids: String,
+}
+
+/// Batch job status response
+#[derive(Debug, Serialize)]
+pub struct BatchStatusResponse {
+    pub jobs: Vec<JobStatusResponse>,
+    /// Requested IDs that do not exist
+    pub missing: Vec<String>,
+}
+
+fn if_none_match(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok())
+}
+
+fn not_modified(etag: &str) -> Result<Response> {
+    Response::builder()
+        .status(StatusCode::NOT_MODIFIED)
+        .header(header::ETAG, etag)
+        .body(axum::body::Body::empty())
+        .map_err(|e| Error::string(&format!("Failed to build response: {}", e)))
+}
+
 /// Get job status by ID
 ///
 /// GET /agent/jobs/:job_id
+///
+/// Supports `If-None-Match` (304 when unchanged) and long-polling with
+/// `?wait=30s`: the request is held until the status differs from the
+/// client's ETag (or, without one, until the job finishes).
 #[debug_handler]
 pub async fn get_job_status(
     State(ctx): State<AppContext>,
     Path(job_id): Path<String>,
+    Query(query): Query<JobStatusQuery>,
+    headers: HeaderMap,
 ) -> Result<Response> {
-    let job = generation_logs::Entity::find()
-        .filter(generation_logs::Column::JobId.eq(&job_id))
-        .one(&ctx.db)
-        .await?;
-
-    let job = match job {
-        Some(j) => j,
-        None => {
-            return format::json(serde_json::json!({
-                "error": "Job not found",
-                "job_id": job_id
-            }));
-        }
+    let known_etag = if_none_match(&headers);
+    let wait = match query.wait.as_deref() {
+        Some(wait) => Some(
+            JobStatusService::parse_wait(wait)
+                .ok_or_else(|| Error::BadRequest(format!("Invalid wait value: {}", wait)))?,
+        ),
+        None => None,
     };
 
-    // Get queue position if still queued
-    let queue_position = if job.status == "queued" {
-        JobQueueProcessor::get_queue_position(&ctx.db, &job_id).await.ok().flatten()
-    } else {
-        None
+    let status = match wait {
+        Some(wait) => JobStatusService::wait_for_change(&ctx.db, &job_id, known_etag, wait).await,
+        None => JobStatusService::load(&ctx.db, &job_id).await,
+    }
+    .map_err(|e| Error::string(&e.to_string()))?;
+
+    let Some(status) = status else {
+        return format::json(serde_json::json!({
+            "error": "Job not found",
+            "job_id": job_id
+        }));
     };
 
-    // Estimate wait time (rough: 30 seconds per job ahead)
-    let estimated_wait_secs = queue_position.map(|pos| pos * 30);
+    let etag = status.etag();
+    if known_etag.is_some_and(|known| JobStatusService::matches(known, &etag)) {
+        return not_modified(&etag);
+    }
+
+    format::render().etag(&etag)?.json(status)
+}
+
+/// Get the status of several jobs at once
+///
+/// GET /agent/jobs?ids=a,b,c
+///
+/// Used by job list views; answers 304 when nothing in the list changed.
+#[debug_handler]
+pub async fn get_batch_status(
+    State(ctx): State<AppContext>,
+    Query(query): Query<BatchStatusQuery>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let mut ids: Vec<String> = Vec::new();
+    for id in query.ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        if !ids.iter().any(|known| known == id) {
+            ids.push(id.to_string());
+        }
+    }
+    if ids.is_empty() {
+        return Err(Error::BadRequest("ids must list at least one job ID".to_string()));
+    }
+    if ids.len() > MAX_BATCH {
+        return Err(Error::BadRequest(format!("At most {} job IDs per request", MAX_BATCH)));
+    }
 
-    // Parse artifacts if completed
-    let artifacts = job.artifacts.as_ref().and_then(|a| serde_json::from_str(a).ok());
+    let jobs = JobStatusService::load_many(&ctx.db, &ids)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+    let missing = ids
+        .into_iter()
+        .filter(|id| !jobs.iter().any(|job| &job.job_id == id))
+        .collect();
 
-    // Parse warnings
-    let warnings: Option<Vec<String>> = job
-        .warnings
-        .as_ref()
-        .and_then(|w| serde_json::from_str(w).ok());
+    let tags: Vec<String> = jobs.iter().map(JobStatusResponse::etag).collect();
+    let tags: Vec<&str> = tags.iter().map(String::as_str).collect();
+    let etag = JobStatusService::etag_for(&tags);
+    if if_none_match(&headers).is_some_and(|known| JobStatusService::matches(known, &etag)) {
+        return not_modified(&etag);
+    }
 
-    format::json(JobStatusResponse {
-        job_id: job_id.clone(),
-        status: job.status.clone(),
-        queue_position,
-        estimated_wait_secs,
-        artifacts,
-        warnings,
-        error: job.error_message.clone(),
-        generation_time_ms: job.generation_time_ms,
-        product: job.product.clone(),
-        timestamps: JobTimestamps {
-            queued_at: job.queued_at.map(|t| t.to_rfc3339()),
-            started_at: job.started_at.map(|t| t.to_rfc3339()),
-            completed_at: job.completed_at.map(|t| t.to_rfc3339()),
-        },
-    })
+    format::render()
+        .etag(&etag)?
+        .json(BatchStatusResponse { jobs, missing })
 }
 
 /// Get queue statistics
@@ -160,6 +193,48 @@ pub async fn get_queue_stats(State(ctx): State<AppContext>) -> Result<Response>
     })
 }
 
+/// Autoscaling hints: queue depth, queue wait and per-provider saturation
+///
+/// GET /agent/queue/scaling
+///
+/// JSON by default; Prometheus text with `?format=prometheus` or a
+/// `text/plain` / OpenMetrics `Accept` header (Prometheus scrapes). When
+/// `SCALING_METRICS_TOKEN` is set, it is required as a bearer token.
+#[debug_handler]
+pub async fn get_scaling_hints(
+    State(ctx): State<AppContext>,
+    headers: HeaderMap,
+    Query(query): Query<ScalingQuery>,
+) -> Result<Response> {
+    if let Some(token) = std::env::var("SCALING_METRICS_TOKEN").ok().filter(|t| !t.trim().is_empty()) {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if bearer != Some(token.trim()) {
+            return Err(Error::Unauthorized("Invalid scaling metrics token".to_string()));
+        }
+    }
+
+    let hints = ScalingHints::collect(&ctx.db, &ScalingConfig::from_env())
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+
+    let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let prometheus = match query.format.as_deref() {
+        Some(format) => format.eq_ignore_ascii_case("prometheus"),
+        None => accept.contains("text/plain") || accept.contains("openmetrics"),
+    };
+    if prometheus {
+        return Response::builder()
+            .header(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")
+            .body(axum::body::Body::from(hints.to_prometheus()))
+            .map_err(|e| Error::string(&format!("Failed to build response: {}", e)));
+    }
+
+    format::json(hints)
+}
+
 /// Cancel a queued job
 ///
 /// DELETE /agent/jobs/:job_id
@@ -199,6 +274,7 @@ pub async fn cancel_job(
     active_job.status = Set("cancelled".to_string());
     active_job.completed_at = Set(Some(chrono::Utc::now().into()));
     active_job.update(&ctx.db).await?;
+    JobStatusService::notify();
 
     format::json(serde_json::json!({
         "success": true,
@@ -207,11 +283,110 @@ pub async fn cancel_job(
     }))
 }
 
+/// Download a completed job's artifacts as a ZIP
+///
+/// GET /agent/jobs/:job_id/download
+///
+/// Files are written with the output settings and file headers of the job's
+/// project (`context.project`), or UTF-8/LF without headers when the project
+/// has none. Answers 304 when `If-None-Match` matches the artifacts and
+/// output settings.
+#[debug_handler]
+pub async fn download_job(
+    State(ctx): State<AppContext>,
+    Path(job_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let job = generation_logs::Entity::find()
+        .filter(generation_logs::Column::JobId.eq(&job_id))
+        .one(&ctx.db)
+        .await?
+        .ok_or_else(|| Error::NotFound)?;
+
+    let artifacts = match (job.status.as_str(), job.artifacts.as_deref()) {
+        ("completed", Some(artifacts)) => artifacts,
+        _ if job.artifacts_purged_at.is_some() => return Err(RetentionService::expired_error(job.artifacts_expire_at)),
+        _ => return Err(Error::BadRequest(format!("Job {} has no artifacts to download", job_id))),
+    };
+
+    let settings = GenerationHistoryService::project_settings(&ctx.db, &job).await;
+    let output_format = settings.as_ref().map(|s| s.output_format()).unwrap_or_default();
+    let file_headers = settings.as_ref().map(|s| (s.file_headers(), HeaderVars::new(&s.project)));
+
+    let etag = JobStatusService::etag_for(&[
+        artifacts,
+        &format!("{:?}", output_format),
+        &format!("{:?}", file_headers),
+    ]);
+    if if_none_match(&headers).is_some_and(|known| JobStatusService::matches(known, &etag)) {
+        return not_modified(&etag);
+    }
+
+    let mut files = ArtifactPackager::files_for(&job.product, artifacts)
+        .map_err(|e| Error::string(&format!("Failed to read artifacts: {}", e)))?;
+    if let Some((file_headers, vars)) = &file_headers {
+        ArtifactPackager::stamp_headers(&mut files, file_headers, vars);
+    }
+    let zip = ArtifactPackager::zip(&files, &output_format).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    let mut response = Response::builder()
+        .header("Content-Type", "application/zip")
+        .header(header::ETAG, &etag)
+        .header("Content-Disposition", format!("attachment; filename=\"{}.zip\"", job_id));
+    if let Some(expire_at) = job.artifacts_expire_at {
+        response = response.header(ARTIFACTS_EXPIRE_HEADER, expire_at.to_rfc3339());
+    }
+    response
+        .body(zip.into())
+        .map_err(|e| Error::string(&format!("Failed to build response: {}", e)))
+}
+
+/// Push a completed job's artifacts to its project's Git remote
+///
+/// POST /agent/jobs/:job_id/push
+///
+/// Commits the files (as packaged for download) on a new branch and, when the
+/// project's Git integration has a merge request target, opens a pull/merge
+/// request against the base branch. Only the user who queued the job can
+/// push it, with a JWT or API key: the push uses the server's Git token.
+#[debug_handler]
+pub async fn push_job(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Path(job_id): Path<String>,
+) -> Result<Response> {
+    let user_id = caller
+        .user_id
+        .ok_or_else(|| Error::Unauthorized("Pushing a job needs a JWT or API key".to_string()))?;
+    let job = generation_logs::Entity::find()
+        .filter(generation_logs::Column::JobId.eq(&job_id))
+        .filter(generation_logs::Column::UserId.eq(user_id))
+        .one(&ctx.db)
+        .await?
+        .ok_or_else(|| Error::NotFound)?;
+
+    let pushed = GitPublisher::push_job(&ctx.db, &job)
+        .await
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    format::json(serde_json::json!({
+        "success": true,
+        "job_id": job_id,
+        "branch": pushed.branch,
+        "commit": pushed.commit,
+        "merge_request_url": pushed.merge_request_url,
+    }))
+}
+
 /// Routes for job status API
 pub fn routes() -> Routes {
     Routes::new()
         .prefix("agent/")
+        .add("jobs", get(get_batch_status))
         .add("jobs/{job_id}", get(get_job_status))
         .add("jobs/{job_id}", delete(cancel_job))
+        .add("jobs/{job_id}/download", get(download_job))
+        .add("jobs/{job_id}/push", post(push_job))
         .add("queue/stats", get(get_queue_stats))
+        .add("queue/scaling", get(get_scaling_hints))
 }
@@ -2,9 +2,15 @@
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
 use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
 use serde::{Deserialize, Serialize};
 
-use crate::models::_entities::knowledge_bases::{ActiveModel, Entity, Model};
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::knowledge_bases::{Column, Entity};
+use crate::models::knowledge_base_revisions;
+use crate::services::admin::knowledge_base::{CreateParams, KnowledgeBaseService, KnowledgeEntryDto, UpdateParams};
+use crate::services::TenantScope;
+use crate::utils::OptionalField;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Params {
@@ -16,67 +22,99 @@ pub struct Params {
     pub relevance_tags: Option<serde_json::Value>,
     pub priority: Option<String>,
     pub token_estimate: Option<i32>,
-    pub version: Option<i32>,
     pub is_active: Option<bool>,
-    }
+}
 
 impl Params {
-    fn update(&self, item: &mut ActiveModel) {
-      item.name = Set(self.name.clone());
-      item.category = Set(self.category.clone());
-      item.component = Set(self.component.clone());
-      item.section = Set(self.section.clone());
-      item.content = Set(self.content.clone());
-      item.relevance_tags = Set(self.relevance_tags.clone());
-      item.priority = Set(self.priority.clone());
-      item.token_estimate = Set(self.token_estimate);
-      item.version = Set(self.version);
-      item.is_active = Set(self.is_active);
-      }
-}
+    /// Tags as the comma-separated text the service splits
+    fn tags_text(&self) -> Option<String> {
+        match self.relevance_tags.as_ref()? {
+            serde_json::Value::Array(tags) => {
+                Some(tags.iter().filter_map(serde_json::Value::as_str).collect::<Vec<_>>().join(","))
+            }
+            serde_json::Value::String(tags) => Some(tags.clone()),
+            _ => None,
+        }
+    }
+
+    fn into_create(self) -> CreateParams {
+        CreateParams {
+            relevance_tags: self.tags_text(),
+            name: self.name,
+            category: self.category,
+            component: self.component,
+            section: self.section,
+            content: self.content,
+            priority: self.priority,
+            token_estimate: self.token_estimate,
+            is_active: self.is_active,
+            company: None,
+        }
+    }
 
-async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
-    let item = Entity::find_by_id(id).one(&ctx.db).await?;
-    item.ok_or_else(|| Error::NotFound)
+    fn into_update(self) -> UpdateParams {
+        UpdateParams {
+            relevance_tags: OptionalField::Present(self.tags_text()),
+            name: Some(self.name),
+            category: Some(self.category),
+            content: Some(self.content),
+            component: OptionalField::Present(self.component),
+            section: OptionalField::Present(self.section),
+            priority: OptionalField::Present(self.priority),
+            token_estimate: OptionalField::Present(self.token_estimate),
+            is_active: OptionalField::Present(self.is_active),
+        }
+    }
 }
 
 #[debug_handler]
-pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(Entity::find().all(&ctx.db).await?)
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let items = Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?;
+    format::json(items.into_iter().map(KnowledgeEntryDto::from).collect::<Vec<_>>())
 }
 
 #[debug_handler]
-pub async fn add(State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
-    let mut item = ActiveModel {
-        ..Default::default()
-    };
-    params.update(&mut item);
-    let item = item.insert(&ctx.db).await?;
-    format::json(item)
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(KnowledgeBaseService::create(&ctx.db, &scope, &user.email, params.into_create()).await?)
 }
 
 #[debug_handler]
 pub async fn update(
+    auth: auth::JWT,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<Params>,
 ) -> Result<Response> {
-    let item = load_item(&ctx, id).await?;
-    let mut item = item.into_active_model();
-    params.update(&mut item);
-    let item = item.update(&ctx.db).await?;
-    format::json(item)
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(KnowledgeBaseService::update(&ctx.db, &scope, &user.email, id, params.into_update()).await?)
 }
 
+/// Deactivate an entry (entries are kept for their revision history)
 #[debug_handler]
-pub async fn remove(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    load_item(&ctx, id).await?.delete(&ctx.db).await?;
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    KnowledgeBaseService::delete(&ctx.db, &scope, &user.email, id).await?;
     format::empty()
 }
 
 #[debug_handler]
-pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(load_item(&ctx, id).await?)
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(KnowledgeBaseService::find_by_id(&ctx.db, &TenantScope::for_user(&user), id).await?)
+}
+
+/// Revision history of an entry, newest first
+#[debug_handler]
+pub async fn revisions(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    KnowledgeBaseService::find_by_id(&ctx.db, &TenantScope::for_user(&user), id).await?;
+    format::json(knowledge_base_revisions::Model::history(&ctx.db, id).await?)
 }
 
 pub fn routes() -> Routes {
@@ -85,6 +123,7 @@ pub fn routes() -> Routes {
         .add("/", get(list))
         .add("/", post(add))
         .add("{id}", get(get_one))
+        .add("{id}/revisions", get(revisions))
         .add("{id}", delete(remove))
         .add("{id}", put(update))
         .add("{id}", patch(update))
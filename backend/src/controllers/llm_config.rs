@@ -2,9 +2,14 @@
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
 use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
 use serde::{Deserialize, Serialize};
 
-use crate::models::_entities::llm_configs::{ActiveModel, Entity, Model};
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::llm_configs::{Column, Entity};
+use crate::services::admin::llm_config::{CreateParams, LlmConfigService, UpdateParams};
+use crate::services::TenantScope;
+use crate::utils::OptionalField;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Params {
@@ -21,66 +26,88 @@ pub struct Params {
     pub model_path: Option<String>,
     pub n_ctx: Option<i32>,
     pub n_threads: Option<i32>,
+    /// Fallback order behind the active config (NULL = not a fallback)
+    pub fallback_priority: Option<i32>,
 }
 
 impl Params {
-    fn update(&self, item: &mut ActiveModel) {
-        item.name = Set(self.name.clone());
-        item.provider = Set(self.provider.clone());
-        item.endpoint_url = Set(self.endpoint_url.clone());
-        item.model_name = Set(self.model_name.clone());
-        item.api_key = Set(self.api_key.clone());
-        item.temperature = Set(self.temperature);
-        item.max_tokens = Set(self.max_tokens);
-        item.is_active = Set(self.is_active);
-        item.model_path = Set(self.model_path.clone());
-        item.n_ctx = Set(self.n_ctx);
-        item.n_threads = Set(self.n_threads);
+    fn into_create(self) -> CreateParams {
+        CreateParams {
+            name: self.name,
+            provider: self.provider,
+            model_name: Some(self.model_name),
+            endpoint_url: self.endpoint_url,
+            api_key: self.api_key,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            is_active: self.is_active,
+            model_path: self.model_path,
+            n_ctx: self.n_ctx,
+            n_threads: self.n_threads,
+            timeout_secs: None,
+            company: None,
+            fallback_priority: self.fallback_priority,
+        }
     }
-}
 
-async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
-    let item = Entity::find_by_id(id).one(&ctx.db).await?;
-    item.ok_or_else(|| Error::NotFound)
+    /// Replaces every field but the timeout (blank endpoint = none)
+    fn into_update(self) -> UpdateParams {
+        UpdateParams {
+            name: Some(self.name),
+            provider: Some(self.provider),
+            model_name: Some(self.model_name),
+            endpoint_url: Some(self.endpoint_url.unwrap_or_default()),
+            api_key: OptionalField::Present(self.api_key),
+            temperature: OptionalField::Present(self.temperature),
+            max_tokens: OptionalField::Present(self.max_tokens),
+            is_active: OptionalField::Present(self.is_active),
+            model_path: OptionalField::Present(self.model_path),
+            n_ctx: OptionalField::Present(self.n_ctx),
+            n_threads: OptionalField::Present(self.n_threads),
+            timeout_secs: OptionalField::Missing,
+            fallback_priority: OptionalField::Present(self.fallback_priority),
+        }
+    }
 }
 
 #[debug_handler]
-pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(Entity::find().all(&ctx.db).await?)
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(Entity::find().filter(scope.owned(Column::Company)).all(&ctx.db).await?)
 }
 
 #[debug_handler]
-pub async fn add(State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
-    let mut item = ActiveModel {
-        ..Default::default()
-    };
-    params.update(&mut item);
-    let item = item.insert(&ctx.db).await?;
-    format::json(item)
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(LlmConfigService::create(&ctx.db, &scope, &user.email, params.into_create()).await?)
 }
 
 #[debug_handler]
 pub async fn update(
+    auth: auth::JWT,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<Params>,
 ) -> Result<Response> {
-    let item = load_item(&ctx, id).await?;
-    let mut item = item.into_active_model();
-    params.update(&mut item);
-    let item = item.update(&ctx.db).await?;
-    format::json(item)
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(LlmConfigService::update(&ctx.db, &scope, &user.email, id, params.into_update()).await?)
 }
 
 #[debug_handler]
-pub async fn remove(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    load_item(&ctx, id).await?.delete(&ctx.db).await?;
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    LlmConfigService::delete(&ctx.db, &scope, &user.email, id).await?;
     format::empty()
 }
 
 #[debug_handler]
-pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(load_item(&ctx, id).await?)
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(LlmConfigService::find_by_id(&ctx.db, &TenantScope::for_user(&user), id).await?)
 }
 
 pub fn routes() -> Routes {
@@ -3,12 +3,23 @@ pub mod generate;
 pub mod home;
 pub mod jobs;
 pub mod review;
+pub mod refactor;
+pub mod screen_registry;
+pub mod intent;
 pub mod qa;
 
 pub mod prompt_template;
 pub mod company_rule;
+pub mod project_output_setting;
+pub mod naming_profile;
+pub mod pipeline_profile;
+pub mod saved_intent;
+pub mod spring_module;
 pub mod generation_log;
+pub mod my_generation;
+pub mod schema_change;
 pub mod llm_config;
 
 pub mod admin;
-pub mod knowledge_base;
\ No newline at end of file
+pub mod knowledge_base;
+pub mod api_docs;
\ No newline at end of file
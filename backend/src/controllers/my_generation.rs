@@ -0,0 +1,176 @@
+//! Generation history of the calling user (IDE plugin).
+//!
+//! Authenticated with the user's JWT; only their own generations are listed.
+
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+
+use axum::debug_handler;
+use axum::extract::{Path, Query};
+use axum::http::header;
+use loco_rs::prelude::*;
+use serde::Deserialize;
+
+use crate::domain::HeaderVars;
+use crate::models::users;
+use crate::services::retention::ARTIFACTS_EXPIRE_HEADER;
+use crate::services::{
+    ArtifactPackager, GenerationHistoryService, HistoryQuery, LineageQuery, LineageService, RetentionService,
+    TodoResolution, TodoResolver,
+};
+
+/// Values for the TODOs of a generation
+#[derive(Debug, Deserialize)]
+pub struct ResolveTodosRequest {
+    pub resolutions: Vec<TodoResolution>,
+}
+
+async fn current_user_id(ctx: &AppContext, auth: &auth::JWT) -> Result<i32> {
+    Ok(users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?.id)
+}
+
+/// List the caller's generations
+///
+/// GET /api/my/generations?entity=member&product=xframe5-ui&date_from=2026-10-01&date_to=2026-10-09
+#[debug_handler]
+pub async fn list(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Response> {
+    let user_id = current_user_id(&ctx, &auth).await?;
+    query.date_range().map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    let page = GenerationHistoryService::search(&ctx.db, user_id, &query)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+    format::json(page)
+}
+
+/// Download one of the caller's generations again as a ZIP
+///
+/// GET /api/my/generations/:id/download
+#[debug_handler]
+pub async fn download(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let user_id = current_user_id(&ctx, &auth).await?;
+    let log = GenerationHistoryService::find(&ctx.db, user_id, id)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?
+        .ok_or_else(|| Error::NotFound)?;
+
+    let mut files = match log.artifacts.as_deref() {
+        Some(artifacts) => ArtifactPackager::files_for(&log.product, artifacts)
+            .map_err(|e| Error::string(&format!("Failed to read artifacts: {}", e)))?,
+        None => Vec::new(),
+    };
+    if files.is_empty() {
+        if log.artifacts_purged_at.is_some() {
+            return Err(RetentionService::expired_error(log.artifacts_expire_at));
+        }
+        return Err(Error::BadRequest(format!("Generation {} has no artifacts to download", id)));
+    }
+
+    let settings = GenerationHistoryService::project_settings(&ctx.db, &log).await;
+    if let Some(settings) = &settings {
+        ArtifactPackager::stamp_headers(&mut files, &settings.file_headers(), &HeaderVars::new(&settings.project));
+    }
+    let output_format = settings.map(|s| s.output_format()).unwrap_or_default();
+    let zip = ArtifactPackager::zip(&files, &output_format).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    let mut response = Response::builder()
+        .header("Content-Type", "application/zip")
+        .header(
+            "Content-Disposition",
+            format!("attachment; filename=\"generation-{}.zip\"", id),
+        )
+        .header(header::CACHE_CONTROL, "private");
+    if let Some(expire_at) = log.artifacts_expire_at {
+        response = response.header(ARTIFACTS_EXPIRE_HEADER, expire_at.to_rfc3339());
+    }
+    response
+        .body(zip.into())
+        .map_err(|e| Error::string(&format!("Failed to build response: {}", e)))
+}
+
+async fn load_generation(ctx: &AppContext, auth: &auth::JWT, id: i32) -> Result<crate::models::_entities::generation_logs::Model> {
+    let user_id = current_user_id(ctx, auth).await?;
+    GenerationHistoryService::find(&ctx.db, user_id, id)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?
+        .ok_or_else(|| Error::NotFound)
+}
+
+/// List the TODO placeholders of one of the caller's generations
+///
+/// GET /api/my/generations/:id/todos
+#[debug_handler]
+pub async fn todos(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let log = load_generation(&ctx, &auth, id).await?;
+    let (artifacts, _) = TodoResolver::generation(&log).map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(TodoResolver::find_in_artifacts(&artifacts))
+}
+
+/// Substitute values for TODOs and re-validate the files
+///
+/// POST /api/my/generations/:id/todos/resolve
+///
+/// Request:
+/// ```json
+/// { "resolutions": [{ "id": "task_list.js:149", "value": "/task/selectTaskList.do" }] }
+/// ```
+///
+/// The stored generation is not changed; the resolved files are returned.
+#[debug_handler]
+pub async fn resolve_todos(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(req): Json<ResolveTodosRequest>,
+) -> Result<Response> {
+    let log = load_generation(&ctx, &auth, id).await?;
+    let (artifacts, intent) = TodoResolver::generation(&log).map_err(|e| Error::BadRequest(e.to_string()))?;
+    if req.resolutions.is_empty() {
+        return Err(Error::BadRequest("resolutions are required".to_string()));
+    }
+    let result = TodoResolver::resolve_artifacts(&artifacts, &intent, &req.resolutions)
+        .map_err(|e| Error::string(&format!("Validation failed: {}", e)))?;
+    format::json(result)
+}
+
+/// Column lineage of one of the caller's generations
+///
+/// GET /api/my/generations/:id/lineage?column=EMAIL
+/// GET /api/my/generations/:id/lineage?file=member_list.xml&line=42
+///
+/// Without a filter every source column is listed with the generated lines using it.
+#[debug_handler]
+pub async fn lineage(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Query(query): Query<LineageQuery>,
+) -> Result<Response> {
+    let log = load_generation(&ctx, &auth, id).await?;
+    let lineage = LineageService::of_log(&log)
+        .ok_or_else(|| Error::BadRequest(format!("Generation {} has no artifacts to trace", id)))?;
+    format::json(LineageService::filter(lineage, &query))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/my/generations/")
+        .add("/", get(list))
+        .add("{id}/download", get(download))
+        .add("{id}/todos", get(todos))
+        .add("{id}/lineage", get(lineage))
+        .add("{id}/todos/resolve", post(resolve_todos))
+}
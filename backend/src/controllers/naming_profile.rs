@@ -0,0 +1,127 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::validate_template;
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::naming_profiles::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::TenantScope;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Params {
+    /// Profile name (selected with `options.naming_profile`)
+    pub name: String,
+    pub xml_template: Option<String>,
+    pub js_template: Option<String>,
+    pub java_template: Option<String>,
+    pub mapper_xml_template: Option<String>,
+}
+
+/// Blank templates are stored as unset (the default applies)
+fn template(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|t| !t.is_empty()).map(str::to_string)
+}
+
+impl Params {
+    fn update(&self, item: &mut ActiveModel) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::BadRequest("name is required".to_string()));
+        }
+        for (field, value) in [
+            ("xml_template", &self.xml_template),
+            ("js_template", &self.js_template),
+            ("java_template", &self.java_template),
+            ("mapper_xml_template", &self.mapper_xml_template),
+        ] {
+            if let Some(value) = template(value) {
+                validate_template(&value).map_err(|e| Error::BadRequest(format!("{}: {}", field, e)))?;
+            }
+        }
+
+        item.name = Set(self.name.trim().to_string());
+        item.xml_template = Set(template(&self.xml_template));
+        item.js_template = Set(template(&self.js_template));
+        item.java_template = Set(template(&self.java_template));
+        item.mapper_xml_template = Set(template(&self.mapper_xml_template));
+        Ok(())
+    }
+}
+
+async fn load_item(ctx: &AppContext, scope: &TenantScope, id: i32) -> Result<Model> {
+    let item = Entity::find_by_id(id)
+        .filter(scope.readable(Column::Company))
+        .one(&ctx.db)
+        .await?;
+    item.ok_or_else(|| Error::NotFound)
+}
+
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?)
+}
+
+#[debug_handler]
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let mut item = ActiveModel {
+        company: Set(scope.company_for_new(None)),
+        ..Default::default()
+    };
+    params.update(&mut item)?;
+    let item = item.insert(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Create, None, Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn update(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<Params>,
+) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let before = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(before.company.as_deref())?;
+    let mut item = before.clone().into_active_model();
+    params.update(&mut item)?;
+    let item = item.update(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Update, Some(&before), Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let item = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(item.company.as_deref())?;
+    item.clone().delete(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Delete, Some(&item), None).await?;
+    format::empty()
+}
+
+#[debug_handler]
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(load_item(&ctx, &TenantScope::for_user(&user), id).await?)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/naming_profiles/")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("{id}", get(get_one))
+        .add("{id}", delete(remove))
+        .add("{id}", put(update))
+        .add("{id}", patch(update))
+}
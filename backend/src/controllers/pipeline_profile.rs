@@ -0,0 +1,146 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use std::collections::BTreeMap;
+
+use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
+use serde::{Deserialize, Serialize};
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::pipeline_profiles::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::pipeline::{PassOverride, PipelineProfile};
+use crate::services::TenantScope;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Params {
+    /// Profile name (selected with `options.pipeline_profile`)
+    pub name: String,
+    pub description: Option<String>,
+    /// Product whose requests use this profile by default (e.g. `xframe5-ui`)
+    pub default_for_product: Option<String>,
+    /// Pass name -> `skip`, `strict`, `relaxed` or `dev`
+    #[serde(default)]
+    pub passes: BTreeMap<String, PassOverride>,
+}
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+impl Params {
+    fn update(&self, item: &mut ActiveModel) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::BadRequest("name is required".to_string()));
+        }
+        PipelineProfile::new(self.name.trim(), self.passes.clone()).map_err(Error::BadRequest)?;
+
+        item.name = Set(self.name.trim().to_string());
+        item.description = Set(optional(&self.description));
+        item.default_for_product = Set(optional(&self.default_for_product));
+        item.passes = Set(serde_json::to_value(&self.passes)?);
+        Ok(())
+    }
+}
+
+/// Only one profile per product may be the default within a company (or
+/// among the shared profiles)
+async fn ensure_single_default(ctx: &AppContext, company: Option<&str>, params: &Params, id: Option<i32>) -> Result<()> {
+    let Some(product) = optional(&params.default_for_product) else {
+        return Ok(());
+    };
+    let same_company = match company {
+        Some(code) => Column::Company.eq(code),
+        None => Column::Company.is_null(),
+    };
+    let existing = Entity::find()
+        .filter(Column::DefaultForProduct.eq(product.as_str()))
+        .filter(same_company)
+        .one(&ctx.db)
+        .await?;
+    match existing {
+        Some(other) if Some(other.id) != id => Err(Error::BadRequest(format!(
+            "profile '{}' is already the default for {}",
+            other.name, product
+        ))),
+        _ => Ok(()),
+    }
+}
+
+async fn load_item(ctx: &AppContext, scope: &TenantScope, id: i32) -> Result<Model> {
+    let item = Entity::find_by_id(id)
+        .filter(scope.readable(Column::Company))
+        .one(&ctx.db)
+        .await?;
+    item.ok_or_else(|| Error::NotFound)
+}
+
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?)
+}
+
+#[debug_handler]
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let company = TenantScope::for_user(&user).company_for_new(None);
+    ensure_single_default(&ctx, company.as_deref(), &params, None).await?;
+    let mut item = ActiveModel {
+        company: Set(company),
+        ..Default::default()
+    };
+    params.update(&mut item)?;
+    let item = item.insert(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Create, None, Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn update(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<Params>,
+) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let before = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(before.company.as_deref())?;
+    ensure_single_default(&ctx, before.company.as_deref(), &params, Some(id)).await?;
+    let mut item = before.clone().into_active_model();
+    params.update(&mut item)?;
+    let item = item.update(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Update, Some(&before), Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let item = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(item.company.as_deref())?;
+    item.clone().delete(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Delete, Some(&item), None).await?;
+    format::empty()
+}
+
+#[debug_handler]
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(load_item(&ctx, &TenantScope::for_user(&user), id).await?)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/pipeline_profiles/")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("{id}", get(get_one))
+        .add("{id}", delete(remove))
+        .add("{id}", put(update))
+        .add("{id}", patch(update))
+}
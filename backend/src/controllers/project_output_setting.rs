@@ -0,0 +1,143 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{FileHeaders, GitIntegration, IssueTracker, OutputFormat};
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::project_output_settings::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::TenantScope;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Params {
+    /// Project identifier (matches `context.project` of generation requests)
+    pub project: String,
+    #[serde(flatten)]
+    pub format: OutputFormat,
+    /// Header comments stamped on packaged files (none when omitted)
+    #[serde(default)]
+    pub file_headers: Option<FileHeaders>,
+    /// Git remote generated files are pushed to (none when omitted)
+    #[serde(default)]
+    pub git_integration: Option<GitIntegration>,
+    /// Jira/Redmine commented on for generations with an issue key (none when omitted)
+    #[serde(default)]
+    pub issue_tracker: Option<IssueTracker>,
+}
+
+impl Params {
+    fn update(&self, item: &mut ActiveModel) -> Result<()> {
+        if self.project.trim().is_empty() {
+            return Err(Error::BadRequest("project is required".to_string()));
+        }
+        self.format.validate().map_err(Error::BadRequest)?;
+        let file_headers = match self.file_headers.as_ref().filter(|h| !h.is_empty()) {
+            Some(headers) => {
+                headers.validate().map_err(Error::BadRequest)?;
+                Some(serde_json::to_value(headers).map_err(|e| Error::BadRequest(e.to_string()))?)
+            }
+            None => None,
+        };
+        let git_integration = match &self.git_integration {
+            Some(git) => {
+                git.validate().map_err(Error::BadRequest)?;
+                Some(serde_json::to_value(git).map_err(|e| Error::BadRequest(e.to_string()))?)
+            }
+            None => None,
+        };
+        let issue_tracker = match &self.issue_tracker {
+            Some(tracker) => {
+                tracker.validate().map_err(Error::BadRequest)?;
+                Some(serde_json::to_value(tracker).map_err(|e| Error::BadRequest(e.to_string()))?)
+            }
+            None => None,
+        };
+
+        item.project = Set(self.project.trim().to_string());
+        item.encoding = Set(self.format.encoding.as_str().to_string());
+        item.bom = Set(self.format.bom);
+        item.line_ending = Set(self.format.line_ending.as_str().to_string());
+        item.file_headers = Set(file_headers);
+        item.git_integration = Set(git_integration);
+        item.issue_tracker = Set(issue_tracker);
+        Ok(())
+    }
+}
+
+async fn load_item(ctx: &AppContext, scope: &TenantScope, id: i32) -> Result<Model> {
+    let item = Entity::find_by_id(id)
+        .filter(scope.readable(Column::Company))
+        .one(&ctx.db)
+        .await?;
+    item.ok_or_else(|| Error::NotFound)
+}
+
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    format::json(Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?)
+}
+
+#[debug_handler]
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let mut item = ActiveModel {
+        company: Set(scope.company_for_new(None)),
+        ..Default::default()
+    };
+    params.update(&mut item)?;
+    let item = item.insert(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Create, None, Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn update(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<Params>,
+) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let before = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(before.company.as_deref())?;
+    let mut item = before.clone().into_active_model();
+    params.update(&mut item)?;
+    let item = item.update(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Update, Some(&before), Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let item = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(item.company.as_deref())?;
+    item.clone().delete(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Delete, Some(&item), None).await?;
+    format::empty()
+}
+
+#[debug_handler]
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(load_item(&ctx, &TenantScope::for_user(&user), id).await?)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/project_output_settings/")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("{id}", get(get_one))
+        .add("{id}", delete(remove))
+        .add("{id}", put(update))
+        .add("{id}", patch(update))
+}
@@ -1,10 +1,17 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
+use axum::extract::Query;
 use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
 use serde::{Deserialize, Serialize};
 
-use crate::models::_entities::prompt_templates::{ActiveModel, Entity, Model};
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::prompt_templates::{Column, Entity, Model};
+use crate::services::admin::prompt_template::{CreateParams, PromptTemplateService, UpdateParams};
+use crate::services::template_variables::PlaygroundRequest;
+use crate::services::{TemplateCoverageAnalyzer, TemplateService, TemplateVariables, TenantScope};
+use crate::utils::OptionalField;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Params {
@@ -13,64 +20,191 @@ pub struct Params {
     pub screen_type: Option<String>,
     pub system_prompt: String,
     pub user_prompt_template: String,
-    pub version: i32,
-    pub is_active: Option<bool>,
+}
+
+impl Params {
+    /// New templates start inactive; activation goes through an approved
+    /// proposal (admin panel)
+    fn into_create(self) -> CreateParams {
+        CreateParams {
+            name: self.name,
+            product: self.product,
+            screen_type: self.screen_type,
+            system_prompt: self.system_prompt,
+            user_prompt_template: self.user_prompt_template,
+            is_active: None,
+            propose_activation: None,
+            company: None,
+        }
     }
 
+    fn into_update(self) -> UpdateParams {
+        UpdateParams {
+            name: Some(self.name),
+            product: Some(self.product),
+            system_prompt: Some(self.system_prompt),
+            user_prompt_template: Some(self.user_prompt_template),
+            screen_type: OptionalField::Present(self.screen_type),
+            is_active: OptionalField::Missing,
+        }
+    }
+}
+
 impl Params {
-    fn update(&self, item: &mut ActiveModel) {
-      item.name = Set(self.name.clone());
-      item.product = Set(self.product.clone());
-      item.screen_type = Set(self.screen_type.clone());
-      item.system_prompt = Set(self.system_prompt.clone());
-      item.user_prompt_template = Set(self.user_prompt_template.clone());
-      item.version = Set(self.version);
-      item.is_active = Set(self.is_active);
-      }
+    /// Check the template variables; unknown ones are rejected, the rest noted
+    fn check_variables(&self) -> Result<Vec<String>> {
+        TemplateVariables::validate(
+            &self.product,
+            self.screen_type.as_deref(),
+            &self.system_prompt,
+            &self.user_prompt_template,
+        )
+            .map(|check| check.notes())
+            .map_err(|e| Error::BadRequest(e.to_string()))
+    }
+}
+
+/// Saved template with the notes of the variable check
+#[derive(Debug, Serialize)]
+pub struct SavedTemplate {
+    #[serde(flatten)]
+    pub item: Model,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VariablesQuery {
+    pub product: String,
+    /// `review`, `qa` or a screen type (default: generation templates)
+    pub screen_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CoverageQuery {
+    pub product: String,
+    pub screen_type: Option<String>,
+}
+
+/// Caller and template scope of a request
+async fn caller(ctx: &AppContext, auth: &auth::JWT) -> Result<(AuthUser, TenantScope)> {
+    let user = AuthUser::from_jwt(ctx, auth).await?;
+    let scope = TenantScope::for_user(&user);
+    Ok((user, scope))
 }
 
-async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
-    let item = Entity::find_by_id(id).one(&ctx.db).await?;
-    item.ok_or_else(|| Error::NotFound)
+async fn load_item(ctx: &AppContext, auth: &auth::JWT, id: i32) -> Result<Model> {
+    let (_, scope) = caller(ctx, auth).await?;
+    PromptTemplateService::find_by_id(&ctx.db, &scope, id).await
 }
 
 #[debug_handler]
-pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(Entity::find().all(&ctx.db).await?)
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let (_, scope) = caller(&ctx, &auth).await?;
+    format::json(Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?)
 }
 
 #[debug_handler]
-pub async fn add(State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
-    let mut item = ActiveModel {
-        ..Default::default()
-    };
-    params.update(&mut item);
-    let item = item.insert(&ctx.db).await?;
-    format::json(item)
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let (user, scope) = caller(&ctx, &auth).await?;
+    let notes = params.check_variables()?;
+    let item = PromptTemplateService::create(&ctx.db, &scope, &user.email, params.into_create()).await?;
+    format::json(SavedTemplate { item, notes })
 }
 
+/// Update a template; prompt edits of an active template come back as a
+/// new inactive version proposed for activation
 #[debug_handler]
 pub async fn update(
+    auth: auth::JWT,
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<Params>,
 ) -> Result<Response> {
-    let item = load_item(&ctx, id).await?;
-    let mut item = item.into_active_model();
-    params.update(&mut item);
-    let item = item.update(&ctx.db).await?;
-    format::json(item)
+    let (user, scope) = caller(&ctx, &auth).await?;
+    let notes = params.check_variables()?;
+    let item = PromptTemplateService::update(&ctx.db, &scope, &user.email, id, params.into_update()).await?;
+    format::json(SavedTemplate { item, notes })
 }
 
 #[debug_handler]
-pub async fn remove(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    load_item(&ctx, id).await?.delete(&ctx.db).await?;
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let (user, scope) = caller(&ctx, &auth).await?;
+    PromptTemplateService::delete(&ctx.db, &scope, &user.email, id).await?;
     format::empty()
 }
 
 #[debug_handler]
-pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(load_item(&ctx, id).await?)
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    format::json(load_item(&ctx, &auth, id).await?)
+}
+
+/// Prompt rules of a template that no validator or pipeline pass checks
+#[debug_handler]
+pub async fn coverage(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    format::json(TemplateCoverageAnalyzer::analyze(&load_item(&ctx, &auth, id).await?))
+}
+
+/// Supported template variables of a product / template kind
+///
+/// GET /api/prompt_templates/variables?product=xframe5-ui&screen_type=list
+///
+/// Response:
+/// ```json
+/// {
+///   "user": [{ "name": "dsl_description", "description": "Full screen specification ..." }, ...],
+///   "system": []
+/// }
+/// ```
+#[debug_handler]
+pub async fn variables(Query(query): Query<VariablesQuery>) -> Result<Response> {
+    let variables = TemplateVariables::for_template(&query.product, query.screen_type.as_deref())
+        .ok_or_else(|| Error::BadRequest(format!("No template variables are defined for {}", query.product)))?;
+    format::json(variables)
+}
+
+/// Compile a sample input through a template and show the variable values
+///
+/// POST /api/prompt_templates/{id}/playground
+///
+/// Request:
+/// ```json
+/// { "input": { "type": "db_schema", "table": "member", "columns": [...] }, "company_rules": null }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "variables": [{ "name": "screen_name", "description": "Screen name", "value": "member_list", "used": true }, ...],
+///   "check": { "unknown": [], "unused": ["form_fields"], "system_placeholders": [] },
+///   "notes": ["Unused variables: {{form_fields}}"],
+///   "user_prompt": "Generate an xFrame5 list screen ..."
+/// }
+/// ```
+#[debug_handler]
+pub async fn playground(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(request): Json<PlaygroundRequest>,
+) -> Result<Response> {
+    let item = load_item(&ctx, &auth, id).await?;
+    let result = TemplateVariables::playground(&item, &request).map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(result)
+}
+
+/// Coverage of the active template for `product` / `screen_type`
+#[debug_handler]
+pub async fn active_coverage(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Query(query): Query<CoverageQuery>,
+) -> Result<Response> {
+    let (_, scope) = caller(&ctx, &auth).await?;
+    let template = TemplateService::get_active(&ctx.db, &query.product, query.screen_type.as_deref(), &scope)
+    .await
+    .map_err(|_| Error::NotFound)?;
+    format::json(TemplateCoverageAnalyzer::analyze(&template))
 }
 
 pub fn routes() -> Routes {
@@ -78,7 +212,11 @@ pub fn routes() -> Routes {
         .prefix("api/prompt_templates/")
         .add("/", get(list))
         .add("/", post(add))
+        .add("coverage", get(active_coverage))
+        .add("variables", get(variables))
         .add("{id}", get(get_one))
+        .add("{id}/coverage", get(coverage))
+        .add("{id}/playground", post(playground))
         .add("{id}", delete(remove))
         .add("{id}", put(update))
         .add("{id}", patch(update))
@@ -7,6 +7,7 @@ use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::domain::{QAInput, QAMeta, QAOptions, QAResponse, QAStatus};
+use crate::middleware::api_auth::ApiCaller;
 use crate::services::QAService;
 
 /// API request for Q&A
@@ -57,7 +58,11 @@ pub struct QAApiRequest {
 /// }
 /// ```
 #[debug_handler]
-pub async fn qa(State(ctx): State<AppContext>, Json(req): Json<QAApiRequest>) -> Result<Response> {
+pub async fn qa(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Json(req): Json<QAApiRequest>,
+) -> Result<Response> {
     // Validate product
     if req.product.is_empty() {
         return format::json(QAResponse {
@@ -95,12 +100,9 @@ pub async fn qa(State(ctx): State<AppContext>, Json(req): Json<QAApiRequest>) ->
         });
     }
 
-    // TODO: Extract user ID from JWT token when auth is integrated
-    let user_id: i32 = 1; // Default to system user for now
-
     // Answer question
     let result =
-        QAService::answer(&ctx.db, req.input, &req.product, &req.options, Some(user_id)).await;
+        QAService::answer(&ctx.db, req.input, &req.product, &req.options, caller.user_id).await;
 
     match result {
         Ok(response) => format::json(response),
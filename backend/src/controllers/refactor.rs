@@ -0,0 +1,128 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+
+use axum::debug_handler;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::middleware::api_auth::ApiCaller;
+use crate::services::{PackagedFile, PatternDriftService, RenameKind, SymbolRenamer};
+
+/// API request for a symbol rename
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameRequest {
+    /// Files of the generation (screen XML/JS, Java, MyBatis XML)
+    pub files: Vec<PackagedFile>,
+
+    /// Current name (`fn_search`, `ds_task`, or an entity such as `task`)
+    pub from: String,
+
+    /// New name
+    pub to: String,
+
+    /// `function`, `dataset`, `component` or `entity` (default: looked up in the files)
+    #[serde(default)]
+    pub kind: Option<RenameKind>,
+}
+
+/// Rename a symbol or entity across the files of a generation
+///
+/// POST /agent/refactor/rename
+///
+/// Request:
+/// ```json
+/// {
+///   "files": [
+///     { "name": "task_list.xml", "content": "<screen ...>" },
+///     { "name": "task_list.js", "content": "this.fn_search = ..." }
+///   ],
+///   "from": "fn_search",
+///   "to": "fn_query"
+/// }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "kind": "function",
+///   "files": [{ "name": "task_list.xml", "content": "..." }, ...],
+///   "changes": [{ "file": "task_list.js", "line": 2, "before": "...", "after": "..." }],
+///   "skipped": [{ "file": "task_list.js", "line": 1, "reason": "comment" }]
+/// }
+/// ```
+#[debug_handler]
+pub async fn rename(Json(req): Json<RenameRequest>) -> Result<Response> {
+    if req.files.is_empty() {
+        return Err(Error::BadRequest("files are required".to_string()));
+    }
+    let result = SymbolRenamer::rename(&req.files, &req.from, &req.to, req.kind)
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(result)
+}
+
+/// API request for duplicate and drift detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatesRequest {
+    /// Project whose generated screens are compared
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Screen files to compare as well (only `.js` files are read)
+    #[serde(default)]
+    pub files: Vec<PackagedFile>,
+}
+
+/// Find copy-paste duplicates and pattern drift across a project's screens
+///
+/// POST /agent/refactor/duplicates
+///
+/// Compares the latest generated JS of every screen of `project` together
+/// with the uploaded `files`.
+///
+/// Request:
+/// ```json
+/// { "project": "erp", "files": [{ "name": "member_popup.js", "content": "this.fn_search = ..." }] }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "project": "erp",
+///   "files": 42,
+///   "functions": 310,
+///   "duplicates": [{
+///     "kind": "diverged",
+///     "functions": [{ "file": "member_list.js", "function": "fn_search", "line": 12, "generation_id": 81, "similarity": 1.0 }, ...],
+///     "differences": ["member_popup.js:15: if (name == null) {"],
+///     "suggestion": "2 copies of fn_search were changed separately ..."
+///   }],
+///   "drift": [{ "function": "fn_save", "reference": {...}, "drifted": [...], "suggestion": "..." }]
+/// }
+/// ```
+#[debug_handler]
+pub async fn duplicates(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Json(req): Json<DuplicatesRequest>,
+) -> Result<Response> {
+    let report = match req.project.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(project) => {
+            PatternDriftService::analyze_project(&ctx.db, project, &caller.tenant(), &req.files)
+                .await
+                .map_err(|e| Error::string(&e.to_string()))?
+        }
+        None if req.files.is_empty() => {
+            return Err(Error::BadRequest("project or files are required".to_string()));
+        }
+        None => PatternDriftService::analyze_files(&req.files),
+    };
+    format::json(report)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("agent/")
+        .add("refactor/rename", post(rename))
+        .add("refactor/duplicates", post(duplicates))
+}
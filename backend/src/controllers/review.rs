@@ -3,13 +3,18 @@
 #![allow(clippy::unused_async)]
 
 use axum::debug_handler;
+use axum::extract::{DefaultBodyLimit, Multipart, Query};
+use axum::http::header;
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::domain::{
-    ReviewContext, ReviewInput, ReviewMeta, ReviewOptions, ReviewResponse, ReviewStatus,
+    ReviewContext, ReviewGateResponse, ReviewInput, ReviewMeta, ReviewOptions, ReviewPolicy,
+    ReviewResponse, ReviewStatus,
 };
-use crate::services::ReviewService;
+use crate::middleware::api_auth::ApiCaller;
+use crate::services::project_review::MAX_ARCHIVE_BYTES;
+use crate::services::{ProjectArchive, ProjectReviewService, ReviewGate, ReviewPatcher, ReviewService};
 
 /// API request for code review
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,46 +74,19 @@ pub struct ReviewApiRequest {
 /// ```
 #[debug_handler]
 pub async fn review(
+    caller: ApiCaller,
     State(ctx): State<AppContext>,
     Json(req): Json<ReviewApiRequest>,
 ) -> Result<Response> {
-    // Validate product
-    if req.product.is_empty() {
-        return format::json(ReviewResponse {
-            status: ReviewStatus::Error,
-            review: None,
-            error: Some("Product is required".to_string()),
-            meta: ReviewMeta::new("unknown", 0),
-        });
-    }
-
-    // Validate code input
-    if req.input.code.trim().is_empty() {
-        return format::json(ReviewResponse {
-            status: ReviewStatus::Error,
-            review: None,
-            error: Some("Code is required for review".to_string()),
-            meta: ReviewMeta::new("unknown", 0),
-        });
-    }
-
-    // Check code size limit (50KB)
-    const MAX_CODE_SIZE: usize = 50 * 1024;
-    if req.input.code.len() > MAX_CODE_SIZE {
+    if let Some(error) = validate_review_request(&req) {
         return format::json(ReviewResponse {
             status: ReviewStatus::Error,
             review: None,
-            error: Some(format!(
-                "Code exceeds maximum size limit of {} bytes",
-                MAX_CODE_SIZE
-            )),
+            error: Some(error),
             meta: ReviewMeta::new("unknown", 0),
         });
     }
 
-    // TODO: Extract user ID from JWT token when auth is integrated
-    let user_id: i32 = 1; // Default to system user for now
-
     // Perform code review
     let result = ReviewService::review(
         &ctx.db,
@@ -116,7 +94,7 @@ pub async fn review(
         &req.product,
         &req.options,
         &req.context,
-        Some(user_id),
+        caller.user_id,
     )
     .await;
 
@@ -134,9 +112,340 @@ pub async fn review(
     }
 }
 
+/// Check product, code presence and size limit (50KB)
+fn validate_review_request(req: &ReviewApiRequest) -> Option<String> {
+    const MAX_CODE_SIZE: usize = 50 * 1024;
+
+    if req.product.is_empty() {
+        return Some("Product is required".to_string());
+    }
+    if req.input.code.trim().is_empty() {
+        return Some("Code is required for review".to_string());
+    }
+    if req.input.code.len() > MAX_CODE_SIZE {
+        return Some(format!(
+            "Code exceeds maximum size limit of {} bytes",
+            MAX_CODE_SIZE
+        ));
+    }
+    None
+}
+
+/// API request for the review gate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewGateApiRequest {
+    #[serde(flatten)]
+    pub review: ReviewApiRequest,
+
+    /// Gate policy (default: fail on any error)
+    #[serde(default)]
+    pub policy: ReviewPolicy,
+}
+
+/// Review gate endpoint - review code and evaluate it against a policy
+///
+/// POST /agent/review/gate
+///
+/// Intended for pre-commit/CI use: the caller exits with `exit_code`
+/// (0 pass, 1 policy violation, 2 review error).
+///
+/// Request: same as `/agent/review` plus
+/// ```json
+/// {
+///   "policy": {
+///     "max_errors": 0,
+///     "max_warnings": 10,
+///     "min_score": 70,
+///     "categories": { "security": { "max_warnings": 0 } }
+///   }
+/// }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "status": "success",
+///   "exit_code": 1,
+///   "gate": {
+///     "passed": false,
+///     "summary": { "total": { "errors": 1, ... }, "by_category": {...}, "score": 65 },
+///     "violations": [{ "rule": "max_errors", "category": null, "actual": 1, "limit": 0 }]
+///   },
+///   "review": { ... },
+///   "meta": { ... }
+/// }
+/// ```
+#[debug_handler]
+pub async fn gate(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Json(req): Json<ReviewGateApiRequest>,
+) -> Result<Response> {
+    let ReviewGateApiRequest { review: req, policy } = req;
+
+    if let Some(error) = validate_review_request(&req) {
+        return format::json(ReviewGateResponse::error(error, ReviewMeta::new("unknown", 0)));
+    }
+
+    let result = ReviewService::review(
+        &ctx.db,
+        req.input,
+        &req.product,
+        &req.options,
+        &req.context,
+        caller.user_id,
+    )
+    .await;
+
+    match result {
+        Ok(ReviewResponse {
+            review: Some(review),
+            meta,
+            ..
+        }) => {
+            let gate = ReviewGate::evaluate(&review, &policy);
+            format::json(ReviewGateResponse::evaluated(gate, review, meta))
+        }
+        Ok(response) => format::json(ReviewGateResponse::error(
+            response.error.unwrap_or_else(|| "Review produced no result".to_string()),
+            response.meta,
+        )),
+        Err(e) => {
+            tracing::error!("Review gate failed: {}", e);
+            format::json(ReviewGateResponse::error(
+                format!("Review failed: {}", e),
+                ReviewMeta::new(format!("{}-review-v1", req.product), 0),
+            ))
+        }
+    }
+}
+
+/// API request for applying review patches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyPatchRequest {
+    /// Original file content the review was run against
+    pub code: String,
+
+    /// Unified diff patches taken from review issues
+    pub patches: Vec<String>,
+}
+
+/// API response for applying review patches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyPatchResponse {
+    pub status: ReviewStatus,
+
+    /// Patched file content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Number of patches applied
+    pub applied: usize,
+
+    /// Reasons for patches that could not be applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Apply review patches and return the patched file
+///
+/// POST /agent/review/apply
+///
+/// Patches are applied in order; a patch that no longer matches the code
+/// is skipped and reported in `errors`.
+///
+/// Request:
+/// ```json
+/// {
+///   "code": "<screen>...",
+///   "patches": ["--- a/member_list.xml\n+++ b/member_list.xml\n@@ -2,1 +2,1 @@\n-...\n+...\n"]
+/// }
+/// ```
+///
+/// Response:
+/// ```json
+/// { "status": "success", "code": "<screen>...", "applied": 1 }
+/// ```
+#[debug_handler]
+pub async fn apply(Json(req): Json<ApplyPatchRequest>) -> Result<Response> {
+    if req.code.is_empty() {
+        return format::json(ApplyPatchResponse {
+            status: ReviewStatus::Error,
+            code: None,
+            applied: 0,
+            errors: vec!["Code is required".to_string()],
+        });
+    }
+
+    let mut code = req.code;
+    let mut applied = 0;
+    let mut errors = Vec::new();
+
+    for (idx, patch) in req.patches.iter().enumerate() {
+        match ReviewPatcher::apply(&code, patch) {
+            Ok(patched) => {
+                code = patched;
+                applied += 1;
+            }
+            Err(e) => errors.push(format!("Patch {}: {}", idx + 1, e)),
+        }
+    }
+
+    format::json(ApplyPatchResponse {
+        status: ReviewStatus::Success,
+        code: Some(code),
+        applied,
+        errors,
+    })
+}
+
+/// Report format of a project review
+#[derive(Debug, Deserialize)]
+pub struct ProjectReviewQuery {
+    /// json (default), sarif or html
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// Project review endpoint - review every file of an uploaded project
+///
+/// POST /agent/review/project?format=json|sarif|html
+///
+/// Multipart form (up to 20 MB):
+/// - `file`: ZIP of the project's XML and JS files
+/// - `project`: report title (default: the archive name)
+/// - `product`: only `xframe5-ui` (default)
+/// - `llm`: `true` adds the LLM review of each file (slow; up to 40 files)
+/// - `company_id`, `language`: as in `/agent/review` options
+///
+/// `sarif` and `html` answer with a downloadable report; `json` with the
+/// report itself:
+/// ```json
+/// {
+///   "project": "erp-legacy",
+///   "summary": { "files": 48, "lines": 21034, "total": { "errors": 3, ... }, ... },
+///   "findings": [{ "severity": "warning", "category": "naming", "files": [...], "message": "..." }],
+///   "files": [{ "path": "screens/member_list.xml", "counts": {...}, "issues": [...] }]
+/// }
+/// ```
+#[debug_handler]
+pub async fn project(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    ViewEngine(v): ViewEngine<TeraView>,
+    Query(query): Query<ProjectReviewQuery>,
+    mut multipart: Multipart,
+) -> Result<Response> {
+    let format = query.format.unwrap_or_else(|| "json".to_string());
+    if !["json", "sarif", "html"].contains(&format.as_str()) {
+        return Err(Error::BadRequest(format!("Unknown report format: {}", format)));
+    }
+
+    let mut archive = None;
+    let mut project = None;
+    let mut product = "xframe5-ui".to_string();
+    let mut llm = false;
+    let mut company_id = None;
+    let mut language = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        Error::BadRequest(format!("Failed to read multipart field: {}", e))
+    })? {
+        let name = field.name().unwrap_or("").to_string();
+        let read_error = |e: axum::extract::multipart::MultipartError| {
+            Error::BadRequest(format!("Failed to read field: {}", e))
+        };
+        let text = |value: String| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+
+        match name.as_str() {
+            "file" => {
+                let filename = field.file_name().unwrap_or("project.zip").to_string();
+                if project.is_none() {
+                    project = text(filename.trim_end_matches(".zip").to_string());
+                }
+                archive = Some(field.bytes().await.map_err(read_error)?);
+            }
+            "project" => {
+                if let Some(value) = text(field.text().await.map_err(read_error)?) {
+                    project = Some(value);
+                }
+            }
+            "product" => {
+                if let Some(value) = text(field.text().await.map_err(read_error)?) {
+                    product = value;
+                }
+            }
+            "llm" => {
+                let value = field.text().await.map_err(read_error)?;
+                llm = value == "true" || value == "on";
+            }
+            "company_id" => company_id = text(field.text().await.map_err(read_error)?),
+            "language" => language = text(field.text().await.map_err(read_error)?),
+            _ => {}
+        }
+    }
+
+    let archive = archive.ok_or_else(|| Error::BadRequest("A project ZIP is required".to_string()))?;
+    if product != "xframe5-ui" {
+        return Err(Error::BadRequest(format!("Project review is not available for {}", product)));
+    }
+    let project = project.unwrap_or_else(|| "project".to_string());
+    let archive = ProjectArchive::read(&archive).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    // Through serde so unset fields get the `/agent/review` defaults
+    let mut options = serde_json::json!({ "company_id": company_id });
+    if let Some(language) = language {
+        options["language"] = language.into();
+    }
+    let options: ReviewOptions = serde_json::from_value(options)?;
+
+    let report = ProjectReviewService::review(&ctx.db, archive, &project, &product, llm, &options, caller.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Project review failed: {}", e);
+            Error::string(&format!("Project review failed: {}", e))
+        })?;
+
+    let filename: String = project
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let (content_type, body, extension) = match format.as_str() {
+        "sarif" => (
+            "application/sarif+json",
+            serde_json::to_string_pretty(&report.to_sarif())?,
+            "sarif",
+        ),
+        "html" => (
+            "text/html; charset=utf-8",
+            v.render(
+                "review/project_report.html",
+                data!({
+                    "report": &report,
+                    "generated_at": report.generated_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                }),
+            )?,
+            "html",
+        ),
+        _ => return format::json(report),
+    };
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}-review.{}\"", filename, extension),
+        )
+        .body(body.into())
+        .map_err(|e| Error::string(&format!("Failed to build response: {}", e)))
+}
+
 /// Routes for the review API
 pub fn routes() -> Routes {
     Routes::new()
         .prefix("agent/")
         .add("review", post(review))
+        .add("review/gate", post(gate))
+        .add("review/apply", post(apply))
+        .add("review/project", post(project).layer(DefaultBodyLimit::max(MAX_ARCHIVE_BYTES)))
 }
@@ -0,0 +1,182 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::extract::Query;
+use loco_rs::prelude::*;
+use sea_orm::QueryFilter;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{diff_intents, IntentChange, UiIntent};
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::generation_logs;
+use crate::models::_entities::saved_intents::{ActiveModel, Column, Entity, Model};
+use crate::models::users;
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::TenantScope;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Params {
+    /// Library name (referenced by `{"type": "saved_intent", "name": ...}` inputs)
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub intent: UiIntent,
+}
+
+impl Params {
+    fn update(&self, item: &mut ActiveModel) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::BadRequest("name is required".to_string()));
+        }
+        let tags: Vec<String> = self
+            .tags
+            .iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        item.name = Set(self.name.trim().to_string());
+        item.tags = Set((!tags.is_empty()).then(|| serde_json::json!(tags)));
+        item.intent = Set(serde_json::to_value(&self.intent).map_err(|e| Error::BadRequest(e.to_string()))?);
+        Ok(())
+    }
+
+    /// Reject an intent that repeats the screen structure of another entry the scope can read
+    async fn check_duplicate(&self, ctx: &AppContext, scope: &TenantScope, id: Option<i32>) -> Result<()> {
+        if let Some(existing) = Model::find_duplicate(&ctx.db, &self.intent, id, scope).await? {
+            return Err(Error::BadRequest(format!(
+                "intent duplicates saved intent '{}' (id {})",
+                existing.name, existing.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiffResponse {
+    pub saved_intent_id: i32,
+    pub generation_log_id: i32,
+    /// Changes from the logged intent to the saved one
+    pub changes: Vec<IntentChange>,
+}
+
+async fn load_item(ctx: &AppContext, scope: &TenantScope, id: i32) -> Result<Model> {
+    let item = Entity::find_by_id(id)
+        .filter(scope.readable(Column::Company))
+        .one(&ctx.db)
+        .await?;
+    item.ok_or_else(|| Error::NotFound)
+}
+
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>, Query(query): Query<ListQuery>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let items = Entity::find().filter(scope.readable(Column::Company)).all(&ctx.db).await?;
+    let items: Vec<Model> = match query.tag.as_deref() {
+        Some(tag) => items.into_iter().filter(|i| i.tags().iter().any(|t| t == tag)).collect(),
+        None => items,
+    };
+    format::json(items)
+}
+
+#[debug_handler]
+pub async fn add(auth: auth::JWT, State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    params.check_duplicate(&ctx, &scope, None).await?;
+    let mut item = ActiveModel {
+        company: Set(scope.company_for_new(None)),
+        ..Default::default()
+    };
+    params.update(&mut item)?;
+    let item = item.insert(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Create, None, Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn update(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<Params>,
+) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let before = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(before.company.as_deref())?;
+    params.check_duplicate(&ctx, &scope, Some(id)).await?;
+    let mut item = before.clone().into_active_model();
+    params.update(&mut item)?;
+    let item = item.update(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Update, Some(&before), Some(&item)).await?;
+    format::json(item)
+}
+
+#[debug_handler]
+pub async fn remove(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    let scope = TenantScope::for_user(&user);
+    let item = load_item(&ctx, &scope, id).await?;
+    scope.ensure_writable(item.company.as_deref())?;
+    item.clone().delete(&ctx.db).await?;
+    AuditLogService::record(&ctx.db, &user.email, AuditAction::Delete, Some(&item), None).await?;
+    format::empty()
+}
+
+#[debug_handler]
+pub async fn get_one(auth: auth::JWT, Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = AuthUser::from_jwt(&ctx, &auth).await?;
+    format::json(load_item(&ctx, &TenantScope::for_user(&user), id).await?)
+}
+
+/// Compare with the intent recorded in one of the caller's generation logs
+#[debug_handler]
+pub async fn diff(
+    auth: auth::JWT,
+    Path((id, log_id)): Path<(i32, i32)>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let scope = TenantScope::new(user.company.as_deref());
+    let item = load_item(&ctx, &scope, id).await?;
+    let log = generation_logs::Entity::find_by_id(log_id)
+        .filter(generation_logs::Column::UserId.eq(user.id))
+        .filter(scope.owned(generation_logs::Column::Company))
+        .one(&ctx.db)
+        .await?
+        .ok_or_else(|| Error::NotFound)?;
+    if log.product == "spring-backend" {
+        return Err(Error::BadRequest(format!(
+            "generation log {} recorded a Spring intent, not a UI intent",
+            log_id
+        )));
+    }
+
+    let logged: serde_json::Value =
+        serde_json::from_str(&log.ui_intent).map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(DiffResponse {
+        saved_intent_id: item.id,
+        generation_log_id: log.id,
+        changes: diff_intents(&logged, &item.intent),
+    })
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/saved_intents/")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("{id}", get(get_one))
+        .add("{id}", delete(remove))
+        .add("{id}", put(update))
+        .add("{id}", patch(update))
+        .add("{id}/diff/{log_id}", get(diff))
+}
@@ -0,0 +1,112 @@
+//! Schema changes affecting the calling user's generations (IDE plugin).
+//!
+//! The plugin sends the tables it introspected; screens and Spring modules
+//! generated from a table that changed since are listed as stale and can be
+//! regenerated with the column diff in the prompt.
+
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+
+use axum::debug_handler;
+use axum::extract::Path;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{SchemaDiff, SchemaInput};
+use crate::models::users;
+use crate::services::schema_change::SOURCE_INTROSPECTION;
+use crate::services::{GenerationHistoryService, SchemaChangeService, StaleArtifact, TenantScope};
+
+/// Tables read from the user's database
+#[derive(Debug, Deserialize)]
+pub struct SnapshotRequest {
+    pub tables: Vec<SchemaInput>,
+}
+
+/// Tables that changed since their last snapshot, and the stale generations
+#[derive(Debug, Serialize)]
+pub struct SnapshotResponse {
+    pub changed: Vec<SchemaDiff>,
+    pub stale: Vec<StaleArtifact>,
+}
+
+async fn current_user_id(ctx: &AppContext, auth: &auth::JWT) -> Result<i32> {
+    Ok(users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?.id)
+}
+
+/// List the caller's generations whose tables changed since
+///
+/// GET /api/my/schema-changes
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user_id = current_user_id(&ctx, &auth).await?;
+    let stale = SchemaChangeService::stale(&ctx.db, user_id)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+    format::json(stale)
+}
+
+/// Record introspected tables
+///
+/// POST /api/my/schema-changes/snapshots
+///
+/// Request:
+/// ```json
+/// { "tables": [{ "table": "TB_MEMBER", "columns": [{ "name": "EMAIL", "column_type": "VARCHAR2(200)", "nullable": true, "pk": false }] }] }
+/// ```
+#[debug_handler]
+pub async fn snapshots(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(req): Json<SnapshotRequest>,
+) -> Result<Response> {
+    let user_id = current_user_id(&ctx, &auth).await?;
+    if req.tables.is_empty() {
+        return Err(Error::BadRequest("tables are required".to_string()));
+    }
+    let tenant = TenantScope::for_user_id(&ctx.db, Some(user_id)).await;
+
+    let mut changed = Vec::new();
+    for table in &req.tables {
+        let diff = SchemaChangeService::record(&ctx.db, tenant.company(), table, SOURCE_INTROSPECTION)
+            .await
+            .map_err(|e| Error::string(&e.to_string()))?;
+        changed.extend(diff);
+    }
+    let stale = SchemaChangeService::stale(&ctx.db, user_id)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+    format::json(SnapshotResponse { changed, stale })
+}
+
+/// Regenerate a stale generation against the current schema
+///
+/// POST /api/my/schema-changes/:id/regenerate
+///
+/// Only the columns in the diff change; the new generation is logged as usual.
+#[debug_handler]
+pub async fn regenerate(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let user_id = current_user_id(&ctx, &auth).await?;
+    let log = GenerationHistoryService::find(&ctx.db, user_id, id)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?
+        .ok_or_else(|| Error::NotFound)?;
+
+    let regeneration = SchemaChangeService::regenerate(&ctx.db, user_id, &log)
+        .await
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(regeneration)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/my/schema-changes/")
+        .add("/", get(list))
+        .add("snapshots", post(snapshots))
+        .add("{id}/regenerate", post(regenerate))
+}
@@ -0,0 +1,111 @@
+//! Screen Naming Registry Controller
+//!
+//! Lookups for the IDE plugin, so it can warn before a developer generates
+//! over a screen a teammate already delivered in the project.
+
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+
+use axum::debug_handler;
+use axum::extract::Query;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::middleware::api_auth::ApiCaller;
+use crate::services::ScreenRegistry;
+
+/// Query of the screen list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreensQuery {
+    pub project: String,
+}
+
+/// Query of a name check (`screen_id`, `file` or both)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenCheckQuery {
+    pub project: String,
+    #[serde(default)]
+    pub screen_id: Option<String>,
+    /// XML or JS file name
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+fn project(project: &str) -> Result<&str> {
+    match project.trim() {
+        "" => Err(Error::BadRequest("project is required".to_string())),
+        project => Ok(project),
+    }
+}
+
+/// Screens delivered in a project and who owns them
+///
+/// GET /agent/screens?project=erp
+///
+/// Response:
+/// ```json
+/// {
+///   "project": "erp",
+///   "screens": [{
+///     "screen_id": "SCREEN_MEMBER_LIST",
+///     "xml_filename": "member_list.xml",
+///     "js_filename": "member_list.js",
+///     "generation_id": 81,
+///     "user_id": 2,
+///     "owner": "Kim",
+///     "updated_at": "2026-10-16T09:00:00+00:00"
+///   }]
+/// }
+/// ```
+#[debug_handler]
+pub async fn list(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ScreensQuery>,
+) -> Result<Response> {
+    let project = project(&query.project)?;
+    let tenant = caller.tenant();
+    let screens = ScreenRegistry::list(&ctx.db, project, tenant.company())
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+    format::json(serde_json::json!({ "project": project, "screens": screens }))
+}
+
+/// Whether the caller may use a screen id or file name in a project
+///
+/// GET /agent/screens/check?project=erp&screen_id=SCREEN_MEMBER_LIST
+///
+/// Response:
+/// ```json
+/// {
+///   "available": false,
+///   "taken_by": { "screen_id": "SCREEN_MEMBER_LIST", "owner": "Kim", ... },
+///   "suggestion": "SCREEN_MEMBER_LIST_2"
+/// }
+/// ```
+#[debug_handler]
+pub async fn check_screen(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Query(query): Query<ScreenCheckQuery>,
+) -> Result<Response> {
+    let project = project(&query.project)?;
+    let screen_id = query.screen_id.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let file = query.file.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    if screen_id.is_none() && file.is_none() {
+        return Err(Error::BadRequest("screen_id or file is required".to_string()));
+    }
+
+    let tenant = caller.tenant();
+    let result = ScreenRegistry::check(&ctx.db, project, tenant.company(), caller.stored_user_id(), screen_id, file)
+        .await
+        .map_err(|e| Error::string(&e.to_string()))?;
+    format::json(result)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("agent/")
+        .add("screens", get(list))
+        .add("screens/check", get(check_screen))
+}
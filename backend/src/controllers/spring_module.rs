@@ -0,0 +1,72 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+
+use axum::debug_handler;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{GenerateInput, GenerateOptions, RequestContext};
+use crate::middleware::api_auth::ApiCaller;
+use crate::services::SpringModuleService;
+
+/// API request for multi-entity Spring generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpringModuleApiRequest {
+    /// One input per entity (any generate input type)
+    pub entities: Vec<GenerateInput>,
+
+    /// Generation options (shared by all entities)
+    #[serde(default)]
+    pub options: GenerateOptions,
+
+    /// Request context; `project` is the module's base package
+    #[serde(default)]
+    pub context: RequestContext,
+}
+
+/// Generate a Spring module with shared base classes
+///
+/// POST /agent/generate/spring-module
+///
+/// Request:
+/// ```json
+/// {
+///   "entities": [
+///     { "type": "db_schema", "table": "TB_MEMBER", "columns": [...] },
+///     { "type": "db_schema", "table": "TB_ORDER", "columns": [...] }
+///   ],
+///   "context": { "project": "com.company.erp" }
+/// }
+/// ```
+///
+/// Response:
+/// ```json
+/// {
+///   "status": "success",
+///   "shared": [{ "name": "ApiResponse", "package": "...", "path": "...", "source": "..." }],
+///   "entities": [{ "status": "success", "artifacts": { ... }, ... }],
+///   "warnings": [],
+///   "meta": { ... }
+/// }
+/// ```
+#[debug_handler]
+pub async fn generate_module(
+    caller: ApiCaller,
+    State(ctx): State<AppContext>,
+    Json(req): Json<SpringModuleApiRequest>,
+) -> Result<Response> {
+    req.context.validate().map_err(Error::BadRequest)?;
+
+    let response =
+        SpringModuleService::generate(&ctx.db, req.entities, &req.options, &req.context, caller.user_id)
+            .await
+            .map_err(|e| Error::BadRequest(e.to_string()))?;
+    format::json(response)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("agent/")
+        .add("generate/spring-module", post(generate_module))
+}
@@ -0,0 +1,126 @@
+//! Accessibility Compliance Report
+//!
+//! Every generated screen comes with a structured report of its accessibility
+//! checks (input labels, touch target sizes, color contrast, tab order). The
+//! report is evidence for the customer's web-accessibility certification
+//! audits; it never blocks a generation. The contrast estimate starts from the
+//! company rule set's `style` section.
+
+use serde::{Deserialize, Serialize};
+
+/// WCAG AA contrast for normal text
+pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+/// WCAG AA contrast for large text (18pt, or 14pt bold)
+pub const MIN_LARGE_TEXT_CONTRAST_RATIO: f64 = 3.0;
+
+/// `style` section of a company rule set: the screen palette and the
+/// minimum touch target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StyleProfile {
+    /// Body text color (e.g. "#333333"; xFrame5 "00333333" also accepted)
+    #[serde(default)]
+    pub text_color: Option<String>,
+
+    /// Screen background color (e.g. "#FFFFFF")
+    #[serde(default)]
+    pub background_color: Option<String>,
+
+    /// Text color of primary buttons
+    #[serde(default)]
+    pub button_text_color: Option<String>,
+
+    /// Background color of primary buttons
+    #[serde(default)]
+    pub button_background_color: Option<String>,
+
+    /// Minimum width and height of clickable controls, in px (WCAG 2.2: 24)
+    #[serde(default = "default_min_target_size")]
+    pub min_target_size: u32,
+}
+
+impl Default for StyleProfile {
+    fn default() -> Self {
+        Self {
+            text_color: None,
+            background_color: None,
+            button_text_color: None,
+            button_background_color: None,
+            min_target_size: default_min_target_size(),
+        }
+    }
+}
+
+fn default_min_target_size() -> u32 {
+    24
+}
+
+/// One check of the report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessibilityCheck {
+    /// Controls (or color pairs) checked
+    pub checked: usize,
+
+    /// One finding per failing control
+    #[serde(default)]
+    pub issues: Vec<String>,
+}
+
+impl AccessibilityCheck {
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Accessibility compliance report of a generated screen
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccessibilityReport {
+    /// Every check passed
+    pub compliant: bool,
+
+    /// Inputs have a visible label
+    pub input_labels: AccessibilityCheck,
+
+    /// Clickable controls meet the minimum target size
+    pub touch_targets: AccessibilityCheck,
+
+    /// Text/background pairs meet the WCAG AA contrast ratio
+    pub color_contrast: AccessibilityCheck,
+
+    /// Lowest contrast ratio found (4.5 for 4.5:1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowest_contrast_ratio: Option<f64>,
+
+    /// Focusable controls have ids and every declared tab order is complete
+    pub tab_order: AccessibilityCheck,
+
+    /// Set when the screen XML could not be parsed (nothing was checked)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<String>,
+}
+
+impl AccessibilityReport {
+    /// Report of a screen whose XML could not be parsed
+    pub fn unparsed(error: impl Into<String>) -> Self {
+        Self {
+            parse_error: Some(error.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set `compliant` from the checks
+    pub fn finish(mut self) -> Self {
+        self.compliant = self.parse_error.is_none()
+            && self.input_labels.passed()
+            && self.touch_targets.passed()
+            && self.color_contrast.passed()
+            && self.tab_order.passed();
+        self
+    }
+
+    /// Report file name next to the screen XML (`member_list.xml` → `member_list_a11y.json`)
+    pub fn filename(xml_filename: &str) -> String {
+        let stem = xml_filename.strip_suffix(".xml").unwrap_or(xml_filename);
+        format!("{}_a11y.json", stem)
+    }
+}
@@ -0,0 +1,267 @@
+//! Address Fields
+//!
+//! A Korean address is entered as three linked fields: the postcode and the
+//! base address, both filled from the postcode search popup, and the detail
+//! address the user types afterwards. The popup is the customer's
+//! address-search screen from the company rule set's `address` section, else
+//! a `/common/zipcode_popup.xml` placeholder.
+//!
+//! Control and function names are derived from the address id, so the prompt
+//! and the Address Field Validator agree on them: `btn_<id>_search`,
+//! `fn_<id>_search` and `fn_<id>_callback`.
+
+use serde::{Deserialize, Serialize};
+
+use super::ui_intent::UiIntent;
+
+/// Address-search screen used when the company rule set names none
+pub const DEFAULT_ADDRESS_POPUP: &str = "/common/zipcode_popup.xml";
+
+/// Postcode search popup (`address` section of a company rule set)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressPopup {
+    /// Address-search screen (e.g. "/common/zip/zip_search.xml")
+    #[serde(default = "default_popup_url")]
+    pub url: String,
+
+    /// Common helper opening the popup (e.g. "gfn_openZipcode"), called with
+    /// the screen and the callback name; `loadpopup` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub helper: Option<String>,
+
+    #[serde(default = "default_popup_width")]
+    pub width: u32,
+
+    #[serde(default = "default_popup_height")]
+    pub height: u32,
+
+    /// Key of the postcode in the popup's return value
+    #[serde(default = "default_zip_key")]
+    pub zip_key: String,
+
+    /// Key of the base address in the popup's return value
+    #[serde(default = "default_address_key")]
+    pub address_key: String,
+}
+
+fn default_popup_url() -> String {
+    DEFAULT_ADDRESS_POPUP.to_string()
+}
+
+fn default_popup_width() -> u32 {
+    500
+}
+
+fn default_popup_height() -> u32 {
+    600
+}
+
+fn default_zip_key() -> String {
+    "zonecode".to_string()
+}
+
+fn default_address_key() -> String {
+    "roadAddress".to_string()
+}
+
+impl Default for AddressPopup {
+    fn default() -> Self {
+        Self {
+            url: default_popup_url(),
+            helper: None,
+            width: default_popup_width(),
+            height: default_popup_height(),
+            zip_key: default_zip_key(),
+            address_key: default_address_key(),
+        }
+    }
+}
+
+/// Postcode, base address and detail address of one dataset, with the
+/// search button opening the popup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressIntent {
+    /// Address id (default "addr" → `btn_addr_search`)
+    #[serde(default = "default_address_id")]
+    pub id: String,
+
+    /// Caption of the field group
+    #[serde(default = "default_address_label")]
+    pub label: String,
+
+    pub dataset_id: String,
+
+    /// Postcode column (e.g. "ZIP_CD")
+    pub zip_column: String,
+
+    /// Base address column, filled by the popup (e.g. "ADDR1")
+    pub address_column: String,
+
+    /// Detail address column, typed by the user (e.g. "ADDR2")
+    pub detail_column: String,
+
+    /// Popup of this address (none: the company's, else the default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub popup: Option<AddressPopup>,
+}
+
+fn default_address_id() -> String {
+    "addr".to_string()
+}
+
+fn default_address_label() -> String {
+    "주소".to_string()
+}
+
+impl AddressIntent {
+    pub fn new(
+        dataset_id: impl Into<String>,
+        zip_column: impl Into<String>,
+        address_column: impl Into<String>,
+        detail_column: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: default_address_id(),
+            label: default_address_label(),
+            dataset_id: dataset_id.into(),
+            zip_column: zip_column.into(),
+            address_column: address_column.into(),
+            detail_column: detail_column.into(),
+            popup: None,
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_popup(mut self, popup: AddressPopup) -> Self {
+        self.popup = Some(popup);
+        self
+    }
+
+    /// Popup the search button opens
+    pub fn popup(&self) -> AddressPopup {
+        self.popup.clone().unwrap_or_default()
+    }
+
+    /// Postcode, base address and detail address columns
+    pub fn columns(&self) -> [&str; 3] {
+        [self.zip_column.as_str(), self.address_column.as_str(), self.detail_column.as_str()]
+    }
+
+    /// Field bound to a column (`ADDR2` → `field_addr2`)
+    pub fn field(column: &str) -> String {
+        format!("field_{}", column.to_lowercase())
+    }
+
+    /// Button opening the popup
+    pub fn button(&self) -> String {
+        format!("btn_{}_search", self.id)
+    }
+
+    /// Function the button calls
+    pub fn search_function(&self) -> String {
+        format!("fn_{}_search", self.id)
+    }
+
+    /// Function receiving the popup's return value
+    pub fn callback_function(&self) -> String {
+        format!("fn_{}_callback", self.id)
+    }
+
+    /// `fn_<id>_search` and `fn_<id>_callback`
+    pub fn functions(&self) -> String {
+        let popup = self.popup();
+        let open = match popup.helper.as_deref().filter(|h| !h.trim().is_empty()) {
+            Some(helper) => format!("    {}(\"{}\", \"{}\");", helper, popup.url, self.callback_function()),
+            None => format!(
+                "    loadpopup({{\n        url: \"{}\",\n        width: {},\n        height: {},\n        modal: true,\n        \
+                 title: \"{} 검색\",\n        on_popupdestroy: \"{}\"\n    }});",
+                popup.url,
+                popup.width,
+                popup.height,
+                self.label,
+                self.callback_function()
+            ),
+        };
+        format!(
+            "this.{search} = function() {{\n{open}\n}};\n\n\
+             this.{callback} = function(returnValue) {{\n    if (!returnValue) {{\n        return;\n    }}\n    \
+             {zip}.setvalue(returnValue.{zip_key});\n    {address}.setvalue(returnValue.{address_key});\n    \
+             {detail}.setvalue(\"\");\n    {detail}.setfocus();\n}};\n",
+            search = self.search_function(),
+            open = open,
+            callback = self.callback_function(),
+            zip = Self::field(&self.zip_column),
+            zip_key = popup.zip_key,
+            address = Self::field(&self.address_column),
+            address_key = popup.address_key,
+            detail = Self::field(&self.detail_column),
+        )
+    }
+}
+
+impl UiIntent {
+    pub fn with_address(mut self, address: AddressIntent) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    /// Give addresses without their own popup the company rule set's
+    pub fn apply_address_popup(&mut self, popup: &AddressPopup) {
+        for address in self.addresses.iter_mut().filter(|a| a.popup.is_none()) {
+            address.popup = Some(popup.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScreenType;
+
+    #[test]
+    fn test_functions_open_popup_and_fill_fields() {
+        let address = AddressIntent::new("ds_customer", "ZIP_CD", "ADDR1", "ADDR2");
+        assert_eq!(address.button(), "btn_addr_search");
+        assert_eq!(address.columns(), ["ZIP_CD", "ADDR1", "ADDR2"]);
+
+        let js = address.functions();
+        assert!(js.contains("this.fn_addr_search = function() {"));
+        assert!(js.contains("url: \"/common/zipcode_popup.xml\""));
+        assert!(js.contains("on_popupdestroy: \"fn_addr_callback\""));
+        assert!(js.contains("field_zip_cd.setvalue(returnValue.zonecode);"));
+        assert!(js.contains("field_addr1.setvalue(returnValue.roadAddress);"));
+        assert!(js.contains("field_addr2.setfocus();"));
+    }
+
+    #[test]
+    fn test_company_popup_applies_to_addresses_without_one() {
+        let popup = AddressPopup {
+            url: "/common/zip/zip_search.xml".to_string(),
+            helper: Some("gfn_openZipcode".to_string()),
+            ..AddressPopup::default()
+        };
+        let own = AddressPopup { url: "/hr/addr_popup.xml".to_string(), ..AddressPopup::default() };
+        let mut intent = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_address(AddressIntent::new("ds_customer", "ZIP_CD", "ADDR1", "ADDR2"))
+            .with_address(
+                AddressIntent::new("ds_customer", "WORK_ZIP_CD", "WORK_ADDR1", "WORK_ADDR2")
+                    .with_id("work_addr")
+                    .with_popup(own),
+            );
+        intent.apply_address_popup(&popup);
+
+        assert!(intent.addresses[0]
+            .functions()
+            .contains("gfn_openZipcode(\"/common/zip/zip_search.xml\", \"fn_addr_callback\");"));
+        assert!(intent.addresses[1].functions().contains("url: \"/hr/addr_popup.xml\""));
+    }
+}
@@ -0,0 +1,358 @@
+//! Artifact Naming Templates
+//!
+//! File names and paths of generated artifacts come from templates such as
+//! `SCR_{ENTITY}_{TYPE}_{yyyyMMdd}.xml` or `sqlmap/{entity}.xml`. The case of a
+//! placeholder picks the case of the value: `{entity}` → `order_detail`,
+//! `{ENTITY}` → `ORDER_DETAIL`, `{Entity}` → `OrderDetail`. Placeholders made of
+//! `yMdHms` letters are date patterns. Values come from the request, so any
+//! character outside `[A-Za-z0-9_-]` is replaced with `_` before substitution;
+//! a screen or entity name can't add path segments to the archive.
+
+use chrono::NaiveDateTime;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{SpringArtifacts, SpringIntent, UiIntent};
+
+pub const DEFAULT_XML_TEMPLATE: &str = "{screen_name}.xml";
+pub const DEFAULT_JS_TEMPLATE: &str = "{screen_name}.js";
+pub const DEFAULT_JAVA_TEMPLATE: &str = "src/main/java/{package_path}/{class}.java";
+pub const DEFAULT_MAPPER_XML_TEMPLATE: &str = "src/main/resources/mapper/{Entity}Mapper.xml";
+
+/// Variables whose case follows the placeholder
+const CASED_VARS: &[&str] = &["screen_name", "entity", "type", "table", "layer"];
+
+/// Variables inserted as-is (`{class}`, `{package_path}`)
+const VERBATIM_VARS: &[&str] = &["class", "package_path"];
+
+/// A set of naming templates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamingProfile {
+    /// xFrame5 view XML
+    pub xml: String,
+
+    /// xFrame5 event script
+    pub js: String,
+
+    /// Spring Java sources (controller, service, DTO, mapper interface)
+    pub java: String,
+
+    /// MyBatis mapper XML
+    pub mapper_xml: String,
+}
+
+impl Default for NamingProfile {
+    fn default() -> Self {
+        Self {
+            xml: DEFAULT_XML_TEMPLATE.to_string(),
+            js: DEFAULT_JS_TEMPLATE.to_string(),
+            java: DEFAULT_JAVA_TEMPLATE.to_string(),
+            mapper_xml: DEFAULT_MAPPER_XML_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl NamingProfile {
+    /// xFrame5 XML and JS file names
+    pub fn screen_files(&self, vars: &NamingVars) -> (String, String) {
+        (vars.render(&self.xml), vars.render(&self.js))
+    }
+
+    /// Path of one Java source
+    pub fn java_path(&self, vars: &NamingVars, class: String, package: &str, layer: &str) -> String {
+        vars.clone()
+            .with("class", class)
+            .with("layer", layer.to_string())
+            .with("package_path", package.replace('.', "/"))
+            .render(&self.java)
+    }
+
+    /// Paths of the Spring file tree
+    pub fn spring_paths(
+        &self,
+        artifacts: &SpringArtifacts,
+        intent: &SpringIntent,
+        vars: &NamingVars,
+    ) -> SpringFilePaths {
+        let java = |source: &str, class: String, layer: &str| {
+            let package = Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;")
+                .unwrap()
+                .captures(source)
+                .map(|cap| cap[1].to_string())
+                .unwrap_or_else(|| format!("{}.{}", intent.package_base, layer));
+            self.java_path(vars, class, &package, layer)
+        };
+
+        SpringFilePaths {
+            controller: java(&artifacts.controller, intent.controller_name(), "controller"),
+            service_interface: java(&artifacts.service_interface, intent.service_name(), "service"),
+            service_impl: java(&artifacts.service_impl, intent.service_impl_name(), "service"),
+            dto: java(&artifacts.dto, intent.dto_name(), "dto"),
+            search_dto: artifacts
+                .search_dto
+                .as_deref()
+                .map(|s| java(s, format!("{}SearchDTO", intent.entity_name), "dto")),
+            mapper_interface: java(&artifacts.mapper_interface, intent.mapper_name(), "mapper"),
+            mapper_xml: vars.clone().with("layer", "mapper").render(&self.mapper_xml),
+        }
+    }
+}
+
+/// Relative paths of the generated Spring files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpringFilePaths {
+    pub controller: String,
+    pub service_interface: String,
+    pub service_impl: String,
+    pub dto: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_dto: Option<String>,
+    pub mapper_interface: String,
+    pub mapper_xml: String,
+}
+
+/// Values substituted into naming templates
+#[derive(Debug, Clone)]
+pub struct NamingVars {
+    values: Vec<(&'static str, String)>,
+    timestamp: NaiveDateTime,
+}
+
+impl NamingVars {
+    pub fn new(timestamp: NaiveDateTime) -> Self {
+        Self {
+            values: Vec::new(),
+            timestamp,
+        }
+    }
+
+    /// Set a variable (known names only; see `CASED_VARS` / `VERBATIM_VARS`)
+    pub fn with(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.values.retain(|(n, _)| *n != name);
+        self.values.push((name, value.into()));
+        self
+    }
+
+    /// Variables of an xFrame5 screen; the entity comes from the first dataset (`ds_member` → `member`)
+    pub fn for_screen(intent: &UiIntent, timestamp: NaiveDateTime) -> Self {
+        let entity = intent
+            .datasets
+            .first()
+            .map(|ds| ds.id.strip_prefix("ds_").unwrap_or(&ds.id).to_string())
+            .unwrap_or_else(|| {
+                let suffix = format!("_{}", intent.screen_type.as_str());
+                let name = intent.screen_name.to_lowercase();
+                name.strip_suffix(&suffix).unwrap_or(&name).to_string()
+            });
+        Self::new(timestamp)
+            .with("screen_name", intent.screen_name.clone())
+            .with("entity", entity)
+            .with("type", intent.screen_type.as_str())
+    }
+
+    /// Variables of a Spring entity
+    pub fn for_spring(intent: &SpringIntent, timestamp: NaiveDateTime) -> Self {
+        Self::new(timestamp)
+            .with("entity", intent.entity_name.clone())
+            .with("table", intent.table_name.clone())
+    }
+
+    /// Substitute placeholders; unknown ones are left in place
+    pub fn render(&self, template: &str) -> String {
+        placeholder_re()
+            .replace_all(template, |cap: &regex::Captures| {
+                let name = &cap[1];
+                if let Some(format) = date_format(name) {
+                    return self.timestamp.format(&format).to_string();
+                }
+                let key = name.to_lowercase();
+                match self.values.iter().find(|(n, _)| *n == key) {
+                    Some((_, value)) if key == "package_path" => value
+                        .split('/')
+                        .map(path_segment)
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                    Some((_, value)) if VERBATIM_VARS.contains(&key.as_str()) => path_segment(value),
+                    Some((_, value)) => apply_case(name, &path_segment(value)),
+                    None => cap[0].to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+/// A value as one path segment: characters outside `[A-Za-z0-9_-]` become `_`
+fn path_segment(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+fn placeholder_re() -> Regex {
+    Regex::new(r"\{(\w+)\}").unwrap()
+}
+
+/// `yyyyMMdd` → `%Y%m%d`; `None` if the name isn't a date pattern
+fn date_format(name: &str) -> Option<String> {
+    if !name.chars().all(|c| "yMdHms".contains(c)) {
+        return None;
+    }
+    let mut format = String::new();
+    let mut rest = name;
+    while !rest.is_empty() {
+        let (token, spec) = [
+            ("yyyy", "%Y"),
+            ("yy", "%y"),
+            ("MM", "%m"),
+            ("dd", "%d"),
+            ("HH", "%H"),
+            ("mm", "%M"),
+            ("ss", "%S"),
+        ]
+        .into_iter()
+        .find(|(token, _)| rest.starts_with(token))?;
+        format.push_str(spec);
+        rest = &rest[token.len()..];
+    }
+    Some(format)
+}
+
+/// Split `OrderDetail`, `order_detail`, `Order Detail` into words
+fn words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in value.chars() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Case the value like the placeholder: lower → snake, UPPER → SCREAMING_SNAKE, Mixed → Pascal
+fn apply_case(placeholder: &str, value: &str) -> String {
+    let words = words(value);
+    if placeholder.chars().all(|c| !c.is_uppercase()) {
+        words.join("_").to_lowercase()
+    } else if placeholder.chars().all(|c| !c.is_lowercase()) {
+        words.join("_").to_uppercase()
+    } else {
+        words
+            .iter()
+            .map(|w| {
+                let mut chars = w.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+/// Reject unknown placeholders, stray braces and paths escaping the archive root
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let template = template.trim();
+    if template.is_empty() {
+        return Err("template is empty".to_string());
+    }
+    if template.starts_with('/') || template.contains('\\') || template.split('/').any(|s| s == "..") {
+        return Err(format!("'{}' must be a relative path without '..'", template));
+    }
+    for cap in placeholder_re().captures_iter(template) {
+        let name = &cap[1];
+        let key = name.to_lowercase();
+        let known = CASED_VARS.contains(&key.as_str())
+            || (VERBATIM_VARS.contains(&key.as_str()) && name == key)
+            || date_format(name).is_some();
+        if !known {
+            return Err(format!("unknown placeholder {{{}}}", name));
+        }
+    }
+    if placeholder_re().replace_all(template, "").contains(['{', '}']) {
+        return Err(format!("'{}' has unbalanced braces", template));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DatasetIntent, ScreenType};
+
+    fn timestamp() -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2026, 10, 16)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_render_cases_and_dates() {
+        let mut intent = UiIntent::new("order_detail_list", ScreenType::List);
+        intent.datasets.push(DatasetIntent::new("ds_order_detail"));
+        let vars = NamingVars::for_screen(&intent, timestamp());
+
+        assert_eq!(vars.render("SCR_{ENTITY}_{TYPE}_{yyyyMMdd}.xml"), "SCR_ORDER_DETAIL_LIST_20261016.xml");
+        assert_eq!(vars.render("{Entity}/{screen_name}_{HHmm}.js"), "OrderDetail/order_detail_list_0930.js");
+        assert_eq!(vars.render("{unknown}.xml"), "{unknown}.xml");
+
+        let (xml, js) = NamingProfile::default().screen_files(&vars);
+        assert_eq!((xml.as_str(), js.as_str()), ("order_detail_list.xml", "order_detail_list.js"));
+    }
+
+    #[test]
+    fn test_values_cannot_add_path_segments() {
+        let vars = NamingVars::new(timestamp())
+            .with("screen_name", "../../etc/passwd")
+            .with("entity", "a/b")
+            .with("class", "Evil/../Controller")
+            .with("package_path", "com/../acme/./erp");
+
+        assert_eq!(vars.render("{screen_name}.xml"), "etc_passwd.xml");
+        assert_eq!(vars.render("sqlmap/{entity}.xml"), "sqlmap/a_b.xml");
+        assert_eq!(vars.render("{package_path}/{class}.java"), "com/__/acme/_/erp/Evil____Controller.java");
+        assert!(!vars.render("{ENTITY}/{screen_name}").contains(".."));
+    }
+
+    #[test]
+    fn test_spring_paths() {
+        let intent = SpringIntent::new("OrderDetail", "TB_ORDER_DETAIL", "com.company.erp");
+        let mut artifacts = SpringArtifacts::new();
+        artifacts.controller = "package com.company.erp.web;\n\npublic class OrderDetailController {}".to_string();
+
+        let profile = NamingProfile {
+            mapper_xml: "sqlmap/{entity}.xml".to_string(),
+            ..NamingProfile::default()
+        };
+        let paths = profile.spring_paths(&artifacts, &intent, &NamingVars::for_spring(&intent, timestamp()));
+        assert_eq!(paths.controller, "src/main/java/com/company/erp/web/OrderDetailController.java");
+        assert_eq!(paths.dto, "src/main/java/com/company/erp/dto/OrderDetailDTO.java");
+        assert_eq!(paths.mapper_xml, "sqlmap/order_detail.xml");
+        assert_eq!(paths.search_dto, None);
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("SCR_{ENTITY}_{TYPE}_{yyyyMMdd}.xml").is_ok());
+        assert!(validate_template("{Class}.java").is_err());
+        assert!(validate_template("{entity}.xml}").is_err());
+        assert!(validate_template("../{entity}.xml").is_err());
+        assert!(validate_template("{yyyyQ}.xml").is_err());
+    }
+}
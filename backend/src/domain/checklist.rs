@@ -0,0 +1,124 @@
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+
+/// Artifact a checklist item is checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistTarget {
+    Xml,
+    Js,
+}
+
+impl ChecklistTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecklistTarget::Xml => "xml",
+            ChecklistTarget::Js => "js",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "xml" => Some(ChecklistTarget::Xml),
+            "js" | "javascript" => Some(ChecklistTarget::Js),
+            _ => None,
+        }
+    }
+}
+
+/// Something every generated screen of a type must contain
+/// (e.g. "every list screen needs a print button")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// Shown in the prompt and in warnings
+    pub label: String,
+
+    pub target: ChecklistTarget,
+
+    /// Case-insensitive regex the target must match
+    pub pattern: String,
+}
+
+impl ChecklistItem {
+    pub fn new(label: impl Into<String>, target: ChecklistTarget, pattern: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            target,
+            pattern: pattern.into(),
+        }
+    }
+
+    /// Whether the pattern compiles (checked when an item is saved)
+    pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Whether the generated code satisfies the item
+    ///
+    /// A pattern that no longer compiles is matched as plain text.
+    pub fn is_satisfied(&self, xml: &str, javascript: &str) -> bool {
+        let code = match self.target {
+            ChecklistTarget::Xml => xml,
+            ChecklistTarget::Js => javascript,
+        };
+        match RegexBuilder::new(&self.pattern).case_insensitive(true).build() {
+            Ok(re) => re.is_match(code),
+            Err(_) => code.to_lowercase().contains(&self.pattern.to_lowercase()),
+        }
+    }
+
+    /// Checklist section appended to the system prompt
+    pub fn render(items: &[ChecklistItem]) -> String {
+        let mut out = String::from("Verify ALL are included:\n");
+        for target in [ChecklistTarget::Xml, ChecklistTarget::Js] {
+            let labels: Vec<&str> = items
+                .iter()
+                .filter(|i| i.target == target)
+                .map(|i| i.label.as_str())
+                .collect();
+            if labels.is_empty() {
+                continue;
+            }
+            out.push_str(match target {
+                ChecklistTarget::Xml => "\nIn the XML:\n",
+                ChecklistTarget::Js => "\nIn the JavaScript:\n",
+            });
+            for label in labels {
+                out.push_str(&format!("[ ] {}\n", label));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_satisfied_checks_the_target() {
+        let print = ChecklistItem::new("Print button", ChecklistTarget::Xml, r#"<pushbutton[^>]*name="btn_print""#);
+        assert!(print.is_satisfied(r#"<PushButton x="0" name="btn_print"/>"#, ""));
+        assert!(!print.is_satisfied("", r#"<pushbutton name="btn_print"/>"#));
+
+        // Broken regex falls back to a text match
+        let broken = ChecklistItem::new("Search", ChecklistTarget::Js, "fn_search(");
+        assert!(ChecklistItem::validate_pattern(&broken.pattern).is_err());
+        assert!(broken.is_satisfied("", "this.FN_SEARCH();"));
+    }
+
+    #[test]
+    fn test_render_groups_by_target() {
+        let items = vec![
+            ChecklistItem::new("fn_search function", ChecklistTarget::Js, "fn_search"),
+            ChecklistItem::new("Print button", ChecklistTarget::Xml, "btn_print"),
+        ];
+        let rendered = ChecklistItem::render(&items);
+        assert!(rendered.find("In the XML:\n[ ] Print button").unwrap() < rendered.find("In the JavaScript:").unwrap());
+        assert!(rendered.contains("[ ] fn_search function"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Column Archetypes
+//!
+//! Reusable column definitions managed in the admin panel (e.g. "금액",
+//! "사업자등록번호", "우편번호"): a column matched by name or comment takes
+//! the archetype's label, control, length, validation and format, so every
+//! screen shows it the same way. Matching happens right after normalization;
+//! the matched archetype is recorded on `ColumnIntent::archetype`, described
+//! in the prompt and checked by the `ArchetypeValidator` pass.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::locale_format::ColumnFormat;
+use super::ui_intent::{ColumnIntent, DataType, UiIntent, UiType};
+
+/// Reusable definition of a kind of column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnArchetype {
+    /// Archetype name, recorded on matched columns (e.g. "사업자등록번호")
+    pub name: String,
+
+    /// Column name patterns, `*` as wildcard (e.g. "*_AMT", "BIZ_REG_NO")
+    #[serde(default)]
+    pub name_patterns: Vec<String>,
+
+    /// Words of the column comment (or label) that select the archetype
+    #[serde(default)]
+    pub comment_keywords: Vec<String>,
+
+    /// Label of matched columns (none: keep the column's)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_type: Option<UiType>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<DataType>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+
+    /// Regex the entered value must match (e.g. `^\d{3}-\d{2}-\d{5}$`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<String>,
+
+    /// Display format: a number pattern ("#,##0"), a date pattern
+    /// ("yyyy-MM-dd") or an input mask ("###-##-#####")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl ColumnArchetype {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            name_patterns: Vec::new(),
+            comment_keywords: Vec::new(),
+            label: None,
+            ui_type: None,
+            data_type: None,
+            max_length: None,
+            validation: None,
+            format: None,
+        }
+    }
+
+    pub fn with_name_patterns(mut self, patterns: &[&str]) -> Self {
+        self.name_patterns = patterns.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    pub fn with_comment_keywords(mut self, keywords: &[&str]) -> Self {
+        self.comment_keywords = keywords.iter().map(|k| k.to_string()).collect();
+        self
+    }
+
+    /// UI type from its admin/DSL name (e.g. "date_picker")
+    pub fn parse_ui_type(value: &str) -> Option<UiType> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_lowercase())).ok()
+    }
+
+    /// Data type from its admin/DSL name (e.g. "decimal")
+    pub fn parse_data_type(value: &str) -> Option<DataType> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_lowercase())).ok()
+    }
+
+    /// Whether the validation regex compiles (checked when an archetype is saved)
+    pub fn validate_validation(pattern: &str) -> Result<(), String> {
+        Regex::new(pattern).map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    /// Whether a column name matches one of the name patterns (case-insensitive)
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.name_patterns.iter().any(|pattern| {
+            let regex = format!("^{}$", pattern.trim().split('*').map(regex::escape).collect::<Vec<_>>().join(".*"));
+            RegexBuilder::new(&regex)
+                .case_insensitive(true)
+                .build()
+                .is_ok_and(|re| re.is_match(name.trim()))
+        })
+    }
+
+    /// Whether a column comment contains one of the keywords
+    pub fn matches_comment(&self, comment: &str) -> bool {
+        let comment = comment.to_lowercase();
+        self.comment_keywords
+            .iter()
+            .map(|k| k.trim().to_lowercase())
+            .any(|k| !k.is_empty() && comment.contains(&k))
+    }
+
+    /// Archetype of a column: a name match first, then a comment match, in
+    /// the order given
+    pub fn find<'a>(archetypes: &'a [ColumnArchetype], name: &str, comment: Option<&str>) -> Option<&'a ColumnArchetype> {
+        archetypes
+            .iter()
+            .find(|a| a.matches_name(name))
+            .or_else(|| comment.and_then(|c| archetypes.iter().find(|a| a.matches_comment(c))))
+    }
+
+    /// Column format of the display format
+    pub fn column_format(&self) -> Option<ColumnFormat> {
+        let format = self.format.as_deref().map(str::trim).filter(|f| !f.is_empty())?;
+        if format.contains(['y', 'M', 'd']) {
+            return Some(ColumnFormat::date(format));
+        }
+        if format.contains('0') && format.chars().all(|c| matches!(c, '#' | ',' | '.' | '0')) {
+            let decimals = format.split_once('.').map_or(0, |(_, d)| d.len() as u32);
+            return Some(ColumnFormat::number(format.contains(','), decimals));
+        }
+        Some(ColumnFormat::mask(format))
+    }
+
+    /// Give a column the archetype's rules
+    ///
+    /// Key, hidden and code columns keep their control; personal data
+    /// columns keep their masking instead of the archetype's format.
+    pub fn apply(&self, column: &mut ColumnIntent) {
+        column.archetype = Some(self.name.clone());
+        if let Some(label) = &self.label {
+            column.label = label.clone();
+        }
+        if let Some(data_type) = self.data_type {
+            column.data_type = data_type;
+        }
+        if let Some(ui_type) = self.ui_type {
+            if !column.is_pk && column.ui_type != UiType::Hidden && column.code_group.is_none() {
+                column.ui_type = ui_type;
+            }
+        }
+        if self.max_length.is_some() {
+            column.max_length = self.max_length;
+        }
+        if self.validation.is_some() {
+            column.validation = self.validation.clone();
+        }
+        if column.sensitivity.is_none() {
+            if let Some(format) = self.column_format() {
+                column.format = Some(format);
+            }
+        }
+    }
+}
+
+impl UiIntent {
+    /// Columns matched to an archetype, across all datasets
+    pub fn archetype_columns(&self) -> Vec<&ColumnIntent> {
+        self.datasets
+            .iter()
+            .flat_map(|ds| ds.columns.iter())
+            .filter(|c| c.archetype.is_some())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archetypes() -> Vec<ColumnArchetype> {
+        vec![
+            ColumnArchetype {
+                max_length: Some(12),
+                validation: Some(r"^\d{3}-\d{2}-\d{5}$".to_string()),
+                format: Some("###-##-#####".to_string()),
+                ..ColumnArchetype::new("사업자등록번호")
+                    .with_name_patterns(&["BIZ_REG_NO", "*_BIZ_NO"])
+                    .with_comment_keywords(&["사업자등록번호", "사업자번호"])
+            },
+            ColumnArchetype {
+                ui_type: Some(UiType::Number),
+                data_type: Some(DataType::Decimal),
+                format: Some("#,##0".to_string()),
+                ..ColumnArchetype::new("금액").with_name_patterns(&["*_AMT"]).with_comment_keywords(&["금액"])
+            },
+        ]
+    }
+
+    #[test]
+    fn test_find_by_name_then_comment() {
+        let archetypes = archetypes();
+        assert_eq!(ColumnArchetype::find(&archetypes, "cust_biz_no", None).unwrap().name, "사업자등록번호");
+        assert_eq!(ColumnArchetype::find(&archetypes, "TOTAL_AMT", Some("사업자번호")).unwrap().name, "금액");
+        assert_eq!(ColumnArchetype::find(&archetypes, "REG_NO", Some("사업자번호")).unwrap().name, "사업자등록번호");
+        assert!(ColumnArchetype::find(&archetypes, "AMT_TYPE", Some("구분")).is_none());
+    }
+
+    #[test]
+    fn test_apply_sets_rules_and_format() {
+        let archetypes = archetypes();
+        let mut amount = ColumnIntent::new("TOTAL_AMT", "합계");
+        archetypes[1].apply(&mut amount);
+        assert_eq!(amount.archetype.as_deref(), Some("금액"));
+        assert_eq!((amount.ui_type, amount.data_type), (UiType::Number, DataType::Decimal));
+        assert_eq!(amount.format.unwrap().number_pattern().as_deref(), Some("#,##0"));
+
+        let mut biz = ColumnIntent::new("BIZ_REG_NO", "사업자번호");
+        archetypes[0].apply(&mut biz);
+        assert_eq!(biz.max_length, Some(12));
+        assert_eq!(biz.format.unwrap().mask.as_deref(), Some("###-##-#####"));
+
+        let mut key = ColumnIntent::new("ORDER_AMT", "금액").primary_key();
+        archetypes[1].apply(&mut key);
+        assert_eq!(key.ui_type, UiType::Hidden);
+    }
+}
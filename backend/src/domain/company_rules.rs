@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::accessibility::StyleProfile;
+use super::address::AddressPopup;
+use super::date_policy::DatePolicy;
+use super::locale_format::FormatRules;
+use super::print::PrintRules;
+use super::search_state::SearchStateRules;
+
+/// Structured company rule sections (stored as JSON in `company_rules.sections`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompanyRuleSections {
+    /// Naming conventions (variables, functions, component ids)
+    #[serde(default)]
+    pub naming: Option<String>,
+
+    /// Layout rules keyed by screen type ("list", "detail", ...); "*" applies to all
+    #[serde(default)]
+    pub layout: BTreeMap<String, String>,
+
+    /// Security rules
+    #[serde(default)]
+    pub security: Option<String>,
+
+    /// Comment/documentation rules
+    #[serde(default)]
+    pub comments: Option<String>,
+
+    /// APIs that must not appear in generated or reviewed code
+    #[serde(default)]
+    pub forbidden_apis: Vec<ForbiddenApi>,
+
+    /// Built-in deny-list patterns this rule set explicitly permits
+    #[serde(default)]
+    pub allowed_apis: Vec<String>,
+
+    /// Default report tool for screens with printing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub print: Option<PrintRules>,
+
+    /// Address-search popup of screens with address fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<AddressPopup>,
+
+    /// Customer helpers storing kept search conditions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_state: Option<SearchStateRules>,
+
+    /// Standard UX behaviors wired into generated screens (all on when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ux: Option<UxBehaviors>,
+
+    /// Error-handling conventions of generated JavaScript
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_handling: Option<ErrorHandlingRules>,
+
+    /// Screen palette and touch target size (accessibility report)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<StyleProfile>,
+
+    /// Formatting of generated Java (Google Java Style when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_format: Option<JavaFormatStyle>,
+
+    /// Date/number formats of screen columns (locale defaults when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formats: Option<FormatRules>,
+
+    /// Storage zone and JSON patterns of dates in generated Spring code (KST when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_policy: Option<DatePolicy>,
+}
+
+/// Formatting of generated Java; every field defaults to Google Java Style
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JavaFormatStyle {
+    /// Spaces per block level
+    #[serde(default = "default_java_indent")]
+    pub indent: usize,
+
+    /// Extra spaces for continuation lines
+    #[serde(default = "default_java_continuation_indent")]
+    pub continuation_indent: usize,
+
+    /// Longest line before wrapping
+    #[serde(default = "default_java_max_line_length")]
+    pub max_line_length: usize,
+
+    /// Import groups by package prefix (e.g. ["java.", "javax.", "org.", "com.acme."]),
+    /// separated by a blank line; empty keeps all non-static imports in one group
+    #[serde(default)]
+    pub import_groups: Vec<String>,
+}
+
+impl Default for JavaFormatStyle {
+    fn default() -> Self {
+        Self {
+            indent: default_java_indent(),
+            continuation_indent: default_java_continuation_indent(),
+            max_line_length: default_java_max_line_length(),
+            import_groups: Vec::new(),
+        }
+    }
+}
+
+fn default_java_indent() -> usize {
+    2
+}
+
+fn default_java_continuation_indent() -> usize {
+    4
+}
+
+fn default_java_max_line_length() -> usize {
+    100
+}
+
+/// How generated JavaScript handles failed transactions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorHandlingRules {
+    /// Helper that shows error messages to the user (e.g. "gfn_alert")
+    #[serde(default = "default_alert_helper")]
+    pub alert_helper: String,
+
+    /// Name ending of transaction callbacks (e.g. "Callback" for fn_searchCallback)
+    #[serde(default = "default_callback_suffix")]
+    pub callback_suffix: String,
+}
+
+impl Default for ErrorHandlingRules {
+    fn default() -> Self {
+        Self {
+            alert_helper: default_alert_helper(),
+            callback_suffix: default_callback_suffix(),
+        }
+    }
+}
+
+fn default_alert_helper() -> String {
+    "alert".to_string()
+}
+
+fn default_callback_suffix() -> String {
+    "Callback".to_string()
+}
+
+/// Standard UX behaviors of the company's screens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UxBehaviors {
+    /// Enter in a search field runs `fn_search`
+    #[serde(default = "enabled")]
+    pub enter_to_search: bool,
+
+    /// F5 does not reload the page (and lose the user's input)
+    #[serde(default = "enabled")]
+    pub f5_guard: bool,
+
+    /// A second click on a save button within a second is ignored
+    #[serde(default = "enabled")]
+    pub double_submit_guard: bool,
+}
+
+impl Default for UxBehaviors {
+    fn default() -> Self {
+        Self {
+            enter_to_search: true,
+            f5_guard: true,
+            double_submit_guard: true,
+        }
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// A forbidden API entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForbiddenApi {
+    /// Literal text to match (e.g. "eval(", "document.write")
+    pub pattern: String,
+
+    /// Suggested replacement
+    #[serde(default)]
+    pub replacement: Option<String>,
+
+    /// Why the API is forbidden
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    /// Products this entry applies to (empty = all)
+    #[serde(default)]
+    pub products: Vec<String>,
+}
+
+/// A forbidden API occurrence found in code
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForbiddenApiHit {
+    pub pattern: String,
+    pub line: u32,
+    pub replacement: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl ForbiddenApi {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            replacement: None,
+            reason: None,
+            products: Vec::new(),
+        }
+    }
+
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = Some(replacement.into());
+        self
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Whether this entry applies to the given product
+    pub fn applies_to(&self, product: &str) -> bool {
+        self.products.is_empty() || self.products.iter().any(|p| p == product)
+    }
+
+    /// Find all occurrences (1-based line numbers) of the forbidden APIs in code
+    pub fn scan(code: &str, apis: &[ForbiddenApi]) -> Vec<ForbiddenApiHit> {
+        let mut hits = Vec::new();
+        for (idx, line) in code.lines().enumerate() {
+            for api in apis {
+                if !api.pattern.is_empty() && line.contains(&api.pattern) {
+                    hits.push(ForbiddenApiHit {
+                        pattern: api.pattern.clone(),
+                        line: (idx + 1) as u32,
+                        replacement: api.replacement.clone(),
+                        reason: api.reason.clone(),
+                    });
+                }
+            }
+        }
+        hits
+    }
+}
+
+impl ForbiddenApiHit {
+    /// Human-readable message for warnings and review issues
+    pub fn message(&self) -> String {
+        let mut msg = format!("Forbidden API '{}' used at line {}", self.pattern, self.line);
+        if let Some(reason) = &self.reason {
+            msg.push_str(&format!(" ({})", reason));
+        }
+        if let Some(replacement) = &self.replacement {
+            msg.push_str(&format!("; use '{}' instead", replacement));
+        }
+        msg
+    }
+}
+
+impl CompanyRuleSections {
+    /// Whether no section has content
+    pub fn is_empty(&self) -> bool {
+        self.naming.as_deref().is_none_or(str::is_empty)
+            && self.layout.values().all(String::is_empty)
+            && self.security.as_deref().is_none_or(str::is_empty)
+            && self.comments.as_deref().is_none_or(str::is_empty)
+            && self.forbidden_apis.is_empty()
+            && self.allowed_apis.is_empty()
+            && self.print.is_none()
+            && self.address.is_none()
+            && self.search_state.is_none()
+            && self.ux.is_none()
+            && self.error_handling.is_none()
+            && self.style.is_none()
+            && self.java_format.is_none()
+            && self.formats.is_none()
+            && self.date_policy.is_none()
+    }
+
+    /// Forbidden APIs that apply to the product
+    pub fn forbidden_apis_for(&self, product: &str) -> Vec<ForbiddenApi> {
+        self.forbidden_apis
+            .iter()
+            .filter(|api| api.applies_to(product))
+            .cloned()
+            .collect()
+    }
+
+    /// Render only the sections relevant to the product and screen type.
+    ///
+    /// Layout rules are UI-only and are skipped for Spring products. With no
+    /// screen type (e.g. code review) every layout entry is included.
+    pub fn render(&self, product: &str, screen_type: Option<&str>) -> String {
+        let mut out = String::new();
+
+        if let Some(naming) = self.naming.as_deref().filter(|s| !s.is_empty()) {
+            out.push_str(&format!("## Naming\n{}\n\n", naming.trim()));
+        }
+
+        if !product.contains("spring") {
+            let layout: Vec<_> = self
+                .layout
+                .iter()
+                .filter(|(key, value)| {
+                    !value.is_empty()
+                        && (key.as_str() == "*"
+                            || screen_type.is_none_or(|st| key.as_str() == st))
+                })
+                .map(|(_, value)| value.trim())
+                .collect();
+            if !layout.is_empty() {
+                out.push_str(&format!("## Layout\n{}\n\n", layout.join("\n")));
+            }
+        }
+
+        if let Some(security) = self.security.as_deref().filter(|s| !s.is_empty()) {
+            out.push_str(&format!("## Security\n{}\n\n", security.trim()));
+        }
+
+        if let Some(comments) = self.comments.as_deref().filter(|s| !s.is_empty()) {
+            out.push_str(&format!("## Comments\n{}\n\n", comments.trim()));
+        }
+
+        if let Some(errors) = self.error_handling.as_ref().filter(|_| !product.contains("spring")) {
+            out.push_str(&format!(
+                "## Error Handling\n- Every transaction callback (fn_xxx{}) checks the error code and shows the \
+                 message with {}()\n- No empty catch blocks; never only console.log an error\n\n",
+                errors.callback_suffix, errors.alert_helper
+            ));
+        }
+
+        if let Some(style) = self.style.as_ref().filter(|_| !product.contains("spring")) {
+            out.push_str("## Style\n");
+            if let (Some(text), Some(background)) = (&style.text_color, &style.background_color) {
+                out.push_str(&format!("- Text color {} on background {}\n", text, background));
+            }
+            if let (Some(text), Some(background)) = (&style.button_text_color, &style.button_background_color) {
+                out.push_str(&format!("- Primary buttons: text {} on {}\n", text, background));
+            }
+            out.push_str(&format!(
+                "- Buttons and inputs at least {}x{} px\n\n",
+                style.min_target_size, style.min_target_size
+            ));
+        }
+
+        let forbidden = self.forbidden_apis_for(product);
+        if !forbidden.is_empty() {
+            out.push_str("## Forbidden APIs\n");
+            for api in &forbidden {
+                out.push_str(&format!("- Do not use `{}`", api.pattern));
+                if let Some(replacement) = &api.replacement {
+                    out.push_str(&format!(" (use `{}` instead)", replacement));
+                }
+                if let Some(reason) = &api.reason {
+                    out.push_str(&format!(": {}", reason));
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+
+        out.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CompanyRuleSections {
+        let mut layout = BTreeMap::new();
+        layout.insert("*".to_string(), "Buttons top-right".to_string());
+        layout.insert("list".to_string(), "Search panel above grid".to_string());
+        layout.insert("detail".to_string(), "Two-column form".to_string());
+
+        CompanyRuleSections {
+            naming: Some("fn_ prefix for handlers".to_string()),
+            layout,
+            security: None,
+            comments: Some("Korean comments".to_string()),
+            forbidden_apis: vec![
+                ForbiddenApi::new("eval(").with_replacement("JSON.parse("),
+                ForbiddenApi {
+                    products: vec!["spring-backend".to_string()],
+                    ..ForbiddenApi::new("System.out.println")
+                },
+            ],
+            allowed_apis: vec![],
+            print: None,
+            address: None,
+            search_state: None,
+            ux: None,
+            error_handling: Some(ErrorHandlingRules {
+                alert_helper: "gfn_alert".to_string(),
+                ..Default::default()
+            }),
+            style: Some(StyleProfile {
+                text_color: Some("#333333".to_string()),
+                background_color: Some("#FFFFFF".to_string()),
+                ..Default::default()
+            }),
+            java_format: None,
+            formats: None,
+            date_policy: None,
+        }
+    }
+
+    #[test]
+    fn test_render_filters_layout_by_screen_type() {
+        let rendered = sample().render("xframe5-ui", Some("list"));
+        assert!(rendered.contains("Search panel above grid"));
+        assert!(rendered.contains("Buttons top-right"));
+        assert!(!rendered.contains("Two-column form"));
+        assert!(!rendered.contains("## Security"));
+        assert!(rendered.contains("(fn_xxxCallback) checks the error code and shows the message with gfn_alert()"));
+        assert!(rendered.contains("## Style\n- Text color #333333 on background #FFFFFF\n- Buttons and inputs at least 24x24 px"));
+    }
+
+    #[test]
+    fn test_render_spring_skips_layout() {
+        let rendered = sample().render("spring-backend", None);
+        assert!(!rendered.contains("## Layout"));
+        assert!(!rendered.contains("## Error Handling"));
+        assert!(rendered.contains("System.out.println"));
+    }
+
+    #[test]
+    fn test_forbidden_apis_scoped_by_product() {
+        let rendered = sample().render("xframe5-ui", Some("list"));
+        assert!(rendered.contains("Do not use `eval(` (use `JSON.parse(` instead)"));
+        assert!(!rendered.contains("System.out.println"));
+    }
+
+    #[test]
+    fn test_scan_reports_lines() {
+        let apis = sample().forbidden_apis_for("xframe5-ui");
+        let hits = ForbiddenApi::scan("var a = 1;\nvar b = eval(x);", &apis);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line, 2);
+        assert!(hits[0].message().contains("use 'JSON.parse(' instead"));
+    }
+}
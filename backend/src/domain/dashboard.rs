@@ -0,0 +1,239 @@
+//! Dashboard Intent
+//!
+//! KPI cards and charts of a dashboard screen, each fed by one aggregate
+//! query (dataset). Positions are computed here, not by the LLM, so the same
+//! intent always produces the same layout:
+//!
+//! ```text
+//! [refresh]
+//! [kpi] [kpi] [kpi] [kpi]      4 cards per row
+//! [chart      ] [chart      ]  2 charts per row
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Screen width of the generated dashboard (same as list screens)
+pub const DASHBOARD_WIDTH: u32 = 1024;
+
+const MARGIN: u32 = 10;
+const GAP: u32 = 10;
+/// Below the refresh button
+const TOP: u32 = 50;
+const KPIS_PER_ROW: u32 = 4;
+const KPI_HEIGHT: u32 = 80;
+const CHARTS_PER_ROW: u32 = 2;
+const CHART_HEIGHT: u32 = 300;
+
+/// KPI cards and charts of a dashboard screen
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DashboardIntent {
+    #[serde(default)]
+    pub kpis: Vec<KpiIntent>,
+
+    #[serde(default)]
+    pub charts: Vec<ChartIntent>,
+
+    /// Reload every dataset on a timer (seconds); refresh button only if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_seconds: Option<u32>,
+}
+
+/// Single-value card (e.g. today's order count)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KpiIntent {
+    /// Card panel name (e.g. "pnl_kpi_total_amt")
+    pub id: String,
+
+    pub label: String,
+
+    /// Dataset holding the value (first row)
+    pub dataset_id: String,
+
+    pub value_column: String,
+}
+
+impl KpiIntent {
+    pub fn new(dataset_id: impl Into<String>, value_column: impl Into<String>, label: impl Into<String>) -> Self {
+        let value_column = value_column.into();
+        Self {
+            id: format!("pnl_kpi_{}", value_column.to_lowercase()),
+            label: label.into(),
+            dataset_id: dataset_id.into(),
+            value_column,
+        }
+    }
+}
+
+/// Chart of a grouped aggregate (one series per value column)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChartIntent {
+    /// Chart component name (e.g. "chart_order")
+    pub id: String,
+
+    pub title: String,
+
+    pub chart_type: ChartType,
+
+    /// Bound dataset (`link_data`)
+    pub dataset_id: String,
+
+    /// X axis / slice labels (the GROUP BY column)
+    pub category_column: String,
+
+    /// Series (the aggregate columns)
+    pub value_columns: Vec<String>,
+}
+
+/// Chart kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartType {
+    Bar,
+    Line,
+    Pie,
+}
+
+impl ChartType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChartType::Bar => "bar",
+            ChartType::Line => "line",
+            ChartType::Pie => "pie",
+        }
+    }
+
+    /// Line for time categories (dates, months), bar otherwise
+    pub fn for_category(column: &str) -> Self {
+        let lower = column.to_lowercase();
+        let is_time = ["date", "month", "year", "week", "day", "ym", "dt"]
+            .iter()
+            .any(|t| lower == *t || lower.ends_with(&format!("_{}", t)) || lower.starts_with(&format!("{}_", t)));
+        if is_time {
+            ChartType::Line
+        } else {
+            ChartType::Bar
+        }
+    }
+}
+
+/// Position of a KPI card or chart on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Placement {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DashboardIntent {
+    /// Datasets the dashboard reads, in order, without duplicates
+    pub fn dataset_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = Vec::new();
+        let all = self
+            .kpis
+            .iter()
+            .map(|k| k.dataset_id.as_str())
+            .chain(self.charts.iter().map(|c| c.dataset_id.as_str()));
+        for id in all {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    /// `(component id, placement)` of every card, then every chart
+    pub fn layout(&self) -> Vec<(&str, Placement)> {
+        let content = DASHBOARD_WIDTH - 2 * MARGIN;
+        let mut placements = Vec::new();
+
+        let kpi_width = (content - (KPIS_PER_ROW - 1) * GAP) / KPIS_PER_ROW;
+        for (i, kpi) in self.kpis.iter().enumerate() {
+            let (row, col) = (i as u32 / KPIS_PER_ROW, i as u32 % KPIS_PER_ROW);
+            placements.push((
+                kpi.id.as_str(),
+                Placement {
+                    x: MARGIN + col * (kpi_width + GAP),
+                    y: TOP + row * (KPI_HEIGHT + GAP),
+                    width: kpi_width,
+                    height: KPI_HEIGHT,
+                },
+            ));
+        }
+
+        let kpi_rows = (self.kpis.len() as u32).div_ceil(KPIS_PER_ROW);
+        let charts_top = TOP + kpi_rows * (KPI_HEIGHT + GAP);
+        let chart_width = (content - (CHARTS_PER_ROW - 1) * GAP) / CHARTS_PER_ROW;
+        for (i, chart) in self.charts.iter().enumerate() {
+            let (row, col) = (i as u32 / CHARTS_PER_ROW, i as u32 % CHARTS_PER_ROW);
+            placements.push((
+                chart.id.as_str(),
+                Placement {
+                    x: MARGIN + col * (chart_width + GAP),
+                    y: charts_top + row * (CHART_HEIGHT + GAP),
+                    width: chart_width,
+                    height: CHART_HEIGHT,
+                },
+            ));
+        }
+
+        placements
+    }
+
+    /// Screen height that fits the layout
+    pub fn height(&self) -> u32 {
+        self.layout()
+            .iter()
+            .map(|(_, p)| p.y + p.height + MARGIN)
+            .max()
+            .unwrap_or(TOP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chart(id: &str) -> ChartIntent {
+        ChartIntent {
+            id: id.to_string(),
+            title: id.to_string(),
+            chart_type: ChartType::Bar,
+            dataset_id: format!("ds_{}", id),
+            category_column: "DEPT_NAME".to_string(),
+            value_columns: vec!["TOTAL_AMT".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_layout_is_deterministic_grid() {
+        let dashboard = DashboardIntent {
+            kpis: (1..=5).map(|i| KpiIntent::new("ds_summary", format!("V{}", i), "KPI")).collect(),
+            charts: vec![chart("a"), chart("b"), chart("c")],
+            refresh_seconds: None,
+        };
+        let layout = dashboard.layout();
+
+        assert_eq!(layout.len(), 8);
+        assert_eq!(layout[0], ("pnl_kpi_v1", Placement { x: 10, y: 50, width: 243, height: 80 }));
+        assert_eq!(layout[3].1.x, 10 + 3 * 253);
+        // Fifth card wraps to a second row
+        assert_eq!(layout[4].1, Placement { x: 10, y: 140, width: 243, height: 80 });
+        // Charts start under the cards, two per row
+        assert_eq!(layout[5], ("a", Placement { x: 10, y: 230, width: 497, height: 300 }));
+        assert_eq!(layout[6].1.x, 517);
+        assert_eq!(layout[7].1.y, 540);
+        assert_eq!(dashboard.height(), 850);
+        assert_eq!(dashboard.layout(), layout);
+
+        assert_eq!(dashboard.dataset_ids(), vec!["ds_summary", "ds_a", "ds_b", "ds_c"]);
+    }
+
+    #[test]
+    fn test_chart_type_for_category() {
+        assert_eq!(ChartType::for_category("ORDER_MONTH"), ChartType::Line);
+        assert_eq!(ChartType::for_category("reg_dt"), ChartType::Line);
+        assert_eq!(ChartType::for_category("DEPT_NAME"), ChartType::Bar);
+        assert_eq!(ChartType::for_category("DAYTIME_FLAG"), ChartType::Bar);
+    }
+}
@@ -0,0 +1,191 @@
+//! Date and Time Policy
+//!
+//! How generated Spring code stores and serializes dates: the zone stored
+//! timestamps are in, whether date-times carry that zone in their Java type,
+//! and the JSON patterns of date fields. The policy comes from the company
+//! rule set's `date_policy` section (KST local date-times when unset) and
+//! lands in the prompt as `@JsonFormat` annotations and MyBatis typeHandlers;
+//! `SpringValidator` checks the output against it.
+
+use serde::{Deserialize, Serialize};
+
+use super::spring_intent::to_camel_case;
+use super::ui_intent::{ColumnIntent, DataType};
+
+/// Zone timestamps are stored in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum StorageZone {
+    Utc,
+    /// Korea Standard Time, what most on-premise customers store
+    #[default]
+    Kst,
+}
+
+impl StorageZone {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageZone::Utc => "UTC",
+            StorageZone::Kst => "KST",
+        }
+    }
+
+    /// `java.time.ZoneId` of the zone
+    pub fn zone_id(&self) -> &'static str {
+        match self {
+            StorageZone::Utc => "UTC",
+            StorageZone::Kst => "Asia/Seoul",
+        }
+    }
+}
+
+/// Legacy date APIs generated code must not use, with their java.time replacement
+pub const LEGACY_DATE_APIS: &[(&str, &str)] = &[
+    ("java.util.Date", "LocalDate / LocalDateTime"),
+    ("java.sql.Timestamp", "LocalDateTime"),
+    ("java.util.Calendar", "ZonedDateTime"),
+    ("SimpleDateFormat", "DateTimeFormatter"),
+];
+
+/// `date_policy` section of a company rule set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatePolicy {
+    #[serde(default)]
+    pub storage_zone: StorageZone,
+
+    /// Date-times as `OffsetDateTime` in the storage zone instead of `LocalDateTime`
+    #[serde(default)]
+    pub explicit_zone: bool,
+
+    /// JSON pattern of date fields
+    #[serde(default = "default_date_pattern")]
+    pub date_pattern: String,
+
+    /// JSON pattern of date-time fields
+    #[serde(default = "default_date_time_pattern")]
+    pub date_time_pattern: String,
+}
+
+impl Default for DatePolicy {
+    fn default() -> Self {
+        Self {
+            storage_zone: StorageZone::default(),
+            explicit_zone: false,
+            date_pattern: default_date_pattern(),
+            date_time_pattern: default_date_time_pattern(),
+        }
+    }
+}
+
+fn default_date_pattern() -> String {
+    "yyyy-MM-dd".to_string()
+}
+
+fn default_date_time_pattern() -> String {
+    "yyyy-MM-dd HH:mm:ss".to_string()
+}
+
+impl DatePolicy {
+    /// java.time type of a date column (None for other columns)
+    pub fn java_type(&self, data_type: &DataType) -> Option<&'static str> {
+        match data_type {
+            DataType::Date => Some("LocalDate"),
+            DataType::DateTime if self.explicit_zone => Some("OffsetDateTime"),
+            DataType::DateTime => Some("LocalDateTime"),
+            _ => None,
+        }
+    }
+
+    /// JSON pattern of a date column
+    pub fn pattern(&self, data_type: &DataType) -> Option<&str> {
+        match data_type {
+            DataType::Date => Some(&self.date_pattern),
+            DataType::DateTime => Some(&self.date_time_pattern),
+            _ => None,
+        }
+    }
+
+    /// `@JsonFormat` annotation of a date column; date-times name the storage zone
+    pub fn json_format(&self, data_type: &DataType) -> Option<String> {
+        let pattern = self.pattern(data_type)?;
+        Some(match data_type {
+            DataType::DateTime => format!(
+                "@JsonFormat(pattern = \"{}\", timezone = \"{}\")",
+                pattern,
+                self.storage_zone.zone_id()
+            ),
+            _ => format!("@JsonFormat(pattern = \"{}\")", pattern),
+        })
+    }
+
+    /// MyBatis typeHandler of a date column
+    pub fn type_handler(&self, data_type: &DataType) -> Option<&'static str> {
+        match self.java_type(data_type)? {
+            "LocalDate" => Some("org.apache.ibatis.type.LocalDateTypeHandler"),
+            "OffsetDateTime" => Some("org.apache.ibatis.type.OffsetDateTimeTypeHandler"),
+            _ => Some("org.apache.ibatis.type.LocalDateTimeTypeHandler"),
+        }
+    }
+
+    /// Prompt section for the date columns (empty when there are none)
+    pub fn prompt_section(&self, columns: &[ColumnIntent]) -> String {
+        let dates: Vec<&ColumnIntent> = columns.iter().filter(|c| self.java_type(&c.data_type).is_some()).collect();
+        if dates.is_empty() {
+            return String::new();
+        }
+
+        let zone = self.storage_zone.zone_id();
+        let mut section = String::from("\nDATE/TIME POLICY:\n");
+        section.push_str(&format!(
+            "- Timestamps are stored in {} ({}); take the current time with {}.now(ZoneId.of(\"{}\")), never the server's default zone\n",
+            self.storage_zone.as_str(),
+            zone,
+            if self.explicit_zone { "OffsetDateTime" } else { "LocalDateTime" },
+            zone
+        ));
+        let legacy: Vec<&str> = LEGACY_DATE_APIS.iter().map(|(api, _)| *api).collect();
+        section.push_str(&format!("- Use java.time only; never {}\n", legacy.join(", ")));
+        for col in dates {
+            let field = to_camel_case(&col.name);
+            section.push_str(&format!(
+                "- {} {}: {} in the DTO; typeHandler={} in the mapper XML\n",
+                self.java_type(&col.data_type).unwrap_or_default(),
+                field,
+                self.json_format(&col.data_type).unwrap_or_default(),
+                self.type_handler(&col.data_type).unwrap_or_default()
+            ));
+        }
+        section
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_maps_date_columns() {
+        let policy: DatePolicy = serde_json::from_str(r#"{"storage_zone": "UTC", "explicit_zone": true}"#).unwrap();
+        assert_eq!(policy.date_pattern, "yyyy-MM-dd");
+        assert_eq!(policy.java_type(&DataType::DateTime), Some("OffsetDateTime"));
+        assert_eq!(policy.java_type(&DataType::String), None);
+        assert_eq!(
+            policy.json_format(&DataType::DateTime).unwrap(),
+            "@JsonFormat(pattern = \"yyyy-MM-dd HH:mm:ss\", timezone = \"UTC\")"
+        );
+        assert_eq!(policy.type_handler(&DataType::Date), Some("org.apache.ibatis.type.LocalDateTypeHandler"));
+
+        let columns = vec![
+            ColumnIntent::new("member_name", "회원명"),
+            ColumnIntent::new("reg_dt", "등록일시").with_data_type(DataType::DateTime),
+        ];
+        let section = DatePolicy::default().prompt_section(&columns);
+        assert!(section.contains("stored in KST (Asia/Seoul)"));
+        assert!(section.contains(
+            "- LocalDateTime regDt: @JsonFormat(pattern = \"yyyy-MM-dd HH:mm:ss\", timezone = \"Asia/Seoul\") in the DTO; \
+             typeHandler=org.apache.ibatis.type.LocalDateTimeTypeHandler in the mapper XML"
+        ));
+        assert!(!section.contains("memberName"));
+        assert!(DatePolicy::default().prompt_section(&columns[..1]).is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! Database Dialects and Column Types
+//!
+//! Column types arrive exactly as written in the customer's DDL or data
+//! dictionary, so parsing accepts Oracle forms (`VARCHAR2(100 CHAR)`,
+//! `NUMBER(10,2)`, `TIMESTAMP(6) WITH TIME ZONE`) as well as PostgreSQL and
+//! MySQL ones.
+
+use serde::{Deserialize, Serialize};
+
+/// Source database dialect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DbDialect {
+    Postgres,
+    Mysql,
+    Oracle,
+}
+
+impl DbDialect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DbDialect::Postgres => "postgres",
+            DbDialect::Mysql => "mysql",
+            DbDialect::Oracle => "oracle",
+        }
+    }
+
+    /// Guess the dialect from types only Oracle uses (VARCHAR2, NUMBER, ...)
+    pub fn detect<'a>(column_types: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        column_types
+            .into_iter()
+            .map(ColumnType::parse)
+            .any(|t| t.is_oracle_only())
+            .then_some(DbDialect::Oracle)
+    }
+}
+
+/// A column type split into base name and arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnType {
+    /// Upper-case type name without arguments (e.g. `VARCHAR2`, `TIMESTAMP WITH TIME ZONE`)
+    pub base: String,
+
+    /// First argument: length or precision (`BYTE`/`CHAR` length semantics are ignored)
+    pub precision: Option<u32>,
+
+    /// Second argument: scale
+    pub scale: Option<u32>,
+}
+
+impl ColumnType {
+    pub fn parse(db_type: &str) -> Self {
+        let upper = db_type.trim().to_uppercase();
+        let Some((start, end)) = upper.find('(').zip(upper.find(')')).filter(|(s, e)| s < e) else {
+            return Self {
+                base: upper,
+                precision: None,
+                scale: None,
+            };
+        };
+
+        let mut args = upper[start + 1..end].split(',');
+        let number = |arg: Option<&str>| {
+            arg.and_then(|a| a.split_whitespace().next())
+                .and_then(|a| a.parse::<u32>().ok())
+        };
+        let precision = number(args.next());
+        let scale = number(args.next());
+
+        let base = format!("{} {}", upper[..start].trim(), upper[end + 1..].trim());
+        Self {
+            base: base.trim().to_string(),
+            precision,
+            scale,
+        }
+    }
+
+    /// Length of a single-argument type (`VARCHAR2(100 CHAR)` → 100, `DECIMAL(10,2)` → none)
+    pub fn length(&self) -> Option<u32> {
+        self.precision.filter(|_| self.scale.is_none())
+    }
+
+    /// Oracle `NUMBER`
+    pub fn is_number(&self) -> bool {
+        self.base == "NUMBER"
+    }
+
+    /// `NUMBER(p)` / `NUMBER(p,0)`; a bare `NUMBER` may hold fractions
+    pub fn is_integral_number(&self) -> bool {
+        self.is_number() && self.precision.is_some() && self.scale.unwrap_or(0) == 0
+    }
+
+    /// Types that only exist in Oracle
+    pub fn is_oracle_only(&self) -> bool {
+        matches!(
+            self.base.as_str(),
+            "VARCHAR2" | "NVARCHAR2" | "NUMBER" | "NCLOB" | "RAW" | "LONG RAW" | "BINARY_FLOAT" | "BINARY_DOUBLE"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_oracle_types() {
+        let varchar = ColumnType::parse("varchar2(100 char)");
+        assert_eq!(varchar.base, "VARCHAR2");
+        assert_eq!(varchar.length(), Some(100));
+
+        let amount = ColumnType::parse("NUMBER(10,2)");
+        assert_eq!((amount.precision, amount.scale), (Some(10), Some(2)));
+        assert_eq!(amount.length(), None);
+        assert!(!amount.is_integral_number());
+
+        assert!(ColumnType::parse("NUMBER(10)").is_integral_number());
+        assert!(!ColumnType::parse("NUMBER").is_integral_number());
+        assert_eq!(ColumnType::parse("TIMESTAMP(6) WITH TIME ZONE").base, "TIMESTAMP WITH TIME ZONE");
+    }
+
+    #[test]
+    fn test_detect_dialect() {
+        assert_eq!(DbDialect::detect(["VARCHAR2(50)", "DATE"]), Some(DbDialect::Oracle));
+        assert_eq!(DbDialect::detect(["VARCHAR(50)", "DATE"]), None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Error-Code Catalog
+//!
+//! The project's standard error codes, uploaded by administrators as CSV or
+//! JSON. Generated Spring exceptions and xFrame5 callback branches must use
+//! these codes instead of inventing their own: the catalog is listed in the
+//! prompt, and `SpringValidator` and the `ErrorHandlingValidator` pass flag
+//! code-like literals that are not in it.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// One entry of the catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorCode {
+    /// Code as the code base writes it (e.g. "E1001", "MEM-404")
+    pub code: String,
+
+    /// Message shown to the user
+    #[serde(default)]
+    pub message: String,
+
+    /// HTTP status the Spring exception handler answers with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+}
+
+/// Standard error codes of a project
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorCodeCatalog {
+    #[serde(default)]
+    pub codes: Vec<ErrorCode>,
+}
+
+impl ErrorCodeCatalog {
+    /// Parse an uploaded file by its extension (`.csv` or `.json`)
+    pub fn parse(content: &str, filename: &str) -> Result<Self, String> {
+        let lower = filename.to_lowercase();
+        if lower.ends_with(".json") {
+            Self::from_json(content)
+        } else if lower.ends_with(".csv") {
+            Self::from_csv(content)
+        } else {
+            Err("Unsupported file type. Please upload .csv or .json".to_string())
+        }
+    }
+
+    /// JSON array of entries, or an object with a `codes` array
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let codes = match serde_json::from_str::<Vec<ErrorCode>>(content) {
+            Ok(codes) => codes,
+            Err(_) => serde_json::from_str::<Self>(content)
+                .map_err(|e| format!("Invalid catalog JSON: {}", e))?
+                .codes,
+        };
+        Self::checked(codes)
+    }
+
+    /// CSV with a `code,message,http_status` header (columns in any order;
+    /// without a header the columns are taken in that order)
+    pub fn from_csv(content: &str) -> Result<Self, String> {
+        let mut rows = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(split_csv_line)
+            .peekable();
+
+        let mut columns = (0, Some(1), Some(2));
+        if let Some(header) = rows.peek() {
+            let names: Vec<String> = header.iter().map(|h| h.to_lowercase()).collect();
+            let find = |keys: &[&str]| names.iter().position(|n| keys.contains(&n.as_str()));
+            if let Some(code) = find(&["code", "error_code", "코드"]) {
+                columns = (code, find(&["message", "msg", "메시지"]), find(&["http_status", "status", "http"]));
+                rows.next();
+            }
+        }
+
+        let (code_col, message_col, status_col) = columns;
+        let mut codes = Vec::new();
+        for (i, row) in rows.enumerate() {
+            let field = |col: Option<usize>| col.and_then(|c| row.get(c)).map(|v| v.trim().to_string());
+            let http_status = match field(status_col).filter(|s| !s.is_empty()) {
+                Some(status) => Some(
+                    status
+                        .parse::<u16>()
+                        .map_err(|_| format!("Row {}: invalid HTTP status '{}'", i + 1, status))?,
+                ),
+                None => None,
+            };
+            codes.push(ErrorCode {
+                code: field(Some(code_col)).unwrap_or_default(),
+                message: field(message_col).unwrap_or_default(),
+                http_status,
+            });
+        }
+        Self::checked(codes)
+    }
+
+    /// Trimmed entries; rejects blank and duplicate codes
+    fn checked(codes: Vec<ErrorCode>) -> Result<Self, String> {
+        let mut catalog = Self::default();
+        for mut entry in codes {
+            entry.code = entry.code.trim().to_string();
+            entry.message = entry.message.trim().to_string();
+            if entry.code.is_empty() {
+                return Err("Every entry needs a code".to_string());
+            }
+            if catalog.get(&entry.code).is_some() {
+                return Err(format!("Code '{}' appears more than once", entry.code));
+            }
+            catalog.codes.push(entry);
+        }
+        if catalog.is_empty() {
+            return Err("The catalog has no codes".to_string());
+        }
+        Ok(catalog)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    pub fn get(&self, code: &str) -> Option<&ErrorCode> {
+        self.codes.iter().find(|c| c.code == code)
+    }
+
+    /// Add the entries of another catalog whose codes are not in this one
+    pub fn merge(&mut self, other: ErrorCodeCatalog) {
+        for entry in other.codes {
+            if self.get(&entry.code).is_none() {
+                self.codes.push(entry);
+            }
+        }
+    }
+
+    /// First code answered with an HTTP status
+    pub fn code_for_status(&self, status: u16) -> Option<&str> {
+        self.codes
+            .iter()
+            .find(|c| c.http_status == Some(status))
+            .map(|c| c.code.as_str())
+    }
+
+    /// Quoted literals shaped like the catalog's codes: one of their prefixes
+    /// followed by digits (`E1001` and `MEM-404` give `E\d+` and `MEM-\d+`)
+    ///
+    /// Codes without both a prefix and trailing digits ("NOT_FOUND", "1001")
+    /// cannot be told from other strings and are not looked for.
+    fn code_pattern(&self) -> Option<Regex> {
+        let mut prefixes: Vec<&str> = self
+            .codes
+            .iter()
+            .filter(|c| c.code.ends_with(|ch: char| ch.is_ascii_digit()))
+            .map(|c| c.code.trim_end_matches(|ch: char| ch.is_ascii_digit()))
+            .filter(|prefix| !prefix.is_empty())
+            .collect();
+        prefixes.sort_unstable();
+        prefixes.dedup();
+        if prefixes.is_empty() {
+            return None;
+        }
+        let alternatives: Vec<String> = prefixes.iter().map(|p| regex::escape(p)).collect();
+        Regex::new(&format!(r#"["']((?:{})\d+)["']"#, alternatives.join("|"))).ok()
+    }
+
+    /// `(line, code)` of the first use of every code-like literal not in the catalog
+    pub fn unknown_codes(&self, code: &str) -> Vec<(usize, String)> {
+        let Some(pattern) = self.code_pattern() else {
+            return Vec::new();
+        };
+        let mut unknown: Vec<(usize, String)> = Vec::new();
+        for caps in pattern.captures_iter(code) {
+            let found = &caps[1];
+            if self.get(found).is_some() || unknown.iter().any(|(_, c)| c == found) {
+                continue;
+            }
+            let line = code[..caps.get(0).map_or(0, |m| m.start())].matches('\n').count() + 1;
+            unknown.push((line, found.to_string()));
+        }
+        unknown
+    }
+
+    /// Prompt section listing the codes (empty without codes)
+    pub fn render(&self, product: &str) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut out = String::from("Use only these error codes; never invent one:\n");
+        for entry in &self.codes {
+            out.push_str(&format!("- {}", entry.code));
+            if let Some(status) = entry.http_status {
+                out.push_str(&format!(" (HTTP {})", status));
+            }
+            if !entry.message.is_empty() {
+                out.push_str(&format!(": {}", entry.message));
+            }
+            out.push('\n');
+        }
+        if product.contains("spring") {
+            out.push_str(
+                "Throw BusinessException with the matching code and its message; the exception handler \
+                 answers with the listed HTTP status.\n",
+            );
+        } else {
+            out.push_str(
+                "Transaction callbacks branch on these codes and show the listed message; any other \
+                 failure shows the server message.\n",
+            );
+        }
+        out
+    }
+}
+
+/// Fields of a CSV line; double quotes enclose commas and `""` is a quote
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "code,message,http_status\n\
+                       E1001,\"회원을 찾을 수 없습니다, 다시 조회하세요\",404\n\
+                       E2001,중복된 아이디입니다,409\n\
+                       MEM-500,서버 오류,\n";
+
+    #[test]
+    fn test_parse_csv_and_json() {
+        let catalog = ErrorCodeCatalog::parse(CSV, "codes.CSV").unwrap();
+        assert_eq!(catalog.codes.len(), 3);
+        assert_eq!(catalog.get("E1001").unwrap().message, "회원을 찾을 수 없습니다, 다시 조회하세요");
+        assert_eq!(catalog.get("MEM-500").unwrap().http_status, None);
+        assert_eq!(catalog.code_for_status(409), Some("E2001"));
+
+        let json = r#"{"codes": [{"code": "E1001", "message": "없음", "http_status": 404}]}"#;
+        assert_eq!(ErrorCodeCatalog::parse(json, "codes.json").unwrap().codes.len(), 1);
+        assert!(ErrorCodeCatalog::from_json(r#"[{"code": "E1"}, {"code": "E1"}]"#).is_err());
+        assert!(ErrorCodeCatalog::parse(CSV, "codes.xlsx").is_err());
+    }
+
+    #[test]
+    fn test_unknown_codes() {
+        let catalog = ErrorCodeCatalog::from_csv(CSV).unwrap();
+        let js = "if (errCode == \"E1001\") {\n    gfn_alert(errMsg);\n} else if (errCode == 'E1002') {\n}\nvar a = \"E1002\"; var b = \"MEM-404\"; var c = \"EDIT\";";
+
+        assert_eq!(
+            catalog.unknown_codes(js),
+            vec![(3, "E1002".to_string()), (5, "MEM-404".to_string())]
+        );
+
+        let prompt = catalog.render("spring-backend");
+        assert!(prompt.contains("- E2001 (HTTP 409): 중복된 아이디입니다\n"));
+        assert!(prompt.contains("Throw BusinessException"));
+        assert!(ErrorCodeCatalog::default().render("xframe5-ui").is_empty());
+    }
+}
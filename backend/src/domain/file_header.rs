@@ -0,0 +1,265 @@
+//! Generated File Headers
+//!
+//! Per-project header comments stamped on packaged files: copyright, project
+//! code, generator version and the 자동생성 notice. Templates are plain text;
+//! the comment syntax follows the file type, and XML headers go after the
+//! XML declaration.
+//!
+//! Placeholders: `{project}`, `{file}`, `{generator}`, `{date}`, `{year}`.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Line added to every header when manual edits are forbidden
+pub const DO_NOT_EDIT_MARKER: &str = "DO NOT EDIT: 자동 생성 파일입니다. 직접 수정하지 마십시오.";
+
+const PLACEHOLDERS: [&str; 5] = ["project", "file", "generator", "date", "year"];
+
+/// Header templates of a project, per artifact type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileHeaders {
+    /// Screen XML and MyBatis mapper XML
+    #[serde(default)]
+    pub xml: Option<String>,
+
+    #[serde(default)]
+    pub javascript: Option<String>,
+
+    #[serde(default)]
+    pub java: Option<String>,
+
+    /// End every header with `DO_NOT_EDIT_MARKER`
+    #[serde(default)]
+    pub do_not_edit: bool,
+}
+
+/// Values for the template placeholders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderVars {
+    pub project: String,
+    pub generator: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+}
+
+impl HeaderVars {
+    /// Variables for a project, dated today
+    pub fn new(project: impl Into<String>) -> Self {
+        Self {
+            project: project.into(),
+            generator: format!("xFrame5 Code Generator {}", env!("CARGO_PKG_VERSION")),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Comment syntax of an artifact type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommentStyle {
+    Xml,
+    Block,
+}
+
+impl FileHeaders {
+    /// Reject templates that can't be rendered
+    pub fn validate(&self) -> Result<(), String> {
+        let placeholder = Regex::new(r"\{(\w+)\}").unwrap();
+        for (kind, template) in self.templates() {
+            if let Some(cap) = placeholder
+                .captures_iter(template)
+                .find(|cap| !PLACEHOLDERS.contains(&&cap[1]))
+            {
+                return Err(format!("Unknown placeholder {} in the {} header", &cap[0], kind));
+            }
+            if kind == "xml" && template.contains("--") {
+                return Err("The xml header can't contain \"--\"".to_string());
+            }
+            if template.contains("*/") {
+                return Err(format!("The {} header can't contain \"*/\"", kind));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any artifact type has a header
+    pub fn is_empty(&self) -> bool {
+        self.templates().next().is_none()
+    }
+
+    /// Header comment for a file, if its type has a template
+    pub fn render(&self, file_name: &str, vars: &HeaderVars) -> Option<String> {
+        let (template, style) = self.template_for(file_name)?;
+        let year = vars.date.get(..4).unwrap_or_default();
+        let text = template
+            .replace("{project}", &vars.project)
+            .replace("{file}", base_name(file_name))
+            .replace("{generator}", &vars.generator)
+            .replace("{date}", &vars.date)
+            .replace("{year}", year);
+        Some(self.comment(&text, style))
+    }
+
+    /// Content with the header in front (after an XML declaration); files
+    /// that already carry it are returned unchanged
+    pub fn stamp(&self, file_name: &str, content: &str, vars: &HeaderVars) -> String {
+        let Some(header) = self.render(file_name, vars) else {
+            return content.to_string();
+        };
+        if self.has_header(file_name, content) {
+            return content.to_string();
+        }
+
+        let (declaration, body) = split_declaration(content);
+        if declaration.is_empty() {
+            format!("{}\n{}", header, body)
+        } else {
+            format!("{}\n{}\n{}", declaration, header, body.trim_start_matches(['\r', '\n']))
+        }
+    }
+
+    /// Whether a file starts with its type's header (any placeholder value);
+    /// files without a template always pass
+    pub fn has_header(&self, file_name: &str, content: &str) -> bool {
+        let Some((template, style)) = self.template_for(file_name) else {
+            return true;
+        };
+        let mut pattern = regex::escape(&self.comment(template, style));
+        for name in PLACEHOLDERS {
+            pattern = pattern.replace(&format!(r"\{{{}\}}", name), r"[^\n]*");
+        }
+        let normalized = content.replace("\r\n", "\n");
+        let (_, body) = split_declaration(&normalized);
+        Regex::new(&format!(r"^\s*{}", pattern))
+            .map(|re| re.is_match(body))
+            .unwrap_or(false)
+    }
+
+    fn templates(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [("xml", &self.xml), ("javascript", &self.javascript), ("java", &self.java)]
+            .into_iter()
+            .filter_map(|(kind, template)| {
+                template
+                    .as_deref()
+                    .filter(|t| !t.trim().is_empty())
+                    .map(|t| (kind, t))
+            })
+    }
+
+    fn template_for(&self, file_name: &str) -> Option<(&str, CommentStyle)> {
+        let extension = file_name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase())?;
+        let (template, style) = match extension.as_str() {
+            "xml" => (&self.xml, CommentStyle::Xml),
+            "js" => (&self.javascript, CommentStyle::Block),
+            "java" => (&self.java, CommentStyle::Block),
+            _ => return None,
+        };
+        template
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| (t, style))
+    }
+
+    fn comment(&self, text: &str, style: CommentStyle) -> String {
+        let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        if self.do_not_edit {
+            lines.push(DO_NOT_EDIT_MARKER);
+        }
+        match style {
+            CommentStyle::Xml => {
+                let body: Vec<String> = lines.iter().map(|l| format!("  {}", l).trim_end().to_string()).collect();
+                format!("<!--\n{}\n-->", body.join("\n"))
+            }
+            CommentStyle::Block => {
+                let body: Vec<String> = lines.iter().map(|l| format!(" * {}", l).trim_end().to_string()).collect();
+                format!("/*\n{}\n */", body.join("\n"))
+            }
+        }
+    }
+}
+
+/// File name without its directories
+fn base_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// `(<?xml ...?>, rest)`, or `("", content)` without a declaration
+fn split_declaration(content: &str) -> (&str, &str) {
+    let trimmed = content.trim_start_matches('\u{feff}');
+    if trimmed.starts_with("<?xml") {
+        if let Some(end) = trimmed.find("?>") {
+            return (&trimmed[..end + 2], &trimmed[end + 2..]);
+        }
+    }
+    ("", content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> FileHeaders {
+        FileHeaders {
+            xml: Some("Copyright (c) {year} ACME\nFile: {file}".to_string()),
+            javascript: None,
+            java: Some("Project {project} - {generator}".to_string()),
+            do_not_edit: true,
+        }
+    }
+
+    fn vars() -> HeaderVars {
+        HeaderVars {
+            project: "CRM01".to_string(),
+            generator: "gen 1.0".to_string(),
+            date: "2026-10-17".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_stamp_after_xml_declaration() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<screen/>";
+        let stamped = headers().stamp("ui/member_list.xml", xml, &vars());
+        assert_eq!(
+            stamped,
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!--\n  Copyright (c) 2026 ACME\n  File: member_list.xml\n  {}\n-->\n<screen/>",
+                DO_NOT_EDIT_MARKER
+            )
+        );
+        // Stamping twice keeps one header
+        assert_eq!(headers().stamp("ui/member_list.xml", &stamped, &vars()), stamped);
+    }
+
+    #[test]
+    fn test_has_header_matches_any_placeholder_value() {
+        let java = headers().stamp("MemberController.java", "public class MemberController {}", &vars());
+        assert!(java.starts_with("/*\n * Project CRM01 - gen 1.0\n"));
+
+        let other = HeaderVars {
+            project: "HR".to_string(),
+            ..vars()
+        };
+        let other_java = headers().stamp("A.java", "class A {}", &other).replace('\n', "\r\n");
+        assert!(headers().has_header("A.java", &other_java));
+        assert!(!headers().has_header("A.java", "class A {}"));
+
+        // No template for JavaScript: nothing to stamp or check
+        assert_eq!(headers().stamp("a.js", "var a;", &vars()), "var a;");
+        assert!(headers().has_header("a.js", "var a;"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(headers().validate().is_ok());
+        let unknown = FileHeaders {
+            java: Some("{author}".to_string()),
+            ..Default::default()
+        };
+        assert!(unknown.validate().unwrap_err().contains("{author}"));
+        let dashes = FileHeaders {
+            xml: Some("-- generated --".to_string()),
+            ..Default::default()
+        };
+        assert!(dashes.validate().is_err());
+    }
+}
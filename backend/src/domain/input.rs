@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::{AccessibilityReport, DbDialect, ReportTool, UiIntent};
+
 /// Input types for the generation API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -10,6 +12,14 @@ pub enum GenerateInput {
     QuerySample(QuerySampleInput),
     /// Natural language description
     NaturalLanguage(NaturalLanguageInput),
+    /// Aggregate queries for a dashboard (KPI cards and charts)
+    Dashboard(DashboardInput),
+    /// A complete UI intent (skips normalization)
+    Intent(UiIntent),
+    /// Reference to an intent in the saved intent library
+    SavedIntent(SavedIntentInput),
+    /// YAML screen spec mapped directly to an intent
+    Dsl(DslInput),
 }
 
 /// Database schema input
@@ -32,8 +42,19 @@ pub struct SchemaInput {
     /// Foreign key relationships
     #[serde(default)]
     pub foreign_keys: Vec<ForeignKey>,
+
+    /// Estimated row count (large tables require paged screens)
+    #[serde(default)]
+    pub row_count: Option<u64>,
+
+    /// Source database; detected from the column types when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<DbDialect>,
 }
 
+/// Row count above which a table is treated as large
+pub const LARGE_TABLE_ROW_THRESHOLD: u64 = 100_000;
+
 impl SchemaInput {
     pub fn new(table: impl Into<String>) -> Self {
         Self {
@@ -42,6 +63,8 @@ impl SchemaInput {
             columns: Vec::new(),
             primary_keys: Vec::new(),
             foreign_keys: Vec::new(),
+            row_count: None,
+            dialect: None,
         }
     }
 
@@ -59,6 +82,27 @@ impl SchemaInput {
         self.primary_keys.push(column.into());
         self
     }
+
+    pub fn with_row_count(mut self, rows: u64) -> Self {
+        self.row_count = Some(rows);
+        self
+    }
+
+    pub fn with_dialect(mut self, dialect: DbDialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
+    /// Declared dialect, or the one implied by Oracle-only column types
+    pub fn effective_dialect(&self) -> Option<DbDialect> {
+        self.dialect
+            .or_else(|| DbDialect::detect(self.columns.iter().map(|c| c.column_type.as_str())))
+    }
+
+    /// Whether the row count hint marks this table as large
+    pub fn is_large(&self) -> bool {
+        self.row_count.is_some_and(|rows| rows >= LARGE_TABLE_ROW_THRESHOLD)
+    }
 }
 
 /// Schema column definition
@@ -171,6 +215,40 @@ pub struct QueryColumn {
     pub label: Option<String>,
 }
 
+/// Dashboard input: one aggregate query per KPI group or chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardInput {
+    /// Screen name; `<first table>_dashboard` when omitted
+    #[serde(default)]
+    pub screen_name: Option<String>,
+
+    /// Dashboard description/purpose
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Queries without GROUP BY become KPI cards (one per aggregate column);
+    /// grouped queries become charts
+    pub queries: Vec<QuerySampleInput>,
+
+    /// Auto-refresh interval in seconds
+    #[serde(default)]
+    pub refresh_seconds: Option<u32>,
+}
+
+/// Saved intent reference input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedIntentInput {
+    /// Name of the saved intent
+    pub name: String,
+}
+
+/// Screen DSL input (see `ScreenDsl` for the YAML schema)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DslInput {
+    /// YAML spec: screen, type, datasets, grids, actions
+    pub yaml: String,
+}
+
 /// Natural language description input
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NaturalLanguageInput {
@@ -230,6 +308,52 @@ pub struct GenerateOptions {
 
     /// Company ID for custom rules
     pub company_id: Option<String>,
+
+    /// Race the active LLM against `LLM_RACE_PARTNER`; the first output passing
+    /// strict validation wins (screen generation only)
+    #[serde(default)]
+    pub race: bool,
+
+    /// Parse generated Java for syntax, structure and import checks (slower)
+    #[serde(default)]
+    pub deep_validation: bool,
+
+    /// Naming profile for artifact file names (default: `{screen_name}.xml`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub naming_profile: Option<String>,
+
+    /// Pipeline profile with per-pass overrides (default: the product's default profile)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline_profile: Option<String>,
+
+    /// Edit rows in the grids and save only the changed rows (see `GridIntent::editable`)
+    #[serde(default)]
+    pub editable_grid: bool,
+
+    /// Add a print button and a print-layout stub
+    #[serde(default)]
+    pub print: bool,
+
+    /// Report tool for `print` (default: the company rule set's, else xFrame5 print)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_tool: Option<ReportTool>,
+
+    /// Save the search panel on search and restore it when the screen opens again
+    #[serde(default)]
+    pub keep_search_conditions: bool,
+
+    /// Keep the model's XML layout instead of the canonical formatting
+    #[serde(default)]
+    pub preserve_xml_format: bool,
+
+    /// Rows of sample INSERTs to package with schema-based artifacts (at most 1000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_data_rows: Option<u32>,
+
+    /// Locale of date/number formats (e.g. "ko-KR"; default: the company rule
+    /// set's `formats`, else no formats)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 fn default_language() -> String {
@@ -248,6 +372,23 @@ pub struct RequestContext {
     /// Output formats requested
     #[serde(default = "default_outputs")]
     pub output: Vec<String>,
+
+    /// Requirements ticket the generation implements (e.g. "ERP-123", "#4521")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_key: Option<String>,
+}
+
+impl RequestContext {
+    /// Reject an issue key no tracker would accept
+    pub fn validate(&self) -> Result<(), String> {
+        match self.issue_key.as_deref() {
+            Some(key) if !super::is_valid_issue_key(key) => Err(format!(
+                "Invalid issue_key '{}' (expected a Jira key like ERP-123 or a Redmine id like #4521)",
+                key
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 fn default_outputs() -> Vec<String> {
@@ -270,6 +411,10 @@ pub struct GenerateResponse {
     /// Error message (if status is error)
     pub error: Option<String>,
 
+    /// Machine-readable cause of the error (e.g. `provider_refusal`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+
     /// Response metadata
     pub meta: ResponseMeta,
 }
@@ -283,6 +428,16 @@ pub enum GenerateStatus {
     Error,
 }
 
+impl GenerateStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::PartialSuccess => "partial_success",
+            Self::Error => "error",
+        }
+    }
+}
+
 /// Generated artifacts
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneratedArtifacts {
@@ -299,6 +454,44 @@ pub struct GeneratedArtifacts {
     /// Suggested JavaScript filename (e.g., "task_list.js")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub js_filename: Option<String>,
+
+    /// Further screens generated in the same response (e.g. the popup of a list screen)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_screens: Vec<ScreenArtifact>,
+
+    /// Print-layout stub for the report designer (`print` option)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub print_layout: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub print_layout_filename: Option<String>,
+
+    /// Accessibility compliance report of the main screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessibility_report: Option<AccessibilityReport>,
+
+    /// Sample INSERT script for the source table (`test_data_rows` option)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_data: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_data_filename: Option<String>,
+}
+
+/// One additional screen of a multi-screen response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenArtifact {
+    /// `id` attribute of the `<screen>` element, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_id: Option<String>,
+
+    pub xml: String,
+
+    pub javascript: String,
+
+    pub xml_filename: String,
+
+    pub js_filename: String,
 }
 
 /// Response metadata (NO LLM details exposed)
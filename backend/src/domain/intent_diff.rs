@@ -0,0 +1,164 @@
+//! Intent Comparison
+//!
+//! Structural diff between two intents (as JSON) and duplicate detection for
+//! the saved intent library. Arrays of objects are matched by `id` or `name`
+//! so a reordered dataset shows up as unchanged rather than as many edits.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::UiIntent;
+
+/// One difference between two intents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentChange {
+    /// Path of the changed value (e.g. `datasets[ds_member].columns[MEMBER_ID].ui_type`)
+    pub path: String,
+
+    /// Value before (absent if added)
+    pub before: Option<Value>,
+
+    /// Value after (absent if removed)
+    pub after: Option<Value>,
+}
+
+/// Differences from `before` to `after`
+pub fn diff_intents(before: &Value, after: &Value) -> Vec<IntentChange> {
+    let mut changes = Vec::new();
+    diff_value("", before, after, &mut changes);
+    changes
+}
+
+/// Same screen structure, ignoring the screen name and notes
+pub fn same_structure(a: &UiIntent, b: &UiIntent) -> bool {
+    let structure = |intent: &UiIntent| {
+        let mut value = serde_json::to_value(intent).unwrap_or(Value::Null);
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("screen_name");
+            obj.remove("notes");
+        }
+        value
+    };
+    structure(a) == structure(b)
+}
+
+fn diff_value(path: &str, before: &Value, after: &Value, changes: &mut Vec<IntentChange>) {
+    match (before, after) {
+        (Value::Object(b), Value::Object(a)) => {
+            for (key, b_val) in b {
+                let child = join(path, key);
+                match a.get(key) {
+                    Some(a_val) => diff_value(&child, b_val, a_val, changes),
+                    None => changes.push(change(child, Some(b_val), None)),
+                }
+            }
+            for (key, a_val) in a.iter().filter(|(k, _)| !b.contains_key(*k)) {
+                changes.push(change(join(path, key), None, Some(a_val)));
+            }
+        }
+        (Value::Array(b), Value::Array(a)) if keyed(b) && keyed(a) => {
+            for b_item in b {
+                let key = item_key(b_item).unwrap_or_default();
+                let child = format!("{}[{}]", path, key);
+                match a.iter().find(|a_item| item_key(a_item) == Some(key)) {
+                    Some(a_item) => diff_value(&child, b_item, a_item, changes),
+                    None => changes.push(change(child, Some(b_item), None)),
+                }
+            }
+            for a_item in a {
+                let key = item_key(a_item).unwrap_or_default();
+                if !b.iter().any(|b_item| item_key(b_item) == Some(key)) {
+                    changes.push(change(format!("{}[{}]", path, key), None, Some(a_item)));
+                }
+            }
+        }
+        (Value::Array(b), Value::Array(a)) => {
+            for i in 0..b.len().max(a.len()) {
+                let child = format!("{}[{}]", path, i);
+                match (b.get(i), a.get(i)) {
+                    (Some(b_item), Some(a_item)) => diff_value(&child, b_item, a_item, changes),
+                    (b_item, a_item) => changes.push(change(child, b_item, a_item)),
+                }
+            }
+        }
+        _ if before != after => changes.push(change(path.to_string(), Some(before), Some(after))),
+        _ => {}
+    }
+}
+
+fn change(path: String, before: Option<&Value>, after: Option<&Value>) -> IntentChange {
+    IntentChange {
+        path,
+        before: before.cloned(),
+        after: after.cloned(),
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// `id` or `name` of an array item
+fn item_key(item: &Value) -> Option<&str> {
+    item.get("id").or_else(|| item.get("name")).and_then(Value::as_str)
+}
+
+/// Every item is an object with a unique key
+fn keyed(items: &[Value]) -> bool {
+    let keys: Vec<&str> = items.iter().filter_map(item_key).collect();
+    !items.is_empty()
+        && keys.len() == items.len()
+        && keys.iter().enumerate().all(|(i, k)| !keys[..i].contains(k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_matches_items_by_key() {
+        let before = json!({
+            "screen_name": "member_list",
+            "datasets": [{"id": "ds_member", "columns": [
+                {"name": "MEMBER_ID", "ui_type": "hidden"},
+                {"name": "MEMBER_NAME", "ui_type": "input"}
+            ]}]
+        });
+        let after = json!({
+            "screen_name": "member_list",
+            "datasets": [{"id": "ds_member", "columns": [
+                {"name": "MEMBER_NAME", "ui_type": "input"},
+                {"name": "MEMBER_ID", "ui_type": "input"},
+                {"name": "EMAIL", "ui_type": "input"}
+            ]}],
+            "notes": "reviewed"
+        });
+
+        let paths: Vec<String> = diff_intents(&before, &after).into_iter().map(|c| c.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "datasets[ds_member].columns[MEMBER_ID].ui_type",
+                "datasets[ds_member].columns[EMAIL]",
+                "notes",
+            ]
+        );
+        assert!(diff_intents(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_same_structure_ignores_name_and_notes() {
+        use crate::domain::ScreenType;
+
+        let a = UiIntent::new("member_list", ScreenType::List);
+        let mut b = UiIntent::new("member_list_copy", ScreenType::List);
+        b.notes = Some("copy".to_string());
+        assert!(same_structure(&a, &b));
+        assert!(!same_structure(&a, &UiIntent::new("member_list", ScreenType::Detail)));
+    }
+}
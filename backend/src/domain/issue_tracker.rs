@@ -0,0 +1,221 @@
+//! Issue Tracker Linking
+//!
+//! A generation request may name the requirements ticket it implements
+//! (`context.issue_key`, e.g. "ERP-123" in Jira or "#4521" in Redmine). The
+//! key is stored on the generation log; projects with an `issue_tracker` in
+//! their output settings also get a comment on the ticket with the status,
+//! validation summary and download link of every generation.
+//!
+//! Tokens are never stored; `token_env` names the environment variable of the
+//! server that holds one.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted issue key
+const MAX_ISSUE_KEY_LEN: usize = 64;
+
+/// Tracker product
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueTrackerKind {
+    Jira,
+    Redmine,
+}
+
+/// `issue_tracker` of a project's output settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueTracker {
+    pub kind: IssueTrackerKind,
+
+    /// Tracker root (e.g. "https://acme.atlassian.net", "https://redmine.acme.co.kr")
+    pub base_url: String,
+
+    /// Environment variable holding the API token (Jira) or API key (Redmine)
+    pub token_env: String,
+
+    /// Environment variable holding the Jira Cloud account email; without it
+    /// the token is sent as a bearer token (Jira Server/Data Center PAT)
+    #[serde(default)]
+    pub user_env: Option<String>,
+
+    /// Public URL of this server, for the download link in comments
+    #[serde(default)]
+    pub link_base_url: Option<String>,
+}
+
+/// Is `key` shaped like an issue key of any supported tracker?
+pub fn is_valid_issue_key(key: &str) -> bool {
+    key.len() <= MAX_ISSUE_KEY_LEN
+        && (IssueTrackerKind::Jira.key_pattern().is_match(key) || IssueTrackerKind::Redmine.key_pattern().is_match(key))
+}
+
+impl IssueTrackerKind {
+    fn key_pattern(&self) -> Regex {
+        match self {
+            Self::Jira => Regex::new(r"^[A-Z][A-Z0-9_]+-[0-9]+$").unwrap(),
+            Self::Redmine => Regex::new(r"^#?[0-9]+$").unwrap(),
+        }
+    }
+}
+
+impl IssueTracker {
+    /// Reject settings that can't be posted with
+    pub fn validate(&self) -> Result<(), String> {
+        let base = self.base_url.trim();
+        if !(base.starts_with("https://") || base.starts_with("http://")) {
+            return Err("issue_tracker.base_url must be an http(s) URL".to_string());
+        }
+        let env_name = Regex::new(r"^[A-Z_][A-Z0-9_]*$").unwrap();
+        for name in std::iter::once(self.token_env.as_str()).chain(self.user_env.as_deref()) {
+            if !env_name.is_match(name) {
+                return Err(format!("Invalid environment variable name '{}'", name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Issue id in API paths ("#4521" → "4521"), or `None` when `key` isn't
+    /// a key of this tracker
+    pub fn issue_id<'a>(&self, key: &'a str) -> Option<&'a str> {
+        self.kind
+            .key_pattern()
+            .is_match(key)
+            .then(|| key.trim_start_matches('#'))
+    }
+
+    /// Endpoint and JSON body adding `comment` to an issue
+    pub fn comment_request(&self, issue_id: &str, comment: &str) -> (String, serde_json::Value) {
+        let base = self.base_url.trim().trim_end_matches('/');
+        match self.kind {
+            IssueTrackerKind::Jira => (
+                format!("{}/rest/api/2/issue/{}/comment", base, issue_id),
+                serde_json::json!({ "body": comment }),
+            ),
+            IssueTrackerKind::Redmine => (
+                format!("{}/issues/{}.json", base, issue_id),
+                serde_json::json!({ "issue": { "notes": comment } }),
+            ),
+        }
+    }
+
+    /// Download link of a generation log, when the public URL is known
+    pub fn download_link(&self, log_id: i32) -> Option<String> {
+        self.link_base_url
+            .as_deref()
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+            .map(|url| format!("{}/api/my/generations/{}/download", url, log_id))
+    }
+}
+
+/// What a tracker comment reports about a generation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationSummary {
+    pub log_id: i32,
+    pub product: String,
+    /// Screen or entity name
+    pub screen: Option<String>,
+    pub status: String,
+    pub quality_score: Option<i32>,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Warnings listed in a comment before "... and N more"
+const MAX_LISTED_WARNINGS: usize = 5;
+
+impl GenerationSummary {
+    /// Plain-text comment (renders in both Jira wiki markup and Redmine textile)
+    pub fn comment(&self, download_link: Option<&str>) -> String {
+        let mut lines = vec![format!(
+            "Code generation #{}: {} ({}) - {}",
+            self.log_id,
+            self.screen.as_deref().unwrap_or("artifacts"),
+            self.product,
+            self.status
+        )];
+        if let Some(score) = self.quality_score {
+            lines.push(format!("Quality score: {}/100", score));
+        }
+        if let Some(error) = &self.error {
+            lines.push(format!("Error: {}", error));
+        }
+        if self.warnings.is_empty() {
+            lines.push("Validation: no warnings".to_string());
+        } else {
+            lines.push(format!("Validation: {} warning(s)", self.warnings.len()));
+            lines.extend(self.warnings.iter().take(MAX_LISTED_WARNINGS).map(|w| format!("- {}", w)));
+            if self.warnings.len() > MAX_LISTED_WARNINGS {
+                lines.push(format!("- ... and {} more", self.warnings.len() - MAX_LISTED_WARNINGS));
+            }
+        }
+        if let Some(link) = download_link {
+            lines.push(format!("Artifacts: {}", link));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(kind: IssueTrackerKind) -> IssueTracker {
+        IssueTracker {
+            kind,
+            base_url: "https://tracker.acme.co.kr/".to_string(),
+            token_env: "TRACKER_TOKEN".to_string(),
+            user_env: None,
+            link_base_url: Some("https://codegen.acme.co.kr".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_issue_keys_per_tracker() {
+        assert!(is_valid_issue_key("ERP-123"));
+        assert!(is_valid_issue_key("#4521"));
+        assert!(!is_valid_issue_key("ERP-123; DROP"));
+
+        let jira = tracker(IssueTrackerKind::Jira);
+        assert_eq!(jira.issue_id("ERP-123"), Some("ERP-123"));
+        assert_eq!(jira.issue_id("#4521"), None);
+        assert_eq!(
+            jira.comment_request("ERP-123", "done").0,
+            "https://tracker.acme.co.kr/rest/api/2/issue/ERP-123/comment"
+        );
+
+        let redmine = tracker(IssueTrackerKind::Redmine);
+        assert_eq!(redmine.issue_id("#4521"), Some("4521"));
+        assert_eq!(
+            redmine.comment_request("4521", "done"),
+            (
+                "https://tracker.acme.co.kr/issues/4521.json".to_string(),
+                serde_json::json!({"issue": {"notes": "done"}})
+            )
+        );
+    }
+
+    #[test]
+    fn test_comment_summarizes_validation() {
+        let summary = GenerationSummary {
+            log_id: 42,
+            product: "xframe5-ui".to_string(),
+            screen: Some("member_list".to_string()),
+            status: "partial_success".to_string(),
+            quality_score: Some(81),
+            warnings: (1..=7).map(|i| format!("warning {}", i)).collect(),
+            error: None,
+        };
+        let link = tracker(IssueTrackerKind::Jira).download_link(42);
+        assert_eq!(
+            summary.comment(link.as_deref()),
+            "Code generation #42: member_list (xframe5-ui) - partial_success\n\
+             Quality score: 81/100\n\
+             Validation: 7 warning(s)\n\
+             - warning 1\n- warning 2\n- warning 3\n- warning 4\n- warning 5\n\
+             - ... and 2 more\n\
+             Artifacts: https://codegen.acme.co.kr/api/my/generations/42/download"
+        );
+    }
+}
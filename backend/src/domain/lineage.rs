@@ -0,0 +1,332 @@
+//! Column Lineage
+//!
+//! Where each source column of an intent ended up in the generated files:
+//! dataset declarations, grid columns, control bindings and script
+//! references of a screen; DTO fields and mapper SQL of a Spring module.
+//! Built from the intent and the final files once a generation finishes and
+//! stored on its log, so reviewers can ask where a field came from and
+//! regeneration can point at the places a schema change touches.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{to_camel_case, SpringIntent, UiIntent};
+
+/// Longest snippet kept per target
+const MAX_SNIPPET_CHARS: usize = 120;
+
+/// How a generated line uses the column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineageKind {
+    /// Column of an `<xlinkdataset>` declaration
+    DatasetColumn,
+    /// Grid `<data name="...">`
+    GridColumn,
+    /// Control bound with `link_data="ds:COLUMN"`
+    Binding,
+    /// String literal in the screen script
+    Script,
+    /// DTO field declaration
+    JavaField,
+    /// Column in mapper SQL
+    SqlColumn,
+    /// `#{field}` parameter in mapper SQL
+    SqlParameter,
+}
+
+/// One generated line using a column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineageTarget {
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+    pub kind: LineageKind,
+    /// The line, trimmed
+    pub snippet: String,
+}
+
+/// A source column and where it was generated to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnLineage {
+    /// Source table (absent for datasets without one)
+    pub table: Option<String>,
+
+    /// Dataset id (screens)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<String>,
+
+    pub column: String,
+
+    /// Java field name (Spring)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+
+    /// Path of the column in the logged intent
+    pub intent_path: String,
+
+    pub targets: Vec<LineageTarget>,
+}
+
+/// Lineage of all columns of a generation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lineage {
+    pub columns: Vec<ColumnLineage>,
+}
+
+impl Lineage {
+    /// Lineage of a screen generation; `files` are (name, content) pairs
+    pub fn for_screen(intent: &UiIntent, files: &[(&str, &str)]) -> Self {
+        let mut columns: Vec<ColumnLineage> = intent
+            .datasets
+            .iter()
+            .flat_map(|dataset| {
+                dataset.columns.iter().map(move |column| ColumnLineage {
+                    table: dataset.table_name.clone(),
+                    dataset: Some(dataset.id.clone()),
+                    column: column.name.clone(),
+                    field: None,
+                    intent_path: format!("datasets[{}].columns[{}]", dataset.id, column.name),
+                    targets: Vec::new(),
+                })
+            })
+            .collect();
+
+        for (name, content) in files {
+            if name.ends_with(".xml") {
+                scan_screen_xml(&mut columns, name, content);
+            } else if name.ends_with(".js") {
+                scan_script(&mut columns, name, content);
+            }
+        }
+        Self { columns }
+    }
+
+    /// Lineage of a Spring generation; `files` are (name, content) pairs
+    pub fn for_spring(intent: &SpringIntent, files: &[(&str, &str)]) -> Self {
+        let mut columns: Vec<ColumnLineage> = intent
+            .columns
+            .iter()
+            .map(|column| ColumnLineage {
+                table: Some(intent.table_name.clone()),
+                dataset: None,
+                column: column.name.clone(),
+                field: Some(to_camel_case(&column.name)),
+                intent_path: format!("columns[{}]", column.name),
+                targets: Vec::new(),
+            })
+            .collect();
+
+        for (name, content) in files {
+            if name.ends_with(".java") {
+                scan_java(&mut columns, name, content);
+            } else if name.ends_with(".xml") {
+                scan_mapper_xml(&mut columns, name, content);
+            }
+        }
+        Self { columns }
+    }
+
+    /// Lineage of a column (every dataset using it), case-insensitive
+    pub fn column(&self, name: &str) -> Vec<&ColumnLineage> {
+        self.columns.iter().filter(|c| c.column.eq_ignore_ascii_case(name.trim())).collect()
+    }
+
+    /// Columns a generated line comes from
+    pub fn at(&self, file: &str, line: usize) -> Vec<&ColumnLineage> {
+        self.columns
+            .iter()
+            .filter(|c| c.targets.iter().any(|t| t.line == line && same_file(&t.file, file)))
+            .collect()
+    }
+
+    /// Generated lines using any of `columns`, by file and line
+    pub fn targets_of<S: AsRef<str>>(&self, columns: &[S]) -> Vec<LineageTarget> {
+        let mut targets: Vec<LineageTarget> = self
+            .columns
+            .iter()
+            .filter(|c| columns.iter().any(|name| name.as_ref().eq_ignore_ascii_case(&c.column)))
+            .flat_map(|c| c.targets.iter().cloned())
+            .collect();
+        targets.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        targets.dedup();
+        targets
+    }
+}
+
+/// Same file, given with or without its directory
+fn same_file(stored: &str, asked: &str) -> bool {
+    stored == asked || stored.rsplit('/').next() == asked.rsplit('/').next()
+}
+
+fn push(column: &mut ColumnLineage, file: &str, line: usize, kind: LineageKind, text: &str) {
+    let snippet: String = text.trim().chars().take(MAX_SNIPPET_CHARS).collect();
+    column.targets.push(LineageTarget {
+        file: file.to_string(),
+        line,
+        kind,
+        snippet,
+    });
+}
+
+fn attr<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    let re = Regex::new(&format!(r#"\b{}\s*=\s*"([^"]*)""#, regex::escape(name))).unwrap();
+    re.captures(text).map(|cap| cap.get(1).unwrap().as_str())
+}
+
+fn scan_screen_xml(columns: &mut [ColumnLineage], file: &str, content: &str) {
+    let binding = Regex::new(r#"link_data\s*=\s*"(\w+):(\w+)""#).unwrap();
+    let grid_data = Regex::new(r#"<data\b[^>]*\bname\s*=\s*"(\w+)""#).unwrap();
+    // Dataset whose `columns` attribute is being read (may span lines)
+    let mut dataset: Option<String> = None;
+
+    for (index, text) in content.lines().enumerate() {
+        let line = index + 1;
+        if text.contains("<xlinkdataset") {
+            dataset = attr(text, "id").map(str::to_string);
+        }
+        if let Some(id) = &dataset {
+            for column in columns.iter_mut().filter(|c| c.dataset.as_deref() == Some(id.as_str())) {
+                let declared = Regex::new(&format!(r#"(?i)(^|[\s;"]){}:"#, regex::escape(&column.column))).unwrap();
+                if declared.is_match(text) {
+                    push(column, file, line, LineageKind::DatasetColumn, text);
+                }
+            }
+            if text.contains('>') {
+                dataset = None;
+            }
+            continue;
+        }
+
+        if let Some(cap) = binding.captures(text) {
+            for column in columns.iter_mut().filter(|c| {
+                c.dataset.as_deref() == Some(&cap[1]) && c.column.eq_ignore_ascii_case(&cap[2])
+            }) {
+                push(column, file, line, LineageKind::Binding, text);
+            }
+        } else if let Some(cap) = grid_data.captures(text) {
+            for column in columns.iter_mut().filter(|c| c.column.eq_ignore_ascii_case(&cap[1])) {
+                push(column, file, line, LineageKind::GridColumn, text);
+            }
+        }
+    }
+}
+
+fn scan_script(columns: &mut [ColumnLineage], file: &str, content: &str) {
+    let literal = Regex::new(r#"["'](\w+)["']"#).unwrap();
+    for (index, text) in content.lines().enumerate() {
+        let mut used: Vec<&str> = literal.captures_iter(text).filter_map(|cap| cap.get(1)).map(|m| m.as_str()).collect();
+        used.dedup();
+        for column in columns.iter_mut().filter(|c| used.iter().any(|u| u.eq_ignore_ascii_case(&c.column))) {
+            push(column, file, index + 1, LineageKind::Script, text);
+        }
+    }
+}
+
+fn scan_java(columns: &mut [ColumnLineage], file: &str, content: &str) {
+    for column in columns.iter_mut() {
+        let Some(field) = column.field.clone() else {
+            continue;
+        };
+        let declaration = Regex::new(&format!(r"\bprivate\s+[\w<>,.\[\]\s]+?\s+{}\s*[;=]", regex::escape(&field))).unwrap();
+        for (index, text) in content.lines().enumerate() {
+            if declaration.is_match(text) {
+                push(column, file, index + 1, LineageKind::JavaField, text);
+            }
+        }
+    }
+}
+
+fn scan_mapper_xml(columns: &mut [ColumnLineage], file: &str, content: &str) {
+    if !content.contains("<mapper") {
+        return;
+    }
+    let parameters = Regex::new(r"#\{[^}]*\}").unwrap();
+    for column in columns.iter_mut() {
+        let field = column.field.clone().unwrap_or_else(|| to_camel_case(&column.column));
+        let parameter = Regex::new(&format!(r"#\{{\s*{}\s*[,}}]", regex::escape(&field))).unwrap();
+        let sql_column = Regex::new(&format!(r"(?i)(^|[^\w#{{]){}\b", regex::escape(&column.column))).unwrap();
+        for (index, text) in content.lines().enumerate() {
+            let without_parameters = parameters.replace_all(text, "");
+            if sql_column.is_match(&without_parameters) {
+                push(column, file, index + 1, LineageKind::SqlColumn, text);
+            } else if parameter.is_match(text) {
+                push(column, file, index + 1, LineageKind::SqlParameter, text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnIntent, DatasetIntent, ScreenType};
+
+    fn member_screen() -> UiIntent {
+        UiIntent::new("member_list", ScreenType::List).with_dataset(
+            DatasetIntent::new("ds_member").with_table("TB_MEMBER").with_columns(vec![
+                ColumnIntent::new("MEMBER_NAME", "Name"),
+                ColumnIntent::new("EMAIL", "Email"),
+            ]),
+        )
+    }
+
+    const XML: &str = r#"<screen id="member_list">
+<xlinkdataset id="ds_member" desc="Members"
+  columns="MEMBER_NAME:&quot;Name&quot;:50:&quot;&quot;:&quot;&quot;;
+           EMAIL:&quot;Email&quot;:100:&quot;&quot;:&quot;&quot;"/>
+<grid name="grid_member" link_data="ds_member">
+  <data name="MEMBER_NAME" width="120"/>
+</grid>
+<normal_field name="field_email" link_data="ds_member:EMAIL"/>
+</screen>"#;
+
+    const JS: &str = r#"var email = ds_member.getcolumn(row, "EMAIL");"#;
+
+    #[test]
+    fn test_screen_lineage() {
+        let lineage = Lineage::for_screen(&member_screen(), &[("member_list.xml", XML), ("member_list.js", JS)]);
+
+        let email = lineage.column("email");
+        assert_eq!(email.len(), 1);
+        let places: Vec<_> = email[0].targets.iter().map(|t| (t.file.as_str(), t.line, t.kind)).collect();
+        assert_eq!(
+            places,
+            vec![
+                ("member_list.xml", 4, LineageKind::DatasetColumn),
+                ("member_list.xml", 8, LineageKind::Binding),
+                ("member_list.js", 1, LineageKind::Script),
+            ]
+        );
+
+        let at_grid = lineage.at("screens/member_list.xml", 6);
+        assert_eq!(at_grid.len(), 1);
+        assert_eq!(at_grid[0].column, "MEMBER_NAME");
+        assert_eq!(at_grid[0].intent_path, "datasets[ds_member].columns[MEMBER_NAME]");
+    }
+
+    #[test]
+    fn test_spring_lineage() {
+        let intent = SpringIntent::new("Member", "TB_MEMBER", "com.company.project")
+            .with_column(ColumnIntent::new("MEMBER_NAME", "Name"));
+        let dto = "public class MemberDTO {\n    private String memberName;\n}";
+        let mapper = "<mapper namespace=\"MemberMapper\">\n  SELECT MEMBER_NAME FROM TB_MEMBER\n  WHERE MEMBER_NAME = #{memberName}\n  VALUES (#{memberName})\n</mapper>";
+
+        let lineage = Lineage::for_spring(&intent, &[("MemberDTO.java", dto), ("MemberMapper.xml", mapper)]);
+        let column = &lineage.columns[0];
+        assert_eq!(column.field.as_deref(), Some("memberName"));
+        let kinds: Vec<_> = column.targets.iter().map(|t| (t.line, t.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (2, LineageKind::JavaField),
+                (2, LineageKind::SqlColumn),
+                (3, LineageKind::SqlColumn),
+                (4, LineageKind::SqlParameter),
+            ]
+        );
+
+        let targets = lineage.targets_of(&["member_name"]);
+        assert_eq!(targets.first().map(|t| t.file.as_str()), Some("MemberDTO.java"));
+    }
+}
@@ -0,0 +1,405 @@
+//! Locale Formats
+//!
+//! Date and number display formats of a screen's columns. The request's
+//! `locale` option (or the company rule set's) picks the defaults: date
+//! patterns, thousand separators and the currency of amount columns; the
+//! rule set's `formats` section overrides individual entries. Formats are
+//! carried on `ColumnIntent::format`, passed to the prompt and enforced on
+//! `datepicker` / `numericex_field` controls and grid columns by the
+//! `FormatValidator` pass.
+//!
+//! Decimal columns show the scale of their schema type (`DECIMAL(15,2)` → two
+//! decimal places), even without a locale, and carry the rule set's rounding
+//! mode so the generated code comments how computed values are rounded.
+
+use serde::{Deserialize, Serialize};
+
+use super::ui_intent::{ColumnIntent, DataType, UiIntent, UiType};
+
+/// Locale used when only the company rule set asks for formats
+pub const DEFAULT_LOCALE: &str = "ko-KR";
+
+/// Name endings of amount columns when the rule set lists none
+const DEFAULT_CURRENCY_COLUMNS: &[&str] = &["_AMT", "_AMOUNT", "_PRICE", "_FEE", "_COST"];
+
+/// Locale defaults: (locale, date, date-time, currency)
+const LOCALES: &[(&str, &str, &str, &str)] = &[
+    ("ko-KR", "yyyy-MM-dd", "yyyy-MM-dd HH:mm:ss", "KRW"),
+    ("en-US", "MM/dd/yyyy", "MM/dd/yyyy HH:mm:ss", "USD"),
+    ("en-GB", "dd/MM/yyyy", "dd/MM/yyyy HH:mm:ss", "GBP"),
+    ("ja-JP", "yyyy/MM/dd", "yyyy/MM/dd HH:mm:ss", "JPY"),
+    ("zh-CN", "yyyy-MM-dd", "yyyy-MM-dd HH:mm:ss", "CNY"),
+    ("de-DE", "dd.MM.yyyy", "dd.MM.yyyy HH:mm:ss", "EUR"),
+];
+
+/// Currencies without minor units
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &["KRW", "JPY", "VND", "IDR"];
+
+/// How computed decimal values are rounded (`formats.rounding` of a company
+/// rule set; the names of Java's `RoundingMode`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RoundingMode {
+    /// Half away from zero (사사오입)
+    #[default]
+    HalfUp,
+    /// Half to the even neighbour (banker's rounding)
+    HalfEven,
+    /// Toward zero (절사)
+    Down,
+    /// Away from zero (절상)
+    Up,
+    Floor,
+    Ceiling,
+}
+
+impl RoundingMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoundingMode::HalfUp => "HALF_UP",
+            RoundingMode::HalfEven => "HALF_EVEN",
+            RoundingMode::Down => "DOWN",
+            RoundingMode::Up => "UP",
+            RoundingMode::Floor => "FLOOR",
+            RoundingMode::Ceiling => "CEILING",
+        }
+    }
+}
+
+/// Display format of one column
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnFormat {
+    /// Date pattern of date and date-time columns (e.g. "yyyy-MM-dd")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+
+    /// Group thousands with commas (numeric columns)
+    #[serde(default)]
+    pub thousand_separator: bool,
+
+    /// Digits after the decimal point (numeric columns)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_places: Option<u32>,
+
+    /// ISO 4217 code of an amount column (e.g. "KRW")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// Input mask of a text column (e.g. "###-##-#####", from a column archetype)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<String>,
+
+    /// Rounding of computed values (decimal columns)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rounding: Option<RoundingMode>,
+}
+
+impl ColumnFormat {
+    pub fn date(format: impl Into<String>) -> Self {
+        Self {
+            date_format: Some(format.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn number(thousand_separator: bool, decimal_places: u32) -> Self {
+        Self {
+            thousand_separator,
+            decimal_places: Some(decimal_places),
+            ..Default::default()
+        }
+    }
+
+    pub fn mask(mask: impl Into<String>) -> Self {
+        Self {
+            mask: Some(mask.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the format describes numbers
+    pub fn is_numeric(&self) -> bool {
+        self.thousand_separator || self.decimal_places.is_some() || self.currency.is_some()
+    }
+
+    /// `pattern` of numeric fields and grid columns (e.g. "#,##0.00")
+    pub fn number_pattern(&self) -> Option<String> {
+        if !self.is_numeric() {
+            return None;
+        }
+        let mut pattern = if self.thousand_separator { "#,##0" } else { "0" }.to_string();
+        let decimals = self.decimal_places.unwrap_or(0) as usize;
+        if decimals > 0 {
+            pattern.push('.');
+            pattern.push_str(&"0".repeat(decimals));
+        }
+        Some(pattern)
+    }
+
+    /// Problems with the format for a column of `data_type`
+    pub fn type_mismatch(&self, data_type: DataType) -> Option<String> {
+        let is_date = matches!(data_type, DataType::Date | DataType::DateTime);
+        let is_number = matches!(data_type, DataType::Integer | DataType::Decimal);
+        if let Some(date) = &self.date_format {
+            if !is_date {
+                return Some(format!("date format '{}' for data type {}", date, data_type.as_str()));
+            }
+            if data_type == DataType::Date && date.contains('H') {
+                return Some(format!("date format '{}' shows a time on a date column", date));
+            }
+        }
+        if self.mask.is_some() && (is_date || is_number) {
+            return Some(format!("input mask for data type {}", data_type.as_str()));
+        }
+        if self.is_numeric() && !is_number {
+            return Some(format!("number format for data type {}", data_type.as_str()));
+        }
+        if data_type == DataType::Integer && self.decimal_places.is_some_and(|d| d > 0) {
+            return Some("decimal places on an integer column".to_string());
+        }
+        None
+    }
+}
+
+/// `formats` section of a company rule set; unset entries come from the locale
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatRules {
+    /// Locale when the request names none (e.g. "ko-KR")
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    #[serde(default)]
+    pub date_format: Option<String>,
+
+    #[serde(default)]
+    pub date_time_format: Option<String>,
+
+    /// Group thousands in numeric columns (default: yes)
+    #[serde(default)]
+    pub thousand_separator: Option<bool>,
+
+    /// Digits after the decimal point of decimal columns (default: 2)
+    #[serde(default)]
+    pub decimal_places: Option<u32>,
+
+    /// Currency of amount columns (default: the locale's)
+    #[serde(default)]
+    pub currency: Option<String>,
+
+    /// Rounding of computed decimal values (default: HALF_UP)
+    #[serde(default)]
+    pub rounding: Option<RoundingMode>,
+
+    /// Name endings of amount columns (default: `_AMT`, `_AMOUNT`, `_PRICE`, `_FEE`, `_COST`)
+    #[serde(default)]
+    pub currency_columns: Vec<String>,
+}
+
+/// Formats resolved from a locale and the company's rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocaleFormats {
+    pub locale: String,
+    pub date_format: String,
+    pub date_time_format: String,
+    pub thousand_separator: bool,
+    pub decimal_places: u32,
+    pub currency: String,
+    pub currency_columns: Vec<String>,
+    pub rounding: RoundingMode,
+}
+
+impl LocaleFormats {
+    /// Supported locale names
+    pub fn locales() -> Vec<&'static str> {
+        LOCALES.iter().map(|(locale, ..)| *locale).collect()
+    }
+
+    /// Formats of `locale` (else the rule set's, else `ko-KR`) with the rule
+    /// set's overrides
+    pub fn resolve(locale: Option<&str>, rules: Option<&FormatRules>) -> Result<Self, String> {
+        let rules = rules.cloned().unwrap_or_default();
+        let requested = locale
+            .or(rules.locale.as_deref())
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .unwrap_or(DEFAULT_LOCALE);
+        let (name, date, date_time, currency) = LOCALES
+            .iter()
+            .find(|(name, ..)| name.eq_ignore_ascii_case(requested) || name.replace('-', "_").eq_ignore_ascii_case(requested))
+            .ok_or_else(|| {
+                format!("Unknown locale '{}'. Supported: {}", requested, Self::locales().join(", "))
+            })?;
+
+        let currency_columns = if rules.currency_columns.is_empty() {
+            DEFAULT_CURRENCY_COLUMNS.iter().map(|s| s.to_string()).collect()
+        } else {
+            rules.currency_columns.iter().map(|s| s.trim().to_uppercase()).collect()
+        };
+        Ok(Self {
+            locale: name.to_string(),
+            date_format: rules.date_format.unwrap_or_else(|| date.to_string()),
+            date_time_format: rules.date_time_format.unwrap_or_else(|| date_time.to_string()),
+            thousand_separator: rules.thousand_separator.unwrap_or(true),
+            decimal_places: rules.decimal_places.unwrap_or(2),
+            currency: rules.currency.map(|c| c.trim().to_uppercase()).unwrap_or_else(|| currency.to_string()),
+            currency_columns,
+            rounding: rules.rounding.unwrap_or_default(),
+        })
+    }
+
+    /// Format of a column by its data type (`None` for text, hidden, code and
+    /// personal data columns)
+    pub fn column_format(&self, column: &ColumnIntent) -> Option<ColumnFormat> {
+        if column.ui_type == UiType::Hidden || column.code_group.is_some() || column.sensitivity.is_some() {
+            return None;
+        }
+        match column.data_type {
+            DataType::Date => Some(ColumnFormat::date(&self.date_format)),
+            DataType::DateTime => Some(ColumnFormat::date(&self.date_time_format)),
+            DataType::Integer | DataType::Decimal => {
+                let is_decimal = column.data_type == DataType::Decimal;
+                let rounding = is_decimal.then_some(self.rounding);
+                let name = column.name.to_uppercase();
+                if self.currency_columns.iter().any(|suffix| name.ends_with(suffix.as_str())) {
+                    let currency_decimals = if ZERO_DECIMAL_CURRENCIES.contains(&self.currency.as_str()) { 0 } else { 2 };
+                    let decimals = if is_decimal { column.scale.unwrap_or(currency_decimals) } else { 0 };
+                    return Some(ColumnFormat {
+                        currency: Some(self.currency.clone()),
+                        rounding,
+                        ..ColumnFormat::number(self.thousand_separator, decimals)
+                    });
+                }
+                let decimals = if is_decimal { column.scale.unwrap_or(self.decimal_places) } else { 0 };
+                Some(ColumnFormat { rounding, ..ColumnFormat::number(self.thousand_separator, decimals) })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl UiIntent {
+    /// Give every column without a format the locale's (explicit formats are
+    /// kept) and record the locale for the label language check
+    pub fn apply_formats(&mut self, formats: &LocaleFormats) {
+        self.locale = Some(formats.locale.clone());
+        for column in self.datasets.iter_mut().flat_map(|ds| ds.columns.iter_mut()) {
+            if column.format.is_none() {
+                column.format = formats.column_format(column);
+            }
+        }
+    }
+
+    /// Give decimal columns with a schema scale and no format a grouped
+    /// number format with that many decimal places (screens without a locale)
+    pub fn apply_scale_formats(&mut self) {
+        for column in self.datasets.iter_mut().flat_map(|ds| ds.columns.iter_mut()) {
+            if column.format.is_some() || column.data_type != DataType::Decimal {
+                continue;
+            }
+            if column.ui_type == UiType::Hidden || column.code_group.is_some() || column.sensitivity.is_some() {
+                continue;
+            }
+            if let Some(scale) = column.scale {
+                column.format = Some(ColumnFormat::number(true, scale));
+            }
+        }
+    }
+
+    /// Columns with a display format, across all datasets
+    pub fn formatted_columns(&self) -> Vec<&ColumnIntent> {
+        self.datasets
+            .iter()
+            .flat_map(|ds| ds.columns.iter())
+            .filter(|c| c.format.is_some())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DatasetIntent, ScreenType};
+
+    fn intent() -> UiIntent {
+        UiIntent::new("order_list", ScreenType::List).with_dataset(DatasetIntent::new("ds_order").with_columns(vec![
+            ColumnIntent::new("ORDER_NO", "주문번호").with_data_type(DataType::Integer).primary_key(),
+            ColumnIntent::new("ORDER_DATE", "주문일").with_data_type(DataType::Date),
+            ColumnIntent::new("QTY", "수량").with_data_type(DataType::Integer),
+            ColumnIntent::new("RATE", "할인율").with_data_type(DataType::Decimal),
+            ColumnIntent::new("TOTAL_AMT", "합계").with_data_type(DataType::Decimal),
+            ColumnIntent::new("MEMO", "메모"),
+        ]))
+    }
+
+    fn format_of<'a>(intent: &'a UiIntent, name: &str) -> Option<&'a ColumnFormat> {
+        intent.datasets[0].columns.iter().find(|c| c.name == name).unwrap().format.as_ref()
+    }
+
+    #[test]
+    fn test_locale_defaults_by_data_type() {
+        let mut intent = intent();
+        intent.apply_formats(&LocaleFormats::resolve(None, None).unwrap());
+
+        assert_eq!(format_of(&intent, "ORDER_NO"), None);
+        assert_eq!(format_of(&intent, "ORDER_DATE").unwrap().date_format.as_deref(), Some("yyyy-MM-dd"));
+        assert_eq!(format_of(&intent, "QTY").unwrap().number_pattern().as_deref(), Some("#,##0"));
+        assert_eq!(format_of(&intent, "RATE").unwrap().number_pattern().as_deref(), Some("#,##0.00"));
+        let total = format_of(&intent, "TOTAL_AMT").unwrap();
+        assert_eq!((total.number_pattern().as_deref(), total.currency.as_deref()), (Some("#,##0"), Some("KRW")));
+        assert_eq!(format_of(&intent, "MEMO"), None);
+    }
+
+    #[test]
+    fn test_company_rules_override_locale() {
+        let rules = FormatRules {
+            locale: Some("ko-KR".to_string()),
+            date_format: Some("yyyy.MM.dd".to_string()),
+            thousand_separator: Some(false),
+            ..Default::default()
+        };
+        let mut intent = intent();
+        intent.apply_formats(&LocaleFormats::resolve(Some("en_us"), Some(&rules)).unwrap());
+
+        assert_eq!(intent.locale.as_deref(), Some("en-US"));
+        assert_eq!(format_of(&intent, "ORDER_DATE").unwrap().date_format.as_deref(), Some("yyyy.MM.dd"));
+        let total = format_of(&intent, "TOTAL_AMT").unwrap();
+        assert_eq!((total.number_pattern().as_deref(), total.currency.as_deref()), (Some("0.00"), Some("USD")));
+
+        let err = LocaleFormats::resolve(Some("xx-XX"), None).unwrap_err();
+        assert!(err.starts_with("Unknown locale 'xx-XX'. Supported: ko-KR, en-US"));
+    }
+
+    #[test]
+    fn test_schema_scale_sets_decimal_places() {
+        let rules = FormatRules { rounding: Some(RoundingMode::Down), ..Default::default() };
+        let mut intent = intent();
+        intent.datasets[0].columns[3].scale = Some(4);
+        intent.datasets[0].columns[4].scale = Some(2);
+        intent.apply_formats(&LocaleFormats::resolve(None, Some(&rules)).unwrap());
+
+        let rate = format_of(&intent, "RATE").unwrap();
+        assert_eq!((rate.number_pattern().as_deref(), rate.rounding), (Some("#,##0.0000"), Some(RoundingMode::Down)));
+        assert_eq!(format_of(&intent, "TOTAL_AMT").unwrap().number_pattern().as_deref(), Some("#,##0.00"));
+        assert_eq!(format_of(&intent, "QTY").unwrap().rounding, None);
+
+        // Without a locale only decimal columns with a scale are formatted
+        let mut intent = self::intent();
+        intent.datasets[0].columns[3].scale = Some(3);
+        intent.apply_scale_formats();
+        assert_eq!(format_of(&intent, "RATE").unwrap().number_pattern().as_deref(), Some("#,##0.000"));
+        assert_eq!(intent.formatted_columns().len(), 1);
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        assert_eq!(ColumnFormat::date("yyyy-MM-dd").type_mismatch(DataType::Date), None);
+        assert_eq!(
+            ColumnFormat::date("yyyy-MM-dd").type_mismatch(DataType::Integer).as_deref(),
+            Some("date format 'yyyy-MM-dd' for data type integer")
+        );
+        assert!(ColumnFormat::date("yyyy-MM-dd HH:mm").type_mismatch(DataType::Date).is_some());
+        assert!(ColumnFormat::number(true, 2).type_mismatch(DataType::String).is_some());
+        assert!(ColumnFormat::number(true, 2).type_mismatch(DataType::Integer).is_some());
+        assert_eq!(ColumnFormat::number(true, 0).type_mismatch(DataType::Integer), None);
+    }
+}
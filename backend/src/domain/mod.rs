@@ -1,11 +1,63 @@
 mod ui_intent;
+mod dashboard;
+mod wizard;
+mod tabs;
+mod address;
+mod print;
+mod search_state;
+mod accessibility;
 mod input;
 mod spring_intent;
 mod review;
+mod review_gate;
+mod project_review;
 mod qa;
+mod company_rules;
+mod sensitive_data;
+mod db_type;
+mod output_format;
+mod file_header;
+mod git_integration;
+mod issue_tracker;
+mod artifact_naming;
+mod intent_diff;
+mod spring_module;
+mod checklist;
+mod locale_format;
+mod column_archetype;
+mod schema_diff;
+mod lineage;
+mod date_policy;
+mod error_code;
 
 pub use ui_intent::*;
+pub use dashboard::*;
+pub use wizard::*;
+pub use tabs::*;
+pub use address::*;
+pub use print::*;
+pub use search_state::*;
+pub use accessibility::*;
 pub use input::*;
 pub use spring_intent::*;
 pub use review::*;
+pub use review_gate::*;
+pub use project_review::*;
 pub use qa::*;
+pub use company_rules::*;
+pub use sensitive_data::*;
+pub use db_type::*;
+pub use output_format::*;
+pub use file_header::*;
+pub use git_integration::*;
+pub use issue_tracker::*;
+pub use checklist::*;
+pub use artifact_naming::*;
+pub use intent_diff::*;
+pub use spring_module::*;
+pub use locale_format::*;
+pub use column_archetype::*;
+pub use schema_diff::*;
+pub use lineage::*;
+pub use date_policy::*;
+pub use error_code::*;
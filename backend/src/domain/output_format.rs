@@ -0,0 +1,132 @@
+//! Output File Format
+//!
+//! Per-project file settings applied when artifacts are packaged for
+//! download. Some xFrame5 IDE setups on Windows only open EUC-KR, or UTF-8
+//! with a BOM, with CRLF line endings.
+
+use serde::{Deserialize, Serialize};
+
+/// Character encoding of packaged files (read through [`OutputEncoding::parse`],
+/// so `cp949`/`ms949` are accepted as EUC-KR)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub enum OutputEncoding {
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "euc-kr")]
+    EucKr,
+}
+
+impl OutputEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputEncoding::Utf8 => "utf-8",
+            OutputEncoding::EucKr => "euc-kr",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Some(OutputEncoding::Utf8),
+            "euc-kr" | "euckr" | "cp949" | "ms949" => Some(OutputEncoding::EucKr),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<String> for OutputEncoding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value).ok_or_else(|| format!("Unsupported encoding '{}'", value))
+    }
+}
+
+/// Line ending style of packaged files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "lf",
+            LineEnding::Crlf => "crlf",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "lf" => Some(LineEnding::Lf),
+            "crlf" => Some(LineEnding::Crlf),
+            _ => None,
+        }
+    }
+
+    /// Convert all line breaks (LF, CRLF or lone CR) to this style
+    pub fn apply(&self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+        match self {
+            LineEnding::Lf => normalized,
+            LineEnding::Crlf => normalized.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// File format settings for packaged artifacts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct OutputFormat {
+    #[serde(default)]
+    pub encoding: OutputEncoding,
+
+    /// Prefix files with a byte order mark (UTF-8 only)
+    #[serde(default)]
+    pub bom: bool,
+
+    #[serde(default)]
+    pub line_ending: LineEnding,
+}
+
+impl OutputFormat {
+    /// Reject combinations that can't be written
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bom && self.encoding != OutputEncoding::Utf8 {
+            return Err(format!("BOM is only supported for utf-8, not {}", self.encoding.as_str()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_ending_apply() {
+        let text = "a\r\nb\nc\rd";
+        assert_eq!(LineEnding::Lf.apply(text), "a\nb\nc\nd");
+        assert_eq!(LineEnding::Crlf.apply(text), "a\r\nb\r\nc\r\nd");
+    }
+
+    #[test]
+    fn test_parse_and_validate() {
+        assert_eq!(OutputEncoding::parse("CP949"), Some(OutputEncoding::EucKr));
+        assert_eq!(OutputEncoding::parse("latin1"), None);
+
+        let format: OutputFormat =
+            serde_json::from_str(r#"{"encoding": "euc-kr", "bom": true}"#).unwrap();
+        assert_eq!(format.line_ending, LineEnding::Lf);
+        assert!(format.validate().is_err());
+
+        let format: OutputFormat = serde_json::from_str(r#"{"encoding": "cp949"}"#).unwrap();
+        assert_eq!(format.encoding, OutputEncoding::EucKr);
+        assert_eq!(serde_json::to_value(format.encoding).unwrap(), "euc-kr");
+        assert!(serde_json::from_str::<OutputFormat>(r#"{"encoding": "latin1"}"#).is_err());
+        assert!(OutputFormat::default().validate().is_ok());
+    }
+}
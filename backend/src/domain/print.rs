@@ -0,0 +1,272 @@
+//! Print / Report Output
+//!
+//! A screen with printing gets a 인쇄 button, an `fn_print` that hands the
+//! screen's rows to the customer's report tool, and a print-layout stub the
+//! report designer starts from. The tool comes from the request, then the
+//! company rule set's `print` section, then xFrame5's own print.
+
+use serde::{Deserialize, Serialize};
+
+use super::ui_intent::{UiIntent, UiType};
+
+/// Report tool used by the customer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportTool {
+    /// Built-in xFrame5 print
+    #[default]
+    Xframe5,
+    /// OZ Report viewer
+    Oz,
+    /// Crystal Reports
+    Crystal,
+}
+
+impl ReportTool {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportTool::Xframe5 => "xframe5",
+            ReportTool::Oz => "oz",
+            ReportTool::Crystal => "crystal",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportTool::Xframe5 => "xFrame5 print",
+            ReportTool::Oz => "OZ Report",
+            ReportTool::Crystal => "Crystal Reports",
+        }
+    }
+
+    /// Report file the layout is recreated as in the designer
+    pub fn report_extension(&self) -> &'static str {
+        match self {
+            ReportTool::Xframe5 => "xml",
+            ReportTool::Oz => "ozr",
+            ReportTool::Crystal => "rpt",
+        }
+    }
+}
+
+/// `print` section of a company rule set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintRules {
+    #[serde(default)]
+    pub tool: ReportTool,
+
+    /// Common helper that opens the report (e.g. "gfn_openOzViewer")
+    #[serde(default)]
+    pub helper: Option<String>,
+
+    /// Server folder of the report files (e.g. "/report/member/")
+    #[serde(default)]
+    pub report_path: Option<String>,
+}
+
+/// Printing of a screen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintIntent {
+    pub tool: ReportTool,
+
+    /// Report name, without folder or extension (e.g. "member_list_print")
+    pub report_name: String,
+
+    /// Dataset whose rows are printed
+    pub dataset_id: String,
+
+    /// Helper `fn_print` must call; a TODO is left when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub helper: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_path: Option<String>,
+}
+
+impl PrintIntent {
+    /// Printing of an intent's first grid (or dataset), with the requested
+    /// tool or the company default
+    pub fn for_intent(intent: &UiIntent, tool: Option<ReportTool>, rules: Option<&PrintRules>) -> Self {
+        let rules = rules.cloned().unwrap_or_default();
+        let dataset_id = intent
+            .grids
+            .first()
+            .map(|g| g.dataset_id.clone())
+            .or_else(|| intent.datasets.first().map(|ds| ds.id.clone()))
+            .unwrap_or_else(|| "ds_list".to_string());
+        Self {
+            tool: tool.unwrap_or(rules.tool),
+            report_name: format!("{}_print", intent.screen_name),
+            dataset_id,
+            helper: rules.helper.filter(|h| !h.trim().is_empty()),
+            report_path: rules.report_path.filter(|p| !p.trim().is_empty()),
+        }
+    }
+
+    /// Report file as the tool opens it (`report_path` + name + extension)
+    pub fn report_file(&self) -> String {
+        format!(
+            "{}{}.{}",
+            self.report_path.as_deref().unwrap_or(""),
+            self.report_name,
+            self.tool.report_extension()
+        )
+    }
+
+    /// Stub file name next to the screen XML (`member_list.xml` → `member_list_print.xml`)
+    pub fn layout_filename(xml_filename: &str) -> String {
+        match xml_filename.strip_suffix(".xml") {
+            Some(stem) => format!("{}_print.xml", stem),
+            None => format!("{}_print.xml", xml_filename),
+        }
+    }
+
+    /// Print-layout stub: title, column headers, one detail row bound to the
+    /// dataset and a page footer, to be recreated in the report designer
+    pub fn layout_stub(&self, intent: &UiIntent) -> String {
+        let fields = Self::fields(intent, &self.dataset_id);
+        let orientation = if fields.len() > 8 { "landscape" } else { "portrait" };
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<!-- Print layout stub for {}: recreate as {} in the report designer -->\n",
+            self.tool.label(),
+            self.report_file()
+        ));
+        xml.push_str(&format!(
+            "<print_layout name=\"{}\" tool=\"{}\" paper=\"A4\" orientation=\"{}\" link_data=\"{}\">\n",
+            escape(&self.report_name),
+            self.tool.as_str(),
+            orientation,
+            escape(&self.dataset_id)
+        ));
+        xml.push_str("  <band name=\"title\" height=\"40\">\n");
+        xml.push_str(&format!(
+            "    <text name=\"txt_title\" text=\"{}\"/>\n",
+            escape(&intent.screen_name)
+        ));
+        xml.push_str("  </band>\n");
+
+        if fields.is_empty() {
+            xml.push_str("  <!-- TODO: add the printed columns -->\n");
+        } else {
+            xml.push_str("  <band name=\"header\" height=\"24\">\n");
+            for (name, label, width) in &fields {
+                xml.push_str(&format!(
+                    "    <text name=\"hdr_{}\" text=\"{}\" width=\"{}\"/>\n",
+                    escape(name),
+                    escape(label),
+                    width
+                ));
+            }
+            xml.push_str("  </band>\n");
+            xml.push_str(&format!(
+                "  <band name=\"detail\" height=\"20\" repeat=\"{}\">\n",
+                escape(&self.dataset_id)
+            ));
+            for (name, _, width) in &fields {
+                xml.push_str(&format!(
+                    "    <field name=\"{}\" link_data=\"{}:{}\" width=\"{}\"/>\n",
+                    escape(name),
+                    escape(&self.dataset_id),
+                    escape(name),
+                    width
+                ));
+            }
+            xml.push_str("  </band>\n");
+        }
+
+        xml.push_str("  <band name=\"footer\" height=\"20\">\n");
+        xml.push_str("    <text name=\"txt_page\" text=\"{page} / {total_pages}\"/>\n");
+        xml.push_str("  </band>\n");
+        xml.push_str("</print_layout>\n");
+        xml
+    }
+
+    /// `(column, label, width)` printed: the grid's columns, or the visible
+    /// dataset columns when the dataset has no grid
+    fn fields(intent: &UiIntent, dataset_id: &str) -> Vec<(String, String, u32)> {
+        if let Some(grid) = intent.grids.iter().find(|g| g.dataset_id == dataset_id) {
+            return grid
+                .columns
+                .iter()
+                .map(|c| {
+                    let width = c
+                        .width
+                        .as_deref()
+                        .and_then(|w| w.trim_end_matches("px").parse().ok())
+                        .unwrap_or(100);
+                    (c.name.clone(), c.header.clone(), width)
+                })
+                .collect();
+        }
+        intent
+            .datasets
+            .iter()
+            .filter(|ds| ds.id == dataset_id)
+            .flat_map(|ds| ds.columns.iter())
+            .filter(|c| c.ui_type != UiType::Hidden)
+            .map(|c| (c.name.clone(), c.label.clone(), 100))
+            .collect()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnIntent, DatasetIntent, GridColumnIntent, GridIntent, ScreenType};
+
+    fn member_list() -> UiIntent {
+        UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(
+                DatasetIntent::new("ds_member")
+                    .with_column(ColumnIntent::new("MEMBER_ID", "ID").primary_key())
+                    .with_column(ColumnIntent::new("MEMBER_NAME", "회원명")),
+            )
+            .with_grid(
+                GridIntent::new("grid_member", "ds_member")
+                    .with_column(GridColumnIntent::new("MEMBER_NAME", "회원명 & 별칭").with_width("150")),
+            )
+    }
+
+    #[test]
+    fn test_tool_from_request_then_company_rules() {
+        let rules = PrintRules {
+            tool: ReportTool::Oz,
+            helper: Some("gfn_openOzViewer".to_string()),
+            report_path: Some("/report/".to_string()),
+        };
+        let print = PrintIntent::for_intent(&member_list(), None, Some(&rules));
+        assert_eq!(print.tool, ReportTool::Oz);
+        assert_eq!(print.report_file(), "/report/member_list_print.ozr");
+        assert_eq!(print.helper.as_deref(), Some("gfn_openOzViewer"));
+
+        let print = PrintIntent::for_intent(&member_list(), Some(ReportTool::Crystal), Some(&rules));
+        assert_eq!(print.tool, ReportTool::Crystal);
+
+        let print = PrintIntent::for_intent(&member_list(), None, None);
+        assert_eq!(print.tool, ReportTool::Xframe5);
+        assert_eq!(print.dataset_id, "ds_member");
+        assert_eq!(print.helper, None);
+    }
+
+    #[test]
+    fn test_layout_stub_uses_grid_columns() {
+        let intent = member_list();
+        let stub = PrintIntent::for_intent(&intent, Some(ReportTool::Oz), None).layout_stub(&intent);
+
+        assert!(stub.contains("<!-- Print layout stub for OZ Report: recreate as member_list_print.ozr"));
+        assert!(stub.contains("orientation=\"portrait\" link_data=\"ds_member\""));
+        assert!(stub.contains("<text name=\"hdr_MEMBER_NAME\" text=\"회원명 &amp; 별칭\" width=\"150\"/>"));
+        assert!(stub.contains("<field name=\"MEMBER_NAME\" link_data=\"ds_member:MEMBER_NAME\" width=\"150\"/>"));
+        assert!(!stub.contains("MEMBER_ID"));
+    }
+}
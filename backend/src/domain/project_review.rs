@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::review::{IssueCategory, IssueSeverity, ReviewIssue};
+use super::review_gate::SeverityCounts;
+
+/// Review of one file of an uploaded project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileReview {
+    /// Path inside the archive
+    pub path: String,
+
+    /// xml or javascript
+    pub file_type: String,
+
+    pub lines: usize,
+
+    #[serde(default)]
+    pub issues: Vec<ReviewIssue>,
+
+    /// Issue counts per severity
+    #[serde(default)]
+    pub counts: SeverityCounts,
+
+    /// The LLM reviewed the file as well
+    #[serde(default)]
+    pub llm_reviewed: bool,
+}
+
+impl FileReview {
+    pub fn new(path: impl Into<String>, file_type: impl Into<String>, lines: usize, issues: Vec<ReviewIssue>) -> Self {
+        let mut counts = SeverityCounts::default();
+        for issue in &issues {
+            counts.add(issue.severity);
+        }
+        Self {
+            path: path.into(),
+            file_type: file_type.into(),
+            lines,
+            issues,
+            counts,
+            llm_reviewed: false,
+        }
+    }
+}
+
+/// Finding about the project as a whole (missing counterparts, duplicate
+/// screen ids, skipped files)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFinding {
+    pub severity: IssueSeverity,
+
+    pub category: IssueCategory,
+
+    /// Files the finding is about
+    #[serde(default)]
+    pub files: Vec<String>,
+
+    pub message: String,
+}
+
+impl ProjectFinding {
+    pub fn new(severity: IssueSeverity, category: IssueCategory, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            category,
+            files: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn with_files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+}
+
+/// Totals of a project review
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectReviewSummary {
+    /// Files reviewed
+    pub files: usize,
+
+    /// Lines reviewed
+    pub lines: usize,
+
+    /// File issues and project findings per severity
+    pub total: SeverityCounts,
+
+    /// Counts per category (only categories with issues)
+    pub by_category: HashMap<IssueCategory, SeverityCounts>,
+
+    /// Files with at least one error
+    pub files_with_errors: usize,
+}
+
+/// Report of a bulk review over an uploaded project archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectReviewReport {
+    /// Project name (archive name unless given)
+    pub project: String,
+
+    pub product: String,
+
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+
+    /// Files went through the LLM review as well as the static checks
+    pub llm_review: bool,
+
+    pub summary: ProjectReviewSummary,
+
+    #[serde(default)]
+    pub findings: Vec<ProjectFinding>,
+
+    /// Reviewed files by path
+    #[serde(default)]
+    pub files: Vec<FileReview>,
+}
+
+impl ProjectReviewReport {
+    pub fn new(
+        project: impl Into<String>,
+        product: impl Into<String>,
+        mut files: Vec<FileReview>,
+        findings: Vec<ProjectFinding>,
+        llm_review: bool,
+    ) -> Self {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut summary = ProjectReviewSummary {
+            files: files.len(),
+            lines: files.iter().map(|f| f.lines).sum(),
+            files_with_errors: files.iter().filter(|f| f.counts.errors > 0).count(),
+            ..Default::default()
+        };
+        let severities = files
+            .iter()
+            .flat_map(|f| f.issues.iter().map(|i| (i.category, i.severity)))
+            .chain(findings.iter().map(|f| (f.category, f.severity)));
+        for (category, severity) in severities {
+            summary.total.add(severity);
+            summary.by_category.entry(category).or_default().add(severity);
+        }
+
+        Self {
+            project: project.into(),
+            product: product.into(),
+            generated_at: chrono::Utc::now(),
+            llm_review,
+            summary,
+            findings,
+            files,
+        }
+    }
+
+    /// SARIF 2.1.0 log of the report, one rule per issue category
+    ///
+    /// Project findings are reported against each of their files (or without
+    /// a location when they have none).
+    pub fn to_sarif(&self) -> Value {
+        let rules: Vec<Value> = IssueCategory::ALL
+            .iter()
+            .map(|category| {
+                json!({
+                    "id": category.as_str(),
+                    "shortDescription": { "text": format!("xFrame5 {} review", category.as_str().replace('_', " ")) },
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for file in &self.files {
+            for issue in &file.issues {
+                let text = match &issue.suggestion {
+                    Some(suggestion) => format!("{} (suggestion: {})", issue.message, suggestion),
+                    None => issue.message.clone(),
+                };
+                results.push(sarif_result(issue.category, issue.severity, &text, &[file.path.as_str()], issue.line));
+            }
+        }
+        for finding in &self.findings {
+            let files: Vec<&str> = finding.files.iter().map(String::as_str).collect();
+            results.push(sarif_result(finding.category, finding.severity, &finding.message, &files, 0));
+        }
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": format!("{}-project-review", self.product),
+                        "rules": rules,
+                    }
+                },
+                "automationDetails": { "id": format!("{}/{}", self.project, self.generated_at.to_rfc3339()) },
+                "results": results,
+            }]
+        })
+    }
+}
+
+fn sarif_result(category: IssueCategory, severity: IssueSeverity, text: &str, files: &[&str], line: u32) -> Value {
+    let level = match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info | IssueSeverity::Suggestion => "note",
+    };
+    let locations: Vec<Value> = files
+        .iter()
+        .map(|path| {
+            let mut location = json!({ "artifactLocation": { "uri": path } });
+            // SARIF lines start at 1; 0 means the issue has no line
+            if line > 0 {
+                location["region"] = json!({ "startLine": line });
+            }
+            json!({ "physicalLocation": location })
+        })
+        .collect();
+
+    json!({
+        "ruleId": category.as_str(),
+        "level": level,
+        "message": { "text": text },
+        "locations": locations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(severity: IssueSeverity, line: u32) -> ReviewIssue {
+        ReviewIssue {
+            severity,
+            category: IssueCategory::Syntax,
+            line,
+            message: "onclick must be on_click".to_string(),
+            suggestion: None,
+            patch: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_counts_files_and_findings() {
+        let report = ProjectReviewReport::new(
+            "takeover",
+            "xframe5-ui",
+            vec![
+                FileReview::new("b/member_list.xml", "xml", 40, vec![issue(IssueSeverity::Error, 3)]),
+                FileReview::new("a/member_list.js", "javascript", 60, vec![issue(IssueSeverity::Warning, 0)]),
+            ],
+            vec![ProjectFinding::new(IssueSeverity::Warning, IssueCategory::Pattern, "No JavaScript for screen")],
+            false,
+        );
+
+        assert_eq!(report.files[0].path, "a/member_list.js");
+        assert_eq!(report.summary.lines, 100);
+        assert_eq!(report.summary.files_with_errors, 1);
+        assert_eq!(report.summary.total.errors, 1);
+        assert_eq!(report.summary.total.warnings, 2);
+        assert_eq!(report.summary.by_category[&IssueCategory::Pattern].warnings, 1);
+    }
+
+    #[test]
+    fn test_sarif_locations() {
+        let report = ProjectReviewReport::new(
+            "takeover",
+            "xframe5-ui",
+            vec![FileReview::new("member_list.xml", "xml", 40, vec![issue(IssueSeverity::Error, 3)])],
+            vec![ProjectFinding::new(IssueSeverity::Info, IssueCategory::Pattern, "Skipped").with_files(vec![])],
+            false,
+        );
+        let sarif = report.to_sarif();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            3
+        );
+        assert_eq!(results[1]["level"], "note");
+        assert!(results[1]["locations"].as_array().unwrap().is_empty());
+    }
+}
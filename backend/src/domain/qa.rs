@@ -152,6 +152,10 @@ pub struct QAMeta {
 
     /// Answer time in milliseconds
     pub answer_time_ms: u64,
+
+    /// Served from the response cache
+    #[serde(default)]
+    pub cached: bool,
 }
 
 impl QAMeta {
@@ -160,6 +164,7 @@ impl QAMeta {
             generator: generator.into(),
             timestamp: chrono::Utc::now(),
             answer_time_ms,
+            cached: false,
         }
     }
 }
@@ -166,6 +166,10 @@ pub struct ReviewIssue {
 
     /// Suggested fix
     pub suggestion: Option<String>,
+
+    /// Line-precise fix as a unified diff (applied via `/agent/review/apply`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch: Option<String>,
 }
 
 /// Issue severity levels
@@ -178,8 +182,19 @@ pub enum IssueSeverity {
     Suggestion,
 }
 
+impl IssueSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+            Self::Suggestion => "suggestion",
+        }
+    }
+}
+
 /// Issue categories
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum IssueCategory {
     Syntax,
@@ -190,6 +205,28 @@ pub enum IssueCategory {
     BestPractice,
 }
 
+impl IssueCategory {
+    pub const ALL: [IssueCategory; 6] = [
+        Self::Syntax,
+        Self::Pattern,
+        Self::Naming,
+        Self::Performance,
+        Self::Security,
+        Self::BestPractice,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Syntax => "syntax",
+            Self::Pattern => "pattern",
+            Self::Naming => "naming",
+            Self::Performance => "performance",
+            Self::Security => "security",
+            Self::BestPractice => "best_practice",
+        }
+    }
+}
+
 /// Quality score breakdown
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewScore {
@@ -222,6 +259,10 @@ pub struct ReviewMeta {
 
     /// Review time in milliseconds
     pub review_time_ms: u64,
+
+    /// Served from the response cache
+    #[serde(default)]
+    pub cached: bool,
 }
 
 impl ReviewMeta {
@@ -230,6 +271,7 @@ impl ReviewMeta {
             generator: generator.into(),
             timestamp: chrono::Utc::now(),
             review_time_ms,
+            cached: false,
         }
     }
 }
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::review::{IssueCategory, IssueSeverity, ReviewMeta, ReviewResult, ReviewStatus};
+
+/// Gate policy for pass/fail evaluation of a review
+///
+/// Limits are inclusive: `max_errors: 0` fails on the first error.
+/// A `None` limit is not enforced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewPolicy {
+    /// Maximum total errors (default: 0)
+    #[serde(default = "default_max_errors")]
+    pub max_errors: Option<u32>,
+
+    /// Maximum total warnings
+    #[serde(default)]
+    pub max_warnings: Option<u32>,
+
+    /// Minimum overall score (0-100)
+    #[serde(default)]
+    pub min_score: Option<u8>,
+
+    /// Per-category limits
+    #[serde(default)]
+    pub categories: HashMap<IssueCategory, SeverityLimits>,
+}
+
+fn default_max_errors() -> Option<u32> {
+    Some(0)
+}
+
+impl Default for ReviewPolicy {
+    fn default() -> Self {
+        Self {
+            max_errors: default_max_errors(),
+            max_warnings: None,
+            min_score: None,
+            categories: HashMap::new(),
+        }
+    }
+}
+
+/// Error/warning limits for a single category
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeverityLimits {
+    #[serde(default)]
+    pub max_errors: Option<u32>,
+
+    #[serde(default)]
+    pub max_warnings: Option<u32>,
+}
+
+/// Issue counts per severity
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    pub errors: u32,
+    pub warnings: u32,
+    pub infos: u32,
+    pub suggestions: u32,
+}
+
+impl SeverityCounts {
+    /// Count one issue of `severity`
+    pub fn add(&mut self, severity: IssueSeverity) {
+        match severity {
+            IssueSeverity::Error => self.errors += 1,
+            IssueSeverity::Warning => self.warnings += 1,
+            IssueSeverity::Info => self.infos += 1,
+            IssueSeverity::Suggestion => self.suggestions += 1,
+        }
+    }
+}
+
+/// Machine-readable summary of the reviewed issues
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GateSummary {
+    /// Totals across all categories
+    pub total: SeverityCounts,
+
+    /// Counts per category (only categories with issues)
+    pub by_category: HashMap<IssueCategory, SeverityCounts>,
+
+    /// Overall review score, if the reviewer produced one
+    pub score: Option<u8>,
+}
+
+/// A single policy limit that was exceeded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateViolation {
+    /// Limit that failed (e.g. "max_errors", "min_score")
+    pub rule: String,
+
+    /// Category the limit applies to (None for totals)
+    pub category: Option<IssueCategory>,
+
+    /// Observed value
+    pub actual: u32,
+
+    /// Configured limit
+    pub limit: u32,
+}
+
+/// Gate evaluation result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewGateResult {
+    /// Whether the code passed the policy
+    pub passed: bool,
+
+    /// Issue counts
+    pub summary: GateSummary,
+
+    /// Exceeded limits (empty when passed)
+    pub violations: Vec<GateViolation>,
+}
+
+/// Exit status for CI/pre-commit callers
+pub const GATE_EXIT_PASS: i32 = 0;
+pub const GATE_EXIT_FAIL: i32 = 1;
+pub const GATE_EXIT_ERROR: i32 = 2;
+
+/// Review gate response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewGateResponse {
+    /// Status of the underlying review
+    pub status: ReviewStatus,
+
+    /// Process exit code a CI wrapper should use (0 pass, 1 fail, 2 error)
+    pub exit_code: i32,
+
+    /// Gate result (absent if the review itself failed)
+    pub gate: Option<ReviewGateResult>,
+
+    /// Full review for reporting
+    pub review: Option<ReviewResult>,
+
+    /// Error message (if status is error)
+    pub error: Option<String>,
+
+    /// Response metadata
+    pub meta: ReviewMeta,
+}
+
+impl ReviewGateResponse {
+    pub fn evaluated(gate: ReviewGateResult, review: ReviewResult, meta: ReviewMeta) -> Self {
+        Self {
+            status: ReviewStatus::Success,
+            exit_code: if gate.passed { GATE_EXIT_PASS } else { GATE_EXIT_FAIL },
+            gate: Some(gate),
+            review: Some(review),
+            error: None,
+            meta,
+        }
+    }
+
+    pub fn error(error: impl Into<String>, meta: ReviewMeta) -> Self {
+        Self {
+            status: ReviewStatus::Error,
+            exit_code: GATE_EXIT_ERROR,
+            gate: None,
+            review: None,
+            error: Some(error.into()),
+            meta,
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Schema Comparison
+//!
+//! Column-level diff between two snapshots of a table (see
+//! `SchemaChangeService`). Column names are compared case-insensitively and
+//! types ignoring case and whitespace, so `varchar(100)` and `VARCHAR (100)`
+//! are the same type. The diff of the columns a screen or module uses is
+//! added to its regeneration prompt so only the affected parts change.
+
+use serde::{Deserialize, Serialize};
+
+use super::{SchemaColumn, SchemaInput};
+
+/// What changed about a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnChangeKind {
+    Added,
+    Removed,
+    TypeChanged,
+    NullabilityChanged,
+    PrimaryKeyChanged,
+    CommentChanged,
+}
+
+/// One changed column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnChange {
+    /// Column name as in the newer snapshot (older one if removed)
+    pub column: String,
+
+    pub kind: ColumnChangeKind,
+
+    /// Type, nullability, key flag or comment before (absent if added)
+    pub before: Option<String>,
+
+    /// Same after (absent if removed)
+    pub after: Option<String>,
+}
+
+/// Changes of one table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    pub table: String,
+    pub changes: Vec<ColumnChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changes that matter to an artifact using `columns`: changes of those
+    /// columns and every added column
+    pub fn affecting<S: AsRef<str>>(&self, columns: &[S]) -> SchemaDiff {
+        let changes = self
+            .changes
+            .iter()
+            .filter(|c| {
+                c.kind == ColumnChangeKind::Added || columns.iter().any(|name| name.as_ref().eq_ignore_ascii_case(&c.column))
+            })
+            .cloned()
+            .collect();
+        SchemaDiff {
+            table: self.table.clone(),
+            changes,
+        }
+    }
+
+    /// Columns this diff removes
+    pub fn removed(&self) -> impl Iterator<Item = &str> {
+        self.changes_of(ColumnChangeKind::Removed)
+    }
+
+    /// Columns this diff adds
+    pub fn added(&self) -> impl Iterator<Item = &str> {
+        self.changes_of(ColumnChangeKind::Added)
+    }
+
+    fn changes_of(&self, kind: ColumnChangeKind) -> impl Iterator<Item = &str> {
+        self.changes.iter().filter(move |c| c.kind == kind).map(|c| c.column.as_str())
+    }
+
+    /// Instruction added to the regeneration prompt
+    pub fn prompt_note(&self) -> String {
+        let mut note = format!(
+            "SCHEMA CHANGE: table {} changed since the previous generation. \
+             Update only the parts that use these columns and keep everything else as it was:\n",
+            self.table
+        );
+        for change in &self.changes {
+            let line = match change.kind {
+                ColumnChangeKind::Added => format!("added ({})", change.after.as_deref().unwrap_or("")),
+                ColumnChangeKind::Removed => "removed; drop it everywhere it is used".to_string(),
+                ColumnChangeKind::TypeChanged => format!("type {} -> {}", show(&change.before), show(&change.after)),
+                ColumnChangeKind::NullabilityChanged => {
+                    format!("nullable {} -> {}", show(&change.before), show(&change.after))
+                }
+                ColumnChangeKind::PrimaryKeyChanged => {
+                    format!("primary key {} -> {}", show(&change.before), show(&change.after))
+                }
+                ColumnChangeKind::CommentChanged => {
+                    format!("comment '{}' -> '{}'", show(&change.before), show(&change.after))
+                }
+            };
+            note.push_str(&format!("- {}: {}\n", change.column, line));
+        }
+        note
+    }
+}
+
+fn show(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("")
+}
+
+/// Column changes from `before` to `after`
+pub fn diff_schemas(before: &SchemaInput, after: &SchemaInput) -> SchemaDiff {
+    let find = |schema: &SchemaInput, name: &str| -> Option<SchemaColumn> {
+        schema.columns.iter().find(|c| c.name.eq_ignore_ascii_case(name)).cloned()
+    };
+    let mut changes = Vec::new();
+
+    for old in &before.columns {
+        let Some(new) = find(after, &old.name) else {
+            changes.push(ColumnChange {
+                column: old.name.clone(),
+                kind: ColumnChangeKind::Removed,
+                before: Some(old.column_type.clone()),
+                after: None,
+            });
+            continue;
+        };
+        let mut push = |kind, before: String, after: String| {
+            changes.push(ColumnChange {
+                column: new.name.clone(),
+                kind,
+                before: Some(before),
+                after: Some(after),
+            })
+        };
+        if normalize_type(&old.column_type) != normalize_type(&new.column_type) {
+            push(ColumnChangeKind::TypeChanged, old.column_type.clone(), new.column_type.clone());
+        }
+        if old.nullable != new.nullable {
+            push(ColumnChangeKind::NullabilityChanged, old.nullable.to_string(), new.nullable.to_string());
+        }
+        let (old_pk, new_pk) = (is_pk(before, old), is_pk(after, &new));
+        if old_pk != new_pk {
+            push(ColumnChangeKind::PrimaryKeyChanged, old_pk.to_string(), new_pk.to_string());
+        }
+        let comment = |c: &SchemaColumn| c.comment.as_deref().map(str::trim).unwrap_or("").to_string();
+        if comment(old) != comment(&new) {
+            push(ColumnChangeKind::CommentChanged, comment(old), comment(&new));
+        }
+    }
+
+    for new in after.columns.iter().filter(|c| find(before, &c.name).is_none()) {
+        changes.push(ColumnChange {
+            column: new.name.clone(),
+            kind: ColumnChangeKind::Added,
+            before: None,
+            after: Some(new.column_type.clone()),
+        });
+    }
+
+    SchemaDiff {
+        table: after.table.clone(),
+        changes,
+    }
+}
+
+fn normalize_type(column_type: &str) -> String {
+    column_type.split_whitespace().collect::<String>().to_uppercase()
+}
+
+fn is_pk(schema: &SchemaInput, column: &SchemaColumn) -> bool {
+    column.pk || schema.primary_keys.iter().any(|k| k.eq_ignore_ascii_case(&column.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member() -> SchemaInput {
+        SchemaInput::new("TB_MEMBER")
+            .with_column(SchemaColumn::new("MEMBER_ID", "NUMBER(10)"))
+            .with_column(SchemaColumn::new("MEMBER_NAME", "VARCHAR2(50)"))
+            .with_column(SchemaColumn::new("FAX", "VARCHAR2(20)"))
+            .with_primary_key("MEMBER_ID")
+    }
+
+    #[test]
+    fn test_unchanged_schema_has_no_diff() {
+        let mut same = member();
+        same.columns[1].column_type = "varchar2 (50)".to_string();
+        same.columns[1].name = "member_name".to_string();
+        assert!(diff_schemas(&member(), &same).is_empty());
+    }
+
+    #[test]
+    fn test_diff_detects_column_changes() {
+        let mut after = member();
+        after.columns.retain(|c| c.name != "FAX");
+        after.columns[1].column_type = "VARCHAR2(100)".to_string();
+        after.columns[1].nullable = false;
+        after = after.with_column(SchemaColumn::new("EMAIL", "VARCHAR2(200)"));
+
+        let diff = diff_schemas(&member(), &after);
+        let kinds: Vec<_> = diff.changes.iter().map(|c| (c.column.as_str(), c.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("MEMBER_NAME", ColumnChangeKind::TypeChanged),
+                ("MEMBER_NAME", ColumnChangeKind::NullabilityChanged),
+                ("FAX", ColumnChangeKind::Removed),
+                ("EMAIL", ColumnChangeKind::Added),
+            ]
+        );
+        assert_eq!(diff.removed().collect::<Vec<_>>(), vec!["FAX"]);
+
+        let note = diff.prompt_note();
+        assert!(note.contains("table TB_MEMBER"));
+        assert!(note.contains("- MEMBER_NAME: type VARCHAR2(50) -> VARCHAR2(100)"));
+        assert!(note.contains("- EMAIL: added (VARCHAR2(200))"));
+    }
+
+    #[test]
+    fn test_affecting_keeps_used_columns_and_additions() {
+        let mut after = member();
+        after.columns.retain(|c| c.name != "FAX");
+        after.columns[1].column_type = "VARCHAR2(100)".to_string();
+        after = after.with_column(SchemaColumn::new("EMAIL", "VARCHAR2(200)"));
+
+        let diff = diff_schemas(&member(), &after).affecting(&["member_id", "member_name"]);
+        let columns: Vec<_> = diff.changes.iter().map(|c| c.column.as_str()).collect();
+        assert_eq!(columns, vec!["MEMBER_NAME", "EMAIL"]);
+    }
+}
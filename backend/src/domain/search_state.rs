@@ -0,0 +1,118 @@
+//! Search Condition Persistence
+//!
+//! A list screen with kept search conditions saves its search panel
+//! (`ds_search`) when it searches and restores it when it is opened again, so
+//! users coming back to the list find their filters. The storage goes through
+//! the customer's own helpers when the company rule set names them, otherwise
+//! through the browser session.
+
+use serde::{Deserialize, Serialize};
+
+use super::ui_intent::UiIntent;
+
+/// Saves the search panel, called by `fn_search`
+pub const SAVE_SEARCH_FUNCTION: &str = "fn_saveSearchState";
+
+/// Restores the search panel, called by `on_load` before the first search
+pub const RESTORE_SEARCH_FUNCTION: &str = "fn_restoreSearchState";
+
+/// `search_state` section of a company rule set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchStateRules {
+    /// Common helper storing a dataset under a key (e.g. "gfn_saveSearch")
+    #[serde(default)]
+    pub save_helper: Option<String>,
+
+    /// Common helper filling a dataset from a key (e.g. "gfn_loadSearch")
+    #[serde(default)]
+    pub restore_helper: Option<String>,
+}
+
+/// Kept search conditions of a screen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchStateIntent {
+    /// Storage key (e.g. "search:member_list")
+    pub storage_key: String,
+
+    /// Dataset of the search panel
+    pub dataset_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub save_helper: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restore_helper: Option<String>,
+}
+
+impl SearchStateIntent {
+    /// Kept search conditions of an intent, stored through the company helpers
+    /// when both are configured
+    pub fn for_intent(intent: &UiIntent, rules: Option<&SearchStateRules>) -> Self {
+        let rules = rules.cloned().unwrap_or_default();
+        let helpers = rules
+            .save_helper
+            .filter(|h| !h.trim().is_empty())
+            .zip(rules.restore_helper.filter(|h| !h.trim().is_empty()));
+        Self {
+            storage_key: format!("search:{}", intent.screen_name),
+            dataset_id: "ds_search".to_string(),
+            save_helper: helpers.as_ref().map(|(save, _)| save.clone()),
+            restore_helper: helpers.map(|(_, restore)| restore),
+        }
+    }
+
+    /// `fn_saveSearchState` and `fn_restoreSearchState`
+    pub fn helper_functions(&self) -> String {
+        let (save, restore) = match (&self.save_helper, &self.restore_helper) {
+            (Some(save), Some(restore)) => (
+                format!("    {}(\"{}\", {});", save, self.storage_key, self.dataset_id),
+                format!("    {}(\"{}\", {});", restore, self.storage_key, self.dataset_id),
+            ),
+            _ => (
+                format!(
+                    "    sessionStorage.setItem(\"{}\", JSON.stringify({}.getData()));",
+                    self.storage_key, self.dataset_id
+                ),
+                format!(
+                    "    var saved = sessionStorage.getItem(\"{}\");\n    if (saved) {{\n        {}.setData(JSON.parse(saved));\n    }}",
+                    self.storage_key, self.dataset_id
+                ),
+            ),
+        };
+        format!(
+            "// Keeps the search conditions for users returning to this screen\n\
+             this.{} = function() {{\n{}\n}};\n\n\
+             this.{} = function() {{\n{}\n}};\n",
+            SAVE_SEARCH_FUNCTION, save, RESTORE_SEARCH_FUNCTION, restore
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScreenType;
+
+    #[test]
+    fn test_helpers_from_company_rules_or_session() {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let rules = SearchStateRules {
+            save_helper: Some("gfn_saveSearch".to_string()),
+            restore_helper: Some("gfn_loadSearch".to_string()),
+        };
+        let js = SearchStateIntent::for_intent(&intent, Some(&rules)).helper_functions();
+        assert!(js.contains("this.fn_saveSearchState = function() {\n    gfn_saveSearch(\"search:member_list\", ds_search);"));
+        assert!(js.contains("gfn_loadSearch(\"search:member_list\", ds_search);"));
+
+        // One helper alone isn't a usable pattern
+        let rules = SearchStateRules {
+            save_helper: Some("gfn_saveSearch".to_string()),
+            restore_helper: None,
+        };
+        let state = SearchStateIntent::for_intent(&intent, Some(&rules));
+        assert_eq!(state.save_helper, None);
+        let js = state.helper_functions();
+        assert!(js.contains("sessionStorage.setItem(\"search:member_list\", JSON.stringify(ds_search.getData()));"));
+        assert!(js.contains("ds_search.setData(JSON.parse(saved));"));
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+/// Classification of columns holding personal data that must be masked on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensitiveKind {
+    /// Resident registration number (주민등록번호)
+    ResidentNumber,
+    /// Credit/debit card number
+    CardNumber,
+    /// Phone or mobile number
+    Phone,
+}
+
+/// JS hook generated screens must call before showing an unmasked value
+pub const UNMASK_AUTH_HOOK: &str = "fn_check_unmask_auth";
+
+impl SensitiveKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SensitiveKind::ResidentNumber => "resident_number",
+            SensitiveKind::CardNumber => "card_number",
+            SensitiveKind::Phone => "phone",
+        }
+    }
+
+    /// Human-readable name used in prompts and findings
+    pub fn description(&self) -> &'static str {
+        match self {
+            SensitiveKind::ResidentNumber => "resident registration number",
+            SensitiveKind::CardNumber => "card number",
+            SensitiveKind::Phone => "phone number",
+        }
+    }
+
+    /// Masked display pattern (`#` = shown digit, `*` = masked digit)
+    pub fn mask_pattern(&self) -> &'static str {
+        match self {
+            SensitiveKind::ResidentNumber => "######-#******",
+            SensitiveKind::CardNumber => "####-****-****-####",
+            SensitiveKind::Phone => "###-****-####",
+        }
+    }
+
+    /// Classify a column from its name and comment.
+    ///
+    /// Names are matched per `_`-separated token so e.g. `telecom_cd` is not
+    /// taken for a phone number; comments are matched on Korean keywords.
+    pub fn classify(name: &str, comment: Option<&str>) -> Option<Self> {
+        Self::classify_name(name).or_else(|| comment.and_then(Self::classify_comment))
+    }
+
+    fn classify_name(name: &str) -> Option<Self> {
+        let lower = name.to_lowercase();
+        let tokens: Vec<&str> = lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .collect();
+        let has = |candidates: &[&str]| tokens.iter().any(|t| candidates.contains(t));
+        let followed_by_no = |word: &str| {
+            tokens
+                .windows(2)
+                .any(|w| w[0] == word && ["no", "num", "number", "reg"].contains(&w[1]))
+        };
+
+        if has(&["rrn", "ssn", "jumin", "juminno"]) || followed_by_no("resident") {
+            return Some(SensitiveKind::ResidentNumber);
+        }
+        if has(&["cardno", "cardnum"]) || followed_by_no("card") {
+            return Some(SensitiveKind::CardNumber);
+        }
+        if has(&["phone", "tel", "telno", "mobile", "hp", "hpno", "cellphone"]) {
+            return Some(SensitiveKind::Phone);
+        }
+        None
+    }
+
+    fn classify_comment(comment: &str) -> Option<Self> {
+        if comment.contains("주민") {
+            Some(SensitiveKind::ResidentNumber)
+        } else if comment.contains("카드번호") {
+            Some(SensitiveKind::CardNumber)
+        } else if ["전화", "휴대폰", "핸드폰", "연락처"]
+            .iter()
+            .any(|k| comment.contains(k))
+        {
+            Some(SensitiveKind::Phone)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for SensitiveKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_name() {
+        assert_eq!(SensitiveKind::classify("JUMIN_NO", None), Some(SensitiveKind::ResidentNumber));
+        assert_eq!(SensitiveKind::classify("resident_no", None), Some(SensitiveKind::ResidentNumber));
+        assert_eq!(SensitiveKind::classify("card_no", None), Some(SensitiveKind::CardNumber));
+        assert_eq!(SensitiveKind::classify("mobile_no", None), Some(SensitiveKind::Phone));
+        assert_eq!(SensitiveKind::classify("HP_NO", None), Some(SensitiveKind::Phone));
+    }
+
+    #[test]
+    fn test_classify_by_comment() {
+        assert_eq!(SensitiveKind::classify("col1", Some("주민등록번호")), Some(SensitiveKind::ResidentNumber));
+        assert_eq!(SensitiveKind::classify("col2", Some("결제 카드번호")), Some(SensitiveKind::CardNumber));
+        assert_eq!(SensitiveKind::classify("col3", Some("비상 연락처")), Some(SensitiveKind::Phone));
+    }
+
+    #[test]
+    fn test_no_false_positives() {
+        assert_eq!(SensitiveKind::classify("telecom_cd", None), None);
+        assert_eq!(SensitiveKind::classify("card_type", Some("카드 종류")), None);
+        assert_eq!(SensitiveKind::classify("member_name", Some("회원명")), None);
+    }
+}
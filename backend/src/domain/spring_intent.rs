@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::ColumnIntent;
+use super::{ColumnIntent, ColumnType, DatePolicy, DbDialect, SpringFilePaths};
 
 /// Internal DSL for representing Spring Framework code generation intent.
 /// This is the normalized representation for generating backend code.
@@ -23,6 +23,14 @@ pub struct SpringIntent {
 
     /// Additional options
     pub options: SpringOptions,
+
+    /// Database dialect of the source schema, when known
+    #[serde(default)]
+    pub dialect: Option<DbDialect>,
+
+    /// Additional notes or requirements
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
 }
 
 impl SpringIntent {
@@ -44,6 +52,8 @@ impl SpringIntent {
                 CrudOperation::Delete,
             ],
             options: SpringOptions::default(),
+            dialect: None,
+            notes: None,
         }
     }
 
@@ -67,6 +77,16 @@ impl SpringIntent {
         self
     }
 
+    pub fn with_dialect(mut self, dialect: Option<DbDialect>) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Copy of the intent without the request notes
+    pub fn without_notes(&self) -> Self {
+        Self { notes: None, ..self.clone() }
+    }
+
     /// Get the controller class name
     pub fn controller_name(&self) -> String {
         format!("{}Controller", self.entity_name)
@@ -188,6 +208,10 @@ pub struct SpringOptions {
 
     /// Base response wrapper class (e.g., "ApiResponse")
     pub response_wrapper: Option<String>,
+
+    /// Storage zone and JSON patterns of date fields
+    #[serde(default)]
+    pub date_policy: DatePolicy,
 }
 
 impl Default for SpringOptions {
@@ -200,6 +224,7 @@ impl Default for SpringOptions {
             include_audit_fields: true,
             generate_search_dto: true,
             response_wrapper: Some("ApiResponse".to_string()),
+            date_policy: DatePolicy::default(),
         }
     }
 }
@@ -231,6 +256,17 @@ pub struct SpringArtifacts {
     /// Validation warnings
     #[serde(default)]
     pub warnings: Vec<String>,
+
+    /// File tree from the naming profile (absent in older logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paths: Option<SpringFilePaths>,
+
+    /// Sample INSERT script for the source table (`test_data_rows` option)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_data: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_data_filename: Option<String>,
 }
 
 impl SpringArtifacts {
@@ -244,6 +280,9 @@ impl SpringArtifacts {
             mapper_interface: String::new(),
             mapper_xml: String::new(),
             warnings: Vec::new(),
+            paths: None,
+            test_data: None,
+            test_data_filename: None,
         }
     }
 
@@ -277,6 +316,16 @@ impl JavaType {
     /// Infer Java type from database column type
     pub fn from_db_type(db_type: &str) -> Self {
         let upper = db_type.to_uppercase();
+        let parsed = ColumnType::parse(db_type);
+
+        // Oracle NUMBER: width decides the integer type
+        if parsed.is_number() {
+            return match parsed.precision {
+                Some(p) if parsed.is_integral_number() && p <= 9 => JavaType::Integer,
+                Some(p) if parsed.is_integral_number() && p <= 18 => JavaType::Long,
+                _ => JavaType::BigDecimal,
+            };
+        }
 
         if upper.contains("VARCHAR") || upper.contains("CHAR") || upper.contains("TEXT") || upper.contains("CLOB") {
             JavaType::String
@@ -294,13 +343,21 @@ impl JavaType {
             JavaType::LocalDateTime
         } else if upper.contains("DATE") {
             JavaType::LocalDate
-        } else if upper.contains("BLOB") || upper.contains("BINARY") {
+        } else if upper.contains("BLOB") || upper.contains("BINARY") || upper.contains("RAW") {
             JavaType::ByteArray
         } else {
             JavaType::String
         }
     }
 
+    /// Infer Java type with dialect semantics (Oracle DATE holds a time of day)
+    pub fn from_db_type_for(db_type: &str, dialect: Option<DbDialect>) -> Self {
+        match Self::from_db_type(db_type) {
+            JavaType::LocalDate if dialect == Some(DbDialect::Oracle) => JavaType::LocalDateTime,
+            java_type => java_type,
+        }
+    }
+
     /// Get the Java type name
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -408,6 +465,22 @@ mod tests {
         assert_eq!(JavaType::from_db_type("BOOLEAN"), JavaType::Boolean);
     }
 
+    #[test]
+    fn test_java_type_inference_oracle() {
+        assert_eq!(JavaType::from_db_type("VARCHAR2(100 CHAR)"), JavaType::String);
+        assert_eq!(JavaType::from_db_type("NCLOB"), JavaType::String);
+        assert_eq!(JavaType::from_db_type("NUMBER(5)"), JavaType::Integer);
+        assert_eq!(JavaType::from_db_type("NUMBER(12,0)"), JavaType::Long);
+        assert_eq!(JavaType::from_db_type("NUMBER(10,2)"), JavaType::BigDecimal);
+        assert_eq!(JavaType::from_db_type("NUMBER"), JavaType::BigDecimal);
+        assert_eq!(JavaType::from_db_type("RAW(16)"), JavaType::ByteArray);
+        assert_eq!(
+            JavaType::from_db_type_for("DATE", Some(DbDialect::Oracle)),
+            JavaType::LocalDateTime
+        );
+        assert_eq!(JavaType::from_db_type_for("DATE", None), JavaType::LocalDate);
+    }
+
     #[test]
     fn test_camel_case_conversion() {
         assert_eq!(to_camel_case("member_id"), "memberId");
@@ -0,0 +1,86 @@
+//! Spring Module Types
+//!
+//! A module is several entities generated together against one set of
+//! shared classes (response wrapper, base controller, exceptions) that are
+//! generated once instead of per entity.
+
+use serde::{Deserialize, Serialize};
+
+/// A class shared by every entity of a module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedSpringClass {
+    /// Simple class name (e.g. `ApiResponse`)
+    pub name: String,
+
+    /// Package (e.g. `com.company.erp.common.response`)
+    pub package: String,
+
+    /// File path from the naming profile
+    pub path: String,
+
+    /// Java source
+    pub source: String,
+}
+
+impl SharedSpringClass {
+    pub fn fqcn(&self) -> String {
+        format!("{}.{}", self.package, self.name)
+    }
+}
+
+/// Shared classes the per-entity slices build on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpringModuleContext {
+    /// Base package every entity lives under
+    pub package_base: String,
+
+    /// Response wrapper class name
+    pub response_wrapper: String,
+
+    pub shared: Vec<SharedSpringClass>,
+}
+
+impl SpringModuleContext {
+    /// Package holding the shared classes
+    pub fn common_package(package_base: &str) -> String {
+        format!("{}.common", package_base)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&SharedSpringClass> {
+        self.shared.iter().find(|c| c.name == name)
+    }
+
+    /// Prompt section telling the LLM to use (not regenerate) the shared classes
+    pub fn prompt_section(&self) -> String {
+        let mut section = String::from("\nSHARED MODULE CLASSES (already exist - import them, do NOT generate them):\n");
+        for class in &self.shared {
+            section.push_str(&format!("- {}\n", class.fqcn()));
+        }
+        section.push_str("- Controllers extend BaseController and use its ok()/created() helpers\n");
+        section.push_str(&format!("- Wrap every response in {}\n", self.response_wrapper));
+        section.push_str("- Throw ResourceNotFoundException for missing rows and BusinessException for rule violations\n");
+        section
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_section_lists_shared_classes() {
+        let context = SpringModuleContext {
+            package_base: "com.company.erp".to_string(),
+            response_wrapper: "ApiResponse".to_string(),
+            shared: vec![SharedSpringClass {
+                name: "ApiResponse".to_string(),
+                package: "com.company.erp.common.response".to_string(),
+                path: String::new(),
+                source: String::new(),
+            }],
+        };
+        assert!(context.prompt_section().contains("- com.company.erp.common.response.ApiResponse\n"));
+        assert!(context.find("ApiResponse").is_some());
+        assert_eq!(SpringModuleContext::common_package("com.company.erp"), "com.company.erp.common");
+    }
+}
@@ -0,0 +1,137 @@
+//! Tab Intent
+//!
+//! Tab container of a detail screen: one xFrame5 `<tab>` control whose items
+//! each hold a panel of related fields. With lazy loading only the first tab's
+//! dataset is loaded when the screen opens; a tab bound to another dataset
+//! loads it the first time the tab is shown.
+//!
+//! Component and function names are derived from the container and tab ids,
+//! so the prompt and the Tab Panel Validator agree on them:
+//! `tab_<container id>`, `pnl_tab_<id>`, `fn_load_tab_<id>` and `fn_tab_changed`.
+
+use serde::{Deserialize, Serialize};
+
+/// Handler of the tab control's change event (lazy tabs)
+pub const TAB_CHANGED_FUNCTION: &str = "fn_tab_changed";
+
+/// Tabs of a detail screen, in order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TabsIntent {
+    /// Container id (default "detail" → `tab_detail`)
+    #[serde(default = "default_container_id")]
+    pub id: String,
+
+    pub tabs: Vec<TabIntent>,
+
+    /// Load the datasets of the other tabs when they are first shown
+    #[serde(default)]
+    pub lazy: bool,
+}
+
+fn default_container_id() -> String {
+    "detail".to_string()
+}
+
+/// One tab item and the fields on its panel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TabIntent {
+    /// Tab id (e.g. "basic", "address")
+    pub id: String,
+
+    pub title: String,
+
+    /// Dataset whose fields the tab shows
+    pub dataset_id: String,
+
+    /// Columns on the tab (empty: every column of the dataset)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+}
+
+impl TabIntent {
+    pub fn new(id: impl Into<String>, title: impl Into<String>, dataset_id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            dataset_id: dataset_id.into(),
+            columns: Vec::new(),
+        }
+    }
+
+    pub fn with_columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Panel inside the tab item holding the tab's fields
+    pub fn panel(&self) -> String {
+        format!("pnl_tab_{}", self.id)
+    }
+
+    /// Function that loads the tab's dataset the first time it is shown
+    pub fn load_function(&self) -> String {
+        format!("fn_load_tab_{}", self.id)
+    }
+}
+
+impl TabsIntent {
+    pub fn new(tabs: Vec<TabIntent>) -> Self {
+        Self {
+            id: default_container_id(),
+            tabs,
+            lazy: false,
+        }
+    }
+
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// The `<tab>` control
+    pub fn control(&self) -> String {
+        format!("tab_{}", self.id)
+    }
+
+    /// Tabs that load their dataset when first shown: with lazy loading, the
+    /// tabs bound to another dataset than the first tab's
+    pub fn lazy_tabs(&self) -> Vec<&TabIntent> {
+        let Some(first) = self.tabs.first().filter(|_| self.lazy) else {
+            return Vec::new();
+        };
+        self.tabs.iter().filter(|tab| tab.dataset_id != first.dataset_id).collect()
+    }
+
+    /// Datasets bound by the tabs, in tab order, without duplicates
+    pub fn dataset_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = Vec::new();
+        for tab in &self.tabs {
+            if !ids.contains(&tab.dataset_id.as_str()) {
+                ids.push(&tab.dataset_id);
+            }
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_tabs_skip_the_first_dataset() {
+        let tabs = TabsIntent::new(vec![
+            TabIntent::new("basic", "기본", "ds_customer"),
+            TabIntent::new("address", "주소", "ds_customer").with_columns(vec!["ADDR_1".to_string()]),
+            TabIntent::new("contract", "계약", "ds_contract"),
+        ]);
+        assert_eq!(tabs.control(), "tab_detail");
+        assert_eq!(tabs.dataset_ids(), ["ds_customer", "ds_contract"]);
+        assert!(tabs.lazy_tabs().is_empty());
+
+        let tabs = tabs.lazy();
+        let lazy: Vec<String> = tabs.lazy_tabs().iter().map(|t| t.load_function()).collect();
+        assert_eq!(lazy, ["fn_load_tab_contract"]);
+        assert_eq!(tabs.tabs[1].panel(), "pnl_tab_address");
+    }
+}
@@ -1,5 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use super::address::AddressIntent;
+use super::dashboard::DashboardIntent;
+use super::locale_format::ColumnFormat;
+use super::print::PrintIntent;
+use super::search_state::SearchStateIntent;
+use super::sensitive_data::SensitiveKind;
+use super::tabs::TabsIntent;
+use super::wizard::WizardIntent;
+
 /// Internal DSL for representing screen generation intent.
 /// This is the normalized representation that the prompt compiler uses.
 /// LLM receives structured intent, not raw input.
@@ -22,6 +31,34 @@ pub struct UiIntent {
 
     /// Additional notes or requirements
     pub notes: Option<String>,
+
+    /// KPI cards and charts (dashboard screens)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashboard: Option<DashboardIntent>,
+
+    /// Ordered steps (wizard screens)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wizard: Option<WizardIntent>,
+
+    /// Tab container grouping the fields (detail screens)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tabs: Option<TabsIntent>,
+
+    /// Postcode/address field groups with the postcode search popup
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<AddressIntent>,
+
+    /// Print button and report output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub print: Option<PrintIntent>,
+
+    /// Search conditions kept between visits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_state: Option<SearchStateIntent>,
+
+    /// Locale of the screen's labels and messages (`ko-KR` when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 impl UiIntent {
@@ -33,6 +70,13 @@ impl UiIntent {
             grids: Vec::new(),
             actions: Vec::new(),
             notes: None,
+            dashboard: None,
+            wizard: None,
+            tabs: None,
+            addresses: Vec::new(),
+            print: None,
+            search_state: None,
+            locale: None,
         }
     }
 
@@ -55,6 +99,61 @@ impl UiIntent {
         self.notes = Some(notes.into());
         self
     }
+
+    /// Copy of the intent without the request notes
+    pub fn without_notes(&self) -> Self {
+        Self { notes: None, ..self.clone() }
+    }
+
+    pub fn with_dashboard(mut self, dashboard: DashboardIntent) -> Self {
+        self.dashboard = Some(dashboard);
+        self
+    }
+
+    pub fn with_wizard(mut self, wizard: WizardIntent) -> Self {
+        self.wizard = Some(wizard);
+        self
+    }
+
+    pub fn with_tabs(mut self, tabs: TabsIntent) -> Self {
+        self.tabs = Some(tabs);
+        self
+    }
+
+    /// Print through a report tool (`print` option): adds the 인쇄 button
+    pub fn enable_print(&mut self, print: PrintIntent) {
+        if !self.actions.iter().any(|a| a.action_type == ActionType::Print) {
+            self.actions.push(ActionIntent::new("print", "인쇄", ActionType::Print));
+        }
+        self.print = Some(print);
+    }
+
+    /// Wizard steps: the declared ones, or one per dataset on a wizard screen
+    pub fn wizard_steps(&self) -> Option<WizardIntent> {
+        match (&self.wizard, self.screen_type) {
+            (Some(wizard), _) => Some(wizard.clone()),
+            (None, ScreenType::Wizard) if !self.datasets.is_empty() => {
+                Some(WizardIntent::from_datasets(&self.datasets))
+            }
+            _ => None,
+        }
+    }
+
+    /// Make every grid editable (`editable_grid` option)
+    pub fn enable_grid_editing(&mut self) {
+        for grid in &mut self.grids {
+            grid.editable = true;
+        }
+    }
+
+    /// Columns classified as personal data, across all datasets
+    pub fn sensitive_columns(&self) -> Vec<&ColumnIntent> {
+        self.datasets
+            .iter()
+            .flat_map(|ds| ds.columns.iter())
+            .filter(|c| c.sensitivity.is_some())
+            .collect()
+    }
 }
 
 /// Screen type classification
@@ -69,6 +168,10 @@ pub enum ScreenType {
     Popup,
     /// List with detail popup
     ListWithPopup,
+    /// KPI cards and charts over aggregate queries
+    Dashboard,
+    /// Multi-step input with one panel per step
+    Wizard,
 }
 
 impl ScreenType {
@@ -78,6 +181,8 @@ impl ScreenType {
             ScreenType::Detail => "detail",
             ScreenType::Popup => "popup",
             ScreenType::ListWithPopup => "list_with_popup",
+            ScreenType::Dashboard => "dashboard",
+            ScreenType::Wizard => "wizard",
         }
     }
 }
@@ -153,8 +258,28 @@ pub struct ColumnIntent {
     /// Maximum length (for strings)
     pub max_length: Option<u32>,
 
+    /// Digits after the decimal point in the schema (`DECIMAL(15,2)` → 2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>,
+
     /// Additional validation rules
     pub validation: Option<String>,
+
+    /// Personal data classification (display must be masked)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<SensitiveKind>,
+
+    /// Common code group filling a combo/radio (e.g. "CM001")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_group: Option<String>,
+
+    /// Date/number display format (see `LocaleFormats`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ColumnFormat>,
+
+    /// Column archetype the column was matched to (see `ColumnArchetype`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archetype: Option<String>,
 }
 
 impl ColumnIntent {
@@ -168,7 +293,12 @@ impl ColumnIntent {
             readonly: false,
             is_pk: false,
             max_length: None,
+            scale: None,
             validation: None,
+            sensitivity: None,
+            code_group: None,
+            format: None,
+            archetype: None,
         }
     }
 
@@ -203,6 +333,25 @@ impl ColumnIntent {
         self.max_length = Some(len);
         self
     }
+
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn with_sensitivity(mut self, kind: Option<SensitiveKind>) -> Self {
+        self.sensitivity = kind;
+        self
+    }
+
+    /// Fill the control from a common code group (shown as a combo unless radio)
+    pub fn with_code_group(mut self, code_group: impl Into<String>) -> Self {
+        self.code_group = Some(code_group.into());
+        if self.ui_type != UiType::Radio {
+            self.ui_type = UiType::Combo;
+        }
+        self
+    }
 }
 
 /// UI control type
@@ -300,6 +449,10 @@ pub struct GridIntent {
 
     /// Rows per page (if paginated)
     pub page_size: Option<u32>,
+
+    /// Server-side paging is mandatory (large table)
+    #[serde(default)]
+    pub requires_paging: bool,
 }
 
 impl GridIntent {
@@ -312,6 +465,7 @@ impl GridIntent {
             editable: false,
             paginated: true,
             page_size: Some(20),
+            requires_paging: false,
         }
     }
 
@@ -335,6 +489,14 @@ impl GridIntent {
         self.page_size = None;
         self
     }
+
+    /// Require server-side paging (page parameters and total count)
+    pub fn require_paging(mut self) -> Self {
+        self.paginated = true;
+        self.page_size = self.page_size.or(Some(20));
+        self.requires_paging = true;
+        self
+    }
 }
 
 /// Grid column display settings
@@ -493,5 +655,11 @@ pub fn default_actions_for_screen_type(screen_type: ScreenType) -> Vec<ActionInt
             ActionIntent::new("add", "신규", ActionType::Add),
             ActionIntent::new("delete", "삭제", ActionType::Delete),
         ],
+        ScreenType::Dashboard => vec![ActionIntent::new("refresh", "새로고침", ActionType::Search)],
+        ScreenType::Wizard => vec![
+            ActionIntent::new("prev", "이전", ActionType::Custom).at_bottom(),
+            ActionIntent::new("next", "다음", ActionType::Custom).at_bottom(),
+            ActionIntent::new("submit", "완료", ActionType::Save).at_bottom(),
+        ],
     }
 }
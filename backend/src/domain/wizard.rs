@@ -0,0 +1,101 @@
+//! Wizard Intent
+//!
+//! Ordered steps of a multi-step input screen. Each step is a panel bound to
+//! its own dataset and checked by its own validation function; only one panel
+//! is visible at a time, and the final submit sends every step dataset in a
+//! single transaction.
+//!
+//! Component and function names are derived from the step id, so the prompt
+//! and the Wizard Step Validator agree on them:
+//! `pnl_step_<id>`, `fn_validate_<id>`, and `fn_prev` / `fn_next` / `fn_submit`.
+
+use serde::{Deserialize, Serialize};
+
+use super::ui_intent::DatasetIntent;
+
+/// Navigation and submit functions of every wizard
+pub const WIZARD_FUNCTIONS: [&str; 3] = ["fn_prev", "fn_next", "fn_submit"];
+
+/// Steps of a wizard screen, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WizardIntent {
+    pub steps: Vec<WizardStep>,
+}
+
+/// One page of a wizard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WizardStep {
+    /// Step id (e.g. "basic", "address")
+    pub id: String,
+
+    pub title: String,
+
+    /// Dataset edited on this step
+    pub dataset_id: String,
+}
+
+impl WizardStep {
+    pub fn new(id: impl Into<String>, title: impl Into<String>, dataset_id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            dataset_id: dataset_id.into(),
+        }
+    }
+
+    /// Panel holding the step's fields
+    pub fn panel(&self) -> String {
+        format!("pnl_step_{}", self.id)
+    }
+
+    /// Function that checks the step's required fields before moving on
+    pub fn validate_function(&self) -> String {
+        format!("fn_validate_{}", self.id)
+    }
+}
+
+impl WizardIntent {
+    /// One step per dataset, in dataset order
+    pub fn from_datasets(datasets: &[DatasetIntent]) -> Self {
+        let steps = datasets
+            .iter()
+            .map(|ds| {
+                let id = ds.id.trim_start_matches("ds_").to_string();
+                let title = ds.table_name.clone().unwrap_or_else(|| id.clone());
+                WizardStep::new(id, title, &ds.id)
+            })
+            .collect();
+        Self { steps }
+    }
+
+    /// Datasets sent by the final submit, in step order, without duplicates
+    pub fn dataset_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = Vec::new();
+        for step in &self.steps {
+            if !ids.contains(&step.dataset_id.as_str()) {
+                ids.push(&step.dataset_id);
+            }
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_from_datasets() {
+        let datasets = vec![
+            DatasetIntent::new("ds_member").with_table("MEMBER"),
+            DatasetIntent::new("ds_member_address"),
+        ];
+        let wizard = WizardIntent::from_datasets(&datasets);
+
+        assert_eq!(wizard.steps.len(), 2);
+        assert_eq!(wizard.steps[0].title, "MEMBER");
+        assert_eq!(wizard.steps[1].panel(), "pnl_step_member_address");
+        assert_eq!(wizard.steps[1].validate_function(), "fn_validate_member_address");
+        assert_eq!(wizard.dataset_ids(), vec!["ds_member", "ds_member_address"]);
+    }
+}
@@ -1 +1,2 @@
+pub mod redaction;
 pub mod view_engine;
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use axum::{middleware::from_fn, Router as AxumRouter};
+use loco_rs::{
+    app::{AppContext, Initializer},
+    Result,
+};
+use sea_orm::EntityTrait;
+use tracing::info;
+
+use crate::{middleware::redaction::scrub_responses, models::_entities::llm_configs, services::redaction};
+
+/// Scrubs LLM details from API responses (see `services::redaction`)
+pub struct RedactionInitializer;
+
+#[async_trait]
+impl Initializer for RedactionInitializer {
+    fn name(&self) -> String {
+        "response-redaction".to_string()
+    }
+
+    async fn after_routes(&self, router: AxumRouter, ctx: &AppContext) -> Result<AxumRouter> {
+        let redactor = redaction::redactor();
+        if !redactor.enabled() {
+            info!("response redaction disabled");
+            return Ok(router);
+        }
+
+        // Configs not used since startup are still known to the redactor
+        for config in llm_configs::Entity::find().all(&ctx.db).await? {
+            redactor.remember(&config.model_name, config.endpoint_url.as_deref());
+        }
+        Ok(router.layer(from_fn(scrub_responses)))
+    }
+}
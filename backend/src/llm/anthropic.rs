@@ -1,8 +1,9 @@
-use super::LlmBackend;
+use super::stream::{spawn_http, Framing};
+use super::{GenParams, GenerationOutput, LlmBackend, StreamObserver, TokenStream, TokenUsage, DEFAULT_MAX_TOKENS};
 use async_trait::async_trait;
 use reqwest::Client;
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Anthropic API backend - Claude 3.5 Sonnet
 /// WARNING: Remote provider - use for development/testing only
@@ -41,6 +42,40 @@ impl AnthropicBackend {
             client: Client::new(),
         }
     }
+
+    /// Messages request body (the API's own temperature default applies when unset)
+    fn body(&self, prompt: &str, params: &GenParams) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "max_tokens": params.max_tokens_or(DEFAULT_MAX_TOKENS),
+            "messages": [{"role": "user", "content": prompt}]
+        });
+        if let Some(temperature) = params.temperature {
+            body["temperature"] = serde_json::json!(temperature);
+        }
+        params.add_sampling(&mut body, "stop_sequences");
+        body
+    }
+
+    /// Text of a message stream event
+    fn delta(event: &serde_json::Value) -> anyhow::Result<Option<String>> {
+        if event["type"] == "error" {
+            anyhow::bail!("Anthropic stream failed: {}", event["error"]["message"]);
+        }
+        Ok(event["delta"]["text"].as_str().map(str::to_string))
+    }
+
+    /// Token counts of a response, or of a `message_start` / `message_delta`
+    /// stream event
+    fn usage(event: &serde_json::Value) -> Option<TokenUsage> {
+        let usage = match event["type"].as_str() {
+            Some("message_start") => &event["message"]["usage"],
+            _ => &event["usage"],
+        };
+        usage
+            .is_object()
+            .then(|| TokenUsage::from_json(usage, "input_tokens", "output_tokens"))
+    }
 }
 
 #[async_trait]
@@ -53,13 +88,10 @@ impl LlmBackend for AnthropicBackend {
         &self.model
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let started = Instant::now();
         let url = format!("{}/messages", self.endpoint);
-        let body = serde_json::json!({
-            "model": self.model,
-            "max_tokens": 4096,
-            "messages": [{"role": "user", "content": prompt}]
-        });
+        let body = self.body(prompt, params);
 
         let response = self
             .client
@@ -79,10 +111,47 @@ impl LlmBackend for AnthropicBackend {
         }
 
         let result: serde_json::Value = response.json().await?;
-        Ok(result["content"][0]["text"]
+        let text = result["content"][0]["text"]
             .as_str()
             .unwrap_or("")
-            .to_string())
+            .to_string();
+        Ok(GenerationOutput::new(text, Self::usage(&result).unwrap_or_default(), started))
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        let started = Instant::now();
+        let url = format!("{}/messages", self.endpoint);
+        let mut body = self.body(prompt, params);
+        body["stream"] = serde_json::json!(true);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&body)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Anthropic request failed ({}): {}", status, text);
+        }
+
+        Ok(spawn_http(started, response, Framing::Sse, Self::delta, Self::usage))
+    }
+
+    async fn generate_observed_with(
+        &self,
+        prompt: &str,
+        params: &GenParams,
+        observer: &mut dyn StreamObserver,
+    ) -> anyhow::Result<GenerationOutput> {
+        // Dropping the stream on abort closes the connection, which stops generation
+        self.generate_stream_with(prompt, params).await?.observe(observer).await
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
@@ -0,0 +1,193 @@
+//! Fallback chain: a primary backend backed up by others
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+
+use super::stream::Watched;
+use super::{GenParams, GenerationOutput, LlmBackend, StreamAborted, StreamObserver, TokenStream};
+
+/// Tries its backends in order, moving on to the next one when a call fails
+///
+/// Once a backend fails (or fails its health check) the chain stays on the
+/// next one for later calls, so a generation and its retry do not wait for
+/// the same dead server twice. `name()` and `model()` report the backend that
+/// answered last, which is what generation logs record.
+pub struct FallbackBackend {
+    backends: Vec<Box<dyn LlmBackend>>,
+    /// Backend the next call starts with
+    current: AtomicUsize,
+}
+
+impl FallbackBackend {
+    pub fn new(primary: Box<dyn LlmBackend>, fallbacks: Vec<Box<dyn LlmBackend>>) -> Self {
+        let mut backends = vec![primary];
+        backends.extend(fallbacks);
+        Self { backends, current: AtomicUsize::new(0) }
+    }
+
+    /// Backends still to try, with their position in the chain
+    fn remaining(&self) -> impl Iterator<Item = (usize, &dyn LlmBackend)> {
+        let start = self.current.load(Ordering::Relaxed);
+        self.backends
+            .iter()
+            .enumerate()
+            .skip(start)
+            .map(|(i, backend)| (i, backend.as_ref()))
+    }
+
+    fn answered(&self, index: usize) {
+        self.current.store(index, Ordering::Relaxed);
+    }
+
+    /// Note a failed call; the next call starts after `index`
+    fn failed(&self, index: usize, backend: &dyn LlmBackend, error: &anyhow::Error) {
+        if index + 1 < self.backends.len() {
+            tracing::warn!(
+                "LLM backend {} ({}) failed, falling back to the next one: {}",
+                backend.name(),
+                backend.model(),
+                error
+            );
+            self.current.store(index + 1, Ordering::Relaxed);
+        }
+    }
+
+    fn exhausted(&self, last: Option<anyhow::Error>) -> anyhow::Error {
+        match last {
+            Some(e) if self.backends.len() > 1 => {
+                anyhow::anyhow!("All {} LLM backends failed, last error: {}", self.backends.len(), e)
+            }
+            Some(e) => e,
+            None => anyhow::anyhow!("No LLM backend left to try"),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmBackend for FallbackBackend {
+    fn name(&self) -> &str {
+        self.backends[self.current.load(Ordering::Relaxed)].name()
+    }
+
+    fn model(&self) -> &str {
+        self.backends[self.current.load(Ordering::Relaxed)].model()
+    }
+
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let mut last = None;
+        for (i, backend) in self.remaining() {
+            match backend.generate_with(prompt, params).await {
+                Ok(output) => {
+                    self.answered(i);
+                    return Ok(output);
+                }
+                Err(e) => {
+                    self.failed(i, backend, &e);
+                    last = Some(e);
+                }
+            }
+        }
+        Err(self.exhausted(last))
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        let mut last = None;
+        for (i, backend) in self.remaining() {
+            match backend.generate_stream_with(prompt, params).await {
+                Ok(stream) => {
+                    self.answered(i);
+                    return Ok(stream);
+                }
+                Err(e) => {
+                    self.failed(i, backend, &e);
+                    last = Some(e);
+                }
+            }
+        }
+        Err(self.exhausted(last))
+    }
+
+    async fn generate_observed_with(
+        &self,
+        prompt: &str,
+        params: &GenParams,
+        observer: &mut dyn StreamObserver,
+    ) -> anyhow::Result<GenerationOutput> {
+        let mut last = None;
+        for (i, backend) in self.remaining() {
+            let mut watched = Watched::new(&mut *observer);
+            match backend.generate_observed_with(prompt, params, &mut watched).await {
+                Ok(output) => {
+                    self.answered(i);
+                    return Ok(output);
+                }
+                // The observer has seen part of a response: it cannot start over
+                Err(e) if e.is::<StreamAborted>() || watched.received() > 0 => {
+                    self.answered(i);
+                    return Err(e);
+                }
+                Err(e) => {
+                    self.failed(i, backend, &e);
+                    last = Some(e);
+                }
+            }
+        }
+        Err(self.exhausted(last))
+    }
+
+    async fn health_check(&self) -> anyhow::Result<()> {
+        let mut last = None;
+        for (i, backend) in self.remaining() {
+            match backend.health_check().await {
+                Ok(()) => {
+                    self.answered(i);
+                    return Ok(());
+                }
+                Err(e) => {
+                    self.failed(i, backend, &e);
+                    last = Some(e);
+                }
+            }
+        }
+        Err(self.exhausted(last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{MockLlmBackend, MockResponse};
+
+    fn chain() -> (FallbackBackend, MockLlmBackend, MockLlmBackend) {
+        let primary = MockLlmBackend::failing("connection refused");
+        let secondary = MockLlmBackend::with_responses(vec![MockResponse::Success("<screen/>".to_string())]);
+        let backend = FallbackBackend::new(Box::new(primary.clone()), vec![Box::new(secondary.clone())]);
+        (backend, primary, secondary)
+    }
+
+    #[tokio::test]
+    async fn test_fallback_answers_and_sticks_to_next_backend() {
+        let (backend, primary, secondary) = chain();
+
+        assert_eq!(backend.generate("prompt").await.unwrap().text, "<screen/>");
+        assert_eq!(backend.generate("prompt").await.unwrap().text, "<screen/>");
+        // The failed primary is not called again
+        assert_eq!(primary.call_count(), 1);
+        assert_eq!(secondary.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_health_check_skips_unhealthy_primary() {
+        let secondary = MockLlmBackend::new();
+        let backend = FallbackBackend::new(Box::new(MockLlmBackend::unhealthy()), vec![Box::new(secondary.clone())]);
+
+        assert!(backend.health_check().await.is_ok());
+        backend.generate("prompt").await.unwrap();
+        assert_eq!(secondary.call_count(), 1);
+
+        let dead = FallbackBackend::new(Box::new(MockLlmBackend::unhealthy()), vec![Box::new(MockLlmBackend::unhealthy())]);
+        let error = dead.health_check().await.unwrap_err();
+        assert!(error.to_string().starts_with("All 2 LLM backends failed"));
+    }
+}
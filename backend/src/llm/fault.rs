@@ -0,0 +1,51 @@
+//! Fault-injecting LLM backend (`fault-injection` feature only)
+
+use async_trait::async_trait;
+
+use super::{GenParams, GenerationOutput, LlmBackend};
+use crate::services::fault_injection::FaultInjector;
+
+/// Wraps a backend and delays, fails or corrupts calls per the fault settings
+pub struct FaultInjectingBackend {
+    inner: Box<dyn LlmBackend>,
+}
+
+impl FaultInjectingBackend {
+    pub fn new(inner: Box<dyn LlmBackend>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl LlmBackend for FaultInjectingBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        if let Some(delay) = FaultInjector::llm_delay() {
+            tracing::warn!("Fault injection: delaying LLM call by {}ms", delay.as_millis());
+            tokio::time::sleep(delay).await;
+        }
+        if FaultInjector::llm_error() {
+            tracing::warn!("Fault injection: failing LLM call");
+            anyhow::bail!("Injected fault: LLM request failed");
+        }
+
+        let mut output = self.inner.generate_with(prompt, params).await?;
+
+        if let Some(corruption) = FaultInjector::llm_corruption() {
+            tracing::warn!("Fault injection: corrupting LLM output ({:?})", corruption);
+            output.text = corruption.apply(&output.text);
+        }
+        Ok(output)
+    }
+
+    async fn health_check(&self) -> anyhow::Result<()> {
+        self.inner.health_check().await
+    }
+}
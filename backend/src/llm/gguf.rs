@@ -0,0 +1,377 @@
+//! GGUF Metadata Reader
+//!
+//! Reads the header of a GGUF model file (no llama.cpp needed) to get the
+//! training context, parameter count and quantization, and turns them into
+//! recommendations for the local-llama-cpp configuration.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Context the v3 screen prompts need (≈14KB template + intent + KB + output)
+pub const V3_PROMPT_MIN_CONTEXT: u32 = 8192;
+
+/// Largest context the admin form accepts
+pub const MAX_CONTEXT_SIZE: u32 = 32768;
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+
+/// Model facts read from a GGUF header
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GgufInfo {
+    pub architecture: Option<String>,
+    pub name: Option<String>,
+    /// `{arch}.context_length` (n_ctx_train)
+    pub context_length: Option<u32>,
+    /// `general.file_type` (llama_ftype)
+    pub file_type: Option<u32>,
+    /// Sum of all tensor element counts
+    pub parameter_count: Option<u64>,
+}
+
+impl GgufInfo {
+    /// Read the metadata of a GGUF file
+    pub fn read(path: &Path) -> Result<Self> {
+        let mut reader = GgufReader(BufReader::new(File::open(path)?));
+
+        let mut magic = [0u8; 4];
+        reader.0.read_exact(&mut magic)?;
+        if &magic != GGUF_MAGIC {
+            bail!("Not a GGUF file: {:?}", path);
+        }
+        let version = reader.u32()?;
+        if version < 2 {
+            bail!("Unsupported GGUF version {}", version);
+        }
+        let tensor_count = reader.u64()?;
+        let kv_count = reader.u64()?;
+
+        let mut info = GgufInfo::default();
+        let mut context_lengths: Vec<(String, u64)> = Vec::new();
+        for _ in 0..kv_count {
+            let key = reader.string()?;
+            let value_type = reader.u32()?;
+            match key.as_str() {
+                "general.architecture" if value_type == 8 => info.architecture = Some(reader.string()?),
+                "general.name" if value_type == 8 => info.name = Some(reader.string()?),
+                "general.file_type" => info.file_type = reader.integer(value_type)?.map(|v| v as u32),
+                k if k.ends_with(".context_length") => {
+                    if let Some(v) = reader.integer(value_type)? {
+                        context_lengths.push((k.trim_end_matches(".context_length").to_string(), v));
+                    }
+                }
+                _ => reader.skip_value(value_type)?,
+            }
+        }
+        info.context_length = context_lengths
+            .iter()
+            .find(|(arch, _)| Some(arch) == info.architecture.as_ref())
+            .or(context_lengths.first())
+            .map(|(_, v)| (*v).min(u32::MAX as u64) as u32);
+
+        let mut parameters: u64 = 0;
+        for _ in 0..tensor_count {
+            reader.string()?;
+            let n_dims = reader.u32()?;
+            let mut elements: u64 = 1;
+            for _ in 0..n_dims {
+                elements = elements.saturating_mul(reader.u64()?);
+            }
+            reader.u32()?; // ggml type
+            reader.u64()?; // offset
+            parameters = parameters.saturating_add(elements);
+        }
+        info.parameter_count = (tensor_count > 0).then_some(parameters);
+
+        Ok(info)
+    }
+
+    /// Quantization name from `general.file_type`
+    pub fn quantization(&self) -> Option<&'static str> {
+        let name = match self.file_type? {
+            0 => "F32",
+            1 => "F16",
+            2 => "Q4_0",
+            3 => "Q4_1",
+            7 => "Q8_0",
+            8 => "Q5_0",
+            9 => "Q5_1",
+            10 => "Q2_K",
+            11 => "Q3_K_S",
+            12 => "Q3_K_M",
+            13 => "Q3_K_L",
+            14 => "Q4_K_S",
+            15 => "Q4_K_M",
+            16 => "Q5_K_S",
+            17 => "Q5_K_M",
+            18 => "Q6_K",
+            19 => "IQ2_XXS",
+            20 => "IQ2_XS",
+            21 => "Q2_K_S",
+            22 => "IQ3_XS",
+            23 => "IQ3_XXS",
+            24 => "IQ1_S",
+            25 => "IQ4_NL",
+            26 => "IQ3_S",
+            27 => "IQ3_M",
+            28 => "IQ2_S",
+            29 => "IQ2_M",
+            30 => "IQ4_XS",
+            31 => "IQ1_M",
+            32 => "BF16",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// Parameter count as `7.2B` / `350M`
+    pub fn parameter_label(&self) -> Option<String> {
+        let count = self.parameter_count? as f64;
+        Some(if count >= 1e9 {
+            format!("{:.1}B", count / 1e9)
+        } else {
+            format!("{:.0}M", count / 1e6)
+        })
+    }
+
+    /// Context size to use when none is configured
+    pub fn recommended_context(&self) -> Option<u32> {
+        self.context_length.map(|n| n.min(MAX_CONTEXT_SIZE))
+    }
+
+    /// Recommendations for a configured context size (`Warning:` / `Note:` prefixed)
+    pub fn advise(&self, configured_ctx: Option<u32>) -> Vec<String> {
+        let mut advice = Vec::new();
+
+        match (configured_ctx, self.context_length) {
+            (Some(configured), Some(trained)) if configured > trained => advice.push(format!(
+                "Warning: Context size {} exceeds the model's training context {}; output degrades past {} tokens",
+                configured, trained, trained
+            )),
+            (None, Some(_)) => advice.push(format!(
+                "Note: No context size configured; {} (from the model) will be used",
+                self.recommended_context().unwrap_or_default()
+            )),
+            _ => {}
+        }
+
+        if let Some(trained) = self.context_length.filter(|n| *n < V3_PROMPT_MIN_CONTEXT) {
+            advice.push(format!(
+                "Warning: Training context {} is below the ~{} tokens the v3 screen prompts need",
+                trained, V3_PROMPT_MIN_CONTEXT
+            ));
+        } else if let Some(configured) = configured_ctx.filter(|n| *n < V3_PROMPT_MIN_CONTEXT) {
+            advice.push(format!(
+                "Note: Context size {} may truncate v3 screen prompts; {} or more is recommended",
+                configured, V3_PROMPT_MIN_CONTEXT
+            ));
+        }
+
+        if let Some(label) = self.parameter_label() {
+            let billions = self.parameter_count.unwrap_or_default() as f64 / 1e9;
+            if billions < 3.0 {
+                advice.push(format!(
+                    "Warning: {} parameters is unlikely to follow the large v3 prompts; use a 7B+ model",
+                    label
+                ));
+            } else if billions < 7.0 {
+                advice.push(format!("Note: {} parameters may miss v3 prompt rules; 7B+ is recommended", label));
+            }
+        }
+
+        if let Some(quant) = self.quantization() {
+            let aggressive = quant.starts_with("Q2") || quant.starts_with("Q3") || quant.starts_with("IQ1")
+                || quant.starts_with("IQ2") || quant.starts_with("IQ3");
+            if aggressive {
+                advice.push(format!(
+                    "Warning: {} quantization degrades instruction following; prefer Q4_K_M or better",
+                    quant
+                ));
+            }
+        }
+
+        advice
+    }
+}
+
+/// Little-endian GGUF primitives
+struct GgufReader<R>(R);
+
+impl<R: Read + Seek> GgufReader<R> {
+    fn u32(&mut self) -> Result<u32> {
+        let mut buf = [0u8; 4];
+        self.0.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut buf = [0u8; 8];
+        self.0.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u64()?;
+        if len > 1 << 20 {
+            bail!("GGUF string too long ({} bytes)", len);
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.0.read_exact(&mut buf)?;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// Read an unsigned/signed integer value; other types are skipped
+    fn integer(&mut self, value_type: u32) -> Result<Option<u64>> {
+        let value = match value_type {
+            0 | 1 => {
+                let mut buf = [0u8; 1];
+                self.0.read_exact(&mut buf)?;
+                buf[0] as u64
+            }
+            2 | 3 => {
+                let mut buf = [0u8; 2];
+                self.0.read_exact(&mut buf)?;
+                u16::from_le_bytes(buf) as u64
+            }
+            4 | 5 => self.u32()? as u64,
+            10 | 11 => self.u64()?,
+            _ => {
+                self.skip_value(value_type)?;
+                return Ok(None);
+            }
+        };
+        Ok(Some(value))
+    }
+
+    fn skip_value(&mut self, value_type: u32) -> Result<()> {
+        match value_type {
+            8 => {
+                let len = self.u64()?;
+                self.0.seek(SeekFrom::Current(len as i64))?;
+            }
+            9 => {
+                let item_type = self.u32()?;
+                let count = self.u64()?;
+                match Self::fixed_size(item_type) {
+                    Some(size) => {
+                        self.0.seek(SeekFrom::Current((size * count) as i64))?;
+                    }
+                    None => {
+                        for _ in 0..count {
+                            self.skip_value(item_type)?;
+                        }
+                    }
+                }
+            }
+            t => match Self::fixed_size(t) {
+                Some(size) => {
+                    self.0.seek(SeekFrom::Current(size as i64))?;
+                }
+                None => bail!("Unknown GGUF value type {}", t),
+            },
+        }
+        Ok(())
+    }
+
+    fn fixed_size(value_type: u32) -> Option<u64> {
+        match value_type {
+            0 | 1 | 7 => Some(1),
+            2 | 3 => Some(2),
+            4..=6 => Some(4),
+            10..=12 => Some(8),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal GGUF: header, a few KVs (incl. a string array) and tensor infos
+    fn write_gguf(path: &Path, context_length: u32, file_type: u32, tensors: &[&[u64]]) {
+        fn string(buf: &mut Vec<u8>, s: &str) {
+            buf.extend((s.len() as u64).to_le_bytes());
+            buf.extend(s.as_bytes());
+        }
+        let mut buf = Vec::new();
+        buf.extend(GGUF_MAGIC);
+        buf.extend(3u32.to_le_bytes());
+        buf.extend((tensors.len() as u64).to_le_bytes());
+        buf.extend(4u64.to_le_bytes());
+
+        string(&mut buf, "general.architecture");
+        buf.extend(8u32.to_le_bytes());
+        string(&mut buf, "llama");
+        string(&mut buf, "tokenizer.ggml.tokens");
+        buf.extend(9u32.to_le_bytes());
+        buf.extend(8u32.to_le_bytes());
+        buf.extend(2u64.to_le_bytes());
+        string(&mut buf, "<s>");
+        string(&mut buf, "</s>");
+        string(&mut buf, "llama.context_length");
+        buf.extend(4u32.to_le_bytes());
+        buf.extend(context_length.to_le_bytes());
+        string(&mut buf, "general.file_type");
+        buf.extend(4u32.to_le_bytes());
+        buf.extend(file_type.to_le_bytes());
+
+        for (i, dims) in tensors.iter().enumerate() {
+            string(&mut buf, &format!("blk.{}.weight", i));
+            buf.extend((dims.len() as u32).to_le_bytes());
+            for d in *dims {
+                buf.extend(d.to_le_bytes());
+            }
+            buf.extend(0u32.to_le_bytes());
+            buf.extend(0u64.to_le_bytes());
+        }
+        std::fs::write(path, buf).unwrap();
+    }
+
+    #[test]
+    fn test_read_metadata() {
+        let path = std::env::temp_dir().join("coder_gguf_test_read.gguf");
+        write_gguf(&path, 16384, 15, &[&[4096, 100_000], &[4096, 4096, 400]]);
+        let info = GgufInfo::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(info.architecture.as_deref(), Some("llama"));
+        assert_eq!(info.context_length, Some(16384));
+        assert_eq!(info.quantization(), Some("Q4_K_M"));
+        assert_eq!(info.parameter_count, Some(4096 * 100_000 + 4096 * 4096 * 400));
+        assert_eq!(info.parameter_label().as_deref(), Some("7.1B"));
+        assert_eq!(info.recommended_context(), Some(16384));
+        assert!(info.advise(Some(8192)).is_empty());
+    }
+
+    #[test]
+    fn test_not_gguf() {
+        let path = std::env::temp_dir().join("coder_gguf_test_invalid.gguf");
+        std::fs::write(&path, b"not a model").unwrap();
+        assert!(GgufInfo::read(&path).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_advise() {
+        let info = GgufInfo {
+            context_length: Some(4096),
+            file_type: Some(11),
+            parameter_count: Some(1_500_000_000),
+            ..Default::default()
+        };
+        let advice = info.advise(Some(8192));
+        assert_eq!(advice.len(), 4);
+        assert!(advice[0].contains("exceeds the model's training context 4096"));
+        assert!(advice[1].contains("below the ~8192 tokens"));
+        assert!(advice[2].contains("1.5B parameters"));
+        assert!(advice[3].contains("Q3_K_S"));
+
+        let info = GgufInfo { context_length: Some(131072), ..Default::default() };
+        assert_eq!(info.recommended_context(), Some(MAX_CONTEXT_SIZE));
+        assert_eq!(info.advise(Some(4096)).len(), 1);
+        assert!(info.advise(None)[0].starts_with("Note: No context size configured; 32768"));
+    }
+}
@@ -1,8 +1,8 @@
-use super::LlmBackend;
+use super::{GenParams, GenerationOutput, LlmBackend, TokenUsage, DEFAULT_MAX_TOKENS, DEFAULT_TEMPERATURE};
 use async_trait::async_trait;
 use reqwest::Client;
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Groq API backend - Fast inference, free tier available
 /// WARNING: Remote provider - use for development/testing only
@@ -53,14 +53,16 @@ impl LlmBackend for GroqBackend {
         &self.model
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let started = Instant::now();
         let url = format!("{}/chat/completions", self.endpoint);
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": self.model,
             "messages": [{"role": "user", "content": prompt}],
-            "max_tokens": 4096,
-            "temperature": 0.7
+            "max_tokens": params.max_tokens_or(DEFAULT_MAX_TOKENS),
+            "temperature": params.temperature_or(DEFAULT_TEMPERATURE)
         });
+        params.add_sampling(&mut body, "stop");
 
         let response = self
             .client
@@ -78,10 +80,11 @@ impl LlmBackend for GroqBackend {
         }
 
         let result: serde_json::Value = response.json().await?;
-        Ok(result["choices"][0]["message"]["content"]
+        let text = result["choices"][0]["message"]["content"]
             .as_str()
             .unwrap_or("")
-            .to_string())
+            .to_string();
+        Ok(GenerationOutput::new(text, TokenUsage::from_openai(&result).unwrap_or_default(), started))
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
@@ -1,8 +1,8 @@
-use super::LlmBackend;
+use super::{GenParams, GenerationOutput, LlmBackend, TokenUsage, DEFAULT_MAX_TOKENS, DEFAULT_TEMPERATURE};
 use async_trait::async_trait;
 use reqwest::Client;
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// llama.cpp server backend - OpenAI-compatible API
 pub struct LlamaCppBackend {
@@ -48,15 +48,17 @@ impl LlmBackend for LlamaCppBackend {
         &self.model
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let started = Instant::now();
         // llama.cpp server uses OpenAI-compatible /v1/completions
         let url = format!("{}/v1/completions", self.endpoint);
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": self.model,
             "prompt": prompt,
-            "max_tokens": 4096,
-            "temperature": 0.7
+            "max_tokens": params.max_tokens_or(DEFAULT_MAX_TOKENS),
+            "temperature": params.temperature_or(DEFAULT_TEMPERATURE)
         });
+        params.add_sampling(&mut body, "stop");
 
         let response = self
             .client
@@ -73,10 +75,11 @@ impl LlmBackend for LlamaCppBackend {
         }
 
         let result: serde_json::Value = response.json().await?;
-        Ok(result["choices"][0]["text"]
+        let text = result["choices"][0]["text"]
             .as_str()
             .unwrap_or("")
-            .to_string())
+            .to_string();
+        Ok(GenerationOutput::new(text, TokenUsage::from_openai(&result).unwrap_or_default(), started))
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
@@ -0,0 +1,131 @@
+//! Local embedding model using llama.cpp native Rust bindings
+//!
+//! Runs a GGUF sentence encoder (e.g. bge-small, nomic-embed-text,
+//! multilingual-e5-small) in-process, so knowledge retrieval needs no remote
+//! embedding API. Shares the llama.cpp backend with `LocalLlamaCppBackend`.
+//!
+//! Enable with: cargo build --features local-llm
+
+use std::path::PathBuf;
+
+#[cfg(feature = "local-llm")]
+use std::num::NonZeroU32;
+#[cfg(feature = "local-llm")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "local-llm")]
+use llama_cpp_2::{
+    context::params::LlamaContextParams,
+    llama_batch::LlamaBatch,
+    model::{params::LlamaModelParams, AddBos, LlamaModel},
+};
+#[cfg(feature = "local-llm")]
+use tracing::info;
+
+#[cfg(feature = "local-llm")]
+use super::local_llama_cpp::get_or_init_backend;
+
+/// In-process GGUF embedding model (loaded on first use)
+pub struct LocalEmbedder {
+    model_path: PathBuf,
+    #[allow(dead_code)]
+    n_ctx: u32,
+    #[allow(dead_code)]
+    n_threads: u32,
+    #[cfg(feature = "local-llm")]
+    model: Arc<Mutex<Option<LlamaModel>>>,
+}
+
+// LlamaModel is Send but not Sync, we handle thread safety via Mutex
+#[cfg(feature = "local-llm")]
+unsafe impl Send for LocalEmbedder {}
+#[cfg(feature = "local-llm")]
+unsafe impl Sync for LocalEmbedder {}
+
+impl LocalEmbedder {
+    pub fn new(model_path: PathBuf, n_ctx: u32, n_threads: u32) -> Self {
+        Self {
+            model_path,
+            n_ctx,
+            n_threads,
+            #[cfg(feature = "local-llm")]
+            model: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Get model file name
+    pub fn model_name(&self) -> &str {
+        self.model_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+    }
+
+    /// Embedding of `text` (blocking; truncated to the context size)
+    #[cfg(feature = "local-llm")]
+    pub fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let backend = get_or_init_backend().map_err(|e| anyhow::anyhow!("Backend init failed: {}", e))?;
+
+        let mut model_guard = self
+            .model
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire model lock: {}", e))?;
+        if model_guard.is_none() {
+            if !self.model_path.exists() {
+                anyhow::bail!("Embedding model file not found: {:?}", self.model_path);
+            }
+            info!("Loading local embedding model: {:?}", self.model_path);
+            let model = LlamaModel::load_from_file(backend, &self.model_path, &LlamaModelParams::default())
+                .map_err(|e| anyhow::anyhow!("Failed to load embedding model: {}", e))?;
+            *model_guard = Some(model);
+        }
+        let model = model_guard
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Embedding model not loaded"))?;
+
+        let ctx_params = LlamaContextParams::default()
+            .with_n_ctx(NonZeroU32::new(self.n_ctx))
+            .with_n_batch(self.n_ctx)
+            .with_n_ubatch(self.n_ctx)
+            .with_n_threads(self.n_threads as i32)
+            .with_n_threads_batch(self.n_threads as i32)
+            .with_embeddings(true);
+        let mut ctx = model
+            .new_context(backend, ctx_params)
+            .map_err(|e| anyhow::anyhow!("Failed to create embedding context: {}", e))?;
+
+        let mut tokens = model
+            .str_to_token(text, AddBos::Always)
+            .map_err(|e| anyhow::anyhow!("Failed to tokenize text: {}", e))?;
+        tokens.truncate(self.n_ctx as usize);
+
+        let mut batch = LlamaBatch::new(tokens.len(), 1);
+        batch
+            .add_sequence(&tokens, 0, false)
+            .map_err(|e| anyhow::anyhow!("Failed to add tokens to batch: {}", e))?;
+        ctx.decode(&mut batch)
+            .map_err(|e| anyhow::anyhow!("Failed to encode text: {}", e))?;
+
+        let embedding = ctx
+            .embeddings_seq_ith(0)
+            .map_err(|e| anyhow::anyhow!("Failed to read embedding: {}", e))?;
+        Ok(embedding.to_vec())
+    }
+
+    #[cfg(not(feature = "local-llm"))]
+    pub fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let _ = text;
+        anyhow::bail!("Local embedding model not enabled. Build with: cargo build --features local-llm")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_name() {
+        let embedder = LocalEmbedder::new(PathBuf::from("/models/bge-small-en-v1.5-q8_0.gguf"), 512, 4);
+        assert_eq!(embedder.model_name(), "bge-small-en-v1.5-q8_0.gguf");
+    }
+}
@@ -5,16 +5,16 @@
 //!
 //! Enable with: cargo build --features local-llm
 
-use super::LlmBackend;
+use super::{GenParams, GenerationOutput, GgufInfo, LlmBackend, TokenStream};
 use async_trait::async_trait;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "local-llm")]
 use std::sync::Arc;
 
 #[cfg(feature = "local-llm")]
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 #[cfg(feature = "local-llm")]
 use llama_cpp_2::{
@@ -23,7 +23,6 @@ use llama_cpp_2::{
     llama_batch::LlamaBatch,
     model::{params::LlamaModelParams, AddBos, LlamaModel, Special},
     sampling::LlamaSampler,
-    token::LlamaToken,
 };
 
 #[cfg(feature = "local-llm")]
@@ -32,13 +31,19 @@ use std::num::NonZeroU32;
 #[cfg(feature = "local-llm")]
 use std::sync::{Mutex, OnceLock};
 
+#[cfg(feature = "local-llm")]
+use std::time::Instant;
+
+#[cfg(feature = "local-llm")]
+use super::{stream::Chunk, TokenUsage};
+
 /// Global backend instance - llama.cpp backend can only be initialized once per process
 #[cfg(feature = "local-llm")]
 static LLAMA_BACKEND: OnceLock<Result<LlamaBackend, String>> = OnceLock::new();
 
 /// Initialize or get the global llama backend
 #[cfg(feature = "local-llm")]
-fn get_or_init_backend() -> Result<&'static LlamaBackend, String> {
+pub(super) fn get_or_init_backend() -> Result<&'static LlamaBackend, String> {
     let result = LLAMA_BACKEND.get_or_init(|| {
         info!("Initializing global llama.cpp backend");
         LlamaBackend::init().map_err(|e| format!("Failed to initialize llama backend: {}", e))
@@ -50,6 +55,20 @@ fn get_or_init_backend() -> Result<&'static LlamaBackend, String> {
     }
 }
 
+/// Complete UTF-8 text at the start of `pending` (a token may end inside a
+/// character); invalid bytes are replaced
+#[cfg(feature = "local-llm")]
+fn take_utf8(pending: &mut Vec<u8>) -> String {
+    let valid = match std::str::from_utf8(pending) {
+        Ok(text) => text.len(),
+        // Incomplete character at the end: keep it for the next token
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => return String::from_utf8_lossy(&std::mem::take(pending)).into_owned(),
+    };
+    let bytes: Vec<u8> = pending.drain(..valid).collect();
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
 /// Local LLM Backend using native llama.cpp bindings
 ///
 /// Unlike `LlamaCppBackend` which requires a separate llama-server,
@@ -111,7 +130,7 @@ impl LocalLlamaCppBackend {
     ///
     /// Environment variables:
     /// - LLM_MODEL_PATH: Path to GGUF model file (required)
-    /// - LLM_CONTEXT_SIZE: Context window size (default: 4096)
+    /// - LLM_CONTEXT_SIZE: Context window size (default: the model's training context, else 4096)
     /// - LLM_THREADS: Number of CPU threads (default: 4)
     /// - LLM_MAX_TOKENS: Max tokens to generate (default: 4096)
     /// - LLM_TEMPERATURE: Sampling temperature (default: 0.7)
@@ -123,6 +142,7 @@ impl LocalLlamaCppBackend {
         let n_ctx = env::var("LLM_CONTEXT_SIZE")
             .ok()
             .and_then(|s| s.parse().ok())
+            .or_else(|| Self::detect_context_size(&model_path))
             .unwrap_or(4096);
 
         let n_threads = env::var("LLM_THREADS")
@@ -164,6 +184,11 @@ impl LocalLlamaCppBackend {
         self.model_path.exists()
     }
 
+    /// Context size from the GGUF header (training context, capped)
+    pub fn detect_context_size(model_path: &Path) -> Option<u32> {
+        GgufInfo::read(model_path).ok()?.recommended_context()
+    }
+
     /// Load the model (lazy loading, blocking)
     #[cfg(feature = "local-llm")]
     fn ensure_loaded_sync(&self) -> anyhow::Result<()> {
@@ -183,6 +208,18 @@ impl LocalLlamaCppBackend {
         }
 
         info!("Loading local model: {:?}", self.model_path);
+        match GgufInfo::read(&self.model_path) {
+            Ok(gguf) => {
+                for advice in gguf.advise(Some(self.n_ctx)) {
+                    if advice.starts_with("Warning:") {
+                        warn!("{}", advice);
+                    } else {
+                        info!("{}", advice);
+                    }
+                }
+            }
+            Err(e) => warn!("Could not read GGUF metadata: {}", e),
+        }
         info!(
             "Config: n_ctx={}, n_threads={}, max_tokens={}, temperature={}",
             self.n_ctx, self.n_threads, self.max_tokens, self.temperature
@@ -213,8 +250,17 @@ impl LocalLlamaCppBackend {
     }
 
     /// Generate text using the local model (blocking)
+    ///
+    /// `on_text` gets each piece of text as it is decoded; returning `false`
+    /// stops generation, as does one of the `stop` sequences (left out of the
+    /// text). Returns the text with the prompt and generated token counts.
     #[cfg(feature = "local-llm")]
-    fn generate_sync(&self, prompt: &str) -> anyhow::Result<String> {
+    fn generate_sync(
+        &self,
+        prompt: &str,
+        stop: &[String],
+        on_text: &mut dyn FnMut(&str) -> bool,
+    ) -> anyhow::Result<(String, TokenUsage)> {
         self.ensure_loaded_sync()?;
 
         debug!(
@@ -294,8 +340,10 @@ impl LocalLlamaCppBackend {
             LlamaSampler::greedy(),
         ]);
 
-        // Generate tokens
-        let mut output_tokens: Vec<LlamaToken> = Vec::new();
+        // Generate tokens, decoding them as they come (a character may span tokens)
+        let mut n_generated = 0usize;
+        let mut output = String::new();
+        let mut pending: Vec<u8> = Vec::new();
         let mut n_cur = prompt_token_count as i32;
         let max_pos = prompt_token_count as i32 + max_tokens as i32;
         let mut last_batch_size = prompt_token_count;
@@ -309,7 +357,28 @@ impl LocalLlamaCppBackend {
                 break;
             }
 
-            output_tokens.push(new_token_id);
+            n_generated += 1;
+            let token_bytes = model
+                .token_to_bytes(new_token_id, Special::Plaintext)
+                .map_err(|e| anyhow::anyhow!("Failed to convert token to bytes: {}", e))?;
+            pending.extend_from_slice(&token_bytes);
+            let text = take_utf8(&mut pending);
+            if !text.is_empty() {
+                let start = output.len();
+                output.push_str(&text);
+                if let Some(at) = stop.iter().filter_map(|s| output.find(s.as_str())).min() {
+                    if at > start {
+                        on_text(&output[start..at]);
+                    }
+                    output.truncate(at);
+                    debug!("Stop sequence reached");
+                    break;
+                }
+                if !on_text(&text) {
+                    debug!("Generation stopped by the reader");
+                    break;
+                }
+            }
 
             gen_batch.clear();
             gen_batch
@@ -323,21 +392,20 @@ impl LocalLlamaCppBackend {
                 .map_err(|e| anyhow::anyhow!("Failed to decode token: {}", e))?;
         }
 
-        debug!("Generated {} tokens", output_tokens.len());
+        debug!("Generated {} tokens", n_generated);
 
-        // Convert tokens to string
-        let mut output_bytes: Vec<u8> = Vec::new();
-        for token in &output_tokens {
-            let token_bytes = model
-                .token_to_bytes(*token, Special::Plaintext)
-                .map_err(|e| anyhow::anyhow!("Failed to convert token to bytes: {}", e))?;
-            output_bytes.extend_from_slice(&token_bytes);
+        if !pending.is_empty() {
+            let rest = String::from_utf8_lossy(&pending).into_owned();
+            output.push_str(&rest);
+            on_text(&rest);
         }
-
-        let output = String::from_utf8_lossy(&output_bytes).into_owned();
         debug!("Generated output: {} chars", output.len());
 
-        Ok(output)
+        let usage = TokenUsage::new(
+            u32::try_from(prompt_token_count).ok(),
+            u32::try_from(n_generated).ok(),
+        );
+        Ok((output, usage))
     }
 }
 
@@ -351,20 +419,22 @@ impl LlmBackend for LocalLlamaCppBackend {
         self.model_name()
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
         #[cfg(feature = "local-llm")]
         {
+            let started = Instant::now();
             // Clone Arc references for the blocking task
             let model = self.model.clone();
             let model_path = self.model_path.clone();
             let n_ctx = self.n_ctx;
             let n_threads = self.n_threads;
-            let max_tokens = self.max_tokens;
-            let temperature = self.temperature;
+            let max_tokens = params.max_tokens_or(self.max_tokens);
+            let temperature = params.temperature_or(self.temperature);
+            let stop = params.stop.clone();
             let prompt = prompt.to_string();
 
             // Run blocking inference in a separate thread
-            let result = tokio::task::spawn_blocking(move || {
+            let (text, usage) = tokio::task::spawn_blocking(move || {
                 let temp_provider = LocalLlamaCppBackend {
                     model_path,
                     n_ctx,
@@ -373,17 +443,55 @@ impl LlmBackend for LocalLlamaCppBackend {
                     temperature,
                     model,
                 };
-                temp_provider.generate_sync(&prompt)
+                temp_provider.generate_sync(&prompt, &stop, &mut |_| true)
             })
             .await
             .map_err(|e| anyhow::anyhow!("Task join error: {}", e))??;
 
-            Ok(result)
+            Ok(GenerationOutput::new(text, usage, started))
+        }
+
+        #[cfg(not(feature = "local-llm"))]
+        {
+            let _ = (prompt, params);
+            anyhow::bail!(
+                "Local LLM feature not enabled. Build with: cargo build --features local-llm"
+            )
+        }
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        #[cfg(feature = "local-llm")]
+        {
+            let temp_provider = LocalLlamaCppBackend {
+                model_path: self.model_path.clone(),
+                n_ctx: self.n_ctx,
+                n_threads: self.n_threads,
+                max_tokens: params.max_tokens_or(self.max_tokens),
+                temperature: params.temperature_or(self.temperature),
+                model: self.model.clone(),
+            };
+            let stop = params.stop.clone();
+            let prompt = prompt.to_string();
+            let (sender, stream) = TokenStream::channel();
+
+            // Inference runs in a blocking thread; it stops once the stream is dropped
+            tokio::task::spawn_blocking(move || {
+                let result = temp_provider.generate_sync(&prompt, &stop, &mut |text| {
+                    sender.blocking_send(Ok(Chunk::Text(text.to_string()))).is_ok()
+                });
+                let _ = match result {
+                    Ok((_, usage)) => sender.blocking_send(Ok(Chunk::Usage(usage))),
+                    Err(e) => sender.blocking_send(Err(e)),
+                };
+            });
+
+            Ok(stream)
         }
 
         #[cfg(not(feature = "local-llm"))]
         {
-            let _ = prompt;
+            let _ = (prompt, params);
             anyhow::bail!(
                 "Local LLM feature not enabled. Build with: cargo build --features local-llm"
             )
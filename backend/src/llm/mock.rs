@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use super::LlmBackend;
+use super::{GenParams, GenerationOutput, LlmBackend};
 
 /// Mock LLM backend for testing purposes.
 ///
@@ -17,6 +17,8 @@ pub struct MockLlmBackend {
     responses: Vec<MockResponse>,
     /// Current response index
     call_count: Arc<AtomicUsize>,
+    /// Parameters of the last call
+    last_params: Arc<Mutex<Option<GenParams>>>,
     /// Whether health check should succeed
     healthy: bool,
 }
@@ -41,6 +43,7 @@ impl MockLlmBackend {
                 js: Self::default_js(),
             }],
             call_count: Arc::new(AtomicUsize::new(0)),
+            last_params: Arc::new(Mutex::new(None)),
             healthy: true,
         }
     }
@@ -50,6 +53,7 @@ impl MockLlmBackend {
         Self {
             responses,
             call_count: Arc::new(AtomicUsize::new(0)),
+            last_params: Arc::new(Mutex::new(None)),
             healthy: true,
         }
     }
@@ -59,6 +63,7 @@ impl MockLlmBackend {
         Self {
             responses: vec![MockResponse::Error(error_message.to_string())],
             call_count: Arc::new(AtomicUsize::new(0)),
+            last_params: Arc::new(Mutex::new(None)),
             healthy: true,
         }
     }
@@ -68,6 +73,7 @@ impl MockLlmBackend {
         Self {
             responses: vec![],
             call_count: Arc::new(AtomicUsize::new(0)),
+            last_params: Arc::new(Mutex::new(None)),
             healthy: false,
         }
     }
@@ -83,6 +89,7 @@ impl MockLlmBackend {
                 },
             ],
             call_count: Arc::new(AtomicUsize::new(0)),
+            last_params: Arc::new(Mutex::new(None)),
             healthy: true,
         }
     }
@@ -92,6 +99,11 @@ impl MockLlmBackend {
         self.call_count.load(Ordering::SeqCst)
     }
 
+    /// Parameters the last generate call was made with
+    pub fn last_params(&self) -> Option<GenParams> {
+        self.last_params.lock().ok().and_then(|p| p.clone())
+    }
+
     fn default_xml() -> String {
         r#"<Dataset id="ds_member">
   <Column name="id" type="STRING" size="20" />
@@ -153,17 +165,21 @@ impl LlmBackend for MockLlmBackend {
         "mock-model"
     }
 
-    async fn generate(&self, _prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, _prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
         let idx = self.call_count.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut last) = self.last_params.lock() {
+            *last = Some(params.clone());
+        }
         let response_idx = idx % self.responses.len();
 
-        match &self.responses[response_idx] {
-            MockResponse::Success(text) => Ok(text.clone()),
-            MockResponse::Error(msg) => Err(anyhow::anyhow!("{}", msg)),
+        let text = match &self.responses[response_idx] {
+            MockResponse::Success(text) => text.clone(),
+            MockResponse::Error(msg) => return Err(anyhow::anyhow!("{}", msg)),
             MockResponse::XFrame5Output { xml, js } => {
-                Ok(format!("--- XML ---\n{}\n\n--- JS ---\n{}", xml, js))
+                format!("--- XML ---\n{}\n\n--- JS ---\n{}", xml, js)
             }
-        }
+        };
+        Ok(GenerationOutput::from(text))
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
@@ -182,7 +198,7 @@ mod tests {
     #[tokio::test]
     async fn test_mock_default_response() {
         let mock = MockLlmBackend::new();
-        let result = mock.generate("test prompt").await.unwrap();
+        let result = mock.generate("test prompt").await.unwrap().text;
 
         assert!(result.contains("--- XML ---"));
         assert!(result.contains("--- JS ---"));
@@ -196,7 +212,8 @@ mod tests {
         )]);
 
         let result = mock.generate("test").await.unwrap();
-        assert_eq!(result, "Custom response");
+        assert_eq!(result.text, "Custom response");
+        assert_eq!(result.prompt_tokens, None);
     }
 
     #[tokio::test]
@@ -234,9 +251,9 @@ mod tests {
             MockResponse::Success("Second".to_string()),
         ]);
 
-        assert_eq!(mock.generate("").await.unwrap(), "First");
-        assert_eq!(mock.generate("").await.unwrap(), "Second");
-        assert_eq!(mock.generate("").await.unwrap(), "First"); // Cycles back
+        assert_eq!(mock.generate("").await.unwrap().text, "First");
+        assert_eq!(mock.generate("").await.unwrap().text, "Second");
+        assert_eq!(mock.generate("").await.unwrap().text, "First"); // Cycles back
     }
 
     #[tokio::test]
@@ -250,6 +267,6 @@ mod tests {
         // Second call succeeds
         let second = mock.generate("test").await;
         assert!(second.is_ok());
-        assert!(second.unwrap().contains("--- XML ---"));
+        assert!(second.unwrap().text.contains("--- XML ---"));
     }
 }
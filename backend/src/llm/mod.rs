@@ -1,27 +1,47 @@
 mod ollama;
 mod llama_cpp;
 mod local_llama_cpp;
+mod local_embedder;
 mod vllm;
 mod groq;
 mod openai;
 mod anthropic;
 mod mock;
+mod fallback;
+mod retry;
+mod gguf;
+mod output;
+mod params;
+mod stream;
+#[cfg(feature = "fault-injection")]
+mod fault;
 
-pub use ollama::{OllamaBackend, OllamaModel, OllamaModelDetails};
+pub use ollama::{OllamaBackend, OllamaModel, OllamaModelDetails, OllamaPullStatus};
 pub use llama_cpp::LlamaCppBackend;
 pub use local_llama_cpp::LocalLlamaCppBackend;
-pub use vllm::VllmBackend;
+pub use local_embedder::LocalEmbedder;
+pub use vllm::{VllmBackend, VllmModel};
 pub use groq::GroqBackend;
 pub use openai::OpenAIBackend;
 pub use anthropic::AnthropicBackend;
 pub use mock::{MockLlmBackend, MockResponse};
+pub use fallback::FallbackBackend;
+pub use retry::{RetryPolicy, RetryingBackend};
+pub use gguf::{GgufInfo, MAX_CONTEXT_SIZE, V3_PROMPT_MIN_CONTEXT};
+pub use output::{GenerationOutput, LlmUsage, TokenUsage};
+pub use params::{GenParams, DEFAULT_MAX_TOKENS, DEFAULT_TEMPERATURE};
+pub use stream::{StreamAborted, StreamObserver, TokenStream};
+#[cfg(feature = "fault-injection")]
+pub use fault::FaultInjectingBackend;
 
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
 use std::env;
 use std::path::PathBuf;
 
 use crate::models::_entities::llm_configs;
+use crate::services::lookup_cache::{LookupCache, Namespace};
+use crate::services::redaction;
 
 /// Core trait for LLM backends.
 /// All implementations must be Send + Sync for async contexts.
@@ -35,8 +55,53 @@ pub trait LlmBackend: Send + Sync {
     /// Model name for internal logging only
     fn model(&self) -> &str;
 
-    /// Generate response from prompt
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String>;
+    /// Generate response from prompt with the backend's sampling defaults
+    async fn generate(&self, prompt: &str) -> anyhow::Result<GenerationOutput> {
+        self.generate_with(prompt, &GenParams::default()).await
+    }
+
+    /// Generate response from prompt with per-call sampling parameters, with
+    /// the token counts the provider reports and the call's latency
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput>;
+
+    /// Generate, returning the response text as it is produced
+    async fn generate_stream(&self, prompt: &str) -> anyhow::Result<TokenStream> {
+        self.generate_stream_with(prompt, &GenParams::default()).await
+    }
+
+    /// [`generate_stream`](Self::generate_stream) with per-call sampling parameters
+    ///
+    /// Streaming backends yield the text deltas as the model writes them; the
+    /// observed generation (stream prevalidation) reads them, no endpoint
+    /// exposes them yet. The default generates the whole response and yields
+    /// it at once.
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        Ok(TokenStream::once(self.generate_with(prompt, params).await?))
+    }
+
+    /// Generate while `observer` watches the response stream in
+    async fn generate_observed(
+        &self,
+        prompt: &str,
+        observer: &mut dyn StreamObserver,
+    ) -> anyhow::Result<GenerationOutput> {
+        self.generate_observed_with(prompt, &GenParams::default(), observer).await
+    }
+
+    /// [`generate_observed`](Self::generate_observed) with per-call sampling parameters
+    ///
+    /// Backends that stream report each text delta, so the observer can abort
+    /// a broken response early (returned as a [`StreamAborted`] error). The
+    /// default generates without streaming and does not observe.
+    async fn generate_observed_with(
+        &self,
+        prompt: &str,
+        params: &GenParams,
+        observer: &mut dyn StreamObserver,
+    ) -> anyhow::Result<GenerationOutput> {
+        let _ = observer;
+        self.generate_with(prompt, params).await
+    }
 
     /// Health check for the backend
     async fn health_check(&self) -> anyhow::Result<()>;
@@ -53,7 +118,7 @@ pub trait LlmBackend: Send + Sync {
 ///
 /// For local-llama-cpp provider (native llama.cpp bindings):
 /// - LLM_MODEL_PATH: Path to GGUF model file
-/// - LLM_CONTEXT_SIZE: Context window size (default: 4096)
+/// - LLM_CONTEXT_SIZE: Context window size (default: from the GGUF, else 4096)
 /// - LLM_THREADS: Number of CPU threads (default: 4)
 /// - LLM_MAX_TOKENS: Max tokens to generate (default: 4096)
 /// - LLM_TEMPERATURE: Sampling temperature (default: 0.7)
@@ -82,14 +147,19 @@ pub fn create_backend_from_env() -> Box<dyn LlmBackend> {
 /// Create LLM backend from database configuration, falling back to environment variables.
 ///
 /// This function:
-/// 1. Queries the database for an active LLM config (is_active = true)
+/// 1. Queries the database for the company's active LLM config, then the
+///    shared one (`company` NULL)
 /// 2. If found, creates the backend from database settings
 /// 3. If not found, falls back to create_backend_from_env()
+/// 4. Chains the configs marked as fallback candidates behind it (see
+///    [`FallbackBackend`])
 ///
 /// This allows runtime configuration changes via the admin panel without server restart.
-pub async fn create_backend_from_db_or_env(db: &DatabaseConnection) -> Box<dyn LlmBackend> {
-    // Try to get active config from database
-    match get_active_llm_config(db).await {
+pub async fn create_backend_from_db_or_env(
+    db: &DatabaseConnection,
+    company: Option<&str>,
+) -> Box<dyn LlmBackend> {
+    let (backend, active_id) = match resolve_llm_config(db, company).await {
         Some(config) => {
             tracing::info!(
                 "Using LLM config from database: {} ({}/{})",
@@ -97,27 +167,138 @@ pub async fn create_backend_from_db_or_env(db: &DatabaseConnection) -> Box<dyn L
                 config.provider,
                 config.model_name
             );
-            create_backend_from_config(&config)
+            (create_backend_from_config(&config), Some(config.id))
         }
         None => {
             tracing::info!("No active LLM config in database, using environment variables");
-            create_backend_from_env()
+            (create_backend_from_env(), None)
         }
-    }
+    };
+
+    let fallbacks = get_fallback_llm_configs(db, company, active_id).await;
+    let backend: Box<dyn LlmBackend> = if fallbacks.is_empty() {
+        backend
+    } else {
+        tracing::info!(
+            "LLM fallback chain: {}",
+            fallbacks.iter().map(|c| c.name.as_str()).collect::<Vec<_>>().join(" → ")
+        );
+        Box::new(FallbackBackend::new(
+            backend,
+            fallbacks.iter().map(create_backend_from_config).collect(),
+        ))
+    };
+
+    #[cfg(feature = "fault-injection")]
+    let backend: Box<dyn LlmBackend> = Box::new(FaultInjectingBackend::new(backend));
+
+    backend
 }
 
-/// Get the active LLM configuration from database
-async fn get_active_llm_config(db: &DatabaseConnection) -> Option<llm_configs::Model> {
-    llm_configs::Entity::find()
-        .filter(llm_configs::Column::IsActive.eq(true))
+/// Create the second backend for race mode.
+///
+/// `LLM_RACE_PARTNER` names the LLM config (admin panel) that races the active
+/// one, typically a remote provider racing the local model. Returns `None`
+/// when unset, unknown, or the same config as the active one.
+pub async fn create_race_partner(db: &DatabaseConnection) -> Option<Box<dyn LlmBackend>> {
+    let name = env::var("LLM_RACE_PARTNER").ok().filter(|n| !n.trim().is_empty())?;
+    let config = llm_configs::Entity::find()
+        .filter(llm_configs::Column::Name.eq(name.trim()))
         .one(db)
         .await
         .ok()
-        .flatten()
+        .flatten();
+
+    match config {
+        Some(config) if config.is_active != Some(true) => Some(create_backend_from_config(&config)),
+        Some(_) => {
+            tracing::warn!("LLM_RACE_PARTNER '{}' is the active config, race disabled", name);
+            None
+        }
+        None => {
+            tracing::warn!("LLM_RACE_PARTNER '{}' not found in LLM configs, race disabled", name);
+            None
+        }
+    }
+}
+
+/// Provider a company's generations currently run on (same lookup as
+/// [`create_backend_from_db_or_env`], without building a backend)
+pub async fn active_provider(db: &DatabaseConnection, company: Option<&str>) -> String {
+    let mut config = None;
+    if company.is_some() {
+        config = get_active_llm_config(db, company).await;
+    }
+    if config.is_none() {
+        config = get_active_llm_config(db, None).await;
+    }
+    match config {
+        Some(config) => config.provider,
+        None => env::var("LLM_PROVIDER").unwrap_or_else(|_| "ollama".to_string()),
+    }
+}
+
+/// Active LLM configuration used for a company: its own, else the shared one
+pub async fn resolve_llm_config(db: &DatabaseConnection, company: Option<&str>) -> Option<llm_configs::Model> {
+    if company.is_some() {
+        if let Some(config) = get_active_llm_config(db, company).await {
+            return Some(config);
+        }
+    }
+    get_active_llm_config(db, None).await
+}
+
+/// Get the active LLM configuration of a company (None = shared) from database
+async fn get_active_llm_config(db: &DatabaseConnection, company: Option<&str>) -> Option<llm_configs::Model> {
+    let key = company.unwrap_or("");
+    let config = LookupCache::get_or_load(Namespace::LlmConfigs, key, || async {
+        let same_company = match company {
+            Some(company) => llm_configs::Column::Company.eq(company),
+            None => llm_configs::Column::Company.is_null(),
+        };
+        llm_configs::Entity::find()
+            .filter(llm_configs::Column::IsActive.eq(true))
+            .filter(same_company)
+            .one(db)
+            .await
+    })
+    .await;
+    config.ok().flatten()
+}
+
+/// Fallback candidates of a company (its own and the shared ones), in
+/// priority order, without the active config
+async fn get_fallback_llm_configs(
+    db: &DatabaseConnection,
+    company: Option<&str>,
+    active_id: Option<i32>,
+) -> Vec<llm_configs::Model> {
+    let key = format!("fallbacks:{}", company.unwrap_or(""));
+    let configs = LookupCache::get_or_load(Namespace::LlmConfigs, &key, || async {
+        let same_company = match company {
+            Some(company) => Condition::any()
+                .add(llm_configs::Column::Company.eq(company))
+                .add(llm_configs::Column::Company.is_null()),
+            None => Condition::all().add(llm_configs::Column::Company.is_null()),
+        };
+        llm_configs::Entity::find()
+            .filter(llm_configs::Column::FallbackPriority.is_not_null())
+            .filter(same_company)
+            .order_by_asc(llm_configs::Column::FallbackPriority)
+            .order_by_asc(llm_configs::Column::Id)
+            .all(db)
+            .await
+    })
+    .await;
+    configs
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| Some(c.id) != active_id)
+        .collect()
 }
 
 /// Create LLM backend from database configuration
-fn create_backend_from_config(config: &llm_configs::Model) -> Box<dyn LlmBackend> {
+pub fn create_backend_from_config(config: &llm_configs::Model) -> Box<dyn LlmBackend> {
     // Priority: 1) config.timeout_secs, 2) LLM_TIMEOUT_SECONDS env var, 3) default 120
     let timeout_seconds: u64 = config.timeout_secs
         .map(|t| t as u64)
@@ -129,7 +310,7 @@ fn create_backend_from_config(config: &llm_configs::Model) -> Box<dyn LlmBackend
         if config.timeout_secs.is_some() { "config" } else { "env/default" }
     );
 
-    match config.provider.as_str() {
+    let backend: Box<dyn LlmBackend> = match config.provider.as_str() {
         "ollama" => Box::new(OllamaBackend::new(
             config.endpoint_url.clone().unwrap_or_else(|| "http://localhost:11434".to_string()),
             config.model_name.clone(),
@@ -148,7 +329,11 @@ fn create_backend_from_config(config: &llm_configs::Model) -> Box<dyn LlmBackend
                     PathBuf::from("llm-models").join(&config.model_name)
                 });
 
-            let n_ctx = config.n_ctx.unwrap_or(4096) as u32;
+            // No configured context: use the model's training context (capped)
+            let n_ctx = config.n_ctx
+                .map(|n| n as u32)
+                .or_else(|| LocalLlamaCppBackend::detect_context_size(&model_path))
+                .unwrap_or(4096);
             let n_threads = config.n_threads.unwrap_or(4) as u32;
             let max_tokens = config.max_tokens.unwrap_or(4096) as u32;
             let temperature = config.temperature.unwrap_or(0.7);
@@ -196,7 +381,11 @@ fn create_backend_from_config(config: &llm_configs::Model) -> Box<dyn LlmBackend
                 timeout_seconds,
             ))
         }
-    }
+    };
+
+    // Keep the configured model and endpoint out of API responses
+    redaction::redactor().remember(&config.model_name, config.endpoint_url.as_deref());
+    backend
 }
 
 #[cfg(test)]
@@ -1,9 +1,10 @@
-use super::LlmBackend;
+use super::stream::{spawn_http, DeltaParser, Framing};
+use super::{GenParams, GenerationOutput, LlmBackend, TokenStream, TokenUsage};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Available model info from Ollama
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +30,19 @@ struct OllamaTagsResponse {
     models: Vec<OllamaModel>,
 }
 
+/// Progress line streamed by Ollama while pulling a model
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OllamaPullStatus {
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub completed: Option<u64>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
 /// Ollama backend - default for on-premise production
 pub struct OllamaBackend {
     endpoint: String,
@@ -81,6 +95,81 @@ impl OllamaBackend {
         Ok(result.models)
     }
 
+    /// Pull a model onto the Ollama server, reporting each progress line
+    ///
+    /// Downloads can take a long time, so no request timeout is applied.
+    pub async fn pull_model(
+        &self,
+        model: &str,
+        mut on_progress: impl FnMut(OllamaPullStatus),
+    ) -> anyhow::Result<()> {
+        let url = format!("{}/api/pull", self.endpoint);
+        let body = serde_json::json!({
+            "model": model,
+            "stream": true
+        });
+
+        let mut response = self.client.post(&url).json(&body).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Ollama pull failed ({}): {}", status, text);
+        }
+
+        // Newline-delimited JSON; a line may span several chunks
+        let mut buffer = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=pos).collect();
+                Self::report_pull_line(&line, &mut on_progress)?;
+            }
+        }
+        Self::report_pull_line(&buffer, &mut on_progress)
+    }
+
+    fn report_pull_line(line: &[u8], on_progress: &mut impl FnMut(OllamaPullStatus)) -> anyhow::Result<()> {
+        let line = String::from_utf8_lossy(line);
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        let status: OllamaPullStatus = serde_json::from_str(line.trim())?;
+        if let Some(error) = &status.error {
+            anyhow::bail!("Ollama pull failed: {}", error);
+        }
+        on_progress(status);
+        Ok(())
+    }
+
+    /// Generate request body; set parameters go to `options` (the model's
+    /// Modelfile defaults apply otherwise)
+    fn body(&self, prompt: &str, params: &GenParams, stream: bool) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "stream": stream
+        });
+        if !params.is_default() {
+            let mut options = serde_json::json!({});
+            if let Some(temperature) = params.temperature {
+                options["temperature"] = serde_json::json!(temperature);
+            }
+            if let Some(max_tokens) = params.max_tokens {
+                options["num_predict"] = serde_json::json!(max_tokens);
+            }
+            params.add_sampling(&mut options, "stop");
+            body["options"] = options;
+        }
+        body
+    }
+
+    /// Token counts of the final (`done`) response
+    fn usage(response: &serde_json::Value) -> Option<TokenUsage> {
+        (response["done"] == true)
+            .then(|| TokenUsage::from_json(response, "prompt_eval_count", "eval_count"))
+    }
+
     /// Get the endpoint URL
     pub fn endpoint(&self) -> &str {
         &self.endpoint
@@ -97,13 +186,10 @@ impl LlmBackend for OllamaBackend {
         &self.model
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let started = Instant::now();
         let url = format!("{}/api/generate", self.endpoint);
-        let body = serde_json::json!({
-            "model": self.model,
-            "prompt": prompt,
-            "stream": false
-        });
+        let body = self.body(prompt, params, false);
 
         let response = self
             .client
@@ -120,7 +206,36 @@ impl LlmBackend for OllamaBackend {
         }
 
         let result: serde_json::Value = response.json().await?;
-        Ok(result["response"].as_str().unwrap_or("").to_string())
+        let text = result["response"].as_str().unwrap_or("").to_string();
+        Ok(GenerationOutput::new(text, Self::usage(&result).unwrap_or_default(), started))
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        let started = Instant::now();
+        let url = format!("{}/api/generate", self.endpoint);
+        let body = self.body(prompt, params, true);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&body)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Ollama request failed ({}): {}", status, text);
+        }
+
+        let delta: DeltaParser = |event| {
+            if let Some(error) = event["error"].as_str() {
+                anyhow::bail!("Ollama stream failed: {}", error);
+            }
+            Ok(event["response"].as_str().map(str::to_string))
+        };
+        Ok(spawn_http(started, response, Framing::JsonLines, delta, Self::usage))
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
@@ -1,8 +1,12 @@
-use super::LlmBackend;
+use super::stream::{spawn_http, Framing};
+use super::{
+    GenParams, GenerationOutput, LlmBackend, StreamObserver, TokenStream, TokenUsage, DEFAULT_MAX_TOKENS,
+    DEFAULT_TEMPERATURE,
+};
 use async_trait::async_trait;
 use reqwest::Client;
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// OpenAI API backend - GPT-4o, GPT-4o-mini
 /// WARNING: Remote provider - use for development/testing only
@@ -40,6 +44,26 @@ impl OpenAIBackend {
             client: Client::new(),
         }
     }
+
+    /// Chat completion request body
+    fn body(&self, prompt: &str, params: &GenParams) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "messages": [{"role": "user", "content": prompt}],
+            "max_tokens": params.max_tokens_or(DEFAULT_MAX_TOKENS),
+            "temperature": params.temperature_or(DEFAULT_TEMPERATURE)
+        });
+        params.add_sampling(&mut body, "stop");
+        body
+    }
+
+    /// Text of a chat completion chunk
+    fn delta(event: &serde_json::Value) -> anyhow::Result<Option<String>> {
+        if let Some(message) = event["error"]["message"].as_str() {
+            anyhow::bail!("OpenAI stream failed: {}", message);
+        }
+        Ok(event["choices"][0]["delta"]["content"].as_str().map(str::to_string))
+    }
 }
 
 #[async_trait]
@@ -52,14 +76,10 @@ impl LlmBackend for OpenAIBackend {
         &self.model
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let started = Instant::now();
         let url = format!("{}/chat/completions", self.endpoint);
-        let body = serde_json::json!({
-            "model": self.model,
-            "messages": [{"role": "user", "content": prompt}],
-            "max_tokens": 4096,
-            "temperature": 0.7
-        });
+        let body = self.body(prompt, params);
 
         let response = self
             .client
@@ -77,10 +97,45 @@ impl LlmBackend for OpenAIBackend {
         }
 
         let result: serde_json::Value = response.json().await?;
-        Ok(result["choices"][0]["message"]["content"]
+        let text = result["choices"][0]["message"]["content"]
             .as_str()
             .unwrap_or("")
-            .to_string())
+            .to_string();
+        Ok(GenerationOutput::new(text, TokenUsage::from_openai(&result).unwrap_or_default(), started))
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        let started = Instant::now();
+        let url = format!("{}/chat/completions", self.endpoint);
+        let mut body = self.body(prompt, params);
+        body["stream"] = serde_json::json!(true);
+        body["stream_options"] = serde_json::json!({"include_usage": true});
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("OpenAI request failed ({}): {}", status, text);
+        }
+
+        Ok(spawn_http(started, response, Framing::Sse, Self::delta, TokenUsage::from_openai))
+    }
+
+    async fn generate_observed_with(
+        &self,
+        prompt: &str,
+        params: &GenParams,
+        observer: &mut dyn StreamObserver,
+    ) -> anyhow::Result<GenerationOutput> {
+        self.generate_stream_with(prompt, params).await?.observe(observer).await
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
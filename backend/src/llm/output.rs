@@ -0,0 +1,138 @@
+//! Result of one LLM call, with its token usage and latency
+
+use std::time::Instant;
+
+use sea_orm::Set;
+
+use crate::models::_entities::generation_logs;
+
+/// Tokens a provider reports for one call (`None` when it does not say)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: Option<u32>, completion_tokens: Option<u32>) -> Self {
+        Self { prompt_tokens, completion_tokens }
+    }
+
+    /// Counts from a JSON object, e.g. `usage` of an OpenAI-compatible response
+    pub(crate) fn from_json(value: &serde_json::Value, prompt_key: &str, completion_key: &str) -> Self {
+        let count = |key: &str| value[key].as_u64().and_then(|n| u32::try_from(n).ok());
+        Self::new(count(prompt_key), count(completion_key))
+    }
+
+    /// `usage` of an OpenAI-compatible response or final stream chunk
+    /// (OpenAI, vLLM, Groq, llama.cpp server)
+    pub(crate) fn from_openai(response: &serde_json::Value) -> Option<Self> {
+        let usage = &response["usage"];
+        usage
+            .is_object()
+            .then(|| Self::from_json(usage, "prompt_tokens", "completion_tokens"))
+    }
+
+    /// Later counts replace earlier ones (streams report them in pieces)
+    pub fn merge(&mut self, other: TokenUsage) {
+        self.prompt_tokens = other.prompt_tokens.or(self.prompt_tokens);
+        self.completion_tokens = other.completion_tokens.or(self.completion_tokens);
+    }
+}
+
+/// Response of [`LlmBackend::generate`](super::LlmBackend::generate)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationOutput {
+    pub text: String,
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+    /// Wall time of the call, from sending the prompt to the last token
+    pub latency_ms: u64,
+}
+
+impl GenerationOutput {
+    /// Output of a call started at `started`
+    pub fn new(text: String, usage: TokenUsage, started: Instant) -> Self {
+        Self {
+            text,
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            latency_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    pub fn usage(&self) -> TokenUsage {
+        TokenUsage::new(self.prompt_tokens, self.completion_tokens)
+    }
+}
+
+impl From<String> for GenerationOutput {
+    /// Text without usage (mocks and tests)
+    fn from(text: String) -> Self {
+        Self { text, ..Default::default() }
+    }
+}
+
+/// Usage summed over the LLM calls of one generation (retries included)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LlmUsage {
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+    pub latency_ms: u64,
+}
+
+impl LlmUsage {
+    pub fn add(&mut self, output: &GenerationOutput) {
+        let sum = |total: Option<u32>, count: Option<u32>| match (total, count) {
+            (Some(total), Some(count)) => Some(total.saturating_add(count)),
+            (total, count) => total.or(count),
+        };
+        self.prompt_tokens = sum(self.prompt_tokens, output.prompt_tokens);
+        self.completion_tokens = sum(self.completion_tokens, output.completion_tokens);
+        self.latency_ms = self.latency_ms.saturating_add(output.latency_ms);
+    }
+
+    /// Copy the usage onto a generation log (counts saturate at `i32::MAX`)
+    pub fn apply(&self, log: &mut generation_logs::ActiveModel) {
+        let column = |n: u64| n.min(i32::MAX as u64) as i32;
+        log.prompt_tokens = Set(self.prompt_tokens.map(|n| column(n.into())));
+        log.completion_tokens = Set(self.completion_tokens.map(|n| column(n.into())));
+        log.llm_latency_ms = Set(Some(column(self.latency_ms)));
+    }
+
+    /// Usage of a single call
+    pub fn of(output: &GenerationOutput) -> Self {
+        let mut usage = Self::default();
+        usage.add(output);
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_sums_calls_and_keeps_unknown_counts_out() {
+        let first = GenerationOutput {
+            text: "<screen>".to_string(),
+            prompt_tokens: Some(1200),
+            completion_tokens: Some(800),
+            latency_ms: 9000,
+        };
+        let retry = GenerationOutput {
+            text: "<screen/>".to_string(),
+            prompt_tokens: Some(1500),
+            completion_tokens: None,
+            latency_ms: 7000,
+        };
+
+        let mut usage = LlmUsage::of(&first);
+        usage.add(&retry);
+        assert_eq!(usage.prompt_tokens, Some(2700));
+        assert_eq!(usage.completion_tokens, Some(800));
+        assert_eq!(usage.latency_ms, 16000);
+
+        assert_eq!(LlmUsage::of(&GenerationOutput::from("x".to_string())).prompt_tokens, None);
+    }
+}
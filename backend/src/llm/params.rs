@@ -0,0 +1,105 @@
+//! Per-call generation parameters
+
+use serde::{Deserialize, Serialize};
+
+/// Tokens to generate when neither the call nor the backend config sets it
+pub const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Sampling temperature when neither the call nor the backend config sets it
+pub const DEFAULT_TEMPERATURE: f32 = 0.7;
+
+/// Sampling parameters of one call; unset fields keep the backend's defaults
+///
+/// Reviews want deterministic output (low temperature) while screens
+/// generated from a natural-language request do better with some freedom,
+/// so callers pass these per call (`LlmBackend::generate_with`) instead of
+/// configuring one set per backend.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Nucleus sampling: only tokens within this probability mass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Sequences that end the response (not included in it)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+}
+
+impl GenParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    pub fn with_stop(mut self, stop: impl Into<String>) -> Self {
+        self.stop.push(stop.into());
+        self
+    }
+
+    /// Whether every field keeps the backend's default
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Temperature of the call, else the backend's
+    pub fn temperature_or(&self, default: f32) -> f32 {
+        self.temperature.unwrap_or(default)
+    }
+
+    /// Token limit of the call, else the backend's
+    pub fn max_tokens_or(&self, default: u32) -> u32 {
+        self.max_tokens.unwrap_or(default)
+    }
+
+    /// Add `top_p` and the stop sequences (under `stop_key`) to a request body
+    pub(super) fn add_sampling(&self, body: &mut serde_json::Value, stop_key: &str) {
+        if let Some(top_p) = self.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        if !self.stop.is_empty() {
+            body[stop_key] = serde_json::json!(self.stop);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_params_keep_backend_defaults() {
+        let params = GenParams::new();
+        assert!(params.is_default());
+        assert_eq!(params.temperature_or(DEFAULT_TEMPERATURE), 0.7);
+        assert_eq!(params.max_tokens_or(DEFAULT_MAX_TOKENS), 4096);
+
+        let mut body = serde_json::json!({"model": "m"});
+        params.add_sampling(&mut body, "stop");
+        assert_eq!(body, serde_json::json!({"model": "m"}));
+
+        let params = GenParams::new().with_temperature(0.1).with_top_p(0.9).with_stop("</screen>");
+        params.add_sampling(&mut body, "stop_sequences");
+        assert_eq!(params.temperature_or(DEFAULT_TEMPERATURE), 0.1);
+        assert_eq!(body, serde_json::json!({"model": "m", "top_p": 0.9f32, "stop_sequences": ["</screen>"]}));
+    }
+}
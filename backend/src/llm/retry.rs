@@ -0,0 +1,278 @@
+//! Retry with exponential backoff for transient LLM failures
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use regex::Regex;
+
+use super::stream::Watched;
+use super::{GenParams, GenerationOutput, LlmBackend, StreamAborted, StreamObserver, TokenStream};
+
+/// When and how long to wait before calling a backend again
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Calls per request, the first one included (1 = no retry)
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for each later one
+    pub initial_backoff: Duration,
+    /// Upper bound of a single wait
+    pub max_backoff: Duration,
+    /// Random spread of each wait, as a fraction of it (0.2 = ±20%)
+    pub jitter: f64,
+    /// Retry requests that timed out
+    pub retry_on_timeout: bool,
+    /// Retry 5xx responses
+    pub retry_on_server_error: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            jitter: 0.2,
+            retry_on_timeout: true,
+            retry_on_server_error: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy from `LLM_RETRY_*` environment variables (defaults otherwise)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_string());
+        let flag = |name: &str, default: bool| match var(name).as_deref() {
+            Some("true") | Some("1") => true,
+            Some("false") | Some("0") => false,
+            _ => default,
+        };
+        Self {
+            max_attempts: var("LLM_RETRY_MAX_ATTEMPTS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_attempts),
+            initial_backoff: var("LLM_RETRY_BACKOFF_MS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.initial_backoff),
+            max_backoff: var("LLM_RETRY_MAX_BACKOFF_MS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.max_backoff),
+            jitter: var("LLM_RETRY_JITTER")
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| (0.0..=1.0).contains(v))
+                .unwrap_or(defaults.jitter),
+            retry_on_timeout: flag("LLM_RETRY_ON_TIMEOUT", defaults.retry_on_timeout),
+            retry_on_server_error: flag("LLM_RETRY_ON_5XX", defaults.retry_on_server_error),
+        }
+    }
+
+    /// No retry: every call is made once
+    pub fn disabled() -> Self {
+        Self { max_attempts: 1, ..Self::default() }
+    }
+
+    /// Wait before retry `retry` (1 = the first retry), without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// `backoff` spread by the jitter
+    fn wait(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if self.jitter <= 0.0 || backoff.is_zero() {
+            return backoff;
+        }
+        // Uniform in [1 - jitter, 1 + jitter)
+        let factor = 1.0 + self.jitter * (2.0 * unit() - 1.0);
+        backoff.mul_f64(factor.max(0.0))
+    }
+
+    /// Whether an error is transient under this policy
+    ///
+    /// Timeouts and 5xx responses are; client errors (4xx), bad credentials
+    /// and observer aborts are not, since calling again cannot fix them.
+    pub fn is_retryable(&self, error: &anyhow::Error) -> bool {
+        if error.is::<StreamAborted>() {
+            return false;
+        }
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                if e.is_timeout() {
+                    return self.retry_on_timeout;
+                }
+                if let Some(status) = e.status() {
+                    return status.is_server_error() && self.retry_on_server_error;
+                }
+            }
+        }
+        // Providers report error responses as "<provider> request failed (503 ...): ..."
+        let status = Regex::new(r"\((\d{3})[ )]").unwrap();
+        match status.captures(&error.to_string()) {
+            Some(cap) => cap[1].starts_with('5') && self.retry_on_server_error,
+            None => false,
+        }
+    }
+}
+
+/// Uniform random number in [0, 1)
+fn unit() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Calls its backend again, with exponential backoff, when a call fails with
+/// a transient error (see [`RetryPolicy::is_retryable`])
+///
+/// Observed generations are only retried while the observer has seen no text:
+/// a stream that broke halfway cannot start over on the same observer.
+pub struct RetryingBackend {
+    inner: Box<dyn LlmBackend>,
+    policy: RetryPolicy,
+}
+
+impl RetryingBackend {
+    pub fn new(inner: Box<dyn LlmBackend>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Wrap a backend with the policy from the environment
+    pub fn from_env(inner: Box<dyn LlmBackend>) -> Box<dyn LlmBackend> {
+        let policy = RetryPolicy::from_env();
+        if policy.max_attempts <= 1 {
+            return inner;
+        }
+        Box::new(Self::new(inner, policy))
+    }
+
+    /// Whether a failed attempt is followed by another; waits the backoff if so
+    async fn should_retry(&self, attempt: u32, call: &str, error: &anyhow::Error) -> bool {
+        if attempt >= self.policy.max_attempts || !self.policy.is_retryable(error) {
+            return false;
+        }
+        let wait = self.policy.wait(attempt);
+        tracing::warn!(
+            "LLM {} on {} ({}) failed (attempt {}/{}), retrying in {} ms: {}",
+            call,
+            self.inner.name(),
+            self.inner.model(),
+            attempt,
+            self.policy.max_attempts,
+            wait.as_millis(),
+            error
+        );
+        tokio::time::sleep(wait).await;
+        true
+    }
+}
+
+#[async_trait]
+impl LlmBackend for RetryingBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let mut attempt = 1;
+        loop {
+            match self.inner.generate_with(prompt, params).await {
+                Err(e) if self.should_retry(attempt, "generate", &e).await => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        let mut attempt = 1;
+        loop {
+            match self.inner.generate_stream_with(prompt, params).await {
+                Err(e) if self.should_retry(attempt, "stream", &e).await => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+
+    async fn generate_observed_with(
+        &self,
+        prompt: &str,
+        params: &GenParams,
+        observer: &mut dyn StreamObserver,
+    ) -> anyhow::Result<GenerationOutput> {
+        let mut attempt = 1;
+        loop {
+            let mut watched = Watched::new(&mut *observer);
+            let result = self.inner.generate_observed_with(prompt, params, &mut watched).await;
+            let received = watched.received();
+            match result {
+                Err(e) if received == 0 && self.should_retry(attempt, "generate", &e).await => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+
+    async fn health_check(&self) -> anyhow::Result<()> {
+        let mut attempt = 1;
+        loop {
+            match self.inner.health_check().await {
+                Err(e) if self.should_retry(attempt, "health check", &e).await => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{MockLlmBackend, MockResponse};
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy { initial_backoff: Duration::ZERO, jitter: 0.0, ..RetryPolicy::default() }
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_error_until_success() {
+        let inner = MockLlmBackend::with_responses(vec![
+            MockResponse::Error("vLLM request failed (503 Service Unavailable): overloaded".to_string()),
+            MockResponse::Success("<screen/>".to_string()),
+        ]);
+        let backend = RetryingBackend::new(Box::new(inner.clone()), policy());
+
+        let params = GenParams::new().with_temperature(0.1);
+        assert_eq!(backend.generate_with("prompt", &params).await.unwrap().text, "<screen/>");
+        assert_eq!(inner.call_count(), 2);
+        assert_eq!(inner.last_params(), Some(params));
+
+        let down = MockLlmBackend::failing("Ollama request failed (502 Bad Gateway): ");
+        let backend = RetryingBackend::new(Box::new(down.clone()), policy());
+        assert!(backend.generate("prompt").await.is_err());
+        assert_eq!(down.call_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let inner = MockLlmBackend::failing("OpenAI request failed (401 Unauthorized): invalid api key");
+        let backend = RetryingBackend::new(Box::new(inner.clone()), policy());
+
+        assert!(backend.generate("prompt").await.is_err());
+        assert_eq!(inner.call_count(), 1);
+
+        let policy = RetryPolicy { initial_backoff: Duration::from_millis(500), ..policy() };
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3), Duration::from_secs(2));
+        assert_eq!(policy.backoff(10), Duration::from_secs(8));
+    }
+}
@@ -0,0 +1,285 @@
+//! Streaming support shared by the backends
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use super::{GenerationOutput, TokenUsage};
+
+/// Deltas buffered between a backend and a slow reader
+const STREAM_BUFFER: usize = 256;
+
+/// Watches a response while it streams in and may stop it early
+pub trait StreamObserver: Send {
+    /// Called with each piece of text as it arrives
+    fn on_text(&mut self, text: &str) -> Result<(), StreamAborted>;
+
+    /// Called once the response is complete
+    fn on_end(&mut self) -> Result<(), StreamAborted> {
+        Ok(())
+    }
+}
+
+/// A streamed response stopped by its observer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamAborted {
+    pub reason: String,
+    /// Characters received before the abort
+    pub received_chars: usize,
+}
+
+impl fmt::Display for StreamAborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Stream aborted after {} chars: {}", self.received_chars, self.reason)
+    }
+}
+
+impl std::error::Error for StreamAborted {}
+
+/// Counts the text an observer saw, so wrapping backends (retry, fallback)
+/// can tell a failed connection from a stream that broke halfway
+pub(crate) struct Watched<'a> {
+    inner: &'a mut dyn StreamObserver,
+    received: usize,
+}
+
+impl<'a> Watched<'a> {
+    pub(crate) fn new(inner: &'a mut dyn StreamObserver) -> Self {
+        Self { inner, received: 0 }
+    }
+
+    /// Bytes of text passed on to the observer
+    pub(crate) fn received(&self) -> usize {
+        self.received
+    }
+}
+
+impl StreamObserver for Watched<'_> {
+    fn on_text(&mut self, text: &str) -> Result<(), StreamAborted> {
+        self.received += text.len();
+        self.inner.on_text(text)
+    }
+
+    fn on_end(&mut self) -> Result<(), StreamAborted> {
+        self.inner.on_end()
+    }
+}
+
+/// Text of a response as the backend produces it
+///
+/// Yields the text deltas in order; an error ends the stream. Dropping the
+/// stream stops the producer: an HTTP response is dropped (closing the
+/// connection), local inference stops at the next token. Token counts the
+/// backend reports along the way end up in the collected output.
+pub struct TokenStream {
+    receiver: mpsc::Receiver<anyhow::Result<Chunk>>,
+    usage: TokenUsage,
+    started: Instant,
+}
+
+/// What a backend sends down a [`TokenStream`]
+#[derive(Debug)]
+pub(crate) enum Chunk {
+    Text(String),
+    Usage(TokenUsage),
+}
+
+/// Sending side of a [`TokenStream`]
+pub(crate) type TokenSender = mpsc::Sender<anyhow::Result<Chunk>>;
+
+impl TokenStream {
+    /// Stream of a call starting now
+    pub(crate) fn channel() -> (TokenSender, Self) {
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        let stream = Self { receiver, usage: TokenUsage::default(), started: Instant::now() };
+        (sender, stream)
+    }
+
+    /// Stream of an already complete response (backends that do not stream)
+    pub fn once(output: GenerationOutput) -> Self {
+        let (sender, mut stream) = Self::channel();
+        let latency = Duration::from_millis(output.latency_ms);
+        stream.started = stream.started.checked_sub(latency).unwrap_or(stream.started);
+        stream.usage = output.usage();
+        // The buffer has room, and the receiver is alive
+        let _ = sender.try_send(Ok(Chunk::Text(output.text)));
+        stream
+    }
+
+    /// Next text delta; `None` once the response is complete
+    pub async fn next(&mut self) -> Option<anyhow::Result<String>> {
+        loop {
+            match self.receiver.recv().await? {
+                Ok(Chunk::Text(text)) => return Some(Ok(text)),
+                Ok(Chunk::Usage(usage)) => self.usage.merge(usage),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    /// The whole response
+    pub async fn collect(mut self) -> anyhow::Result<GenerationOutput> {
+        let mut output = String::new();
+        while let Some(text) = self.next().await {
+            output.push_str(&text?);
+        }
+        Ok(GenerationOutput::new(output, self.usage, self.started))
+    }
+
+    /// The whole response, with `observer` watching each delta (an abort
+    /// drops the stream)
+    pub async fn observe(mut self, observer: &mut dyn StreamObserver) -> anyhow::Result<GenerationOutput> {
+        let mut output = String::new();
+        while let Some(text) = self.next().await {
+            let text = text?;
+            output.push_str(&text);
+            observer.on_text(&text)?;
+        }
+        observer.on_end()?;
+        Ok(GenerationOutput::new(output, self.usage, self.started))
+    }
+}
+
+/// How a streaming HTTP response frames its events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Framing {
+    /// Server-sent events (`data: {...}`)
+    Sse,
+    /// One JSON object per line (Ollama)
+    JsonLines,
+}
+
+/// Text of one stream event (`None` for events without text), or the
+/// provider's error
+pub(crate) type DeltaParser = fn(&serde_json::Value) -> anyhow::Result<Option<String>>;
+
+/// Token counts carried by one stream event, if any
+pub(crate) type UsageParser = fn(&serde_json::Value) -> Option<TokenUsage>;
+
+/// Stream the text of a streaming HTTP response (request sent at `started`),
+/// reading it in a task
+pub(crate) fn spawn_http(
+    started: Instant,
+    response: reqwest::Response,
+    framing: Framing,
+    delta: DeltaParser,
+    usage: UsageParser,
+) -> TokenStream {
+    let (sender, mut stream) = TokenStream::channel();
+    stream.started = started;
+    tokio::spawn(async move {
+        if let Err(e) = read_http(response, framing, delta, usage, &sender).await {
+            let _ = sender.send(Err(e)).await;
+        }
+    });
+    stream
+}
+
+async fn read_http(
+    mut response: reqwest::Response,
+    framing: Framing,
+    delta: DeltaParser,
+    usage: UsageParser,
+    sender: &TokenSender,
+) -> anyhow::Result<()> {
+    let mut lines = LineReader::default();
+    let mut sse = SseReader::default();
+    while let Some(chunk) = response.chunk().await? {
+        let events = match framing {
+            Framing::Sse => sse.push(&chunk),
+            Framing::JsonLines => lines.push(&chunk),
+        };
+        for event in events {
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(&event) else {
+                continue;
+            };
+            let text = delta(&event)?.filter(|t| !t.is_empty()).map(Chunk::Text);
+            for chunk in text.into_iter().chain(usage(&event).map(Chunk::Usage)) {
+                if sender.send(Ok(chunk)).await.is_err() {
+                    // Reader is gone; dropping the response closes the connection
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits a byte stream into lines (without line endings)
+///
+/// Buffers raw bytes, since a chunk may end inside a multi-byte character.
+#[derive(Debug, Default)]
+pub(crate) struct LineReader {
+    buffer: Vec<u8>,
+}
+
+impl LineReader {
+    /// Lines completed by `chunk`
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            lines.push(String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']).to_string());
+        }
+        lines
+    }
+}
+
+/// Splits a server-sent event stream into `data:` payloads
+#[derive(Debug, Default)]
+pub(crate) struct SseReader {
+    lines: LineReader,
+}
+
+impl SseReader {
+    /// Payloads of the lines completed by `chunk`
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.lines
+            .push(chunk)
+            .iter()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(str::trim_start)
+            .filter(|data| !data.is_empty() && *data != "[DONE]")
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_reader_joins_split_lines() {
+        let mut reader = SseReader::default();
+        assert!(reader.push(b"event: content_block_delta\ndata: {\"a\":").is_empty());
+        assert_eq!(reader.push(b"1}\r\n\ndata: [DONE]\n"), vec!["{\"a\":1}".to_string()]);
+
+        // Korean text split inside a character
+        let line = "data: 회원목록\n".as_bytes();
+        assert!(reader.push(&line[..8]).is_empty());
+        assert_eq!(reader.push(&line[8..]), vec!["회원목록".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_token_stream_ends_with_error() {
+        let (sender, stream) = TokenStream::channel();
+        sender.send(Ok(Chunk::Text("<screen".to_string()))).await.unwrap();
+        sender.send(Ok(Chunk::Usage(TokenUsage::new(Some(120), None)))).await.unwrap();
+        sender.send(Ok(Chunk::Text(">".to_string()))).await.unwrap();
+        sender.send(Ok(Chunk::Usage(TokenUsage::new(None, Some(2))))).await.unwrap();
+        drop(sender);
+        let output = stream.collect().await.unwrap();
+        assert_eq!(output.text, "<screen>");
+        assert_eq!(output.usage(), TokenUsage::new(Some(120), Some(2)));
+
+        let (sender, stream) = TokenStream::channel();
+        sender.send(Ok(Chunk::Text("<screen".to_string()))).await.unwrap();
+        sender.send(Err(anyhow::anyhow!("connection reset"))).await.unwrap();
+        drop(sender);
+        assert!(stream.collect().await.is_err());
+    }
+}
@@ -1,8 +1,23 @@
-use super::LlmBackend;
+use super::stream::{spawn_http, DeltaParser, Framing};
+use super::{GenParams, GenerationOutput, LlmBackend, TokenStream, TokenUsage, DEFAULT_MAX_TOKENS, DEFAULT_TEMPERATURE};
 use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Model served by vLLM (OpenAI-compatible `/v1/models` entry)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VllmModel {
+    pub id: String,
+    #[serde(default)]
+    pub max_model_len: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct VllmModelsResponse {
+    data: Vec<VllmModel>,
+}
 
 /// vLLM server backend - OpenAI-compatible API with optional authentication
 pub struct VllmBackend {
@@ -45,6 +60,37 @@ impl VllmBackend {
             client: Client::new(),
         }
     }
+
+    /// Completion request body
+    fn body(&self, prompt: &str, params: &GenParams) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "max_tokens": params.max_tokens_or(DEFAULT_MAX_TOKENS),
+            "temperature": params.temperature_or(DEFAULT_TEMPERATURE)
+        });
+        params.add_sampling(&mut body, "stop");
+        body
+    }
+
+    /// List models served by the vLLM server
+    pub async fn list_models(&self) -> anyhow::Result<Vec<VllmModel>> {
+        let url = format!("{}/v1/models", self.endpoint);
+        let mut request = self.client.get(&url).timeout(Duration::from_secs(10));
+
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to list models from vLLM ({})", response.status());
+        }
+
+        let result: VllmModelsResponse = response.json().await?;
+        Ok(result.data)
+    }
 }
 
 #[async_trait]
@@ -57,14 +103,10 @@ impl LlmBackend for VllmBackend {
         &self.model
     }
 
-    async fn generate(&self, prompt: &str) -> anyhow::Result<String> {
+    async fn generate_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<GenerationOutput> {
+        let started = Instant::now();
         let url = format!("{}/v1/completions", self.endpoint);
-        let body = serde_json::json!({
-            "model": self.model,
-            "prompt": prompt,
-            "max_tokens": 4096,
-            "temperature": 0.7
-        });
+        let body = self.body(prompt, params);
 
         let mut request = self.client.post(&url).json(&body);
 
@@ -81,10 +123,41 @@ impl LlmBackend for VllmBackend {
         }
 
         let result: serde_json::Value = response.json().await?;
-        Ok(result["choices"][0]["text"]
+        let text = result["choices"][0]["text"]
             .as_str()
             .unwrap_or("")
-            .to_string())
+            .to_string();
+        Ok(GenerationOutput::new(text, TokenUsage::from_openai(&result).unwrap_or_default(), started))
+    }
+
+    async fn generate_stream_with(&self, prompt: &str, params: &GenParams) -> anyhow::Result<TokenStream> {
+        let started = Instant::now();
+        let url = format!("{}/v1/completions", self.endpoint);
+        let mut body = self.body(prompt, params);
+        body["stream"] = serde_json::json!(true);
+        body["stream_options"] = serde_json::json!({"include_usage": true});
+
+        let mut request = self.client.post(&url).json(&body);
+
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.timeout(self.timeout).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("vLLM request failed ({}): {}", status, text);
+        }
+
+        let delta: DeltaParser = |event| {
+            if let Some(message) = event["error"]["message"].as_str().or(event["message"].as_str()) {
+                anyhow::bail!("vLLM stream failed: {}", message);
+            }
+            Ok(event["choices"][0]["text"].as_str().map(str::to_string))
+        };
+        Ok(spawn_http(started, response, Framing::Sse, delta, TokenUsage::from_openai))
     }
 
     async fn health_check(&self) -> anyhow::Result<()> {
@@ -0,0 +1,115 @@
+//! Plugin API Caller
+//!
+//! Resolves who made a plugin request (`/agent/*`) from its
+//! `Authorization: Bearer` header: a JWT from `/api/auth/login`, or the
+//! user's API key. The caller's company is the tenant of everything the
+//! request reads and writes.
+//!
+//! Calls without credentials are accepted in single-company deployments
+//! (attributed to the system user) and rejected once users belong to
+//! companies, since an anonymous call cannot be given a tenant.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+use loco_rs::{app::AppContext, auth, Error};
+use std::future::Future;
+
+use crate::models::users::{self, SYSTEM_USER_ID};
+use crate::services::TenantScope;
+
+/// Caller of a plugin API request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiCaller {
+    /// Authenticated user (None = anonymous)
+    pub user_id: Option<i32>,
+    /// Tenant company code (None = platform)
+    pub company: Option<String>,
+}
+
+impl ApiCaller {
+    /// Anonymous caller of a single-company deployment
+    pub fn anonymous() -> Self {
+        Self::default()
+    }
+
+    /// User rows created by the request are attributed to
+    pub fn stored_user_id(&self) -> i32 {
+        self.user_id.unwrap_or(SYSTEM_USER_ID)
+    }
+
+    pub fn tenant(&self) -> TenantScope {
+        TenantScope::new(self.company.as_deref())
+    }
+
+    fn from_user(user: users::Model) -> Self {
+        Self {
+            user_id: Some(user.id),
+            company: user.company,
+        }
+    }
+
+    /// Caller of a bearer token: a valid JWT, else an API key
+    async fn resolve(ctx: &AppContext, token: &str) -> Result<Self, Error> {
+        let unauthorized = || Error::Unauthorized("Invalid token or API key".to_string());
+
+        let jwt_config = ctx.config.get_jwt_config()?;
+        if let Ok(claims) = auth::jwt::JWT::new(&jwt_config.secret).validate(token) {
+            let user = users::Model::find_by_pid(&ctx.db, &claims.claims.pid)
+                .await
+                .map_err(|_| unauthorized())?;
+            return Ok(Self::from_user(user));
+        }
+
+        let user = users::Model::find_by_api_key(&ctx.db, token)
+            .await
+            .map_err(|_| unauthorized())?;
+        Ok(Self::from_user(user))
+    }
+}
+
+/// Bearer token of the request
+fn bearer_token(parts: &Parts) -> Option<String> {
+    let value = parts.headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let token = value.strip_prefix("Bearer ").map(str::trim)?;
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+impl FromRequestParts<AppContext> for ApiCaller {
+    type Rejection = Error;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppContext,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
+        let token = bearer_token(parts);
+        let ctx = state.clone();
+
+        async move {
+            match token {
+                Some(token) => Self::resolve(&ctx, &token).await,
+                None if TenantScope::is_multi_company(&ctx.db).await => Err(Error::Unauthorized(
+                    "Authentication required: send a JWT or API key as a Bearer token".to_string(),
+                )),
+                None => Ok(Self::anonymous()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymous_caller_is_platform_system_user() {
+        let caller = ApiCaller::anonymous();
+        assert_eq!(caller.stored_user_id(), SYSTEM_USER_ID);
+        assert_eq!(caller.tenant(), TenantScope::Platform);
+
+        let caller = ApiCaller { user_id: Some(7), company: Some("acme".to_string()) };
+        assert_eq!(caller.stored_user_id(), 7);
+        assert_eq!(caller.tenant().company(), Some("acme"));
+    }
+}
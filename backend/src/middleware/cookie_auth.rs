@@ -20,6 +20,28 @@ pub struct AuthUser {
     pub pid: String,
     pub name: String,
     pub email: String,
+    /// Tenant company code (None = platform administrator)
+    #[serde(default)]
+    pub company: Option<String>,
+}
+
+impl AuthUser {
+    /// Administrator of a `/api` request authenticated with a bearer JWT
+    pub async fn from_jwt(ctx: &AppContext, jwt: &loco_rs::prelude::auth::JWT) -> loco_rs::Result<Self> {
+        let user = users::Model::find_by_pid(&ctx.db, &jwt.claims.pid).await?;
+        Ok(Self::from(user))
+    }
+}
+
+impl From<users::Model> for AuthUser {
+    fn from(user: users::Model) -> Self {
+        Self {
+            pid: user.pid.to_string(),
+            name: user.name,
+            email: user.email,
+            company: user.company,
+        }
+    }
 }
 
 /// Error that redirects to login page
@@ -104,11 +126,7 @@ impl FromRequestParts<AppContext> for AuthUser {
                     redirect_to: redirect_to.clone(),
                 })?;
 
-            Ok(AuthUser {
-                pid: user.pid.to_string(),
-                name: user.name,
-                email: user.email,
-            })
+            Ok(AuthUser::from(user))
         }
     }
 }
@@ -2,4 +2,6 @@
 //!
 //! Custom middleware for authentication, logging, etc.
 
+pub mod api_auth;
 pub mod cookie_auth;
+pub mod redaction;
@@ -0,0 +1,119 @@
+//! Response Redaction Middleware
+//!
+//! Runs the process-wide `Redactor` over JSON and text responses, so no
+//! handler can leak provider or model details to the plugin. Binary bodies
+//! (ZIP downloads), streamed or larger bodies (over `MAX_SCRUB_BYTES`) and
+//! exempt admin paths pass through untouched.
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::Request,
+    http::{header, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+use crate::services::redaction::{redactor, Redactor};
+
+/// Largest body buffered for scrubbing
+const MAX_SCRUB_BYTES: usize = 2 * 1024 * 1024;
+
+/// Scrub the response of every request the redactor applies to
+pub async fn scrub_responses(request: Request, next: Next) -> Response {
+    let redactor = redactor();
+    if !redactor.applies_to(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let response = next.run(request).await;
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if !is_text(&content_type) || !fits(response.body()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_SCRUB_BYTES).await {
+        Ok(bytes) => scrub_body(redactor, &content_type, bytes.to_vec()),
+        Err(err) => {
+            tracing::warn!(error = %err, "Could not read response body for redaction");
+            parts.status = StatusCode::INTERNAL_SERVER_ERROR;
+            Vec::new()
+        }
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Content types that are scrubbed (XML and JavaScript are artifacts)
+fn is_text(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+    (mime.starts_with("text/") && mime != "text/html") || mime == "application/json" || mime.ends_with("+json")
+}
+
+/// Whether the body has a known size small enough to buffer
+fn fits(body: &Body) -> bool {
+    body.size_hint()
+        .exact()
+        .is_some_and(|len| usize::try_from(len).is_ok_and(|len| len <= MAX_SCRUB_BYTES))
+}
+
+/// `body` with LLM details removed; JSON bodies are only re-serialized when
+/// something was scrubbed
+pub fn scrub_body(redactor: &Redactor, content_type: &str, body: Vec<u8>) -> Vec<u8> {
+    if content_type.contains("json") {
+        if let Ok(mut value) = serde_json::from_slice::<Value>(&body) {
+            if !redactor.scrub_json(&mut value) {
+                return body;
+            }
+            return serde_json::to_vec(&value).unwrap_or_default();
+        }
+    }
+    let text = String::from_utf8_lossy(&body);
+    let scrubbed = redactor.scrub_text(&text);
+    if scrubbed == text {
+        body
+    } else {
+        scrubbed.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::redaction::RedactionConfig;
+
+    #[test]
+    fn test_scrub_body() {
+        let redactor = Redactor::new(RedactionConfig::default());
+
+        let error = br#"{"error":"internal_server_error","description":"Ollama request failed (500): {\"error\":\"model 'codellama:13b' not found\"}"}"#;
+        let scrubbed: Value = serde_json::from_slice(&scrub_body(&redactor, "application/json", error.to_vec())).unwrap();
+        assert_eq!(
+            scrubbed,
+            serde_json::json!({"error": "internal_server_error", "description": "[redacted] request failed (500)"})
+        );
+
+        let clean = b"{ \"status\": \"success\" }".to_vec();
+        assert_eq!(scrub_body(&redactor, "application/json", clean.clone()), clean);
+
+        let text = b"LLM unavailable: http://localhost:11434 refused".to_vec();
+        assert_eq!(
+            scrub_body(&redactor, "text/plain; charset=utf-8", text),
+            b"LLM unavailable: [redacted] refused".to_vec()
+        );
+
+        assert!(is_text("application/json; charset=utf-8"));
+        assert!(!is_text("application/zip"));
+        assert!(!is_text("text/html; charset=utf-8"));
+        assert!(!is_text("application/javascript"));
+
+        assert!(fits(&Body::from(error.to_vec())));
+        assert!(!fits(&Body::from(vec![b' '; MAX_SCRUB_BYTES + 1])));
+    }
+}
@@ -15,6 +15,14 @@ pub struct Model {
     pub naming_convention: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub additional_rules: Option<String>,
+    pub sections: Option<Json>,
+    /// Tenant company code (NULL = shared by all tenants)
+    pub company: Option<String>,
+    /// Why the content guard quarantined this row (NULL = usable in prompts)
+    #[sea_orm(column_type = "Text", nullable)]
+    pub quarantine_reason: Option<String>,
+    /// Administrator whose save was quarantined (cannot approve it)
+    pub quarantined_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -22,6 +22,8 @@ pub struct Model {
     pub warnings: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub error_message: Option<String>,
+    /// Machine-readable cause of a failure (e.g. `provider_refusal`)
+    pub error_code: Option<String>,
     pub generation_time_ms: Option<i32>,
     pub user_id: i32,
     #[sea_orm(unique)]
@@ -36,6 +38,51 @@ pub struct Model {
     pub model_name: Option<String>,
     /// LLM provider used for generation (internal audit only)
     pub provider: Option<String>,
+    /// Deterministic artifact quality score (0-100)
+    pub quality_score: Option<i32>,
+    /// Knowledge base entry revisions included in the prompt (JSON array)
+    #[sea_orm(column_type = "Text", nullable)]
+    pub knowledge_revisions: Option<String>,
+    /// Tenant company code of the requesting user (NULL = platform)
+    pub company: Option<String>,
+    /// Until when the claiming worker holds the job (see the job queue)
+    pub lease_expires_at: Option<DateTimeWithTimeZone>,
+    /// How many times a worker claimed the job
+    pub attempts: i32,
+    /// When the (first) LLM call started
+    pub llm_started_at: Option<DateTimeWithTimeZone>,
+    /// When the (last) LLM call returned
+    pub llm_finished_at: Option<DateTimeWithTimeZone>,
+    /// When post-processing of the output finished
+    pub pipeline_finished_at: Option<DateTimeWithTimeZone>,
+    /// Project generated for (`context.project`; package base for Spring)
+    pub project: Option<String>,
+    /// Pipeline profile the output was post-processed with
+    pub pipeline_profile: Option<String>,
+    /// Per-pass overrides of that profile at generation time (JSON object)
+    #[sea_orm(column_type = "Text", nullable)]
+    pub pipeline_overrides: Option<String>,
+    /// Generation log this one replays (admin replay action)
+    pub replay_of: Option<i32>,
+    /// Requirements ticket of the request (`context.issue_key`)
+    pub issue_key: Option<String>,
+    /// Canary rollout running when the generation was routed
+    pub canary_id: Option<i32>,
+    /// Arm it took: `canary` or `stable`
+    pub canary_arm: Option<String>,
+    /// Where each source column ended up in the generated files (JSON)
+    #[sea_orm(column_type = "Text", nullable)]
+    pub lineage: Option<String>,
+    /// When the retention cleanup may purge the artifacts (NULL = kept)
+    pub artifacts_expire_at: Option<DateTimeWithTimeZone>,
+    /// When the retention cleanup purged them
+    pub artifacts_purged_at: Option<DateTimeWithTimeZone>,
+    /// Prompt tokens of the LLM calls (summed over retries)
+    pub prompt_tokens: Option<i32>,
+    /// Tokens the LLM generated (summed over retries)
+    pub completion_tokens: Option<i32>,
+    /// Time spent in LLM calls
+    pub llm_latency_ms: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
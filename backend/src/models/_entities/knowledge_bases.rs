@@ -21,7 +21,26 @@ pub struct Model {
     pub token_estimate: Option<i32>,
     pub version: Option<i32>,
     pub is_active: Option<bool>,
+    pub usage_count: i32,
+    pub clean_count: i32,
+    pub finding_count: i32,
+    /// Tenant company code (NULL = shared by all tenants)
+    pub company: Option<String>,
+    /// Why the content guard quarantined this row (NULL = usable in prompts)
+    #[sea_orm(column_type = "Text", nullable)]
+    pub quarantine_reason: Option<String>,
+    /// Administrator whose save was quarantined (cannot approve it)
+    pub quarantined_by: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::knowledge_base_revisions::Entity")]
+    KnowledgeBaseRevisions,
+}
+
+impl Related<super::knowledge_base_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::KnowledgeBaseRevisions.def()
+    }
+}
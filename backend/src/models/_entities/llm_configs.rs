@@ -27,6 +27,11 @@ pub struct Model {
     pub n_threads: Option<i32>,
     /// Request timeout in seconds (NULL = use LLM_TIMEOUT_SECONDS env var, default 120)
     pub timeout_secs: Option<i32>,
+    /// Tenant company code (NULL = shared fallback config)
+    pub company: Option<String>,
+    /// Order in which this config backs up the active one when it fails
+    /// (lowest first; NULL = not a fallback candidate)
+    pub fallback_priority: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -2,9 +2,31 @@
 
 pub mod prelude;
 
+pub mod checklist_items;
+pub mod column_archetypes;
 pub mod company_rules;
+pub mod distillation_examples;
+pub mod distillation_settings;
+pub mod error_code_catalogs;
 pub mod generation_logs;
 pub mod knowledge_bases;
+pub mod knowledge_base_revisions;
 pub mod llm_configs;
+pub mod notification_settings;
+pub mod project_output_settings;
+pub mod naming_profiles;
+pub mod pipeline_profiles;
+pub mod saved_intents;
+pub mod race_results;
 pub mod prompt_templates;
 pub mod users;
+pub mod validation_plugins;
+pub mod admin_audit_logs;
+pub mod template_activations;
+pub mod canary_rollouts;
+pub mod batch_schedules;
+pub mod calendar_overrides;
+pub mod schema_snapshots;
+pub mod retention_policies;
+pub mod screen_registrations;
+pub mod queue_settings;
@@ -1,8 +1,30 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.17
 
+pub use super::checklist_items::Entity as ChecklistItems;
+pub use super::column_archetypes::Entity as ColumnArchetypes;
 pub use super::company_rules::Entity as CompanyRules;
+pub use super::distillation_examples::Entity as DistillationExamples;
+pub use super::distillation_settings::Entity as DistillationSettings;
+pub use super::error_code_catalogs::Entity as ErrorCodeCatalogs;
 pub use super::generation_logs::Entity as GenerationLogs;
 pub use super::knowledge_bases::Entity as KnowledgeBases;
+pub use super::knowledge_base_revisions::Entity as KnowledgeBaseRevisions;
 pub use super::llm_configs::Entity as LlmConfigs;
+pub use super::notification_settings::Entity as NotificationSettings;
+pub use super::project_output_settings::Entity as ProjectOutputSettings;
+pub use super::naming_profiles::Entity as NamingProfiles;
+pub use super::pipeline_profiles::Entity as PipelineProfiles;
+pub use super::saved_intents::Entity as SavedIntents;
+pub use super::race_results::Entity as RaceResults;
 pub use super::prompt_templates::Entity as PromptTemplates;
 pub use super::users::Entity as Users;
+pub use super::validation_plugins::Entity as ValidationPlugins;
+pub use super::admin_audit_logs::Entity as AdminAuditLogs;
+pub use super::template_activations::Entity as TemplateActivations;
+pub use super::canary_rollouts::Entity as CanaryRollouts;
+pub use super::batch_schedules::Entity as BatchSchedules;
+pub use super::calendar_overrides::Entity as CalendarOverrides;
+pub use super::schema_snapshots::Entity as SchemaSnapshots;
+pub use super::retention_policies::Entity as RetentionPolicies;
+pub use super::screen_registrations::Entity as ScreenRegistrations;
+pub use super::queue_settings::Entity as QueueSettings;
@@ -19,7 +19,18 @@ pub struct Model {
     pub user_prompt_template: String,
     pub version: i32,
     pub is_active: Option<bool>,
+    /// Tenant company code (NULL = shared by all tenants)
+    pub company: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::template_activations::Entity")]
+    TemplateActivations,
+}
+
+impl Related<super::template_activations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TemplateActivations.def()
+    }
+}
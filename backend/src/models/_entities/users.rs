@@ -24,6 +24,10 @@ pub struct Model {
     pub email_verified_at: Option<DateTimeWithTimeZone>,
     pub magic_link_token: Option<String>,
     pub magic_link_expiration: Option<DateTimeWithTimeZone>,
+    /// Tenant company code (NULL = platform administrator)
+    pub company: Option<String>,
+    /// License role matched by retention policies (e.g. `poc`, `licensed`)
+    pub role: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
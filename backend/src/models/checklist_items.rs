@@ -0,0 +1,71 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{Condition, QueryOrder};
+
+use crate::domain::{ChecklistItem, ChecklistTarget};
+use crate::services::TenantScope;
+pub use super::_entities::checklist_items::{ActiveModel, Model, Entity};
+use super::_entities::checklist_items::Column;
+pub type ChecklistItems = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Active items for a product/screen type: shared items plus the tenant's own
+    ///
+    /// Items without a screen type apply to every screen. A lookup error
+    /// yields no items, so generation never fails on the checklist.
+    pub async fn for_screen(
+        db: &DatabaseConnection,
+        product: &str,
+        screen_type: &str,
+        tenant: &TenantScope,
+    ) -> Vec<ChecklistItem> {
+        let result = Entity::find()
+            .filter(Column::Product.eq(product))
+            .filter(Column::IsActive.eq(Some(true)))
+            .filter(
+                Condition::any()
+                    .add(Column::ScreenType.is_null())
+                    .add(Column::ScreenType.eq(screen_type)),
+            )
+            .filter(tenant.usable(Column::Company))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await;
+
+        match result {
+            Ok(items) => items.iter().filter_map(Self::checklist_item).collect(),
+            Err(e) => {
+                tracing::error!("Failed to load checklist for {}/{}: {}", product, screen_type, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Domain item; `None` for an unknown target
+    pub fn checklist_item(&self) -> Option<ChecklistItem> {
+        let target = ChecklistTarget::parse(&self.target)?;
+        Some(ChecklistItem::new(self.label.clone(), target, self.pattern.clone()))
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
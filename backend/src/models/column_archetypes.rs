@@ -0,0 +1,79 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+
+use crate::domain::ColumnArchetype;
+use crate::services::TenantScope;
+pub use super::_entities::column_archetypes::{ActiveModel, Model, Entity};
+use super::_entities::column_archetypes::Column;
+pub type ColumnArchetypes = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Active archetypes a generation matches columns against: the tenant's
+    /// own first, then the shared ones
+    ///
+    /// A lookup error yields no archetypes, so generation never fails on them.
+    pub async fn active(db: &DatabaseConnection, tenant: &TenantScope) -> Vec<ColumnArchetype> {
+        let query = Entity::find().filter(Column::IsActive.eq(Some(true)));
+        let result = tenant
+            .prefer_own(query, Column::Company)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await;
+
+        match result {
+            Ok(archetypes) => archetypes.iter().map(Self::archetype).collect(),
+            Err(e) => {
+                tracing::error!("Failed to load column archetypes: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Domain archetype (unknown UI/data types are ignored)
+    pub fn archetype(&self) -> ColumnArchetype {
+        ColumnArchetype {
+            name_patterns: split_list(self.name_patterns.as_deref()),
+            comment_keywords: split_list(self.comment_keywords.as_deref()),
+            label: self.label.clone(),
+            ui_type: self.ui_type.as_deref().and_then(ColumnArchetype::parse_ui_type),
+            data_type: self.data_type.as_deref().and_then(ColumnArchetype::parse_data_type),
+            max_length: self.max_length.and_then(|n| u32::try_from(n).ok()),
+            validation: self.validation.clone(),
+            format: self.format.clone(),
+            ..ColumnArchetype::new(self.name.clone())
+        }
+    }
+}
+
+/// Entries of a comma- or newline-separated list
+fn split_list(raw: Option<&str>) -> Vec<String> {
+    raw.unwrap_or_default()
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -1,4 +1,10 @@
+use loco_rs::model::{ModelError, ModelResult};
 use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue;
+
+use crate::domain::CompanyRuleSections;
+use crate::services::content_guard::{ContentGuard, ContentScan};
+use crate::services::TenantScope;
 pub use super::_entities::company_rules::{ActiveModel, Model, Entity};
 pub type CompanyRules = Entity;
 
@@ -8,21 +14,111 @@ impl ActiveModelBehavior for ActiveModel {
     where
         C: ConnectionTrait,
     {
-        if !insert && self.updated_at.is_unchanged() {
-            let mut this = self;
+        let this = self.guard_content();
+        if !insert && this.updated_at.is_unchanged() {
+            let mut this = this;
             this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
             Ok(this)
         } else {
-            Ok(self)
+            Ok(this)
         }
     }
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+    /// Find a rule set by name, among the shared rule sets and the tenant's own
+    ///
+    /// Quarantined rule sets are skipped until approved.
+    pub async fn find_by_name(
+        db: &DatabaseConnection,
+        name: &str,
+        tenant: &TenantScope,
+    ) -> ModelResult<Self> {
+        let query = Entity::find()
+            .filter(super::_entities::company_rules::Column::Name.eq(name))
+            .filter(super::_entities::company_rules::Column::QuarantineReason.is_null());
+        let item = tenant
+            .prefer_own(query, super::_entities::company_rules::Column::Company)
+            .one(db)
+            .await?;
+        item.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Structured sections, with the legacy `naming_convention` column as naming fallback
+    pub fn rule_sections(&self) -> CompanyRuleSections {
+        let mut sections: CompanyRuleSections = self
+            .sections
+            .clone()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+
+        if sections.naming.as_deref().is_none_or(str::is_empty) {
+            sections.naming = self.naming_convention.clone();
+        }
+        sections
+    }
+
+    /// Render the rules relevant to a product/screen type for prompt injection.
+    ///
+    /// Free-text `additional_rules` are always appended.
+    pub fn render_for(&self, product: &str, screen_type: Option<&str>) -> String {
+        let mut rendered = self.rule_sections().render(product, screen_type);
+
+        if let Some(additional) = self.additional_rules.as_deref().filter(|s| !s.trim().is_empty()) {
+            if !rendered.is_empty() {
+                rendered.push_str("\n\n");
+            }
+            rendered.push_str(additional.trim());
+        }
+        rendered
+    }
+}
 
 // implement your write-oriented logic here
-impl ActiveModel {}
+impl ActiveModel {
+    /// Sanitize the rule texts and decide the quarantine when any of them changes
+    ///
+    /// All three fields are rescanned, so editing a clean field does not lift
+    /// a quarantine caused by another one.
+    fn guard_content(mut self) -> Self {
+        if !(self.naming_convention.is_set() || self.additional_rules.is_set() || self.sections.is_set()) {
+            return self;
+        }
+
+        let guard = ContentGuard::from_env();
+        let mut scan = ContentScan::default();
+        let current = |value: &ActiveValue<Option<String>>| match value {
+            ActiveValue::Set(v) | ActiveValue::Unchanged(v) => v.clone(),
+            ActiveValue::NotSet => None,
+        };
+        let naming = current(&self.naming_convention).map(|t| guard.sanitize("naming_convention", &t, &mut scan));
+        let additional = current(&self.additional_rules).map(|t| guard.sanitize("additional_rules", &t, &mut scan));
+        let sections = match &self.sections {
+            ActiveValue::Set(v) | ActiveValue::Unchanged(v) => v.clone(),
+            ActiveValue::NotSet => None,
+        }
+        .map(|v| guard.sanitize_json("sections", v, &mut scan));
+
+        self.naming_convention = ActiveValue::Set(naming);
+        self.additional_rules = ActiveValue::Set(additional);
+        self.sections = ActiveValue::Set(sections);
+
+        match scan.quarantine_reason() {
+            Some(reason) => {
+                self.quarantine_reason = ActiveValue::Set(Some(reason));
+                if !self.quarantined_by.is_set() {
+                    self.quarantined_by = ActiveValue::Set(None);
+                }
+            }
+            None => {
+                self.quarantine_reason = ActiveValue::Set(None);
+                self.quarantined_by = ActiveValue::Set(None);
+            }
+        }
+        self
+    }
+}
 
 // implement your custom finders, selectors oriented logic here
 impl Entity {}
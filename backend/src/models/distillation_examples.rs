@@ -0,0 +1,59 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue;
+
+pub use super::_entities::distillation_examples::{ActiveModel, Model, Entity};
+use super::_entities::distillation_examples::Column;
+pub type DistillationExamples = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Examples of a product and screen type captured for `company` (`None` = platform)
+    pub async fn candidates(
+        db: &DatabaseConnection,
+        product: &str,
+        screen_type: &str,
+        company: Option<&str>,
+    ) -> Result<Vec<Self>, DbErr> {
+        let company = match company {
+            Some(code) => Column::Company.eq(code),
+            None => Column::Company.is_null(),
+        };
+        Entity::find()
+            .filter(Column::Product.eq(product))
+            .filter(Column::ScreenType.eq(screen_type))
+            .filter(company)
+            .all(db)
+            .await
+    }
+
+    /// Record that the example went into a prompt (feeds LRU eviction)
+    pub async fn mark_used(self, db: &DatabaseConnection) -> Result<Self, DbErr> {
+        let use_count = self.use_count + 1;
+        let mut item: ActiveModel = self.into();
+        item.use_count = ActiveValue::Set(use_count);
+        item.last_used_at = ActiveValue::Set(Some(chrono::Utc::now().into()));
+        item.update(db).await
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
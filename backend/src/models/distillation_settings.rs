@@ -0,0 +1,84 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, QueryOrder};
+
+pub use super::_entities::distillation_settings::{ActiveModel, Model, Entity};
+use super::_entities::distillation_settings::Column;
+pub type DistillationSettings = Entity;
+
+/// Eviction policies, in the order the admin form lists them
+pub const EVICTION_POLICIES: [&str; 3] = ["lru", "oldest", "lowest_quality"];
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Stored settings, or the defaults (capture off) before the first save
+    pub async fn current(db: &DatabaseConnection) -> Self {
+        Entity::find()
+            .order_by_asc(Column::Id)
+            .one(db)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| {
+                let now = chrono::Utc::now().into();
+                Self {
+                    created_at: now,
+                    updated_at: now,
+                    id: 0,
+                    enabled: false,
+                    max_examples: 200,
+                    eviction: "lru".to_string(),
+                    min_quality: 80,
+                }
+            })
+    }
+
+    /// Replace the settings (the table holds a single row)
+    pub async fn save(
+        db: &DatabaseConnection,
+        enabled: bool,
+        max_examples: i32,
+        eviction: &str,
+        min_quality: i32,
+    ) -> Result<Self, DbErr> {
+        let current = Self::current(db).await;
+        let insert = current.id == 0;
+        let mut item: ActiveModel = if insert {
+            ActiveModel {
+                ..Default::default()
+            }
+        } else {
+            current.into()
+        };
+        item.enabled = ActiveValue::Set(enabled);
+        item.max_examples = ActiveValue::Set(max_examples);
+        item.eviction = ActiveValue::Set(eviction.to_string());
+        item.min_quality = ActiveValue::Set(min_quality);
+        if insert {
+            item.insert(db).await
+        } else {
+            item.update(db).await
+        }
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,77 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+
+use crate::domain::{ErrorCode, ErrorCodeCatalog};
+use crate::services::TenantScope;
+pub use super::_entities::error_code_catalogs::{ActiveModel, Model, Entity};
+use super::_entities::error_code_catalogs::Column;
+pub type ErrorCodeCatalogs = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Codes of the enabled catalogs a generation may use: the tenant's own
+    /// first, then the shared ones (an own entry wins over a shared one)
+    ///
+    /// A lookup error yields an empty catalog, so generation never fails on it.
+    pub async fn active(db: &DatabaseConnection, tenant: &TenantScope) -> ErrorCodeCatalog {
+        let query = Entity::find().filter(Column::Enabled.eq(true));
+        let result = tenant
+            .prefer_own(query, Column::Company)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await;
+
+        let mut merged = ErrorCodeCatalog::default();
+        match result {
+            Ok(catalogs) => {
+                for catalog in catalogs {
+                    merged.merge(catalog.catalog());
+                }
+            }
+            Err(e) => tracing::error!("Failed to load error-code catalogs: {}", e),
+        }
+        merged
+    }
+
+    /// Domain catalog (empty if the stored codes don't parse)
+    pub fn catalog(&self) -> ErrorCodeCatalog {
+        ErrorCodeCatalog {
+            codes: serde_json::from_str::<Vec<ErrorCode>>(&self.codes).unwrap_or_default(),
+        }
+    }
+
+    pub async fn find_by_name(
+        db: &DatabaseConnection,
+        name: &str,
+        company: Option<&str>,
+    ) -> Result<Option<Self>, DbErr> {
+        let query = Entity::find().filter(Column::Name.eq(name));
+        let query = match company {
+            Some(company) => query.filter(Column::Company.eq(company)),
+            None => query.filter(Column::Company.is_null()),
+        };
+        query.one(db).await
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -4,10 +4,26 @@ pub type GenerationLogs = Entity;
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
-    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    async fn before_save<C>(self, db: &C, insert: bool) -> std::result::Result<Self, DbErr>
     where
         C: ConnectionTrait,
     {
+        #[cfg(feature = "fault-injection")]
+        if crate::services::fault_injection::FaultInjector::db_write_fails() {
+            tracing::warn!("Fault injection: failing generation log write");
+            return Err(DbErr::Custom("Injected fault: database write failed".to_string()));
+        }
+
+        // Logs belong to the requesting user's company
+        if insert && self.company.is_not_set() {
+            if let Some(user_id) = self.user_id.try_as_ref().copied() {
+                let user = super::_entities::users::Entity::find_by_id(user_id).one(db).await?;
+                let mut this = self;
+                this.company = sea_orm::ActiveValue::Set(user.and_then(|u| u.company));
+                return Ok(this);
+            }
+        }
+
         if !insert && self.updated_at.is_unchanged() {
             let mut this = self;
             this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
@@ -0,0 +1,104 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{QueryOrder, Set};
+
+pub use super::_entities::knowledge_base_revisions::{ActiveModel, Model, Entity};
+use super::_entities::knowledge_base_revisions::Column;
+use super::_entities::knowledge_bases;
+pub type KnowledgeBaseRevisions = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Record the entry's current state as a new revision if it differs from
+    /// the latest one; returns the entry's current revision number
+    pub async fn record<C>(db: &C, entry: &knowledge_bases::Model) -> Result<i32, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let latest = Entity::find()
+            .filter(Column::KnowledgeBaseId.eq(entry.id))
+            .order_by_desc(Column::Revision)
+            .one(db)
+            .await?;
+
+        let revision = match &latest {
+            Some(latest) if latest.matches(entry) => return Ok(latest.revision),
+            Some(latest) => latest.revision + 1,
+            None => entry.version.unwrap_or(1),
+        };
+
+        ActiveModel {
+            knowledge_base_id: Set(entry.id),
+            revision: Set(revision),
+            name: Set(entry.name.clone()),
+            category: Set(entry.category.clone()),
+            component: Set(entry.component.clone()),
+            section: Set(entry.section.clone()),
+            content: Set(entry.content.clone()),
+            relevance_tags: Set(entry.relevance_tags.clone()),
+            priority: Set(entry.priority.clone()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Ok(revision)
+    }
+
+    /// Whether the revision has the same prompt-relevant fields as the entry
+    pub fn matches(&self, entry: &knowledge_bases::Model) -> bool {
+        self.name == entry.name
+            && self.category == entry.category
+            && self.component == entry.component
+            && self.section == entry.section
+            && self.content == entry.content
+            && self.relevance_tags == entry.relevance_tags
+            && self.priority == entry.priority
+    }
+
+    /// Revisions of an entry, newest first
+    pub async fn history(db: &DatabaseConnection, knowledge_base_id: i32) -> Result<Vec<Self>, DbErr> {
+        Entity::find()
+            .filter(Column::KnowledgeBaseId.eq(knowledge_base_id))
+            .order_by_desc(Column::Revision)
+            .all(db)
+            .await
+    }
+
+    /// Revisions for `(entry id, revision)` pairs, in the given order
+    ///
+    /// Pairs without a stored revision are skipped.
+    pub async fn resolve(db: &DatabaseConnection, revisions: &[(i32, i32)]) -> Result<Vec<Self>, DbErr> {
+        let mut resolved = Vec::with_capacity(revisions.len());
+        for (id, revision) in revisions {
+            let found = Entity::find()
+                .filter(Column::KnowledgeBaseId.eq(*id))
+                .filter(Column::Revision.eq(*revision))
+                .one(db)
+                .await?;
+            resolved.extend(found);
+        }
+        Ok(resolved)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -1,5 +1,11 @@
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::ActiveValue;
 pub use super::_entities::knowledge_bases::{ActiveModel, Model, Entity};
+use super::_entities::knowledge_bases::Column;
+use super::knowledge_base_revisions;
+use crate::services::content_guard::{ContentGuard, ContentScan};
+use crate::services::lookup_cache::{LookupCache, Namespace};
 pub type KnowledgeBases = Entity;
 
 #[async_trait::async_trait]
@@ -8,21 +14,79 @@ impl ActiveModelBehavior for ActiveModel {
     where
         C: ConnectionTrait,
     {
-        if !insert && self.updated_at.is_unchanged() {
-            let mut this = self;
+        let this = self.guard_content();
+        if !insert && this.updated_at.is_unchanged() {
+            let mut this = this;
             this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
             Ok(this)
         } else {
-            Ok(self)
+            Ok(this)
         }
     }
+
+    /// Every save that changes prompt-relevant fields becomes a new revision;
+    /// `version` tracks the current revision number
+    async fn after_save<C>(model: Model, db: &C, _insert: bool) -> std::result::Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        LookupCache::invalidate(Namespace::Knowledge).await;
+
+        let revision = knowledge_base_revisions::Model::record(db, &model).await?;
+        if model.version == Some(revision) {
+            return Ok(model);
+        }
+
+        // Bulk update so the hooks don't run again
+        Entity::update_many()
+            .col_expr(Column::Version, Expr::value(revision))
+            .filter(Column::Id.eq(model.id))
+            .exec(db)
+            .await?;
+        Ok(Model { version: Some(revision), ..model })
+    }
+
+    async fn after_delete<C>(self, _db: &C) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        LookupCache::invalidate(Namespace::Knowledge).await;
+        Ok(self)
+    }
 }
 
 // implement your read-oriented logic here
 impl Model {}
 
 // implement your write-oriented logic here
-impl ActiveModel {}
+impl ActiveModel {
+    /// Sanitize newly saved content and decide its quarantine
+    ///
+    /// Clean content lifts an earlier quarantine. `quarantined_by` is set by
+    /// callers that know the saving administrator; otherwise it is cleared.
+    fn guard_content(mut self) -> Self {
+        let ActiveValue::Set(content) = &self.content else {
+            return self;
+        };
+        let mut scan = ContentScan::default();
+        let cleaned = ContentGuard::from_env().sanitize("content", content, &mut scan);
+        self.content = ActiveValue::Set(cleaned);
+
+        match scan.quarantine_reason() {
+            Some(reason) => {
+                self.quarantine_reason = ActiveValue::Set(Some(reason));
+                if !self.quarantined_by.is_set() {
+                    self.quarantined_by = ActiveValue::Set(None);
+                }
+            }
+            None => {
+                self.quarantine_reason = ActiveValue::Set(None);
+                self.quarantined_by = ActiveValue::Set(None);
+            }
+        }
+        self
+    }
+}
 
 // implement your custom finders, selectors oriented logic here
 impl Entity {}
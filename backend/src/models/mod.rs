@@ -5,3 +5,25 @@ pub mod company_rules;
 pub mod generation_logs;
 pub mod llm_configs;
 pub mod knowledge_bases;
+pub mod knowledge_base_revisions;
+pub mod project_output_settings;
+pub mod naming_profiles;
+pub mod pipeline_profiles;
+pub mod saved_intents;
+pub mod race_results;
+pub mod checklist_items;
+pub mod distillation_examples;
+pub mod distillation_settings;
+pub mod notification_settings;
+pub mod validation_plugins;
+pub mod admin_audit_logs;
+pub mod template_activations;
+pub mod canary_rollouts;
+pub mod batch_schedules;
+pub mod calendar_overrides;
+pub mod schema_snapshots;
+pub mod retention_policies;
+pub mod screen_registrations;
+pub mod queue_settings;
+pub mod column_archetypes;
+pub mod error_code_catalogs;
@@ -0,0 +1,75 @@
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::entity::prelude::*;
+
+use crate::domain::NamingProfile;
+use crate::services::TenantScope;
+pub use super::_entities::naming_profiles::{ActiveModel, Model, Entity};
+pub type NamingProfiles = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Find a profile by name (`options.naming_profile`), among the shared
+    /// profiles and the tenant's own
+    pub async fn find_by_name(db: &DatabaseConnection, name: &str, tenant: &TenantScope) -> ModelResult<Self> {
+        let query = Entity::find().filter(super::_entities::naming_profiles::Column::Name.eq(name));
+        let item = tenant
+            .prefer_own(query, super::_entities::naming_profiles::Column::Company)
+            .one(db)
+            .await?;
+        item.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Profile selected by `options.naming_profile`; the defaults plus a note if it doesn't exist
+    pub async fn profile_or_default(
+        db: &DatabaseConnection,
+        name: Option<&str>,
+        tenant: &TenantScope,
+    ) -> (NamingProfile, Option<String>) {
+        let Some(name) = name.filter(|n| !n.trim().is_empty()) else {
+            return (NamingProfile::default(), None);
+        };
+        match Self::find_by_name(db, name.trim(), tenant).await {
+            Ok(profile) => (profile.naming_profile(), None),
+            Err(_) => (
+                NamingProfile::default(),
+                Some(format!("Note: naming profile '{}' not found, default file names used", name)),
+            ),
+        }
+    }
+
+    /// Stored templates; unset ones use the defaults
+    pub fn naming_profile(&self) -> NamingProfile {
+        let template = |value: &Option<String>, default: String| {
+            value.clone().filter(|t| !t.trim().is_empty()).unwrap_or(default)
+        };
+        let defaults = NamingProfile::default();
+        NamingProfile {
+            xml: template(&self.xml_template, defaults.xml),
+            js: template(&self.js_template, defaults.js),
+            java: template(&self.java_template, defaults.java),
+            mapper_xml: template(&self.mapper_xml_template, defaults.mapper_xml),
+        }
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
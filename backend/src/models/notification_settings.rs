@@ -0,0 +1,88 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, QueryOrder};
+
+pub use super::_entities::notification_settings::{ActiveModel, Model, Entity};
+use super::_entities::notification_settings::Column;
+pub type NotificationSettings = Entity;
+
+/// Chat services, in the order the admin form lists them
+pub const NOTIFICATION_PROVIDERS: [&str; 2] = ["slack", "mattermost"];
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Stored settings, or the defaults (no webhook, nothing posted) before the first save
+    pub async fn current(db: &DatabaseConnection) -> Self {
+        Entity::find()
+            .order_by_asc(Column::Id)
+            .one(db)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| {
+                let now = chrono::Utc::now().into();
+                Self {
+                    created_at: now,
+                    updated_at: now,
+                    id: 0,
+                    provider: "slack".to_string(),
+                    webhook_url: None,
+                    channel: None,
+                    admin_base_url: None,
+                    notify_completed: false,
+                    notify_failed: true,
+                    notify_failure_spike: true,
+                    spike_threshold: 5,
+                    spike_window_minutes: 15,
+                }
+            })
+    }
+
+    /// Replace the settings with `settings` (the table holds a single row)
+    pub async fn save(db: &DatabaseConnection, settings: Self) -> Result<Self, DbErr> {
+        let current = Self::current(db).await;
+        let insert = current.id == 0;
+        let mut item: ActiveModel = if insert {
+            ActiveModel {
+                ..Default::default()
+            }
+        } else {
+            current.into()
+        };
+        item.provider = ActiveValue::Set(settings.provider);
+        item.webhook_url = ActiveValue::Set(settings.webhook_url);
+        item.channel = ActiveValue::Set(settings.channel);
+        item.admin_base_url = ActiveValue::Set(settings.admin_base_url);
+        item.notify_completed = ActiveValue::Set(settings.notify_completed);
+        item.notify_failed = ActiveValue::Set(settings.notify_failed);
+        item.notify_failure_spike = ActiveValue::Set(settings.notify_failure_spike);
+        item.spike_threshold = ActiveValue::Set(settings.spike_threshold);
+        item.spike_window_minutes = ActiveValue::Set(settings.spike_window_minutes);
+        if insert {
+            item.insert(db).await
+        } else {
+            item.update(db).await
+        }
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,101 @@
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::entity::prelude::*;
+
+use crate::services::pipeline::{PassOverride, PipelineProfile};
+use crate::services::TenantScope;
+pub use super::_entities::pipeline_profiles::{ActiveModel, Model, Entity};
+pub type PipelineProfiles = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Find a profile by name (`options.pipeline_profile`), among the shared
+    /// profiles and the tenant's own
+    pub async fn find_by_name(db: &DatabaseConnection, name: &str, tenant: &TenantScope) -> ModelResult<Self> {
+        let query = Entity::find().filter(super::_entities::pipeline_profiles::Column::Name.eq(name));
+        let item = tenant
+            .prefer_own(query, super::_entities::pipeline_profiles::Column::Company)
+            .one(db)
+            .await?;
+        item.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Default profile of a product, if one is marked; the tenant's own
+    /// default wins over a shared one
+    pub async fn find_default_for(
+        db: &DatabaseConnection,
+        product: &str,
+        tenant: &TenantScope,
+    ) -> ModelResult<Option<Self>> {
+        let query = Entity::find().filter(super::_entities::pipeline_profiles::Column::DefaultForProduct.eq(product));
+        Ok(tenant
+            .prefer_own(query, super::_entities::pipeline_profiles::Column::Company)
+            .one(db)
+            .await?)
+    }
+
+    /// Profile for a request: the one named in `options.pipeline_profile`,
+    /// else the product's default; no overrides plus a note when the named
+    /// profile doesn't exist
+    pub async fn resolve(
+        db: &DatabaseConnection,
+        product: &str,
+        requested: Option<&str>,
+        tenant: &TenantScope,
+    ) -> (PipelineProfile, Option<String>) {
+        let profile = match requested.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(name) => match Self::find_by_name(db, name, tenant).await {
+                Ok(profile) => profile,
+                Err(_) => {
+                    return (
+                        PipelineProfile::default(),
+                        Some(format!("Note: pipeline profile '{}' not found, all passes run normally", name)),
+                    )
+                }
+            },
+            None => match Self::find_default_for(db, product, tenant).await {
+                Ok(Some(profile)) => profile,
+                _ => return (PipelineProfile::default(), None),
+            },
+        };
+
+        match profile.pipeline_profile() {
+            Ok(pipeline_profile) => (pipeline_profile, None),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid pipeline profile '{}': {}", profile.name, e);
+                (
+                    PipelineProfile::default(),
+                    Some(format!("Note: pipeline profile '{}' is invalid, all passes run normally", profile.name)),
+                )
+            }
+        }
+    }
+
+    /// Stored overrides as a pipeline profile
+    pub fn pipeline_profile(&self) -> Result<PipelineProfile, String> {
+        let passes = serde_json::from_value::<std::collections::BTreeMap<String, PassOverride>>(self.passes.clone())
+            .map_err(|e| e.to_string())?;
+        PipelineProfile::new(&self.name, passes)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
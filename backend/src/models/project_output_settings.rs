@@ -0,0 +1,74 @@
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::entity::prelude::*;
+
+use crate::domain::{FileHeaders, GitIntegration, IssueTracker, LineEnding, OutputEncoding, OutputFormat};
+use crate::services::TenantScope;
+pub use super::_entities::project_output_settings::{ActiveModel, Model, Entity};
+pub type ProjectOutputSettings = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Find the settings of a project (`RequestContext.project`), the
+    /// tenant's own before shared ones
+    pub async fn find_by_project(db: &DatabaseConnection, project: &str, tenant: &TenantScope) -> ModelResult<Self> {
+        let query = Entity::find().filter(super::_entities::project_output_settings::Column::Project.eq(project));
+        let item = tenant
+            .prefer_own(query, super::_entities::project_output_settings::Column::Company)
+            .one(db)
+            .await?;
+        item.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Stored settings as an output format (unknown values fall back to defaults)
+    pub fn output_format(&self) -> OutputFormat {
+        OutputFormat {
+            encoding: OutputEncoding::parse(&self.encoding).unwrap_or_default(),
+            bom: self.bom,
+            line_ending: LineEnding::parse(&self.line_ending).unwrap_or_default(),
+        }
+    }
+
+    /// Stored header templates (none when unset or unreadable)
+    pub fn file_headers(&self) -> FileHeaders {
+        self.file_headers
+            .clone()
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Stored Git integration (none when unset or unreadable)
+    pub fn git_integration(&self) -> Option<GitIntegration> {
+        self.git_integration
+            .clone()
+            .and_then(|json| serde_json::from_value(json).ok())
+    }
+
+    /// Stored issue tracker (none when unset or unreadable)
+    pub fn issue_tracker(&self) -> Option<IssueTracker> {
+        self.issue_tracker
+            .clone()
+            .and_then(|json| serde_json::from_value(json).ok())
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
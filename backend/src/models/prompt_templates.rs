@@ -1,4 +1,5 @@
 use sea_orm::entity::prelude::*;
+use crate::services::lookup_cache::{LookupCache, Namespace};
 pub use super::_entities::prompt_templates::{ActiveModel, Model, Entity};
 pub type PromptTemplates = Entity;
 
@@ -16,6 +17,23 @@ impl ActiveModelBehavior for ActiveModel {
             Ok(self)
         }
     }
+
+    async fn after_save<C>(model: Model, _db: &C, _insert: bool) -> std::result::Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        // Generations read active templates through the lookup cache
+        LookupCache::invalidate(Namespace::Templates).await;
+        Ok(model)
+    }
+
+    async fn after_delete<C>(self, _db: &C) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        LookupCache::invalidate(Namespace::Templates).await;
+        Ok(self)
+    }
 }
 
 // implement your read-oriented logic here
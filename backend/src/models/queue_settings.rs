@@ -0,0 +1,74 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, QueryOrder};
+
+pub use super::_entities::queue_settings::{ActiveModel, Model, Entity};
+use super::_entities::queue_settings::Column;
+pub type QueueSettings = Entity;
+
+/// Fairness policies, in the order the admin form lists them
+pub const QUEUE_FAIRNESS: [&str; 3] = ["off", "user", "project"];
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Stored settings, or the defaults (queue order, no cap) before the first save
+    pub async fn current(db: &DatabaseConnection) -> Self {
+        Entity::find()
+            .order_by_asc(Column::Id)
+            .one(db)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| {
+                let now = chrono::Utc::now().into();
+                Self {
+                    created_at: now,
+                    updated_at: now,
+                    id: 0,
+                    fairness: "off".to_string(),
+                    max_in_flight: 0,
+                }
+            })
+    }
+
+    /// Replace the settings (the table holds a single row)
+    pub async fn save(db: &DatabaseConnection, fairness: &str, max_in_flight: i32) -> Result<Self, DbErr> {
+        let current = Self::current(db).await;
+        let insert = current.id == 0;
+        let mut item: ActiveModel = if insert {
+            ActiveModel {
+                ..Default::default()
+            }
+        } else {
+            current.into()
+        };
+        item.fairness = ActiveValue::Set(fairness.to_string());
+        item.max_in_flight = ActiveValue::Set(max_in_flight);
+        if insert {
+            item.insert(db).await
+        } else {
+            item.update(db).await
+        }
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,97 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Serialize;
+
+pub use super::_entities::race_results::{ActiveModel, Model, Entity};
+use super::_entities::race_results::Column;
+pub type RaceResults = Entity;
+
+/// Races considered when looking for a dominant provider
+const ROUTING_WINDOW: u64 = 20;
+
+/// Share of wins after which racing is skipped
+const DOMINANCE: f64 = 0.9;
+
+/// Wins of one provider/model across races
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderWins {
+    pub provider: String,
+    pub model: String,
+    pub races: u32,
+    pub wins: u32,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Most recent races of a product
+    pub async fn recent(db: &DatabaseConnection, product: &str, limit: u64) -> Result<Vec<Self>, DbErr> {
+        Entity::find()
+            .filter(Column::Product.eq(product))
+            .order_by_desc(Column::Id)
+            .paginate(db, limit)
+            .fetch_page(0)
+            .await
+    }
+
+    /// Per provider/model wins across the given races
+    pub fn win_rates(races: &[Self]) -> Vec<ProviderWins> {
+        let mut rates: Vec<ProviderWins> = Vec::new();
+        for race in races {
+            for (provider, model, won) in [
+                (&race.winner_provider, &race.winner_model, true),
+                (&race.loser_provider, &race.loser_model, false),
+            ] {
+                let idx = match rates.iter().position(|r| &r.provider == provider && &r.model == model) {
+                    Some(idx) => idx,
+                    None => {
+                        rates.push(ProviderWins {
+                            provider: provider.clone(),
+                            model: model.clone(),
+                            races: 0,
+                            wins: 0,
+                        });
+                        rates.len() - 1
+                    }
+                };
+                rates[idx].races += 1;
+                rates[idx].wins += u32::from(won);
+            }
+        }
+        rates.sort_by_key(|r| std::cmp::Reverse(r.wins));
+        rates
+    }
+
+    /// Provider/model that won nearly every recent race of a product, if any
+    pub async fn dominant(db: &DatabaseConnection, product: &str) -> Result<Option<ProviderWins>, DbErr> {
+        let races = Self::recent(db, product, ROUTING_WINDOW).await?;
+        if (races.len() as u64) < ROUTING_WINDOW {
+            return Ok(None);
+        }
+        Ok(Self::win_rates(&races)
+            .into_iter()
+            .find(|r| f64::from(r.wins) >= f64::from(r.races) * DOMINANCE))
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
+
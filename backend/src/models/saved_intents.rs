@@ -0,0 +1,78 @@
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::entity::prelude::*;
+
+use crate::domain::{same_structure, UiIntent};
+use crate::services::TenantScope;
+pub use super::_entities::saved_intents::{ActiveModel, Model, Entity};
+pub type SavedIntents = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Find a saved intent by name, among the shared entries and the tenant's own
+    pub async fn find_by_name(db: &DatabaseConnection, name: &str, tenant: &TenantScope) -> ModelResult<Self> {
+        let query = Entity::find().filter(super::_entities::saved_intents::Column::Name.eq(name));
+        let item = tenant
+            .prefer_own(query, super::_entities::saved_intents::Column::Company)
+            .one(db)
+            .await?;
+        item.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Intent of a saved entry referenced by a generation request
+    pub async fn load_ui_intent(db: &DatabaseConnection, name: &str, tenant: &TenantScope) -> anyhow::Result<UiIntent> {
+        let item = Self::find_by_name(db, name, tenant)
+            .await
+            .map_err(|_| anyhow::anyhow!("Saved intent '{}' not found", name))?;
+        Ok(item.ui_intent()?)
+    }
+
+    /// Entry the tenant can read whose intent has the same screen structure
+    /// (name and notes ignored)
+    pub async fn find_duplicate(
+        db: &DatabaseConnection,
+        intent: &UiIntent,
+        exclude_id: Option<i32>,
+        tenant: &TenantScope,
+    ) -> std::result::Result<Option<Self>, DbErr> {
+        let items = Entity::find()
+            .filter(tenant.readable(super::_entities::saved_intents::Column::Company))
+            .all(db)
+            .await?;
+        Ok(items.into_iter().find(|item| {
+            Some(item.id) != exclude_id && item.ui_intent().is_ok_and(|saved| same_structure(&saved, intent))
+        }))
+    }
+
+    pub fn ui_intent(&self) -> serde_json::Result<UiIntent> {
+        serde_json::from_value(self.intent.clone())
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.tags
+            .clone()
+            .and_then(|t| serde_json::from_value(t).ok())
+            .unwrap_or_default()
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
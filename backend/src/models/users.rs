@@ -10,6 +10,14 @@ pub use super::_entities::users::{self, ActiveModel, Entity, Model};
 pub const MAGIC_LINK_LENGTH: i8 = 32;
 pub const MAGIC_LINK_EXPIRATION_MIN: i8 = 5;
 
+/// User rows created by anonymous plugin calls are attributed to (single-company deployments)
+pub const SYSTEM_USER_ID: i32 = 1;
+
+/// User behind a stored row (None = the system user standing in for anonymous calls)
+pub fn known_user(user_id: i32) -> Option<i32> {
+    (user_id != SYSTEM_USER_ID).then_some(user_id)
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LoginParams {
     pub email: String,
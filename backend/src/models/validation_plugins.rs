@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{QueryOrder, QuerySelect};
+
+pub use super::_entities::validation_plugins::{ActiveModel, Model, Entity};
+use super::_entities::validation_plugins::Column;
+pub type ValidationPlugins = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// `(id, name, sha256)` of the enabled plugins in upload order, without the modules
+    pub async fn enabled_digests(db: &DatabaseConnection) -> Result<Vec<(i32, String, String)>, DbErr> {
+        Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .column(Column::Name)
+            .column(Column::Sha256)
+            .filter(Column::Enabled.eq(true))
+            .order_by_asc(Column::Id)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    pub async fn find_by_name(db: &DatabaseConnection, name: &str) -> Result<Option<Self>, DbErr> {
+        Entity::find().filter(Column::Name.eq(name)).one(db).await
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
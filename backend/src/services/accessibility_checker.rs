@@ -0,0 +1,392 @@
+//! Accessibility Checks of Generated Screens
+//!
+//! Builds the `AccessibilityReport` of an xFrame5 screen from its XML:
+//! - Input labels: every visible input has a non-empty `<text>` sibling to its
+//!   left or above it (check boxes and radio buttons carry their own `text`)
+//! - Touch targets: buttons and inputs are at least the style profile's
+//!   minimum size
+//! - Color contrast: the style profile's palette and every colored control
+//!   against its (inherited) background, WCAG relative luminance
+//! - Tab order: focusable controls have unique `control_id`s and every
+//!   `<tab_order>` lists all focusable children of its container
+//!
+//! Geometry is read from `x`/`y`/`width`/`height`, relative to the parent panel.
+
+use roxmltree::{Document, Node};
+
+use crate::domain::{
+    AccessibilityCheck, AccessibilityReport, StyleProfile, MIN_CONTRAST_RATIO, MIN_LARGE_TEXT_CONTRAST_RATIO,
+};
+
+/// Inputs besides the `*_field` family
+const INPUT_TAGS: &[&str] = &["textarea", "combobox", "datepicker", "checkbox", "radiobutton"];
+
+/// Inputs labelled by their own `text`
+const SELF_LABELLED_TAGS: &[&str] = &["checkbox", "radiobutton"];
+
+/// Farthest a label may sit left of / above its input, in px
+const MAX_LABEL_GAP_LEFT: i32 = 80;
+const MAX_LABEL_GAP_ABOVE: i32 = 30;
+
+const DEFAULT_TEXT: &str = "000000";
+const DEFAULT_BACKGROUND: &str = "FFFFFF";
+
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+/// Builds accessibility reports
+pub struct AccessibilityChecker;
+
+impl AccessibilityChecker {
+    /// Report of a screen, with the company style profile when there is one
+    pub fn report(xml: &str, style: Option<&StyleProfile>) -> AccessibilityReport {
+        let doc = match Document::parse(xml) {
+            Ok(doc) => doc,
+            Err(e) => return AccessibilityReport::unparsed(format!("Screen XML is not well-formed: {}", e)),
+        };
+        let style = style.cloned().unwrap_or_default();
+        let visible: Vec<Node> = doc
+            .descendants()
+            .filter(|n| n.is_element() && !Self::hidden(*n))
+            .collect();
+
+        let (color_contrast, lowest_contrast_ratio) = Self::color_contrast(&visible, &style);
+        AccessibilityReport {
+            input_labels: Self::input_labels(&visible),
+            touch_targets: Self::touch_targets(&visible, style.min_target_size),
+            color_contrast,
+            lowest_contrast_ratio,
+            tab_order: Self::tab_order(&doc),
+            ..Default::default()
+        }
+        .finish()
+    }
+
+    fn input_labels(nodes: &[Node]) -> AccessibilityCheck {
+        let mut check = AccessibilityCheck::default();
+        for node in nodes.iter().filter(|n| Self::is_input(n.tag_name().name())) {
+            check.checked += 1;
+            let tag = node.tag_name().name();
+            let labelled = if SELF_LABELLED_TAGS.contains(&tag) {
+                Self::has_text(*node)
+            } else {
+                Self::rect(*node).is_some_and(|field| {
+                    node.parent_element()
+                        .into_iter()
+                        .flat_map(|parent| parent.children())
+                        .filter(|l| l.has_tag_name("text") && Self::has_text(*l) && !Self::hidden(*l))
+                        .filter_map(Self::rect)
+                        .any(|label| Self::labels(&label, &field))
+                })
+            };
+            if !labelled {
+                check.issues.push(format!("{} ({}) has no label", Self::name(*node), tag));
+            }
+        }
+        check
+    }
+
+    /// Label left of the field on the same row, or above it in the same column
+    fn labels(label: &Rect, field: &Rect) -> bool {
+        let same_row = label.y < field.y + field.height && field.y < label.y + label.height;
+        let left_gap = field.x - (label.x + label.width);
+        let same_column = label.x < field.x + field.width && field.x < label.x + label.width;
+        let above_gap = field.y - (label.y + label.height);
+        (same_row && (-4..=MAX_LABEL_GAP_LEFT).contains(&left_gap))
+            || (same_column && (-4..=MAX_LABEL_GAP_ABOVE).contains(&above_gap))
+    }
+
+    fn touch_targets(nodes: &[Node], min: u32) -> AccessibilityCheck {
+        let min = min as i32;
+        let mut check = AccessibilityCheck::default();
+        for node in nodes {
+            let tag = node.tag_name().name();
+            if tag != "pushbutton" && !Self::is_input(tag) {
+                continue;
+            }
+            let Some(rect) = Self::rect(*node) else {
+                continue;
+            };
+            check.checked += 1;
+            if rect.width < min || rect.height < min {
+                check.issues.push(format!(
+                    "{} ({}) is {}x{}, below {}x{}",
+                    Self::name(*node),
+                    tag,
+                    rect.width,
+                    rect.height,
+                    min,
+                    min
+                ));
+            }
+        }
+        check
+    }
+
+    fn color_contrast(nodes: &[Node], style: &StyleProfile) -> (AccessibilityCheck, Option<f64>) {
+        let text = style.text_color.as_deref().and_then(Self::rgb).unwrap_or_else(|| DEFAULT_TEXT.to_string());
+        let background = style
+            .background_color
+            .as_deref()
+            .and_then(Self::rgb)
+            .unwrap_or_else(|| DEFAULT_BACKGROUND.to_string());
+
+        // (what, foreground, background, large text)
+        let mut pairs = vec![("style text".to_string(), text.clone(), background.clone(), false)];
+        if let (Some(fg), Some(bg)) = (
+            style.button_text_color.as_deref().and_then(Self::rgb),
+            style.button_background_color.as_deref().and_then(Self::rgb),
+        ) {
+            pairs.push(("style buttons".to_string(), fg, bg, false));
+        }
+        for node in nodes {
+            let colored = node.has_attribute("text_color") || node.has_attribute("back_color");
+            if !colored || !(Self::has_text(*node) || Self::is_input(node.tag_name().name())) {
+                continue;
+            }
+            let fg = node.attribute("text_color").and_then(Self::rgb).unwrap_or_else(|| text.clone());
+            let bg = node
+                .ancestors()
+                .find_map(|n| n.attribute("back_color").and_then(Self::rgb))
+                .unwrap_or_else(|| background.clone());
+            pairs.push((Self::name(*node), fg, bg, Self::large_text(*node)));
+        }
+
+        let mut check = AccessibilityCheck::default();
+        let mut lowest: Option<f64> = None;
+        for (what, fg, bg, large) in pairs {
+            let ratio = Self::contrast_ratio(&fg, &bg);
+            let required = if large { MIN_LARGE_TEXT_CONTRAST_RATIO } else { MIN_CONTRAST_RATIO };
+            check.checked += 1;
+            lowest = Some(lowest.map_or(ratio, |l| l.min(ratio)));
+            if ratio < required {
+                check.issues.push(format!(
+                    "{}: #{} on #{} is {:.2}:1, below {}:1",
+                    what, fg, bg, ratio, required
+                ));
+            }
+        }
+        (check, lowest.map(|r| (r * 100.0).round() / 100.0))
+    }
+
+    fn tab_order(doc: &Document) -> AccessibilityCheck {
+        let mut check = AccessibilityCheck::default();
+        let mut ids: Vec<(String, String)> = Vec::new();
+        for node in doc.descendants().filter(|n| n.is_element()) {
+            let focusable = Self::focusable(node.tag_name().name());
+            if focusable {
+                check.checked += 1;
+            }
+            match node.attribute("control_id") {
+                Some(id) => {
+                    if let Some((_, first)) = ids.iter().find(|(seen, _)| seen == id) {
+                        check.issues.push(format!(
+                            "control_id {} is used by {} and {}",
+                            id,
+                            first,
+                            Self::name(node)
+                        ));
+                    } else {
+                        ids.push((id.to_string(), Self::name(node)));
+                    }
+                }
+                None if focusable => {
+                    check.issues.push(format!("{} ({}) has no control_id", Self::name(node), node.tag_name().name()));
+                }
+                None => {}
+            }
+        }
+
+        for order in doc.descendants().filter(|n| n.has_tag_name("tab_order")) {
+            let Some(container) = order.parent_element() else {
+                continue;
+            };
+            let listed: Vec<&str> = order
+                .attribute("order_info")
+                .unwrap_or("")
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .collect();
+            let children: Vec<Node> = container
+                .children()
+                .filter(|c| {
+                    c.is_element() && c.has_attribute("control_id")
+                        && (Self::focusable(c.tag_name().name()) || c.has_tag_name("panel"))
+                })
+                .collect();
+            for child in &children {
+                if !listed.contains(&child.attribute("control_id").unwrap_or("")) {
+                    check.issues.push(format!(
+                        "Tab order of {} misses {}",
+                        Self::name(container),
+                        Self::name(*child)
+                    ));
+                }
+            }
+            for id in listed {
+                if !children.iter().any(|c| c.attribute("control_id") == Some(id)) {
+                    check.issues.push(format!(
+                        "Tab order of {} lists control {} that is not in it",
+                        Self::name(container),
+                        id
+                    ));
+                }
+            }
+        }
+        check
+    }
+
+    fn is_input(tag: &str) -> bool {
+        tag.ends_with("_field") || INPUT_TAGS.contains(&tag)
+    }
+
+    fn focusable(tag: &str) -> bool {
+        Self::is_input(tag) || tag == "pushbutton" || tag == "grid"
+    }
+
+    fn hidden(node: Node) -> bool {
+        node.ancestors().any(|n| n.attribute("hidden") == Some("1"))
+    }
+
+    fn has_text(node: Node) -> bool {
+        node.attribute("text").is_some_and(|t| !t.trim().is_empty())
+    }
+
+    fn name(node: Node) -> String {
+        node.attribute("name")
+            .or_else(|| node.attribute("id"))
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("<{}>", node.tag_name().name()))
+    }
+
+    fn rect(node: Node) -> Option<Rect> {
+        let attr = |name: &str| node.attribute(name)?.trim().parse::<i32>().ok();
+        Some(Rect {
+            x: attr("x")?,
+            y: attr("y")?,
+            width: attr("width")?,
+            height: attr("height")?,
+        })
+    }
+
+    /// `font="Malgun Gothic,14,1,..."` (face, size in pt, bold) of 18pt, or 14pt bold
+    fn large_text(node: Node) -> bool {
+        let Some(font) = node.attribute("font") else {
+            return false;
+        };
+        let parts: Vec<&str> = font.split(',').map(str::trim).collect();
+        let size: u32 = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let bold = parts.get(2) == Some(&"1");
+        size >= 18 || (size >= 14 && bold)
+    }
+
+    /// `RRGGBB` of "#RRGGBB", "RRGGBB" or xFrame5's "00RRGGBB"
+    fn rgb(color: &str) -> Option<String> {
+        let hex = color.trim().trim_start_matches('#');
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        match hex.len() {
+            6 => Some(hex.to_uppercase()),
+            8 => Some(hex[2..].to_uppercase()),
+            _ => None,
+        }
+    }
+
+    fn contrast_ratio(fg: &str, bg: &str) -> f64 {
+        let (a, b) = (Self::luminance(fg), Self::luminance(bg));
+        let (light, dark) = if a > b { (a, b) } else { (b, a) };
+        (light + 0.05) / (dark + 0.05)
+    }
+
+    /// WCAG relative luminance of `RRGGBB`
+    fn luminance(rgb: &str) -> f64 {
+        let channel = |i: usize| {
+            let c = u8::from_str_radix(&rgb[i..i + 2], 16).unwrap_or(0) as f64 / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(0) + 0.7152 * channel(2) + 0.0722 * channel(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETAIL_XML: &str = r#"<screen id="SCREEN_TASK_DETAIL" width="600" height="500">
+  <panel control_id="1" name="pnl_header" x="0" y="0" width="600" height="50" back_color="00F8F9FA">
+    <text control_id="2" name="txt_popup_title" x="20" y="12" width="400" height="28" text="업무 상세"
+          font="Malgun Gothic,14,1,0,0,0" text_color="00333333"/>
+  </panel>
+  <panel control_id="10" name="pnl_form" x="10" y="60" width="580" height="370" back_color="00FFFFFF">
+    <tab_order order_option="0" order_info="12,13"/>
+    <text control_id="11" name="txt_title_label" x="20" y="20" width="100" height="24" text="제목"/>
+    <normal_field control_id="12" name="field_title" x="130" y="18" width="420" height="24"
+                  link_data="ds_detail:TASK_TITLE"/>
+    <text control_id="14" name="txt_due_label" x="130" y="50" width="100" height="20" text="마감일"/>
+    <datepicker control_id="13" name="field_due_date" x="130" y="72" width="150" height="24"
+                link_data="ds_detail:DUE_DATE"/>
+    <text control_id="16" name="txt_error" x="20" y="325" width="530" height="24" text=""
+          text_color="00DC3545" hidden="1"/>
+  </panel>
+  <pushbutton control_id="31" name="btn_save" x="360" y="440" width="100" height="32" text="저장"
+              back_color="000056B3" text_color="00FFFFFF"/>
+</screen>"#;
+
+    #[test]
+    fn test_compliant_screen() {
+        let report = AccessibilityChecker::report(DETAIL_XML, None);
+        assert!(report.compliant, "{:?}", report);
+        assert_eq!(report.input_labels.checked, 2);
+        assert_eq!(report.touch_targets.checked, 3);
+        assert_eq!(report.tab_order.checked, 3);
+        // style text + title + save button
+        assert_eq!(report.color_contrast.checked, 3);
+        assert_eq!(report.lowest_contrast_ratio, Some(7.04));
+    }
+
+    #[test]
+    fn test_findings_per_check() {
+        let xml = DETAIL_XML
+            .replace(r#"text="마감일""#, r#"text="""#)
+            .replace(r#"height="32" text="저장""#, r#"height="20" text="저장""#)
+            .replace("000056B3", "00007BFF")
+            .replace(r#"order_info="12,13""#, r#"order_info="12,99""#);
+        let style = StyleProfile {
+            text_color: Some("#999999".to_string()),
+            ..Default::default()
+        };
+        let report = AccessibilityChecker::report(&xml, Some(&style));
+
+        assert!(!report.compliant);
+        assert_eq!(report.input_labels.issues, vec!["field_due_date (datepicker) has no label"]);
+        assert_eq!(report.touch_targets.issues, vec!["btn_save (pushbutton) is 100x20, below 24x24"]);
+        assert_eq!(
+            report.color_contrast.issues,
+            vec![
+                "style text: #999999 on #FFFFFF is 2.85:1, below 4.5:1",
+                "btn_save: #FFFFFF on #007BFF is 3.98:1, below 4.5:1",
+            ]
+        );
+        assert_eq!(
+            report.tab_order.issues,
+            vec![
+                "Tab order of pnl_form misses field_due_date",
+                "Tab order of pnl_form lists control 99 that is not in it",
+            ]
+        );
+
+        let report = AccessibilityChecker::report("<screen>", None);
+        assert!(!report.compliant);
+        assert!(report.parse_error.unwrap().starts_with("Screen XML is not well-formed"));
+    }
+}
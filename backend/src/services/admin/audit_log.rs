@@ -0,0 +1,500 @@
+//! Admin Audit Log Service
+//!
+//! Every create/update/delete of prompt templates, company rules, LLM configs
+//! and knowledge entries made through the admin panel, and of project output
+//! settings, pipeline and naming profiles and saved intents made through the
+//! API, is recorded with the acting administrator and before/after snapshots.
+//! Records are append-only: the panel can search and export them but never
+//! edit them.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::domain::{diff_intents, IntentChange};
+use crate::models::_entities::admin_audit_logs::{ActiveModel, Column, Entity, Model};
+use crate::models::_entities::{
+    company_rules, knowledge_bases, llm_configs, naming_profiles, pipeline_profiles, project_output_settings,
+    prompt_templates, saved_intents,
+};
+use crate::services::wasm_plugin::sha256_hex;
+use crate::services::TenantScope;
+
+const DEFAULT_PAGE_SIZE: u64 = 50;
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Most records written to one CSV export
+pub const MAX_EXPORT_ROWS: u64 = 10_000;
+
+/// Change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    /// LLM config made the active one
+    Activate,
+    /// Quarantined content released by a second administrator
+    Approve,
+    /// Prompt template imported from a YAML/JSON file
+    Import,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 6] = [
+        AuditAction::Create,
+        AuditAction::Update,
+        AuditAction::Delete,
+        AuditAction::Activate,
+        AuditAction::Approve,
+        AuditAction::Import,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Activate => "activate",
+            AuditAction::Approve => "approve",
+            AuditAction::Import => "import",
+        }
+    }
+}
+
+/// Admin entity whose changes are audited
+pub trait Audited: Serialize {
+    /// Table name stored as `entity_type`
+    const ENTITY: &'static str;
+
+    fn audit_id(&self) -> i32;
+    fn audit_name(&self) -> &str;
+    fn audit_company(&self) -> Option<&str>;
+
+    /// Stored snapshot (timestamps dropped; the log row has its own)
+    fn snapshot(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("created_at");
+            obj.remove("updated_at");
+        }
+        value
+    }
+}
+
+impl Audited for prompt_templates::Model {
+    const ENTITY: &'static str = "prompt_templates";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+impl Audited for company_rules::Model {
+    const ENTITY: &'static str = "company_rules";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+impl Audited for knowledge_bases::Model {
+    const ENTITY: &'static str = "knowledge_bases";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+impl Audited for llm_configs::Model {
+    const ENTITY: &'static str = "llm_configs";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+
+    /// API keys are never stored; a short fingerprint still shows rotations
+    fn snapshot(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("created_at");
+            obj.remove("updated_at");
+            if let Some(key) = self.api_key.as_deref().filter(|k| !k.is_empty()) {
+                let fingerprint = format!("sha256:{}", &sha256_hex(key.as_bytes())[..8]);
+                obj.insert("api_key".to_string(), Value::String(fingerprint));
+            }
+        }
+        value
+    }
+}
+
+impl Audited for project_output_settings::Model {
+    const ENTITY: &'static str = "project_output_settings";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.project
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+impl Audited for pipeline_profiles::Model {
+    const ENTITY: &'static str = "pipeline_profiles";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+impl Audited for naming_profiles::Model {
+    const ENTITY: &'static str = "naming_profiles";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+impl Audited for saved_intents::Model {
+    const ENTITY: &'static str = "saved_intents";
+
+    fn audit_id(&self) -> i32 {
+        self.id
+    }
+    fn audit_name(&self) -> &str {
+        &self.name
+    }
+    fn audit_company(&self) -> Option<&str> {
+        self.company.as_deref()
+    }
+}
+
+/// Query parameters for search with pagination
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct QueryParams {
+    /// Search keyword (matches actor or entity name)
+    #[serde(default)]
+    pub keyword: Option<String>,
+
+    /// Filter by entity type (table name)
+    #[serde(default)]
+    pub entity_type: Option<String>,
+
+    /// Filter by action
+    #[serde(default)]
+    pub action: Option<String>,
+
+    /// Filter by entity id (history of one entity)
+    #[serde(default)]
+    pub entity_id: Option<i32>,
+
+    /// Date range filter - from (`YYYY-MM-DD`, inclusive)
+    #[serde(default)]
+    pub date_from: Option<String>,
+
+    /// Date range filter - to (`YYYY-MM-DD`, inclusive)
+    #[serde(default)]
+    pub date_to: Option<String>,
+
+    /// Page number (1-indexed)
+    pub page: Option<u64>,
+
+    /// Page size
+    pub page_size: Option<u64>,
+}
+
+/// Paginated response
+#[derive(Debug, Serialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+/// Audit record with its field changes, for the detail view
+#[derive(Debug, Serialize)]
+pub struct AuditLogDetail {
+    pub item: Model,
+    pub changes: Vec<IntentChange>,
+}
+
+pub struct AuditLogService;
+
+impl AuditLogService {
+    /// Record a change of `before` into `after` (`None` before = created,
+    /// `None` after = deleted)
+    pub async fn record<M: Audited>(
+        db: &DatabaseConnection,
+        actor: &str,
+        action: AuditAction,
+        before: Option<&M>,
+        after: Option<&M>,
+    ) -> Result<()> {
+        let Some(entity) = after.or(before) else {
+            return Ok(());
+        };
+
+        ActiveModel {
+            entity_type: Set(M::ENTITY.to_string()),
+            entity_id: Set(entity.audit_id()),
+            entity_name: Set(entity.audit_name().to_string()),
+            action: Set(action.as_str().to_string()),
+            actor: Set(actor.to_string()),
+            company: Set(entity.audit_company().map(str::to_string)),
+            before: Set(before.map(Audited::snapshot)),
+            after: Set(after.map(Audited::snapshot)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Record an imported prompt template
+    pub async fn record_import(db: &DatabaseConnection, actor: &str, template_id: i32) -> Result<()> {
+        let template = prompt_templates::Entity::find_by_id(template_id)
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        Self::record(db, actor, AuditAction::Import, None, Some(&template)).await
+    }
+
+    /// Entity types and actions offered as filters
+    pub fn filter_options() -> (Vec<&'static str>, Vec<&'static str>) {
+        (
+            vec![
+                prompt_templates::Model::ENTITY,
+                company_rules::Model::ENTITY,
+                llm_configs::Model::ENTITY,
+                knowledge_bases::Model::ENTITY,
+                project_output_settings::Model::ENTITY,
+                pipeline_profiles::Model::ENTITY,
+                naming_profiles::Model::ENTITY,
+                saved_intents::Model::ENTITY,
+            ],
+            AuditAction::ALL.iter().map(|a| a.as_str()).collect(),
+        )
+    }
+
+    /// Build query with filters, newest first
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> Result<sea_orm::Select<Entity>> {
+        // Tenant administrators see the changes to their company's entities only
+        let mut condition = scope.owned(Column::Company);
+
+        if let Some(keyword) = params.keyword.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+            condition = condition.add(
+                Condition::any()
+                    .add(Column::Actor.contains(keyword))
+                    .add(Column::EntityName.contains(keyword)),
+            );
+        }
+        if let Some(entity_type) = params.entity_type.as_deref().filter(|t| !t.is_empty()) {
+            condition = condition.add(Column::EntityType.eq(entity_type));
+        }
+        if let Some(action) = params.action.as_deref().filter(|a| !a.is_empty()) {
+            condition = condition.add(Column::Action.eq(action));
+        }
+        if let Some(entity_id) = params.entity_id {
+            condition = condition.add(Column::EntityId.eq(entity_id));
+        }
+        if let Some(from) = Self::parse_date(params.date_from.as_deref())? {
+            condition = condition.add(Column::CreatedAt.gte(from));
+        }
+        if let Some(to) = Self::parse_date(params.date_to.as_deref())? {
+            condition = condition.add(Column::CreatedAt.lt(to + Duration::days(1)));
+        }
+
+        Ok(Entity::find()
+            .filter(condition)
+            .order_by(Column::CreatedAt, Order::Desc)
+            .order_by(Column::Id, Order::Desc))
+    }
+
+    /// Start of a `YYYY-MM-DD` day (UTC); blank = no bound
+    fn parse_date(raw: Option<&str>) -> Result<Option<DateTime<FixedOffset>>> {
+        let Some(raw) = raw.map(str::trim).filter(|d| !d.is_empty()) else {
+            return Ok(None);
+        };
+        let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .map_err(|_| Error::BadRequest(format!("Invalid date: {}", raw)))?;
+        Ok(Some(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().fixed_offset()))
+    }
+
+    /// Search with pagination and filters
+    pub async fn search(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: &QueryParams,
+    ) -> Result<PageResponse<Model>> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+        let paginator = Self::build_query(scope, params)?.paginate(db, page_size);
+
+        let total_items = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+
+        Ok(PageResponse {
+            items,
+            page,
+            page_size,
+            total_pages,
+            total_items,
+        })
+    }
+
+    /// Find by ID with the changed fields
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<AuditLogDetail> {
+        let item = Entity::find_by_id(id)
+            .filter(scope.owned(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+
+        let empty = Value::Object(Default::default());
+        let changes = diff_intents(
+            item.before.as_ref().unwrap_or(&empty),
+            item.after.as_ref().unwrap_or(&empty),
+        );
+        Ok(AuditLogDetail { item, changes })
+    }
+
+    /// Matching records as CSV (newest first, at most [`MAX_EXPORT_ROWS`])
+    pub async fn export_csv(db: &DatabaseConnection, scope: &TenantScope, params: &QueryParams) -> Result<String> {
+        let items = Self::build_query(scope, params)?
+            .limit(MAX_EXPORT_ROWS)
+            .all(db)
+            .await?;
+        Ok(Self::to_csv(&items))
+    }
+
+    fn to_csv(items: &[Model]) -> String {
+        let mut csv = String::from("id,created_at,actor,action,entity_type,entity_id,entity_name,company,before,after\n");
+        for item in items {
+            let json = |v: &Option<Value>| v.as_ref().map(Value::to_string).unwrap_or_default();
+            let fields = [
+                item.id.to_string(),
+                item.created_at.to_rfc3339(),
+                item.actor.clone(),
+                item.action.clone(),
+                item.entity_type.clone(),
+                item.entity_id.to_string(),
+                item.entity_name.clone(),
+                item.company.clone().unwrap_or_default(),
+                json(&item.before),
+                json(&item.after),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| Self::csv_field(f)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Quote a CSV field; a leading formula character is neutralised so
+    /// spreadsheets don't evaluate administrator-entered names
+    fn csv_field(value: &str) -> String {
+        let value = if value.starts_with(['=', '+', '-', '@']) {
+            format!("'{}", value)
+        } else {
+            value.to_string()
+        };
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn llm_config(api_key: Option<&str>) -> llm_configs::Model {
+        let now = chrono::Utc::now().fixed_offset();
+        llm_configs::Model {
+            created_at: now,
+            updated_at: now,
+            id: 3,
+            name: "primary".to_string(),
+            provider: "ollama".to_string(),
+            endpoint_url: None,
+            model_name: "model".to_string(),
+            api_key: api_key.map(str::to_string),
+            temperature: None,
+            max_tokens: None,
+            is_active: Some(true),
+            model_path: None,
+            n_ctx: None,
+            n_threads: None,
+            timeout_secs: None,
+            company: None,
+            fallback_priority: None,
+        }
+    }
+
+    #[test]
+    fn test_llm_config_snapshot_hides_api_key() {
+        let snapshot = llm_config(Some("secret-key")).snapshot();
+        let key = snapshot["api_key"].as_str().unwrap();
+        assert!(key.starts_with("sha256:"));
+        assert!(!snapshot.to_string().contains("secret-key"));
+        assert!(snapshot.get("updated_at").is_none());
+
+        assert_ne!(llm_config(Some("rotated-key")).snapshot()["api_key"], snapshot["api_key"]);
+        assert_eq!(llm_config(None).snapshot()["api_key"], Value::Null);
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(AuditLogService::csv_field("plain"), "plain");
+        assert_eq!(AuditLogService::csv_field("a,b"), "\"a,b\"");
+        assert_eq!(AuditLogService::csv_field(r#"{"k":"v"}"#), r#""{""k"":""v""}""#);
+        assert_eq!(AuditLogService::csv_field("=HYPERLINK(1)"), "'=HYPERLINK(1)");
+    }
+}
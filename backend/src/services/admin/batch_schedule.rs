@@ -0,0 +1,250 @@
+//! Batch Schedule Admin Service
+//!
+//! Run times of the nightly batch jobs and the calendar overrides they
+//! respect. Job schedules are changed by platform administrators; tenant
+//! administrators add days off, worked days and maintenance windows for their
+//! own company.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection};
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::batch_schedules;
+use crate::models::_entities::calendar_overrides::{ActiveModel, Column, Entity, Model};
+use crate::services::business_calendar::{korea_now, parse_time, DayOff, KoreanHolidays};
+use crate::services::{BatchJob, BatchScheduleService, TenantScope};
+use crate::utils::{bool_from_str_or_bool, i32_from_str_or_number};
+
+/// Override kinds, in the order the admin form lists them
+pub const OVERRIDE_KINDS: [&str; 3] = ["holiday", "workday", "maintenance"];
+
+/// Days ahead listed as upcoming days off
+const UPCOMING_DAYS: i64 = 90;
+
+/// Schedule form
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScheduleParams {
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub enabled: Option<bool>,
+    pub run_at: String,
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub business_days_only: Option<bool>,
+}
+
+/// Override form
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OverrideParams {
+    pub kind: String,
+    /// `YYYY-MM-DD` (blank = every `weekday`, maintenance only)
+    pub date: Option<String>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub weekday: Option<i32>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub name: String,
+    /// Tenant company (platform administrators only; blank = every company)
+    pub company: Option<String>,
+}
+
+/// Job with its schedule and next run, for the admin list
+#[derive(Debug, Serialize)]
+pub struct JobRow {
+    pub job: BatchJob,
+    pub key: &'static str,
+    pub label: &'static str,
+    pub schedule: batch_schedules::Model,
+    /// Next run in Korean time for the viewer's company (`YYYY-MM-DD HH:MM`)
+    pub next_run: Option<String>,
+}
+
+/// Day off within the next weeks
+#[derive(Debug, Serialize)]
+pub struct UpcomingDayOff {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+pub struct BatchScheduleAdminService;
+
+impl BatchScheduleAdminService {
+    /// Every job with its schedule and next run for the scope's company
+    pub async fn jobs(db: &DatabaseConnection, scope: &TenantScope) -> Result<Vec<JobRow>> {
+        let mut rows = Vec::new();
+        for job in BatchJob::ALL {
+            rows.push(Self::row(db, scope, job).await?);
+        }
+        Ok(rows)
+    }
+
+    async fn row(db: &DatabaseConnection, scope: &TenantScope, job: BatchJob) -> Result<JobRow> {
+        let now = korea_now();
+        let schedule = BatchScheduleService::schedule(db, job).await?;
+        let calendar = BatchScheduleService::calendar(db, now.date()).await?;
+        let next_run = BatchScheduleService::next_run(&schedule, &calendar, scope.company(), now)
+            .map(|at| at.format("%Y-%m-%d %H:%M").to_string());
+
+        Ok(JobRow { job, key: job.key(), label: job.label(), schedule, next_run })
+    }
+
+    /// Change a job's schedule (platform administrators only)
+    pub async fn update_schedule(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        job: &str,
+        params: ScheduleParams,
+    ) -> Result<JobRow> {
+        Self::require_platform(scope)?;
+        let job = BatchJob::parse(job).ok_or_else(|| Error::NotFound)?;
+        let run_at = parse_time(&params.run_at)
+            .ok_or_else(|| Error::BadRequest("Run time must be HH:MM".to_string()))?;
+
+        let stored = batch_schedules::Entity::find()
+            .filter(batch_schedules::Column::Job.eq(job.key()))
+            .one(db)
+            .await?;
+        let insert = stored.is_none();
+        let mut item: batch_schedules::ActiveModel = match stored {
+            Some(stored) => stored.into(),
+            None => batch_schedules::ActiveModel {
+                job: Set(job.key().to_string()),
+                ..Default::default()
+            },
+        };
+        item.enabled = Set(params.enabled.unwrap_or(false));
+        item.run_at = Set(run_at.format("%H:%M").to_string());
+        item.business_days_only = Set(params.business_days_only.unwrap_or(false));
+        item.updated_by = Set(Some(actor.to_string()));
+        if insert {
+            item.insert(db).await?;
+        } else {
+            item.update(db).await?;
+        }
+
+        Self::row(db, scope, job).await
+    }
+
+    /// Overrides readable by the scope that are not over yet, by date
+    pub async fn overrides(db: &DatabaseConnection, scope: &TenantScope) -> Result<Vec<Model>> {
+        let today = korea_now().date();
+        let items = Entity::find()
+            .filter(scope.readable(Column::Company))
+            .filter(Condition::any().add(Column::Date.is_null()).add(Column::Date.gte(today)))
+            .order_by_asc(Column::Date)
+            .order_by_asc(Column::Weekday)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await?;
+        Ok(items)
+    }
+
+    /// Holidays and days-off overrides of the scope's company in the coming weeks
+    /// (weekends left out)
+    pub async fn upcoming_days_off(db: &DatabaseConnection, scope: &TenantScope) -> Result<Vec<UpcomingDayOff>> {
+        let today = korea_now().date();
+        let calendar = BatchScheduleService::calendar(db, today).await?;
+
+        Ok((0..UPCOMING_DAYS)
+            .map(|offset| today + Duration::days(offset))
+            .filter_map(|date| match calendar.day_off(date, scope.company()) {
+                Some(DayOff::Holiday(name)) => Some(UpcomingDayOff { date, name }),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// True when the built-in holiday table does not cover the coming weeks
+    pub fn calendar_outdated() -> bool {
+        let until = korea_now().date() + Duration::days(UPCOMING_DAYS);
+        !KoreanHolidays::is_tabulated(until.year())
+    }
+
+    /// Add a day off, a worked day or a maintenance window
+    pub async fn create_override(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        params: OverrideParams,
+    ) -> Result<Model> {
+        let kind = params.kind.trim().to_string();
+        if !OVERRIDE_KINDS.contains(&kind.as_str()) {
+            return Err(Error::BadRequest(format!("Kind must be one of: {}", OVERRIDE_KINDS.join(", "))));
+        }
+        let name = params.name.trim().to_string();
+        if name.is_empty() {
+            return Err(Error::BadRequest("Name is required".to_string()));
+        }
+        let date = non_empty(params.date)
+            .map(|raw| {
+                NaiveDate::parse_from_str(&raw, "%Y-%m-%d")
+                    .map_err(|_| Error::BadRequest("Date must be YYYY-MM-DD".to_string()))
+            })
+            .transpose()?;
+        let time = |raw: Option<String>| {
+            non_empty(raw)
+                .map(|raw| {
+                    parse_time(&raw)
+                        .map(|t| t.format("%H:%M").to_string())
+                        .ok_or_else(|| Error::BadRequest("Times must be HH:MM".to_string()))
+                })
+                .transpose()
+        };
+        let start_time = time(params.start_time)?;
+        let end_time = time(params.end_time)?;
+
+        let weekday = if kind == "maintenance" {
+            match (date, params.weekday) {
+                (Some(_), _) => None,
+                (None, Some(weekday)) if (0..=6).contains(&weekday) => Some(weekday),
+                _ => return Err(Error::BadRequest("Maintenance windows need a date or a weekday".to_string())),
+            }
+        } else {
+            if date.is_none() {
+                return Err(Error::BadRequest("Days off and worked days need a date".to_string()));
+            }
+            if start_time.is_some() || end_time.is_some() {
+                return Err(Error::BadRequest("Only maintenance windows have times".to_string()));
+            }
+            None
+        };
+
+        let item = ActiveModel {
+            kind: Set(kind),
+            date: Set(date),
+            weekday: Set(weekday),
+            start_time: Set(start_time),
+            end_time: Set(end_time),
+            company: Set(scope.company_for_new(params.company)),
+            name: Set(name),
+            created_by: Set(actor.to_string()),
+            ..Default::default()
+        };
+        Ok(item.insert(db).await?)
+    }
+
+    /// Remove an override
+    pub async fn delete_override(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        let item = Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        scope.ensure_writable(item.company.as_deref())?;
+        item.delete(db).await?;
+        Ok(())
+    }
+
+    fn require_platform(scope: &TenantScope) -> Result<()> {
+        if *scope != TenantScope::Platform {
+            return Err(Error::Unauthorized(
+                "Batch schedules can only be changed by platform administrators".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
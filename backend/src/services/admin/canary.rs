@@ -0,0 +1,273 @@
+//! Canary Admin Service
+//!
+//! Starting canary rollouts from the admin panel and deciding them by hand.
+//! Template canaries are started by reviewing a pending activation proposal
+//! (the reviewer must differ from the proposer); LLM config canaries by any
+//! administrator owning the config.
+
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection};
+use serde::Serialize;
+
+use crate::llm::resolve_llm_config;
+use crate::models::_entities::canary_rollouts::{ActiveModel, Column, Entity, Model};
+use crate::models::_entities::{llm_configs, prompt_templates, template_activations};
+use crate::services::admin::LlmConfigService;
+use crate::services::canary::{ArmStats, CanaryConfig, CanaryService, MAX_PERCENT};
+use crate::services::{content_guard, TemplateRollout, TenantScope};
+
+/// Most canaries listed
+const LIST_LIMIT: u64 = 100;
+
+/// Canary with its versions and live arm statistics, for the admin list
+#[derive(Debug, Serialize)]
+pub struct CanaryRow {
+    pub item: Model,
+    pub candidate: String,
+    pub stable: String,
+    pub stable_stats: ArmStats,
+    pub canary_stats: ArmStats,
+    pub stable_score: f32,
+    pub canary_score: f32,
+}
+
+pub struct CanaryAdminService;
+
+impl CanaryAdminService {
+    /// Recent canaries readable by the scope, newest first
+    pub async fn list(db: &DatabaseConnection, scope: &TenantScope) -> Result<Vec<CanaryRow>> {
+        let items = Entity::find()
+            .filter(scope.readable(Column::Company))
+            .order_by_desc(Column::Id)
+            .limit(LIST_LIMIT)
+            .all(db)
+            .await?;
+
+        let mut rows = Vec::new();
+        for item in items {
+            rows.push(Self::row(db, item).await?);
+        }
+        Ok(rows)
+    }
+
+    async fn row(db: &DatabaseConnection, item: Model) -> Result<CanaryRow> {
+        let candidate = CanaryService::subject(db, &item).await?;
+        let stable = match (item.stable_template_id, item.stable_llm_config_id) {
+            (Some(id), _) => prompt_templates::Entity::find_by_id(id)
+                .one(db)
+                .await?
+                .map(|t| format!("v{}", t.version)),
+            (None, Some(id)) => llm_configs::Entity::find_by_id(id).one(db).await?.map(|c| c.name),
+            (None, None) => None,
+        };
+        let (stable_stats, canary_stats) = CanaryService::stats(db, item.id).await?;
+
+        Ok(CanaryRow {
+            candidate,
+            stable: stable.unwrap_or_else(|| "-".to_string()),
+            stable_score: stable_stats.avg_score(),
+            canary_score: canary_stats.avg_score(),
+            stable_stats,
+            canary_stats,
+            item,
+        })
+    }
+
+    async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        let item = Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        scope.ensure_writable(item.company.as_deref())?;
+        Ok(item)
+    }
+
+    /// Traffic share typed by the administrator (blank = default)
+    fn percent(raw: Option<&str>, defaults: &CanaryConfig) -> Result<i32> {
+        let Some(raw) = raw.map(|r| r.trim().trim_end_matches('%')).filter(|r| !r.is_empty()) else {
+            return Ok(defaults.percent);
+        };
+        raw.parse::<i32>()
+            .ok()
+            .filter(|p| (1..=MAX_PERCENT).contains(p))
+            .ok_or_else(|| Error::BadRequest(format!("Canary share must be 1-{}%", MAX_PERCENT)))
+    }
+
+    async fn ensure_no_running(db: &DatabaseConnection, condition: Condition) -> Result<()> {
+        let running = Entity::find()
+            .filter(Column::Status.eq("running"))
+            .filter(condition)
+            .one(db)
+            .await?;
+        if running.is_some() {
+            return Err(Error::BadRequest("A canary is already running for this slot".to_string()));
+        }
+        Ok(())
+    }
+
+    fn same_company(company: Option<&str>) -> Condition {
+        match company {
+            Some(company) => Condition::all().add(Column::Company.eq(company)),
+            None => Condition::all().add(Column::Company.is_null()),
+        }
+    }
+
+    /// Review a pending activation by sending a share of traffic to it first
+    pub async fn start_template(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        reviewer: &str,
+        activation_id: i32,
+        percent: Option<&str>,
+    ) -> Result<Model> {
+        let activation = template_activations::Entity::find_by_id(activation_id)
+            .filter(scope.readable(template_activations::Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        scope.ensure_writable(activation.company.as_deref())?;
+        if activation.status != "pending" {
+            return Err(Error::BadRequest(format!("Activation is {}, not pending", activation.status)));
+        }
+        if !content_guard::can_approve(Some(&activation.proposed_by), reviewer) {
+            return Err(Error::BadRequest(
+                "Canaries must be started by a different administrator than the proposer".to_string(),
+            ));
+        }
+        if activation.window_end.is_some_and(|end| end <= Utc::now()) {
+            return Err(Error::BadRequest("Activation window has closed".to_string()));
+        }
+
+        let defaults = CanaryConfig::from_env();
+        let percent = Self::percent(percent, &defaults)?;
+        let template = prompt_templates::Entity::find_by_id(activation.prompt_template_id)
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        if template.screen_type.is_none() {
+            return Err(Error::BadRequest("Canaries need a template with a screen type".to_string()));
+        }
+        let stable = TemplateRollout::slot(db, &template).await?.into_iter().next().ok_or_else(|| {
+            Error::BadRequest("No active version to compare against; approve the activation instead".to_string())
+        })?;
+        Self::ensure_no_running(
+            db,
+            Self::same_company(template.company.as_deref())
+                .add(Column::Kind.eq("template"))
+                .add(Column::Product.eq(template.product.as_str()))
+                .add(Column::ScreenType.eq(template.screen_type.clone())),
+        )
+        .await?;
+
+        if !TemplateRollout::claim(db, activation.id, "pending", "canary").await? {
+            return Err(Error::BadRequest("Activation was reviewed in the meantime".to_string()));
+        }
+        let mut reviewed: template_activations::ActiveModel = activation.clone().into();
+        reviewed.reviewed_by = Set(Some(reviewer.to_string()));
+        reviewed.review_note = Set(Some(format!("Canary at {}%", percent)));
+        reviewed.reviewed_at = Set(Some(Utc::now().into()));
+        reviewed.update(db).await?;
+
+        let item = ActiveModel {
+            kind: Set("template".to_string()),
+            status: Set("running".to_string()),
+            product: Set(Some(template.product.clone())),
+            screen_type: Set(template.screen_type.clone()),
+            company: Set(template.company.clone()),
+            template_activation_id: Set(Some(activation.id)),
+            candidate_template_id: Set(Some(template.id)),
+            stable_template_id: Set(Some(stable.id)),
+            percent: Set(percent),
+            min_samples: Set(defaults.min_samples),
+            max_score_drop: Set(defaults.max_score_drop),
+            max_error_increase: Set(defaults.max_error_increase),
+            started_by: Set(reviewer.to_string()),
+            ..Default::default()
+        };
+        Ok(item.insert(db).await?)
+    }
+
+    /// Send a share of the company's generations to an inactive LLM config
+    pub async fn start_llm_config(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        config_id: i32,
+        percent: Option<&str>,
+    ) -> Result<Model> {
+        let config = LlmConfigService::find_by_id(db, scope, config_id).await?;
+        scope.ensure_writable(config.company.as_deref())?;
+        if config.is_active == Some(true) {
+            return Err(Error::BadRequest("LLM config is already active".to_string()));
+        }
+
+        let defaults = CanaryConfig::from_env();
+        let percent = Self::percent(percent, &defaults)?;
+        let stable = resolve_llm_config(db, config.company.as_deref())
+            .await
+            .filter(|stable| stable.company == config.company)
+            .ok_or_else(|| {
+                Error::BadRequest("No active config of the same company to compare against; activate it instead".to_string())
+            })?;
+        Self::ensure_no_running(
+            db,
+            Self::same_company(config.company.as_deref()).add(Column::Kind.eq("llm_config")),
+        )
+        .await?;
+
+        let item = ActiveModel {
+            kind: Set("llm_config".to_string()),
+            status: Set("running".to_string()),
+            company: Set(config.company.clone()),
+            candidate_llm_config_id: Set(Some(config.id)),
+            stable_llm_config_id: Set(Some(stable.id)),
+            percent: Set(percent),
+            min_samples: Set(defaults.min_samples),
+            max_score_drop: Set(defaults.max_score_drop),
+            max_error_increase: Set(defaults.max_error_increase),
+            started_by: Set(actor.to_string()),
+            ..Default::default()
+        };
+        Ok(item.insert(db).await?)
+    }
+
+    /// Promote a running canary before the evaluator decides
+    pub async fn promote(db: &DatabaseConnection, scope: &TenantScope, actor: &str, id: i32) -> Result<CanaryRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        Self::ensure_running(&item)?;
+        let item = CanaryService::promote(db, item, actor, "Promoted by hand").await?;
+        Self::row(db, item).await
+    }
+
+    /// Roll a running canary back before the evaluator decides
+    pub async fn roll_back(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        id: i32,
+        reason: Option<String>,
+    ) -> Result<CanaryRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        Self::ensure_running(&item)?;
+        let reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| "Rolled back by hand".to_string());
+        let item = CanaryService::roll_back(db, item, actor, &reason).await?;
+        Self::row(db, item).await
+    }
+
+    fn ensure_running(item: &Model) -> Result<()> {
+        if item.status != "running" {
+            return Err(Error::BadRequest(format!("Canary is already {}", item.status)));
+        }
+        Ok(())
+    }
+
+    /// Thresholds new canaries start with, shown on the canaries page
+    pub fn defaults() -> CanaryConfig {
+        CanaryConfig::from_env()
+    }
+}
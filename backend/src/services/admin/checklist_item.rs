@@ -0,0 +1,237 @@
+//! Checklist Item Service
+//!
+//! Business logic for checklist item CRUD operations. Items are rendered
+//! into generation prompts and checked by the pipeline's Checklist Validator.
+
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{ChecklistItem, ChecklistTarget};
+use crate::models::_entities::checklist_items::{ActiveModel, Column, Entity, Model};
+use crate::services::TenantScope;
+use crate::utils::{bool_from_str_or_bool, optional_bool_from_str_or_bool, OptionalField};
+
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Query parameters for search with pagination
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct QueryParams {
+    /// Search keyword (matches label or pattern)
+    pub keyword: Option<String>,
+
+    /// Filter by screen type ("*" = items for every screen type)
+    pub screen_type: Option<String>,
+
+    /// Page number (1-indexed)
+    pub page: Option<u64>,
+
+    /// Page size
+    pub page_size: Option<u64>,
+}
+
+/// Create parameters
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateParams {
+    pub product: String,
+    /// Blank = every screen type
+    pub screen_type: Option<String>,
+    pub label: String,
+    pub target: String,
+    pub pattern: String,
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub is_active: Option<bool>,
+    /// Tenant company (platform administrators only; blank = shared)
+    pub company: Option<String>,
+}
+
+/// Update parameters
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateParams {
+    // Required fields that can be updated
+    pub product: Option<String>,
+    pub label: Option<String>,
+    pub target: Option<String>,
+    pub pattern: Option<String>,
+
+    // Optional fields - use OptionalField for proper PATCH semantics
+    #[serde(default)]
+    pub screen_type: OptionalField<String>,
+    #[serde(default, deserialize_with = "optional_bool_from_str_or_bool")]
+    pub is_active: OptionalField<bool>,
+}
+
+/// Paginated response
+#[derive(Debug, Serialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+pub struct ChecklistItemService;
+
+impl ChecklistItemService {
+    /// Build query with filters; items are listed by product, screen type and id
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        let mut condition = scope.readable(Column::Company);
+
+        if let Some(keyword) = params.keyword.as_deref().filter(|k| !k.is_empty()) {
+            condition = condition.add(
+                Condition::any()
+                    .add(Column::Label.contains(keyword))
+                    .add(Column::Pattern.contains(keyword)),
+            );
+        }
+
+        match params.screen_type.as_deref() {
+            Some("*") => condition = condition.add(Column::ScreenType.is_null()),
+            Some(screen_type) if !screen_type.is_empty() => {
+                condition = condition.add(Column::ScreenType.eq(screen_type))
+            }
+            _ => {}
+        }
+
+        Entity::find()
+            .filter(condition)
+            .order_by_asc(Column::Product)
+            .order_by_asc(Column::ScreenType)
+            .order_by_asc(Column::Id)
+    }
+
+    /// Search with pagination and filters
+    pub async fn search(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: &QueryParams,
+    ) -> Result<PageResponse<Model>> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+        let paginator = Self::build_query(scope, params).paginate(db, page_size);
+        let total_items = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+
+        Ok(PageResponse {
+            items,
+            page,
+            page_size,
+            total_pages,
+            total_items,
+        })
+    }
+
+    /// Find by ID
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    /// Create new checklist item
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: CreateParams,
+    ) -> Result<Model> {
+        if params.product.trim().is_empty() {
+            return Err(Error::BadRequest("Product is required".to_string()));
+        }
+        if params.label.trim().is_empty() {
+            return Err(Error::BadRequest("Label is required".to_string()));
+        }
+        let target = Self::parse_target(&params.target)?;
+        let pattern = Self::parse_pattern(&params.pattern)?;
+
+        let item = ActiveModel {
+            product: Set(params.product.trim().to_string()),
+            screen_type: Set(Self::screen_type(params.screen_type)),
+            label: Set(params.label.trim().to_string()),
+            target: Set(target.as_str().to_string()),
+            pattern: Set(pattern),
+            is_active: Set(params.is_active),
+            company: Set(scope.company_for_new(params.company)),
+            ..Default::default()
+        };
+
+        let item = item.insert(db).await?;
+        Ok(item)
+    }
+
+    /// Update existing checklist item
+    pub async fn update(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+        params: UpdateParams,
+    ) -> Result<Model> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        let mut item: ActiveModel = item.into();
+
+        // Required fields
+        if let Some(product) = params.product {
+            if product.trim().is_empty() {
+                return Err(Error::BadRequest("Product cannot be empty".to_string()));
+            }
+            item.product = Set(product.trim().to_string());
+        }
+        if let Some(label) = params.label {
+            if label.trim().is_empty() {
+                return Err(Error::BadRequest("Label cannot be empty".to_string()));
+            }
+            item.label = Set(label.trim().to_string());
+        }
+        if let Some(target) = params.target {
+            item.target = Set(Self::parse_target(&target)?.as_str().to_string());
+        }
+        if let Some(pattern) = params.pattern {
+            item.pattern = Set(Self::parse_pattern(&pattern)?);
+        }
+
+        // Optional fields - only update if Present (not Missing)
+        if let OptionalField::Present(opt_value) = params.screen_type {
+            item.screen_type = Set(Self::screen_type(opt_value));
+        }
+        if let OptionalField::Present(opt_value) = params.is_active {
+            item.is_active = Set(opt_value);
+        }
+
+        let item = item.update(db).await?;
+        Ok(item)
+    }
+
+    /// Delete checklist item
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        item.delete(db).await?;
+        Ok(())
+    }
+
+    fn parse_target(raw: &str) -> Result<ChecklistTarget> {
+        ChecklistTarget::parse(raw)
+            .ok_or_else(|| Error::BadRequest(format!("Target must be 'xml' or 'js', got '{}'", raw)))
+    }
+
+    fn parse_pattern(raw: &str) -> Result<String> {
+        let pattern = raw.trim();
+        if pattern.is_empty() {
+            return Err(Error::BadRequest("Pattern is required".to_string()));
+        }
+        ChecklistItem::validate_pattern(pattern)
+            .map_err(|e| Error::BadRequest(format!("Invalid pattern: {}", e)))?;
+        Ok(pattern.to_string())
+    }
+
+    /// Blank screen type = every screen type
+    fn screen_type(raw: Option<String>) -> Option<String> {
+        raw.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+    }
+}
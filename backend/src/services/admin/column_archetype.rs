@@ -0,0 +1,281 @@
+//! Column Archetype Service
+//!
+//! Business logic for column archetype CRUD operations. Archetypes are
+//! matched to columns after normalization and their rules are described in
+//! the prompt and checked by the pipeline's Archetype Validator.
+
+use loco_rs::prelude::*;
+use sea_orm::{query::*, ActiveValue, DatabaseConnection, PaginatorTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ColumnArchetype;
+use crate::models::_entities::column_archetypes::{ActiveModel, Column, Entity, Model};
+use crate::services::TenantScope;
+use crate::utils::{
+    bool_from_str_or_bool, i32_from_str_or_number, optional_bool_from_str_or_bool,
+    optional_i32_from_str_or_number, OptionalField,
+};
+
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Query parameters for search with pagination
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct QueryParams {
+    /// Search keyword (matches name, label, name patterns or comment keywords)
+    pub keyword: Option<String>,
+
+    /// Page number (1-indexed)
+    pub page: Option<u64>,
+
+    /// Page size
+    pub page_size: Option<u64>,
+}
+
+/// Create parameters
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateParams {
+    pub name: String,
+    /// Comma-separated column name patterns
+    pub name_patterns: Option<String>,
+    /// Comma-separated comment keywords
+    pub comment_keywords: Option<String>,
+    pub label: Option<String>,
+    pub ui_type: Option<String>,
+    pub data_type: Option<String>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub max_length: Option<i32>,
+    pub validation: Option<String>,
+    pub format: Option<String>,
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub is_active: Option<bool>,
+    /// Tenant company (platform administrators only; blank = shared)
+    pub company: Option<String>,
+}
+
+/// Update parameters
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateParams {
+    // Required fields that can be updated
+    pub name: Option<String>,
+
+    // Optional fields - use OptionalField for proper PATCH semantics
+    #[serde(default)]
+    pub name_patterns: OptionalField<String>,
+    #[serde(default)]
+    pub comment_keywords: OptionalField<String>,
+    #[serde(default)]
+    pub label: OptionalField<String>,
+    #[serde(default)]
+    pub ui_type: OptionalField<String>,
+    #[serde(default)]
+    pub data_type: OptionalField<String>,
+    #[serde(default, deserialize_with = "optional_i32_from_str_or_number")]
+    pub max_length: OptionalField<i32>,
+    #[serde(default)]
+    pub validation: OptionalField<String>,
+    #[serde(default)]
+    pub format: OptionalField<String>,
+    #[serde(default, deserialize_with = "optional_bool_from_str_or_bool")]
+    pub is_active: OptionalField<bool>,
+}
+
+/// Paginated response
+#[derive(Debug, Serialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+pub struct ColumnArchetypeService;
+
+impl ColumnArchetypeService {
+    /// Build query with filters; archetypes are listed in matching order (by id)
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        let mut condition = scope.readable(Column::Company);
+
+        if let Some(keyword) = params.keyword.as_deref().filter(|k| !k.is_empty()) {
+            condition = condition.add(
+                Condition::any()
+                    .add(Column::Name.contains(keyword))
+                    .add(Column::Label.contains(keyword))
+                    .add(Column::NamePatterns.contains(keyword))
+                    .add(Column::CommentKeywords.contains(keyword)),
+            );
+        }
+
+        Entity::find().filter(condition).order_by_asc(Column::Id)
+    }
+
+    /// Search with pagination and filters
+    pub async fn search(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: &QueryParams,
+    ) -> Result<PageResponse<Model>> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+        let paginator = Self::build_query(scope, params).paginate(db, page_size);
+        let total_items = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+
+        Ok(PageResponse {
+            items,
+            page,
+            page_size,
+            total_pages,
+            total_items,
+        })
+    }
+
+    /// Find by ID
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    /// Create new column archetype
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: CreateParams,
+    ) -> Result<Model> {
+        if params.name.trim().is_empty() {
+            return Err(Error::BadRequest("Name is required".to_string()));
+        }
+
+        let item = ActiveModel {
+            name: Set(params.name.trim().to_string()),
+            name_patterns: Set(Self::text(params.name_patterns)),
+            comment_keywords: Set(Self::text(params.comment_keywords)),
+            label: Set(Self::text(params.label)),
+            ui_type: Set(Self::parse_ui_type(params.ui_type)?),
+            data_type: Set(Self::parse_data_type(params.data_type)?),
+            max_length: Set(Self::parse_max_length(params.max_length)?),
+            validation: Set(Self::parse_validation(params.validation)?),
+            format: Set(Self::text(params.format)),
+            is_active: Set(params.is_active),
+            company: Set(scope.company_for_new(params.company)),
+            ..Default::default()
+        };
+        Self::ensure_matchable(&item)?;
+
+        let item = item.insert(db).await?;
+        Ok(item)
+    }
+
+    /// Update existing column archetype
+    pub async fn update(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+        params: UpdateParams,
+    ) -> Result<Model> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        let mut item: ActiveModel = item.into();
+
+        // Required fields
+        if let Some(name) = params.name {
+            if name.trim().is_empty() {
+                return Err(Error::BadRequest("Name cannot be empty".to_string()));
+            }
+            item.name = Set(name.trim().to_string());
+        }
+
+        // Optional fields - only update if Present (not Missing)
+        if let OptionalField::Present(opt_value) = params.name_patterns {
+            item.name_patterns = Set(Self::text(opt_value));
+        }
+        if let OptionalField::Present(opt_value) = params.comment_keywords {
+            item.comment_keywords = Set(Self::text(opt_value));
+        }
+        if let OptionalField::Present(opt_value) = params.label {
+            item.label = Set(Self::text(opt_value));
+        }
+        if let OptionalField::Present(opt_value) = params.ui_type {
+            item.ui_type = Set(Self::parse_ui_type(opt_value)?);
+        }
+        if let OptionalField::Present(opt_value) = params.data_type {
+            item.data_type = Set(Self::parse_data_type(opt_value)?);
+        }
+        if let OptionalField::Present(opt_value) = params.max_length {
+            item.max_length = Set(Self::parse_max_length(opt_value)?);
+        }
+        if let OptionalField::Present(opt_value) = params.validation {
+            item.validation = Set(Self::parse_validation(opt_value)?);
+        }
+        if let OptionalField::Present(opt_value) = params.format {
+            item.format = Set(Self::text(opt_value));
+        }
+        if let OptionalField::Present(opt_value) = params.is_active {
+            item.is_active = Set(opt_value);
+        }
+        Self::ensure_matchable(&item)?;
+
+        let item = item.update(db).await?;
+        Ok(item)
+    }
+
+    /// Delete column archetype
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        item.delete(db).await?;
+        Ok(())
+    }
+
+    /// An archetype without name patterns or comment keywords matches nothing
+    fn ensure_matchable(item: &ActiveModel) -> Result<()> {
+        let has = |value: &ActiveValue<Option<String>>| match value {
+            ActiveValue::Set(v) | ActiveValue::Unchanged(v) => v.as_ref().is_some_and(|v| !v.is_empty()),
+            ActiveValue::NotSet => false,
+        };
+        if has(&item.name_patterns) || has(&item.comment_keywords) {
+            Ok(())
+        } else {
+            Err(Error::BadRequest("Name patterns or comment keywords are required".to_string()))
+        }
+    }
+
+    fn parse_ui_type(raw: Option<String>) -> Result<Option<String>> {
+        let Some(value) = Self::text(raw) else { return Ok(None) };
+        ColumnArchetype::parse_ui_type(&value)
+            .map(|_| Some(value.to_lowercase()))
+            .ok_or_else(|| Error::BadRequest(format!("Unknown UI type '{}'", value)))
+    }
+
+    fn parse_data_type(raw: Option<String>) -> Result<Option<String>> {
+        let Some(value) = Self::text(raw) else { return Ok(None) };
+        ColumnArchetype::parse_data_type(&value)
+            .map(|_| Some(value.to_lowercase()))
+            .ok_or_else(|| Error::BadRequest(format!("Unknown data type '{}'", value)))
+    }
+
+    fn parse_max_length(raw: Option<i32>) -> Result<Option<i32>> {
+        match raw {
+            Some(n) if n <= 0 => Err(Error::BadRequest("Max length must be positive".to_string())),
+            other => Ok(other),
+        }
+    }
+
+    fn parse_validation(raw: Option<String>) -> Result<Option<String>> {
+        let Some(pattern) = Self::text(raw) else { return Ok(None) };
+        ColumnArchetype::validate_validation(&pattern)
+            .map_err(|e| Error::BadRequest(format!("Invalid validation pattern: {}", e)))?;
+        Ok(Some(pattern))
+    }
+
+    /// Trimmed text, blank = none
+    fn text(raw: Option<String>) -> Option<String> {
+        raw.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+    }
+}
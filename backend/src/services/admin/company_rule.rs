@@ -7,7 +7,11 @@ use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error};
 
+use crate::domain::CompanyRuleSections;
 use crate::models::_entities::company_rules::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::content_guard;
+use crate::services::TenantScope;
 use crate::utils::OptionalField;
 
 const DEFAULT_PAGE_SIZE: u64 = 20;
@@ -38,6 +42,10 @@ pub struct CreateParams {
     pub name: String,
     pub naming_convention: Option<String>,
     pub additional_rules: Option<String>,
+    /// Structured sections as JSON text (see `CompanyRuleSections`)
+    pub sections: Option<String>,
+    /// Tenant company (platform administrators only; blank = shared)
+    pub company: Option<String>,
 }
 
 /// Update parameters
@@ -51,6 +59,8 @@ pub struct UpdateParams {
     pub naming_convention: OptionalField<String>,
     #[serde(default)]
     pub additional_rules: OptionalField<String>,
+    #[serde(default)]
+    pub sections: OptionalField<String>,
 }
 
 /// Paginated response
@@ -67,8 +77,8 @@ pub struct CompanyRuleService;
 
 impl CompanyRuleService {
     /// Build query with filters and sorting
-    fn build_query(params: &QueryParams) -> sea_orm::Select<Entity> {
-        let mut condition = Condition::all();
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        let mut condition = scope.readable(Column::Company);
 
         // Keyword search
         if let Some(keyword) = &params.keyword {
@@ -97,6 +107,7 @@ impl CompanyRuleService {
     /// Search with pagination, filters, and sorting
     pub async fn search(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         params: &QueryParams,
     ) -> Result<PageResponse<Model>> {
         debug!("CompanyRuleService::search - params: {:?}", params);
@@ -105,7 +116,7 @@ impl CompanyRuleService {
         let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
         debug!("CompanyRuleService::search - page: {}, page_size: {}", page, page_size);
 
-        let query = Self::build_query(params);
+        let query = Self::build_query(scope, params);
         let paginator = query.paginate(db, page_size);
 
         debug!("CompanyRuleService::search - counting items");
@@ -153,39 +164,55 @@ impl CompanyRuleService {
     }
 
     /// Find by ID
-    pub async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<Model> {
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
         Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
             .one(db)
             .await?
             .ok_or_else(|| Error::NotFound)
     }
 
-    /// Create new company rule
-    pub async fn create(db: &DatabaseConnection, params: CreateParams) -> Result<Model> {
+    /// Create new company rule (`actor` = saving administrator, audited and
+    /// recorded if quarantined)
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        params: CreateParams,
+    ) -> Result<Model> {
         // Validation
         if params.name.trim().is_empty() {
             return Err(Error::BadRequest("Name is required".to_string()));
         }
 
+        let sections = Self::parse_sections(params.sections.as_deref())?;
+
         let item = ActiveModel {
             name: Set(params.name.trim().to_string()),
             naming_convention: Set(params.naming_convention),
             additional_rules: Set(params.additional_rules),
+            sections: Set(sections),
+            company: Set(scope.company_for_new(params.company)),
+            quarantined_by: Set(Some(actor.to_string())),
             ..Default::default()
         };
 
         let item = item.insert(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Create, None, Some(&item)).await?;
         Ok(item)
     }
 
     /// Update existing company rule
     pub async fn update(
         db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
         id: i32,
         params: UpdateParams,
     ) -> Result<Model> {
-        let item = Self::find_by_id(db, id).await?;
-        let mut item: ActiveModel = item.into();
+        let before = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(before.company.as_deref())?;
+        let mut item: ActiveModel = before.clone().into();
 
         // Required field
         if let Some(name) = params.name {
@@ -202,15 +229,68 @@ impl CompanyRuleService {
         if let OptionalField::Present(opt_value) = params.additional_rules {
             item.additional_rules = Set(opt_value);
         }
+        if let OptionalField::Present(opt_value) = params.sections {
+            item.sections = Set(Self::parse_sections(opt_value.as_deref())?);
+        }
+        if item.naming_convention.is_set() || item.additional_rules.is_set() || item.sections.is_set() {
+            item.quarantined_by = Set(Some(actor.to_string()));
+        }
+
+        let item = item.update(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Update, Some(&before), Some(&item)).await?;
+        Ok(item)
+    }
+
+    /// Parse and validate structured sections JSON (blank clears the sections)
+    fn parse_sections(raw: Option<&str>) -> Result<Option<serde_json::Value>> {
+        let raw = match raw.map(str::trim) {
+            Some(r) if !r.is_empty() => r,
+            _ => return Ok(None),
+        };
+
+        let sections: CompanyRuleSections = serde_json::from_str(raw)
+            .map_err(|e| Error::BadRequest(format!("Invalid sections JSON: {}", e)))?;
+
+        if sections.forbidden_apis.iter().any(|api| api.pattern.trim().is_empty()) {
+            return Err(Error::BadRequest(
+                "Forbidden API pattern cannot be empty".to_string(),
+            ));
+        }
+
+        if sections.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::to_value(sections)?))
+    }
+
+    /// Release a quarantined rule set into prompts (second administrator only)
+    pub async fn approve(db: &DatabaseConnection, scope: &TenantScope, approver: &str, id: i32) -> Result<Model> {
+        let before = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(before.company.as_deref())?;
+
+        if before.quarantine_reason.is_none() {
+            return Err(Error::BadRequest("Rule set is not quarantined".to_string()));
+        }
+        if !content_guard::can_approve(before.quarantined_by.as_deref(), approver) {
+            return Err(Error::BadRequest(
+                "Quarantined content must be approved by a different administrator".to_string(),
+            ));
+        }
 
+        let mut item: ActiveModel = before.clone().into();
+        item.quarantine_reason = Set(None);
+        item.quarantined_by = Set(None);
         let item = item.update(db).await?;
+        AuditLogService::record(db, approver, AuditAction::Approve, Some(&before), Some(&item)).await?;
         Ok(item)
     }
 
     /// Delete company rule
-    pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<()> {
-        let item = Self::find_by_id(db, id).await?;
-        item.delete(db).await?;
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, actor: &str, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        item.clone().delete(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Delete, Some(&item), None).await?;
         Ok(())
     }
 }
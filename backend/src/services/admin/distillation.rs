@@ -0,0 +1,221 @@
+//! Distillation Corpus Service
+//!
+//! Admin view of the few-shot corpus captured from remote providers: the
+//! capture settings (platform administrators only) and the examples of the
+//! tenant, which can be pinned against eviction or deleted.
+
+use chrono::{DateTime, FixedOffset};
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::distillation_examples::{Column, Entity, Model};
+use crate::models::distillation_settings::{self, EVICTION_POLICIES};
+use crate::services::distillation::DistillationService;
+use crate::services::TenantScope;
+use crate::utils::{bool_from_str_or_bool, i32_from_str_or_number};
+
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Largest corpus size per tenant that can be configured
+const MAX_CORPUS_SIZE: i32 = 10_000;
+
+/// Query parameters for search with pagination
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct QueryParams {
+    /// Filter by screen type
+    pub screen_type: Option<String>,
+
+    /// Page number (1-indexed)
+    pub page: Option<u64>,
+
+    /// Page size
+    pub page_size: Option<u64>,
+}
+
+/// Settings form
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SettingsParams {
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub enabled: Option<bool>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub max_examples: Option<i32>,
+    pub eviction: Option<String>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub min_quality: Option<i32>,
+}
+
+/// Example without its (large) output, for the list
+#[derive(Debug, Clone, Serialize)]
+pub struct ExampleRow {
+    pub id: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub product: String,
+    pub screen_type: String,
+    pub features: String,
+    pub source_provider: String,
+    pub source_model: String,
+    pub quality_score: Option<i32>,
+    pub company: Option<String>,
+    pub pinned: bool,
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<FixedOffset>>,
+    /// Size of the XML and JavaScript in characters
+    pub output_chars: usize,
+}
+
+impl From<Model> for ExampleRow {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            created_at: m.created_at,
+            product: m.product,
+            screen_type: m.screen_type,
+            features: m.features,
+            source_provider: m.source_provider,
+            source_model: m.source_model,
+            quality_score: m.quality_score,
+            company: m.company,
+            pinned: m.pinned,
+            use_count: m.use_count,
+            last_used_at: m.last_used_at,
+            output_chars: m.xml.chars().count() + m.javascript.chars().count(),
+        }
+    }
+}
+
+/// Paginated response
+#[derive(Debug, Serialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+pub struct DistillationAdminService;
+
+impl DistillationAdminService {
+    /// Examples of the scope's own tenant, most used first
+    pub async fn search(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: &QueryParams,
+    ) -> Result<PageResponse<ExampleRow>> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+        let mut query = Entity::find().filter(Self::own(scope));
+        if let Some(screen_type) = params.screen_type.as_deref().filter(|s| !s.is_empty()) {
+            query = query.filter(Column::ScreenType.eq(screen_type));
+        }
+        let paginator = query
+            .order_by_desc(Column::Pinned)
+            .order_by_desc(Column::UseCount)
+            .order_by_desc(Column::Id)
+            .paginate(db, page_size);
+
+        let total_items = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+        let items = paginator.fetch_page(page - 1).await?;
+
+        Ok(PageResponse {
+            items: items.into_iter().map(ExampleRow::from).collect(),
+            page,
+            page_size,
+            total_pages,
+            total_items,
+        })
+    }
+
+    /// Current capture settings
+    pub async fn settings(db: &DatabaseConnection) -> distillation_settings::Model {
+        distillation_settings::Model::current(db).await
+    }
+
+    /// Change the capture settings (platform administrators only)
+    pub async fn update_settings(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: SettingsParams,
+    ) -> Result<distillation_settings::Model> {
+        if *scope != TenantScope::Platform {
+            return Err(Error::Unauthorized(
+                "Distillation settings can only be changed by platform administrators".to_string(),
+            ));
+        }
+        let current = Self::settings(db).await;
+
+        let max_examples = params.max_examples.unwrap_or(current.max_examples);
+        if !(1..=MAX_CORPUS_SIZE).contains(&max_examples) {
+            return Err(Error::BadRequest(format!(
+                "Corpus size must be between 1 and {}",
+                MAX_CORPUS_SIZE
+            )));
+        }
+        let min_quality = params.min_quality.unwrap_or(current.min_quality);
+        if !(0..=100).contains(&min_quality) {
+            return Err(Error::BadRequest("Minimum quality must be between 0 and 100".to_string()));
+        }
+        let eviction = params.eviction.unwrap_or(current.eviction);
+        if !EVICTION_POLICIES.contains(&eviction.as_str()) {
+            return Err(Error::BadRequest(format!(
+                "Eviction must be one of: {}",
+                EVICTION_POLICIES.join(", ")
+            )));
+        }
+
+        let settings = distillation_settings::Model::save(
+            db,
+            params.enabled.unwrap_or(false),
+            max_examples,
+            &eviction,
+            min_quality,
+        )
+        .await?;
+        Ok(settings)
+    }
+
+    /// Pin or unpin an example
+    pub async fn toggle_pin(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<ExampleRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        let pinned = !item.pinned;
+        let mut item = item.into_active_model();
+        item.pinned = Set(pinned);
+        Ok(item.update(db).await?.into())
+    }
+
+    /// Delete an example
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        item.delete(db).await?;
+        Ok(())
+    }
+
+    /// Apply the corpus size now (e.g. after lowering it), returning how many
+    /// examples were removed
+    pub async fn evict(db: &DatabaseConnection, scope: &TenantScope) -> Result<u64> {
+        let settings = Self::settings(db).await;
+        DistillationService::evict(db, &settings, scope.company())
+            .await
+            .map_err(|e| Error::string(&e.to_string()))
+    }
+
+    async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        Entity::find_by_id(id)
+            .filter(Self::own(scope))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    /// Examples are never shared: platform administrators see the platform's
+    fn own(scope: &TenantScope) -> Condition {
+        match scope.company() {
+            Some(code) => Condition::all().add(Column::Company.eq(code)),
+            None => Condition::all().add(Column::Company.is_null()),
+        }
+    }
+}
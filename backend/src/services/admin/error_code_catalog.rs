@@ -0,0 +1,154 @@
+//! Error-Code Catalog Admin Service
+//!
+//! Upload, enable/disable and delete the projects' standard error-code
+//! catalogs. Tenants manage their own catalogs; platform administrators
+//! also manage the shared ones every tenant falls back to.
+
+use loco_rs::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{DatabaseConnection, QueryOrder};
+use serde::Serialize;
+
+use crate::domain::{ErrorCode, ErrorCodeCatalog};
+use crate::models::_entities::error_code_catalogs::{ActiveModel, Column, Entity, Model};
+use crate::services::TenantScope;
+
+/// Largest catalog file that can be uploaded
+pub const MAX_CATALOG_BYTES: usize = 1024 * 1024;
+
+/// Codes shown in the preview of a catalog row
+const PREVIEW_CODES: usize = 5;
+
+/// Catalog as shown in the admin panel
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogRow {
+    pub id: i32,
+    pub name: String,
+    pub code_count: i32,
+    /// First codes of the catalog
+    pub preview: Vec<ErrorCode>,
+    pub enabled: bool,
+    pub company: Option<String>,
+    pub uploaded_by: Option<String>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<Model> for CatalogRow {
+    fn from(m: Model) -> Self {
+        let preview = m.catalog().codes.into_iter().take(PREVIEW_CODES).collect();
+        Self {
+            id: m.id,
+            name: m.name,
+            code_count: m.code_count,
+            preview,
+            enabled: m.enabled,
+            company: m.company,
+            uploaded_by: m.uploaded_by,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+/// An uploaded catalog file
+#[derive(Debug)]
+pub struct UploadParams {
+    pub name: String,
+    pub filename: String,
+    pub content: String,
+    /// Replace the codes of an existing catalog with the same name
+    pub replace: bool,
+}
+
+pub struct ErrorCodeCatalogService;
+
+impl ErrorCodeCatalogService {
+    /// Catalogs the scope can read, its own first
+    pub async fn list(db: &DatabaseConnection, scope: &TenantScope) -> Result<Vec<CatalogRow>> {
+        let catalogs = Entity::find()
+            .filter(scope.readable(Column::Company))
+            .order_by_asc(Column::Company)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await?;
+        Ok(catalogs.into_iter().map(CatalogRow::from).collect())
+    }
+
+    /// Store a catalog after parsing it; platform administrators upload
+    /// shared catalogs, tenants their company's
+    pub async fn upload(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: UploadParams,
+        uploaded_by: &str,
+    ) -> Result<CatalogRow> {
+        let name = params.name.trim().to_string();
+        if name.is_empty() || name.len() > 64 {
+            return Err(Error::BadRequest("Catalog name must be 1-64 characters".to_string()));
+        }
+        if params.content.len() > MAX_CATALOG_BYTES {
+            return Err(Error::BadRequest(format!(
+                "Catalog is larger than {} KB",
+                MAX_CATALOG_BYTES / 1024
+            )));
+        }
+        let catalog = ErrorCodeCatalog::parse(&params.content, &params.filename).map_err(Error::BadRequest)?;
+        let codes = serde_json::to_string(&catalog.codes).map_err(|e| Error::string(&e.to_string()))?;
+        let code_count = catalog.codes.len() as i32;
+
+        let company = scope.company_for_new(None);
+        let existing = Model::find_by_name(db, &name, company.as_deref()).await?;
+        let item = match existing {
+            Some(_) if !params.replace => {
+                return Err(Error::BadRequest(format!("A catalog named '{}' already exists", name)));
+            }
+            Some(existing) => {
+                scope.ensure_writable(existing.company.as_deref())?;
+                let mut item = existing.into_active_model();
+                item.codes = Set(codes);
+                item.code_count = Set(code_count);
+                item.uploaded_by = Set(Some(uploaded_by.to_string()));
+                item.update(db).await?
+            }
+            None => {
+                ActiveModel {
+                    name: Set(name),
+                    codes: Set(codes),
+                    code_count: Set(code_count),
+                    enabled: Set(true),
+                    company: Set(company),
+                    uploaded_by: Set(Some(uploaded_by.to_string())),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?
+            }
+        };
+        Ok(item.into())
+    }
+
+    /// Enable or disable a catalog
+    pub async fn toggle(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<CatalogRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        let enabled = !item.enabled;
+        let mut item = item.into_active_model();
+        item.enabled = Set(enabled);
+        Ok(item.update(db).await?.into())
+    }
+
+    /// Delete a catalog
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        item.delete(db).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)
+    }
+}
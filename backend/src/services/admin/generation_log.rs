@@ -1,7 +1,8 @@
 //! Generation Log Service
 //!
 //! Business logic for generation log viewing (audit trail).
-//! Read-only - no create/update/delete operations.
+//! Read-only - no create/update/delete operations; a replay writes a new
+//! log linked to the original.
 
 use chrono::{DateTime, FixedOffset};
 use loco_rs::prelude::*;
@@ -9,7 +10,10 @@ use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
 use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::generation_logs::{Column, Entity, Model};
-use crate::models::_entities::users;
+use crate::models::_entities::{llm_configs, users};
+use crate::services::sla::JobTimeline;
+use crate::domain::Lineage;
+use crate::services::{LineageService, ReplayOutcome, ReplayService, TenantScope};
 
 const DEFAULT_PAGE_SIZE: u64 = 50;
 const MAX_PAGE_SIZE: u64 = 100;
@@ -31,11 +35,30 @@ pub struct GenerationLogWithUser {
     /// Parsed from JSON string to Vec for template iteration
     pub warnings: Vec<String>,
     pub error_message: Option<String>,
+    /// Machine-readable cause of the failure
+    pub error_code: Option<String>,
     pub template_version: i32,
     /// LLM provider used (internal audit)
     pub provider: Option<String>,
     /// LLM model name used (internal audit)
     pub model_name: Option<String>,
+    /// Tenant company of the requesting user
+    pub company: Option<String>,
+    /// Stage durations of queued jobs
+    pub timeline: JobTimeline,
+    /// Log this generation replays
+    pub replay_of: Option<i32>,
+    /// Requirements ticket of the request
+    pub issue_key: Option<String>,
+}
+
+/// LLM config offered as a replay override
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayTarget {
+    pub id: i32,
+    pub name: String,
+    pub provider: String,
+    pub model_name: String,
 }
 
 impl GenerationLogWithUser {
@@ -52,6 +75,8 @@ impl GenerationLogWithUser {
             .and_then(|w| serde_json::from_str(w).ok())
             .unwrap_or_default();
 
+        let timeline = JobTimeline::of(&log);
+
         Self {
             id: log.id,
             created_at: log.created_at,
@@ -66,9 +91,14 @@ impl GenerationLogWithUser {
             artifacts: log.artifacts,
             warnings,
             error_message: log.error_message,
+            error_code: log.error_code,
             template_version: log.template_version,
             provider: log.provider,
             model_name: log.model_name,
+            company: log.company,
+            timeline,
+            replay_of: log.replay_of,
+            issue_key: log.issue_key,
         }
     }
 }
@@ -91,6 +121,10 @@ pub struct QueryParams {
     /// Filter by input type
     pub input_type: Option<String>,
 
+    /// Filter by requirements ticket (e.g. "ERP-123")
+    #[serde(default)]
+    pub issue_key: Option<String>,
+
     /// Date range filter - from
     #[serde(default)]
     pub date_from: Option<String>,
@@ -126,8 +160,9 @@ pub struct GenerationLogService;
 
 impl GenerationLogService {
     /// Build query with filters and sorting
-    fn build_query(params: &QueryParams) -> sea_orm::Select<Entity> {
-        let mut condition = Condition::all();
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        // Logs are never shared between tenants
+        let mut condition = scope.owned(Column::Company);
 
         // Status filter (single select)
         if let Some(status) = &params.status {
@@ -148,6 +183,11 @@ impl GenerationLogService {
             }
         }
 
+        // Issue key filter
+        if let Some(issue_key) = params.issue_key.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+            condition = condition.add(Column::IssueKey.eq(issue_key));
+        }
+
         // Note: keyword and date_from/date_to filters not yet implemented
         // They are accepted to prevent 400 errors but don't filter data yet
 
@@ -172,12 +212,13 @@ impl GenerationLogService {
     /// Search with pagination, filters, and sorting
     pub async fn search(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         params: &QueryParams,
     ) -> Result<PageResponse<GenerationLogWithUser>> {
         let page = params.page.unwrap_or(1).max(1);
         let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
 
-        let query = Self::build_query(params);
+        let query = Self::build_query(scope, params);
         let paginator = query.paginate(db, page_size);
 
         let total_items = paginator.num_items().await?;
@@ -201,8 +242,13 @@ impl GenerationLogService {
     }
 
     /// Find by ID with user info
-    pub async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<GenerationLogWithUser> {
+    pub async fn find_by_id(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+    ) -> Result<GenerationLogWithUser> {
         let log = Entity::find_by_id(id)
+            .filter(scope.owned(Column::Company))
             .one(db)
             .await?
             .ok_or_else(|| Error::NotFound)?;
@@ -210,4 +256,51 @@ impl GenerationLogService {
         let user = users::Entity::find_by_id(log.user_id).one(db).await.ok().flatten();
         Ok(GenerationLogWithUser::from_models(log, user))
     }
+
+    /// Where the source columns of a log ended up in its files
+    pub async fn lineage(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Option<Lineage>> {
+        let log = Entity::find_by_id(id)
+            .filter(scope.owned(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        Ok(LineageService::of_log(&log))
+    }
+
+    /// LLM configs a replay can be pinned to instead of the recorded one
+    pub async fn replay_targets(db: &DatabaseConnection, scope: &TenantScope) -> Result<Vec<ReplayTarget>> {
+        let configs = llm_configs::Entity::find()
+            .filter(scope.usable(llm_configs::Column::Company))
+            .order_by_asc(llm_configs::Column::Name)
+            .all(db)
+            .await?;
+
+        Ok(configs
+            .into_iter()
+            .map(|c| ReplayTarget {
+                id: c.id,
+                name: c.name,
+                provider: c.provider,
+                model_name: c.model_name,
+            })
+            .collect())
+    }
+
+    /// Re-run a logged generation (see [`ReplayService`])
+    pub async fn replay(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+        llm_config_id: Option<i32>,
+    ) -> Result<ReplayOutcome> {
+        let log = Entity::find_by_id(id)
+            .filter(scope.owned(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+
+        ReplayService::replay(db, &log, llm_config_id)
+            .await
+            .map_err(|e| Error::BadRequest(e.to_string()))
+    }
 }
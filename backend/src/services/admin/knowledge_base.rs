@@ -7,6 +7,9 @@ use sea_orm::{query::*, DatabaseConnection, JsonValue, PaginatorTrait};
 use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::knowledge_bases::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::content_guard;
+use crate::services::TenantScope;
 use crate::utils::{
     bool_from_str_or_bool, i32_from_str_or_number,
     optional_bool_from_str_or_bool, optional_i32_from_str_or_number,
@@ -55,6 +58,8 @@ pub struct CreateParams {
     pub token_estimate: Option<i32>,
     #[serde(default, deserialize_with = "bool_from_str_or_bool")]
     pub is_active: Option<bool>,
+    /// Tenant company (platform administrators only; blank = shared)
+    pub company: Option<String>,
 }
 
 /// Update parameters
@@ -104,6 +109,20 @@ pub struct KnowledgeEntryDto {
     pub token_estimate: Option<i32>,
     pub version: Option<i32>,
     pub is_active: Option<bool>,
+    /// Tenant company (None = shared)
+    pub company: Option<String>,
+    /// Generations whose prompt included this entry
+    pub usage_count: i32,
+    /// Of those, generations that validated without findings
+    pub clean_count: i32,
+    /// Total validation findings across those generations
+    pub finding_count: i32,
+    /// Ranking boost computed from the counts above
+    pub usage_boost: f32,
+    /// Content guard findings; the entry stays out of prompts until approved
+    pub quarantine_reason: Option<String>,
+    /// Administrator whose save was quarantined
+    pub quarantined_by: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -134,6 +153,16 @@ impl From<Model> for KnowledgeEntryDto {
             token_estimate: model.token_estimate,
             version: model.version,
             is_active: model.is_active,
+            company: model.company,
+            usage_count: model.usage_count,
+            clean_count: model.clean_count,
+            finding_count: model.finding_count,
+            usage_boost: crate::services::KnowledgeBaseService::usage_boost(
+                model.usage_count,
+                model.clean_count,
+            ),
+            quarantine_reason: model.quarantine_reason,
+            quarantined_by: model.quarantined_by,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
         }
@@ -144,8 +173,8 @@ pub struct KnowledgeBaseService;
 
 impl KnowledgeBaseService {
     /// Build query with filters and sorting
-    fn build_query(params: &QueryParams) -> sea_orm::Select<Entity> {
-        let mut condition = Condition::all();
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        let mut condition = scope.readable(Column::Company);
 
         // Keyword search (name or content)
         if let Some(keyword) = &params.keyword {
@@ -183,6 +212,7 @@ impl KnowledgeBaseService {
             Some("name") => query.order_by(Column::Name, order),
             Some("category") => query.order_by(Column::Category, order),
             Some("priority") => query.order_by(Column::Priority, order),
+            Some("usage") => query.order_by(Column::UsageCount, order),
             _ => query.order_by(Column::UpdatedAt, order),
         };
 
@@ -192,6 +222,7 @@ impl KnowledgeBaseService {
     /// Search with pagination
     pub async fn search(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         params: &QueryParams,
     ) -> Result<PageResponse<KnowledgeEntryDto>> {
         let page = params.page.unwrap_or(1).max(1);
@@ -201,7 +232,7 @@ impl KnowledgeBaseService {
             .min(MAX_PAGE_SIZE)
             .max(1);
 
-        let query = Self::build_query(params);
+        let query = Self::build_query(scope, params);
         let paginator = query.paginate(db, page_size);
 
         let total_items = paginator.num_items().await?;
@@ -220,17 +251,31 @@ impl KnowledgeBaseService {
     }
 
     /// Find by ID
-    pub async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<KnowledgeEntryDto> {
-        let model = Entity::find_by_id(id)
+    pub async fn find_by_id(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+    ) -> Result<KnowledgeEntryDto> {
+        Ok(KnowledgeEntryDto::from(Self::find_model(db, scope, id).await?))
+    }
+
+    /// Find an entry readable by the scope
+    async fn find_model(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
             .one(db)
             .await?
-            .ok_or_else(|| Error::NotFound)?;
-
-        Ok(KnowledgeEntryDto::from(model))
+            .ok_or_else(|| Error::NotFound)
     }
 
-    /// Create new entry
-    pub async fn create(db: &DatabaseConnection, params: CreateParams) -> Result<KnowledgeEntryDto> {
+    /// Create new entry (`actor` = saving administrator, audited and recorded
+    /// if quarantined)
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        params: CreateParams,
+    ) -> Result<KnowledgeEntryDto> {
         // Parse comma-separated tags
         let relevance_tags = params.relevance_tags.and_then(|tags_str| {
             let tags: Vec<String> = tags_str
@@ -259,25 +304,28 @@ impl KnowledgeBaseService {
             token_estimate: Set(params.token_estimate),
             version: Set(Some(1)),
             is_active: Set(params.is_active.or(Some(true))),
+            company: Set(scope.company_for_new(params.company)),
+            quarantined_by: Set(Some(actor.to_string())),
             ..Default::default()
         };
 
         let model = active_model.insert(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Create, None, Some(&model)).await?;
         Ok(KnowledgeEntryDto::from(model))
     }
 
     /// Update existing entry
     pub async fn update(
         db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
         id: i32,
         params: UpdateParams,
     ) -> Result<KnowledgeEntryDto> {
-        let model = Entity::find_by_id(id)
-            .one(db)
-            .await?
-            .ok_or_else(|| Error::NotFound)?;
+        let before = Self::find_model(db, scope, id).await?;
+        scope.ensure_writable(before.company.as_deref())?;
 
-        let mut active_model: ActiveModel = model.into();
+        let mut active_model: ActiveModel = before.clone().into();
 
         // Required fields
         if let Some(name) = params.name {
@@ -290,6 +338,7 @@ impl KnowledgeBaseService {
 
         if let Some(content) = params.content {
             active_model.content = Set(content);
+            active_model.quarantined_by = Set(Some(actor.to_string()));
         }
 
         // Optional fields - only update if Present (not Missing)
@@ -334,19 +383,46 @@ impl KnowledgeBaseService {
         }
 
         let updated = active_model.update(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Update, Some(&before), Some(&updated)).await?;
+        Ok(KnowledgeEntryDto::from(updated))
+    }
+
+    /// Release a quarantined entry into prompts (second administrator only)
+    pub async fn approve(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        approver: &str,
+        id: i32,
+    ) -> Result<KnowledgeEntryDto> {
+        let before = Self::find_model(db, scope, id).await?;
+        scope.ensure_writable(before.company.as_deref())?;
+
+        if before.quarantine_reason.is_none() {
+            return Err(Error::BadRequest("Entry is not quarantined".to_string()));
+        }
+        if !content_guard::can_approve(before.quarantined_by.as_deref(), approver) {
+            return Err(Error::BadRequest(
+                "Quarantined content must be approved by a different administrator".to_string(),
+            ));
+        }
+
+        let mut active_model: ActiveModel = before.clone().into();
+        active_model.quarantine_reason = Set(None);
+        active_model.quarantined_by = Set(None);
+        let updated = active_model.update(db).await?;
+        AuditLogService::record(db, approver, AuditAction::Approve, Some(&before), Some(&updated)).await?;
         Ok(KnowledgeEntryDto::from(updated))
     }
 
     /// Delete entry (soft delete)
-    pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<()> {
-        let model = Entity::find_by_id(id)
-            .one(db)
-            .await?
-            .ok_or_else(|| Error::NotFound)?;
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, actor: &str, id: i32) -> Result<()> {
+        let before = Self::find_model(db, scope, id).await?;
+        scope.ensure_writable(before.company.as_deref())?;
 
-        let mut active_model: ActiveModel = model.into();
+        let mut active_model: ActiveModel = before.clone().into();
         active_model.is_active = Set(Some(false));
-        active_model.update(db).await?;
+        let deactivated = active_model.update(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Delete, Some(&before), Some(&deactivated)).await?;
 
         Ok(())
     }
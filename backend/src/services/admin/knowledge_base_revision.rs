@@ -0,0 +1,128 @@
+//! Knowledge Base Revision Admin Service
+//!
+//! Revision history of a knowledge entry, each revision diffed against the
+//! previous one for the admin panel.
+
+use loco_rs::prelude::*;
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::knowledge_base_revisions::Model;
+
+/// Unchanged lines shown around each change
+const DIFF_CONTEXT_LINES: usize = 2;
+
+/// One line of a content diff
+#[derive(Debug, Serialize)]
+pub struct DiffLine {
+    /// `+` added, `-` removed, ` ` context, `…` skipped lines
+    pub tag: &'static str,
+    pub text: String,
+}
+
+/// Revision DTO for admin views
+#[derive(Debug, Serialize)]
+pub struct RevisionDto {
+    pub revision: i32,
+    pub created_at: String,
+    pub name: String,
+    /// Fields changed since the previous revision (empty for the first)
+    pub changed_fields: Vec<&'static str>,
+    /// Content diff against the previous revision
+    pub diff: Vec<DiffLine>,
+}
+
+pub struct KnowledgeRevisionService;
+
+impl KnowledgeRevisionService {
+    /// Revisions of an entry, newest first
+    pub async fn history(db: &DatabaseConnection, knowledge_base_id: i32) -> Result<Vec<RevisionDto>> {
+        let revisions = Model::history(db, knowledge_base_id).await?;
+
+        Ok(revisions
+            .iter()
+            .enumerate()
+            .map(|(i, rev)| {
+                let previous = revisions.get(i + 1);
+                RevisionDto {
+                    revision: rev.revision,
+                    created_at: rev.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    name: rev.name.clone(),
+                    changed_fields: previous.map(|p| Self::changed_fields(p, rev)).unwrap_or_default(),
+                    diff: previous.map(|p| Self::diff(&p.content, &rev.content)).unwrap_or_default(),
+                }
+            })
+            .collect())
+    }
+
+    fn changed_fields(old: &Model, new: &Model) -> Vec<&'static str> {
+        [
+            ("name", old.name != new.name),
+            ("category", old.category != new.category),
+            ("component", old.component != new.component),
+            ("section", old.section != new.section),
+            ("content", old.content != new.content),
+            ("relevance_tags", old.relevance_tags != new.relevance_tags),
+            ("priority", old.priority != new.priority),
+        ]
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(field, _)| field)
+        .collect()
+    }
+
+    /// Line diff with a few lines of context around each change
+    pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+        let diff = TextDiff::from_lines(old, new);
+        let mut lines = Vec::new();
+
+        for (i, group) in diff.grouped_ops(DIFF_CONTEXT_LINES).iter().enumerate() {
+            if i > 0 {
+                lines.push(DiffLine { tag: "…", text: String::new() });
+            }
+            for op in group {
+                for change in diff.iter_changes(op) {
+                    let tag = match change.tag() {
+                        ChangeTag::Insert => "+",
+                        ChangeTag::Delete => "-",
+                        ChangeTag::Equal => " ",
+                    };
+                    lines.push(DiffLine {
+                        tag,
+                        text: change.value().trim_end_matches('\n').to_string(),
+                    });
+                }
+            }
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_marks_changed_lines() {
+        let old = "a\nb\nc\n";
+        let new = "a\nB\nc\n";
+        let diff = KnowledgeRevisionService::diff(old, new);
+        let rendered: Vec<String> = diff.iter().map(|l| format!("{}{}", l.tag, l.text)).collect();
+        assert_eq!(rendered, vec![" a", "-b", "+B", " c"]);
+    }
+
+    #[test]
+    fn test_diff_skips_distant_unchanged_lines() {
+        let old: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let new = old.replace("line 2\n", "line two\n").replace("line 19\n", "line nineteen\n");
+        let diff = KnowledgeRevisionService::diff(&old, &new);
+        assert!(diff.iter().any(|l| l.tag == "…"));
+        assert!(!diff.iter().any(|l| l.text == "line 10"));
+    }
+
+    #[test]
+    fn test_identical_content_has_no_diff() {
+        assert!(KnowledgeRevisionService::diff("same\n", "same\n").is_empty());
+    }
+}
@@ -6,7 +6,10 @@ use loco_rs::prelude::*;
 use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
 use serde::{Deserialize, Serialize};
 
+use crate::llm::GgufInfo;
 use crate::models::_entities::llm_configs::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::TenantScope;
 use crate::utils::{
     bool_from_str_or_bool, f32_from_str_or_number, i32_from_str_or_number,
     optional_bool_from_str_or_bool, optional_f32_from_str_or_number, optional_i32_from_str_or_number,
@@ -16,6 +19,9 @@ use crate::utils::{
 const DEFAULT_PAGE_SIZE: u64 = 20;
 const MAX_PAGE_SIZE: u64 = 100;
 
+/// Highest fallback priority (configs are tried from 1 upwards)
+const MAX_FALLBACK_PRIORITY: i32 = 99;
+
 /// Query parameters for search with pagination
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct QueryParams {
@@ -73,6 +79,13 @@ pub struct CreateParams {
     /// Request timeout in seconds (NULL = use LLM_TIMEOUT_SECONDS env var, default 120)
     #[serde(default, deserialize_with = "i32_from_str_or_number")]
     pub timeout_secs: Option<i32>,
+
+    /// Tenant company (platform administrators only; blank = shared fallback)
+    pub company: Option<String>,
+
+    /// Fallback order behind the active config (blank = not a fallback)
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub fallback_priority: Option<i32>,
 }
 
 /// Update parameters
@@ -106,6 +119,29 @@ pub struct UpdateParams {
     /// Request timeout in seconds (NULL = use LLM_TIMEOUT_SECONDS env var, default 120)
     #[serde(default, deserialize_with = "optional_i32_from_str_or_number")]
     pub timeout_secs: OptionalField<i32>,
+
+    /// Fallback order behind the active config (blank = not a fallback)
+    #[serde(default, deserialize_with = "optional_i32_from_str_or_number")]
+    pub fallback_priority: OptionalField<i32>,
+}
+
+/// Query parameters for the local model advisor
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ModelAdviceParams {
+    pub model_path: Option<String>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub n_ctx: Option<i32>,
+}
+
+/// GGUF metadata with recommendations for the configured context
+#[derive(Debug, Serialize, Default)]
+pub struct ModelAdvice {
+    pub info: Option<GgufInfo>,
+    pub quantization: Option<String>,
+    pub parameters: Option<String>,
+    pub recommended_context: Option<u32>,
+    pub messages: Vec<String>,
+    pub error: Option<String>,
 }
 
 /// Paginated response
@@ -122,8 +158,9 @@ pub struct LlmConfigService;
 
 impl LlmConfigService {
     /// Build query with filters and sorting
-    fn build_query(params: &QueryParams) -> sea_orm::Select<Entity> {
-        let mut condition = Condition::all();
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        // Configs hold API keys: tenants only see their own
+        let mut condition = scope.owned(Column::Company);
 
         // Keyword search (name or provider)
         if let Some(keyword) = &params.keyword {
@@ -167,12 +204,13 @@ impl LlmConfigService {
     /// Search with pagination, filters, and sorting
     pub async fn search(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         params: &QueryParams,
     ) -> Result<PageResponse<Model>> {
         let page = params.page.unwrap_or(1).max(1);
         let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
 
-        let query = Self::build_query(params);
+        let query = Self::build_query(scope, params);
         let paginator = query.paginate(db, page_size);
 
         let total_items = paginator.num_items().await?;
@@ -189,15 +227,21 @@ impl LlmConfigService {
     }
 
     /// Find by ID
-    pub async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<Model> {
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
         Entity::find_by_id(id)
+            .filter(scope.owned(Column::Company))
             .one(db)
             .await?
             .ok_or_else(|| Error::NotFound)
     }
 
-    /// Create new LLM config
-    pub async fn create(db: &DatabaseConnection, params: CreateParams) -> Result<Model> {
+    /// Create new LLM config (`actor` = saving administrator, audited)
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        params: CreateParams,
+    ) -> Result<Model> {
         // Validation
         if params.name.trim().is_empty() {
             return Err(Error::BadRequest("Name is required".to_string()));
@@ -277,6 +321,10 @@ impl LlmConfigService {
             }
         }
 
+        if let Some(priority) = params.fallback_priority {
+            Self::validate_fallback_priority(priority)?;
+        }
+
         let item = ActiveModel {
             name: Set(params.name.trim().to_string()),
             provider: Set(params.provider.trim().to_string()),
@@ -290,21 +338,26 @@ impl LlmConfigService {
             n_ctx: Set(params.n_ctx),
             n_threads: Set(params.n_threads),
             timeout_secs: Set(params.timeout_secs),
+            company: Set(scope.company_for_new(params.company)),
+            fallback_priority: Set(params.fallback_priority),
             ..Default::default()
         };
 
         let item = item.insert(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Create, None, Some(&item)).await?;
         Ok(item)
     }
 
     /// Update existing LLM config
     pub async fn update(
         db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
         id: i32,
         params: UpdateParams,
     ) -> Result<Model> {
-        let item = Self::find_by_id(db, id).await?;
-        let mut item: ActiveModel = item.into();
+        let before = Self::find_by_id(db, scope, id).await?;
+        let mut item: ActiveModel = before.clone().into();
 
         // Required fields
         if let Some(name) = params.name {
@@ -381,44 +434,91 @@ impl LlmConfigService {
             }
             item.timeout_secs = Set(opt_value);
         }
+        if let OptionalField::Present(opt_value) = params.fallback_priority {
+            if let Some(priority) = opt_value {
+                Self::validate_fallback_priority(priority)?;
+            }
+            item.fallback_priority = Set(opt_value);
+        }
 
         let item = item.update(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Update, Some(&before), Some(&item)).await?;
         Ok(item)
     }
 
     /// Delete LLM config
-    pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<()> {
-        let item = Self::find_by_id(db, id).await?;
-        item.delete(db).await?;
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, actor: &str, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        item.clone().delete(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Delete, Some(&item), None).await?;
         Ok(())
     }
 
     /// Activate a specific LLM config and deactivate all others
     ///
-    /// This ensures only one LLM config is active at a time.
-    pub async fn activate(db: &DatabaseConnection, id: i32) -> Result<Model> {
+    /// This ensures only one LLM config is active at a time per company
+    /// (and one shared config). The deactivated configs are audited as updates.
+    pub async fn activate(db: &DatabaseConnection, scope: &TenantScope, actor: &str, id: i32) -> Result<Model> {
         // First, verify the item exists
-        let _item = Self::find_by_id(db, id).await?;
+        let item = Self::find_by_id(db, scope, id).await?;
 
-        // Deactivate all currently active configs
+        // Deactivate all currently active configs of the same company
         use sea_orm::QueryFilter;
+        let same_company = match &item.company {
+            Some(company) => Column::Company.eq(company.as_str()),
+            None => Column::Company.is_null(),
+        };
         let active_configs = Entity::find()
             .filter(Column::IsActive.eq(Some(true)))
+            .filter(same_company)
             .all(db)
             .await?;
 
         for config in active_configs {
-            let mut active_model: ActiveModel = config.into();
+            let mut active_model: ActiveModel = config.clone().into();
             active_model.is_active = Set(Some(false));
-            active_model.update(db).await?;
+            let deactivated = active_model.update(db).await?;
+            AuditLogService::record(db, actor, AuditAction::Update, Some(&config), Some(&deactivated)).await?;
         }
 
         // Now activate the specified config
-        let item = Self::find_by_id(db, id).await?;
-        let mut item: ActiveModel = item.into();
+        let before = Self::find_by_id(db, scope, id).await?;
+        let mut item: ActiveModel = before.clone().into();
         item.is_active = Set(Some(true));
         let item = item.update(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Activate, Some(&before), Some(&item)).await?;
 
         Ok(item)
     }
+
+    fn validate_fallback_priority(priority: i32) -> Result<()> {
+        if !(1..=MAX_FALLBACK_PRIORITY).contains(&priority) {
+            return Err(Error::BadRequest(format!(
+                "Fallback priority must be between 1 and {}",
+                MAX_FALLBACK_PRIORITY
+            )));
+        }
+        Ok(())
+    }
+
+    /// Read the GGUF header of a local model and advise on the context size
+    pub fn model_advice(params: &ModelAdviceParams) -> ModelAdvice {
+        let Some(path) = params.model_path.as_deref().map(str::trim).filter(|p| !p.is_empty()) else {
+            return ModelAdvice::default();
+        };
+        match GgufInfo::read(std::path::Path::new(path)) {
+            Ok(info) => ModelAdvice {
+                quantization: info.quantization().map(str::to_string),
+                parameters: info.parameter_label(),
+                recommended_context: info.recommended_context(),
+                messages: info.advise(params.n_ctx.map(|n| n.max(0) as u32)),
+                info: Some(info),
+                error: None,
+            },
+            Err(e) => ModelAdvice {
+                error: Some(e.to_string()),
+                ..Default::default()
+            },
+        }
+    }
 }
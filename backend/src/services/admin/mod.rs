@@ -9,6 +9,19 @@ pub mod llm_config;
 pub mod generation_log;
 pub mod user;
 pub mod knowledge_base;
+pub mod knowledge_base_revision;
+pub mod checklist_item;
+pub mod column_archetype;
+pub mod error_code_catalog;
+pub mod distillation;
+pub mod notification;
+pub mod queue;
+pub mod validation_plugin;
+pub mod audit_log;
+pub mod template_activation;
+pub mod canary;
+pub mod batch_schedule;
+pub mod retention_policy;
 
 pub use prompt_template::PromptTemplateService;
 pub use company_rule::CompanyRuleService;
@@ -16,3 +29,16 @@ pub use llm_config::LlmConfigService;
 pub use generation_log::GenerationLogService;
 pub use user::UserService;
 pub use knowledge_base::KnowledgeBaseService as AdminKnowledgeBaseService;
+pub use knowledge_base_revision::KnowledgeRevisionService;
+pub use checklist_item::ChecklistItemService;
+pub use column_archetype::ColumnArchetypeService;
+pub use error_code_catalog::ErrorCodeCatalogService;
+pub use distillation::DistillationAdminService;
+pub use notification::NotificationAdminService;
+pub use queue::QueueAdminService;
+pub use validation_plugin::ValidationPluginService;
+pub use audit_log::{AuditAction, AuditLogService};
+pub use template_activation::TemplateActivationService;
+pub use canary::CanaryAdminService;
+pub use batch_schedule::BatchScheduleAdminService;
+pub use retention_policy::RetentionPolicyAdminService;
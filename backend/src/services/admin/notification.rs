@@ -0,0 +1,154 @@
+//! Chat Notification Settings Service
+//!
+//! The Slack/Mattermost channel job events are posted to and the per-event
+//! toggles. Platform administrators only; the webhook URL is a credential
+//! and is never sent back to the browser.
+
+use loco_rs::prelude::*;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::models::notification_settings::{self, NOTIFICATION_PROVIDERS};
+use crate::services::{Notifier, TenantScope};
+use crate::utils::{bool_from_str_or_bool, i32_from_str_or_number};
+
+/// Longest spike window that can be configured (one day)
+const MAX_SPIKE_WINDOW_MINUTES: i32 = 1440;
+
+/// Settings form (an empty webhook URL keeps the stored one)
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SettingsParams {
+    pub provider: Option<String>,
+    pub webhook_url: Option<String>,
+    pub channel: Option<String>,
+    pub admin_base_url: Option<String>,
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub notify_completed: Option<bool>,
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub notify_failed: Option<bool>,
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub notify_failure_spike: Option<bool>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub spike_threshold: Option<i32>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub spike_window_minutes: Option<i32>,
+}
+
+/// Settings as shown in the admin panel
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsView {
+    pub provider: String,
+    /// Webhook host only (e.g. "hooks.slack.com"), none while unset
+    pub webhook_host: Option<String>,
+    pub channel: Option<String>,
+    pub admin_base_url: Option<String>,
+    pub notify_completed: bool,
+    pub notify_failed: bool,
+    pub notify_failure_spike: bool,
+    pub spike_threshold: i32,
+    pub spike_window_minutes: i32,
+}
+
+impl From<notification_settings::Model> for SettingsView {
+    fn from(m: notification_settings::Model) -> Self {
+        Self {
+            provider: m.provider,
+            webhook_host: m.webhook_url.as_deref().map(|url| {
+                url.split("://").nth(1).unwrap_or(url).split('/').next().unwrap_or_default().to_string()
+            }),
+            channel: m.channel,
+            admin_base_url: m.admin_base_url,
+            notify_completed: m.notify_completed,
+            notify_failed: m.notify_failed,
+            notify_failure_spike: m.notify_failure_spike,
+            spike_threshold: m.spike_threshold,
+            spike_window_minutes: m.spike_window_minutes,
+        }
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+pub struct NotificationAdminService;
+
+impl NotificationAdminService {
+    /// Current settings
+    pub async fn settings(db: &DatabaseConnection) -> SettingsView {
+        notification_settings::Model::current(db).await.into()
+    }
+
+    /// Change the settings (platform administrators only)
+    pub async fn update_settings(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: SettingsParams,
+    ) -> Result<SettingsView> {
+        Self::require_platform(scope)?;
+        let current = notification_settings::Model::current(db).await;
+
+        let provider = non_empty(params.provider).unwrap_or_else(|| current.provider.clone());
+        if !NOTIFICATION_PROVIDERS.contains(&provider.as_str()) {
+            return Err(Error::BadRequest(format!(
+                "Provider must be one of: {}",
+                NOTIFICATION_PROVIDERS.join(", ")
+            )));
+        }
+        let webhook_url = non_empty(params.webhook_url).or_else(|| current.webhook_url.clone());
+        if webhook_url.as_deref().is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://")) {
+            return Err(Error::BadRequest("Webhook URL must be an http(s) URL".to_string()));
+        }
+        let admin_base_url = non_empty(params.admin_base_url);
+        if admin_base_url.as_deref().is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://")) {
+            return Err(Error::BadRequest("Admin URL must be an http(s) URL".to_string()));
+        }
+        let spike_threshold = params.spike_threshold.unwrap_or(current.spike_threshold);
+        if spike_threshold < 1 {
+            return Err(Error::BadRequest("Spike threshold must be at least 1".to_string()));
+        }
+        let spike_window_minutes = params.spike_window_minutes.unwrap_or(current.spike_window_minutes);
+        if !(1..=MAX_SPIKE_WINDOW_MINUTES).contains(&spike_window_minutes) {
+            return Err(Error::BadRequest(format!(
+                "Spike window must be between 1 and {} minutes",
+                MAX_SPIKE_WINDOW_MINUTES
+            )));
+        }
+
+        let settings = notification_settings::Model::save(
+            db,
+            notification_settings::Model {
+                provider,
+                webhook_url,
+                channel: non_empty(params.channel),
+                admin_base_url,
+                notify_completed: params.notify_completed.unwrap_or(false),
+                notify_failed: params.notify_failed.unwrap_or(false),
+                notify_failure_spike: params.notify_failure_spike.unwrap_or(false),
+                spike_threshold,
+                spike_window_minutes,
+                ..current
+            },
+        )
+        .await?;
+        Ok(settings.into())
+    }
+
+    /// Post a test message to the configured channel (platform administrators only)
+    pub async fn send_test(db: &DatabaseConnection, scope: &TenantScope) -> Result<()> {
+        Self::require_platform(scope)?;
+        let settings = notification_settings::Model::current(db).await;
+        Notifier::send_test(&settings)
+            .await
+            .map_err(|e| Error::BadRequest(e.to_string()))
+    }
+
+    fn require_platform(scope: &TenantScope) -> Result<()> {
+        if *scope != TenantScope::Platform {
+            return Err(Error::Unauthorized(
+                "Notification settings can only be changed by platform administrators".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,29 @@
 //! Prompt Template Service
 //!
 //! Business logic for prompt template CRUD operations.
+//!
+//! Active templates go through two-person approval: editing the prompts of
+//! an active template saves them as a new inactive version and proposes its
+//! activation, so the running version only changes once another
+//! administrator approves.
 
 use loco_rs::prelude::*;
 use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
 use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::prompt_templates::{ActiveModel, Column, Entity, Model};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::admin::template_activation::{ProposeParams, TemplateActivationService};
+use crate::services::template_variables::{PlaygroundRequest, PlaygroundResult};
+use crate::services::{TemplateVariables, TenantScope};
 use crate::utils::{bool_from_str_or_bool, optional_bool_from_str_or_bool, OptionalField};
 
 const DEFAULT_PAGE_SIZE: u64 = 20;
 const MAX_PAGE_SIZE: u64 = 100;
 
+const ACTIVATION_NEEDS_APPROVAL: &str =
+    "Templates are activated through an activation proposal approved by another administrator";
+
 /// Query parameters for search with pagination
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct QueryParams {
@@ -48,6 +60,11 @@ pub struct CreateParams {
     pub user_prompt_template: String,
     #[serde(default, deserialize_with = "bool_from_str_or_bool")]
     pub is_active: Option<bool>,
+    /// File an activation proposal for the new template
+    #[serde(default, deserialize_with = "bool_from_str_or_bool")]
+    pub propose_activation: Option<bool>,
+    /// Tenant company (platform administrators only; blank = shared)
+    pub company: Option<String>,
 }
 
 /// Update parameters
@@ -66,6 +83,25 @@ pub struct UpdateParams {
     pub is_active: OptionalField<bool>,
 }
 
+/// Sample input of the template playground
+pub const PLAYGROUND_SAMPLE_INPUT: &str = r#"{
+  "type": "db_schema",
+  "table": "member",
+  "columns": [
+    { "name": "member_id", "column_type": "INTEGER", "nullable": false, "pk": true },
+    { "name": "member_name", "column_type": "VARCHAR(100)", "nullable": false, "pk": false, "comment": "회원명" }
+  ],
+  "primary_keys": ["member_id"]
+}"#;
+
+/// Template playground parameters (form fields)
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PlaygroundParams {
+    /// Generation input as JSON
+    pub input: String,
+    pub company_rules: Option<String>,
+}
+
 /// Paginated response
 #[derive(Debug, Serialize)]
 pub struct PageResponse<T> {
@@ -80,8 +116,8 @@ pub struct PromptTemplateService;
 
 impl PromptTemplateService {
     /// Build query with filters and sorting
-    fn build_query(params: &QueryParams) -> sea_orm::Select<Entity> {
-        let mut condition = Condition::all();
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        let mut condition = scope.readable(Column::Company);
 
         // Keyword search (name or product)
         if let Some(keyword) = &params.keyword {
@@ -125,12 +161,13 @@ impl PromptTemplateService {
     /// Search with pagination, filters, and sorting
     pub async fn search(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         params: &QueryParams,
     ) -> Result<PageResponse<Model>> {
         let page = params.page.unwrap_or(1).max(1);
         let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
 
-        let query = Self::build_query(params);
+        let query = Self::build_query(scope, params);
         let paginator = query.paginate(db, page_size);
 
         let total_items = paginator.num_items().await?;
@@ -147,15 +184,23 @@ impl PromptTemplateService {
     }
 
     /// Find by ID
-    pub async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<Model> {
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
         Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
             .one(db)
             .await?
             .ok_or_else(|| Error::NotFound)
     }
 
-    /// Create new prompt template
-    pub async fn create(db: &DatabaseConnection, params: CreateParams) -> Result<Model> {
+    /// Create new prompt template (`actor` = saving administrator, audited)
+    ///
+    /// Templates start inactive; activation needs an approved proposal.
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        params: CreateParams,
+    ) -> Result<Model> {
         // Validation
         if params.name.trim().is_empty() {
             return Err(Error::BadRequest("Name is required".to_string()));
@@ -166,6 +211,16 @@ impl PromptTemplateService {
         if params.system_prompt.trim().is_empty() {
             return Err(Error::BadRequest("System prompt is required".to_string()));
         }
+        if params.is_active == Some(true) {
+            return Err(Error::BadRequest(ACTIVATION_NEEDS_APPROVAL.to_string()));
+        }
+        TemplateVariables::validate(
+            params.product.trim(),
+            params.screen_type.as_deref(),
+            &params.system_prompt,
+            &params.user_prompt_template,
+        )
+            .map_err(|e| Error::BadRequest(e.to_string()))?;
 
         let item = ActiveModel {
             name: Set(params.name.trim().to_string()),
@@ -174,22 +229,55 @@ impl PromptTemplateService {
             system_prompt: Set(params.system_prompt),
             user_prompt_template: Set(params.user_prompt_template),
             version: Set(1),
-            is_active: Set(params.is_active),
+            is_active: Set(Some(false)),
+            company: Set(scope.company_for_new(params.company)),
             ..Default::default()
         };
 
         let item = item.insert(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Create, None, Some(&item)).await?;
+
+        if params.propose_activation == Some(true) {
+            TemplateActivationService::propose(db, scope, actor, item.id, ProposeParams::default()).await?;
+        }
         Ok(item)
     }
 
     /// Update existing prompt template
+    ///
+    /// Prompt edits of an active template are saved as a new version (see
+    /// [`Self::new_version`]), which is returned instead.
     pub async fn update(
         db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
         id: i32,
         params: UpdateParams,
     ) -> Result<Model> {
-        let item = Self::find_by_id(db, id).await?;
-        let mut item: ActiveModel = item.into();
+        let before = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(before.company.as_deref())?;
+        if params.is_active == OptionalField::Present(Some(true)) && before.is_active != Some(true) {
+            return Err(Error::BadRequest(ACTIVATION_NEEDS_APPROVAL.to_string()));
+        }
+        let screen_type = match &params.screen_type {
+            OptionalField::Present(screen_type) => screen_type.as_deref(),
+            _ => before.screen_type.as_deref(),
+        };
+        TemplateVariables::validate(
+            params.product.as_deref().unwrap_or(&before.product),
+            screen_type,
+            params.system_prompt.as_deref().unwrap_or(&before.system_prompt),
+            params.user_prompt_template.as_deref().unwrap_or(&before.user_prompt_template),
+        )
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+
+        let prompts_changed = params.system_prompt.as_ref().is_some_and(|p| *p != before.system_prompt)
+            || params.user_prompt_template.as_ref().is_some_and(|p| *p != before.user_prompt_template);
+        if before.is_active == Some(true) && prompts_changed {
+            return Self::new_version(db, scope, actor, &before, params).await;
+        }
+
+        let mut item: ActiveModel = before.clone().into();
 
         // Required fields
         if let Some(name) = params.name {
@@ -221,13 +309,97 @@ impl PromptTemplateService {
         item.version = Set(current_version + 1);
 
         let item = item.update(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Update, Some(&before), Some(&item)).await?;
+        Ok(item)
+    }
+
+    /// Save an edit of an active template as a new inactive version of its
+    /// slot and propose activating it; the active version stays untouched
+    async fn new_version(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        active: &Model,
+        params: UpdateParams,
+    ) -> Result<Model> {
+        let name = match params.name {
+            Some(name) if name.trim().is_empty() => {
+                return Err(Error::BadRequest("Name cannot be empty".to_string()));
+            }
+            Some(name) => name.trim().to_string(),
+            None => active.name.clone(),
+        };
+        let screen_type = match params.screen_type {
+            OptionalField::Present(screen_type) => screen_type,
+            _ => active.screen_type.clone(),
+        };
+        let product = params.product.unwrap_or_else(|| active.product.clone());
+
+        // Versions of the slot (product, screen type, company)
+        let slot = Condition::all()
+            .add(Column::Product.eq(product.as_str()))
+            .add(match &screen_type {
+                Some(screen_type) => Column::ScreenType.eq(screen_type.as_str()),
+                None => Column::ScreenType.is_null(),
+            })
+            .add(match &active.company {
+                Some(company) => Column::Company.eq(company.as_str()),
+                None => Column::Company.is_null(),
+            });
+        let latest = Entity::find()
+            .filter(slot)
+            .order_by_desc(Column::Version)
+            .one(db)
+            .await?
+            .map_or(active.version, |latest| latest.version.max(active.version));
+
+        let item = ActiveModel {
+            name: Set(name),
+            product: Set(product),
+            screen_type: Set(screen_type),
+            system_prompt: Set(params.system_prompt.unwrap_or_else(|| active.system_prompt.clone())),
+            user_prompt_template: Set(params
+                .user_prompt_template
+                .unwrap_or_else(|| active.user_prompt_template.clone())),
+            version: Set(latest + 1),
+            is_active: Set(Some(false)),
+            company: Set(active.company.clone()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        AuditLogService::record(db, actor, AuditAction::Create, None, Some(&item)).await?;
+
+        let proposal = ProposeParams {
+            note: Some(format!("Edit of active version {}", active.version)),
+            ..ProposeParams::default()
+        };
+        TemplateActivationService::propose(db, scope, actor, item.id, proposal).await?;
         Ok(item)
     }
 
+    /// Compile a sample input through a template (variable values and user prompt)
+    pub async fn playground(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+        params: PlaygroundParams,
+    ) -> Result<PlaygroundResult> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        let request = PlaygroundRequest {
+            input: serde_json::from_str(&params.input)
+                .map_err(|e| Error::BadRequest(format!("Invalid input JSON: {}", e)))?,
+            company_rules: params.company_rules.filter(|r| !r.trim().is_empty()),
+        };
+        TemplateVariables::playground(&item, &request).map_err(|e| Error::BadRequest(e.to_string()))
+    }
+
     /// Delete prompt template
-    pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<()> {
-        let item = Self::find_by_id(db, id).await?;
-        item.delete(db).await?;
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, actor: &str, id: i32) -> Result<()> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        scope.ensure_writable(item.company.as_deref())?;
+        item.clone().delete(db).await?;
+        AuditLogService::record(db, actor, AuditAction::Delete, Some(&item), None).await?;
         Ok(())
     }
 }
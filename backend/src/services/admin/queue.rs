@@ -0,0 +1,76 @@
+//! Job Queue Settings Service
+//!
+//! How workers share the generation queue: whether users or projects take
+//! turns and how many jobs one of them may have running. Platform
+//! administrators only; read by the job queue on every claim.
+
+use loco_rs::prelude::*;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::models::queue_settings::{self, QUEUE_FAIRNESS};
+use crate::services::TenantScope;
+use crate::utils::i32_from_str_or_number;
+use crate::workers::{JobQueueProcessor, QueueStats};
+
+/// Highest per-owner cap that can be configured
+const MAX_IN_FLIGHT: i32 = 100;
+
+/// Settings form
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SettingsParams {
+    pub fairness: Option<String>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub max_in_flight: Option<i32>,
+}
+
+pub struct QueueAdminService;
+
+impl QueueAdminService {
+    /// Current settings
+    pub async fn settings(db: &DatabaseConnection) -> queue_settings::Model {
+        queue_settings::Model::current(db).await
+    }
+
+    /// Jobs per status, shown above the settings
+    pub async fn stats(db: &DatabaseConnection) -> Result<QueueStats> {
+        JobQueueProcessor::get_queue_stats(db)
+            .await
+            .map_err(|e| Error::string(&e.to_string()))
+    }
+
+    /// Change the settings (platform administrators only)
+    pub async fn update_settings(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: SettingsParams,
+    ) -> Result<queue_settings::Model> {
+        if *scope != TenantScope::Platform {
+            return Err(Error::Unauthorized(
+                "Queue settings can only be changed by platform administrators".to_string(),
+            ));
+        }
+        let current = Self::settings(db).await;
+
+        let fairness = params
+            .fairness
+            .map(|f| f.trim().to_lowercase())
+            .filter(|f| !f.is_empty())
+            .unwrap_or(current.fairness);
+        if !QUEUE_FAIRNESS.contains(&fairness.as_str()) {
+            return Err(Error::BadRequest(format!(
+                "Fairness must be one of: {}",
+                QUEUE_FAIRNESS.join(", ")
+            )));
+        }
+        let max_in_flight = params.max_in_flight.unwrap_or(current.max_in_flight);
+        if !(0..=MAX_IN_FLIGHT).contains(&max_in_flight) {
+            return Err(Error::BadRequest(format!(
+                "Running jobs per owner must be between 0 (no cap) and {}",
+                MAX_IN_FLIGHT
+            )));
+        }
+
+        Ok(queue_settings::Model::save(db, &fairness, max_in_flight).await?)
+    }
+}
@@ -0,0 +1,124 @@
+//! Retention Policy Admin Service
+//!
+//! Artifact retention per user role and product. Policies are global and
+//! changed by platform administrators only; tenant administrators see them.
+
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection};
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::retention_policies::{ActiveModel, Column, Entity, Model};
+use crate::models::_entities::users;
+use crate::services::admin::user::normalize_role;
+use crate::services::TenantScope;
+use crate::utils::i32_from_str_or_number;
+
+/// Longest retention a policy may set (10 years)
+const MAX_RETENTION_DAYS: i32 = 3650;
+
+/// Policy form
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PolicyParams {
+    /// Blank = every role
+    pub role: Option<String>,
+    /// Blank = every product
+    pub product: Option<String>,
+    #[serde(default, deserialize_with = "i32_from_str_or_number")]
+    pub retention_days: Option<i32>,
+}
+
+pub struct RetentionPolicyAdminService;
+
+impl RetentionPolicyAdminService {
+    /// Every policy, catch-all first
+    pub async fn policies(db: &DatabaseConnection) -> Result<Vec<Model>> {
+        let items = Entity::find()
+            .order_by_asc(Column::Role)
+            .order_by_asc(Column::Product)
+            .all(db)
+            .await?;
+        Ok(items)
+    }
+
+    /// Roles assigned to users, for the form's suggestions
+    pub async fn roles(db: &DatabaseConnection) -> Result<Vec<String>> {
+        let roles: Vec<Option<String>> = users::Entity::find()
+            .select_only()
+            .column(users::Column::Role)
+            .filter(users::Column::Role.is_not_null())
+            .distinct()
+            .order_by_asc(users::Column::Role)
+            .into_tuple()
+            .all(db)
+            .await?;
+        Ok(roles.into_iter().flatten().collect())
+    }
+
+    /// Set the retention of a role / product pair (platform administrators only)
+    pub async fn save(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        params: PolicyParams,
+    ) -> Result<Model> {
+        Self::require_platform(scope)?;
+        let days = params
+            .retention_days
+            .filter(|days| (1..=MAX_RETENTION_DAYS).contains(days))
+            .ok_or_else(|| {
+                Error::BadRequest(format!("Retention must be between 1 and {} days", MAX_RETENTION_DAYS))
+            })?;
+        let role = normalize_role(params.role);
+        let product = params.product.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+
+        let stored = Entity::find()
+            .filter(match &role {
+                Some(role) => Column::Role.eq(role.as_str()),
+                None => Column::Role.is_null(),
+            })
+            .filter(match &product {
+                Some(product) => Column::Product.eq(product.as_str()),
+                None => Column::Product.is_null(),
+            })
+            .one(db)
+            .await?;
+
+        let item = match stored {
+            Some(stored) => {
+                let mut item: ActiveModel = stored.into();
+                item.retention_days = Set(days);
+                item.updated_by = Set(Some(actor.to_string()));
+                item.update(db).await?
+            }
+            None => {
+                ActiveModel {
+                    role: Set(role),
+                    product: Set(product),
+                    retention_days: Set(days),
+                    updated_by: Set(Some(actor.to_string())),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?
+            }
+        };
+        Ok(item)
+    }
+
+    /// Remove a policy (platform administrators only)
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        Self::require_platform(scope)?;
+        let item = Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error::NotFound)?;
+        item.delete(db).await?;
+        Ok(())
+    }
+
+    fn require_platform(scope: &TenantScope) -> Result<()> {
+        if *scope != TenantScope::Platform {
+            return Err(Error::Unauthorized(
+                "Retention policies can only be changed by platform administrators".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
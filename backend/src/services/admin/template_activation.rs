@@ -0,0 +1,263 @@
+//! Template Activation Service
+//!
+//! Two-person approval of prompt template activations: an administrator
+//! proposes an inactive template version, a different administrator approves
+//! or rejects it. Approved activations take effect at once or when their
+//! window opens (see [`TemplateRollout`]).
+
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use loco_rs::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{query::*, DatabaseConnection};
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::prompt_templates;
+use crate::models::_entities::template_activations::{ActiveModel, Column, Entity, Model};
+use crate::services::{content_guard, RolloutConfig, TemplateRollout, TenantScope};
+
+/// Most activations listed
+const LIST_LIMIT: u64 = 200;
+
+/// Statuses of proposals still awaiting activation
+const OPEN_STATUSES: [&str; 2] = ["pending", "scheduled"];
+
+/// Proposal parameters
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ProposeParams {
+    pub note: Option<String>,
+    /// Earliest activation (`YYYY-MM-DDTHH:MM`, server local time; blank = on approval)
+    pub window_start: Option<String>,
+    /// Latest activation (blank = no deadline)
+    pub window_end: Option<String>,
+}
+
+/// Activation with its template, for the admin list
+#[derive(Debug, Serialize)]
+pub struct ActivationRow {
+    pub item: Model,
+    pub template_name: String,
+    pub product: String,
+    pub screen_type: Option<String>,
+    pub version: i32,
+    pub previous_version: Option<i32>,
+    /// Warning rate since activation in percent (active activations only)
+    pub current_warning_pct: Option<i32>,
+    pub current_generations: Option<u64>,
+    /// Warning rate of the replaced version in percent
+    pub baseline_warning_pct: Option<i32>,
+}
+
+fn percent(rate: f32) -> i32 {
+    (rate * 100.0).round() as i32
+}
+
+pub struct TemplateActivationService;
+
+impl TemplateActivationService {
+    /// Recent activations readable by the scope, newest first
+    pub async fn list(db: &DatabaseConnection, scope: &TenantScope, status: Option<&str>) -> Result<Vec<ActivationRow>> {
+        let mut query = Entity::find()
+            .filter(scope.readable(Column::Company))
+            .order_by_desc(Column::Id)
+            .limit(LIST_LIMIT);
+        if let Some(status) = status.filter(|s| !s.is_empty()) {
+            query = query.filter(Column::Status.eq(status));
+        }
+
+        let mut rows = Vec::new();
+        for item in query.all(db).await? {
+            rows.push(Self::row(db, item).await?);
+        }
+        Ok(rows)
+    }
+
+    async fn row(db: &DatabaseConnection, item: Model) -> Result<ActivationRow> {
+        let template = prompt_templates::Entity::find_by_id(item.prompt_template_id)
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        let previous_version = match item.previous_template_id {
+            Some(id) => prompt_templates::Entity::find_by_id(id).one(db).await?.map(|p| p.version),
+            None => None,
+        };
+        let current = match (item.status.as_str(), item.activated_at) {
+            ("active", Some(activated_at)) => {
+                Some(TemplateRollout::warning_rate(db, &template, activated_at, Utc::now().into()).await?)
+            }
+            _ => None,
+        };
+
+        Ok(ActivationRow {
+            template_name: template.name,
+            product: template.product,
+            screen_type: template.screen_type,
+            version: template.version,
+            previous_version,
+            current_warning_pct: current.map(|c| percent(c.rate())),
+            current_generations: current.map(|c| c.generations),
+            baseline_warning_pct: item.baseline_warning_rate.map(percent),
+            item,
+        })
+    }
+
+    async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        let item = Entity::find_by_id(id)
+            .filter(scope.readable(Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        scope.ensure_writable(item.company.as_deref())?;
+        Ok(item)
+    }
+
+    /// Propose activating an inactive template
+    pub async fn propose(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        template_id: i32,
+        params: ProposeParams,
+    ) -> Result<ActivationRow> {
+        let template = prompt_templates::Entity::find_by_id(template_id)
+            .filter(scope.readable(prompt_templates::Column::Company))
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound)?;
+        scope.ensure_writable(template.company.as_deref())?;
+
+        if template.is_active == Some(true) {
+            return Err(Error::BadRequest("Template is already active".to_string()));
+        }
+        let open = Entity::find()
+            .filter(Column::PromptTemplateId.eq(template.id))
+            .filter(Column::Status.is_in(OPEN_STATUSES))
+            .one(db)
+            .await?;
+        if open.is_some() {
+            return Err(Error::BadRequest(
+                "An activation of this template is already awaiting approval".to_string(),
+            ));
+        }
+
+        let window_start = Self::parse_time(params.window_start.as_deref())?;
+        let window_end = Self::parse_time(params.window_end.as_deref())?;
+        if let Some(end) = window_end {
+            if end <= Utc::now() || window_start.is_some_and(|start| end <= start) {
+                return Err(Error::BadRequest(
+                    "Activation window must end in the future and after it starts".to_string(),
+                ));
+            }
+        }
+
+        let item = ActiveModel {
+            prompt_template_id: Set(template.id),
+            status: Set("pending".to_string()),
+            company: Set(template.company.clone()),
+            proposed_by: Set(actor.to_string()),
+            note: Set(params.note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())),
+            window_start: Set(window_start),
+            window_end: Set(window_end),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Self::row(db, item).await
+    }
+
+    /// `YYYY-MM-DDTHH:MM` (HTML datetime-local) in server local time
+    fn parse_time(raw: Option<&str>) -> Result<Option<DateTimeWithTimeZone>> {
+        let Some(raw) = raw.map(str::trim).filter(|t| !t.is_empty()) else {
+            return Ok(None);
+        };
+        let naive = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M")
+            .map_err(|_| Error::BadRequest(format!("Invalid time: {}", raw)))?;
+        let local = Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .ok_or_else(|| Error::BadRequest(format!("Invalid local time: {}", raw)))?;
+        Ok(Some(local.fixed_offset()))
+    }
+
+    /// Approve a pending proposal (a different administrator than the proposer)
+    pub async fn approve(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        approver: &str,
+        id: i32,
+        note: Option<String>,
+    ) -> Result<ActivationRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        if item.status != "pending" {
+            return Err(Error::BadRequest(format!("Activation is {}, not pending", item.status)));
+        }
+        if !content_guard::can_approve(Some(&item.proposed_by), approver) {
+            return Err(Error::BadRequest(
+                "Activations must be approved by a different administrator".to_string(),
+            ));
+        }
+        if item.window_end.is_some_and(|end| end <= Utc::now()) {
+            return Err(Error::BadRequest("Activation window has closed".to_string()));
+        }
+
+        let opens_now = item.window_start.is_none_or(|start| start <= Utc::now());
+        let mut item: ActiveModel = item.into();
+        item.status = Set("scheduled".to_string());
+        item.reviewed_by = Set(Some(approver.to_string()));
+        item.review_note = Set(note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()));
+        item.reviewed_at = Set(Some(Utc::now().into()));
+        let mut item = item.update(db).await?;
+
+        if opens_now {
+            item = TemplateRollout::activate(db, item, approver).await?;
+        }
+        Self::row(db, item).await
+    }
+
+    /// Reject a proposal before it takes effect (withdrawn when the proposer rejects it)
+    pub async fn reject(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        reviewer: &str,
+        id: i32,
+        note: Option<String>,
+    ) -> Result<ActivationRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        if !OPEN_STATUSES.contains(&item.status.as_str()) {
+            return Err(Error::BadRequest(format!("Activation is already {}", item.status)));
+        }
+
+        let withdrawn = item.proposed_by.eq_ignore_ascii_case(reviewer);
+        let mut item: ActiveModel = item.into();
+        item.status = Set(if withdrawn { "cancelled" } else { "rejected" }.to_string());
+        item.reviewed_by = Set(Some(reviewer.to_string()));
+        item.review_note = Set(note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()));
+        item.reviewed_at = Set(Some(Utc::now().into()));
+        let item = item.update(db).await?;
+        Self::row(db, item).await
+    }
+
+    /// Roll an active activation back to the previous version by hand
+    pub async fn rollback(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        actor: &str,
+        id: i32,
+        reason: Option<String>,
+    ) -> Result<ActivationRow> {
+        let item = Self::find_by_id(db, scope, id).await?;
+        if item.status != "active" {
+            return Err(Error::BadRequest(format!("Activation is {}, not active", item.status)));
+        }
+        let reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| format!("Rolled back by {}", actor));
+        let item = TemplateRollout::rollback(db, item, actor, &reason).await?;
+        Self::row(db, item).await
+    }
+
+    /// Rollback thresholds, shown on the activations page
+    pub fn config() -> RolloutConfig {
+        RolloutConfig::from_env()
+    }
+}
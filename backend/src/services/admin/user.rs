@@ -7,6 +7,8 @@ use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
 use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::users::{ActiveModel, Column, Entity, Model};
+use crate::services::TenantScope;
+use crate::utils::OptionalField;
 
 const DEFAULT_PAGE_SIZE: u64 = 20;
 const MAX_PAGE_SIZE: u64 = 100;
@@ -39,6 +41,10 @@ pub struct CreateParams {
     pub name: String,
     pub email: String,
     pub password: String,
+    /// Tenant company (platform administrators only; blank = platform user)
+    pub company: Option<String>,
+    /// License role for retention policies (platform administrators only)
+    pub role: Option<String>,
 }
 
 /// Update parameters
@@ -47,6 +53,12 @@ pub struct UpdateParams {
     pub name: Option<String>,
     pub email: Option<String>,
     pub password: Option<String>,
+    /// Tenant company (platform administrators only)
+    #[serde(default)]
+    pub company: OptionalField<String>,
+    /// License role for retention policies (platform administrators only)
+    #[serde(default)]
+    pub role: OptionalField<String>,
 }
 
 /// User response without sensitive fields
@@ -57,6 +69,8 @@ pub struct UserResponse {
     pub name: String,
     pub email: String,
     pub email_verified: bool,
+    pub company: Option<String>,
+    pub role: Option<String>,
     pub created_at: chrono::DateTime<chrono::FixedOffset>,
     pub updated_at: chrono::DateTime<chrono::FixedOffset>,
 }
@@ -69,6 +83,8 @@ impl From<Model> for UserResponse {
             name: model.name,
             email: model.email,
             email_verified: model.email_verified_at.is_some(),
+            company: model.company,
+            role: model.role,
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
@@ -85,12 +101,17 @@ pub struct PageResponse<T> {
     pub total_items: u64,
 }
 
+/// Trimmed, lowercase role; blank = no role
+pub fn normalize_role(role: Option<String>) -> Option<String> {
+    role.map(|r| r.trim().to_lowercase()).filter(|r| !r.is_empty())
+}
+
 pub struct UserService;
 
 impl UserService {
     /// Build query with filters and sorting
-    fn build_query(params: &QueryParams) -> sea_orm::Select<Entity> {
-        let mut condition = Condition::all();
+    fn build_query(scope: &TenantScope, params: &QueryParams) -> sea_orm::Select<Entity> {
+        let mut condition = scope.owned(Column::Company);
 
         // Keyword search (name or email)
         if let Some(keyword) = &params.keyword {
@@ -133,12 +154,13 @@ impl UserService {
     /// Search with pagination, filters, and sorting
     pub async fn search(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         params: &QueryParams,
     ) -> Result<PageResponse<UserResponse>> {
         let page = params.page.unwrap_or(1).max(1);
         let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
 
-        let query = Self::build_query(params);
+        let query = Self::build_query(scope, params);
         let paginator = query.paginate(db, page_size);
 
         let total_items = paginator.num_items().await?;
@@ -160,16 +182,25 @@ impl UserService {
     }
 
     /// Find by ID
-    pub async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<UserResponse> {
-        let user = Entity::find_by_id(id)
+    pub async fn find_by_id(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<UserResponse> {
+        Ok(UserResponse::from(Self::find_model(db, scope, id).await?))
+    }
+
+    /// Find a user visible to the scope (tenants only see their own company)
+    async fn find_model(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<Model> {
+        Entity::find_by_id(id)
+            .filter(scope.owned(Column::Company))
             .one(db)
             .await?
-            .ok_or_else(|| Error::NotFound)?;
-        Ok(UserResponse::from(user))
+            .ok_or_else(|| Error::NotFound)
     }
 
     /// Create new user
-    pub async fn create(db: &DatabaseConnection, params: CreateParams) -> Result<UserResponse> {
+    pub async fn create(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: CreateParams,
+    ) -> Result<UserResponse> {
         use loco_rs::hash;
 
         // Validation
@@ -203,6 +234,8 @@ impl UserService {
             name: Set(params.name.trim().to_string()),
             email: Set(params.email.trim().to_lowercase()),
             password: Set(password_hash),
+            company: Set(scope.company_for_new(params.company)),
+            role: Set(if *scope == TenantScope::Platform { normalize_role(params.role) } else { None }),
             ..Default::default()
         };
 
@@ -213,17 +246,33 @@ impl UserService {
     /// Update existing user
     pub async fn update(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         id: i32,
         params: UpdateParams,
     ) -> Result<UserResponse> {
         use loco_rs::hash;
 
-        let user = Entity::find_by_id(id)
-            .one(db)
-            .await?
-            .ok_or_else(|| Error::NotFound)?;
+        let user = Self::find_model(db, scope, id).await?;
         let mut user: ActiveModel = user.into();
 
+        if let OptionalField::Present(company) = params.company {
+            if *scope != TenantScope::Platform {
+                return Err(Error::Unauthorized(
+                    "Only platform administrators can change a user's company".to_string(),
+                ));
+            }
+            user.company = Set(TenantScope::new(company.as_deref()).company().map(str::to_string));
+        }
+
+        if let OptionalField::Present(role) = params.role {
+            if *scope != TenantScope::Platform {
+                return Err(Error::Unauthorized(
+                    "Only platform administrators can change a user's role".to_string(),
+                ));
+            }
+            user.role = Set(normalize_role(role));
+        }
+
         if let Some(name) = params.name {
             if name.trim().is_empty() {
                 return Err(Error::BadRequest("Name cannot be empty".to_string()));
@@ -263,12 +312,14 @@ impl UserService {
     }
 
     /// Delete user
-    pub async fn delete(db: &DatabaseConnection, id: i32, current_user_pid: &str) -> Result<()> {
+    pub async fn delete(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        id: i32,
+        current_user_pid: &str,
+    ) -> Result<()> {
         // Find the user to delete
-        let user = Entity::find_by_id(id)
-            .one(db)
-            .await?
-            .ok_or_else(|| Error::NotFound)?;
+        let user = Self::find_model(db, scope, id).await?;
 
         // Prevent self-deletion (compare pids)
         if user.pid.to_string() == current_user_pid {
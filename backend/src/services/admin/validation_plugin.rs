@@ -0,0 +1,163 @@
+//! Validation Plugin Admin Service
+//!
+//! Upload, enable/disable and delete the customer WASM checks run by the
+//! `WasmPlugins` pipeline pass. Platform administrators only: a plugin runs
+//! on every tenant's generations.
+
+use loco_rs::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{DatabaseConnection, QueryOrder};
+use serde::Serialize;
+
+use crate::models::_entities::validation_plugins::{ActiveModel, Column, Entity, Model};
+use crate::services::wasm_plugin::sha256_hex;
+use crate::services::{TenantScope, WasmPlugin};
+
+/// Largest module that can be uploaded
+pub const MAX_PLUGIN_BYTES: usize = 10 * 1024 * 1024;
+
+/// Plugin as shown in the admin panel (without the module)
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginRow {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// First 12 hex digits of the module's SHA-256
+    pub sha256_short: String,
+    pub size_bytes: i32,
+    pub enabled: bool,
+    pub uploaded_by: Option<String>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl From<Model> for PluginRow {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            description: m.description,
+            sha256_short: m.sha256.chars().take(12).collect(),
+            size_bytes: m.size_bytes,
+            enabled: m.enabled,
+            uploaded_by: m.uploaded_by,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+/// An uploaded module
+#[derive(Debug)]
+pub struct UploadParams {
+    pub name: String,
+    pub description: Option<String>,
+    pub wasm: Vec<u8>,
+    /// Replace the module of an existing plugin with the same name
+    pub replace: bool,
+}
+
+pub struct ValidationPluginService;
+
+impl ValidationPluginService {
+    /// All plugins, in the order they run
+    pub async fn list(db: &DatabaseConnection) -> Result<Vec<PluginRow>> {
+        let plugins = Entity::find().order_by_asc(Column::Id).all(db).await?;
+        Ok(plugins.into_iter().map(PluginRow::from).collect())
+    }
+
+    /// Store a module after checking it compiles and implements the plugin
+    /// interface (platform administrators only)
+    pub async fn upload(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        params: UploadParams,
+        uploaded_by: &str,
+    ) -> Result<PluginRow> {
+        Self::require_platform(scope)?;
+
+        let name = params.name.trim().to_string();
+        if name.is_empty()
+            || name.len() > 64
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::BadRequest(
+                "Plugin name must be 1-64 letters, digits, '-' or '_'".to_string(),
+            ));
+        }
+        if params.wasm.is_empty() {
+            return Err(Error::BadRequest("No module uploaded".to_string()));
+        }
+        if params.wasm.len() > MAX_PLUGIN_BYTES {
+            return Err(Error::BadRequest(format!(
+                "Module is larger than {} MB",
+                MAX_PLUGIN_BYTES / 1024 / 1024
+            )));
+        }
+        WasmPlugin::compile(&name, &params.wasm).map_err(Error::BadRequest)?;
+
+        let description = params.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        let sha256 = sha256_hex(&params.wasm);
+        let size_bytes = params.wasm.len() as i32;
+
+        let existing = Model::find_by_name(db, &name).await?;
+        let plugin = match existing {
+            Some(_) if !params.replace => {
+                return Err(Error::BadRequest(format!("A plugin named '{}' already exists", name)));
+            }
+            Some(existing) => {
+                let mut plugin = existing.into_active_model();
+                plugin.description = Set(description);
+                plugin.wasm = Set(params.wasm);
+                plugin.sha256 = Set(sha256);
+                plugin.size_bytes = Set(size_bytes);
+                plugin.uploaded_by = Set(Some(uploaded_by.to_string()));
+                plugin.update(db).await?
+            }
+            None => {
+                ActiveModel {
+                    name: Set(name),
+                    description: Set(description),
+                    wasm: Set(params.wasm),
+                    sha256: Set(sha256),
+                    size_bytes: Set(size_bytes),
+                    enabled: Set(true),
+                    uploaded_by: Set(Some(uploaded_by.to_string())),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?
+            }
+        };
+        Ok(plugin.into())
+    }
+
+    /// Enable or disable a plugin (platform administrators only)
+    pub async fn toggle(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<PluginRow> {
+        Self::require_platform(scope)?;
+        let plugin = Self::find_by_id(db, id).await?;
+        let enabled = !plugin.enabled;
+        let mut plugin = plugin.into_active_model();
+        plugin.enabled = Set(enabled);
+        Ok(plugin.update(db).await?.into())
+    }
+
+    /// Delete a plugin (platform administrators only)
+    pub async fn delete(db: &DatabaseConnection, scope: &TenantScope, id: i32) -> Result<()> {
+        Self::require_platform(scope)?;
+        let plugin = Self::find_by_id(db, id).await?;
+        plugin.delete(db).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(db: &DatabaseConnection, id: i32) -> Result<Model> {
+        Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error::NotFound)
+    }
+
+    fn require_platform(scope: &TenantScope) -> Result<()> {
+        if *scope != TenantScope::Platform {
+            return Err(Error::Unauthorized(
+                "Validation plugins can only be managed by platform administrators".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
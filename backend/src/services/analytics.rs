@@ -2,7 +2,9 @@
 //!
 //! Provides statistics and analytics for generation requests.
 
-use chrono::{DateTime, Duration, FixedOffset, Utc};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Utc};
 use loco_rs::prelude::*;
 use sea_orm::{
     query::*, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
@@ -11,6 +13,10 @@ use sea_orm::{
 use serde::Serialize;
 
 use crate::models::_entities::generation_logs::{Column, Entity};
+use crate::services::pipeline::prevalidator::{parse_abort_note, ABORT_NOTE_PREFIX};
+use crate::services::pipeline::WarningCode;
+use crate::services::sla::{SlaConfig, SlaReport};
+use crate::services::TenantScope;
 
 /// Generation statistics
 #[derive(Debug, Serialize)]
@@ -25,6 +31,8 @@ pub struct GenerationStats {
     pub success_rate: f32,
     /// Average generation time in milliseconds
     pub avg_generation_time_ms: f32,
+    /// Average artifact quality score (0-100) of scored generations
+    pub avg_quality_score: f32,
     /// Requests in the last 24 hours
     pub requests_last_24h: u64,
     /// Requests in the last 7 days
@@ -56,7 +64,67 @@ pub struct DashboardAnalytics {
     pub by_input_type: Vec<CategoryStats>,
     pub by_status: Vec<CategoryStats>,
     pub requests_by_day: Vec<TimeSeriesPoint>,
+    /// Average quality score per day (0 on days without scored generations)
+    pub quality_by_day: Vec<TimeSeriesPoint>,
     pub recent_activity: RecentActivity,
+    /// SLA compliance of queued jobs over the last 7 days
+    pub sla: SlaReport,
+    /// Early stream aborts over the last 7 days
+    pub stream_aborts: StreamAbortStats,
+}
+
+/// Streamed responses aborted early by prevalidation
+#[derive(Debug, Serialize)]
+pub struct StreamAbortStats {
+    /// Aborted streams (first attempts and retries)
+    pub aborts: u64,
+    /// Generations with at least one aborted stream
+    pub affected_generations: u64,
+    /// Share of all generations with an abort (0-100)
+    pub abort_rate: f32,
+    /// Average characters received before an abort
+    pub avg_chars_at_abort: f32,
+    pub by_reason: Vec<CategoryStats>,
+}
+
+/// Warning codes by template version of one product
+#[derive(Debug, Serialize)]
+pub struct WarningHeatmap {
+    pub product: String,
+    /// Products with generations in the window, for the product switcher
+    pub products: Vec<String>,
+    pub days: i64,
+    /// Columns, oldest version first
+    pub versions: Vec<VersionColumn>,
+    /// Codes seen in at least one version
+    pub rows: Vec<HeatmapRow>,
+}
+
+/// Template version and the number of generations it produced
+#[derive(Debug, Serialize)]
+pub struct VersionColumn {
+    pub version: i32,
+    pub generations: u64,
+}
+
+/// One warning code across template versions
+#[derive(Debug, Serialize)]
+pub struct HeatmapRow {
+    pub code: WarningCode,
+    pub label: &'static str,
+    /// One cell per entry of [`WarningHeatmap::versions`]
+    pub cells: Vec<HeatmapCell>,
+}
+
+/// Generations of one template version with a warning code
+#[derive(Debug, Serialize)]
+pub struct HeatmapCell {
+    pub count: u64,
+    /// Share of the version's generations (0-100)
+    pub rate: f32,
+    /// Relative change of `rate` against the previous version, in percent
+    /// (`None` for the first version or when the previous rate was 0)
+    pub change: Option<f32>,
 }
 
 /// Recent activity summary
@@ -69,14 +137,25 @@ pub struct RecentActivity {
 pub struct AnalyticsService;
 
 impl AnalyticsService {
+    /// Generation logs visible to the scope (tenants only see their own)
+    fn logs(scope: &TenantScope) -> Select<Entity> {
+        Entity::find().filter(scope.owned(Column::Company))
+    }
+
     /// Get complete dashboard analytics
-    pub async fn get_dashboard_analytics(db: &DatabaseConnection) -> Result<DashboardAnalytics> {
-        let generation_stats = Self::get_generation_stats(db).await?;
-        let by_product = Self::get_stats_by_product(db).await?;
-        let by_input_type = Self::get_stats_by_input_type(db).await?;
-        let by_status = Self::get_stats_by_status(db).await?;
-        let requests_by_day = Self::get_requests_by_day(db, 7).await?;
-        let recent_activity = Self::get_recent_activity(db).await?;
+    pub async fn get_dashboard_analytics(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<DashboardAnalytics> {
+        let generation_stats = Self::get_generation_stats(db, scope).await?;
+        let by_product = Self::get_stats_by_product(db, scope).await?;
+        let by_input_type = Self::get_stats_by_input_type(db, scope).await?;
+        let by_status = Self::get_stats_by_status(db, scope).await?;
+        let requests_by_day = Self::get_requests_by_day(db, scope, 7).await?;
+        let quality_by_day = Self::get_quality_by_day(db, scope, 7).await?;
+        let recent_activity = Self::get_recent_activity(db, scope).await?;
+        let sla = Self::get_sla_report(db, scope, 7).await?;
+        let stream_aborts = Self::get_stream_abort_stats(db, scope, 7).await?;
 
         Ok(DashboardAnalytics {
             generation_stats,
@@ -84,21 +163,27 @@ impl AnalyticsService {
             by_input_type,
             by_status,
             requests_by_day,
+            quality_by_day,
             recent_activity,
+            sla,
+            stream_aborts,
         })
     }
 
     /// Get overall generation statistics
-    pub async fn get_generation_stats(db: &DatabaseConnection) -> Result<GenerationStats> {
-        let total_requests = Entity::find().count(db).await.unwrap_or(0);
+    pub async fn get_generation_stats(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<GenerationStats> {
+        let total_requests = Self::logs(scope).count(db).await.unwrap_or(0);
 
-        let success_count = Entity::find()
+        let success_count = Self::logs(scope)
             .filter(Column::Status.eq("success"))
             .count(db)
             .await
             .unwrap_or(0);
 
-        let failure_count = Entity::find()
+        let failure_count = Self::logs(scope)
             .filter(Column::Status.eq("error"))
             .count(db)
             .await
@@ -111,7 +196,8 @@ impl AnalyticsService {
         };
 
         // Calculate average generation time
-        let avg_generation_time_ms = Self::calculate_avg_generation_time(db).await?;
+        let avg_generation_time_ms = Self::calculate_avg_generation_time(db, scope).await?;
+        let avg_quality_score = Self::average(&Self::quality_scores(db, scope, None).await?);
 
         // Time-based counts
         let now = Utc::now();
@@ -119,19 +205,19 @@ impl AnalyticsService {
         let last_7d = now - Duration::days(7);
         let last_30d = now - Duration::days(30);
 
-        let requests_last_24h = Entity::find()
+        let requests_last_24h = Self::logs(scope)
             .filter(Column::CreatedAt.gte(last_24h))
             .count(db)
             .await
             .unwrap_or(0);
 
-        let requests_last_7d = Entity::find()
+        let requests_last_7d = Self::logs(scope)
             .filter(Column::CreatedAt.gte(last_7d))
             .count(db)
             .await
             .unwrap_or(0);
 
-        let requests_last_30d = Entity::find()
+        let requests_last_30d = Self::logs(scope)
             .filter(Column::CreatedAt.gte(last_30d))
             .count(db)
             .await
@@ -143,16 +229,20 @@ impl AnalyticsService {
             failure_count,
             success_rate,
             avg_generation_time_ms,
+            avg_quality_score,
             requests_last_24h,
             requests_last_7d,
             requests_last_30d,
         })
     }
 
-    async fn calculate_avg_generation_time(db: &DatabaseConnection) -> Result<f32> {
+    async fn calculate_avg_generation_time(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<f32> {
         // Get all generation times and calculate average in Rust
         // (More portable than DB-specific AVG function)
-        let logs = Entity::find()
+        let logs = Self::logs(scope)
             .filter(Column::GenerationTimeMs.is_not_null())
             .select_only()
             .column(Column::GenerationTimeMs)
@@ -174,31 +264,70 @@ impl AnalyticsService {
         }
     }
 
+    /// Quality scores of scored generations, optionally within `[start, end]`
+    async fn quality_scores(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        range: Option<(NaiveDateTime, NaiveDateTime)>,
+    ) -> Result<Vec<i32>> {
+        let mut query = Self::logs(scope).filter(Column::QualityScore.is_not_null());
+        if let Some((start, end)) = range {
+            query = query
+                .filter(Column::CreatedAt.gte(start))
+                .filter(Column::CreatedAt.lte(end));
+        }
+        let scores = query
+            .select_only()
+            .column(Column::QualityScore)
+            .into_tuple::<i32>()
+            .all(db)
+            .await?;
+        Ok(scores)
+    }
+
+    fn average(scores: &[i32]) -> f32 {
+        if scores.is_empty() {
+            0.0
+        } else {
+            scores.iter().map(|s| *s as i64).sum::<i64>() as f32 / scores.len() as f32
+        }
+    }
+
     /// Get statistics grouped by product
-    pub async fn get_stats_by_product(db: &DatabaseConnection) -> Result<Vec<CategoryStats>> {
-        Self::get_category_stats(db, Column::Product).await
+    pub async fn get_stats_by_product(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<Vec<CategoryStats>> {
+        Self::get_category_stats(db, scope, Column::Product).await
     }
 
     /// Get statistics grouped by input type
-    pub async fn get_stats_by_input_type(db: &DatabaseConnection) -> Result<Vec<CategoryStats>> {
-        Self::get_category_stats(db, Column::InputType).await
+    pub async fn get_stats_by_input_type(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<Vec<CategoryStats>> {
+        Self::get_category_stats(db, scope, Column::InputType).await
     }
 
     /// Get statistics grouped by status
-    pub async fn get_stats_by_status(db: &DatabaseConnection) -> Result<Vec<CategoryStats>> {
-        Self::get_category_stats(db, Column::Status).await
+    pub async fn get_stats_by_status(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<Vec<CategoryStats>> {
+        Self::get_category_stats(db, scope, Column::Status).await
     }
 
     async fn get_category_stats(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         column: Column,
     ) -> Result<Vec<CategoryStats>> {
         // Get total count
-        let total = Entity::find().count(db).await.unwrap_or(0) as f32;
+        let total = Self::logs(scope).count(db).await.unwrap_or(0) as f32;
 
         // Get all records and group in Rust for simplicity
         // Use into_tuple to only select the one column we need
-        let logs: Vec<String> = Entity::find()
+        let logs: Vec<String> = Self::logs(scope)
             .select_only()
             .column(column)
             .into_tuple::<String>()
@@ -236,6 +365,7 @@ impl AnalyticsService {
     /// Get requests per day for the last N days
     pub async fn get_requests_by_day(
         db: &DatabaseConnection,
+        scope: &TenantScope,
         days: i64,
     ) -> Result<Vec<TimeSeriesPoint>> {
         let now = Utc::now();
@@ -251,7 +381,7 @@ impl AnalyticsService {
                 .and_hms_opt(23, 59, 59)
                 .unwrap();
 
-            let count = Entity::find()
+            let count = Self::logs(scope)
                 .filter(Column::CreatedAt.gte(day_start))
                 .filter(Column::CreatedAt.lte(day_end))
                 .count(db)
@@ -269,10 +399,39 @@ impl AnalyticsService {
         Ok(results)
     }
 
+    /// Get the average quality score per day for the last N days
+    pub async fn get_quality_by_day(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        days: i64,
+    ) -> Result<Vec<TimeSeriesPoint>> {
+        let now = Utc::now();
+        let mut results = Vec::new();
+
+        for i in (0..days).rev() {
+            let day = (now - Duration::days(i)).date_naive();
+            let range = (
+                day.and_hms_opt(0, 0, 0).unwrap(),
+                day.and_hms_opt(23, 59, 59).unwrap(),
+            );
+            let scores = Self::quality_scores(db, scope, Some(range)).await?;
+
+            results.push(TimeSeriesPoint {
+                label: day.format("%m/%d").to_string(),
+                value: Self::average(&scores).round() as u64,
+            });
+        }
+
+        Ok(results)
+    }
+
     /// Get recent activity summary
-    pub async fn get_recent_activity(db: &DatabaseConnection) -> Result<RecentActivity> {
+    pub async fn get_recent_activity(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+    ) -> Result<RecentActivity> {
         // Get the most recent generation
-        let last_log = Entity::find()
+        let last_log = Self::logs(scope)
             .order_by_desc(Column::CreatedAt)
             .one(db)
             .await?;
@@ -283,7 +442,7 @@ impl AnalyticsService {
         let now = Utc::now();
         let last_24h = now - Duration::hours(24);
 
-        let logs = Entity::find()
+        let logs = Self::logs(scope)
             .filter(Column::CreatedAt.gte(last_24h))
             .select_only()
             .column(Column::UserId)
@@ -301,4 +460,168 @@ impl AnalyticsService {
             active_users_24h,
         })
     }
+
+    /// Early stream aborts of generations in the last `days` days
+    pub async fn get_stream_abort_stats(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        days: i64,
+    ) -> Result<StreamAbortStats> {
+        let since = Utc::now() - Duration::days(days);
+        let total = Self::logs(scope)
+            .filter(Column::CreatedAt.gte(since))
+            .count(db)
+            .await
+            .unwrap_or(0);
+
+        let warnings = Self::logs(scope)
+            .filter(Column::CreatedAt.gte(since))
+            .filter(Column::Warnings.contains(ABORT_NOTE_PREFIX))
+            .select_only()
+            .column(Column::Warnings)
+            .into_tuple::<Option<String>>()
+            .all(db)
+            .await?;
+
+        let mut affected_generations = 0u64;
+        let mut chars = Vec::new();
+        let mut reasons: HashMap<String, u64> = HashMap::new();
+        for json in warnings.into_iter().flatten() {
+            let notes: Vec<String> = serde_json::from_str(&json).unwrap_or_default();
+            let aborts: Vec<(usize, &str)> = notes.iter().filter_map(|n| parse_abort_note(n)).collect();
+            if aborts.is_empty() {
+                continue;
+            }
+            affected_generations += 1;
+            for (received, reason) in aborts {
+                chars.push(received);
+                // "code block never closed (truncated response)" -> "code block never closed"
+                let reason = reason.split(" (").next().unwrap_or(reason);
+                *reasons.entry(reason.to_string()).or_default() += 1;
+            }
+        }
+
+        let aborts = chars.len() as u64;
+        let mut by_reason: Vec<CategoryStats> = reasons
+            .into_iter()
+            .map(|(category, count)| CategoryStats {
+                category,
+                count,
+                percentage: count as f32 / aborts as f32 * 100.0,
+            })
+            .collect();
+        by_reason.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.category.cmp(&b.category)));
+
+        Ok(StreamAbortStats {
+            aborts,
+            affected_generations,
+            abort_rate: if total > 0 {
+                affected_generations as f32 / total as f32 * 100.0
+            } else {
+                0.0
+            },
+            avg_chars_at_abort: if aborts > 0 {
+                chars.iter().sum::<usize>() as f32 / aborts as f32
+            } else {
+                0.0
+            },
+            by_reason,
+        })
+    }
+
+    /// SLA compliance of jobs completed in the last `days` days
+    pub async fn get_sla_report(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        days: i64,
+    ) -> Result<SlaReport> {
+        let jobs = Self::logs(scope)
+            .filter(Column::Status.eq("completed"))
+            .filter(Column::QueuedAt.is_not_null())
+            .filter(Column::CompletedAt.gte(Utc::now() - Duration::days(days)))
+            .all(db)
+            .await?;
+
+        Ok(SlaReport::from_jobs(SlaConfig::from_env(), &jobs))
+    }
+
+    /// Warning codes per template version of `product` over the last `days` days
+    pub async fn get_warning_heatmap(
+        db: &DatabaseConnection,
+        scope: &TenantScope,
+        product: &str,
+        days: i64,
+    ) -> Result<WarningHeatmap> {
+        let since = Utc::now() - Duration::days(days);
+
+        let mut products: Vec<String> = Self::logs(scope)
+            .filter(Column::CreatedAt.gte(since))
+            .select_only()
+            .column(Column::Product)
+            .distinct()
+            .into_tuple::<String>()
+            .all(db)
+            .await?;
+        products.sort();
+
+        let logs = Self::logs(scope)
+            .filter(Column::CreatedAt.gte(since))
+            .filter(Column::Product.eq(product))
+            .select_only()
+            .column(Column::TemplateVersion)
+            .column(Column::Warnings)
+            .into_tuple::<(i32, Option<String>)>()
+            .all(db)
+            .await?;
+
+        // version -> (generations, generations per code)
+        let mut by_version: std::collections::BTreeMap<i32, (u64, HashMap<WarningCode, u64>)> =
+            std::collections::BTreeMap::new();
+        for (version, warnings) in logs {
+            let (generations, codes) = by_version.entry(version).or_default();
+            *generations += 1;
+            for code in warnings.as_deref().map(WarningCode::in_warnings).unwrap_or_default() {
+                *codes.entry(code).or_default() += 1;
+            }
+        }
+
+        let rows = WarningCode::ALL
+            .iter()
+            .filter(|code| by_version.values().any(|(_, codes)| codes.contains_key(*code)))
+            .map(|code| {
+                let mut previous: Option<f32> = None;
+                let cells = by_version
+                    .values()
+                    .map(|(generations, codes)| {
+                        let count = codes.get(code).copied().unwrap_or(0);
+                        let rate = count as f32 / *generations as f32 * 100.0;
+                        let change = previous
+                            .filter(|prev| *prev > 0.0)
+                            .map(|prev| (rate - prev) / prev * 100.0);
+                        previous = Some(rate);
+                        HeatmapCell { count, rate, change }
+                    })
+                    .collect();
+                HeatmapRow {
+                    code: *code,
+                    label: code.label(),
+                    cells,
+                }
+            })
+            .collect();
+
+        Ok(WarningHeatmap {
+            product: product.to_string(),
+            products,
+            days,
+            versions: by_version
+                .iter()
+                .map(|(version, (generations, _))| VersionColumn {
+                    version: *version,
+                    generations: *generations,
+                })
+                .collect(),
+            rows,
+        })
+    }
 }
@@ -0,0 +1,354 @@
+//! Artifact Packaging
+//!
+//! Turns a finished job's artifacts into files and a ZIP archive, applying
+//! the project's file headers and output format (encoding, BOM, line
+//! endings). Encoding is
+//! checked up front so a file is never silently written with `&#...;`
+//! replacements for characters EUC-KR can't represent.
+
+use std::io::{Cursor, Write};
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+
+use crate::domain::{AccessibilityReport, FileHeaders, GeneratedArtifacts, HeaderVars, OutputEncoding, OutputFormat, SpringArtifacts};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// A named text file ready for packaging
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackagedFile {
+    pub name: String,
+    pub content: String,
+}
+
+impl PackagedFile {
+    fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Service for packaging generated artifacts
+pub struct ArtifactPackager;
+
+impl ArtifactPackager {
+    /// Files of a finished job (`generation_logs.artifacts` JSON)
+    pub fn files_for(product: &str, artifacts_json: &str) -> Result<Vec<PackagedFile>> {
+        if product == "spring-backend" {
+            let artifacts: Option<SpringArtifacts> = serde_json::from_str(artifacts_json)?;
+            let artifacts = artifacts.ok_or_else(|| anyhow!("Job has no artifacts"))?;
+            let mut files = Self::spring_files(&artifacts);
+            if let Some(script) = artifacts.test_data {
+                files.push(PackagedFile::new(
+                    artifacts.test_data_filename.unwrap_or_else(|| "test_data.sql".to_string()),
+                    script,
+                ));
+            }
+            return Ok(files);
+        }
+
+        let artifacts: Option<GeneratedArtifacts> = serde_json::from_str(artifacts_json)?;
+        let artifacts = artifacts.ok_or_else(|| anyhow!("Job has no artifacts"))?;
+        let xml_filename = artifacts.xml_filename.unwrap_or_else(|| "screen.xml".to_string());
+        let mut files = Vec::new();
+        if let Some(xml) = artifacts.xml {
+            files.push(PackagedFile::new(&xml_filename, xml));
+        }
+        if let Some(js) = artifacts.javascript {
+            files.push(PackagedFile::new(artifacts.js_filename.unwrap_or_else(|| "screen.js".to_string()), js));
+        }
+        for screen in artifacts.additional_screens {
+            files.push(PackagedFile::new(screen.xml_filename, screen.xml));
+            if !screen.javascript.trim().is_empty() {
+                files.push(PackagedFile::new(screen.js_filename, screen.javascript));
+            }
+        }
+        if let Some(layout) = artifacts.print_layout {
+            files.push(PackagedFile::new(
+                artifacts.print_layout_filename.unwrap_or_else(|| "screen_print.xml".to_string()),
+                layout,
+            ));
+        }
+        if let Some(report) = artifacts.accessibility_report {
+            files.push(PackagedFile::new(
+                AccessibilityReport::filename(&xml_filename),
+                serde_json::to_string_pretty(&report)?,
+            ));
+        }
+        if let Some(script) = artifacts.test_data {
+            files.push(PackagedFile::new(
+                artifacts.test_data_filename.unwrap_or_else(|| "test_data.sql".to_string()),
+                script,
+            ));
+        }
+        Ok(files)
+    }
+
+    /// Spring files at their naming-profile paths; logs without a file tree use
+    /// the declared class/interface names (mapper XML after its namespace)
+    fn spring_files(artifacts: &SpringArtifacts) -> Vec<PackagedFile> {
+        if let Some(paths) = &artifacts.paths {
+            let mut files = vec![
+                PackagedFile::new(&paths.controller, &artifacts.controller),
+                PackagedFile::new(&paths.service_interface, &artifacts.service_interface),
+                PackagedFile::new(&paths.service_impl, &artifacts.service_impl),
+                PackagedFile::new(&paths.dto, &artifacts.dto),
+            ];
+            if let Some((path, search_dto)) = paths.search_dto.as_ref().zip(artifacts.search_dto.as_ref()) {
+                files.push(PackagedFile::new(path, search_dto));
+            }
+            files.push(PackagedFile::new(&paths.mapper_interface, &artifacts.mapper_interface));
+            files.push(PackagedFile::new(&paths.mapper_xml, &artifacts.mapper_xml));
+            files.retain(|f| !f.content.trim().is_empty());
+            return files;
+        }
+
+        let type_re = Regex::new(r"\b(?:class|interface)\s+(\w+)").unwrap();
+        let java = |source: &str, fallback: &str| {
+            let name = type_re
+                .captures(source)
+                .map(|cap| cap[1].to_string())
+                .unwrap_or_else(|| fallback.to_string());
+            PackagedFile::new(format!("{}.java", name), source)
+        };
+
+        let mut files = vec![
+            java(&artifacts.controller, "Controller"),
+            java(&artifacts.service_interface, "Service"),
+            java(&artifacts.service_impl, "ServiceImpl"),
+            java(&artifacts.dto, "DTO"),
+        ];
+        if let Some(search_dto) = &artifacts.search_dto {
+            files.push(java(search_dto, "SearchDTO"));
+        }
+        files.push(java(&artifacts.mapper_interface, "Mapper"));
+
+        let mapper_xml_name = Regex::new(r#"namespace\s*=\s*"(?:[\w.]*\.)?(\w+)""#)
+            .unwrap()
+            .captures(&artifacts.mapper_xml)
+            .map(|cap| cap[1].to_string())
+            .unwrap_or_else(|| "Mapper".to_string());
+        files.push(PackagedFile::new(format!("{}.xml", mapper_xml_name), artifacts.mapper_xml.clone()));
+
+        files.retain(|f| !f.content.trim().is_empty());
+        files
+    }
+
+    /// Put the project's header comment on each file (files that already
+    /// have it are left alone)
+    pub fn stamp_headers(files: &mut [PackagedFile], headers: &FileHeaders, vars: &HeaderVars) {
+        for file in files.iter_mut() {
+            file.content = headers.stamp(&file.name, &file.content, vars);
+        }
+    }
+
+    /// Characters the encoding can't represent (deduplicated, in order of appearance)
+    pub fn unrepresentable(text: &str, encoding: OutputEncoding) -> Vec<char> {
+        if encoding == OutputEncoding::Utf8 {
+            return Vec::new();
+        }
+
+        let mut found = Vec::new();
+        for c in text.chars().filter(|c| !c.is_ascii()) {
+            if found.contains(&c) {
+                continue;
+            }
+            let mut buf = [0u8; 4];
+            let (_, _, had_errors) = encoding_rs::EUC_KR.encode(c.encode_utf8(&mut buf));
+            if had_errors {
+                found.push(c);
+            }
+        }
+        found
+    }
+
+    /// Encode one file's text with the output format
+    pub fn encode(text: &str, format: &OutputFormat) -> Result<Vec<u8>> {
+        format.validate().map_err(|e| anyhow!(e))?;
+        let text = format.line_ending.apply(text);
+
+        match format.encoding {
+            OutputEncoding::Utf8 => {
+                let mut bytes = if format.bom { UTF8_BOM.to_vec() } else { Vec::new() };
+                bytes.extend_from_slice(text.as_bytes());
+                Ok(bytes)
+            }
+            OutputEncoding::EucKr => {
+                let (bytes, _, had_errors) = encoding_rs::EUC_KR.encode(&text);
+                if had_errors {
+                    let chars: String = Self::unrepresentable(&text, format.encoding).into_iter().collect();
+                    return Err(anyhow!("Characters not representable in euc-kr: {}", chars));
+                }
+                Ok(bytes.into_owned())
+            }
+        }
+    }
+
+    /// Make an XML declaration's `encoding` match the file encoding
+    fn sync_xml_declaration(content: &str, encoding: OutputEncoding) -> String {
+        Regex::new(r#"^(\s*<\?xml[^>]*\bencoding\s*=\s*")[^"]*(")"#)
+            .unwrap()
+            .replace(content, format!("${{1}}{}${{2}}", encoding.as_str().to_uppercase()))
+            .into_owned()
+    }
+
+    /// Build a ZIP archive; fails before writing anything if a file can't be encoded
+    pub fn zip(files: &[PackagedFile], format: &OutputFormat) -> Result<Vec<u8>> {
+        let encoded = files
+            .iter()
+            .map(|f| {
+                let content = if f.name.ends_with(".xml") {
+                    Self::sync_xml_declaration(&f.content, format.encoding)
+                } else {
+                    f.content.clone()
+                };
+                Self::encode(&content, format)
+                    .map(|bytes| (f.name.as_str(), bytes))
+                    .map_err(|e| anyhow!("{}: {}", f.name, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, bytes) in encoded {
+            writer.start_file(name, options)?;
+            writer.write_all(&bytes)?;
+        }
+        Ok(writer.finish()?.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LineEnding;
+
+    fn euc_kr_crlf() -> OutputFormat {
+        OutputFormat {
+            encoding: OutputEncoding::EucKr,
+            bom: false,
+            line_ending: LineEnding::Crlf,
+        }
+    }
+
+    #[test]
+    fn test_encode_utf8_bom_crlf() {
+        let format = OutputFormat {
+            bom: true,
+            line_ending: LineEnding::Crlf,
+            ..OutputFormat::default()
+        };
+        let bytes = ArtifactPackager::encode("a\nb", &format).unwrap();
+        assert_eq!(bytes, b"\xEF\xBB\xBFa\r\nb");
+    }
+
+    #[test]
+    fn test_encode_euc_kr() {
+        let bytes = ArtifactPackager::encode("// 회원\n", &euc_kr_crlf()).unwrap();
+        assert_eq!(bytes, b"// \xC8\xB8\xBF\xF8\r\n");
+    }
+
+    #[test]
+    fn test_unrepresentable_chars_rejected() {
+        let text = "// 회원 😀 🚀 😀";
+        assert_eq!(
+            ArtifactPackager::unrepresentable(text, OutputEncoding::EucKr),
+            vec!['😀', '🚀']
+        );
+
+        let files = vec![PackagedFile::new("member.js", text)];
+        let err = ArtifactPackager::zip(&files, &euc_kr_crlf()).unwrap_err();
+        assert!(err.to_string().starts_with("member.js: Characters not representable"));
+    }
+
+    #[test]
+    fn test_files_for_xframe5_and_spring() {
+        let json = r#"{"xml":"<screen/>","javascript":"this.fn_search = function() {};","xml_filename":"member_list.xml","js_filename":"member_list.js"}"#;
+        let files = ArtifactPackager::files_for("xframe5-ui", json).unwrap();
+        assert_eq!(files[0].name, "member_list.xml");
+        assert_eq!(files[1].name, "member_list.js");
+
+        let mut value: serde_json::Value = serde_json::from_str(json).unwrap();
+        value["accessibility_report"] = serde_json::to_value(AccessibilityReport::default().finish()).unwrap();
+        let files = ArtifactPackager::files_for("xframe5-ui", &value.to_string()).unwrap();
+        assert_eq!(files[2].name, "member_list_a11y.json");
+        assert!(files[2].content.contains("\"compliant\": true"));
+
+        value["test_data"] = "INSERT INTO TB_MEMBER (MEMBER_ID) VALUES (1);\n".into();
+        value["test_data_filename"] = "tb_member_test_data.sql".into();
+        let files = ArtifactPackager::files_for("xframe5-ui", &value.to_string()).unwrap();
+        assert_eq!(files[3].name, "tb_member_test_data.sql");
+
+        let mut spring = SpringArtifacts::new();
+        spring.controller = "public class MemberController {}".to_string();
+        spring.mapper_xml = r#"<mapper namespace="com.company.member.mapper.MemberMapper">"#.to_string();
+        let files = ArtifactPackager::files_for("spring-backend", &serde_json::to_string(&spring).unwrap()).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["MemberController.java", "MemberMapper.xml"]);
+
+        spring.paths = Some(crate::domain::SpringFilePaths {
+            controller: "src/main/java/com/company/member/web/MemberController.java".to_string(),
+            service_interface: String::new(),
+            service_impl: String::new(),
+            dto: String::new(),
+            search_dto: None,
+            mapper_interface: String::new(),
+            mapper_xml: "sqlmap/member.xml".to_string(),
+        });
+        let files = ArtifactPackager::files_for("spring-backend", &serde_json::to_string(&spring).unwrap()).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["src/main/java/com/company/member/web/MemberController.java", "sqlmap/member.xml"]);
+
+        spring.test_data = Some("INSERT INTO TB_MEMBER (MEMBER_ID) VALUES (1);\n".to_string());
+        spring.test_data_filename = Some("tb_member_test_data.sql".to_string());
+        let files = ArtifactPackager::files_for("spring-backend", &serde_json::to_string(&spring).unwrap()).unwrap();
+        assert_eq!(files.last().unwrap().name, "tb_member_test_data.sql");
+    }
+
+    #[test]
+    fn test_xml_declaration_follows_encoding() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<screen/>";
+        assert_eq!(
+            ArtifactPackager::sync_xml_declaration(xml, OutputEncoding::EucKr),
+            "<?xml version=\"1.0\" encoding=\"EUC-KR\"?>\n<screen/>"
+        );
+        assert_eq!(ArtifactPackager::sync_xml_declaration("<screen/>", OutputEncoding::EucKr), "<screen/>");
+    }
+
+    #[test]
+    fn test_stamp_headers_per_artifact_type() {
+        let headers = FileHeaders {
+            xml: Some("{project} {file}".to_string()),
+            javascript: Some("Generated by {generator}".to_string()),
+            java: None,
+            do_not_edit: false,
+        };
+        let vars = HeaderVars {
+            project: "CRM".to_string(),
+            generator: "gen".to_string(),
+            date: "2026-10-17".to_string(),
+        };
+        let mut files = vec![
+            PackagedFile::new("member_list.xml", "<screen/>"),
+            PackagedFile::new("member_list.js", "var a;"),
+            PackagedFile::new("Member.java", "class Member {}"),
+        ];
+        ArtifactPackager::stamp_headers(&mut files, &headers, &vars);
+        assert_eq!(files[0].content, "<!--\n  CRM member_list.xml\n-->\n<screen/>");
+        assert_eq!(files[1].content, "/*\n * Generated by gen\n */\nvar a;");
+        assert_eq!(files[2].content, "class Member {}");
+        assert!(files.iter().all(|f| headers.has_header(&f.name, &f.content)));
+    }
+
+    #[test]
+    fn test_zip_roundtrip_size() {
+        let files = vec![PackagedFile::new("a.js", "x"), PackagedFile::new("b.xml", "<y/>")];
+        let bytes = ArtifactPackager::zip(&files, &OutputFormat::default()).unwrap();
+        assert_eq!(&bytes[..2], b"PK");
+    }
+}
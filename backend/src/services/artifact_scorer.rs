@@ -0,0 +1,264 @@
+//! Artifact Quality Scoring
+//!
+//! Deterministic 0-100 score of generated artifacts, independent of the LLM.
+//! Four weighted components:
+//!
+//! | Component | Points | Measure |
+//! |-----------|--------|---------|
+//! | validation | 40 | -8 per `Warning`/`Error` finding |
+//! | checklist | 30 | share of required functions/components present |
+//! | todo | 15 | TODO density (0 at 10 TODOs per 100 lines) |
+//! | naming | 15 | share of identifiers following conventions |
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::domain::{CrudOperation, SpringArtifacts, SpringIntent, UiIntent};
+
+const VALIDATION_POINTS: f32 = 40.0;
+const CHECKLIST_POINTS: f32 = 30.0;
+const TODO_POINTS: f32 = 15.0;
+const NAMING_POINTS: f32 = 15.0;
+
+/// Points lost per validation finding
+const FINDING_PENALTY: f32 = 8.0;
+
+/// TODOs per 100 lines at which the TODO component reaches 0
+const MAX_TODO_DENSITY: f32 = 10.0;
+
+/// Quality score with its components (components sum to `total`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QualityScore {
+    pub total: u8,
+    pub validation: u8,
+    pub checklist: u8,
+    pub todo: u8,
+    pub naming: u8,
+}
+
+impl QualityScore {
+    fn from_ratios(findings: usize, checklist: f32, todo_density: f32, naming: f32) -> Self {
+        let validation = (VALIDATION_POINTS - FINDING_PENALTY * findings as f32).max(0.0).round() as u8;
+        let checklist = (CHECKLIST_POINTS * checklist).round() as u8;
+        let todo = (TODO_POINTS * (1.0 - todo_density / MAX_TODO_DENSITY).max(0.0)).round() as u8;
+        let naming = (NAMING_POINTS * naming).round() as u8;
+        Self {
+            total: validation + checklist + todo + naming,
+            validation,
+            checklist,
+            todo,
+            naming,
+        }
+    }
+}
+
+/// Computes artifact quality scores
+pub struct ArtifactScorer;
+
+impl ArtifactScorer {
+    /// Score an xFrame5 screen (XML + JS) against its intent
+    pub fn score_screen(xml: &str, js: &str, intent: &UiIntent, warnings: &[String]) -> QualityScore {
+        // Checklist: action functions, datasets and grids from the intent
+        let mut checks: Vec<bool> = intent
+            .actions
+            .iter()
+            .map(|a| Self::defines_js_function(js, &a.function_name))
+            .collect();
+        checks.extend(intent.datasets.iter().map(|d| Self::has_component(xml, "Dataset", &d.id)));
+        checks.extend(intent.grids.iter().map(|g| Self::has_component(xml, "Grid", &g.id)));
+
+        // Naming: ds_ datasets, grid_/grd_ grids, fn_ functions
+        let mut names: Vec<bool> = Self::component_ids(xml, "Dataset")
+            .iter()
+            .map(|id| id.starts_with("ds_"))
+            .collect();
+        names.extend(
+            Self::component_ids(xml, "Grid")
+                .iter()
+                .map(|id| id.starts_with("grid_") || id.starts_with("grd_")),
+        );
+        names.extend(Self::js_functions(js).iter().map(|f| f.starts_with("fn_")));
+
+        QualityScore::from_ratios(
+            Self::findings(warnings),
+            Self::ratio(&checks),
+            Self::todo_density(&[xml, js]),
+            Self::ratio(&names),
+        )
+    }
+
+    /// Score Spring artifacts against their intent
+    pub fn score_spring(artifacts: &SpringArtifacts, intent: &SpringIntent, warnings: &[String]) -> QualityScore {
+        let xml = &artifacts.mapper_xml;
+
+        // Checklist: stereotype annotations, mapper namespace, one statement per CRUD operation
+        let mut checks = vec![
+            artifacts.controller.contains("@RestController") || artifacts.controller.contains("@Controller"),
+            artifacts.service_impl.contains("@Service"),
+            artifacts.mapper_interface.contains("@Mapper"),
+            xml.contains("namespace="),
+        ];
+        checks.extend(intent.crud_operations.iter().map(|op| {
+            let tag = match op {
+                CrudOperation::Create => "<insert",
+                CrudOperation::Read | CrudOperation::ReadList => "<select",
+                CrudOperation::Update => "<update",
+                CrudOperation::Delete => "<delete",
+            };
+            xml.contains(tag)
+        }));
+
+        // Naming: each file declares the class name derived from the entity
+        let names = [
+            Self::declares(&artifacts.controller, "class", &intent.controller_name()),
+            Self::declares(&artifacts.service_interface, "interface", &intent.service_name()),
+            Self::declares(&artifacts.service_impl, "class", &intent.service_impl_name()),
+            Self::declares(&artifacts.dto, "class", &intent.dto_name()),
+            Self::declares(&artifacts.mapper_interface, "interface", &intent.mapper_name()),
+        ];
+
+        let sources: [&str; 7] = [
+            &artifacts.controller,
+            &artifacts.service_interface,
+            &artifacts.service_impl,
+            &artifacts.dto,
+            artifacts.search_dto.as_deref().unwrap_or_default(),
+            &artifacts.mapper_interface,
+            xml,
+        ];
+
+        QualityScore::from_ratios(
+            Self::findings(warnings),
+            Self::ratio(&checks),
+            Self::todo_density(&sources),
+            Self::ratio(&names),
+        )
+    }
+
+    /// Validation findings (`Note:` entries are informational)
+    fn findings(warnings: &[String]) -> usize {
+        warnings
+            .iter()
+            .filter(|w| w.contains("Warning") || w.contains("Error"))
+            .count()
+    }
+
+    /// Share of passed checks; an empty checklist counts as complete
+    fn ratio(checks: &[bool]) -> f32 {
+        if checks.is_empty() {
+            return 1.0;
+        }
+        checks.iter().filter(|c| **c).count() as f32 / checks.len() as f32
+    }
+
+    /// TODOs per 100 non-empty lines
+    fn todo_density(sources: &[&str]) -> f32 {
+        let lines = sources
+            .iter()
+            .flat_map(|s| s.lines())
+            .filter(|l| !l.trim().is_empty())
+            .count();
+        if lines == 0 {
+            return 0.0;
+        }
+        let todos: usize = sources.iter().map(|s| s.matches("TODO").count()).sum();
+        todos as f32 * 100.0 / lines as f32
+    }
+
+    fn has_component(xml: &str, tag: &str, id: &str) -> bool {
+        Self::component_ids(xml, tag).iter().any(|found| found == id)
+    }
+
+    fn component_ids(xml: &str, tag: &str) -> Vec<String> {
+        let re = Regex::new(&format!(r#"<{}\b[^>]*\bid\s*=\s*"([^"]*)""#, tag)).unwrap();
+        re.captures_iter(xml).map(|c| c[1].to_string()).collect()
+    }
+
+    fn defines_js_function(js: &str, name: &str) -> bool {
+        Self::js_functions(js).iter().any(|f| f == name)
+    }
+
+    /// Functions declared as `this.name = function` or `function name(`
+    fn js_functions(js: &str) -> Vec<String> {
+        let re = Regex::new(r"(?:this\.(\w+)\s*=\s*function\b|\bfunction\s+(\w+)\s*\()").unwrap();
+        re.captures_iter(js)
+            .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+            .map(|m| m.as_str().to_string())
+            .collect()
+    }
+
+    fn declares(source: &str, kind: &str, name: &str) -> bool {
+        Regex::new(&format!(r"\b{}\s+{}\b", kind, regex::escape(name)))
+            .map(|re| re.is_match(source))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ActionIntent, ActionType, DatasetIntent, GridIntent, ScreenType};
+
+    fn screen_intent() -> UiIntent {
+        UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(DatasetIntent::new("ds_member"))
+            .with_grid(GridIntent::new("grid_member", "ds_member"))
+            .with_action(ActionIntent::new("search", "조회", ActionType::Search))
+            .with_action(ActionIntent::new("save", "저장", ActionType::Save))
+    }
+
+    const XML: &str = r#"<Dataset id="ds_member"/>
+<Grid id="grid_member" dataset="ds_member"/>"#;
+
+    #[test]
+    fn test_clean_screen_scores_full() {
+        let js = "this.fn_search = function() {\n  load();\n};\nthis.fn_save = function() {\n  save();\n};";
+        let score = ArtifactScorer::score_screen(XML, js, &screen_intent(), &["Note: ok".to_string()]);
+        assert_eq!(score.total, 100);
+    }
+
+    #[test]
+    fn test_screen_penalties() {
+        let js = "this.fn_search = function() {\n  // TODO: transaction\n};\nthis.doSave = function() {};";
+        let warnings = vec!["Warning: Expected function 'fn_save' not found".to_string()];
+        let score = ArtifactScorer::score_screen(XML, js, &screen_intent(), &warnings);
+
+        assert_eq!(score.validation, 32);
+        // fn_save missing: 3 of 4 checks
+        assert_eq!(score.checklist, 23);
+        // 1 TODO in 6 lines is past the density limit
+        assert_eq!(score.todo, 0);
+        // doSave lacks the fn_ prefix: 3 of 4 names
+        assert_eq!(score.naming, 11);
+        assert_eq!(score.total, 66);
+    }
+
+    #[test]
+    fn test_score_is_deterministic() {
+        let js = "function fn_search() {}\n// TODO: save";
+        let a = ArtifactScorer::score_screen(XML, js, &screen_intent(), &[]);
+        let b = ArtifactScorer::score_screen(XML, js, &screen_intent(), &[]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_spring_score() {
+        let intent = SpringIntent::new("Member", "TB_MEMBER", "com.company.project")
+            .with_operations(vec![CrudOperation::Create, CrudOperation::ReadList]);
+        let mut artifacts = SpringArtifacts::new();
+        artifacts.controller = "@RestController\npublic class MemberController {}".to_string();
+        artifacts.service_interface = "public interface MemberService {}".to_string();
+        artifacts.service_impl = "@Service\npublic class MemberServiceImpl {}".to_string();
+        artifacts.dto = "public class MemberDto {}".to_string();
+        artifacts.mapper_interface = "@Mapper\npublic interface MemberMapper {}".to_string();
+        artifacts.mapper_xml = r#"<mapper namespace="x"><insert id="insert"/></mapper>"#.to_string();
+
+        let score = ArtifactScorer::score_spring(&artifacts, &intent, &[]);
+        assert_eq!(score.validation, 40);
+        // No <select>: 5 of 6 checks
+        assert_eq!(score.checklist, 25);
+        // MemberDto instead of MemberDTO: 4 of 5 names
+        assert_eq!(score.naming, 12);
+        assert_eq!(score.todo, 15);
+    }
+}
@@ -0,0 +1,292 @@
+//! Autoscaling Hints
+//!
+//! Queue depth, queue wait and per-provider saturation of async generation
+//! jobs, for scaling worker replicas (Kubernetes HPA through a custom/external
+//! metrics adapter, or KEDA). Served as JSON or in the Prometheus text format.
+//!
+//! Everything is read from `generation_logs`, so every replica reports the
+//! same cluster-wide numbers. Settings come from the environment:
+//! - `SCALING_PROVIDER_CAPACITY`: concurrent jobs per provider, e.g.
+//!   `ollama=2,vllm=8`
+//! - `SCALING_DEFAULT_CAPACITY`: capacity of unlisted providers (default 1)
+//! - `SCALING_JOBS_PER_REPLICA`: jobs one worker replica runs at a time (default 1)
+//! - `SCALING_WAIT_WINDOW_SECS`: period the average wait covers (default 900)
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
+use serde::Serialize;
+
+use crate::llm::active_provider;
+use crate::models::_entities::generation_logs::{Column, Entity};
+
+/// Scaling settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScalingConfig {
+    pub provider_capacity: BTreeMap<String, u32>,
+    pub default_capacity: u32,
+    pub jobs_per_replica: u32,
+    pub wait_window_secs: i64,
+}
+
+impl Default for ScalingConfig {
+    fn default() -> Self {
+        Self {
+            provider_capacity: BTreeMap::new(),
+            default_capacity: 1,
+            jobs_per_replica: 1,
+            wait_window_secs: 900,
+        }
+    }
+}
+
+impl ScalingConfig {
+    /// Load from environment
+    pub fn from_env() -> Self {
+        let number = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .filter(|v| *v > 0)
+        };
+        let defaults = Self::default();
+
+        Self {
+            provider_capacity: std::env::var("SCALING_PROVIDER_CAPACITY")
+                .map(|v| Self::parse_capacity(&v))
+                .unwrap_or_default(),
+            default_capacity: number("SCALING_DEFAULT_CAPACITY").unwrap_or(defaults.default_capacity),
+            jobs_per_replica: number("SCALING_JOBS_PER_REPLICA").unwrap_or(defaults.jobs_per_replica),
+            wait_window_secs: number("SCALING_WAIT_WINDOW_SECS")
+                .map_or(defaults.wait_window_secs, i64::from),
+        }
+    }
+
+    /// Parse `provider=capacity` pairs; malformed entries are skipped
+    pub fn parse_capacity(value: &str) -> BTreeMap<String, u32> {
+        value
+            .split(',')
+            .filter_map(|pair| {
+                let (provider, capacity) = pair.split_once('=')?;
+                let capacity = capacity.trim().parse::<u32>().ok().filter(|c| *c > 0)?;
+                Some((provider.trim().to_string(), capacity)).filter(|(p, _)| !p.is_empty())
+            })
+            .collect()
+    }
+
+    fn capacity_of(&self, provider: &str) -> u32 {
+        self.provider_capacity.get(provider).copied().unwrap_or(self.default_capacity)
+    }
+}
+
+/// Load on one provider
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderSaturation {
+    pub provider: String,
+    /// Jobs running on the provider
+    pub in_flight: u64,
+    /// Jobs the provider is sized for
+    pub capacity: u32,
+    /// `in_flight / capacity` (1.0 = full, above = overcommitted)
+    pub saturation: f64,
+}
+
+/// Autoscaling metrics at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScalingHints {
+    /// Jobs waiting for a worker
+    pub queue_depth: u64,
+    /// Jobs being generated
+    pub processing: u64,
+    /// Average queue wait of the jobs started within the wait window
+    pub avg_wait_seconds: Option<f64>,
+    /// How long the oldest queued job has been waiting
+    pub oldest_wait_seconds: Option<i64>,
+    /// Worker replicas that would run every queued and running job at once
+    pub desired_replicas: u64,
+    pub providers: Vec<ProviderSaturation>,
+}
+
+impl ScalingHints {
+    /// Current hints from the job table
+    pub async fn collect(db: &DatabaseConnection, config: &ScalingConfig) -> anyhow::Result<Self> {
+        let now = Utc::now();
+
+        let queued: Vec<(Option<DateTimeWithTimeZone>, DateTimeWithTimeZone)> = Entity::find()
+            .select_only()
+            .column(Column::QueuedAt)
+            .column(Column::CreatedAt)
+            .filter(Column::JobId.is_not_null())
+            .filter(Column::Status.eq("queued"))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let processing: Vec<Option<String>> = Entity::find()
+            .select_only()
+            .column(Column::Company)
+            .filter(Column::JobId.is_not_null())
+            .filter(Column::Status.eq("processing"))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let started: Vec<(Option<DateTimeWithTimeZone>, Option<DateTimeWithTimeZone>)> = Entity::find()
+            .select_only()
+            .column(Column::QueuedAt)
+            .column(Column::StartedAt)
+            .filter(Column::JobId.is_not_null())
+            .filter(Column::StartedAt.gte(now - Duration::seconds(config.wait_window_secs)))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        // Running jobs use their company's active provider
+        let mut companies: BTreeMap<Option<String>, u64> = BTreeMap::new();
+        for company in processing {
+            *companies.entry(company).or_default() += 1;
+        }
+        let mut in_flight: BTreeMap<String, u64> = BTreeMap::new();
+        for (company, jobs) in companies {
+            *in_flight.entry(active_provider(db, company.as_deref()).await).or_default() += jobs;
+        }
+
+        // Jobs queued before stage timestamps existed count from their creation
+        let queued_at: Vec<DateTime<Utc>> = queued
+            .into_iter()
+            .map(|(queued, created)| queued.unwrap_or(created).with_timezone(&Utc))
+            .collect();
+        let waits: Vec<f64> = started
+            .into_iter()
+            .filter_map(|(queued, started)| Some((started? - queued?).num_milliseconds() as f64 / 1000.0))
+            .collect();
+
+        Ok(Self::compute(config, now, &queued_at, &waits, in_flight))
+    }
+
+    /// Hints from queue entry times, recent waits (seconds) and running jobs per provider
+    pub fn compute(
+        config: &ScalingConfig,
+        now: DateTime<Utc>,
+        queued_at: &[DateTime<Utc>],
+        waits: &[f64],
+        mut in_flight: BTreeMap<String, u64>,
+    ) -> Self {
+        for provider in config.provider_capacity.keys() {
+            in_flight.entry(provider.clone()).or_default();
+        }
+        let providers = in_flight
+            .into_iter()
+            .map(|(provider, in_flight)| {
+                let capacity = config.capacity_of(&provider);
+                ProviderSaturation {
+                    saturation: in_flight as f64 / f64::from(capacity),
+                    provider,
+                    in_flight,
+                    capacity,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let queue_depth = queued_at.len() as u64;
+        let processing = providers.iter().map(|p| p.in_flight).sum::<u64>();
+
+        Self {
+            queue_depth,
+            processing,
+            avg_wait_seconds: (!waits.is_empty()).then(|| waits.iter().sum::<f64>() / waits.len() as f64),
+            oldest_wait_seconds: queued_at.iter().min().map(|at| (now - *at).num_seconds().max(0)),
+            desired_replicas: (queue_depth + processing).div_ceil(u64::from(config.jobs_per_replica.max(1))),
+            providers,
+        }
+    }
+
+    /// Prometheus text exposition format (gauges)
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, help: &str, samples: &[(String, String)]| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} gauge", name);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{} {}", name, labels, value);
+            }
+        };
+        let plain = |value: String| vec![(String::new(), value)];
+        let per_provider = |value: &dyn Fn(&ProviderSaturation) -> String| {
+            self.providers
+                .iter()
+                .map(|p| (format!("{{provider=\"{}\"}}", p.provider.replace(['\\', '"'], "")), value(p)))
+                .collect::<Vec<_>>()
+        };
+
+        gauge("coder_queue_depth", "Generation jobs waiting for a worker", &plain(self.queue_depth.to_string()));
+        gauge("coder_jobs_processing", "Generation jobs being generated", &plain(self.processing.to_string()));
+        gauge(
+            "coder_queue_wait_seconds_avg",
+            "Average queue wait of recently started jobs",
+            &plain(self.avg_wait_seconds.unwrap_or(0.0).to_string()),
+        );
+        gauge(
+            "coder_queue_oldest_wait_seconds",
+            "Wait of the oldest queued job",
+            &plain(self.oldest_wait_seconds.unwrap_or(0).to_string()),
+        );
+        gauge(
+            "coder_desired_replicas",
+            "Worker replicas needed to run all queued and running jobs",
+            &plain(self.desired_replicas.to_string()),
+        );
+        gauge("coder_provider_in_flight", "Jobs running per provider", &per_provider(&|p| p.in_flight.to_string()));
+        gauge("coder_provider_capacity", "Concurrent jobs a provider is sized for", &per_provider(&|p| p.capacity.to_string()));
+        gauge("coder_provider_saturation", "Running jobs over capacity per provider", &per_provider(&|p| p.saturation.to_string()));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_hints() {
+        let config = ScalingConfig {
+            provider_capacity: ScalingConfig::parse_capacity("ollama=2, vllm=8, bad, groq=0"),
+            jobs_per_replica: 2,
+            ..Default::default()
+        };
+        assert_eq!(config.provider_capacity.len(), 2);
+
+        let now = Utc::now();
+        let queued = [now - Duration::seconds(90), now - Duration::seconds(10), now];
+        let in_flight = BTreeMap::from([("ollama".to_string(), 3), ("openai".to_string(), 1)]);
+        let hints = ScalingHints::compute(&config, now, &queued, &[4.0, 8.0], in_flight);
+
+        assert_eq!(hints.queue_depth, 3);
+        assert_eq!(hints.processing, 4);
+        assert_eq!(hints.avg_wait_seconds, Some(6.0));
+        assert_eq!(hints.oldest_wait_seconds, Some(90));
+        assert_eq!(hints.desired_replicas, 4);
+
+        let ollama = &hints.providers[0];
+        assert_eq!((ollama.provider.as_str(), ollama.capacity, ollama.saturation), ("ollama", 2, 1.5));
+        let openai = &hints.providers[1];
+        assert_eq!((openai.provider.as_str(), openai.capacity), ("openai", 1));
+        let vllm = &hints.providers[2];
+        assert_eq!((vllm.in_flight, vllm.saturation), (0, 0.0));
+    }
+
+    #[test]
+    fn test_prometheus_format() {
+        let config = ScalingConfig::default();
+        let now = Utc::now();
+        let hints = ScalingHints::compute(&config, now, &[], &[], BTreeMap::from([("vllm".to_string(), 1)]));
+        let text = hints.to_prometheus();
+
+        assert!(text.contains("# TYPE coder_queue_depth gauge\ncoder_queue_depth 0\n"));
+        assert!(text.contains("coder_queue_wait_seconds_avg 0\n"));
+        assert!(text.contains("coder_provider_saturation{provider=\"vllm\"} 1\n"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Batch Job Schedules
+//!
+//! When the nightly batch jobs (regeneration, retention cleanup and drift
+//! detection) may run. Each job has a run time in Korean time and can be
+//! limited to business days (see [`BusinessCalendar`]); a customer's
+//! maintenance window covering the run time always skips that run.
+//!
+//! The jobs are started by cron and ask first; the task fails when the job
+//! must not run:
+//!
+//! ```sh
+//! cargo loco task batch_check job:retention_cleanup && cargo loco task retention_cleanup
+//! ```
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use loco_rs::prelude::*;
+use sea_orm::{query::*, DatabaseConnection};
+use serde::Serialize;
+
+use crate::models::_entities::{batch_schedules, calendar_overrides};
+use crate::services::business_calendar::{parse_time, BusinessCalendar};
+
+/// Days searched for the next run
+const MAX_LOOKAHEAD_DAYS: i64 = 366;
+
+/// Batch jobs with a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchJob {
+    NightlyRegeneration,
+    RetentionCleanup,
+    DriftDetection,
+}
+
+impl BatchJob {
+    pub const ALL: [Self; 3] = [Self::NightlyRegeneration, Self::RetentionCleanup, Self::DriftDetection];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::NightlyRegeneration => "nightly_regeneration",
+            Self::RetentionCleanup => "retention_cleanup",
+            Self::DriftDetection => "drift_detection",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NightlyRegeneration => "Nightly regeneration",
+            Self::RetentionCleanup => "Retention cleanup",
+            Self::DriftDetection => "Drift detection",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|job| job.key() == raw.trim())
+    }
+
+    /// Schedule used until an administrator saves one: staggered after
+    /// midnight, business days only
+    pub fn default_schedule(&self) -> batch_schedules::Model {
+        let run_at = match self {
+            Self::NightlyRegeneration => "02:00",
+            Self::RetentionCleanup => "03:00",
+            Self::DriftDetection => "04:00",
+        };
+        let now = Utc::now().into();
+        batch_schedules::Model {
+            created_at: now,
+            updated_at: now,
+            id: 0,
+            job: self.key().to_string(),
+            enabled: true,
+            run_at: run_at.to_string(),
+            business_days_only: true,
+            updated_by: None,
+        }
+    }
+}
+
+/// Whether a job may run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchDecision {
+    Run,
+    Skip(String),
+}
+
+pub struct BatchScheduleService;
+
+impl BatchScheduleService {
+    /// Stored schedule of a job, or its default
+    pub async fn schedule(db: &DatabaseConnection, job: BatchJob) -> Result<batch_schedules::Model> {
+        let stored = batch_schedules::Entity::find()
+            .filter(batch_schedules::Column::Job.eq(job.key()))
+            .one(db)
+            .await?;
+        Ok(stored.unwrap_or_else(|| job.default_schedule()))
+    }
+
+    /// Holidays with the overrides that are not over yet (`today` in Korean time)
+    pub async fn calendar(db: &DatabaseConnection, today: chrono::NaiveDate) -> Result<BusinessCalendar> {
+        use calendar_overrides::Column;
+
+        let overrides = calendar_overrides::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(Column::Date.is_null())
+                    .add(Column::Date.gte(today - Duration::days(1))),
+            )
+            .all(db)
+            .await?;
+        Ok(BusinessCalendar::new(overrides))
+    }
+
+    /// May `job` run for `company` at `at` (Korean time)?
+    pub async fn check(
+        db: &DatabaseConnection,
+        job: BatchJob,
+        company: Option<&str>,
+        at: NaiveDateTime,
+    ) -> Result<BatchDecision> {
+        let schedule = Self::schedule(db, job).await?;
+        let calendar = Self::calendar(db, at.date()).await?;
+        Ok(Self::decide(&schedule, &calendar, company, at))
+    }
+
+    /// Decide a run at `at` from the schedule and calendar
+    pub fn decide(
+        schedule: &batch_schedules::Model,
+        calendar: &BusinessCalendar,
+        company: Option<&str>,
+        at: NaiveDateTime,
+    ) -> BatchDecision {
+        if !schedule.enabled {
+            return BatchDecision::Skip("Job is disabled".to_string());
+        }
+        if schedule.business_days_only {
+            if let Some(day_off) = calendar.day_off(at.date(), company) {
+                return BatchDecision::Skip(format!("{} is a day off ({})", at.date(), day_off.reason()));
+            }
+        }
+        if let Some((window, end)) = calendar.maintenance(at, company) {
+            return BatchDecision::Skip(format!(
+                "Maintenance window '{}' until {}",
+                window.name,
+                end.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        BatchDecision::Run
+    }
+
+    /// First scheduled run at or after `after` that is not skipped
+    pub fn next_run(
+        schedule: &batch_schedules::Model,
+        calendar: &BusinessCalendar,
+        company: Option<&str>,
+        after: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        if !schedule.enabled {
+            return None;
+        }
+        let run_at = parse_time(&schedule.run_at)?;
+        (0..=MAX_LOOKAHEAD_DAYS)
+            .map(|offset| (after.date() + Duration::days(offset)).and_time(run_at))
+            .filter(|at| *at >= after)
+            .find(|at| Self::decide(schedule, calendar, company, *at) == BatchDecision::Run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(y: i32, m: u32, d: u32, time: &str) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap().and_time(parse_time(time).unwrap())
+    }
+
+    fn maintenance(date: NaiveDate, start: &str, end: &str) -> calendar_overrides::Model {
+        let now = Utc::now().into();
+        calendar_overrides::Model {
+            created_at: now,
+            updated_at: now,
+            id: 1,
+            kind: "maintenance".to_string(),
+            date: Some(date),
+            weekday: None,
+            start_time: Some(start.to_string()),
+            end_time: Some(end.to_string()),
+            company: Some("acme".to_string()),
+            name: "DB upgrade".to_string(),
+            created_by: "admin@example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_job() {
+        assert_eq!(BatchJob::parse("drift_detection"), Some(BatchJob::DriftDetection));
+        assert_eq!(BatchJob::parse("reindex"), None);
+    }
+
+    #[test]
+    fn test_decide_skips_days_off_and_maintenance() {
+        let schedule = BatchJob::RetentionCleanup.default_schedule();
+        let day = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        let calendar = BusinessCalendar::new(vec![maintenance(day, "01:00", "05:00")]);
+
+        // Hangul Day
+        assert!(matches!(
+            BatchScheduleService::decide(&schedule, &calendar, None, at(2026, 10, 9, "03:00")),
+            BatchDecision::Skip(reason) if reason.contains("Hangul Day")
+        ));
+        assert_eq!(
+            BatchScheduleService::decide(&schedule, &calendar, None, at(2026, 10, 20, "03:00")),
+            BatchDecision::Run
+        );
+        assert!(matches!(
+            BatchScheduleService::decide(&schedule, &calendar, Some("acme"), at(2026, 10, 20, "03:00")),
+            BatchDecision::Skip(reason) if reason.starts_with("Maintenance window 'DB upgrade'")
+        ));
+
+        let every_day = batch_schedules::Model { business_days_only: false, ..schedule };
+        assert_eq!(
+            BatchScheduleService::decide(&every_day, &calendar, None, at(2026, 10, 9, "03:00")),
+            BatchDecision::Run
+        );
+    }
+
+    #[test]
+    fn test_next_run_skips_chuseok_week() {
+        let schedule = BatchJob::NightlyRegeneration.default_schedule();
+        let calendar = BusinessCalendar::new(Vec::new());
+
+        // 2025-10-03 to 10-09 are all days off (holidays, weekend, substitute)
+        let next = BatchScheduleService::next_run(&schedule, &calendar, None, at(2025, 10, 2, "02:30"));
+        assert_eq!(next, Some(at(2025, 10, 10, "02:00")));
+
+        // Today's run time not passed yet
+        let next = BatchScheduleService::next_run(&schedule, &calendar, None, at(2025, 10, 2, "01:00"));
+        assert_eq!(next, Some(at(2025, 10, 2, "02:00")));
+
+        let disabled = batch_schedules::Model { enabled: false, ..schedule };
+        assert_eq!(BatchScheduleService::next_run(&disabled, &calendar, None, at(2025, 10, 2, "01:00")), None);
+    }
+}
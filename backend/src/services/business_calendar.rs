@@ -0,0 +1,334 @@
+//! Business Calendar
+//!
+//! Korean public holidays and the day-off / maintenance overrides entered in
+//! the admin panel, used to decide when batch jobs may run:
+//! - Solar holidays, Seollal, Buddha's Birthday and Chuseok, plus the
+//!   substitute holidays (대체공휴일) they trigger. Lunar holidays are
+//!   tabulated for 2024-2030; other years need `holiday` overrides.
+//! - `holiday` / `workday` overrides add or remove days off (election days,
+//!   company anniversaries, worked substitute holidays), for every company or
+//!   one customer.
+//! - `maintenance` overrides block jobs during a customer's window, on one
+//!   day or every week.
+//!
+//! Times are Korean Standard Time (UTC+9, no daylight saving).
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::Serialize;
+
+use crate::models::_entities::calendar_overrides::Model;
+
+/// Korean Standard Time offset
+const KST_OFFSET_SECS: i32 = 9 * 3600;
+
+/// Solar (month, day)
+type MonthDay = (u32, u32);
+
+/// Seollal, Buddha's Birthday and Chuseok of the tabulated years
+const LUNAR_HOLIDAYS: [(i32, MonthDay, MonthDay, MonthDay); 7] = [
+    (2024, (2, 10), (5, 15), (9, 17)),
+    (2025, (1, 29), (5, 5), (10, 6)),
+    (2026, (2, 17), (5, 24), (9, 25)),
+    (2027, (2, 7), (5, 13), (9, 15)),
+    (2028, (1, 27), (5, 2), (10, 3)),
+    (2029, (2, 13), (5, 20), (9, 22)),
+    (2030, (2, 3), (5, 9), (9, 12)),
+];
+
+/// Current Korean local time
+pub fn korea_now() -> NaiveDateTime {
+    to_korea_time(Utc::now())
+}
+
+/// Korean local time of an instant
+pub fn to_korea_time(at: DateTime<Utc>) -> NaiveDateTime {
+    let kst = FixedOffset::east_opt(KST_OFFSET_SECS).expect("valid KST offset");
+    at.with_timezone(&kst).naive_local()
+}
+
+/// `HH:MM` time of a schedule or maintenance window
+pub fn parse_time(raw: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(raw.trim(), "%H:%M").ok()
+}
+
+/// A public holiday
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// Why a day is not a business day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayOff {
+    Weekend(Weekday),
+    Holiday(String),
+}
+
+impl DayOff {
+    pub fn reason(&self) -> String {
+        match self {
+            Self::Weekend(Weekday::Sat) => "Saturday".to_string(),
+            Self::Weekend(_) => "Sunday".to_string(),
+            Self::Holiday(name) => name.clone(),
+        }
+    }
+}
+
+/// When a holiday falling on a weekend (or on another holiday) is substituted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Substitute {
+    Never,
+    /// Seollal and Chuseok: on a Sunday
+    Sunday,
+    /// Other national holidays: on a Saturday or Sunday
+    Weekend,
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+pub struct KoreanHolidays;
+
+impl KoreanHolidays {
+    /// True when the lunar holidays of `year` are known
+    pub fn is_tabulated(year: i32) -> bool {
+        LUNAR_HOLIDAYS.iter().any(|(y, ..)| *y == year)
+    }
+
+    /// Public holidays of `year` with their substitute holidays, in date order
+    pub fn of_year(year: i32) -> Vec<Holiday> {
+        let date = |(month, day): (u32, u32)| NaiveDate::from_ymd_opt(year, month, day);
+        let mut base: Vec<(NaiveDate, &'static str, Substitute)> = [
+            ((1, 1), "New Year's Day", Substitute::Never),
+            ((3, 1), "Independence Movement Day", Substitute::Weekend),
+            ((5, 5), "Children's Day", Substitute::Weekend),
+            ((6, 6), "Memorial Day", Substitute::Never),
+            ((8, 15), "Liberation Day", Substitute::Weekend),
+            ((10, 3), "National Foundation Day", Substitute::Weekend),
+            ((10, 9), "Hangul Day", Substitute::Weekend),
+            ((12, 25), "Christmas", Substitute::Weekend),
+        ]
+        .into_iter()
+        .filter_map(|(day, name, rule)| date(day).map(|d| (d, name, rule)))
+        .collect();
+
+        if let Some((_, seollal, buddha, chuseok)) = LUNAR_HOLIDAYS.iter().find(|(y, ..)| *y == year) {
+            // Seollal and Chuseok are the day itself and the days either side
+            for (day, name) in [(seollal, "Seollal"), (chuseok, "Chuseok")] {
+                if let Some(day) = date(*day) {
+                    for offset in -1..=1 {
+                        base.push((day + Duration::days(offset), name, Substitute::Sunday));
+                    }
+                }
+            }
+            if let Some(day) = date(*buddha) {
+                base.push((day, "Buddha's Birthday", Substitute::Weekend));
+            }
+        }
+        base.sort_by_key(|(d, ..)| *d);
+
+        let mut holidays: Vec<Holiday> = Vec::new();
+        let mut substitutes: Vec<Holiday> = Vec::new();
+        let mut i = 0;
+        while i < base.len() {
+            let day = base[i].0;
+            let same_day: Vec<_> = base[i..].iter().take_while(|(d, ..)| *d == day).collect();
+            i += same_day.len();
+
+            let names: Vec<&str> = same_day.iter().map(|(_, name, _)| *name).collect();
+            holidays.push(Holiday { date: day, name: names.join(" / ") });
+
+            // One substitute per day, on the first following weekday that is not a holiday
+            let overlaps = same_day.len() > 1;
+            let trigger = same_day.iter().find(|(_, _, rule)| match rule {
+                Substitute::Never => false,
+                Substitute::Sunday => overlaps || day.weekday() == Weekday::Sun,
+                Substitute::Weekend => overlaps || is_weekend(day),
+            });
+            if let Some((_, name, _)) = trigger {
+                let mut next = day + Duration::days(1);
+                while is_weekend(next)
+                    || base.iter().any(|(d, ..)| *d == next)
+                    || substitutes.iter().any(|h| h.date == next)
+                {
+                    next += Duration::days(1);
+                }
+                substitutes.push(Holiday { date: next, name: format!("Substitute holiday ({})", name) });
+            }
+        }
+
+        holidays.extend(substitutes);
+        holidays.sort_by_key(|h| h.date);
+        holidays
+    }
+
+    /// Name of the public holiday on `date`
+    pub fn name(date: NaiveDate) -> Option<String> {
+        let mut holidays = Self::of_year(date.year());
+        // A December substitute can fall into the next year
+        holidays.extend(Self::of_year(date.year() - 1).into_iter().filter(|h| h.date.year() == date.year()));
+        holidays.into_iter().find(|h| h.date == date).map(|h| h.name)
+    }
+}
+
+/// Public holidays with the admin overrides
+pub struct BusinessCalendar {
+    overrides: Vec<Model>,
+}
+
+impl BusinessCalendar {
+    pub fn new(overrides: Vec<Model>) -> Self {
+        Self { overrides }
+    }
+
+    fn applies(item: &Model, kind: &str, company: Option<&str>) -> bool {
+        item.kind == kind && (item.company.is_none() || item.company.as_deref() == company)
+    }
+
+    /// Why `date` is a day off for `company` (None = every company), if it is
+    pub fn day_off(&self, date: NaiveDate, company: Option<&str>) -> Option<DayOff> {
+        let on_date = |kind: &'static str| {
+            self.overrides
+                .iter()
+                .find(move |o| o.date == Some(date) && Self::applies(o, kind, company))
+        };
+        if on_date("workday").is_some() {
+            return None;
+        }
+        if let Some(item) = on_date("holiday") {
+            return Some(DayOff::Holiday(item.name.clone()));
+        }
+        if let Some(name) = KoreanHolidays::name(date) {
+            return Some(DayOff::Holiday(name));
+        }
+        is_weekend(date).then(|| DayOff::Weekend(date.weekday()))
+    }
+
+    /// Maintenance window of `company` covering `at`, with the time it ends
+    pub fn maintenance(&self, at: NaiveDateTime, company: Option<&str>) -> Option<(&Model, NaiveDateTime)> {
+        let days = [at.date(), at.date() - Duration::days(1)];
+        self.overrides
+            .iter()
+            .filter(|o| Self::applies(o, "maintenance", company))
+            .find_map(|o| {
+                days.iter()
+                    .filter(|day| Self::on_day(o, **day))
+                    .map(|day| Self::window(o, *day))
+                    .find(|(start, end)| *start <= at && at < *end)
+                    .map(|(_, end)| (o, end))
+            })
+    }
+
+    /// True when a maintenance override is on `day` (its date, or its weekday every week)
+    fn on_day(item: &Model, day: NaiveDate) -> bool {
+        match item.date {
+            Some(date) => date == day,
+            None => item.weekday == Some(day.weekday().num_days_from_monday() as i32),
+        }
+    }
+
+    /// Start and end of a maintenance window starting on `day`
+    fn window(item: &Model, day: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let start = day.and_time(item.start_time.as_deref().and_then(parse_time).unwrap_or(NaiveTime::MIN));
+        let end = match item.end_time.as_deref().and_then(parse_time) {
+            Some(end) => day.and_time(end),
+            None => (day + Duration::days(1)).and_time(NaiveTime::MIN),
+        };
+        // An end before the start runs past midnight
+        let end = if end <= start { end + Duration::days(1) } else { end };
+        (start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn at(y: i32, m: u32, d: u32, time: &str) -> NaiveDateTime {
+        day(y, m, d).and_time(parse_time(time).unwrap())
+    }
+
+    fn entry(kind: &str, date: Option<NaiveDate>, company: Option<&str>) -> Model {
+        let now = Utc::now().into();
+        Model {
+            created_at: now,
+            updated_at: now,
+            id: 1,
+            kind: kind.to_string(),
+            date,
+            weekday: None,
+            start_time: None,
+            end_time: None,
+            company: company.map(str::to_string),
+            name: format!("{} override", kind),
+            created_by: "admin@example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_holidays_2025_include_substitutes() {
+        let dates: Vec<NaiveDate> = KoreanHolidays::of_year(2025).into_iter().map(|h| h.date).collect();
+        // Independence Movement Day on a Saturday
+        assert!(dates.contains(&day(2025, 3, 3)));
+        // Children's Day and Buddha's Birthday on the same day
+        assert!(dates.contains(&day(2025, 5, 5)));
+        assert!(dates.contains(&day(2025, 5, 6)));
+        // Chuseok starting on a Sunday
+        assert!(dates.contains(&day(2025, 10, 8)));
+        assert!(!dates.contains(&day(2025, 10, 10)));
+        assert_eq!(dates.len(), 17);
+    }
+
+    #[test]
+    fn test_holidays_2026_chuseok_saturday_is_not_substituted() {
+        let dates: Vec<NaiveDate> = KoreanHolidays::of_year(2026).into_iter().map(|h| h.date).collect();
+        assert!(dates.contains(&day(2026, 9, 26)));
+        assert!(!dates.contains(&day(2026, 9, 28)));
+        // Liberation Day and National Foundation Day on Saturdays
+        assert!(dates.contains(&day(2026, 8, 17)));
+        assert!(dates.contains(&day(2026, 10, 5)));
+        assert_eq!(
+            KoreanHolidays::name(day(2026, 5, 25)).as_deref(),
+            Some("Substitute holiday (Buddha's Birthday)")
+        );
+    }
+
+    #[test]
+    fn test_day_off_applies_overrides() {
+        let calendar = BusinessCalendar::new(vec![
+            entry("workday", Some(day(2026, 10, 5)), Some("acme")),
+            entry("holiday", Some(day(2026, 6, 3)), None),
+        ]);
+        assert_eq!(calendar.day_off(day(2026, 10, 6), None), None);
+        assert_eq!(calendar.day_off(day(2026, 10, 10), None), Some(DayOff::Weekend(Weekday::Sat)));
+        assert!(matches!(calendar.day_off(day(2026, 10, 5), None), Some(DayOff::Holiday(_))));
+        assert_eq!(calendar.day_off(day(2026, 10, 5), Some("acme")), None);
+        assert_eq!(
+            calendar.day_off(day(2026, 6, 3), Some("acme")),
+            Some(DayOff::Holiday("holiday override".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_maintenance_windows() {
+        let mut nightly = entry("maintenance", None, Some("acme"));
+        nightly.weekday = Some(6); // every Sunday
+        nightly.start_time = Some("22:00".to_string());
+        nightly.end_time = Some("06:00".to_string());
+        let calendar = BusinessCalendar::new(vec![nightly, entry("maintenance", Some(day(2026, 10, 21)), None)]);
+
+        // Sunday 2026-10-18 22:00 until Monday 06:00
+        let (_, end) = calendar.maintenance(at(2026, 10, 19, "02:00"), Some("acme")).unwrap();
+        assert_eq!(end, at(2026, 10, 19, "06:00"));
+        assert!(calendar.maintenance(at(2026, 10, 19, "06:00"), Some("acme")).is_none());
+        assert!(calendar.maintenance(at(2026, 10, 19, "02:00"), Some("other")).is_none());
+        // Whole-day window for every company
+        assert!(calendar.maintenance(at(2026, 10, 21, "23:59"), Some("other")).is_some());
+    }
+}
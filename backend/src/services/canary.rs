@@ -0,0 +1,511 @@
+//! Canary Rollouts
+//!
+//! Sends a share of screen generations to a candidate prompt template or LLM
+//! config while the rest keep the stable one, then decides from both arms:
+//! - Routing: an unpinned generation in a running canary's slot takes the
+//!   canary arm with probability `percent`. Both arms are logged with the
+//!   canary (`generation_logs.canary_id` / `canary_arm`).
+//! - Evaluation (every minute): once each arm has `min_samples` finished
+//!   generations, the candidate is promoted unless its average ArtifactScorer
+//!   score dropped more than `max_score_drop` points or its error rate rose
+//!   more than `max_error_increase`, in which case it is rolled back.
+//!
+//! A promoted template canary goes through [`TemplateRollout::activate`], so
+//! the new version is still watched for warning spikes afterwards.
+
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{query::*, DatabaseConnection};
+use serde::Serialize;
+
+use crate::llm::resolve_llm_config;
+use crate::models::_entities::canary_rollouts::{self, Column, Entity};
+use crate::models::_entities::{generation_logs, llm_configs, prompt_templates, template_activations};
+use crate::services::admin::LlmConfigService;
+use crate::services::{GenerationPins, Notifier, TemplateRollout, TemplateService, TenantScope};
+
+/// Seconds between evaluation runs
+const TICK_SECS: u64 = 60;
+
+/// Actor recorded for promotions and rollbacks decided by the evaluator
+pub const CANARY_ACTOR: &str = "canary-rollout";
+
+/// Largest share of traffic a canary may take
+pub const MAX_PERCENT: i32 = 50;
+
+/// Generation statuses that have no result yet
+const UNFINISHED_STATUSES: [&str; 2] = ["queued", "processing"];
+
+/// Generation statuses counted as errors
+const FAILED_STATUSES: [&str; 2] = ["error", "failed"];
+
+/// Traffic share and decision thresholds of a canary
+#[derive(Debug, Clone, Serialize)]
+pub struct CanaryConfig {
+    /// Share of matching generations routed to the candidate (percent)
+    pub percent: i32,
+    /// Finished generations each arm needs before a decision
+    pub min_samples: i32,
+    /// Largest tolerated drop of the average quality score (points of 100)
+    pub max_score_drop: f32,
+    /// Largest tolerated rise of the error rate (0.05 = 5 points)
+    pub max_error_increase: f32,
+}
+
+impl Default for CanaryConfig {
+    fn default() -> Self {
+        Self {
+            percent: 10,
+            min_samples: 30,
+            max_score_drop: 5.0,
+            max_error_increase: 0.05,
+        }
+    }
+}
+
+impl CanaryConfig {
+    /// Defaults for new canaries (`CANARY_*` variables)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_string());
+        Self {
+            percent: var("CANARY_DEFAULT_PERCENT")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| (1..=MAX_PERCENT).contains(v))
+                .unwrap_or(defaults.percent),
+            min_samples: var("CANARY_MIN_SAMPLES")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.min_samples),
+            max_score_drop: var("CANARY_MAX_SCORE_DROP")
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f32| (0.0..=100.0).contains(v))
+                .unwrap_or(defaults.max_score_drop),
+            max_error_increase: var("CANARY_MAX_ERROR_INCREASE")
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f32| (0.0..=1.0).contains(v))
+                .unwrap_or(defaults.max_error_increase),
+        }
+    }
+
+    /// Thresholds a running canary was started with
+    pub fn of(canary: &canary_rollouts::Model) -> Self {
+        Self {
+            percent: canary.percent,
+            min_samples: canary.min_samples,
+            max_score_drop: canary.max_score_drop,
+            max_error_increase: canary.max_error_increase,
+        }
+    }
+
+    /// Promote or roll back once both arms have enough samples
+    pub fn decide(&self, stable: &ArmStats, canary: &ArmStats) -> Option<Decision> {
+        let min = self.min_samples.max(1) as u64;
+        if stable.samples < min || canary.samples < min {
+            return None;
+        }
+
+        let score_drop = stable.avg_score() - canary.avg_score();
+        let error_increase = canary.error_rate() - stable.error_rate();
+        let summary = format!(
+            "score {:.1} vs {:.1}, errors {:.0}% vs {:.0}% over {}/{} generations",
+            canary.avg_score(),
+            stable.avg_score(),
+            canary.error_rate() * 100.0,
+            stable.error_rate() * 100.0,
+            canary.samples,
+            stable.samples
+        );
+        if score_drop > self.max_score_drop {
+            return Some(Decision::RollBack(format!("Quality dropped: {}", summary)));
+        }
+        if error_increase > self.max_error_increase {
+            return Some(Decision::RollBack(format!("Error rate rose: {}", summary)));
+        }
+        Some(Decision::Promote(format!("Within thresholds: {}", summary)))
+    }
+}
+
+/// Outcome of an evaluation, with its reason
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decision {
+    Promote(String),
+    RollBack(String),
+}
+
+/// Path a generation took during a canary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanaryArm {
+    Canary,
+    Stable,
+}
+
+impl CanaryArm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Canary => "canary",
+            Self::Stable => "stable",
+        }
+    }
+}
+
+/// Finished generations of one arm
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ArmStats {
+    pub samples: u64,
+    pub errors: u64,
+    /// Generations with a quality score, and the sum of those scores
+    pub scored: u64,
+    pub score_sum: u64,
+}
+
+impl ArmStats {
+    fn add(&mut self, status: &str, quality_score: Option<i32>) {
+        self.samples += 1;
+        if FAILED_STATUSES.contains(&status) {
+            self.errors += 1;
+        }
+        if let Some(score) = quality_score {
+            self.scored += 1;
+            self.score_sum += score.max(0) as u64;
+        }
+    }
+
+    /// Average ArtifactScorer total (0-100) of the scored generations
+    pub fn avg_score(&self) -> f32 {
+        if self.scored == 0 {
+            return 0.0;
+        }
+        self.score_sum as f32 / self.scored as f32
+    }
+
+    pub fn error_rate(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.errors as f32 / self.samples as f32
+    }
+}
+
+/// Canary assignment of one generation
+#[derive(Debug, Clone)]
+pub struct CanaryRoute {
+    pub canary_id: i32,
+    pub arm: CanaryArm,
+    /// Candidate to use (canary arm only)
+    pub template: Option<prompt_templates::Model>,
+    pub llm_config: Option<llm_configs::Model>,
+}
+
+impl CanaryRoute {
+    /// `pins` with the candidate pinned on the canary arm
+    pub fn pin(&self, pins: &GenerationPins) -> GenerationPins {
+        GenerationPins {
+            template: pins.template.clone().or_else(|| self.template.clone()),
+            llm_config: pins.llm_config.clone().or_else(|| self.llm_config.clone()),
+            replay_of: pins.replay_of,
+        }
+    }
+}
+
+pub struct CanaryService;
+
+impl CanaryService {
+    /// Assign a screen generation to a running canary of its slot, if any
+    pub async fn route(
+        db: &DatabaseConnection,
+        product: &str,
+        screen_type: &str,
+        tenant: &TenantScope,
+    ) -> Option<CanaryRoute> {
+        match Self::find_route(db, product, screen_type, tenant).await {
+            Ok(route) => route,
+            Err(e) => {
+                tracing::warn!("Could not route generation to a canary: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_route(
+        db: &DatabaseConnection,
+        product: &str,
+        screen_type: &str,
+        tenant: &TenantScope,
+    ) -> Result<Option<CanaryRoute>> {
+        let running = Entity::find()
+            .filter(Column::Status.eq("running"))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await?;
+
+        for canary in running {
+            let take = Self::roll(canary.percent);
+            let arm = if take { CanaryArm::Canary } else { CanaryArm::Stable };
+            match canary.kind.as_str() {
+                "template" => {
+                    if canary.product.as_deref() != Some(product) || canary.screen_type.as_deref() != Some(screen_type) {
+                        continue;
+                    }
+                    // The canary's company must own the template the tenant would get
+                    let stable = TemplateService::get_active(db, product, Some(screen_type), tenant).await.ok();
+                    let company = match &stable {
+                        Some(stable) => stable.company.clone(),
+                        None => tenant.company().map(str::to_string),
+                    };
+                    if canary.company != company {
+                        continue;
+                    }
+                    let template = match (take, canary.candidate_template_id) {
+                        (true, Some(id)) => prompt_templates::Entity::find_by_id(id).one(db).await?,
+                        _ => None,
+                    };
+                    if take && template.is_none() {
+                        continue;
+                    }
+                    return Ok(Some(CanaryRoute { canary_id: canary.id, arm, template, llm_config: None }));
+                }
+                "llm_config" => {
+                    let Some(stable) = resolve_llm_config(db, tenant.company()).await else {
+                        continue;
+                    };
+                    if canary.company != stable.company {
+                        continue;
+                    }
+                    let llm_config = match (take, canary.candidate_llm_config_id) {
+                        (true, Some(id)) => llm_configs::Entity::find_by_id(id).one(db).await?,
+                        _ => None,
+                    };
+                    if take && llm_config.is_none() {
+                        continue;
+                    }
+                    return Ok(Some(CanaryRoute { canary_id: canary.id, arm, template: None, llm_config }));
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// True with a probability of `percent` percent
+    fn roll(percent: i32) -> bool {
+        (uuid::Uuid::new_v4().as_u128() % 100) < percent.clamp(0, 100) as u128
+    }
+
+    /// Finished generations of both arms as `(stable, canary)`
+    pub async fn stats(db: &DatabaseConnection, canary_id: i32) -> Result<(ArmStats, ArmStats)> {
+        use generation_logs::Column as Log;
+
+        let rows = generation_logs::Entity::find()
+            .select_only()
+            .column(Log::CanaryArm)
+            .column(Log::Status)
+            .column(Log::QualityScore)
+            .filter(Log::CanaryId.eq(canary_id))
+            .filter(Log::Status.is_not_in(UNFINISHED_STATUSES))
+            .into_tuple::<(Option<String>, String, Option<i32>)>()
+            .all(db)
+            .await?;
+
+        let (mut stable, mut canary) = (ArmStats::default(), ArmStats::default());
+        for (arm, status, score) in rows {
+            match arm.as_deref() {
+                Some("canary") => canary.add(&status, score),
+                _ => stable.add(&status, score),
+            }
+        }
+        Ok((stable, canary))
+    }
+
+    /// Move a canary from `running` to `to`; false if already decided
+    async fn claim(db: &DatabaseConnection, id: i32, to: &str) -> Result<bool> {
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(to))
+            .col_expr(Column::UpdatedAt, Expr::value(DateTimeWithTimeZone::from(Utc::now())))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq("running"))
+            .exec(db)
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    /// Label of a canary's candidate for messages
+    pub async fn subject(db: &DatabaseConnection, canary: &canary_rollouts::Model) -> Result<String> {
+        let subject = match (canary.candidate_template_id, canary.candidate_llm_config_id) {
+            (Some(id), _) => prompt_templates::Entity::find_by_id(id)
+                .one(db)
+                .await?
+                .map(|t| format!("template {} v{}", t.name, t.version)),
+            (None, Some(id)) => llm_configs::Entity::find_by_id(id)
+                .one(db)
+                .await?
+                .map(|c| format!("LLM config {}", c.name)),
+            (None, None) => None,
+        };
+        Ok(subject.unwrap_or_else(|| format!("canary #{}", canary.id)))
+    }
+
+    /// Make the candidate the active version (`actor` = deciding administrator or the evaluator)
+    pub async fn promote(
+        db: &DatabaseConnection,
+        canary: canary_rollouts::Model,
+        actor: &str,
+        reason: &str,
+    ) -> Result<canary_rollouts::Model> {
+        if !Self::claim(db, canary.id, "promoted").await? {
+            return Ok(Entity::find_by_id(canary.id).one(db).await?.unwrap_or(canary));
+        }
+
+        if let Some(activation_id) = canary.template_activation_id {
+            if TemplateRollout::claim(db, activation_id, "canary", "scheduled").await? {
+                if let Some(activation) = template_activations::Entity::find_by_id(activation_id).one(db).await? {
+                    let reviewer = activation.reviewed_by.clone().unwrap_or_else(|| actor.to_string());
+                    TemplateRollout::activate(db, activation, &reviewer).await?;
+                }
+            }
+        }
+        if let Some(config_id) = canary.candidate_llm_config_id {
+            LlmConfigService::activate(db, &TenantScope::Platform, actor, config_id).await?;
+        }
+
+        Self::decided(db, canary, actor, true, reason).await
+    }
+
+    /// Stop routing to the candidate and keep the stable version
+    pub async fn roll_back(
+        db: &DatabaseConnection,
+        canary: canary_rollouts::Model,
+        actor: &str,
+        reason: &str,
+    ) -> Result<canary_rollouts::Model> {
+        if !Self::claim(db, canary.id, "rolled_back").await? {
+            return Ok(Entity::find_by_id(canary.id).one(db).await?.unwrap_or(canary));
+        }
+
+        if let Some(activation_id) = canary.template_activation_id {
+            if TemplateRollout::claim(db, activation_id, "canary", "rolled_back").await? {
+                if let Some(activation) = template_activations::Entity::find_by_id(activation_id).one(db).await? {
+                    let mut item: template_activations::ActiveModel = activation.into();
+                    item.rolled_back_at = Set(Some(Utc::now().into()));
+                    item.rollback_reason = Set(Some(format!("Canary rolled back: {}", reason)));
+                    item.update(db).await?;
+                }
+            }
+        }
+
+        Self::decided(db, canary, actor, false, reason).await
+    }
+
+    /// Record the outcome with the arm statistics at the decision
+    async fn decided(
+        db: &DatabaseConnection,
+        canary: canary_rollouts::Model,
+        actor: &str,
+        promoted: bool,
+        reason: &str,
+    ) -> Result<canary_rollouts::Model> {
+        let (stable, current) = Self::stats(db, canary.id).await?;
+        if actor == CANARY_ACTOR {
+            let subject = Self::subject(db, &canary).await?;
+            Notifier::canary_decided(db, &subject, promoted, reason);
+        }
+
+        let outcome = if actor == CANARY_ACTOR {
+            reason.to_string()
+        } else {
+            format!("{} (by {})", reason, actor)
+        };
+        let mut item: canary_rollouts::ActiveModel = canary.into();
+        item.decided_at = Set(Some(Utc::now().into()));
+        item.outcome = Set(Some(outcome));
+        item.stable_samples = Set(Some(stable.samples as i32));
+        item.stable_score = Set(Some(stable.avg_score()));
+        item.canary_samples = Set(Some(current.samples as i32));
+        item.canary_score = Set(Some(current.avg_score()));
+        Ok(item.update(db).await?)
+    }
+
+    /// One evaluator run: decide every running canary with enough samples
+    pub async fn evaluate(db: &DatabaseConnection) -> Result<()> {
+        let running = Entity::find().filter(Column::Status.eq("running")).all(db).await?;
+        for canary in running {
+            let (stable, current) = Self::stats(db, canary.id).await?;
+            match CanaryConfig::of(&canary).decide(&stable, &current) {
+                Some(Decision::Promote(reason)) => {
+                    tracing::info!("Promoting canary #{}: {}", canary.id, reason);
+                    Self::promote(db, canary, CANARY_ACTOR, &reason).await?;
+                }
+                Some(Decision::RollBack(reason)) => {
+                    tracing::warn!("Rolling back canary #{}: {}", canary.id, reason);
+                    Self::roll_back(db, canary, CANARY_ACTOR, &reason).await?;
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Start the background canary evaluator
+pub fn start_evaluator(db: DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(TICK_SECS));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = CanaryService::evaluate(&db).await {
+                tracing::warn!("Canary evaluation failed: {}", e);
+            }
+        }
+    });
+
+    tracing::info!("Canary evaluator started");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arm(samples: u64, errors: u64, avg_score: u64) -> ArmStats {
+        let scored = samples - errors;
+        ArmStats { samples, errors, scored, score_sum: scored * avg_score }
+    }
+
+    #[test]
+    fn test_decide_waits_for_both_arms() {
+        let config = CanaryConfig::default();
+        assert_eq!(config.decide(&arm(500, 0, 80), &arm(29, 0, 80)), None);
+        assert_eq!(config.decide(&arm(29, 0, 80), &arm(30, 0, 80)), None);
+    }
+
+    #[test]
+    fn test_decide_promotes_within_thresholds() {
+        let config = CanaryConfig::default();
+        // 4 points lower, 3 points more errors: tolerated
+        let decision = config.decide(&arm(100, 2, 80), &arm(40, 2, 76));
+        assert!(matches!(decision, Some(Decision::Promote(_))));
+    }
+
+    #[test]
+    fn test_decide_rolls_back_on_score_drop_or_errors() {
+        let config = CanaryConfig::default();
+        let decision = config.decide(&arm(100, 0, 80), &arm(40, 0, 70));
+        assert!(matches!(decision, Some(Decision::RollBack(reason)) if reason.starts_with("Quality dropped")));
+
+        let decision = config.decide(&arm(100, 1, 80), &arm(40, 4, 80));
+        assert!(matches!(decision, Some(Decision::RollBack(reason)) if reason.starts_with("Error rate rose")));
+    }
+
+    #[test]
+    fn test_arm_stats_averages_scored_generations() {
+        let mut stats = ArmStats::default();
+        stats.add("success", Some(90));
+        stats.add("partial_success", Some(70));
+        stats.add("error", None);
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.avg_score(), 80.0);
+        assert!((stats.error_rate() - 1.0 / 3.0).abs() < 1e-6);
+    }
+}
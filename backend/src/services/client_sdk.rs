@@ -0,0 +1,251 @@
+//! Client SDK Generation
+//!
+//! Renders a dependency-free TypeScript client (`fetch` based) from the
+//! OpenAPI document, so the IDE plugin and the web console compile against
+//! the server's current request/response shapes. Written by the `client_sdk`
+//! task together with `openapi.json`, which other generators (e.g. Java) can
+//! consume.
+//!
+//! Method and type names come from the operation IDs
+//! (`post_agent_generate` → `postAgentGenerate`, `PostAgentGenerateRequest`).
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+pub struct TypeScriptClient;
+
+impl TypeScriptClient {
+    /// TypeScript module for an OpenAPI document
+    pub fn render(doc: &Value) -> String {
+        let mut types = String::new();
+        let mut methods = String::new();
+
+        let empty = serde_json::Map::new();
+        for (path, item) in doc["paths"].as_object().unwrap_or(&empty) {
+            for (method, operation) in item.as_object().unwrap_or(&empty) {
+                let id = operation["operationId"].as_str().unwrap_or_default();
+                Self::render_operation(path, method, id, operation, &mut types, &mut methods);
+            }
+        }
+
+        format!(
+            "// Generated by `cargo loco task client_sdk` from the OpenAPI document (API {version}).\n\
+             // Do not edit: regenerate after API changes.\n\n{prelude}\n{types}\
+             export class CodeGeneratorClient {{\n  private readonly baseUrl: string;\n\n  \
+             constructor(baseUrl: string, private readonly options: ClientOptions = {{}}) {{\n    \
+             this.baseUrl = baseUrl.replace(/\\/+$/, \"\");\n  }}\n{methods}\n{request}}}\n",
+            version = doc["info"]["version"].as_str().unwrap_or("unknown"),
+            prelude = PRELUDE,
+            request = REQUEST,
+        )
+    }
+
+    fn render_operation(path: &str, method: &str, id: &str, operation: &Value, types: &mut String, methods: &mut String) {
+        let type_name = Self::pascal_case(id);
+        let json_body = |value: &Value| value["content"]["application/json"]["schema"].clone();
+
+        let mut params = Vec::new();
+        let mut url = path.to_string();
+        let mut query = Vec::new();
+        for param in operation["parameters"].as_array().into_iter().flatten() {
+            let name = param["name"].as_str().unwrap_or_default();
+            match param["in"].as_str() {
+                Some("path") => {
+                    let arg = Self::camel_case(name);
+                    let kind = if param["schema"]["type"] == "integer" { "number" } else { "string" };
+                    url = url.replace(&format!("{{{}}}", name), &format!("${{encodeURIComponent(String({}))}}", arg));
+                    params.push(format!("{}: {}", arg, kind));
+                }
+                Some("query") => query.push(name.to_string()),
+                _ => {}
+            }
+        }
+
+        let body = match operation.get("requestBody") {
+            Some(request) => {
+                let schema = json_body(request);
+                let name = format!("{}Request", type_name);
+                let _ = writeln!(types, "export type {} = {};\n", name, Self::type_of(&schema, 0));
+                params.push(format!("body: {}", name));
+                "body"
+            }
+            None => "undefined",
+        };
+        if !query.is_empty() {
+            let name = format!("{}Query", type_name);
+            let fields: String = query.iter().map(|q| format!("  {}?: string | number | boolean;\n", Self::property(q))).collect();
+            let _ = writeln!(types, "export type {} = {{\n{}}};\n", name, fields);
+            params.push(format!("query?: {}", name));
+        }
+
+        // Downloads (ZIP) and undocumented responses
+        let ok = &operation["responses"]["200"];
+        let (returns, raw) = if path.ends_with("/download") {
+            ("Response".to_string(), true)
+        } else if ok.get("content").is_some() {
+            let name = format!("{}Response", type_name);
+            let _ = writeln!(types, "export type {} = {};\n", name, Self::type_of(&json_body(ok), 0));
+            (name, false)
+        } else {
+            ("unknown".to_string(), false)
+        };
+
+        let _ = write!(
+            methods,
+            "\n  /** {} ({} {}) */\n  {}({}): Promise<{}> {{\n    return this.request(\"{}\", `{}`, {}, {}, {});\n  }}\n",
+            operation["summary"].as_str().unwrap_or_default(),
+            method.to_uppercase(),
+            path,
+            Self::camel_case(id),
+            params.join(", "),
+            returns,
+            method.to_uppercase(),
+            url,
+            if query.is_empty() { "undefined" } else { "query" },
+            body,
+            raw,
+        );
+    }
+
+    /// TypeScript type of a schema (object properties are optional)
+    pub fn type_of(schema: &Value, indent: usize) -> String {
+        let base = match schema["type"].as_str() {
+            Some("string") => "string".to_string(),
+            Some("integer" | "number") => "number".to_string(),
+            Some("boolean") => "boolean".to_string(),
+            Some("array") => {
+                let item = Self::type_of(&schema["items"], indent);
+                if item.contains(" | ") { format!("({})[]", item) } else { format!("{}[]", item) }
+            }
+            Some("object") => match schema["properties"].as_object().filter(|p| !p.is_empty()) {
+                Some(properties) => {
+                    let pad = "  ".repeat(indent + 1);
+                    let fields: String = properties
+                        .iter()
+                        .map(|(name, property)| {
+                            format!("{}{}?: {};\n", pad, Self::property(name), Self::type_of(property, indent + 1))
+                        })
+                        .collect();
+                    format!("{{\n{}{}}}", fields, "  ".repeat(indent))
+                }
+                None => "Record<string, unknown>".to_string(),
+            },
+            _ => return "unknown".to_string(),
+        };
+        if schema["nullable"] == true { format!("{} | null", base) } else { base }
+    }
+
+    /// Property name, quoted unless it is a plain identifier
+    fn property(name: &str) -> String {
+        let plain = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if plain { name.to_string() } else { format!("\"{}\"", name) }
+    }
+
+    fn pascal_case(id: &str) -> String {
+        id.split('_')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                chars.next().map_or_else(String::new, |first| first.to_ascii_uppercase().to_string() + chars.as_str())
+            })
+            .collect()
+    }
+
+    fn camel_case(id: &str) -> String {
+        let pascal = Self::pascal_case(id);
+        let mut chars = pascal.chars();
+        chars.next().map_or_else(String::new, |first| first.to_ascii_lowercase().to_string() + chars.as_str())
+    }
+}
+
+const PRELUDE: &str = r#"/** Non-2xx answer of the server */
+export class ApiError extends Error {
+  constructor(public readonly status: number, public readonly body: string) {
+    super(`HTTP ${status}: ${body}`);
+  }
+}
+
+export interface ClientOptions {
+  /** JWT for the `/api/my/...` endpoints */
+  token?: string;
+  /** fetch implementation (default: the global fetch) */
+  fetch?: typeof fetch;
+}
+"#;
+
+const REQUEST: &str = r#"  private async request<T>(method: string, path: string, query: object | undefined, body: unknown, raw: boolean): Promise<T> {
+    const url = new URL(this.baseUrl + path);
+    for (const [key, value] of Object.entries(query ?? {})) {
+      if (value !== undefined && value !== null) url.searchParams.set(key, String(value));
+    }
+    const headers: Record<string, string> = { Accept: "application/json" };
+    if (body !== undefined) headers["Content-Type"] = "application/json";
+    if (this.options.token) headers["Authorization"] = `Bearer ${this.options.token}`;
+
+    const response = await (this.options.fetch ?? fetch)(url.toString(), {
+      method,
+      headers,
+      body: body === undefined ? undefined : JSON.stringify(body),
+    });
+    if (!response.ok) throw new ApiError(response.status, await response.text());
+    if (raw) return response as unknown as T;
+    const text = await response.text();
+    return ((response.headers.get("Content-Type") ?? "").includes("json") ? JSON.parse(text) : text) as T;
+  }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::openapi::{ApiOperation, OpenApi};
+    use serde_json::json;
+
+    fn client() -> String {
+        let routes = vec![
+            ("/agent/jobs/{job_id}".to_string(), vec!["GET".to_string()]),
+            ("/agent/jobs/{job_id}/download".to_string(), vec!["GET".to_string()]),
+            ("/agent/review/apply".to_string(), vec!["POST".to_string()]),
+        ];
+        let operations = [
+            ApiOperation::new("get", "/agent/jobs/{job_id}", "jobs", "Status of a job")
+                .query("wait", "Long-poll")
+                .response(json!({ "job_id": "j", "queue_position": 2, "error": null })),
+            ApiOperation::new("post", "/agent/review/apply", "review", "Apply review patches")
+                .request(json!({ "code": "<screen/>", "patches": ["@@"], "x-mode": true })),
+        ];
+        TypeScriptClient::render(&OpenApi::document(&routes, &operations))
+    }
+
+    #[test]
+    fn test_renders_typed_methods() {
+        let ts = client();
+        assert!(ts.contains(
+            "export type GetAgentJobsJobIdResponse = {\n  error?: unknown;\n  job_id?: string;\n  queue_position?: number;\n};"
+        ));
+        assert!(ts.contains(
+            "  getAgentJobsJobId(jobId: string, query?: GetAgentJobsJobIdQuery): Promise<GetAgentJobsJobIdResponse> {\n    \
+             return this.request(\"GET\", `/agent/jobs/${encodeURIComponent(String(jobId))}`, query, undefined, false);"
+        ));
+        assert!(ts.contains("export type PostAgentReviewApplyRequest = {\n  code?: string;\n  patches?: string[];\n  \"x-mode\"?: boolean;\n};"));
+        assert!(ts.contains("postAgentReviewApply(body: PostAgentReviewApplyRequest): Promise<unknown>"));
+    }
+
+    #[test]
+    fn test_downloads_return_raw_response() {
+        let ts = client();
+        assert!(ts.contains("getAgentJobsJobIdDownload(jobId: string): Promise<Response>"));
+        assert!(ts.contains("undefined, undefined, true);"));
+    }
+
+    #[test]
+    fn test_nullable_types() {
+        assert_eq!(TypeScriptClient::type_of(&json!({ "type": "string", "nullable": true }), 0), "string | null");
+        assert_eq!(
+            TypeScriptClient::type_of(&json!({ "type": "array", "items": { "type": "integer", "nullable": true } }), 0),
+            "(number | null)[]"
+        );
+        assert_eq!(TypeScriptClient::type_of(&json!({ "type": "object" }), 0), "Record<string, unknown>");
+    }
+}
@@ -0,0 +1,294 @@
+//! Public Demo Mode
+//!
+//! For sales demos on machines without a GPU. With `DEMO_MODE=true`,
+//! `/agent/generate` answers with curated sample screens picked by the entity
+//! the input names (member, order, notice). The sample goes through the normal
+//! parser and validators in place of LLM output, so warnings about the input
+//! show as they would in production. Every artifact is watermarked, each
+//! client gets `DEMO_RATE_LIMIT` generations per hour (default 10), and
+//! nothing of the request is written to the database.
+//!
+//! The client is the socket peer. Forwarded headers are only believed from
+//! the reverse proxies listed in `DEMO_TRUSTED_PROXIES`, since anyone else
+//! could send a fresh address with every request.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+
+use crate::domain::{GenerateInput, GenerateResponse};
+use crate::llm::{MockLlmBackend, MockResponse};
+use crate::services::GenerationService;
+
+/// Line put at the top of every demo artifact
+pub const WATERMARK: &str = "DEMO OUTPUT - curated sample, not generated from your input";
+
+/// Product the samples are written for
+const DEMO_PRODUCT: &str = "xframe5-ui";
+
+const RATE_WINDOW: Duration = Duration::from_secs(3600);
+
+/// A curated screen and the entity words that select it
+pub struct DemoSample {
+    pub entity: &'static str,
+    keywords: &'static [&'static str],
+    output: &'static str,
+}
+
+/// Samples in preference order; the first is the fallback
+const SAMPLES: &[DemoSample] = &[
+    DemoSample {
+        entity: "member",
+        keywords: &["member", "user", "customer", "회원", "고객", "사용자"],
+        output: include_str!("../fixtures/demo/member_list.txt"),
+    },
+    DemoSample {
+        entity: "order",
+        keywords: &["order", "sales", "purchase", "주문", "매출", "구매"],
+        output: include_str!("../fixtures/demo/order_list.txt"),
+    },
+    DemoSample {
+        entity: "notice",
+        keywords: &["notice", "board", "bbs", "article", "공지", "게시"],
+        output: include_str!("../fixtures/demo/notice_list.txt"),
+    },
+];
+
+/// Demo mode settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoConfig {
+    pub enabled: bool,
+    /// Generations per client per hour
+    pub rate_limit: u32,
+    /// Peers whose `X-Forwarded-For` / `X-Real-IP` name the client
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl DemoConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("DEMO_MODE").is_ok_and(|v| v.trim().eq_ignore_ascii_case("true")),
+            rate_limit: std::env::var("DEMO_RATE_LIMIT")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(10),
+            trusted_proxies: std::env::var("DEMO_TRUSTED_PROXIES")
+                .map(|v| v.split(',').filter_map(|ip| ip.trim().parse().ok()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Address a request is rate limited by: the socket peer, or the address
+    /// a trusted proxy forwarded for
+    pub fn client_address(&self, peer: Option<SocketAddr>, headers: &axum::http::HeaderMap) -> String {
+        let Some(peer) = peer.map(|addr| addr.ip()) else {
+            return "unknown".to_string();
+        };
+        if !self.trusted_proxies.contains(&peer) {
+            return peer.to_string();
+        }
+
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        // Proxies append to X-Forwarded-For: the last untrusted hop is the client
+        let forwarded = header("x-forwarded-for").and_then(|v| {
+            v.rsplit(',')
+                .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+                .find(|ip| !self.trusted_proxies.contains(ip))
+        });
+        forwarded
+            .or_else(|| header("x-real-ip").and_then(|v| v.trim().parse().ok()))
+            .unwrap_or(peer)
+            .to_string()
+    }
+}
+
+/// Fixed one-hour window per client
+#[derive(Default)]
+pub struct DemoRateLimiter {
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl DemoRateLimiter {
+    /// Count a request of `client`; `Err` holds the seconds until its window resets
+    pub fn check(&self, client: &str, limit: u32, now: Instant) -> Result<(), u64> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        windows.retain(|_, (start, _)| now.duration_since(*start) < RATE_WINDOW);
+
+        let (start, count) = windows.entry(client.to_string()).or_insert((now, 0));
+        if *count >= limit {
+            let reset = RATE_WINDOW.saturating_sub(now.duration_since(*start));
+            return Err(reset.as_secs().max(1));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Serves demo generations
+pub struct DemoService;
+
+impl DemoService {
+    /// Settings read from env on first use
+    pub fn config() -> &'static DemoConfig {
+        static CONFIG: OnceLock<DemoConfig> = OnceLock::new();
+        CONFIG.get_or_init(DemoConfig::from_env)
+    }
+
+    pub fn limiter() -> &'static DemoRateLimiter {
+        static LIMITER: OnceLock<DemoRateLimiter> = OnceLock::new();
+        LIMITER.get_or_init(DemoRateLimiter::default)
+    }
+
+    /// Sample for the entity the input names, else the first one
+    pub fn sample_for(input: &GenerateInput) -> &'static DemoSample {
+        let text = match input {
+            GenerateInput::DbSchema(schema) => schema.table.clone(),
+            GenerateInput::QuerySample(sample) => sample.query.clone(),
+            GenerateInput::NaturalLanguage(nl) => nl.description.clone(),
+            GenerateInput::Dashboard(dashboard) => [&dashboard.screen_name, &dashboard.description]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" "),
+            GenerateInput::Intent(intent) => intent.screen_name.clone(),
+            GenerateInput::SavedIntent(saved) => saved.name.clone(),
+            GenerateInput::Dsl(dsl) => dsl.yaml.lines().next().unwrap_or_default().to_string(),
+        }
+        .to_lowercase();
+        SAMPLES
+            .iter()
+            .find(|sample| sample.keywords.iter().any(|k| text.contains(k)))
+            .unwrap_or(&SAMPLES[0])
+    }
+
+    /// Run the sample of `input` through the pipeline and watermark the result
+    pub async fn generate(input: GenerateInput, product: &str) -> Result<GenerateResponse> {
+        if product != DEMO_PRODUCT {
+            bail!("Demo mode only generates {} screens", DEMO_PRODUCT);
+        }
+        if matches!(input, GenerateInput::SavedIntent(_)) {
+            bail!("Saved intents are not available in demo mode");
+        }
+
+        let sample = Self::sample_for(&input);
+        let llm = MockLlmBackend::with_responses(vec![MockResponse::Success(sample.output.to_string())]);
+        let mut response = GenerationService::generate_with_backend(input, product, None, &llm).await?;
+        Self::watermark(&mut response, sample);
+        Ok(response)
+    }
+
+    fn watermark(response: &mut GenerateResponse, sample: &DemoSample) {
+        if let Some(artifacts) = response.artifacts.as_mut() {
+            if let Some(xml) = artifacts.xml.as_mut() {
+                *xml = watermark_xml(xml);
+            }
+            if let Some(js) = artifacts.javascript.as_mut() {
+                *js = format!("// {}\n{}", WATERMARK, js);
+            }
+            for screen in &mut artifacts.additional_screens {
+                screen.xml = watermark_xml(&screen.xml);
+                screen.javascript = format!("// {}\n{}", WATERMARK, screen.javascript);
+            }
+        }
+        response.warnings.insert(
+            0,
+            format!(
+                "Demo mode: showing the curated '{}' sample; connect a model server to generate from your input",
+                sample.entity
+            ),
+        );
+        response.meta.generator = format!("{}-demo", DEMO_PRODUCT);
+    }
+}
+
+/// Comment after the XML declaration (a comment may not precede it)
+fn watermark_xml(xml: &str) -> String {
+    let comment = format!("<!-- {} -->", WATERMARK);
+    match xml.trim_start().strip_prefix("<?xml").and_then(|rest| rest.split_once("?>")) {
+        Some((declaration, body)) => format!("<?xml{}?>\n{}{}", declaration, comment, body),
+        None => format!("{}\n{}", comment, xml),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{NaturalLanguageInput, SchemaColumn, SchemaInput};
+
+    #[test]
+    fn test_sample_follows_entity_keyword() {
+        let order = GenerateInput::DbSchema(SchemaInput::new("TB_ORDER"));
+        assert_eq!(DemoService::sample_for(&order).entity, "order");
+
+        let notice = GenerateInput::NaturalLanguage(NaturalLanguageInput::new("공지사항 목록 화면"));
+        assert_eq!(DemoService::sample_for(&notice).entity, "notice");
+
+        let other = GenerateInput::DbSchema(SchemaInput::new("TB_WAREHOUSE"));
+        assert_eq!(DemoService::sample_for(&other).entity, "member");
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let limiter = DemoRateLimiter::default();
+        let start = Instant::now();
+        assert!(limiter.check("10.0.0.1", 2, start).is_ok());
+        assert!(limiter.check("10.0.0.1", 2, start).is_ok());
+        let retry = limiter.check("10.0.0.1", 2, start + Duration::from_secs(600)).unwrap_err();
+        assert_eq!(retry, 3000);
+        assert!(limiter.check("10.0.0.2", 2, start).is_ok());
+        assert!(limiter.check("10.0.0.1", 2, start + RATE_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_client_address_trusts_only_configured_proxies() {
+        let config = DemoConfig {
+            enabled: true,
+            rate_limit: 10,
+            trusted_proxies: vec!["10.0.0.9".parse().unwrap()],
+        };
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("x-forwarded-for", "1.2.3.4, 203.0.113.7".parse().unwrap());
+        let peer = |ip: &str| Some(SocketAddr::new(ip.parse().unwrap(), 40000));
+
+        // A direct client cannot pick its own address
+        assert_eq!(config.client_address(peer("198.51.100.1"), &headers), "198.51.100.1");
+        // Behind the proxy, the last hop it appended is the client
+        assert_eq!(config.client_address(peer("10.0.0.9"), &headers), "203.0.113.7");
+        assert_eq!(config.client_address(peer("10.0.0.9"), &axum::http::HeaderMap::new()), "10.0.0.9");
+        assert_eq!(config.client_address(None, &headers), "unknown");
+    }
+
+    #[test]
+    fn test_watermark_xml_keeps_declaration_first() {
+        let xml = watermark_xml("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<screen id=\"SCREEN_A\"/>");
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!-- DEMO OUTPUT"));
+        assert!(xml.ends_with("<screen id=\"SCREEN_A\"/>"));
+
+        assert!(watermark_xml("<screen/>").starts_with("<!-- DEMO OUTPUT"));
+    }
+
+    #[tokio::test]
+    async fn test_demo_generation_is_watermarked() {
+        let input = GenerateInput::DbSchema(
+            SchemaInput::new("TB_MEMBER")
+                .with_column(SchemaColumn::new("MEMBER_ID", "VARCHAR(20)"))
+                .with_column(SchemaColumn::new("MEMBER_NAME", "VARCHAR(50)"))
+                .with_primary_key("MEMBER_ID"),
+        );
+        let response = DemoService::generate(input, "xframe5-ui").await.unwrap();
+
+        let artifacts = response.artifacts.unwrap();
+        assert!(artifacts.xml.unwrap().contains(WATERMARK));
+        assert!(artifacts.javascript.unwrap().starts_with(&format!("// {}", WATERMARK)));
+        assert!(response.warnings[0].starts_with("Demo mode"));
+        assert_eq!(response.meta.generator, "xframe5-ui-demo");
+
+        let spring = GenerateInput::DbSchema(SchemaInput::new("TB_MEMBER"));
+        assert!(DemoService::generate(spring, "spring-backend").await.is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Distillation Corpus
+//!
+//! Validated screens generated by a remote provider are kept as examples,
+//! keyed by features of their intent (screen type, tables, columns, widget
+//! types, actions). When a local model generates a similar screen later, the
+//! nearest example is added to its prompt as few-shot context, which lifts
+//! on-prem quality without fine-tuning.
+//!
+//! Capture is off until enabled in the admin panel, where the corpus size per
+//! tenant and the eviction policy are set. Examples never cross tenants.
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set};
+
+use crate::domain::{GeneratedArtifacts, UiIntent};
+use crate::models::_entities::distillation_examples::Column;
+use crate::models::{distillation_examples, distillation_settings};
+
+/// Providers whose outputs are worth distilling (everything else runs on-prem)
+const REMOTE_PROVIDERS: [&str; 3] = ["openai", "anthropic", "groq"];
+
+/// Examples less similar than this are not offered
+const MIN_SIMILARITY: f32 = 0.3;
+
+/// Characters of each example file put into a prompt
+const MAX_EXAMPLE_CHARS: usize = 6000;
+
+/// Whether a backend name is a remote provider
+pub fn is_remote_provider(provider: &str) -> bool {
+    REMOTE_PROVIDERS.contains(&provider)
+}
+
+pub struct DistillationService;
+
+impl DistillationService {
+    /// Features of an intent, as sorted and deduplicated tokens
+    pub fn intent_features(intent: &UiIntent) -> Vec<String> {
+        let mut features = BTreeSet::new();
+        features.insert(format!("type:{}", intent.screen_type.as_str()));
+        for word in intent.screen_name.to_lowercase().split(['_', '-', ' ']).filter(|w| !w.is_empty()) {
+            features.insert(format!("name:{}", word));
+        }
+        for dataset in &intent.datasets {
+            if let Some(table) = &dataset.table_name {
+                features.insert(format!("table:{}", table.to_lowercase()));
+            }
+            for column in &dataset.columns {
+                features.insert(format!("col:{}", column.name.to_lowercase()));
+                features.insert(format!("ui:{}", column.ui_type.as_str()));
+            }
+        }
+        for grid in &intent.grids {
+            for (flag, set) in [("editable", grid.editable), ("selectable", grid.selectable), ("paged", grid.paginated)] {
+                if set {
+                    features.insert(format!("grid:{}", flag));
+                }
+            }
+        }
+        for action in &intent.actions {
+            features.insert(format!("action:{}", action.id.to_lowercase()));
+        }
+        features.into_iter().collect()
+    }
+
+    /// Jaccard similarity of two space separated feature lists
+    pub fn similarity(a: &str, b: &str) -> f32 {
+        let a: BTreeSet<&str> = a.split_whitespace().collect();
+        let b: BTreeSet<&str> = b.split_whitespace().collect();
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 0.0;
+        }
+        a.intersection(&b).count() as f32 / union as f32
+    }
+
+    /// Keep a validated remote output as an example (no-op when disabled or
+    /// below the quality bar)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn capture(
+        db: &DatabaseConnection,
+        product: &str,
+        intent: &UiIntent,
+        artifacts: &GeneratedArtifacts,
+        quality_score: i32,
+        provider: &str,
+        model: &str,
+        company: Option<&str>,
+    ) -> Result<bool> {
+        let settings = distillation_settings::Model::current(db).await;
+        if !settings.enabled || !is_remote_provider(provider) || quality_score < settings.min_quality {
+            return Ok(false);
+        }
+        let (Some(xml), Some(javascript)) = (&artifacts.xml, &artifacts.javascript) else {
+            return Ok(false);
+        };
+
+        distillation_examples::ActiveModel {
+            product: Set(product.to_string()),
+            screen_type: Set(intent.screen_type.as_str().to_string()),
+            features: Set(Self::intent_features(intent).join(" ")),
+            ui_intent: Set(serde_json::to_string(intent)?),
+            xml: Set(xml.clone()),
+            javascript: Set(javascript.clone()),
+            source_provider: Set(provider.to_string()),
+            source_model: Set(model.to_string()),
+            quality_score: Set(Some(quality_score)),
+            company: Set(company.map(str::to_string)),
+            pinned: Set(false),
+            use_count: Set(0),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Self::evict(db, &settings, company).await?;
+        Ok(true)
+    }
+
+    /// Most similar example for a local model's prompt, if any is close enough
+    pub async fn nearest(
+        db: &DatabaseConnection,
+        product: &str,
+        intent: &UiIntent,
+        company: Option<&str>,
+    ) -> Result<Option<distillation_examples::Model>> {
+        if !distillation_settings::Model::current(db).await.enabled {
+            return Ok(None);
+        }
+
+        let features = Self::intent_features(intent).join(" ");
+        let candidates =
+            distillation_examples::Model::candidates(db, product, intent.screen_type.as_str(), company).await?;
+        let best = candidates
+            .into_iter()
+            .map(|example| (Self::similarity(&features, &example.features), example))
+            .filter(|(score, _)| *score >= MIN_SIMILARITY)
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        match best {
+            Some((score, example)) => {
+                tracing::info!("Using distillation example {} (similarity {:.2})", example.id, score);
+                Ok(Some(example.mark_used(db).await?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Prompt section showing an example's output
+    pub fn few_shot_section(example: &distillation_examples::Model) -> String {
+        format!(
+            "## Reference Example\n\
+            A validated screen for a similar request. Follow its structure and conventions, \
+            but generate only what the current request asks for.\n\n\
+            --- XML ---\n{}\n\n--- JS ---\n{}",
+            truncate(&example.xml, MAX_EXAMPLE_CHARS),
+            truncate(&example.javascript, MAX_EXAMPLE_CHARS)
+        )
+    }
+
+    /// Delete unpinned examples of a tenant beyond the corpus size, returning
+    /// how many were removed
+    pub async fn evict(
+        db: &DatabaseConnection,
+        settings: &distillation_settings::Model,
+        company: Option<&str>,
+    ) -> Result<u64> {
+        let owned = match company {
+            Some(code) => Column::Company.eq(code),
+            None => Column::Company.is_null(),
+        };
+        let total = distillation_examples::Entity::find()
+            .filter(owned.clone())
+            .count(db)
+            .await?;
+        let excess = total.saturating_sub(settings.max_examples.max(0) as u64);
+        if excess == 0 {
+            return Ok(0);
+        }
+
+        let query = distillation_examples::Entity::find()
+            .filter(owned)
+            .filter(Column::Pinned.eq(false));
+        let query = match settings.eviction.as_str() {
+            "oldest" => query.order_by_asc(Column::CreatedAt),
+            "lowest_quality" => query
+                .order_by_asc(Column::QualityScore)
+                .order_by_asc(Column::CreatedAt),
+            // lru: never used first, then by last use
+            _ => query
+                .order_by_asc(Column::LastUsedAt.is_not_null())
+                .order_by_asc(Column::LastUsedAt)
+                .order_by_asc(Column::CreatedAt),
+        };
+        let victims: Vec<i32> = query
+            .paginate(db, excess)
+            .fetch_page(0)
+            .await?
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+
+        let removed = distillation_examples::Entity::delete_many()
+            .filter(Column::Id.is_in(victims))
+            .exec(db)
+            .await?
+            .rows_affected;
+        if removed > 0 {
+            tracing::info!("Evicted {} distillation example(s) ({})", removed, settings.eviction);
+        }
+        Ok(removed)
+    }
+}
+
+/// First `max` characters of `text`, on a char boundary
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}\n...[truncated]", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ActionIntent, ActionType, ColumnIntent, DatasetIntent, ScreenType};
+
+    fn member_list() -> UiIntent {
+        UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(
+                DatasetIntent::new("ds_member")
+                    .with_table("TB_MEMBER")
+                    .with_column(ColumnIntent::new("MEMBER_NAME", "이름")),
+            )
+            .with_action(ActionIntent::new("search", "조회", ActionType::Search))
+    }
+
+    #[test]
+    fn test_intent_features() {
+        assert_eq!(
+            DistillationService::intent_features(&member_list()),
+            vec![
+                "action:search",
+                "col:member_name",
+                "name:list",
+                "name:member",
+                "table:tb_member",
+                "type:list",
+                "ui:input",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_similarity() {
+        let a = DistillationService::intent_features(&member_list()).join(" ");
+        let b = DistillationService::intent_features(&UiIntent::new("order_list", ScreenType::List)).join(" ");
+        assert_eq!(DistillationService::similarity(&a, &a), 1.0);
+        assert!((DistillationService::similarity(&a, &b) - 2.0 / 8.0).abs() < 1e-6);
+        assert_eq!(DistillationService::similarity("", ""), 0.0);
+    }
+
+    #[test]
+    fn test_remote_providers() {
+        assert!(is_remote_provider("anthropic"));
+        assert!(!is_remote_provider("ollama"));
+        assert!(!is_remote_provider("local-llama-cpp"));
+    }
+}
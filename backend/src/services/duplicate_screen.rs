@@ -0,0 +1,250 @@
+//! Duplicate Screen Detection
+//!
+//! Before a generation starts, looks through the generation log for an
+//! earlier successful generation of the same screen (screen name and type;
+//! entity for Spring) in the same project. Teams otherwise end up with
+//! several diverging versions of `member_list`.
+//!
+//! `DUPLICATE_SCREEN_CHECK` selects what happens on a match: `warn` (default)
+//! adds a warning to the response, `block` rejects the request unless it is
+//! sent with `force=true`, `off` skips the lookup.
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::Serialize;
+
+use crate::domain::{GenerateInput, RequestContext};
+use crate::models::_entities::generation_logs::{Column, Entity};
+use crate::models::saved_intents;
+use crate::services::{NormalizerService, SpringGenerationService, TenantScope};
+
+/// Log statuses of generations that produced a screen
+const PRODUCED_STATUSES: [&str; 3] = ["success", "partial_success", "completed"];
+
+/// Most recent matching rows checked per lookup
+const CANDIDATES: u64 = 20;
+
+/// What to do when a screen was generated before
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    Off,
+    #[default]
+    Warn,
+    Block,
+}
+
+impl DuplicatePolicy {
+    pub fn from_env() -> Self {
+        match std::env::var("DUPLICATE_SCREEN_CHECK")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "off" | "false" | "0" => Self::Off,
+            "block" => Self::Block,
+            _ => Self::Warn,
+        }
+    }
+}
+
+/// What identifies a screen within a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenKey {
+    pub product: String,
+    pub project: Option<String>,
+    /// Screen name (UI) or entity name (Spring)
+    pub name: String,
+    /// Screen type (UI only)
+    pub screen_type: Option<String>,
+}
+
+impl ScreenKey {
+    fn intent_field(&self) -> &'static str {
+        if self.screen_type.is_some() {
+            "screen_name"
+        } else {
+            "entity_name"
+        }
+    }
+
+    /// Whether a logged intent is this screen
+    fn matches(&self, intent_json: &str) -> bool {
+        let Ok(intent) = serde_json::from_str::<serde_json::Value>(intent_json) else {
+            return false;
+        };
+        let field = |key: &str| intent.get(key).and_then(|v| v.as_str());
+        field(self.intent_field()) == Some(self.name.as_str())
+            && self.screen_type.as_deref().is_none_or(|t| field("screen_type") == Some(t))
+    }
+}
+
+/// Earlier generation of the requested screen
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateScreen {
+    pub generation_id: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl DuplicateScreen {
+    pub fn message(&self) -> String {
+        let screen = match &self.screen_type {
+            Some(screen_type) => format!("{} ({})", self.name, screen_type),
+            None => self.name.clone(),
+        };
+        let project = self
+            .project
+            .as_deref()
+            .map(|p| format!(" in project {}", p))
+            .unwrap_or_default();
+        format!(
+            "Duplicate screen: {} was already generated{} on {} (generation {}); reuse or update it instead",
+            screen,
+            project,
+            self.created_at.format("%Y-%m-%d %H:%M"),
+            self.generation_id
+        )
+    }
+}
+
+/// Looks up earlier generations of a screen
+pub struct DuplicateScreenDetector;
+
+impl DuplicateScreenDetector {
+    /// Screen a request would generate, when it can be told without the LLM
+    /// (saved intents are looked up among those `tenant` can use)
+    pub async fn key_for(
+        db: &DatabaseConnection,
+        product: &str,
+        input: &GenerateInput,
+        context: &RequestContext,
+        tenant: &TenantScope,
+    ) -> Option<ScreenKey> {
+        if product == "spring-backend" {
+            // Spring logs record the package base as their project
+            let package_base = context.project.as_deref().unwrap_or("com.company.project");
+            let intent = SpringGenerationService::normalize(db, input, package_base, tenant).await.ok()?;
+            return Some(ScreenKey {
+                product: product.to_string(),
+                project: Some(intent.package_base),
+                name: intent.entity_name,
+                screen_type: None,
+            });
+        }
+
+        let intent = match input {
+            GenerateInput::SavedIntent(saved) => saved_intents::Model::load_ui_intent(db, &saved.name, tenant).await.ok()?,
+            _ => NormalizerService::normalize(input).ok()?,
+        };
+        Some(ScreenKey {
+            product: product.to_string(),
+            project: context.project.clone(),
+            name: intent.screen_name,
+            screen_type: Some(intent.screen_type.as_str().to_string()),
+        })
+    }
+
+    /// Latest successful generation of `key` visible to `tenant`
+    pub async fn find(
+        db: &DatabaseConnection,
+        key: &ScreenKey,
+        tenant: &TenantScope,
+    ) -> Result<Option<DuplicateScreen>> {
+        let project = match &key.project {
+            Some(project) => Column::Project.eq(project.as_str()),
+            None => Column::Project.is_null(),
+        };
+        let candidates = Entity::find()
+            .filter(tenant.owned(Column::Company))
+            .filter(Column::Product.eq(key.product.as_str()))
+            .filter(project)
+            .filter(Column::Status.is_in(PRODUCED_STATUSES))
+            .filter(Column::UiIntent.contains(format!("\"{}\":\"{}\"", key.intent_field(), key.name)))
+            .order_by_desc(Column::CreatedAt)
+            .limit(CANDIDATES)
+            .all(db)
+            .await?;
+
+        Ok(candidates
+            .into_iter()
+            .find(|log| key.matches(&log.ui_intent))
+            .map(|log| DuplicateScreen {
+                generation_id: log.id,
+                created_at: log.created_at,
+                name: key.name.clone(),
+                screen_type: key.screen_type.clone(),
+                project: log.project,
+            }))
+    }
+
+    /// Earlier generation of the screen a request would generate
+    pub async fn check(
+        db: &DatabaseConnection,
+        product: &str,
+        input: &GenerateInput,
+        context: &RequestContext,
+        user_id: Option<i32>,
+    ) -> Result<Option<DuplicateScreen>> {
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+        let Some(key) = Self::key_for(db, product, input, context, &tenant).await else {
+            return Ok(None);
+        };
+        Self::find(db, &key, &tenant).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ui_key() -> ScreenKey {
+        ScreenKey {
+            product: "xframe5-ui".to_string(),
+            project: Some("crm".to_string()),
+            name: "member_list".to_string(),
+            screen_type: Some("list".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_key_matches_screen_name_and_type() {
+        let key = ui_key();
+        assert!(key.matches(r#"{"screen_name":"member_list","screen_type":"list","datasets":[]}"#));
+        assert!(!key.matches(r#"{"screen_name":"member_list","screen_type":"detail"}"#));
+        assert!(!key.matches(r#"{"screen_name":"member_list_old","screen_type":"list"}"#));
+        assert!(!key.matches("pending"));
+    }
+
+    #[test]
+    fn test_spring_key_matches_entity() {
+        let key = ScreenKey {
+            product: "spring-backend".to_string(),
+            project: Some("com.company.crm".to_string()),
+            name: "Member".to_string(),
+            screen_type: None,
+        };
+        assert!(key.matches(r#"{"entity_name":"Member","table_name":"TB_MEMBER"}"#));
+        assert!(!key.matches(r#"{"entity_name":"MemberGrade"}"#));
+    }
+
+    #[test]
+    fn test_message_names_screen_and_generation() {
+        let duplicate = DuplicateScreen {
+            generation_id: 42,
+            created_at: DateTime::parse_from_rfc3339("2026-10-09T14:30:00+09:00").unwrap(),
+            name: "member_list".to_string(),
+            screen_type: Some("list".to_string()),
+            project: Some("crm".to_string()),
+        };
+        assert_eq!(
+            duplicate.message(),
+            "Duplicate screen: member_list (list) was already generated in project crm on 2026-10-09 14:30 (generation 42); reuse or update it instead"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Fault Injection
+//!
+//! Chaos hooks for resilience testing, compiled only with the
+//! `fault-injection` feature so production builds cannot enable them.
+//!
+//! When active, a share of LLM calls is delayed, failed or returns corrupted
+//! output, and a share of generation log writes fails - enough to watch
+//! retries, lease expiry and job failure handling do their work before
+//! go-live. Settings come from the environment at startup and can be changed
+//! at runtime through `/admin/fault-injection`.
+//!
+//! Environment (rates are percentages, 0-100):
+//! - `FAULT_INJECTION`: `1`/`true` to enable
+//! - `FAULT_LLM_DELAY_RATE`, `FAULT_LLM_DELAY_MS` (default 5000)
+//! - `FAULT_LLM_ERROR_RATE`
+//! - `FAULT_LLM_CORRUPT_RATE`
+//! - `FAULT_DB_WRITE_FAIL_RATE`
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+/// Fault injection settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Master switch
+    pub enabled: bool,
+    /// Percentage of LLM calls delayed by `llm_delay_ms`
+    pub llm_delay_rate: f64,
+    /// Added latency for delayed calls
+    pub llm_delay_ms: u64,
+    /// Percentage of LLM calls that fail
+    pub llm_error_rate: f64,
+    /// Percentage of LLM responses that are corrupted
+    pub llm_corrupt_rate: f64,
+    /// Percentage of generation log writes that fail
+    pub db_write_fail_rate: f64,
+}
+
+impl FaultConfig {
+    /// Load from environment
+    pub fn from_env() -> Self {
+        let rate = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+
+        Self {
+            enabled: matches!(
+                std::env::var("FAULT_INJECTION").as_deref().map(str::trim),
+                Ok("1") | Ok("true") | Ok("yes")
+            ),
+            llm_delay_rate: rate("FAULT_LLM_DELAY_RATE"),
+            llm_delay_ms: std::env::var("FAULT_LLM_DELAY_MS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(5000),
+            llm_error_rate: rate("FAULT_LLM_ERROR_RATE"),
+            llm_corrupt_rate: rate("FAULT_LLM_CORRUPT_RATE"),
+            db_write_fail_rate: rate("FAULT_DB_WRITE_FAIL_RATE"),
+        }
+        .clamped()
+    }
+
+    /// Rates limited to 0-100, delay to ten minutes
+    pub fn clamped(mut self) -> Self {
+        for rate in [
+            &mut self.llm_delay_rate,
+            &mut self.llm_error_rate,
+            &mut self.llm_corrupt_rate,
+            &mut self.db_write_fail_rate,
+        ] {
+            *rate = if rate.is_finite() { rate.clamp(0.0, 100.0) } else { 0.0 };
+        }
+        self.llm_delay_ms = self.llm_delay_ms.min(600_000);
+        self
+    }
+}
+
+/// Kind of damage done to an LLM response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corruption {
+    /// Cut off halfway, as if the stream broke
+    Truncate,
+    /// Closing tags removed, so the XML no longer parses
+    UnclosedTags,
+    /// Prose instead of code
+    Refusal,
+}
+
+impl Corruption {
+    /// Apply the corruption to a response
+    pub fn apply(self, output: &str) -> String {
+        match self {
+            Self::Truncate => {
+                let mut cut = output.len() / 2;
+                while !output.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                output[..cut].to_string()
+            }
+            Self::UnclosedTags => output.replace("</", "<"),
+            Self::Refusal => "I'm sorry, but I can't help with generating this screen.".to_string(),
+        }
+    }
+}
+
+fn config() -> &'static RwLock<FaultConfig> {
+    static CONFIG: OnceLock<RwLock<FaultConfig>> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let config = FaultConfig::from_env();
+        if config.enabled {
+            tracing::warn!("Fault injection is ENABLED: {:?}", config);
+        }
+        RwLock::new(config)
+    })
+}
+
+/// Uniform random number in [0, 100)
+fn roll() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64 * 100.0
+}
+
+/// Fault injection decisions
+pub struct FaultInjector;
+
+impl FaultInjector {
+    /// Current settings
+    pub fn config() -> FaultConfig {
+        config().read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Replace the settings at runtime
+    pub fn set_config(new_config: FaultConfig) -> FaultConfig {
+        let new_config = new_config.clamped();
+        tracing::warn!("Fault injection settings changed: {:?}", new_config);
+        if let Ok(mut current) = config().write() {
+            *current = new_config.clone();
+        }
+        new_config
+    }
+
+    fn hit(rate: impl Fn(&FaultConfig) -> f64) -> bool {
+        let config = Self::config();
+        config.enabled && rate(&config) > 0.0 && roll() < rate(&config)
+    }
+
+    /// Delay to add before an LLM call, if any
+    pub fn llm_delay() -> Option<std::time::Duration> {
+        Self::hit(|c| c.llm_delay_rate)
+            .then(|| std::time::Duration::from_millis(Self::config().llm_delay_ms))
+    }
+
+    /// Whether this LLM call should fail
+    pub fn llm_error() -> bool {
+        Self::hit(|c| c.llm_error_rate)
+    }
+
+    /// Corruption to apply to this LLM response, if any
+    pub fn llm_corruption() -> Option<Corruption> {
+        if !Self::hit(|c| c.llm_corrupt_rate) {
+            return None;
+        }
+        Some(match (roll() / 100.0 * 3.0) as u8 {
+            0 => Corruption::Truncate,
+            1 => Corruption::UnclosedTags,
+            _ => Corruption::Refusal,
+        })
+    }
+
+    /// Whether this database write should fail
+    pub fn db_write_fails() -> bool {
+        Self::hit(|c| c.db_write_fail_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_range() {
+        for _ in 0..1000 {
+            let value = roll();
+            assert!((0.0..100.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_clamped_rates() {
+        let config = FaultConfig {
+            llm_error_rate: 250.0,
+            llm_corrupt_rate: -5.0,
+            db_write_fail_rate: f64::NAN,
+            llm_delay_ms: u64::MAX,
+            ..Default::default()
+        }
+        .clamped();
+        assert_eq!(config.llm_error_rate, 100.0);
+        assert_eq!(config.llm_corrupt_rate, 0.0);
+        assert_eq!(config.db_write_fail_rate, 0.0);
+        assert_eq!(config.llm_delay_ms, 600_000);
+    }
+
+    #[test]
+    fn test_corruptions() {
+        let xml = "<Screen><Grid>한글</Grid></Screen>";
+        assert!(xml.starts_with(&Corruption::Truncate.apply(xml)));
+        assert!(Corruption::Truncate.apply(xml).len() < xml.len());
+        assert!(!Corruption::UnclosedTags.apply(xml).contains("</"));
+        assert!(!Corruption::Refusal.apply(xml).contains('<'));
+    }
+}
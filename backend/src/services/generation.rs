@@ -1,16 +1,42 @@
 use crate::domain::{
-    GenerateInput, GenerateOptions, GenerateResponse, GenerateStatus, GeneratedArtifacts,
-    RequestContext, ResponseMeta,
+    GenerateInput, GenerateOptions, GenerateResponse, GenerateStatus, GeneratedArtifacts, LocaleFormats,
+    NamingProfile, NamingVars, PrintIntent, RequestContext, ResponseMeta, SearchStateIntent,
 };
-use crate::llm::{create_backend_from_db_or_env, create_backend_from_env};
-use crate::models::_entities::generation_logs;
-use crate::services::{NormalizerService, PromptCompiler, TemplateService};
-use crate::services::pipeline::{PostProcessingPipeline, ExecutionMode};
+use crate::llm::{create_backend_from_config, create_backend_from_db_or_env, create_backend_from_env, create_race_partner, GenParams, LlmBackend, LlmUsage, RetryingBackend, StreamAborted};
+use crate::models::_entities::{generation_logs, llm_configs, prompt_templates};
+use crate::models::users::SYSTEM_USER_ID;
+use crate::models::{column_archetypes, company_rules, naming_profiles, pipeline_profiles, race_results, saved_intents};
+use crate::services::distillation::{is_remote_provider, DistillationService};
+use crate::services::canary::{CanaryRoute, CanaryService};
+use crate::services::schema_change::SchemaChangeService;
+use crate::services::refusal::REFUSAL_ERROR_CODE;
+use crate::services::screen_registry::{CollisionPolicy, ScreenRegistry};
+use crate::services::{AccessibilityChecker, ArtifactScorer, IntentComplexity, IssueTrackerService, KnowledgeBaseService, KnowledgeRevision, LineageService, NormalizerService, PromptCompiler, RaceRunner, RaceSide, Refusal, RetentionService, TemplateService, TenantScope, TestDataGenerator, WasmPlugins};
+use crate::services::pipeline::{passes::{ApiDenylistFilter, ChecklistValidator, ErrorHandlingValidator, UxBehaviorPass}, prevalidator, screen_artifacts, PipelineProfile, PipelineRules, PostProcessingPipeline, ExecutionMode, StreamPrevalidator};
+use crate::services::generation_hooks::{self, FinishedArtifacts, HookContext, NormalizedIntent, PipelineOutcome};
+use crate::services::sla::{self, Stage};
+use crate::services::telemetry::{self, traced, Span, SpanKind};
 use anyhow::{anyhow, Result};
-use chrono::Utc;
+use chrono::{Local, Utc};
 use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
 use std::time::Instant;
 
+/// Sampling temperature of screens described in natural language
+const NL_TEMPERATURE: f32 = 0.7;
+
+/// Sampling temperature of screens from a schema, query, DSL or intent
+const STRUCTURED_TEMPERATURE: f32 = 0.3;
+
+/// Template and LLM config a generation must use instead of the active ones
+/// (a replay pins those of the generation it reproduces)
+#[derive(Debug, Clone, Default)]
+pub struct GenerationPins {
+    pub template: Option<prompt_templates::Model>,
+    pub llm_config: Option<llm_configs::Model>,
+    /// Generation log being replayed (stored on the new log)
+    pub replay_of: Option<i32>,
+}
+
 /// Service for orchestrating the generation flow
 pub struct GenerationService;
 
@@ -21,69 +47,274 @@ impl GenerationService {
         input: GenerateInput,
         product: &str,
         options: &GenerateOptions,
-        _context: &RequestContext,
+        context: &RequestContext,
         user_id: Option<i32>,
+    ) -> Result<GenerateResponse> {
+        Self::generate_pinned(db, input, product, options, context, user_id, &GenerationPins::default()).await
+    }
+
+    /// Generate with a pinned template and/or LLM config
+    pub async fn generate_pinned(
+        db: &DatabaseConnection,
+        input: GenerateInput,
+        product: &str,
+        options: &GenerateOptions,
+        context: &RequestContext,
+        user_id: Option<i32>,
+        pins: &GenerationPins,
     ) -> Result<GenerateResponse> {
         let start = Instant::now();
 
-        // 1. Normalize input to UiIntent
-        let intent = NormalizerService::normalize(&input)?;
+        // Templates, rules, archetypes, knowledge and LLM config of the user's company
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+
+        // 1. Normalize input to UiIntent (saved intents come from the library)
+        let mut intent = match &input {
+            GenerateInput::SavedIntent(saved) => saved_intents::Model::load_ui_intent(db, &saved.name, &tenant).await?,
+            _ => {
+                let archetypes = column_archetypes::Model::active(db, &tenant).await;
+                NormalizerService::normalize_with_archetypes(&input, &archetypes)?
+            }
+        };
+        if options.editable_grid {
+            intent.enable_grid_editing();
+        }
+
+        SchemaChangeService::record_input(db, tenant.company(), &input).await;
+
+        // A running canary sends a share of unpinned generations to its candidate
+        let canary = match (&pins.template, &pins.llm_config) {
+            (None, None) => CanaryService::route(db, product, intent.screen_type.as_str(), &tenant).await,
+            _ => None,
+        };
+        let pins = &canary.as_ref().map_or_else(|| pins.clone(), |route| route.pin(pins));
 
         // 2. Get template version for logging
-        let template = TemplateService::get_active(db, product, Some(intent.screen_type.as_str()))
-            .await
-            .ok();
+        let template = match &pins.template {
+            Some(template) => Some(template.clone()),
+            None => TemplateService::get_active(db, product, Some(intent.screen_type.as_str()), &tenant)
+                .await
+                .ok(),
+        };
         let template_version = template.as_ref().map(|t| t.version).unwrap_or(0);
 
-        // 3. Compile prompt
-        let prompt = PromptCompiler::compile(
-            db,
-            &intent,
+        // File names from the selected naming profile
+        let (naming, naming_note) =
+            naming_profiles::Model::profile_or_default(db, options.naming_profile.as_deref(), &tenant).await;
+        let naming_vars = NamingVars::for_screen(&intent, Local::now().naive_local());
+        let (xml_filename, js_filename) = naming.screen_files(&naming_vars);
+
+        // Per-pass overrides from the requested (or product default) pipeline profile
+        let (mut pipeline_profile, profile_note) =
+            pipeline_profiles::Model::resolve(db, product, options.pipeline_profile.as_deref(), &tenant).await;
+        if options.preserve_xml_format {
+            pipeline_profile = pipeline_profile.skipping("XmlFormatter");
+        }
+        // Pick up validation plugins uploaded or toggled since the last generation
+        WasmPlugins::sync(db).await;
+
+        // Company rule set configures the API deny-list pass, print defaults and the address popup
+        let rule_sections = match options.company_id.as_deref() {
+            Some(cid) => company_rules::Model::find_by_name(db, cid, &tenant)
+                .await
+                .ok()
+                .map(|r| r.rule_sections()),
+            None => None,
+        };
+        if options.print {
+            let rules = rule_sections.as_ref().and_then(|s| s.print.as_ref());
+            let print = PrintIntent::for_intent(&intent, options.report_tool, rules);
+            intent.enable_print(print);
+        }
+        if let Some(popup) = rule_sections.as_ref().and_then(|s| s.address.as_ref()) {
+            intent.apply_address_popup(popup);
+        }
+        if options.keep_search_conditions {
+            let rules = rule_sections.as_ref().and_then(|s| s.search_state.as_ref());
+            intent.search_state = Some(SearchStateIntent::for_intent(&intent, rules));
+        }
+        let format_rules = rule_sections.as_ref().and_then(|s| s.formats.as_ref());
+        if options.locale.is_some() || format_rules.is_some() {
+            let formats = LocaleFormats::resolve(options.locale.as_deref(), format_rules).map_err(|e| anyhow!(e))?;
+            intent.apply_formats(&formats);
+        }
+        // Decimal columns show their schema scale even without a locale
+        intent.apply_scale_formats();
+
+        // Deployment hooks see the intent with all request options applied
+        let hooks = generation_hooks::registry();
+        let hook_ctx = HookContext {
             product,
-            options.company_id.as_deref(),
-        )
-        .await?;
+            user_id,
+            company_id: options.company_id.as_deref(),
+            issue_key: context.issue_key.as_deref(),
+        };
+        hooks.intent_normalized(&hook_ctx, NormalizedIntent::Screen(&mut intent));
+        // Very wide screens: warn and suggest a list + tabbed detail split
+        let complexity_notes = IntentComplexity::estimate(&intent).notes();
+
+        // 3. Compile prompt
+        let mut prompt = match &pins.template {
+            Some(template) => {
+                PromptCompiler::compile_with_template(
+                    db,
+                    &intent,
+                    product,
+                    options.company_id.as_deref(),
+                    &tenant,
+                    Some(template.clone()),
+                )
+                .await?
+            }
+            None => {
+                PromptCompiler::compile(db, &intent, product, options.company_id.as_deref(), &tenant).await?
+            }
+        };
 
         // 4. Generate via LLM (DB config takes priority, falls back to env)
-        let llm = create_backend_from_db_or_env(db).await;
+        let llm = match &pins.llm_config {
+            Some(config) => create_backend_from_config(config),
+            None => create_backend_from_db_or_env(db, tenant.company()).await,
+        };
+        // Timeouts and 5xx responses are retried with backoff (LLM_RETRY_*)
+        let llm = RetryingBackend::from_env(llm);
+        let (llm, partner) = if options.race {
+            Self::race_backends(db, product, llm).await
+        } else {
+            (llm, None)
+        };
 
-        // Capture LLM info for audit logging (internal only)
-        let llm_provider = llm.name().to_string();
-        let llm_model = llm.model().to_string();
+        // Local models get the nearest distilled remote output as a few-shot example
+        if partner.is_none() && !is_remote_provider(llm.name()) {
+            match DistillationService::nearest(db, product, &intent, tenant.company()).await {
+                Ok(Some(example)) => prompt.add_section(&DistillationService::few_shot_section(&example)),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Could not load distillation example: {}", e),
+            }
+        }
+        // A refused request is retried without its notes (hooks run on that prompt again)
+        let mut sanitized = prompt.without_notes();
+        hooks.prompt_compiled(&hook_ctx, &mut prompt.system, &mut prompt.user);
 
         // Health check
         llm.health_check().await.map_err(|e| {
             anyhow!("LLM server not available: {}. Please check your LLM configuration.", e)
         })?;
 
-        let raw_output = llm.generate(&prompt.full()).await?;
-
-        // Log raw output for debugging (truncated)
-        let output_preview = if raw_output.len() > 500 {
-            format!("{}...[truncated, total {} chars]", &raw_output[..500], raw_output.len())
-        } else {
-            raw_output.clone()
+        // Capture LLM info for audit logging (internal only; after the health
+        // check, which may move a fallback chain on to a backup backend)
+        let mut llm_provider = llm.name().to_string();
+        let mut llm_model = llm.model().to_string();
+
+        // Rule-set specific passes, shared by every pipeline run below
+        let pipeline_rules = PipelineRules::new()
+            .with_denylist(ApiDenylistFilter::for_rules(rule_sections.as_ref(), product))
+            .with_checklist(ChecklistValidator::with_items(prompt.checklist.clone()))
+            .with_ux(UxBehaviorPass::for_rules(rule_sections.as_ref()))
+            .with_errors(
+                ErrorHandlingValidator::for_rules(rule_sections.as_ref()).with_catalog(prompt.error_codes.clone()),
+            )
+            .with_profile(pipeline_profile.clone());
+
+        let params = Self::gen_params(&input);
+        let mut stream_notes = Vec::new();
+        let mut llm_usage = LlmUsage::default();
+        sla::mark(Stage::LlmStart);
+        let pipeline_result = match &partner {
+            // Race mode: the first output passing strict validation wins
+            Some(partner) => {
+                let accept = |mut raw: String| {
+                    hooks.llm_completed(&hook_ctx, 1, &mut raw);
+                    if let Some(refusal) = Refusal::detect(&raw) {
+                        return Err(refusal.into());
+                    }
+                    PostProcessingPipeline::run_with_rules(raw, &intent, ExecutionMode::Strict, &pipeline_rules)
+                };
+                let race = RaceRunner::run(llm.as_ref(), partner.as_ref(), &prompt.full(), &params, accept).await;
+                sla::mark(Stage::LlmEnd);
+                match race {
+                    Ok(outcome) => {
+                        RaceRunner::record(db, product, llm.as_ref(), partner.as_ref(), &outcome).await;
+                        llm_usage = outcome.usage;
+                        if outcome.winner == RaceSide::Partner {
+                            llm_provider = partner.name().to_string();
+                            llm_model = partner.model().to_string();
+                        }
+                        Ok(outcome.value)
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            None => {
+                // Streaming backends are prevalidated; a broken stream is aborted and retried
+                let streamed = traced(
+                    Span::child("llm.generate", SpanKind::Client)
+                        .with_attr("llm.provider", llm_provider.as_str())
+                        .with_attr("attempt", 1i64),
+                    llm.generate_observed_with(&prompt.full(), &params, &mut StreamPrevalidator::new()),
+                )
+                .await;
+                sla::mark(Stage::LlmEnd);
+                match streamed {
+                    Err(e) if Self::note_stream_abort(&e, &mut stream_notes) => Err(e),
+                    streamed => {
+                        let output = streamed?;
+                        llm_usage.add(&output);
+                        llm_provider = llm.name().to_string();
+                        llm_model = llm.model().to_string();
+                        let mut raw_output = output.text;
+                        hooks.llm_completed(&hook_ctx, 1, &mut raw_output);
+
+                        // Log raw output for debugging (truncated)
+                        let output_preview = if raw_output.len() > 500 {
+                            format!("{}...[truncated, total {} chars]", &raw_output[..500], raw_output.len())
+                        } else {
+                            raw_output.clone()
+                        };
+                        tracing::debug!("LLM raw output preview:\n{}", output_preview);
+
+                        // 5. Run through post-processing pipeline
+                        // Execution mode is derived from strictMode option
+                        let execution_mode = ExecutionMode::from_strict_mode(options.strict_mode);
+
+                        let mut pipeline_span = Span::child("pipeline.run", SpanKind::Internal)
+                            .with_attr("screen_type", intent.screen_type.as_str());
+                        // A refusal is retried with a sanitized prompt instead of being parsed
+                        let pipeline_result = match Refusal::detect(&raw_output) {
+                            Some(refusal) => Err(refusal.into()),
+                            None => PostProcessingPipeline::run_with_rules(raw_output.clone(), &intent, execution_mode, &pipeline_rules),
+                        };
+                        match &pipeline_result {
+                            Ok(result) => pipeline_span.set_attr("warnings", result.warnings.len()),
+                            Err(e) => pipeline_span.set_error(e),
+                        }
+                        pipeline_span.end();
+                        pipeline_result
+                    }
+                }
+            }
         };
-        tracing::debug!("LLM raw output preview:\n{}", output_preview);
 
-        // 5. Run through post-processing pipeline
-        // Execution mode is derived from strictMode option
-        let execution_mode = ExecutionMode::from_strict_mode(options.strict_mode);
-
-        let pipeline_result = PostProcessingPipeline::run(
-            raw_output.clone(),
-            &intent,
-            execution_mode,
-        );
-
-        let (artifacts, warnings, status, error_message) = match pipeline_result {
+        let mut error_code = None;
+        let (mut artifacts, mut warnings, mut status, mut error_message) = match pipeline_result {
             Ok(result) => {
                 // Convert pipeline result to GeneratedArtifacts
                 let artifacts = GeneratedArtifacts {
                     xml: Some(result.xml),
                     javascript: Some(result.javascript),
-                    xml_filename: Some(format!("{}.xml", intent.screen_name.to_lowercase().replace(' ', "_"))),
-                    js_filename: Some(format!("{}.js", intent.screen_name.to_lowercase().replace(' ', "_"))),
+                    xml_filename: Some(xml_filename.clone()),
+                    js_filename: Some(js_filename.clone()),
+                    additional_screens: screen_artifacts(
+                        result.additional_screens,
+                        &naming,
+                        &naming_vars,
+                        &[&xml_filename, &js_filename],
+                    ),
+                    print_layout: None,
+                    print_layout_filename: None,
+                    accessibility_report: None,
+                    test_data: None,
+                    test_data_filename: None,
                 };
 
                 let status = if result.warnings.iter().any(|w| w.contains("Warning") || w.contains("Error")) {
@@ -96,31 +327,80 @@ impl GenerationService {
             }
             Err(e) => {
                 // Pipeline failed - try retry once
-                tracing::warn!("First generation failed pipeline: {}", e);
-
-                // Retry with more explicit instructions
-                let retry_prompt = format!(
-                    "{}\n\nIMPORTANT: Your previous response could not be parsed. \
-                    Please ensure you output exactly two sections:\n\
-                    --- XML ---\n<your XML here>\n\n--- JS ---\n<your JavaScript here>",
-                    prompt.full()
-                );
-
-                match llm.generate(&retry_prompt).await {
-                    Ok(retry_output) => {
+                let refused = e.downcast_ref::<Refusal>().is_some();
+                let retry_prompt = if refused {
+                    // Refused - retry without the request notes
+                    tracing::warn!("First generation was refused: {}", e);
+                    hooks.prompt_compiled(&hook_ctx, &mut sanitized.system, &mut sanitized.user);
+                    Refusal::retry_prompt(&sanitized.full())
+                } else {
+                    tracing::warn!("First generation failed pipeline: {}", e);
+
+                    // Retry with more explicit instructions
+                    format!(
+                        "{}\n\nIMPORTANT: Your previous response could not be parsed. \
+                        Please ensure you output exactly two sections:\n\
+                        --- XML ---\n<your XML here>\n\n--- JS ---\n<your JavaScript here>",
+                        prompt.full()
+                    )
+                };
+
+                let retry_span = Span::child("llm.generate", SpanKind::Client)
+                    .with_attr("llm.provider", llm_provider.as_str())
+                    .with_attr("attempt", 2i64);
+                let retry = traced(
+                    retry_span,
+                    llm.generate_observed_with(&retry_prompt, &params, &mut StreamPrevalidator::new()),
+                )
+                .await;
+                sla::mark(Stage::LlmEnd);
+                if let Err(e) = &retry {
+                    Self::note_stream_abort(e, &mut stream_notes);
+                }
+                match retry {
+                    Ok(output) => {
+                        llm_usage.add(&output);
+                        llm_provider = llm.name().to_string();
+                        llm_model = llm.model().to_string();
+                        let mut retry_output = output.text;
+                        hooks.llm_completed(&hook_ctx, 2, &mut retry_output);
                         // Use Relaxed mode for retry to be more permissive
-                        match PostProcessingPipeline::run(retry_output, &intent, ExecutionMode::Relaxed) {
+                        let retry_result = match Refusal::detect(&retry_output) {
+                            Some(refusal) => Err(refusal.into()),
+                            None => PostProcessingPipeline::run_with_rules(retry_output, &intent, ExecutionMode::Relaxed, &pipeline_rules),
+                        };
+                        match retry_result {
                             Ok(result) => {
                                 let artifacts = GeneratedArtifacts {
                                     xml: Some(result.xml),
                                     javascript: Some(result.javascript),
-                                    xml_filename: Some(format!("{}.xml", intent.screen_name.to_lowercase().replace(' ', "_"))),
-                                    js_filename: Some(format!("{}.js", intent.screen_name.to_lowercase().replace(' ', "_"))),
+                                    xml_filename: Some(xml_filename.clone()),
+                                    js_filename: Some(js_filename.clone()),
+                                    additional_screens: screen_artifacts(
+                                        result.additional_screens,
+                                        &naming,
+                                        &naming_vars,
+                                        &[&xml_filename, &js_filename],
+                                    ),
+                                    print_layout: None,
+                                    print_layout_filename: None,
+                                    accessibility_report: None,
+                                    test_data: None,
+                                    test_data_filename: None,
                                 };
                                 let mut warnings = result.warnings;
-                                warnings.push("Note: Generation required retry".to_string());
+                                warnings.push(if refused {
+                                    Refusal::retried_note()
+                                } else {
+                                    "Note: Generation required retry".to_string()
+                                });
                                 (Some(artifacts), warnings, GenerateStatus::PartialSuccess, None)
                             }
+                            Err(retry_err) if retry_err.is::<Refusal>() => {
+                                tracing::warn!("Retry was refused too: {}", retry_err);
+                                error_code = Some(REFUSAL_ERROR_CODE.to_string());
+                                (None, vec![retry_err.to_string()], GenerateStatus::Error, Some(Refusal::error_message()))
+                            }
                             Err(retry_err) => {
                                 (None, vec![], GenerateStatus::Error, Some(format!("Pipeline failed after retry: {}", retry_err)))
                             }
@@ -132,10 +412,100 @@ impl GenerationService {
                 }
             }
         };
+        sla::mark(Stage::PipelineEnd);
+
+        // Print-layout stub goes next to the screen XML
+        if let (Some(print), Some(generated)) = (&intent.print, artifacts.as_mut()) {
+            generated.print_layout = Some(print.layout_stub(&intent));
+            generated.print_layout_filename = Some(PrintIntent::layout_filename(&xml_filename));
+        }
+
+        // Accessibility report of the main screen, against the company palette
+        if let Some(generated) = artifacts.as_mut() {
+            let style = rule_sections.as_ref().and_then(|s| s.style.as_ref());
+            generated.accessibility_report =
+                generated.xml.as_deref().map(|xml| AccessibilityChecker::report(xml, style));
+        }
+
+        // Sample rows for the source table
+        if let (GenerateInput::DbSchema(schema), Some(rows), Some(generated)) =
+            (&input, options.test_data_rows.filter(|r| *r > 0), artifacts.as_mut())
+        {
+            generated.test_data = Some(TestDataGenerator::insert_script(schema, rows));
+            generated.test_data_filename = Some(TestDataGenerator::filename(schema));
+        }
+
+        // Screen ids and file names a teammate already delivered in the project
+        if let (Some(project), Some(generated)) = (context.project.as_deref(), artifacts.as_mut()) {
+            let policy = CollisionPolicy::from_env();
+            let owner = user_id.unwrap_or(SYSTEM_USER_ID);
+            match ScreenRegistry::resolve(db, project, tenant.company(), owner, generated, policy).await {
+                Ok(notes) => warnings.extend(notes),
+                Err(e) => {
+                    status = GenerateStatus::Error;
+                    error_message = Some(e.to_string());
+                    artifacts = None;
+                }
+            }
+        }
+
+        warnings.extend(naming_note);
+        warnings.extend(profile_note);
+        warnings.extend(complexity_notes);
+        warnings.extend(stream_notes);
+
+        hooks.pipeline_finished(
+            &hook_ctx,
+            PipelineOutcome {
+                status: &mut status,
+                artifacts: artifacts.as_mut().map(FinishedArtifacts::Screen),
+                warnings: &mut warnings,
+                error: error_message.as_deref(),
+            },
+        );
 
         let generation_time_ms = start.elapsed().as_millis() as u64;
+        telemetry::telemetry().record_generation(product, status.as_str(), generation_time_ms);
+
+        // 6. Record how the knowledge entries in the prompt fared (feeds KB ranking)
+        let findings = match status {
+            GenerateStatus::Error => 1,
+            _ => warnings
+                .iter()
+                .filter(|w| w.contains("Warning") || w.contains("Error"))
+                .count(),
+        };
+        if let Err(e) = KnowledgeBaseService::record_usage(db, &prompt.knowledge_ids(), findings).await {
+            tracing::error!("Failed to record knowledge usage: {}", e);
+        }
+
+        // Validated remote output becomes a few-shot example for local models
+        if let (GenerateStatus::Success, Some(generated)) = (&status, &artifacts) {
+            let quality = ArtifactScorer::score_screen(
+                generated.xml.as_deref().unwrap_or_default(),
+                generated.javascript.as_deref().unwrap_or_default(),
+                &intent,
+                &warnings,
+            )
+            .total as i32;
+            if let Err(e) = DistillationService::capture(
+                db,
+                product,
+                &intent,
+                generated,
+                quality,
+                &llm_provider,
+                &llm_model,
+                tenant.company(),
+            )
+            .await
+            {
+                tracing::warn!("Could not capture distillation example: {}", e);
+            }
+        }
 
-        // 6. Log to audit trail (NO input data stored)
+        // 7. Log to audit trail (NO input data stored)
+        sla::llm_usage(llm_usage);
         let log_result = Self::log_generation(
             db,
             product,
@@ -146,10 +516,18 @@ impl GenerationService {
             &artifacts,
             &warnings,
             error_message.as_deref(),
+            error_code.as_deref(),
             generation_time_ms as i32,
             user_id,
             Some(&llm_provider),
             Some(&llm_model),
+            &llm_usage,
+            &prompt.knowledge_revisions,
+            context,
+            tenant.company(),
+            &pipeline_profile,
+            pins.replay_of,
+            canary.as_ref(),
         )
         .await;
 
@@ -157,12 +535,13 @@ impl GenerationService {
             tracing::error!("Failed to log generation: {}", e);
         }
 
-        // 7. Build response (NO LLM details exposed)
+        // 8. Build response (NO LLM details exposed)
         Ok(GenerateResponse {
             status,
             artifacts,
             warnings,
             error: error_message,
+            error_code,
             meta: ResponseMeta {
                 generator: format!("{}-v1", product),
                 timestamp: Utc::now(),
@@ -176,6 +555,17 @@ impl GenerationService {
         input: GenerateInput,
         product: &str,
         company_rules: Option<&str>,
+    ) -> Result<GenerateResponse> {
+        let llm = RetryingBackend::from_env(create_backend_from_env());
+        Self::generate_with_backend(input, product, company_rules, llm.as_ref()).await
+    }
+
+    /// Generate with default templates and the given backend, storing nothing
+    pub async fn generate_with_backend(
+        input: GenerateInput,
+        product: &str,
+        company_rules: Option<&str>,
+        llm: &dyn LlmBackend,
     ) -> Result<GenerateResponse> {
         let start = Instant::now();
 
@@ -186,10 +576,9 @@ impl GenerationService {
         let prompt = PromptCompiler::compile_with_defaults(&intent, company_rules);
 
         // 3. Generate via LLM
-        let llm = create_backend_from_env();
         llm.health_check().await?;
 
-        let raw_output = llm.generate(&prompt.full()).await?;
+        let raw_output = llm.generate_with(&prompt.full(), &Self::gen_params(&input)).await?.text;
 
         // 4. Run through post-processing pipeline (Relaxed mode for defaults)
         let result = PostProcessingPipeline::run(
@@ -206,11 +595,26 @@ impl GenerationService {
             GenerateStatus::Success
         };
 
+        let naming = NamingProfile::default();
+        let naming_vars = NamingVars::for_screen(&intent, Local::now().naive_local());
+        let (xml_filename, js_filename) = naming.screen_files(&naming_vars);
+        let accessibility_report = AccessibilityChecker::report(&result.xml, None);
         let artifacts = GeneratedArtifacts {
             xml: Some(result.xml),
             javascript: Some(result.javascript),
-            xml_filename: Some(format!("{}.xml", intent.screen_name.to_lowercase().replace(' ', "_"))),
-            js_filename: Some(format!("{}.js", intent.screen_name.to_lowercase().replace(' ', "_"))),
+            additional_screens: screen_artifacts(
+                result.additional_screens,
+                &naming,
+                &naming_vars,
+                &[&xml_filename, &js_filename],
+            ),
+            xml_filename: Some(xml_filename),
+            js_filename: Some(js_filename),
+            print_layout: None,
+            print_layout_filename: None,
+            accessibility_report: Some(accessibility_report),
+            test_data: None,
+            test_data_filename: None,
         };
 
         Ok(GenerateResponse {
@@ -218,6 +622,7 @@ impl GenerationService {
             artifacts: Some(artifacts),
             warnings: result.warnings,
             error: None,
+            error_code: None,
             meta: ResponseMeta {
                 generator: format!("{}-v1", product),
                 timestamp: Utc::now(),
@@ -226,6 +631,49 @@ impl GenerationService {
         })
     }
 
+    /// Record an early stream abort as a generation log note; `false` for other errors
+    fn note_stream_abort(error: &anyhow::Error, notes: &mut Vec<String>) -> bool {
+        let Some(aborted) = error.downcast_ref::<StreamAborted>() else {
+            return false;
+        };
+        tracing::warn!("{}", aborted);
+        notes.push(prevalidator::abort_note(aborted));
+        true
+    }
+
+    /// Sampling parameters by input: a natural-language request leaves the
+    /// model room to fill in the screen, structured inputs already fix it
+    fn gen_params(input: &GenerateInput) -> GenParams {
+        match input {
+            GenerateInput::NaturalLanguage(_) => GenParams::new().with_temperature(NL_TEMPERATURE),
+            _ => GenParams::new().with_temperature(STRUCTURED_TEMPERATURE),
+        }
+    }
+
+    /// Backends for race mode as `(primary, partner)`. Without a configured
+    /// partner, or when one side won nearly all recent races of the product,
+    /// only that backend is used.
+    async fn race_backends(
+        db: &DatabaseConnection,
+        product: &str,
+        llm: Box<dyn LlmBackend>,
+    ) -> (Box<dyn LlmBackend>, Option<Box<dyn LlmBackend>>) {
+        let Some(partner) = create_race_partner(db).await else {
+            return (llm, None);
+        };
+        match race_results::Model::dominant(db, product).await.ok().flatten() {
+            Some(d) if d.provider == partner.name() && d.model == partner.model() => {
+                tracing::info!("Race skipped: partner won {}/{} recent races", d.wins, d.races);
+                (partner, None)
+            }
+            Some(d) if d.provider == llm.name() && d.model == llm.model() => {
+                tracing::info!("Race skipped: primary won {}/{} recent races", d.wins, d.races);
+                (llm, None)
+            }
+            _ => (llm, Some(partner)),
+        }
+    }
+
     /// Log generation to audit trail
     async fn log_generation(
         db: &DatabaseConnection,
@@ -237,29 +685,42 @@ impl GenerationService {
         artifacts: &Option<GeneratedArtifacts>,
         warnings: &[String],
         error_message: Option<&str>,
+        error_code: Option<&str>,
         generation_time_ms: i32,
         user_id: Option<i32>,
         provider: Option<&str>,
         model_name: Option<&str>,
+        llm_usage: &LlmUsage,
+        knowledge_revisions: &[KnowledgeRevision],
+        context: &RequestContext,
+        company: Option<&str>,
+        pipeline_profile: &PipelineProfile,
+        replay_of: Option<i32>,
+        canary: Option<&CanaryRoute>,
     ) -> Result<()> {
         // Determine input type (without storing actual input data - 개인정보 보호)
         let input_type = match input {
             GenerateInput::DbSchema(_) => "db-schema",
             GenerateInput::QuerySample(_) => "query-sample",
             GenerateInput::NaturalLanguage(_) => "natural-language",
+            GenerateInput::Dashboard(_) => "dashboard",
+            GenerateInput::Intent(_) => "intent",
+            GenerateInput::SavedIntent(_) => "saved-intent",
+            GenerateInput::Dsl(_) => "dsl",
         };
 
-        let status_str = match status {
-            GenerateStatus::Success => "success",
-            GenerateStatus::PartialSuccess => "partial_success",
-            GenerateStatus::Error => "error",
-        };
+        let status_str = status.as_str();
 
         // Store UI intent (meta model) instead of raw input
         let ui_intent_json = serde_json::to_string(intent)?;
 
         // Store artifacts
         let artifacts_json = artifacts.as_ref().map(|a| serde_json::to_string(a).ok()).flatten();
+        let lineage = LineageService::build_json(product, &ui_intent_json, artifacts_json.as_deref());
+        let artifacts_expire_at = match &artifacts_json {
+            Some(_) => RetentionService::expiry(db, user_id, product, Utc::now().into()).await?,
+            None => None,
+        };
 
         // Store warnings
         let warnings_json = if warnings.is_empty() {
@@ -268,7 +729,17 @@ impl GenerationService {
             Some(serde_json::to_string(warnings)?)
         };
 
-        let log = generation_logs::ActiveModel {
+        let quality_score = artifacts.as_ref().map(|a| {
+            ArtifactScorer::score_screen(
+                a.xml.as_deref().unwrap_or_default(),
+                a.javascript.as_deref().unwrap_or_default(),
+                intent,
+                warnings,
+            )
+            .total as i32
+        });
+
+        let mut log = generation_logs::ActiveModel {
             product: Set(product.to_string()),
             input_type: Set(input_type.to_string()),
             ui_intent: Set(ui_intent_json),
@@ -277,14 +748,39 @@ impl GenerationService {
             artifacts: Set(artifacts_json),
             warnings: Set(warnings_json),
             error_message: Set(error_message.map(|s| s.to_string())),
+            error_code: Set(error_code.map(|s| s.to_string())),
             generation_time_ms: Set(Some(generation_time_ms)),
-            user_id: Set(user_id.unwrap_or(1)), // Default to system user
+            user_id: Set(user_id.unwrap_or(SYSTEM_USER_ID)),
             provider: Set(provider.map(|s| s.to_string())),
             model_name: Set(model_name.map(|s| s.to_string())),
+            quality_score: Set(quality_score),
+            knowledge_revisions: Set(if knowledge_revisions.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(knowledge_revisions)?)
+            }),
+            project: Set(context.project.clone()),
+            company: Set(company.map(|s| s.to_string())),
+            pipeline_profile: Set(pipeline_profile.name.clone()),
+            pipeline_overrides: Set(pipeline_profile.overrides_json()),
+            replay_of: Set(replay_of),
+            issue_key: Set(context.issue_key.clone()),
+            canary_id: Set(canary.map(|c| c.canary_id)),
+            canary_arm: Set(canary.map(|c| c.arm.as_str().to_string())),
+            lineage: Set(lineage),
+            artifacts_expire_at: Set(artifacts_expire_at),
             ..Default::default()
         };
+        llm_usage.apply(&mut log);
+
+        let log = log.insert(db).await?;
+        IssueTrackerService::report(db, &log);
 
-        log.insert(db).await?;
+        if let (Some(project), Some(generated)) = (context.project.as_deref(), artifacts) {
+            if let Err(e) = ScreenRegistry::register(db, project, company, log.user_id, log.id, generated).await {
+                tracing::warn!("Could not register screens of generation {}: {}", log.id, e);
+            }
+        }
 
         Ok(())
     }
@@ -293,7 +789,7 @@ impl GenerationService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::SchemaInput;
+    use crate::domain::{NaturalLanguageInput, SchemaInput};
 
     #[test]
     fn test_input_type_detection() {
@@ -302,7 +798,19 @@ mod tests {
             GenerateInput::DbSchema(_) => "db-schema",
             GenerateInput::QuerySample(_) => "query-sample",
             GenerateInput::NaturalLanguage(_) => "natural-language",
+            GenerateInput::Dashboard(_) => "dashboard",
+            GenerateInput::Intent(_) => "intent",
+            GenerateInput::SavedIntent(_) => "saved-intent",
+            GenerateInput::Dsl(_) => "dsl",
         };
         assert_eq!(input_type, "db-schema");
     }
+
+    #[test]
+    fn test_gen_params_by_input() {
+        let nl = GenerateInput::NaturalLanguage(NaturalLanguageInput::new("회원 목록 화면"));
+        assert_eq!(GenerationService::gen_params(&nl).temperature, Some(NL_TEMPERATURE));
+        let schema = GenerateInput::DbSchema(SchemaInput::new("member"));
+        assert_eq!(GenerationService::gen_params(&schema).temperature, Some(STRUCTURED_TEMPERATURE));
+    }
 }
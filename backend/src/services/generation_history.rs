@@ -0,0 +1,281 @@
+//! Generation History
+//!
+//! A developer's own past generations, for the IDE plugin: filterable by
+//! entity, product and date, with short previews of the generated files so
+//! the right one can be picked before downloading it again.
+//!
+//! Only what the developer already received is exposed; LLM provider and
+//! model stay in the admin generation log.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+
+use crate::models::_entities::generation_logs::{Column, Entity, Model};
+use crate::models::project_output_settings;
+use crate::services::{ArtifactPackager, PackagedFile, TenantScope};
+use crate::workers::generation::GenerateJobRequest;
+
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Lines of each file shown in a preview
+const PREVIEW_LINES: usize = 15;
+
+/// Filters for the history list
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoryQuery {
+    /// Screen or entity name (e.g. "member"), matched against the logged intent
+    pub entity: Option<String>,
+    pub product: Option<String>,
+    /// First day to include (YYYY-MM-DD)
+    pub date_from: Option<String>,
+    /// Last day to include (YYYY-MM-DD)
+    pub date_to: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+impl HistoryQuery {
+    /// Parsed date filters (both days included)
+    pub fn date_range(&self) -> Result<(Option<NaiveDate>, Option<NaiveDate>)> {
+        let parse = |value: &Option<String>| -> Result<Option<NaiveDate>> {
+            match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                Some(v) => NaiveDate::parse_from_str(v, "%Y-%m-%d")
+                    .map(Some)
+                    .map_err(|_| anyhow::anyhow!("Invalid date '{}', expected YYYY-MM-DD", v)),
+                None => Ok(None),
+            }
+        };
+        let (from, to) = (parse(&self.date_from)?, parse(&self.date_to)?);
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                anyhow::bail!("date_from {} is after date_to {}", from, to);
+            }
+        }
+        Ok((from, to))
+    }
+}
+
+/// Start of a generated file
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ArtifactPreview {
+    pub name: String,
+    pub total_lines: usize,
+    /// First lines of the file
+    pub preview: String,
+    pub truncated: bool,
+}
+
+impl ArtifactPreview {
+    fn of(file: &PackagedFile) -> Self {
+        let total_lines = file.content.lines().count();
+        Self {
+            name: file.name.clone(),
+            total_lines,
+            preview: file.content.lines().take(PREVIEW_LINES).collect::<Vec<_>>().join("\n"),
+            truncated: total_lines > PREVIEW_LINES,
+        }
+    }
+}
+
+/// One past generation
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryItem {
+    pub id: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub product: String,
+    pub input_type: String,
+    pub status: String,
+    /// Screen name (UI) or entity name (Spring) of the intent
+    pub entity: Option<String>,
+    pub job_id: Option<String>,
+    pub generation_time_ms: Option<i32>,
+    pub quality_score: Option<i32>,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+    pub files: Vec<ArtifactPreview>,
+    /// ZIP of the files, when the generation produced any
+    pub download_url: Option<String>,
+    /// When the retention policy removes the files (None = kept)
+    pub artifacts_expire_at: Option<DateTime<FixedOffset>>,
+    /// The retention policy already removed them
+    pub artifacts_purged: bool,
+}
+
+impl HistoryItem {
+    fn of(log: Model) -> Self {
+        let files: Vec<ArtifactPreview> = log
+            .artifacts
+            .as_deref()
+            .and_then(|a| ArtifactPackager::files_for(&log.product, a).ok())
+            .unwrap_or_default()
+            .iter()
+            .map(ArtifactPreview::of)
+            .collect();
+
+        Self {
+            id: log.id,
+            created_at: log.created_at,
+            entity: entity_name(&log.ui_intent),
+            input_type: log.input_type,
+            status: log.status,
+            job_id: log.job_id,
+            generation_time_ms: log.generation_time_ms,
+            quality_score: log.quality_score,
+            warnings: log
+                .warnings
+                .as_deref()
+                .and_then(|w| serde_json::from_str(w).ok())
+                .unwrap_or_default(),
+            error: log.error_message,
+            download_url: (!files.is_empty()).then(|| format!("/api/my/generations/{}/download", log.id)),
+            artifacts_expire_at: log.artifacts_expire_at,
+            artifacts_purged: log.artifacts_purged_at.is_some(),
+            files,
+            product: log.product,
+        }
+    }
+}
+
+/// Page of past generations
+#[derive(Debug, Serialize)]
+pub struct HistoryPage {
+    pub items: Vec<HistoryItem>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+/// Screen name of a UI intent or entity name of a Spring intent
+pub(crate) fn entity_name(intent_json: &str) -> Option<String> {
+    let intent: serde_json::Value = serde_json::from_str(intent_json).ok()?;
+    ["entity_name", "screen_name"]
+        .iter()
+        .find_map(|key| intent.get(key).and_then(|v| v.as_str()))
+        .map(str::to_string)
+}
+
+/// A user's generation history
+pub struct GenerationHistoryService;
+
+impl GenerationHistoryService {
+    /// Generations of `user_id`, newest first
+    pub async fn search(
+        db: &DatabaseConnection,
+        user_id: i32,
+        query: &HistoryQuery,
+    ) -> Result<HistoryPage> {
+        let (date_from, date_to) = query.date_range()?;
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+        let mut select = Entity::find().filter(Column::UserId.eq(user_id));
+        if let Some(entity) = query.entity.as_deref().map(str::trim).filter(|e| !e.is_empty()) {
+            select = select.filter(Column::UiIntent.contains(entity));
+        }
+        if let Some(product) = query.product.as_deref().filter(|p| !p.is_empty()) {
+            select = select.filter(Column::Product.eq(product));
+        }
+        if let Some(from) = date_from {
+            select = select.filter(Column::CreatedAt.gte(from.and_time(Default::default())));
+        }
+        if let Some(to) = date_to {
+            let end = (to + Duration::days(1)).and_time(Default::default());
+            select = select.filter(Column::CreatedAt.lt(end));
+        }
+
+        let paginator = select.order_by_desc(Column::CreatedAt).paginate(db, page_size);
+        let total_items = paginator.num_items().await?;
+        let total_pages = paginator.num_pages().await?;
+        let logs = paginator.fetch_page(page - 1).await?;
+
+        Ok(HistoryPage {
+            items: logs.into_iter().map(HistoryItem::of).collect(),
+            page,
+            page_size,
+            total_pages,
+            total_items,
+        })
+    }
+
+    /// Generation `id` if it belongs to `user_id`
+    pub async fn find(db: &DatabaseConnection, user_id: i32, id: i32) -> Result<Option<Model>> {
+        Ok(Entity::find_by_id(id)
+            .filter(Column::UserId.eq(user_id))
+            .one(db)
+            .await?)
+    }
+
+    /// Output settings of the job's project (`context.project`), if it has
+    /// any; files of other jobs are written as UTF-8/LF without headers
+    pub async fn project_settings(db: &DatabaseConnection, log: &Model) -> Option<project_output_settings::Model> {
+        let project = log
+            .request_payload
+            .as_deref()
+            .and_then(|p| serde_json::from_str::<GenerateJobRequest>(p).ok())
+            .and_then(|r| r.context.project)?;
+        project_output_settings::Model::find_by_project(db, &project, &TenantScope::new(log.company.as_deref()))
+            .await
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_name_from_intents() {
+        assert_eq!(
+            entity_name(r#"{"screen_name":"member_list","screen_type":"list"}"#).as_deref(),
+            Some("member_list")
+        );
+        assert_eq!(
+            entity_name(r#"{"entity_name":"Member","table_name":"TB_MEMBER"}"#).as_deref(),
+            Some("Member")
+        );
+        assert_eq!(entity_name("not json"), None);
+    }
+
+    #[test]
+    fn test_preview_keeps_first_lines() {
+        let content = (1..=40).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
+        let preview = ArtifactPreview::of(&PackagedFile {
+            name: "member_list.js".to_string(),
+            content,
+        });
+        assert_eq!(preview.total_lines, 40);
+        assert!(preview.truncated);
+        assert_eq!(preview.preview.lines().count(), PREVIEW_LINES);
+        assert!(preview.preview.ends_with("line 15"));
+    }
+
+    #[test]
+    fn test_date_range_validation() {
+        let query = HistoryQuery {
+            date_from: Some("2026-10-01".to_string()),
+            date_to: Some("2026-10-09".to_string()),
+            ..Default::default()
+        };
+        let (from, to) = query.date_range().unwrap();
+        assert_eq!(from, NaiveDate::from_ymd_opt(2026, 10, 1));
+        assert_eq!(to, NaiveDate::from_ymd_opt(2026, 10, 9));
+
+        let reversed = HistoryQuery {
+            date_from: Some("2026-10-09".to_string()),
+            date_to: Some("2026-10-01".to_string()),
+            ..Default::default()
+        };
+        assert!(reversed.date_range().is_err());
+
+        let invalid = HistoryQuery {
+            date_from: Some("10/01/2026".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.date_range().is_err());
+        assert_eq!(HistoryQuery::default().date_range().unwrap(), (None, None));
+    }
+}
@@ -0,0 +1,247 @@
+//! Generation Lifecycle Hooks
+//!
+//! Deployments customize generation without forking the pipeline by
+//! implementing `GenerationHook` in a small extension crate and registering it
+//! on the `HookRegistry` of the app context:
+//!
+//! ```rust,ignore
+//! async fn after_context(ctx: AppContext) -> Result<AppContext> {
+//!     services::generation_hooks::install(&ctx);
+//!     HookRegistry::from_context(&ctx).register(acme_codegen::CopyrightHook);
+//!     Ok(ctx)
+//! }
+//! ```
+//!
+//! Hooks run in registration order, synchronously, on the request's task, and
+//! may change what they are handed:
+//!
+//! | Hook | When | Mutable |
+//! |------|------|---------|
+//! | `on_intent_normalized` | Input normalized, request options applied | UI / Spring intent |
+//! | `on_prompt_compiled` | Before the first LLM call, and before the notes-free retry of a refused request | System and user prompt |
+//! | `on_llm_completed` | Each LLM output, before parsing | Raw output |
+//! | `on_pipeline_finished` | Validated artifacts, before logging | Status, artifacts, warnings |
+//!
+//! A panicking hook is logged and skipped; the generation continues with
+//! whatever the hook had changed before it panicked.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use loco_rs::app::AppContext;
+
+use crate::domain::{GenerateStatus, GeneratedArtifacts, SpringArtifacts, SpringIntent, UiIntent};
+
+/// The generation a hook runs for
+#[derive(Debug, Clone, Copy)]
+pub struct HookContext<'a> {
+    /// `xframe5-ui`, `spring-backend`, ...
+    pub product: &'a str,
+    pub user_id: Option<i32>,
+    /// `options.company_id` of the request
+    pub company_id: Option<&'a str>,
+    pub issue_key: Option<&'a str>,
+}
+
+/// Intent of the generation, by product
+pub enum NormalizedIntent<'a> {
+    Screen(&'a mut UiIntent),
+    Spring(&'a mut SpringIntent),
+}
+
+/// Compiled prompt sent to the LLM
+pub struct PromptParts<'a> {
+    pub system: &'a mut String,
+    pub user: &'a mut String,
+}
+
+/// One LLM response
+pub struct LlmOutput<'a> {
+    /// 1 for the first call, 2 for the retry
+    pub attempt: u32,
+    pub raw: &'a mut String,
+}
+
+/// Artifacts of a finished generation, by product
+pub enum FinishedArtifacts<'a> {
+    Screen(&'a mut GeneratedArtifacts),
+    Spring(&'a mut SpringArtifacts),
+}
+
+/// Result of a finished generation
+pub struct PipelineOutcome<'a> {
+    pub status: &'a mut GenerateStatus,
+    /// None when the generation failed
+    pub artifacts: Option<FinishedArtifacts<'a>>,
+    pub warnings: &'a mut Vec<String>,
+    /// Error message of a failed generation
+    pub error: Option<&'a str>,
+}
+
+/// Extension point in the generation lifecycle; every method defaults to no-op
+pub trait GenerationHook: Send + Sync {
+    /// Shown in logs when the hook panics
+    fn name(&self) -> &str;
+
+    fn on_intent_normalized(&self, _ctx: &HookContext, _intent: &mut NormalizedIntent) {}
+
+    fn on_prompt_compiled(&self, _ctx: &HookContext, _prompt: &mut PromptParts) {}
+
+    fn on_llm_completed(&self, _ctx: &HookContext, _output: &mut LlmOutput) {}
+
+    fn on_pipeline_finished(&self, _ctx: &HookContext, _outcome: &mut PipelineOutcome) {}
+}
+
+/// Registered hooks (cheap to clone; clones share the list)
+#[derive(Clone, Default)]
+pub struct HookRegistry {
+    hooks: Arc<RwLock<Vec<Arc<dyn GenerationHook>>>>,
+}
+
+static REGISTRY: OnceLock<HookRegistry> = OnceLock::new();
+
+/// Process-wide registry the generation services dispatch to
+pub fn registry() -> &'static HookRegistry {
+    REGISTRY.get_or_init(HookRegistry::default)
+}
+
+/// Put the registry into the app context's shared store
+pub fn install(ctx: &AppContext) {
+    ctx.shared_store.insert(registry().clone());
+}
+
+impl HookRegistry {
+    /// Registry of the app context (the process-wide one when not installed)
+    pub fn from_context(ctx: &AppContext) -> Self {
+        ctx.shared_store
+            .get::<HookRegistry>()
+            .unwrap_or_else(|| registry().clone())
+    }
+
+    pub fn register(&self, hook: impl GenerationHook + 'static) {
+        let mut hooks = self.hooks.write().unwrap_or_else(|e| e.into_inner());
+        tracing::info!("Registered generation hook '{}'", hook.name());
+        hooks.push(Arc::new(hook));
+    }
+
+    /// Names in registration order
+    pub fn names(&self) -> Vec<String> {
+        self.snapshot().iter().map(|h| h.name().to_string()).collect()
+    }
+
+    pub fn intent_normalized(&self, ctx: &HookContext, mut intent: NormalizedIntent) {
+        self.each("on_intent_normalized", |hook| hook.on_intent_normalized(ctx, &mut intent));
+    }
+
+    pub fn prompt_compiled(&self, ctx: &HookContext, system: &mut String, user: &mut String) {
+        let mut prompt = PromptParts { system, user };
+        self.each("on_prompt_compiled", |hook| hook.on_prompt_compiled(ctx, &mut prompt));
+    }
+
+    pub fn llm_completed(&self, ctx: &HookContext, attempt: u32, raw: &mut String) {
+        let mut output = LlmOutput { attempt, raw };
+        self.each("on_llm_completed", |hook| hook.on_llm_completed(ctx, &mut output));
+    }
+
+    pub fn pipeline_finished(&self, ctx: &HookContext, mut outcome: PipelineOutcome) {
+        self.each("on_pipeline_finished", |hook| hook.on_pipeline_finished(ctx, &mut outcome));
+    }
+
+    /// Hooks are cloned out so a slow hook never blocks registration
+    fn snapshot(&self) -> Vec<Arc<dyn GenerationHook>> {
+        self.hooks.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn each(&self, event: &str, mut call: impl FnMut(&dyn GenerationHook)) {
+        for hook in self.snapshot() {
+            if catch_unwind(AssertUnwindSafe(|| call(hook.as_ref()))).is_err() {
+                tracing::error!("Generation hook '{}' panicked in {}; skipped", hook.name(), event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Stamp;
+
+    impl GenerationHook for Stamp {
+        fn name(&self) -> &str {
+            "stamp"
+        }
+
+        fn on_intent_normalized(&self, _ctx: &HookContext, intent: &mut NormalizedIntent) {
+            if let NormalizedIntent::Screen(intent) = intent {
+                intent.screen_name = format!("acme_{}", intent.screen_name);
+            }
+        }
+
+        fn on_llm_completed(&self, ctx: &HookContext, output: &mut LlmOutput) {
+            output.raw.push_str(&format!("\n// {} attempt {}", ctx.product, output.attempt));
+        }
+
+        fn on_pipeline_finished(&self, _ctx: &HookContext, outcome: &mut PipelineOutcome) {
+            outcome.warnings.push("Warning: reviewed by stamp".to_string());
+            *outcome.status = GenerateStatus::PartialSuccess;
+        }
+    }
+
+    struct Broken;
+
+    impl GenerationHook for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn on_prompt_compiled(&self, _ctx: &HookContext, prompt: &mut PromptParts) {
+            prompt.user.push_str("\nbefore panic");
+            panic!("hook bug");
+        }
+    }
+
+    fn ctx() -> HookContext<'static> {
+        HookContext {
+            product: "xframe5-ui",
+            user_id: Some(1),
+            company_id: None,
+            issue_key: None,
+        }
+    }
+
+    #[test]
+    fn test_hooks_run_in_order_and_mutate() {
+        let registry = HookRegistry::default();
+        registry.register(Stamp);
+        registry.register(Broken);
+        assert_eq!(registry.names(), vec!["stamp", "broken"]);
+
+        let mut intent = UiIntent::new("member_list", crate::domain::ScreenType::List);
+        registry.intent_normalized(&ctx(), NormalizedIntent::Screen(&mut intent));
+        assert_eq!(intent.screen_name, "acme_member_list");
+
+        let mut raw = "--- XML ---".to_string();
+        registry.llm_completed(&ctx(), 2, &mut raw);
+        assert!(raw.ends_with("// xframe5-ui attempt 2"));
+
+        let (mut status, mut warnings) = (GenerateStatus::Success, vec![]);
+        registry.pipeline_finished(
+            &ctx(),
+            PipelineOutcome { status: &mut status, artifacts: None, warnings: &mut warnings, error: None },
+        );
+        assert_eq!(status, GenerateStatus::PartialSuccess);
+        assert_eq!(warnings, vec!["Warning: reviewed by stamp"]);
+    }
+
+    #[test]
+    fn test_panicking_hook_is_skipped() {
+        let registry = HookRegistry::default();
+        registry.register(Broken);
+        registry.register(Broken);
+
+        let (mut system, mut user) = (String::new(), "prompt".to_string());
+        registry.prompt_compiled(&ctx(), &mut system, &mut user);
+        assert_eq!(user, "prompt\nbefore panic\nbefore panic");
+    }
+}
@@ -0,0 +1,256 @@
+//! Git Publisher
+//!
+//! Commits the files of a completed job to the Git remote of its project:
+//! a shallow clone of the base branch, a `<prefix><job_id>` branch with the
+//! packaged files (same encoding and headers as the ZIP download) and a
+//! commit message carrying the generation's provenance. With a merge request
+//! target the branch is then proposed against the base branch through the
+//! GitHub or GitLab API.
+//!
+//! Uses the `git` executable of the server. The clone lives in a temporary
+//! directory that is removed afterwards.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::domain::{GitIntegration, GitProvenance, GitProvider, HeaderVars, MergeRequestTarget, OutputFormat};
+use crate::models::_entities::{generation_logs, users};
+use crate::services::generation_history::entity_name;
+use crate::services::{ArtifactPackager, GenerationHistoryService};
+
+/// Author of generation commits
+const COMMIT_AUTHOR: &str = "xFrame5 Code Generator";
+const COMMIT_EMAIL: &str = "codegen@localhost";
+
+/// Outcome of a push
+#[derive(Debug, Clone, Serialize)]
+pub struct GitPushResult {
+    pub branch: String,
+    /// Commit SHA
+    pub commit: String,
+    /// Web URL of the opened pull/merge request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_request_url: Option<String>,
+}
+
+/// Pushes generated files to project Git remotes
+pub struct GitPublisher;
+
+impl GitPublisher {
+    /// Push a completed job to its project's remote
+    pub async fn push_job(db: &DatabaseConnection, job: &generation_logs::Model) -> Result<GitPushResult> {
+        let job_id = job.job_id.clone().ok_or_else(|| anyhow!("Generation has no job id"))?;
+        let artifacts = match (job.status.as_str(), job.artifacts.as_deref()) {
+            ("completed", Some(artifacts)) => artifacts,
+            _ => bail!("Job {} has no artifacts to push", job_id),
+        };
+        let settings = GenerationHistoryService::project_settings(db, job)
+            .await
+            .ok_or_else(|| anyhow!("Job {} has no project output settings", job_id))?;
+        let git = settings
+            .git_integration()
+            .ok_or_else(|| anyhow!("Project {} has no Git integration", settings.project))?;
+        git.validate().map_err(|e| anyhow!(e))?;
+        let token = Self::token(&git)?;
+
+        let mut files = ArtifactPackager::files_for(&job.product, artifacts)?;
+        ArtifactPackager::stamp_headers(&mut files, &settings.file_headers(), &HeaderVars::new(&settings.project));
+        let format: OutputFormat = settings.output_format();
+        let encoded = files
+            .iter()
+            .map(|f| Ok((git.file_path(&f.name), ArtifactPackager::encode(&f.content, &format)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let requested_by = users::Entity::find_by_id(job.user_id).one(db).await?.map(|u| u.email);
+        let provenance = GitProvenance {
+            job_id: job_id.clone(),
+            product: job.product.clone(),
+            screen: entity_name(&job.ui_intent),
+            project: Some(settings.project.clone()),
+            template_version: job.template_version,
+            requested_by,
+            generated_at: job.completed_at.unwrap_or(job.created_at).to_rfc3339(),
+        };
+
+        let branch = git.branch_name(&job_id);
+        let commit = {
+            let (git, token, branch) = (git.clone(), token.clone(), branch.clone());
+            let message = provenance.commit_message();
+            tokio::task::spawn_blocking(move || Self::commit_and_push(&git, token.as_deref(), &branch, &encoded, &message))
+                .await??
+        };
+        tracing::info!("Job {} pushed to {} ({})", job_id, branch, commit);
+
+        let merge_request_url = match (&git.merge_request, token.as_deref()) {
+            (Some(target), Some(token)) => Some(Self::open_merge_request(target, token, &git, &branch, &provenance).await?),
+            _ => None,
+        };
+
+        Ok(GitPushResult {
+            branch,
+            commit,
+            merge_request_url,
+        })
+    }
+
+    /// Push a just-completed job when its project has `auto_push` (failures are only logged)
+    pub async fn auto_push(db: &DatabaseConnection, job: &generation_logs::Model) {
+        let Some(settings) = GenerationHistoryService::project_settings(db, job).await else {
+            return;
+        };
+        if !settings.git_integration().is_some_and(|git| git.auto_push) {
+            return;
+        }
+        if let Err(e) = Self::push_job(db, job).await {
+            tracing::warn!("Auto push of job {} failed: {}", job.job_id.as_deref().unwrap_or("-"), e);
+        }
+    }
+
+    fn token(git: &GitIntegration) -> Result<Option<String>> {
+        let Some(name) = git.token_env.as_deref() else {
+            return Ok(None);
+        };
+        match std::env::var(name).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) {
+            Some(token) => Ok(Some(token)),
+            None => bail!("{} is not set on the server", name),
+        }
+    }
+
+    /// Clone, write the files, commit and push; returns the commit SHA
+    fn commit_and_push(
+        git: &GitIntegration,
+        token: Option<&str>,
+        branch: &str,
+        files: &[(String, Vec<u8>)],
+        message: &str,
+    ) -> Result<String> {
+        let dir = std::env::temp_dir().join(format!("codegen-push-{}", uuid::Uuid::new_v4()));
+        let result = (|| {
+            let remote = git.authenticated_url(token);
+            let dir_arg = dir.to_string_lossy().to_string();
+            run_git(
+                None,
+                &["clone", "--depth", "1", "--branch", &git.base_branch, &remote, &dir_arg],
+                token,
+            )?;
+            run_git(Some(&dir), &["checkout", "-b", branch], token)?;
+            for (path, bytes) in files {
+                let target = safe_join(&dir, path)?;
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&target, bytes)?;
+            }
+            run_git(Some(&dir), &["add", "--all"], token)?;
+            run_git(
+                Some(&dir),
+                &[
+                    "-c",
+                    &format!("user.name={}", COMMIT_AUTHOR),
+                    "-c",
+                    &format!("user.email={}", COMMIT_EMAIL),
+                    "commit",
+                    "--allow-empty",
+                    "-m",
+                    message,
+                ],
+                token,
+            )?;
+            run_git(Some(&dir), &["push", "origin", branch], token)?;
+            run_git(Some(&dir), &["rev-parse", "HEAD"], token)
+        })();
+        let _ = std::fs::remove_dir_all(&dir);
+        result
+    }
+
+    /// Open a pull/merge request; returns its web URL
+    async fn open_merge_request(
+        target: &MergeRequestTarget,
+        token: &str,
+        git: &GitIntegration,
+        branch: &str,
+        provenance: &GitProvenance,
+    ) -> Result<String> {
+        let message = provenance.commit_message();
+        let (title, description) = message.split_once("\n\n").unwrap_or((&message, ""));
+        let client = reqwest::Client::new();
+        let (request, url_field) = match target.provider {
+            GitProvider::Github => (
+                client
+                    .post(format!("{}/repos/{}/pulls", target.api_url(), target.repository.trim()))
+                    .bearer_auth(token)
+                    .header("Accept", "application/vnd.github+json")
+                    .header("User-Agent", COMMIT_AUTHOR)
+                    .json(&json!({"title": title, "head": branch, "base": git.base_branch, "body": description})),
+                "html_url",
+            ),
+            GitProvider::Gitlab => (
+                client
+                    .post(format!(
+                        "{}/projects/{}/merge_requests",
+                        target.api_url(),
+                        urlencoding::encode(target.repository.trim())
+                    ))
+                    .header("PRIVATE-TOKEN", token)
+                    .json(&json!({
+                        "source_branch": branch,
+                        "target_branch": git.base_branch,
+                        "title": title,
+                        "description": description,
+                    })),
+                "web_url",
+            ),
+        };
+
+        let response = request.send().await.context("Merge request API unreachable")?;
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            bail!(
+                "Merge request for {} rejected ({}): {}",
+                branch,
+                status,
+                body.get("message").and_then(|m| m.as_str()).unwrap_or("no details")
+            );
+        }
+        body.get(url_field)
+            .and_then(|u| u.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Merge request for {} opened without {}", branch, url_field))
+    }
+}
+
+/// Run git; output on success, stderr (token removed) on failure
+fn run_git(dir: Option<&Path>, args: &[&str], token: Option<&str>) -> Result<String> {
+    let mut command = Command::new("git");
+    command.args(args).env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command.output().context("git is not installed on the server")?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    let mut stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if let Some(token) = token {
+        stderr = stderr
+            .replace(token, "***")
+            .replace(urlencoding::encode(token).as_ref(), "***");
+    }
+    let subcommand = args.iter().find(|a| !a.starts_with('-') && !a.contains('=')).copied();
+    bail!("git {} failed: {}", subcommand.unwrap_or_default(), stderr)
+}
+
+/// `path` under `dir`, refusing anything that would leave it
+fn safe_join(dir: &Path, path: &str) -> Result<PathBuf> {
+    let relative = Path::new(path);
+    if relative.is_absolute() || relative.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        bail!("Refusing to write {} outside the repository", path);
+    }
+    Ok(dir.join(relative))
+}
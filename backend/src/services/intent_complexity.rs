@@ -0,0 +1,396 @@
+//! Intent Complexity
+//!
+//! Estimates how big a screen an intent asks for before any prompt is sent.
+//! Very wide tables (80+ columns) give a single screen nobody can use and a
+//! prompt plus output that runs past the model's token budget, so wide
+//! intents get a warning and a suggestion to split them:
+//!
+//! - a list screen with the key columns only (primary key, required and
+//!   short fields, at most [`LIST_KEY_COLUMNS`])
+//! - a detail screen with every field, laid out in tabs of related columns
+//!   (grouped by the first `_` segment of the column name, e.g. `ADDR_*`,
+//!   at most [`TAB_COLUMNS`] per tab)
+//!
+//! [`IntentComplexity::split`] builds both intents, the detail one with a
+//! [`TabsIntent`] of the column groups; they can be saved or generated one
+//! after the other.
+
+use serde::Serialize;
+
+use crate::domain::{
+    default_actions_for_screen_type, ColumnIntent, DatasetIntent, GridColumnIntent, GridIntent, ScreenType,
+    TabIntent, TabsIntent, UiIntent, UiType,
+};
+use crate::services::prompt_compression::estimate_tokens;
+use crate::services::PromptCompiler;
+
+/// Fields on one screen above which generation warns
+pub const WIDE_SCREEN_COLUMNS: usize = 40;
+
+/// Fields on one screen above which the screen should be split
+pub const SPLIT_SCREEN_COLUMNS: usize = 80;
+
+/// Prompt plus expected output above which the screen should be split
+pub const TOKEN_BUDGET: usize = 24_000;
+
+/// Visible grid columns of the list screen of a split
+pub const LIST_KEY_COLUMNS: usize = 8;
+
+/// Fields per tab of the detail screen of a split
+pub const TAB_COLUMNS: usize = 20;
+
+/// Rough output per field: the component, its label and a grid column
+const OUTPUT_TOKENS_PER_FIELD: usize = 90;
+
+/// Name groups smaller than this go to the 기타 tab
+const MIN_GROUP_COLUMNS: usize = 3;
+
+/// How wide an intent is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplexityLevel {
+    Normal,
+    /// Generates, but the screen is crowded
+    Wide,
+    /// Should be split into a list and a tabbed detail screen
+    TooWide,
+}
+
+/// Related columns shown together on one detail tab
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnGroup {
+    /// Tab id (`basic`, `addr`, `addr_2`, `other`)
+    pub id: String,
+    pub title: String,
+    pub columns: Vec<String>,
+}
+
+/// Size of an intent and how to split it
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplexityReport {
+    pub level: ComplexityLevel,
+
+    /// Dataset columns across all datasets
+    pub field_count: usize,
+
+    /// Grid columns across all grids
+    pub grid_column_count: usize,
+
+    /// Dataset with the most columns, which a split works on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub widest_dataset: Option<String>,
+
+    pub estimated_prompt_tokens: usize,
+    pub estimated_output_tokens: usize,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+
+    /// Suggested detail tabs (wide and too wide intents)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub column_groups: Vec<ColumnGroup>,
+}
+
+impl ComplexityReport {
+    pub fn estimated_total_tokens(&self) -> usize {
+        self.estimated_prompt_tokens + self.estimated_output_tokens
+    }
+
+    /// Generation notes: the size, then the suggestions (`Note:` so they do
+    /// not count as findings against the generated code)
+    pub fn notes(&self) -> Vec<String> {
+        let size = match self.level {
+            ComplexityLevel::Normal => return Vec::new(),
+            ComplexityLevel::Wide => "Note: wide screen",
+            ComplexityLevel::TooWide => "Note: screen too wide for one generation",
+        };
+        let mut notes = vec![format!(
+            "{}: {} fields, {} grid columns, ~{} tokens of prompt and output",
+            size,
+            self.field_count,
+            self.grid_column_count,
+            self.estimated_total_tokens()
+        )];
+        notes.extend(self.suggestions.iter().cloned());
+        notes
+    }
+}
+
+/// A wide intent split into a list and a tabbed detail screen
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenSplit {
+    pub list: UiIntent,
+    pub detail: UiIntent,
+}
+
+pub struct IntentComplexity;
+
+impl IntentComplexity {
+    pub fn estimate(intent: &UiIntent) -> ComplexityReport {
+        let field_count: usize = intent.datasets.iter().map(|ds| ds.columns.len()).sum();
+        let grid_column_count: usize = intent.grids.iter().map(|g| g.columns.len()).sum();
+        let widest = Self::widest_dataset(intent);
+
+        let prompt = PromptCompiler::compile_with_defaults(intent, None);
+        let estimated_prompt_tokens = estimate_tokens(&prompt.full());
+        let estimated_output_tokens = (field_count + grid_column_count) * OUTPUT_TOKENS_PER_FIELD;
+
+        let total = estimated_prompt_tokens + estimated_output_tokens;
+        let level = if field_count > SPLIT_SCREEN_COLUMNS || total > TOKEN_BUDGET {
+            ComplexityLevel::TooWide
+        } else if field_count > WIDE_SCREEN_COLUMNS {
+            ComplexityLevel::Wide
+        } else {
+            ComplexityLevel::Normal
+        };
+
+        let column_groups = match (level, widest) {
+            (ComplexityLevel::Normal, _) | (_, None) => Vec::new(),
+            (_, Some(dataset)) => Self::group_columns(&dataset.columns),
+        };
+
+        let mut suggestions = Vec::new();
+        if level != ComplexityLevel::Normal {
+            if grid_column_count > LIST_KEY_COLUMNS * 2 {
+                suggestions.push(format!(
+                    "Suggestion: show only the key columns in the grid ({} of {}) and open the rest in a detail screen",
+                    LIST_KEY_COLUMNS, grid_column_count
+                ));
+            }
+            if column_groups.len() > 1 && intent.tabs.is_none() {
+                let titles: Vec<String> = column_groups
+                    .iter()
+                    .map(|g| format!("{} ({})", g.title, g.columns.len()))
+                    .collect();
+                suggestions.push(format!("Suggestion: lay the detail fields out in tabs: {}", titles.join(", ")));
+            }
+        }
+        if level == ComplexityLevel::TooWide {
+            suggestions.push(
+                "Suggestion: split into a list and a tabbed detail screen (POST /agent/intent/complexity with \"split\": true)"
+                    .to_string(),
+            );
+        }
+
+        ComplexityReport {
+            level,
+            field_count,
+            grid_column_count,
+            widest_dataset: widest.map(|ds| ds.id.clone()),
+            estimated_prompt_tokens,
+            estimated_output_tokens,
+            suggestions,
+            column_groups,
+        }
+    }
+
+    /// List and tabbed detail intents for a wide intent (`None` when it has
+    /// no dataset or its widest dataset fits on one tab)
+    pub fn split(intent: &UiIntent) -> Option<ScreenSplit> {
+        let dataset = Self::widest_dataset(intent)?;
+        if dataset.columns.len() <= TAB_COLUMNS {
+            return None;
+        }
+        let base = Self::base_name(&intent.screen_name);
+
+        // List: key columns, PKs kept (hidden) to open the detail
+        let keys = Self::key_columns(&dataset.columns);
+        let mut list_dataset = DatasetIntent::new(&dataset.id);
+        list_dataset.table_name = dataset.table_name.clone();
+        list_dataset.columns = dataset
+            .columns
+            .iter()
+            .filter(|c| c.is_pk || keys.contains(&c.name.as_str()))
+            .cloned()
+            .collect();
+        let grid_id = intent
+            .grids
+            .iter()
+            .find(|g| g.dataset_id == dataset.id)
+            .map_or_else(|| "grid_list".to_string(), |g| g.id.clone());
+        let grid = GridIntent::new(grid_id, &dataset.id).with_columns(
+            keys.iter()
+                .filter_map(|name| dataset.columns.iter().find(|c| c.name == *name))
+                .map(|c| Self::grid_column(intent, &dataset.id, c))
+                .collect(),
+        );
+        let mut list = UiIntent::new(format!("{}_list", base), ScreenType::List).with_dataset(list_dataset);
+        list.grids.push(grid);
+        list.actions = default_actions_for_screen_type(ScreenType::List);
+        list.locale = intent.locale.clone();
+        list.notes = Some(format!("Open {}_detail with the selected row's key to see every field.", base));
+
+        // Detail: every field of the dataset, one tab per column group
+        let tabs: Vec<TabIntent> = Self::group_columns(&dataset.columns)
+            .into_iter()
+            .map(|group| TabIntent::new(group.id, group.title, &dataset.id).with_columns(group.columns))
+            .collect();
+        let mut detail = UiIntent::new(format!("{}_detail", base), ScreenType::Detail)
+            .with_dataset(dataset.clone())
+            .with_tabs(TabsIntent::new(tabs));
+        detail.actions = default_actions_for_screen_type(ScreenType::Detail);
+        detail.locale = intent.locale.clone();
+
+        Some(ScreenSplit { list, detail })
+    }
+
+    fn widest_dataset(intent: &UiIntent) -> Option<&DatasetIntent> {
+        // First of equally wide datasets (max_by_key keeps the last)
+        intent
+            .datasets
+            .iter()
+            .rev()
+            .max_by_key(|ds| ds.columns.len())
+            .filter(|ds| !ds.columns.is_empty())
+    }
+
+    /// `member_list` / `member_detail` → `member`
+    fn base_name(screen_name: &str) -> &str {
+        ["_list", "_detail", "_popup", "_mgmt"]
+            .iter()
+            .find_map(|suffix| screen_name.strip_suffix(suffix))
+            .filter(|base| !base.is_empty())
+            .unwrap_or(screen_name)
+    }
+
+    /// Visible columns of the list grid: required ones first, then short
+    /// fields in dataset order
+    fn key_columns(columns: &[ColumnIntent]) -> Vec<&str> {
+        let listable = |c: &&ColumnIntent| {
+            !c.is_pk && !matches!(c.ui_type, UiType::Hidden | UiType::TextArea) && c.max_length.is_none_or(|n| n <= 100)
+        };
+        let required = columns.iter().filter(listable).filter(|c| c.required);
+        let optional = columns.iter().filter(listable).filter(|c| !c.required);
+        let mut keys: Vec<&str> = required.chain(optional).map(|c| c.name.as_str()).take(LIST_KEY_COLUMNS).collect();
+        // Back in dataset order
+        keys.sort_by_key(|name| columns.iter().position(|c| c.name == *name));
+        keys
+    }
+
+    /// The intent's own grid column, else a plain one
+    fn grid_column(intent: &UiIntent, dataset_id: &str, column: &ColumnIntent) -> GridColumnIntent {
+        intent
+            .grids
+            .iter()
+            .filter(|g| g.dataset_id == dataset_id)
+            .flat_map(|g| g.columns.iter())
+            .find(|gc| gc.name == column.name)
+            .cloned()
+            .unwrap_or_else(|| GridColumnIntent::new(&column.name, &column.label))
+    }
+
+    /// Tabs of related columns: 기본 (keys and required), one per name
+    /// prefix shared by enough columns, then 기타; tabs over
+    /// [`TAB_COLUMNS`] are cut into numbered parts
+    fn group_columns(columns: &[ColumnIntent]) -> Vec<ColumnGroup> {
+        let prefix = |c: &ColumnIntent| c.name.split('_').next().unwrap_or(&c.name).to_uppercase();
+
+        let mut basic = Vec::new();
+        let mut by_prefix: Vec<(String, Vec<String>)> = Vec::new();
+        for column in columns {
+            if column.is_pk || column.required {
+                basic.push(column.name.clone());
+                continue;
+            }
+            let key = prefix(column);
+            match by_prefix.iter_mut().find(|(p, _)| *p == key) {
+                Some((_, names)) => names.push(column.name.clone()),
+                None => by_prefix.push((key, vec![column.name.clone()])),
+            }
+        }
+
+        // (id, title, columns)
+        let mut groups = vec![("basic".to_string(), "기본".to_string(), basic)];
+        let mut other = Vec::new();
+        for (key, names) in by_prefix {
+            if names.len() >= MIN_GROUP_COLUMNS {
+                groups.push((key.to_lowercase(), key, names));
+            } else {
+                other.extend(names);
+            }
+        }
+        groups.push(("other".to_string(), "기타".to_string(), other));
+
+        groups
+            .into_iter()
+            .filter(|(_, _, names)| !names.is_empty())
+            .flat_map(|(id, title, names)| {
+                let parts = names.len().div_ceil(TAB_COLUMNS);
+                names
+                    .chunks(TAB_COLUMNS)
+                    .enumerate()
+                    .map(|(i, chunk)| match parts {
+                        1 => ColumnGroup { id: id.clone(), title: title.clone(), columns: chunk.to_vec() },
+                        _ => ColumnGroup {
+                            id: format!("{}_{}", id, i + 1),
+                            title: format!("{} {}", title, i + 1),
+                            columns: chunk.to_vec(),
+                        },
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide_intent(extra: usize) -> UiIntent {
+        let mut dataset = DatasetIntent::new("ds_customer")
+            .with_table("TB_CUSTOMER")
+            .with_column(ColumnIntent::new("CUST_ID", "고객ID").primary_key())
+            .with_column(ColumnIntent::new("CUST_NM", "고객명").required());
+        for i in 0..extra {
+            let prefix = ["ADDR", "BANK", "CONTRACT"][i % 3];
+            dataset = dataset.with_column(ColumnIntent::new(format!("{}_{:02}", prefix, i), format!("항목{}", i)));
+        }
+        let grid = GridIntent::new("grid_customer", "ds_customer").with_columns(
+            dataset.columns.iter().map(|c| GridColumnIntent::new(&c.name, &c.label)).collect(),
+        );
+        UiIntent::new("customer_list", ScreenType::List).with_dataset(dataset).with_grid(grid)
+    }
+
+    #[test]
+    fn test_estimate_levels() {
+        let narrow = IntentComplexity::estimate(&wide_intent(10));
+        assert_eq!(narrow.level, ComplexityLevel::Normal);
+        assert!(narrow.notes().is_empty());
+        assert!(narrow.column_groups.is_empty());
+
+        let wide = IntentComplexity::estimate(&wide_intent(88));
+        assert_eq!(wide.level, ComplexityLevel::TooWide);
+        assert_eq!(wide.field_count, 90);
+        assert_eq!(wide.widest_dataset.as_deref(), Some("ds_customer"));
+        let notes = wide.notes();
+        assert!(notes[0].starts_with("Note: screen too wide"));
+        assert!(notes.iter().any(|w| w.contains("lay the detail fields out in tabs")));
+    }
+
+    #[test]
+    fn test_split_into_list_and_tabbed_detail() {
+        let split = IntentComplexity::split(&wide_intent(88)).unwrap();
+
+        assert_eq!(split.list.screen_name, "customer_list");
+        assert_eq!(split.list.grids[0].id, "grid_customer");
+        assert_eq!(split.list.grids[0].columns.len(), LIST_KEY_COLUMNS);
+        assert_eq!(split.list.grids[0].columns[0].name, "CUST_NM");
+        // PK kept in the list dataset to open the detail
+        assert!(split.list.datasets[0].columns.iter().any(|c| c.name == "CUST_ID"));
+
+        assert_eq!(split.detail.screen_name, "customer_detail");
+        assert_eq!(split.detail.screen_type, ScreenType::Detail);
+        assert_eq!(split.detail.datasets[0].columns.len(), 90);
+
+        let tabs = &split.detail.tabs.as_ref().unwrap().tabs;
+        let titles: Vec<&str> = tabs.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["기본", "ADDR 1", "ADDR 2", "BANK 1", "BANK 2", "CONTRACT 1", "CONTRACT 2"]);
+        assert_eq!(tabs[1].panel(), "pnl_tab_addr_1");
+        assert!(tabs.iter().all(|t| t.dataset_id == "ds_customer" && t.columns.len() <= TAB_COLUMNS));
+        let fields: usize = tabs.iter().map(|t| t.columns.len()).sum();
+        assert_eq!(fields, 90);
+
+        assert!(IntentComplexity::split(&wide_intent(10)).is_none());
+    }
+}
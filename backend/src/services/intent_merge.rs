@@ -0,0 +1,487 @@
+//! Intent Merge
+//!
+//! Applies short refinements ("status column should be a combobox with code
+//! group CM001, hide email") onto an intent normalized from a schema or any
+//! other input. Instructions are read with a fixed command grammar (English
+//! or Korean), never by the LLM, so the same base and instructions always
+//! give the same intent. The result lists what each instruction did, the
+//! instructions that weren't understood, and a structural diff.
+//!
+//! One instruction per line, or separated by `,` / `;`. Each one names a
+//! column (DB name, label, or a unique `_`-separated part of a name such as
+//! `email` for `EMAIL_ADDR`) and what to do with it:
+//!
+//! | Command | Examples |
+//! |---------|----------|
+//! | Hide / show | `hide email`, `REG_DATE 숨김`, `show MEMO` |
+//! | Control | `status should be a combobox`, `gender radio`, `비고 텍스트영역` |
+//! | Code group | `status with code group CM001`, `상태 공통코드 CM001` |
+//! | Required / read-only | `name is required`, `가입일 수정불가` |
+//! | Label | `label status as 처리상태`, `상태 라벨 처리상태` |
+//! | Max length | `name max length 50`, `이름 최대 50자` |
+//! | Grid | `page size 50`, `editable grid`, `그리드 편집` |
+//! | Buttons | `add export button`, `remove the delete button`, `삭제 버튼 제거` |
+
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    diff_intents, ActionIntent, ColumnIntent, DataType, GenerateInput, GridColumnIntent, IntentChange, UiIntent,
+    UiType,
+};
+use crate::models::saved_intents;
+use crate::services::screen_dsl::ACTION_SHORTHANDS;
+use crate::services::{NormalizerService, TenantScope};
+
+/// One change an instruction asks for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Refinement {
+    Hide { column: String },
+    Show { column: String },
+    UiType { column: String, ui_type: UiType },
+    CodeGroup { column: String, code_group: String },
+    Required { column: String },
+    Readonly { column: String },
+    Label { column: String, label: String },
+    MaxLength { column: String, max_length: u32 },
+    PageSize { page_size: u32 },
+    EditableGrid,
+    AddAction { action: String },
+    RemoveAction { action: String },
+}
+
+/// An instruction and the refinements read from it
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedInstruction {
+    pub instruction: String,
+    pub refinements: Vec<Refinement>,
+}
+
+/// An instruction that was left out, and why
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedInstruction {
+    pub instruction: String,
+    pub reason: String,
+}
+
+/// Result of a merge
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeResult {
+    /// Refined intent (send as `{"type": "intent", ...}` to generate)
+    pub intent: UiIntent,
+    pub applied: Vec<AppliedInstruction>,
+    pub skipped: Vec<SkippedInstruction>,
+    /// Structural diff from the base intent
+    pub changes: Vec<IntentChange>,
+}
+
+/// Control keywords, checked in order (`date time` before `date`)
+const UI_KEYWORDS: &[(&str, UiType)] = &[
+    (r"combo\s*box|combo|drop\s*down|select\s*box|콤보|드롭다운|선택\s*상자", UiType::Combo),
+    (r"radio|라디오", UiType::Radio),
+    (r"check\s*box|체크\s*박스", UiType::Checkbox),
+    (r"text\s*area|multi-?line|텍스트\s*영역|여러\s*줄", UiType::TextArea),
+    (r"date\s*time\s*picker|일시|날짜\s*시간", UiType::DateTimePicker),
+    (r"date\s*picker|calendar|달력", UiType::DatePicker),
+    (r"number\s*(?:field|input)|numeric|숫자", UiType::Number),
+    (r"file\s*(?:upload|input)|첨부|파일", UiType::File),
+    (r"text\s*(?:field|input|box)|입력\s*칸|텍스트\s*박스", UiType::Input),
+];
+
+/// Korean particles stripped from the end of a word before column lookup
+const PARTICLES: &[&str] = &["으로", "에서", "은", "는", "을", "를", "이", "가", "의", "로", "도"];
+
+struct Grammar {
+    split: Regex,
+    words: Regex,
+    hide: Regex,
+    show: Regex,
+    required: Regex,
+    readonly: Regex,
+    code_group: Regex,
+    label: Regex,
+    max_length: Regex,
+    page_size: Regex,
+    editable_grid: Regex,
+    button: Regex,
+    remove: Regex,
+    add: Regex,
+    ui: Vec<(Regex, UiType)>,
+}
+
+fn grammar() -> &'static Grammar {
+    static GRAMMAR: OnceLock<Grammar> = OnceLock::new();
+    GRAMMAR.get_or_init(|| {
+        let re = |pattern: &str| Regex::new(&format!("(?i){}", pattern)).unwrap();
+        Grammar {
+            split: Regex::new(r"[\n;,]+").unwrap(),
+            words: Regex::new(r"[\p{L}\p{N}_]+").unwrap(),
+            hide: re(r"\bhide\b|\bhidden\b|숨김|숨기|숨겨|감추|감춰"),
+            show: re(r"\bshow\b|\bvisible\b|보이기|표시"),
+            required: re(r"\brequired\b|\bmandatory\b|필수"),
+            readonly: re(r"read-?\s*only|\bnot editable\b|읽기\s*전용|수정\s*불가"),
+            code_group: re(r"(?:code\s*group|common\s*code|공통\s*코드|코드\s*그룹)\s*[:=]?\s*([A-Za-z0-9_-]+)"),
+            label: re(concat!(
+                r#"(?:\blabel\b|\brename\b)\s+(?:the\s+)?(?:column\s+)?[\p{L}\p{N}_]+\s+(?:as|to)\s+["'“]?([^"'”]+?)["'”]?\s*$"#,
+                r#"|(?:\blabel(?:ed|led)?\b|\btitled?\b|\bcalled\b|라벨|제목)(?:은|는|을|를)?\s*(?:\bas\b|\bto\b|\bis\b|[:=])?\s*["'“]?([^"'”]+?)["'”]?\s*(?:으로|로)?\s*$"#,
+            )),
+            max_length: re(r"max(?:imum)?\s*length\s*(?:of\s*)?(\d+)|(\d+)\s*char(?:acter)?s?\b|최대\s*(\d+)\s*자"),
+            page_size: re(r"page\s*size\s*(?:of\s*)?(\d+)|(\d+)\s*rows?\s*per\s*page|페이지\s*(?:크기|사이즈)\s*(\d+)|(\d+)\s*건씩"),
+            editable_grid: re(r"editable\s*grid|grid\s*(?:should\s*be\s*|is\s*)?editable|그리드\s*편집|편집\s*(?:가능한?\s*)?그리드"),
+            button: re(r"([\p{L}\p{N}_]+)\s*(?:button|버튼)"),
+            remove: re(r"\bremove\b|\bdrop\b|\bwithout\b|\bno\b|제거|빼|없애|없이"),
+            add: re(r"\badd\b|\bwith\b|추가|넣"),
+            ui: UI_KEYWORDS.iter().map(|(pattern, ui)| (re(pattern), *ui)).collect(),
+        }
+    })
+}
+
+/// Applies refinement instructions onto intents
+pub struct IntentMerger;
+
+impl IntentMerger {
+    /// Intent of a base input (saved intents come from the tenant's library)
+    pub async fn base_intent(db: &DatabaseConnection, input: &GenerateInput, tenant: &TenantScope) -> Result<UiIntent> {
+        match input {
+            GenerateInput::SavedIntent(saved) => saved_intents::Model::load_ui_intent(db, &saved.name, tenant).await,
+            _ => NormalizerService::normalize(input),
+        }
+    }
+
+    /// Apply the instructions onto a copy of `base`
+    pub fn merge(base: &UiIntent, instructions: &str) -> Result<MergeResult> {
+        let instructions: Vec<&str> = grammar()
+            .split
+            .split(instructions)
+            .map(|s| s.trim().trim_end_matches('.').trim())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if instructions.is_empty() {
+            return Err(anyhow!("No instructions to merge"));
+        }
+
+        let mut intent = base.clone();
+        let mut applied = Vec::new();
+        let mut skipped = Vec::new();
+        for instruction in instructions {
+            match Self::parse(&intent, instruction) {
+                Ok(refinements) => {
+                    for refinement in &refinements {
+                        Self::apply(&mut intent, refinement);
+                    }
+                    applied.push(AppliedInstruction {
+                        instruction: instruction.to_string(),
+                        refinements,
+                    });
+                }
+                Err(reason) => skipped.push(SkippedInstruction {
+                    instruction: instruction.to_string(),
+                    reason,
+                }),
+            }
+        }
+
+        let changes = diff_intents(&serde_json::to_value(base)?, &serde_json::to_value(&intent)?);
+        Ok(MergeResult {
+            intent,
+            applied,
+            skipped,
+            changes,
+        })
+    }
+
+    /// Refinements of one instruction
+    fn parse(intent: &UiIntent, instruction: &str) -> std::result::Result<Vec<Refinement>, String> {
+        let g = grammar();
+
+        if let Some(cap) = g.button.captures(instruction) {
+            let action = Self::resolve_action(&cap[1])?;
+            let rest = instruction.replacen(&cap[0], " ", 1);
+            return if g.remove.is_match(&rest) {
+                Ok(vec![Refinement::RemoveAction { action }])
+            } else if g.add.is_match(&rest) {
+                Ok(vec![Refinement::AddAction { action }])
+            } else {
+                Err("say whether to add or remove the button".to_string())
+            };
+        }
+
+        let mut refinements = Vec::new();
+        if let Some(size) = Self::number(&g.page_size, instruction) {
+            refinements.push(Refinement::PageSize { page_size: size });
+        }
+        if g.editable_grid.is_match(instruction) {
+            refinements.push(Refinement::EditableGrid);
+        }
+        if !refinements.is_empty() {
+            return Ok(refinements);
+        }
+
+        let column = Self::resolve_column(intent, instruction)?.name.clone();
+        let mut rest = instruction.to_string();
+
+        if let Some(cap) = g.code_group.captures(&rest) {
+            refinements.push(Refinement::CodeGroup {
+                column: column.clone(),
+                code_group: cap[1].to_string(),
+            });
+            rest = rest.replacen(&cap[0], " ", 1);
+        }
+        if let Some(cap) = g.label.captures(&rest) {
+            let label = cap.get(1).or_else(|| cap.get(2)).map(|m| m.as_str().trim().to_string()).unwrap_or_default();
+            rest = rest.replacen(&cap[0], " ", 1);
+            if !label.is_empty() {
+                refinements.push(Refinement::Label { column: column.clone(), label });
+            }
+        }
+        if let Some(max_length) = Self::number(&g.max_length, &rest) {
+            refinements.push(Refinement::MaxLength { column: column.clone(), max_length });
+        }
+        if g.hide.is_match(&rest) {
+            refinements.push(Refinement::Hide { column: column.clone() });
+        } else if g.show.is_match(&rest) {
+            refinements.push(Refinement::Show { column: column.clone() });
+        }
+        if let Some((_, ui_type)) = g.ui.iter().find(|(re, _)| re.is_match(&rest)) {
+            refinements.push(Refinement::UiType { column: column.clone(), ui_type: *ui_type });
+        }
+        if g.required.is_match(&rest) {
+            refinements.push(Refinement::Required { column: column.clone() });
+        }
+        if g.readonly.is_match(&rest) {
+            refinements.push(Refinement::Readonly { column: column.clone() });
+        }
+
+        if refinements.is_empty() {
+            return Err(format!("nothing to change on {} was recognized", column));
+        }
+        Ok(refinements)
+    }
+
+    /// First word of the instruction naming a column
+    fn resolve_column<'a>(intent: &'a UiIntent, instruction: &str) -> std::result::Result<&'a ColumnIntent, String> {
+        let columns: Vec<&ColumnIntent> = intent.datasets.iter().flat_map(|ds| ds.columns.iter()).collect();
+        for word in grammar().words.find_iter(instruction).map(|m| m.as_str()) {
+            let word = PARTICLES
+                .iter()
+                .find_map(|p| word.strip_suffix(p).filter(|w| !w.is_empty()))
+                .filter(|_| !columns.iter().any(|c| c.label == word || c.name.eq_ignore_ascii_case(word)))
+                .unwrap_or(word);
+
+            if let Some(column) = columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(word))
+                .or_else(|| columns.iter().find(|c| c.label == word))
+            {
+                return Ok(*column);
+            }
+            let partial: Vec<&ColumnIntent> = columns
+                .iter()
+                .copied()
+                .filter(|c| c.name.split('_').any(|part| part.eq_ignore_ascii_case(word)))
+                .collect();
+            match partial.as_slice() {
+                [column] => return Ok(column),
+                [] => {}
+                many => {
+                    let names: Vec<&str> = many.iter().map(|c| c.name.as_str()).collect();
+                    return Err(format!("'{}' matches several columns ({})", word, names.join(", ")));
+                }
+            }
+        }
+        Err("no column of the intent is named".to_string())
+    }
+
+    /// Action id for a button word (`delete`, `삭제`, `excel`)
+    fn resolve_action(word: &str) -> std::result::Result<String, String> {
+        let word = word.to_lowercase();
+        let word = if word == "excel" { "export".to_string() } else { word };
+        ACTION_SHORTHANDS
+            .iter()
+            .find(|(id, label, _)| *id == word || *label == word)
+            .map(|(id, _, _)| id.to_string())
+            .ok_or_else(|| {
+                let known: Vec<&str> = ACTION_SHORTHANDS.iter().map(|(id, _, _)| *id).collect();
+                format!("unknown button '{}' (known: {})", word, known.join(", "))
+            })
+    }
+
+    /// First captured number of a pattern with alternative groups
+    fn number(re: &Regex, text: &str) -> Option<u32> {
+        re.captures(text)
+            .and_then(|cap| cap.iter().skip(1).flatten().next().and_then(|m| m.as_str().parse().ok()))
+    }
+
+    fn apply(intent: &mut UiIntent, refinement: &Refinement) {
+        let column = match refinement {
+            Refinement::Hide { column }
+            | Refinement::Show { column }
+            | Refinement::UiType { column, .. }
+            | Refinement::CodeGroup { column, .. }
+            | Refinement::Required { column }
+            | Refinement::Readonly { column }
+            | Refinement::Label { column, .. }
+            | Refinement::MaxLength { column, .. } => column.as_str(),
+            Refinement::PageSize { page_size } => {
+                for grid in &mut intent.grids {
+                    grid.paginated = true;
+                    grid.page_size = Some(*page_size);
+                }
+                return;
+            }
+            Refinement::EditableGrid => {
+                intent.enable_grid_editing();
+                return;
+            }
+            Refinement::AddAction { action } => {
+                if !intent.actions.iter().any(|a| a.id == *action) {
+                    if let Some((id, label, action_type)) = ACTION_SHORTHANDS.iter().find(|(id, _, _)| id == action) {
+                        intent.actions.push(ActionIntent::new(*id, *label, *action_type));
+                    }
+                }
+                return;
+            }
+            Refinement::RemoveAction { action } => {
+                intent.actions.retain(|a| a.id != *action);
+                return;
+            }
+        };
+
+        for dataset in &mut intent.datasets {
+            let Some(col) = dataset.columns.iter_mut().find(|c| c.name == column) else {
+                continue;
+            };
+            match refinement {
+                Refinement::Hide { .. } => col.ui_type = UiType::Hidden,
+                Refinement::Show { .. } if col.ui_type == UiType::Hidden => {
+                    col.ui_type = match col.data_type {
+                        DataType::Integer | DataType::Decimal => UiType::Number,
+                        DataType::Date => UiType::DatePicker,
+                        DataType::DateTime => UiType::DateTimePicker,
+                        DataType::Text => UiType::TextArea,
+                        _ => UiType::Input,
+                    };
+                }
+                Refinement::UiType { ui_type, .. } => col.ui_type = *ui_type,
+                Refinement::CodeGroup { code_group, .. } => *col = col.clone().with_code_group(code_group),
+                Refinement::Required { .. } => col.required = true,
+                Refinement::Readonly { .. } => col.readonly = true,
+                Refinement::Label { label, .. } => col.label = label.clone(),
+                Refinement::MaxLength { max_length, .. } => col.max_length = Some(*max_length),
+                _ => {}
+            }
+
+            let (label, hidden) = (col.label.clone(), col.ui_type == UiType::Hidden);
+            for grid in intent.grids.iter_mut().filter(|g| g.dataset_id == dataset.id) {
+                match refinement {
+                    Refinement::Hide { .. } => grid.columns.retain(|c| c.name != column),
+                    Refinement::Show { .. } if !hidden && !grid.columns.iter().any(|c| c.name == column) => {
+                        grid.columns.push(GridColumnIntent::new(column, &label));
+                    }
+                    Refinement::Label { .. } => {
+                        for grid_column in grid.columns.iter_mut().filter(|c| c.name == column) {
+                            grid_column.header = label.clone();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ActionType, DatasetIntent, GridIntent, ScreenType};
+
+    fn member_intent() -> UiIntent {
+        let columns = vec![
+            ColumnIntent::new("MEMBER_ID", "회원ID").primary_key(),
+            ColumnIntent::new("MEMBER_NAME", "회원명"),
+            ColumnIntent::new("EMAIL_ADDR", "이메일"),
+            ColumnIntent::new("STATUS_CD", "상태"),
+            ColumnIntent::new("REG_DATE", "등록일").with_data_type(DataType::Date),
+        ];
+        let grid_columns = columns
+            .iter()
+            .filter(|c| c.ui_type != UiType::Hidden)
+            .map(|c| GridColumnIntent::new(&c.name, &c.label))
+            .collect();
+        UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(DatasetIntent::new("ds_member").with_columns(columns))
+            .with_grid(GridIntent::new("grid_member", "ds_member").with_columns(grid_columns))
+            .with_action(ActionIntent::new("search", "조회", ActionType::Search))
+            .with_action(ActionIntent::new("delete", "삭제", ActionType::Delete))
+    }
+
+    #[test]
+    fn test_english_refinements() {
+        let base = member_intent();
+        let result = IntentMerger::merge(
+            &base,
+            "status column should be a combobox with code group CM001, hide email; name is required and max length 50",
+        )
+        .unwrap();
+        assert!(result.skipped.is_empty(), "{:?}", result.skipped);
+
+        let cols = &result.intent.datasets[0].columns;
+        assert_eq!(cols[3].ui_type, UiType::Combo);
+        assert_eq!(cols[3].code_group.as_deref(), Some("CM001"));
+        assert_eq!(cols[2].ui_type, UiType::Hidden);
+        assert!(cols[1].required);
+        assert_eq!(cols[1].max_length, Some(50));
+        assert!(!result.intent.grids[0].columns.iter().any(|c| c.name == "EMAIL_ADDR"));
+
+        assert_eq!(
+            result.applied[0].refinements,
+            vec![
+                Refinement::CodeGroup { column: "STATUS_CD".to_string(), code_group: "CM001".to_string() },
+                Refinement::UiType { column: "STATUS_CD".to_string(), ui_type: UiType::Combo },
+            ]
+        );
+        assert!(result.changes.iter().any(|c| c.path == "datasets[ds_member].columns[STATUS_CD].code_group"));
+        assert!(result.changes.iter().any(|c| c.path == "grids[grid_member].columns[EMAIL_ADDR]" && c.after.is_none()));
+    }
+
+    #[test]
+    fn test_korean_refinements_and_buttons() {
+        let result = IntentMerger::merge(
+            &member_intent(),
+            "상태는 공통코드 CM002 라디오\n등록일 수정불가\n상태 라벨 처리상태\n삭제 버튼 제거\n엑셀 버튼 추가\n페이지 크기 50",
+        )
+        .unwrap();
+        assert!(result.skipped.is_empty(), "{:?}", result.skipped);
+
+        let cols = &result.intent.datasets[0].columns;
+        assert_eq!(cols[3].ui_type, UiType::Radio);
+        assert_eq!(cols[3].code_group.as_deref(), Some("CM002"));
+        assert_eq!(cols[3].label, "처리상태");
+        assert!(cols[4].readonly);
+        assert_eq!(result.intent.grids[0].columns[2].header, "처리상태");
+        let actions: Vec<&str> = result.intent.actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(actions, vec!["search", "export"]);
+        assert_eq!(result.intent.grids[0].page_size, Some(50));
+    }
+
+    #[test]
+    fn test_unrecognized_instructions_are_reported() {
+        let result = IntentMerger::merge(&member_intent(), "hide phone, member should sparkle, add magic button").unwrap();
+        let reasons: Vec<&str> = result.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "no column of the intent is named",
+                "'member' matches several columns (MEMBER_ID, MEMBER_NAME)",
+                "unknown button 'magic' (known: search, add, save, delete, close, export, print, refresh)",
+            ]
+        );
+        assert!(result.changes.is_empty());
+        assert!(IntentMerger::merge(&member_intent(), " ;\n").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Issue Tracker Comments
+//!
+//! Posts a comment on the requirements ticket of a generation (its
+//! `issue_key`) when the project's output settings have an `issue_tracker`:
+//! status, quality score, validation warnings and the download link. Runs in
+//! the background after the generation is logged; a tracker that is down or
+//! rejects the comment never fails the generation.
+//!
+//! Only the log's own data is sent; LLM provider and model stay internal.
+
+use anyhow::{anyhow, bail, Context, Result};
+use sea_orm::DatabaseConnection;
+
+use crate::domain::{GenerationSummary, IssueTracker, IssueTrackerKind};
+use crate::models::_entities::generation_logs;
+use crate::models::project_output_settings;
+use crate::services::generation_history::entity_name;
+use crate::services::TenantScope;
+
+/// Comments generations on their issue tracker tickets
+pub struct IssueTrackerService;
+
+impl IssueTrackerService {
+    /// Comment a just-logged generation on its ticket, in the background
+    pub fn report(db: &DatabaseConnection, log: &generation_logs::Model) {
+        // Replays are admin checks, not new deliverables of the ticket
+        if log.issue_key.is_none() || log.replay_of.is_some() {
+            return;
+        }
+        let (db, log) = (db.clone(), log.clone());
+        tokio::spawn(async move {
+            if let Err(e) = Self::post(&db, &log).await {
+                tracing::warn!(
+                    "Could not comment generation {} on {}: {}",
+                    log.id,
+                    log.issue_key.as_deref().unwrap_or_default(),
+                    e
+                );
+            }
+        });
+    }
+
+    async fn post(db: &DatabaseConnection, log: &generation_logs::Model) -> Result<()> {
+        let (Some(key), Some(project)) = (log.issue_key.as_deref(), log.project.as_deref()) else {
+            return Ok(());
+        };
+        let Some(tracker) = project_output_settings::Model::find_by_project(db, project, &TenantScope::new(log.company.as_deref()))
+            .await
+            .ok()
+            .and_then(|s| s.issue_tracker())
+        else {
+            return Ok(());
+        };
+        let issue_id = tracker
+            .issue_id(key)
+            .ok_or_else(|| anyhow!("{} is not a {:?} issue key", key, tracker.kind))?;
+
+        let summary = GenerationSummary {
+            log_id: log.id,
+            product: log.product.clone(),
+            screen: entity_name(&log.ui_intent),
+            status: log.status.clone(),
+            quality_score: log.quality_score,
+            warnings: log
+                .warnings
+                .as_deref()
+                .and_then(|w| serde_json::from_str(w).ok())
+                .unwrap_or_default(),
+            error: log.error_message.clone(),
+        };
+        let comment = summary.comment(tracker.download_link(log.id).as_deref());
+        Self::send(&tracker, issue_id, &comment).await
+    }
+
+    async fn send(tracker: &IssueTracker, issue_id: &str, comment: &str) -> Result<()> {
+        let env = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let token = env(&tracker.token_env).ok_or_else(|| anyhow!("{} is not set on the server", tracker.token_env))?;
+
+        let (url, body) = tracker.comment_request(issue_id, comment);
+        let client = reqwest::Client::new();
+        let request = match tracker.kind {
+            IssueTrackerKind::Jira => client.post(&url),
+            IssueTrackerKind::Redmine => client.put(&url),
+        };
+        let request = match (tracker.kind, tracker.user_env.as_deref().and_then(env)) {
+            (IssueTrackerKind::Redmine, _) => request.header("X-Redmine-API-Key", token),
+            (_, Some(user)) => request.basic_auth(user, Some(token)),
+            (_, None) => request.bearer_auth(token),
+        };
+
+        let response = request.json(&body).send().await.context("Issue tracker unreachable")?;
+        if !response.status().is_success() {
+            bail!("{} answered {}", url, response.status());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,380 @@
+//! Java Compilation Check
+//!
+//! Writes a generated Spring slice into a temporary project and compiles it
+//! with the server's JDK, so type errors (wrong method names, mismatched
+//! signatures between service and implementation, missing getters) are found
+//! before delivery. String and syntax checks can't see those.
+//!
+//! Disabled by default; configured from the environment:
+//! - `JAVA_COMPILE_CHECK`: `off`, `javac` or `maven`
+//! - `JAVA_COMPILE_TOOL`: `javac` / `mvn` executable (default: from `PATH`,
+//!   `javac` from `JAVA_HOME` when set)
+//! - `JAVA_COMPILE_CLASSPATH`: jars the slice compiles against with `javac`
+//!   (Spring, MyBatis, Lombok, validation), e.g. `/opt/coder/java-libs/*`
+//! - `JAVA_COMPILE_POM`: `pom.xml` to use instead of the built-in Spring Boot
+//!   2.7 skeleton (`maven`)
+//! - `JAVA_COMPILE_MAVEN_ARGS`: extra Maven arguments, e.g. `-o -s /opt/settings.xml`
+//! - `JAVA_COMPILE_TIMEOUT_SECS`: limit per slice (default 120)
+//!
+//! Compiler errors become `Warning:` lines with file and line; a toolchain
+//! that can't run is a `Note:` and never fails the generation.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+
+use crate::domain::SpringArtifacts;
+
+/// Compiler errors reported per slice (the rest are summarized)
+const MAX_ERRORS: usize = 20;
+
+/// Built-in Maven skeleton (Spring Boot 2.7 web, validation, MyBatis, Lombok)
+const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">
+  <modelVersion>4.0.0</modelVersion>
+  <parent>
+    <groupId>org.springframework.boot</groupId>
+    <artifactId>spring-boot-starter-parent</artifactId>
+    <version>2.7.18</version>
+    <relativePath/>
+  </parent>
+  <groupId>codegen</groupId>
+  <artifactId>compile-check</artifactId>
+  <version>0.0.1</version>
+  <properties>
+    <java.version>11</java.version>
+    <project.build.sourceEncoding>UTF-8</project.build.sourceEncoding>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>org.springframework.boot</groupId>
+      <artifactId>spring-boot-starter-web</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>org.springframework.boot</groupId>
+      <artifactId>spring-boot-starter-validation</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>org.mybatis.spring.boot</groupId>
+      <artifactId>mybatis-spring-boot-starter</artifactId>
+      <version>2.3.2</version>
+    </dependency>
+    <dependency>
+      <groupId>org.projectlombok</groupId>
+      <artifactId>lombok</artifactId>
+      <scope>provided</scope>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+/// Compiler used by the check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileToolchain {
+    Javac,
+    Maven,
+}
+
+/// Compilation check settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaCompileConfig {
+    pub toolchain: CompileToolchain,
+    /// `javac` or `mvn` executable
+    pub tool: String,
+    /// `javac` classpath
+    pub classpath: Option<String>,
+    /// Project file replacing the built-in skeleton (`maven`)
+    pub pom: Option<PathBuf>,
+    pub maven_args: Vec<String>,
+    pub timeout: Duration,
+}
+
+impl JavaCompileConfig {
+    /// Load from environment (`None` = disabled)
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let toolchain = match var("JAVA_COMPILE_CHECK")?.to_lowercase().as_str() {
+            "javac" => CompileToolchain::Javac,
+            "maven" | "mvn" => CompileToolchain::Maven,
+            "off" | "false" => return None,
+            other => {
+                tracing::warn!("Unknown JAVA_COMPILE_CHECK '{}', compilation check disabled", other);
+                return None;
+            }
+        };
+        let tool = var("JAVA_COMPILE_TOOL").unwrap_or_else(|| match toolchain {
+            CompileToolchain::Javac => var("JAVA_HOME")
+                .map(|home| Path::new(&home).join("bin").join("javac").to_string_lossy().to_string())
+                .unwrap_or_else(|| "javac".to_string()),
+            CompileToolchain::Maven => "mvn".to_string(),
+        });
+
+        Some(Self {
+            toolchain,
+            tool,
+            classpath: var("JAVA_COMPILE_CLASSPATH"),
+            pom: var("JAVA_COMPILE_POM").map(PathBuf::from),
+            maven_args: var("JAVA_COMPILE_MAVEN_ARGS")
+                .map(|args| args.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            timeout: Duration::from_secs(
+                var("JAVA_COMPILE_TIMEOUT_SECS").and_then(|v| v.parse().ok()).unwrap_or(120),
+            ),
+        })
+    }
+}
+
+/// Compiles generated Spring slices
+pub struct JavaCompiler;
+
+impl JavaCompiler {
+    /// Compile a slice; returns `Warning:` lines for compiler errors, or a
+    /// `Note:` when the check could not run
+    pub async fn check_artifacts(config: &JavaCompileConfig, artifacts: &SpringArtifacts) -> Vec<String> {
+        let sources = Self::sources(artifacts);
+        let config = config.clone();
+        let result = tokio::task::spawn_blocking(move || Self::compile(&config, &sources)).await;
+
+        match result {
+            Ok(Ok(findings)) => findings,
+            Ok(Err(e)) => vec![format!("Note: Compilation check skipped: {}", e)],
+            Err(e) => vec![format!("Note: Compilation check skipped: {}", e)],
+        }
+    }
+
+    /// Source files of a slice under `src/main/java`, placed by their package
+    pub fn sources(artifacts: &SpringArtifacts) -> Vec<(String, String)> {
+        let package = Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").unwrap();
+        let type_name =
+            Regex::new(r"(?m)^\s*(?:public\s+|abstract\s+|final\s+)*(?:class|interface|enum|record)\s+(\w+)").unwrap();
+
+        [
+            Some(&artifacts.controller),
+            Some(&artifacts.service_interface),
+            Some(&artifacts.service_impl),
+            Some(&artifacts.dto),
+            artifacts.search_dto.as_ref(),
+            Some(&artifacts.mapper_interface),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|source| {
+            let name = type_name.captures(source)?[1].to_string();
+            let dir = package
+                .captures(source)
+                .map(|cap| cap[1].replace('.', "/"))
+                .unwrap_or_default();
+            let path = Path::new("src/main/java").join(dir).join(format!("{}.java", name));
+            Some((path.to_string_lossy().to_string(), source.clone()))
+        })
+        .collect()
+    }
+
+    fn compile(config: &JavaCompileConfig, sources: &[(String, String)]) -> Result<Vec<String>> {
+        let dir = std::env::temp_dir().join(format!("codegen-compile-{}", uuid::Uuid::new_v4()));
+        let result = (|| {
+            for (path, content) in sources {
+                let file = dir.join(path);
+                std::fs::create_dir_all(file.parent().unwrap_or(&dir))?;
+                std::fs::write(&file, content)?;
+            }
+
+            let mut command = Command::new(&config.tool);
+            command.current_dir(&dir);
+            match config.toolchain {
+                CompileToolchain::Javac => {
+                    command.args(["-d", "target/classes", "-encoding", "UTF-8", "-Xmaxerrs", "100"]);
+                    if let Some(classpath) = &config.classpath {
+                        command.args(["-cp", classpath]);
+                    }
+                    command.args(sources.iter().map(|(path, _)| path));
+                }
+                CompileToolchain::Maven => {
+                    let pom = match &config.pom {
+                        Some(path) => std::fs::read_to_string(path)
+                            .with_context(|| format!("cannot read {}", path.display()))?,
+                        None => POM.to_string(),
+                    };
+                    std::fs::write(dir.join("pom.xml"), pom)?;
+                    command.args(["-q", "-B", "compile"]).args(&config.maven_args);
+                }
+            }
+
+            let (success, output) = Self::run(command, config.timeout)?;
+            let errors = Self::parse_errors(&output);
+            if success || !errors.is_empty() {
+                return Ok(Self::findings(errors));
+            }
+            // Failed without compiler errors: the build itself did not run
+            let reason = output
+                .lines()
+                .map(str::trim)
+                .find(|line| line.starts_with("[ERROR]") || line.starts_with("error:"))
+                .unwrap_or("compiler exited with an error")
+                .to_string();
+            Err(anyhow!(reason))
+        })();
+        let _ = std::fs::remove_dir_all(&dir);
+        result
+    }
+
+    /// Run with a time limit; exit status and combined output
+    fn run(mut command: Command, timeout: Duration) -> Result<(bool, String)> {
+        let program = command.get_program().to_string_lossy().to_string();
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("{} is not installed on the server", program))?;
+
+        // Drain both pipes so a chatty build can't block on a full buffer
+        let mut stdout = child.stdout.take().expect("piped stdout");
+        let mut stderr = child.stderr.take().expect("piped stderr");
+        let out = std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = stdout.read_to_string(&mut buf);
+            buf
+        });
+        let err = std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf);
+            buf
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() > deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(anyhow!("{} did not finish within {}s", program, timeout.as_secs()));
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        };
+
+        let output = format!("{}\n{}", out.join().unwrap_or_default(), err.join().unwrap_or_default());
+        Ok((status.success(), output))
+    }
+
+    /// Compiler errors as (file, line, message) from `javac` or Maven output
+    pub fn parse_errors(output: &str) -> Vec<(String, usize, String)> {
+        let javac = Regex::new(r"^(.+?\.java):(\d+): error: (.+)$").unwrap();
+        let maven = Regex::new(r"^\[ERROR\] (.+?\.java):\[(\d+),\d+\] (.+)$").unwrap();
+        let symbol = Regex::new(r"^(?:\[ERROR\])?\s+symbol:\s+(.+)$").unwrap();
+
+        let mut errors: Vec<(String, usize, String)> = Vec::new();
+        for line in output.lines() {
+            let line = line.trim_end();
+            if let Some(cap) = javac.captures(line).or_else(|| maven.captures(line)) {
+                let file = Path::new(&cap[1])
+                    .file_name()
+                    .map_or_else(|| cap[1].to_string(), |f| f.to_string_lossy().to_string());
+                let error = (file, cap[2].parse().unwrap_or(0), cap[3].trim().to_string());
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            } else if let (Some(cap), Some(last)) = (symbol.captures(line), errors.last_mut()) {
+                if !last.2.contains("(symbol:") {
+                    last.2 = format!("{} (symbol: {})", last.2, cap[1].trim());
+                }
+            }
+        }
+        errors
+    }
+
+    fn findings(errors: Vec<(String, usize, String)>) -> Vec<String> {
+        let total = errors.len();
+        let mut findings: Vec<String> = errors
+            .into_iter()
+            .take(MAX_ERRORS)
+            .map(|(file, line, message)| format!("Warning: Compile error {} line {}: {}", file, line, message))
+            .collect();
+        if total > MAX_ERRORS {
+            findings.push(format!("Warning: Compile error: {} more not shown", total - MAX_ERRORS));
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_placed_by_package() {
+        let artifacts = SpringArtifacts {
+            controller: "package com.company.erp.controller;\n\n@RestController\npublic class MemberController {}".to_string(),
+            service_interface: "package com.company.erp.service;\npublic interface MemberService {}".to_string(),
+            service_impl: String::new(),
+            dto: "public class MemberDTO {}".to_string(),
+            search_dto: None,
+            mapper_interface: "package com.company.erp.mapper;\n@Mapper\npublic interface MemberMapper {}".to_string(),
+            mapper_xml: "<mapper/>".to_string(),
+            warnings: vec![],
+            paths: None,
+            test_data: None,
+            test_data_filename: None,
+        };
+        let paths: Vec<String> = JavaCompiler::sources(&artifacts).into_iter().map(|(path, _)| path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "src/main/java/com/company/erp/controller/MemberController.java",
+                "src/main/java/com/company/erp/service/MemberService.java",
+                "src/main/java/MemberDTO.java",
+                "src/main/java/com/company/erp/mapper/MemberMapper.java",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_javac_errors() {
+        let output = "src/main/java/com/company/erp/service/impl/MemberServiceImpl.java:24: error: cannot find symbol
+        return memberMapper.selectMembr(search);
+                           ^
+  symbol:   method selectMembr(MemberSearchDTO)
+  location: variable memberMapper of type MemberMapper
+src/main/java/com/company/erp/dto/MemberDTO.java:8: error: class, interface, enum, or record expected
+2 errors";
+        let errors = JavaCompiler::parse_errors(output);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            (
+                "MemberServiceImpl.java".to_string(),
+                24,
+                "cannot find symbol (symbol: method selectMembr(MemberSearchDTO))".to_string()
+            )
+        );
+        assert_eq!(errors[1].1, 8);
+    }
+
+    #[test]
+    fn test_parse_maven_errors() {
+        let output = "[ERROR] COMPILATION ERROR :
+[ERROR] /tmp/codegen-compile-1/src/main/java/com/x/MemberController.java:[31,42] incompatible types: java.lang.String cannot be converted to java.lang.Long
+[ERROR] /tmp/codegen-compile-1/src/main/java/com/x/MemberController.java:[31,42] incompatible types: java.lang.String cannot be converted to java.lang.Long
+[ERROR] Failed to execute goal org.apache.maven.plugins:maven-compiler-plugin";
+        let findings = JavaCompiler::findings(JavaCompiler::parse_errors(output));
+        assert_eq!(
+            findings,
+            vec!["Warning: Compile error MemberController.java line 31: incompatible types: java.lang.String cannot be converted to java.lang.Long"]
+        );
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        std::env::remove_var("JAVA_COMPILE_CHECK");
+        assert!(JavaCompileConfig::from_env().is_none());
+    }
+}
@@ -0,0 +1,360 @@
+//! Java Syntax Checks
+//!
+//! Parses generated Java with tree-sitter (opt-in via `options.deep_validation`)
+//! to catch what substring checks miss: syntax errors and unbalanced braces,
+//! wrong declaration kinds, bodiless or misplaced method bodies, and types
+//! used without an import. Every issue carries a 1-based line number.
+
+use tree_sitter::{Node, Parser};
+
+use crate::domain::SpringArtifacts;
+
+/// Types usable without an import
+const JAVA_LANG: &[&str] = &[
+    "Object", "String", "StringBuilder", "CharSequence", "Boolean", "Byte", "Character", "Short",
+    "Integer", "Long", "Float", "Double", "Number", "Math", "Void", "System", "Class", "Enum",
+    "Record", "Iterable", "Comparable", "Runnable", "AutoCloseable", "Thread", "Throwable",
+    "Exception", "RuntimeException", "Error", "IllegalArgumentException", "IllegalStateException",
+    "NullPointerException", "UnsupportedOperationException", "IndexOutOfBoundsException",
+    "NumberFormatException", "ClassCastException", "ArithmeticException", "CloneNotSupportedException",
+    "InterruptedException", "Override", "Deprecated", "SuppressWarnings", "FunctionalInterface",
+    "SafeVarargs",
+];
+
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+/// A problem found in one Java source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaIssue {
+    /// 1-based line
+    pub line: usize,
+    pub message: String,
+}
+
+/// tree-sitter based checks for generated Java
+pub struct JavaSyntaxChecker;
+
+impl JavaSyntaxChecker {
+    /// Check every Java file of a slice; returns `Warning:` lines
+    pub fn check_artifacts(artifacts: &SpringArtifacts) -> Vec<String> {
+        let mut files = vec![
+            ("controller", artifacts.controller.as_str(), "class"),
+            ("service", artifacts.service_interface.as_str(), "interface"),
+            ("service_impl", artifacts.service_impl.as_str(), "class"),
+            ("dto", artifacts.dto.as_str(), "class"),
+            ("mapper", artifacts.mapper_interface.as_str(), "interface"),
+        ];
+        if let Some(search_dto) = &artifacts.search_dto {
+            files.push(("search_dto", search_dto.as_str(), "class"));
+        }
+
+        // Types of the slice that share a package need no import
+        let declared: Vec<(String, String)> = files
+            .iter()
+            .flat_map(|(_, source, _)| Self::declared_types(source))
+            .collect();
+
+        files
+            .iter()
+            .filter(|(_, source, _)| !source.trim().is_empty())
+            .flat_map(|(label, source, kind)| {
+                let package = Self::package(source);
+                let same_package: Vec<&str> = declared
+                    .iter()
+                    .filter(|(pkg, _)| *pkg == package)
+                    .map(|(_, name)| name.as_str())
+                    .collect();
+                Self::check(source, Some(kind), &same_package)
+                    .into_iter()
+                    .map(move |issue| format!("Warning: {} line {}: {}", label, issue.line, issue.message))
+            })
+            .collect()
+    }
+
+    /// Check one Java source; `expected_kind` is `class` or `interface`
+    pub fn check(source: &str, expected_kind: Option<&str>, same_package: &[&str]) -> Vec<JavaIssue> {
+        let Some(tree) = Self::parse(source) else {
+            return vec![JavaIssue { line: 1, message: "could not be parsed".to_string() }];
+        };
+        let root = tree.root_node();
+        let src = source.as_bytes();
+        let mut issues = Vec::new();
+
+        Self::syntax_errors(root, src, &mut issues);
+        Self::structure(root, src, expected_kind, &mut issues);
+        Self::missing_imports(root, src, same_package, &mut issues);
+
+        issues.sort_by_key(|i| i.line);
+        issues
+    }
+
+    fn parse(source: &str) -> Option<tree_sitter::Tree> {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_java::LANGUAGE.into()).ok()?;
+        parser.parse(source, None)
+    }
+
+    fn issue(node: Node, message: String) -> JavaIssue {
+        JavaIssue {
+            line: node.start_position().row + 1,
+            message,
+        }
+    }
+
+    fn text<'a>(node: Node, src: &'a [u8]) -> &'a str {
+        node.utf8_text(src).unwrap_or_default()
+    }
+
+    fn syntax_errors(node: Node, src: &[u8], issues: &mut Vec<JavaIssue>) {
+        if node.is_missing() {
+            let message = match node.kind() {
+                "}" => "unbalanced braces: missing `}`".to_string(),
+                kind => format!("missing `{}`", kind),
+            };
+            issues.push(Self::issue(node, message));
+            return;
+        }
+        if node.is_error() {
+            let text = Self::text(node, src).trim();
+            let message = if text == "}" {
+                "unbalanced braces: unexpected `}`".to_string()
+            } else {
+                let snippet: String = text.lines().next().unwrap_or_default().chars().take(40).collect();
+                format!("syntax error near `{}`", snippet)
+            };
+            issues.push(Self::issue(node, message));
+            return;
+        }
+        if node.has_error() {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                Self::syntax_errors(child, src, issues);
+            }
+        }
+    }
+
+    fn structure(root: Node, src: &[u8], expected_kind: Option<&str>, issues: &mut Vec<JavaIssue>) {
+        let mut cursor = root.walk();
+        let types: Vec<Node> = root
+            .named_children(&mut cursor)
+            .filter(|n| TYPE_DECLARATIONS.contains(&n.kind()))
+            .collect();
+
+        let Some(first) = types.first() else {
+            issues.push(JavaIssue { line: 1, message: "no class or interface declaration".to_string() });
+            return;
+        };
+        if let Some(expected) = expected_kind {
+            let found = first.kind().trim_end_matches("_declaration");
+            if found != expected {
+                issues.push(Self::issue(*first, format!("expected {} declaration, found {}", expected, found)));
+            }
+        }
+
+        for decl in &types {
+            let Some(body) = decl.child_by_field_name("body") else { continue };
+            let is_interface = decl.kind() == "interface_declaration";
+            let mut cursor = body.walk();
+            for method in body.named_children(&mut cursor).filter(|n| n.kind() == "method_declaration") {
+                let name = method.child_by_field_name("name").map(|n| Self::text(n, src)).unwrap_or("?");
+                let modifiers = Self::modifiers(method, src);
+                let has_body = method.child_by_field_name("body").is_some();
+
+                if !is_interface && !has_body && !modifiers.contains("abstract") && !modifiers.contains("native") {
+                    issues.push(Self::issue(method, format!("method `{}` has no body", name)));
+                }
+                if is_interface
+                    && has_body
+                    && !["default", "static", "private"].iter().any(|m| modifiers.contains(m))
+                {
+                    issues.push(Self::issue(method, format!("interface method `{}` has a body without `default`", name)));
+                }
+            }
+        }
+    }
+
+    fn modifiers(node: Node, src: &[u8]) -> String {
+        let mut cursor = node.walk();
+        let modifiers = node
+            .children(&mut cursor)
+            .find(|n| n.kind() == "modifiers")
+            .map(|n| Self::text(n, src).to_string());
+        modifiers.unwrap_or_default()
+    }
+
+    fn missing_imports(root: Node, src: &[u8], same_package: &[&str], issues: &mut Vec<JavaIssue>) {
+        let mut imported: Vec<&str> = Vec::new();
+        let mut cursor = root.walk();
+        for import in root.named_children(&mut cursor).filter(|n| n.kind() == "import_declaration") {
+            let mut c = import.walk();
+            if import.children(&mut c).any(|n| n.kind() == "asterisk") {
+                // A wildcard import could supply any type
+                return;
+            }
+            let text = Self::text(import, src).trim_end_matches(';').trim();
+            imported.extend(text.rsplit('.').next());
+        }
+
+        let mut declared: Vec<&str> = Vec::new();
+        let mut used: Vec<(&str, Node)> = Vec::new();
+        Self::collect_types(root, src, &mut declared, &mut used);
+
+        let mut reported: Vec<&str> = Vec::new();
+        for (name, node) in used {
+            let known = JAVA_LANG.contains(&name)
+                || imported.contains(&name)
+                || declared.contains(&name)
+                || same_package.contains(&name)
+                || reported.contains(&name);
+            if !known {
+                issues.push(Self::issue(node, format!("`{}` is used but not imported", name)));
+                reported.push(name);
+            }
+        }
+    }
+
+    /// Declared type names (classes, type parameters) and type usages
+    fn collect_types<'a>(node: Node<'a>, src: &'a [u8], declared: &mut Vec<&'a str>, used: &mut Vec<(&'a str, Node<'a>)>) {
+        let parent_kind = node.parent().map(|p| p.kind()).unwrap_or_default();
+        match node.kind() {
+            kind if TYPE_DECLARATIONS.contains(&kind) => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    declared.push(Self::text(name, src));
+                }
+            }
+            "type_parameter" => {
+                let mut cursor = node.walk();
+                let name = node.named_children(&mut cursor).find(|n| n.kind() == "type_identifier");
+                if let Some(name) = name {
+                    declared.push(Self::text(name, src));
+                }
+            }
+            "type_identifier" => {
+                // `Map.Entry` needs `Map`; in `java.util.List` the package parts are lowercase
+                let is_qualifier_head = node.prev_sibling().is_none();
+                if parent_kind != "scoped_type_identifier" || is_qualifier_head {
+                    let name = Self::text(node, src);
+                    if name.starts_with(char::is_uppercase) {
+                        used.push((name, node));
+                    }
+                }
+            }
+            "marker_annotation" | "annotation" => {
+                if let Some(name) = node.child_by_field_name("name").filter(|n| n.kind() == "identifier") {
+                    used.push((Self::text(name, src), name));
+                }
+            }
+            "method_invocation" | "field_access" => {
+                // Static access such as `ResponseEntity.ok(...)` or `HttpStatus.CREATED`
+                if let Some(object) = node.child_by_field_name("object").filter(|n| n.kind() == "identifier") {
+                    let name = Self::text(object, src);
+                    let is_constant = name.chars().all(|c| c.is_uppercase() || c == '_' || c.is_ascii_digit());
+                    if name.starts_with(char::is_uppercase) && !is_constant {
+                        used.push((name, object));
+                    }
+                }
+            }
+            "package_declaration" | "import_declaration" => return,
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::collect_types(child, src, declared, used);
+        }
+    }
+
+    /// `package` of a source (empty if none)
+    fn package(source: &str) -> String {
+        source
+            .lines()
+            .map(str::trim)
+            .find_map(|l| l.strip_prefix("package "))
+            .map(|p| p.trim_end_matches(';').trim().to_string())
+            .unwrap_or_default()
+    }
+
+    /// `(package, name)` of the types a source declares
+    fn declared_types(source: &str) -> Vec<(String, String)> {
+        let Some(tree) = Self::parse(source) else { return Vec::new() };
+        let root = tree.root_node();
+        let package = Self::package(source);
+        let mut cursor = root.walk();
+        let names: Vec<(String, String)> = root
+            .named_children(&mut cursor)
+            .filter(|n| TYPE_DECLARATIONS.contains(&n.kind()))
+            .filter_map(|n| n.child_by_field_name("name"))
+            .map(|n| (package.clone(), Self::text(n, source.as_bytes()).to_string()))
+            .collect();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTROLLER: &str = r#"package com.company.member.controller;
+
+import com.company.member.dto.MemberDTO;
+import org.springframework.http.ResponseEntity;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class MemberController {
+
+    public ResponseEntity<MemberDTO> get(Long id) {
+        return ResponseEntity.ok(null);
+    }
+}
+"#;
+
+    #[test]
+    fn test_clean_source() {
+        assert!(JavaSyntaxChecker::check(CONTROLLER, Some("class"), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_missing_import_with_line() {
+        let source = CONTROLLER.replace("import org.springframework.http.ResponseEntity;\n", "");
+        let issues = JavaSyntaxChecker::check(&source, Some("class"), &[]);
+        assert_eq!(
+            issues,
+            vec![JavaIssue { line: 9, message: "`ResponseEntity` is used but not imported".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_braces_and_kind() {
+        let source = CONTROLLER.trim_end().trim_end_matches('}');
+        let issues = JavaSyntaxChecker::check(source, Some("interface"), &[]);
+        assert!(issues.iter().any(|i| i.message == "unbalanced braces: missing `}`"), "{:?}", issues);
+        assert!(issues.iter().any(|i| i.message == "expected interface declaration, found class"));
+    }
+
+    #[test]
+    fn test_method_bodies() {
+        let source = "package a;\npublic interface MemberService {\n    void save();\n    int count() { return 0; }\n}\n";
+        let issues = JavaSyntaxChecker::check(source, Some("interface"), &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 4);
+
+        let source = "package a;\npublic class MemberServiceImpl {\n    public void save();\n}\n";
+        let issues = JavaSyntaxChecker::check(source, Some("class"), &[]);
+        assert_eq!(issues[0].message, "method `save` has no body");
+    }
+
+    #[test]
+    fn test_same_package_types_resolve() {
+        let mut artifacts = SpringArtifacts::new();
+        artifacts.service_interface = "package a.service;\npublic interface MemberService {}".to_string();
+        artifacts.service_impl = "package a.service;\npublic class MemberServiceImpl implements MemberService {}".to_string();
+        assert!(JavaSyntaxChecker::check_artifacts(&artifacts).is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+//! Job Status
+//!
+//! Builds the status view of queued generation jobs and lets clients wait
+//! for changes instead of polling. Every status view carries an ETag (a hash
+//! of its content); a waiting request sleeps until the job's ETag differs
+//! from the one the client already has, or the wait times out.
+//!
+//! Workers in this process wake waiters through [`JobStatusService::notify`];
+//! jobs finished by another process (the `queue_processor` task) are picked
+//! up by a periodic re-check.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::models::_entities::generation_logs;
+use crate::workers::JobQueueProcessor;
+
+/// Longest wait a client may request
+pub const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Most jobs per batch status request
+pub const MAX_BATCH: usize = 100;
+
+/// How often a waiter re-reads the job when no local worker wakes it
+const RECHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Job status response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobStatusResponse {
+    /// Job ID
+    pub job_id: String,
+    /// Current status: queued, processing, completed, failed, cancelled
+    pub status: String,
+    /// Position in queue (if queued)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<i64>,
+    /// Estimated wait time in seconds (rough estimate)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_wait_secs: Option<i64>,
+    /// Generated artifacts (if completed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<serde_json::Value>,
+    /// When the artifacts are removed by the retention policy (RFC 3339);
+    /// past expiries mean they are gone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts_expire_at: Option<String>,
+    /// Warnings from generation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+    /// Error message (if failed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Machine-readable cause of the failure (e.g. `provider_refusal`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Generation time in milliseconds (if completed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_time_ms: Option<i32>,
+    /// Product type
+    pub product: String,
+    /// Timestamps
+    pub timestamps: JobTimestamps,
+}
+
+/// Job timestamps
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobTimestamps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+}
+
+impl JobStatusResponse {
+    /// Whether the job will not change any more
+    pub fn is_finished(&self) -> bool {
+        JobStatusService::is_terminal(&self.status)
+    }
+
+    /// Quoted ETag of this status view
+    pub fn etag(&self) -> String {
+        JobStatusService::etag_for(&[&serde_json::to_string(self).unwrap_or_default()])
+    }
+}
+
+fn notifier() -> &'static Notify {
+    static NOTIFY: OnceLock<Notify> = OnceLock::new();
+    NOTIFY.get_or_init(Notify::new)
+}
+
+/// Job status service
+pub struct JobStatusService;
+
+impl JobStatusService {
+    /// Wake every request waiting on a job status (call after a status change)
+    pub fn notify() {
+        notifier().notify_waiters();
+    }
+
+    /// Statuses a job never leaves
+    pub fn is_terminal(status: &str) -> bool {
+        matches!(status, "completed" | "failed" | "cancelled")
+    }
+
+    /// Quoted ETag for a set of content parts
+    pub fn etag_for(parts: &[&str]) -> String {
+        let mut hasher = DefaultHasher::new();
+        parts.hash(&mut hasher);
+        format!("\"{:016x}\"", hasher.finish())
+    }
+
+    /// Whether an `If-None-Match` header value matches an ETag
+    ///
+    /// Accepts `*`, comma-separated lists and weak (`W/`) validators.
+    pub fn matches(if_none_match: &str, etag: &str) -> bool {
+        if_none_match.split(',').map(str::trim).any(|candidate| {
+            candidate == "*" || candidate.trim_start_matches("W/") == etag
+        })
+    }
+
+    /// Parse a `wait` query value ("30", "30s", "1m"), capped at [`MAX_WAIT`]
+    pub fn parse_wait(value: &str) -> Option<Duration> {
+        let value = value.trim().to_ascii_lowercase();
+        let secs = if let Some(minutes) = value.strip_suffix('m') {
+            minutes.trim().parse::<u64>().ok()?.saturating_mul(60)
+        } else {
+            value.strip_suffix('s').unwrap_or(&value).trim().parse::<u64>().ok()?
+        };
+        Some(Duration::from_secs(secs).min(MAX_WAIT))
+    }
+
+    /// Load the status view of a job (`None` if there is no such job)
+    pub async fn load(db: &DatabaseConnection, job_id: &str) -> anyhow::Result<Option<JobStatusResponse>> {
+        let job = generation_logs::Entity::find()
+            .filter(generation_logs::Column::JobId.eq(job_id))
+            .one(db)
+            .await?;
+
+        match job {
+            Some(job) => Ok(Some(Self::build(db, job).await)),
+            None => Ok(None),
+        }
+    }
+
+    /// Load the status views of several jobs in one query
+    ///
+    /// Unknown IDs are left out; results follow the order of `job_ids`.
+    pub async fn load_many(
+        db: &DatabaseConnection,
+        job_ids: &[String],
+    ) -> anyhow::Result<Vec<JobStatusResponse>> {
+        let jobs = generation_logs::Entity::find()
+            .filter(generation_logs::Column::JobId.is_in(job_ids.iter().cloned()))
+            .all(db)
+            .await?;
+
+        let mut responses = Vec::with_capacity(jobs.len());
+        for job_id in job_ids {
+            if let Some(job) = jobs.iter().find(|j| j.job_id.as_deref() == Some(job_id)) {
+                responses.push(Self::build(db, job.clone()).await);
+            }
+        }
+        Ok(responses)
+    }
+
+    /// Wait until the job's ETag differs from `known_etag` or `wait` runs out
+    ///
+    /// Without a known ETag the request waits for the job to finish. Returns
+    /// the latest status view (`None` if the job does not exist).
+    pub async fn wait_for_change(
+        db: &DatabaseConnection,
+        job_id: &str,
+        known_etag: Option<&str>,
+        wait: Duration,
+    ) -> anyhow::Result<Option<JobStatusResponse>> {
+        let deadline = Instant::now() + wait.min(MAX_WAIT);
+
+        loop {
+            // Register before reading so a change between the read and the
+            // sleep still wakes us
+            let notified = notifier().notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let Some(status) = Self::load(db, job_id).await? else {
+                return Ok(None);
+            };
+            let changed = match known_etag {
+                Some(known) => !Self::matches(known, &status.etag()),
+                None => status.is_finished(),
+            };
+            let now = Instant::now();
+            if changed || status.is_finished() || now >= deadline {
+                return Ok(Some(status));
+            }
+
+            let sleep = (deadline - now).min(RECHECK_INTERVAL);
+            tokio::select! {
+                () = &mut notified => {}
+                () = tokio::time::sleep(sleep) => {}
+            }
+        }
+    }
+
+    async fn build(db: &DatabaseConnection, job: generation_logs::Model) -> JobStatusResponse {
+        let job_id = job.job_id.clone().unwrap_or_default();
+
+        // Get queue position if still queued
+        let queue_position = if job.status == "queued" {
+            JobQueueProcessor::get_queue_position(db, &job_id).await.ok().flatten()
+        } else {
+            None
+        };
+
+        // Estimate wait time (rough: 30 seconds per job ahead)
+        let estimated_wait_secs = queue_position.map(|pos| pos * 30);
+
+        let artifacts = job.artifacts.as_ref().and_then(|a| serde_json::from_str(a).ok());
+        let warnings: Option<Vec<String>> = job
+            .warnings
+            .as_ref()
+            .and_then(|w| serde_json::from_str(w).ok());
+
+        JobStatusResponse {
+            job_id,
+            status: job.status,
+            queue_position,
+            estimated_wait_secs,
+            artifacts,
+            artifacts_expire_at: job.artifacts_expire_at.map(|t| t.to_rfc3339()),
+            warnings,
+            error: job.error_message,
+            error_code: job.error_code,
+            generation_time_ms: job.generation_time_ms,
+            product: job.product,
+            timestamps: JobTimestamps {
+                queued_at: job.queued_at.map(|t| t.to_rfc3339()),
+                started_at: job.started_at.map(|t| t.to_rfc3339()),
+                completed_at: job.completed_at.map(|t| t.to_rfc3339()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(JobStatusService::parse_wait("30"), Some(Duration::from_secs(30)));
+        assert_eq!(JobStatusService::parse_wait("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(JobStatusService::parse_wait("1m"), Some(MAX_WAIT));
+        assert_eq!(JobStatusService::parse_wait("600"), Some(MAX_WAIT));
+        assert_eq!(JobStatusService::parse_wait("soon"), None);
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let etag = JobStatusService::etag_for(&["completed"]);
+        assert!(JobStatusService::matches(&etag, &etag));
+        assert!(JobStatusService::matches(&format!("W/{}", etag), &etag));
+        assert!(JobStatusService::matches(&format!("\"other\", {}", etag), &etag));
+        assert!(JobStatusService::matches("*", &etag));
+        assert!(!JobStatusService::matches("\"other\"", &etag));
+        assert_ne!(etag, JobStatusService::etag_for(&["queued"]));
+    }
+}
@@ -3,11 +3,18 @@
 //! Manages xFrame5 knowledge base for selective inclusion in prompts.
 //! Supports both database storage and file-based fallback.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use loco_rs::prelude::*;
-use sea_orm::{query::*, DatabaseConnection, JsonValue};
+use sea_orm::{query::*, sea_query::Expr, DatabaseConnection, JsonValue};
 use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::{knowledge_bases, prelude::*};
+use crate::models::knowledge_base_revisions;
+use crate::services::lookup_cache::{LookupCache, Namespace};
+use crate::services::vector_store::{vector_store, Document, VectorStore};
+use crate::services::TenantScope;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct KnowledgeQuery {
@@ -28,8 +35,29 @@ pub struct KnowledgeEntry {
     pub relevance_tags: Option<Vec<String>>,
     pub priority: Option<String>,
     pub token_estimate: Option<i32>,
+    /// Current revision (see `knowledge_base_revisions`)
+    pub revision: i32,
+    /// Ranking adjustment learned from generation outcomes
+    pub usage_boost: f32,
 }
 
+/// Knowledge entry revision included in a prompt (recorded on the generation log)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeRevision {
+    pub id: i32,
+    pub revision: i32,
+}
+
+/// Largest ranking adjustment (positive or negative) from usage statistics
+const MAX_USAGE_BOOST: f32 = 0.2;
+
+/// Generations needed before the usage boost applies at full weight
+const USAGE_CONFIDENCE_RUNS: f32 = 10.0;
+
+/// Share of semantic similarity in the Q&A score when vector retrieval is on
+/// (the rest is keyword relevance, so exact component names still win)
+const VECTOR_WEIGHT: f32 = 0.7;
+
 impl From<knowledge_bases::Model> for KnowledgeEntry {
     fn from(model: knowledge_bases::Model) -> Self {
         let relevance_tags = model.relevance_tags.and_then(|json| {
@@ -54,6 +82,8 @@ impl From<knowledge_bases::Model> for KnowledgeEntry {
             relevance_tags,
             priority: model.priority,
             token_estimate: model.token_estimate,
+            revision: model.version.unwrap_or(1),
+            usage_boost: KnowledgeBaseService::usage_boost(model.usage_count, model.clean_count),
         }
     }
 }
@@ -61,9 +91,29 @@ impl From<knowledge_bases::Model> for KnowledgeEntry {
 pub struct KnowledgeBaseService;
 
 impl KnowledgeBaseService {
-    /// Query knowledge base entries based on criteria
-    pub async fn query(db: &DatabaseConnection, query: &KnowledgeQuery) -> Result<Vec<KnowledgeEntry>> {
-        let mut selector = KnowledgeBases::find().filter(knowledge_bases::Column::IsActive.eq(true));
+    /// Query knowledge base entries based on criteria (shared entries plus the tenant's own)
+    pub async fn query(
+        db: &DatabaseConnection,
+        query: &KnowledgeQuery,
+        tenant: &TenantScope,
+    ) -> Result<Vec<KnowledgeEntry>> {
+        let key = format!(
+            "query:{}:{}",
+            tenant.company().unwrap_or(""),
+            serde_json::to_string(query).unwrap_or_default()
+        );
+        LookupCache::get_or_load(Namespace::Knowledge, &key, || Self::query_uncached(db, query, tenant)).await
+    }
+
+    async fn query_uncached(
+        db: &DatabaseConnection,
+        query: &KnowledgeQuery,
+        tenant: &TenantScope,
+    ) -> Result<Vec<KnowledgeEntry>> {
+        let mut selector = KnowledgeBases::find()
+            .filter(knowledge_bases::Column::IsActive.eq(true))
+            .filter(knowledge_bases::Column::QuarantineReason.is_null())
+            .filter(tenant.usable(knowledge_bases::Column::Company));
 
         // Filter by category
         if let Some(category) = &query.category {
@@ -84,7 +134,7 @@ impl KnowledgeBaseService {
             .map_err(|e| Error::string(&format!("Failed to query knowledge base: {}", e)))?;
 
         // Filter by relevance tags if provided (post-query since JSONB queries are complex)
-        let entries: Vec<KnowledgeEntry> = if let Some(tags) = &query.relevance_tags {
+        let mut entries: Vec<KnowledgeEntry> = if let Some(tags) = &query.relevance_tags {
             results
                 .into_iter()
                 .map(KnowledgeEntry::from)
@@ -100,6 +150,13 @@ impl KnowledgeBaseService {
             results.into_iter().map(KnowledgeEntry::from).collect()
         };
 
+        // Entries that led to clean generations first (stable: ties keep priority/name order)
+        entries.sort_by(|a, b| {
+            b.usage_boost
+                .partial_cmp(&a.usage_boost)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
         Ok(entries)
     }
 
@@ -107,6 +164,7 @@ impl KnowledgeBaseService {
     pub async fn for_screen_type(
         db: &DatabaseConnection,
         screen_type: &str,
+        tenant: &TenantScope,
     ) -> Result<Vec<KnowledgeEntry>> {
         let query = KnowledgeQuery {
             category: None,
@@ -115,13 +173,14 @@ impl KnowledgeBaseService {
             priority: None,
         };
 
-        Self::query(db, &query).await
+        Self::query(db, &query, tenant).await
     }
 
     /// Get knowledge for specific component
     pub async fn for_component(
         db: &DatabaseConnection,
         component: &str,
+        tenant: &TenantScope,
     ) -> Result<Vec<KnowledgeEntry>> {
         let query = KnowledgeQuery {
             category: Some("component".to_string()),
@@ -130,7 +189,7 @@ impl KnowledgeBaseService {
             priority: None,
         };
 
-        Self::query(db, &query).await
+        Self::query(db, &query, tenant).await
     }
 
     /// Assemble knowledge content into a single string
@@ -142,6 +201,28 @@ impl KnowledgeBaseService {
             .join("\n\n---\n\n")
     }
 
+    /// Knowledge content exactly as a past prompt saw it, from the revisions
+    /// recorded on its generation log (in prompt order)
+    pub async fn content_at(db: &DatabaseConnection, revisions: &[KnowledgeRevision]) -> Result<String> {
+        let pairs: Vec<(i32, i32)> = revisions.iter().map(|r| (r.id, r.revision)).collect();
+        let resolved = knowledge_base_revisions::Model::resolve(db, &pairs)
+            .await
+            .map_err(|e| Error::string(&format!("Failed to load knowledge revisions: {}", e)))?;
+        if resolved.len() < pairs.len() {
+            return Err(Error::string(&format!(
+                "{} of {} knowledge revisions are no longer stored",
+                pairs.len() - resolved.len(),
+                pairs.len()
+            )));
+        }
+
+        Ok(resolved
+            .iter()
+            .map(|r| r.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n---\n\n"))
+    }
+
     /// Estimate total tokens for knowledge entries
     pub fn estimate_tokens(entries: &[KnowledgeEntry]) -> i32 {
         entries
@@ -158,6 +239,7 @@ impl KnowledgeBaseService {
         let result = KnowledgeBases::find()
             .filter(knowledge_bases::Column::Name.eq(name))
             .filter(knowledge_bases::Column::IsActive.eq(true))
+            .filter(knowledge_bases::Column::QuarantineReason.is_null())
             .one(db)
             .await
             .map_err(|e| Error::string(&format!("Failed to find knowledge entry: {}", e)))?;
@@ -261,6 +343,72 @@ impl KnowledgeBaseService {
         Ok(())
     }
 
+    /// Ranking boost from prompt usage statistics.
+    ///
+    /// Uses the smoothed clean rate `(clean + 1) / (usage + 2)`, centred on 0.5
+    /// and scaled to ±`MAX_USAGE_BOOST`; weight grows with usage so a few runs
+    /// don't swing the ranking.
+    pub fn usage_boost(usage_count: i32, clean_count: i32) -> f32 {
+        if usage_count <= 0 {
+            return 0.0;
+        }
+        let usage = usage_count as f32;
+        let clean_rate = (clean_count.clamp(0, usage_count) as f32 + 1.0) / (usage + 2.0);
+        let confidence = (usage / USAGE_CONFIDENCE_RUNS).min(1.0);
+        (clean_rate - 0.5) * 2.0 * MAX_USAGE_BOOST * confidence
+    }
+
+    /// Record that entries were included in a generation prompt and how it validated
+    ///
+    /// Uses a bulk column update so `updated_at` (and the KB revision) is untouched.
+    pub async fn record_usage(db: &DatabaseConnection, ids: &[i32], findings: usize) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let clean = i32::from(findings == 0);
+        KnowledgeBases::update_many()
+            .col_expr(
+                knowledge_bases::Column::UsageCount,
+                Expr::col(knowledge_bases::Column::UsageCount).add(1),
+            )
+            .col_expr(
+                knowledge_bases::Column::CleanCount,
+                Expr::col(knowledge_bases::Column::CleanCount).add(clean),
+            )
+            .col_expr(
+                knowledge_bases::Column::FindingCount,
+                Expr::col(knowledge_bases::Column::FindingCount).add(findings as i32),
+            )
+            .filter(knowledge_bases::Column::Id.is_in(ids.iter().copied()))
+            .exec(db)
+            .await
+            .map_err(|e| Error::string(&format!("Failed to record knowledge usage: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Revision marker that changes whenever an entry is added, edited or removed
+    ///
+    /// Every save bumps `updated_at`; hard deletes change the count.
+    pub async fn revision(db: &DatabaseConnection) -> Result<String> {
+        let count = KnowledgeBases::find()
+            .count(db)
+            .await
+            .map_err(|e| Error::string(&format!("Failed to count knowledge entries: {}", e)))?;
+        let latest = KnowledgeBases::find()
+            .order_by_desc(knowledge_bases::Column::UpdatedAt)
+            .one(db)
+            .await
+            .map_err(|e| Error::string(&format!("Failed to read knowledge revision: {}", e)))?;
+
+        Ok(format!(
+            "{}:{}",
+            count,
+            latest.map(|m| m.updated_at.to_rfc3339()).unwrap_or_default()
+        ))
+    }
+
     /// List all entries (for admin)
     pub async fn list_all(db: &DatabaseConnection) -> Result<Vec<KnowledgeEntry>> {
         let results = KnowledgeBases::find()
@@ -280,23 +428,43 @@ impl KnowledgeBaseService {
         question: &str,
         product: &str,
         max_results: usize,
+        tenant: &TenantScope,
     ) -> Result<Vec<(KnowledgeEntry, f32)>> {
         // Extract keywords from question
         let keywords = Self::extract_keywords(question);
 
         // Get all active entries
-        let all_entries = KnowledgeBases::find()
-            .filter(knowledge_bases::Column::IsActive.eq(true))
-            .all(db)
-            .await
-            .map_err(|e| Error::string(&format!("Failed to search knowledge base: {}", e)))?;
+        let key = format!("active:{}", tenant.company().unwrap_or(""));
+        let all_entries: Vec<KnowledgeEntry> = LookupCache::get_or_load(Namespace::Knowledge, &key, || async {
+            KnowledgeBases::find()
+                .filter(knowledge_bases::Column::IsActive.eq(true))
+                .filter(knowledge_bases::Column::QuarantineReason.is_null())
+                .filter(tenant.usable(knowledge_bases::Column::Company))
+                .all(db)
+                .await
+                .map(|models| models.into_iter().map(KnowledgeEntry::from).collect())
+                .map_err(|e| Error::string(&format!("Failed to search knowledge base: {}", e)))
+        })
+        .await?;
+
+        // Semantic similarity from the embedded vector store (KB_RETRIEVAL=vector)
+        let similarities = match vector_store() {
+            Some(store) => Self::vector_similarities(store, &all_entries, question, max_results).await,
+            None => None,
+        };
 
         // Score each entry by relevance
         let mut scored: Vec<(KnowledgeEntry, f32)> = all_entries
             .into_iter()
-            .map(KnowledgeEntry::from)
             .map(|entry| {
-                let score = Self::calculate_relevance(&entry, &keywords, product);
+                let keyword_score = Self::calculate_relevance(&entry, &keywords, product);
+                let score = match &similarities {
+                    Some(similarities) => {
+                        let similarity = similarities.get(&entry.id).copied().unwrap_or(0.0).max(0.0);
+                        VECTOR_WEIGHT * similarity + (1.0 - VECTOR_WEIGHT) * keyword_score
+                    }
+                    None => keyword_score,
+                };
                 (entry, score)
             })
             .filter(|(_, score)| *score > 0.1) // Minimum threshold
@@ -309,6 +477,45 @@ impl KnowledgeBaseService {
         Ok(scored.into_iter().take(max_results).collect())
     }
 
+    /// Similarity of each entry to the question, or `None` to fall back to keywords
+    async fn vector_similarities(
+        store: Arc<VectorStore>,
+        entries: &[KnowledgeEntry],
+        question: &str,
+        max_results: usize,
+    ) -> Option<HashMap<i32, f32>> {
+        let documents: Vec<Document> = entries
+            .iter()
+            .map(|entry| Document {
+                id: entry.id,
+                text: format!(
+                    "{}\n{}\n{}",
+                    entry.name,
+                    entry.section.as_deref().unwrap_or_default(),
+                    entry.content
+                ),
+            })
+            .collect();
+        let question = question.to_string();
+        // Embedding is CPU-bound; candidates beyond max_results still get keyword scores
+        let result = tokio::task::spawn_blocking(move || {
+            store.similarities(&documents, &question, max_results * 3)
+        })
+        .await;
+
+        match result {
+            Ok(Ok(similarities)) => Some(similarities),
+            Ok(Err(e)) => {
+                tracing::warn!("Vector retrieval failed, using keyword search: {}", e);
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Vector retrieval task failed, using keyword search: {}", e);
+                None
+            }
+        }
+    }
+
     /// Extract keywords from a question
     fn extract_keywords(question: &str) -> Vec<String> {
         // Common stop words to filter out
@@ -413,8 +620,13 @@ impl KnowledgeBaseService {
             }
         }
 
+        // Usage boost only re-ranks entries that already matched
+        if score > 0.0 {
+            score += entry.usage_boost;
+        }
+
         // Normalize to 0-1 range
-        score.min(1.0)
+        score.clamp(0.0, 1.0)
     }
 
     /// Get knowledge entries formatted for Q&A prompt
@@ -423,8 +635,9 @@ impl KnowledgeBaseService {
         question: &str,
         product: &str,
         max_entries: usize,
+        tenant: &TenantScope,
     ) -> Result<(String, Vec<(i32, String, String, Option<String>, f32)>)> {
-        let scored_entries = Self::search_for_qa(db, question, product, max_entries).await?;
+        let scored_entries = Self::search_for_qa(db, question, product, max_entries, tenant).await?;
 
         if scored_entries.is_empty() {
             return Ok((String::new(), vec![]));
@@ -470,3 +683,27 @@ impl KnowledgeFileFallback {
         Ok(format!("{}\n\n{}", base, patterns))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_boost_neutral_without_usage() {
+        assert_eq!(KnowledgeBaseService::usage_boost(0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_usage_boost_sign_follows_clean_rate() {
+        assert!(KnowledgeBaseService::usage_boost(20, 19) > 0.15);
+        assert!(KnowledgeBaseService::usage_boost(20, 1) < -0.15);
+        assert!(KnowledgeBaseService::usage_boost(20, 19) <= MAX_USAGE_BOOST);
+    }
+
+    #[test]
+    fn test_usage_boost_damped_for_few_runs() {
+        let few = KnowledgeBaseService::usage_boost(2, 2);
+        let many = KnowledgeBaseService::usage_boost(20, 20);
+        assert!(few > 0.0 && few < many);
+    }
+}
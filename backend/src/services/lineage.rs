@@ -0,0 +1,144 @@
+//! Column Lineage Service
+//!
+//! Builds the lineage of a finished generation (see [`Lineage`]) from its
+//! logged intent and artifacts, and answers lineage questions about a log:
+//! everything generated from a column, or the columns behind a line.
+//! Logs written before lineage was stored get it built on the fly.
+
+use serde::Deserialize;
+
+use crate::domain::{Lineage, SpringIntent, UiIntent};
+use crate::models::_entities::generation_logs;
+use crate::services::ArtifactPackager;
+
+/// Lineage filter
+#[derive(Debug, Default, Deserialize)]
+pub struct LineageQuery {
+    /// Source column (case-insensitive)
+    pub column: Option<String>,
+    /// Generated file, with or without its directory
+    pub file: Option<String>,
+    /// Line in `file` (1-based)
+    pub line: Option<usize>,
+}
+
+pub struct LineageService;
+
+impl LineageService {
+    /// Lineage from a logged intent and artifacts (`None` when either is missing)
+    pub fn build(product: &str, intent_json: &str, artifacts_json: &str) -> Option<Lineage> {
+        let files = ArtifactPackager::files_for(product, artifacts_json).ok()?;
+        let files: Vec<(&str, &str)> = files.iter().map(|f| (f.name.as_str(), f.content.as_str())).collect();
+
+        if product == "spring-backend" {
+            let intent: SpringIntent = serde_json::from_str(intent_json).ok()?;
+            Some(Lineage::for_spring(&intent, &files))
+        } else {
+            let intent: UiIntent = serde_json::from_str(intent_json).ok()?;
+            Some(Lineage::for_screen(&intent, &files))
+        }
+    }
+
+    /// Lineage JSON to store on a new log
+    pub fn build_json(product: &str, intent_json: &str, artifacts_json: Option<&str>) -> Option<String> {
+        let lineage = Self::build(product, intent_json, artifacts_json?)?;
+        serde_json::to_string(&lineage).ok()
+    }
+
+    /// Stored lineage of a log, or built from its intent and artifacts
+    pub fn of_log(log: &generation_logs::Model) -> Option<Lineage> {
+        if let Some(lineage) = log.lineage.as_deref().and_then(|l| serde_json::from_str(l).ok()) {
+            return Some(lineage);
+        }
+        Self::build(&log.product, &log.ui_intent, log.artifacts.as_deref()?)
+    }
+
+    /// Columns matching the query, keeping only the targets in `file` (and `line`)
+    pub fn filter(lineage: Lineage, query: &LineageQuery) -> Lineage {
+        let file = query.file.as_deref().map(str::trim).filter(|f| !f.is_empty());
+        let in_file = |target_file: &str| {
+            file.is_none_or(|f| target_file == f || target_file.rsplit('/').next() == f.rsplit('/').next())
+        };
+
+        let columns = lineage
+            .columns
+            .into_iter()
+            .filter(|c| {
+                query
+                    .column
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .is_none_or(|name| c.column.eq_ignore_ascii_case(name))
+            })
+            .filter_map(|mut c| {
+                if file.is_some() {
+                    c.targets
+                        .retain(|t| in_file(&t.file) && query.line.is_none_or(|line| t.line == line));
+                    if c.targets.is_empty() {
+                        return None;
+                    }
+                }
+                Some(c)
+            })
+            .collect();
+        Lineage { columns }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnLineage, LineageKind, LineageTarget};
+
+    fn lineage() -> Lineage {
+        let column = |name: &str, line: usize| ColumnLineage {
+            table: Some("TB_MEMBER".to_string()),
+            dataset: Some("ds_member".to_string()),
+            column: name.to_string(),
+            field: None,
+            intent_path: format!("datasets[ds_member].columns[{}]", name),
+            targets: vec![
+                LineageTarget {
+                    file: "screens/member_list.xml".to_string(),
+                    line,
+                    kind: LineageKind::Binding,
+                    snippet: String::new(),
+                },
+                LineageTarget {
+                    file: "screens/member_list.js".to_string(),
+                    line: 3,
+                    kind: LineageKind::Script,
+                    snippet: String::new(),
+                },
+            ],
+        };
+        Lineage {
+            columns: vec![column("MEMBER_NAME", 10), column("EMAIL", 11)],
+        }
+    }
+
+    #[test]
+    fn test_filter_by_column() {
+        let query = LineageQuery {
+            column: Some("email".to_string()),
+            ..Default::default()
+        };
+        let filtered = LineageService::filter(lineage(), &query);
+        assert_eq!(filtered.columns.len(), 1);
+        assert_eq!(filtered.columns[0].targets.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_file_and_line() {
+        let query = LineageQuery {
+            file: Some("member_list.xml".to_string()),
+            line: Some(11),
+            ..Default::default()
+        };
+        let filtered = LineageService::filter(lineage(), &query);
+        assert_eq!(filtered.columns.len(), 1);
+        assert_eq!(filtered.columns[0].column, "EMAIL");
+        assert_eq!(filtered.columns[0].targets.len(), 1);
+    }
+}
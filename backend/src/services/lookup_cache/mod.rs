@@ -0,0 +1,269 @@
+//! Lookup Cache
+//!
+//! Caches the rows every generation reads - the active prompt template, the
+//! active LLM config and knowledge base queries - so they are not fetched
+//! from the database on each request.
+//!
+//! Two stores are available, chosen with `CACHE_BACKEND`:
+//! - `memory` (default): per-process map with a TTL
+//! - `redis`: shared by every instance behind a load balancer (`REDIS_URL`)
+//! - `off`: always read from the database
+//!
+//! Entries live for `CACHE_TTL_SECS` (default 60). Saving or deleting a
+//! template, LLM config or knowledge entry invalidates its whole namespace
+//! right away (see the model hooks), so admin edits apply to the next
+//! generation. Redis invalidation bumps a per-namespace generation counter,
+//! which reaches every instance at once.
+//!
+//! Cache failures never fail a request: a store that cannot be reached is
+//! treated as a miss and the value is loaded from the database.
+
+mod redis;
+
+pub use redis::RedisStore;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use loco_rs::environment::Environment;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Entries kept by the in-process store (expired and oldest are evicted first)
+const MAX_MEMORY_ENTRIES: usize = 2000;
+
+/// Group of cached lookups that is invalidated together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    /// Active prompt templates
+    Templates,
+    /// Active LLM configs
+    LlmConfigs,
+    /// Knowledge base queries
+    Knowledge,
+}
+
+impl Namespace {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Templates => "templates",
+            Self::LlmConfigs => "llm_configs",
+            Self::Knowledge => "knowledge",
+        }
+    }
+}
+
+/// Storage behind the lookup cache (values are JSON strings)
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Cached value, `None` on a miss or when the store is unreachable
+    async fn get(&self, namespace: Namespace, key: &str) -> Option<String>;
+
+    /// Store a value for `ttl`
+    async fn set(&self, namespace: Namespace, key: &str, value: String, ttl: Duration);
+
+    /// Drop every entry of a namespace
+    async fn invalidate(&self, namespace: Namespace);
+}
+
+/// In-process store
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: RwLock<HashMap<(Namespace, String), (Instant, String)>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryStore {
+    async fn get(&self, namespace: Namespace, key: &str) -> Option<String> {
+        let entries = self.entries.read().ok()?;
+        entries
+            .get(&(namespace, key.to_string()))
+            .filter(|(expires_at, _)| *expires_at > Instant::now())
+            .map(|(_, value)| value.clone())
+    }
+
+    async fn set(&self, namespace: Namespace, key: &str, value: String, ttl: Duration) {
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+        let now = Instant::now();
+        if entries.len() >= MAX_MEMORY_ENTRIES {
+            entries.retain(|_, (expires_at, _)| *expires_at > now);
+        }
+        if entries.len() >= MAX_MEMORY_ENTRIES {
+            // Entries share one TTL, so the soonest to expire is the oldest
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (expires_at, _))| *expires_at)
+                .map(|(k, _)| k.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert((namespace, key.to_string()), (now + ttl, value));
+    }
+
+    async fn invalidate(&self, namespace: Namespace) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|(ns, _), _| *ns != namespace);
+        }
+    }
+}
+
+/// Cache settings, read from the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// `memory`, `redis` or `off` (`CACHE_BACKEND`)
+    pub backend: String,
+
+    /// Redis URL (`REDIS_URL`, default `redis://127.0.0.1:6379`)
+    pub redis_url: String,
+
+    /// Entry lifetime (`CACHE_TTL_SECS`, default 60)
+    pub ttl: Duration,
+}
+
+impl CacheConfig {
+    /// Load from environment
+    ///
+    /// Tests default to `off` so cached rows never outlive a database reset.
+    pub fn from_env(environment: &Environment) -> Self {
+        let default_backend = if *environment == Environment::Test { "off" } else { "memory" };
+        Self {
+            backend: std::env::var("CACHE_BACKEND")
+                .map(|b| b.trim().to_lowercase())
+                .unwrap_or_else(|_| default_backend.to_string()),
+            redis_url: std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+            ttl: Duration::from_secs(
+                std::env::var("CACHE_TTL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(60),
+            ),
+        }
+    }
+}
+
+struct LookupCacheState {
+    store: Arc<dyn CacheStore>,
+    ttl: Duration,
+}
+
+static STATE: OnceLock<LookupCacheState> = OnceLock::new();
+
+/// Set up the cache from the environment (no-op when already set up or `off`)
+pub fn init(environment: &Environment) {
+    let config = CacheConfig::from_env(environment);
+    let store: Arc<dyn CacheStore> = match config.backend.as_str() {
+        "off" | "none" | "" => {
+            tracing::info!("Lookup cache disabled");
+            return;
+        }
+        "redis" => match RedisStore::from_url(&config.redis_url) {
+            Ok(store) => Arc::new(store),
+            Err(e) => {
+                tracing::warn!("Invalid REDIS_URL ({}), using in-process lookup cache", e);
+                Arc::new(MemoryStore::new())
+            }
+        },
+        "memory" => Arc::new(MemoryStore::new()),
+        other => {
+            tracing::warn!("Unknown CACHE_BACKEND '{}', using in-process lookup cache", other);
+            Arc::new(MemoryStore::new())
+        }
+    };
+
+    tracing::info!("Lookup cache: {} (ttl {}s)", config.backend, config.ttl.as_secs());
+    let _ = STATE.set(LookupCacheState { store, ttl: config.ttl });
+}
+
+/// Cache facade used by the lookup services
+pub struct LookupCache;
+
+impl LookupCache {
+    /// Cached value for `key`, loading (and storing) it on a miss
+    ///
+    /// Load errors are returned as-is and never cached.
+    pub async fn get_or_load<T, E, F, Fut>(namespace: Namespace, key: &str, load: F) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let Some(state) = STATE.get() else {
+            return load().await;
+        };
+
+        if let Some(cached) = state.store.get(namespace, key).await {
+            if let Ok(value) = serde_json::from_str(&cached) {
+                return Ok(value);
+            }
+        }
+
+        let value = load().await?;
+        if let Ok(json) = serde_json::to_string(&value) {
+            state.store.set(namespace, key, json, state.ttl).await;
+        }
+        Ok(value)
+    }
+
+    /// Drop a namespace after its rows changed
+    pub async fn invalidate(namespace: Namespace) {
+        if let Some(state) = STATE.get() {
+            state.store.invalidate(namespace).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store_expires_and_invalidates() {
+        let store = MemoryStore::new();
+        store
+            .set(Namespace::Templates, "a", "1".to_string(), Duration::from_secs(60))
+            .await;
+        store
+            .set(Namespace::Knowledge, "a", "2".to_string(), Duration::from_secs(60))
+            .await;
+        store
+            .set(Namespace::Templates, "gone", "3".to_string(), Duration::ZERO)
+            .await;
+
+        assert_eq!(store.get(Namespace::Templates, "a").await.as_deref(), Some("1"));
+        assert_eq!(store.get(Namespace::Templates, "gone").await, None);
+
+        store.invalidate(Namespace::Templates).await;
+        assert_eq!(store.get(Namespace::Templates, "a").await, None);
+        assert_eq!(store.get(Namespace::Knowledge, "a").await.as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_evicts_oldest_when_full() {
+        let store = MemoryStore::new();
+        for i in 0..MAX_MEMORY_ENTRIES {
+            store
+                .set(Namespace::Knowledge, &i.to_string(), String::new(), Duration::from_secs(60 + i as u64))
+                .await;
+        }
+        store
+            .set(Namespace::Knowledge, "new", String::new(), Duration::from_secs(600))
+            .await;
+
+        assert_eq!(store.get(Namespace::Knowledge, "0").await, None);
+        assert!(store.get(Namespace::Knowledge, "1").await.is_some());
+        assert!(store.get(Namespace::Knowledge, "new").await.is_some());
+    }
+}
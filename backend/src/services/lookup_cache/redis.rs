@@ -0,0 +1,82 @@
+//! Redis store
+//!
+//! Keys are `coder:cache:{namespace}:{generation}:{key}`. Invalidating a
+//! namespace increments `coder:cache:{namespace}:gen`; entries of older
+//! generations are never read again and expire with their TTL.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::{CacheStore, Namespace};
+use crate::services::redis_client::{RedisClient, Reply};
+
+const KEY_PREFIX: &str = "coder:cache";
+
+/// Redis-backed cache store shared by every instance
+pub struct RedisStore {
+    client: RedisClient,
+}
+
+impl RedisStore {
+    /// Store for a `redis://` URL (connects lazily)
+    pub fn from_url(url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: RedisClient::from_url(url)?,
+        })
+    }
+
+    /// Run a command, logging failures (the cache treats them as misses)
+    async fn command(&self, args: &[&str]) -> Option<Reply> {
+        match self.client.command(args).await {
+            Ok(reply) => Some(reply),
+            Err(e) => {
+                tracing::debug!("Lookup cache: {:#}", e);
+                None
+            }
+        }
+    }
+
+    /// Current generation of a namespace (`None` when Redis is unreachable)
+    async fn generation(&self, namespace: Namespace) -> Option<i64> {
+        let key = format!("{}:{}:gen", KEY_PREFIX, namespace.as_str());
+        let generation = self.command(&["GET", &key]).await?.into_string();
+        Some(generation.and_then(|g| g.parse().ok()).unwrap_or(0))
+    }
+
+    fn entry_key(namespace: Namespace, generation: i64, key: &str) -> String {
+        format!("{}:{}:{}:{}", KEY_PREFIX, namespace.as_str(), generation, key)
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisStore {
+    async fn get(&self, namespace: Namespace, key: &str) -> Option<String> {
+        let generation = self.generation(namespace).await?;
+        self.command(&["GET", &Self::entry_key(namespace, generation, key)])
+            .await?
+            .into_string()
+    }
+
+    async fn set(&self, namespace: Namespace, key: &str, value: String, ttl: Duration) {
+        let Some(generation) = self.generation(namespace).await else {
+            return;
+        };
+        let ttl = ttl.as_secs().max(1).to_string();
+        self.command(&["SET", &Self::entry_key(namespace, generation, key), &value, "EX", &ttl])
+            .await;
+    }
+
+    async fn invalidate(&self, namespace: Namespace) {
+        let key = format!("{}:{}:gen", KEY_PREFIX, namespace.as_str());
+        match self.command(&["INCR", &key]).await {
+            Some(Reply::Integer(generation)) => {
+                tracing::debug!("Cached {} invalidated (generation {})", namespace.as_str(), generation);
+            }
+            _ => tracing::warn!(
+                "Could not invalidate cached {} in Redis; stale entries expire with their TTL",
+                namespace.as_str()
+            ),
+        }
+    }
+}
@@ -1,35 +1,146 @@
 mod generation;
 mod normalizer;
+mod screen_dsl;
+mod intent_merge;
+mod intent_complexity;
 mod prompt_compiler;
+mod prompt_compression;
 mod template;
 mod template_importer;
 pub mod xframe5_validator;
 mod spring_normalizer;
 pub mod spring_validator;
+mod java_syntax;
+mod java_formatter;
+mod mybatis_validator;
+mod mybatis_sql;
+mod java_compiler;
+mod query_plan;
+mod test_data;
+mod race;
 mod spring_prompt_compiler;
 mod spring_generation;
+mod spring_common;
+mod spring_module;
 pub mod admin;
 pub mod system_monitor;
 pub mod analytics;
 pub mod metrics_history;
 mod knowledge_base_service;
 mod review_service;
+mod review_patch;
+mod review_gate;
 mod qa_service;
+pub mod response_cache;
 pub mod pipeline;
+pub mod telemetry;
+mod artifact_package;
+mod artifact_scorer;
+mod accessibility_checker;
+pub mod content_guard;
+mod generation_history;
+mod git_publisher;
+mod issue_tracker;
+mod notifier;
+mod autoscaling;
+pub mod distillation;
+mod replay;
+mod todo_resolver;
+mod symbol_rename;
+mod duplicate_screen;
+mod model_catalog;
+mod template_coverage;
+mod tenant;
+pub mod template_rollout;
+pub mod template_variables;
+pub mod canary;
+pub mod business_calendar;
+pub mod batch_schedule;
+pub mod schema_change;
+pub mod lineage;
+pub mod demo;
+pub mod job_status;
+pub mod client_sdk;
+pub mod openapi;
+pub mod lookup_cache;
+pub mod redaction;
+pub mod refusal;
+pub mod retention;
+pub mod project_review;
+pub mod pattern_drift;
+pub mod screen_registry;
+pub mod generation_hooks;
+pub mod wasm_plugin;
+pub mod redis_client;
+pub mod sla;
+pub mod vector_store;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 
-pub use generation::GenerationService;
+pub use generation::{GenerationPins, GenerationService};
 pub use normalizer::NormalizerService;
-pub use prompt_compiler::{CompiledPrompt, PromptCompiler};
+pub use screen_dsl::ScreenDsl;
+pub use intent_merge::{IntentMerger, MergeResult, Refinement};
+pub use intent_complexity::{ColumnGroup, ComplexityLevel, ComplexityReport, IntentComplexity, ScreenSplit};
+pub use prompt_compiler::{CompiledPrompt, PromptCompiler, PromptSection, PromptSections};
+pub use prompt_compression::PromptCompression;
 pub use template::TemplateService;
 pub use template_importer::{ImportOptions, ImportResult, TemplateImporter};
 pub use spring_normalizer::SpringNormalizerService;
 pub use spring_validator::SpringValidator;
+pub use java_syntax::{JavaIssue, JavaSyntaxChecker};
+pub use java_formatter::JavaFormatter;
+pub use mybatis_validator::MyBatisValidator;
+pub use mybatis_sql::{ConditionSql, MyBatisSqlChecker};
+pub use java_compiler::{CompileToolchain, JavaCompileConfig, JavaCompiler};
+pub use query_plan::{QueryPlanAdvisor, QueryPlanConfig};
+pub use test_data::{TestDataGenerator, MAX_TEST_DATA_ROWS};
+pub use race::{RaceOutcome, RaceRunner, RaceSide};
 pub use spring_prompt_compiler::SpringPromptCompiler;
 pub use spring_generation::{SpringGenerationService, SpringGenerateResponse};
+pub use spring_common::SpringCommonClasses;
+pub use spring_module::{SpringModuleResponse, SpringModuleService};
 pub use system_monitor::{SystemMonitor, SystemMetrics};
 pub use analytics::AnalyticsService;
 pub use knowledge_base_service::{
-    KnowledgeBaseService, KnowledgeEntry, KnowledgeFileFallback, KnowledgeQuery,
+    KnowledgeBaseService, KnowledgeEntry, KnowledgeFileFallback, KnowledgeQuery, KnowledgeRevision,
 };
 pub use review_service::ReviewService;
+pub use review_patch::ReviewPatcher;
+pub use review_gate::ReviewGate;
 pub use qa_service::QAService;
+pub use artifact_package::{ArtifactPackager, PackagedFile};
+pub use artifact_scorer::{ArtifactScorer, QualityScore};
+pub use accessibility_checker::AccessibilityChecker;
+pub use git_publisher::{GitPublisher, GitPushResult};
+pub use issue_tracker::IssueTrackerService;
+pub use notifier::{ChatEvent, Notifier};
+pub use autoscaling::{ProviderSaturation, ScalingConfig, ScalingHints};
+pub use content_guard::{ContentFinding, ContentGuard, ContentScan, FindingKind};
+pub use distillation::DistillationService;
+pub use generation_history::{GenerationHistoryService, HistoryQuery};
+pub use replay::{ReplayOutcome, ReplayService};
+pub use symbol_rename::{RenameChange, RenameKind, RenameResult, SkippedOccurrence, SymbolRenamer};
+pub use todo_resolver::{TodoItem, TodoKind, TodoResolution, TodoResolveResult, TodoResolver};
+pub use duplicate_screen::{DuplicatePolicy, DuplicateScreen, DuplicateScreenDetector};
+pub use template_coverage::{CoverageReport, RuleCoverage, RuleStatus, TemplateCoverageAnalyzer};
+pub use tenant::TenantScope;
+pub use template_rollout::{RolloutConfig, TemplateRollout};
+pub use template_variables::{TemplateVariables, VariableCheck};
+pub use canary::{CanaryConfig, CanaryService};
+pub use business_calendar::{BusinessCalendar, KoreanHolidays};
+pub use batch_schedule::{BatchDecision, BatchJob, BatchScheduleService};
+pub use schema_change::{Regeneration, SchemaChangeService, StaleArtifact};
+pub use lineage::{LineageQuery, LineageService};
+pub use demo::{DemoConfig, DemoService};
+pub use job_status::{JobStatusResponse, JobStatusService};
+pub use lookup_cache::LookupCache;
+pub use redaction::{RedactionConfig, Redactor};
+pub use refusal::{Refusal, REFUSAL_ERROR_CODE};
+pub use retention::RetentionService;
+pub use project_review::{ProjectArchive, ProjectReviewService};
+pub use pattern_drift::{PatternDriftService, PatternReport};
+pub use screen_registry::{CollisionPolicy, ScreenRegistry};
+pub use generation_hooks::{GenerationHook, HookContext, HookRegistry};
+pub use wasm_plugin::{PluginFinding, PluginSeverity, WasmPlugin, WasmPlugins};
+pub use model_catalog::{AvailableModel, ModelCatalog, PullProgress};
@@ -0,0 +1,199 @@
+//! Model Catalog
+//!
+//! Lists the models an Ollama or vLLM server offers, so the admin LLM config
+//! form can show a dropdown instead of a free-text field, and pulls missing
+//! models onto Ollama servers.
+//!
+//! A pull runs in the background; its progress is kept in memory per
+//! endpoint and model and polled by the form until it finishes.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use serde::Serialize;
+
+use crate::llm::{OllamaBackend, OllamaPullStatus, VllmBackend};
+
+/// Model offered by an LLM server
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AvailableModel {
+    pub name: String,
+    /// Size, parameters or context length, when the server reports them
+    pub description: Option<String>,
+}
+
+/// State of a model pull
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PullProgress {
+    pub endpoint: String,
+    pub model: String,
+    /// Last status line from Ollama (e.g. "pulling manifest", "success")
+    pub status: String,
+    pub completed: u64,
+    pub total: u64,
+    /// 0-100, 0 while the size is unknown
+    pub percent: u8,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+impl PullProgress {
+    fn new(endpoint: &str, model: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            model: model.to_string(),
+            status: "starting".to_string(),
+            completed: 0,
+            total: 0,
+            percent: 0,
+            done: false,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, line: OllamaPullStatus) {
+        self.status = line.status;
+        // Each layer reports its own total; lines without sizes keep the last layer's
+        if let Some(total) = line.total {
+            self.total = total;
+            self.completed = line.completed.unwrap_or(0).min(total);
+        }
+        self.percent = match self.total {
+            0 => 0,
+            total => (self.completed * 100 / total) as u8,
+        };
+    }
+}
+
+fn pulls() -> &'static RwLock<HashMap<(String, String), PullProgress>> {
+    static PULLS: OnceLock<RwLock<HashMap<(String, String), PullProgress>>> = OnceLock::new();
+    PULLS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn set_progress(progress: PullProgress) {
+    if let Ok(mut pulls) = pulls().write() {
+        pulls.insert((progress.endpoint.clone(), progress.model.clone()), progress);
+    }
+}
+
+/// Model listing and pulling
+pub struct ModelCatalog;
+
+impl ModelCatalog {
+    /// Models offered by the server at `endpoint`
+    pub async fn list(
+        provider: &str,
+        endpoint: &str,
+        api_key: Option<String>,
+    ) -> anyhow::Result<Vec<AvailableModel>> {
+        let endpoint = endpoint.trim_end_matches('/').to_string();
+
+        match provider {
+            "ollama" => {
+                let models = OllamaBackend::new(endpoint, String::new(), 10).list_models().await?;
+                Ok(models
+                    .into_iter()
+                    .map(|m| AvailableModel {
+                        description: m.details.and_then(|d| d.parameter_size),
+                        name: m.name,
+                    })
+                    .collect())
+            }
+            "vllm" => {
+                let models = VllmBackend::new(endpoint, String::new(), api_key, 10).list_models().await?;
+                Ok(models
+                    .into_iter()
+                    .map(|m| AvailableModel {
+                        description: m.max_model_len.map(|len| format!("{} ctx", len)),
+                        name: m.id,
+                    })
+                    .collect())
+            }
+            other => anyhow::bail!("Model listing is not supported for provider '{}'", other),
+        }
+    }
+
+    /// Start pulling `model` onto the Ollama server (no-op while a pull of it runs)
+    pub fn start_pull(endpoint: &str, model: &str) -> anyhow::Result<PullProgress> {
+        let endpoint = endpoint.trim_end_matches('/');
+        let model = model.trim();
+        if endpoint.is_empty() || model.is_empty() {
+            anyhow::bail!("Endpoint URL and model name are required to pull a model");
+        }
+
+        if let Some(progress) = Self::progress(endpoint, model).filter(|p| !p.done) {
+            return Ok(progress);
+        }
+
+        let progress = PullProgress::new(endpoint, model);
+        set_progress(progress.clone());
+
+        let mut state = progress.clone();
+        tokio::spawn(async move {
+            let ollama = OllamaBackend::new(state.endpoint.clone(), String::new(), 10);
+            let model = state.model.clone();
+            let result = ollama
+                .pull_model(&model, |line| {
+                    state.update(line);
+                    set_progress(state.clone());
+                })
+                .await;
+
+            state.done = true;
+            match result {
+                Ok(()) => {
+                    state.percent = 100;
+                    tracing::info!("Pulled model {} onto {}", state.model, state.endpoint);
+                }
+                Err(e) => {
+                    tracing::warn!("Pulling model {} onto {} failed: {}", state.model, state.endpoint, e);
+                    state.error = Some(e.to_string());
+                }
+            }
+            set_progress(state);
+        });
+
+        Ok(progress)
+    }
+
+    /// Progress of the latest pull of `model` onto `endpoint`
+    pub fn progress(endpoint: &str, model: &str) -> Option<PullProgress> {
+        let key = (endpoint.trim_end_matches('/').to_string(), model.trim().to_string());
+        pulls().read().ok()?.get(&key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(status: &str, total: Option<u64>, completed: Option<u64>) -> OllamaPullStatus {
+        OllamaPullStatus {
+            status: status.to_string(),
+            total,
+            completed,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_pull_progress_percent() {
+        let mut progress = PullProgress::new("http://ollama:11434", "llama3.1:8b");
+        progress.update(line("pulling manifest", None, None));
+        assert_eq!(progress.percent, 0);
+
+        progress.update(line("pulling 8eeb52dfb3bb", Some(4_000), Some(1_000)));
+        assert_eq!(progress.percent, 25);
+
+        // Status lines without sizes keep the last layer's numbers
+        progress.update(line("verifying sha256 digest", None, None));
+        assert_eq!(progress.percent, 25);
+        assert_eq!(progress.status, "verifying sha256 digest");
+    }
+
+    #[test]
+    fn test_start_pull_requires_model() {
+        assert!(ModelCatalog::start_pull("http://ollama:11434", " ").is_err());
+        assert!(ModelCatalog::progress("http://ollama:11434", "unknown").is_none());
+    }
+}
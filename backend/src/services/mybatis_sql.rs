@@ -0,0 +1,514 @@
+//! MyBatis Statement SQL Checks
+//!
+//! Flattens each mapper statement to plain SQL and parses it with sqlparser in
+//! the intent's dialect (PostgreSQL or MySQL; Oracle and unknown sources use
+//! the generic dialect, which covers the ANSI subset the templates emit).
+//!
+//! Dynamic SQL is expanded with every branch taken: `<if>` bodies are kept,
+//! `<choose>` takes its first `<when>`, `<where>`/`<set>`/`<trim>` apply their
+//! prefix and overrides, `<foreach>` is emitted once and `<include>` pulls in
+//! the `<sql>` fragment. `#{}` becomes a bind placeholder and `${}` an opaque
+//! identifier.
+//!
+//! Findings are scoped by statement id:
+//! - syntax errors (a `Note:` when the statement uses `${}` substitution)
+//! - UPDATE/DELETE without WHERE, or whose WHERE only comes from `<if>`s
+//! - SELECT without FROM, SQL that doesn't match the statement tag
+//! - columns that aren't in the intent (statements on the intent's table only)
+
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
+
+use regex::{NoExpand, Regex};
+use roxmltree::{Document, Node, ParsingOptions};
+use sqlparser::ast::{AssignmentTarget, Expr, ObjectName, Query, SelectItem, SetExpr, Statement, Visit, Visitor};
+use sqlparser::dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect};
+use sqlparser::parser::Parser;
+
+use crate::domain::{DbDialect, SpringIntent};
+
+const STATEMENTS: &[&str] = &["select", "insert", "update", "delete"];
+
+/// Stand-in for `${}` substitutions, never reported as a column
+const DYNAMIC: &str = "__dynamic__";
+
+/// Pseudo-columns and niladic functions the parser reads as identifiers
+const PSEUDO_COLUMNS: &[&str] = &[
+    "SYSDATE", "SYSTIMESTAMP", "ROWNUM", "ROWID", "LEVEL", "NEXTVAL", "CURRVAL", "CURRENT_DATE",
+    "CURRENT_TIME", "CURRENT_TIMESTAMP", "LOCALTIMESTAMP", "CURRENT_USER", "USER",
+];
+
+/// Audit columns the templates add when `include_audit_fields` is set
+const AUDIT_COLUMNS: &[&str] = &["CREATED_AT", "UPDATED_AT", "CREATED_BY", "UPDATED_BY"];
+
+/// `<include>` nesting limit (guards against fragments including each other)
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Which `<if>`/`<when>` bodies flattening keeps
+#[derive(Clone, Copy)]
+enum Branches<'a, 'input> {
+    All,
+    None,
+    /// Only this condition (and the conditions enclosing it)
+    Only(Node<'a, 'input>),
+}
+
+impl Branches<'_, '_> {
+    fn takes(&self, condition: Node) -> bool {
+        match self {
+            Branches::All => true,
+            Branches::None => false,
+            Branches::Only(target) => target.ancestors().any(|n| n == condition),
+        }
+    }
+}
+
+/// A search condition of a statement with the SQL run when only it is set
+#[derive(Debug, Clone)]
+pub struct ConditionSql {
+    /// The condition's `test` expression
+    pub test: String,
+    pub sql: String,
+}
+
+/// Tables, columns and select-list aliases referenced by a statement
+#[derive(Default)]
+struct References {
+    tables: BTreeSet<String>,
+    columns: Vec<String>,
+    aliases: BTreeSet<String>,
+}
+
+impl Visitor for References {
+    type Break = ();
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<()> {
+        if let Some(name) = relation.0.last() {
+            self.tables.insert(name.value.to_uppercase());
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        if let SetExpr::Select(select) = query.body.as_ref() {
+            for item in &select.projection {
+                if let SelectItem::ExprWithAlias { alias, .. } = item {
+                    self.aliases.insert(alias.value.to_uppercase());
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<()> {
+        match expr {
+            Expr::Identifier(ident) => self.columns.push(ident.value.clone()),
+            Expr::CompoundIdentifier(parts) => self.columns.extend(parts.last().map(|p| p.value.clone())),
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
+        match statement {
+            Statement::Insert(insert) => self.columns.extend(insert.columns.iter().map(|c| c.value.clone())),
+            Statement::Update { assignments, .. } => {
+                for assignment in assignments {
+                    let targets = match &assignment.target {
+                        AssignmentTarget::ColumnName(name) => std::slice::from_ref(name),
+                        AssignmentTarget::Tuple(names) => names.as_slice(),
+                    };
+                    self.columns
+                        .extend(targets.iter().filter_map(|n| n.0.last()).map(|p| p.value.clone()));
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// SQL checks for the statements of a MyBatis mapper XML
+pub struct MyBatisSqlChecker;
+
+impl MyBatisSqlChecker {
+    /// Check every statement of the mapper XML against the intent
+    pub fn check(mapper_xml: &str, intent: &SpringIntent) -> Vec<String> {
+        // Malformed XML is already reported by MyBatisValidator
+        let options = ParsingOptions { allow_dtd: true, ..ParsingOptions::default() };
+        let Ok(doc) = Document::parse_with_options(mapper_xml, options) else {
+            return Vec::new();
+        };
+        let root = doc.root_element();
+
+        let mut warnings = Vec::new();
+        for statement in root.children().filter(|n| STATEMENTS.contains(&n.tag_name().name())) {
+            let id = statement.attribute("id").unwrap_or("?");
+            for finding in Self::check_statement(root, statement, intent) {
+                warnings.push(match finding.strip_prefix("Note: ") {
+                    Some(note) => format!("Note: Mapper SQL '{}': {}", id, note),
+                    None => format!("Warning: Mapper SQL '{}': {}", id, finding),
+                });
+            }
+        }
+        warnings
+    }
+
+    /// Bind-parameterized SQL of statement `id`, with every condition set
+    pub fn statement_sql(mapper_xml: &str, id: &str, dialect: Option<DbDialect>) -> Option<String> {
+        let options = ParsingOptions { allow_dtd: true, ..ParsingOptions::default() };
+        let doc = Document::parse_with_options(mapper_xml, options).ok()?;
+        let root = doc.root_element();
+        let statement = root.children().find(|n| n.is_element() && n.attribute("id") == Some(id))?;
+        Some(Self::to_sql(&Self::flatten(root, statement, Branches::All, 0), dialect))
+    }
+
+    /// SQL of statement `id` once per `<if>`/`<when>` condition, with only that one set
+    pub fn condition_variants(mapper_xml: &str, id: &str, dialect: Option<DbDialect>) -> Vec<ConditionSql> {
+        let options = ParsingOptions { allow_dtd: true, ..ParsingOptions::default() };
+        let Ok(doc) = Document::parse_with_options(mapper_xml, options) else {
+            return Vec::new();
+        };
+        let root = doc.root_element();
+        let Some(statement) = root.children().find(|n| n.is_element() && n.attribute("id") == Some(id)) else {
+            return Vec::new();
+        };
+        statement
+            .descendants()
+            .filter(|n| n.has_tag_name("if") || n.has_tag_name("when"))
+            .map(|condition| ConditionSql {
+                test: condition.attribute("test").unwrap_or_default().to_string(),
+                sql: Self::to_sql(&Self::flatten(root, statement, Branches::Only(condition), 0), dialect),
+            })
+            .collect()
+    }
+
+    fn check_statement(root: Node, statement: Node, intent: &SpringIntent) -> Vec<String> {
+        let tag = statement.tag_name().name();
+        let sql = Self::to_sql(&Self::flatten(root, statement, Branches::All, 0), intent.dialect);
+        let dialect = Self::dialect(intent.dialect);
+
+        let statements = match Parser::parse_sql(dialect.as_ref(), &sql) {
+            Ok(statements) => statements,
+            Err(e) => {
+                let error = e.to_string().trim_start_matches("sql parser error: ").to_string();
+                return vec![if sql.contains(DYNAMIC) {
+                    format!("Note: syntax not checked, ${{}} substitution: {}", error)
+                } else {
+                    format!("syntax error: {}", error)
+                }];
+            }
+        };
+        let Some(parsed) = statements.first() else {
+            return vec![format!("<{}> has no SQL", tag)];
+        };
+
+        let mut findings = Vec::new();
+        if statements.len() > 1 {
+            findings.push(format!("contains {} SQL statements; MyBatis runs one per statement", statements.len()));
+        }
+
+        let kind = Self::kind(parsed);
+        let expected = match tag {
+            "select" => kind == "SELECT",
+            "insert" => kind == "INSERT" || kind == "MERGE",
+            "update" => kind == "UPDATE" || kind == "MERGE",
+            _ => kind == "DELETE",
+        };
+        if !expected {
+            findings.push(format!("<{}> contains a {} statement", tag, kind));
+        }
+
+        match parsed {
+            Statement::Query(query) => {
+                if let SetExpr::Select(select) = query.body.as_ref() {
+                    if select.from.is_empty() {
+                        findings.push(match intent.dialect {
+                            Some(DbDialect::Oracle) => "SELECT has no FROM clause (Oracle needs FROM DUAL)".to_string(),
+                            _ => "SELECT has no FROM clause".to_string(),
+                        });
+                    }
+                }
+            }
+            Statement::Update { selection, .. } => findings.extend(Self::check_where(root, statement, "UPDATE", selection.is_some())),
+            Statement::Delete(delete) => findings.extend(Self::check_where(root, statement, "DELETE", delete.selection.is_some())),
+            _ => {}
+        }
+
+        findings.extend(Self::check_columns(parsed, intent));
+        findings
+    }
+
+    /// UPDATE/DELETE must keep a WHERE clause even when no `<if>` matches
+    fn check_where(root: Node, statement: Node, kind: &str, has_where: bool) -> Option<String> {
+        if !has_where {
+            return Some(format!("{} without WHERE affects every row", kind));
+        }
+        let minimal = Self::flatten(root, statement, Branches::None, 0);
+        let where_re = Regex::new(r"(?i)\bWHERE\b").unwrap();
+        (!where_re.is_match(&minimal))
+            .then(|| format!("{} WHERE clause only comes from <if> conditions; with none set it affects every row", kind))
+    }
+
+    /// Columns not in the intent, for statements that only touch the intent's table
+    fn check_columns(parsed: &Statement, intent: &SpringIntent) -> Vec<String> {
+        if intent.columns.is_empty() {
+            return Vec::new();
+        }
+        let mut refs = References::default();
+        let _ = parsed.visit(&mut refs);
+
+        let table = intent.table_name.rsplit('.').next().unwrap_or_default().to_uppercase();
+        if refs.tables.iter().any(|t| *t != table && t != "DUAL") {
+            return Vec::new();
+        }
+
+        let mut known: BTreeSet<String> = intent.columns.iter().map(|c| c.name.to_uppercase()).collect();
+        known.extend(PSEUDO_COLUMNS.iter().map(|c| c.to_string()));
+        if intent.options.include_audit_fields {
+            known.extend(AUDIT_COLUMNS.iter().map(|c| c.to_string()));
+        }
+        known.extend(refs.aliases);
+
+        let mut reported = BTreeSet::new();
+        refs.columns
+            .into_iter()
+            .filter(|c| c != DYNAMIC && !known.contains(&c.to_uppercase()))
+            .filter(|c| reported.insert(c.to_uppercase()))
+            .map(|c| format!("column '{}' is not a column of {}", c, intent.table_name))
+            .collect()
+    }
+
+    /// Statement text with dynamic tags expanded
+    fn flatten(root: Node, node: Node, branches: Branches, depth: usize) -> String {
+        let mut out = String::new();
+        for child in node.children() {
+            if child.is_text() {
+                out.push_str(child.text().unwrap_or_default());
+                continue;
+            }
+            if !child.is_element() {
+                continue;
+            }
+            let inner = |n: Node| Self::flatten(root, n, branches, depth);
+            match child.tag_name().name() {
+                "if" if branches.takes(child) => out.push_str(&inner(child)),
+                "if" | "bind" | "selectKey" => {}
+                "choose" => {
+                    let when = match branches {
+                        Branches::All => child.children().find(|n| n.has_tag_name("when")),
+                        _ => child.children().find(|n| n.has_tag_name("when") && branches.takes(*n)),
+                    };
+                    let branch = when.or_else(|| child.children().find(|n| n.has_tag_name("otherwise")));
+                    out.push_str(&branch.map(inner).unwrap_or_default());
+                }
+                "where" => out.push_str(&Self::trim(&inner(child), "WHERE", "", "AND|OR", "")),
+                "set" => out.push_str(&Self::trim(&inner(child), "SET", "", "", ",")),
+                "trim" => out.push_str(&Self::trim(
+                    &inner(child),
+                    child.attribute("prefix").unwrap_or_default(),
+                    child.attribute("suffix").unwrap_or_default(),
+                    child.attribute("prefixOverrides").unwrap_or_default(),
+                    child.attribute("suffixOverrides").unwrap_or_default(),
+                )),
+                "foreach" => {
+                    out.push(' ');
+                    out.push_str(child.attribute("open").unwrap_or_default());
+                    out.push_str(&inner(child));
+                    out.push_str(child.attribute("close").unwrap_or_default());
+                    out.push(' ');
+                }
+                "include" if depth < MAX_INCLUDE_DEPTH => {
+                    let refid = child.attribute("refid").unwrap_or_default();
+                    let refid = refid.rsplit('.').next().unwrap_or(refid);
+                    if let Some(fragment) = root
+                        .children()
+                        .find(|n| n.has_tag_name("sql") && n.attribute("id") == Some(refid))
+                    {
+                        out.push_str(&Self::flatten(root, fragment, branches, depth + 1));
+                    }
+                }
+                _ => out.push_str(&inner(child)),
+            }
+        }
+        out
+    }
+
+    /// MyBatis `<trim>`: overrides are `|`-separated and case-insensitive
+    fn trim(content: &str, prefix: &str, suffix: &str, prefix_overrides: &str, suffix_overrides: &str) -> String {
+        let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let mut body = content.trim();
+        for ov in prefix_overrides.split('|').map(str::trim).filter(|o| !o.is_empty()) {
+            let head = body.get(..ov.len()).unwrap_or_default();
+            let rest = &body[head.len()..];
+            if head.eq_ignore_ascii_case(ov) && !(word(ov.chars().last()) && word(rest.chars().next())) {
+                body = rest.trim_start();
+                break;
+            }
+        }
+        for ov in suffix_overrides.split('|').map(str::trim).filter(|o| !o.is_empty()) {
+            let split = body.len().saturating_sub(ov.len());
+            let (rest, tail) = (body.get(..split).unwrap_or_default(), body.get(split..).unwrap_or_default());
+            if tail.eq_ignore_ascii_case(ov) && !(word(ov.chars().next()) && word(rest.chars().last())) {
+                body = rest.trim_end();
+                break;
+            }
+        }
+        if body.is_empty() {
+            return " ".to_string();
+        }
+        format!(" {} {} {} ", prefix, body, suffix)
+    }
+
+    /// Replace MyBatis parameters: `#{}` binds, `${}` text substitution
+    fn to_sql(text: &str, dialect: Option<DbDialect>) -> String {
+        let bind = Regex::new(r"#\{[^}]*\}").unwrap();
+        let substitution = Regex::new(r"\$\{[^}]*\}").unwrap();
+        let placeholder = match dialect {
+            Some(DbDialect::Postgres) => "$1",
+            _ => "?",
+        };
+        let sql = substitution.replace_all(text, DYNAMIC);
+        bind.replace_all(&sql, NoExpand(placeholder)).trim().trim_end_matches(';').to_string()
+    }
+
+    fn dialect(dialect: Option<DbDialect>) -> Box<dyn Dialect> {
+        match dialect {
+            Some(DbDialect::Postgres) => Box::new(PostgreSqlDialect {}),
+            Some(DbDialect::Mysql) => Box::new(MySqlDialect {}),
+            Some(DbDialect::Oracle) | None => Box::new(GenericDialect {}),
+        }
+    }
+
+    fn kind(statement: &Statement) -> &'static str {
+        match statement {
+            Statement::Query(_) => "SELECT",
+            Statement::Insert(_) => "INSERT",
+            Statement::Update { .. } => "UPDATE",
+            Statement::Delete(_) => "DELETE",
+            Statement::Merge { .. } => "MERGE",
+            _ => "non-DML",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ColumnIntent;
+
+    fn intent(dialect: Option<DbDialect>) -> SpringIntent {
+        SpringIntent::new("Member", "TB_MEMBER", "com.company.project")
+            .with_column(ColumnIntent::new("MEMBER_ID", "회원ID").primary_key())
+            .with_column(ColumnIntent::new("MEMBER_NAME", "회원명"))
+            .with_column(ColumnIntent::new("EMAIL", "이메일"))
+            .with_dialect(dialect)
+    }
+
+    fn mapper(statements: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE mapper PUBLIC "-//mybatis.org//DTD Mapper 3.0//EN" "http://mybatis.org/dtd/mybatis-3-mapper.dtd">
+<mapper namespace="com.company.project.mapper.MemberMapper">
+    <sql id="columns">MEMBER_ID, MEMBER_NAME, EMAIL</sql>
+{}
+</mapper>
+"#,
+            statements
+        )
+    }
+
+    #[test]
+    fn test_dynamic_statements_pass() {
+        let xml = mapper(
+            r#"    <select id="selectList" resultType="MemberDTO">
+        SELECT <include refid="columns"/>, ROWNUM AS RN
+        FROM TB_MEMBER M
+        <where>
+            <if test="memberName != null">AND M.MEMBER_NAME LIKE '%' || #{memberName} || '%'</if>
+            <if test="ids != null">AND MEMBER_ID IN
+                <foreach collection="ids" item="id" open="(" separator="," close=")">#{id}</foreach>
+            </if>
+            <choose>
+                <when test="email != null">AND EMAIL = #{email}</when>
+                <otherwise>AND EMAIL IS NOT NULL</otherwise>
+            </choose>
+        </where>
+        ORDER BY RN
+    </select>
+    <insert id="insert">
+        <selectKey keyProperty="memberId" resultType="long" order="BEFORE">SELECT SEQ_MEMBER.NEXTVAL FROM DUAL</selectKey>
+        INSERT INTO TB_MEMBER (MEMBER_ID, MEMBER_NAME, CREATED_AT) VALUES (#{memberId}, #{memberName}, SYSDATE)
+    </insert>
+    <update id="update">
+        UPDATE TB_MEMBER
+        <set>
+            <if test="memberName != null">MEMBER_NAME = #{memberName},</if>
+            <if test="email != null">EMAIL = #{email},</if>
+        </set>
+        WHERE MEMBER_ID = #{memberId}
+    </update>
+    <delete id="delete">DELETE FROM TB_MEMBER WHERE MEMBER_ID = #{memberId}</delete>"#,
+        );
+        for dialect in [Some(DbDialect::Postgres), Some(DbDialect::Mysql), Some(DbDialect::Oracle), None] {
+            assert_eq!(MyBatisSqlChecker::check(&xml, &intent(dialect)), Vec::<String>::new(), "{:?}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let xml = mapper(
+            r#"    <select id="selectById">SELECT MEMBER_ID, FROM TB_MEMBER WHERE MEMBER_ID = #{memberId}</select>
+    <select id="selectSorted">SELECT MEMBER_ID FROM TB_MEMBER ORDER BY MEMBER_ID ${sortOrder} DESC</select>"#,
+        );
+        let warnings = MyBatisSqlChecker::check(&xml, &intent(Some(DbDialect::Postgres)));
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].starts_with("Warning: Mapper SQL 'selectById': syntax error:"), "{}", warnings[0]);
+        assert!(warnings[1].starts_with("Note: Mapper SQL 'selectSorted': syntax not checked"), "{}", warnings[1]);
+
+        // MySQL-only syntax is accepted in the MySQL dialect
+        let xml = mapper(r#"    <select id="page">SELECT MEMBER_ID FROM TB_MEMBER LIMIT #{offset}, #{size}</select>"#);
+        assert!(MyBatisSqlChecker::check(&xml, &intent(Some(DbDialect::Mysql))).is_empty());
+    }
+
+    #[test]
+    fn test_missing_where_and_from() {
+        let xml = mapper(
+            r#"    <delete id="deleteAll">DELETE FROM TB_MEMBER</delete>
+    <update id="updateName">
+        UPDATE TB_MEMBER SET MEMBER_NAME = #{memberName}
+        <where><if test="memberId != null">MEMBER_ID = #{memberId}</if></where>
+    </update>
+    <select id="now">SELECT CURRENT_TIMESTAMP</select>
+    <select id="remove">DELETE FROM TB_MEMBER WHERE MEMBER_ID = #{memberId}</select>"#,
+        );
+        let warnings = MyBatisSqlChecker::check(&xml, &intent(Some(DbDialect::Oracle)));
+        assert_eq!(
+            warnings,
+            vec![
+                "Warning: Mapper SQL 'deleteAll': DELETE without WHERE affects every row",
+                "Warning: Mapper SQL 'updateName': UPDATE WHERE clause only comes from <if> conditions; with none set it affects every row",
+                "Warning: Mapper SQL 'now': SELECT has no FROM clause (Oracle needs FROM DUAL)",
+                "Warning: Mapper SQL 'remove': <select> contains a DELETE statement",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_columns() {
+        let xml = mapper(
+            r#"    <select id="selectById">SELECT MEMBER_ID, MEMBER_NM FROM TB_MEMBER WHERE member_nm = #{name} AND PHONE = #{phone}</select>
+    <update id="update">UPDATE TB_MEMBER SET MEMBER_PW = #{pw} WHERE MEMBER_ID = #{memberId}</update>
+    <select id="selectWithDept">SELECT M.MEMBER_ID, D.DEPT_NAME FROM TB_MEMBER M JOIN TB_DEPT D ON D.DEPT_ID = M.DEPT_ID</select>"#,
+        );
+        let warnings = MyBatisSqlChecker::check(&xml, &intent(None));
+        assert_eq!(
+            warnings,
+            vec![
+                "Warning: Mapper SQL 'selectById': column 'MEMBER_NM' is not a column of TB_MEMBER",
+                "Warning: Mapper SQL 'selectById': column 'PHONE' is not a column of TB_MEMBER",
+                "Warning: Mapper SQL 'update': column 'MEMBER_PW' is not a column of TB_MEMBER",
+            ]
+        );
+    }
+}
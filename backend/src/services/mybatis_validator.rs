@@ -0,0 +1,446 @@
+//! MyBatis Mapper Validation
+//!
+//! Parses the mapper XML and cross-checks it against the generated Java:
+//! namespace vs. mapper interface, interface methods vs. statement ids,
+//! `parameterType`/`resultType`/`resultMap` types vs. generated classes, and
+//! `#{}` parameters vs. DTO fields.
+
+use regex::Regex;
+use roxmltree::{Document, Node, ParsingOptions};
+
+use crate::domain::SpringArtifacts;
+
+const STATEMENTS: &[&str] = &["select", "insert", "update", "delete"];
+
+/// MyBatis built-in type aliases (lowercase, `_int` etc. are matched without `_`)
+const TYPE_ALIASES: &[&str] = &[
+    "string", "byte", "char", "character", "long", "short", "int", "integer", "double", "float",
+    "boolean", "date", "decimal", "bigdecimal", "biginteger", "object", "map", "hashmap", "list",
+    "arraylist", "collection", "iterator", "resultset",
+];
+
+/// A class of the generated slice
+struct JavaClass {
+    package: String,
+    name: String,
+    fields: Vec<String>,
+}
+
+/// A method of the mapper interface
+struct MapperMethod {
+    name: String,
+    /// Parameter types (generics stripped) of parameters without `@Param`
+    plain_params: Vec<String>,
+    /// `@Param` names with their types
+    named_params: Vec<(String, String)>,
+    /// Mapped by `@Select`/`@Insert`/... instead of XML
+    annotated: bool,
+}
+
+/// Resolution of a type attribute
+enum TypeRef<'a> {
+    Builtin,
+    Generated(&'a JavaClass),
+    Unknown,
+}
+
+/// Semantic checks for MyBatis mapper XML
+pub struct MyBatisValidator;
+
+impl MyBatisValidator {
+    /// Check the mapper XML of a slice against its Java classes
+    pub fn check(artifacts: &SpringArtifacts) -> Vec<String> {
+        if artifacts.mapper_xml.trim().is_empty() {
+            return Vec::new();
+        }
+        // Mapper XML always carries the MyBatis DOCTYPE
+        let options = ParsingOptions { allow_dtd: true, ..ParsingOptions::default() };
+        let doc = match Document::parse_with_options(&artifacts.mapper_xml, options) {
+            Ok(doc) => doc,
+            Err(e) => return vec![format!("Warning: Mapper XML is not well-formed: {}", e)],
+        };
+        let root = doc.root_element();
+        if root.tag_name().name() != "mapper" {
+            return vec![format!("Warning: Mapper XML root is <{}>, expected <mapper>", root.tag_name().name())];
+        }
+
+        let mut sources = vec![
+            &artifacts.controller,
+            &artifacts.service_interface,
+            &artifacts.service_impl,
+            &artifacts.dto,
+            &artifacts.mapper_interface,
+        ];
+        sources.extend(artifacts.search_dto.as_ref());
+        let classes: Vec<JavaClass> = sources.into_iter().filter_map(|s| Self::java_class(s)).collect();
+        let methods = Self::mapper_methods(&artifacts.mapper_interface);
+
+        let mut warnings = Vec::new();
+        Self::check_namespace(&doc, root, &artifacts.mapper_interface, &mut warnings);
+        Self::check_statement_ids(&doc, root, &methods, &mut warnings);
+        Self::check_result_maps(&doc, root, &classes, &mut warnings);
+
+        for statement in root.children().filter(|n| STATEMENTS.contains(&n.tag_name().name())) {
+            let method = statement
+                .attribute("id")
+                .and_then(|id| methods.iter().find(|m| m.name == id));
+            Self::check_statement(&doc, statement, method, &classes, &mut warnings);
+        }
+
+        warnings
+    }
+
+    fn at(doc: &Document, node: Node, message: String) -> String {
+        let line = doc.text_pos_at(node.range().start).row;
+        format!("Warning: Mapper XML line {}: {}", line, message)
+    }
+
+    fn check_namespace(doc: &Document, root: Node, interface: &str, warnings: &mut Vec<String>) {
+        let Some(class) = Self::java_class(interface) else { return };
+        let expected = if class.package.is_empty() {
+            class.name.clone()
+        } else {
+            format!("{}.{}", class.package, class.name)
+        };
+        match root.attribute("namespace") {
+            Some(ns) if ns == expected => {}
+            Some(ns) => warnings.push(Self::at(
+                doc,
+                root,
+                format!("namespace '{}' does not match mapper interface '{}'", ns, expected),
+            )),
+            None => warnings.push(Self::at(doc, root, format!("<mapper> has no namespace (expected '{}')", expected))),
+        }
+    }
+
+    fn check_statement_ids(doc: &Document, root: Node, methods: &[MapperMethod], warnings: &mut Vec<String>) {
+        let statements: Vec<Node> = root
+            .children()
+            .filter(|n| STATEMENTS.contains(&n.tag_name().name()))
+            .collect();
+
+        for method in methods.iter().filter(|m| !m.annotated) {
+            if !statements.iter().any(|s| s.attribute("id") == Some(method.name.as_str())) {
+                warnings.push(Self::at(
+                    doc,
+                    root,
+                    format!("mapper method '{}' has no matching statement", method.name),
+                ));
+            }
+        }
+
+        let mut seen: Vec<&str> = Vec::new();
+        for statement in &statements {
+            let Some(id) = statement.attribute("id") else {
+                warnings.push(Self::at(doc, *statement, format!("<{}> has no id", statement.tag_name().name())));
+                continue;
+            };
+            if seen.contains(&id) {
+                warnings.push(Self::at(doc, *statement, format!("statement id '{}' is defined more than once", id)));
+            }
+            seen.push(id);
+            if !methods.is_empty() && !methods.iter().any(|m| m.name == id) {
+                let line = doc.text_pos_at(statement.range().start).row;
+                warnings.push(format!(
+                    "Note: Mapper XML line {}: statement '{}' has no mapper interface method",
+                    line, id
+                ));
+            }
+        }
+    }
+
+    fn check_result_maps(doc: &Document, root: Node, classes: &[JavaClass], warnings: &mut Vec<String>) {
+        for result_map in root.children().filter(|n| n.has_tag_name("resultMap")) {
+            let Some(ty) = result_map.attribute("type") else { continue };
+            match Self::resolve(ty, classes) {
+                TypeRef::Unknown => warnings.push(Self::at(
+                    doc,
+                    result_map,
+                    format!("resultMap type '{}' is not a generated class", ty),
+                )),
+                TypeRef::Generated(class) => {
+                    for mapping in result_map.descendants().filter(|n| n.has_tag_name("id") || n.has_tag_name("result")) {
+                        let Some(property) = mapping.attribute("property") else { continue };
+                        if !class.fields.iter().any(|f| f == property) {
+                            warnings.push(Self::at(
+                                doc,
+                                mapping,
+                                format!("property '{}' is not a field of {}", property, class.name),
+                            ));
+                        }
+                    }
+                }
+                TypeRef::Builtin => {}
+            }
+        }
+    }
+
+    fn check_statement(
+        doc: &Document,
+        statement: Node,
+        method: Option<&MapperMethod>,
+        classes: &[JavaClass],
+        warnings: &mut Vec<String>,
+    ) {
+        for attr in ["parameterType", "resultType"] {
+            let Some(ty) = statement.attribute(attr) else { continue };
+            if let TypeRef::Unknown = Self::resolve(ty, classes) {
+                warnings.push(Self::at(doc, statement, format!("{} '{}' is not a generated class", attr, ty)));
+            }
+        }
+        if let Some(result_map) = statement.attribute("resultMap") {
+            let root = doc.root_element();
+            let defined = root
+                .children()
+                .any(|n| n.has_tag_name("resultMap") && n.attribute("id") == Some(result_map));
+            if !defined && !result_map.contains('.') {
+                warnings.push(Self::at(doc, statement, format!("resultMap '{}' is not defined", result_map)));
+            }
+        }
+
+        // Names bound inside the statement itself
+        let bound: Vec<&str> = statement
+            .descendants()
+            .filter(|n| n.has_tag_name("foreach") || n.has_tag_name("bind"))
+            .flat_map(|n| [n.attribute("item"), n.attribute("index"), n.attribute("name")])
+            .flatten()
+            .collect();
+
+        let param_re = Regex::new(r"#\{\s*([\w.]+)").unwrap();
+        for text_node in statement.descendants().filter(|n| n.is_text()) {
+            for cap in param_re.captures_iter(text_node.text().unwrap_or_default()) {
+                let path = &cap[1];
+                let (head, tail) = path.split_once('.').map_or((path, None), |(h, t)| (h, Some(t)));
+                if head == "_parameter" || bound.contains(&head) {
+                    continue;
+                }
+                if let Some(problem) = Self::check_param(head, tail, statement, method, classes) {
+                    warnings.push(Self::at(doc, text_node, problem));
+                }
+            }
+        }
+    }
+
+    /// Problem with one `#{}` parameter, if its source type is known
+    fn check_param(
+        head: &str,
+        tail: Option<&str>,
+        statement: Node,
+        method: Option<&MapperMethod>,
+        classes: &[JavaClass],
+    ) -> Option<String> {
+        let has_field = |class: &JavaClass, field: &str| class.fields.iter().any(|f| f == field);
+        let id = statement.attribute("id").unwrap_or("?");
+
+        if let Some(method) = method.filter(|m| !m.named_params.is_empty()) {
+            let Some((_, ty)) = method.named_params.iter().find(|(name, _)| name == head) else {
+                return Some(format!("#{{{}}} in '{}' is not a @Param of the mapper method", head, id));
+            };
+            let field = tail?.split('.').next()?;
+            return match Self::resolve(ty, classes) {
+                TypeRef::Generated(class) if !has_field(class, field) => {
+                    Some(format!("#{{{}.{}}} in '{}': {} has no field '{}'", head, field, id, class.name, field))
+                }
+                _ => None,
+            };
+        }
+
+        let param_type = statement
+            .attribute("parameterType")
+            .map(str::to_string)
+            .or_else(|| method.filter(|m| m.plain_params.len() == 1).map(|m| m.plain_params[0].clone()))?;
+        match Self::resolve(&param_type, classes) {
+            TypeRef::Generated(class) if !class.fields.is_empty() && !has_field(class, head) => {
+                Some(format!("#{{{}}} in '{}' is not a field of {}", head, id, class.name))
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve<'a>(ty: &str, classes: &'a [JavaClass]) -> TypeRef<'a> {
+        let ty = ty.trim();
+        if ty.starts_with("java.") {
+            return TypeRef::Builtin;
+        }
+        if let Some((package, name)) = ty.rsplit_once('.') {
+            return classes
+                .iter()
+                .find(|c| c.name == name && c.package == package)
+                .map_or(TypeRef::Unknown, TypeRef::Generated);
+        }
+        if let Some(class) = classes.iter().find(|c| c.name == ty) {
+            return TypeRef::Generated(class);
+        }
+        let alias = ty.trim_start_matches('_').to_lowercase();
+        if TYPE_ALIASES.contains(&alias.as_str()) {
+            TypeRef::Builtin
+        } else {
+            TypeRef::Unknown
+        }
+    }
+
+    /// Package, first type name and private fields of a Java source
+    fn java_class(source: &str) -> Option<JavaClass> {
+        let package = Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").unwrap();
+        let decl = Regex::new(r"\b(?:class|interface|record)\s+(\w+)").unwrap();
+        let field = Regex::new(r"(?m)^\s*private\s+(?:final\s+)?[\w<>\[\],.?\s]+?\s+(\w+)\s*(?:=[^;]*)?;").unwrap();
+
+        let name = decl.captures(source)?[1].to_string();
+        Some(JavaClass {
+            package: package.captures(source).map(|c| c[1].to_string()).unwrap_or_default(),
+            name,
+            fields: field.captures_iter(source).map(|c| c[1].to_string()).collect(),
+        })
+    }
+
+    /// Abstract methods of the mapper interface
+    fn mapper_methods(source: &str) -> Vec<MapperMethod> {
+        let comments = Regex::new(r"(?s)/\*.*?\*/|//[^\n]*").unwrap();
+        let annotation = Regex::new(r#"^@(\w+)(?:\s*\((?:[^()"]|"[^"]*"|\([^()]*\))*\))?\s*"#).unwrap();
+        let param_annotation = Regex::new(r#"@Param\s*\(\s*(?:value\s*=\s*)?"(\w+)"\s*\)"#).unwrap();
+        let generics = Regex::new(r"<[^<>]*(?:<[^<>]*>[^<>]*)*>").unwrap();
+
+        let source = comments.replace_all(source, "");
+        let Some(start) = source.find("interface").and_then(|i| source[i..].find('{').map(|j| i + j + 1)) else {
+            return Vec::new();
+        };
+        let body = &source[start..source.rfind('}').unwrap_or(source.len())];
+
+        let mut methods = Vec::new();
+        for chunk in body.split(';') {
+            let mut rest = chunk.trim();
+            let mut annotated = false;
+            while let Some(cap) = annotation.captures(rest) {
+                annotated |= ["Select", "Insert", "Update", "Delete", "SelectProvider", "InsertProvider", "UpdateProvider", "DeleteProvider"]
+                    .contains(&&cap[1]);
+                rest = rest[cap[0].len()..].trim_start();
+            }
+            let Some(open) = rest.find('(') else { continue };
+            let Some(name) = rest[..open].split_whitespace().last() else { continue };
+            if rest.starts_with("default ") || rest.starts_with("static ") {
+                continue;
+            }
+            let params = rest[open + 1..rest.rfind(')').unwrap_or(rest.len())].to_string();
+            let params = generics.replace_all(&params, "");
+
+            let mut plain_params = Vec::new();
+            let mut named_params = Vec::new();
+            for param in params.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                let name = param_annotation.captures(param).map(|c| c[1].to_string());
+                let without = param_annotation.replace_all(param, "");
+                let tokens: Vec<&str> = without.split_whitespace().filter(|t| *t != "final").collect();
+                let ty = tokens.iter().rev().nth(1).copied().unwrap_or_default().to_string();
+                match name {
+                    Some(name) => named_params.push((name, ty)),
+                    None => plain_params.push(ty),
+                }
+            }
+
+            methods.push(MapperMethod {
+                name: name.to_string(),
+                plain_params,
+                named_params,
+                annotated,
+            });
+        }
+        methods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifacts() -> SpringArtifacts {
+        let mut artifacts = SpringArtifacts::new();
+        artifacts.dto = r#"package com.company.project.dto;
+
+public class MemberDTO {
+    private Long memberId;
+    private String memberName;
+}
+"#
+        .to_string();
+        artifacts.mapper_interface = r#"package com.company.project.mapper;
+
+import org.apache.ibatis.annotations.Mapper;
+import org.apache.ibatis.annotations.Param;
+
+@Mapper
+public interface MemberMapper {
+    // Find one member
+    MemberDTO selectById(Long memberId);
+    List<MemberDTO> selectList(@Param("keyword") String keyword, @Param("dto") MemberDTO dto);
+    int insert(MemberDTO dto);
+}
+"#
+        .to_string();
+        artifacts.mapper_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE mapper PUBLIC "-//mybatis.org//DTD Mapper 3.0//EN" "http://mybatis.org/dtd/mybatis-3-mapper.dtd">
+<mapper namespace="com.company.project.mapper.MemberMapper">
+    <resultMap id="memberMap" type="com.company.project.dto.MemberDTO">
+        <id property="memberId" column="MEMBER_ID"/>
+        <result property="memberName" column="MEMBER_NAME"/>
+    </resultMap>
+    <select id="selectById" parameterType="long" resultMap="memberMap">
+        SELECT * FROM TB_MEMBER WHERE MEMBER_ID = #{memberId}
+    </select>
+    <select id="selectList" resultMap="memberMap">
+        SELECT * FROM TB_MEMBER WHERE MEMBER_NAME LIKE #{keyword} AND MEMBER_ID = #{dto.memberId}
+    </select>
+    <insert id="insert" parameterType="MemberDTO">
+        INSERT INTO TB_MEMBER (MEMBER_ID, MEMBER_NAME) VALUES (#{memberId}, #{memberName, jdbcType=VARCHAR})
+    </insert>
+</mapper>
+"#
+        .to_string();
+        artifacts
+    }
+
+    #[test]
+    fn test_consistent_mapper() {
+        assert_eq!(MyBatisValidator::check(&artifacts()), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_namespace_and_missing_statement() {
+        let mut artifacts = artifacts();
+        artifacts.mapper_xml = artifacts
+            .mapper_xml
+            .replace("mapper.MemberMapper\"", "dao.MemberDao\"")
+            .replace("<insert id=\"insert\"", "<insert id=\"insertMember\"");
+        let warnings = MyBatisValidator::check(&artifacts);
+        assert!(warnings.contains(&"Warning: Mapper XML line 3: namespace 'com.company.project.dao.MemberDao' does not match mapper interface 'com.company.project.mapper.MemberMapper'".to_string()));
+        assert!(warnings.iter().any(|w| w.contains("mapper method 'insert' has no matching statement")));
+        assert!(warnings.iter().any(|w| w.starts_with("Note:") && w.contains("'insertMember'")));
+    }
+
+    #[test]
+    fn test_types_and_parameters() {
+        let mut artifacts = artifacts();
+        artifacts.mapper_xml = artifacts
+            .mapper_xml
+            .replace("#{memberName, jdbcType", "#{memberNm, jdbcType")
+            .replace("#{dto.memberId}", "#{dto.id}")
+            .replace("#{keyword}", "#{name}")
+            .replace("parameterType=\"long\"", "parameterType=\"com.company.project.vo.MemberVO\"")
+            .replace("resultMap=\"memberMap\">\n        SELECT * FROM TB_MEMBER WHERE MEMBER_ID", "resultMap=\"memberResult\">\n        SELECT * FROM TB_MEMBER WHERE MEMBER_ID");
+        let warnings = MyBatisValidator::check(&artifacts);
+        assert!(warnings.iter().any(|w| w.contains("resultMap 'memberResult' is not defined")));
+        assert!(warnings.iter().any(|w| w.contains("#{dto.id} in 'selectList': MemberDTO has no field 'id'")));
+        assert!(warnings.iter().any(|w| w.contains("#{name} in 'selectList' is not a @Param")));
+        assert!(warnings.iter().any(|w| w.contains("parameterType 'com.company.project.vo.MemberVO' is not a generated class")));
+        assert!(warnings.iter().any(|w| w.contains("#{memberNm} in 'insert' is not a field of MemberDTO")));
+        // Unknown parameterType: field checks are skipped
+        assert!(!warnings.iter().any(|w| w.contains("#{memberId} in 'selectById'")));
+    }
+
+    #[test]
+    fn test_malformed_xml() {
+        let mut artifacts = artifacts();
+        artifacts.mapper_xml = artifacts.mapper_xml.replace("</select>\n    <insert", "\n    <insert");
+        let warnings = MyBatisValidator::check(&artifacts);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Warning: Mapper XML is not well-formed"));
+    }
+}
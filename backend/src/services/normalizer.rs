@@ -1,9 +1,12 @@
 use crate::domain::{
-    ColumnIntent, DataType, DatasetIntent, GenerateInput, GridColumnIntent,
-    GridIntent, NaturalLanguageInput, QuerySampleInput, SchemaColumn, SchemaInput, ScreenType,
-    UiIntent, UiType, default_actions_for_screen_type,
+    ChartIntent, ChartType, ColumnArchetype, ColumnIntent, ColumnType, DashboardInput, DashboardIntent, DbDialect, DataType,
+    DatasetIntent, GenerateInput, GridColumnIntent, GridIntent, KpiIntent, NaturalLanguageInput, QuerySampleInput,
+    SchemaColumn, SchemaInput, ScreenType, SensitiveKind, UiIntent, UiType, default_actions_for_screen_type,
 };
+use crate::services::ScreenDsl;
 use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::collections::HashMap;
 
 /// Service for normalizing various input types to UiIntent DSL
 pub struct NormalizerService;
@@ -15,7 +18,54 @@ impl NormalizerService {
             GenerateInput::DbSchema(schema) => Self::normalize_schema(schema),
             GenerateInput::QuerySample(query) => Self::normalize_query(query),
             GenerateInput::NaturalLanguage(nl) => Self::normalize_natural_language(nl),
+            GenerateInput::Dashboard(dashboard) => Self::normalize_dashboard(dashboard),
+            GenerateInput::Intent(intent) => Ok(intent.clone()),
+            GenerateInput::Dsl(dsl) => ScreenDsl::parse(&dsl.yaml),
+            GenerateInput::SavedIntent(saved) => {
+                Err(anyhow!("Saved intent '{}' must be loaded before normalization", saved.name))
+            }
+        }
+    }
+
+    /// Normalize and match the columns to column archetypes
+    ///
+    /// Schema columns are matched by name and comment; columns of other
+    /// inputs by name and label. A column naming its archetype keeps it.
+    pub fn normalize_with_archetypes(input: &GenerateInput, archetypes: &[ColumnArchetype]) -> Result<UiIntent> {
+        let mut intent = Self::normalize(input)?;
+        if archetypes.is_empty() {
+            return Ok(intent);
+        }
+
+        let comments: HashMap<String, &str> = match input {
+            GenerateInput::DbSchema(schema) => schema
+                .columns
+                .iter()
+                .filter_map(|c| Some((c.name.to_uppercase(), c.comment.as_deref()?)))
+                .collect(),
+            _ => HashMap::new(),
+        };
+        let mut headers = HashMap::new();
+        for column in intent.datasets.iter_mut().flat_map(|ds| ds.columns.iter_mut()) {
+            let named = column.archetype.as_deref().and_then(|name| archetypes.iter().find(|a| a.name == name));
+            let comment = comments.get(&column.name.to_uppercase()).copied().unwrap_or(column.label.as_str());
+            let Some(archetype) = named.or_else(|| ColumnArchetype::find(archetypes, &column.name, Some(comment)))
+            else {
+                continue;
+            };
+            archetype.apply(column);
+            if archetype.label.is_some() {
+                headers.insert(column.name.clone(), column.label.clone());
+            }
+        }
+        // Grid headers follow the archetype's label
+        for grid_column in intent.grids.iter_mut().flat_map(|g| g.columns.iter_mut()) {
+            if let Some(label) = headers.get(&grid_column.name) {
+                grid_column.header = label.clone();
+            }
         }
+
+        Ok(intent)
     }
 
     /// Normalize database schema input to UiIntent
@@ -24,10 +74,11 @@ impl NormalizerService {
         let dataset_id = format!("ds_{}", input.table.to_lowercase());
 
         // Convert schema columns to column intents
+        let dialect = input.effective_dialect();
         let columns: Vec<ColumnIntent> = input
             .columns
             .iter()
-            .map(|c| Self::schema_column_to_intent(c, &input.primary_keys))
+            .map(|c| Self::schema_column_to_intent(c, &input.primary_keys, dialect))
             .collect();
 
         // Create grid columns from visible columns (exclude hidden PKs)
@@ -42,9 +93,12 @@ impl NormalizerService {
             .with_table(&input.table)
             .with_columns(columns);
 
-        // Build grid
-        let grid = GridIntent::new(format!("grid_{}", input.table.to_lowercase()), &dataset_id)
+        // Build grid (large tables must page on the server)
+        let mut grid = GridIntent::new(format!("grid_{}", input.table.to_lowercase()), &dataset_id)
             .with_columns(grid_columns);
+        if input.is_large() {
+            grid = grid.require_paging();
+        }
 
         // Build intent with default actions
         let actions = default_actions_for_screen_type(ScreenType::List);
@@ -63,14 +117,19 @@ impl NormalizerService {
     }
 
     /// Convert a schema column to column intent
-    fn schema_column_to_intent(col: &SchemaColumn, primary_keys: &[String]) -> ColumnIntent {
+    pub fn schema_column_to_intent(
+        col: &SchemaColumn,
+        primary_keys: &[String],
+        dialect: Option<DbDialect>,
+    ) -> ColumnIntent {
         let is_pk = col.pk || primary_keys.contains(&col.name);
         let label = Self::infer_label(&col.name, col.comment.as_deref());
-        let (ui_type, data_type) = Self::infer_types(&col.column_type, is_pk);
+        let (ui_type, data_type) = Self::infer_types(&col.column_type, is_pk, dialect);
 
         let mut intent = ColumnIntent::new(&col.name, label)
             .with_ui_type(ui_type)
-            .with_data_type(data_type);
+            .with_data_type(data_type)
+            .with_sensitivity(SensitiveKind::classify(&col.name, col.comment.as_deref()));
 
         if is_pk {
             intent = intent.primary_key();
@@ -83,31 +142,43 @@ impl NormalizerService {
             intent = intent.with_max_length(len);
         }
 
+        // Keep the scale of DECIMAL(p,s) for the display format
+        if data_type == DataType::Decimal {
+            if let Some(scale) = ColumnType::parse(&col.column_type).scale {
+                intent = intent.with_scale(scale);
+            }
+        }
+
         intent
     }
 
     /// Infer UI type and data type from database column type
-    fn infer_types(db_type: &str, is_pk: bool) -> (UiType, DataType) {
+    fn infer_types(db_type: &str, is_pk: bool, dialect: Option<DbDialect>) -> (UiType, DataType) {
         if is_pk {
             return (UiType::Hidden, DataType::Integer);
         }
 
-        let upper = db_type.to_uppercase();
+        let parsed = ColumnType::parse(db_type);
+        let upper = parsed.base.as_str();
 
-        // Check for specific types
-        if upper.starts_with("VARCHAR") || upper.starts_with("CHAR") || upper == "NVARCHAR" {
-            let len = Self::extract_varchar_length(db_type).unwrap_or(255);
+        // Check for specific types (VARCHAR2/NVARCHAR2/NCHAR are Oracle)
+        if upper.starts_with("VARCHAR") || upper.starts_with("NVARCHAR") || upper.starts_with("CHAR") || upper.starts_with("NCHAR") {
+            let len = parsed.length().unwrap_or(255);
             if len > 500 {
                 return (UiType::TextArea, DataType::String);
             }
             return (UiType::Input, DataType::String);
         }
 
-        if upper.starts_with("TEXT") || upper.starts_with("CLOB") || upper == "LONGTEXT" {
+        if upper.starts_with("TEXT") || upper.ends_with("CLOB") || upper == "LONGTEXT" || upper == "LONG" {
             return (UiType::TextArea, DataType::Text);
         }
 
+        // Oracle DATE carries a time of day
         if upper == "DATE" {
+            if dialect == Some(DbDialect::Oracle) {
+                return (UiType::DateTimePicker, DataType::DateTime);
+            }
             return (UiType::DatePicker, DataType::Date);
         }
 
@@ -119,15 +190,23 @@ impl NormalizerService {
             return (UiType::Checkbox, DataType::Boolean);
         }
 
+        // Oracle NUMBER(p) / NUMBER(p,0) are integers; bare NUMBER or NUMBER(p,s) are decimals
+        if parsed.is_number() {
+            if parsed.is_integral_number() {
+                return (UiType::Number, DataType::Integer);
+            }
+            return (UiType::Number, DataType::Decimal);
+        }
+
         if upper.starts_with("INT") || upper == "BIGINT" || upper == "SMALLINT" || upper == "TINYINT" {
             return (UiType::Number, DataType::Integer);
         }
 
-        if upper.starts_with("DECIMAL") || upper.starts_with("NUMERIC") || upper == "FLOAT" || upper == "DOUBLE" || upper == "REAL" {
+        if upper.starts_with("DECIMAL") || upper.starts_with("NUMERIC") || upper == "FLOAT" || upper == "DOUBLE" || upper == "REAL" || upper.starts_with("BINARY_") {
             return (UiType::Number, DataType::Decimal);
         }
 
-        if upper.starts_with("BLOB") || upper == "BINARY" || upper == "VARBINARY" {
+        if upper.starts_with("BLOB") || upper == "BINARY" || upper == "VARBINARY" || upper.ends_with("RAW") {
             return (UiType::File, DataType::Binary);
         }
 
@@ -137,15 +216,7 @@ impl NormalizerService {
 
     /// Extract length from VARCHAR(n) type
     fn extract_varchar_length(db_type: &str) -> Option<u32> {
-        let upper = db_type.to_uppercase();
-        if let Some(start) = upper.find('(') {
-            if let Some(end) = upper.find(')') {
-                if let Ok(len) = upper[start + 1..end].trim().parse() {
-                    return Some(len);
-                }
-            }
-        }
-        None
+        ColumnType::parse(db_type).length()
     }
 
     /// Infer display label from column name
@@ -220,11 +291,13 @@ impl NormalizerService {
                     let (ui_type, data_type) = c
                         .column_type
                         .as_ref()
-                        .map(|t| Self::infer_types(t, false))
+                        .map(|t| Self::infer_types(t, false, None))
                         .unwrap_or((UiType::Input, DataType::String));
+                    let sensitivity = SensitiveKind::classify(&c.name, c.label.as_deref());
                     ColumnIntent::new(&c.name, label)
                         .with_ui_type(ui_type)
                         .with_data_type(data_type)
+                        .with_sensitivity(sensitivity)
                 })
                 .collect()
         } else {
@@ -262,6 +335,153 @@ impl NormalizerService {
         Ok(intent)
     }
 
+    /// Normalize aggregate queries to a dashboard UiIntent: queries without
+    /// GROUP BY give KPI cards, grouped queries give charts
+    pub fn normalize_dashboard(input: &DashboardInput) -> Result<UiIntent> {
+        if input.queries.is_empty() {
+            return Err(anyhow!("Dashboard input needs at least one query"));
+        }
+
+        let mut datasets: Vec<DatasetIntent> = Vec::new();
+        let mut dashboard = DashboardIntent {
+            refresh_seconds: input.refresh_seconds,
+            ..Default::default()
+        };
+
+        for (n, sample) in input.queries.iter().enumerate() {
+            let n = n + 1;
+            let query = sample.query.split_whitespace().collect::<Vec<_>>().join(" ");
+            let table_name = Self::extract_table_from_query(&query)?;
+            let items = Self::select_items(&query)
+                .ok_or_else(|| anyhow!("Dashboard query {} must name each column (alias the aggregates, no SELECT *)", n))?;
+
+            let mut dataset_id = format!("ds_{}", table_name.to_lowercase());
+            if datasets.iter().any(|ds| ds.id == dataset_id) {
+                dataset_id = format!("{}_{}", dataset_id, n);
+            }
+
+            let label_of = |name: &str| {
+                sample
+                    .result_columns
+                    .iter()
+                    .flatten()
+                    .find(|c| c.name.eq_ignore_ascii_case(name))
+                    .and_then(|c| c.label.clone())
+                    .unwrap_or_else(|| Self::infer_label(name, None))
+            };
+
+            let (values, categories): (Vec<_>, Vec<_>) =
+                items.iter().partition(|(expr, _)| Self::is_aggregate(expr));
+            if values.is_empty() {
+                return Err(anyhow!(
+                    "Dashboard query {} has no aggregate column (SUM, COUNT, AVG, MIN, MAX)",
+                    n
+                ));
+            }
+
+            let columns = items
+                .iter()
+                .map(|(expr, name)| {
+                    let column = ColumnIntent::new(name, label_of(name)).readonly();
+                    if !Self::is_aggregate(expr) {
+                        column
+                    } else if expr.to_uppercase().trim_start().starts_with("COUNT") {
+                        column.with_ui_type(UiType::Number).with_data_type(DataType::Integer)
+                    } else {
+                        column.with_ui_type(UiType::Number).with_data_type(DataType::Decimal)
+                    }
+                })
+                .collect();
+            datasets.push(DatasetIntent::new(&dataset_id).with_table(&table_name).with_columns(columns));
+
+            if query.to_uppercase().contains(" GROUP BY ") {
+                let (_, category) = categories
+                    .first()
+                    .ok_or_else(|| anyhow!("Dashboard query {} has no GROUP BY column in its SELECT", n))?;
+                dashboard.charts.push(ChartIntent {
+                    id: format!("chart_{}", dataset_id.trim_start_matches("ds_")),
+                    title: sample.description.clone().unwrap_or_else(|| label_of(&table_name)),
+                    chart_type: ChartType::for_category(category),
+                    dataset_id: dataset_id.clone(),
+                    category_column: category.clone(),
+                    value_columns: values.iter().map(|(_, name)| name.clone()).collect(),
+                });
+            } else {
+                for (_, name) in values {
+                    let mut kpi = KpiIntent::new(&dataset_id, name, label_of(name));
+                    if dashboard.kpis.iter().any(|k| k.id == kpi.id) {
+                        kpi.id = format!("{}_{}", kpi.id, n);
+                    }
+                    dashboard.kpis.push(kpi);
+                }
+            }
+        }
+
+        let screen_name = input.screen_name.clone().unwrap_or_else(|| {
+            let table = datasets[0].table_name.as_deref().unwrap_or("screen");
+            format!("{}_dashboard", table.to_lowercase())
+        });
+
+        let mut intent = UiIntent::new(screen_name, ScreenType::Dashboard).with_dashboard(dashboard);
+        for dataset in datasets {
+            intent = intent.with_dataset(dataset);
+        }
+        if let Some(ref desc) = input.description {
+            intent = intent.with_notes(desc.clone());
+        }
+        for action in default_actions_for_screen_type(ScreenType::Dashboard) {
+            intent = intent.with_action(action);
+        }
+
+        Ok(intent)
+    }
+
+    /// `(expression, column name)` of each SELECT item, splitting on top-level
+    /// commas only; `None` for `SELECT *` or an unparsable query
+    fn select_items(query: &str) -> Option<Vec<(String, String)>> {
+        let upper = query.to_uppercase();
+        let select_pos = upper.find("SELECT")?;
+        let from_pos = upper.find(" FROM ")?;
+        let clause = query[select_pos + 6..from_pos].trim();
+        if clause == "*" || clause.is_empty() {
+            return None;
+        }
+
+        let mut parts = Vec::new();
+        let (mut depth, mut start) = (0i32, 0);
+        for (i, c) in clause.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(&clause[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&clause[start..]);
+
+        let alias = Regex::new(r#"(?i)^(.*?)(?:\s+AS)?\s+["'`]?(\w+)["'`]?$"#).unwrap();
+        parts
+            .into_iter()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|part| match alias.captures(part) {
+                Some(cap) if !cap[1].trim().is_empty() => Some((cap[1].trim().to_string(), cap[2].to_string())),
+                _ if part.contains('(') => None,
+                _ => {
+                    let name = part.rsplit('.').next().unwrap_or(part);
+                    Some((part.to_string(), name.trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string()))
+                }
+            })
+            .collect()
+    }
+
+    fn is_aggregate(expr: &str) -> bool {
+        Regex::new(r"(?i)\b(?:SUM|COUNT|AVG|MIN|MAX)\s*\(").unwrap().is_match(expr)
+    }
+
     /// Extract main table name from SELECT query
     fn extract_table_from_query(query: &str) -> Result<String> {
         let upper = query.to_uppercase();
@@ -325,7 +545,10 @@ impl NormalizerService {
                 };
 
                 let label = Self::infer_label(name, None);
-                Some(ColumnIntent::new(name, label))
+                Some(
+                    ColumnIntent::new(name, label)
+                        .with_sensitivity(SensitiveKind::classify(name, None)),
+                )
             })
             .collect();
 
@@ -347,6 +570,8 @@ impl NormalizerService {
                 "detail" => ScreenType::Detail,
                 "popup" => ScreenType::Popup,
                 "list_with_popup" | "listwithpopup" => ScreenType::ListWithPopup,
+                "dashboard" => ScreenType::Dashboard,
+                "wizard" => ScreenType::Wizard,
                 _ => ScreenType::List,
             })
             .unwrap_or(ScreenType::List);
@@ -483,26 +708,108 @@ mod tests {
         assert_eq!(intent.grids.len(), 1);
     }
 
+    #[test]
+    fn test_normalize_schema_classifies_sensitive_columns() {
+        let schema = SchemaInput::new("member")
+            .with_column(SchemaColumn::new("id", "INTEGER").primary_key())
+            .with_column(SchemaColumn::new("jumin_no", "CHAR(13)"))
+            .with_column(SchemaColumn::new("contact", "VARCHAR(20)").with_comment("연락처"))
+            .with_column(SchemaColumn::new("email", "VARCHAR(255)"));
+
+        let intent = NormalizerService::normalize_schema(&schema).unwrap();
+        let columns = &intent.datasets[0].columns;
+
+        assert_eq!(columns[1].sensitivity, Some(SensitiveKind::ResidentNumber));
+        assert_eq!(columns[2].sensitivity, Some(SensitiveKind::Phone));
+        assert_eq!(columns[3].sensitivity, None);
+        assert_eq!(intent.sensitive_columns().len(), 2);
+    }
+
+    #[test]
+    fn test_normalize_with_archetypes_matches_name_and_comment() {
+        let archetypes = vec![
+            ColumnArchetype {
+                label: Some("사업자등록번호".to_string()),
+                max_length: Some(12),
+                ..ColumnArchetype::new("사업자등록번호").with_comment_keywords(&["사업자"])
+            },
+            ColumnArchetype {
+                ui_type: Some(UiType::Number),
+                data_type: Some(DataType::Decimal),
+                ..ColumnArchetype::new("금액").with_name_patterns(&["*_AMT"])
+            },
+        ];
+        let schema = SchemaInput::new("vendor")
+            .with_column(SchemaColumn::new("vendor_id", "INTEGER").primary_key())
+            .with_column(SchemaColumn::new("reg_no", "VARCHAR(20)").with_comment("사업자 번호"))
+            .with_column(SchemaColumn::new("credit_amt", "VARCHAR(20)"));
+
+        let input = GenerateInput::DbSchema(schema);
+        let intent = NormalizerService::normalize_with_archetypes(&input, &archetypes).unwrap();
+        let columns = &intent.datasets[0].columns;
+
+        assert_eq!(columns[0].archetype, None);
+        assert_eq!(columns[1].archetype.as_deref(), Some("사업자등록번호"));
+        assert_eq!((columns[1].label.as_str(), columns[1].max_length), ("사업자등록번호", Some(12)));
+        assert_eq!(intent.grids[0].columns[0].header, "사업자등록번호");
+        assert_eq!(columns[2].archetype.as_deref(), Some("금액"));
+        assert_eq!((columns[2].ui_type, columns[2].data_type), (UiType::Number, DataType::Decimal));
+    }
+
+    #[test]
+    fn test_normalize_schema_oracle_types() {
+        let schema = SchemaInput::new("TB_ORDER")
+            .with_column(SchemaColumn::new("ORDER_NO", "VARCHAR2(20 CHAR)").with_comment("주문번호"))
+            .with_column(SchemaColumn::new("QTY", "NUMBER(5)"))
+            .with_column(SchemaColumn::new("AMOUNT", "NUMBER(12,2)"))
+            .with_column(SchemaColumn::new("ORDER_DATE", "DATE"))
+            .with_column(SchemaColumn::new("MEMO", "NCLOB"));
+
+        assert_eq!(schema.effective_dialect(), Some(DbDialect::Oracle));
+        let intent = NormalizerService::normalize_schema(&schema).unwrap();
+        let columns = &intent.datasets[0].columns;
+
+        assert_eq!(columns[0].data_type, DataType::String);
+        assert_eq!(columns[0].max_length, Some(20));
+        assert_eq!(columns[1].data_type, DataType::Integer);
+        assert_eq!((columns[2].data_type, columns[2].scale), (DataType::Decimal, Some(2)));
+        assert_eq!(columns[3].ui_type, UiType::DateTimePicker);
+        assert_eq!(columns[4].data_type, DataType::Text);
+    }
+
+    #[test]
+    fn test_normalize_schema_large_table_requires_paging() {
+        let schema = SchemaInput::new("orders")
+            .with_column(SchemaColumn::new("order_no", "VARCHAR(20)"))
+            .with_row_count(5_000_000);
+        let intent = NormalizerService::normalize_schema(&schema).unwrap();
+        assert!(intent.grids[0].requires_paging);
+
+        let small = SchemaInput::new("codes").with_row_count(300);
+        let intent = NormalizerService::normalize_schema(&small).unwrap();
+        assert!(!intent.grids[0].requires_paging);
+    }
+
     #[test]
     fn test_infer_types() {
         assert_eq!(
-            NormalizerService::infer_types("VARCHAR(100)", false),
+            NormalizerService::infer_types("VARCHAR(100)", false, None),
             (UiType::Input, DataType::String)
         );
         assert_eq!(
-            NormalizerService::infer_types("TEXT", false),
+            NormalizerService::infer_types("TEXT", false, None),
             (UiType::TextArea, DataType::Text)
         );
         assert_eq!(
-            NormalizerService::infer_types("DATE", false),
+            NormalizerService::infer_types("DATE", false, None),
             (UiType::DatePicker, DataType::Date)
         );
         assert_eq!(
-            NormalizerService::infer_types("BOOLEAN", false),
+            NormalizerService::infer_types("BOOLEAN", false, None),
             (UiType::Checkbox, DataType::Boolean)
         );
         assert_eq!(
-            NormalizerService::infer_types("INTEGER", false),
+            NormalizerService::infer_types("INTEGER", false, None),
             (UiType::Number, DataType::Integer)
         );
     }
@@ -533,4 +840,46 @@ mod tests {
             "users"
         );
     }
+
+    #[test]
+    fn test_normalize_dashboard() {
+        let input = DashboardInput {
+            screen_name: None,
+            description: Some("영업 현황".to_string()),
+            queries: vec![
+                QuerySampleInput::new("SELECT SUM(ORDER_AMT) AS TOTAL_AMT, COUNT(*) AS ORDER_CNT FROM orders"),
+                QuerySampleInput::new(
+                    "SELECT TO_CHAR(ORDER_DATE, 'YYYY-MM') AS ORDER_MONTH, SUM(ORDER_AMT) AS TOTAL_AMT\n\
+                     FROM orders\nGROUP BY TO_CHAR(ORDER_DATE, 'YYYY-MM')",
+                )
+                .with_description("월별 매출"),
+            ],
+            refresh_seconds: Some(60),
+        };
+        let intent = NormalizerService::normalize_dashboard(&input).unwrap();
+
+        assert_eq!(intent.screen_name, "orders_dashboard");
+        assert_eq!(intent.screen_type, ScreenType::Dashboard);
+        assert_eq!(intent.datasets.len(), 2);
+        assert_eq!(intent.datasets[1].id, "ds_orders_2");
+        assert_eq!(intent.datasets[0].columns[1].data_type, DataType::Integer);
+
+        let dashboard = intent.dashboard.unwrap();
+        assert_eq!(dashboard.kpis.len(), 2);
+        assert_eq!(dashboard.kpis[0].id, "pnl_kpi_total_amt");
+        assert_eq!(dashboard.charts.len(), 1);
+        let chart = &dashboard.charts[0];
+        assert_eq!(chart.id, "chart_orders_2");
+        assert_eq!(chart.title, "월별 매출");
+        assert_eq!(chart.chart_type, ChartType::Line);
+        assert_eq!(chart.category_column, "ORDER_MONTH");
+        assert_eq!(chart.value_columns, vec!["TOTAL_AMT"]);
+        assert_eq!(intent.actions[0].function_name, "fn_refresh");
+
+        let plain = DashboardInput {
+            queries: vec![QuerySampleInput::new("SELECT MEMBER_ID, NAME FROM members")],
+            ..input
+        };
+        assert!(NormalizerService::normalize_dashboard(&plain).is_err());
+    }
 }
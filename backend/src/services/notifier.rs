@@ -0,0 +1,309 @@
+//! Chat Notifications
+//!
+//! Posts async job results to a Slack or Mattermost channel through its
+//! incoming webhook: completed jobs, failed jobs and failure spikes (many
+//! failed jobs within a few minutes), each switchable in the admin panel.
+//! Automatic prompt template rollbacks and canary decisions are always posted.
+//! Messages name the screen/entity, status and warning count and link to the
+//! admin generation log; LLM provider and model are never included.
+//!
+//! Posting runs in the background and never affects the job.
+
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+
+use crate::models::_entities::generation_logs::{self, Column, Entity};
+use crate::models::notification_settings;
+use crate::services::generation_history::entity_name;
+use crate::services::{DuplicateScreenDetector, TenantScope};
+use crate::workers::generation::GenerateJobRequest;
+
+/// Name the messages are posted under
+const BOT_NAME: &str = "xFrame5 Code Generator";
+
+/// When the last failure spike was posted (one post per spike window)
+static LAST_SPIKE_ALERT: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+/// What a message reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatEvent {
+    Completed { log_id: i32, entity: String, product: String, warnings: usize },
+    Failed { log_id: i32, entity: String, product: String, error: String },
+    FailureSpike { failed: u64, window_minutes: i32 },
+    TemplateRolledBack { template: String, version: i32, reason: String },
+    CanaryDecided { subject: String, promoted: bool, reason: String },
+}
+
+/// Posts job events to the configured chat channel
+pub struct Notifier;
+
+impl Notifier {
+    /// Report a finished (completed or failed) async job, in the background
+    pub fn job_finished(db: &DatabaseConnection, job: &generation_logs::Model) {
+        let (db, job) = (db.clone(), job.clone());
+        tokio::spawn(async move {
+            if let Err(e) = Self::report_job(&db, &job).await {
+                tracing::warn!("Could not post job {} to chat: {}", job.job_id.as_deref().unwrap_or("-"), e);
+            }
+        });
+    }
+
+    /// Post a failure spike when the failed jobs of the window reach the threshold
+    pub async fn check_failure_spike(db: &DatabaseConnection) -> Result<()> {
+        let settings = notification_settings::Model::current(db).await;
+        if !settings.notify_failure_spike || settings.webhook_url.is_none() {
+            return Ok(());
+        }
+        let now = Utc::now();
+        let window = Duration::minutes(i64::from(settings.spike_window_minutes.max(1)));
+        let failed = Entity::find()
+            .filter(Column::JobId.is_not_null())
+            .filter(Column::Status.eq("failed"))
+            .filter(Column::CompletedAt.gte(now - window))
+            .count(db)
+            .await?;
+        if failed < settings.spike_threshold.max(1) as u64 {
+            return Ok(());
+        }
+        {
+            let mut last = LAST_SPIKE_ALERT.lock().unwrap_or_else(|e| e.into_inner());
+            if last.is_some_and(|at| now - at < window) {
+                return Ok(());
+            }
+            *last = Some(now);
+        }
+        let event = ChatEvent::FailureSpike {
+            failed,
+            window_minutes: settings.spike_window_minutes,
+        };
+        Self::post(&settings, &Self::message(&settings, &event)).await
+    }
+
+    /// Report an automatic template rollback, in the background
+    pub fn template_rolled_back(db: &DatabaseConnection, template: &str, version: i32, reason: &str) {
+        let db = db.clone();
+        let event = ChatEvent::TemplateRolledBack {
+            template: template.to_string(),
+            version,
+            reason: reason.to_string(),
+        };
+        Self::post_event(db, event);
+    }
+
+    /// Report an automatic canary promotion or rollback, in the background
+    pub fn canary_decided(db: &DatabaseConnection, subject: &str, promoted: bool, reason: &str) {
+        let event = ChatEvent::CanaryDecided {
+            subject: subject.to_string(),
+            promoted,
+            reason: reason.to_string(),
+        };
+        Self::post_event(db.clone(), event);
+    }
+
+    /// Post a rollout event when a webhook is configured
+    fn post_event(db: DatabaseConnection, event: ChatEvent) {
+        tokio::spawn(async move {
+            let settings = notification_settings::Model::current(&db).await;
+            if settings.webhook_url.is_none() {
+                return;
+            }
+            if let Err(e) = Self::post(&settings, &Self::message(&settings, &event)).await {
+                tracing::warn!("Could not post rollout event to chat: {}", e);
+            }
+        });
+    }
+
+    /// Post a test message with `settings` (admin "Send test")
+    pub async fn send_test(settings: &notification_settings::Model) -> Result<()> {
+        Self::post(settings, "Test message: code generation notifications are set up for this channel.").await
+    }
+
+    async fn report_job(db: &DatabaseConnection, job: &generation_logs::Model) -> Result<()> {
+        let settings = notification_settings::Model::current(db).await;
+        if settings.webhook_url.is_none() {
+            return Ok(());
+        }
+        let failed = job.status == "failed";
+        if failed && settings.notify_failure_spike {
+            Self::check_failure_spike(db).await?;
+        }
+        if (failed && !settings.notify_failed) || (!failed && !settings.notify_completed) {
+            return Ok(());
+        }
+
+        let entity = Self::entity(db, job).await.unwrap_or_else(|| "unknown".to_string());
+        let event = if failed {
+            ChatEvent::Failed {
+                log_id: job.id,
+                entity,
+                product: job.product.clone(),
+                error: job.error_message.clone().unwrap_or_default(),
+            }
+        } else {
+            ChatEvent::Completed {
+                log_id: job.id,
+                entity,
+                product: job.product.clone(),
+                warnings: job
+                    .warnings
+                    .as_deref()
+                    .and_then(|w| serde_json::from_str::<Vec<String>>(w).ok())
+                    .map_or(0, |w| w.len()),
+            }
+        };
+        Self::post(&settings, &Self::message(&settings, &event)).await
+    }
+
+    /// Screen or entity of a job (queued jobs only log their intent once generated)
+    async fn entity(db: &DatabaseConnection, job: &generation_logs::Model) -> Option<String> {
+        if let Some(name) = entity_name(&job.ui_intent) {
+            return Some(name);
+        }
+        let request: GenerateJobRequest = serde_json::from_str(job.request_payload.as_deref()?).ok()?;
+        let tenant = TenantScope::new(job.company.as_deref());
+        DuplicateScreenDetector::key_for(db, &request.product, &request.input, &request.context, &tenant)
+            .await
+            .map(|key| key.name)
+    }
+
+    /// Message text in the provider's markup (Slack mrkdwn or Mattermost Markdown)
+    pub fn message(settings: &notification_settings::Model, event: &ChatEvent) -> String {
+        let markdown = settings.provider == "mattermost";
+        let bold = |text: &str| if markdown { format!("**{}**", text) } else { format!("*{}*", text) };
+        let log_link = |log_id: i32| {
+            let label = format!("log #{}", log_id);
+            match settings.admin_base_url.as_deref().map(|u| u.trim().trim_end_matches('/')).filter(|u| !u.is_empty()) {
+                Some(base) if markdown => format!("[{}]({}/admin/generation-logs/{})", label, base, log_id),
+                Some(base) => format!("<{}/admin/generation-logs/{}|{}>", base, log_id, label),
+                None => label,
+            }
+        };
+        match event {
+            ChatEvent::Completed { log_id, entity, product, warnings } => format!(
+                ":white_check_mark: {}: {} ({}) - {} warning(s) - {}",
+                bold("Generation completed"),
+                entity,
+                product,
+                warnings,
+                log_link(*log_id)
+            ),
+            ChatEvent::Failed { log_id, entity, product, error } => format!(
+                ":x: {}: {} ({}) - {} - {}",
+                bold("Generation failed"),
+                entity,
+                product,
+                error.lines().next().unwrap_or("no error message"),
+                log_link(*log_id)
+            ),
+            ChatEvent::FailureSpike { failed, window_minutes } => format!(
+                ":rotating_light: {}: {} jobs failed in the last {} minutes",
+                bold("Failure spike"),
+                failed,
+                window_minutes
+            ),
+            ChatEvent::TemplateRolledBack { template, version, reason } => format!(
+                ":leftwards_arrow_with_hook: {}: {} v{} - {}",
+                bold("Template rolled back"),
+                template,
+                version,
+                reason
+            ),
+            ChatEvent::CanaryDecided { subject, promoted: true, reason } => format!(
+                ":white_check_mark: {}: {} - {}",
+                bold("Canary promoted"),
+                subject,
+                reason
+            ),
+            ChatEvent::CanaryDecided { subject, promoted: false, reason } => format!(
+                ":leftwards_arrow_with_hook: {}: {} - {}",
+                bold("Canary rolled back"),
+                subject,
+                reason
+            ),
+        }
+    }
+
+    async fn post(settings: &notification_settings::Model, text: &str) -> Result<()> {
+        let Some(url) = settings.webhook_url.as_deref() else {
+            bail!("No webhook URL configured");
+        };
+        let mut payload = json!({ "text": text, "username": BOT_NAME });
+        if let Some(channel) = settings.channel.as_deref().filter(|c| !c.is_empty()) {
+            payload["channel"] = json!(channel);
+        }
+        let response = reqwest::Client::new()
+            .post(url)
+            .json(&payload)
+            .send()
+            .await
+            .context("Chat webhook unreachable")?;
+        if !response.status().is_success() {
+            bail!("Chat webhook answered {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(provider: &str) -> notification_settings::Model {
+        let now = Utc::now().into();
+        notification_settings::Model {
+            created_at: now,
+            updated_at: now,
+            id: 1,
+            provider: provider.to_string(),
+            webhook_url: Some("https://hooks.slack.com/services/T/B/X".to_string()),
+            channel: None,
+            admin_base_url: Some("https://codegen.acme.co.kr/".to_string()),
+            notify_completed: true,
+            notify_failed: true,
+            notify_failure_spike: true,
+            spike_threshold: 5,
+            spike_window_minutes: 15,
+        }
+    }
+
+    #[test]
+    fn test_job_messages_link_admin_log() {
+        let completed = ChatEvent::Completed {
+            log_id: 42,
+            entity: "member_list".to_string(),
+            product: "xframe5-ui".to_string(),
+            warnings: 3,
+        };
+        assert_eq!(
+            Notifier::message(&settings("slack"), &completed),
+            ":white_check_mark: *Generation completed*: member_list (xframe5-ui) - 3 warning(s) - \
+             <https://codegen.acme.co.kr/admin/generation-logs/42|log #42>"
+        );
+
+        let failed = ChatEvent::Failed {
+            log_id: 43,
+            entity: "Member".to_string(),
+            product: "spring-backend".to_string(),
+            error: "Generation timed out\nretry failed".to_string(),
+        };
+        assert_eq!(
+            Notifier::message(&settings("mattermost"), &failed),
+            ":x: **Generation failed**: Member (spring-backend) - Generation timed out - \
+             [log #43](https://codegen.acme.co.kr/admin/generation-logs/43)"
+        );
+    }
+
+    #[test]
+    fn test_spike_message() {
+        let mut settings = settings("slack");
+        settings.admin_base_url = None;
+        assert_eq!(
+            Notifier::message(&settings, &ChatEvent::FailureSpike { failed: 7, window_minutes: 15 }),
+            ":rotating_light: *Failure spike*: 7 jobs failed in the last 15 minutes"
+        );
+    }
+}
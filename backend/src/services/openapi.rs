@@ -0,0 +1,302 @@
+//! OpenAPI Document
+//!
+//! Builds the OpenAPI 3.0 document served at `/api/openapi.json` from the
+//! registered routes: every public JSON route (`/agent/...`, `/api/...`) gets
+//! an operation, so new endpoints show up without extra work. Documented
+//! operations add a summary, query parameters and request/response examples;
+//! their schemas are derived from the examples, which are round-tripped
+//! through the typed request/response structs so field names and defaults
+//! always match what the server accepts.
+//!
+//! LLM configuration and prompt template APIs are left out (admin panel only).
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Route prefixes of the public JSON APIs
+const PUBLIC_PREFIXES: &[&str] = &["/agent/", "/api/"];
+
+/// Routes never listed (LLM internals, the documentation itself)
+const HIDDEN_PREFIXES: &[&str] = &["/api/llm_configs", "/api/prompt_templates", "/api/openapi.json", "/api/docs"];
+
+/// Routes that need the user's JWT (`Authorization: Bearer ...`)
+const AUTHENTICATED_PREFIXES: &[&str] = &[
+    "/api/my/",
+    "/api/auth/current",
+    "/api/company_rules",
+    "/api/knowledge_bases",
+    "/api/llm_configs",
+    "/api/prompt_templates",
+    "/api/project_output_settings",
+    "/api/pipeline_profiles",
+    "/api/naming_profiles",
+    "/api/saved_intents",
+];
+
+/// Documentation of one route
+#[derive(Debug, Clone)]
+pub struct ApiOperation {
+    /// Lowercase HTTP method (`get`, `post`, ...)
+    pub method: &'static str,
+    /// Route as registered (`/agent/jobs/{job_id}`)
+    pub path: &'static str,
+    pub tag: &'static str,
+    pub summary: &'static str,
+    /// Query parameters: name and description
+    pub query: Vec<(&'static str, &'static str)>,
+    /// Example request body
+    pub request: Option<Value>,
+    /// Example `200` response body
+    pub response: Option<Value>,
+}
+
+impl ApiOperation {
+    pub fn new(method: &'static str, path: &'static str, tag: &'static str, summary: &'static str) -> Self {
+        Self {
+            method,
+            path,
+            tag,
+            summary,
+            query: Vec::new(),
+            request: None,
+            response: None,
+        }
+    }
+
+    pub fn query(mut self, name: &'static str, description: &'static str) -> Self {
+        self.query.push((name, description));
+        self
+    }
+
+    pub fn request(mut self, example: Value) -> Self {
+        self.request = Some(example);
+        self
+    }
+
+    pub fn response(mut self, example: Value) -> Self {
+        self.response = Some(example);
+        self
+    }
+}
+
+pub struct OpenApi;
+
+impl OpenApi {
+    /// OpenAPI document for `routes` (path and methods of every registered route)
+    pub fn document(routes: &[(String, Vec<String>)], operations: &[ApiOperation]) -> Value {
+        let mut paths = Map::new();
+        for (path, methods) in routes {
+            let path = Self::normalize(path);
+            if !PUBLIC_PREFIXES.iter().any(|p| path.starts_with(p)) || HIDDEN_PREFIXES.iter().any(|p| path.starts_with(p)) {
+                continue;
+            }
+            let item = paths.entry(path.clone()).or_insert_with(|| json!({}));
+            for method in methods {
+                let method = method.to_lowercase();
+                let documented = operations.iter().find(|op| op.method == method && Self::normalize(op.path) == path);
+                item[method.as_str()] = Self::operation(&method, &path, documented);
+            }
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "Enterprise Code Generator API",
+                "version": env!("CARGO_PKG_VERSION"),
+                "description": "Code generation, review and Q&A endpoints used by the IDE plugins.",
+            },
+            "paths": paths,
+            "components": {
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+                },
+            },
+        })
+    }
+
+    /// `example` after a round trip through `T`, so it carries the real field
+    /// names and every defaulted field (an example `T` rejects is an error)
+    pub fn example<T: Serialize + DeserializeOwned>(example: Value) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(serde_json::from_value::<T>(example)?)
+    }
+
+    /// Schema of a JSON example (types only; examples show optional fields too)
+    pub fn schema_of(example: &Value) -> Value {
+        match example {
+            Value::Null => json!({ "nullable": true }),
+            Value::Bool(_) => json!({ "type": "boolean" }),
+            Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+            Value::Number(_) => json!({ "type": "integer" }),
+            Value::String(s) if chrono::DateTime::parse_from_rfc3339(s).is_ok() => {
+                json!({ "type": "string", "format": "date-time" })
+            }
+            Value::String(_) => json!({ "type": "string" }),
+            Value::Array(items) => json!({
+                "type": "array",
+                "items": items.first().map_or_else(|| json!({}), Self::schema_of),
+            }),
+            Value::Object(fields) => json!({
+                "type": "object",
+                "properties": fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), Self::schema_of(value)))
+                    .collect::<Map<_, _>>(),
+            }),
+        }
+    }
+
+    fn operation(method: &str, path: &str, documented: Option<&ApiOperation>) -> Value {
+        let mut parameters: Vec<Value> = Self::path_params(path)
+            .into_iter()
+            .map(|name| {
+                let kind = if name == "id" || (name.ends_with("_id") && name != "job_id") { "integer" } else { "string" };
+                json!({ "name": name, "in": "path", "required": true, "schema": { "type": kind } })
+            })
+            .collect();
+
+        let (tag, summary) = match documented {
+            Some(op) => (op.tag.to_string(), op.summary.to_string()),
+            None => Self::crud_summary(method, path),
+        };
+        let mut operation = json!({
+            "tags": [tag],
+            "summary": summary,
+            "operationId": Self::operation_id(method, path),
+        });
+
+        let ok = match documented.and_then(|op| op.response.as_ref()) {
+            Some(example) => json!({
+                "description": "Success",
+                "content": { "application/json": { "schema": Self::schema_of(example), "example": example } },
+            }),
+            None => json!({ "description": "Success" }),
+        };
+        operation["responses"] = json!({
+            "200": ok,
+            "400": { "description": "Invalid request" },
+            "404": { "description": "Not found" },
+        });
+
+        if let Some(op) = documented {
+            parameters.extend(op.query.iter().map(|(name, description)| {
+                json!({ "name": name, "in": "query", "required": false, "description": description, "schema": { "type": "string" } })
+            }));
+            if let Some(example) = &op.request {
+                operation["requestBody"] = json!({
+                    "required": true,
+                    "content": { "application/json": { "schema": Self::schema_of(example), "example": example } },
+                });
+            }
+        } else if matches!(method, "post" | "put" | "patch") {
+            operation["requestBody"] = json!({
+                "content": { "application/json": { "schema": { "type": "object" } } },
+            });
+        }
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+        if AUTHENTICATED_PREFIXES.iter().any(|p| path.starts_with(p)) {
+            operation["security"] = json!([{ "bearerAuth": [] }]);
+            operation["responses"]["401"] = json!({ "description": "Missing or invalid token" });
+        }
+        operation
+    }
+
+    /// Tag and summary of an undocumented route (the resource APIs are CRUD)
+    fn crud_summary(method: &str, path: &str) -> (String, String) {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let resource = segments.get(1).copied().unwrap_or("api").to_string();
+        let by_id = segments.last().is_some_and(|s| *s == "{id}");
+        let summary = match (method, by_id, segments.len()) {
+            ("get", false, 2) => format!("List {}", resource),
+            ("post", false, 2) => format!("Create {}", resource),
+            ("get", true, _) => format!("Get one of {}", resource),
+            ("put" | "patch", true, _) => format!("Update one of {}", resource),
+            ("delete", true, _) => format!("Delete one of {}", resource),
+            _ => format!("{} {}", method.to_uppercase(), path),
+        };
+        (resource, summary)
+    }
+
+    fn path_params(path: &str) -> Vec<String> {
+        path.split('/')
+            .filter_map(|s| s.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn operation_id(method: &str, path: &str) -> String {
+        let name: String = path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("{}{}", method, name.trim_end_matches('_'))
+    }
+
+    /// Leading slash, no trailing slash (`api/company_rules/` → `/api/company_rules`)
+    fn normalize(path: &str) -> String {
+        let trimmed = path.trim_matches('/');
+        format!("/{}", trimmed.replace("//", "/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routes() -> Vec<(String, Vec<String>)> {
+        [
+            ("/agent/generate", vec!["POST"]),
+            ("/agent/jobs/{job_id}", vec!["GET", "DELETE"]),
+            ("/api/company_rules/", vec!["GET", "POST"]),
+            ("/api/company_rules/{id}", vec!["GET", "PUT"]),
+            ("/api/llm_configs/", vec!["GET"]),
+            ("/api/my/generations/{id}/download", vec!["GET"]),
+            ("/admin/dashboard", vec!["GET"]),
+        ]
+        .into_iter()
+        .map(|(path, methods)| (path.to_string(), methods.into_iter().map(str::to_string).collect()))
+        .collect()
+    }
+
+    #[test]
+    fn test_document_lists_public_routes() {
+        let generate = ApiOperation::new("post", "/agent/generate", "generate", "Generate a screen")
+            .query("mode", "`async` to queue the request")
+            .request(json!({ "product": "xframe5-ui", "priority": 3 }))
+            .response(json!({ "status": "success", "warnings": ["w"] }));
+        let doc = OpenApi::document(&routes(), &[generate]);
+        let paths = doc["paths"].as_object().unwrap();
+
+        assert_eq!(paths.len(), 5);
+        assert!(!paths.contains_key("/api/llm_configs"));
+        assert!(!paths.contains_key("/admin/dashboard"));
+
+        let op = &paths["/agent/generate"]["post"];
+        assert_eq!(op["summary"], "Generate a screen");
+        assert_eq!(op["parameters"][0]["name"], "mode");
+        assert_eq!(op["requestBody"]["content"]["application/json"]["schema"]["properties"]["priority"]["type"], "integer");
+        assert_eq!(op["responses"]["200"]["content"]["application/json"]["schema"]["properties"]["warnings"]["items"]["type"], "string");
+
+        assert_eq!(paths["/agent/jobs/{job_id}"]["delete"]["parameters"][0]["schema"]["type"], "string");
+        assert_eq!(paths["/api/company_rules"]["post"]["summary"], "Create company_rules");
+        assert_eq!(paths["/api/company_rules/{id}"]["put"]["parameters"][0]["schema"]["type"], "integer");
+        assert_eq!(paths["/api/company_rules/{id}"]["put"]["security"][0], json!({ "bearerAuth": [] }));
+        assert_eq!(paths["/api/my/generations/{id}/download"]["get"]["security"][0], json!({ "bearerAuth": [] }));
+    }
+
+    #[test]
+    fn test_schema_of_example() {
+        let schema = OpenApi::schema_of(&json!({
+            "at": "2026-10-16T09:00:00Z",
+            "score": 0.5,
+            "error": null,
+            "files": [{ "name": "a.xml" }],
+        }));
+        assert_eq!(schema["properties"]["at"]["format"], "date-time");
+        assert_eq!(schema["properties"]["score"]["type"], "number");
+        assert_eq!(schema["properties"]["error"]["nullable"], true);
+        assert_eq!(schema["properties"]["files"]["items"]["properties"]["name"]["type"], "string");
+    }
+}
@@ -0,0 +1,693 @@
+//! Duplicate Code and Pattern Drift
+//!
+//! Compares the JavaScript functions of a project's screens for copy-paste
+//! code: functions repeated verbatim across screens (to move into a shared
+//! script), copies that diverged in small ways (the same `fn_search` with one
+//! condition changed), and functions that drifted from the pattern the
+//! templates generate.
+//!
+//! Functions are compared by shingles of 4 consecutive tokens (comments and
+//! whitespace dropped, string and number literals blanked) through an
+//! inverted index, so a project of a few hundred screens is compared without
+//! checking every pair. The reference pattern of a function name is its
+//! variant closest to all others among the screens generated with the newest
+//! template version; uploaded files are only a reference when no generated
+//! screen has the function.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use anyhow::Result;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::Serialize;
+
+use crate::models::_entities::generation_logs::{Column, Entity};
+use crate::services::{ArtifactPackager, PackagedFile, TenantScope};
+
+/// Tokens per shingle
+const SHINGLE_SIZE: usize = 4;
+
+/// Functions shorter than this (stubs, one-line setters) are not compared
+const MIN_TOKENS: usize = 20;
+
+/// Similarity from which two functions in different screens are copies
+const DUPLICATE_THRESHOLD: f64 = 0.8;
+
+/// Similarity to the reference below which a function has drifted
+const DRIFT_THRESHOLD: f64 = 0.5;
+
+/// Screens a function name must appear in to have a reference pattern
+const MIN_DRIFT_FILES: usize = 3;
+
+/// Differing lines listed per duplicate group
+const MAX_DIFFERENCES: usize = 10;
+
+/// Most recent generations of a project compared
+const MAX_LOGS: u64 = 500;
+
+/// Log statuses of generations that produced a screen
+const PRODUCED_STATUSES: [&str; 3] = ["success", "partial_success", "completed"];
+
+/// JavaScript function of a screen
+#[derive(Debug, Clone)]
+pub struct CodeUnit {
+    pub file: String,
+    pub function: String,
+    /// Line of the definition
+    pub line: u32,
+    /// Generation the file comes from (None for uploaded files)
+    pub generation_id: Option<i32>,
+    pub template_version: Option<i32>,
+    /// Source lines of the function
+    source: Vec<String>,
+    tokens: Vec<String>,
+}
+
+impl CodeUnit {
+    /// Functions defined in a JS file (`this.fn_x = function`, `function fn_x`
+    /// and `var fn_x = function`)
+    pub fn extract(file: &str, js: &str) -> Vec<Self> {
+        let tokens = tokenize(js);
+        let mut units = Vec::new();
+        let mut idx = 0;
+
+        while idx < tokens.len() {
+            let text = |i: usize| tokens.get(i).map(|t| t.text.as_str());
+            let name = match (text(idx), text(idx + 1), text(idx + 2), text(idx + 3), text(idx + 4)) {
+                (Some("this"), Some("."), Some(name), Some("="), Some("function")) => Some((name, idx + 5)),
+                (Some("function"), Some(name), Some("("), _, _) if is_identifier(name) => Some((name, idx + 2)),
+                (Some("var" | "let" | "const"), Some(name), Some("="), Some("function"), _) => Some((name, idx + 4)),
+                _ => None,
+            };
+            let Some((name, after)) = name else {
+                idx += 1;
+                continue;
+            };
+            let Some(open) = (after..tokens.len()).find(|&i| tokens[i].text == "{") else {
+                break;
+            };
+
+            let mut depth = 0;
+            let mut close = None;
+            for (i, token) in tokens.iter().enumerate().skip(open) {
+                match token.text.as_str() {
+                    "{" => depth += 1,
+                    "}" => {
+                        depth -= 1;
+                        if depth == 0 {
+                            close = Some(i);
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let Some(close) = close else {
+                break;
+            };
+
+            let first_line = tokens[idx].line;
+            let last_line = tokens[close].line;
+            units.push(Self {
+                file: file.to_string(),
+                function: name.to_string(),
+                line: first_line,
+                generation_id: None,
+                template_version: None,
+                source: js
+                    .lines()
+                    .skip(first_line as usize - 1)
+                    .take((last_line - first_line) as usize + 1)
+                    .map(str::to_string)
+                    .collect(),
+                tokens: tokens[open..=close].iter().map(|t| t.normalized()).collect(),
+            });
+            // Nested functions are part of their outer function
+            idx = close + 1;
+        }
+        units
+    }
+
+    /// Mark the unit as coming from a generation
+    pub fn generated(mut self, generation_id: i32, template_version: i32) -> Self {
+        self.generation_id = Some(generation_id);
+        self.template_version = Some(template_version);
+        self
+    }
+
+    fn shingles(&self) -> HashSet<u64> {
+        self.tokens
+            .windows(SHINGLE_SIZE.min(self.tokens.len()))
+            .map(|window| {
+                let mut hasher = DefaultHasher::new();
+                window.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect()
+    }
+
+    fn function_ref(&self, similarity: f64) -> FunctionRef {
+        FunctionRef {
+            file: self.file.clone(),
+            function: self.function.clone(),
+            line: self.line,
+            generation_id: self.generation_id,
+            similarity: (similarity * 100.0).round() / 100.0,
+        }
+    }
+}
+
+struct Token {
+    text: String,
+    line: u32,
+}
+
+impl Token {
+    /// Literals are blanked so copies differing in a message or code still match
+    fn normalized(&self) -> String {
+        match self.text.chars().next() {
+            Some('"' | '\'' | '`') => "\"\"".to_string(),
+            Some(c) if c.is_ascii_digit() => "0".to_string(),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+}
+
+/// Tokens of JavaScript source, without comments and whitespace
+fn tokenize(js: &str) -> Vec<Token> {
+    let chars: Vec<char> = js.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let start_line = line;
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            '"' | '\'' | '`' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    match chars[i] {
+                        '\\' => i += 1,
+                        '\n' => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let continues = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$' || (ch == '.' && c.is_ascii_digit());
+                while i < chars.len() && continues(chars[i]) {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+        tokens.push(Token {
+            text: chars[start..i.min(chars.len())].iter().collect(),
+            line: start_line,
+        });
+    }
+    tokens
+}
+
+/// Inverted index of function shingles
+#[derive(Default)]
+pub struct SimilarityIndex {
+    units: Vec<CodeUnit>,
+    shingles: Vec<HashSet<u64>>,
+    postings: HashMap<u64, Vec<usize>>,
+}
+
+impl SimilarityIndex {
+    /// Index a function (stubs are skipped)
+    pub fn add(&mut self, unit: CodeUnit) {
+        if unit.tokens.len() < MIN_TOKENS {
+            return;
+        }
+        let id = self.units.len();
+        let shingles = unit.shingles();
+        for shingle in &shingles {
+            self.postings.entry(*shingle).or_default().push(id);
+        }
+        self.shingles.push(shingles);
+        self.units.push(unit);
+    }
+
+    pub fn units(&self) -> &[CodeUnit] {
+        &self.units
+    }
+
+    /// Jaccard similarity of the shingles of two indexed functions
+    pub fn similarity(&self, a: usize, b: usize) -> f64 {
+        if self.units[a].tokens == self.units[b].tokens {
+            return 1.0;
+        }
+        let shared = self.shingles[a].intersection(&self.shingles[b]).count();
+        let union = self.shingles[a].len() + self.shingles[b].len() - shared;
+        if union == 0 {
+            0.0
+        } else {
+            shared as f64 / union as f64
+        }
+    }
+
+    /// Pairs of functions at least `min` similar, found through shared shingles
+    pub fn similar_pairs(&self, min: f64) -> Vec<(usize, usize, f64)> {
+        let mut pairs = Vec::new();
+        for (a, shingles) in self.shingles.iter().enumerate() {
+            let mut shared: HashMap<usize, usize> = HashMap::new();
+            for shingle in shingles {
+                for &b in self.postings[shingle].iter().filter(|&&b| b > a) {
+                    *shared.entry(b).or_default() += 1;
+                }
+            }
+            for (b, count) in shared {
+                // Jaccard is at most the shared count over the larger set
+                if (count as f64) < min * shingles.len().max(self.shingles[b].len()) as f64 {
+                    continue;
+                }
+                let similarity = self.similarity(a, b);
+                if similarity >= min {
+                    pairs.push((a, b, similarity));
+                }
+            }
+        }
+        pairs.sort_by_key(|&(a, b, _)| (a, b));
+        pairs
+    }
+
+    /// Member most similar to all others (first on ties)
+    fn medoid(&self, members: &[usize], candidates: &[usize]) -> usize {
+        let mut best = (candidates[0], f64::MIN);
+        for &candidate in candidates {
+            let total: f64 = members.iter().map(|&m| self.similarity(candidate, m)).sum();
+            if total > best.1 {
+                best = (candidate, total);
+            }
+        }
+        best.0
+    }
+}
+
+/// Function in a finding
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionRef {
+    pub file: String,
+    pub function: String,
+    pub line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_id: Option<i32>,
+    /// Similarity to the group's reference (0.0-1.0)
+    pub similarity: f64,
+}
+
+/// How the copies of a duplicate group relate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Same code in every screen
+    Identical,
+    /// Copies that were changed separately
+    Diverged,
+}
+
+/// Copies of a function across screens
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
+    /// Reference copy first
+    pub functions: Vec<FunctionRef>,
+    /// Lines of diverged copies the reference does not have (`file:line: code`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<String>,
+    pub suggestion: String,
+}
+
+/// Functions that drifted from the reference pattern of their name
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternDrift {
+    pub function: String,
+    pub reference: FunctionRef,
+    pub drifted: Vec<FunctionRef>,
+    pub suggestion: String,
+}
+
+/// Duplicates and drift of a project's screens
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PatternReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// JS files compared
+    pub files: usize,
+    /// Functions compared (stubs excluded)
+    pub functions: usize,
+    pub duplicates: Vec<DuplicateGroup>,
+    pub drift: Vec<PatternDrift>,
+}
+
+pub struct PatternDriftService;
+
+impl PatternDriftService {
+    /// Compare the screens generated for `project` and any uploaded JS files
+    pub async fn analyze_project(
+        db: &DatabaseConnection,
+        project: &str,
+        tenant: &TenantScope,
+        uploaded: &[PackagedFile],
+    ) -> Result<PatternReport> {
+        let logs = Entity::find()
+            .filter(tenant.owned(Column::Company))
+            .filter(Column::Project.eq(project))
+            .filter(Column::Product.eq("xframe5-ui"))
+            .filter(Column::Status.is_in(PRODUCED_STATUSES))
+            .filter(Column::Artifacts.is_not_null())
+            .order_by_desc(Column::CreatedAt)
+            .limit(MAX_LOGS)
+            .all(db)
+            .await?;
+
+        // Only the latest generation of each file counts
+        let mut seen = HashSet::new();
+        let mut units = Vec::new();
+        for log in &logs {
+            let Some(artifacts) = log.artifacts.as_deref() else {
+                continue;
+            };
+            let Ok(files) = ArtifactPackager::files_for(&log.product, artifacts) else {
+                continue;
+            };
+            for file in files.iter().filter(|f| f.name.ends_with(".js")) {
+                if seen.insert(file.name.clone()) {
+                    units.extend(
+                        CodeUnit::extract(&file.name, &file.content)
+                            .into_iter()
+                            .map(|unit| unit.generated(log.id, log.template_version)),
+                    );
+                }
+            }
+        }
+        units.extend(Self::uploaded_units(uploaded));
+
+        let mut report = Self::analyze(units);
+        report.project = Some(project.to_string());
+        report.files = seen.len() + uploaded.iter().filter(|f| f.name.ends_with(".js")).count();
+        Ok(report)
+    }
+
+    /// Compare uploaded screen files only
+    pub fn analyze_files(files: &[PackagedFile]) -> PatternReport {
+        let mut report = Self::analyze(Self::uploaded_units(files));
+        report.files = files.iter().filter(|f| f.name.ends_with(".js")).count();
+        report
+    }
+
+    fn uploaded_units(files: &[PackagedFile]) -> Vec<CodeUnit> {
+        files
+            .iter()
+            .filter(|f| f.name.ends_with(".js"))
+            .flat_map(|f| CodeUnit::extract(&f.name, &f.content))
+            .collect()
+    }
+
+    /// Duplicate groups and drift of a set of functions
+    pub fn analyze(units: Vec<CodeUnit>) -> PatternReport {
+        let mut index = SimilarityIndex::default();
+        for unit in units {
+            index.add(unit);
+        }
+        let files: HashSet<&str> = index.units().iter().map(|u| u.file.as_str()).collect();
+
+        PatternReport {
+            project: None,
+            files: files.len(),
+            functions: index.units().len(),
+            duplicates: Self::duplicates(&index),
+            drift: Self::drift(&index),
+        }
+    }
+
+    /// Groups of copies in different files, joined transitively
+    fn duplicates(index: &SimilarityIndex) -> Vec<DuplicateGroup> {
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        let units = index.units();
+        let mut parent: Vec<usize> = (0..units.len()).collect();
+        for (a, b, _) in index.similar_pairs(DUPLICATE_THRESHOLD) {
+            if units[a].file != units[b].file {
+                let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+                parent[ra.max(rb)] = ra.min(rb);
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for i in 0..units.len() {
+            let r = root(&mut parent, i);
+            groups.entry(r).or_default().push(i);
+        }
+
+        groups
+            .into_values()
+            .filter(|members| members.len() > 1)
+            .map(|members| {
+                let reference = index.medoid(&members, &members);
+                let mut ordered = vec![reference];
+                ordered.extend(members.iter().copied().filter(|&m| m != reference));
+
+                let functions: Vec<FunctionRef> = ordered
+                    .iter()
+                    .map(|&m| units[m].function_ref(index.similarity(reference, m)))
+                    .collect();
+                let differences = Self::differences(&units[reference], ordered[1..].iter().map(|&m| &units[m]));
+                let identical =
+                    differences.is_empty() && ordered.iter().all(|&m| units[m].tokens == units[reference].tokens);
+                let names = Self::names(ordered.iter().map(|&m| &units[m]));
+
+                let suggestion = if identical {
+                    format!(
+                        "{} is identical in {} screens; move it to a shared script and call it from each screen",
+                        names,
+                        functions.len()
+                    )
+                } else {
+                    let lowest = functions.iter().map(|f| f.similarity).fold(1.0, f64::min);
+                    format!(
+                        "{} copies of {} were changed separately (down to {:.0}% similar); align them with {} in {} and move the common part to a shared script",
+                        functions.len(),
+                        names,
+                        lowest * 100.0,
+                        units[reference].function,
+                        units[reference].file
+                    )
+                };
+                DuplicateGroup {
+                    kind: if identical { DuplicateKind::Identical } else { DuplicateKind::Diverged },
+                    functions,
+                    differences,
+                    suggestion,
+                }
+            })
+            .collect()
+    }
+
+    /// Functions far from the reference of their name
+    fn drift(index: &SimilarityIndex) -> Vec<PatternDrift> {
+        let units = index.units();
+        let mut by_name: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, unit) in units.iter().enumerate() {
+            by_name.entry(unit.function.as_str()).or_default().push(i);
+        }
+
+        let mut drift = Vec::new();
+        for (name, members) in by_name {
+            let files: HashSet<&str> = members.iter().map(|&m| units[m].file.as_str()).collect();
+            if files.len() < MIN_DRIFT_FILES {
+                continue;
+            }
+
+            // Generated with the newest template first, uploaded files as fallback
+            let newest = members.iter().filter_map(|&m| units[m].template_version).max();
+            let candidates: Vec<usize> = match newest {
+                Some(version) => members.iter().copied().filter(|&m| units[m].template_version == Some(version)).collect(),
+                None => members.clone(),
+            };
+            let reference = index.medoid(&members, &candidates);
+
+            let drifted: Vec<FunctionRef> = members
+                .iter()
+                .filter(|&&m| m != reference)
+                .map(|&m| (m, index.similarity(reference, m)))
+                .filter(|&(_, similarity)| similarity < DRIFT_THRESHOLD)
+                .map(|(m, similarity)| units[m].function_ref(similarity))
+                .collect();
+            if drifted.is_empty() {
+                continue;
+            }
+
+            let reference = &units[reference];
+            drift.push(PatternDrift {
+                function: name.to_string(),
+                suggestion: format!(
+                    "{} of {} screens implement {} differently from {}; regenerate them or bring them back to the common pattern",
+                    drifted.len(),
+                    files.len(),
+                    name,
+                    reference.file
+                ),
+                reference: reference.function_ref(1.0),
+                drifted,
+            });
+        }
+        drift
+    }
+
+    /// Function names of a group (`fn_search`, or `fn_search / fn_query` when renamed)
+    fn names<'a>(units: impl Iterator<Item = &'a CodeUnit>) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        for unit in units {
+            if !names.contains(&unit.function.as_str()) {
+                names.push(&unit.function);
+            }
+        }
+        names.join(" / ")
+    }
+
+    /// Lines of the copies the reference does not have
+    fn differences<'a>(reference: &CodeUnit, copies: impl Iterator<Item = &'a CodeUnit>) -> Vec<String> {
+        let known: HashSet<&str> = reference.source.iter().map(|l| l.trim()).collect();
+        copies
+            .flat_map(|copy| {
+                copy.source
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| !line.trim().is_empty() && !known.contains(line.trim()))
+                    .map(move |(offset, line)| format!("{}:{}: {}", copy.file, copy.line as usize + offset, line.trim()))
+            })
+            .take(MAX_DIFFERENCES)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH: &str = r#"
+this.fn_search = function() {
+    // Search members
+    var name = this.edt_name.getValue();
+    if (name == "") {
+        alert("이름을 입력하세요");
+        return;
+    }
+    this.ds_member.clear();
+    this.ds_member.setParam("NAME", name);
+    this.ds_member.load("/member/list", 1);
+};
+"#;
+
+    #[test]
+    fn test_extract_functions() {
+        let js = format!("{}\nfunction fn_close() {{ screen.close(); }}\n", SEARCH);
+        let units = CodeUnit::extract("member_list.js", &js);
+
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].function, "fn_search");
+        assert_eq!(units[0].line, 2);
+        assert_eq!(units[0].source.len(), 11);
+        assert!(!units[0].tokens.iter().any(|t| t.contains("Search")));
+        assert_eq!(units[1].function, "fn_close");
+    }
+
+    #[test]
+    fn test_identical_and_diverged_copies() {
+        let diverged = SEARCH.replace("name == \"\"", "name == null").replace("이름을", "성명을");
+        let mut units = CodeUnit::extract("member_list.js", SEARCH);
+        units.extend(CodeUnit::extract("member_popup.js", SEARCH));
+        units.extend(CodeUnit::extract("member_edit.js", &diverged));
+
+        let report = PatternDriftService::analyze(units);
+        let group = &report.duplicates[0];
+
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(group.kind, DuplicateKind::Diverged);
+        assert_eq!(group.functions.len(), 3);
+        assert_eq!(
+            group.differences,
+            vec!["member_edit.js:5: if (name == null) {", "member_edit.js:6: alert(\"성명을 입력하세요\");"]
+        );
+
+        let identical = PatternDriftService::analyze(
+            ["a.js", "b.js"].iter().flat_map(|f| CodeUnit::extract(f, SEARCH)).collect(),
+        );
+        assert_eq!(identical.duplicates[0].kind, DuplicateKind::Identical);
+    }
+
+    #[test]
+    fn test_drift_from_newest_template() {
+        let rewritten = r#"
+this.fn_search = function() {
+    var params = { NAME: this.edt_name.getValue(), PAGE: this.currentPage };
+    http.post("/member/search", params, function(result) {
+        this.grid_member.setData(result.rows);
+        this.lbl_count.setValue(result.total);
+    });
+};
+"#;
+        let units: Vec<CodeUnit> = [("a.js", SEARCH, 3), ("b.js", SEARCH, 3), ("c.js", SEARCH, 2)]
+            .iter()
+            .enumerate()
+            .flat_map(|(id, (file, js, version))| {
+                CodeUnit::extract(file, js).into_iter().map(move |u| u.generated(id as i32 + 1, *version))
+            })
+            .chain(CodeUnit::extract("d.js", rewritten))
+            .collect();
+
+        let report = PatternDriftService::analyze(units);
+        let drift = &report.drift[0];
+
+        assert_eq!(drift.function, "fn_search");
+        assert_eq!(drift.reference.file, "a.js");
+        assert_eq!(drift.drifted.len(), 1);
+        assert_eq!(drift.drifted[0].file, "d.js");
+    }
+}
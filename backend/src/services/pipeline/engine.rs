@@ -1,31 +1,135 @@
 //! Pipeline Engine - Central coordinator for post-processing passes
 
-use super::{ExecutionMode, GenerationContext, GenerationResult, Pass, PassResult};
+use super::passes::{ApiDenylistFilter, ChecklistValidator, ErrorHandlingValidator, UxBehaviorPass};
+use super::{ExecutionMode, GenerationContext, GenerationResult, Pass, PassResult, PipelineProfile, ScreenSection};
 use crate::domain::UiIntent;
 use anyhow::{anyhow, Result};
 
+/// Rule-set specific passes and profile of a generation (deny-list,
+/// checklist, UX behaviors, error handling); built once and reused by every
+/// pipeline run of the generation
+#[derive(Clone)]
+pub struct PipelineRules {
+    denylist: ApiDenylistFilter,
+    checklist: ChecklistValidator,
+    ux: UxBehaviorPass,
+    errors: ErrorHandlingValidator,
+    profile: PipelineProfile,
+}
+
+impl PipelineRules {
+    /// Built-in rules, default profile
+    pub fn new() -> Self {
+        Self {
+            denylist: ApiDenylistFilter::new(),
+            checklist: ChecklistValidator::new(),
+            ux: UxBehaviorPass::new(),
+            errors: ErrorHandlingValidator::new(),
+            profile: PipelineProfile::default(),
+        }
+    }
+
+    pub fn with_denylist(mut self, denylist: ApiDenylistFilter) -> Self {
+        self.denylist = denylist;
+        self
+    }
+
+    pub fn with_checklist(mut self, checklist: ChecklistValidator) -> Self {
+        self.checklist = checklist;
+        self
+    }
+
+    pub fn with_ux(mut self, ux: UxBehaviorPass) -> Self {
+        self.ux = ux;
+        self
+    }
+
+    pub fn with_errors(mut self, errors: ErrorHandlingValidator) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    pub fn with_profile(mut self, profile: PipelineProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+impl Default for PipelineRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Post-processing pipeline that executes passes in fixed order
 pub struct PostProcessingPipeline {
     passes: Vec<Box<dyn Pass>>,
+    profile: PipelineProfile,
 }
 
 impl PostProcessingPipeline {
     /// Create a new pipeline with all passes in correct order
     pub fn new() -> Self {
+        Self::with_denylist(super::passes::ApiDenylistFilter::new())
+    }
+
+    /// Create a pipeline using the given deny-list filter (e.g. from company rules)
+    pub fn with_denylist(denylist: ApiDenylistFilter) -> Self {
+        Self::with_rules(PipelineRules::new().with_denylist(denylist))
+    }
+
+    /// Create a pipeline using a deny-list, the checklist for the screen type,
+    /// the company's UX behaviors and error-handling conventions and the
+    /// overrides of a pipeline profile
+    pub fn with_rules(rules: PipelineRules) -> Self {
         use super::passes::*;
 
+        let PipelineRules { denylist, checklist, ux, errors, profile } = rules;
+
         Self {
             passes: vec![
                 Box::new(OutputParser::new()),
                 Box::new(Canonicalizer::new()),
+                Box::new(IdentifierNormalizer::new()),
                 Box::new(SymbolLinker::new()),
+                Box::new(ux),
                 Box::new(ApiAllowlistFilter::new()),
+                Box::new(denylist),
                 Box::new(GraphValidator::new()),
+                Box::new(PagingValidator::new()),
+                Box::new(EditableGridValidator::new()),
+                Box::new(ChartBindingValidator::new()),
+                Box::new(WizardStepValidator::new()),
+                Box::new(TabPanelValidator::new()),
+                Box::new(PrintValidator::new()),
+                Box::new(SearchStateValidator::new()),
+                Box::new(errors),
+                Box::new(FormatValidator::new()),
+                Box::new(LabelLanguageValidator::new()),
+                Box::new(ArchetypeValidator::new()),
+                Box::new(AddressFieldValidator::new()),
                 Box::new(MinimalismPass::new()),
+                Box::new(SensitiveDataPass::new()),
+                Box::new(checklist),
+                Box::new(RuntimeSmokeTest::from_env()),
+                Box::new(WasmPluginPass::loaded()),
+                Box::new(XmlFormatter::new()),
             ],
+            profile,
         }
     }
 
+    /// Apply per-pass overrides from a pipeline profile
+    pub fn with_profile(mut self, profile: PipelineProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Names of the passes in execution order
+    pub fn pass_names(&self) -> Vec<&'static str> {
+        self.passes.iter().map(|pass| pass.name()).collect()
+    }
+
     /// Run the complete pipeline on raw LLM output
     ///
     /// # Arguments
@@ -45,6 +149,27 @@ impl PostProcessingPipeline {
         pipeline.execute(raw_output, intent, mode)
     }
 
+    /// Run the pipeline with a rule-set specific deny-list
+    pub fn run_with_denylist(
+        raw_output: String,
+        intent: &UiIntent,
+        mode: ExecutionMode,
+        denylist: ApiDenylistFilter,
+    ) -> Result<GenerationResult> {
+        Self::with_denylist(denylist).execute(raw_output, intent, mode)
+    }
+
+    /// Run the pipeline with the rule-set specific passes and profile of a
+    /// generation
+    pub fn run_with_rules(
+        raw_output: String,
+        intent: &UiIntent,
+        mode: ExecutionMode,
+        rules: &PipelineRules,
+    ) -> Result<GenerationResult> {
+        Self::with_rules(rules.clone()).execute(raw_output, intent, mode)
+    }
+
     /// Execute the pipeline
     fn execute(
         &self,
@@ -60,11 +185,77 @@ impl PostProcessingPipeline {
             mode
         );
 
-        for (i, pass) in self.passes.iter().enumerate() {
+        let passes: Vec<&dyn Pass> = self.passes.iter().map(|pass| pass.as_ref()).collect();
+        Self::run_passes(&passes, &mut ctx, &self.profile)?;
+
+        // Passes after the parser, once per additional screen
+        let screen_passes: Vec<&dyn Pass> = self.passes[1..]
+            .iter()
+            .map(|pass| pass.as_ref())
+            .filter(|pass| !pass.main_screen_only())
+            .collect();
+        let screens = std::mem::take(&mut ctx.additional_screens);
+        for (i, screen) in screens.into_iter().enumerate() {
+            let label = screen.label(i);
+            let mut screen_ctx = GenerationContext::new(String::new(), intent.clone(), mode);
+            screen_ctx.xml = Some(screen.xml);
+            screen_ctx.javascript = Some(screen.javascript);
+
+            Self::run_passes(&screen_passes, &mut screen_ctx, &self.profile)
+                .map_err(|e| anyhow!("Screen '{}': {}", label, e))?;
+
+            ctx.warnings.extend(
+                screen_ctx
+                    .warnings
+                    .into_iter()
+                    .map(|w| format!("[{}] {}", label, w)),
+            );
+            ctx.additional_screens.push(ScreenSection {
+                screen_id: screen.screen_id,
+                xml: screen_ctx.xml.unwrap_or_default(),
+                javascript: screen_ctx.javascript.unwrap_or_default(),
+            });
+        }
+
+        tracing::info!(
+            "Pipeline completed with {} warnings",
+            ctx.warnings.len()
+        );
+
+        // Ensure we have both XML and JS
+        if ctx.xml.is_none() {
+            return Err(anyhow!("Pipeline did not produce XML output"));
+        }
+        if ctx.javascript.is_none() {
+            return Err(anyhow!("Pipeline did not produce JavaScript output"));
+        }
+
+        Ok(GenerationResult::from_context(ctx)
+            .expect("XML and JS were verified above"))
+    }
+
+    /// Run passes in order, adding warnings to the context
+    ///
+    /// Errors are fatal in Strict mode and downgraded to warnings otherwise.
+    /// A profile override skips the pass or runs it in its own mode.
+    fn run_passes(passes: &[&dyn Pass], ctx: &mut GenerationContext, profile: &PipelineProfile) -> Result<()> {
+        let request_mode = ctx.execution_mode;
+        for (i, pass) in passes.iter().enumerate() {
             let pass_name = pass.name();
-            tracing::debug!("Running pass {}: {}", i, pass_name);
+            if let Some(pass_override) = profile.get(pass_name) {
+                match pass_override.mode() {
+                    Some(mode) => ctx.execution_mode = mode,
+                    None => {
+                        tracing::debug!("Skipping pass {}: {} (pipeline profile)", i, pass_name);
+                        continue;
+                    }
+                }
+            }
+            tracing::debug!("Running pass {}: {} in {:?} mode", i, pass_name, ctx.execution_mode);
 
-            let result = pass.run(&mut ctx);
+            let result = pass.run(ctx);
+            let strict = ctx.is_strict();
+            ctx.execution_mode = request_mode;
 
             match result {
                 PassResult::Ok => {
@@ -77,7 +268,7 @@ impl PostProcessingPipeline {
                 PassResult::Error(ref msg) => {
                     tracing::error!("Pass {} error: {}", pass_name, msg);
 
-                    if ctx.is_strict() {
+                    if strict {
                         return Err(anyhow!(
                             "Pipeline failed at pass '{}': {}",
                             pass_name,
@@ -91,21 +282,7 @@ impl PostProcessingPipeline {
             }
         }
 
-        tracing::info!(
-            "Pipeline completed with {} warnings",
-            ctx.warnings.len()
-        );
-
-        // Ensure we have both XML and JS
-        if ctx.xml.is_none() {
-            return Err(anyhow!("Pipeline did not produce XML output"));
-        }
-        if ctx.javascript.is_none() {
-            return Err(anyhow!("Pipeline did not produce JavaScript output"));
-        }
-
-        Ok(GenerationResult::from_context(ctx)
-            .expect("XML and JS were verified above"))
+        Ok(())
     }
 }
 
@@ -188,4 +365,120 @@ this.fn_test = function() {};
         // Should succeed with warnings in relaxed mode
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_pipeline_multi_screen() {
+        let raw = r#"
+--- XML ---
+<screen id="SCREEN_MEMBER_POP">
+  <xlinkdataset id="ds_pop"/>
+  <pushbutton name="btn_select" on_click="fn_select"/>
+</screen>
+
+--- JS ---
+this.fn_select = function() {};
+
+--- XML ---
+<screen id="SCREEN_MEMBER_LIST">
+  <xlinkdataset id="ds_list"/>
+  <grid name="grid_list" link_data="ds_list"/>
+</screen>
+
+--- JS ---
+this.fn_search = function() {};
+"#;
+
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let result = PostProcessingPipeline::run(raw.to_string(), &intent, ExecutionMode::Relaxed).unwrap();
+
+        // The screen matching the intent is the main artifact
+        assert!(result.xml.contains("SCREEN_MEMBER_LIST"));
+        assert!(result.javascript.contains("fn_search"));
+        assert_eq!(result.additional_screens.len(), 1);
+        let popup = &result.additional_screens[0];
+        assert_eq!(popup.screen_id.as_deref(), Some("SCREEN_MEMBER_POP"));
+        assert!(popup.javascript.contains("fn_select"));
+        assert!(!popup.xml.contains("SCREEN_MEMBER_LIST"));
+    }
+
+    #[test]
+    fn test_checklist_applies_to_main_screen_only() {
+        use crate::domain::{ChecklistItem, ChecklistTarget};
+
+        let raw = r#"
+--- XML ---
+<screen id="SCREEN_MEMBER_LIST">
+  <grid name="grid_list" link_data="ds_list"/>
+</screen>
+
+--- JS ---
+this.fn_search = function() {};
+
+--- XML ---
+<screen id="SCREEN_MEMBER_POP">
+  <pushbutton name="btn_select" on_click="fn_select"/>
+</screen>
+
+--- JS ---
+this.fn_select = function() {};
+"#;
+
+        let checklist = || {
+            ChecklistValidator::with_items(vec![ChecklistItem::new(
+                "Search function",
+                ChecklistTarget::Js,
+                r"\bfn_search\b",
+            )])
+        };
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let rules = PipelineRules::new().with_checklist(checklist());
+        let result = PostProcessingPipeline::run_with_rules(raw.to_string(), &intent, ExecutionMode::Relaxed, &rules).unwrap();
+        assert!(!result.warnings.iter().any(|w| w.contains("Checklist")));
+
+        // A main screen missing the item is flagged once
+        let raw = raw.replace("fn_search", "fn_find");
+        let result = PostProcessingPipeline::run_with_rules(raw, &intent, ExecutionMode::Relaxed, &rules).unwrap();
+        let missing: Vec<_> = result.warnings.iter().filter(|w| w.contains("Checklist item")).collect();
+        assert_eq!(missing, vec!["Warning: [JS] Checklist item not satisfied: Search function"]);
+    }
+
+    #[test]
+    fn test_profile_overrides_pass_mode() {
+        use crate::domain::{ChecklistItem, ChecklistTarget};
+        use crate::services::pipeline::PassOverride;
+
+        let raw = r#"
+--- XML ---
+<screen id="SCREEN_MEMBER_LIST">
+  <xlinkdataset id="ds_list"/>
+  <grid name="grid_list" link_data="ds_list"/>
+</screen>
+
+--- JS ---
+this.fn_find = function() {};
+"#;
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let run = |passes: &[(&str, PassOverride)]| {
+            let profile = PipelineProfile::new(
+                "test",
+                passes.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            )
+            .unwrap();
+            let checklist =
+                ChecklistValidator::with_items(vec![ChecklistItem::new("Search function", ChecklistTarget::Js, r"\bfn_search\b")]);
+            let rules = PipelineRules::new().with_checklist(checklist).with_profile(profile);
+            PostProcessingPipeline::run_with_rules(raw.to_string(), &intent, ExecutionMode::Strict, &rules)
+        };
+
+        // Strict request fails on the missing checklist item...
+        assert!(run(&[]).is_err());
+
+        // ...unless the profile relaxes that pass
+        let relaxed = run(&[("ChecklistValidator", PassOverride::Relaxed)]).unwrap();
+        assert!(relaxed.warnings.iter().any(|w| w.contains("Checklist item")));
+
+        // ...or skips it
+        let skipped = run(&[("ChecklistValidator", PassOverride::Skip)]).unwrap();
+        assert!(!skipped.warnings.iter().any(|w| w.contains("Checklist")));
+    }
 }
@@ -1,22 +1,60 @@
 //! Deterministic Post-Processing Pipeline for xFrame5 Code Generation
 //!
-//! This module implements a 6-pass pipeline that treats LLM output as untrusted input
+//! This module implements a 26-pass pipeline that treats LLM output as untrusted input
 //! and enforces deterministic correctness for enterprise (financial SI) environments.
 //!
 //! ## Pipeline Order (Fixed)
 //! 1. Output Parser - Split raw output into XML/JS sections
 //! 2. Canonicalizer - Normalize syntax (onclick → on_click, font fixes)
-//! 3. Symbol Linker - Match XML events to JS functions
-//! 4. API Allowlist Filter - Block hallucinated APIs
-//! 5. Graph Validator - Validate Dataset ↔ UI bindings
-//! 6. Minimalism Pass - Remove unused functions
+//! 3. Identifier Normalizer - Align screen/dataset/grid ids with the intent
+//! 4. Symbol Linker - Match XML events to JS functions
+//! 5. UX Behavior Pass - Wire enter-to-search, the F5 guard and the double-submit guard
+//! 6. API Allowlist Filter - Block hallucinated APIs
+//! 7. API Deny-list Filter - Flag forbidden/deprecated APIs (built-in + company rules)
+//! 8. Graph Validator - Validate Dataset ↔ UI bindings
+//! 9. Paging Validator - Require server-side paging for large-table grids
+//! 10. Editable Grid Validator - Require the changed-row save pattern for editable grids
+//! 11. Chart Binding Validator - Require a dataset binding for every dashboard chart
+//! 12. Wizard Step Validator - Require a panel and handlers for every wizard step
+//! 13. Tab Panel Validator - Require the tab control, a panel per tab and lazy tab loaders
+//! 14. Print Validator - Require the print button and report call when printing is requested
+//! 15. Search State Validator - Add the save/restore helpers for kept search conditions and check their calls
+//! 16. Error Handling Validator - Require checked and shown transaction errors, no swallowed exceptions
+//! 17. Format Validator - Set the locale date/number formats and check them against the data types
+//! 18. Label Language Validator - Check captions and messages against the screen locale, translate English button captions
+//! 19. Archetype Validator - Check the control, max_length and validation of columns matched to a column archetype
+//! 20. Address Field Validator - Require bound postcode/address fields and the postcode search popup wiring
+//! 21. Minimalism Pass - Remove unused functions
+//! 22. Sensitive Data Pass - Enforce masking of personal-data columns
+//! 23. Checklist Validator - Check the admin-managed checklist for the screen type
+//! 24. Runtime Smoke Test - Run on_load/fn_search against stubbed xFrame5 APIs (optional)
+//! 25. WASM Plugins - Run the customer checks uploaded by administrators (when any are enabled)
+//! 26. XML Formatter - Pretty-print the XML with canonical attribute order
+//!
+//! When one response holds several screens (list + popup), the Output Parser
+//! keeps the screen matching the intent as the main artifact and the engine
+//! runs passes 2-26 on every other screen separately (except the editable
+//! grid, chart, wizard, tab, print, search state and address checks, which
+//! belong to the main screen).
+//!
+//! Before any pass runs, [`StreamPrevalidator`] watches streamed responses
+//! for output that is clearly broken, so it can be aborted and retried early.
+//!
+//! A [`PipelineProfile`] can skip individual passes or run them in another
+//! execution mode than the request's.
 
 pub mod engine;
 pub mod passes;
+pub mod prevalidator;
+pub mod profile;
+pub mod taxonomy;
 
-pub use engine::PostProcessingPipeline;
+pub use engine::{PipelineRules, PostProcessingPipeline};
+pub use prevalidator::StreamPrevalidator;
+pub use profile::{PassOverride, PipelineProfile};
+pub use taxonomy::WarningCode;
 
-use crate::domain::UiIntent;
+use crate::domain::{NamingProfile, NamingVars, ScreenArtifact, UiIntent};
 
 /// Execution mode determines how errors and warnings are handled
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -87,6 +125,12 @@ pub trait Pass: Send + Sync {
     /// # Returns
     /// * `PassResult` indicating success, warning, or error
     fn run(&self, ctx: &mut GenerationContext) -> PassResult;
+
+    /// Whether the pass only applies to the intent's own screen, not to the
+    /// additional screens of a multi-screen response
+    fn main_screen_only(&self) -> bool {
+        false
+    }
 }
 
 /// Shared mutable state passed through all pipeline passes
@@ -109,6 +153,83 @@ pub struct GenerationContext {
 
     /// Original intent for validation reference
     pub intent: UiIntent,
+
+    /// Further screens split off by the Output Parser
+    pub additional_screens: Vec<ScreenSection>,
+}
+
+/// XML and JavaScript of one screen in a multi-screen response
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenSection {
+    /// `id` attribute of the `<screen>` element, if any
+    pub screen_id: Option<String>,
+
+    pub xml: String,
+
+    pub javascript: String,
+}
+
+impl ScreenSection {
+    /// Whether the screen id names the intent's screen (`SCREEN_MEMBER_LIST` ~ `member_list`)
+    pub fn matches_intent(&self, intent: &UiIntent) -> bool {
+        let normalize = |name: &str| {
+            let name = name.to_lowercase().replace('-', "_");
+            let name = name.strip_prefix("screen_").unwrap_or(&name).to_string();
+            name.strip_prefix("scr_").unwrap_or(&name).to_string()
+        };
+        self.screen_id
+            .as_deref()
+            .is_some_and(|id| normalize(id) == normalize(&intent.screen_name))
+    }
+
+    /// Label used to prefix the screen's warnings
+    pub fn label(&self, index: usize) -> String {
+        self.screen_id
+            .clone()
+            .unwrap_or_else(|| format!("screen {}", index + 2))
+    }
+}
+
+/// Artifacts of additional screens, named with the naming profile
+///
+/// `{screen_name}` is the screen id; a name already taken (by the main
+/// screen or an earlier one) gets a numeric suffix.
+pub fn screen_artifacts(
+    screens: Vec<ScreenSection>,
+    naming: &NamingProfile,
+    vars: &NamingVars,
+    taken: &[&str],
+) -> Vec<ScreenArtifact> {
+    let mut taken: Vec<String> = taken.iter().map(|name| name.to_string()).collect();
+    let mut unique = |name: String| {
+        let mut candidate = name.clone();
+        let mut n = 2;
+        while taken.contains(&candidate) {
+            candidate = match name.rsplit_once('.') {
+                Some((stem, ext)) => format!("{}_{}.{}", stem, n, ext),
+                None => format!("{}_{}", name, n),
+            };
+            n += 1;
+        }
+        taken.push(candidate.clone());
+        candidate
+    };
+
+    screens
+        .into_iter()
+        .enumerate()
+        .map(|(i, screen)| {
+            let screen_vars = vars.clone().with("screen_name", screen.label(i).replace(' ', "_"));
+            let (xml_filename, js_filename) = naming.screen_files(&screen_vars);
+            ScreenArtifact {
+                xml_filename: unique(xml_filename),
+                js_filename: unique(js_filename),
+                screen_id: screen.screen_id,
+                xml: screen.xml,
+                javascript: screen.javascript,
+            }
+        })
+        .collect()
 }
 
 impl GenerationContext {
@@ -121,6 +242,7 @@ impl GenerationContext {
             warnings: Vec::new(),
             execution_mode,
             intent,
+            additional_screens: Vec::new(),
         }
     }
 
@@ -151,6 +273,9 @@ pub struct GenerationResult {
 
     /// All warnings accumulated during processing
     pub warnings: Vec<String>,
+
+    /// Further screens of a multi-screen response, each run through the pipeline
+    pub additional_screens: Vec<ScreenSection>,
 }
 
 impl GenerationResult {
@@ -160,6 +285,7 @@ impl GenerationResult {
             xml: ctx.xml?,
             javascript: ctx.javascript?,
             warnings: ctx.warnings,
+            additional_screens: ctx.additional_screens,
         })
     }
 }
@@ -216,4 +342,59 @@ mod tests {
         assert!(ctx.is_strict());
         assert!(!ctx.is_dev());
     }
+
+    #[test]
+    fn test_screen_section_matches_intent() {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let screen = |id: &str| ScreenSection {
+            screen_id: Some(id.to_string()),
+            xml: String::new(),
+            javascript: String::new(),
+        };
+
+        assert!(screen("SCREEN_MEMBER_LIST").matches_intent(&intent));
+        assert!(screen("member_list").matches_intent(&intent));
+        assert!(!screen("SCREEN_MEMBER_POP").matches_intent(&intent));
+    }
+
+    #[test]
+    fn test_screen_artifacts_unique_names() {
+        let naming = NamingProfile {
+            xml: "{entity}_{type}.xml".to_string(),
+            js: "{entity}_{type}.js".to_string(),
+            ..Default::default()
+        };
+        let vars = NamingVars::new(chrono::NaiveDateTime::default())
+            .with("entity", "member")
+            .with("type", "list");
+        let screens = vec![
+            ScreenSection {
+                screen_id: Some("SCREEN_MEMBER_POP".to_string()),
+                xml: "<screen/>".to_string(),
+                javascript: String::new(),
+            },
+            ScreenSection {
+                screen_id: None,
+                xml: "<screen/>".to_string(),
+                javascript: String::new(),
+            },
+        ];
+
+        let artifacts = screen_artifacts(screens, &naming, &vars, &["member_list.xml", "member_list.js"]);
+        let names: Vec<&str> = artifacts.iter().map(|a| a.xml_filename.as_str()).collect();
+        assert_eq!(names, vec!["member_list_2.xml", "member_list_3.xml"]);
+        assert_eq!(artifacts[0].js_filename, "member_list_2.js");
+
+        let artifacts = screen_artifacts(
+            vec![ScreenSection {
+                screen_id: Some("SCREEN_MEMBER_POP".to_string()),
+                xml: String::new(),
+                javascript: String::new(),
+            }],
+            &NamingProfile::default(),
+            &vars,
+            &[],
+        );
+        assert_eq!(artifacts[0].xml_filename, "screen_member_pop.xml");
+    }
 }
@@ -0,0 +1,261 @@
+//! Pass 4m: Address Field Validator
+//!
+//! Every declared address field group must be complete and wired to the
+//! postcode search popup:
+//! - The dataset is declared in the intent and has the three columns
+//! - The XML binds a field to the postcode, base address and detail address
+//! - The search button (`btn_<id>_search`) calls `fn_<id>_search`; relaxed
+//!   mode sets its `on_click`
+//! - `fn_<id>_search` opens the configured address-search screen and returns
+//!   to `fn_<id>_callback`, which fills the postcode and base address
+//!
+//! Both functions are added when neither exists (see `AddressIntent::functions`).
+
+use super::{EditableGridValidator, FormatValidator};
+use crate::domain::AddressIntent;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::{Captures, Regex, RegexBuilder};
+
+/// Address Field Validator - address fields are bound and open the popup
+pub struct AddressFieldValidator;
+
+impl AddressFieldValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether a control is bound to the dataset column
+    fn has_bound_field(xml: &str, dataset_id: &str, column: &str) -> bool {
+        RegexBuilder::new(&format!(
+            r#"\blink_data\s*=\s*"{}:{}""#,
+            regex::escape(dataset_id),
+            regex::escape(column)
+        ))
+        .case_insensitive(true)
+        .build()
+        .unwrap()
+        .is_match(xml)
+    }
+
+    /// Check the search button; returns the (possibly fixed) XML and findings
+    fn check_button(xml: &str, address: &AddressIntent, fix: bool) -> (String, Vec<String>) {
+        let button_re = Regex::new(r#"<(pushbutton)(\s[^>]*)>"#).unwrap();
+        let button = address.button();
+        let search = address.search_function();
+        let mut found = false;
+        let mut findings = Vec::new();
+
+        let fixed = button_re.replace_all(xml, |cap: &Captures| {
+            let attrs = &cap[2];
+            if FormatValidator::attr(attrs, "name") != Some(button.as_str()) {
+                return cap[0].to_string();
+            }
+            found = true;
+            if FormatValidator::attr(attrs, "on_click").is_some_and(|on_click| on_click.contains(&search)) {
+                return cap[0].to_string();
+            }
+            findings.push(format!("[XML] Address ({}): {} does not call {}", address.id, button, search));
+            if fix {
+                let on_click = format!("eventfunc:{}()", search);
+                format!("<{}{}>", &cap[1], FormatValidator::set_attr(attrs, "on_click", &on_click))
+            } else {
+                cap[0].to_string()
+            }
+        });
+
+        if !found {
+            findings.push(format!("[XML] Address ({}) has no search button {}", address.id, button));
+        }
+        (fixed.into_owned(), findings)
+    }
+
+    /// Whether a function fills a column (through its field or the dataset)
+    fn fills(body: &str, column: &str) -> bool {
+        body.contains(&AddressIntent::field(column)) || body.contains(&format!("\"{}\"", column))
+    }
+
+    fn check_functions(js: &str, address: &AddressIntent) -> Vec<String> {
+        let popup = address.popup();
+        let search = address.search_function();
+        let callback = address.callback_function();
+        let mut findings = Vec::new();
+
+        match EditableGridValidator::function_body(js, &search) {
+            None => findings.push(format!("[JS] Address ({}) has no {}", address.id, search)),
+            Some(body) => {
+                if !body.contains(&popup.url) {
+                    findings.push(format!("[JS] Address ({}): {} does not open {}", address.id, search, popup.url));
+                }
+                if !body.contains(&callback) {
+                    findings.push(format!("[JS] Address ({}): {} does not return to {}", address.id, search, callback));
+                }
+            }
+        }
+
+        match EditableGridValidator::function_body(js, &callback) {
+            None => findings.push(format!("[JS] Address ({}) has no {}", address.id, callback)),
+            Some(body) => {
+                for column in [&address.zip_column, &address.address_column] {
+                    if !Self::fills(body, column) {
+                        findings.push(format!("[JS] Address ({}): {} does not fill {}", address.id, callback, column));
+                    }
+                }
+            }
+        }
+        findings
+    }
+}
+
+impl Default for AddressFieldValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for AddressFieldValidator {
+    fn name(&self) -> &'static str {
+        "AddressFieldValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let addresses = ctx.intent.addresses.clone();
+        if addresses.is_empty() {
+            return PassResult::Ok;
+        }
+        let fix = !ctx.is_strict();
+        let mut findings = Vec::new();
+
+        for address in &addresses {
+            match ctx.intent.datasets.iter().find(|ds| ds.id == address.dataset_id) {
+                None => findings.push(format!("[Intent] Address ({}) uses unknown dataset {}", address.id, address.dataset_id)),
+                Some(dataset) => {
+                    for column in address.columns() {
+                        if !dataset.columns.iter().any(|c| c.name.eq_ignore_ascii_case(column)) {
+                            findings.push(format!(
+                                "[Intent] Address ({}): {} has no column {}",
+                                address.id, address.dataset_id, column
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if let Some(xml) = &ctx.xml {
+                for column in address.columns() {
+                    if !Self::has_bound_field(xml, &address.dataset_id, column) {
+                        findings.push(format!(
+                            "[XML] Address ({}) has no field bound to {}:{}",
+                            address.id, address.dataset_id, column
+                        ));
+                    }
+                }
+                let (fixed, button_findings) = Self::check_button(xml, address, fix);
+                findings.extend(button_findings);
+                ctx.xml = Some(fixed);
+            }
+
+            if let Some(js) = ctx.javascript.clone() {
+                let has_search = EditableGridValidator::function_body(&js, &address.search_function()).is_some();
+                let has_callback = EditableGridValidator::function_body(&js, &address.callback_function()).is_some();
+                if !has_search && !has_callback {
+                    let mut with_functions = js;
+                    if !with_functions.is_empty() && !with_functions.ends_with('\n') {
+                        with_functions.push('\n');
+                    }
+                    with_functions.push('\n');
+                    with_functions.push_str(&address.functions());
+                    ctx.javascript = Some(with_functions);
+                    ctx.add_warning(format!(
+                        "Added address popup functions ({}, {})",
+                        address.search_function(),
+                        address.callback_function()
+                    ));
+                } else {
+                    findings.extend(Self::check_functions(&js, address));
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} address field issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnIntent, DatasetIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const XML: &str = r#"<screen id="SCREEN_CUSTOMER_DETAIL" width="800" height="600">
+  <normal_field control_id="11" name="field_zip_cd" link_data="ds_customer:ZIP_CD"/>
+  <pushbutton control_id="12" name="btn_addr_search" text="우편번호 찾기"/>
+  <normal_field control_id="13" name="field_addr1" link_data="ds_customer:ADDR1"/>
+  <normal_field control_id="14" name="field_addr2" link_data="ds_customer:ADDR2"/>
+</screen>"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_customer").with_columns(vec![
+                ColumnIntent::new("ZIP_CD", "우편번호"),
+                ColumnIntent::new("ADDR1", "주소"),
+                ColumnIntent::new("ADDR2", "상세주소"),
+            ]))
+            .with_address(AddressIntent::new("ds_customer", "ZIP_CD", "ADDR1", "ADDR2"));
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_relaxed_wires_button_and_adds_functions() {
+        let mut ctx = create_context(XML, "this.on_load = function() {\n};", ExecutionMode::Relaxed);
+        assert!(AddressFieldValidator::new().run(&mut ctx).is_warning());
+
+        assert!(ctx.xml.unwrap().contains(r#"text="우편번호 찾기" on_click="eventfunc:fn_addr_search()"/>"#));
+        let js = ctx.javascript.unwrap();
+        assert!(js.contains("this.fn_addr_search = function() {"));
+        assert!(js.contains("field_zip_cd.setvalue(returnValue.zonecode);"));
+        assert_eq!(ctx.warnings.len(), 2);
+        assert!(ctx.warnings[0].starts_with("Added address popup functions"));
+        assert!(ctx.warnings[1].contains("btn_addr_search does not call fn_addr_search"));
+    }
+
+    #[test]
+    fn test_strict_checks_popup_and_callback() {
+        let xml = XML.replace(r#"text="우편번호 찾기""#, r#"text="우편번호 찾기" on_click="eventfunc:fn_addr_search()""#);
+        let address = AddressIntent::new("ds_customer", "ZIP_CD", "ADDR1", "ADDR2");
+        let mut ctx = create_context(&xml, &address.functions(), ExecutionMode::Strict);
+        assert!(matches!(AddressFieldValidator::new().run(&mut ctx), PassResult::Ok));
+
+        let js = address
+            .functions()
+            .replace("/common/zipcode_popup.xml", "/screens/zip.xml")
+            .replace("field_addr1.setvalue(returnValue.roadAddress);", "");
+        let mut ctx = create_context(&xml.replace("ds_customer:ADDR2", "ds_customer:ADDR3"), &js, ExecutionMode::Strict);
+        match AddressFieldValidator::new().run(&mut ctx) {
+            PassResult::Error(e) => {
+                assert!(e.contains("[XML] Address (addr) has no field bound to ds_customer:ADDR2"));
+                assert!(e.contains("fn_addr_search does not open /common/zipcode_popup.xml"));
+                assert!(e.contains("fn_addr_callback does not fill ADDR1"));
+            }
+            other => panic!("expected error, got {:?}", other),
+        }
+    }
+}
@@ -38,6 +38,7 @@ impl ApiAllowlistFilter {
             "setData",
             "getMaxRow",
             "insertRow",
+            "getChangedData",
             // Grid APIs
             "getSelectedRow",
             "setSelectedRow",
@@ -48,6 +49,9 @@ impl ApiAllowlistFilter {
             "setCheckedRow",
             "checkAll",
             "uncheckAll",
+            // Tab APIs
+            "getSelectedTab",
+            "setSelectedTab",
             // Popup/Dialog APIs
             "loadpopup",
             "closepopup",
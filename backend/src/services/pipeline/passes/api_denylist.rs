@@ -0,0 +1,170 @@
+//! Pass 3b: API Deny-list Filter
+//!
+//! Complements the allowlist: flags deprecated xFrame5/browser APIs and
+//! insecure JS patterns, plus any forbidden APIs from the company rule set.
+//! Findings carry the suggested replacement so they can be surfaced in reviews.
+
+use crate::domain::{CompanyRuleSections, ForbiddenApi, ForbiddenApiHit};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+
+/// API Deny-list Filter - flags forbidden and deprecated APIs
+#[derive(Clone)]
+pub struct ApiDenylistFilter {
+    deny_list: Vec<ForbiddenApi>,
+}
+
+impl ApiDenylistFilter {
+    /// Create a filter with the built-in deny-list only
+    pub fn new() -> Self {
+        Self {
+            deny_list: Self::default_deny_list(),
+        }
+    }
+
+    /// Create a filter for a company rule set.
+    ///
+    /// Built-in entries listed in `allowed_apis` are dropped; company entries
+    /// override built-ins with the same pattern.
+    pub fn for_rules(sections: Option<&CompanyRuleSections>, product: &str) -> Self {
+        let Some(sections) = sections else {
+            return Self::new();
+        };
+
+        let company = sections.forbidden_apis_for(product);
+        let mut deny_list: Vec<ForbiddenApi> = Self::default_deny_list()
+            .into_iter()
+            .filter(|api| !sections.allowed_apis.contains(&api.pattern))
+            .filter(|api| !company.iter().any(|c| c.pattern == api.pattern))
+            .collect();
+        deny_list.extend(company);
+
+        Self { deny_list }
+    }
+
+    /// Built-in deny-list (insecure JS and APIs that bypass the xFrame5 runtime)
+    pub fn default_deny_list() -> Vec<ForbiddenApi> {
+        vec![
+            ForbiddenApi::new("eval(")
+                .with_replacement("JSON.parse()")
+                .with_reason("arbitrary code execution"),
+            ForbiddenApi::new("new Function(")
+                .with_replacement("this.fn_xxx = function() { ... }")
+                .with_reason("arbitrary code execution"),
+            ForbiddenApi::new("document.write(")
+                .with_replacement("component.setValue()")
+                .with_reason("overwrites the xFrame5 screen DOM"),
+            ForbiddenApi::new(".innerHTML")
+                .with_replacement("component.setValue()")
+                .with_reason("XSS risk"),
+            ForbiddenApi::new("document.getElementById(")
+                .with_replacement("this.<component_id>")
+                .with_reason("bypasses the xFrame5 component model"),
+            ForbiddenApi::new("window.showModalDialog(")
+                .with_replacement("screen.loadpopup()")
+                .with_reason("deprecated browser API"),
+            ForbiddenApi::new("window.open(")
+                .with_replacement("screen.loadpopup()")
+                .with_reason("bypasses the xFrame5 popup lifecycle"),
+        ]
+    }
+
+    /// Effective deny-list
+    pub fn deny_list(&self) -> &[ForbiddenApi] {
+        &self.deny_list
+    }
+
+    /// Scan code for deny-listed APIs
+    pub fn scan(&self, code: &str) -> Vec<ForbiddenApiHit> {
+        ForbiddenApi::scan(code, &self.deny_list)
+    }
+}
+
+impl Default for ApiDenylistFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for ApiDenylistFilter {
+    fn name(&self) -> &'static str {
+        "ApiDenylistFilter"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            findings.extend(self.scan(xml).iter().map(|hit| format!("[XML] {}", hit.message())));
+        }
+        if let Some(js) = &ctx.javascript {
+            findings.extend(self.scan(js).iter().map(|hit| format!("[JS] {}", hit.message())));
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} forbidden API usage(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    fn create_context(js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("test", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some("<screen/>".to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_clean_code_passes() {
+        let filter = ApiDenylistFilter::new();
+        let mut ctx = create_context("var data = JSON.parse(text);", ExecutionMode::Relaxed);
+        assert!(matches!(filter.run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_eval_flagged_with_replacement_in_relaxed_mode() {
+        let filter = ApiDenylistFilter::new();
+        let mut ctx = create_context("var data = eval(text);", ExecutionMode::Relaxed);
+
+        assert!(filter.run(&mut ctx).is_warning());
+        assert!(ctx.warnings[0].contains("'eval('"));
+        assert!(ctx.warnings[0].contains("use 'JSON.parse()' instead"));
+    }
+
+    #[test]
+    fn test_strict_mode_errors() {
+        let filter = ApiDenylistFilter::new();
+        let mut ctx = create_context("document.write('x');", ExecutionMode::Strict);
+        assert!(filter.run(&mut ctx).is_error());
+    }
+
+    #[test]
+    fn test_company_rules_allow_and_extend() {
+        let sections = CompanyRuleSections {
+            allowed_apis: vec!["window.open(".to_string()],
+            forbidden_apis: vec![ForbiddenApi::new("alert(").with_replacement("screen.alert()")],
+            ..Default::default()
+        };
+        let filter = ApiDenylistFilter::for_rules(Some(&sections), "xframe5-ui");
+
+        assert!(filter.scan("window.open('a');").is_empty());
+        assert_eq!(filter.scan("alert('hi');").len(), 1);
+        assert_eq!(filter.scan("eval(x);").len(), 1);
+    }
+}
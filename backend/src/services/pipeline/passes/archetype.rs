@@ -0,0 +1,223 @@
+//! Pass 4l: Archetype Validator
+//!
+//! Columns matched to a column archetype must look the same on every screen:
+//! - A field bound to the column is the archetype's control
+//!   (e.g. `numericex_field` for an amount, not `normal_field`)
+//! - Its `max_length` is the archetype's; relaxed mode sets it
+//! - When the screen has `fn_validate`, it tests the value against the
+//!   archetype's validation pattern
+//!
+//! Display formats and input masks are checked by the Format Validator.
+//! No-op when no column has an archetype.
+
+use std::collections::HashMap;
+
+use super::{EditableGridValidator, FormatValidator};
+use crate::domain::{ColumnIntent, UiType};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::{Captures, Regex};
+
+/// Input controls whose kind is checked against the column's UI type
+const FIELD_TAGS: &[&str] = &["normal_field", "numericex_field", "datepicker", "textarea", "combobox"];
+
+/// Archetype Validator - archetype columns keep their control, length and validation
+pub struct ArchetypeValidator;
+
+impl ArchetypeValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Control expected for a UI type (`None`: not checked)
+    fn expected_tag(ui_type: UiType) -> Option<&'static str> {
+        match ui_type {
+            UiType::Input => Some("normal_field"),
+            UiType::Number => Some("numericex_field"),
+            UiType::DatePicker | UiType::DateTimePicker => Some("datepicker"),
+            UiType::TextArea => Some("textarea"),
+            UiType::Combo => Some("combobox"),
+            _ => None,
+        }
+    }
+
+    /// Check bound fields; returns the (possibly fixed) XML and findings
+    fn check_fields(xml: &str, columns: &HashMap<String, &ColumnIntent>, fix: bool) -> (String, Vec<String>) {
+        let tag_re = Regex::new(r#"<(\w+)(\s[^>]*)>"#).unwrap();
+        let mut findings = Vec::new();
+
+        let fixed = tag_re.replace_all(xml, |cap: &Captures| {
+            let tag = cap[1].to_lowercase();
+            let attrs = &cap[2];
+            if !FIELD_TAGS.contains(&tag.as_str()) {
+                return cap[0].to_string();
+            }
+            let Some(column) = FormatValidator::bound_column(&tag, attrs).and_then(|col| columns.get(&col).copied())
+            else {
+                return cap[0].to_string();
+            };
+            let archetype = column.archetype.as_deref().unwrap_or_default();
+
+            if let Some(expected) = Self::expected_tag(column.ui_type).filter(|expected| *expected != tag) {
+                findings.push(format!(
+                    "[XML] <{}> bound to '{}' should be a <{}> (archetype {})",
+                    tag, column.name, expected, archetype
+                ));
+                return cap[0].to_string();
+            }
+
+            let Some(len) = column.max_length.filter(|_| matches!(tag.as_str(), "normal_field" | "textarea")) else {
+                return cap[0].to_string();
+            };
+            let len = len.to_string();
+            if FormatValidator::attr(attrs, "max_length") == Some(len.as_str()) {
+                return cap[0].to_string();
+            }
+            findings.push(format!(
+                "[XML] <{}> bound to '{}' should use max_length=\"{}\" (archetype {})",
+                tag, column.name, len, archetype
+            ));
+            if fix {
+                format!("<{}{}>", &cap[1], FormatValidator::set_attr(attrs, "max_length", &len))
+            } else {
+                cap[0].to_string()
+            }
+        });
+
+        (fixed.into_owned(), findings)
+    }
+
+    /// Whether the function tests the value against the pattern (as a regex
+    /// literal or a `RegExp` string)
+    fn checks_pattern(body: &str, pattern: &str) -> bool {
+        body.contains(pattern) || body.contains(&pattern.replace('\\', "\\\\"))
+    }
+}
+
+impl Default for ArchetypeValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for ArchetypeValidator {
+    fn name(&self) -> &'static str {
+        "ArchetypeValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let archetyped = ctx.intent.archetype_columns();
+        if archetyped.is_empty() {
+            return PassResult::Ok;
+        }
+        let columns: HashMap<String, &ColumnIntent> =
+            archetyped.into_iter().map(|c| (c.name.to_uppercase(), c)).collect();
+        let fix = !ctx.is_strict();
+        let mut findings = Vec::new();
+
+        let validate = ctx.javascript.as_deref().and_then(|js| EditableGridValidator::function_body(js, "fn_validate"));
+        if let Some(validate) = validate {
+            for column in columns.values().filter(|c| !c.readonly && c.ui_type != UiType::Hidden) {
+                if let Some(pattern) = column.validation.as_deref().filter(|p| !Self::checks_pattern(validate, p)) {
+                    findings.push(format!(
+                        "[JS] fn_validate does not check '{}' against /{}/ (archetype {})",
+                        column.name,
+                        pattern,
+                        column.archetype.as_deref().unwrap_or_default()
+                    ));
+                }
+            }
+            findings.sort();
+        }
+
+        if let Some(xml) = &ctx.xml {
+            let (fixed, xml_findings) = Self::check_fields(xml, &columns, fix);
+            findings.extend(xml_findings);
+            ctx.xml = Some(fixed);
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} archetype issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnArchetype, DataType, DatasetIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const XML: &str = r#"<screen>
+<normal_field control_id="1" name="field_biz_reg_no" link_data="ds_vendor:BIZ_REG_NO" max_length="20"/>
+<normal_field control_id="2" name="field_credit_amt" link_data="ds_vendor:CREDIT_AMT"/>
+<normal_field control_id="3" name="field_memo" link_data="ds_vendor:MEMO"/>
+</screen>"#;
+
+    const JS: &str = r#"this.fn_validate = function() {
+    if (!/^\d{3}-\d{2}-\d{5}$/.test(ds_vendor.getColumn(0, "BIZ_REG_NO"))) { return false; }
+    return true;
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let biz = ColumnArchetype {
+            max_length: Some(12),
+            validation: Some(r"^\d{3}-\d{2}-\d{5}$".to_string()),
+            ..ColumnArchetype::new("사업자등록번호")
+        };
+        let amount = ColumnArchetype {
+            ui_type: Some(UiType::Number),
+            data_type: Some(DataType::Decimal),
+            ..ColumnArchetype::new("금액")
+        };
+        let mut columns = vec![
+            ColumnIntent::new("BIZ_REG_NO", "사업자등록번호"),
+            ColumnIntent::new("CREDIT_AMT", "여신한도"),
+            ColumnIntent::new("MEMO", "메모"),
+        ];
+        biz.apply(&mut columns[0]);
+        amount.apply(&mut columns[1]);
+        let intent = UiIntent::new("vendor_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_vendor").with_columns(columns));
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_relaxed_sets_length_and_reports_control() {
+        let mut ctx = create_context(XML, JS, ExecutionMode::Relaxed);
+        assert!(ArchetypeValidator::new().run(&mut ctx).is_warning());
+
+        assert!(ctx.xml.unwrap().contains(r#"link_data="ds_vendor:BIZ_REG_NO" max_length="12"/>"#));
+        assert_eq!(ctx.warnings.len(), 2);
+        assert!(ctx.warnings[0].contains("should use max_length=\"12\" (archetype 사업자등록번호)"));
+        assert!(ctx.warnings[1].contains("<normal_field> bound to 'CREDIT_AMT' should be a <numericex_field> (archetype 금액)"));
+    }
+
+    #[test]
+    fn test_strict_requires_validation_pattern() {
+        let xml = XML
+            .replace(r#"max_length="20""#, r#"max_length="12""#)
+            .replace("<normal_field control_id=\"2\"", "<numericex_field control_id=\"2\"");
+        let mut ctx = create_context(&xml, JS, ExecutionMode::Strict);
+        assert!(matches!(ArchetypeValidator::new().run(&mut ctx), PassResult::Ok));
+
+        let js = JS.replace(r"/^\d{3}-\d{2}-\d{5}$/", r"/^\d+$/");
+        let mut ctx = create_context(&xml, &js, ExecutionMode::Strict);
+        match ArchetypeValidator::new().run(&mut ctx) {
+            PassResult::Error(e) => assert!(e.contains("fn_validate does not check 'BIZ_REG_NO'")),
+            other => panic!("expected error, got {:?}", other),
+        }
+    }
+}
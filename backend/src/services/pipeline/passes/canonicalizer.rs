@@ -136,7 +136,7 @@ impl Canonicalizer {
     }
 
     /// Normalize XML content
-    fn canonicalize_xml(&self, xml: &str) -> (String, Vec<String>) {
+    pub(crate) fn canonicalize_xml(&self, xml: &str) -> (String, Vec<String>) {
         let mut result = xml.to_string();
         let mut fixes = Vec::new();
 
@@ -0,0 +1,196 @@
+//! Pass 4d: Chart Binding Validator
+//!
+//! Dashboard screens draw every chart from a dataset:
+//! - Each `<chart>` has `link_data` naming a declared dataset
+//! - Each chart of the intent is present and bound to its dataset
+//! - `fn_refresh` exists and loads every dataset the dashboard reads
+//!
+//! Nothing is auto-fixed; a missing binding can't be guessed.
+
+use super::GraphValidator;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+use std::collections::HashSet;
+
+/// Chart Binding Validator - every dashboard chart reads a dataset
+pub struct ChartBindingValidator;
+
+impl ChartBindingValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `(name, link_data)` of every chart component
+    fn charts(xml: &str) -> Vec<(String, Option<String>)> {
+        let tag = Regex::new(r#"(?i)<chart\b[^>]*>"#).unwrap();
+        let name = Regex::new(r#"\b(?:name|id)\s*=\s*"([^"]+)""#).unwrap();
+        let link = Regex::new(r#"\blink_data\s*=\s*"([^":]*)(?::[^"]*)?""#).unwrap();
+        tag.find_iter(xml)
+            .map(|m| {
+                let tag = m.as_str();
+                let name = name.captures(tag).map(|c| c[1].to_string()).unwrap_or_default();
+                let link = link
+                    .captures(tag)
+                    .map(|c| c[1].trim().to_string())
+                    .filter(|l| !l.is_empty());
+                (name, link)
+            })
+            .collect()
+    }
+
+    fn defines_refresh(js: &str) -> bool {
+        Regex::new(r#"\bfn_refresh\s*=\s*function\b|\bfunction\s+fn_refresh\b"#)
+            .unwrap()
+            .is_match(js)
+    }
+}
+
+impl Default for ChartBindingValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for ChartBindingValidator {
+    fn name(&self) -> &'static str {
+        "ChartBindingValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let is_dashboard =
+            ctx.intent.screen_type == crate::domain::ScreenType::Dashboard || ctx.intent.dashboard.is_some();
+        if !is_dashboard {
+            return PassResult::Ok;
+        }
+        let dashboard = ctx.intent.dashboard.clone().unwrap_or_default();
+
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            let declared: HashSet<String> = GraphValidator::new().extract_datasets(xml);
+            let charts = Self::charts(xml);
+
+            for (name, link) in &charts {
+                match link {
+                    None => findings.push(format!("[XML] Dashboard chart ({}) is not bound to a dataset", name)),
+                    Some(ds) if !declared.contains(ds) => findings.push(format!(
+                        "[XML] Dashboard chart ({}) is bound to undeclared dataset {}",
+                        name, ds
+                    )),
+                    _ => {}
+                }
+            }
+
+            for chart in &dashboard.charts {
+                match charts.iter().find(|(name, _)| *name == chart.id) {
+                    None => findings.push(format!("[XML] Dashboard chart ({}) is missing", chart.id)),
+                    Some((_, Some(ds))) if *ds != chart.dataset_id => findings.push(format!(
+                        "[XML] Dashboard chart ({}) is bound to {} instead of {}",
+                        chart.id, ds, chart.dataset_id
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(js) = &ctx.javascript {
+            if !Self::defines_refresh(js) {
+                findings.push(format!("[JS] Dashboard ({}) has no fn_refresh", ctx.intent.screen_name));
+            }
+            for ds in dashboard.dataset_ids() {
+                if !Regex::new(&format!(r"\b{}\b", regex::escape(ds))).unwrap().is_match(js) {
+                    findings.push(format!("[JS] Dashboard ({}) never loads {}", ctx.intent.screen_name, ds));
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} chart binding issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ChartIntent, ChartType, DashboardIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const DASHBOARD_XML: &str = r#"<screen id="SCREEN_SALES_DASHBOARD" width="1024" height="400">
+  <xlinkdataset id="ds_sales" columns="DEPT_NAME:&quot;Dept&quot;:50;TOTAL_AMT:&quot;Amount&quot;:15"/>
+  <chart control_id="10" name="chart_sales" x="10" y="50" width="497" height="300"
+         link_data="ds_sales" chart_type="bar"/>
+</screen>"#;
+
+    const REFRESH_JS: &str = r#"this.fn_refresh = function() {
+    // TODO: Replace with actual API endpoint
+    ds_sales.load("TODO_API_ENDPOINT");
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let dashboard = DashboardIntent {
+            charts: vec![ChartIntent {
+                id: "chart_sales".to_string(),
+                title: "부서별 매출".to_string(),
+                chart_type: ChartType::Bar,
+                dataset_id: "ds_sales".to_string(),
+                category_column: "DEPT_NAME".to_string(),
+                value_columns: vec!["TOTAL_AMT".to_string()],
+            }],
+            ..Default::default()
+        };
+        let intent = UiIntent::new("sales_dashboard", ScreenType::Dashboard).with_dashboard(dashboard);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_other_screens_not_checked() {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, ExecutionMode::Strict);
+        ctx.xml = Some("<chart name=\"chart_x\"/>".to_string());
+        assert!(matches!(ChartBindingValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_bound_charts_pass() {
+        let mut ctx = create_context(DASHBOARD_XML, REFRESH_JS, ExecutionMode::Strict);
+        assert!(matches!(ChartBindingValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_unbound_chart_flagged() {
+        let xml = DASHBOARD_XML.replace(" link_data=\"ds_sales\"", "");
+        let mut ctx = create_context(&xml, "this.fn_search = function() {};", ExecutionMode::Relaxed);
+
+        assert!(ChartBindingValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 3);
+        assert!(ctx.warnings[0].contains("(chart_sales) is not bound to a dataset"));
+        assert!(ctx.warnings[1].contains("no fn_refresh"));
+        assert!(ctx.warnings[2].contains("never loads ds_sales"));
+
+        let xml = DASHBOARD_XML.replace("link_data=\"ds_sales\"", "link_data=\"ds_other\"");
+        let mut ctx = create_context(&xml, REFRESH_JS, ExecutionMode::Strict);
+        let PassResult::Error(message) = ChartBindingValidator::new().run(&mut ctx) else {
+            panic!("expected an error");
+        };
+        assert!(message.contains("undeclared dataset ds_other"));
+        assert!(message.contains("bound to ds_other instead of ds_sales"));
+    }
+}
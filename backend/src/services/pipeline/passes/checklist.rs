@@ -0,0 +1,122 @@
+//! Pass 7: Checklist Validator
+//!
+//! Checks the main screen against the admin-managed checklist for its
+//! product and screen type (e.g. "every list screen needs a print button").
+//! The same items are rendered into the prompt, so a miss here means the
+//! model ignored an explicit instruction.
+//!
+//! Nothing is auto-fixed; missing components can't be guessed.
+
+use crate::domain::ChecklistItem;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+
+/// Checklist Validator - every checklist item must be satisfied
+#[derive(Clone, Default)]
+pub struct ChecklistValidator {
+    items: Vec<ChecklistItem>,
+}
+
+impl ChecklistValidator {
+    /// Validator without items (passes everything)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validator for the items loaded for the screen
+    pub fn with_items(items: Vec<ChecklistItem>) -> Self {
+        Self { items }
+    }
+
+    /// Items the generated code does not satisfy
+    pub fn missing<'a>(&'a self, xml: &str, javascript: &str) -> Vec<&'a ChecklistItem> {
+        self.items
+            .iter()
+            .filter(|item| !item.is_satisfied(xml, javascript))
+            .collect()
+    }
+}
+
+impl Pass for ChecklistValidator {
+    fn name(&self) -> &'static str {
+        "ChecklistValidator"
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        if self.items.is_empty() {
+            return PassResult::Ok;
+        }
+
+        let xml = ctx.xml.as_deref().unwrap_or_default();
+        let js = ctx.javascript.as_deref().unwrap_or_default();
+        let findings: Vec<String> = self
+            .missing(xml, js)
+            .iter()
+            .map(|item| format!("[{}] Checklist item not satisfied: {}", item.target.as_str().to_uppercase(), item.label))
+            .collect();
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("{} of {} checklist item(s) not satisfied", findings.len(), self.items.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ChecklistTarget, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    fn validator() -> ChecklistValidator {
+        ChecklistValidator::with_items(vec![
+            ChecklistItem::new("Print button", ChecklistTarget::Xml, r#"name="btn_print""#),
+            ChecklistItem::new("fn_print function", ChecklistTarget::Js, r"\bfn_print\b"),
+        ])
+    }
+
+    #[test]
+    fn test_no_items_passes() {
+        let mut ctx = create_context("<screen/>", "", ExecutionMode::Strict);
+        assert!(matches!(ChecklistValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_satisfied_checklist_passes() {
+        let xml = r#"<pushbutton name="btn_print" on_click="eventfunc:fn_print()"/>"#;
+        let mut ctx = create_context(xml, "this.fn_print = function() {};", ExecutionMode::Strict);
+        assert!(matches!(validator().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_missing_items_flagged() {
+        let mut ctx = create_context("<screen/>", "this.fn_print = function() {};", ExecutionMode::Relaxed);
+        assert!(validator().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings, vec!["Warning: [XML] Checklist item not satisfied: Print button"]);
+
+        let mut ctx = create_context("<screen/>", "", ExecutionMode::Strict);
+        let result = validator().run(&mut ctx);
+        assert!(result.is_error());
+        assert!(result.message().unwrap().contains("[JS] Checklist item not satisfied: fn_print function"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Pass 4c: Editable Grid Validator
+//!
+//! Grids edited in place save only what changed (dirty-row pattern):
+//! - The grid has editable data columns (`editable="1"`)
+//! - `fn_save` takes the changed rows with `getChangedData()`
+//! - `fn_save` loops over those rows
+//! - Each row's status (insert/update/delete) is read and sent along
+//!
+//! Nothing is auto-fixed; the save transaction can't be guessed.
+
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+/// Editable Grid Validator - enforces the changed-row save pattern
+pub struct EditableGridValidator;
+
+impl EditableGridValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether grid `name` (or, if it can't be found, any grid) has an
+    /// editable data column
+    fn has_editable_column(xml: &str, name: &str) -> bool {
+        let editable = Regex::new(r#"(?i)\beditable\s*=\s*"(?:1|true)""#).unwrap();
+        let grid = Regex::new(&format!(
+            r#"(?is)<grid\b[^>]*\bname\s*=\s*"{}".*?</grid>"#,
+            regex::escape(name)
+        ))
+        .unwrap();
+        match grid.find(xml) {
+            Some(block) => editable.is_match(block.as_str()),
+            None => editable.is_match(xml),
+        }
+    }
+
+    /// Body of function `name` (between its braces), if defined
+    pub(crate) fn function_body<'a>(js: &'a str, name: &str) -> Option<&'a str> {
+        let name = regex::escape(name);
+        let start = Regex::new(&format!(
+            r#"(?:\b{}\s*=\s*function|\bfunction\s+{})\s*\([^)]*\)\s*\{{"#,
+            name, name
+        ))
+        .unwrap()
+        .find(js)?
+        .end();
+        let mut depth = 1;
+        for (i, c) in js[start..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&js[start..start + i]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some(&js[start..])
+    }
+
+    fn uses_changed_data(body: &str) -> bool {
+        Regex::new(r#"(?i)\bgetChangedData\s*\("#).unwrap().is_match(body)
+    }
+
+    fn iterates_rows(body: &str) -> bool {
+        Regex::new(r#"\bfor\s*\(|\bwhile\s*\(|\.forEach\s*\("#).unwrap().is_match(body)
+    }
+
+    fn handles_row_status(body: &str) -> bool {
+        Regex::new(r#"(?i)\bgetRow(?:Type|Status)\s*\(|\bROW_?(?:STATUS|TYPE)\b|\bROWTYPE_\w+"#)
+            .unwrap()
+            .is_match(body)
+    }
+}
+
+impl Default for EditableGridValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for EditableGridValidator {
+    fn name(&self) -> &'static str {
+        "EditableGridValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let grids: Vec<String> = ctx
+            .intent
+            .grids
+            .iter()
+            .filter(|g| g.editable)
+            .map(|g| g.id.clone())
+            .collect();
+        if grids.is_empty() {
+            return PassResult::Ok;
+        }
+
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            for grid in &grids {
+                if !Self::has_editable_column(xml, grid) {
+                    findings.push(format!("[XML] Editable grid ({}) has no editable=\"1\" column", grid));
+                }
+            }
+        }
+
+        if let Some(js) = &ctx.javascript {
+            let grids = grids.join(", ");
+            match Self::function_body(js, "fn_save") {
+                None => findings.push(format!("[JS] Editable grid ({}) has no fn_save", grids)),
+                Some(body) => {
+                    if !Self::uses_changed_data(body) {
+                        findings.push(format!(
+                            "[JS] Editable grid ({}): fn_save does not take the changed rows with getChangedData()",
+                            grids
+                        ));
+                    }
+                    if !Self::iterates_rows(body) {
+                        findings.push(format!(
+                            "[JS] Editable grid ({}): fn_save does not iterate the changed rows",
+                            grids
+                        ));
+                    }
+                    if !Self::handles_row_status(body) {
+                        findings.push(format!(
+                            "[JS] Editable grid ({}): fn_save does not send the row status (insert/update/delete)",
+                            grids
+                        ));
+                    }
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} editable grid issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{GridIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const EDITABLE_XML: &str = r#"<grid name="grid_member" link_data="ds_member">
+  <column><data name="MEMBER_ID" editable="0"/></column>
+  <column><data name="MEMBER_NAME" editable="1"/></column>
+</grid>"#;
+
+    const SAVE_JS: &str = r#"this.fn_save = function() {
+    var changed = ds_member.getChangedData();
+    for (var i = 0; i < changed.length; i++) {
+        var status = ds_member.getRowType(changed[i]);
+        if (status == "I") { /* insert */ }
+    }
+    // TODO: save transaction
+};
+
+this.fn_delete = function() {};"#;
+
+    fn create_context(xml: &str, js: &str, editable: bool, mode: ExecutionMode) -> GenerationContext {
+        let mut grid = GridIntent::new("grid_member", "ds_member");
+        if editable {
+            grid = grid.editable();
+        }
+        let intent = UiIntent::new("member_list", ScreenType::List).with_grid(grid);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_read_only_grid_not_checked() {
+        let mut ctx = create_context("<grid/>", "", false, ExecutionMode::Strict);
+        assert!(matches!(EditableGridValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_changed_row_save_passes() {
+        let mut ctx = create_context(EDITABLE_XML, SAVE_JS, true, ExecutionMode::Strict);
+        assert!(matches!(EditableGridValidator::new().run(&mut ctx), PassResult::Ok));
+        assert!(EditableGridValidator::function_body(SAVE_JS, "fn_save").unwrap().contains("getRowType"));
+        assert!(!EditableGridValidator::function_body(SAVE_JS, "fn_save").unwrap().contains("fn_delete"));
+    }
+
+    #[test]
+    fn test_whole_dataset_save_flagged() {
+        let js = "this.fn_save = function() {\n    var ds = this.getDataset(\"ds_member\");\n};";
+        let xml = EDITABLE_XML.replace("editable=\"1\"", "editable=\"0\"");
+        let mut ctx = create_context(&xml, js, true, ExecutionMode::Relaxed);
+
+        assert!(EditableGridValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 4);
+        assert!(ctx.warnings[0].contains("editable=\"1\""));
+        assert!(ctx.warnings[1].contains("getChangedData"));
+
+        let mut ctx = create_context(EDITABLE_XML, "this.fn_search = function() {};", true, ExecutionMode::Strict);
+        assert!(EditableGridValidator::new().run(&mut ctx).is_error());
+    }
+}
@@ -0,0 +1,283 @@
+//! Pass 4i: Error Handling Validator
+//!
+//! Failed transactions must reach the user:
+//! - Every transaction callback (`fn_xxxCallback`) takes the error code,
+//!   checks it and shows the message with the standard alert helper
+//! - No empty `catch` blocks
+//! - No `catch` blocks that only write to the console
+//! - Error codes the script compares against come from the project's
+//!   error-code catalog (when one is uploaded)
+//!
+//! The helper and the callback naming come from the company rule set's
+//! `error_handling` section. Nothing is auto-fixed; the message wording and
+//! the recovery belong to the screen.
+
+use super::EditableGridValidator;
+use crate::domain::{CompanyRuleSections, ErrorCodeCatalog, ErrorHandlingRules};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+/// Error Handling Validator - transaction errors are checked and shown
+#[derive(Clone)]
+pub struct ErrorHandlingValidator {
+    rules: ErrorHandlingRules,
+    catalog: ErrorCodeCatalog,
+}
+
+impl ErrorHandlingValidator {
+    pub fn new() -> Self {
+        Self {
+            rules: ErrorHandlingRules::default(),
+            catalog: ErrorCodeCatalog::default(),
+        }
+    }
+
+    /// Conventions of a company rule set (defaults without an `error_handling` section)
+    pub fn for_rules(sections: Option<&CompanyRuleSections>) -> Self {
+        Self {
+            rules: sections
+                .and_then(|s| s.error_handling.clone())
+                .unwrap_or_default(),
+            catalog: ErrorCodeCatalog::default(),
+        }
+    }
+
+    /// Also flag error codes missing from the catalog (none are flagged when it is empty)
+    pub fn with_catalog(mut self, catalog: ErrorCodeCatalog) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// `(name, parameters)` of every function named like a transaction callback
+    fn callbacks(&self, js: &str) -> Vec<(String, Vec<String>)> {
+        let suffix = self.rules.callback_suffix.to_lowercase();
+        Regex::new(r#"\b(\w+)\s*=\s*function\s*\(([^)]*)\)|\bfunction\s+(\w+)\s*\(([^)]*)\)"#)
+            .unwrap()
+            .captures_iter(js)
+            .filter_map(|caps| {
+                let name = caps.get(1).or(caps.get(3))?.as_str().to_string();
+                let params = caps.get(2).or(caps.get(4))?.as_str();
+                let params = params
+                    .split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                name.to_lowercase().ends_with(&suffix).then_some((name, params))
+            })
+            .collect()
+    }
+
+    /// Parameter carrying the error code (`errCode`, `nErrorCode`, `code`, ...)
+    fn error_param(params: &[String]) -> Option<&str> {
+        params
+            .iter()
+            .map(String::as_str)
+            .find(|p| {
+                let lower = p.to_lowercase();
+                lower.contains("err") || lower.contains("code")
+            })
+    }
+
+    /// `(line, body)` of every catch block
+    fn catch_blocks(js: &str) -> Vec<(usize, &str)> {
+        Regex::new(r#"\bcatch\s*\([^)]*\)\s*\{"#)
+            .unwrap()
+            .find_iter(js)
+            .map(|m| {
+                let line = js[..m.start()].matches('\n').count() + 1;
+                let rest = &js[m.end()..];
+                let mut depth = 1;
+                let mut end = rest.len();
+                for (i, c) in rest.char_indices() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                end = i;
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                (line, &rest[..end])
+            })
+            .collect()
+    }
+
+    /// Statements of a block, without blank and comment lines
+    fn statements(body: &str) -> Vec<&str> {
+        body.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("//"))
+            .collect()
+    }
+}
+
+impl Default for ErrorHandlingValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for ErrorHandlingValidator {
+    fn name(&self) -> &'static str {
+        "ErrorHandlingValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let Some(js) = &ctx.javascript else {
+            return PassResult::Ok;
+        };
+
+        let mut findings = Vec::new();
+        let helper = &self.rules.alert_helper;
+
+        for (name, params) in self.callbacks(js) {
+            let body = EditableGridValidator::function_body(js, &name).unwrap_or_default();
+            match Self::error_param(&params) {
+                None => findings.push(format!("[JS] Transaction callback ({}) takes no error code", name)),
+                Some(param) => {
+                    let checked = Regex::new(&format!(r"\bif\s*\([^)]*\b{}\b", regex::escape(param)))
+                        .unwrap()
+                        .is_match(body);
+                    if !checked {
+                        findings.push(format!("[JS] Transaction callback ({}) does not check {}", name, param));
+                    }
+                }
+            }
+            if !Regex::new(&format!(r"\b{}\s*\(", regex::escape(helper))).unwrap().is_match(body) {
+                findings.push(format!(
+                    "[JS] Transaction callback ({}) does not show errors with {}()",
+                    name, helper
+                ));
+            }
+        }
+
+        for (line, body) in Self::catch_blocks(js) {
+            let statements = Self::statements(body);
+            if statements.is_empty() {
+                findings.push(format!("[JS] Empty catch block at line {}", line));
+            } else if statements.iter().all(|s| s.starts_with("console.")) {
+                findings.push(format!("[JS] Error path at line {} only logs to console", line));
+            }
+        }
+
+        for (line, code) in self.catalog.unknown_codes(js) {
+            findings.push(format!(
+                "[JS] Error code '{}' at line {} is not in the error-code catalog",
+                code, line
+            ));
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} error handling issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const HANDLED_JS: &str = r#"this.fn_search = function() {
+    // TODO: Replace with actual API endpoint
+};
+this.fn_searchCallback = function(svcId, errCode, errMsg) {
+    if (errCode < 0) {
+        gfn_alert(errMsg);
+        return;
+    }
+};
+this.fn_parse = function(text) {
+    try {
+        return JSON.parse(text);
+    } catch (e) {
+        gfn_alert("형식 오류");
+        return null;
+    }
+};"#;
+
+    fn create_context(js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    fn validator() -> ErrorHandlingValidator {
+        let sections = CompanyRuleSections {
+            error_handling: Some(ErrorHandlingRules {
+                alert_helper: "gfn_alert".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        ErrorHandlingValidator::for_rules(Some(&sections))
+    }
+
+    #[test]
+    fn test_handled_errors_pass() {
+        let mut ctx = create_context(HANDLED_JS, ExecutionMode::Strict);
+        assert!(matches!(validator().run(&mut ctx), PassResult::Ok));
+
+        // The default helper is alert()
+        let mut ctx = create_context(HANDLED_JS, ExecutionMode::Strict);
+        assert!(ErrorHandlingValidator::new().run(&mut ctx).is_error());
+    }
+
+    #[test]
+    fn test_unhandled_errors_flagged() {
+        let js = HANDLED_JS
+            .replace("    if (errCode < 0) {\n        gfn_alert(errMsg);\n        return;\n    }\n", "    console.log(errMsg);\n")
+            .replace("        gfn_alert(\"형식 오류\");\n        return null;\n", "        console.error(e);\n")
+            + "\nthis.fn_saveCallback = function(svcId) {\n    try { ds_list.clearData(); } catch (e) {}\n};";
+        let mut ctx = create_context(&js, ExecutionMode::Relaxed);
+
+        assert!(validator().run(&mut ctx).is_warning());
+        let warnings: Vec<&str> = ctx.warnings.iter().map(String::as_str).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "Warning: [JS] Transaction callback (fn_searchCallback) does not check errCode",
+                "Warning: [JS] Transaction callback (fn_searchCallback) does not show errors with gfn_alert()",
+                "Warning: [JS] Transaction callback (fn_saveCallback) takes no error code",
+                "Warning: [JS] Transaction callback (fn_saveCallback) does not show errors with gfn_alert()",
+                "Warning: [JS] Error path at line 10 only logs to console",
+                "Warning: [JS] Empty catch block at line 15",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_error_codes_flagged() {
+        let catalog = ErrorCodeCatalog::from_csv("code,message\nE1001,회원 없음\n").unwrap();
+        let js = HANDLED_JS.replace(
+            "    if (errCode < 0) {",
+            "    if (errCode == \"E1001\" || errCode == \"E1009\") {",
+        );
+
+        let mut ctx = create_context(&js, ExecutionMode::Relaxed);
+        assert!(matches!(validator().run(&mut ctx), PassResult::Ok));
+
+        let mut ctx = create_context(&js, ExecutionMode::Relaxed);
+        assert!(validator().with_catalog(catalog).run(&mut ctx).is_warning());
+        assert_eq!(
+            ctx.warnings,
+            vec!["Warning: [JS] Error code 'E1009' at line 5 is not in the error-code catalog".to_string()]
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Pass 4j: Format Validator
+//!
+//! Enforces the locale date/number formats of the intent's columns:
+//! - Every `datepicker` and grid `<data>` bound to a date column carries its
+//!   `date_format`; every `numericex_field` and grid `<data>` bound to a
+//!   numeric column carries its number `pattern`; every `normal_field` and
+//!   grid `<data>` bound to a column with an input mask (column archetypes)
+//!   carries it as `pattern`. Relaxed mode sets them.
+//! - Grid `<data>` of a numeric column is right-aligned (`text_horzalign="2"`).
+//! - The decimal places a control shows must match the column's schema scale
+//!   (`DECIMAL(15,2)` → `#,##0.00`); relaxed mode sets the schema's.
+//! - Formats must suit the declared data types: a date format on a numeric
+//!   column, a `datepicker` bound to a text column or a `numericex_field`
+//!   bound to a date column is reported, never fixed.
+//!
+//! No-op when no column has a format (no `locale` option, `formats` rules or
+//! decimal scale).
+
+use crate::domain::{ColumnIntent, DataType};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+/// Format Validator - propagates and checks column display formats
+pub struct FormatValidator;
+
+impl FormatValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read an attribute value from a tag's attribute string
+    pub(crate) fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+        let re = Regex::new(&format!(r#"\b{}\s*=\s*"([^"]*)""#, regex::escape(name))).unwrap();
+        re.captures(attrs).map(|cap| cap.get(1).unwrap().as_str())
+    }
+
+    /// Add or replace an attribute
+    pub(crate) fn set_attr(attrs: &str, name: &str, value: &str) -> String {
+        let re = Regex::new(&format!(r#"\b{}\s*=\s*"[^"]*""#, regex::escape(name))).unwrap();
+        if re.is_match(attrs) {
+            return re.replace(attrs, format!(r#"{}="{}""#, name, value).as_str()).into_owned();
+        }
+        let trimmed = attrs.trim_end();
+        match trimmed.strip_suffix('/') {
+            Some(body) => format!(r#"{} {}="{}"/"#, body.trim_end(), name, value),
+            None => format!(r#"{} {}="{}""#, trimmed, name, value),
+        }
+    }
+
+    /// Column a control is bound to (`link_data="ds:COL"`, or `name` on grid `<data>`)
+    pub(crate) fn bound_column(tag: &str, attrs: &str) -> Option<String> {
+        if let Some((_, column)) = Self::attr(attrs, "link_data").and_then(|l| l.split_once(':')) {
+            return Some(column.to_uppercase());
+        }
+        if tag == "data" {
+            return Self::attr(attrs, "name").map(str::to_uppercase);
+        }
+        None
+    }
+
+    fn is_date(data_type: DataType) -> bool {
+        matches!(data_type, DataType::Date | DataType::DateTime)
+    }
+
+    fn is_number(data_type: DataType) -> bool {
+        matches!(data_type, DataType::Integer | DataType::Decimal)
+    }
+
+    /// Number patterns (`#,##0.00`), not masks (`######-#******`)
+    fn is_number_pattern(pattern: &str) -> bool {
+        pattern.contains('0') && pattern.chars().all(|c| matches!(c, '#' | ',' | '.' | '0'))
+    }
+
+    /// Digits after the decimal point of a number pattern (`#,##0.00` → 2)
+    fn pattern_decimals(pattern: &str) -> u32 {
+        pattern
+            .split_once('.')
+            .map(|(_, decimals)| decimals.chars().filter(|c| matches!(c, '0' | '#')).count() as u32)
+            .unwrap_or(0)
+    }
+
+    /// A control's number pattern showing other decimal places than the schema scale
+    fn scale_finding(tag: &str, pattern: Option<&str>, column: &ColumnIntent) -> Option<String> {
+        let scale = column.scale?;
+        let shown = Self::pattern_decimals(pattern.filter(|p| Self::is_number_pattern(p))?);
+        (shown != scale).then(|| {
+            format!(
+                "[XML] <{}> bound to '{}' shows {} decimal place(s) but the schema scale is {}",
+                tag, column.name, shown, scale
+            )
+        })
+    }
+
+    /// Type mismatches of one bound control (never fixed)
+    fn type_findings(tag: &str, attrs: &str, column: &ColumnIntent) -> Vec<String> {
+        let data_type = column.data_type;
+        let mut findings = Vec::new();
+        if tag == "datepicker" && !Self::is_date(data_type) {
+            findings.push(format!(
+                "[XML] <datepicker> is bound to '{}' of data type {}",
+                column.name,
+                data_type.as_str()
+            ));
+        }
+        if tag == "numericex_field" && !Self::is_number(data_type) {
+            findings.push(format!(
+                "[XML] <numericex_field> is bound to '{}' of data type {}",
+                column.name,
+                data_type.as_str()
+            ));
+        }
+        if Self::attr(attrs, "date_format").is_some() && !Self::is_date(data_type) {
+            findings.push(format!(
+                "[XML] <{}> bound to '{}' has a date_format but data type {}",
+                tag,
+                column.name,
+                data_type.as_str()
+            ));
+        }
+        if column.sensitivity.is_none()
+            && Self::attr(attrs, "pattern").is_some_and(Self::is_number_pattern)
+            && !Self::is_number(data_type)
+        {
+            findings.push(format!(
+                "[XML] <{}> bound to '{}' has a number pattern but data type {}",
+                tag,
+                column.name,
+                data_type.as_str()
+            ));
+        }
+        findings
+    }
+
+    /// Check bound controls; returns the (possibly formatted) XML and findings
+    fn check_bindings(xml: &str, columns: &HashMap<String, &ColumnIntent>, fix: bool) -> (String, Vec<String>) {
+        let tag_re = Regex::new(r#"<(\w+)(\s[^>]*)>"#).unwrap();
+        let mut findings = Vec::new();
+
+        let fixed = tag_re.replace_all(xml, |cap: &Captures| {
+            let tag = cap[1].to_lowercase();
+            let attrs = &cap[2];
+            if !matches!(tag.as_str(), "datepicker" | "numericex_field" | "normal_field" | "data") {
+                return cap[0].to_string();
+            }
+            let Some(column) = Self::bound_column(&tag, attrs).and_then(|col| columns.get(&col).copied()) else {
+                return cap[0].to_string();
+            };
+
+            let type_findings = Self::type_findings(&tag, attrs, column);
+            if !type_findings.is_empty() {
+                findings.extend(type_findings);
+                return cap[0].to_string();
+            }
+
+            let Some(format) = &column.format else {
+                return cap[0].to_string();
+            };
+            let mut expected: Vec<(&str, String)> =
+                match (tag.as_str(), &format.date_format, format.number_pattern()) {
+                    ("datepicker" | "data", Some(date), _) => Some(("date_format", date.clone())),
+                    ("numericex_field" | "data", None, Some(pattern)) => Some(("pattern", pattern)),
+                    ("normal_field" | "data", None, None) => format.mask.clone().map(|mask| ("pattern", mask)),
+                    _ => None,
+                }
+                .into_iter()
+                .collect();
+            if tag == "data" && Self::is_number(column.data_type) && format.is_numeric() {
+                expected.push(("text_horzalign", "2".to_string()));
+            }
+
+            let mut fixed = attrs.to_string();
+            let mut changed = false;
+            for (name, value) in expected {
+                let current = Self::attr(&fixed, name).map(str::to_string);
+                if current.as_deref() == Some(value.as_str()) {
+                    continue;
+                }
+                let scale_finding = match name {
+                    "pattern" => Self::scale_finding(&tag, current.as_deref(), column),
+                    _ => None,
+                };
+                findings.push(scale_finding.unwrap_or_else(|| {
+                    format!("[XML] <{}> bound to '{}' should use {}=\"{}\"", tag, column.name, name, value)
+                }));
+                if fix {
+                    fixed = Self::set_attr(&fixed, name, &value);
+                    changed = true;
+                }
+            }
+
+            if changed {
+                format!("<{}{}>", &cap[1], fixed)
+            } else {
+                cap[0].to_string()
+            }
+        });
+
+        (fixed.into_owned(), findings)
+    }
+}
+
+impl Default for FormatValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for FormatValidator {
+    fn name(&self) -> &'static str {
+        "FormatValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let formatted = ctx.intent.formatted_columns();
+        if formatted.is_empty() {
+            return PassResult::Ok;
+        }
+
+        let mut findings: Vec<String> = formatted
+            .iter()
+            .filter_map(|c| {
+                let problem = c.format.as_ref()?.type_mismatch(c.data_type)?;
+                Some(format!("[Intent] Column '{}': {}", c.name, problem))
+            })
+            .collect();
+        for column in &formatted {
+            let decimals = column.format.as_ref().and_then(|f| f.decimal_places);
+            if let (Some(scale), Some(decimals)) = (column.scale, decimals) {
+                if decimals != scale {
+                    findings.push(format!(
+                        "[Intent] Column '{}': {} decimal place(s) but the schema scale is {}",
+                        column.name, decimals, scale
+                    ));
+                }
+            }
+        }
+
+        let columns: HashMap<String, &ColumnIntent> = ctx
+            .intent
+            .datasets
+            .iter()
+            .flat_map(|ds| ds.columns.iter())
+            .map(|c| (c.name.to_uppercase(), c))
+            .collect();
+        let fix = !ctx.is_strict();
+
+        if let Some(xml) = &ctx.xml {
+            let (fixed, xml_findings) = Self::check_bindings(xml, &columns, fix);
+            findings.extend(xml_findings);
+            ctx.xml = Some(fixed);
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} format issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnFormat, DatasetIntent, LocaleFormats, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    fn create_context(xml: &str, mode: ExecutionMode) -> GenerationContext {
+        let mut intent = UiIntent::new("order_list", ScreenType::List).with_dataset(
+            DatasetIntent::new("ds_order").with_columns(vec![
+                ColumnIntent::new("ORDER_DATE", "주문일").with_data_type(DataType::Date),
+                ColumnIntent::new("TOTAL_AMT", "합계").with_data_type(DataType::Decimal),
+                ColumnIntent::new("MEMO", "메모"),
+            ]),
+        );
+        intent.apply_formats(&LocaleFormats::resolve(Some("ko-KR"), None).unwrap());
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx
+    }
+
+    const XML: &str = r#"<screen>
+<datepicker control_id="1" name="field_order_date" link_data="ds_order:ORDER_DATE" date_format="yyyy/MM/dd"/>
+<numericex_field control_id="2" name="field_total_amt" link_data="ds_order:TOTAL_AMT"/>
+<grid control_id="3" name="grid_order" link_data="ds_order">
+  <column><header title="주문일"/><data name="ORDER_DATE" link_data="ds_order:ORDER_DATE" date_format="yyyy-MM-dd"/></column>
+</grid>
+</screen>"#;
+
+    #[test]
+    fn test_relaxed_sets_formats() {
+        let mut ctx = create_context(XML, ExecutionMode::Relaxed);
+        assert!(FormatValidator::new().run(&mut ctx).is_warning());
+
+        let xml = ctx.xml.unwrap();
+        assert!(xml.contains(r#"link_data="ds_order:ORDER_DATE" date_format="yyyy-MM-dd"/>"#));
+        assert!(xml.contains(r##"link_data="ds_order:TOTAL_AMT" pattern="#,##0"/>"##));
+        assert_eq!(ctx.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_strict_reports_type_mismatches() {
+        let xml = r#"<datepicker control_id="1" name="field_memo" link_data="ds_order:MEMO"/>"#;
+        let mut ctx = create_context(xml, ExecutionMode::Strict);
+        ctx.intent.datasets[0].columns[2].format = Some(ColumnFormat::number(true, 0));
+
+        match FormatValidator::new().run(&mut ctx) {
+            PassResult::Error(e) => assert_eq!(
+                e,
+                "[Intent] Column 'MEMO': number format for data type string; \
+                 [XML] <datepicker> is bound to 'MEMO' of data type string"
+            ),
+            other => panic!("expected error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decimal_scale_and_grid_alignment() {
+        let xml = r##"<numericex_field control_id="2" name="field_total_amt" link_data="ds_order:TOTAL_AMT" pattern="#,##0.000"/>
+<grid control_id="3" name="grid_order" link_data="ds_order">
+  <column><header title="합계"/><data name="TOTAL_AMT" link_data="ds_order:TOTAL_AMT" pattern="#,##0.00"/></column>
+</grid>"##;
+        let mut intent = UiIntent::new("order_list", ScreenType::List).with_dataset(
+            DatasetIntent::new("ds_order").with_columns(vec![ColumnIntent::new("TOTAL_AMT", "합계")
+                .with_data_type(DataType::Decimal)
+                .with_scale(2)]),
+        );
+        intent.apply_scale_formats();
+        let mut ctx = GenerationContext::new("".to_string(), intent, ExecutionMode::Relaxed);
+        ctx.xml = Some(xml.to_string());
+        assert!(FormatValidator::new().run(&mut ctx).is_warning());
+
+        let fixed = ctx.xml.unwrap();
+        assert!(fixed.contains(r##"link_data="ds_order:TOTAL_AMT" pattern="#,##0.00"/>"##));
+        assert!(fixed.contains(r##"pattern="#,##0.00" text_horzalign="2"/></column>"##));
+        assert_eq!(
+            ctx.warnings,
+            vec![
+                "Warning: [XML] <numericex_field> bound to 'TOTAL_AMT' shows 3 decimal place(s) but the schema scale is 2",
+                "Warning: [XML] <data> bound to 'TOTAL_AMT' should use text_horzalign=\"2\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_formats_is_noop() {
+        let mut ctx = create_context(XML, ExecutionMode::Strict);
+        for column in &mut ctx.intent.datasets[0].columns {
+            column.format = None;
+        }
+        assert!(matches!(FormatValidator::new().run(&mut ctx), PassResult::Ok));
+        assert_eq!(ctx.xml.as_deref(), Some(XML));
+    }
+}
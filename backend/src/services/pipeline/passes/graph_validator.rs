@@ -16,7 +16,7 @@ impl GraphValidator {
     }
 
     /// Extract dataset IDs from XML
-    fn extract_datasets(&self, xml: &str) -> HashSet<String> {
+    pub(crate) fn extract_datasets(&self, xml: &str) -> HashSet<String> {
         let mut datasets = HashSet::new();
 
         // Match <xdataset id="..."> or <xlinkdataset id="..."> or <dataset id="...">
@@ -29,7 +29,7 @@ impl GraphValidator {
     }
 
     /// Extract link_data references from XML
-    fn extract_link_data_refs(&self, xml: &str) -> Vec<(String, String)> {
+    pub(crate) fn extract_link_data_refs(&self, xml: &str) -> Vec<(String, String)> {
         let mut refs = Vec::new();
 
         // Match link_data="ds_xxx" or link_data="ds_xxx:COLUMN"
@@ -0,0 +1,394 @@
+//! Pass 2b: Identifier Normalizer
+//!
+//! Models sometimes carry over the entity of an example or an earlier
+//! screen: SCREEN_MEMBER_LIST when the intent asked for customer_list, with
+//! ds_member and grid_member to match. When the emitted screen id shares the
+//! intent's type suffix but not its entity, the entity words are swapped
+//! everywhere they form part of the screen id, a dataset id, a grid id or a
+//! referenced file name (`member_list.js`), in the XML, the JavaScript and
+//! any additional screens of the response. Intent datasets and grids that
+//! are still missing afterwards are paired with the one unknown id left, if
+//! there is exactly one.
+//!
+//! Screens that share no type suffix with the intent (a popup next to a
+//! list screen) are left alone. Each rename is recorded as a note.
+
+use regex::Regex;
+
+use crate::domain::UiIntent;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+
+/// Identifier Normalizer - aligns screen, dataset and grid ids with the intent
+pub struct IdentifierNormalizer;
+
+/// Entity words to replace, as lowercase token sequences
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EntityMapping {
+    from: Vec<String>,
+    to: Vec<String>,
+}
+
+impl EntityMapping {
+    /// Apply to an identifier, keeping its case style (`ds_member` → `ds_customer`,
+    /// `SCREEN_MEMBER_LIST` → `SCREEN_CUSTOMER_LIST`)
+    fn apply(&self, id: &str) -> String {
+        let tokens: Vec<&str> = id.split('_').collect();
+        let lower: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+
+        // Already renamed (`to` extends `from`, e.g. order → order_detail)
+        if lower.windows(self.to.len()).any(|w| w == self.to.as_slice()) {
+            return id.to_string();
+        }
+        let Some(start) = lower.windows(self.from.len()).position(|w| w == self.from.as_slice()) else {
+            return id.to_string();
+        };
+
+        let upper = tokens[start].chars().any(|c| c.is_ascii_uppercase())
+            && !tokens[start].chars().any(|c| c.is_ascii_lowercase());
+        let replacement = self.to.iter().map(|t| if upper { t.to_uppercase() } else { t.clone() });
+
+        tokens[..start]
+            .iter()
+            .map(|t| t.to_string())
+            .chain(replacement)
+            .chain(tokens[start + self.from.len()..].iter().map(|t| t.to_string()))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+}
+
+impl IdentifierNormalizer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lowercase words of a screen name, without a `SCREEN_`/`SCR_` prefix
+    fn screen_tokens(name: &str) -> Vec<String> {
+        let name = name.to_lowercase().replace('-', "_");
+        let name = name
+            .strip_prefix("screen_")
+            .or_else(|| name.strip_prefix("scr_"))
+            .unwrap_or(&name);
+        name.split('_').filter(|t| !t.is_empty()).map(str::to_string).collect()
+    }
+
+    /// Entity mapping from the emitted screen id to the intent's screen name
+    ///
+    /// Requires a shared last word (the screen type, e.g. `list`) and a
+    /// differing entity before it.
+    fn entity_mapping(screen_id: &str, intent: &UiIntent) -> Option<EntityMapping> {
+        let emitted = Self::screen_tokens(screen_id);
+        let expected = Self::screen_tokens(&intent.screen_name);
+        let (emitted_type, from) = emitted.split_last()?;
+        let (expected_type, to) = expected.split_last()?;
+
+        if emitted_type != expected_type || from.is_empty() || to.is_empty() || from == to {
+            return None;
+        }
+        Some(EntityMapping {
+            from: from.to_vec(),
+            to: to.to_vec(),
+        })
+    }
+
+    /// `id` attribute of the `<screen>` element
+    fn screen_id(xml: &str) -> Option<String> {
+        Regex::new(r#"(?i)<screen\b[^>]*?\bid\s*=\s*"([^"]+)""#)
+            .unwrap()
+            .captures(xml)
+            .map(|cap| cap[1].to_string())
+    }
+
+    /// Dataset ids declared in the XML
+    fn dataset_ids(xml: &str) -> Vec<String> {
+        let re = Regex::new(r#"<(?:x?(?:link)?dataset|Dataset)\s+[^>]*id="([^"]+)""#).unwrap();
+        Self::unique(re.captures_iter(xml).map(|cap| cap[1].to_string()))
+    }
+
+    /// Grid names/ids declared in the XML
+    fn grid_ids(xml: &str) -> Vec<String> {
+        let re = Regex::new(r#"(?i)<grid\b[^>]*?\b(?:name|id)="([^"]+)""#).unwrap();
+        Self::unique(re.captures_iter(xml).map(|cap| cap[1].to_string()))
+    }
+
+    /// Stems of `.xml`/`.js` file names referenced in the code
+    fn file_stems(text: &str) -> Vec<String> {
+        let re = Regex::new(r#"\b(\w+)\.(?:xml|js)\b"#).unwrap();
+        Self::unique(re.captures_iter(text).map(|cap| cap[1].to_string()))
+    }
+
+    fn unique(ids: impl Iterator<Item = String>) -> Vec<String> {
+        let mut seen = Vec::new();
+        for id in ids {
+            if !seen.contains(&id) {
+                seen.push(id);
+            }
+        }
+        seen
+    }
+
+    /// Replace a whole identifier in the text
+    fn rename(text: &str, from: &str, to: &str) -> String {
+        Regex::new(&format!(r"\b{}\b", regex::escape(from)))
+            .unwrap()
+            .replace_all(text, regex::NoExpand(to))
+            .into_owned()
+    }
+
+    /// Renames for one screen under the mapping, as `(kind, from, to)`
+    fn mapped_renames(xml: &str, js: &str, mapping: &EntityMapping) -> Vec<(&'static str, String, String)> {
+        let mut ids: Vec<(&'static str, String)> = Vec::new();
+        ids.extend(Self::screen_id(xml).map(|id| ("screen id", id)));
+        ids.extend(Self::dataset_ids(xml).into_iter().map(|id| ("dataset id", id)));
+        ids.extend(Self::grid_ids(xml).into_iter().map(|id| ("grid id", id)));
+        ids.extend(Self::file_stems(xml).into_iter().map(|id| ("file name", id)));
+        ids.extend(Self::file_stems(js).into_iter().map(|id| ("file name", id)));
+
+        let mut renames: Vec<(&'static str, String, String)> = Vec::new();
+        for (kind, id) in ids {
+            let renamed = mapping.apply(&id);
+            if renamed != id && !renames.iter().any(|(_, from, _)| *from == id) {
+                renames.push((kind, id, renamed));
+            }
+        }
+        renames
+    }
+
+    /// Pair intent ids missing from the XML with the single unknown id left
+    fn paired_rename(
+        kind: &'static str,
+        expected: Vec<&str>,
+        emitted: &[String],
+    ) -> Option<(&'static str, String, String)> {
+        let missing: Vec<&str> = expected
+            .iter()
+            .copied()
+            .filter(|id| !emitted.iter().any(|e| e == id))
+            .collect();
+        let unknown: Vec<&String> = emitted
+            .iter()
+            .filter(|e| !expected.contains(&e.as_str()))
+            .collect();
+
+        match (missing.as_slice(), unknown.as_slice()) {
+            ([to], [from]) => Some((kind, from.to_string(), to.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl Default for IdentifierNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for IdentifierNormalizer {
+    fn name(&self) -> &'static str {
+        "IdentifierNormalizer"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let (Some(xml), Some(js)) = (ctx.xml.clone(), ctx.javascript.clone()) else {
+            return PassResult::Ok;
+        };
+
+        let screen_id = Self::screen_id(&xml);
+        let mapping = screen_id
+            .as_deref()
+            .and_then(|id| Self::entity_mapping(id, &ctx.intent));
+        let is_intent_screen = mapping.is_some()
+            || screen_id.as_deref().is_none_or(|id| {
+                Self::screen_tokens(id) == Self::screen_tokens(&ctx.intent.screen_name)
+            });
+        if !is_intent_screen {
+            return PassResult::Ok;
+        }
+
+        let mut renames = match &mapping {
+            Some(mapping) => Self::mapped_renames(&xml, &js, mapping),
+            None => Vec::new(),
+        };
+        let renamed = |id: &String| {
+            renames
+                .iter()
+                .find(|(_, from, _)| from == id)
+                .map(|(_, _, to)| to.clone())
+                .unwrap_or_else(|| id.clone())
+        };
+        let datasets: Vec<String> = Self::dataset_ids(&xml).iter().map(renamed).collect();
+        let grids: Vec<String> = Self::grid_ids(&xml).iter().map(renamed).collect();
+        let paired = [
+            Self::paired_rename(
+                "dataset id",
+                ctx.intent.datasets.iter().map(|d| d.id.as_str()).collect(),
+                &datasets,
+            ),
+            Self::paired_rename(
+                "grid id",
+                ctx.intent.grids.iter().map(|g| g.id.as_str()).collect(),
+                &grids,
+            ),
+        ];
+        for (kind, from, to) in paired.into_iter().flatten() {
+            // Chain onto an entity rename of the same id
+            match renames.iter_mut().find(|(_, _, current)| *current == from) {
+                Some(rename) => rename.2 = to,
+                None => renames.push((kind, from, to)),
+            }
+        }
+
+        if renames.is_empty() {
+            return PassResult::Ok;
+        }
+
+        let apply = |text: &str| {
+            renames
+                .iter()
+                .fold(text.to_string(), |text, (_, from, to)| Self::rename(&text, from, to))
+        };
+        ctx.xml = Some(apply(&xml));
+        ctx.javascript = Some(apply(&js));
+
+        // Other screens of the response carry the same wrong entity
+        if let Some(mapping) = &mapping {
+            for screen in &mut ctx.additional_screens {
+                for (kind, from, to) in Self::mapped_renames(&screen.xml, &screen.javascript, mapping) {
+                    screen.xml = Self::rename(&screen.xml, &from, &to);
+                    screen.javascript = Self::rename(&screen.javascript, &from, &to);
+                    if kind == "screen id" {
+                        screen.screen_id = Some(to.clone());
+                    }
+                    renames.push((kind, from, to));
+                }
+            }
+        }
+
+        for (kind, from, to) in &renames {
+            tracing::info!("Renamed {} '{}' to '{}'", kind, from, to);
+            ctx.add_warning(format!("Note: Renamed {} '{}' to '{}' to match the intent", kind, from, to));
+        }
+
+        PassResult::Ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DatasetIntent, GridIntent, ScreenType};
+    use crate::services::pipeline::{ExecutionMode, ScreenSection};
+
+    fn create_context(xml: &str, js: &str) -> GenerationContext {
+        let intent = UiIntent::new("customer_list", ScreenType::List)
+            .with_dataset(DatasetIntent::new("ds_customer"))
+            .with_grid(GridIntent::new("grid_customer", "ds_customer"));
+        let mut ctx = GenerationContext::new(String::new(), intent, ExecutionMode::Relaxed);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_entity_mapping_apply() {
+        let mapping = EntityMapping {
+            from: vec!["member".to_string()],
+            to: vec!["customer".to_string()],
+        };
+        assert_eq!(mapping.apply("SCREEN_MEMBER_LIST"), "SCREEN_CUSTOMER_LIST");
+        assert_eq!(mapping.apply("ds_member"), "ds_customer");
+        assert_eq!(mapping.apply("grid_member_list"), "grid_customer_list");
+        assert_eq!(mapping.apply("ds_search"), "ds_search");
+
+        let mapping = EntityMapping {
+            from: vec!["order".to_string()],
+            to: vec!["order".to_string(), "detail".to_string()],
+        };
+        assert_eq!(mapping.apply("ds_order"), "ds_order_detail");
+        assert_eq!(mapping.apply("ds_order_detail"), "ds_order_detail");
+    }
+
+    #[test]
+    fn test_entity_mapping_needs_shared_type() {
+        let intent = UiIntent::new("customer_list", ScreenType::List);
+        assert!(IdentifierNormalizer::entity_mapping("SCREEN_MEMBER_LIST", &intent).is_some());
+        assert!(IdentifierNormalizer::entity_mapping("SCREEN_CUSTOMER_LIST", &intent).is_none());
+        assert!(IdentifierNormalizer::entity_mapping("SCREEN_MEMBER_POP", &intent).is_none());
+    }
+
+    #[test]
+    fn test_renames_wrong_entity() {
+        let xml = r#"<screen id="SCREEN_MEMBER_LIST">
+  <xlinkdataset id="ds_member" columns="ID:string"/>
+  <xlinkdataset id="ds_search" columns="KEYWORD:string"/>
+  <grid name="grid_member" link_data="ds_member"/>
+</screen>"#;
+        let js = r#"this.fn_search = function() {
+  ds_member.clear();
+  this.grid_member.refresh();
+  screen.loadportletpopup("pop", "/member_list_pop.xml");
+};"#;
+
+        let mut ctx = create_context(xml, js);
+        let result = IdentifierNormalizer::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        let xml = ctx.xml.unwrap();
+        let js = ctx.javascript.unwrap();
+        assert!(xml.contains(r#"id="SCREEN_CUSTOMER_LIST""#));
+        assert!(xml.contains(r#"<xlinkdataset id="ds_customer""#));
+        assert!(xml.contains(r#"id="ds_search""#));
+        assert!(xml.contains(r#"link_data="ds_customer""#));
+        assert!(js.contains("ds_customer.clear()"));
+        assert!(js.contains("this.grid_customer.refresh()"));
+        assert!(js.contains("/customer_list_pop.xml"));
+        assert!(!js.contains("member"));
+        assert!(ctx.warnings.iter().any(|w| w.contains("'ds_member' to 'ds_customer'")));
+        assert!(ctx.warnings.iter().all(|w| w.starts_with("Note:")));
+    }
+
+    #[test]
+    fn test_pairs_single_unknown_dataset() {
+        let xml = r#"<screen id="SCREEN_CUSTOMER_LIST">
+  <xlinkdataset id="ds_list" columns="ID:string"/>
+  <grid name="grid_customer" link_data="ds_list"/>
+</screen>"#;
+        let js = "this.fn_search = function() { ds_list.clear(); };";
+
+        let mut ctx = create_context(xml, js);
+        IdentifierNormalizer::new().run(&mut ctx);
+
+        assert!(ctx.xml.unwrap().contains(r#"link_data="ds_customer""#));
+        assert!(ctx.javascript.unwrap().contains("ds_customer.clear()"));
+        assert_eq!(ctx.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_leaves_other_screens_alone() {
+        let xml = r#"<screen id="SCREEN_MEMBER_POP"><xlinkdataset id="ds_pop"/></screen>"#;
+
+        let mut ctx = create_context(xml, "this.fn_select = function() {};");
+        IdentifierNormalizer::new().run(&mut ctx);
+
+        assert_eq!(ctx.xml.unwrap(), xml);
+        assert!(ctx.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_renames_in_additional_screens() {
+        let xml = r#"<screen id="SCREEN_MEMBER_LIST"><xlinkdataset id="ds_customer"/></screen>"#;
+
+        let mut ctx = create_context(xml, "this.fn_search = function() {};");
+        ctx.additional_screens.push(ScreenSection {
+            screen_id: Some("SCREEN_MEMBER_POP".to_string()),
+            xml: r#"<screen id="SCREEN_MEMBER_POP"><xlinkdataset id="ds_member_pop"/></screen>"#.to_string(),
+            javascript: "ds_member_pop.clear();".to_string(),
+        });
+        IdentifierNormalizer::new().run(&mut ctx);
+
+        let popup = &ctx.additional_screens[0];
+        assert_eq!(popup.screen_id.as_deref(), Some("SCREEN_CUSTOMER_POP"));
+        assert!(popup.xml.contains("ds_customer_pop"));
+        assert_eq!(popup.javascript, "ds_customer_pop.clear();");
+    }
+}
@@ -0,0 +1,318 @@
+//! Pass 4k: Label Language Validator
+//!
+//! Some models switch captions or messages to English although the screen is
+//! Korean. The visible text of the screen is checked against the intent's
+//! locale (`ko-KR` when unset):
+//! - Captions: `text` attributes, grid `<header title>` and `<text>` bodies
+//! - Messages: string literals passed to `alert`/`confirm` calls in the JS
+//!
+//! Korean screens need mostly Hangul (short all-caps words like `ID` or `PDF`
+//! don't count); screens of other locales must not contain Hangul. Relaxed
+//! mode translates English button captions of Korean screens (the label of
+//! the button's action, else the label dictionary); everything else is only
+//! reported.
+
+use crate::domain::{ActionIntent, DEFAULT_LOCALE};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use crate::services::screen_dsl::ACTION_SHORTHANDS;
+use regex::{Captures, Regex};
+
+/// Korean captions need at least this share of Hangul letters
+const MIN_HANGUL_RATIO: f32 = 0.5;
+
+/// Fewer Latin letters than this are never a violation (`No`, `%s`)
+const MIN_LATIN_LETTERS: usize = 3;
+
+/// English button captions and their Korean labels, besides the action
+/// shorthands (`search` → `조회`, ...)
+const BUTTON_CAPTIONS: &[(&str, &str)] = &[
+    ("find", "조회"),
+    ("inquiry", "조회"),
+    ("query", "조회"),
+    ("new", "신규"),
+    ("create", "신규"),
+    ("remove", "삭제"),
+    ("edit", "수정"),
+    ("modify", "수정"),
+    ("update", "수정"),
+    ("cancel", "취소"),
+    ("ok", "확인"),
+    ("confirm", "확인"),
+    ("select", "선택"),
+    ("reset", "초기화"),
+    ("clear", "초기화"),
+    ("excel", "엑셀"),
+    ("export to excel", "엑셀"),
+    ("download", "다운로드"),
+    ("upload", "업로드"),
+    ("reload", "새로고침"),
+    ("prev", "이전"),
+    ("previous", "이전"),
+    ("back", "이전"),
+    ("next", "다음"),
+    ("finish", "완료"),
+    ("complete", "완료"),
+    ("apply", "적용"),
+    ("approve", "승인"),
+    ("reject", "반려"),
+];
+
+/// Label Language Validator - keeps captions and messages in the screen's language
+pub struct LabelLanguageValidator;
+
+impl LabelLanguageValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read an attribute value from a tag's attribute string
+    fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+        let re = Regex::new(&format!(r#"\b{}\s*=\s*"([^"]*)""#, regex::escape(name))).unwrap();
+        re.captures(attrs).map(|cap| cap.get(1).unwrap().as_str())
+    }
+
+    /// Replace an existing attribute
+    fn set_attr(attrs: &str, name: &str, value: &str) -> String {
+        let re = Regex::new(&format!(r#"\b{}\s*=\s*"[^"]*""#, regex::escape(name))).unwrap();
+        re.replace(attrs, format!(r#"{}="{}""#, name, value).as_str()).into_owned()
+    }
+
+    fn is_hangul(c: char) -> bool {
+        matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+    }
+
+    /// Abbreviations kept in Korean captions (`ID`, `PDF`, `URL2`)
+    fn is_acronym(word: &str) -> bool {
+        word.len() <= 5
+            && word.chars().any(|c| c.is_ascii_uppercase())
+            && word.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    }
+
+    fn is_korean(locale: &str) -> bool {
+        locale.to_lowercase().starts_with("ko")
+    }
+
+    /// Whether the text is written in another language than the locale's
+    fn language_mismatch(text: &str, korean: bool) -> bool {
+        let (mut hangul, mut latin) = (0usize, 0usize);
+        let words = text.split(|c: char| !c.is_alphanumeric()).filter(|w| !Self::is_acronym(w));
+        for c in words.flat_map(str::chars) {
+            if Self::is_hangul(c) {
+                hangul += 1;
+            } else if c.is_ascii_alphabetic() {
+                latin += 1;
+            }
+        }
+
+        if !korean {
+            return hangul > 0;
+        }
+        latin >= MIN_LATIN_LETTERS && (hangul as f32) / ((hangul + latin) as f32) < MIN_HANGUL_RATIO
+    }
+
+    /// Korean caption of an English button: its action's label, else the dictionary
+    fn translate(caption: &str, name: Option<&str>, actions: &[ActionIntent]) -> Option<String> {
+        let from_action = name.and_then(|name| {
+            actions
+                .iter()
+                .find(|a| name.eq_ignore_ascii_case(&format!("btn_{}", a.id)))
+                .filter(|a| !Self::language_mismatch(&a.label, true))
+                .map(|a| a.label.clone())
+        });
+        if from_action.is_some() {
+            return from_action;
+        }
+
+        let key = caption.trim().trim_end_matches("...").trim().to_lowercase();
+        ACTION_SHORTHANDS
+            .iter()
+            .map(|(id, label, _)| (*id, *label))
+            .chain(BUTTON_CAPTIONS.iter().copied())
+            .find(|(english, _)| *english == key)
+            .map(|(_, label)| label.to_string())
+    }
+
+    /// Check captions; returns the (possibly translated) XML and findings
+    fn check_xml(
+        xml: &str,
+        locale: &str,
+        actions: &[ActionIntent],
+        fix: bool,
+    ) -> (String, Vec<String>) {
+        let korean = Self::is_korean(locale);
+        let tag_re = Regex::new(r#"<(\w+)(\s[^>]*)>"#).unwrap();
+        let body_re = Regex::new(r#"<text\b[^>]*>([^<]+)</text>"#).unwrap();
+        let mut findings = Vec::new();
+
+        let fixed = tag_re.replace_all(xml, |cap: &Captures| {
+            let tag = cap[1].to_lowercase();
+            let attrs = &cap[2];
+            let caption_attr = if tag == "header" { "title" } else { "text" };
+            let Some(caption) = Self::attr(attrs, caption_attr) else {
+                return cap[0].to_string();
+            };
+            if !Self::language_mismatch(caption, korean) {
+                return cap[0].to_string();
+            }
+
+            let name = Self::attr(attrs, "name");
+            let control = match name {
+                Some(name) => format!("<{} name=\"{}\">", tag, name),
+                None => format!("<{}>", tag),
+            };
+            let translated = if fix && korean && tag == "pushbutton" {
+                Self::translate(caption, name, actions)
+            } else {
+                None
+            };
+            match translated {
+                Some(label) => {
+                    findings.push(format!("[XML] {} caption \"{}\" translated to \"{}\"", control, caption, label));
+                    format!("<{}{}>", &cap[1], Self::set_attr(attrs, caption_attr, &label))
+                }
+                None => {
+                    findings.push(format!(
+                        "[XML] {} caption \"{}\" is not in the screen language ({})",
+                        control, caption, locale
+                    ));
+                    cap[0].to_string()
+                }
+            }
+        });
+
+        for cap in body_re.captures_iter(&fixed) {
+            let text = cap[1].trim();
+            if Self::language_mismatch(text, korean) {
+                findings.push(format!("[XML] <text> caption \"{}\" is not in the screen language ({})", text, locale));
+            }
+        }
+
+        (fixed.into_owned(), findings)
+    }
+
+    /// Messages shown with `alert`/`confirm` (`alert`, `screen.alert`, `gfn_confirm`)
+    fn check_js(js: &str, locale: &str) -> Vec<String> {
+        let korean = Self::is_korean(locale);
+        let message_re = Regex::new(r#"(?i)\b\w*(?:alert|confirm)\s*\(\s*(?:"([^"\n]*)"|'([^'\n]*)')"#).unwrap();
+        message_re
+            .captures_iter(js)
+            .filter_map(|cap| cap.get(1).or(cap.get(2)).map(|m| m.as_str()))
+            .filter(|message| Self::language_mismatch(message, korean))
+            .map(|message| format!("[JS] Message \"{}\" is not in the screen language ({})", message, locale))
+            .collect()
+    }
+}
+
+impl Default for LabelLanguageValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for LabelLanguageValidator {
+    fn name(&self) -> &'static str {
+        "LabelLanguageValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let locale = ctx.intent.locale.clone().unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+        let fix = !ctx.is_strict();
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            let (fixed, xml_findings) = Self::check_xml(xml, &locale, &ctx.intent.actions, fix);
+            findings.extend(xml_findings);
+            ctx.xml = Some(fixed);
+        }
+        if let Some(js) = &ctx.javascript {
+            findings.extend(Self::check_js(js, &locale));
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} label language issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ActionType, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    fn create_context(xml: &str, js: &str, locale: Option<&str>, mode: ExecutionMode) -> GenerationContext {
+        let mut intent = UiIntent::new("order_list", ScreenType::List)
+            .with_action(ActionIntent::new("search", "조회", ActionType::Search));
+        intent.locale = locale.map(str::to_string);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    const XML: &str = r#"<screen>
+<text control_id="1" name="txt_order_id" text="주문 ID"/>
+<pushbutton control_id="2" name="btn_search" text="Search"/>
+<pushbutton control_id="3" name="btn_excel" text="Export to Excel"/>
+<pushbutton control_id="4" name="btn_pdf" text="PDF"/>
+<grid control_id="5" name="grid_order" link_data="ds_order">
+  <column><header title="Order Date"/><data name="ORDER_DATE"/></column>
+</grid>
+</screen>"#;
+
+    #[test]
+    fn test_relaxed_translates_button_captions() {
+        let js = "function fn_search() {\n  screen.alert(\"Please select a row\");\n  gfn_confirm('저장하시겠습니까?');\n}";
+        let mut ctx = create_context(XML, js, None, ExecutionMode::Relaxed);
+        assert!(LabelLanguageValidator::new().run(&mut ctx).is_warning());
+
+        let xml = ctx.xml.unwrap();
+        assert!(xml.contains(r#"name="btn_search" text="조회"/>"#));
+        assert!(xml.contains(r#"name="btn_excel" text="엑셀"/>"#));
+        assert!(xml.contains(r#"text="PDF"/>"#));
+        assert!(xml.contains(r#"<header title="Order Date"/>"#));
+        assert_eq!(
+            ctx.warnings,
+            vec![
+                "Warning: [XML] <pushbutton name=\"btn_search\"> caption \"Search\" translated to \"조회\"",
+                "Warning: [XML] <pushbutton name=\"btn_excel\"> caption \"Export to Excel\" translated to \"엑셀\"",
+                "Warning: [XML] <header> caption \"Order Date\" is not in the screen language (ko-KR)",
+                "Warning: [JS] Message \"Please select a row\" is not in the screen language (ko-KR)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_reports_hangul_on_english_screens() {
+        let xml = r#"<pushbutton control_id="1" name="btn_search" text="Search"/><text control_id="2">주문일</text>"#;
+        let mut ctx = create_context(xml, "alert(\"Saved\");", Some("en-US"), ExecutionMode::Strict);
+
+        match LabelLanguageValidator::new().run(&mut ctx) {
+            PassResult::Error(e) => {
+                assert_eq!(e, "[XML] <text> caption \"주문일\" is not in the screen language (en-US)")
+            }
+            other => panic!("expected error, got {:?}", other),
+        }
+        assert_eq!(ctx.xml.as_deref(), Some(xml));
+    }
+
+    #[test]
+    fn test_language_mismatch() {
+        assert!(!LabelLanguageValidator::language_mismatch("회원 ID", true));
+        assert!(!LabelLanguageValidator::language_mismatch("{0}건 조회되었습니다", true));
+        assert!(!LabelLanguageValidator::language_mismatch("No", true));
+        assert!(LabelLanguageValidator::language_mismatch("Member Name", true));
+        assert!(LabelLanguageValidator::language_mismatch("Total 금액", true));
+        assert!(!LabelLanguageValidator::language_mismatch("Member Name", false));
+    }
+}
@@ -4,14 +4,54 @@
 
 mod output_parser;
 mod canonicalizer;
+mod identifier_normalizer;
 mod symbol_linker;
+mod ux_behavior;
 mod api_allowlist;
+mod api_denylist;
+mod checklist;
 mod graph_validator;
+mod paging;
+mod editable_grid;
+mod chart_binding;
+mod wizard;
+mod tabs;
+mod print;
+mod search_state;
+mod error_handling;
+mod format;
+mod label_language;
+mod archetype;
+mod address;
 mod minimalism;
+mod sensitive_data;
+mod runtime_smoke;
+mod wasm_plugins;
+mod xml_formatter;
 
 pub use output_parser::OutputParser;
 pub use canonicalizer::Canonicalizer;
+pub use identifier_normalizer::IdentifierNormalizer;
 pub use symbol_linker::SymbolLinker;
+pub use ux_behavior::UxBehaviorPass;
 pub use api_allowlist::ApiAllowlistFilter;
+pub use api_denylist::ApiDenylistFilter;
+pub use checklist::ChecklistValidator;
 pub use graph_validator::GraphValidator;
+pub use paging::PagingValidator;
+pub use editable_grid::EditableGridValidator;
+pub use chart_binding::ChartBindingValidator;
+pub use wizard::WizardStepValidator;
+pub use tabs::TabPanelValidator;
+pub use print::PrintValidator;
+pub use search_state::SearchStateValidator;
+pub use error_handling::ErrorHandlingValidator;
+pub use format::FormatValidator;
+pub use label_language::LabelLanguageValidator;
+pub use archetype::ArchetypeValidator;
+pub use address::AddressFieldValidator;
 pub use minimalism::MinimalismPass;
+pub use sensitive_data::SensitiveDataPass;
+pub use runtime_smoke::RuntimeSmokeTest;
+pub use wasm_plugins::WasmPluginPass;
+pub use xml_formatter::XmlFormatter;
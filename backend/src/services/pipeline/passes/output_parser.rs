@@ -1,8 +1,196 @@
 //! Pass 0: Output Parser
 //!
 //! Splits raw LLM output into XML and JavaScript sections.
+//!
+//! Provider output is not uniform: some models wrap each section in nested
+//! code fences, repeat "```xml" inside the XML itself, or stamp the response
+//! with watermark lines and zero-width characters. The parser strips
+//! watermarks first, then splits by section markers, then by fenced blocks,
+//! then by content. A line tokenizer tracks fence nesting so stray fences
+//! never reach later passes.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::services::pipeline::{GenerationContext, Pass, PassResult, ScreenSection};
+
+/// Zero-width characters some providers insert as an invisible watermark
+const INVISIBLE_CHARS: [char; 5] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+
+/// Matches attribution lines such as "// Generated by ChatGPT"
+fn watermark_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)^\s*(?://|#|<!--|/\*|\*|-|—)?\s*(?:generated|created|produced|written)\s+(?:by|with|using)\s+(?:an?\s+)?(?:ai\b|llm\b|chatgpt|gpt|claude|gemini|llama|qwen|mistral|ollama|openai|anthropic|assistant|language model)",
+        )
+        .expect("Failed to compile watermark regex")
+    })
+}
+
+/// Opening and closing `<screen>` tags
+fn screen_tag_re() -> &'static (Regex, Regex, Regex) {
+    static RE: OnceLock<(Regex, Regex, Regex)> = OnceLock::new();
+    RE.get_or_init(|| {
+        (
+            Regex::new(r"(?i)<screen[\s>/]").expect("Failed to compile screen regex"),
+            Regex::new(r"(?i)</screen\s*>").expect("Failed to compile screen regex"),
+            Regex::new(r#"\bid\s*=\s*"([^"]*)""#).expect("Failed to compile screen regex"),
+        )
+    })
+}
+
+/// Section markers, in order of preference
+const XML_MARKERS: [&str; 8] = [
+    "--- XML ---",
+    "---XML---",
+    "<!-- XML -->",
+    "```xml",
+    "**XML:**",
+    "**XML**",
+    "## XML",
+    "# XML",
+];
+const JS_MARKERS: [&str; 13] = [
+    "--- JS ---",
+    "---JS---",
+    "// JS",
+    "```javascript",
+    "```js",
+    "**JavaScript:**",
+    "**JavaScript**",
+    "**JS:**",
+    "**JS**",
+    "## JavaScript",
+    "# JavaScript",
+    "## JS",
+    "# JS",
+];
+
+/// Patterns that start JavaScript when no marker is present
+const JS_PATTERNS: [&str; 6] = ["this.", "function ", "var ", "let ", "const ", "//"];
+
+/// Line-level token of LLM output
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    /// Opening fence with its info string ("xml", "javascript" or "")
+    Open(&'a str),
+    /// Closing fence
+    Close,
+    /// Any other line
+    Text(&'a str),
+}
+
+/// Section a fence info string names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    Xml,
+    Js,
+}
+
+impl SectionKind {
+    fn from_lang(lang: &str) -> Option<Self> {
+        match lang.to_ascii_lowercase().as_str() {
+            "xml" | "html" => Some(Self::Xml),
+            "js" | "javascript" | "jsx" | "ecmascript" => Some(Self::Js),
+            _ => None,
+        }
+    }
+}
+
+/// Tokenize by line, tracking fence nesting
+///
+/// Each token carries the depth of the block it belongs to. A fence with an
+/// info string opens a block even inside another one (the "```xml inside
+/// the XML" quirk); a bare fence closes the innermost block, or opens one at
+/// the top level. A fence naming the other section's language inside an
+/// open block means the provider never closed the previous section, so it
+/// starts a new top-level block. Fences glued to code (```` ```xml<screen> ````,
+/// ```` </screen>``` ````) are split off.
+fn tokenize(text: &str) -> Vec<(Token<'_>, usize)> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut outer: Option<SectionKind> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if let Some(rest) = trimmed.strip_prefix("```") {
+            let lang_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+')))
+                .unwrap_or(rest.len());
+            let (lang, tail) = rest.split_at(lang_len);
+
+            if lang.is_empty() && depth > 0 {
+                tokens.push((Token::Close, depth));
+                depth -= 1;
+            } else {
+                let kind = SectionKind::from_lang(lang);
+                if depth > 0 && kind.is_some() && outer.is_some() && kind != outer {
+                    depth = 0;
+                }
+                if depth == 0 {
+                    outer = kind;
+                }
+                depth += 1;
+                tokens.push((Token::Open(lang), depth));
+            }
+
+            // Code glued to the fence; anything else is info-string metadata
+            let tail = tail.trim().trim_end_matches("```").trim_end();
+            if tail.starts_with('<') {
+                tokens.push((Token::Text(tail), depth));
+            }
+        } else if let Some(body) = line.trim_end().strip_suffix("```") {
+            if !body.trim().is_empty() {
+                tokens.push((Token::Text(body), depth));
+            }
+            if depth > 0 {
+                tokens.push((Token::Close, depth));
+                depth -= 1;
+            }
+        } else {
+            tokens.push((Token::Text(line), depth));
+        }
+    }
 
-use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+    tokens
+}
+
+/// Whether `pos` falls inside a quoted string on its line
+///
+/// A single quote right after a letter is read as an apostrophe
+/// ("Here's the XML"), not as the start of a string.
+fn in_string_literal(text: &str, pos: usize) -> bool {
+    let line_start = text[..pos].rfind('\n').map(|p| p + 1).unwrap_or(0);
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut prev: Option<char> = None;
+
+    for c in text[line_start..pos].chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '`' => quote = Some(c),
+            None if c == '\'' && !prev.is_some_and(|p| p.is_alphanumeric()) => quote = Some(c),
+            None => {}
+        }
+        prev = Some(c);
+    }
+
+    quote.is_some()
+}
+
+/// First occurrence of `pattern` at or after `from` that is not inside a string literal
+fn find_outside_literals(text: &str, from: usize, pattern: &str) -> Option<usize> {
+    text[from..]
+        .match_indices(pattern)
+        .map(|(pos, _)| from + pos)
+        .find(|&pos| !in_string_literal(text, pos))
+}
 
 /// Output Parser - splits raw LLM output into XML and JS sections
 pub struct OutputParser;
@@ -12,36 +200,70 @@ impl OutputParser {
         Self
     }
 
-    /// Find a section marker in the text
-    fn find_section_marker(text: &str, markers: &[&str]) -> Option<(usize, usize)> {
+    /// Remove provider watermarks: zero-width characters and attribution lines
+    fn strip_watermarks(raw: &str) -> String {
+        let cleaned: String = raw.chars().filter(|c| !INVISIBLE_CHARS.contains(c)).collect();
+        if !cleaned.lines().any(|line| watermark_re().is_match(line)) {
+            return cleaned;
+        }
+
+        cleaned
+            .lines()
+            .filter(|line| !watermark_re().is_match(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Find a section marker in the text, starting at `from`
+    ///
+    /// Markers are tried in order. Occurrences inside a string literal
+    /// (`text="// JS"`) or glued to a longer word (```` ```json ````) are skipped.
+    fn find_section_marker(text: &str, markers: &[&str], from: usize) -> Option<(usize, usize)> {
         for marker in markers {
-            if let Some(pos) = text.find(marker) {
+            let found = text[from..]
+                .match_indices(marker)
+                .map(|(pos, _)| from + pos)
+                .find(|&pos| {
+                    let glued = text[pos + marker.len()..]
+                        .chars()
+                        .next()
+                        .is_some_and(|c| c.is_alphanumeric());
+                    !glued && !in_string_literal(text, pos)
+                });
+            if let Some(pos) = found {
                 return Some((pos, marker.len()));
             }
         }
         None
     }
 
+    /// Remove fence lines at any nesting depth
+    ///
+    /// Text after the last bare fence is prose following the code block
+    /// ("This screen uses...") and is dropped as well.
+    fn strip_fences(text: &str) -> String {
+        let tokens = tokenize(text);
+        let is_code = |token: &Token| matches!(token, Token::Text(line) if !line.trim().is_empty());
+        let prose_from = tokens
+            .iter()
+            .rposition(|(token, _)| matches!(token, Token::Close | Token::Open("")))
+            .filter(|&last| tokens[..last].iter().any(|(token, _)| is_code(token)));
+
+        tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| prose_from.is_none_or(|last| *i < last))
+            .filter_map(|(_, (token, _))| match token {
+                Token::Text(line) => Some(*line),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Clean a section by removing markdown code blocks and trimming
     fn clean_section(text: &str) -> String {
-        let mut result = text.trim().to_string();
-
-        // Remove leading markdown code block markers
-        result = result
-            .trim_start_matches("```xml")
-            .trim_start_matches("```javascript")
-            .trim_start_matches("```js")
-            .trim_start_matches("```")
-            .trim()
-            .to_string();
-
-        // Remove trailing markdown code block
-        if let Some(backtick_pos) = result.rfind("```") {
-            let before_backtick = result[..backtick_pos].trim();
-            if !before_backtick.is_empty() {
-                result = before_backtick.to_string();
-            }
-        }
+        let mut result = Self::strip_fences(text).trim().to_string();
 
         // For XML: ensure we end at </screen> if present
         if result.contains("</screen>") {
@@ -75,20 +297,76 @@ impl OutputParser {
         result.trim().to_string()
     }
 
+    /// Try to split content by fenced code blocks
+    ///
+    /// The first top-level block tagged (or looking like) XML becomes the
+    /// XML section and the first JS block the JavaScript section. Nested
+    /// fences inside a block are dropped, and an unterminated last block
+    /// (truncated output) still counts.
+    fn split_by_fences(raw: &str) -> Option<(String, String)> {
+        let mut xml: Option<String> = None;
+        let mut js: Option<String> = None;
+        let mut current: Option<(&str, Vec<&str>)> = None;
+
+        let mut finish = |block: Option<(&str, Vec<&str>)>| {
+            let Some((lang, lines)) = block else {
+                return;
+            };
+            let body = lines.join("\n").trim().to_string();
+            if body.is_empty() {
+                return;
+            }
+            let kind = SectionKind::from_lang(lang).unwrap_or(if body.starts_with('<') {
+                SectionKind::Xml
+            } else {
+                SectionKind::Js
+            });
+            match kind {
+                SectionKind::Xml => {
+                    xml.get_or_insert(body);
+                }
+                SectionKind::Js => {
+                    js.get_or_insert(body);
+                }
+            }
+        };
+
+        for (token, depth) in tokenize(raw) {
+            match token {
+                Token::Open(lang) if depth == 1 => finish(current.replace((lang, Vec::new()))),
+                // "```\n```xml" - the nested fence names the block
+                Token::Open(lang) => {
+                    if let Some((outer, _)) = current.as_mut() {
+                        if outer.is_empty() {
+                            *outer = lang;
+                        }
+                    }
+                }
+                Token::Close if depth == 1 => finish(current.take()),
+                Token::Close => {}
+                Token::Text(line) => {
+                    if let Some((_, lines)) = current.as_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+        finish(current.take());
+
+        Some((xml?, js?))
+    }
+
     /// Try to split content by detecting XML and JS patterns
     fn split_by_content(raw: &str) -> Option<(String, String)> {
         // Look for XML start
         let xml_start = raw.find('<')?;
 
         // Find where XML ends (look for closing tag followed by JS patterns)
-        let js_patterns = ["this.", "function ", "var ", "let ", "const ", "//"];
-
         let mut xml_end = raw.len();
         let mut js_start = raw.len();
 
-        for pattern in js_patterns {
-            if let Some(pos) = raw[xml_start..].find(pattern) {
-                let abs_pos = xml_start + pos;
+        for pattern in JS_PATTERNS {
+            if let Some(abs_pos) = find_outside_literals(raw, xml_start, pattern) {
                 if let Some(last_bracket) = raw[..abs_pos].rfind('>') {
                     if last_bracket + 1 < abs_pos && abs_pos < js_start {
                         xml_end = last_bracket + 1;
@@ -111,6 +389,180 @@ impl OutputParser {
 
         Some((xml, js))
     }
+
+    /// Split a response holding several `<screen>` documents
+    ///
+    /// Each screen takes the JavaScript that follows it. JS blocks headed by
+    /// a comment naming another screen (`// SCREEN_MEMBER_POP`) go to that
+    /// screen; unheaded blocks go to the screen whose element ids and
+    /// handlers they reference most, or else the screen before them.
+    /// Returns fewer than two sections for ordinary single-screen output.
+    pub fn split_screens(raw: &str) -> Vec<ScreenSection> {
+        let (open_re, close_re, id_re) = screen_tag_re();
+
+        // Screen documents, in order
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        let mut from = 0;
+        while let Some(open) = open_re
+            .find_iter(&raw[from..])
+            .map(|m| from + m.start())
+            .find(|&pos| !in_string_literal(raw, pos))
+        {
+            let Some(tag_end) = raw[open..].find('>').map(|p| open + p + 1) else {
+                break;
+            };
+            let end = if raw[..tag_end].ends_with("/>") {
+                tag_end
+            } else {
+                match close_re.find(&raw[tag_end..]) {
+                    Some(m) => tag_end + m.end(),
+                    None => return Vec::new(),
+                }
+            };
+            spans.push((open, end));
+            from = end;
+        }
+        if spans.len() < 2 {
+            return Vec::new();
+        }
+
+        let mut screens: Vec<ScreenSection> = spans
+            .iter()
+            .map(|&(start, end)| {
+                let xml = &raw[start..end];
+                ScreenSection {
+                    screen_id: raw[start..]
+                        .find('>')
+                        .and_then(|tag_end| id_re.captures(&xml[..tag_end]))
+                        .map(|cap| cap[1].to_string()),
+                    xml: Self::clean_section(xml),
+                    javascript: String::new(),
+                }
+            })
+            .collect();
+
+        // JS between each screen and the next, split at screen header comments
+        let ids: Vec<String> = screens.iter().filter_map(|s| s.screen_id.clone()).collect();
+        let mut assigned: Vec<Vec<String>> = vec![Vec::new(); screens.len()];
+        for (i, &(_, end)) in spans.iter().enumerate() {
+            let gap_end = spans.get(i + 1).map(|&(start, _)| start).unwrap_or(raw.len());
+            let js = Self::js_in_gap(&raw[end..gap_end]);
+            for (header, block) in Self::split_js_blocks(&js, &ids) {
+                let target = header
+                    .and_then(|id| screens.iter().position(|s| s.screen_id.as_deref() == Some(id)))
+                    .or_else(|| Self::best_match(&screens, &block))
+                    .unwrap_or(i);
+                assigned[target].push(block);
+            }
+        }
+
+        if assigned.iter().all(|blocks| blocks.is_empty()) {
+            return Vec::new();
+        }
+        for (screen, blocks) in screens.iter_mut().zip(assigned) {
+            screen.javascript = blocks.join("\n\n");
+        }
+        screens
+    }
+
+    /// JavaScript in the text between two screen documents
+    ///
+    /// Starts after a JS marker (or at the first JS-looking token) and stops
+    /// at the next XML marker.
+    fn js_in_gap(gap: &str) -> String {
+        let start = Self::find_section_marker(gap, &JS_MARKERS, 0)
+            .map(|(pos, len)| pos + len)
+            .or_else(|| {
+                JS_PATTERNS
+                    .iter()
+                    .filter_map(|pattern| find_outside_literals(gap, 0, pattern))
+                    .min()
+            });
+        let Some(start) = start else {
+            return String::new();
+        };
+        let end = Self::find_section_marker(gap, &XML_MARKERS, start)
+            .map(|(pos, _)| pos)
+            .unwrap_or(gap.len());
+
+        Self::clean_section(&gap[start..end])
+    }
+
+    /// Split JavaScript at comment lines naming a screen id
+    ///
+    /// Returns `(header screen id, block)`; text before the first header has
+    /// no header.
+    fn split_js_blocks<'a>(js: &str, ids: &'a [String]) -> Vec<(Option<&'a str>, String)> {
+        let header_of = |line: &str| {
+            let comment = line.trim_start();
+            if !(comment.starts_with("//") || comment.starts_with("/*")) {
+                return None;
+            }
+            let comment = comment.to_lowercase();
+            ids.iter()
+                .find(|id| {
+                    comment
+                        .match_indices(&id.to_lowercase())
+                        .any(|(pos, matched)| {
+                            let before = comment[..pos].chars().next_back();
+                            let after = comment[pos + matched.len()..].chars().next();
+                            !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+                                && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+                        })
+                })
+                .map(|id| id.as_str())
+        };
+
+        let mut blocks: Vec<(Option<&str>, Vec<&str>)> = vec![(None, Vec::new())];
+        for line in js.lines() {
+            match header_of(line) {
+                Some(id) => blocks.push((Some(id), vec![line])),
+                None => blocks.last_mut().expect("starts with one block").1.push(line),
+            }
+        }
+
+        blocks
+            .into_iter()
+            .map(|(header, lines)| (header, lines.join("\n").trim().to_string()))
+            .filter(|(_, block)| !block.is_empty())
+            .collect()
+    }
+
+    /// Screen whose element ids and event handlers the JS block uses most
+    ///
+    /// `None` on a tie or when the block references none of them.
+    fn best_match(screens: &[ScreenSection], block: &str) -> Option<usize> {
+        static SYMBOL_RE: OnceLock<Regex> = OnceLock::new();
+        let symbol_re = SYMBOL_RE.get_or_init(|| {
+            Regex::new(r#"\b(?:id|name|on_\w+)\s*=\s*"(?:eventfunc:)?(\w+)"#).expect("Failed to compile symbol regex")
+        });
+        let word_re = |word: &str| Regex::new(&format!(r"\b{}\b", regex::escape(word))).ok();
+
+        let scores: Vec<usize> = screens
+            .iter()
+            .map(|screen| {
+                let mut symbols: Vec<&str> =
+                    symbol_re.captures_iter(&screen.xml).filter_map(|cap| cap.get(1)).map(|m| m.as_str()).collect();
+                symbols.sort_unstable();
+                symbols.dedup();
+                symbols
+                    .into_iter()
+                    .filter(|symbol| word_re(symbol).is_some_and(|re| re.is_match(block)))
+                    .count()
+            })
+            .collect();
+
+        let best = *scores.iter().max()?;
+        if best == 0 || scores.iter().filter(|&&score| score == best).count() > 1 {
+            return None;
+        }
+        scores.iter().position(|&score| score == best)
+    }
+
+    /// Fence-based split, then content-based
+    fn split_fallback(raw: &str) -> Option<(String, String)> {
+        Self::split_by_fences(raw).or_else(|| Self::split_by_content(raw))
+    }
 }
 
 impl Default for OutputParser {
@@ -125,37 +577,34 @@ impl Pass for OutputParser {
     }
 
     fn run(&self, ctx: &mut GenerationContext) -> PassResult {
-        let raw = &ctx.raw_output;
+        let raw = Self::strip_watermarks(&ctx.raw_output);
+        let raw = raw.as_str();
+
+        // Several <screen> documents: keep the intent's screen, queue the rest
+        let screens = Self::split_screens(raw);
+        if screens.len() > 1 {
+            let mut screens = screens;
+            let primary = screens
+                .iter()
+                .position(|screen| screen.matches_intent(&ctx.intent))
+                .unwrap_or(0);
+            let main = screens.remove(primary);
+            if main.javascript.is_empty() {
+                return PassResult::Error("JavaScript section is empty".to_string());
+            }
+            tracing::info!("Output contains {} screens", screens.len() + 1);
 
-        // Try marker-based splitting first
-        let xml_markers = [
-            "--- XML ---",
-            "---XML---",
-            "<!-- XML -->",
-            "```xml",
-            "**XML:**",
-            "**XML**",
-            "## XML",
-            "# XML",
-        ];
-        let js_markers = [
-            "--- JS ---",
-            "---JS---",
-            "// JS",
-            "```javascript",
-            "```js",
-            "**JavaScript:**",
-            "**JavaScript**",
-            "**JS:**",
-            "**JS**",
-            "## JavaScript",
-            "# JavaScript",
-            "## JS",
-            "# JS",
-        ];
+            ctx.xml = Some(main.xml);
+            ctx.javascript = Some(main.javascript);
+            ctx.additional_screens = screens;
+            return PassResult::Ok;
+        }
 
-        let xml_marker = Self::find_section_marker(raw, &xml_markers);
-        let js_marker = Self::find_section_marker(raw, &js_markers);
+        // Try marker-based splitting first
+        let xml_marker = Self::find_section_marker(raw, &XML_MARKERS, 0);
+        let js_marker = xml_marker.and_then(|(xml_start, xml_marker_len)| {
+            Self::find_section_marker(raw, &JS_MARKERS, xml_start + xml_marker_len)
+        });
 
         let (xml, js) = match (xml_marker, js_marker) {
             (Some((xml_start, xml_marker_len)), Some((js_start, js_marker_len))) => {
@@ -170,10 +619,10 @@ impl Pass for OutputParser {
                     return PassResult::Error("XML section is empty".to_string());
                 }
 
-                // If JS is empty after marker-based split, try content-based fallback
+                // If JS is empty after marker-based split, try the fallbacks
                 if js.is_empty() {
-                    match Self::split_by_content(raw) {
-                        Some((_, js_fallback)) => (xml, js_fallback),
+                    match Self::split_fallback(raw) {
+                        Some((_, js_fallback)) => (xml, Self::clean_section(&js_fallback)),
                         None => {
                             return PassResult::Error(
                                 "JavaScript section is empty".to_string(),
@@ -185,8 +634,8 @@ impl Pass for OutputParser {
                 }
             }
             _ => {
-                // No markers found, try content-based splitting
-                match Self::split_by_content(raw) {
+                // No markers found, try fence- and content-based splitting
+                match Self::split_fallback(raw) {
                     Some((xml, js)) => (Self::clean_section(&xml), Self::clean_section(&js)),
                     None => {
                         return PassResult::Error(
@@ -283,4 +732,282 @@ this.fn_test = function() {};
 
         assert!(matches!(result, PassResult::Error(_)));
     }
+
+    #[test]
+    fn test_tokenize_tracks_nesting() {
+        let tokens = tokenize("```xml\n```xml\n<screen/>\n```\n```");
+        let depths: Vec<usize> = tokens.iter().map(|(_, depth)| *depth).collect();
+        assert_eq!(depths, vec![1, 2, 2, 2, 1]);
+        assert_eq!(tokens[2].0, Token::Text("<screen/>"));
+    }
+
+    #[test]
+    fn test_nested_xml_fence_inside_xml() {
+        // Provider repeats the fence inside the XML block
+        let raw = r#"```xml
+```xml
+<screen id="test">
+  <dataset id="ds_list"/>
+</screen>
+```
+```
+
+```javascript
+this.fn_search = function() {};
+```"#;
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        let xml = ctx.xml.unwrap();
+        assert!(xml.starts_with("<screen"));
+        assert!(xml.ends_with("</screen>"));
+        assert!(!xml.contains("```"));
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_unclosed_fence_before_js_fence() {
+        // Provider opens a second XML fence and never closes the first
+        let raw = "```xml\n```xml\n<screen id=\"test\"/>\n```\n```js\nthis.fn_init = function() {};\n```";
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        assert_eq!(ctx.xml.unwrap(), "<screen id=\"test\"/>");
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_init = function() {};");
+    }
+
+    #[test]
+    fn test_fences_inside_marker_sections() {
+        // Provider wraps every marked section in its own fence
+        let raw = r#"--- XML ---
+```xml
+<screen id="test">
+```xml
+  <grid id="grid_list"/>
+</screen>
+```
+
+--- JS ---
+```javascript
+this.fn_search = function() {};
+```
+
+This screen searches the list on load."#;
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        let xml = ctx.xml.unwrap();
+        assert!(xml.contains("<grid id=\"grid_list\"/>"));
+        assert!(!xml.contains("```"));
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_bare_fences_without_markers() {
+        let raw = "Here you go:\n```\n<screen id=\"test\"/>\n```\n\n```\nthis.fn_search = function() {};\n```";
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        assert_eq!(ctx.xml.unwrap(), "<screen id=\"test\"/>");
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_fence_glued_to_code() {
+        let raw = "```xml<screen id=\"test\"/>```\n```js\nthis.fn_search = function() {};```";
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        assert_eq!(ctx.xml.unwrap(), "<screen id=\"test\"/>");
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_js_marker_inside_string_literal() {
+        let raw = r#"--- XML ---
+<screen id="test">
+  <text id="lbl_hint" text="// JS handlers are bound on load"/>
+</screen>
+
+--- JS ---
+this.fn_search = function() {
+  this.lbl_hint.text = "--- JS ---";
+};
+"#;
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        let xml = ctx.xml.unwrap();
+        assert!(xml.contains("lbl_hint"));
+        assert!(xml.ends_with("</screen>"));
+        assert!(ctx.javascript.unwrap().contains("\"--- JS ---\""));
+    }
+
+    #[test]
+    fn test_content_split_skips_string_literals() {
+        let raw = "<screen id=\"test\">\n  <link url=\"http://host/list\"/>\n</screen>\n\nthis.fn_search = function() {};";
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        assert!(ctx.xml.unwrap().ends_with("</screen>"));
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_js_marker_before_xml_marker_does_not_panic() {
+        let raw = "--- JS ---\nthis.fn_search = function() {};\n--- XML ---\n<screen id=\"test\"/>";
+
+        let mut ctx = create_context(raw);
+        let _ = OutputParser::new().run(&mut ctx);
+    }
+
+    #[test]
+    fn test_json_fence_is_not_js_marker() {
+        assert!(OutputParser::find_section_marker("```json\n{}", &["```js"], 0).is_none());
+        assert_eq!(
+            OutputParser::find_section_marker("Here's the code: ```js\n", &["```js"], 0),
+            Some((17, 5))
+        );
+    }
+
+    #[test]
+    fn test_strip_watermarks() {
+        let raw = "<!-- Generated by ChatGPT -->\n<screen\u{200B} id=\"test\"/>\n// Generated with an AI assistant\nthis.fn_search = function() {};\n\u{FEFF}";
+        let cleaned = OutputParser::strip_watermarks(raw);
+
+        assert_eq!(cleaned, "<screen id=\"test\"/>\nthis.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_watermarked_output_parses() {
+        let raw = "\u{FEFF}--- XML ---\n<screen id=\"test\"/>\n\n--- JS ---\nthis.fn_search = function() {};\n// Generated by Claude";
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+    }
+
+    #[test]
+    fn test_code_comments_are_not_watermarks() {
+        let raw = "// Generated list is sorted by date\nthis.fn_search = function() {};";
+        assert_eq!(OutputParser::strip_watermarks(raw), raw);
+    }
+
+    #[test]
+    fn test_split_screens_alternating_pairs() {
+        let raw = r#"--- XML ---
+```xml
+<screen id="SCREEN_MEMBER_LIST">
+  <grid id="grid_member" on_dblclick="fn_openPopup"/>
+</screen>
+```
+
+--- JS ---
+```javascript
+this.fn_openPopup = function() {};
+```
+
+--- XML ---
+```xml
+<screen id="SCREEN_MEMBER_POP">
+  <button id="btn_select" on_click="fn_select"/>
+</screen>
+```
+
+--- JS ---
+```javascript
+this.fn_select = function() {};
+```"#;
+
+        let screens = OutputParser::split_screens(raw);
+        assert_eq!(screens.len(), 2);
+        assert_eq!(screens[0].screen_id.as_deref(), Some("SCREEN_MEMBER_LIST"));
+        assert_eq!(screens[0].javascript, "this.fn_openPopup = function() {};");
+        assert_eq!(screens[1].screen_id.as_deref(), Some("SCREEN_MEMBER_POP"));
+        assert!(screens[1].xml.ends_with("</screen>"));
+        assert_eq!(screens[1].javascript, "this.fn_select = function() {};");
+    }
+
+    #[test]
+    fn test_split_screens_js_after_all_xml_by_header() {
+        let raw = r#"--- XML ---
+<screen id="SCREEN_MEMBER_LIST">
+  <grid id="grid_member"/>
+</screen>
+
+<screen id="SCREEN_MEMBER_POP">
+  <button id="btn_select"/>
+</screen>
+
+--- JS ---
+// SCREEN_MEMBER_LIST
+this.fn_search = function() {};
+
+// SCREEN_MEMBER_POP.js
+this.fn_select = function() {};
+"#;
+
+        let screens = OutputParser::split_screens(raw);
+        assert_eq!(screens.len(), 2);
+        assert!(screens[0].javascript.contains("fn_search"));
+        assert!(!screens[0].javascript.contains("fn_select"));
+        assert!(screens[1].javascript.contains("fn_select"));
+    }
+
+    #[test]
+    fn test_split_screens_pairs_by_referenced_symbols() {
+        // No headers: blocks go to the screen whose ids and handlers they use
+        let raw = r#"<screen id="SCREEN_A">
+  <grid name="grid_member" on_itemdblclick="eventfunc:fn_openPopup()"/>
+</screen>
+<screen id="SCREEN_B">
+  <pushbutton name="btn_select" on_click="eventfunc:fn_select()"/>
+</screen>
+
+this.fn_openPopup = function() {
+  this.grid_member.refresh();
+};
+"#;
+
+        let screens = OutputParser::split_screens(raw);
+        assert_eq!(screens.len(), 2);
+        assert!(screens[0].javascript.contains("fn_openPopup"));
+        assert!(screens[1].javascript.is_empty());
+    }
+
+    #[test]
+    fn test_single_screen_is_not_split() {
+        let raw = "--- XML ---\n<screen id=\"test\"/>\n--- JS ---\nthis.fn = function() {};";
+        assert!(OutputParser::split_screens(raw).is_empty());
+    }
+
+    #[test]
+    fn test_multi_screen_keeps_intent_screen() {
+        let raw = "<screen id=\"SCREEN_POP\"/>\nthis.fn_select = function() {};\n<screen id=\"SCREEN_TEST\"/>\nthis.fn_search = function() {};";
+
+        let mut ctx = create_context(raw);
+        let result = OutputParser::new().run(&mut ctx);
+
+        assert!(matches!(result, PassResult::Ok));
+        assert_eq!(ctx.xml.unwrap(), "<screen id=\"SCREEN_TEST\"/>");
+        assert_eq!(ctx.javascript.unwrap(), "this.fn_search = function() {};");
+        assert_eq!(ctx.additional_screens.len(), 1);
+        assert_eq!(ctx.additional_screens[0].javascript, "this.fn_select = function() {};");
+    }
 }
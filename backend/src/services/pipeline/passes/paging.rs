@@ -0,0 +1,153 @@
+//! Pass 4b: Paging Validator
+//!
+//! Grids over large tables (row-count hint in the schema input) must page on
+//! the server instead of loading the whole table:
+//! - The search transaction sends a page parameter (PAGE_NO) in ds_search
+//! - The response total count (TOTAL_COUNT) is handled
+//! - The screen has a pagingbar for the grid
+//!
+//! Nothing is auto-fixed; the transaction contract can't be guessed.
+
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+/// Paging Validator - enforces paged loading for large-table grids
+pub struct PagingValidator;
+
+impl PagingValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether the JS sends a page number parameter
+    fn has_page_param(js: &str) -> bool {
+        Regex::new(r#"(?i)\bPAGE_?(?:NO|NUM|INDEX)\b"#)
+            .unwrap()
+            .is_match(js)
+    }
+
+    /// Whether the JS reads the total row count
+    fn has_total_count(js: &str) -> bool {
+        Regex::new(r#"(?i)\b(?:TOTAL_?(?:COUNT|CNT)|TOT_?CNT)\b"#)
+            .unwrap()
+            .is_match(js)
+    }
+
+    /// Whether the XML has a paging bar
+    fn has_pagingbar(xml: &str) -> bool {
+        Regex::new(r#"(?i)<pagingbar\b"#).unwrap().is_match(xml)
+    }
+}
+
+impl Default for PagingValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for PagingValidator {
+    fn name(&self) -> &'static str {
+        "PagingValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let grids: Vec<String> = ctx
+            .intent
+            .grids
+            .iter()
+            .filter(|g| g.requires_paging)
+            .map(|g| g.id.clone())
+            .collect();
+        if grids.is_empty() {
+            return PassResult::Ok;
+        }
+
+        let grids = grids.join(", ");
+        let mut findings = Vec::new();
+
+        if let Some(js) = &ctx.javascript {
+            if !Self::has_page_param(js) {
+                findings.push(format!(
+                    "[JS] Large-table grid ({}) is searched without a page parameter (PAGE_NO) in ds_search",
+                    grids
+                ));
+            }
+            if !Self::has_total_count(js) {
+                findings.push(format!(
+                    "[JS] Large-table grid ({}) does not handle the total count (TOTAL_COUNT)",
+                    grids
+                ));
+            }
+        }
+
+        if let Some(xml) = &ctx.xml {
+            if !Self::has_pagingbar(xml) {
+                findings.push(format!("[XML] Large-table grid ({}) has no pagingbar", grids));
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} paging issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{GridIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const PAGED_JS: &str = r#"this.fn_search = function() {
+    ds_search.setitemtext(0, "PAGE_NO", this.nPageNo);
+    // TOTAL_COUNT is returned with the result
+};"#;
+
+    fn create_context(xml: &str, js: &str, large: bool, mode: ExecutionMode) -> GenerationContext {
+        let mut grid = GridIntent::new("grid_orders", "ds_orders");
+        if large {
+            grid = grid.require_paging();
+        }
+        let intent = UiIntent::new("orders_list", ScreenType::List).with_grid(grid);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_small_table_not_checked() {
+        let mut ctx = create_context("<grid/>", "", false, ExecutionMode::Strict);
+        assert!(matches!(PagingValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_paged_screen_passes() {
+        let xml = r#"<grid name="grid_orders"/><pagingbar name="pgb_orders"/>"#;
+        let mut ctx = create_context(xml, PAGED_JS, true, ExecutionMode::Strict);
+        assert!(matches!(PagingValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_full_load_flagged() {
+        let js = "this.fn_search = function() {\n    // load everything\n};";
+        let mut ctx = create_context("<grid name=\"grid_orders\"/>", js, true, ExecutionMode::Relaxed);
+
+        assert!(PagingValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 3);
+        assert!(ctx.warnings[0].contains("PAGE_NO"));
+
+        let mut ctx = create_context("<grid/>", js, true, ExecutionMode::Strict);
+        assert!(PagingValidator::new().run(&mut ctx).is_error());
+    }
+}
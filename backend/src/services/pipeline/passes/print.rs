@@ -0,0 +1,168 @@
+//! Pass 4g: Print Validator
+//!
+//! Screens generated with the `print` option must be able to print:
+//! - A 인쇄 button (`btn_print`, or any component calling `fn_print`)
+//! - `fn_print` exists and passes the printed dataset
+//! - `fn_print` calls the company's report helper, when one is configured
+//!
+//! Nothing is auto-fixed; the report call depends on the customer's tool.
+
+use super::EditableGridValidator;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+/// Print Validator - requested printing has its button and report call
+pub struct PrintValidator;
+
+impl PrintValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn has_button(xml: &str) -> bool {
+        Regex::new(r#"\bname\s*=\s*"btn_print"|eventfunc:fn_print\s*\("#)
+            .unwrap()
+            .is_match(xml)
+    }
+
+    fn mentions(code: &str, name: &str) -> bool {
+        Regex::new(&format!(r"\b{}\b", regex::escape(name)))
+            .unwrap()
+            .is_match(code)
+    }
+}
+
+impl Default for PrintValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for PrintValidator {
+    fn name(&self) -> &'static str {
+        "PrintValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let Some(print) = ctx.intent.print.clone() else {
+            return PassResult::Ok;
+        };
+
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            if !Self::has_button(xml) {
+                findings.push("[XML] Print button (btn_print) is missing".to_string());
+            }
+        }
+
+        if let Some(js) = &ctx.javascript {
+            match EditableGridValidator::function_body(js, "fn_print") {
+                None => findings.push("[JS] Print button (btn_print) has no fn_print".to_string()),
+                Some(body) => {
+                    if !Self::mentions(body, &print.dataset_id) {
+                        findings.push(format!(
+                            "[JS] Print button (btn_print): fn_print does not pass {}",
+                            print.dataset_id
+                        ));
+                    }
+                    if let Some(helper) = &print.helper {
+                        if !Self::mentions(body, helper) {
+                            findings.push(format!(
+                                "[JS] Print button (btn_print): fn_print does not call {} ({})",
+                                helper,
+                                print.tool.label()
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} print issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DatasetIntent, GridIntent, PrintIntent, PrintRules, ReportTool, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const PRINT_XML: &str = r#"<screen id="SCREEN_MEMBER_LIST" width="1024" height="768">
+  <pushbutton control_id="5" name="btn_print" x="900" y="10" width="60" height="25" text="인쇄"
+              on_click="eventfunc:fn_print()"/>
+</screen>"#;
+
+    const PRINT_JS: &str = r#"this.fn_print = function() {
+    if (ds_member.getrowcount() == 0) return;
+    gfn_openOzViewer(ds_member, "/report/member_list_print.ozr");
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let mut intent = UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(DatasetIntent::new("ds_member"))
+            .with_grid(GridIntent::new("grid_member", "ds_member"));
+        let rules = PrintRules {
+            tool: ReportTool::Oz,
+            helper: Some("gfn_openOzViewer".to_string()),
+            report_path: Some("/report/".to_string()),
+        };
+        intent.enable_print(PrintIntent::for_intent(&intent, None, Some(&rules)));
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_screens_without_print_not_checked() {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, ExecutionMode::Strict);
+        ctx.xml = Some("<screen/>".to_string());
+        ctx.javascript = Some(String::new());
+        assert!(matches!(PrintValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_wired_print_passes() {
+        let mut ctx = create_context(PRINT_XML, PRINT_JS, ExecutionMode::Strict);
+        assert!(matches!(PrintValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_missing_print_wiring_flagged() {
+        let xml = PRINT_XML
+            .replace("btn_print", "btn_output")
+            .replace(" on_click=\"eventfunc:fn_print()\"", "");
+        let js = PRINT_JS.replace("gfn_openOzViewer(ds_member, ", "// TODO: open report(");
+        let mut ctx = create_context(&xml, &js, ExecutionMode::Relaxed);
+
+        assert!(PrintValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 2);
+        assert!(ctx.warnings[0].contains("Print button (btn_print) is missing"));
+        assert!(ctx.warnings[1].contains("fn_print does not call gfn_openOzViewer (OZ Report)"));
+
+        let mut ctx = create_context(PRINT_XML, "this.fn_search = function() {};", ExecutionMode::Strict);
+        let PassResult::Error(message) = PrintValidator::new().run(&mut ctx) else {
+            panic!("expected an error");
+        };
+        assert!(message.contains("has no fn_print"));
+    }
+}
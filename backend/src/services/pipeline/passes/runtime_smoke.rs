@@ -0,0 +1,289 @@
+//! Pass 7b: Runtime Smoke Test
+//!
+//! Loads the screen's JavaScript into an embedded QuickJS engine and calls
+//! `on_load` and `fn_search`, catching the runtime errors static checks miss:
+//! undefined variables, typos in dataset or component names, calls to
+//! functions that don't exist, and syntax errors.
+//!
+//! The engine has no file, network or timer access. The xFrame5 API surface
+//! is stubbed: every dataset and component declared in the XML, the platform
+//! globals (`screen`, `factory`, `alert`, `transaction`, ...) and company
+//! helpers (`gfn_*`, `cfn_*`) are permissive objects whose methods accept
+//! anything and return another stub (`0` / `""` when compared). Names the XML
+//! doesn't declare stay undefined, so a misspelled `ds_membr` fails.
+//! Transaction callbacks and popups are never invoked.
+//!
+//! Optional: enabled with `JS_SMOKE_TEST=true`. Nothing is auto-fixed.
+
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use rquickjs::context::EvalOptions;
+use rquickjs::{CatchResultExt, CaughtError, Context, Ctx, Runtime};
+
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+
+/// Entry points called after loading the script, in order
+const ENTRY_POINTS: [&str; 2] = ["on_load", "fn_search"];
+
+/// Platform globals of the xFrame5 runtime
+const PLATFORM_GLOBALS: &[&str] = &[
+    "screen",
+    "factory",
+    "application",
+    "console",
+    "alert",
+    "confirm",
+    "loadpopup",
+    "closepopup",
+    "getPopupData",
+    "setPopupData",
+    "transaction",
+    "submit",
+];
+
+/// Execution budget per screen
+const TIMEOUT: Duration = Duration::from_secs(2);
+const MEMORY_LIMIT: usize = 32 * 1024 * 1024;
+
+/// Permissive stub: any property is another stub, calls return a stub,
+/// comparisons see `0` (or `""` in string context)
+const STUB_PRELUDE: &str = r#"var __stub = function (path) {
+  var target = function () { return __stub(path + "()"); };
+  return new Proxy(target, {
+    get: function (t, key) {
+      if (key === Symbol.toPrimitive) return function (hint) { return hint === "string" ? "" : 0; };
+      if (key === "toString") return function () { return ""; };
+      if (key === "valueOf") return function () { return 0; };
+      if (key === "length") return 0;
+      if (typeof key === "symbol") return undefined;
+      return __stub(path + "." + key);
+    },
+    set: function () { return true; }
+  });
+};
+"#;
+
+/// Runtime Smoke Test - runs the screen's load and search handlers
+pub struct RuntimeSmokeTest {
+    enabled: bool,
+}
+
+impl RuntimeSmokeTest {
+    /// Always runs (regardless of `JS_SMOKE_TEST`)
+    pub fn enabled() -> Self {
+        Self { enabled: true }
+    }
+
+    /// Runs when `JS_SMOKE_TEST=true`
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("JS_SMOKE_TEST").is_ok_and(|v| v.trim().eq_ignore_ascii_case("true")),
+        }
+    }
+
+    /// Names the stubbed API surface defines: XML ids/names, platform
+    /// globals and company helpers used by the script
+    fn stubbed_names(xml: &str, js: &str) -> BTreeSet<String> {
+        let declared = Regex::new(r#"\b(?:id|name)\s*=\s*"([A-Za-z_]\w*)""#).unwrap();
+        let helpers = Regex::new(r"\b[a-z]+fn_\w+").unwrap();
+
+        declared
+            .captures_iter(xml)
+            .map(|cap| cap[1].to_string())
+            .chain(PLATFORM_GLOBALS.iter().map(|name| name.to_string()))
+            .chain(helpers.find_iter(js).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+
+    /// Runtime errors of the script (empty when every entry point ran)
+    pub fn execute(xml: &str, js: &str) -> Result<Vec<String>, String> {
+        let runtime = Runtime::new().map_err(|e| format!("JS engine unavailable: {}", e))?;
+        runtime.set_memory_limit(MEMORY_LIMIT);
+        let deadline = Instant::now() + TIMEOUT;
+        runtime.set_interrupt_handler(Some(Box::new(move || Instant::now() > deadline)));
+        let context = Context::full(&runtime).map_err(|e| format!("JS engine unavailable: {}", e))?;
+
+        context.with(|ctx| {
+            let stubs: String = Self::stubbed_names(xml, js)
+                .iter()
+                .map(|name| format!("this.{0} = __stub(\"{0}\");\n", name))
+                .collect();
+            Self::eval(&ctx, format!("{}{}", STUB_PRELUDE, stubs))
+                .catch(&ctx)
+                .map_err(|e| format!("JS stubs failed to load: {}", e))?;
+
+            if let Err(e) = Self::eval(&ctx, js).catch(&ctx) {
+                return Ok(vec![format!("Runtime error (load): {}", Self::describe(e))]);
+            }
+
+            let mut errors = Vec::new();
+            for entry in ENTRY_POINTS {
+                let call = format!(
+                    "if (typeof {0} === \"function\") {0}.call(this, __stub(\"obj\"), __stub(\"e\"));",
+                    entry
+                );
+                if let Err(e) = Self::eval(&ctx, call).catch(&ctx) {
+                    errors.push(format!("Runtime error ({}): {}", entry, Self::describe(e)));
+                }
+            }
+            Ok(errors)
+        })
+    }
+
+    /// Evaluate as a non-strict global script, like the xFrame5 runtime
+    fn eval(ctx: &Ctx<'_>, source: impl Into<Vec<u8>>) -> rquickjs::Result<()> {
+        let mut options = EvalOptions::default();
+        options.strict = false;
+        ctx.eval_with_options(source, options)
+    }
+
+    /// `ReferenceError: ds_membr is not defined at line 12`
+    fn describe(error: CaughtError<'_>) -> String {
+        match error {
+            CaughtError::Exception(ex) => {
+                let name: String = ex.as_object().get("name").unwrap_or_else(|_| "Error".to_string());
+                let message = ex.message().unwrap_or_default();
+                if message == "interrupted" {
+                    return format!("did not finish within {}s (endless loop?)", TIMEOUT.as_secs());
+                }
+                let line = ex
+                    .stack()
+                    .and_then(|stack| {
+                        Regex::new(r"eval_script:(\d+)")
+                            .unwrap()
+                            .captures(&stack)
+                            .map(|cap| cap[1].to_string())
+                    });
+                match line {
+                    Some(line) => format!("{}: {} at line {}", name, message, line),
+                    None => format!("{}: {}", name, message),
+                }
+            }
+            other => other.to_string().trim().to_string(),
+        }
+    }
+}
+
+impl Default for RuntimeSmokeTest {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl Pass for RuntimeSmokeTest {
+    fn name(&self) -> &'static str {
+        "RuntimeSmokeTest"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        if !self.enabled {
+            return PassResult::Ok;
+        }
+        let (Some(xml), Some(js)) = (&ctx.xml, &ctx.javascript) else {
+            return PassResult::Ok;
+        };
+
+        let findings = match Self::execute(xml, js) {
+            Ok(findings) => findings,
+            Err(e) => {
+                ctx.add_warning(format!("Note: Runtime smoke test skipped: {}", e));
+                return PassResult::Ok;
+            }
+        };
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: [JS] {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} runtime smoke test issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const XML: &str = r#"<screen id="SCREEN_MEMBER_LIST">
+  <xlinkdataset id="ds_member"/>
+  <grid name="grid_member" link_data="ds_member"/>
+  <field name="edt_name"/>
+</screen>"#;
+
+    fn create_context(js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(XML.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_valid_screen_runs() {
+        let js = r#"this.on_load = function() {
+    fn_search();
+};
+
+this.fn_search = function() {
+    if (edt_name.getvalue() == "") {
+        alert("이름을 입력하세요");
+    }
+    ds_member.clearData();
+    for (var i = 0; i < ds_member.getRowCount(); i++) {
+        grid_member.setCellValue(i, 0, gfn_trim(ds_member.getColumn(i, "member_name")));
+    }
+    transaction("search", "/member/list", "", "ds_member=ds_member", this.fn_callback);
+};"#;
+        let mut ctx = create_context(js, ExecutionMode::Strict);
+        assert!(matches!(RuntimeSmokeTest::enabled().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_dataset_typo_flagged() {
+        let js = r#"this.fn_search = function() {
+    ds_membr.clearData();
+};"#;
+        let errors = RuntimeSmokeTest::execute(XML, js).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Runtime error (fn_search): ReferenceError:"), "{}", errors[0]);
+        assert!(errors[0].contains("ds_membr"));
+        assert!(errors[0].ends_with("at line 2"), "{}", errors[0]);
+
+        let mut ctx = create_context(js, ExecutionMode::Strict);
+        assert!(RuntimeSmokeTest::enabled().run(&mut ctx).is_error());
+    }
+
+    #[test]
+    fn test_missing_function_and_syntax_errors() {
+        let js = "this.on_load = function() {\n    this.fn_serach();\n};";
+        let mut ctx = create_context(js, ExecutionMode::Relaxed);
+        assert!(RuntimeSmokeTest::enabled().run(&mut ctx).is_warning());
+        assert!(ctx.warnings[0].contains("Runtime error (on_load): TypeError"), "{}", ctx.warnings[0]);
+
+        let errors = RuntimeSmokeTest::execute(XML, "this.fn_search = function() {").unwrap();
+        assert!(errors[0].starts_with("Runtime error (load): SyntaxError"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_endless_loop_interrupted() {
+        let errors = RuntimeSmokeTest::execute(XML, "this.fn_search = function() { while (true) {} };").unwrap();
+        assert!(errors[0].contains("did not finish"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        std::env::remove_var("JS_SMOKE_TEST");
+        let mut ctx = create_context("ds_unknown.clearData();", ExecutionMode::Strict);
+        assert!(matches!(RuntimeSmokeTest::from_env().run(&mut ctx), PassResult::Ok));
+    }
+}
@@ -0,0 +1,160 @@
+//! Pass 4h: Search State Validator
+//!
+//! Screens generated with kept search conditions must save and restore them:
+//! - `fn_saveSearchState` / `fn_restoreSearchState` are added when missing
+//!   (company helpers or the browser session, see `SearchStateIntent`)
+//! - The XML declares the search dataset
+//! - `on_load` restores the conditions, `fn_search` saves them
+//!
+//! Only the helpers are added; where the calls go is left to the screen.
+
+use super::{EditableGridValidator, GraphValidator};
+use crate::domain::{RESTORE_SEARCH_FUNCTION, SAVE_SEARCH_FUNCTION};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+
+/// Search State Validator - kept search conditions are saved and restored
+pub struct SearchStateValidator;
+
+impl SearchStateValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn calls(body: Option<&str>, function: &str) -> bool {
+        body.is_some_and(|body| body.contains(&format!("{}(", function)))
+    }
+}
+
+impl Default for SearchStateValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for SearchStateValidator {
+    fn name(&self) -> &'static str {
+        "SearchStateValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let Some(state) = ctx.intent.search_state.clone() else {
+            return PassResult::Ok;
+        };
+
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            if !GraphValidator::new().extract_datasets(xml).contains(&state.dataset_id) {
+                findings.push(format!("[XML] Search state: no {} dataset to keep", state.dataset_id));
+            }
+        }
+
+        if let Some(js) = ctx.javascript.clone() {
+            let has_save = EditableGridValidator::function_body(&js, SAVE_SEARCH_FUNCTION).is_some();
+            let has_restore = EditableGridValidator::function_body(&js, RESTORE_SEARCH_FUNCTION).is_some();
+            if !has_save && !has_restore {
+                let mut with_helpers = js.clone();
+                if !with_helpers.is_empty() && !with_helpers.ends_with('\n') {
+                    with_helpers.push('\n');
+                }
+                with_helpers.push('\n');
+                with_helpers.push_str(&state.helper_functions());
+                ctx.javascript = Some(with_helpers);
+                ctx.add_warning(format!(
+                    "Added search state helpers ({}, {})",
+                    SAVE_SEARCH_FUNCTION, RESTORE_SEARCH_FUNCTION
+                ));
+            } else if !(has_save && has_restore) {
+                findings.push(format!(
+                    "[JS] Search state: {} and {} must both be defined",
+                    SAVE_SEARCH_FUNCTION, RESTORE_SEARCH_FUNCTION
+                ));
+            }
+
+            if !Self::calls(EditableGridValidator::function_body(&js, "on_load"), RESTORE_SEARCH_FUNCTION) {
+                findings.push(format!("[JS] Search state: on_load does not call {}", RESTORE_SEARCH_FUNCTION));
+            }
+            if !Self::calls(EditableGridValidator::function_body(&js, "fn_search"), SAVE_SEARCH_FUNCTION) {
+                findings.push(format!("[JS] Search state: fn_search does not call {}", SAVE_SEARCH_FUNCTION));
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} search state issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, SearchStateIntent, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const LIST_XML: &str = r#"<screen id="SCREEN_MEMBER_LIST" width="1024" height="768">
+  <xlinkdataset id="ds_search" columns="MEMBER_NAME:&quot;Name&quot;:50"/>
+</screen>"#;
+
+    const LIST_JS: &str = r#"this.on_load = function() {
+    this.fn_restoreSearchState();
+    this.fn_search();
+};
+this.fn_search = function() {
+    this.fn_saveSearchState();
+    // TODO: Replace with actual API endpoint
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let mut intent = UiIntent::new("member_list", ScreenType::List);
+        intent.search_state = Some(SearchStateIntent::for_intent(&intent, None));
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_helpers_added_to_wired_screen() {
+        let mut ctx = create_context(LIST_XML, LIST_JS, ExecutionMode::Strict);
+        assert!(matches!(SearchStateValidator::new().run(&mut ctx), PassResult::Ok));
+
+        let js = ctx.javascript.unwrap();
+        assert!(js.starts_with(LIST_JS));
+        assert!(js.contains("this.fn_saveSearchState = function() {"));
+        assert!(js.contains("this.fn_restoreSearchState = function() {"));
+        assert_eq!(ctx.warnings, vec!["Added search state helpers (fn_saveSearchState, fn_restoreSearchState)"]);
+    }
+
+    #[test]
+    fn test_missing_calls_flagged() {
+        let xml = LIST_XML.replace("ds_search", "ds_cond");
+        let js = LIST_JS
+            .replace("    this.fn_restoreSearchState();\n", "")
+            .replace("    this.fn_saveSearchState();\n", "");
+        let mut ctx = create_context(&xml, &js, ExecutionMode::Relaxed);
+
+        assert!(SearchStateValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 4);
+        assert!(ctx.warnings[0].contains("Added search state helpers"));
+        assert!(ctx.warnings[1].contains("no ds_search dataset to keep"));
+        assert!(ctx.warnings[2].contains("on_load does not call fn_restoreSearchState"));
+        assert!(ctx.warnings[3].contains("fn_search does not call fn_saveSearchState"));
+
+        let mut ctx = create_context(LIST_XML, &js, ExecutionMode::Strict);
+        assert!(SearchStateValidator::new().run(&mut ctx).is_error());
+    }
+}
@@ -0,0 +1,251 @@
+//! Pass 6: Sensitive Data Masking
+//!
+//! Enforces masking on columns the normalizer classified as personal data
+//! (resident registration number, card number, phone):
+//! - Every grid `<data>`/field bound to such a column must carry a masked
+//!   `pattern` (e.g. `######-#******`); relaxed mode adds it
+//! - Unmasking must go through the `fn_check_unmask_auth` permission hook;
+//!   relaxed mode adds a deny-by-default stub when the hook is missing
+//!
+//! Runs after the Minimalism pass so the hook stub is never pruned.
+
+use crate::domain::{SensitiveKind, UNMASK_AUTH_HOOK};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+/// Sensitive Data Pass - enforces masked display of classified columns
+pub struct SensitiveDataPass;
+
+impl SensitiveDataPass {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Classified columns keyed by upper-case name
+    fn sensitive_columns(ctx: &GenerationContext) -> HashMap<String, SensitiveKind> {
+        ctx.intent
+            .sensitive_columns()
+            .into_iter()
+            .filter_map(|c| c.sensitivity.map(|kind| (c.name.to_uppercase(), kind)))
+            .collect()
+    }
+
+    /// Read an attribute value from a tag's attribute string
+    fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+        let re = Regex::new(&format!(r#"\b{}\s*=\s*"([^"]*)""#, regex::escape(name))).unwrap();
+        re.captures(attrs).map(|cap| cap.get(1).unwrap().as_str())
+    }
+
+    /// Column a display tag is bound to (`link_data="ds:COL"`, or `name` on grid `<data>`)
+    fn bound_column(tag: &str, attrs: &str) -> Option<String> {
+        if tag.to_lowercase().contains("dataset") {
+            return None;
+        }
+        if let Some(column) = Self::attr(attrs, "link_data").and_then(|l| l.split_once(':')) {
+            return Some(column.1.to_uppercase());
+        }
+        if tag == "data" {
+            return Self::attr(attrs, "name").map(str::to_uppercase);
+        }
+        None
+    }
+
+    /// Add or replace the `pattern` attribute with the mask
+    fn apply_mask(attrs: &str, mask: &str) -> String {
+        let re = Regex::new(r#"\bpattern\s*=\s*"[^"]*""#).unwrap();
+        if re.is_match(attrs) {
+            return re
+                .replace(attrs, format!(r#"pattern="{}""#, mask).as_str())
+                .into_owned();
+        }
+        let trimmed = attrs.trim_end();
+        match trimmed.strip_suffix('/') {
+            Some(body) => format!(r#"{} pattern="{}"/"#, body.trim_end(), mask),
+            None => format!(r#"{} pattern="{}""#, trimmed, mask),
+        }
+    }
+
+    /// Check display bindings; returns the (possibly masked) XML and findings
+    fn check_bindings(
+        xml: &str,
+        sensitive: &HashMap<String, SensitiveKind>,
+        fix: bool,
+    ) -> (String, Vec<String>) {
+        let tag_re = Regex::new(r#"<(\w+)(\s[^>]*)>"#).unwrap();
+        let mut findings = Vec::new();
+
+        let fixed = tag_re.replace_all(xml, |cap: &Captures| {
+            let tag = &cap[1];
+            let attrs = &cap[2];
+            let Some((column, kind)) = Self::bound_column(tag, attrs)
+                .and_then(|col| sensitive.get(&col).map(|kind| (col, *kind)))
+            else {
+                return cap[0].to_string();
+            };
+
+            if Self::attr(attrs, "pattern").is_some_and(|p| p.contains('*')) {
+                return cap[0].to_string();
+            }
+
+            findings.push(format!(
+                "[XML] Sensitive column '{}' ({}) is displayed unmasked in <{}>; use pattern=\"{}\"",
+                column,
+                kind.description(),
+                tag,
+                kind.mask_pattern()
+            ));
+
+            if fix {
+                format!("<{}{}>", tag, Self::apply_mask(attrs, kind.mask_pattern()))
+            } else {
+                cap[0].to_string()
+            }
+        });
+
+        (fixed.into_owned(), findings)
+    }
+
+    /// Whether the JS defines the unmask permission hook
+    fn defines_hook(js: &str) -> bool {
+        Regex::new(&format!(r#"this\.{}\s*=\s*function"#, UNMASK_AUTH_HOOK))
+            .unwrap()
+            .is_match(js)
+    }
+
+    /// Whether the JS reveals masked values without calling the hook
+    fn unmasks_without_hook(js: &str) -> bool {
+        let call = Regex::new(&format!(r#"{}\s*\("#, UNMASK_AUTH_HOOK)).unwrap();
+        let unmasks = js.to_lowercase().replace(UNMASK_AUTH_HOOK, "").contains("unmask");
+        unmasks && !call.is_match(js)
+    }
+
+    /// Deny-by-default hook stub (to be wired to the permission system)
+    fn hook_stub() -> String {
+        format!(
+            "\n\n// 마스킹 해제 권한 확인 (권한 시스템 연동 필요)\nthis.{} = function(column) {{\n\treturn false;\n}};\n",
+            UNMASK_AUTH_HOOK
+        )
+    }
+}
+
+impl Default for SensitiveDataPass {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for SensitiveDataPass {
+    fn name(&self) -> &'static str {
+        "SensitiveDataPass"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let sensitive = Self::sensitive_columns(ctx);
+        if sensitive.is_empty() {
+            return PassResult::Ok;
+        }
+
+        let fix = !ctx.is_strict();
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            let (fixed, xml_findings) = Self::check_bindings(xml, &sensitive, fix);
+            ctx.xml = Some(fixed);
+            findings.extend(xml_findings);
+        }
+
+        if let Some(js) = &ctx.javascript {
+            if Self::unmasks_without_hook(js) {
+                findings.push(format!(
+                    "[JS] Masked values are revealed without calling '{}'",
+                    UNMASK_AUTH_HOOK
+                ));
+            }
+            if !Self::defines_hook(js) {
+                findings.push(format!(
+                    "[JS] Unmask permission hook '{}' is not defined",
+                    UNMASK_AUTH_HOOK
+                ));
+                if fix {
+                    ctx.javascript = Some(format!("{}{}", js.trim_end(), Self::hook_stub()));
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Enforced masking for {} finding(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnIntent, DatasetIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const HOOK_JS: &str = "this.fn_check_unmask_auth = function(column) {\n\treturn false;\n};";
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let dataset = DatasetIntent::new("ds_member")
+            .with_column(ColumnIntent::new("MEMBER_NAME", "회원명"))
+            .with_column(
+                ColumnIntent::new("JUMIN_NO", "주민번호")
+                    .with_sensitivity(Some(SensitiveKind::ResidentNumber)),
+            );
+        let intent = UiIntent::new("member_list", ScreenType::List).with_dataset(dataset);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_masked_column_passes() {
+        let xml = "<data name=\"JUMIN_NO\" link_data=\"ds_member:JUMIN_NO\" pattern=\"######-#******\"/>";
+        let mut ctx = create_context(xml, HOOK_JS, ExecutionMode::Strict);
+        assert!(matches!(SensitiveDataPass::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_raw_column_masked_in_relaxed_mode() {
+        let xml = r#"<data name="JUMIN_NO" link_data="ds_member:JUMIN_NO" width="120"/>
+<data name="MEMBER_NAME" link_data="ds_member:MEMBER_NAME"/>"#;
+        let mut ctx = create_context(xml, HOOK_JS, ExecutionMode::Relaxed);
+
+        assert!(SensitiveDataPass::new().run(&mut ctx).is_warning());
+        let xml = ctx.xml.as_ref().unwrap();
+        assert!(xml.contains("width=\"120\" pattern=\"######-#******\"/>"));
+        assert!(xml.contains(r#"<data name="MEMBER_NAME" link_data="ds_member:MEMBER_NAME"/>"#));
+        assert!(ctx.warnings[0].contains("'JUMIN_NO' (resident registration number)"));
+    }
+
+    #[test]
+    fn test_raw_field_errors_in_strict_mode() {
+        let xml = r#"<field id="edt_jumin" link_data="ds_member:jumin_no"></field>"#;
+        let mut ctx = create_context(xml, HOOK_JS, ExecutionMode::Strict);
+        assert!(SensitiveDataPass::new().run(&mut ctx).is_error());
+    }
+
+    #[test]
+    fn test_missing_hook_stubbed_and_unguarded_unmask_flagged() {
+        let xml = "<data name=\"JUMIN_NO\" pattern=\"######-#******\"/>";
+        let js = "this.fn_unmask = function() {\n\tthis.grid_member.setpattern(\"\");\n};";
+        let mut ctx = create_context(xml, js, ExecutionMode::Relaxed);
+
+        assert!(SensitiveDataPass::new().run(&mut ctx).is_warning());
+        assert!(ctx.javascript.as_ref().unwrap().contains("this.fn_check_unmask_auth = function(column)"));
+        assert!(ctx.warnings.iter().any(|w| w.contains("revealed without calling")));
+    }
+}
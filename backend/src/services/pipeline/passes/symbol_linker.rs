@@ -16,7 +16,7 @@ impl SymbolLinker {
     }
 
     /// Extract function names referenced in XML event handlers
-    fn extract_xml_handlers(&self, xml: &str) -> HashSet<String> {
+    pub(crate) fn extract_xml_handlers(&self, xml: &str) -> HashSet<String> {
         let mut handlers = HashSet::new();
 
         // Match eventfunc:fn_xxx patterns
@@ -35,7 +35,7 @@ impl SymbolLinker {
     }
 
     /// Extract function names defined in JavaScript
-    fn extract_js_functions(&self, js: &str) -> HashSet<String> {
+    pub(crate) fn extract_js_functions(&self, js: &str) -> HashSet<String> {
         let mut functions = HashSet::new();
 
         // Match this.fn_xxx = function patterns
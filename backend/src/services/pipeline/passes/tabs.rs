@@ -0,0 +1,208 @@
+//! Pass 4f: Tab Panel Validator
+//!
+//! Every declared tab of a tabbed detail screen must be there and bound:
+//! - The tab control (`tab_<container id>`) exists
+//! - Each tab has its panel (`pnl_tab_<id>`) inside the tab control
+//! - Each dataset a tab shows is declared in the intent and in the XML
+//! - With lazy loading, `fn_tab_changed` exists and each lazy tab's
+//!   `fn_load_tab_<id>` exists and loads its dataset
+//!
+//! Nothing is auto-fixed; tab contents can't be guessed.
+
+use std::collections::HashSet;
+
+use super::{EditableGridValidator, GraphValidator};
+use crate::domain::TAB_CHANGED_FUNCTION;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+/// Tab Panel Validator - every tab has a panel and a declared dataset
+pub struct TabPanelValidator;
+
+impl TabPanelValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Content of the named `<tab>` control
+    fn tab_control<'a>(xml: &'a str, control: &str) -> Option<&'a str> {
+        Regex::new(&format!(
+            r#"(?s)<tab\b[^>]*\bname\s*=\s*"{}"[^>]*>(.*?)</tab>"#,
+            regex::escape(control)
+        ))
+        .unwrap()
+        .captures(xml)
+        .map(|c| c.get(1).map_or("", |m| m.as_str()))
+    }
+
+    fn has_panel(xml: &str, panel: &str) -> bool {
+        Regex::new(&format!(r#"<panel\b[^>]*\bname\s*=\s*"{}""#, regex::escape(panel)))
+            .unwrap()
+            .is_match(xml)
+    }
+
+    fn mentions(code: &str, name: &str) -> bool {
+        Regex::new(&format!(r"\b{}\b", regex::escape(name)))
+            .unwrap()
+            .is_match(code)
+    }
+}
+
+impl Default for TabPanelValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for TabPanelValidator {
+    fn name(&self) -> &'static str {
+        "TabPanelValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let Some(tabs) = ctx.intent.tabs.clone().filter(|t| !t.tabs.is_empty()) else {
+            return PassResult::Ok;
+        };
+
+        let mut findings = Vec::new();
+
+        for tab in &tabs.tabs {
+            if !ctx.intent.datasets.iter().any(|ds| ds.id == tab.dataset_id) {
+                findings.push(format!("[Intent] Tab ({}) shows unknown dataset {}", tab.id, tab.dataset_id));
+            }
+        }
+
+        if let Some(xml) = &ctx.xml {
+            match Self::tab_control(xml, &tabs.control()) {
+                None => findings.push(format!("[XML] Tab control ({}) is missing", tabs.control())),
+                Some(control) => {
+                    for tab in &tabs.tabs {
+                        if !Self::has_panel(control, &tab.panel()) {
+                            findings.push(format!("[XML] Tab ({}) has no panel {}", tab.id, tab.panel()));
+                        }
+                    }
+                }
+            }
+
+            let declared: HashSet<String> = GraphValidator::new().extract_datasets(xml);
+            for ds in tabs.dataset_ids() {
+                if !declared.contains(ds) {
+                    findings.push(format!("[XML] Tab dataset {} is not declared", ds));
+                }
+            }
+        }
+
+        let lazy = tabs.lazy_tabs();
+        if let (Some(js), false) = (&ctx.javascript, lazy.is_empty()) {
+            if EditableGridValidator::function_body(js, TAB_CHANGED_FUNCTION).is_none() {
+                findings.push(format!("[JS] Tabs ({}) have no {}", tabs.control(), TAB_CHANGED_FUNCTION));
+            }
+            for tab in &lazy {
+                match EditableGridValidator::function_body(js, &tab.load_function()) {
+                    None => findings.push(format!("[JS] Tab ({}) has no {}", tab.id, tab.load_function())),
+                    Some(body) if !Self::mentions(body, &tab.dataset_id) => findings.push(format!(
+                        "[JS] Tab ({}): {} does not load {}",
+                        tab.id,
+                        tab.load_function(),
+                        tab.dataset_id
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} tab issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DatasetIntent, ScreenType, TabIntent, TabsIntent, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const TABS_XML: &str = r#"<screen id="SCREEN_CUSTOMER_DETAIL" width="800" height="600">
+  <xlinkdataset id="ds_customer" desc="Customer" columns="CUST_ID:&quot;ID&quot;:10:&quot;&quot;:&quot;&quot;"/>
+  <xlinkdataset id="ds_contract" desc="Contract" columns="CONTRACT_NO:&quot;No&quot;:10:&quot;&quot;:&quot;&quot;"/>
+  <tab control_id="1" name="tab_detail" x="10" y="10" width="780" height="500" on_tabchanged="eventfunc:fn_tab_changed(objInst, nPrevItem, nCurItem)">
+    <tab_item title="기본" width="120" height="24">
+      <panel control_id="2" name="pnl_tab_basic" x="0" y="0" width="780" height="470"/>
+    </tab_item>
+    <tab_item title="계약" width="120" height="24">
+      <panel control_id="3" name="pnl_tab_contract" x="0" y="0" width="780" height="470"/>
+    </tab_item>
+  </tab>
+</screen>"#;
+
+    const TABS_JS: &str = r#"this.fn_tab_changed = function(objInst, nPrevItem, nCurItem) {
+    if (nCurItem == 1) { fn_load_tab_contract(); }
+};
+this.fn_load_tab_contract = function() {
+    if (ds_contract.getRowCount() > 0) { return; }
+    // TODO: Replace with actual API endpoint
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let tabs = TabsIntent::new(vec![
+            TabIntent::new("basic", "기본", "ds_customer"),
+            TabIntent::new("contract", "계약", "ds_contract"),
+        ])
+        .lazy();
+        let intent = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_customer"))
+            .with_dataset(DatasetIntent::new("ds_contract"))
+            .with_tabs(tabs);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_complete_tabs_pass() {
+        let mut ctx = create_context(TABS_XML, TABS_JS, ExecutionMode::Strict);
+        assert!(matches!(TabPanelValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_missing_panel_dataset_and_loader_flagged() {
+        let xml = TABS_XML
+            .replace("pnl_tab_contract", "pnl_contract")
+            .replace(r#"<xlinkdataset id="ds_contract""#, r#"<xlinkdataset id="ds_contracts""#);
+        let js = TABS_JS.replace("ds_contract.getRowCount()", "ds_customer.getRowCount()");
+        let mut ctx = create_context(&xml, &js, ExecutionMode::Relaxed);
+
+        assert!(TabPanelValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 3);
+        assert!(ctx.warnings[0].contains("Tab (contract) has no panel pnl_tab_contract"));
+        assert!(ctx.warnings[1].contains("Tab dataset ds_contract is not declared"));
+        assert!(ctx.warnings[2].contains("fn_load_tab_contract does not load ds_contract"));
+
+        let mut ctx = create_context(&xml, TABS_JS, ExecutionMode::Strict);
+        assert!(TabPanelValidator::new().run(&mut ctx).is_error());
+    }
+
+    #[test]
+    fn test_undeclared_tab_dataset_in_intent() {
+        let mut ctx = create_context(TABS_XML, TABS_JS, ExecutionMode::Relaxed);
+        ctx.intent.datasets.retain(|ds| ds.id != "ds_contract");
+        assert!(TabPanelValidator::new().run(&mut ctx).is_warning());
+        assert!(ctx.warnings[0].contains("[Intent] Tab (contract) shows unknown dataset ds_contract"));
+    }
+}
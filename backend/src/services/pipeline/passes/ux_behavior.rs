@@ -0,0 +1,318 @@
+//! Pass 2b: UX Behavior Pass
+//!
+//! Wires the behaviors users expect from every screen, so they don't depend
+//! on the model remembering them:
+//! - Enter in a search field runs `fn_search` (`fn_searchKeydown`)
+//! - F5 doesn't reload the page and lose the input (`fn_screenPrekeydown`)
+//! - A save clicked twice within a second is sent once (`fn_isDoubleSubmit`)
+//!
+//! Handlers the model already wrote are kept and checked instead. The `ux`
+//! section of the company rule set turns individual behaviors off.
+
+use super::EditableGridValidator;
+use crate::domain::{ActionType, CompanyRuleSections, UxBehaviors};
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+const SEARCH_KEYDOWN: &str = "fn_searchKeydown";
+const SCREEN_PREKEYDOWN: &str = "fn_screenPrekeydown";
+const DOUBLE_SUBMIT: &str = "fn_isDoubleSubmit";
+
+/// UX Behavior Pass - enter-to-search, F5 guard and double-submit guard
+#[derive(Clone)]
+pub struct UxBehaviorPass {
+    behaviors: UxBehaviors,
+}
+
+impl UxBehaviorPass {
+    pub fn new() -> Self {
+        Self {
+            behaviors: UxBehaviors::default(),
+        }
+    }
+
+    /// Behaviors of a company rule set (all on without a `ux` section)
+    pub fn for_rules(sections: Option<&CompanyRuleSections>) -> Self {
+        Self {
+            behaviors: sections.and_then(|s| s.ux).unwrap_or_default(),
+        }
+    }
+
+    fn defines(js: &str, name: &str) -> bool {
+        EditableGridValidator::function_body(js, name).is_some()
+    }
+
+    fn append_function(js: &mut String, function: &str) {
+        if !js.is_empty() && !js.ends_with('\n') {
+            js.push('\n');
+        }
+        js.push_str(function);
+    }
+
+    /// Add `on_keydown` to search fields (bound to ds_search or named *search*)
+    fn wire_search_fields(xml: &str) -> (String, usize) {
+        let field = Regex::new(r#"<(\w+_field)\b([^>]*?)\s*(/?>)"#).unwrap();
+        let search = Regex::new(r#"\blink_data\s*=\s*"ds_search\b|\bname\s*=\s*"[^"]*search[^"]*""#).unwrap();
+        let mut count = 0;
+        let wired = field.replace_all(xml, |caps: &regex::Captures| {
+            let attrs = &caps[2];
+            if attrs.contains("on_keydown") || !search.is_match(attrs) {
+                return caps[0].to_string();
+            }
+            count += 1;
+            format!(
+                "<{}{} on_keydown=\"eventfunc:{}(objInst, keycode)\"{}",
+                &caps[1], attrs, SEARCH_KEYDOWN, &caps[3]
+            )
+        });
+        (wired.to_string(), count)
+    }
+
+    /// Add `on_prekeydown` to the screen element unless it has one
+    fn wire_screen_keys(xml: &str) -> Option<String> {
+        let screen = Regex::new(r#"<screen\b([^>]*?)\s*(/?>)"#).unwrap();
+        let caps = screen.captures(xml)?;
+        if caps[1].contains("on_prekeydown") {
+            return None;
+        }
+        let whole = caps.get(0)?;
+        Some(format!(
+            "{}<screen{} on_prekeydown=\"eventfunc:{}(objInst, keycode)\"{}{}",
+            &xml[..whole.start()],
+            &caps[1],
+            SCREEN_PREKEYDOWN,
+            &caps[2],
+            &xml[whole.end()..]
+        ))
+    }
+
+    /// Save functions of the screen: `fn_save` and the intent's save actions
+    fn save_functions(ctx: &GenerationContext) -> Vec<String> {
+        let mut functions = vec!["fn_save".to_string()];
+        for action in &ctx.intent.actions {
+            if action.action_type == ActionType::Save && !functions.contains(&action.function_name) {
+                functions.push(action.function_name.clone());
+            }
+        }
+        functions
+    }
+
+    /// Return early from `function` on a repeated click
+    fn guard_function(js: &str, function: &str) -> Option<String> {
+        let start = Regex::new(&format!(
+            r#"\b{}\s*=\s*function\s*\([^)]*\)\s*\{{"#,
+            regex::escape(function)
+        ))
+        .unwrap()
+        .find(js)?;
+        Some(format!(
+            "{}\n    if (this.{}()) return;{}",
+            &js[..start.end()],
+            DOUBLE_SUBMIT,
+            &js[start.end()..]
+        ))
+    }
+}
+
+impl Default for UxBehaviorPass {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for UxBehaviorPass {
+    fn name(&self) -> &'static str {
+        "UxBehaviorPass"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let (Some(mut xml), Some(mut js)) = (ctx.xml.clone(), ctx.javascript.clone()) else {
+            return PassResult::Ok;
+        };
+
+        let mut fixes = Vec::new();
+        let mut findings = Vec::new();
+
+        if self.behaviors.enter_to_search && Self::defines(&js, "fn_search") {
+            let (wired, count) = Self::wire_search_fields(&xml);
+            if count > 0 {
+                xml = wired;
+                fixes.push(format!("Added Enter-to-search to {} search field(s)", count));
+            }
+            match EditableGridValidator::function_body(&js, SEARCH_KEYDOWN) {
+                Some(body) if !body.contains("fn_search") => findings.push(format!(
+                    "[JS] Enter-to-search handler ({}) never calls fn_search",
+                    SEARCH_KEYDOWN
+                )),
+                Some(_) => {}
+                None if xml.contains(SEARCH_KEYDOWN) => Self::append_function(
+                    &mut js,
+                    &format!(
+                        "\n// Enter in a search field runs the search\n\
+                         this.{} = function(objInst, keycode) {{\n    \
+                         if (keycode == 13) {{\n        this.fn_search();\n    }}\n}};\n",
+                        SEARCH_KEYDOWN
+                    ),
+                ),
+                None => {}
+            }
+        }
+
+        if self.behaviors.f5_guard {
+            if let Some(wired) = Self::wire_screen_keys(&xml) {
+                xml = wired;
+                fixes.push("Added F5 refresh guard to the screen".to_string());
+            }
+            match EditableGridValidator::function_body(&js, SCREEN_PREKEYDOWN) {
+                Some(body) if !body.contains("116") => findings.push(format!(
+                    "[JS] F5 refresh guard ({}) does not block F5 (keycode 116)",
+                    SCREEN_PREKEYDOWN
+                )),
+                Some(_) => {}
+                None if xml.contains(SCREEN_PREKEYDOWN) => Self::append_function(
+                    &mut js,
+                    &format!(
+                        "\n// F5 would reload the page and lose the input; 1 marks the key as handled\n\
+                         this.{} = function(objInst, keycode) {{\n    \
+                         if (keycode == 116) {{\n        return 1;\n    }}\n    return 0;\n}};\n",
+                        SCREEN_PREKEYDOWN
+                    ),
+                ),
+                None => {}
+            }
+        }
+
+        if self.behaviors.double_submit_guard {
+            let mut guarded = Vec::new();
+            for function in Self::save_functions(ctx) {
+                let unguarded = EditableGridValidator::function_body(&js, &function)
+                    .is_some_and(|body| !body.contains(DOUBLE_SUBMIT));
+                if let Some(with_guard) = Self::guard_function(&js, &function).filter(|_| unguarded) {
+                    js = with_guard;
+                    guarded.push(function);
+                }
+            }
+            if !guarded.is_empty() {
+                fixes.push(format!("Added double-submit guard to {}", guarded.join(", ")));
+            }
+            if js.contains(&format!("this.{}()", DOUBLE_SUBMIT)) && !Self::defines(&js, DOUBLE_SUBMIT) {
+                Self::append_function(
+                    &mut js,
+                    &format!(
+                        "\n// A second save click within a second is ignored\n\
+                         this.{} = function() {{\n    \
+                         var now = Date.now();\n    \
+                         if (this.lastSubmitAt && now - this.lastSubmitAt < 1000) {{\n        return true;\n    }}\n    \
+                         this.lastSubmitAt = now;\n    return false;\n}};\n",
+                        DOUBLE_SUBMIT
+                    ),
+                );
+            }
+        }
+
+        ctx.xml = Some(xml);
+        ctx.javascript = Some(js);
+        for fix in fixes {
+            ctx.add_warning(fix);
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} UX behavior issue(s)", findings.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const LIST_XML: &str = r#"<screen id="SCREEN_MEMBER_LIST" width="1024" height="768">
+  <hangul_field control_id="1" name="edt_member_name" link_data="ds_search:MEMBER_NAME"/>
+  <normal_field control_id="2" name="edt_search_id"/>
+  <normal_field control_id="3" name="edt_memo" link_data="ds_member:MEMO"/>
+</screen>"#;
+
+    const LIST_JS: &str = r#"this.fn_search = function() {
+    // TODO: Replace with actual API endpoint
+};
+this.fn_save = function() {
+    // TODO: Replace with actual API endpoint
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_behaviors_wired() {
+        let mut ctx = create_context(LIST_XML, LIST_JS, ExecutionMode::Strict);
+        assert!(matches!(UxBehaviorPass::new().run(&mut ctx), PassResult::Ok));
+
+        let xml = ctx.xml.clone().unwrap();
+        let js = ctx.javascript.clone().unwrap();
+        assert_eq!(xml.matches("on_keydown=\"eventfunc:fn_searchKeydown(objInst, keycode)\"").count(), 2);
+        assert!(xml.contains("link_data=\"ds_member:MEMO\"/>"));
+        assert!(xml.contains("height=\"768\" on_prekeydown=\"eventfunc:fn_screenPrekeydown(objInst, keycode)\">"));
+        assert!(js.contains("this.fn_searchKeydown = function(objInst, keycode) {\n    if (keycode == 13)"));
+        assert!(js.contains("if (keycode == 116)"));
+        assert!(js.contains("this.fn_save = function() {\n    if (this.fn_isDoubleSubmit()) return;"));
+        assert!(js.contains("this.fn_isDoubleSubmit = function() {"));
+        assert_eq!(ctx.warnings.len(), 3);
+        assert!(ctx.warnings[0].contains("Added Enter-to-search to 2 search field(s)"));
+        assert!(ctx.warnings[2].contains("Added double-submit guard to fn_save"));
+
+        // Running again changes nothing
+        let mut again = create_context(&xml, &js, ExecutionMode::Strict);
+        assert!(matches!(UxBehaviorPass::new().run(&mut again), PassResult::Ok));
+        assert_eq!(again.xml.unwrap(), xml);
+        assert_eq!(again.javascript.unwrap(), js);
+        assert!(again.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_company_rules_turn_behaviors_off() {
+        let sections = CompanyRuleSections {
+            ux: Some(UxBehaviors {
+                enter_to_search: false,
+                f5_guard: false,
+                double_submit_guard: true,
+            }),
+            ..Default::default()
+        };
+        let mut ctx = create_context(LIST_XML, LIST_JS, ExecutionMode::Strict);
+        UxBehaviorPass::for_rules(Some(&sections)).run(&mut ctx);
+
+        assert_eq!(ctx.xml.as_deref(), Some(LIST_XML));
+        assert!(ctx.javascript.unwrap().contains("fn_isDoubleSubmit"));
+    }
+
+    #[test]
+    fn test_existing_handler_checked() {
+        let js = format!(
+            "{}\nthis.fn_searchKeydown = function(objInst, keycode) {{\n    return 0;\n}};",
+            LIST_JS
+        );
+        let mut ctx = create_context(LIST_XML, &js, ExecutionMode::Relaxed);
+        assert!(UxBehaviorPass::new().run(&mut ctx).is_warning());
+        assert!(ctx.warnings.iter().any(|w| w.contains("(fn_searchKeydown) never calls fn_search")));
+
+        let mut ctx = create_context(LIST_XML, &js, ExecutionMode::Strict);
+        assert!(UxBehaviorPass::new().run(&mut ctx).is_error());
+    }
+}
@@ -0,0 +1,134 @@
+//! Pass 7c: WASM Validation Plugins
+//!
+//! Runs the customer checks administrators uploaded as WebAssembly modules
+//! (see `services::wasm_plugin`) on the screen's XML, JavaScript and intent.
+//!
+//! Plugin errors fail strict generations; plugin warnings become `Warning:`
+//! entries, plugin notes `Note:` entries. A plugin that traps or runs out of
+//! its budget is skipped with a note. No-op when no plugin is enabled.
+
+use std::sync::Arc;
+
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use crate::services::wasm_plugin::{PluginSeverity, WasmPlugin, WasmPlugins};
+
+/// WASM Plugin Pass - runs uploaded customer checks
+pub struct WasmPluginPass {
+    plugins: Arc<Vec<WasmPlugin>>,
+}
+
+impl WasmPluginPass {
+    /// Enabled plugins as of the last sync
+    pub fn loaded() -> Self {
+        Self::with_plugins(WasmPlugins::loaded())
+    }
+
+    pub fn with_plugins(plugins: Arc<Vec<WasmPlugin>>) -> Self {
+        Self { plugins }
+    }
+}
+
+impl Default for WasmPluginPass {
+    fn default() -> Self {
+        Self::loaded()
+    }
+}
+
+impl Pass for WasmPluginPass {
+    fn name(&self) -> &'static str {
+        "WasmPlugins"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        if self.plugins.is_empty() {
+            return PassResult::Ok;
+        }
+        let (Some(xml), Some(js)) = (ctx.xml.clone(), ctx.javascript.clone()) else {
+            return PassResult::Ok;
+        };
+        let intent = serde_json::to_string(&ctx.intent).unwrap_or_else(|_| "{}".to_string());
+
+        let mut errors = Vec::new();
+        let mut warnings = 0;
+        for plugin in self.plugins.iter() {
+            let findings = match plugin.validate(&xml, &js, &intent) {
+                Ok(findings) => findings,
+                Err(e) => {
+                    ctx.add_warning(format!("Note: Plugin '{}' skipped: {}", plugin.name, e));
+                    continue;
+                }
+            };
+            for finding in findings {
+                match finding.severity {
+                    PluginSeverity::Error => errors.push(format!("[{}] {}", plugin.name, finding.message)),
+                    PluginSeverity::Warning => {
+                        warnings += 1;
+                        ctx.add_warning(format!("Warning: [{}] {}", plugin.name, finding.message));
+                    }
+                    PluginSeverity::Note => {
+                        ctx.add_warning(format!("Note: [{}] {}", plugin.name, finding.message));
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() && ctx.is_strict() {
+            return PassResult::Error(errors.join("; "));
+        }
+        for error in &errors {
+            ctx.add_warning(format!("Warning: {}", error));
+        }
+        let issues = errors.len() + warnings;
+        if issues == 0 {
+            return PassResult::Ok;
+        }
+        PassResult::Warning(format!("Found {} plugin issue(s)", issues))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+    use crate::services::wasm_plugin::tests::PASSWORD_CHECK;
+
+    fn pass() -> WasmPluginPass {
+        let plugin = WasmPlugin::compile("acme-security", &wat::parse_str(PASSWORD_CHECK).unwrap()).unwrap();
+        WasmPluginPass::with_plugins(Arc::new(vec![plugin]))
+    }
+
+    fn create_context(js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some("<screen id=\"SCREEN_MEMBER_LIST\"/>".to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_plugin_error_fails_strict_and_warns_relaxed() {
+        let js = "this.fn_login = function() { console.log(edt_password.getvalue()); };";
+
+        let mut ctx = create_context(js, ExecutionMode::Strict);
+        match pass().run(&mut ctx) {
+            PassResult::Error(e) => assert_eq!(e, "[acme-security] password in log"),
+            other => panic!("expected error, got {:?}", other),
+        }
+
+        let mut ctx = create_context(js, ExecutionMode::Relaxed);
+        assert!(pass().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings, vec!["Warning: [acme-security] password in log"]);
+    }
+
+    #[test]
+    fn test_clean_screen_gets_plugin_notes() {
+        let mut ctx = create_context("this.fn_search = function() {};", ExecutionMode::Strict);
+        assert!(matches!(pass().run(&mut ctx), PassResult::Ok));
+        assert_eq!(ctx.warnings, vec!["Note: [acme-security] checked"]);
+
+        let mut ctx = create_context("this.fn_search = function() {};", ExecutionMode::Strict);
+        assert!(matches!(WasmPluginPass::with_plugins(Arc::default()).run(&mut ctx), PassResult::Ok));
+        assert!(ctx.warnings.is_empty());
+    }
+}
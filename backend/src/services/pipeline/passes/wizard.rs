@@ -0,0 +1,174 @@
+//! Pass 4e: Wizard Step Validator
+//!
+//! Every declared step of a wizard screen must be reachable and checked:
+//! - Each step has its panel (`pnl_step_<id>`)
+//! - Each step has its validation function (`fn_validate_<id>`)
+//! - `fn_prev`, `fn_next` and `fn_submit` exist
+//! - `fn_submit` sends every step dataset
+//!
+//! Nothing is auto-fixed; step contents can't be guessed.
+
+use super::EditableGridValidator;
+use crate::domain::WIZARD_FUNCTIONS;
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+use regex::Regex;
+
+/// Wizard Step Validator - every step has a panel and handlers
+pub struct WizardStepValidator;
+
+impl WizardStepValidator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn has_panel(xml: &str, panel: &str) -> bool {
+        Regex::new(&format!(r#"<panel\b[^>]*\bname\s*=\s*"{}""#, regex::escape(panel)))
+            .unwrap()
+            .is_match(xml)
+    }
+
+    fn defines(js: &str, function: &str) -> bool {
+        EditableGridValidator::function_body(js, function).is_some()
+    }
+
+    fn mentions(code: &str, name: &str) -> bool {
+        Regex::new(&format!(r"\b{}\b", regex::escape(name)))
+            .unwrap()
+            .is_match(code)
+    }
+}
+
+impl Default for WizardStepValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for WizardStepValidator {
+    fn name(&self) -> &'static str {
+        "WizardStepValidator"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let Some(wizard) = ctx.intent.wizard_steps() else {
+            return PassResult::Ok;
+        };
+
+        let mut findings = Vec::new();
+
+        if let Some(xml) = &ctx.xml {
+            for step in &wizard.steps {
+                if !Self::has_panel(xml, &step.panel()) {
+                    findings.push(format!("[XML] Wizard step ({}) has no panel {}", step.id, step.panel()));
+                }
+            }
+        }
+
+        if let Some(js) = &ctx.javascript {
+            for step in &wizard.steps {
+                if !Self::defines(js, &step.validate_function()) {
+                    findings.push(format!(
+                        "[JS] Wizard step ({}) has no {}",
+                        step.id,
+                        step.validate_function()
+                    ));
+                }
+            }
+            for function in WIZARD_FUNCTIONS {
+                if !Self::defines(js, function) {
+                    findings.push(format!("[JS] Wizard ({}) has no {}", ctx.intent.screen_name, function));
+                }
+            }
+            if let Some(body) = EditableGridValidator::function_body(js, "fn_submit") {
+                for ds in wizard.dataset_ids() {
+                    if !Self::mentions(body, ds) {
+                        findings.push(format!("[JS] Wizard ({}): fn_submit does not send {}", ctx.intent.screen_name, ds));
+                    }
+                }
+            }
+        }
+
+        if findings.is_empty() {
+            return PassResult::Ok;
+        }
+
+        if ctx.is_strict() {
+            return PassResult::Error(findings.join("; "));
+        }
+
+        for finding in &findings {
+            ctx.add_warning(format!("Warning: {}", finding));
+        }
+
+        PassResult::Warning(format!("Found {} wizard issue(s)", findings.len()))
+    }
+
+    fn main_screen_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DatasetIntent, ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const WIZARD_XML: &str = r#"<screen id="SCREEN_MEMBER_JOIN" width="800" height="600">
+  <panel control_id="1" name="pnl_step_member" x="10" y="10" width="780" height="500"/>
+  <panel control_id="2" name="pnl_step_agreement" x="10" y="10" width="780" height="500" visible="0"/>
+</screen>"#;
+
+    const WIZARD_JS: &str = r#"this.fn_validate_member = function() { return true; };
+this.fn_validate_agreement = function() { return true; };
+this.fn_prev = function() {};
+this.fn_next = function() {};
+this.fn_submit = function() {
+    // TODO: Replace with actual API endpoint
+    var inputs = "ds_member=ds_member ds_agreement=ds_agreement";
+};"#;
+
+    fn create_context(xml: &str, js: &str, mode: ExecutionMode) -> GenerationContext {
+        let intent = UiIntent::new("member_join", ScreenType::Wizard)
+            .with_dataset(DatasetIntent::new("ds_member"))
+            .with_dataset(DatasetIntent::new("ds_agreement"));
+        let mut ctx = GenerationContext::new("".to_string(), intent, mode);
+        ctx.xml = Some(xml.to_string());
+        ctx.javascript = Some(js.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_other_screens_not_checked() {
+        let intent = UiIntent::new("member_list", ScreenType::List).with_dataset(DatasetIntent::new("ds_member"));
+        let mut ctx = GenerationContext::new("".to_string(), intent, ExecutionMode::Strict);
+        ctx.javascript = Some(String::new());
+        assert!(matches!(WizardStepValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_complete_wizard_passes() {
+        let mut ctx = create_context(WIZARD_XML, WIZARD_JS, ExecutionMode::Strict);
+        assert!(matches!(WizardStepValidator::new().run(&mut ctx), PassResult::Ok));
+    }
+
+    #[test]
+    fn test_missing_step_flagged() {
+        let xml = WIZARD_XML.replace("pnl_step_agreement", "pnl_terms");
+        let js = WIZARD_JS
+            .replace("this.fn_validate_agreement = function() { return true; };\n", "")
+            .replace(" ds_agreement=ds_agreement", "")
+            .replace("this.fn_prev = function() {};\n", "");
+        let mut ctx = create_context(&xml, &js, ExecutionMode::Relaxed);
+
+        assert!(WizardStepValidator::new().run(&mut ctx).is_warning());
+        assert_eq!(ctx.warnings.len(), 4);
+        assert!(ctx.warnings[0].contains("Wizard step (agreement) has no panel pnl_step_agreement"));
+        assert!(ctx.warnings[1].contains("has no fn_validate_agreement"));
+        assert!(ctx.warnings[2].contains("has no fn_prev"));
+        assert!(ctx.warnings[3].contains("fn_submit does not send ds_agreement"));
+
+        let mut ctx = create_context(&xml, WIZARD_JS, ExecutionMode::Strict);
+        assert!(WizardStepValidator::new().run(&mut ctx).is_error());
+    }
+}
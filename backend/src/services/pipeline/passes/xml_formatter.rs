@@ -0,0 +1,273 @@
+//! Pass 8: XML Formatter
+//!
+//! Pretty-prints the screen XML so a regenerated screen diffs cleanly against
+//! the version in Git:
+//! - One element per line, two spaces per nesting level
+//! - Attributes in a canonical order: `control_id`, `id`, `name`, geometry,
+//!   then the rest alphabetically, event handlers (`on_*`) last
+//! - Tags longer than the line limit get one attribute per line, aligned
+//!   under the first
+//!
+//! Attribute values, text, comments and the XML declaration are copied as they
+//! are. Runs last so markup added by earlier passes is formatted too; XML the
+//! formatter can't read is left untouched with a note. Requests with
+//! `preserve_xml_format` skip the pass through the pipeline profile.
+
+use regex::Regex;
+
+use crate::services::pipeline::{GenerationContext, Pass, PassResult};
+
+/// Spaces per nesting level
+const INDENT: usize = 2;
+
+/// Longest single-line tag before attributes are split over lines
+const MAX_LINE_LENGTH: usize = 120;
+
+/// Attributes that come first, in this order
+const LEADING_ATTRIBUTES: &[&str] = &["control_id", "id", "name", "x", "y", "right", "bottom", "width", "height"];
+
+/// A piece of the XML
+enum Token<'a> {
+    /// Declaration, doctype, comment or CDATA, copied as is
+    Verbatim(&'a str),
+    Open {
+        name: &'a str,
+        /// `(name, quoted value)`
+        attributes: Vec<(&'a str, &'a str)>,
+        self_closing: bool,
+    },
+    Close(&'a str),
+    Text(&'a str),
+}
+
+/// XML Formatter - canonical indentation and attribute order
+pub struct XmlFormatter;
+
+impl XmlFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The formatted XML, or why it can't be formatted
+    pub fn format(xml: &str) -> Result<String, String> {
+        let tokens = Self::tokenize(xml)?;
+        let mut lines: Vec<String> = Vec::new();
+        let mut open: Vec<&str> = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let pad = " ".repeat(open.len() * INDENT);
+            match &tokens[i] {
+                Token::Verbatim(raw) => lines.push(format!("{}{}", pad, raw)),
+                Token::Text(text) => lines.push(format!("{}{}", pad, text)),
+                Token::Open { name, attributes, self_closing: true } => {
+                    lines.push(Self::tag(&pad, name, attributes, "/>"));
+                }
+                Token::Open { name, attributes, .. } => match (tokens.get(i + 1), tokens.get(i + 2)) {
+                    // Text-only and empty elements stay on one line
+                    (Some(Token::Text(text)), Some(Token::Close(close))) if close == name => {
+                        lines.push(format!("{}{}</{}>", Self::tag(&pad, name, attributes, ">"), text, name));
+                        i += 2;
+                    }
+                    (Some(Token::Close(close)), _) if close == name => {
+                        lines.push(format!("{}</{}>", Self::tag(&pad, name, attributes, ">"), name));
+                        i += 1;
+                    }
+                    _ => {
+                        lines.push(Self::tag(&pad, name, attributes, ">"));
+                        open.push(name);
+                    }
+                },
+                Token::Close(name) => {
+                    match open.pop() {
+                        Some(expected) if expected == *name => {}
+                        Some(expected) => return Err(format!("</{}> closes <{}>", name, expected)),
+                        None => return Err(format!("</{}> without an opening tag", name)),
+                    }
+                    lines.push(format!("{}</{}>", " ".repeat(open.len() * INDENT), name));
+                }
+            }
+            i += 1;
+        }
+        if let Some(name) = open.last() {
+            return Err(format!("<{}> is never closed", name));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn tokenize(xml: &str) -> Result<Vec<Token<'_>>, String> {
+        let attribute_re = Regex::new(r#"([\w:.-]+)\s*=\s*("[^"]*"|'[^']*')"#).unwrap();
+        let mut tokens = Vec::new();
+        let mut pos = 0;
+        while let Some(start) = xml[pos..].find('<').map(|i| pos + i) {
+            let text = xml[pos..start].trim();
+            if !text.is_empty() {
+                tokens.push(Token::Text(text));
+            }
+            let rest = &xml[start..];
+            let closer = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>"), ("<!", ">")]
+                .into_iter()
+                .find(|(open, _)| rest.starts_with(open));
+            let end = if let Some((open, close)) = closer {
+                let end = rest.find(close).ok_or_else(|| format!("unclosed {}", open))? + close.len();
+                tokens.push(Token::Verbatim(&rest[..end]));
+                end
+            } else {
+                let end = Self::tag_end(rest).ok_or("unclosed tag")?;
+                let body = &rest[1..end - 1];
+                if let Some(name) = body.strip_prefix('/') {
+                    tokens.push(Token::Close(name.trim()));
+                } else {
+                    let self_closing = body.ends_with('/');
+                    let body = body.trim_end_matches('/');
+                    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+                    let (name, rest_of_tag) = body.split_at(name_end);
+                    if name.is_empty() {
+                        return Err("tag without a name".to_string());
+                    }
+                    let attributes: Vec<(&str, &str)> = attribute_re
+                        .captures_iter(rest_of_tag)
+                        .map(|c| (c.get(1).unwrap().as_str(), c.get(2).unwrap().as_str()))
+                        .collect();
+                    if !attribute_re.replace_all(rest_of_tag, "").trim().is_empty() {
+                        return Err(format!("unreadable attributes in <{}>", name));
+                    }
+                    tokens.push(Token::Open { name, attributes, self_closing });
+                }
+                end
+            };
+            pos = start + end;
+        }
+        let text = xml[pos..].trim();
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+        Ok(tokens)
+    }
+
+    /// Offset just past the `>` ending the tag at the start of `rest` (quotes respected)
+    fn tag_end(rest: &str) -> Option<usize> {
+        let mut quote = None;
+        for (i, c) in rest.char_indices() {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), _) if q == c => quote = None,
+                (None, '>') => return Some(i + 1),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn tag(pad: &str, name: &str, attributes: &[(&str, &str)], end: &str) -> String {
+        let mut attributes = attributes.to_vec();
+        attributes.sort_by_key(|(attr, _)| Self::rank(attr));
+        let rendered: Vec<String> = attributes.iter().map(|(attr, value)| format!("{}={}", attr, value)).collect();
+
+        let single = if rendered.is_empty() {
+            format!("{}<{}{}", pad, name, end)
+        } else {
+            format!("{}<{} {}{}", pad, name, rendered.join(" "), end)
+        };
+        let multi_line_value = attributes.iter().any(|(_, value)| value.contains('\n'));
+        if rendered.len() <= 1 || (!multi_line_value && single.chars().count() <= MAX_LINE_LENGTH) {
+            return single;
+        }
+        let align = format!("\n{}{}", pad, " ".repeat(name.len() + 2));
+        format!("{}<{} {}{}", pad, name, rendered.join(&align), end)
+    }
+
+    /// Sort key: leading attributes in their order, then the rest, then events
+    fn rank(attribute: &str) -> (u8, usize, String) {
+        match LEADING_ATTRIBUTES.iter().position(|a| *a == attribute) {
+            Some(index) => (0, index, String::new()),
+            None if attribute.starts_with("on_") => (2, 0, attribute.to_string()),
+            None => (1, 0, attribute.to_string()),
+        }
+    }
+}
+
+impl Default for XmlFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pass for XmlFormatter {
+    fn name(&self) -> &'static str {
+        "XmlFormatter"
+    }
+
+    fn run(&self, ctx: &mut GenerationContext) -> PassResult {
+        let Some(xml) = &ctx.xml else {
+            return PassResult::Ok;
+        };
+        match Self::format(xml) {
+            Ok(formatted) => {
+                ctx.xml = Some(formatted);
+            }
+            Err(reason) => {
+                ctx.add_warning(format!("Note: XML left unformatted: {}", reason));
+            }
+        }
+        PassResult::Ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::pipeline::ExecutionMode;
+
+    const RAW_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<screen width="1024" id="SCREEN_MEMBER_LIST" height="768"><!-- 회원 목록 -->
+    <xlinkdataset desc="회원" id="ds_member" columns="MEMBER_ID:&quot;ID&quot;:10"/>
+<panel name="pnl_search" height="50" control_id="1" x="0" y="0" width="1024">
+      <text y="12" x="20" name="txt_name" control_id="2" width="60" height="24">이름</text>
+      <pushbutton on_click="eventfunc:fn_search()" text="조회" x="900" y="10" width="100" height="30" control_id="3" name="btn_search" font="맑은 고딕,9,1,0,0,0" back_color="00007BFF" text_color="00FFFFFF"/>
+</panel></screen>"#;
+
+    const FORMATTED_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<screen id="SCREEN_MEMBER_LIST" width="1024" height="768">
+  <!-- 회원 목록 -->
+  <xlinkdataset id="ds_member" columns="MEMBER_ID:&quot;ID&quot;:10" desc="회원"/>
+  <panel control_id="1" name="pnl_search" x="0" y="0" width="1024" height="50">
+    <text control_id="2" name="txt_name" x="20" y="12" width="60" height="24">이름</text>
+    <pushbutton control_id="3"
+                name="btn_search"
+                x="900"
+                y="10"
+                width="100"
+                height="30"
+                back_color="00007BFF"
+                font="맑은 고딕,9,1,0,0,0"
+                text="조회"
+                text_color="00FFFFFF"
+                on_click="eventfunc:fn_search()"/>
+  </panel>
+</screen>"#;
+
+    #[test]
+    fn test_formats_with_canonical_attribute_order() {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new(String::new(), intent, ExecutionMode::Strict);
+        ctx.xml = Some(RAW_XML.to_string());
+
+        assert!(matches!(XmlFormatter::new().run(&mut ctx), PassResult::Ok));
+        assert_eq!(ctx.xml.as_deref(), Some(FORMATTED_XML));
+        assert!(ctx.warnings.is_empty());
+        assert_eq!(XmlFormatter::format(FORMATTED_XML).unwrap(), FORMATTED_XML);
+    }
+
+    #[test]
+    fn test_unreadable_xml_left_as_is() {
+        let xml = "<screen id=\"S\"><panel></screen>";
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        let mut ctx = GenerationContext::new(String::new(), intent, ExecutionMode::Strict);
+        ctx.xml = Some(xml.to_string());
+
+        assert!(matches!(XmlFormatter::new().run(&mut ctx), PassResult::Ok));
+        assert_eq!(ctx.xml.as_deref(), Some(xml));
+        assert_eq!(ctx.warnings, vec!["Note: XML left unformatted: </screen> closes <panel>"]);
+    }
+}
@@ -0,0 +1,247 @@
+//! Stream Prevalidator
+//!
+//! Cheap checks run on an xFrame5 response while it streams in, so an
+//! obviously broken generation is aborted and retried instead of being paid
+//! for in full. Only checks with no false positives on output the pipeline
+//! can repair belong here; everything else is left to the passes.
+//!
+//! - No XML section or `<screen>` tag within the first 2000 characters
+//! - `</screen>` before any `<screen>`, or a `<screen>` opened inside another
+//! - The same line repeated 20 times in a row (a generation loop)
+//! - At the end: no `<screen>` at all, an unclosed `<screen>` or a code block
+//!   left open (truncated output)
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::llm::{StreamAborted, StreamObserver};
+
+/// Characters to wait for an XML section before giving up
+const XML_DEADLINE_CHARS: usize = 2000;
+
+/// Identical consecutive lines taken as a generation loop
+const MAX_REPEATED_LINES: usize = 20;
+
+/// Prefix of the generation log note left by an abort
+pub const ABORT_NOTE_PREFIX: &str = "Note: Aborted streamed response";
+
+/// Generation log note for an aborted stream
+pub fn abort_note(aborted: &StreamAborted) -> String {
+    format!("{} after {} chars ({})", ABORT_NOTE_PREFIX, aborted.received_chars, aborted.reason)
+}
+
+/// Characters received and reason from a note written by [`abort_note`]
+pub fn parse_abort_note(note: &str) -> Option<(usize, &str)> {
+    let rest = note.strip_prefix(ABORT_NOTE_PREFIX)?.strip_prefix(" after ")?;
+    let (chars, reason) = rest.split_once(" chars (")?;
+    Some((chars.parse().ok()?, reason.strip_suffix(')')?))
+}
+
+fn screen_tags() -> &'static (Regex, Regex) {
+    static RE: OnceLock<(Regex, Regex)> = OnceLock::new();
+    RE.get_or_init(|| {
+        (
+            Regex::new(r"(?i)<screen[\s>]").expect("Failed to compile screen regex"),
+            Regex::new(r"(?i)</screen\s*>").expect("Failed to compile screen regex"),
+        )
+    })
+}
+
+/// Incremental checks over a streamed xFrame5 response
+#[derive(Debug, Default)]
+pub struct StreamPrevalidator {
+    /// Text of the line still streaming in
+    partial: String,
+    received_chars: usize,
+    xml_seen: bool,
+    open_screens: usize,
+    screens_seen: usize,
+    /// Last fence line opened a language block
+    block_open: bool,
+    last_line: String,
+    repeats: usize,
+}
+
+impl StreamPrevalidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn abort(&self, reason: impl Into<String>) -> Result<(), StreamAborted> {
+        Err(StreamAborted {
+            reason: reason.into(),
+            received_chars: self.received_chars,
+        })
+    }
+
+    fn mentions_xml(text: &str) -> bool {
+        text.contains("XML") || text.contains("```xml") || text.contains("<?xml") || screen_tags().0.is_match(text)
+    }
+
+    fn check_line(&mut self, line: &str) -> Result<(), StreamAborted> {
+        let trimmed = line.trim();
+
+        // Nested and repeated fences are common, so only remember whether the
+        // last fence opened a block (```lang) or closed one
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            self.block_open = !lang.trim().is_empty();
+        }
+
+        if !trimmed.is_empty() {
+            if trimmed == self.last_line {
+                self.repeats += 1;
+                if self.repeats >= MAX_REPEATED_LINES {
+                    return self.abort(format!("line repeated {} times", self.repeats));
+                }
+            } else {
+                self.last_line = trimmed.to_string();
+                self.repeats = 1;
+            }
+        }
+
+        if Self::mentions_xml(trimmed) {
+            self.xml_seen = true;
+        }
+
+        let (open_re, close_re) = screen_tags();
+        let mut tags: Vec<(usize, bool)> = open_re
+            .find_iter(line)
+            .map(|m| (m.start(), true))
+            .chain(close_re.find_iter(line).map(|m| (m.start(), false)))
+            .collect();
+        tags.sort_unstable();
+
+        for (_, opening) in tags {
+            if opening {
+                if self.open_screens > 0 {
+                    return self.abort("screen tag opened inside another screen");
+                }
+                self.open_screens += 1;
+                self.screens_seen += 1;
+                self.xml_seen = true;
+            } else if self.open_screens == 0 {
+                return self.abort("closing screen tag without an opening tag");
+            } else {
+                self.open_screens -= 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl StreamObserver for StreamPrevalidator {
+    fn on_text(&mut self, text: &str) -> Result<(), StreamAborted> {
+        self.received_chars += text.chars().count();
+        self.partial.push_str(text);
+
+        while let Some(pos) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=pos).collect();
+            self.check_line(&line)?;
+        }
+
+        // The line still streaming in counts too (single-line XML)
+        if !self.xml_seen && self.received_chars > XML_DEADLINE_CHARS && !Self::mentions_xml(&self.partial) {
+            return self.abort(format!("no XML within the first {} characters", XML_DEADLINE_CHARS));
+        }
+        Ok(())
+    }
+
+    fn on_end(&mut self) -> Result<(), StreamAborted> {
+        let rest = std::mem::take(&mut self.partial);
+        self.check_line(&rest)?;
+
+        if self.screens_seen == 0 {
+            return self.abort("no screen tag in the response");
+        }
+        if self.open_screens > 0 {
+            return self.abort("screen tag never closed (truncated response)");
+        }
+        if self.block_open {
+            return self.abort("code block never closed (truncated response)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `text` in small chunks, like a token stream
+    fn stream(text: &str) -> Result<(), StreamAborted> {
+        let mut validator = StreamPrevalidator::new();
+        let chars: Vec<char> = text.chars().collect();
+        for chunk in chars.chunks(7) {
+            validator.on_text(&chunk.iter().collect::<String>())?;
+        }
+        validator.on_end()
+    }
+
+    const VALID: &str = "--- XML ---\n```xml\n<Screen id=\"SCREEN_MEMBER_LIST\">\n  <grid id=\"grid_list\"/>\n</Screen>\n```\n\n--- JS ---\n```javascript\nfunction fn_search() {\n}\n```\n";
+
+    #[test]
+    fn test_abort_note_round_trip() {
+        let aborted = StreamAborted {
+            reason: "code block never closed (truncated response)".to_string(),
+            received_chars: 4210,
+        };
+        assert_eq!(
+            parse_abort_note(&abort_note(&aborted)),
+            Some((4210, "code block never closed (truncated response)"))
+        );
+        assert_eq!(parse_abort_note("Note: Generation required retry"), None);
+    }
+
+    #[test]
+    fn test_valid_response_passes() {
+        assert!(stream(VALID).is_ok());
+
+        // Two screens one after another (list + popup)
+        let multi = format!("{}{}", VALID, VALID.replace("MEMBER_LIST", "MEMBER_POPUP"));
+        assert!(stream(&multi).is_ok());
+    }
+
+    #[test]
+    fn test_nested_and_stray_screen_tags_abort() {
+        let nested = format!(
+            "--- XML ---\n<Screen id=\"A\">\n<Screen id=\"B\">\n{}",
+            "<grid id=\"grid_list\"/>\n<button id=\"btn_search\"/>\n".repeat(50)
+        );
+        let aborted = stream(&nested).unwrap_err();
+        assert!(aborted.reason.contains("inside another screen"));
+        // Stopped right after the offending line
+        assert!(aborted.received_chars < 60);
+
+        assert!(stream("--- XML ---\n</Screen>\n").unwrap_err().reason.contains("without an opening"));
+    }
+
+    #[test]
+    fn test_prose_without_xml_aborts_at_deadline() {
+        let prose = "I would be happy to explain how screens work. ".repeat(100);
+        let aborted = stream(&prose).unwrap_err();
+        assert!(aborted.reason.contains("no XML"));
+        assert!(aborted.received_chars <= XML_DEADLINE_CHARS + 7);
+
+        // A short refusal is caught at the end
+        assert!(stream("I can't help with that.").unwrap_err().reason.contains("no screen tag"));
+    }
+
+    #[test]
+    fn test_repeated_lines_abort() {
+        let looping = format!("--- XML ---\n<Screen id=\"A\">\n{}", "<column name=\"x\"/>\n".repeat(30));
+        assert!(stream(&looping).unwrap_err().reason.contains("repeated"));
+    }
+
+    #[test]
+    fn test_truncated_output_aborts_at_end() {
+        let truncated = "```xml\n<Screen id=\"A\">\n</Screen>\n```\n```javascript\nfunction fn_search() {\n";
+        assert!(stream(truncated).unwrap_err().reason.contains("code block"));
+        assert!(stream("```xml\n<Screen id=\"A\">\n<grid").unwrap_err().reason.contains("never closed"));
+
+        // Nested fences close cleanly
+        let nested = "```xml\n```xml\n<Screen id=\"A\">\n</Screen>\n```\n```\n";
+        assert!(stream(nested).is_ok());
+    }
+}
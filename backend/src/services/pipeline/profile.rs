@@ -0,0 +1,160 @@
+//! Pipeline Profiles
+//!
+//! A profile overrides how individual passes run: skipped entirely, or with
+//! their own execution mode instead of the request's. E.g. a `poc` profile
+//! running `ApiAllowlistFilter` relaxed reports unknown APIs as warnings even
+//! in strict requests; a `debug` profile skipping `MinimalismPass` keeps the
+//! unused functions the model wrote.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ExecutionMode;
+
+/// Names of the passes, in pipeline order
+pub const PASS_NAMES: [&str; 26] = [
+    "OutputParser",
+    "Canonicalizer",
+    "IdentifierNormalizer",
+    "SymbolLinker",
+    "UxBehaviorPass",
+    "ApiAllowlistFilter",
+    "ApiDenylistFilter",
+    "GraphValidator",
+    "PagingValidator",
+    "EditableGridValidator",
+    "ChartBindingValidator",
+    "WizardStepValidator",
+    "TabPanelValidator",
+    "PrintValidator",
+    "SearchStateValidator",
+    "ErrorHandlingValidator",
+    "FormatValidator",
+    "LabelLanguageValidator",
+    "ArchetypeValidator",
+    "AddressFieldValidator",
+    "MinimalismPass",
+    "SensitiveDataPass",
+    "ChecklistValidator",
+    "RuntimeSmokeTest",
+    "WasmPlugins",
+    "XmlFormatter",
+];
+
+/// Passes every profile must run: the rest of the pipeline needs the parser's
+/// output, and the security passes keep forbidden APIs and unmasked personal
+/// data out of the generated code
+const REQUIRED_PASSES: [&str; 3] = ["OutputParser", "ApiDenylistFilter", "SensitiveDataPass"];
+
+/// How one pass runs under a profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PassOverride {
+    Skip,
+    Strict,
+    Relaxed,
+    Dev,
+}
+
+impl PassOverride {
+    /// Execution mode the pass runs in (`None` = skipped)
+    pub fn mode(self) -> Option<ExecutionMode> {
+        match self {
+            Self::Skip => None,
+            Self::Strict => Some(ExecutionMode::Strict),
+            Self::Relaxed => Some(ExecutionMode::Relaxed),
+            Self::Dev => Some(ExecutionMode::Dev),
+        }
+    }
+}
+
+/// Named set of per-pass overrides (the default profile has none)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineProfile {
+    pub name: Option<String>,
+    pub passes: BTreeMap<String, PassOverride>,
+}
+
+impl PipelineProfile {
+    /// Profile from stored overrides, rejecting unknown or required passes
+    pub fn new(name: impl Into<String>, passes: BTreeMap<String, PassOverride>) -> Result<Self, String> {
+        for (pass, value) in &passes {
+            if !PASS_NAMES.contains(&pass.as_str()) {
+                return Err(format!(
+                    "Unknown pass '{}' (expected one of: {})",
+                    pass,
+                    PASS_NAMES.join(", ")
+                ));
+            }
+            if *value == PassOverride::Skip && REQUIRED_PASSES.contains(&pass.as_str()) {
+                return Err(format!("Pass '{}' cannot be skipped", pass));
+            }
+        }
+        Ok(Self {
+            name: Some(name.into()),
+            passes,
+        })
+    }
+
+    /// Override for a pass, if the profile has one
+    pub fn get(&self, pass: &str) -> Option<PassOverride> {
+        self.passes.get(pass).copied()
+    }
+
+    /// The profile with one more pass skipped (request options such as
+    /// `preserve_xml_format`)
+    pub fn skipping(mut self, pass: &str) -> Self {
+        self.passes.insert(pass.to_string(), PassOverride::Skip);
+        self
+    }
+
+    /// Overrides as a JSON object, for the generation log (`None` when empty)
+    pub fn overrides_json(&self) -> Option<String> {
+        if self.passes.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&self.passes).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passes(entries: &[(&str, PassOverride)]) -> BTreeMap<String, PassOverride> {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_profile_validation() {
+        let profile = PipelineProfile::new(
+            "poc",
+            passes(&[("ApiAllowlistFilter", PassOverride::Relaxed), ("MinimalismPass", PassOverride::Skip)]),
+        )
+        .unwrap();
+        assert_eq!(profile.get("MinimalismPass"), Some(PassOverride::Skip));
+        assert_eq!(profile.get("GraphValidator"), None);
+        assert_eq!(
+            profile.overrides_json().unwrap(),
+            r#"{"ApiAllowlistFilter":"relaxed","MinimalismPass":"skip"}"#
+        );
+
+        assert!(PipelineProfile::new("bad", passes(&[("Minimalism", PassOverride::Skip)])).is_err());
+        assert!(PipelineProfile::new("bad", passes(&[("OutputParser", PassOverride::Skip)])).is_err());
+        assert!(PipelineProfile::new("bad", passes(&[("ApiDenylistFilter", PassOverride::Skip)])).is_err());
+        assert!(PipelineProfile::new("bad", passes(&[("SensitiveDataPass", PassOverride::Skip)])).is_err());
+        assert!(PipelineProfile::new("ok", passes(&[("OutputParser", PassOverride::Strict)])).is_ok());
+
+        let profile = profile.skipping("XmlFormatter");
+        assert_eq!(profile.get("XmlFormatter"), Some(PassOverride::Skip));
+        assert_eq!(profile.get("ApiAllowlistFilter"), Some(PassOverride::Relaxed));
+    }
+
+    #[test]
+    fn test_pass_names_match_pipeline() {
+        let pipeline = crate::services::pipeline::PostProcessingPipeline::new();
+        assert_eq!(pipeline.pass_names(), PASS_NAMES.to_vec());
+    }
+}
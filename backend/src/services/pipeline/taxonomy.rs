@@ -0,0 +1,274 @@
+//! Warning Taxonomy
+//!
+//! Generation logs keep the pipeline's warnings as free-form text. This maps
+//! each message back to a stable code so findings can be aggregated across
+//! generations, e.g. to compare how often template versions trip the
+//! Canonicalizer's grid version fix.
+
+use serde::Serialize;
+
+use super::prevalidator::ABORT_NOTE_PREFIX;
+
+/// Stable code of a pipeline finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCode {
+    MissingEventfuncPrefix,
+    MissingHandlerParens,
+    HtmlEventAttribute,
+    FontName,
+    DatasetType,
+    GridVersionMissing,
+    FunctionStyle,
+    MissingOnLoad,
+    MissingFunction,
+    UxBehavior,
+    HallucinatedApi,
+    ForbiddenApi,
+    InvalidDatasetRef,
+    MissingDataset,
+    MissingPaging,
+    EditableGrid,
+    ChartBinding,
+    WizardStep,
+    TabPanel,
+    PrintButton,
+    SearchState,
+    ErrorHandling,
+    ColumnArchetype,
+    AddressField,
+    UnusedFunction,
+    SensitiveData,
+    ChecklistUnmet,
+    RuntimeError,
+    TodoPlaceholder,
+    IntentRename,
+    StreamAbort,
+    XmlUnformatted,
+    Retry,
+    PassError,
+    Other,
+}
+
+/// Message fragments identifying each code, checked in order
+const RULES: &[(&str, WarningCode)] = &[
+    ("Error (non-strict)", WarningCode::PassError),
+    (ABORT_NOTE_PREFIX, WarningCode::StreamAbort),
+    ("Generation required retry", WarningCode::Retry),
+    ("'eventfunc:' prefix", WarningCode::MissingEventfuncPrefix),
+    ("Added missing '()'", WarningCode::MissingHandlerParens),
+    ("font name(s)", WarningCode::FontName),
+    ("occurrence(s) of '", WarningCode::HtmlEventAttribute),
+    ("<xdataset>", WarningCode::DatasetType),
+    ("grid element(s)", WarningCode::GridVersionMissing),
+    ("xFrame5 method style", WarningCode::FunctionStyle),
+    ("Added on_load handler", WarningCode::MissingOnLoad),
+    ("Expected function '", WarningCode::MissingFunction),
+    ("stub for missing function", WarningCode::MissingFunction),
+    ("stub functions", WarningCode::MissingFunction),
+    ("Enter-to-search", WarningCode::UxBehavior),
+    ("F5 refresh guard", WarningCode::UxBehavior),
+    ("double-submit guard", WarningCode::UxBehavior),
+    ("UX behavior issue", WarningCode::UxBehavior),
+    ("hallucinated API", WarningCode::HallucinatedApi),
+    ("potentially invalid API", WarningCode::HallucinatedApi),
+    ("Forbidden API", WarningCode::ForbiddenApi),
+    ("forbidden API", WarningCode::ForbiddenApi),
+    ("non-existent dataset", WarningCode::InvalidDatasetRef),
+    ("invalid dataset reference", WarningCode::InvalidDatasetRef),
+    ("No datasets found", WarningCode::MissingDataset),
+    ("No Dataset element", WarningCode::MissingDataset),
+    ("Large-table grid", WarningCode::MissingPaging),
+    ("paging issue", WarningCode::MissingPaging),
+    ("Editable grid (", WarningCode::EditableGrid),
+    ("editable grid issue", WarningCode::EditableGrid),
+    ("Dashboard chart (", WarningCode::ChartBinding),
+    ("Dashboard (", WarningCode::ChartBinding),
+    ("chart binding issue", WarningCode::ChartBinding),
+    ("Wizard step (", WarningCode::WizardStep),
+    ("Wizard (", WarningCode::WizardStep),
+    ("wizard issue", WarningCode::WizardStep),
+    ("Tab (", WarningCode::TabPanel),
+    ("Tab control (", WarningCode::TabPanel),
+    ("Tabs (", WarningCode::TabPanel),
+    ("Tab dataset ", WarningCode::TabPanel),
+    ("tab issue", WarningCode::TabPanel),
+    ("Print button (", WarningCode::PrintButton),
+    ("print issue", WarningCode::PrintButton),
+    ("Search state:", WarningCode::SearchState),
+    ("search state helpers", WarningCode::SearchState),
+    ("search state issue", WarningCode::SearchState),
+    ("Transaction callback (", WarningCode::ErrorHandling),
+    ("catch block", WarningCode::ErrorHandling),
+    ("only logs to console", WarningCode::ErrorHandling),
+    ("error-code catalog", WarningCode::ErrorHandling),
+    ("error handling issue", WarningCode::ErrorHandling),
+    ("(archetype ", WarningCode::ColumnArchetype),
+    ("archetype issue", WarningCode::ColumnArchetype),
+    ("Address (", WarningCode::AddressField),
+    ("address popup functions", WarningCode::AddressField),
+    ("address field issue", WarningCode::AddressField),
+    ("unused function", WarningCode::UnusedFunction),
+    ("Sensitive column", WarningCode::SensitiveData),
+    ("Masked values", WarningCode::SensitiveData),
+    ("Unmask permission hook", WarningCode::SensitiveData),
+    ("Enforced masking", WarningCode::SensitiveData),
+    ("checklist item", WarningCode::ChecklistUnmet),
+    ("Checklist item", WarningCode::ChecklistUnmet),
+    ("Runtime error (", WarningCode::RuntimeError),
+    ("runtime smoke test issue", WarningCode::RuntimeError),
+    ("TODO placeholder", WarningCode::TodoPlaceholder),
+    ("Note: Renamed", WarningCode::IntentRename),
+    ("XML left unformatted", WarningCode::XmlUnformatted),
+];
+
+impl WarningCode {
+    /// All codes, in display order
+    pub const ALL: [WarningCode; 35] = [
+        Self::MissingEventfuncPrefix,
+        Self::MissingHandlerParens,
+        Self::HtmlEventAttribute,
+        Self::FontName,
+        Self::DatasetType,
+        Self::GridVersionMissing,
+        Self::FunctionStyle,
+        Self::MissingOnLoad,
+        Self::MissingFunction,
+        Self::UxBehavior,
+        Self::HallucinatedApi,
+        Self::ForbiddenApi,
+        Self::InvalidDatasetRef,
+        Self::MissingDataset,
+        Self::MissingPaging,
+        Self::EditableGrid,
+        Self::ChartBinding,
+        Self::WizardStep,
+        Self::TabPanel,
+        Self::PrintButton,
+        Self::SearchState,
+        Self::ErrorHandling,
+        Self::ColumnArchetype,
+        Self::AddressField,
+        Self::UnusedFunction,
+        Self::SensitiveData,
+        Self::ChecklistUnmet,
+        Self::RuntimeError,
+        Self::TodoPlaceholder,
+        Self::IntentRename,
+        Self::StreamAbort,
+        Self::XmlUnformatted,
+        Self::Retry,
+        Self::PassError,
+        Self::Other,
+    ];
+
+    /// Code of a generation log warning
+    pub fn classify(message: &str) -> Self {
+        RULES
+            .iter()
+            .find(|(fragment, _)| message.contains(fragment))
+            .map(|(_, code)| *code)
+            .unwrap_or(Self::Other)
+    }
+
+    /// Codes present in a generation's warnings JSON, each once
+    pub fn in_warnings(json: &str) -> Vec<Self> {
+        let notes: Vec<String> = serde_json::from_str(json).unwrap_or_default();
+        let mut codes: Vec<Self> = notes.iter().map(|n| Self::classify(n)).collect();
+        codes.sort();
+        codes.dedup();
+        codes
+    }
+
+    /// Human-readable name for the dashboard
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MissingEventfuncPrefix => "Missing eventfunc prefix",
+            Self::MissingHandlerParens => "Missing () in event handler",
+            Self::HtmlEventAttribute => "HTML event attribute",
+            Self::FontName => "Wrong font name",
+            Self::DatasetType => "xdataset instead of xlinkdataset",
+            Self::GridVersionMissing => "Grid version missing",
+            Self::FunctionStyle => "Plain function declaration",
+            Self::MissingOnLoad => "Missing on_load handler",
+            Self::MissingFunction => "Missing function (stubbed)",
+            Self::UxBehavior => "Standard UX behavior added",
+            Self::HallucinatedApi => "Hallucinated API",
+            Self::ForbiddenApi => "Forbidden API",
+            Self::InvalidDatasetRef => "Invalid dataset reference",
+            Self::MissingDataset => "No dataset",
+            Self::MissingPaging => "Missing paging",
+            Self::EditableGrid => "Editable grid save",
+            Self::ChartBinding => "Unbound dashboard chart",
+            Self::WizardStep => "Incomplete wizard step",
+            Self::TabPanel => "Incomplete tab",
+            Self::PrintButton => "Missing print button",
+            Self::SearchState => "Search conditions not kept",
+            Self::ErrorHandling => "Unhandled transaction error",
+            Self::ColumnArchetype => "Column archetype not followed",
+            Self::AddressField => "Incomplete address field",
+            Self::UnusedFunction => "Unused function removed",
+            Self::SensitiveData => "Unmasked sensitive data",
+            Self::ChecklistUnmet => "Checklist item not satisfied",
+            Self::RuntimeError => "JavaScript runtime error",
+            Self::TodoPlaceholder => "TODO placeholder",
+            Self::IntentRename => "Renamed to match intent",
+            Self::StreamAbort => "Aborted stream",
+            Self::XmlUnformatted => "XML left unformatted",
+            Self::Retry => "Retried generation",
+            Self::PassError => "Pass error (non-strict)",
+            Self::Other => "Other",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_pipeline_messages() {
+        let cases = [
+            ("Added missing 'eventfunc:' prefix to event handlers", WarningCode::MissingEventfuncPrefix),
+            ("Added version=\"1.1\" to 2 grid element(s)", WarningCode::GridVersionMissing),
+            ("Fixed 3 occurrence(s) of 'onclick' → 'on_click'", WarningCode::HtmlEventAttribute),
+            ("Fixed 1 font name(s): 'Arial' → '맑은 고딕'", WarningCode::FontName),
+            ("Generated stub for missing function: fn_save", WarningCode::MissingFunction),
+            ("[SymbolLinker] Generated 6 stub functions - review carefully", WarningCode::MissingFunction),
+            ("Added double-submit guard to fn_save", WarningCode::UxBehavior),
+            ("Flagged potentially hallucinated API: gfn_foo", WarningCode::HallucinatedApi),
+            ("Warning: [JS] Forbidden API 'eval(' used at line 3", WarningCode::ForbiddenApi),
+            ("[XML] Large-table grid (grid_list) has no pagingbar", WarningCode::MissingPaging),
+            ("Warning: [JS] Editable grid (grid_member) has no fn_save", WarningCode::EditableGrid),
+            ("Warning: [XML] Dashboard chart (chart_sales) is not bound to a dataset", WarningCode::ChartBinding),
+            ("Warning: [JS] Wizard step (address) has no fn_validate_address", WarningCode::WizardStep),
+            ("Warning: [XML] Tab (contract) has no panel pnl_tab_contract", WarningCode::TabPanel),
+            ("Warning: [XML] Print button (btn_print) is missing", WarningCode::PrintButton),
+            ("Warning: [JS] Search state: on_load does not call fn_restoreSearchState", WarningCode::SearchState),
+            ("Warning: [JS] Empty catch block at line 12", WarningCode::ErrorHandling),
+            ("Warning: [JS] Error code 'E1009' at line 5 is not in the error-code catalog", WarningCode::ErrorHandling),
+            ("Warning: [XML] <normal_field> bound to 'TOTAL_AMT' should be a <numericex_field> (archetype 금액)", WarningCode::ColumnArchetype),
+            ("Warning: [JS] Address (addr): fn_addr_search does not open /common/zipcode_popup.xml", WarningCode::AddressField),
+            ("[JS] Checklist item not satisfied: Save button", WarningCode::ChecklistUnmet),
+            ("Warning: [JS] Runtime error (fn_search): ReferenceError: ds_membr is not defined at line 2", WarningCode::RuntimeError),
+            ("Note: Aborted streamed response after 812 chars (no <screen>)", WarningCode::StreamAbort),
+            ("Note: XML left unformatted: </screen> closes <panel>", WarningCode::XmlUnformatted),
+            ("[GraphValidator] Error (non-strict): Component 'grid' references non-existent dataset 'ds'", WarningCode::PassError),
+            ("[Detail] Removed unused function: fn_helper", WarningCode::UnusedFunction),
+            ("Something new", WarningCode::Other),
+        ];
+        for (message, code) in cases {
+            assert_eq!(WarningCode::classify(message), code, "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_codes_in_warnings_are_distinct() {
+        let json = r#"["Removed unused function: fn_a","Removed unused function: fn_b","Added on_load handler calling fn_init and fn_search"]"#;
+        assert_eq!(
+            WarningCode::in_warnings(json),
+            vec![WarningCode::MissingOnLoad, WarningCode::UnusedFunction]
+        );
+        assert!(WarningCode::in_warnings("not json").is_empty());
+    }
+}
@@ -0,0 +1,511 @@
+//! Project Review
+//!
+//! Bulk review of an existing xFrame5 project uploaded as a ZIP, for project
+//! takeover audits. Every XML and JS file goes through the static checks:
+//! - XML well-formedness
+//! - canonicalizer-class problems, with patches as in `/agent/review`
+//! - forbidden APIs (built-in deny-list plus the company rule set)
+//! - accessibility of screens, against the company palette
+//! - event handlers the screen XML references but its JS does not define
+//!
+//! Project findings cover screens whose handlers have no JS file, JS files
+//! without a screen, screen ids used by more than one file, functions copied
+//! across screens or drifted from the common pattern (see `pattern_drift`)
+//! and skipped files.
+//! With the LLM review on, files also go through [`ReviewService::review`]
+//! (at most [`MAX_LLM_FILES`]); the first failure stops it for the rest.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read};
+
+use anyhow::{anyhow, bail, Result};
+use roxmltree::Document;
+use sea_orm::DatabaseConnection;
+
+use crate::domain::{
+    CompanyRuleSections, FileReview, IssueCategory, IssueSeverity, ProjectFinding, ProjectReviewReport,
+    ReviewContext, ReviewInput, ReviewIssue, ReviewOptions, StyleProfile,
+};
+use crate::models::company_rules;
+use crate::services::pattern_drift::{CodeUnit, DuplicateKind, FunctionRef, PatternDriftService};
+use crate::services::pipeline::passes::{ApiDenylistFilter, SymbolLinker};
+use crate::services::{AccessibilityChecker, ReviewPatcher, ReviewService, TenantScope};
+
+/// Largest accepted upload
+pub const MAX_ARCHIVE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Most files reviewed per archive
+const MAX_FILES: usize = 500;
+
+/// Largest single file reviewed
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+
+/// Most bytes all reviewed files may expand to
+const MAX_TOTAL_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Most files sent to the LLM review per archive
+pub const MAX_LLM_FILES: usize = 40;
+
+/// Largest file sent to the LLM review (the `/agent/review` limit)
+const MAX_LLM_CODE_BYTES: usize = 50 * 1024;
+
+/// XML or JS file of the project
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    /// Path inside the archive
+    pub path: String,
+    /// xml or javascript
+    pub file_type: &'static str,
+    pub code: String,
+}
+
+impl ProjectFile {
+    /// Path without extension, pairing a screen XML with its JS
+    fn stem(&self) -> &str {
+        self.path.rsplit_once('.').map_or(self.path.as_str(), |(stem, _)| stem)
+    }
+}
+
+/// Reviewable contents of an uploaded archive
+#[derive(Debug, Default)]
+pub struct ProjectArchive {
+    pub files: Vec<ProjectFile>,
+    /// Files left out, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+impl ProjectArchive {
+    /// Read the XML and JS files of a ZIP (UTF-8 or EUC-KR); other files are ignored
+    pub fn read(bytes: &[u8]) -> Result<Self> {
+        let mut archive =
+            zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| anyhow!("Not a ZIP archive: {}", e))?;
+        let mut project = Self::default();
+        let mut total = 0u64;
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let Some(path) = entry.enclosed_name().map(|p| p.to_string_lossy().replace('\\', "/")) else {
+                project.skipped.push((entry.name().to_string(), "path leaves the archive".to_string()));
+                continue;
+            };
+            if path.starts_with("__MACOSX/") || path.split('/').any(|part| part.starts_with('.')) {
+                continue;
+            }
+            let Some(file_type) = file_type_of(&path) else {
+                continue;
+            };
+            if project.files.len() >= MAX_FILES {
+                project.skipped.push((path, format!("more than {} files in the archive", MAX_FILES)));
+                continue;
+            }
+
+            // The declared size can lie, so the read is bounded too
+            let mut raw = Vec::new();
+            entry.by_ref().take(MAX_FILE_BYTES + 1).read_to_end(&mut raw)?;
+            if raw.len() as u64 > MAX_FILE_BYTES {
+                project.skipped.push((path, format!("larger than {} KB", MAX_FILE_BYTES / 1024)));
+                continue;
+            }
+            total += raw.len() as u64;
+            if total > MAX_TOTAL_BYTES {
+                bail!("Archive expands to more than {} MB of XML and JS", MAX_TOTAL_BYTES / 1024 / 1024);
+            }
+
+            match decode(raw) {
+                Some(code) => project.files.push(ProjectFile { path, file_type, code }),
+                None => project.skipped.push((path, "neither UTF-8 nor EUC-KR text".to_string())),
+            }
+        }
+
+        if project.files.is_empty() {
+            bail!("The archive contains no xFrame5 XML or JS files");
+        }
+        Ok(project)
+    }
+}
+
+fn file_type_of(path: &str) -> Option<&'static str> {
+    let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    match extension.as_str() {
+        "xml" => Some("xml"),
+        "js" => Some("javascript"),
+        _ => None,
+    }
+}
+
+/// Text of a file; legacy projects are often EUC-KR
+fn decode(raw: Vec<u8>) -> Option<String> {
+    match String::from_utf8(raw) {
+        Ok(text) => Some(text.trim_start_matches('\u{feff}').to_string()),
+        Err(e) => encoding_rs::EUC_KR
+            .decode_without_bom_handling_and_without_replacement(e.as_bytes())
+            .map(|text| text.into_owned()),
+    }
+}
+
+pub struct ProjectReviewService;
+
+impl ProjectReviewService {
+    /// Review every file of the archive and build the report
+    pub async fn review(
+        db: &DatabaseConnection,
+        archive: ProjectArchive,
+        project: &str,
+        product: &str,
+        llm: bool,
+        options: &ReviewOptions,
+        user_id: Option<i32>,
+    ) -> Result<ProjectReviewReport> {
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+        let rules = match options.company_id.as_deref() {
+            Some(company_id) => company_rules::Model::find_by_name(db, company_id, &tenant).await.ok(),
+            None => None,
+        };
+        let sections = rules.as_ref().map(|r| r.rule_sections());
+
+        let (mut files, mut findings) = Self::static_review(&archive, product, sections.as_ref());
+
+        if llm {
+            Self::llm_review(db, &archive, &mut files, &mut findings, project, product, options, user_id).await;
+        }
+
+        Ok(ProjectReviewReport::new(project, product, files, findings, llm))
+    }
+
+    /// Static checks of every file plus the project findings
+    pub fn static_review(
+        archive: &ProjectArchive,
+        product: &str,
+        sections: Option<&CompanyRuleSections>,
+    ) -> (Vec<FileReview>, Vec<ProjectFinding>) {
+        let deny_list = ApiDenylistFilter::for_rules(sections, product);
+        let style = sections.and_then(|s| s.style.as_ref());
+        let linker = SymbolLinker::new();
+
+        let scripts: HashMap<&str, &ProjectFile> = archive
+            .files
+            .iter()
+            .filter(|f| f.file_type == "javascript")
+            .map(|f| (f.stem(), f))
+            .collect();
+
+        let mut findings = Vec::new();
+        let mut screen_ids: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut screens_with_xml = Vec::new();
+        let mut files = Vec::new();
+
+        for file in &archive.files {
+            let mut issues = ReviewService::forbidden_api_issues(&file.code, deny_list.deny_list());
+
+            if file.file_type == "xml" {
+                issues.extend(ReviewPatcher::deterministic_issues(&file.code, "xml", &file.path));
+
+                match Document::parse(&file.code) {
+                    Ok(doc) => {
+                        let root = doc.root_element();
+                        if root.has_tag_name("screen") {
+                            screens_with_xml.push(file.stem());
+                            if let Some(id) = root.attribute("id") {
+                                screen_ids.entry(id.to_string()).or_default().push(file.path.clone());
+                            }
+                            issues.extend(Self::accessibility_issues(&file.code, style));
+                            issues.extend(Self::handler_issues(&linker, file, scripts.get(file.stem()).copied(), &mut findings));
+                        }
+                    }
+                    Err(e) => issues.push(ReviewIssue {
+                        severity: IssueSeverity::Error,
+                        category: IssueCategory::Syntax,
+                        line: e.pos().row,
+                        message: format!("XML is not well-formed: {}", e),
+                        suggestion: None,
+                        patch: None,
+                    }),
+                }
+            }
+
+            issues.sort_by_key(|issue| issue.line);
+            files.push(FileReview::new(&file.path, file.file_type, file.code.lines().count(), issues));
+        }
+
+        for (stem, script) in &scripts {
+            if !screens_with_xml.contains(stem) {
+                findings.push(
+                    ProjectFinding::new(
+                        IssueSeverity::Info,
+                        IssueCategory::Pattern,
+                        "JavaScript file without a screen XML next to it",
+                    )
+                    .with_files(vec![script.path.clone()]),
+                );
+            }
+        }
+        for (id, paths) in screen_ids.into_iter().filter(|(_, paths)| paths.len() > 1) {
+            findings.push(
+                ProjectFinding::new(
+                    IssueSeverity::Warning,
+                    IssueCategory::Naming,
+                    format!("Screen id {} is used by {} files", id, paths.len()),
+                )
+                .with_files(paths),
+            );
+        }
+        findings.extend(Self::pattern_findings(archive));
+        for (path, reason) in &archive.skipped {
+            findings.push(
+                ProjectFinding::new(IssueSeverity::Info, IssueCategory::BestPractice, format!("Not reviewed: {}", reason))
+                    .with_files(vec![path.clone()]),
+            );
+        }
+
+        (files, findings)
+    }
+
+    /// Copy-paste duplicates and drifted functions across the JS files
+    fn pattern_findings(archive: &ProjectArchive) -> Vec<ProjectFinding> {
+        let units = archive
+            .files
+            .iter()
+            .filter(|f| f.file_type == "javascript")
+            .flat_map(|f| CodeUnit::extract(&f.path, &f.code))
+            .collect();
+        let report = PatternDriftService::analyze(units);
+
+        let files_of = |functions: &[FunctionRef]| {
+            let mut files: Vec<String> = functions.iter().map(|f| f.file.clone()).collect();
+            files.sort();
+            files.dedup();
+            files
+        };
+        let duplicates = report.duplicates.into_iter().map(|group| {
+            let severity = match group.kind {
+                DuplicateKind::Identical => IssueSeverity::Info,
+                DuplicateKind::Diverged => IssueSeverity::Warning,
+            };
+            ProjectFinding::new(severity, IssueCategory::Pattern, group.suggestion).with_files(files_of(&group.functions))
+        });
+        let drift = report.drift.into_iter().map(|drift| {
+            ProjectFinding::new(IssueSeverity::Info, IssueCategory::Pattern, drift.suggestion)
+                .with_files(files_of(&drift.drifted))
+        });
+        duplicates.chain(drift).collect()
+    }
+
+    /// Failed accessibility checks of a screen
+    fn accessibility_issues(xml: &str, style: Option<&StyleProfile>) -> Vec<ReviewIssue> {
+        let report = AccessibilityChecker::report(xml, style);
+        [&report.input_labels, &report.touch_targets, &report.color_contrast, &report.tab_order]
+            .into_iter()
+            .flat_map(|check| check.issues.iter())
+            .map(|issue| ReviewIssue {
+                severity: IssueSeverity::Warning,
+                category: IssueCategory::BestPractice,
+                line: 0,
+                message: format!("Accessibility: {}", issue),
+                suggestion: None,
+                patch: None,
+            })
+            .collect()
+    }
+
+    /// Handlers of a screen its JS does not define; a screen with handlers but
+    /// no JS at all is a project finding
+    fn handler_issues(
+        linker: &SymbolLinker,
+        screen: &ProjectFile,
+        script: Option<&ProjectFile>,
+        findings: &mut Vec<ProjectFinding>,
+    ) -> Vec<ReviewIssue> {
+        let mut handlers: Vec<String> = linker.extract_xml_handlers(&screen.code).into_iter().collect();
+        handlers.sort();
+        if handlers.is_empty() {
+            return Vec::new();
+        }
+
+        let Some(script) = script else {
+            findings.push(
+                ProjectFinding::new(
+                    IssueSeverity::Error,
+                    IssueCategory::Pattern,
+                    format!("Screen references event handlers ({}) but has no JavaScript file", handlers.join(", ")),
+                )
+                .with_files(vec![screen.path.clone()]),
+            );
+            return Vec::new();
+        };
+
+        let defined = linker.extract_js_functions(&script.code);
+        let script_name = script.path.rsplit('/').next().unwrap_or(&script.path);
+        handlers
+            .into_iter()
+            .filter(|handler| !defined.contains(handler))
+            .map(|handler| {
+                let reference = format!("eventfunc:{}", handler);
+                let line = screen
+                    .code
+                    .lines()
+                    .position(|l| l.contains(&reference))
+                    .map_or(0, |idx| idx as u32 + 1);
+                ReviewIssue {
+                    severity: IssueSeverity::Error,
+                    category: IssueCategory::Pattern,
+                    line,
+                    message: format!("Event handler {} is not defined in {}", handler, script_name),
+                    suggestion: Some(format!("this.{} = function() {{ ... }}", handler)),
+                    patch: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Add the LLM review's issues to the files, skipping ones the static
+    /// checks already report
+    #[allow(clippy::too_many_arguments)]
+    async fn llm_review(
+        db: &DatabaseConnection,
+        archive: &ProjectArchive,
+        files: &mut [FileReview],
+        findings: &mut Vec<ProjectFinding>,
+        project: &str,
+        product: &str,
+        options: &ReviewOptions,
+        user_id: Option<i32>,
+    ) {
+        let mut too_large = Vec::new();
+        let mut over_limit = Vec::new();
+        let mut reviewed = 0;
+
+        for (file, review) in archive.files.iter().zip(files.iter_mut()) {
+            if file.code.len() > MAX_LLM_CODE_BYTES {
+                too_large.push(file.path.clone());
+                continue;
+            }
+            if reviewed >= MAX_LLM_FILES {
+                over_limit.push(file.path.clone());
+                continue;
+            }
+
+            let input = ReviewInput::new(file.code.clone()).with_file_type(file.file_type);
+            let context = ReviewContext {
+                project: Some(project.to_string()),
+                file_name: Some(file.path.clone()),
+            };
+            let result = ReviewService::review(db, input, product, options, &context, user_id)
+                .await
+                .and_then(|response| response.review.ok_or_else(|| anyhow!(response.error.unwrap_or_default())));
+            let result = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::warn!("LLM review of {} failed: {}", file.path, e);
+                    findings.push(
+                        ProjectFinding::new(
+                            IssueSeverity::Info,
+                            IssueCategory::BestPractice,
+                            format!("LLM review stopped: {}", e),
+                        )
+                        .with_files(vec![file.path.clone()]),
+                    );
+                    return;
+                }
+            };
+
+            reviewed += 1;
+            for issue in result.issues {
+                if !review.issues.iter().any(|e| e.line == issue.line && e.message == issue.message) {
+                    review.counts.add(issue.severity);
+                    review.issues.push(issue);
+                }
+            }
+            review.issues.sort_by_key(|issue| issue.line);
+            review.llm_reviewed = true;
+        }
+
+        if !too_large.is_empty() {
+            findings.push(
+                ProjectFinding::new(
+                    IssueSeverity::Info,
+                    IssueCategory::BestPractice,
+                    format!("Not LLM-reviewed: larger than {} KB", MAX_LLM_CODE_BYTES / 1024),
+                )
+                .with_files(too_large),
+            );
+        }
+        if !over_limit.is_empty() {
+            findings.push(
+                ProjectFinding::new(
+                    IssueSeverity::Info,
+                    IssueCategory::BestPractice,
+                    format!("Not LLM-reviewed: only the first {} files are", MAX_LLM_FILES),
+                )
+                .with_files(over_limit),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn zip_of(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in files {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_read_archive() {
+        let (euc_kr, _, _) = encoding_rs::EUC_KR.encode("// 회원 목록\nthis.fn_search = function() {};");
+        let bytes = zip_of(&[
+            ("screens/member_list.xml", &b"<screen id=\"SCREEN_MEMBER_LIST\"/>"[..]),
+            ("screens/member_list.js", &euc_kr[..]),
+            ("screens/logo.png", &b"\x89PNG"[..]),
+            ("__MACOSX/screens/._member_list.xml", &b"junk"[..]),
+        ]);
+        let archive = ProjectArchive::read(&bytes).unwrap();
+
+        assert_eq!(archive.files.len(), 2);
+        assert!(archive.files[1].code.contains("회원 목록"));
+        assert!(ProjectArchive::read(&zip_of(&[("README.md", &b"# app"[..])])).is_err());
+        assert!(ProjectArchive::read(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn test_static_review_findings() {
+        let screen = |id: &str| {
+            format!(
+                "<screen id=\"{}\">\n  <pushbutton control_id=\"1\" x=\"0\" y=\"0\" width=\"80\" height=\"30\" text=\"조회\" on_click=\"eventfunc:fn_search()\"/>\n</screen>",
+                id
+            )
+        };
+        let file = |path: &str, file_type: &'static str, code: String| ProjectFile { path: path.to_string(), file_type, code };
+        let archive = ProjectArchive {
+            files: vec![
+                file("member_list.xml", "xml", screen("SCREEN_MEMBER")),
+                file("member_list.js", "javascript", "this.fn_init = function() { eval(x); };".to_string()),
+                file("member_copy.xml", "xml", screen("SCREEN_MEMBER")),
+                file("broken.xml", "xml", "<screen>\n<panel>\n</screen>".to_string()),
+            ],
+            skipped: vec![("huge.js".to_string(), "larger than 512 KB".to_string())],
+        };
+
+        let (files, findings) = ProjectReviewService::static_review(&archive, "xframe5-ui", None);
+
+        let list = &files[0];
+        assert!(list.issues.iter().any(|i| i.message == "Event handler fn_search is not defined in member_list.js"));
+        assert!(files[1].issues.iter().any(|i| i.category == IssueCategory::Security));
+        assert!(files[3].issues.iter().any(|i| i.message.starts_with("XML is not well-formed")));
+
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert!(messages.contains(&"Screen references event handlers (fn_search) but has no JavaScript file"));
+        assert!(messages.contains(&"Screen id SCREEN_MEMBER is used by 2 files"));
+        assert!(messages.contains(&"Not reviewed: larger than 512 KB"));
+    }
+}
@@ -0,0 +1,674 @@
+mod sections;
+
+pub use sections::{PromptSection, PromptSections};
+
+use crate::domain::{ChecklistItem, ErrorCodeCatalog, ScreenType, UiIntent};
+use crate::models::_entities::prompt_templates;
+use crate::models::{checklist_items, company_rules, error_code_catalogs};
+use crate::services::prompt_compression::{estimate_tokens, remove_sections, PromptCompression};
+use crate::services::template::DefaultTemplates;
+use crate::services::{KnowledgeBaseService, KnowledgeFileFallback, KnowledgeRevision, TenantScope};
+use anyhow::Result;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+/// Compiled prompt ready to be sent to LLM
+#[derive(Debug, Clone)]
+pub struct CompiledPrompt {
+    /// System prompt (role/rules)
+    pub system: String,
+
+    /// User prompt (specific request)
+    pub user: String,
+
+    /// User prompt rendered without the request notes (retry of a refusal)
+    pub user_without_notes: String,
+
+    /// Knowledge base entry revisions included in the system prompt, in order
+    pub knowledge_revisions: Vec<KnowledgeRevision>,
+
+    /// Checklist items included in the system prompt (checked by the pipeline)
+    pub checklist: Vec<ChecklistItem>,
+
+    /// Error codes listed in the system prompt (checked by the pipeline)
+    pub error_codes: ErrorCodeCatalog,
+}
+
+impl CompiledPrompt {
+    /// Ids of the knowledge base entries in the prompt (for usage stats)
+    pub fn knowledge_ids(&self) -> Vec<i32> {
+        self.knowledge_revisions.iter().map(|r| r.id).collect()
+    }
+
+    /// Append a section to the system prompt
+    pub fn add_section(&mut self, section: &str) {
+        self.system = format!("{}\n\n{}", self.system, section);
+    }
+
+    /// Combine system and user prompts into a single prompt string
+    pub fn full(&self) -> String {
+        format!("{}\n\n{}", self.system, self.user)
+    }
+
+    /// The prompt with the notes-free user prompt
+    pub fn without_notes(&self) -> Self {
+        Self {
+            user: self.user_without_notes.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Service for compiling UiIntent into LLM prompts
+pub struct PromptCompiler;
+
+impl PromptCompiler {
+    /// Compile a UiIntent into a prompt using templates from database
+    pub async fn compile(
+        db: &DatabaseConnection,
+        intent: &UiIntent,
+        product: &str,
+        company_id: Option<&str>,
+        tenant: &TenantScope,
+    ) -> Result<CompiledPrompt> {
+        // 1. Load template from DB (or use defaults)
+        let template = Self::load_template(db, product, intent.screen_type.as_str(), tenant).await;
+        Self::compile_with_template(db, intent, product, company_id, tenant, template).await
+    }
+
+    /// Compile with a given template instead of the active one (`None` = defaults)
+    pub async fn compile_with_template(
+        db: &DatabaseConnection,
+        intent: &UiIntent,
+        product: &str,
+        company_id: Option<&str>,
+        tenant: &TenantScope,
+        template: Option<prompt_templates::Model>,
+    ) -> Result<CompiledPrompt> {
+        // 2. Load company rules if provided (only sections relevant to product/screen type)
+        let rules = if let Some(cid) = company_id {
+            Self::load_company_rules(db, cid, tenant)
+                .await
+                .ok()
+                .map(|r| r.render_for(product, Some(intent.screen_type.as_str())))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        // 3. Load knowledge base for screen type
+        let (knowledge, knowledge_revisions) =
+            Self::load_knowledge(db, intent.screen_type.as_str(), tenant).await;
+
+        // 4. Load the component checklist for product/screen type
+        let checklist =
+            checklist_items::Model::for_screen(db, product, intent.screen_type.as_str(), tenant).await;
+
+        // 5. Load the error codes callbacks may branch on
+        let error_codes = error_code_catalogs::Model::active(db, tenant).await;
+
+        // 6. Build system prompt with knowledge (compressed to the intent's sections if enabled)
+        let system = Self::build_system_prompt(
+            &template,
+            intent,
+            &rules,
+            &knowledge,
+            &checklist,
+            &error_codes.render(product),
+        );
+
+        // 7. Build user prompt from intent (and without the notes, for a refusal retry)
+        let user = Self::build_user_prompt(&template, intent, &rules);
+        let user_without_notes = Self::build_user_prompt(&template, &intent.without_notes(), &rules);
+
+        Ok(CompiledPrompt {
+            system,
+            user,
+            user_without_notes,
+            knowledge_revisions,
+            checklist,
+            error_codes,
+        })
+    }
+
+    /// Compile using default templates (no database)
+    pub fn compile_with_defaults(intent: &UiIntent, company_rules: Option<&str>) -> CompiledPrompt {
+        let system = Self::get_default_system_prompt(intent.screen_type);
+        let system = PromptCompression::from_env().apply(&system, intent, 0);
+        let user = Self::build_user_prompt_from_intent(intent, company_rules);
+        let user_without_notes = Self::build_user_prompt_from_intent(&intent.without_notes(), company_rules);
+
+        CompiledPrompt {
+            system,
+            user,
+            user_without_notes,
+            knowledge_revisions: Vec::new(),
+            checklist: Vec::new(),
+            error_codes: ErrorCodeCatalog::default(),
+        }
+    }
+
+    /// Load template from database or return None for defaults
+    async fn load_template(
+        db: &DatabaseConnection,
+        product: &str,
+        screen_type: &str,
+        tenant: &TenantScope,
+    ) -> Option<prompt_templates::Model> {
+        use sea_orm::QueryOrder;
+
+        let query = prompt_templates::Entity::find()
+            .filter(prompt_templates::Column::Product.eq(product))
+            .filter(prompt_templates::Column::ScreenType.eq(Some(screen_type.to_string())))
+            .filter(prompt_templates::Column::IsActive.eq(Some(true)));
+        let result = tenant
+            .prefer_own(query, prompt_templates::Column::Company)
+            .order_by_desc(prompt_templates::Column::Version) // Get highest version
+            .one(db)
+            .await;
+
+        match &result {
+            Ok(Some(t)) => {
+                tracing::info!(
+                    "Loaded template: {} v{} (active={:?}) for {}/{}",
+                    t.name, t.version, t.is_active, product, screen_type
+                );
+            }
+            Ok(None) => {
+                tracing::warn!(
+                    "No active template found for {}/{}, using defaults",
+                    product, screen_type
+                );
+            }
+            Err(e) => {
+                tracing::error!("Error loading template: {}", e);
+            }
+        }
+
+        result.ok().flatten()
+    }
+
+    /// Load company rules from database
+    /// In single-company on-premise mode, we load rules by name
+    async fn load_company_rules(
+        db: &DatabaseConnection,
+        rule_name: &str,
+        tenant: &TenantScope,
+    ) -> Result<company_rules::Model> {
+        company_rules::Model::find_by_name(db, rule_name, tenant)
+            .await
+            .map_err(|_| anyhow::anyhow!("Company rules not found for: {}", rule_name))
+    }
+
+    /// Load knowledge base for screen type, with the ids of the DB entries used
+    async fn load_knowledge(
+        db: &DatabaseConnection,
+        screen_type: &str,
+        tenant: &TenantScope,
+    ) -> (String, Vec<KnowledgeRevision>) {
+        // Convert screen_type to tag format (e.g., "list" -> "list_screen")
+        let tag = format!("{}_screen", screen_type);
+
+        // Query knowledge base for relevant entries
+        match KnowledgeBaseService::for_screen_type(db, &tag, tenant).await {
+            Ok(entries) if !entries.is_empty() => {
+                let token_estimate = KnowledgeBaseService::estimate_tokens(&entries);
+                tracing::info!(
+                    "Loaded {} knowledge entries for screen_type '{}' (~{} tokens)",
+                    entries.len(),
+                    screen_type,
+                    token_estimate
+                );
+
+                // Assemble knowledge content
+                let revisions = entries
+                    .iter()
+                    .map(|e| KnowledgeRevision { id: e.id, revision: e.revision })
+                    .collect();
+                (KnowledgeBaseService::assemble_content(&entries), revisions)
+            }
+            Ok(_) => {
+                // Database query returned empty - try file fallback
+                tracing::warn!(
+                    "No knowledge entries found in database for screen_type '{}', trying file fallback",
+                    screen_type
+                );
+
+                let content = KnowledgeFileFallback::for_screen_type(screen_type)
+                    .unwrap_or_else(|e| {
+                        tracing::error!("File fallback also failed: {}", e);
+                        String::new()
+                    });
+                (content, Vec::new())
+            }
+            Err(e) => {
+                // Database query failed - try file fallback
+                tracing::error!(
+                    "Failed to query knowledge base for '{}': {}, trying file fallback",
+                    screen_type,
+                    e
+                );
+
+                let content = KnowledgeFileFallback::for_screen_type(screen_type)
+                    .unwrap_or_else(|e| {
+                        tracing::error!("File fallback also failed: {}", e);
+                        String::new()
+                    });
+                (content, Vec::new())
+            }
+        }
+    }
+
+    /// Build system prompt from template, rules, knowledge, checklist and error codes
+    fn build_system_prompt(
+        template: &Option<prompt_templates::Model>,
+        intent: &UiIntent,
+        rules: &str,
+        knowledge: &str,
+        checklist: &[ChecklistItem],
+        error_codes: &str,
+    ) -> String {
+        let default_prompt = Self::get_default_system_prompt(intent.screen_type);
+        let base_prompt = template
+            .as_ref()
+            .map(|t| t.system_prompt.as_str())
+            .unwrap_or(&default_prompt);
+
+        // A managed checklist replaces the one embedded in the template
+        let (base_prompt, checklist) = if checklist.is_empty() {
+            (base_prompt.to_string(), String::new())
+        } else {
+            (remove_sections(base_prompt, "COMPONENT CHECKLIST"), ChecklistItem::render(checklist))
+        };
+
+        // Knowledge, checklist, error codes and rules are not segmented but count towards the budget
+        let mut prompt = PromptCompression::from_env().apply(
+            &base_prompt,
+            intent,
+            estimate_tokens(knowledge)
+                + estimate_tokens(&checklist)
+                + estimate_tokens(error_codes)
+                + estimate_tokens(rules),
+        );
+
+        // Add knowledge base if available
+        if !knowledge.is_empty() {
+            prompt.push_str("\n\n# XFRAME5 KNOWLEDGE BASE\n\n");
+            prompt.push_str(knowledge);
+        }
+
+        if !checklist.is_empty() {
+            prompt.push_str("\n\n# COMPONENT CHECKLIST\n\n");
+            prompt.push_str(&checklist);
+        }
+
+        if !error_codes.is_empty() {
+            prompt.push_str("\n\n# ERROR CODES\n\n");
+            prompt.push_str(error_codes);
+        }
+
+        // Append company rules if available
+        if !rules.is_empty() {
+            prompt.push_str("\n\n# COMPANY-SPECIFIC RULES\n\n");
+            prompt.push_str(rules);
+        }
+
+        prompt
+    }
+
+    /// Build user prompt from template and intent
+    fn build_user_prompt(
+        template: &Option<prompt_templates::Model>,
+        intent: &UiIntent,
+        rules: &str,
+    ) -> String {
+        if let Some(t) = template {
+            Self::render_template(&t.user_prompt_template, intent, rules)
+        } else {
+            let rules_ref = if rules.is_empty() { None } else { Some(rules) };
+            Self::build_user_prompt_from_intent(intent, rules_ref)
+        }
+    }
+
+    /// Get default system prompt for screen type
+    fn get_default_system_prompt(screen_type: ScreenType) -> String {
+        match screen_type {
+            ScreenType::List | ScreenType::ListWithPopup => {
+                DefaultTemplates::xframe5_list_system_prompt().to_string()
+            }
+            ScreenType::Detail | ScreenType::Popup => {
+                DefaultTemplates::xframe5_detail_system_prompt().to_string()
+            }
+            ScreenType::Dashboard => DefaultTemplates::xframe5_dashboard_system_prompt().to_string(),
+            ScreenType::Wizard => DefaultTemplates::xframe5_wizard_system_prompt().to_string(),
+        }
+    }
+
+    /// Values of the template variables (see `TemplateVariables`)
+    pub fn template_variables(intent: &UiIntent, company_rules: &str) -> Vec<(&'static str, String)> {
+        let grid_columns = Self::describe_grids(&intent.grids);
+        vec![
+            ("dsl_description", Self::describe_intent(intent)),
+            ("screen_type", intent.screen_type.as_str().to_string()),
+            ("screen_name", intent.screen_name.clone()),
+            ("datasets", Self::describe_datasets(&intent.datasets)),
+            ("grid_columns", grid_columns.clone()),
+            ("form_fields", grid_columns), // Same format for now
+            ("actions", Self::describe_actions(&intent.actions)),
+            ("notes", intent.notes.clone().unwrap_or_default()),
+            ("company_rules", company_rules.to_string()),
+        ]
+    }
+
+    /// Render a template with intent data
+    pub fn render_template(template: &str, intent: &UiIntent, company_rules: &str) -> String {
+        Self::template_variables(intent, company_rules)
+            .iter()
+            .fold(template.to_string(), |rendered, (name, value)| {
+                rendered.replace(&format!("{{{{{}}}}}", name), value)
+            })
+            // Handle conditional blocks (simple version)
+            .lines()
+            .filter(|line| !line.contains("{{#if") && !line.contains("{{/if}}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Build user prompt directly from intent
+    fn build_user_prompt_from_intent(intent: &UiIntent, company_rules: Option<&str>) -> String {
+        let mut prompt = format!(
+            "Generate an xFrame5 {} screen based on the following specification:\n\n",
+            intent.screen_type.as_str()
+        );
+
+        prompt.push_str(&PromptSections::user_prompt().render(intent));
+
+        if let Some(rules) = company_rules {
+            if !rules.is_empty() {
+                prompt.push_str(&format!("\nCompany-specific rules:\n{}\n", rules));
+            }
+        }
+
+        prompt.push_str("\nGenerate the XML and JavaScript code following xFrame5 patterns.");
+
+        prompt
+    }
+
+    /// Generate a human-readable description of the intent
+    fn describe_intent(intent: &UiIntent) -> String {
+        PromptSections::specification().render(intent)
+    }
+
+    /// Describe datasets for template
+    fn describe_datasets(datasets: &[crate::domain::DatasetIntent]) -> String {
+        datasets
+            .iter()
+            .map(|ds| {
+                let cols: Vec<_> = ds.columns.iter().map(|c| c.name.as_str()).collect();
+                format!("{} [{}]", ds.id, cols.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Describe grids for template
+    fn describe_grids(grids: &[crate::domain::GridIntent]) -> String {
+        grids
+            .iter()
+            .map(|g| {
+                let cols: Vec<_> = g.columns.iter().map(|c| c.header.as_str()).collect();
+                format!("{}: {}", g.id, cols.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Describe actions for template
+    fn describe_actions(actions: &[crate::domain::ActionIntent]) -> String {
+        actions
+            .iter()
+            .map(|a| format!("{} ({})", a.label, a.function_name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ColumnIntent, DatasetIntent, GridColumnIntent, GridIntent, UiType};
+
+    fn create_test_intent() -> UiIntent {
+        let columns = vec![
+            ColumnIntent::new("id", "ID").primary_key(),
+            ColumnIntent::new("name", "이름").with_ui_type(UiType::Input).required(),
+            ColumnIntent::new("email", "이메일").with_ui_type(UiType::Input),
+        ];
+
+        let grid_columns = vec![
+            GridColumnIntent::new("name", "이름"),
+            GridColumnIntent::new("email", "이메일"),
+        ];
+
+        let dataset = DatasetIntent::new("ds_member")
+            .with_table("member")
+            .with_columns(columns);
+
+        let grid = GridIntent::new("grid_member", "ds_member")
+            .with_columns(grid_columns);
+
+        UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(dataset)
+            .with_grid(grid)
+    }
+
+    #[test]
+    fn test_compile_with_defaults() {
+        let intent = create_test_intent();
+        let prompt = PromptCompiler::compile_with_defaults(&intent, None);
+
+        assert!(!prompt.system.is_empty());
+        assert!(prompt.user.contains("member_list"));
+        assert!(prompt.user.contains("ds_member"));
+        assert!(prompt.user.contains("이름"));
+    }
+
+    #[test]
+    fn test_describe_intent() {
+        let intent = create_test_intent();
+        let desc = PromptCompiler::describe_intent(&intent);
+
+        assert!(desc.contains("member_list"));
+        assert!(desc.contains("ds_member"));
+        assert!(desc.contains("member"));
+        assert!(!desc.contains("Sensitive columns"));
+    }
+
+    #[test]
+    fn test_describe_intent_includes_masking() {
+        let dataset = DatasetIntent::new("ds_member").with_column(
+            ColumnIntent::new("jumin_no", "주민번호")
+                .with_sensitivity(Some(crate::domain::SensitiveKind::ResidentNumber)),
+        );
+        let intent = UiIntent::new("member_list", ScreenType::List).with_dataset(dataset);
+        let desc = PromptCompiler::describe_intent(&intent);
+
+        assert!(desc.contains("jumin_no (resident registration number): pattern=\"######-#******\""));
+        assert!(desc.contains("this.fn_check_unmask_auth(column)"));
+    }
+
+    #[test]
+    fn test_describe_intent_includes_formats() {
+        let mut intent = create_test_intent();
+        intent.datasets[0].columns.push(
+            ColumnIntent::new("TOTAL_AMT", "합계").with_data_type(crate::domain::DataType::Decimal),
+        );
+        intent.apply_formats(&crate::domain::LocaleFormats::resolve(Some("en-US"), None).unwrap());
+        let desc = PromptCompiler::describe_intent(&intent);
+
+        assert!(desc.contains("- TOTAL_AMT: pattern=\"#,##0.00\" (amount in USD) (round HALF_UP)"));
+        assert!(desc.contains("Bind date columns to datepicker"));
+        assert!(desc.contains("// rounding: HALF_UP, 2 decimal places"));
+    }
+
+    #[test]
+    fn test_describe_intent_includes_archetypes() {
+        use crate::domain::ColumnArchetype;
+
+        let archetype = ColumnArchetype {
+            label: Some("사업자등록번호".to_string()),
+            max_length: Some(12),
+            validation: Some(r"^\d{3}-\d{2}-\d{5}$".to_string()),
+            format: Some("###-##-#####".to_string()),
+            ..ColumnArchetype::new("사업자등록번호").with_name_patterns(&["BIZ_REG_NO"])
+        };
+        let mut column = ColumnIntent::new("BIZ_REG_NO", "Biz Reg No");
+        archetype.apply(&mut column);
+        let intent = UiIntent::new("vendor_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_vendor").with_column(column));
+        let desc = PromptCompiler::describe_intent(&intent);
+
+        assert!(desc.contains(r####"- BIZ_REG_NO: pattern="###-##-#####" (input mask)"####));
+        assert!(desc.contains(
+            r#"- BIZ_REG_NO (사업자등록번호): 사업자등록번호 - input, max_length="12", value must match /^\d{3}-\d{2}-\d{5}$/"#
+        ));
+    }
+
+    #[test]
+    fn test_describe_intent_includes_address_popup() {
+        use crate::domain::AddressIntent;
+
+        let intent = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_customer").with_column(ColumnIntent::new("ZIP_CD", "우편번호")))
+            .with_address(AddressIntent::new("ds_customer", "ZIP_CD", "ADDR1", "ADDR2"));
+        let desc = PromptCompiler::describe_intent(&intent);
+
+        assert!(desc.contains(
+            r#"pushbutton btn_addr_search (우편번호 찾기, on_click="eventfunc:fn_addr_search()") next to it"#
+        ));
+        assert!(desc.contains(r#"normal_field field_addr2 (link_data="ds_customer:ADDR2") for the detail address"#));
+        assert!(desc.contains("url: \"/common/zipcode_popup.xml\","));
+        assert!(desc.contains("this.fn_addr_callback = function(returnValue) {"));
+    }
+
+    #[test]
+    fn test_describe_editable_grid() {
+        let mut intent = UiIntent::new("member_list", ScreenType::List)
+            .with_grid(crate::domain::GridIntent::new("grid_member", "ds_member"));
+        assert!(!PromptCompiler::describe_intent(&intent).contains("getChangedData"));
+
+        intent.enable_grid_editing();
+        let desc = PromptCompiler::describe_intent(&intent);
+        assert!(desc.contains("Editable grid"));
+        assert!(desc.contains("getChangedData()"));
+    }
+
+    #[test]
+    fn test_describe_dashboard_layout() {
+        use crate::domain::{DashboardIntent, KpiIntent};
+
+        let dashboard = DashboardIntent {
+            kpis: vec![KpiIntent::new("ds_orders", "TOTAL_AMT", "총 매출")],
+            refresh_seconds: Some(60),
+            ..Default::default()
+        };
+        let intent = UiIntent::new("orders_dashboard", ScreenType::Dashboard).with_dashboard(dashboard);
+        let desc = PromptCompiler::describe_intent(&intent);
+
+        assert!(desc.contains(
+            "- pnl_kpi_total_amt \"총 매출\": value link_data=\"ds_orders:TOTAL_AMT\" (first row), \
+             x=\"10\" y=\"50\" width=\"243\" height=\"80\""
+        ));
+        assert!(desc.contains("fn_refresh reloads ds_orders"));
+        assert!(desc.contains("every 60 seconds"));
+
+        let prompt = PromptCompiler::compile_with_defaults(&intent, None);
+        assert!(prompt.system.contains("dashboard (statistics) screens"));
+    }
+
+    #[test]
+    fn test_describe_wizard_steps() {
+        use crate::domain::{WizardIntent, WizardStep};
+
+        let intent = UiIntent::new("member_join", ScreenType::Wizard)
+            .with_dataset(DatasetIntent::new("ds_member").with_column(ColumnIntent::new("NAME", "이름").required()))
+            .with_dataset(DatasetIntent::new("ds_agreement"));
+        let desc = PromptCompiler::describe_intent(&intent);
+        assert!(desc.contains("1. pnl_step_member \"member\": fields of ds_member; fn_validate_member checks required NAME"));
+        assert!(desc.contains("sends ds_member, ds_agreement in one save transaction"));
+
+        let intent = intent.with_wizard(WizardIntent {
+            steps: vec![WizardStep::new("terms", "약관 동의", "ds_agreement")],
+        });
+        let desc = PromptCompiler::describe_intent(&intent);
+        assert!(desc.contains("1. pnl_step_terms \"약관 동의\""));
+        assert!(!desc.contains("pnl_step_member"));
+    }
+
+    #[test]
+    fn test_describe_tabs() {
+        use crate::domain::{TabIntent, TabsIntent};
+
+        let intent = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_customer"))
+            .with_dataset(DatasetIntent::new("ds_contract"));
+        let tabs = TabsIntent::new(vec![
+            TabIntent::new("basic", "기본", "ds_customer").with_columns(vec!["CUST_ID".to_string(), "CUST_NM".to_string()]),
+            TabIntent::new("contract", "계약", "ds_contract"),
+        ]);
+
+        let desc = PromptCompiler::describe_intent(&intent.clone().with_tabs(tabs.clone()));
+        assert!(desc.contains("one <tab> control tab_detail"));
+        assert!(desc.contains("1. pnl_tab_basic \"기본\": CUST_ID, CUST_NM of ds_customer"));
+        assert!(desc.contains("2. pnl_tab_contract \"계약\": every field of ds_contract"));
+        assert!(desc.contains("on_load loads ds_customer, ds_contract."));
+
+        let desc = PromptCompiler::describe_intent(&intent.with_tabs(tabs.lazy()));
+        assert!(desc.contains("on_load loads only ds_customer"));
+        assert!(desc.contains("- fn_load_tab_contract loads ds_contract the first time"));
+    }
+
+    #[test]
+    fn test_describe_print() {
+        use crate::domain::{PrintIntent, PrintRules, ReportTool};
+
+        let mut intent = create_test_intent();
+        let rules = PrintRules {
+            tool: ReportTool::Oz,
+            helper: Some("gfn_openOzViewer".to_string()),
+            report_path: Some("/report/".to_string()),
+        };
+        intent.enable_print(PrintIntent::for_intent(&intent, None, Some(&rules)));
+        let desc = PromptCompiler::describe_intent(&intent);
+        assert!(desc.contains("Printing (OZ Report): add pushbutton btn_print"));
+        assert!(desc.contains("calls gfn_openOzViewer(ds_member, \"/report/member_list_print.ozr\")"));
+        assert!(desc.contains("- print (인쇄): fn_print"));
+
+        let mut intent = create_test_intent();
+        intent.enable_print(PrintIntent::for_intent(&intent, None, None));
+        let desc = PromptCompiler::describe_intent(&intent);
+        assert!(desc.contains("// TODO: connect xFrame5 print"));
+    }
+
+    #[test]
+    fn test_describe_search_state() {
+        use crate::domain::SearchStateIntent;
+
+        let mut intent = create_test_intent();
+        assert!(!PromptCompiler::describe_intent(&intent).contains("Keep search conditions"));
+
+        intent.search_state = Some(SearchStateIntent::for_intent(&intent, None));
+        let desc = PromptCompiler::describe_intent(&intent);
+        assert!(desc.contains("bind every search field to ds_search; on_load calls this.fn_restoreSearchState()"));
+        assert!(desc.contains("fn_search calls this.fn_saveSearchState() before its transaction"));
+    }
+
+    #[test]
+    fn test_full_prompt() {
+        let intent = create_test_intent();
+        let prompt = PromptCompiler::compile_with_defaults(&intent, None);
+        let full = prompt.full();
+
+        assert!(full.contains("xFrame5"));
+        assert!(full.contains("member_list"));
+    }
+}
@@ -0,0 +1,718 @@
+//! User Prompt Sections
+//!
+//! The user prompt built from an intent (and the `{{dsl_description}}`
+//! template variable) is assembled from small section renderers, each
+//! describing one aspect of the screen. A section renders to an empty string
+//! when the intent has nothing for it, so sections never need to know about
+//! each other.
+//!
+//! New kinds of sections (e.g. a search panel or permissions) implement
+//! [`PromptSection`] and are added to [`PromptSections::specification`] or
+//! inserted into a list with [`PromptSections::insert_after`].
+
+use super::PromptCompiler;
+use crate::domain::{
+    ActionType, AddressIntent, ScreenType, UiIntent, DASHBOARD_WIDTH, RESTORE_SEARCH_FUNCTION, SAVE_SEARCH_FUNCTION,
+    TAB_CHANGED_FUNCTION, UNMASK_AUTH_HOOK,
+};
+
+/// One part of the user prompt
+pub trait PromptSection: Send + Sync {
+    /// Name of the section (for ordering and debugging)
+    fn name(&self) -> &'static str;
+
+    /// Text of the section for the intent (empty = section omitted)
+    fn render(&self, intent: &UiIntent) -> String;
+}
+
+/// Ordered list of sections
+pub struct PromptSections {
+    sections: Vec<Box<dyn PromptSection>>,
+}
+
+impl PromptSections {
+    /// Screen specification (`{{dsl_description}}`)
+    pub fn specification() -> Self {
+        Self {
+            sections: vec![
+                Box::new(EntitySummary),
+                Box::new(DatasetsSection),
+                Box::new(GridSection),
+                Box::new(ActionsSection),
+                Box::new(PopupSection),
+                Box::new(DashboardSection),
+                Box::new(WizardSection),
+                Box::new(TabsSection),
+                Box::new(AddressSection),
+                Box::new(PrintSection),
+                Box::new(SearchStateSection),
+                Box::new(MaskingSection),
+                Box::new(FormatsSection),
+                Box::new(ArchetypesSection),
+            ],
+        }
+    }
+
+    /// Body of the user prompt built without a template: specification, requirements, notes
+    pub fn user_prompt() -> Self {
+        let mut sections = Self::specification();
+        sections.sections.push(Box::new(RequirementsSection));
+        sections.sections.push(Box::new(NotesSection));
+        sections
+    }
+
+    /// Insert a section after the named one (at the end if there is none)
+    pub fn insert_after(mut self, name: &str, section: Box<dyn PromptSection>) -> Self {
+        let index = self
+            .sections
+            .iter()
+            .position(|s| s.name() == name)
+            .map_or(self.sections.len(), |i| i + 1);
+        self.sections.insert(index, section);
+        self
+    }
+
+    /// Names of the sections, in order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sections.iter().map(|s| s.name()).collect()
+    }
+
+    /// Render all sections for an intent
+    pub fn render(&self, intent: &UiIntent) -> String {
+        self.sections.iter().map(|s| s.render(intent)).collect()
+    }
+}
+
+/// Screen type and name
+pub struct EntitySummary;
+
+impl PromptSection for EntitySummary {
+    fn name(&self) -> &'static str {
+        "entity_summary"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        format!("Create a {} screen named '{}'.\n", intent.screen_type.as_str(), intent.screen_name)
+    }
+}
+
+/// Datasets with their tables and columns
+pub struct DatasetsSection;
+
+impl PromptSection for DatasetsSection {
+    fn name(&self) -> &'static str {
+        "datasets"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        if intent.datasets.is_empty() {
+            return String::new();
+        }
+
+        let mut desc = String::from("\nDatasets:\n");
+        for ds in &intent.datasets {
+            desc.push_str(&format!("- {} (table: {})\n", ds.id, ds.table_name.as_deref().unwrap_or("unknown")));
+            if !ds.columns.is_empty() {
+                desc.push_str("  Columns:\n");
+                for col in &ds.columns {
+                    desc.push_str(&format!(
+                        "    - {} ({}, {}, {}{}{})\n",
+                        col.name,
+                        col.label,
+                        col.ui_type.as_str(),
+                        col.data_type.as_str(),
+                        if col.required { ", required" } else { "" },
+                        col.code_group.as_deref().map(|g| format!(", code group {}", g)).unwrap_or_default()
+                    ));
+                }
+            }
+        }
+        desc
+    }
+}
+
+/// Grids, their columns, paging and in-grid editing
+pub struct GridSection;
+
+impl PromptSection for GridSection {
+    fn name(&self) -> &'static str {
+        "grids"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        if intent.grids.is_empty() {
+            return String::new();
+        }
+
+        let mut desc = String::from("\nGrids:\n");
+        for grid in &intent.grids {
+            desc.push_str(&format!("- {} (bound to {})\n", grid.id, grid.dataset_id));
+            if !grid.columns.is_empty() {
+                desc.push_str("  Columns: ");
+                let col_names: Vec<_> = grid.columns.iter().map(|c| c.header.as_str()).collect();
+                desc.push_str(&col_names.join(", "));
+                desc.push('\n');
+            }
+            if grid.requires_paging {
+                desc.push_str(&format!(
+                    "  Paging required (large table): send PAGE_NO and PAGE_SIZE ({}) in ds_search, \
+                     show TOTAL_COUNT from the response with a pagingbar; never load all rows\n",
+                    grid.page_size.unwrap_or(20)
+                ));
+            }
+            if grid.editable {
+                desc.push_str(
+                    "  Editable grid (in-grid editing): set editable=\"1\" on the data columns users change \
+                     (keys stay editable=\"0\"); add/delete only insert or mark rows in the dataset; \
+                     fn_save takes the changed rows with getChangedData(), loops over them and sends each \
+                     row's status from getRowType() as ROW_STATUS (I = insert, U = update, D = delete) \
+                     in one save transaction\n",
+                );
+            }
+        }
+        desc
+    }
+}
+
+/// Buttons and their functions
+pub struct ActionsSection;
+
+impl PromptSection for ActionsSection {
+    fn name(&self) -> &'static str {
+        "actions"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        if intent.actions.is_empty() {
+            return String::new();
+        }
+
+        let mut desc = String::from("\nActions:\n");
+        for action in &intent.actions {
+            desc.push_str(&format!("- {} ({}): {}\n", action.id, action.label, action.function_name));
+        }
+        desc
+    }
+}
+
+/// How the screen opens popups or is opened as one
+pub struct PopupSection;
+
+impl PromptSection for PopupSection {
+    fn name(&self) -> &'static str {
+        "popup_associations"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let openers: Vec<String> = intent
+            .actions
+            .iter()
+            .filter(|a| a.action_type == ActionType::OpenPopup)
+            .map(|a| format!("- {} ({}) opens its popup\n", a.label, a.function_name))
+            .collect();
+
+        match intent.screen_type {
+            ScreenType::ListWithPopup => {
+                let add = intent
+                    .actions
+                    .iter()
+                    .find(|a| a.action_type == ActionType::Add)
+                    .map_or("fn_add", |a| a.function_name.as_str());
+                format!(
+                    "\nPopup associations (detail popup of this list):\n\
+                     - {} opens the popup with {{ mode: \"create\" }}\n\
+                     - Grid double-click opens it with {{ mode: \"edit\" }} and the key of the clicked row\n\
+                     - The popup returns {{ saved, mode }}; on_popupdestroy calls fn_search when saved is true\n{}",
+                    add,
+                    openers.concat()
+                )
+            }
+            ScreenType::Popup => String::from(
+                "\nPopup screen: opened from a parent screen; read the mode (create/edit) and the key from \
+                 extra_data, and return { saved, mode } with closepopup()\n",
+            ),
+            _ if !openers.is_empty() => format!("\nPopup associations:\n{}", openers.concat()),
+            _ => String::new(),
+        }
+    }
+}
+
+/// KPI cards, charts and their fixed positions for dashboard screens
+pub struct DashboardSection;
+
+impl PromptSection for DashboardSection {
+    fn name(&self) -> &'static str {
+        "dashboard"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let Some(dashboard) = &intent.dashboard else {
+            return String::new();
+        };
+        let layout = dashboard.layout();
+        let position = |id: &str| {
+            layout
+                .iter()
+                .find(|(component, _)| *component == id)
+                .map(|(_, p)| format!("x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"", p.x, p.y, p.width, p.height))
+                .unwrap_or_default()
+        };
+
+        let mut desc = format!(
+            "\nDashboard layout (screen width=\"{}\" height=\"{}\"; use these positions exactly):\n",
+            DASHBOARD_WIDTH,
+            dashboard.height()
+        );
+        if !dashboard.kpis.is_empty() {
+            desc.push_str("KPI cards (panel with label text and value text):\n");
+            for kpi in &dashboard.kpis {
+                desc.push_str(&format!(
+                    "- {} \"{}\": value link_data=\"{}:{}\" (first row), {}\n",
+                    kpi.id,
+                    kpi.label,
+                    kpi.dataset_id,
+                    kpi.value_column,
+                    position(&kpi.id)
+                ));
+            }
+        }
+        if !dashboard.charts.is_empty() {
+            desc.push_str("Charts (<chart> components):\n");
+            for chart in &dashboard.charts {
+                desc.push_str(&format!(
+                    "- {} \"{}\": chart_type=\"{}\" link_data=\"{}\", category {}, series {}, {}\n",
+                    chart.id,
+                    chart.title,
+                    chart.chart_type.as_str(),
+                    chart.dataset_id,
+                    chart.category_column,
+                    chart.value_columns.join(", "),
+                    position(&chart.id)
+                ));
+            }
+        }
+        desc.push_str(&format!(
+            "fn_refresh reloads {} (one transaction each) and redraws each chart when its dataset arrives; \
+             on_load and the refresh button call it",
+            dashboard.dataset_ids().join(", ")
+        ));
+        match dashboard.refresh_seconds {
+            Some(seconds) => desc.push_str(&format!(
+                "; a timer also calls it every {} seconds (stop the timer when the screen closes)\n",
+                seconds
+            )),
+            None => desc.push('\n'),
+        }
+        desc
+    }
+}
+
+/// Steps, step validation and navigation for wizard screens
+pub struct WizardSection;
+
+impl PromptSection for WizardSection {
+    fn name(&self) -> &'static str {
+        "wizard"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let Some(wizard) = intent.wizard_steps() else {
+            return String::new();
+        };
+
+        let mut desc = String::from("\nWizard steps (show one panel at a time, starting with the first):\n");
+        for (i, step) in wizard.steps.iter().enumerate() {
+            let required: Vec<&str> = intent
+                .datasets
+                .iter()
+                .filter(|ds| ds.id == step.dataset_id)
+                .flat_map(|ds| ds.columns.iter())
+                .filter(|c| c.required)
+                .map(|c| c.name.as_str())
+                .collect();
+            desc.push_str(&format!(
+                "{}. {} \"{}\": fields of {}; {} checks {}\n",
+                i + 1,
+                step.panel(),
+                step.title,
+                step.dataset_id,
+                step.validate_function(),
+                if required.is_empty() {
+                    "the step's input".to_string()
+                } else {
+                    format!("required {}", required.join(", "))
+                }
+            ));
+        }
+        desc.push_str(&format!(
+            "fn_next runs the current step's validation and shows the next panel; fn_prev shows the previous \
+             panel without validating; hide 이전 on the first step and show 완료 only on the last.\n\
+             fn_submit validates every step again and sends {} in one save transaction.\n",
+            wizard.dataset_ids().join(", ")
+        ));
+        desc
+    }
+}
+
+/// Tab control, tab panels and lazy tab loading for tabbed detail screens
+pub struct TabsSection;
+
+impl PromptSection for TabsSection {
+    fn name(&self) -> &'static str {
+        "tabs"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let Some(tabs) = intent.tabs.as_ref().filter(|t| !t.tabs.is_empty()) else {
+            return String::new();
+        };
+
+        let mut desc = format!(
+            "\nTabs: one <tab> control {} with a <tab_item> per tab, in this order; each tab_item holds a panel \
+             with the tab's fields:\n",
+            tabs.control()
+        );
+        for (i, tab) in tabs.tabs.iter().enumerate() {
+            let fields = if tab.columns.is_empty() {
+                format!("every field of {}", tab.dataset_id)
+            } else {
+                format!("{} of {}", tab.columns.join(", "), tab.dataset_id)
+            };
+            desc.push_str(&format!("{}. {} \"{}\": {}\n", i + 1, tab.panel(), tab.title, fields));
+        }
+
+        let lazy = tabs.lazy_tabs();
+        if lazy.is_empty() {
+            desc.push_str(&format!("on_load loads {}.\n", tabs.dataset_ids().join(", ")));
+        } else {
+            let eager: Vec<&str> = tabs
+                .dataset_ids()
+                .into_iter()
+                .filter(|ds| !lazy.iter().any(|tab| tab.dataset_id == *ds))
+                .collect();
+            desc.push_str(&format!(
+                "Load tabs lazily: on_load loads only {}. The tab control's on_tabchanged event calls \
+                 {}(objInst, nPrevItem, nCurItem), which calls the load function of the tab now shown:\n",
+                eager.join(", "),
+                TAB_CHANGED_FUNCTION
+            ));
+            for tab in &lazy {
+                desc.push_str(&format!(
+                    "- {} loads {} the first time (skip it when the dataset already has rows)\n",
+                    tab.load_function(),
+                    tab.dataset_id
+                ));
+            }
+        }
+        desc.push_str(&format!(
+            "fn_save validates the fields of every tab (select the tab with the first invalid field) and saves {}.\n",
+            tabs.dataset_ids().join(", ")
+        ));
+        desc
+    }
+}
+
+/// Postcode, base address and detail address fields with the postcode search popup
+pub struct AddressSection;
+
+impl PromptSection for AddressSection {
+    fn name(&self) -> &'static str {
+        "addresses"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let mut desc = String::new();
+        for address in &intent.addresses {
+            let [zip, base, detail] = address.columns();
+            desc.push_str(&format!(
+                "\nAddress \"{label}\" ({ds}): put normal_field {zip_field} (link_data=\"{ds}:{zip}\", read-only) with \
+                 pushbutton {button} (우편번호 찾기, on_click=\"eventfunc:{search}()\") next to it, then normal_field \
+                 {base_field} (link_data=\"{ds}:{base}\", read-only) and normal_field {detail_field} \
+                 (link_data=\"{ds}:{detail}\") for the detail address. The popup fills the postcode and base address; \
+                 write these two functions as given:\n{functions}",
+                label = address.label,
+                ds = address.dataset_id,
+                zip = zip,
+                zip_field = AddressIntent::field(zip),
+                button = address.button(),
+                search = address.search_function(),
+                base = base,
+                base_field = AddressIntent::field(base),
+                detail = detail,
+                detail_field = AddressIntent::field(detail),
+                functions = address.functions(),
+            ));
+        }
+        desc
+    }
+}
+
+/// Print button and fn_print wiring to the customer's report tool
+pub struct PrintSection;
+
+impl PromptSection for PrintSection {
+    fn name(&self) -> &'static str {
+        "print"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let Some(print) = &intent.print else {
+            return String::new();
+        };
+
+        let mut desc = format!(
+            "\nPrinting ({}): add pushbutton btn_print (인쇄) with on_click=\"eventfunc:fn_print()\" next to the other buttons.\n",
+            print.tool.label()
+        );
+        match &print.helper {
+            Some(helper) => desc.push_str(&format!(
+                "fn_print checks {} has rows, then calls {}({}, \"{}\"); do not open the report any other way.\n",
+                print.dataset_id,
+                helper,
+                print.dataset_id,
+                print.report_file()
+            )),
+            None => desc.push_str(&format!(
+                "fn_print checks {} has rows, then opens report \"{}\" with its rows; \
+                 leave a // TODO: connect {} comment where the viewer is called.\n",
+                print.dataset_id,
+                print.report_file(),
+                print.tool.label()
+            )),
+        }
+        desc
+    }
+}
+
+/// Where kept search conditions are saved and restored
+pub struct SearchStateSection;
+
+impl PromptSection for SearchStateSection {
+    fn name(&self) -> &'static str {
+        "search_state"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let Some(state) = &intent.search_state else {
+            return String::new();
+        };
+        format!(
+            "\nKeep search conditions: bind every search field to {}; on_load calls this.{}() before the first \
+             fn_search, and fn_search calls this.{}() before its transaction. Do not write these two \
+             functions; they are added after generation.\n",
+            state.dataset_id, RESTORE_SEARCH_FUNCTION, SAVE_SEARCH_FUNCTION
+        )
+    }
+}
+
+/// Masking instructions for columns classified as personal data
+pub struct MaskingSection;
+
+impl PromptSection for MaskingSection {
+    fn name(&self) -> &'static str {
+        "masking"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let sensitive = intent.sensitive_columns();
+        if sensitive.is_empty() {
+            return String::new();
+        }
+
+        let mut desc = String::from("\nSensitive columns (display masked):\n");
+        for col in sensitive {
+            if let Some(kind) = col.sensitivity {
+                desc.push_str(&format!(
+                    "- {} ({}): pattern=\"{}\"\n",
+                    col.name,
+                    kind.description(),
+                    kind.mask_pattern()
+                ));
+            }
+        }
+        desc.push_str(&format!(
+            "Set the pattern on every grid <data> and field bound to these columns. \
+             Show unmasked values only after this.{}(column) returns true.\n",
+            UNMASK_AUTH_HOOK
+        ));
+        desc
+    }
+}
+
+/// Date/number formats of datepickers, numeric fields and grid columns
+pub struct FormatsSection;
+
+impl PromptSection for FormatsSection {
+    fn name(&self) -> &'static str {
+        "formats"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let formatted = intent.formatted_columns();
+        if formatted.is_empty() {
+            return String::new();
+        }
+
+        let mut desc = String::from("\nDisplay formats:\n");
+        let mut rounded = false;
+        for col in formatted {
+            let Some(format) = &col.format else { continue };
+            let mut attrs = Vec::new();
+            if let Some(date) = &format.date_format {
+                attrs.push(format!("date_format=\"{}\"", date));
+            }
+            if let Some(pattern) = format.number_pattern() {
+                attrs.push(format!("pattern=\"{}\"", pattern));
+            }
+            if let Some(mask) = &format.mask {
+                attrs.push(format!("pattern=\"{}\" (input mask)", mask));
+            }
+            let currency = format.currency.as_deref().map(|c| format!(" (amount in {})", c)).unwrap_or_default();
+            let rounding = match format.rounding {
+                Some(mode) => {
+                    rounded = true;
+                    format!(" (round {})", mode.as_str())
+                }
+                None => String::new(),
+            };
+            desc.push_str(&format!("- {}: {}{}{}\n", col.name, attrs.join(" "), currency, rounding));
+        }
+        desc.push_str(
+            "Bind date columns to datepicker, numeric columns to numericex_field and masked columns to \
+             normal_field, and set these attributes on them and on the grid <data> of the same column. \
+             Right-align numeric grid columns (text_horzalign=\"2\").\n",
+        );
+        if rounded {
+            desc.push_str(
+                "Where JavaScript computes a value of a rounded column, round it to the column's decimal \
+                 places with the given mode and comment the mode (e.g. // rounding: HALF_UP, 2 decimal places).\n",
+            );
+        }
+        desc
+    }
+}
+
+/// Control, length and validation of columns matched to a column archetype
+pub struct ArchetypesSection;
+
+impl PromptSection for ArchetypesSection {
+    fn name(&self) -> &'static str {
+        "archetypes"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let columns = intent.archetype_columns();
+        if columns.is_empty() {
+            return String::new();
+        }
+
+        let mut desc = String::from("\nColumn archetypes (the same rules on every screen):\n");
+        for col in columns {
+            let mut rules = vec![col.ui_type.as_str().to_string()];
+            if let Some(len) = col.max_length {
+                rules.push(format!("max_length=\"{}\"", len));
+            }
+            if let Some(validation) = &col.validation {
+                rules.push(format!("value must match /{}/", validation));
+            }
+            desc.push_str(&format!(
+                "- {} ({}): {} - {}\n",
+                col.name,
+                col.label,
+                col.archetype.as_deref().unwrap_or_default(),
+                rules.join(", ")
+            ));
+        }
+        desc.push_str(
+            "Use exactly this control and max_length for these columns; before saving, fn_validate tests \
+             each value against its pattern and names the field's label in the message.\n",
+        );
+        desc
+    }
+}
+
+/// One-line summary of type, name, datasets, grids and actions
+pub struct RequirementsSection;
+
+impl PromptSection for RequirementsSection {
+    fn name(&self) -> &'static str {
+        "requirements"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        let mut desc = String::from("\n\nRequirements:\n");
+        desc.push_str(&format!("- Screen type: {}\n", intent.screen_type.as_str()));
+        desc.push_str(&format!("- Screen name: {}\n", intent.screen_name));
+        if !intent.datasets.is_empty() {
+            desc.push_str(&format!("- Datasets: {}\n", PromptCompiler::describe_datasets(&intent.datasets)));
+        }
+        if !intent.grids.is_empty() {
+            desc.push_str(&format!("- Grid columns: {}\n", PromptCompiler::describe_grids(&intent.grids)));
+        }
+        if !intent.actions.is_empty() {
+            desc.push_str(&format!("- Actions: {}\n", PromptCompiler::describe_actions(&intent.actions)));
+        }
+        desc
+    }
+}
+
+/// Notes of the request
+pub struct NotesSection;
+
+impl PromptSection for NotesSection {
+    fn name(&self) -> &'static str {
+        "notes"
+    }
+
+    fn render(&self, intent: &UiIntent) -> String {
+        match &intent.notes {
+            Some(notes) => format!("\nAdditional notes:\n{}\n", notes),
+            None => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ActionIntent;
+
+    struct PermissionsSection;
+
+    impl PromptSection for PermissionsSection {
+        fn name(&self) -> &'static str {
+            "permissions"
+        }
+
+        fn render(&self, _intent: &UiIntent) -> String {
+            "\nPermissions: hide btn_delete unless the user has the delete role\n".to_string()
+        }
+    }
+
+    #[test]
+    fn test_insert_section_after_actions() {
+        let sections = PromptSections::specification().insert_after("actions", Box::new(PermissionsSection));
+        assert_eq!(&sections.names()[3..5], &["actions", "permissions"]);
+
+        let intent = UiIntent::new("member_list", ScreenType::List)
+            .with_action(ActionIntent::new("delete", "삭제", ActionType::Delete));
+        let prompt = sections.render(&intent);
+        let actions = prompt.find("- delete (삭제): fn_delete").unwrap();
+        assert!(prompt.find("Permissions:").unwrap() > actions);
+    }
+
+    #[test]
+    fn test_popup_associations() {
+        let list = UiIntent::new("member_list", ScreenType::List);
+        assert_eq!(PopupSection.render(&list), "");
+
+        let intent = UiIntent::new("member_list", ScreenType::ListWithPopup)
+            .with_action(ActionIntent::new("add", "신규", ActionType::Add));
+        let desc = PopupSection.render(&intent);
+        assert!(desc.contains("- fn_add opens the popup with { mode: \"create\" }"));
+        assert!(desc.contains("on_popupdestroy calls fn_search"));
+
+        let popup = UiIntent::new("member_popup", ScreenType::Popup);
+        assert!(PopupSection.render(&popup).contains("closepopup()"));
+    }
+}
@@ -0,0 +1,428 @@
+//! Prompt Compression
+//!
+//! The v3 system prompts carry every xFrame5 pattern (~10k tokens), which
+//! overflows the context of small local models. Compression splits the
+//! template into tagged sections and keeps only those the intent needs, e.g.
+//! a plain list screen gets no popup, create/edit or combobox patterns.
+//!
+//! Sections start at a `## ` heading or a `═══` banner title. Tags come from
+//! an explicit `<!-- tags: popup, create -->` comment on the heading line or
+//! are inferred from its words. A section is dropped when any of its tags
+//! (or its banner's tags) is irrelevant to the intent; untagged sections are
+//! always kept. Tag comments are stripped from the prompt in every mode.
+//!
+//! `PROMPT_COMPRESSION`: `off` (default), `on`, or `auto` (only when the
+//! system prompt would exceed `PROMPT_COMPRESSION_MAX_TOKENS`, default 6000).
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::domain::{ActionType, ScreenType, UiIntent, UiType};
+
+/// Heading words and the tag they imply
+const INFERRED_TAGS: &[(&str, &str)] = &[
+    ("popup", "popup"),
+    ("double-click", "popup"),
+    ("create", "create"),
+    ("edit", "edit"),
+    ("delete", "delete"),
+    ("combo", "combo"),
+    ("paging", "paging"),
+    ("checklist", "reference"),
+    ("reference", "reference"),
+];
+
+/// When the compression pass runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMode {
+    Off,
+    On,
+    /// Only when the prompt is over the token budget
+    Auto,
+}
+
+/// Compression settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptCompression {
+    pub mode: CompressionMode,
+    /// System prompt size above which `auto` compresses
+    pub max_tokens: usize,
+}
+
+impl Default for PromptCompression {
+    fn default() -> Self {
+        Self {
+            mode: CompressionMode::Off,
+            max_tokens: 6000,
+        }
+    }
+}
+
+/// A heading and the lines up to the next one
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptSection {
+    /// `None` for the preamble before the first heading
+    pub title: Option<String>,
+    /// Own tags plus those of the enclosing banner
+    pub tags: BTreeSet<String>,
+    pub lines: Vec<String>,
+}
+
+fn tag_comment() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\s*<!--\s*tags:\s*([^>]*?)\s*-->").expect("Failed to compile tag regex"))
+}
+
+fn is_banner_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.chars().count() >= 10 && line.chars().all(|c| c == '═')
+}
+
+/// Heading text without its tag comment, and the heading's tags
+fn parse_heading(heading: &str) -> (String, BTreeSet<String>) {
+    if let Some(caps) = tag_comment().captures(heading) {
+        let tags = caps[1]
+            .split(',')
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        return (tag_comment().replace(heading, "").trim().to_string(), tags);
+    }
+
+    let lower = heading.to_lowercase();
+    let tags = INFERRED_TAGS
+        .iter()
+        .filter(|(word, _)| lower.contains(word))
+        .map(|(_, tag)| tag.to_string())
+        .collect();
+    (heading.trim().to_string(), tags)
+}
+
+/// Rough token count (4 characters per token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Split a system prompt into its tagged sections
+pub fn segment(prompt: &str) -> Vec<PromptSection> {
+    let lines: Vec<&str> = prompt.lines().collect();
+    let mut sections = vec![PromptSection {
+        title: None,
+        tags: BTreeSet::new(),
+        lines: Vec::new(),
+    }];
+    let mut banner_tags = BTreeSet::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let is_banner = is_banner_rule(line)
+            && i + 2 < lines.len()
+            && !is_banner_rule(lines[i + 1])
+            && is_banner_rule(lines[i + 2]);
+
+        if is_banner {
+            let (title, tags) = parse_heading(lines[i + 1].trim());
+            banner_tags = tags.clone();
+            let indent = &lines[i + 1][..lines[i + 1].len() - lines[i + 1].trim_start().len()];
+            sections.push(PromptSection {
+                title: Some(title.clone()),
+                tags,
+                lines: vec![line.to_string(), format!("{}{}", indent, title), lines[i + 2].to_string()],
+            });
+            i += 3;
+            continue;
+        }
+
+        if let Some(heading) = line.trim_start().strip_prefix("## ") {
+            let (title, mut tags) = parse_heading(heading);
+            tags.extend(banner_tags.iter().cloned());
+            let indent = &line[..line.len() - line.trim_start().len()];
+            sections.push(PromptSection {
+                lines: vec![format!("{}## {}", indent, title)],
+                title: Some(title),
+                tags,
+            });
+        } else if let Some(current) = sections.last_mut() {
+            current.lines.push(line.to_string());
+        }
+        i += 1;
+    }
+
+    sections
+}
+
+/// Whether sections tagged `tag` are needed for `intent`
+pub fn is_relevant(tag: &str, intent: &UiIntent) -> bool {
+    let has_action = |kind: ActionType| intent.actions.iter().any(|a| a.action_type == kind);
+    let popup = intent.screen_type != ScreenType::List
+        || has_action(ActionType::OpenPopup)
+        || has_action(ActionType::ClosePopup)
+        || intent
+            .notes
+            .as_deref()
+            .is_some_and(|n| n.to_lowercase().contains("popup") || n.contains("팝업"));
+
+    match tag {
+        "popup" | "edit" => popup,
+        "create" => popup || has_action(ActionType::Add),
+        "delete" => has_action(ActionType::Delete),
+        "combo" => intent
+            .datasets
+            .iter()
+            .flat_map(|ds| ds.columns.iter())
+            .any(|c| c.ui_type == UiType::Combo),
+        "paging" => intent.grids.iter().any(|g| g.requires_paging),
+        "tabs" => intent.tabs.is_some(),
+        "address" => !intent.addresses.is_empty(),
+        // Checklists repeat the patterns; the validation passes enforce them
+        "reference" => false,
+        // Unknown tags are kept rather than guessed at
+        _ => true,
+    }
+}
+
+/// Prompt with the sections `keep` rejects removed
+///
+/// Template placeholders (`{{...}}`) in dropped sections are kept.
+fn retain_sections(prompt: &str, keep: impl Fn(&PromptSection) -> bool) -> String {
+    let mut kept = Vec::new();
+    for section in segment(prompt) {
+        if keep(&section) {
+            kept.extend(section.lines);
+        } else {
+            kept.extend(section.lines.into_iter().filter(|l| l.contains("{{") && l.contains("}}")));
+        }
+    }
+    kept.join("\n")
+}
+
+/// System prompt with only the sections relevant to `intent`
+pub fn compress(prompt: &str, intent: &UiIntent) -> String {
+    retain_sections(prompt, |section| section.tags.iter().all(|tag| is_relevant(tag, intent)))
+}
+
+/// Prompt without the sections whose title starts with `title` (any case)
+pub fn remove_sections(prompt: &str, title: &str) -> String {
+    let title = title.to_lowercase();
+    retain_sections(prompt, |section| {
+        !section
+            .title
+            .as_deref()
+            .is_some_and(|t| t.to_lowercase().starts_with(&title))
+    })
+}
+
+/// Prompt with the tag comments removed
+pub fn strip_tags(prompt: &str) -> String {
+    if !prompt.contains("<!--") {
+        return prompt.to_string();
+    }
+    prompt
+        .lines()
+        .map(|line| tag_comment().replace(line, ""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl PromptCompression {
+    /// Load from environment
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let mode = match std::env::var("PROMPT_COMPRESSION")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "on" | "true" | "1" => CompressionMode::On,
+            "auto" => CompressionMode::Auto,
+            _ => CompressionMode::Off,
+        };
+
+        Self {
+            mode,
+            max_tokens: std::env::var("PROMPT_COMPRESSION_MAX_TOKENS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_tokens),
+        }
+    }
+
+    /// Apply the configured mode to a template's system prompt
+    ///
+    /// `extra_tokens` is what gets appended after it (knowledge, rules) and
+    /// counts towards the `auto` budget.
+    pub fn apply(&self, prompt: &str, intent: &UiIntent, extra_tokens: usize) -> String {
+        let before = estimate_tokens(prompt);
+        let compress_now = match self.mode {
+            CompressionMode::Off => false,
+            CompressionMode::On => true,
+            CompressionMode::Auto => before + extra_tokens > self.max_tokens,
+        };
+        if !compress_now {
+            return strip_tags(prompt);
+        }
+
+        let compressed = compress(prompt, intent);
+        tracing::info!(
+            "Compressed system prompt for {} screen '{}': ~{} -> ~{} tokens",
+            intent.screen_type.as_str(),
+            intent.screen_name,
+            before,
+            estimate_tokens(&compressed)
+        );
+        compressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ActionIntent, ColumnIntent, DatasetIntent, GridIntent};
+
+    const LIST_V3: &str = include_str!("../fixtures/xframe5-list-v3.yaml");
+
+    fn list_v3_system_prompt() -> String {
+        let yaml: serde_yaml::Value = serde_yaml::from_str(LIST_V3).expect("fixture parses");
+        yaml["system_prompt"].as_str().expect("system_prompt").to_string()
+    }
+
+    fn plain_list() -> UiIntent {
+        UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(DatasetIntent::new("ds_list").with_column(ColumnIntent::new("name", "이름")))
+            .with_grid(GridIntent::new("grid_list", "ds_list"))
+    }
+
+    #[test]
+    fn test_segment_tags_banners_and_headings() {
+        let sections = segment(&list_v3_system_prompt());
+        let find = |title: &str| sections.iter().find(|s| s.title.as_deref() == Some(title)).unwrap();
+
+        assert_eq!(sections[0].title, None);
+        assert!(find("Screen Load").tags.is_empty());
+        assert!(find("Delete Function").tags.contains("delete"));
+
+        let edit = find("Edit Function (Opens Editor Popup in EDIT Mode)");
+        assert_eq!(edit.tags.iter().map(String::as_str).collect::<Vec<_>>(), vec!["edit", "popup"]);
+
+        let checklist = find("COMPONENT CHECKLIST (Verify ALL are included)");
+        assert!(checklist.tags.contains("reference"));
+        // Runs up to the next banner
+        assert!(checklist.lines.iter().any(|l| l.contains("[ ] on_load function")));
+        assert!(!checklist.lines.iter().any(|l| l.contains("CRITICAL VALIDATION RULES")));
+    }
+
+    #[test]
+    fn test_explicit_tags_override_inference_and_are_stripped() {
+        let prompt = "Intro\n## Search Panel <!-- tags: combo -->\nbody\n## Edit Button\nbody";
+        let sections = segment(prompt);
+        assert_eq!(sections[1].title.as_deref(), Some("Search Panel"));
+        assert!(sections[1].tags.contains("combo"));
+        assert_eq!(sections[1].lines[0], "## Search Panel");
+
+        let off = PromptCompression::default().apply(prompt, &plain_list(), 0);
+        assert!(!off.contains("<!--"));
+        assert!(off.contains("## Edit Button"));
+    }
+
+    #[test]
+    fn test_plain_list_prompt_is_halved() {
+        let prompt = list_v3_system_prompt();
+        let compressed = compress(&prompt, &plain_list());
+
+        assert!(estimate_tokens(&compressed) * 2 <= estimate_tokens(&prompt) + estimate_tokens(&prompt) / 10);
+        assert!(compressed.contains("## Grid with Dataset Binding"));
+        assert!(compressed.contains("## Search Function"));
+        assert!(!compressed.contains("loadpopup"));
+        assert!(!compressed.contains("## Combobox with Dataset Binding"));
+        assert!(!compressed.contains("POPUP ASSOCIATION"));
+        // Placeholder survives its section being dropped
+        assert!(compressed.contains("{{company_rules}}"));
+    }
+
+    #[test]
+    fn test_actions_bring_their_sections_back() {
+        let prompt = list_v3_system_prompt();
+        let intent = plain_list()
+            .with_action(ActionIntent::new("btn_delete", "삭제", ActionType::Delete))
+            .with_dataset(
+                DatasetIntent::new("ds_status")
+                    .with_column(ColumnIntent::new("status", "상태").with_ui_type(UiType::Combo)),
+            );
+        let compressed = compress(&prompt, &intent);
+        assert!(compressed.contains("## Delete Function"));
+        assert!(compressed.contains("## Combobox with Dataset Binding"));
+        assert!(!compressed.contains("## Create Function"));
+
+        let popup = UiIntent { screen_type: ScreenType::ListWithPopup, ..plain_list() };
+        let compressed = compress(&prompt, &popup);
+        assert!(compressed.contains("## Create Function"));
+        assert!(compressed.contains("POPUP ASSOCIATION"));
+    }
+
+    #[test]
+    fn test_tab_patterns_only_for_tabbed_screens() {
+        use crate::domain::{TabIntent, TabsIntent};
+
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(include_str!("../fixtures/xframe5-detail-v3.yaml")).expect("fixture parses");
+        let prompt = yaml["system_prompt"].as_str().expect("system_prompt");
+        let detail = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_customer").with_column(ColumnIntent::new("CUST_NM", "고객명")));
+
+        let compressed = compress(prompt, &detail);
+        assert!(!compressed.contains("## Tab Control with Tab Panels"));
+        assert!(!compressed.contains("fn_load_tab_contract"));
+        assert!(compressed.contains("## Button Panel"));
+
+        let tabbed = detail.with_tabs(TabsIntent::new(vec![TabIntent::new("basic", "기본", "ds_customer")]));
+        let compressed = compress(prompt, &tabbed);
+        assert!(compressed.contains("## Tab Control with Tab Panels"));
+        assert!(compressed.contains("## Lazy Tab Loading"));
+    }
+
+    #[test]
+    fn test_address_patterns_only_with_address_fields() {
+        use crate::domain::AddressIntent;
+
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(include_str!("../fixtures/xframe5-detail-v3.yaml")).expect("fixture parses");
+        let prompt = yaml["system_prompt"].as_str().expect("system_prompt");
+        let detail = UiIntent::new("customer_detail", ScreenType::Detail)
+            .with_dataset(DatasetIntent::new("ds_customer").with_column(ColumnIntent::new("ZIP_CD", "우편번호")));
+
+        let compressed = compress(prompt, &detail);
+        assert!(!compressed.contains("## Postcode Search Popup"));
+        assert!(!compressed.contains("btn_addr_search"));
+
+        let with_address = detail.with_address(AddressIntent::new("ds_customer", "ZIP_CD", "ADDR1", "ADDR2"));
+        let compressed = compress(prompt, &with_address);
+        assert!(compressed.contains("## Address Search (Postcode Popup)"));
+        assert!(compressed.contains("this.fn_addr_callback = function(returnValue) {"));
+    }
+
+    #[test]
+    fn test_remove_sections_by_title() {
+        let prompt = list_v3_system_prompt();
+        let without = remove_sections(&prompt, "component checklist");
+        assert!(!without.contains("COMPONENT CHECKLIST"));
+        assert!(!without.contains("[ ] on_load function"));
+        assert!(without.contains("CRITICAL VALIDATION RULES"));
+        assert!(without.contains("## Delete Function"));
+    }
+
+    #[test]
+    fn test_auto_mode_respects_budget() {
+        let prompt = list_v3_system_prompt();
+        let auto = PromptCompression {
+            mode: CompressionMode::Auto,
+            max_tokens: estimate_tokens(&prompt) + 100,
+        };
+        assert_eq!(auto.apply(&prompt, &plain_list(), 0), prompt);
+        assert!(auto.apply(&prompt, &plain_list(), 500).len() < prompt.len());
+    }
+}
@@ -5,9 +5,11 @@
 use crate::domain::{
     CodeExample, KnowledgeReference, QAAnswer, QAInput, QAMeta, QAOptions, QAResponse,
 };
-use crate::llm::create_backend_from_db_or_env;
+use crate::llm::{create_backend_from_db_or_env, LlmUsage};
 use crate::models::_entities::generation_logs;
-use crate::services::{KnowledgeBaseService, TemplateService};
+use crate::models::users::SYSTEM_USER_ID;
+use crate::services::response_cache::{qa_cache, ResponseCache};
+use crate::services::{KnowledgeBaseService, TemplateService, TenantScope};
 use anyhow::{anyhow, Result};
 use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
 use serde_json::Value;
@@ -27,22 +29,62 @@ impl QAService {
     ) -> Result<QAResponse> {
         let start = Instant::now();
 
+        // Templates, knowledge and LLM config of the user's company
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+
         // 1. Load QA template from DB
-        let template = TemplateService::get_active(db, product, Some("qa"))
+        let template = TemplateService::get_active(db, product, Some("qa"), &tenant)
             .await
             .map_err(|_| anyhow!("QA template not found for product: {}", product))?;
 
-        // 2. Query knowledge base for relevant entries
+        // 2. Serve repeated questions from the cache (dropped when the KB changes)
+        let kb_revision = KnowledgeBaseService::revision(db).await.ok();
+        if let Some(revision) = &kb_revision {
+            qa_cache().sync_kb_revision(revision);
+        }
+        let max_references = options.max_references.to_string();
+        let fingerprint = ResponseCache::<QAResponse>::fingerprint(&[
+            product,
+            &template.system_prompt,
+            &template.user_prompt_template,
+            input.context.as_deref().unwrap_or(""),
+            &max_references,
+            tenant.company().unwrap_or(""),
+        ]);
+
+        if kb_revision.is_some() {
+            if let Some(mut cached) = qa_cache().get(fingerprint, &input.question) {
+                let answer_time_ms = start.elapsed().as_millis() as u64;
+                Self::log_qa(
+                    db,
+                    product,
+                    input.question.len(),
+                    cached.references.len(),
+                    answer_time_ms as i32,
+                    user_id,
+                    None,
+                )
+                .await
+                .ok();
+
+                cached.meta = QAMeta::new(format!("{}-qa-v1", product), answer_time_ms);
+                cached.meta.cached = true;
+                return Ok(cached);
+            }
+        }
+
+        // 3. Query knowledge base for relevant entries
         let (knowledge_content, knowledge_refs) = KnowledgeBaseService::get_qa_knowledge(
             db,
             &input.question,
             product,
             options.max_references,
+            &tenant,
         )
         .await
         .unwrap_or_else(|_| (String::new(), vec![]));
 
-        // 3. Compile prompt
+        // 4. Compile prompt
         let (system_prompt, user_prompt) = Self::compile_prompt(
             &template.system_prompt,
             &template.user_prompt_template,
@@ -52,8 +94,8 @@ impl QAService {
 
         let full_prompt = format!("{}\n\n{}", system_prompt, user_prompt);
 
-        // 4. Generate via LLM
-        let llm = create_backend_from_db_or_env(db).await;
+        // 5. Generate via LLM
+        let llm = create_backend_from_db_or_env(db, tenant.company()).await;
 
         llm.health_check().await.map_err(|e| {
             anyhow!(
@@ -62,14 +104,16 @@ impl QAService {
             )
         })?;
 
-        let raw_output = llm.generate(&full_prompt).await?;
+        let output = llm.generate(&full_prompt).await?;
+        let llm_usage = LlmUsage::of(&output);
+        let raw_output = output.text;
 
-        // 5. Parse JSON response
+        // 6. Parse JSON response
         let qa_answer = Self::parse_qa_answer(&raw_output)?;
 
         let answer_time_ms = start.elapsed().as_millis() as u64;
 
-        // 6. Build knowledge references for response
+        // 7. Build knowledge references for response
         let references: Vec<KnowledgeReference> = knowledge_refs
             .into_iter()
             .map(|(id, name, category, section, relevance)| KnowledgeReference {
@@ -81,7 +125,7 @@ impl QAService {
             })
             .collect();
 
-        // 7. Log to audit trail (meta only, NO question content)
+        // 8. Log to audit trail (meta only, NO question content)
         Self::log_qa(
             db,
             product,
@@ -89,16 +133,21 @@ impl QAService {
             references.len(),
             answer_time_ms as i32,
             user_id,
+            Some(llm_usage),
         )
         .await
         .ok(); // Don't fail on log error
 
-        // 8. Build response
-        Ok(QAResponse::success(
+        // 9. Build response
+        let response = QAResponse::success(
             qa_answer,
             references,
             QAMeta::new(format!("{}-qa-v1", product), answer_time_ms),
-        ))
+        );
+        if kb_revision.is_some() {
+            qa_cache().insert(fingerprint, &input.question, response.clone());
+        }
+        Ok(response)
     }
 
     /// Compile the QA prompt using simple string replacement
@@ -300,6 +349,7 @@ impl QAService {
         reference_count: usize,
         answer_time_ms: i32,
         user_id: Option<i32>,
+        llm_usage: Option<LlmUsage>,
     ) -> Result<()> {
         // Store meta information about the Q&A
         let ui_intent_json = serde_json::to_string(&serde_json::json!({
@@ -308,7 +358,7 @@ impl QAService {
             "reference_count": reference_count,
         }))?;
 
-        let log = generation_logs::ActiveModel {
+        let mut log = generation_logs::ActiveModel {
             product: Set(product.to_string()),
             input_type: Set("qa".to_string()),
             ui_intent: Set(ui_intent_json),
@@ -318,9 +368,12 @@ impl QAService {
             warnings: Set(None),
             error_message: Set(None),
             generation_time_ms: Set(Some(answer_time_ms)),
-            user_id: Set(user_id.unwrap_or(1)),
+            user_id: Set(user_id.unwrap_or(SYSTEM_USER_ID)),
             ..Default::default()
         };
+        if let Some(usage) = &llm_usage {
+            usage.apply(&mut log);
+        }
 
         log.insert(db).await?;
 
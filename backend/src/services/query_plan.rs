@@ -0,0 +1,357 @@
+//! Query Plan Advisor
+//!
+//! For customers who give us a read-only connection to their database, asks
+//! the database to `EXPLAIN` the generated `selectList` once per search
+//! condition (`<if>`/`<when>`) and reports the conditions it can only answer
+//! with a full scan: no index on the column, a leading-wildcard `LIKE`, a
+//! function wrapped around the column. DBAs reject those in review, usually
+//! long after generation.
+//!
+//! Only `EXPLAIN` is sent (never `ANALYZE`), inside a read-only transaction.
+//! - PostgreSQL: the statement is prepared and explained as a generic plan
+//!   with `enable_seqscan = off`, so a sequential scan (or an index scan
+//!   without an index condition) that remains means no index serves the
+//!   condition, whatever the table size
+//! - MySQL: bind parameters become sample literals; a scan of type
+//!   `ALL`/`index` with no `possible_keys` is reported
+//!
+//! Disabled by default; configured from the environment:
+//! - `QUERY_PLAN_DATABASE_URL`: read-only `postgres://` or `mysql://` URL
+//! - `QUERY_PLAN_TIMEOUT_SECS`: limit per slice (default 10)
+//!
+//! Findings are advisory `Note:` lines and never change the generation status.
+
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use sea_orm::{
+    AccessMode, ConnectOptions, ConnectionTrait, Database, DatabaseBackend, Statement, TransactionTrait,
+};
+use serde_json::Value as Json;
+use sqlparser::ast::{Expr, Query, Value, VisitMut, VisitorMut};
+use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect};
+use sqlparser::parser::Parser;
+
+use crate::domain::DbDialect;
+use crate::services::MyBatisSqlChecker;
+
+/// Mapper statement of the list screen
+const LIST_STATEMENT: &str = "selectList";
+
+/// Query plan advisor settings
+#[derive(Clone)]
+pub struct QueryPlanConfig {
+    /// Read-only connection to the customer's database
+    pub url: String,
+    pub timeout: Duration,
+}
+
+impl QueryPlanConfig {
+    /// Load from environment (`None` = disabled)
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        Some(Self {
+            url: var("QUERY_PLAN_DATABASE_URL")?,
+            timeout: Duration::from_secs(var("QUERY_PLAN_TIMEOUT_SECS").and_then(|v| v.parse().ok()).unwrap_or(10)),
+        })
+    }
+
+    fn backend(&self) -> Result<DatabaseBackend> {
+        let scheme = self.url.split("://").next().unwrap_or_default().to_lowercase();
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Ok(DatabaseBackend::Postgres),
+            "mysql" | "mariadb" => Ok(DatabaseBackend::MySql),
+            _ => Err(anyhow!("'{}' databases are not supported (PostgreSQL and MySQL only)", scheme)),
+        }
+    }
+}
+
+/// The SQL explained for one search condition
+struct Variant {
+    /// `test` of the condition, or `None` for a statement without conditions
+    condition: Option<String>,
+    sql: String,
+    params: usize,
+}
+
+/// Rewrites bind placeholders: numbered `$n` for a PostgreSQL prepared
+/// statement, sample literals for MySQL
+struct Parameters {
+    numbered: bool,
+    count: usize,
+}
+
+impl VisitorMut for Parameters {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<()> {
+        if self.numbered {
+            return ControlFlow::Continue(());
+        }
+        // LIMIT/OFFSET only take numbers
+        let sample = || Expr::Value(Value::Number("10".to_string(), false));
+        let counts = query
+            .limit
+            .iter_mut()
+            .chain(query.offset.as_mut().map(|o| &mut o.value))
+            .chain(query.fetch.as_mut().and_then(|f| f.quantity.as_mut()));
+        for expr in counts {
+            if matches!(expr, Expr::Value(Value::Placeholder(_))) {
+                *expr = sample();
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_expr(&mut self, expr: &mut Expr) -> ControlFlow<()> {
+        if let Expr::Value(Value::Placeholder(_)) = expr {
+            self.count += 1;
+            *expr = Expr::Value(if self.numbered {
+                Value::Placeholder(format!("${}", self.count))
+            } else {
+                Value::SingleQuotedString("1".to_string())
+            });
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Explains generated list queries against the customer's database
+pub struct QueryPlanAdvisor;
+
+impl QueryPlanAdvisor {
+    /// Advisory `Note:` lines for full scans, or a `Note:` when the check
+    /// could not run
+    pub async fn check(config: &QueryPlanConfig, mapper_xml: &str) -> Vec<String> {
+        match tokio::time::timeout(config.timeout, Self::advise(config, mapper_xml)).await {
+            Ok(Ok(findings)) => findings,
+            Ok(Err(e)) => vec![format!("Note: Query plan check skipped: {}", e)],
+            Err(_) => vec![format!(
+                "Note: Query plan check skipped: no answer within {}s",
+                config.timeout.as_secs()
+            )],
+        }
+    }
+
+    async fn advise(config: &QueryPlanConfig, mapper_xml: &str) -> Result<Vec<String>> {
+        let backend = config.backend()?;
+        let variants = Self::variants(mapper_xml, backend);
+        if variants.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut options = ConnectOptions::new(config.url.clone());
+        options.max_connections(1).connect_timeout(config.timeout).sqlx_logging(false);
+        let db = Database::connect(options)
+            .await
+            .map_err(|e| anyhow!("cannot connect to the customer database: {}", e))?;
+
+        let result = async {
+            let txn = db.begin_with_config(None, Some(AccessMode::ReadOnly)).await?;
+            if backend == DatabaseBackend::Postgres {
+                txn.execute_unprepared("SET LOCAL enable_seqscan = off").await?;
+                txn.execute_unprepared("SET LOCAL plan_cache_mode = force_generic_plan").await?;
+            }
+
+            let mut findings = Vec::new();
+            for (i, variant) in variants.iter().enumerate() {
+                let explain = match backend {
+                    DatabaseBackend::Postgres => {
+                        let name = format!("codegen_plan_{}", i);
+                        txn.execute_unprepared(&format!("PREPARE {} AS {}", name, variant.sql)).await?;
+                        let args = vec!["NULL"; variant.params].join(", ");
+                        let args = if args.is_empty() { String::new() } else { format!("({})", args) };
+                        format!("EXPLAIN (FORMAT JSON) EXECUTE {}{}", name, args)
+                    }
+                    _ => format!("EXPLAIN FORMAT=JSON {}", variant.sql),
+                };
+                let rows = txn.query_all(Statement::from_string(backend, explain)).await?;
+                let row = rows.first().ok_or_else(|| anyhow!("EXPLAIN returned no plan"))?;
+                // PostgreSQL returns a json column, MySQL JSON text
+                let plan: Json = match backend {
+                    DatabaseBackend::Postgres => row.try_get_by_index(0)?,
+                    _ => serde_json::from_str(&row.try_get_by_index::<String>(0)?)?,
+                };
+
+                let mut scans = Vec::new();
+                Self::full_scans(&plan, backend, &mut scans);
+                scans.dedup();
+                for table in scans {
+                    findings.push(Self::finding(variant, &table));
+                }
+            }
+            txn.rollback().await?;
+            Ok::<_, anyhow::Error>(findings)
+        }
+        .await
+        .map_err(|e| anyhow!("EXPLAIN of '{}' failed: {}", LIST_STATEMENT, e));
+
+        let _ = db.close().await;
+        result
+    }
+
+    /// One query per search condition; the statement itself when it has none
+    fn variants(mapper_xml: &str, backend: DatabaseBackend) -> Vec<Variant> {
+        let (dialect, parser_dialect): (DbDialect, Box<dyn Dialect>) = match backend {
+            DatabaseBackend::Postgres => (DbDialect::Postgres, Box::new(PostgreSqlDialect {})),
+            _ => (DbDialect::Mysql, Box::new(MySqlDialect {})),
+        };
+
+        let conditions = MyBatisSqlChecker::condition_variants(mapper_xml, LIST_STATEMENT, Some(dialect));
+        let queries: Vec<(Option<String>, String)> = if conditions.is_empty() {
+            MyBatisSqlChecker::statement_sql(mapper_xml, LIST_STATEMENT, Some(dialect))
+                .map(|sql| (None, sql))
+                .into_iter()
+                .collect()
+        } else {
+            conditions.into_iter().map(|c| (Some(c.test), c.sql)).collect()
+        };
+
+        // Unparseable SQL is already reported by MyBatisSqlChecker
+        queries
+            .into_iter()
+            .filter_map(|(condition, sql)| {
+                let mut statements = Parser::parse_sql(parser_dialect.as_ref(), &sql).ok()?;
+                if statements.len() != 1 {
+                    return None;
+                }
+                let mut params = Parameters {
+                    numbered: backend == DatabaseBackend::Postgres,
+                    count: 0,
+                };
+                let _ = statements.visit(&mut params);
+                Some(Variant {
+                    condition,
+                    sql: statements[0].to_string(),
+                    params: params.count,
+                })
+            })
+            .collect()
+    }
+
+    /// Tables the plan reads in full
+    fn full_scans(node: &Json, backend: DatabaseBackend, scans: &mut Vec<String>) {
+        match node {
+            Json::Object(map) => {
+                match backend {
+                    DatabaseBackend::Postgres => {
+                        let node_type = map.get("Node Type").and_then(Json::as_str).unwrap_or_default();
+                        let full = node_type == "Seq Scan"
+                            || (node_type.starts_with("Index") && !map.contains_key("Index Cond"));
+                        if let (true, Some(table)) = (full, map.get("Relation Name").and_then(Json::as_str)) {
+                            scans.push(table.to_string());
+                        }
+                    }
+                    _ => {
+                        let access = map.get("access_type").and_then(Json::as_str).unwrap_or_default();
+                        let full = (access == "ALL" || access == "index") && !map.contains_key("possible_keys");
+                        if let (true, Some(table)) = (full, map.get("table_name").and_then(Json::as_str)) {
+                            scans.push(table.to_string());
+                        }
+                    }
+                }
+                map.values().for_each(|v| Self::full_scans(v, backend, scans));
+            }
+            Json::Array(items) => items.iter().for_each(|v| Self::full_scans(v, backend, scans)),
+            _ => {}
+        }
+    }
+
+    fn finding(variant: &Variant, table: &str) -> String {
+        let upper = variant.sql.to_uppercase();
+        let hint = if upper.contains("LIKE '%") || upper.contains("LIKE CONCAT('%'") || upper.contains("'%' ||") {
+            "; a leading-wildcard LIKE can't use a B-tree index"
+        } else {
+            ""
+        };
+        match &variant.condition {
+            Some(test) => format!(
+                "Note: Query plan: '{}' with [{}] reads all of {} (no index serves the condition{})",
+                LIST_STATEMENT, test, table, hint
+            ),
+            None => format!("Note: Query plan: '{}' reads all of {}{}", LIST_STATEMENT, table, hint),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE mapper PUBLIC "-//mybatis.org//DTD Mapper 3.0//EN" "http://mybatis.org/dtd/mybatis-3-mapper.dtd">
+<mapper namespace="com.company.project.mapper.MemberMapper">
+    <select id="selectList" resultType="MemberDTO">
+        SELECT MEMBER_ID, MEMBER_NAME FROM TB_MEMBER
+        <where>
+            <if test="memberName != null">AND MEMBER_NAME LIKE CONCAT('%', #{memberName}, '%')</if>
+            <if test="email != null">AND EMAIL = #{email}</if>
+        </where>
+        ORDER BY MEMBER_ID
+        LIMIT #{offset}, #{size}
+    </select>
+</mapper>
+"#;
+
+    #[test]
+    fn test_variants_per_condition() {
+        let variants = QueryPlanAdvisor::variants(MAPPER, DatabaseBackend::MySql);
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].condition.as_deref(), Some("memberName != null"));
+        assert!(variants[0].sql.contains("WHERE MEMBER_NAME LIKE CONCAT('%', '1', '%')"), "{}", variants[0].sql);
+        assert!(!variants[0].sql.contains("EMAIL"));
+        assert!(variants[0].sql.contains("LIMIT 10"), "{}", variants[0].sql);
+
+        let postgres = MAPPER.replace("LIMIT #{offset}, #{size}", "LIMIT #{size} OFFSET #{offset}");
+        let variants = QueryPlanAdvisor::variants(&postgres, DatabaseBackend::Postgres);
+        assert!(variants[1].sql.contains("WHERE EMAIL = $1"), "{}", variants[1].sql);
+        assert!(variants[1].sql.ends_with("LIMIT $2 OFFSET $3"), "{}", variants[1].sql);
+        assert_eq!(variants[1].params, 3);
+    }
+
+    #[test]
+    fn test_full_scans_from_plans() {
+        let postgres: Json = serde_json::from_str(
+            r#"[{"Plan": {"Node Type": "Limit", "Plans": [
+                {"Node Type": "Index Scan", "Relation Name": "tb_member", "Index Name": "tb_member_pkey", "Filter": "(email = $1)"},
+                {"Node Type": "Index Scan", "Relation Name": "tb_dept", "Index Cond": "(dept_id = $2)"}
+            ]}}]"#,
+        )
+        .unwrap();
+        let mut scans = Vec::new();
+        QueryPlanAdvisor::full_scans(&postgres, DatabaseBackend::Postgres, &mut scans);
+        assert_eq!(scans, vec!["tb_member"]);
+
+        let mysql: Json = serde_json::from_str(
+            r#"{"query_block": {"ordering_operation": {"table": {"table_name": "TB_MEMBER", "access_type": "ALL"}}}}"#,
+        )
+        .unwrap();
+        let mut scans = Vec::new();
+        QueryPlanAdvisor::full_scans(&mysql, DatabaseBackend::MySql, &mut scans);
+        assert_eq!(scans, vec!["TB_MEMBER"]);
+
+        let variant = Variant {
+            condition: Some("memberName != null".to_string()),
+            sql: "SELECT * FROM TB_MEMBER WHERE MEMBER_NAME LIKE CONCAT('%', '1', '%')".to_string(),
+            params: 0,
+        };
+        assert_eq!(
+            QueryPlanAdvisor::finding(&variant, "TB_MEMBER"),
+            "Note: Query plan: 'selectList' with [memberName != null] reads all of TB_MEMBER (no index serves the condition; a leading-wildcard LIKE can't use a B-tree index)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_database() {
+        let config = QueryPlanConfig {
+            url: "oracle://scott@db:1521/ORCL".to_string(),
+            timeout: Duration::from_secs(1),
+        };
+        assert_eq!(
+            QueryPlanAdvisor::check(&config, MAPPER).await,
+            vec!["Note: Query plan check skipped: 'oracle' databases are not supported (PostgreSQL and MySQL only)"]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Race Mode
+//!
+//! Sends one prompt to two backends at once (typically the local model and a
+//! remote provider). The first output that passes validation wins; the other
+//! request is dropped, which cancels it. Results are stored in `race_results`
+//! so routing can skip the race once one side keeps winning.
+
+use anyhow::{anyhow, Result};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use std::time::Instant;
+
+use crate::llm::{GenParams, LlmBackend, LlmUsage};
+use crate::models::race_results;
+
+/// Which backend produced the winning output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceSide {
+    Primary,
+    Partner,
+}
+
+/// Winning output of a race
+#[derive(Debug)]
+pub struct RaceOutcome<T> {
+    pub value: T,
+    pub winner: RaceSide,
+    pub winner_ms: u64,
+    /// Tokens and latency of the winning call
+    pub usage: LlmUsage,
+    /// `cancelled` if the other side was still running, `failed` otherwise
+    pub loser_outcome: &'static str,
+}
+
+/// Runs and records provider races
+pub struct RaceRunner;
+
+impl RaceRunner {
+    /// Race `prompt` on both backends; `accept` validates a raw output
+    pub async fn run<T, F>(
+        primary: &dyn LlmBackend,
+        partner: &dyn LlmBackend,
+        prompt: &str,
+        params: &GenParams,
+        accept: F,
+    ) -> Result<RaceOutcome<T>>
+    where
+        F: Fn(String) -> Result<T>,
+    {
+        let start = Instant::now();
+        let primary_run = Self::attempt(primary, prompt, params, &accept);
+        let partner_run = Self::attempt(partner, prompt, params, &accept);
+        tokio::pin!(primary_run, partner_run);
+
+        let mut primary_err: Option<anyhow::Error> = None;
+        let mut partner_err: Option<anyhow::Error> = None;
+        loop {
+            let (side, result) = tokio::select! {
+                result = &mut primary_run, if primary_err.is_none() => (RaceSide::Primary, result),
+                result = &mut partner_run, if partner_err.is_none() => (RaceSide::Partner, result),
+                else => break,
+            };
+            match result {
+                Ok((value, usage)) => {
+                    let other_failed = match side {
+                        RaceSide::Primary => partner_err.is_some(),
+                        RaceSide::Partner => primary_err.is_some(),
+                    };
+                    return Ok(RaceOutcome {
+                        value,
+                        winner: side,
+                        winner_ms: start.elapsed().as_millis() as u64,
+                        usage,
+                        loser_outcome: if other_failed { "failed" } else { "cancelled" },
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!("Race {:?} output rejected: {}", side, e);
+                    match side {
+                        RaceSide::Primary => primary_err = Some(e),
+                        RaceSide::Partner => partner_err = Some(e),
+                    }
+                }
+            }
+        }
+
+        Err(anyhow!(
+            "No output passed validation in race mode: {}",
+            primary_err.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    }
+
+    async fn attempt<T, F>(
+        backend: &dyn LlmBackend,
+        prompt: &str,
+        params: &GenParams,
+        accept: &F,
+    ) -> Result<(T, LlmUsage)>
+    where
+        F: Fn(String) -> Result<T>,
+    {
+        let output = backend.generate_with(prompt, params).await?;
+        let usage = LlmUsage::of(&output);
+        accept(output.text).map(|value| (value, usage))
+    }
+
+    /// Store who won (internal audit, never exposed via the API)
+    pub async fn record<T>(
+        db: &DatabaseConnection,
+        product: &str,
+        primary: &dyn LlmBackend,
+        partner: &dyn LlmBackend,
+        outcome: &RaceOutcome<T>,
+    ) {
+        let (winner, loser) = match outcome.winner {
+            RaceSide::Primary => (primary, partner),
+            RaceSide::Partner => (partner, primary),
+        };
+        let result = race_results::ActiveModel {
+            product: Set(product.to_string()),
+            winner_provider: Set(winner.name().to_string()),
+            winner_model: Set(winner.model().to_string()),
+            loser_provider: Set(loser.name().to_string()),
+            loser_model: Set(loser.model().to_string()),
+            loser_outcome: Set(outcome.loser_outcome.to_string()),
+            winner_ms: Set(outcome.winner_ms.min(i32::MAX as u64) as i32),
+            ..Default::default()
+        }
+        .insert(db)
+        .await;
+
+        if let Err(e) = result {
+            tracing::error!("Failed to record race result: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::GenerationOutput;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    struct DelayedBackend {
+        delay_ms: u64,
+        output: &'static str,
+        finished: Arc<AtomicBool>,
+    }
+
+    impl DelayedBackend {
+        fn new(delay_ms: u64, output: &'static str) -> Self {
+            Self { delay_ms, output, finished: Arc::new(AtomicBool::new(false)) }
+        }
+    }
+
+    #[async_trait]
+    impl LlmBackend for DelayedBackend {
+        fn name(&self) -> &str {
+            "delayed"
+        }
+
+        fn model(&self) -> &str {
+            self.output
+        }
+
+        async fn generate_with(&self, _prompt: &str, _params: &GenParams) -> Result<GenerationOutput> {
+            tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+            self.finished.store(true, Ordering::SeqCst);
+            Ok(GenerationOutput {
+                text: self.output.to_string(),
+                prompt_tokens: Some(100),
+                completion_tokens: Some(self.output.len() as u32),
+                latency_ms: self.delay_ms,
+            })
+        }
+
+        async fn health_check(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn accept(raw: String) -> Result<String> {
+        if raw.starts_with("valid") {
+            Ok(raw)
+        } else {
+            Err(anyhow!("invalid output"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_faster_valid_output_wins() {
+        let local = DelayedBackend::new(200, "valid-local");
+        let remote = DelayedBackend::new(10, "valid-remote");
+        let outcome = RaceRunner::run(&local, &remote, "prompt", &GenParams::default(), accept).await.unwrap();
+        assert_eq!(outcome.winner, RaceSide::Partner);
+        assert_eq!(outcome.value, "valid-remote");
+        assert_eq!(outcome.usage.completion_tokens, Some(12));
+        assert_eq!(outcome.loser_outcome, "cancelled");
+        // The slower request was dropped before it finished
+        assert!(!local.finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_output_loses() {
+        let local = DelayedBackend::new(50, "valid-local");
+        let remote = DelayedBackend::new(10, "garbage");
+        let outcome = RaceRunner::run(&local, &remote, "prompt", &GenParams::default(), accept).await.unwrap();
+        assert_eq!(outcome.winner, RaceSide::Primary);
+        assert_eq!(outcome.loser_outcome, "failed");
+    }
+
+    #[tokio::test]
+    async fn test_both_invalid() {
+        let local = DelayedBackend::new(10, "garbage");
+        let remote = DelayedBackend::new(10, "garbage");
+        assert!(RaceRunner::run(&local, &remote, "prompt", &GenParams::default(), accept).await.is_err());
+    }
+}
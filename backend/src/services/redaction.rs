@@ -0,0 +1,393 @@
+//! Response Redaction
+//!
+//! The plugin must never learn which LLM serves it, yet provider errors
+//! (`OpenAI request failed (429 Too Many Requests): {"error": ...}`) can end
+//! up in warnings and error descriptions, and models sometimes name
+//! themselves in generated comments. The redactor replaces provider names,
+//! model names, LLM endpoints, API keys and provider error bodies with
+//! `[redacted]`; `middleware::redaction` runs it over every response outside
+//! the admin surfaces.
+//!
+//! Only the prose of a response (errors, warnings, meta) is scrubbed.
+//! Generated artifacts are left alone: a learned model name can be an
+//! ordinary identifier, and replacing it would break the generated code.
+//!
+//! Model names and endpoints of configured backends are learned when a backend
+//! is created from an LLM config, so configs added in the admin panel are
+//! covered without listing them.
+//!
+//! Environment variables:
+//! - RESPONSE_REDACTION: `off` disables scrubbing (default: on)
+//! - RESPONSE_REDACTION_TERMS: extra comma-separated terms to scrub
+//! - RESPONSE_REDACTION_EXEMPT: comma-separated path prefixes left untouched
+//!   (default: `/admin,/api/llm_configs,/api/generation_logs`)
+
+use std::sync::{OnceLock, RwLock};
+
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+/// What scrubbed text is replaced with
+pub const REDACTED: &str = "[redacted]";
+
+/// Provider names as they appear in backend names and error messages
+const PROVIDERS: &[&str] = &[
+    "ollama",
+    "openai",
+    "open ai",
+    "anthropic",
+    "groq",
+    "vllm",
+    "llama.cpp",
+    "llama-cpp",
+    "local-llama-cpp",
+    "llama_cpp",
+    "huggingface",
+    "hugging face",
+];
+
+/// Default endpoints of the providers
+const PROVIDER_HOSTS: &[&str] = &[
+    "api.openai.com",
+    "api.anthropic.com",
+    "api.groq.com",
+    "localhost:11434",
+    "127.0.0.1:11434",
+];
+
+/// Admin surfaces, where LLM details are the payload
+const DEFAULT_EXEMPT: &str = "/admin,/api/llm_configs,/api/generation_logs";
+
+/// Shorter learned terms are ignored (they would scrub ordinary words)
+const MIN_TERM_CHARS: usize = 3;
+
+/// JSON fields holding generated code, which is never scrubbed
+const ARTIFACT_FIELDS: &[&str] = &["artifacts"];
+
+/// Redaction settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    /// Terms scrubbed besides the provider names
+    pub terms: Vec<String>,
+    /// LLM endpoints (URLs or host:port)
+    pub endpoints: Vec<String>,
+    /// Path prefixes whose responses are left untouched
+    pub exempt: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            terms: Vec::new(),
+            endpoints: Vec::new(),
+            exempt: split_list(DEFAULT_EXEMPT),
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Settings from the environment, including the env-configured backend's
+    /// model and endpoint
+    pub fn from_env() -> Self {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        let mut terms = env("RESPONSE_REDACTION_TERMS").map(|v| split_list(&v)).unwrap_or_default();
+        terms.extend(env("LLM_MODEL"));
+        terms.extend(env("LLM_MODEL_PATH").and_then(|path| {
+            std::path::Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        }));
+
+        Self {
+            enabled: !env("RESPONSE_REDACTION").is_some_and(|v| {
+                matches!(v.trim().to_lowercase().as_str(), "off" | "false" | "0")
+            }),
+            terms,
+            endpoints: env("LLM_ENDPOINT").into_iter().collect(),
+            exempt: split_list(&env("RESPONSE_REDACTION_EXEMPT").unwrap_or_else(|| DEFAULT_EXEMPT.to_string())),
+        }
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Learned terms and their compiled pattern
+struct Terms {
+    terms: Vec<String>,
+    /// Lowercase host[:port] of every known endpoint
+    hosts: Vec<String>,
+    pattern: Regex,
+}
+
+impl Terms {
+    fn add_term(&mut self, term: &str) -> bool {
+        let term = term.trim();
+        if term.chars().count() < MIN_TERM_CHARS || self.terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+            return false;
+        }
+        self.terms.push(term.to_string());
+        true
+    }
+
+    fn add_endpoint(&mut self, endpoint: &str) -> bool {
+        let Some(host) = endpoint_host(endpoint) else {
+            return false;
+        };
+        let added = !self.hosts.contains(&host);
+        if added {
+            self.hosts.push(host.clone());
+        }
+        self.add_term(&host) || added
+    }
+
+    fn compile(&mut self) {
+        let mut terms = self.terms.clone();
+        terms.sort_by_key(|t| std::cmp::Reverse(t.len()));
+        let alternatives: Vec<String> = terms.iter().map(|t| bounded(t)).collect();
+        self.pattern = Regex::new(&format!("(?i)(?:{})", alternatives.join("|")))
+            .expect("escaped redaction terms form a valid pattern");
+    }
+}
+
+/// `term` escaped, with word boundaries on its word-character edges
+fn bounded(term: &str) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    format!(
+        "{}{}{}",
+        if is_word(term.chars().next()) { r"\b" } else { "" },
+        regex::escape(term),
+        if is_word(term.chars().last()) { r"\b" } else { "" },
+    )
+}
+
+/// Lowercase `host[:port]` of an endpoint URL or bare host
+fn endpoint_host(endpoint: &str) -> Option<String> {
+    let rest = endpoint.trim();
+    let rest = rest.split_once("://").map_or(rest, |(_, r)| r);
+    let host = rest.split(['/', '?', '#']).next()?.rsplit('@').next()?;
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// Patterns scrubbed regardless of configuration
+struct FixedPatterns {
+    /// `<Provider> request failed (status): <body>`, as the LLM backends
+    /// phrase their errors; everything after the colon is the provider's
+    error_body: Regex,
+    url: Regex,
+    api_key: Regex,
+    model_family: Regex,
+}
+
+fn fixed_patterns() -> &'static FixedPatterns {
+    static PATTERNS: OnceLock<FixedPatterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| FixedPatterns {
+        error_body: Regex::new(
+            r"(?is)\b((?:ollama|openai|anthropic|groq|vllm|llama\.cpp)\s+(?:request|stream|pull)\s+failed(?:\s*\([^)]*\))?)\s*:.*",
+        )
+        .unwrap(),
+        url: Regex::new(r#"(?i)\bhttps?://[^\s"'<>()\[\]]+"#).unwrap(),
+        api_key: Regex::new(r"\b(?:sk-[A-Za-z0-9_-]{16,}|gsk_[A-Za-z0-9]{16,}|hf_[A-Za-z0-9]{16,})").unwrap(),
+        model_family: Regex::new(
+            r"(?i)\b(?:gpt-?\d[\w.:-]*|claude-[\w.:-]+|codellama[\w.:-]*|llama-?\d[\w.:-]*|qwen[\d.]*(?:-[\w.:]+)?|mistral[\w.:-]*|mixtral[\w.:-]*|deepseek[\w.:-]*|gemma[\w.:-]*|starcoder[\w.:-]*|[\w.-]+\.gguf)\b",
+        )
+        .unwrap(),
+    })
+}
+
+/// Scrubs LLM details from user-facing text
+pub struct Redactor {
+    config: RedactionConfig,
+    terms: RwLock<Terms>,
+}
+
+static REDACTOR: OnceLock<Redactor> = OnceLock::new();
+
+/// Process-wide redactor (configured from env on first use)
+pub fn redactor() -> &'static Redactor {
+    REDACTOR.get_or_init(|| Redactor::new(RedactionConfig::from_env()))
+}
+
+impl Redactor {
+    pub fn new(config: RedactionConfig) -> Self {
+        let mut terms = Terms {
+            terms: Vec::new(),
+            hosts: Vec::new(),
+            pattern: Regex::new("$^").unwrap(),
+        };
+        for term in PROVIDERS.iter().copied().chain(config.terms.iter().map(String::as_str)) {
+            terms.add_term(term);
+        }
+        for endpoint in PROVIDER_HOSTS.iter().copied().chain(config.endpoints.iter().map(String::as_str)) {
+            terms.add_endpoint(endpoint);
+        }
+        terms.compile();
+
+        Self {
+            config,
+            terms: RwLock::new(terms),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Whether responses to `path` are scrubbed
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.config.enabled && !self.config.exempt.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// Learn the model name and endpoint of a configured backend
+    pub fn remember(&self, model: &str, endpoint: Option<&str>) {
+        let mut terms = self.terms.write().unwrap_or_else(|e| e.into_inner());
+        let added = terms.add_term(model) | endpoint.is_some_and(|e| terms.add_endpoint(e));
+        if added {
+            terms.compile();
+        }
+    }
+
+    /// `text` with every LLM detail replaced
+    pub fn scrub_text(&self, text: &str) -> String {
+        let patterns = fixed_patterns();
+        let terms = self.terms.read().unwrap_or_else(|e| e.into_inner());
+
+        let text = patterns.error_body.replace_all(text, "$1");
+        let text = patterns.url.replace_all(&text, |caps: &Captures| {
+            let url = caps[0].to_lowercase();
+            if terms.hosts.iter().any(|host| url.contains(host.as_str())) {
+                REDACTED.to_string()
+            } else {
+                caps[0].to_string()
+            }
+        });
+        let text = patterns.api_key.replace_all(&text, REDACTED);
+        let text = terms.pattern.replace_all(&text, REDACTED);
+        patterns.model_family.replace_all(&text, REDACTED).into_owned()
+    }
+
+    /// Scrub every string in `value` outside artifact fields; returns whether
+    /// anything changed
+    pub fn scrub_json(&self, value: &mut Value) -> bool {
+        match value {
+            Value::String(text) => {
+                let scrubbed = self.scrub_text(text);
+                let changed = scrubbed != *text;
+                *text = scrubbed;
+                changed
+            }
+            Value::Array(items) => items.iter_mut().fold(false, |changed, item| self.scrub_json(item) | changed),
+            Value::Object(fields) => fields
+                .iter_mut()
+                .filter(|(key, _)| !ARTIFACT_FIELDS.contains(&key.as_str()))
+                .fold(false, |changed, (_, field)| self.scrub_json(field) | changed),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn redactor() -> Redactor {
+        Redactor::new(RedactionConfig {
+            terms: vec!["acme-coder-13b".to_string()],
+            endpoints: vec!["http://gpu-01.internal:8000/v1".to_string()],
+            ..RedactionConfig::default()
+        })
+    }
+
+    /// No provider, model, endpoint, key or provider error text survives
+    fn assert_clean(text: &str) {
+        let lower = text.to_lowercase();
+        for leak in [
+            "openai", "anthropic", "ollama", "groq", "vllm", "llama.cpp", "gpt-4o", "claude", "codellama",
+            "acme-coder", "gpu-01", "11434", "sk-", "rate_limit", "quota",
+        ] {
+            assert!(!lower.contains(leak), "'{}' leaked in: {}", leak, text);
+        }
+    }
+
+    #[test]
+    fn test_provider_errors_are_scrubbed() {
+        let errors = [
+            r#"OpenAI request failed (429 Too Many Requests): {"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "code": "rate_limit"}}"#,
+            "Anthropic stream failed: Overloaded; model claude-3-5-sonnet-20241022 unavailable",
+            "Ollama request failed (404 Not Found): {\n  \"error\": \"model 'codellama:13b' not found\"\n}",
+            "error sending request for url (http://localhost:11434/api/generate): connection refused",
+            "vLLM request failed (500): acme-coder-13b crashed on http://gpu-01.internal:8000/v1/completions",
+            "Groq API not available or invalid API key sk-proj-abcdefghijklmnop1234",
+            "Model file not found: \"llm-models/gpt-4o-distill.Q4_K_M.gguf\"",
+        ];
+        for error in errors {
+            assert_clean(&redactor().scrub_text(error));
+        }
+
+        assert_eq!(
+            redactor().scrub_text(errors[0]),
+            "[redacted] request failed (429 Too Many Requests)"
+        );
+    }
+
+    #[test]
+    fn test_ordinary_text_is_untouched() {
+        let text = "Warning: Field 'memberName' not found in DTO; see https://docs.example.com/xframe5";
+        assert_eq!(redactor().scrub_text(text), text);
+        let code = "function fn_search() { ds_member.load(\"/api/member/list\"); }";
+        assert_eq!(redactor().scrub_text(code), code);
+    }
+
+    #[test]
+    fn test_scrub_json_and_learned_terms() {
+        let redactor = redactor();
+        redactor.remember("team-model-v2", Some("https://llm.corp.example:9443/v1"));
+
+        let mut response = json!({
+            "status": "success",
+            "warnings": ["LLM retry: OpenAI request failed (503): upstream busy", "team-model-v2 timed out"],
+            "error": "Generation failed: https://llm.corp.example:9443/v1/chat refused",
+            "meta": {"generator": "xframe5-ui-v1", "elapsed_ms": 1200},
+        });
+        assert!(redactor.scrub_json(&mut response));
+        let text = response.to_string();
+        assert_clean(&text);
+        assert!(!text.contains("team-model") && !text.contains("llm.corp"));
+        assert_eq!(response["meta"]["generator"], "xframe5-ui-v1");
+
+        // A learned name that is also an identifier in the generated code
+        redactor.remember("grid", None);
+        let artifacts = json!({"javascript": "grid.refresh(); // generated by team-model-v2"});
+        let mut response = json!({"status": "success", "artifacts": artifacts.clone(), "warnings": []});
+        assert!(!redactor.scrub_json(&mut response));
+        assert_eq!(response["artifacts"], artifacts);
+
+        let mut clean = json!({"status": "success", "warnings": []});
+        assert!(!redactor.scrub_json(&mut clean));
+    }
+
+    #[test]
+    fn test_exempt_paths() {
+        let redactor = redactor();
+        assert!(redactor.applies_to("/agent/generate"));
+        assert!(redactor.applies_to("/api/my/generations/3"));
+        assert!(!redactor.applies_to("/admin/llm-configs"));
+        assert!(!redactor.applies_to("/api/llm_configs/1"));
+
+        let off = Redactor::new(RedactionConfig {
+            enabled: false,
+            ..RedactionConfig::default()
+        });
+        assert!(!off.applies_to("/agent/generate"));
+    }
+}
@@ -0,0 +1,218 @@
+//! Redis Client
+//!
+//! Minimal RESP client shared by the lookup cache and the job queue. It
+//! speaks plain commands over one TCP connection, so no Redis client stack is
+//! pulled in. Cache lookups and queue claims are small and generations are
+//! LLM-bound, so a single connection behind a mutex is plenty.
+//!
+//! Every failure (connect, timeout, error reply) is reported as `Err` and
+//! drops the connection; the next command reconnects.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+/// Longest a command may take
+const COMMAND_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// RESP reply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Simple(String),
+    Integer(i64),
+    Bulk(Option<String>),
+    Array(Vec<Reply>),
+}
+
+impl Reply {
+    /// Bulk string value (`None` for nil or other reply types)
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Self::Bulk(value) => value,
+            Self::Simple(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Elements of an array reply (empty for other reply types)
+    pub fn into_array(self) -> Vec<Reply> {
+        match self {
+            Self::Array(items) => items,
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Connection settings and the shared connection
+pub struct RedisClient {
+    address: String,
+    username: Option<String>,
+    password: Option<String>,
+    database: u32,
+    conn: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl RedisClient {
+    /// Parse `redis://[user][:password@]host[:port][/db]` (connects lazily)
+    pub fn from_url(url: &str) -> anyhow::Result<Self> {
+        let url = reqwest::Url::parse(url.trim()).context("invalid URL")?;
+        if url.scheme() != "redis" {
+            bail!("only redis:// URLs are supported");
+        }
+        let host = url.host_str().ok_or_else(|| anyhow!("missing host"))?;
+        let decode = |s: &str| urlencoding::decode(s).map(|s| s.into_owned()).unwrap_or_else(|_| s.to_string());
+        let database = match url.path().trim_start_matches('/') {
+            "" => 0,
+            db => db.parse().context("database must be a number")?,
+        };
+
+        Ok(Self {
+            address: format!("{}:{}", host, url.port().unwrap_or(6379)),
+            username: Some(decode(url.username())).filter(|u| !u.is_empty()),
+            password: url.password().map(decode),
+            database,
+            conn: Mutex::new(None),
+        })
+    }
+
+    /// Run a command
+    pub async fn command(&self, args: &[&str]) -> anyhow::Result<Reply> {
+        let mut conn = self.conn.lock().await;
+        let result = match tokio::time::timeout(COMMAND_TIMEOUT, self.run(&mut conn, args)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("timed out")),
+        };
+        if result.is_err() {
+            *conn = None;
+        }
+        result.with_context(|| format!("Redis {}", args.first().copied().unwrap_or_default()))
+    }
+
+    async fn run(&self, conn: &mut Option<BufReader<TcpStream>>, args: &[&str]) -> anyhow::Result<Reply> {
+        if conn.is_none() {
+            *conn = Some(self.connect().await?);
+        }
+        let stream = conn.as_mut().ok_or_else(|| anyhow!("not connected"))?;
+        send(stream, args).await
+    }
+
+    async fn connect(&self) -> anyhow::Result<BufReader<TcpStream>> {
+        let mut stream = BufReader::new(TcpStream::connect(&self.address).await?);
+
+        if let Some(password) = &self.password {
+            let mut auth = vec!["AUTH"];
+            if let Some(username) = &self.username {
+                auth.push(username);
+            }
+            auth.push(password);
+            expect_ok(send(&mut stream, &auth).await?)?;
+        }
+        if self.database != 0 {
+            expect_ok(send(&mut stream, &["SELECT", &self.database.to_string()]).await?)?;
+        }
+
+        Ok(stream)
+    }
+}
+
+/// Encode a command as a RESP array of bulk strings
+fn encode(args: &[&str]) -> Vec<u8> {
+    let mut out = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        out.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        out.extend_from_slice(arg.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+fn expect_ok(reply: Reply) -> anyhow::Result<()> {
+    match reply {
+        Reply::Simple(status) if status == "OK" => Ok(()),
+        other => bail!("unexpected reply: {:?}", other),
+    }
+}
+
+/// Send a command and read its reply (Redis errors become `Err`)
+async fn send(stream: &mut BufReader<TcpStream>, args: &[&str]) -> anyhow::Result<Reply> {
+    stream.get_mut().write_all(&encode(args)).await?;
+    read_reply(stream).await
+}
+
+async fn read_reply<R: AsyncBufReadExt + Unpin + Send>(stream: &mut R) -> anyhow::Result<Reply> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await? == 0 {
+        bail!("connection closed");
+    }
+    let line = line.trim_end_matches("\r\n");
+    let rest = line.get(1..).unwrap_or_default();
+
+    match line.chars().next() {
+        Some('+') => Ok(Reply::Simple(rest.to_string())),
+        Some('-') => bail!("{}", rest),
+        Some(':') => Ok(Reply::Integer(rest.parse()?)),
+        Some('$') => {
+            let len: i64 = rest.parse()?;
+            if len < 0 {
+                return Ok(Reply::Bulk(None));
+            }
+            let mut data = vec![0u8; len as usize + 2];
+            stream.read_exact(&mut data).await?;
+            data.truncate(len as usize);
+            Ok(Reply::Bulk(Some(String::from_utf8(data)?)))
+        }
+        Some('*') => {
+            let len: i64 = rest.parse()?;
+            let mut items = Vec::with_capacity(len.max(0) as usize);
+            for _ in 0..len.max(0) {
+                items.push(Box::pin(read_reply(stream)).await?);
+            }
+            Ok(Reply::Array(items))
+        }
+        _ => bail!("unexpected reply: {}", line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_command() {
+        assert_eq!(
+            encode(&["SET", "k", "héllo"]),
+            b"*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$6\r\nh\xc3\xa9llo\r\n".to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_replies() {
+        let mut input: &[u8] =
+            b"+OK\r\n:42\r\n$5\r\nhello\r\n$-1\r\n*2\r\n$1\r\na\r\n:1\r\n-ERR wrong type\r\n";
+        let mut reader = BufReader::new(&mut input);
+        assert_eq!(read_reply(&mut reader).await.unwrap(), Reply::Simple("OK".to_string()));
+        assert_eq!(read_reply(&mut reader).await.unwrap(), Reply::Integer(42));
+        assert_eq!(read_reply(&mut reader).await.unwrap(), Reply::Bulk(Some("hello".to_string())));
+        assert_eq!(read_reply(&mut reader).await.unwrap(), Reply::Bulk(None));
+        assert_eq!(
+            read_reply(&mut reader).await.unwrap(),
+            Reply::Array(vec![Reply::Bulk(Some("a".to_string())), Reply::Integer(1)])
+        );
+        assert!(read_reply(&mut reader).await.is_err());
+    }
+
+    #[test]
+    fn test_parse_url() {
+        let client = RedisClient::from_url("redis://:s%40cret@cache.internal:6380/2").unwrap();
+        assert_eq!(client.address, "cache.internal:6380");
+        assert_eq!(client.username, None);
+        assert_eq!(client.password.as_deref(), Some("s@cret"));
+        assert_eq!(client.database, 2);
+
+        assert_eq!(RedisClient::from_url("redis://localhost").unwrap().address, "localhost:6379");
+        assert!(RedisClient::from_url("http://localhost").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Provider Refusals
+//!
+//! Remote providers occasionally decline a request ("I can't help with
+//! that") instead of generating code. Parsing such an answer fails with a
+//! confusing marker error, so the raw output is checked first: a short answer
+//! without output sections that reads like a refusal becomes a [`Refusal`].
+//!
+//! The generation is retried once with a sanitized prompt: the prompt is
+//! rendered again without the free-text notes of the request (where unusual
+//! wording usually comes from), and the business context is stated up front.
+//! A second refusal fails
+//! the generation with the `provider_refusal` error code, on the response and
+//! on the generation log.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Error code of a generation the provider refused
+pub const REFUSAL_ERROR_CODE: &str = "provider_refusal";
+
+/// Longer answers are treated as (broken) output, not refusals
+const MAX_REFUSAL_CHARS: usize = 1500;
+
+/// Characters of the answer kept for the log
+const EXCERPT_CHARS: usize = 200;
+
+/// Stated before the sanitized prompt
+const CONTEXT_PREAMBLE: &str = "Context: this is a routine request from a software team to generate source code \
+     for an internal business application (data entry and inquiry screens). Field and table names refer to \
+     ordinary business records.";
+
+/// Phrases providers refuse with
+fn patterns() -> &'static [Regex] {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            r"(?i)\bI\s*(?:can(?:no|['’])?t|won['’]?t|am unable to|['’]m unable to|am not able to|['’]m not able to)\s+(?:help|assist|comply|provide|generate|create|write|fulfill|fulfil)",
+            r"(?i)\bI['’]?m sorry,?\s+but\b",
+            r"(?i)\b(?:content|usage|safety) polic(?:y|ies)\b",
+            r"(?i)\bagainst (?:my|our|the) (?:guidelines|policies|policy)\b",
+            r"도와(?:드릴|줄) 수 없",
+            r"(?:요청|작업)을? (?:처리|수행)할 수 없",
+        ]
+        .iter()
+        .map(|p| Regex::new(p).unwrap())
+        .collect()
+    })
+}
+
+/// Section markers of generated output (`--- XML ---`, `--- MAPPER_XML ---`)
+fn output_marker() -> &'static Regex {
+    static MARKER: OnceLock<Regex> = OnceLock::new();
+    MARKER.get_or_init(|| Regex::new(r"(?m)^\s*---\s*[A-Z_]+\s*---").unwrap())
+}
+
+/// The provider declined to generate
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Refusal {
+    /// Start of the provider's answer
+    pub excerpt: String,
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The model declined the request: \"{}\"", self.excerpt)
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+impl Refusal {
+    /// Refusal in a raw LLM answer, if it is one
+    pub fn detect(output: &str) -> Option<Self> {
+        let output = output.trim();
+        if output.is_empty()
+            || output.chars().count() > MAX_REFUSAL_CHARS
+            || output.contains("```")
+            || output_marker().is_match(output)
+            || !patterns().iter().any(|p| p.is_match(output))
+        {
+            return None;
+        }
+
+        let first_line = output.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().trim();
+        let mut excerpt: String = first_line.chars().take(EXCERPT_CHARS).collect();
+        if first_line.chars().count() > EXCERPT_CHARS {
+            excerpt.push_str("...");
+        }
+        Some(Self { excerpt })
+    }
+
+    /// Prompt of the retry: the prompt rendered without the request notes
+    /// (see `CompiledPrompt::without_notes`), business context stated first
+    pub fn retry_prompt(sanitized: &str) -> String {
+        format!("{}\n\n{}", CONTEXT_PREAMBLE, sanitized)
+    }
+
+    /// Error returned to the caller after the retry was refused too
+    pub fn error_message() -> String {
+        "The model declined to generate this request, also after a retry without the request notes. \
+         Rephrase the description or notes and try again."
+            .to_string()
+    }
+
+    /// Warning of a generation that succeeded on the sanitized retry
+    pub fn retried_note() -> String {
+        "Note: The model declined the first request; it was retried without the request notes".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, UiIntent};
+    use crate::services::PromptCompiler;
+
+    #[test]
+    fn test_detect_refusals() {
+        let refusal = Refusal::detect("I'm sorry, but I can't help with that request.").unwrap();
+        assert_eq!(refusal.excerpt, "I'm sorry, but I can't help with that request.");
+        assert!(Refusal::detect("I cannot assist with generating this content as it may violate our usage policies.").is_some());
+        assert!(Refusal::detect("죄송하지만 해당 요청은 도와드릴 수 없습니다.").is_some());
+
+        // Output sections or code are never a refusal
+        assert!(Refusal::detect("--- XML ---\n<screen/>\n--- JS ---\n// I can't help it").is_none());
+        assert!(Refusal::detect("```xml\n<screen/>\n```\nI'm sorry, but the JS is missing").is_none());
+        assert!(Refusal::detect("Here is the screen you asked for.").is_none());
+    }
+
+    #[test]
+    fn test_retry_prompt_leaves_out_notes() {
+        let intent = UiIntent::new("Accounts", ScreenType::List).with_notes("Track overdue debtors");
+        let prompt = PromptCompiler::compile_with_defaults(&intent, None);
+        let retry = Refusal::retry_prompt(&prompt.without_notes().full());
+        assert!(retry.starts_with(CONTEXT_PREAMBLE));
+        assert!(!retry.contains("debtors") && !retry.contains("Additional notes"));
+    }
+
+    #[test]
+    fn test_retry_prompt_keeps_template_text_matching_notes() {
+        // Notes that also occur in the prompt template
+        let intent = UiIntent::new("Members", ScreenType::List).with_notes("xFrame5");
+        let prompt = PromptCompiler::compile_with_defaults(&intent, None);
+        let retry = Refusal::retry_prompt(&prompt.without_notes().full());
+
+        assert!(prompt.full().contains("Additional notes:\nxFrame5"));
+        assert!(!retry.contains("Additional notes"));
+        assert!(retry.contains("Generate an xFrame5 list screen"));
+        assert!(retry.contains("following xFrame5 patterns"));
+        assert!(!retry.contains("(omitted)"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Generation Replay
+//!
+//! Re-runs a logged generation to reproduce a reported problem: the recorded
+//! input and options, the template version the log was generated with and
+//! the LLM config matching its provider and model (or one picked by the
+//! admin). The new generation is logged with `replay_of` pointing back.
+//!
+//! Queued jobs keep the full request; synchronous generations only keep the
+//! normalized intent, which is replayed as `intent` input. Anything that
+//! can't be pinned any more (deleted template version or LLM config) is
+//! replaced by the active one and reported in the notes.
+
+use anyhow::{anyhow, bail, Result};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+
+use crate::domain::{GenerateInput, GenerateOptions, GenerateStatus, RequestContext, UiIntent};
+use crate::models::_entities::{generation_logs, llm_configs, prompt_templates};
+use crate::services::{GenerationPins, GenerationService, TenantScope};
+use crate::workers::generation::GenerateJobRequest;
+
+/// Result of a replay
+#[derive(Debug, Serialize)]
+pub struct ReplayOutcome {
+    /// Log of the new generation (`None` if it could not be written)
+    pub log_id: Option<i32>,
+    pub status: GenerateStatus,
+    /// What differs from the original run
+    pub notes: Vec<String>,
+}
+
+pub struct ReplayService;
+
+impl ReplayService {
+    /// Replay generation `log`, optionally with another LLM config
+    pub async fn replay(
+        db: &DatabaseConnection,
+        log: &generation_logs::Model,
+        llm_config_id: Option<i32>,
+    ) -> Result<ReplayOutcome> {
+        if log.product == "spring-backend" {
+            bail!("Replay supports screen generations only");
+        }
+
+        let (request, mut notes) = Self::recorded_request(log)?;
+        let tenant = TenantScope::new(log.company.as_deref());
+
+        let template = match Self::recorded_template(db, log, &tenant).await? {
+            Some(template) => Some(template),
+            None if log.template_version > 0 && log.ui_intent != "pending" => {
+                notes.push(format!(
+                    "Template v{} is no longer available; replayed with the active template",
+                    log.template_version
+                ));
+                None
+            }
+            None => None,
+        };
+
+        let llm_config = match llm_config_id {
+            Some(id) => Some(
+                llm_configs::Entity::find_by_id(id)
+                    .filter(tenant.usable(llm_configs::Column::Company))
+                    .one(db)
+                    .await?
+                    .ok_or_else(|| anyhow!("LLM config {} not found", id))?,
+            ),
+            None => {
+                let config = Self::recorded_llm_config(db, log, &tenant).await?;
+                if config.is_none() && log.provider.is_some() {
+                    notes.push("Recorded LLM config no longer exists; replayed with the active config".to_string());
+                }
+                config
+            }
+        };
+
+        let pins = GenerationPins {
+            template,
+            llm_config,
+            replay_of: Some(log.id),
+        };
+        let response = GenerationService::generate_pinned(
+            db,
+            request.input,
+            &request.product,
+            &request.options,
+            &request.context,
+            Some(log.user_id),
+            &pins,
+        )
+        .await?;
+
+        let log_id = generation_logs::Entity::find()
+            .filter(generation_logs::Column::ReplayOf.eq(log.id))
+            .order_by_desc(generation_logs::Column::Id)
+            .one(db)
+            .await?
+            .map(|l| l.id);
+
+        Ok(ReplayOutcome {
+            log_id,
+            status: response.status,
+            notes,
+        })
+    }
+
+    /// Request to replay: the queued job's payload, or the logged intent
+    fn recorded_request(log: &generation_logs::Model) -> Result<(GenerateJobRequest, Vec<String>)> {
+        let (mut request, mut notes) = Self::parse_recorded(log.request_payload.as_deref(), &log.ui_intent)
+            .ok_or_else(|| anyhow!("Generation {} has no recorded input", log.id))?;
+        request.product = log.product.clone();
+        if request.context.project.is_none() {
+            request.context.project = log.project.clone();
+        }
+        if request.options.pipeline_profile.is_none() {
+            request.options.pipeline_profile = log.pipeline_profile.clone();
+        }
+        // The pinned provider answers alone
+        if request.options.race {
+            request.options.race = false;
+            notes.push("Race mode is off for replays".to_string());
+        }
+        Ok((request, notes))
+    }
+
+    fn parse_recorded(payload: Option<&str>, ui_intent: &str) -> Option<(GenerateJobRequest, Vec<String>)> {
+        if let Some(request) = payload.and_then(|p| serde_json::from_str::<GenerateJobRequest>(p).ok()) {
+            let request = GenerateJobRequest {
+                trace_parent: None,
+                ..request
+            };
+            return Some((request, Vec::new()));
+        }
+
+        let intent: UiIntent = serde_json::from_str(ui_intent).ok()?;
+        let request = GenerateJobRequest {
+            product: String::new(),
+            input: GenerateInput::Intent(intent),
+            options: GenerateOptions::default(),
+            context: RequestContext::default(),
+            trace_parent: None,
+        };
+        let note = "Original input was not stored; replayed from the logged intent with default options";
+        Some((request, vec![note.to_string()]))
+    }
+
+    /// Template with the logged version for the logged screen type
+    async fn recorded_template(
+        db: &DatabaseConnection,
+        log: &generation_logs::Model,
+        tenant: &TenantScope,
+    ) -> Result<Option<prompt_templates::Model>> {
+        let Ok(intent) = serde_json::from_str::<UiIntent>(&log.ui_intent) else {
+            return Ok(None);
+        };
+        let query = prompt_templates::Entity::find()
+            .filter(prompt_templates::Column::Product.eq(&log.product))
+            .filter(prompt_templates::Column::ScreenType.eq(Some(intent.screen_type.as_str().to_string())))
+            .filter(prompt_templates::Column::Version.eq(log.template_version));
+        Ok(tenant
+            .prefer_own(query, prompt_templates::Column::Company)
+            .one(db)
+            .await?)
+    }
+
+    /// LLM config with the logged provider and model
+    async fn recorded_llm_config(
+        db: &DatabaseConnection,
+        log: &generation_logs::Model,
+        tenant: &TenantScope,
+    ) -> Result<Option<llm_configs::Model>> {
+        let (Some(provider), Some(model)) = (&log.provider, &log.model_name) else {
+            return Ok(None);
+        };
+        let query = llm_configs::Entity::find()
+            .filter(llm_configs::Column::Provider.eq(provider))
+            .filter(llm_configs::Column::ModelName.eq(model));
+        Ok(tenant
+            .prefer_own(query, llm_configs::Column::Company)
+            .order_by_desc(llm_configs::Column::IsActive)
+            .one(db)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScreenType;
+
+    fn intent_json() -> String {
+        serde_json::to_string(&UiIntent::new("member_list", ScreenType::List)).unwrap()
+    }
+
+    #[test]
+    fn test_replays_queued_payload() {
+        let payload = serde_json::to_string(&GenerateJobRequest {
+            product: "xframe5".to_string(),
+            input: GenerateInput::Intent(UiIntent::new("member_list", ScreenType::List)),
+            options: GenerateOptions {
+                strict_mode: true,
+                pipeline_profile: Some("poc".to_string()),
+                ..Default::default()
+            },
+            context: RequestContext {
+                project: Some("crm".to_string()),
+                ..Default::default()
+            },
+            trace_parent: Some("00-abc".to_string()),
+        })
+        .unwrap();
+
+        let (request, notes) = ReplayService::parse_recorded(Some(&payload), "pending").unwrap();
+        assert!(notes.is_empty());
+        assert!(request.trace_parent.is_none());
+        assert!(request.options.strict_mode);
+        assert_eq!(request.options.pipeline_profile.as_deref(), Some("poc"));
+        assert_eq!(request.context.project.as_deref(), Some("crm"));
+    }
+
+    #[test]
+    fn test_replays_logged_intent_without_payload() {
+        let (request, notes) = ReplayService::parse_recorded(None, &intent_json()).unwrap();
+        assert!(matches!(request.input, GenerateInput::Intent(ref i) if i.screen_name == "member_list"));
+        assert_eq!(notes.len(), 1);
+
+        assert!(ReplayService::parse_recorded(None, "pending").is_none());
+    }
+}
@@ -0,0 +1,247 @@
+//! Response Cache
+//!
+//! In-memory cache for Q&A and review responses. Q&A questions are matched
+//! by token-set similarity so rephrasings ("grid column width 설정" vs
+//! "how to set grid column width") reuse the same answer; reviews match on
+//! the exact prompt fingerprint.
+//!
+//! Entries expire after a TTL and the whole cache is dropped when the
+//! knowledge base revision changes.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::domain::{QAResponse, ReviewResponse};
+
+/// Cache tuning
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Entry lifetime
+    pub ttl: Duration,
+
+    /// Minimum token-set (Jaccard) similarity for a hit (1.0 = identical tokens)
+    pub similarity_threshold: f32,
+
+    /// Maximum entries kept (oldest are evicted first)
+    pub max_entries: usize,
+}
+
+impl CacheConfig {
+    /// Q&A: one hour, tolerant of rephrasing
+    pub fn qa() -> Self {
+        Self {
+            ttl: Duration::from_secs(60 * 60),
+            similarity_threshold: 0.8,
+            max_entries: 500,
+        }
+    }
+
+    /// Review: ten minutes, exact match only
+    pub fn review() -> Self {
+        Self {
+            ttl: Duration::from_secs(10 * 60),
+            similarity_threshold: 1.0,
+            max_entries: 200,
+        }
+    }
+}
+
+struct CacheEntry<T> {
+    fingerprint: u64,
+    tokens: BTreeSet<String>,
+    value: T,
+    created_at: Instant,
+}
+
+struct CacheState<T> {
+    kb_revision: Option<String>,
+    entries: Vec<CacheEntry<T>>,
+}
+
+/// Thread-safe response cache
+pub struct ResponseCache<T> {
+    config: CacheConfig,
+    state: Arc<RwLock<CacheState<T>>>,
+}
+
+impl<T: Clone> ResponseCache<T> {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(RwLock::new(CacheState {
+                kb_revision: None,
+                entries: Vec::new(),
+            })),
+        }
+    }
+
+    /// Hash the parts that must match exactly (product, template, context, ...)
+    pub fn fingerprint(parts: &[&str]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        parts.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Normalize text into a token set (lower-case words of 2+ chars)
+    pub fn tokens(text: &str) -> BTreeSet<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| w.chars().count() >= 2)
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Jaccard similarity of two token sets (two empty sets are identical)
+    pub fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f32 {
+        let union = a.union(b).count();
+        if union == 0 {
+            return 1.0;
+        }
+        a.intersection(b).count() as f32 / union as f32
+    }
+
+    /// Drop all entries if the knowledge base changed since the last call
+    pub fn sync_kb_revision(&self, revision: &str) {
+        let mut state = self.state.write().unwrap();
+        if state.kb_revision.as_deref() != Some(revision) {
+            if state.kb_revision.is_some() {
+                tracing::info!("Knowledge base changed, dropping {} cached responses", state.entries.len());
+            }
+            state.entries.clear();
+            state.kb_revision = Some(revision.to_string());
+        }
+    }
+
+    /// Find the most similar live entry with the same fingerprint
+    pub fn get(&self, fingerprint: u64, text: &str) -> Option<T> {
+        let tokens = Self::tokens(text);
+        let state = self.state.read().unwrap();
+
+        state
+            .entries
+            .iter()
+            .filter(|e| e.fingerprint == fingerprint && e.created_at.elapsed() < self.config.ttl)
+            .map(|e| (Self::similarity(&e.tokens, &tokens), e))
+            .filter(|(score, _)| *score >= self.config.similarity_threshold)
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, e)| e.value.clone())
+    }
+
+    /// Store a response, evicting expired and then oldest entries
+    pub fn insert(&self, fingerprint: u64, text: &str, value: T) {
+        let mut state = self.state.write().unwrap();
+        let ttl = self.config.ttl;
+        state.entries.retain(|e| e.created_at.elapsed() < ttl);
+
+        if state.entries.len() >= self.config.max_entries {
+            let excess = state.entries.len() + 1 - self.config.max_entries;
+            state.entries.drain(..excess);
+        }
+
+        state.entries.push(CacheEntry {
+            fingerprint,
+            tokens: Self::tokens(text),
+            value,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// Number of stored entries (including expired ones not yet evicted)
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        self.state.write().unwrap().entries.clear();
+    }
+}
+
+/// Global Q&A response cache
+static QA_CACHE: std::sync::OnceLock<ResponseCache<QAResponse>> = std::sync::OnceLock::new();
+
+/// Global review response cache
+static REVIEW_CACHE: std::sync::OnceLock<ResponseCache<ReviewResponse>> = std::sync::OnceLock::new();
+
+/// Get the global Q&A response cache
+pub fn qa_cache() -> &'static ResponseCache<QAResponse> {
+    QA_CACHE.get_or_init(|| ResponseCache::new(CacheConfig::qa()))
+}
+
+/// Get the global review response cache
+pub fn review_cache() -> &'static ResponseCache<ReviewResponse> {
+    REVIEW_CACHE.get_or_init(|| ResponseCache::new(CacheConfig::review()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache() -> ResponseCache<String> {
+        ResponseCache::new(CacheConfig::qa())
+    }
+
+    #[test]
+    fn test_similar_question_hits() {
+        let cache = cache();
+        let fp = ResponseCache::<String>::fingerprint(&["xframe5-ui", "template"]);
+        cache.insert(fp, "How to set grid column width?", "answer".to_string());
+
+        assert_eq!(cache.get(fp, "how to set grid column width"), Some("answer".to_string()));
+        assert_eq!(cache.get(fp, "How do I export grid data to Excel?"), None);
+    }
+
+    #[test]
+    fn test_fingerprint_must_match() {
+        let cache = cache();
+        let fp = ResponseCache::<String>::fingerprint(&["xframe5-ui"]);
+        let other = ResponseCache::<String>::fingerprint(&["spring-backend"]);
+        cache.insert(fp, "grid column width", "answer".to_string());
+
+        assert_eq!(cache.get(other, "grid column width"), None);
+    }
+
+    #[test]
+    fn test_expired_entries_miss() {
+        let cache = ResponseCache::new(CacheConfig {
+            ttl: Duration::ZERO,
+            ..CacheConfig::qa()
+        });
+        cache.insert(1, "grid column width", "answer".to_string());
+        assert_eq!(cache.get(1, "grid column width"), None);
+    }
+
+    #[test]
+    fn test_kb_revision_change_clears() {
+        let cache = cache();
+        cache.sync_kb_revision("3:2026-10-01");
+        cache.insert(1, "grid column width", "answer".to_string());
+
+        cache.sync_kb_revision("3:2026-10-01");
+        assert_eq!(cache.len(), 1);
+
+        cache.sync_kb_revision("4:2026-10-16");
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_max_entries_evicts_oldest() {
+        let cache = ResponseCache::new(CacheConfig {
+            max_entries: 2,
+            ..CacheConfig::review()
+        });
+        cache.insert(1, "", "a".to_string());
+        cache.insert(2, "", "b".to_string());
+        cache.insert(3, "", "c".to_string());
+
+        assert_eq!(cache.get(1, ""), None);
+        assert_eq!(cache.get(3, ""), Some("c".to_string()));
+    }
+}
@@ -0,0 +1,178 @@
+//! Artifact Retention
+//!
+//! How long the artifacts of a generation stay downloadable. Policies match
+//! the role of the requesting user (e.g. PoC users 7 days, licensed teams a
+//! year) and the product; the most specific one wins: role and product, then
+//! role, then product, then the catch-all policy. Without a matching policy
+//! `ARTIFACT_RETENTION_DAYS` applies, and without that artifacts are kept.
+//! Generations without a known user (anonymous plugin calls) get the
+//! shortest policy of their product.
+//!
+//! The expiry is fixed when a generation completes, so a policy change only
+//! affects later generations. The `retention_cleanup` task purges artifacts
+//! and lineage of expired generations; the log row (status, timings, quality
+//! score) stays for statistics.
+
+use std::sync::OnceLock;
+
+use chrono::{Duration, Utc};
+use loco_rs::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::DatabaseConnection;
+
+use crate::models::_entities::generation_logs::{Column, Entity};
+use crate::models::_entities::{retention_policies, users};
+
+/// Response header of artifact downloads carrying their expiry (RFC 3339)
+pub const ARTIFACTS_EXPIRE_HEADER: &str = "X-Artifacts-Expire-At";
+
+pub struct RetentionService;
+
+impl RetentionService {
+    /// Days from `ARTIFACT_RETENTION_DAYS` (unset or 0 = keep)
+    pub fn default_days() -> Option<i32> {
+        static DAYS: OnceLock<Option<i32>> = OnceLock::new();
+        *DAYS.get_or_init(|| {
+            std::env::var("ARTIFACT_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|days| *days > 0)
+        })
+    }
+
+    /// Most specific policy for a user role and product
+    pub fn policy_for<'a>(
+        policies: &'a [retention_policies::Model],
+        role: Option<&str>,
+        product: &str,
+    ) -> Option<&'a retention_policies::Model> {
+        policies
+            .iter()
+            .filter(|p| p.role.as_deref().is_none_or(|r| Some(r) == role))
+            .filter(|p| p.product.as_deref().is_none_or(|r| r == product))
+            .max_by_key(|p| (p.role.is_some(), p.product.is_some(), std::cmp::Reverse(p.id)))
+    }
+
+    /// Shortest policy any user's `product` generations can get
+    pub fn shortest_policy_for<'a>(
+        policies: &'a [retention_policies::Model],
+        product: &str,
+    ) -> Option<&'a retention_policies::Model> {
+        policies
+            .iter()
+            .filter(|p| p.product.as_deref().is_none_or(|r| r == product))
+            .min_by_key(|p| (p.retention_days, p.id))
+    }
+
+    /// Days the artifacts of `user_id`'s `product` generations are kept
+    /// (None user = the shortest policy)
+    pub async fn retention_days(db: &DatabaseConnection, user_id: Option<i32>, product: &str) -> Result<Option<i32>> {
+        let policies = retention_policies::Entity::find().all(db).await?;
+        let policy = match user_id {
+            Some(user_id) => {
+                let role = users::Entity::find_by_id(user_id).one(db).await?.and_then(|u| u.role);
+                Self::policy_for(&policies, role.as_deref(), product)
+            }
+            None => Self::shortest_policy_for(&policies, product),
+        };
+
+        Ok(match policy {
+            Some(policy) => Some(policy.retention_days),
+            None => Self::default_days(),
+        })
+    }
+
+    /// When artifacts completed at `from` expire (None = kept)
+    pub async fn expiry(
+        db: &DatabaseConnection,
+        user_id: Option<i32>,
+        product: &str,
+        from: DateTimeWithTimeZone,
+    ) -> Result<Option<DateTimeWithTimeZone>> {
+        let days = Self::retention_days(db, user_id, product).await?;
+        Ok(days.map(|days| from + Duration::days(i64::from(days))))
+    }
+
+    /// Purge the artifacts of generations expired by `now`; number purged
+    pub async fn purge_expired(db: &DatabaseConnection, now: DateTimeWithTimeZone) -> Result<u64> {
+        let result = Entity::update_many()
+            .col_expr(Column::Artifacts, Expr::value(Option::<String>::None))
+            .col_expr(Column::Lineage, Expr::value(Option::<String>::None))
+            .col_expr(Column::ArtifactsPurgedAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(DateTimeWithTimeZone::from(Utc::now())))
+            .filter(Column::ArtifactsExpireAt.lte(now))
+            .filter(Column::ArtifactsPurgedAt.is_null())
+            .exec(db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Error for a download of purged artifacts
+    pub fn expired_error(expired_at: Option<DateTimeWithTimeZone>) -> Error {
+        match expired_at {
+            Some(at) => Error::BadRequest(format!(
+                "Artifacts expired on {} and were removed by the retention policy",
+                at.format("%Y-%m-%d")
+            )),
+            None => Error::BadRequest("Artifacts were removed by the retention policy".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(id: i32, role: Option<&str>, product: Option<&str>, days: i32) -> retention_policies::Model {
+        let now = Utc::now().into();
+        retention_policies::Model {
+            created_at: now,
+            updated_at: now,
+            id,
+            role: role.map(str::to_string),
+            product: product.map(str::to_string),
+            retention_days: days,
+            updated_by: None,
+        }
+    }
+
+    #[test]
+    fn test_most_specific_policy_wins() {
+        let policies = vec![
+            policy(1, None, None, 30),
+            policy(2, Some("poc"), None, 7),
+            policy(3, None, Some("spring-backend"), 90),
+            policy(4, Some("licensed"), Some("xframe5-ui"), 365),
+        ];
+        let days = |role, product| RetentionService::policy_for(&policies, role, product).map(|p| p.retention_days);
+
+        assert_eq!(days(Some("poc"), "spring-backend"), Some(7));
+        assert_eq!(days(Some("licensed"), "xframe5-ui"), Some(365));
+        assert_eq!(days(Some("licensed"), "spring-backend"), Some(90));
+        assert_eq!(days(None, "xframe5-ui"), Some(30));
+        assert_eq!(RetentionService::policy_for(&policies[1..2], None, "xframe5-ui"), None);
+    }
+
+    #[test]
+    fn test_unknown_user_gets_shortest_policy() {
+        let policies = vec![
+            policy(1, None, None, 30),
+            policy(2, Some("licensed"), None, 365),
+            policy(3, Some("poc"), Some("xframe5-ui"), 7),
+        ];
+        let days = |product| RetentionService::shortest_policy_for(&policies, product).map(|p| p.retention_days);
+
+        assert_eq!(days("xframe5-ui"), Some(7));
+        // The PoC tier is for another product
+        assert_eq!(days("spring-backend"), Some(30));
+        assert_eq!(RetentionService::shortest_policy_for(&[], "xframe5-ui"), None);
+    }
+
+    #[test]
+    fn test_expired_error_names_date() {
+        let at = chrono::DateTime::parse_from_rfc3339("2026-10-09T12:00:00+09:00").unwrap();
+        let message = RetentionService::expired_error(Some(at)).to_string();
+        assert!(message.contains("2026-10-09"));
+    }
+}
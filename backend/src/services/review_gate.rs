@@ -0,0 +1,207 @@
+//! Review Gate Service
+//!
+//! Evaluates a review result against a `ReviewPolicy` so the review API can
+//! be used as a pre-commit or CI gate.
+
+use crate::domain::{
+    GateSummary, GateViolation, IssueCategory, IssueSeverity, ReviewGateResult, ReviewPolicy,
+    ReviewResult, SeverityCounts,
+};
+
+/// Service for policy-based review gating
+pub struct ReviewGate;
+
+impl ReviewGate {
+    /// Evaluate a review against the policy
+    pub fn evaluate(review: &ReviewResult, policy: &ReviewPolicy) -> ReviewGateResult {
+        let summary = Self::summarize(review);
+        let mut violations = Vec::new();
+
+        Self::check_limit(&mut violations, "max_errors", None, summary.total.errors, policy.max_errors);
+        Self::check_limit(
+            &mut violations,
+            "max_warnings",
+            None,
+            summary.total.warnings,
+            policy.max_warnings,
+        );
+
+        // Iterate the policy (not the summary) so output order is stable per request
+        let mut categories: Vec<_> = policy.categories.iter().collect();
+        categories.sort_by_key(|(category, _)| Self::category_order(**category));
+        for (category, limits) in categories {
+            let counts = summary.by_category.get(category).cloned().unwrap_or_default();
+            Self::check_limit(&mut violations, "max_errors", Some(*category), counts.errors, limits.max_errors);
+            Self::check_limit(
+                &mut violations,
+                "max_warnings",
+                Some(*category),
+                counts.warnings,
+                limits.max_warnings,
+            );
+        }
+
+        if let Some(min_score) = policy.min_score {
+            // A missing score cannot prove the minimum, so it counts as 0
+            let score = summary.score.unwrap_or(0);
+            if score < min_score {
+                violations.push(GateViolation {
+                    rule: "min_score".to_string(),
+                    category: None,
+                    actual: score as u32,
+                    limit: min_score as u32,
+                });
+            }
+        }
+
+        ReviewGateResult {
+            passed: violations.is_empty(),
+            summary,
+            violations,
+        }
+    }
+
+    /// Count issues by severity, in total and per category
+    fn summarize(review: &ReviewResult) -> GateSummary {
+        let mut summary = GateSummary {
+            score: review.score.as_ref().map(|s| s.overall),
+            ..Default::default()
+        };
+
+        for issue in &review.issues {
+            Self::increment(&mut summary.total, issue.severity);
+            Self::increment(
+                summary.by_category.entry(issue.category).or_default(),
+                issue.severity,
+            );
+        }
+
+        summary
+    }
+
+    fn increment(counts: &mut SeverityCounts, severity: IssueSeverity) {
+        match severity {
+            IssueSeverity::Error => counts.errors += 1,
+            IssueSeverity::Warning => counts.warnings += 1,
+            IssueSeverity::Info => counts.infos += 1,
+            IssueSeverity::Suggestion => counts.suggestions += 1,
+        }
+    }
+
+    fn check_limit(
+        violations: &mut Vec<GateViolation>,
+        rule: &str,
+        category: Option<IssueCategory>,
+        actual: u32,
+        limit: Option<u32>,
+    ) {
+        if let Some(limit) = limit {
+            if actual > limit {
+                violations.push(GateViolation {
+                    rule: rule.to_string(),
+                    category,
+                    actual,
+                    limit,
+                });
+            }
+        }
+    }
+
+    fn category_order(category: IssueCategory) -> u8 {
+        match category {
+            IssueCategory::Syntax => 0,
+            IssueCategory::Pattern => 1,
+            IssueCategory::Naming => 2,
+            IssueCategory::Performance => 3,
+            IssueCategory::Security => 4,
+            IssueCategory::BestPractice => 5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ReviewIssue, ReviewScore, SeverityLimits};
+
+    fn issue(severity: IssueSeverity, category: IssueCategory) -> ReviewIssue {
+        ReviewIssue {
+            severity,
+            category,
+            line: 1,
+            message: "test".to_string(),
+            suggestion: None,
+            patch: None,
+        }
+    }
+
+    fn review(issues: Vec<ReviewIssue>, overall: Option<u8>) -> ReviewResult {
+        ReviewResult {
+            summary: String::new(),
+            issues,
+            score: overall.map(|overall| ReviewScore {
+                overall,
+                categories: Default::default(),
+            }),
+            improvements: vec![],
+        }
+    }
+
+    #[test]
+    fn test_default_policy_fails_on_any_error() {
+        let result = ReviewGate::evaluate(
+            &review(vec![issue(IssueSeverity::Error, IssueCategory::Syntax)], None),
+            &ReviewPolicy::default(),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.violations[0].rule, "max_errors");
+        assert_eq!(result.summary.total.errors, 1);
+    }
+
+    #[test]
+    fn test_warnings_pass_default_policy() {
+        let result = ReviewGate::evaluate(
+            &review(vec![issue(IssueSeverity::Warning, IssueCategory::Naming)], None),
+            &ReviewPolicy::default(),
+        );
+        assert!(result.passed);
+        assert!(result.violations.is_empty());
+    }
+
+    #[test]
+    fn test_category_limit() {
+        let mut policy = ReviewPolicy::default();
+        policy.categories.insert(
+            IssueCategory::Security,
+            SeverityLimits {
+                max_errors: None,
+                max_warnings: Some(0),
+            },
+        );
+
+        let result = ReviewGate::evaluate(
+            &review(
+                vec![
+                    issue(IssueSeverity::Warning, IssueCategory::Naming),
+                    issue(IssueSeverity::Warning, IssueCategory::Security),
+                ],
+                None,
+            ),
+            &policy,
+        );
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].category, Some(IssueCategory::Security));
+    }
+
+    #[test]
+    fn test_min_score() {
+        let policy = ReviewPolicy {
+            min_score: Some(80),
+            ..Default::default()
+        };
+        assert!(!ReviewGate::evaluate(&review(vec![], Some(70)), &policy).passed);
+        assert!(ReviewGate::evaluate(&review(vec![], Some(85)), &policy).passed);
+        assert!(!ReviewGate::evaluate(&review(vec![], None), &policy).passed);
+    }
+}
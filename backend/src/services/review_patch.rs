@@ -0,0 +1,223 @@
+//! Review Patch Service
+//!
+//! Builds and applies line-precise unified diff patches for review issues.
+//! Deterministic patches are produced for canonicalizer-class issues
+//! (e.g. `onclick` → `on_click`); LLM-provided patches go through the same
+//! applier so the plugin can offer one-click fixes.
+
+use crate::domain::{IssueCategory, IssueSeverity, ReviewIssue};
+use crate::services::pipeline::passes::Canonicalizer;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+
+/// Service for building and applying review patches
+pub struct ReviewPatcher;
+
+impl ReviewPatcher {
+    /// Produce deterministic issues (with patches) for canonicalizer-class problems.
+    ///
+    /// Only XML is checked line by line; a line is reported when the
+    /// canonicalizer would rewrite it.
+    pub fn deterministic_issues(code: &str, file_type: &str, file_name: &str) -> Vec<ReviewIssue> {
+        if file_type != "xml" {
+            return Vec::new();
+        }
+
+        let canonicalizer = Canonicalizer::new();
+        let mut issues = Vec::new();
+
+        for (idx, line) in code.lines().enumerate() {
+            let (fixed, fixes) = canonicalizer.canonicalize_xml(line);
+            if fixed == line || fixes.is_empty() {
+                continue;
+            }
+
+            let line_no = (idx + 1) as u32;
+            issues.push(ReviewIssue {
+                severity: IssueSeverity::Warning,
+                category: IssueCategory::Syntax,
+                line: line_no,
+                message: fixes.join("; "),
+                suggestion: Some(fixed.trim().to_string()),
+                patch: Some(Self::line_patch(file_name, line_no, line, &fixed)),
+            });
+        }
+
+        issues
+    }
+
+    /// Build a single-line unified diff replacing `old_line` with `new_line`
+    pub fn line_patch(file_name: &str, line: u32, old_line: &str, new_line: &str) -> String {
+        let name = if file_name.is_empty() { "code" } else { file_name };
+        format!(
+            "--- a/{name}\n+++ b/{name}\n@@ -{line},1 +{line},1 @@\n-{old_line}\n+{new_line}\n"
+        )
+    }
+
+    /// Apply a unified diff to the given code.
+    ///
+    /// Context and removed lines must match exactly; a mismatching hunk
+    /// fails the whole patch so a stale suggestion never corrupts the file.
+    pub fn apply(code: &str, patch: &str) -> Result<String> {
+        let hunk_re = Regex::new(r"^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@").unwrap();
+
+        let mut lines: Vec<String> = code.lines().map(|l| l.to_string()).collect();
+        let trailing_newline = code.ends_with('\n');
+
+        // Collect hunks: (original start line, body lines)
+        let mut hunks: Vec<(usize, Vec<&str>)> = Vec::new();
+        for raw in patch.lines() {
+            if raw.starts_with("--- ") || raw.starts_with("+++ ") {
+                continue;
+            }
+            if let Some(caps) = hunk_re.captures(raw) {
+                let start: usize = caps[1].parse().map_err(|_| anyhow!("Invalid hunk header: {}", raw))?;
+                hunks.push((start, Vec::new()));
+                continue;
+            }
+            if let Some((_, body)) = hunks.last_mut() {
+                body.push(raw);
+            }
+        }
+
+        if hunks.is_empty() {
+            return Err(anyhow!("Patch contains no hunks"));
+        }
+
+        // Apply in order while tracking the line offset introduced by earlier hunks
+        let mut offset: isize = 0;
+        for (start, body) in hunks {
+            let pos = (start.max(1) - 1).checked_add_signed(offset);
+            let mut pos = match pos {
+                Some(pos) if pos <= lines.len() => pos,
+                _ => {
+                    return Err(anyhow!(
+                        "Patch does not apply (hunk at line {}): file too short",
+                        start
+                    ))
+                }
+            };
+            let mut removed = 0isize;
+            let mut added = 0isize;
+
+            for entry in body {
+                // The marker is the first character, which need not be ASCII in a malformed patch
+                let mut chars = entry.chars();
+                let marker = chars.next();
+                let text = chars.as_str();
+                match marker {
+                    Some(' ') | None => {
+                        Self::expect_line(&lines, pos, text, start)?;
+                        pos += 1;
+                    }
+                    Some('-') => {
+                        Self::expect_line(&lines, pos, text, start)?;
+                        lines.remove(pos);
+                        removed += 1;
+                    }
+                    Some('+') => {
+                        lines.insert(pos, text.to_string());
+                        pos += 1;
+                        added += 1;
+                    }
+                    Some('\\') => {} // "\ No newline at end of file"
+                    _ => return Err(anyhow!("Invalid patch line: {}", entry)),
+                }
+            }
+
+            offset += added - removed;
+        }
+
+        let mut result = lines.join("\n");
+        if trailing_newline {
+            result.push('\n');
+        }
+        Ok(result)
+    }
+
+    /// Verify that the line at `pos` matches the expected patch text
+    fn expect_line(lines: &[String], pos: usize, expected: &str, hunk_start: usize) -> Result<()> {
+        match lines.get(pos) {
+            Some(actual) if actual == expected => Ok(()),
+            Some(actual) => Err(anyhow!(
+                "Patch does not apply (hunk at line {}): expected '{}', found '{}'",
+                hunk_start,
+                expected,
+                actual
+            )),
+            None => Err(anyhow!(
+                "Patch does not apply (hunk at line {}): file too short",
+                hunk_start
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_issue_for_onclick() {
+        let code = "<screen>\n  <pushbutton onclick=\"fn_search\"/>\n</screen>";
+        let issues = ReviewPatcher::deterministic_issues(code, "xml", "member_list.xml");
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 2);
+        let patch = issues[0].patch.as_ref().unwrap();
+        assert!(patch.contains("@@ -2,1 +2,1 @@"));
+        assert!(patch.contains("+  <pushbutton on_click=\"eventfunc:fn_search()\"/>"));
+    }
+
+    #[test]
+    fn test_no_deterministic_issues_for_js() {
+        let issues = ReviewPatcher::deterministic_issues("onclick=\"x\"", "javascript", "a.js");
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_apply_line_patch_roundtrip() {
+        let code = "<screen>\n  <pushbutton onclick=\"fn_search\"/>\n</screen>\n";
+        let issues = ReviewPatcher::deterministic_issues(code, "xml", "a.xml");
+        let patched = ReviewPatcher::apply(code, issues[0].patch.as_ref().unwrap()).unwrap();
+
+        assert!(patched.contains("on_click=\"eventfunc:fn_search()\""));
+        assert!(patched.ends_with("</screen>\n"));
+    }
+
+    #[test]
+    fn test_apply_with_context_lines() {
+        let code = "a\nb\nc\nd";
+        let patch = "@@ -1,3 +1,4 @@\n a\n-b\n+B\n+B2\n c\n";
+        let patched = ReviewPatcher::apply(code, patch).unwrap();
+        assert_eq!(patched, "a\nB\nB2\nc\nd");
+    }
+
+    #[test]
+    fn test_apply_rejects_stale_patch() {
+        let code = "a\nb\nc";
+        let patch = "@@ -2,1 +2,1 @@\n-x\n+y\n";
+        assert!(ReviewPatcher::apply(code, patch).is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_hunk_past_end_of_file() {
+        let code = "a\nb";
+        assert!(ReviewPatcher::apply(code, "@@ -10,0 +10,1 @@\n+c\n").is_err());
+        assert!(ReviewPatcher::apply(code, "@@ -99999999999999999999,1 +1,1 @@\n+c\n").is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_offset_before_start_of_file() {
+        // The first hunk removes two lines, pulling the second one before line 1
+        let code = "a\nb\nc";
+        let patch = "@@ -1,2 +1,0 @@\n-a\n-b\n@@ -1,0 +1,1 @@\n+x\n";
+        assert!(ReviewPatcher::apply(code, patch).is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_multibyte_marker() {
+        let code = "회원\n목록";
+        assert!(ReviewPatcher::apply(code, "@@ -1,1 +1,1 @@\n회원\n").is_err());
+    }
+}
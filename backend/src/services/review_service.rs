@@ -1,15 +1,22 @@
 use crate::domain::{
-    ReviewContext, ReviewInput, ReviewMeta, ReviewOptions, ReviewResponse, ReviewResult,
+    FileHeaders, ForbiddenApi, HeaderVars, ReviewContext, ReviewInput, ReviewMeta, ReviewOptions, ReviewResponse, ReviewResult,
     ReviewScore, CategoryScores, ReviewIssue, IssueSeverity, IssueCategory,
 };
-use crate::llm::create_backend_from_db_or_env;
+use crate::llm::{create_backend_from_db_or_env, GenParams, LlmUsage, RetryingBackend};
 use crate::models::_entities::generation_logs;
-use crate::services::{KnowledgeBaseService, KnowledgeQuery, TemplateService};
+use crate::models::users::SYSTEM_USER_ID;
+use crate::models::{company_rules, project_output_settings};
+use crate::services::pipeline::passes::ApiDenylistFilter;
+use crate::services::response_cache::{review_cache, ResponseCache};
+use crate::services::{KnowledgeBaseService, KnowledgeQuery, ReviewPatcher, TemplateService, TenantScope};
 use anyhow::{anyhow, Result};
 use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
 use serde_json::Value;
 use std::time::Instant;
 
+/// Sampling temperature of reviews: the same code should get the same findings
+const REVIEW_TEMPERATURE: f32 = 0.1;
+
 /// Service for code review operations
 pub struct ReviewService;
 
@@ -28,20 +35,26 @@ impl ReviewService {
         // 1. Detect file type
         let file_type = input.detect_file_type();
 
+        // Templates, rules, knowledge and LLM config of the user's company
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+
         // 2. Load review template from DB
-        let template = TemplateService::get_active(db, product, Some("review"))
+        let template = TemplateService::get_active(db, product, Some("review"), &tenant)
             .await
             .map_err(|_| anyhow!("Review template not found for product: {}", product))?;
 
         // 3. Load relevant knowledge base entries
-        let knowledge = Self::load_knowledge(db, product, &file_type).await;
+        let knowledge = Self::load_knowledge(db, product, &file_type, &tenant).await;
 
         // 4. Load company rules if provided
-        let company_rules = if let Some(ref company_id) = options.company_id {
-            Self::load_company_rules(db, company_id).await
-        } else {
-            String::new()
+        let rules = match options.company_id.as_deref() {
+            Some(company_id) => company_rules::Model::find_by_name(db, company_id, &tenant).await.ok(),
+            None => None,
         };
+        let company_rules = rules
+            .as_ref()
+            .map(|r| r.render_for(product, None))
+            .unwrap_or_default();
 
         // 5. Compile prompt
         let (system_prompt, user_prompt) = Self::compile_prompt(
@@ -56,22 +69,90 @@ impl ReviewService {
         )?;
 
         let full_prompt = format!("{}\n\n{}", system_prompt, user_prompt);
+        let file_name = context.file_name.clone().unwrap_or_default();
+
+        // 5a. Header check against the project's file header templates
+        let project = context.project.as_deref().unwrap_or_default();
+        let settings = match context.project.as_deref() {
+            Some(project) => project_output_settings::Model::find_by_project(db, project, &tenant).await.ok(),
+            None => None,
+        };
+        let header_issues = settings
+            .map(|s| Self::header_issues(&input.code, &file_type, &file_name, &s.file_headers(), project))
+            .unwrap_or_default();
+
+        // 6. Serve identical reviews from the cache (dropped when the KB changes)
+        let kb_revision = KnowledgeBaseService::revision(db).await.ok();
+        if let Some(revision) = &kb_revision {
+            review_cache().sync_kb_revision(revision);
+        }
+        let fingerprint =
+            ResponseCache::<ReviewResponse>::fingerprint(&[product, &full_prompt, &file_name, project]);
+
+        if kb_revision.is_some() {
+            if let Some(mut cached) = review_cache().get(fingerprint, "") {
+                let review_time_ms = start.elapsed().as_millis() as u64;
+                if let Some(review) = &cached.review {
+                    Self::log_review(
+                        db,
+                        product,
+                        &file_type,
+                        input.code.lines().count(),
+                        review.issues.len(),
+                        review.score.as_ref().map(|s| s.overall as i32),
+                        review_time_ms as i32,
+                        user_id,
+                        None,
+                    )
+                    .await
+                    .ok();
+                }
+
+                cached.meta = ReviewMeta::new(format!("{}-review-v1", product), review_time_ms);
+                cached.meta.cached = true;
+                return Ok(cached);
+            }
+        }
 
-        // 6. Generate via LLM
-        let llm = create_backend_from_db_or_env(db).await;
+        // 7. Generate via LLM
+        let llm = RetryingBackend::from_env(create_backend_from_db_or_env(db, tenant.company()).await);
 
         llm.health_check().await.map_err(|e| {
             anyhow!("LLM server not available: {}. Please check your LLM configuration.", e)
         })?;
 
-        let raw_output = llm.generate(&full_prompt).await?;
-
-        // 7. Parse JSON response
-        let review_result = Self::parse_review_result(&raw_output)?;
+        let output = llm.generate_with(&full_prompt, &GenParams::new().with_temperature(REVIEW_TEMPERATURE)).await?;
+        let llm_usage = LlmUsage::of(&output);
+        let raw_output = output.text;
+
+        // 8. Parse JSON response
+        let mut review_result = Self::parse_review_result(&raw_output)?;
+
+        // 8a. Add deterministic (patchable) issues for canonicalizer-class problems
+        Self::merge_deterministic_issues(
+            &mut review_result,
+            ReviewPatcher::deterministic_issues(&input.code, &file_type, &file_name),
+        );
+
+        // 8b. Add forbidden-API findings (built-in deny-list applies to xFrame5 XML/JS only)
+        let rule_sections = rules.as_ref().map(|r| r.rule_sections());
+        let apis = if file_type == "xml" || file_type == "javascript" {
+            ApiDenylistFilter::for_rules(rule_sections.as_ref(), product)
+                .deny_list()
+                .to_vec()
+        } else {
+            rule_sections
+                .map(|s| s.forbidden_apis_for(product))
+                .unwrap_or_default()
+        };
+        review_result
+            .issues
+            .extend(Self::forbidden_api_issues(&input.code, &apis));
+        review_result.issues.extend(header_issues);
 
         let review_time_ms = start.elapsed().as_millis() as u64;
 
-        // 8. Log to audit trail (meta only, NO raw code)
+        // 9. Log to audit trail (meta only, NO raw code)
         Self::log_review(
             db,
             product,
@@ -81,19 +162,29 @@ impl ReviewService {
             review_result.score.as_ref().map(|s| s.overall as i32),
             review_time_ms as i32,
             user_id,
+            Some(llm_usage),
         )
         .await
         .ok(); // Don't fail on log error
 
-        // 9. Build response
-        Ok(ReviewResponse::success(
+        // 10. Build response
+        let response = ReviewResponse::success(
             review_result,
             ReviewMeta::new(format!("{}-review-v1", product), review_time_ms),
-        ))
+        );
+        if kb_revision.is_some() {
+            review_cache().insert(fingerprint, "", response.clone());
+        }
+        Ok(response)
     }
 
     /// Load knowledge entries relevant to the file type
-    async fn load_knowledge(db: &DatabaseConnection, product: &str, file_type: &str) -> String {
+    async fn load_knowledge(
+        db: &DatabaseConnection,
+        product: &str,
+        file_type: &str,
+        tenant: &TenantScope,
+    ) -> String {
         let query = KnowledgeQuery {
             category: Some(if product.contains("spring") {
                 "spring".to_string()
@@ -105,7 +196,7 @@ impl ReviewService {
             priority: Some("high".to_string()),
         };
 
-        match KnowledgeBaseService::query(db, &query).await {
+        match KnowledgeBaseService::query(db, &query, tenant).await {
             Ok(entries) => {
                 if entries.is_empty() {
                     return String::new();
@@ -116,11 +207,48 @@ impl ReviewService {
         }
     }
 
-    /// Load company rules
-    async fn load_company_rules(db: &DatabaseConnection, company_id: &str) -> String {
-        // TODO: Implement company rules loading from company_rules table
-        let _ = (db, company_id);
-        String::new()
+    /// Report company-forbidden APIs as security issues
+    pub(crate) fn forbidden_api_issues(code: &str, apis: &[ForbiddenApi]) -> Vec<ReviewIssue> {
+        ForbiddenApi::scan(code, apis)
+            .into_iter()
+            .map(|hit| ReviewIssue {
+                severity: IssueSeverity::Warning,
+                category: IssueCategory::Security,
+                line: hit.line,
+                message: hit.message(),
+                suggestion: hit.replacement.clone(),
+                patch: None,
+            })
+            .collect()
+    }
+
+    /// Report a missing project file header (with the expected header as
+    /// suggestion)
+    fn header_issues(
+        code: &str,
+        file_type: &str,
+        file_name: &str,
+        headers: &FileHeaders,
+        project: &str,
+    ) -> Vec<ReviewIssue> {
+        // The header template is picked by extension
+        let file_name = match (file_name, file_type) {
+            ("", "javascript") => "file.js",
+            ("", "java") => "File.java",
+            ("", _) => "file.xml",
+            (name, _) => name,
+        };
+        if headers.has_header(file_name, code) {
+            return Vec::new();
+        }
+        vec![ReviewIssue {
+            severity: IssueSeverity::Warning,
+            category: IssueCategory::BestPractice,
+            line: 1,
+            message: "Missing the project's file header".to_string(),
+            suggestion: headers.render(file_name, &HeaderVars::new(project)),
+            patch: None,
+        }]
     }
 
     /// Compile the review prompt using simple string replacement
@@ -350,12 +478,19 @@ impl ReviewService {
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string());
 
+                        let patch = issue
+                            .get("patch")
+                            .and_then(|v| v.as_str())
+                            .filter(|p| p.contains("@@"))
+                            .map(|p| p.to_string());
+
                         Some(ReviewIssue {
                             severity,
                             category,
                             line,
                             message,
                             suggestion,
+                            patch,
                         })
                     })
                     .collect()
@@ -363,6 +498,23 @@ impl ReviewService {
             .unwrap_or_default()
     }
 
+    /// Merge deterministic issues into the LLM result.
+    ///
+    /// When the LLM already reported an issue on the same line, the
+    /// deterministic patch is attached to it instead of duplicating the issue.
+    fn merge_deterministic_issues(result: &mut ReviewResult, deterministic: Vec<ReviewIssue>) {
+        for issue in deterministic {
+            match result
+                .issues
+                .iter_mut()
+                .find(|existing| existing.line == issue.line && existing.patch.is_none())
+            {
+                Some(existing) => existing.patch = issue.patch,
+                None => result.issues.push(issue),
+            }
+        }
+    }
+
     /// Parse severity string to enum
     fn parse_severity(s: &str) -> IssueSeverity {
         match s.to_lowercase().as_str() {
@@ -417,6 +569,7 @@ impl ReviewService {
         score: Option<i32>,
         review_time_ms: i32,
         user_id: Option<i32>,
+        llm_usage: Option<LlmUsage>,
     ) -> Result<()> {
         // Store meta information about the review
         let ui_intent_json = serde_json::to_string(&serde_json::json!({
@@ -427,7 +580,7 @@ impl ReviewService {
             "score": score,
         }))?;
 
-        let log = generation_logs::ActiveModel {
+        let mut log = generation_logs::ActiveModel {
             product: Set(product.to_string()),
             input_type: Set("code-review".to_string()),
             ui_intent: Set(ui_intent_json),
@@ -437,9 +590,12 @@ impl ReviewService {
             warnings: Set(None),
             error_message: Set(None),
             generation_time_ms: Set(Some(review_time_ms)),
-            user_id: Set(user_id.unwrap_or(1)),
+            user_id: Set(user_id.unwrap_or(SYSTEM_USER_ID)),
             ..Default::default()
         };
+        if let Some(usage) = &llm_usage {
+            usage.apply(&mut log);
+        }
 
         log.insert(db).await?;
 
@@ -475,6 +631,71 @@ mod tests {
         assert!(matches!(ReviewService::parse_severity("info"), IssueSeverity::Info));
     }
 
+    #[test]
+    fn test_parse_issues_reads_patch() {
+        let json: Value = serde_json::from_str(
+            r#"{"issues": [
+                {"severity": "warning", "line": 3, "message": "a", "patch": "@@ -3,1 +3,1 @@\n-x\n+y\n"},
+                {"severity": "info", "line": 4, "message": "b", "patch": "not a diff"}
+            ]}"#,
+        )
+        .unwrap();
+        let issues = ReviewService::parse_issues(&json);
+        assert!(issues[0].patch.is_some());
+        assert!(issues[1].patch.is_none());
+    }
+
+    #[test]
+    fn test_merge_deterministic_attaches_patch_to_same_line() {
+        let mut result = ReviewResult {
+            summary: String::new(),
+            issues: vec![ReviewIssue {
+                severity: IssueSeverity::Error,
+                category: IssueCategory::Syntax,
+                line: 2,
+                message: "Use on_click".to_string(),
+                suggestion: None,
+                patch: None,
+            }],
+            score: None,
+            improvements: vec![],
+        };
+        let code = "<screen>\n  <pushbutton onclick=\"fn_search\"/>\n</screen>";
+        let deterministic = ReviewPatcher::deterministic_issues(code, "xml", "a.xml");
+
+        ReviewService::merge_deterministic_issues(&mut result, deterministic);
+
+        assert_eq!(result.issues.len(), 1);
+        assert!(result.issues[0].patch.is_some());
+    }
+
+    #[test]
+    fn test_forbidden_api_issues_carry_replacement() {
+        let apis = ApiDenylistFilter::new().deny_list().to_vec();
+        let issues = ReviewService::forbidden_api_issues("var a = 1;\nvar b = eval(s);", &apis);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 2);
+        assert_eq!(issues[0].category, IssueCategory::Security);
+        assert_eq!(issues[0].suggestion.as_deref(), Some("JSON.parse()"));
+    }
+
+    #[test]
+    fn test_header_issues() {
+        let headers = FileHeaders {
+            java: Some("Copyright (c) {year} ACME".to_string()),
+            ..Default::default()
+        };
+        let issues = ReviewService::header_issues("public class A {}", "java", "", &headers, "CRM");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].suggestion.as_deref().unwrap().starts_with("/*\n * Copyright (c) 20"));
+
+        let stamped = headers.stamp("A.java", "public class A {}", &HeaderVars::new("CRM"));
+        assert!(ReviewService::header_issues(&stamped, "java", "A.java", &headers, "CRM").is_empty());
+        // No xml template
+        assert!(ReviewService::header_issues("<screen/>", "xml", "", &headers, "CRM").is_empty());
+    }
+
     #[test]
     fn test_file_type_detection() {
         let xml_input = ReviewInput::new("<?xml version=\"1.0\"?><screen/>");
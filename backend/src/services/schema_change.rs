@@ -0,0 +1,443 @@
+//! Schema Change Detection
+//!
+//! Keeps snapshots of the tables screens and Spring modules were generated
+//! from: every `db-schema` generation input, plus the tables the IDE plugin
+//! reads by introspection. When the latest snapshot of a table differs from
+//! the one in effect when an artifact was generated, the artifact is listed
+//! as stale with the column changes that affect it.
+//!
+//! Regenerating a stale artifact starts from its logged intent: removed
+//! columns are dropped, changed ones re-inferred and added ones appended,
+//! and the diff goes into the prompt notes so the LLM only touches the
+//! affected parts.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use sea_orm::entity::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde::Serialize;
+
+use crate::domain::{
+    diff_schemas, ColumnChangeKind, ColumnIntent, GenerateInput, GenerateOptions, GenerateStatus, GridColumnIntent,
+    LineageTarget, RequestContext, SchemaColumn, SchemaDiff, SchemaInput, SpringIntent, UiIntent, UiType,
+};
+use crate::models::_entities::generation_logs;
+use crate::models::_entities::schema_snapshots::{ActiveModel, Column, Entity};
+use crate::services::{
+    GenerationService, LineageService, NormalizerService, SpringGenerationService, SpringNormalizerService,
+};
+use crate::workers::generation::GenerateJobRequest;
+
+/// Snapshot taken from a generation's schema input
+pub const SOURCE_GENERATION: &str = "generation";
+/// Snapshot sent by the IDE plugin
+pub const SOURCE_INTROSPECTION: &str = "introspection";
+
+/// Recent generations checked for stale artifacts
+const MAX_CHECKED_LOGS: u64 = 200;
+
+/// Generated screen or module whose table changed since
+#[derive(Debug, Serialize)]
+pub struct StaleArtifact {
+    pub log_id: i32,
+    pub product: String,
+    /// Screen name or Spring entity name
+    pub name: String,
+    pub generated_at: DateTimeWithTimeZone,
+    /// Changes of the columns it uses (and added columns), per table
+    pub diffs: Vec<SchemaDiff>,
+    /// Generated lines using a changed or removed column (from its lineage)
+    pub affected: Vec<LineageTarget>,
+}
+
+/// Result of a regeneration
+#[derive(Debug, Serialize)]
+pub struct Regeneration {
+    /// Changes injected into the prompt
+    pub diffs: Vec<SchemaDiff>,
+    /// Generation response (screen or Spring)
+    pub response: serde_json::Value,
+}
+
+/// Intent of a logged generation (parsed once per regeneration, so the size is not worth boxing)
+#[allow(clippy::large_enum_variant)]
+enum LoggedIntent {
+    Screen(UiIntent),
+    Spring(SpringIntent),
+}
+
+impl LoggedIntent {
+    fn parse(log: &generation_logs::Model) -> Option<Self> {
+        if log.product == "spring-backend" {
+            serde_json::from_str(&log.ui_intent).ok().map(Self::Spring)
+        } else {
+            serde_json::from_str(&log.ui_intent).ok().map(Self::Screen)
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Screen(intent) => &intent.screen_name,
+            Self::Spring(intent) => &intent.entity_name,
+        }
+    }
+
+    /// Tables the artifact reads, with the columns it uses
+    fn tables(&self) -> Vec<(String, Vec<String>)> {
+        let names = |columns: &[ColumnIntent]| columns.iter().map(|c| c.name.clone()).collect();
+        match self {
+            Self::Screen(intent) => intent
+                .datasets
+                .iter()
+                .filter_map(|d| d.table_name.as_ref().map(|t| (t.clone(), names(&d.columns))))
+                .collect(),
+            Self::Spring(intent) => vec![(intent.table_name.clone(), names(&intent.columns))],
+        }
+    }
+}
+
+/// Snapshot key of a table: upper case, without schema
+fn table_key(table: &str) -> String {
+    table.trim().rsplit('.').next().unwrap_or_default().to_uppercase()
+}
+
+fn company_condition(company: Option<&str>) -> Condition {
+    match company {
+        Some(code) => Condition::all().add(Column::Company.eq(code)),
+        None => Condition::all().add(Column::Company.is_null()),
+    }
+}
+
+/// Apply `diff` to the intent columns of its table; returns the added columns
+fn update_columns(
+    columns: &mut Vec<ColumnIntent>,
+    diff: &SchemaDiff,
+    current: &SchemaInput,
+    infer: impl Fn(&SchemaColumn) -> ColumnIntent,
+) -> Vec<ColumnIntent> {
+    let find = |name: &str| current.columns.iter().find(|c| c.name.eq_ignore_ascii_case(name));
+
+    columns.retain(|c| !diff.removed().any(|r| r.eq_ignore_ascii_case(&c.name)));
+    for column in columns.iter_mut() {
+        let kinds: Vec<ColumnChangeKind> = diff
+            .changes
+            .iter()
+            .filter(|c| c.column.eq_ignore_ascii_case(&column.name))
+            .map(|c| c.kind)
+            .collect();
+        let Some(source) = find(&column.name).filter(|_| !kinds.is_empty()) else {
+            continue;
+        };
+        // Formats, code groups and edited labels stay as they were
+        let inferred = infer(source);
+        column.ui_type = inferred.ui_type;
+        column.data_type = inferred.data_type;
+        column.required = inferred.required;
+        column.is_pk = inferred.is_pk;
+        column.max_length = inferred.max_length;
+        if kinds.contains(&ColumnChangeKind::CommentChanged) {
+            column.label = inferred.label;
+        }
+    }
+
+    let added: Vec<ColumnIntent> = diff
+        .added()
+        .filter(|name| !columns.iter().any(|c| c.name.eq_ignore_ascii_case(name)))
+        .filter_map(find)
+        .map(&infer)
+        .collect();
+    columns.extend(added.iter().cloned());
+    added
+}
+
+fn with_diff_notes(notes: Option<String>, diffs: &[(SchemaDiff, SchemaInput)]) -> Option<String> {
+    let mut parts: Vec<String> = notes.into_iter().collect();
+    parts.extend(diffs.iter().map(|(diff, _)| diff.prompt_note()));
+    Some(parts.join("\n\n"))
+}
+
+pub struct SchemaChangeService;
+
+impl SchemaChangeService {
+    /// Store a snapshot of `schema` unless it matches the latest one; returns
+    /// the changes against that one
+    pub async fn record(
+        db: &DatabaseConnection,
+        company: Option<&str>,
+        schema: &SchemaInput,
+        source: &str,
+    ) -> Result<Option<SchemaDiff>> {
+        if schema.columns.is_empty() {
+            return Ok(None);
+        }
+        let previous = Self::snapshot(db, company, &schema.table, None).await?;
+        let diff = previous.map(|previous| diff_schemas(&previous, schema));
+        if diff.as_ref().is_some_and(SchemaDiff::is_empty) {
+            return Ok(None);
+        }
+
+        ActiveModel {
+            company: Set(company.map(str::to_string)),
+            table_name: Set(table_key(&schema.table)),
+            schema_json: Set(serde_json::to_string(schema)?),
+            source: Set(source.to_string()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Ok(diff)
+    }
+
+    /// Snapshot a generation's `db-schema` input (failures are only logged)
+    pub async fn record_input(db: &DatabaseConnection, company: Option<&str>, input: &GenerateInput) {
+        if let GenerateInput::DbSchema(schema) = input {
+            if let Err(e) = Self::record(db, company, schema, SOURCE_GENERATION).await {
+                tracing::warn!("Failed to record schema snapshot of {}: {}", schema.table, e);
+            }
+        }
+    }
+
+    /// Latest snapshot of `table`, or the one in effect at `at`
+    async fn snapshot(
+        db: &DatabaseConnection,
+        company: Option<&str>,
+        table: &str,
+        at: Option<DateTimeWithTimeZone>,
+    ) -> Result<Option<SchemaInput>> {
+        let mut query = Entity::find()
+            .filter(company_condition(company))
+            .filter(Column::TableName.eq(table_key(table)));
+        if let Some(at) = at {
+            query = query.filter(Column::CreatedAt.lte(at));
+        }
+        let snapshot = query.order_by_desc(Column::Id).one(db).await?;
+        Ok(snapshot.and_then(|s| serde_json::from_str(&s.schema_json).ok()))
+    }
+
+    /// The user's latest screens and modules whose tables changed since
+    pub async fn stale(db: &DatabaseConnection, user_id: i32) -> Result<Vec<StaleArtifact>> {
+        let logs = generation_logs::Entity::find()
+            .filter(generation_logs::Column::UserId.eq(user_id))
+            .filter(
+                generation_logs::Column::Status
+                    .is_in([GenerateStatus::Success.as_str(), GenerateStatus::PartialSuccess.as_str()]),
+            )
+            .order_by_desc(generation_logs::Column::Id)
+            .limit(MAX_CHECKED_LOGS)
+            .all(db)
+            .await?;
+
+        let mut seen = HashSet::new();
+        let mut stale = Vec::new();
+        for log in logs {
+            let Some(intent) = LoggedIntent::parse(&log) else {
+                continue;
+            };
+            // Older generations of the same screen were superseded
+            if !seen.insert((log.product.clone(), intent.name().to_string())) {
+                continue;
+            }
+            let diffs = Self::changes_since(db, &log, &intent).await?;
+            if diffs.is_empty() {
+                continue;
+            }
+            let changed: Vec<&str> = diffs
+                .iter()
+                .flat_map(|(diff, _)| diff.changes.iter())
+                .filter(|c| c.kind != ColumnChangeKind::Added)
+                .map(|c| c.column.as_str())
+                .collect();
+            let affected = LineageService::of_log(&log)
+                .map(|lineage| lineage.targets_of(&changed))
+                .unwrap_or_default();
+            stale.push(StaleArtifact {
+                log_id: log.id,
+                product: log.product.clone(),
+                name: intent.name().to_string(),
+                generated_at: log.created_at,
+                diffs: diffs.into_iter().map(|(diff, _)| diff).collect(),
+                affected,
+            });
+        }
+        Ok(stale)
+    }
+
+    /// Relevant changes of each table since `log`, with the current schema
+    async fn changes_since(
+        db: &DatabaseConnection,
+        log: &generation_logs::Model,
+        intent: &LoggedIntent,
+    ) -> Result<Vec<(SchemaDiff, SchemaInput)>> {
+        let company = log.company.as_deref();
+        let mut changes = Vec::new();
+        for (table, columns) in intent.tables() {
+            let Some(then) = Self::snapshot(db, company, &table, Some(log.created_at)).await? else {
+                continue;
+            };
+            let Some(now) = Self::snapshot(db, company, &table, None).await? else {
+                continue;
+            };
+            let diff = diff_schemas(&then, &now).affecting(&columns);
+            if !diff.is_empty() {
+                changes.push((diff, now));
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Regenerate `log` against the current schema of its tables
+    pub async fn regenerate(
+        db: &DatabaseConnection,
+        user_id: i32,
+        log: &generation_logs::Model,
+    ) -> Result<Regeneration> {
+        let Some(intent) = LoggedIntent::parse(log) else {
+            bail!("Generation {} has no recorded intent", log.id);
+        };
+        let changes = Self::changes_since(db, log, &intent).await?;
+        if changes.is_empty() {
+            bail!("Generation {} is up to date with its tables", log.id);
+        }
+        let (options, context) = Self::recorded_options(log);
+
+        let response = match intent {
+            LoggedIntent::Screen(mut intent) => {
+                for (diff, current) in &changes {
+                    let dialect = current.effective_dialect();
+                    let key = table_key(&current.table);
+                    for dataset in intent
+                        .datasets
+                        .iter_mut()
+                        .filter(|d| d.table_name.as_deref().is_some_and(|t| table_key(t) == key))
+                    {
+                        let added = update_columns(&mut dataset.columns, diff, current, |c| {
+                            NormalizerService::schema_column_to_intent(c, &current.primary_keys, dialect)
+                        });
+                        for grid in intent.grids.iter_mut().filter(|g| g.dataset_id == dataset.id) {
+                            grid.columns.retain(|g| !diff.removed().any(|r| r.eq_ignore_ascii_case(&g.name)));
+                            grid.columns.extend(
+                                added
+                                    .iter()
+                                    .filter(|c| c.ui_type != UiType::Hidden)
+                                    .map(|c| GridColumnIntent::new(&c.name, &c.label)),
+                            );
+                        }
+                    }
+                }
+                intent.notes = with_diff_notes(intent.notes.take(), &changes);
+
+                let response = GenerationService::generate(
+                    db,
+                    GenerateInput::Intent(intent),
+                    &log.product,
+                    &options,
+                    &context,
+                    Some(user_id),
+                )
+                .await?;
+                serde_json::to_value(response)?
+            }
+            LoggedIntent::Spring(mut intent) => {
+                for (diff, current) in &changes {
+                    let dialect = current.effective_dialect();
+                    update_columns(&mut intent.columns, diff, current, |c| {
+                        SpringNormalizerService::schema_column_to_intent(c, &current.primary_keys, dialect)
+                    });
+                }
+                intent.notes = with_diff_notes(intent.notes.take(), &changes);
+
+                let input = GenerateInput::DbSchema(changes[0].1.clone());
+                let response = SpringGenerationService::generate_for_intent(
+                    db,
+                    &input,
+                    intent,
+                    &options,
+                    &context,
+                    None,
+                    Some(user_id),
+                )
+                .await?;
+                serde_json::to_value(response)?
+            }
+        };
+
+        Ok(Regeneration {
+            diffs: changes.into_iter().map(|(diff, _)| diff).collect(),
+            response,
+        })
+    }
+
+    /// Options and context of the logged request (defaults for synchronous
+    /// generations, which don't keep it)
+    fn recorded_options(log: &generation_logs::Model) -> (GenerateOptions, RequestContext) {
+        let (mut options, mut context) = log
+            .request_payload
+            .as_deref()
+            .and_then(|p| serde_json::from_str::<GenerateJobRequest>(p).ok())
+            .map(|request| (request.options, request.context))
+            .unwrap_or_default();
+        if context.project.is_none() {
+            context.project = log.project.clone();
+        }
+        if options.pipeline_profile.is_none() {
+            options.pipeline_profile = log.pipeline_profile.clone();
+        }
+        (options, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DataType, DatasetIntent, ScreenType};
+
+    fn member(columns: &[(&str, &str)]) -> SchemaInput {
+        columns
+            .iter()
+            .fold(SchemaInput::new("TB_MEMBER").with_primary_key("MEMBER_ID"), |schema, (name, ty)| {
+                schema.with_column(SchemaColumn::new(*name, *ty))
+            })
+    }
+
+    #[test]
+    fn test_table_key_drops_schema() {
+        assert_eq!(table_key(" hr.tb_member "), "TB_MEMBER");
+        assert_eq!(table_key("TB_MEMBER"), "TB_MEMBER");
+    }
+
+    #[test]
+    fn test_screen_tables_with_used_columns() {
+        let intent = UiIntent::new("member_list", ScreenType::List)
+            .with_dataset(
+                DatasetIntent::new("ds_member")
+                    .with_table("TB_MEMBER")
+                    .with_columns(vec![ColumnIntent::new("MEMBER_NAME", "Name")]),
+            )
+            .with_dataset(DatasetIntent::new("ds_search"));
+
+        let tables = LoggedIntent::Screen(intent).tables();
+        assert_eq!(tables, vec![("TB_MEMBER".to_string(), vec!["MEMBER_NAME".to_string()])]);
+    }
+
+    #[test]
+    fn test_update_columns_applies_diff() {
+        let before = member(&[("MEMBER_ID", "NUMBER(10)"), ("MEMBER_NAME", "VARCHAR2(50)"), ("FAX", "VARCHAR2(20)")]);
+        let after = member(&[("MEMBER_ID", "NUMBER(10)"), ("MEMBER_NAME", "VARCHAR2(100)"), ("EMAIL", "VARCHAR2(200)")]);
+        let infer = |c: &SchemaColumn| NormalizerService::schema_column_to_intent(c, &after.primary_keys, None);
+
+        let mut columns: Vec<ColumnIntent> = before.columns.iter().map(infer).collect();
+        columns[1].label = "Member name".to_string();
+        let diff = diff_schemas(&before, &after);
+
+        let added = update_columns(&mut columns, &diff, &after, infer);
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["MEMBER_ID", "MEMBER_NAME", "EMAIL"]);
+        assert_eq!(added.len(), 1);
+        assert_eq!(columns[1].max_length, Some(100));
+        assert_eq!(columns[1].label, "Member name");
+        assert_eq!(columns[2].data_type, DataType::String);
+    }
+}
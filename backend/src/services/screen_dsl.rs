@@ -0,0 +1,509 @@
+//! Screen DSL
+//!
+//! Analysts who know exactly what the screen should hold write a short YAML
+//! spec instead of a schema or a description. The spec maps one-to-one onto
+//! `UiIntent`; nothing is inferred from column names or wording.
+//!
+//! ```yaml
+//! screen: member_list
+//! type: list_with_popup
+//! datasets:
+//!   - id: ds_member
+//!     table: TB_MEMBER
+//!     columns:
+//!       - { name: MEMBER_ID, label: 회원ID, pk: true }
+//!       - { name: MEMBER_NAME, label: 회원명, required: true, max_length: 50 }
+//!       - { name: JOIN_DATE, label: 가입일, type: date }
+//!       - { name: PHONE, label: 연락처, sensitive: phone }
+//! grids:
+//!   - dataset: ds_member
+//!     columns: [MEMBER_NAME, { name: JOIN_DATE, width: "100", align: center }, PHONE]
+//! actions: [search, add, { id: approve, label: 승인, type: custom }]
+//! ```
+//!
+//! Unknown keys and values are rejected with their location; all semantic
+//! problems (undeclared datasets, missing grid columns, duplicate ids) are
+//! reported together.
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_yaml::Value;
+
+use crate::domain::{
+    default_actions_for_screen_type, ActionIntent, ActionPosition, ActionType, Alignment, ColumnIntent, DataType,
+    DatasetIntent, GridColumnIntent, GridIntent, ScreenType, SensitiveKind, UiIntent, UiType,
+};
+
+/// Actions that can be written by id alone: `(id, label, type)`
+pub(crate) const ACTION_SHORTHANDS: &[(&str, &str, ActionType)] = &[
+    ("search", "조회", ActionType::Search),
+    ("add", "신규", ActionType::Add),
+    ("save", "저장", ActionType::Save),
+    ("delete", "삭제", ActionType::Delete),
+    ("close", "닫기", ActionType::ClosePopup),
+    ("export", "엑셀", ActionType::Export),
+    ("print", "인쇄", ActionType::Print),
+    ("refresh", "새로고침", ActionType::Search),
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScreenSpec {
+    screen: String,
+    #[serde(rename = "type")]
+    screen_type: ScreenType,
+    #[serde(default)]
+    notes: Option<String>,
+    datasets: Vec<DatasetSpec>,
+    #[serde(default)]
+    grids: Vec<GridSpec>,
+    /// Omitted: the default buttons of the screen type
+    #[serde(default)]
+    actions: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DatasetSpec {
+    id: String,
+    #[serde(default)]
+    table: Option<String>,
+    columns: Vec<ColumnSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ColumnSpec {
+    name: String,
+    /// Defaults to the column name
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default, rename = "type")]
+    data_type: Option<DataType>,
+    /// Defaults by data type (date → date_picker, integer → number, ...)
+    #[serde(default)]
+    ui: Option<UiType>,
+    #[serde(default)]
+    pk: bool,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    readonly: bool,
+    #[serde(default)]
+    max_length: Option<u32>,
+    #[serde(default)]
+    validation: Option<String>,
+    #[serde(default)]
+    sensitive: Option<SensitiveKind>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GridSpec {
+    /// Defaults to `grid_<dataset without ds_>`
+    #[serde(default)]
+    id: Option<String>,
+    dataset: String,
+    /// Column names or `{ name, header, width, align, sortable, filterable }`;
+    /// omitted: every visible dataset column
+    #[serde(default)]
+    columns: Option<Vec<Value>>,
+    #[serde(default)]
+    editable: bool,
+    #[serde(default)]
+    selectable: Option<bool>,
+    /// `0` turns paging off
+    #[serde(default)]
+    page_size: Option<u32>,
+    #[serde(default)]
+    requires_paging: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GridColumnSpec {
+    name: String,
+    #[serde(default)]
+    header: Option<String>,
+    #[serde(default)]
+    width: Option<String>,
+    #[serde(default)]
+    align: Option<Alignment>,
+    #[serde(default)]
+    sortable: Option<bool>,
+    #[serde(default)]
+    filterable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ActionSpec {
+    id: String,
+    #[serde(default)]
+    label: Option<String>,
+    /// Defaults to the shorthand's type for known ids, else `custom`
+    #[serde(default, rename = "type")]
+    action_type: Option<ActionType>,
+    #[serde(default)]
+    function: Option<String>,
+    #[serde(default)]
+    position: Option<ActionPosition>,
+}
+
+/// Parses screen DSL specs into UI intents
+pub struct ScreenDsl;
+
+impl ScreenDsl {
+    /// Parse a YAML spec into the intent it describes
+    pub fn parse(yaml: &str) -> Result<UiIntent> {
+        if yaml.trim().is_empty() {
+            return Err(anyhow!("Invalid screen DSL: the spec is empty"));
+        }
+        let spec: ScreenSpec =
+            serde_yaml::from_str(yaml).map_err(|e| anyhow!("Invalid screen DSL: {}", e))?;
+
+        let mut errors = Vec::new();
+        let intent = Self::build(spec, &mut errors);
+        if errors.is_empty() {
+            Ok(intent)
+        } else {
+            Err(anyhow!("Invalid screen DSL: {}", errors.join("; ")))
+        }
+    }
+
+    fn build(spec: ScreenSpec, errors: &mut Vec<String>) -> UiIntent {
+        if !is_identifier(&spec.screen) {
+            errors.push(format!("screen: '{}' must be letters, digits and '_'", spec.screen));
+        }
+        if spec.screen_type == ScreenType::Dashboard {
+            errors.push("type: dashboards are generated from the `dashboard` input (aggregate queries)".to_string());
+        }
+        if spec.datasets.is_empty() {
+            errors.push("datasets: at least one dataset is required".to_string());
+        }
+
+        let mut intent = UiIntent::new(&spec.screen, spec.screen_type);
+        intent.notes = spec.notes;
+
+        let mut dataset_ids = HashSet::new();
+        for (i, ds) in spec.datasets.into_iter().enumerate() {
+            let path = format!("datasets[{}]", i);
+            if !is_identifier(&ds.id) {
+                errors.push(format!("{}.id: '{}' must be letters, digits and '_'", path, ds.id));
+            } else if !dataset_ids.insert(ds.id.clone()) {
+                errors.push(format!("{}.id: dataset '{}' is declared twice", path, ds.id));
+            }
+            if ds.columns.is_empty() {
+                errors.push(format!("{}.columns: dataset '{}' has no columns", path, ds.id));
+            }
+
+            let mut names = HashSet::new();
+            let mut dataset = DatasetIntent::new(&ds.id);
+            dataset.table_name = ds.table;
+            for (j, col) in ds.columns.into_iter().enumerate() {
+                if !names.insert(col.name.clone()) {
+                    errors.push(format!("{}.columns[{}]: column '{}' is declared twice", path, j, col.name));
+                }
+                dataset.columns.push(Self::column(col));
+            }
+            intent.datasets.push(dataset);
+        }
+
+        let mut grid_ids = HashSet::new();
+        for (i, grid_spec) in spec.grids.into_iter().enumerate() {
+            if let Some(grid) = Self::grid(&intent.datasets, grid_spec, &format!("grids[{}]", i), errors) {
+                if !grid_ids.insert(grid.id.clone()) {
+                    errors.push(format!("grids[{}].id: grid '{}' is declared twice", i, grid.id));
+                }
+                intent.grids.push(grid);
+            }
+        }
+
+        intent.actions = match spec.actions {
+            None => default_actions_for_screen_type(spec.screen_type),
+            Some(actions) => {
+                let mut ids = HashSet::new();
+                let mut intents = Vec::new();
+                for (i, value) in actions.into_iter().enumerate() {
+                    let path = format!("actions[{}]", i);
+                    let Some(action) = Self::action(value, &path, errors) else {
+                        continue;
+                    };
+                    if !ids.insert(action.id.clone()) {
+                        errors.push(format!("{}: action '{}' is declared twice", path, action.id));
+                        continue;
+                    }
+                    intents.push(action);
+                }
+                intents
+            }
+        };
+        intent
+    }
+
+    fn column(spec: ColumnSpec) -> ColumnIntent {
+        let data_type = spec.data_type.unwrap_or(DataType::String);
+        let mut column = ColumnIntent::new(&spec.name, spec.label.as_deref().unwrap_or(&spec.name))
+            .with_data_type(data_type)
+            .with_ui_type(default_ui_type(data_type))
+            .with_sensitivity(spec.sensitive);
+        if spec.pk {
+            column = column.primary_key();
+        }
+        if let Some(ui) = spec.ui {
+            column.ui_type = ui;
+        }
+        column.required = spec.required;
+        column.readonly = column.readonly || spec.readonly;
+        column.max_length = spec.max_length;
+        column.validation = spec.validation;
+        column
+    }
+
+    fn grid(datasets: &[DatasetIntent], spec: GridSpec, path: &str, errors: &mut Vec<String>) -> Option<GridIntent> {
+        let Some(dataset) = datasets.iter().find(|ds| ds.id == spec.dataset) else {
+            let declared: Vec<&str> = datasets.iter().map(|ds| ds.id.as_str()).collect();
+            errors.push(format!(
+                "{}.dataset: '{}' is not declared (declared: {})",
+                path,
+                spec.dataset,
+                declared.join(", ")
+            ));
+            return None;
+        };
+
+        let id = spec
+            .id
+            .unwrap_or_else(|| format!("grid_{}", dataset.id.strip_prefix("ds_").unwrap_or(&dataset.id)));
+        let mut grid = GridIntent::new(id, &dataset.id);
+        grid.columns = match spec.columns {
+            None => dataset
+                .columns
+                .iter()
+                .filter(|c| c.ui_type != UiType::Hidden)
+                .map(|c| GridColumnIntent::new(&c.name, &c.label))
+                .collect(),
+            Some(columns) => columns
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, value)| Self::grid_column(dataset, value, &format!("{}.columns[{}]", path, i), errors))
+                .collect(),
+        };
+        if spec.editable {
+            grid = grid.editable();
+        }
+        grid.selectable = spec.selectable.unwrap_or(true);
+        match spec.page_size {
+            Some(0) => grid = grid.not_paginated(),
+            Some(size) => grid.page_size = Some(size),
+            None => {}
+        }
+        if spec.requires_paging {
+            if spec.page_size == Some(0) {
+                errors.push(format!("{}: requires_paging can't be combined with page_size: 0", path));
+            }
+            grid = grid.require_paging();
+        }
+        Some(grid)
+    }
+
+    fn grid_column(
+        dataset: &DatasetIntent,
+        value: Value,
+        path: &str,
+        errors: &mut Vec<String>,
+    ) -> Option<GridColumnIntent> {
+        let spec = match value {
+            Value::String(name) => GridColumnSpec {
+                name,
+                header: None,
+                width: None,
+                align: None,
+                sortable: None,
+                filterable: false,
+            },
+            value => match serde_yaml::from_value::<GridColumnSpec>(value) {
+                Ok(spec) => spec,
+                Err(e) => {
+                    errors.push(format!("{}: {}", path, e));
+                    return None;
+                }
+            },
+        };
+        let Some(column) = dataset.columns.iter().find(|c| c.name == spec.name) else {
+            errors.push(format!("{}: '{}' is not a column of {}", path, spec.name, dataset.id));
+            return None;
+        };
+
+        let mut grid_column = GridColumnIntent::new(&column.name, spec.header.as_deref().unwrap_or(&column.label));
+        grid_column.width = spec.width;
+        grid_column.align = spec.align.unwrap_or(match column.data_type {
+            DataType::Integer | DataType::Decimal => Alignment::Right,
+            DataType::Date | DataType::DateTime | DataType::Boolean => Alignment::Center,
+            _ => Alignment::Left,
+        });
+        grid_column.sortable = spec.sortable.unwrap_or(true);
+        grid_column.filterable = spec.filterable;
+        Some(grid_column)
+    }
+
+    fn action(value: Value, path: &str, errors: &mut Vec<String>) -> Option<ActionIntent> {
+        let spec = match value {
+            Value::String(id) => {
+                let Some((id, label, action_type)) = ACTION_SHORTHANDS.iter().find(|(known, _, _)| *known == id) else {
+                    let known: Vec<&str> = ACTION_SHORTHANDS.iter().map(|(id, _, _)| *id).collect();
+                    errors.push(format!(
+                        "{}: unknown action '{}' (shorthands: {}; write {{ id, label, type }} for others)",
+                        path,
+                        id,
+                        known.join(", ")
+                    ));
+                    return None;
+                };
+                return Some(ActionIntent::new(*id, *label, *action_type));
+            }
+            value => match serde_yaml::from_value::<ActionSpec>(value) {
+                Ok(spec) => spec,
+                Err(e) => {
+                    errors.push(format!("{}: {}", path, e));
+                    return None;
+                }
+            },
+        };
+        if !is_identifier(&spec.id) {
+            errors.push(format!("{}.id: '{}' must be letters, digits and '_'", path, spec.id));
+            return None;
+        }
+
+        let shorthand = ACTION_SHORTHANDS.iter().find(|(known, _, _)| *known == spec.id);
+        let label = spec
+            .label
+            .or_else(|| shorthand.map(|(_, label, _)| label.to_string()))
+            .unwrap_or_else(|| spec.id.clone());
+        let action_type = spec
+            .action_type
+            .or_else(|| shorthand.map(|(_, _, action_type)| *action_type))
+            .unwrap_or(ActionType::Custom);
+        let mut action = ActionIntent::new(&spec.id, label, action_type);
+        if let Some(function) = spec.function {
+            action = action.with_function(function);
+        }
+        if let Some(position) = spec.position {
+            action.position = position;
+        }
+        Some(action)
+    }
+}
+
+/// UI control for a column without `ui`
+fn default_ui_type(data_type: DataType) -> UiType {
+    match data_type {
+        DataType::Integer | DataType::Decimal => UiType::Number,
+        DataType::Boolean => UiType::Checkbox,
+        DataType::Date => UiType::DatePicker,
+        DataType::DateTime => UiType::DateTimePicker,
+        DataType::Text => UiType::TextArea,
+        DataType::Binary => UiType::File,
+        DataType::String => UiType::Input,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMBER_LIST: &str = r#"
+screen: member_list
+type: list_with_popup
+notes: 탈퇴 회원 제외
+datasets:
+  - id: ds_member
+    table: TB_MEMBER
+    columns:
+      - { name: MEMBER_ID, label: 회원ID, pk: true }
+      - { name: MEMBER_NAME, label: 회원명, required: true, max_length: 50 }
+      - { name: JOIN_DATE, label: 가입일, type: date }
+      - { name: POINT, label: 포인트, type: integer }
+      - { name: PHONE, label: 연락처, sensitive: phone }
+grids:
+  - dataset: ds_member
+    page_size: 50
+    columns: [MEMBER_NAME, { name: JOIN_DATE, width: "100" }, POINT, PHONE]
+actions: [search, add, { id: approve, label: 승인 }]
+"#;
+
+    #[test]
+    fn test_spec_maps_to_intent() {
+        let intent = ScreenDsl::parse(MEMBER_LIST).unwrap();
+        assert_eq!(intent.screen_name, "member_list");
+        assert_eq!(intent.screen_type, ScreenType::ListWithPopup);
+        assert_eq!(intent.notes.as_deref(), Some("탈퇴 회원 제외"));
+
+        let ds = &intent.datasets[0];
+        assert_eq!(ds.table_name.as_deref(), Some("TB_MEMBER"));
+        assert!(ds.columns[0].is_pk && ds.columns[0].ui_type == UiType::Hidden);
+        assert!(ds.columns[1].required);
+        assert_eq!(ds.columns[1].max_length, Some(50));
+        assert_eq!(ds.columns[2].ui_type, UiType::DatePicker);
+        assert_eq!(ds.columns[4].sensitivity, Some(SensitiveKind::Phone));
+
+        let grid = &intent.grids[0];
+        assert_eq!(grid.id, "grid_member");
+        assert_eq!(grid.page_size, Some(50));
+        let headers: Vec<&str> = grid.columns.iter().map(|c| c.header.as_str()).collect();
+        assert_eq!(headers, vec!["회원명", "가입일", "포인트", "연락처"]);
+        assert_eq!(grid.columns[1].width.as_deref(), Some("100"));
+        assert_eq!(grid.columns[2].align, Alignment::Right);
+
+        let actions: Vec<(&str, ActionType)> = intent.actions.iter().map(|a| (a.id.as_str(), a.action_type)).collect();
+        assert_eq!(
+            actions,
+            vec![("search", ActionType::Search), ("add", ActionType::Add), ("approve", ActionType::Custom)]
+        );
+        assert_eq!(intent.actions[0].label, "조회");
+    }
+
+    #[test]
+    fn test_unknown_keys_and_values_are_located() {
+        let yaml = "screen: member_list\ntype: list\ndatasets:\n  - id: ds_member\n    colums: []\n";
+        let err = ScreenDsl::parse(yaml).unwrap_err().to_string();
+        assert!(err.contains("unknown field `colums`"), "{}", err);
+        assert!(err.contains("line 5"), "{}", err);
+
+        let yaml = "screen: member_list\ntype: grid\ndatasets: []\n";
+        let err = ScreenDsl::parse(yaml).unwrap_err().to_string();
+        assert!(err.contains("unknown variant `grid`"), "{}", err);
+        assert!(err.contains("list_with_popup"), "{}", err);
+    }
+
+    #[test]
+    fn test_semantic_errors_reported_together() {
+        let yaml = r#"
+screen: member_list
+type: list
+datasets:
+  - id: ds_member
+    columns: [{ name: MEMBER_ID }, { name: MEMBER_ID }]
+grids:
+  - dataset: ds_membr
+  - dataset: ds_member
+    columns: [MEMBER_NAME]
+actions: [search, approve]
+"#;
+        let err = ScreenDsl::parse(yaml).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Invalid screen DSL: datasets[0].columns[1]: column 'MEMBER_ID' is declared twice; \
+             grids[0].dataset: 'ds_membr' is not declared (declared: ds_member); \
+             grids[1].columns[0]: 'MEMBER_NAME' is not a column of ds_member; \
+             actions[1]: unknown action 'approve' (shorthands: search, add, save, delete, close, export, print, refresh; \
+             write { id, label, type } for others)"
+        );
+    }
+}
@@ -0,0 +1,445 @@
+//! Screen Naming Registry
+//!
+//! Screen ids and file names delivered per project, so two developers of a
+//! team do not both ship a `SCREEN_MEMBER_LIST`. Every generation with a
+//! `context.project` registers the screens it produced (main and additional
+//! screens) under the requesting user (the plugin caller, see `ApiCaller`;
+//! anonymous calls of single-company deployments share the system user).
+//! Regenerating one's own screen is not a collision; the registration moves
+//! to the new generation.
+//!
+//! When a generation produces a screen id or file name a teammate already
+//! owns in the project, `SCREEN_COLLISION_POLICY` decides:
+//! - `suffix` (default): the screen gets the first free numeric suffix
+//!   (`SCREEN_MEMBER_LIST_2`, `member_list_2.xml`) and a warning
+//! - `reject`: the generation fails, naming the owner
+//! - `off`: no check (screens are still registered)
+//!
+//! The IDE plugin looks names up before generating (`/agent/screens`).
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use regex::Regex;
+use sea_orm::entity::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::Serialize;
+
+use crate::domain::{GeneratedArtifacts, PrintIntent};
+use crate::models::_entities::screen_registrations::{ActiveModel, Column, Entity, Model};
+use crate::models::_entities::users;
+
+/// Highest suffix tried before giving up
+const MAX_SUFFIX: u32 = 99;
+
+/// What to do when a generated screen name belongs to a teammate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollisionPolicy {
+    Off,
+    #[default]
+    Suffix,
+    Reject,
+}
+
+impl CollisionPolicy {
+    pub fn from_env() -> Self {
+        match std::env::var("SCREEN_COLLISION_POLICY")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "off" | "false" | "0" => Self::Off,
+            "reject" | "block" => Self::Reject,
+            _ => Self::Suffix,
+        }
+    }
+}
+
+/// Names of one generated screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenName {
+    pub screen_id: String,
+    pub xml_filename: Option<String>,
+    pub js_filename: Option<String>,
+}
+
+impl ScreenName {
+    /// Same names with `_n` appended (before the extension of file names)
+    pub fn suffixed(&self, n: u32) -> Self {
+        let file = |name: &String| match name.rsplit_once('.') {
+            Some((stem, ext)) => format!("{}_{}.{}", stem, n, ext),
+            None => format!("{}_{}", name, n),
+        };
+        Self {
+            screen_id: format!("{}_{}", self.screen_id, n),
+            xml_filename: self.xml_filename.as_ref().map(file),
+            js_filename: self.js_filename.as_ref().map(file),
+        }
+    }
+
+    /// Whether a registration holds any of the names
+    fn overlaps(&self, registration: &Model) -> bool {
+        let same = |ours: &Option<String>, theirs: &Option<String>| ours.is_some() && ours == theirs;
+        registration.screen_id == self.screen_id
+            || same(&self.xml_filename, &registration.xml_filename)
+            || same(&self.js_filename, &registration.js_filename)
+    }
+}
+
+/// Registered screen and who it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenOwner {
+    pub screen_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml_filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub js_filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_id: Option<i32>,
+    pub user_id: i32,
+    /// Name of the user
+    pub owner: String,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// Whether a name is free for the caller
+#[derive(Debug, Clone, Serialize)]
+pub struct NameCheck {
+    pub available: bool,
+    /// Teammate's screen holding the name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taken_by: Option<ScreenOwner>,
+    /// First free suffixed screen id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+pub struct ScreenRegistry;
+
+impl ScreenRegistry {
+    /// `id` of the root `<screen>` element
+    pub fn screen_id(xml: &str) -> Option<String> {
+        Regex::new(r#"(?i)<screen\b[^>]*?\bid\s*=\s*"([^"]+)""#)
+            .ok()?
+            .captures(xml)
+            .map(|caps| caps[1].to_string())
+    }
+
+    /// XML with the root `<screen>` id replaced
+    fn with_screen_id(xml: &str, id: &str) -> String {
+        let re = Regex::new(r#"(?i)(<screen\b[^>]*?\bid\s*=\s*")([^"]+)(")"#).unwrap();
+        re.replace(xml, |caps: &regex::Captures| format!("{}{}{}", &caps[1], id, &caps[3]))
+            .into_owned()
+    }
+
+    /// Main and additional screens of a generation (screens without an id are skipped)
+    pub fn names(artifacts: &GeneratedArtifacts) -> Vec<ScreenName> {
+        let main = artifacts.xml.as_deref().and_then(Self::screen_id).map(|screen_id| ScreenName {
+            screen_id,
+            xml_filename: artifacts.xml_filename.clone(),
+            js_filename: artifacts.js_filename.clone(),
+        });
+        let additional = artifacts.additional_screens.iter().filter_map(|screen| {
+            let screen_id = screen.screen_id.clone().or_else(|| Self::screen_id(&screen.xml))?;
+            Some(ScreenName {
+                screen_id,
+                xml_filename: Some(screen.xml_filename.clone()),
+                js_filename: Some(screen.js_filename.clone()),
+            })
+        });
+        main.into_iter().chain(additional).collect()
+    }
+
+    /// A teammate's registration holding any of the names
+    pub fn conflict<'a>(name: &ScreenName, registrations: &'a [Model], user_id: i32) -> Option<&'a Model> {
+        registrations.iter().find(|r| r.user_id != user_id && name.overlaps(r))
+    }
+
+    /// The names, or the first suffixed variant no teammate holds
+    pub fn free_name(name: &ScreenName, registrations: &[Model], user_id: i32) -> Option<ScreenName> {
+        if Self::conflict(name, registrations, user_id).is_none() {
+            return Some(name.clone());
+        }
+        (2..=MAX_SUFFIX)
+            .map(|n| name.suffixed(n))
+            .find(|candidate| Self::conflict(candidate, registrations, user_id).is_none())
+    }
+
+    /// Registrations of a project
+    pub async fn registrations(db: &DatabaseConnection, project: &str, company: Option<&str>) -> Result<Vec<Model>> {
+        let items = Entity::find()
+            .filter(Column::Project.eq(project))
+            .filter(Self::company_condition(company))
+            .order_by_asc(Column::ScreenId)
+            .all(db)
+            .await?;
+        Ok(items)
+    }
+
+    /// Apply the collision policy to freshly generated artifacts
+    ///
+    /// Returns warnings about renamed screens; fails when the policy rejects
+    /// a collision.
+    pub async fn resolve(
+        db: &DatabaseConnection,
+        project: &str,
+        company: Option<&str>,
+        user_id: i32,
+        artifacts: &mut GeneratedArtifacts,
+        policy: CollisionPolicy,
+    ) -> Result<Vec<String>> {
+        if policy == CollisionPolicy::Off {
+            return Ok(Vec::new());
+        }
+        let registrations = Self::registrations(db, project, company).await?;
+        let mut warnings = Vec::new();
+
+        for name in Self::names(artifacts) {
+            let Some(taken) = Self::conflict(&name, &registrations, user_id) else {
+                continue;
+            };
+            let owner = Self::owner_names(db, &[taken.user_id]).await.remove(&taken.user_id).unwrap_or_default();
+            let collision = format!(
+                "Screen {} is already delivered in project {} by {}{}",
+                name.screen_id,
+                project,
+                if owner.is_empty() { format!("user {}", taken.user_id) } else { owner },
+                taken.generation_id.map(|id| format!(" (generation {})", id)).unwrap_or_default()
+            );
+            if policy == CollisionPolicy::Reject {
+                bail!("{}; pick another screen name or ask the owner to hand it over", collision);
+            }
+            let Some(free) = Self::free_name(&name, &registrations, user_id) else {
+                bail!("{}, and no free suffix is left", collision);
+            };
+            Self::rename(artifacts, &name, &free);
+            warnings.push(format!("Warning: {}; renamed to {}", collision, free.screen_id));
+        }
+        Ok(warnings)
+    }
+
+    /// Put `to`'s names on the screen currently named `from`
+    fn rename(artifacts: &mut GeneratedArtifacts, from: &ScreenName, to: &ScreenName) {
+        if artifacts.xml.as_deref().and_then(Self::screen_id).as_ref() == Some(&from.screen_id) {
+            artifacts.xml = artifacts.xml.as_deref().map(|xml| Self::with_screen_id(xml, &to.screen_id));
+            artifacts.xml_filename = to.xml_filename.clone();
+            artifacts.js_filename = to.js_filename.clone();
+            if artifacts.print_layout_filename.is_some() {
+                artifacts.print_layout_filename = to.xml_filename.as_deref().map(PrintIntent::layout_filename);
+            }
+            return;
+        }
+        for screen in &mut artifacts.additional_screens {
+            if Some(&screen.xml_filename) == from.xml_filename.as_ref() {
+                screen.xml = Self::with_screen_id(&screen.xml, &to.screen_id);
+                screen.screen_id = Some(to.screen_id.clone());
+                screen.xml_filename = to.xml_filename.clone().unwrap_or_default();
+                screen.js_filename = to.js_filename.clone().unwrap_or_default();
+            }
+        }
+    }
+
+    /// Record the screens of a logged generation under its user
+    pub async fn register(
+        db: &DatabaseConnection,
+        project: &str,
+        company: Option<&str>,
+        user_id: i32,
+        generation_id: i32,
+        artifacts: &GeneratedArtifacts,
+    ) -> Result<()> {
+        let registrations = Self::registrations(db, project, company).await?;
+        for name in Self::names(artifacts) {
+            let own = registrations
+                .iter()
+                .find(|r| r.user_id == user_id && r.screen_id == name.screen_id);
+            match own {
+                Some(own) => {
+                    let mut item: ActiveModel = own.clone().into();
+                    item.xml_filename = Set(name.xml_filename);
+                    item.js_filename = Set(name.js_filename);
+                    item.generation_id = Set(Some(generation_id));
+                    item.update(db).await?;
+                }
+                None => {
+                    ActiveModel {
+                        company: Set(company.map(str::to_string)),
+                        project: Set(project.to_string()),
+                        screen_id: Set(name.screen_id),
+                        xml_filename: Set(name.xml_filename),
+                        js_filename: Set(name.js_filename),
+                        generation_id: Set(Some(generation_id)),
+                        user_id: Set(user_id),
+                        ..Default::default()
+                    }
+                    .insert(db)
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Screens registered in a project, with their owners
+    pub async fn list(db: &DatabaseConnection, project: &str, company: Option<&str>) -> Result<Vec<ScreenOwner>> {
+        let registrations = Self::registrations(db, project, company).await?;
+        let user_ids: Vec<i32> = registrations.iter().map(|r| r.user_id).collect();
+        let owners = Self::owner_names(db, &user_ids).await;
+        Ok(registrations
+            .into_iter()
+            .map(|r| Self::owner(r, &owners))
+            .collect())
+    }
+
+    /// Whether the caller may use a screen id or file name in a project
+    pub async fn check(
+        db: &DatabaseConnection,
+        project: &str,
+        company: Option<&str>,
+        user_id: i32,
+        screen_id: Option<&str>,
+        file: Option<&str>,
+    ) -> Result<NameCheck> {
+        let registrations = Self::registrations(db, project, company).await?;
+        let taken = registrations.iter().find(|r| {
+            r.user_id != user_id
+                && (screen_id.is_some_and(|id| r.screen_id == id)
+                    || file.is_some_and(|f| r.xml_filename.as_deref() == Some(f) || r.js_filename.as_deref() == Some(f)))
+        });
+        let Some(taken) = taken.cloned() else {
+            return Ok(NameCheck {
+                available: true,
+                taken_by: None,
+                suggestion: None,
+            });
+        };
+
+        let suggestion = screen_id.and_then(|id| {
+            let name = ScreenName {
+                screen_id: id.to_string(),
+                xml_filename: None,
+                js_filename: None,
+            };
+            Self::free_name(&name, &registrations, user_id).map(|free| free.screen_id)
+        });
+        let owners = Self::owner_names(db, &[taken.user_id]).await;
+        Ok(NameCheck {
+            available: false,
+            taken_by: Some(Self::owner(taken, &owners)),
+            suggestion,
+        })
+    }
+
+    fn owner(registration: Model, owners: &HashMap<i32, String>) -> ScreenOwner {
+        ScreenOwner {
+            owner: owners.get(&registration.user_id).cloned().unwrap_or_default(),
+            screen_id: registration.screen_id,
+            xml_filename: registration.xml_filename,
+            js_filename: registration.js_filename,
+            generation_id: registration.generation_id,
+            user_id: registration.user_id,
+            updated_at: registration.updated_at,
+        }
+    }
+
+    async fn owner_names(db: &DatabaseConnection, user_ids: &[i32]) -> HashMap<i32, String> {
+        users::Entity::find()
+            .filter(users::Column::Id.is_in(user_ids.iter().copied()))
+            .all(db)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|u| (u.id, u.name))
+            .collect()
+    }
+
+    /// Projects are per company; platform generations share the NULL company
+    fn company_condition(company: Option<&str>) -> Condition {
+        match company {
+            Some(company) => Condition::all().add(Column::Company.eq(company)),
+            None => Condition::all().add(Column::Company.is_null()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScreenArtifact;
+
+    fn registration(user_id: i32, screen_id: &str, xml_filename: &str) -> Model {
+        let now = chrono::Utc::now().into();
+        Model {
+            created_at: now,
+            updated_at: now,
+            id: user_id,
+            company: None,
+            project: "erp".to_string(),
+            screen_id: screen_id.to_string(),
+            xml_filename: Some(xml_filename.to_string()),
+            js_filename: None,
+            generation_id: Some(10),
+            user_id,
+        }
+    }
+
+    fn artifacts() -> GeneratedArtifacts {
+        GeneratedArtifacts {
+            xml: Some("<screen id=\"SCREEN_MEMBER_LIST\" width=\"1024\">\n</screen>".to_string()),
+            javascript: Some("this.fn_init = function() {};".to_string()),
+            xml_filename: Some("member_list.xml".to_string()),
+            js_filename: Some("member_list.js".to_string()),
+            additional_screens: vec![ScreenArtifact {
+                screen_id: Some("SCREEN_MEMBER_POP".to_string()),
+                xml: "<screen id=\"SCREEN_MEMBER_POP\"/>".to_string(),
+                javascript: String::new(),
+                xml_filename: "member_pop.xml".to_string(),
+                js_filename: "member_pop.js".to_string(),
+            }],
+            print_layout: None,
+            print_layout_filename: None,
+            accessibility_report: None,
+            test_data: None,
+            test_data_filename: None,
+        }
+    }
+
+    #[test]
+    fn test_free_name_skips_teammates_only() {
+        let names = ScreenRegistry::names(&artifacts());
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0].screen_id, "SCREEN_MEMBER_LIST");
+
+        let registrations = vec![
+            registration(2, "SCREEN_MEMBER_LIST", "member_list.xml"),
+            registration(3, "SCREEN_MEMBER_LIST_2", "member_list_2.xml"),
+            registration(1, "SCREEN_MEMBER_LIST_3", "member_list_3.xml"),
+        ];
+        let free = ScreenRegistry::free_name(&names[0], &registrations, 1).unwrap();
+        assert_eq!(free.screen_id, "SCREEN_MEMBER_LIST_3");
+        assert_eq!(free.js_filename.as_deref(), Some("member_list_3.js"));
+
+        // The owner regenerating its own screen keeps the names
+        assert_eq!(ScreenRegistry::free_name(&names[0], &registrations, 2), Some(names[0].clone()));
+        // A taken file name alone is a collision too
+        let renamed = ScreenName { screen_id: "SCREEN_MEMBERS".to_string(), ..names[0].clone() };
+        assert!(ScreenRegistry::conflict(&renamed, &registrations, 1).is_some());
+    }
+
+    #[test]
+    fn test_rename_rewrites_screen_id_and_files() {
+        let mut generated = artifacts();
+        let names = ScreenRegistry::names(&generated);
+        ScreenRegistry::rename(&mut generated, &names[1], &names[1].suffixed(2));
+        ScreenRegistry::rename(&mut generated, &names[0], &names[0].suffixed(2));
+
+        assert!(generated.xml.unwrap().starts_with("<screen id=\"SCREEN_MEMBER_LIST_2\" width=\"1024\">"));
+        assert_eq!(generated.js_filename.as_deref(), Some("member_list_2.js"));
+        let popup = &generated.additional_screens[0];
+        assert_eq!(popup.xml, "<screen id=\"SCREEN_MEMBER_POP_2\"/>");
+        assert_eq!(popup.xml_filename, "member_pop_2.xml");
+    }
+}
@@ -0,0 +1,420 @@
+//! Generation Timeline and SLA
+//!
+//! Queued jobs carry one timestamp per stage:
+//! `queued → started → llm_start → llm_end → pipeline_end → completed`.
+//! The worker installs a recorder around each job; generation services mark
+//! the LLM and pipeline stages with [`mark`] and report the job's token usage
+//! with [`llm_usage`], which do nothing outside a recorder (synchronous API
+//! requests).
+//!
+//! SLA compliance is measured over completed jobs, from queue entry to
+//! completion, against targets from the environment:
+//! - `SLA_TARGET_SECS`: per-screen total (default 180, the contracted 3 minutes)
+//! - `SLA_QUEUE_TARGET_SECS`: optional limit on queue wait
+//! - `SLA_LLM_TARGET_SECS`: optional limit on LLM time
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::Set;
+use serde::Serialize;
+
+use crate::llm::LlmUsage;
+use crate::models::_entities::generation_logs;
+
+/// Breaches listed on the dashboard
+const MAX_BREACHES: usize = 10;
+
+/// Stage boundary marked by the generation services
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// First LLM call starts
+    LlmStart,
+    /// Last LLM call (including retries) returned
+    LlmEnd,
+    /// Post-processing or validation finished
+    PipelineEnd,
+}
+
+/// Stage timestamps recorded while a job ran
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StageTimes {
+    pub llm_started_at: Option<DateTime<Utc>>,
+    pub llm_finished_at: Option<DateTime<Utc>>,
+    pub pipeline_finished_at: Option<DateTime<Utc>>,
+    /// Tokens and time of the job's LLM calls
+    pub llm_usage: Option<LlmUsage>,
+}
+
+impl StageTimes {
+    fn mark_at(&mut self, stage: Stage, at: DateTime<Utc>) {
+        match stage {
+            Stage::LlmStart => {
+                self.llm_started_at.get_or_insert(at);
+            }
+            Stage::LlmEnd => self.llm_finished_at = Some(at),
+            Stage::PipelineEnd => self.pipeline_finished_at = Some(at),
+        }
+    }
+
+    /// Copy the recorded timestamps onto a generation log update
+    pub fn apply(&self, job: &mut generation_logs::ActiveModel) {
+        if let Some(at) = self.llm_started_at {
+            job.llm_started_at = Set(Some(at.into()));
+        }
+        if let Some(at) = self.llm_finished_at {
+            job.llm_finished_at = Set(Some(at.into()));
+        }
+        if let Some(at) = self.pipeline_finished_at {
+            job.pipeline_finished_at = Set(Some(at.into()));
+        }
+        if let Some(usage) = &self.llm_usage {
+            usage.apply(job);
+        }
+    }
+}
+
+tokio::task_local! {
+    static TIMELINE: Arc<Mutex<StageTimes>>;
+}
+
+/// Mark a stage boundary of the job running on this task
+pub fn mark(stage: Stage) {
+    let _ = TIMELINE.try_with(|times| {
+        if let Ok(mut times) = times.lock() {
+            times.mark_at(stage, Utc::now());
+        }
+    });
+}
+
+/// Report the token usage of the job running on this task
+pub fn llm_usage(usage: LlmUsage) {
+    let _ = TIMELINE.try_with(|times| {
+        if let Ok(mut times) = times.lock() {
+            times.llm_usage = Some(usage);
+        }
+    });
+}
+
+/// Run `fut` with a stage recorder installed
+pub async fn record<F: Future>(fut: F) -> (F::Output, StageTimes) {
+    let times = Arc::new(Mutex::new(StageTimes::default()));
+    let output = TIMELINE.scope(times.clone(), fut).await;
+    let times = times.lock().map(|t| t.clone()).unwrap_or_default();
+    (output, times)
+}
+
+/// SLA targets, in seconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SlaConfig {
+    pub total_secs: i64,
+    pub queue_secs: Option<i64>,
+    pub llm_secs: Option<i64>,
+}
+
+impl Default for SlaConfig {
+    fn default() -> Self {
+        Self {
+            total_secs: 180,
+            queue_secs: None,
+            llm_secs: None,
+        }
+    }
+}
+
+impl SlaConfig {
+    /// Load from environment
+    pub fn from_env() -> Self {
+        let secs = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|v| *v > 0)
+        };
+
+        Self {
+            total_secs: secs("SLA_TARGET_SECS").unwrap_or(180),
+            queue_secs: secs("SLA_QUEUE_TARGET_SECS"),
+            llm_secs: secs("SLA_LLM_TARGET_SECS"),
+        }
+    }
+
+    /// Targets the timeline misses (`total`, `queue`, `llm`)
+    pub fn missed(&self, timeline: &JobTimeline) -> Vec<&'static str> {
+        let over = |ms: Option<i64>, secs: Option<i64>| match (ms, secs) {
+            (Some(ms), Some(secs)) => ms > secs * 1000,
+            _ => false,
+        };
+
+        let mut missed = Vec::new();
+        if over(timeline.total_ms, Some(self.total_secs)) {
+            missed.push("total");
+        }
+        if over(timeline.queue_ms, self.queue_secs) {
+            missed.push("queue");
+        }
+        if over(timeline.llm_ms, self.llm_secs) {
+            missed.push("llm");
+        }
+        missed
+    }
+}
+
+/// Stage durations of one job, in milliseconds (`None` when not recorded)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct JobTimeline {
+    /// Queued until a worker claimed it
+    pub queue_ms: Option<i64>,
+    /// Claimed until the first LLM call (normalizing, prompt compilation)
+    pub prepare_ms: Option<i64>,
+    /// LLM calls, including retries
+    pub llm_ms: Option<i64>,
+    /// Post-processing and validation
+    pub pipeline_ms: Option<i64>,
+    /// Scoring and storing the result
+    pub finish_ms: Option<i64>,
+    /// Queued until completed
+    pub total_ms: Option<i64>,
+}
+
+impl JobTimeline {
+    pub fn of(job: &generation_logs::Model) -> Self {
+        let between = |from: Option<DateTime<FixedOffset>>,
+                       to: Option<DateTime<FixedOffset>>| {
+            Some((to? - from?).num_milliseconds().max(0))
+        };
+
+        Self {
+            queue_ms: between(job.queued_at, job.started_at),
+            prepare_ms: between(job.started_at, job.llm_started_at),
+            llm_ms: between(job.llm_started_at, job.llm_finished_at),
+            pipeline_ms: between(job.llm_finished_at, job.pipeline_finished_at),
+            finish_ms: between(job.pipeline_finished_at, job.completed_at),
+            total_ms: between(job.queued_at, job.completed_at),
+        }
+    }
+
+    /// Longest recorded stage
+    pub fn slowest_stage(&self) -> Option<&'static str> {
+        [
+            ("queue", self.queue_ms),
+            ("prepare", self.prepare_ms),
+            ("llm", self.llm_ms),
+            ("pipeline", self.pipeline_ms),
+            ("finish", self.finish_ms),
+        ]
+        .into_iter()
+        .filter_map(|(name, ms)| ms.map(|ms| (name, ms)))
+        .max_by_key(|(_, ms)| *ms)
+        .map(|(name, _)| name)
+    }
+}
+
+/// A job that missed an SLA target
+#[derive(Debug, Clone, Serialize)]
+pub struct SlaBreach {
+    pub id: i32,
+    pub job_id: Option<String>,
+    pub product: String,
+    pub completed_at: Option<DateTime<FixedOffset>>,
+    pub total_secs: f64,
+    pub missed: Vec<&'static str>,
+    pub slowest_stage: Option<&'static str>,
+    pub timeline: JobTimeline,
+}
+
+/// SLA compliance over a set of completed jobs
+#[derive(Debug, Clone, Serialize)]
+pub struct SlaReport {
+    pub config: SlaConfig,
+    /// Jobs measured (completed, with a queue time)
+    pub jobs: u64,
+    pub met: u64,
+    pub breached: u64,
+    /// Share of jobs meeting every target (0-100, 100 without jobs)
+    pub compliance_rate: f32,
+    /// 95th percentile of queued → completed
+    pub p95_secs: f64,
+    /// Most recent breaches first
+    pub breaches: Vec<SlaBreach>,
+}
+
+impl SlaReport {
+    pub fn from_jobs(config: SlaConfig, jobs: &[generation_logs::Model]) -> Self {
+        let mut totals = Vec::new();
+        let mut breaches = Vec::new();
+
+        for job in jobs {
+            let timeline = JobTimeline::of(job);
+            let Some(total_ms) = timeline.total_ms else {
+                continue;
+            };
+            totals.push(total_ms);
+
+            let missed = config.missed(&timeline);
+            if !missed.is_empty() {
+                breaches.push(SlaBreach {
+                    id: job.id,
+                    job_id: job.job_id.clone(),
+                    product: job.product.clone(),
+                    completed_at: job.completed_at,
+                    total_secs: total_ms as f64 / 1000.0,
+                    missed,
+                    slowest_stage: timeline.slowest_stage(),
+                    timeline,
+                });
+            }
+        }
+
+        let measured = totals.len() as u64;
+        let breached = breaches.len() as u64;
+        totals.sort_unstable();
+        let p95_secs = match totals.len() {
+            0 => 0.0,
+            n => totals[((n * 95).div_ceil(100)).saturating_sub(1)] as f64 / 1000.0,
+        };
+
+        breaches.sort_by_key(|b| std::cmp::Reverse(b.completed_at));
+        breaches.truncate(MAX_BREACHES);
+
+        Self {
+            config,
+            jobs: measured,
+            met: measured - breached,
+            breached,
+            compliance_rate: if measured == 0 {
+                100.0
+            } else {
+                (measured - breached) as f32 / measured as f32 * 100.0
+            },
+            p95_secs,
+            breaches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: i32, stages_secs: [Option<i64>; 6]) -> generation_logs::Model {
+        let base = chrono::DateTime::from_timestamp(1_800_000_000, 0).unwrap().fixed_offset();
+        let at = |i: usize| stages_secs[i].map(|s| base + chrono::Duration::seconds(s));
+        generation_logs::Model {
+            created_at: base,
+            updated_at: base,
+            id,
+            product: "xframe5-ui".to_string(),
+            input_type: "db_schema".to_string(),
+            ui_intent: "{}".to_string(),
+            template_version: 1,
+            status: "success".to_string(),
+            artifacts: None,
+            warnings: None,
+            error_message: None,
+            error_code: None,
+            generation_time_ms: None,
+            user_id: 1,
+            job_id: Some(format!("job-{}", id)),
+            request_payload: None,
+            queued_at: at(0),
+            started_at: at(1),
+            completed_at: at(5),
+            priority: 5,
+            model_name: None,
+            provider: None,
+            quality_score: None,
+            knowledge_revisions: None,
+            company: None,
+            lease_expires_at: None,
+            attempts: 1,
+            llm_started_at: at(2),
+            llm_finished_at: at(3),
+            pipeline_finished_at: at(4),
+            project: None,
+            pipeline_profile: None,
+            pipeline_overrides: None,
+            replay_of: None,
+            issue_key: None,
+            canary_id: None,
+            canary_arm: None,
+            lineage: None,
+            artifacts_expire_at: None,
+            artifacts_purged_at: None,
+            prompt_tokens: None,
+            completion_tokens: None,
+            llm_latency_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_mark_outside_recorder_is_ignored() {
+        mark(Stage::LlmStart);
+    }
+
+    #[tokio::test]
+    async fn test_record_keeps_first_start_and_last_end() {
+        let ((), times) = record(async {
+            mark(Stage::LlmStart);
+            mark(Stage::LlmEnd);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            mark(Stage::LlmStart);
+            mark(Stage::LlmEnd);
+            mark(Stage::PipelineEnd);
+        })
+        .await;
+
+        let start = times.llm_started_at.unwrap();
+        let end = times.llm_finished_at.unwrap();
+        assert!(end - start >= chrono::Duration::milliseconds(5));
+        assert!(times.pipeline_finished_at.unwrap() >= end);
+    }
+
+    #[test]
+    fn test_timeline_stages() {
+        let timeline = JobTimeline::of(&job(1, [Some(0), Some(20), Some(25), Some(145), Some(146), Some(147)]));
+        assert_eq!(timeline.queue_ms, Some(20_000));
+        assert_eq!(timeline.llm_ms, Some(120_000));
+        assert_eq!(timeline.total_ms, Some(147_000));
+        assert_eq!(timeline.slowest_stage(), Some("llm"));
+
+        // Synchronous generations have no stage marks
+        let timeline = JobTimeline::of(&job(2, [Some(0), Some(1), None, None, None, Some(9)]));
+        assert_eq!(timeline.llm_ms, None);
+        assert_eq!(timeline.slowest_stage(), Some("queue"));
+    }
+
+    #[test]
+    fn test_report_compliance() {
+        let config = SlaConfig {
+            queue_secs: Some(60),
+            ..SlaConfig::default()
+        };
+        let jobs = vec![
+            job(1, [Some(0), Some(10), Some(12), Some(100), Some(101), Some(102)]),
+            job(2, [Some(0), Some(5), Some(6), Some(200), Some(201), Some(205)]),
+            job(3, [Some(0), Some(90), Some(91), Some(120), Some(121), Some(122)]),
+            // Not measured: never queued
+            job(4, [None, None, None, None, None, Some(500)]),
+        ];
+
+        let report = SlaReport::from_jobs(config, &jobs);
+        assert_eq!(report.jobs, 3);
+        assert_eq!(report.breached, 2);
+        assert_eq!(report.met, 1);
+        assert!((report.compliance_rate - 33.33).abs() < 0.1);
+        assert_eq!(report.p95_secs, 205.0);
+
+        let missed: Vec<_> = report.breaches.iter().map(|b| (b.id, b.missed.clone())).collect();
+        assert!(missed.contains(&(2, vec!["total"])));
+        assert!(missed.contains(&(3, vec!["queue"])));
+    }
+
+    #[test]
+    fn test_empty_report_is_compliant() {
+        let report = SlaReport::from_jobs(SlaConfig::default(), &[]);
+        assert_eq!(report.compliance_rate, 100.0);
+        assert_eq!(report.p95_secs, 0.0);
+    }
+}
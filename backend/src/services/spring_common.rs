@@ -0,0 +1,216 @@
+//! Shared Spring Classes
+//!
+//! Deterministic sources for the classes every entity of a module shares.
+//! They are rendered here rather than by the LLM so all slices compile
+//! against exactly the same API.
+
+use chrono::Local;
+
+use crate::domain::{NamingProfile, NamingVars, SharedSpringClass, SpringModuleContext};
+
+/// Renders the shared classes of a Spring module
+pub struct SpringCommonClasses;
+
+impl SpringCommonClasses {
+    /// Module context with the shared classes for a base package
+    pub fn build(package_base: &str, response_wrapper: &str, naming: &NamingProfile) -> SpringModuleContext {
+        let common = SpringModuleContext::common_package(package_base);
+        let response_pkg = format!("{}.response", common);
+        let controller_pkg = format!("{}.controller", common);
+        let exception_pkg = format!("{}.exception", common);
+
+        let classes = [
+            (response_wrapper.to_string(), response_pkg.clone(), "response", Self::api_response(&response_pkg, response_wrapper)),
+            (
+                "BaseController".to_string(),
+                controller_pkg.clone(),
+                "controller",
+                Self::base_controller(&controller_pkg, &response_pkg, response_wrapper),
+            ),
+            ("BusinessException".to_string(), exception_pkg.clone(), "exception", Self::business_exception(&exception_pkg)),
+            (
+                "ResourceNotFoundException".to_string(),
+                exception_pkg.clone(),
+                "exception",
+                Self::not_found_exception(&exception_pkg),
+            ),
+            (
+                "GlobalExceptionHandler".to_string(),
+                exception_pkg.clone(),
+                "exception",
+                Self::exception_handler(&exception_pkg, &response_pkg, response_wrapper),
+            ),
+        ];
+
+        let vars = NamingVars::new(Local::now().naive_local());
+        let shared = classes
+            .into_iter()
+            .map(|(name, package, layer, source)| SharedSpringClass {
+                path: naming.java_path(&vars, name.clone(), &package, layer),
+                name,
+                package,
+                source,
+            })
+            .collect();
+
+        SpringModuleContext {
+            package_base: package_base.to_string(),
+            response_wrapper: response_wrapper.to_string(),
+            shared,
+        }
+    }
+
+    fn api_response(package: &str, name: &str) -> String {
+        format!(
+            r#"package {package};
+
+public class {name}<T> {{
+
+    private final boolean success;
+    private final String message;
+    private final T data;
+
+    private {name}(boolean success, String message, T data) {{
+        this.success = success;
+        this.message = message;
+        this.data = data;
+    }}
+
+    public static <T> {name}<T> ok(T data) {{
+        return new {name}<>(true, null, data);
+    }}
+
+    public static <T> {name}<T> error(String message) {{
+        return new {name}<>(false, message, null);
+    }}
+
+    public boolean isSuccess() {{
+        return success;
+    }}
+
+    public String getMessage() {{
+        return message;
+    }}
+
+    public T getData() {{
+        return data;
+    }}
+}}
+"#
+        )
+    }
+
+    fn base_controller(package: &str, response_pkg: &str, wrapper: &str) -> String {
+        format!(
+            r#"package {package};
+
+import {response_pkg}.{wrapper};
+import org.springframework.http.HttpStatus;
+import org.springframework.http.ResponseEntity;
+
+public abstract class BaseController {{
+
+    protected <T> ResponseEntity<{wrapper}<T>> ok(T data) {{
+        return ResponseEntity.ok({wrapper}.ok(data));
+    }}
+
+    protected <T> ResponseEntity<{wrapper}<T>> created(T data) {{
+        return ResponseEntity.status(HttpStatus.CREATED).body({wrapper}.ok(data));
+    }}
+
+    protected ResponseEntity<{wrapper}<Void>> noContent() {{
+        return ResponseEntity.ok({wrapper}.ok(null));
+    }}
+}}
+"#
+        )
+    }
+
+    fn business_exception(package: &str) -> String {
+        format!(
+            r#"package {package};
+
+public class BusinessException extends RuntimeException {{
+
+    private final String code;
+
+    public BusinessException(String code, String message) {{
+        super(message);
+        this.code = code;
+    }}
+
+    public String getCode() {{
+        return code;
+    }}
+}}
+"#
+        )
+    }
+
+    fn not_found_exception(package: &str) -> String {
+        format!(
+            r#"package {package};
+
+public class ResourceNotFoundException extends BusinessException {{
+
+    public ResourceNotFoundException(String resource, Object id) {{
+        super("NOT_FOUND", resource + " not found: " + id);
+    }}
+}}
+"#
+        )
+    }
+
+    fn exception_handler(package: &str, response_pkg: &str, wrapper: &str) -> String {
+        format!(
+            r#"package {package};
+
+import {response_pkg}.{wrapper};
+import org.springframework.http.HttpStatus;
+import org.springframework.http.ResponseEntity;
+import org.springframework.web.bind.annotation.ExceptionHandler;
+import org.springframework.web.bind.annotation.RestControllerAdvice;
+
+@RestControllerAdvice
+public class GlobalExceptionHandler {{
+
+    @ExceptionHandler(ResourceNotFoundException.class)
+    public ResponseEntity<{wrapper}<Void>> handleNotFound(ResourceNotFoundException e) {{
+        return ResponseEntity.status(HttpStatus.NOT_FOUND).body({wrapper}.error(e.getMessage()));
+    }}
+
+    @ExceptionHandler(BusinessException.class)
+    public ResponseEntity<{wrapper}<Void>> handleBusiness(BusinessException e) {{
+        return ResponseEntity.badRequest().body({wrapper}.error(e.getMessage()));
+    }}
+
+    @ExceptionHandler(Exception.class)
+    public ResponseEntity<{wrapper}<Void>> handleUnexpected(Exception e) {{
+        // TODO: Log the exception with the project's logging setup
+        return ResponseEntity.status(HttpStatus.INTERNAL_SERVER_ERROR).body({wrapper}.error("Internal server error"));
+    }}
+}}
+"#
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_shared_classes() {
+        let context = SpringCommonClasses::build("com.company.erp", "ApiResponse", &NamingProfile::default());
+        let names: Vec<&str> = context.shared.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["ApiResponse", "BaseController", "BusinessException", "ResourceNotFoundException", "GlobalExceptionHandler"]
+        );
+
+        let base = context.find("BaseController").unwrap();
+        assert!(base.source.starts_with("package com.company.erp.common.controller;"));
+        assert!(base.source.contains("import com.company.erp.common.response.ApiResponse;"));
+        assert_eq!(base.path, "src/main/java/com/company/erp/common/controller/BaseController.java");
+    }
+}
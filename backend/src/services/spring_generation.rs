@@ -1,12 +1,20 @@
 use crate::domain::{
-    GenerateInput, GenerateOptions, GenerateStatus, RequestContext, ResponseMeta, SpringArtifacts,
+    GenerateInput, GenerateOptions, GenerateStatus, JavaFormatStyle, NamingProfile, NamingVars, RequestContext,
+    ResponseMeta, SpringArtifacts, SpringIntent, SpringModuleContext,
 };
-use crate::llm::{create_backend_from_db_or_env, create_backend_from_env};
+use crate::llm::{create_backend_from_db_or_env, create_backend_from_env, LlmUsage};
 use crate::models::_entities::generation_logs;
-use crate::services::{SpringNormalizerService, SpringValidator, TemplateService};
+use crate::models::users::SYSTEM_USER_ID;
+use crate::models::{company_rules, naming_profiles, saved_intents};
+use crate::services::{ArtifactScorer, IssueTrackerService, JavaCompileConfig, JavaCompiler, JavaFormatter, JavaSyntaxChecker, LineageService, QueryPlanAdvisor, QueryPlanConfig, Refusal, RetentionService, SpringNormalizerService, SpringValidator, TemplateService, TenantScope, TestDataGenerator};
+use crate::services::refusal::REFUSAL_ERROR_CODE;
+use crate::services::schema_change::SchemaChangeService;
 use crate::services::spring_prompt_compiler::SpringPromptCompiler;
+use crate::services::generation_hooks::{self, FinishedArtifacts, HookContext, NormalizedIntent, PipelineOutcome};
+use crate::services::sla::{self, Stage};
+use crate::services::telemetry::{self, traced, Span, SpanKind};
 use anyhow::{anyhow, Result};
-use chrono::Utc;
+use chrono::{Local, Utc};
 use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
@@ -27,6 +35,10 @@ pub struct SpringGenerateResponse {
     /// Error message (if status is error)
     pub error: Option<String>,
 
+    /// Machine-readable cause of the error (e.g. `provider_refusal`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+
     /// Response metadata
     pub meta: ResponseMeta,
 }
@@ -43,45 +55,159 @@ impl SpringGenerationService {
         context: &RequestContext,
         user_id: Option<i32>,
     ) -> Result<SpringGenerateResponse> {
-        let start = Instant::now();
-
         // Get package base from context or use default
         let package_base = context.project.as_deref().unwrap_or("com.company.project");
 
         // 1. Normalize input to SpringIntent
-        let intent = SpringNormalizerService::normalize(&input, package_base)?;
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+        let intent = Self::normalize(db, &input, package_base, &tenant).await?;
+
+        Self::generate_for_intent(db, &input, intent, options, context, None, user_id).await
+    }
+
+    /// Normalize input to SpringIntent (saved intents come from the library)
+    pub async fn normalize(
+        db: &DatabaseConnection,
+        input: &GenerateInput,
+        package_base: &str,
+        tenant: &TenantScope,
+    ) -> Result<SpringIntent> {
+        match input {
+            GenerateInput::SavedIntent(saved) => SpringNormalizerService::normalize_ui_intent(
+                &saved_intents::Model::load_ui_intent(db, &saved.name, tenant).await?,
+                package_base,
+            ),
+            _ => SpringNormalizerService::normalize(input, package_base),
+        }
+    }
+
+    /// Generate one entity; within a module the prompt references the shared classes
+    pub async fn generate_for_intent(
+        db: &DatabaseConnection,
+        input: &GenerateInput,
+        mut intent: SpringIntent,
+        options: &GenerateOptions,
+        context: &RequestContext,
+        module: Option<&SpringModuleContext>,
+        user_id: Option<i32>,
+    ) -> Result<SpringGenerateResponse> {
+        let start = Instant::now();
+
+        // Templates, rules and LLM config of the user's company
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+        SchemaChangeService::record_input(db, tenant.company(), input).await;
 
         // 2. Get template version for logging
-        let template = TemplateService::get_active(db, "spring-backend", Some("crud"))
+        let template = TemplateService::get_active(db, "spring-backend", Some("crud"), &tenant)
             .await
             .ok();
         let template_version = template.as_ref().map(|t| t.version).unwrap_or(0);
 
+        let hooks = generation_hooks::registry();
+        let hook_ctx = HookContext {
+            product: "spring-backend",
+            user_id,
+            company_id: options.company_id.as_deref(),
+            issue_key: context.issue_key.as_deref(),
+        };
+
+        // The company's date policy shapes the prompt and the date checks
+        let rule_sections = match options.company_id.as_deref() {
+            Some(cid) => company_rules::Model::find_by_name(db, cid, &tenant)
+                .await
+                .ok()
+                .map(|r| r.rule_sections()),
+            None => None,
+        };
+        if let Some(policy) = rule_sections.as_ref().and_then(|s| s.date_policy.clone()) {
+            intent.options.date_policy = policy;
+        }
+        hooks.intent_normalized(&hook_ctx, NormalizedIntent::Spring(&mut intent));
+
         // 3. Compile prompt
-        let prompt = SpringPromptCompiler::compile(
+        let mut prompt = SpringPromptCompiler::compile(
             db,
             &intent,
             options.company_id.as_deref(),
+            &tenant,
         )
         .await?;
+        if let Some(module) = module {
+            prompt.add_user_section(&module.prompt_section());
+        }
+        // A refused request is retried without its notes (hooks run on that prompt again)
+        let mut sanitized = prompt.without_notes();
+        hooks.prompt_compiled(&hook_ctx, &mut prompt.system, &mut prompt.user);
+
+        // File tree from the selected naming profile
+        let (naming, naming_note) =
+            naming_profiles::Model::profile_or_default(db, options.naming_profile.as_deref(), &tenant).await;
+        let naming_vars = NamingVars::for_spring(&intent, Local::now().naive_local());
+
+        // Company forbidden APIs and the error-code catalog are checked against
+        // every generated file, which is first formatted in the company's Java style
+        let forbidden_apis = rule_sections
+            .as_ref()
+            .map(|s| s.forbidden_apis_for("spring-backend"))
+            .unwrap_or_default();
+        let java_style = rule_sections.and_then(|s| s.java_format).unwrap_or_default();
 
         // 4. Generate via LLM (DB config takes priority, falls back to env)
-        let llm = create_backend_from_db_or_env(db).await;
+        let llm = create_backend_from_db_or_env(db, tenant.company()).await;
 
         // Health check
         llm.health_check().await.map_err(|e| {
             anyhow!("LLM server not available: {}. Please check your LLM configuration.", e)
         })?;
 
-        let raw_output = llm.generate(&prompt.full()).await?;
+        let llm_provider = llm.name().to_string();
+        sla::mark(Stage::LlmStart);
+        let output = traced(
+            Span::child("llm.generate", SpanKind::Client)
+                .with_attr("llm.provider", llm_provider.as_str())
+                .with_attr("attempt", 1i64),
+            llm.generate(&prompt.full()),
+        )
+        .await?;
+        sla::mark(Stage::LlmEnd);
+        let mut llm_usage = LlmUsage::of(&output);
+        let mut raw_output = output.text;
+        hooks.llm_completed(&hook_ctx, 1, &mut raw_output);
 
         // 5. Parse and validate
-        let validation_result = SpringValidator::parse_and_validate(&raw_output, &intent);
+        let mut validate_span = Span::child("pipeline.run", SpanKind::Internal)
+            .with_attr("product", "spring-backend");
+        // A refusal is retried with a sanitized prompt instead of being parsed
+        let validation_result = match Refusal::detect(&raw_output) {
+            Some(refusal) => Err(refusal.into()),
+            None => SpringValidator::parse_and_validate(&raw_output, &intent),
+        };
+        let compile_check = JavaCompileConfig::from_env();
+        let plan_check = QueryPlanConfig::from_env();
+        if let Err(e) = &validation_result {
+            validate_span.set_error(e);
+        }
+        validate_span.end();
 
-        let (artifacts, warnings, status, error_message) = match validation_result {
+        let mut error_code = None;
+        let (mut artifacts, mut warnings, mut status, error_message) = match validation_result {
             Ok(mut validated) => {
                 // Post-process to fix common issues
                 SpringValidator::post_process(&mut validated, &intent);
+                JavaFormatter::format_artifacts(&mut validated, &java_style);
+                SpringValidator::check_forbidden_apis(&mut validated, &forbidden_apis);
+                SpringValidator::check_error_codes(&mut validated, &prompt.error_codes);
+                if options.deep_validation {
+                    let issues = JavaSyntaxChecker::check_artifacts(&validated);
+                    validated.warnings.extend(issues);
+                }
+                if let Some(compile) = &compile_check {
+                    validated.warnings.extend(JavaCompiler::check_artifacts(compile, &validated).await);
+                }
+                if let Some(plan) = &plan_check {
+                    validated.warnings.extend(QueryPlanAdvisor::check(plan, &validated.mapper_xml).await);
+                }
+                validated.paths = Some(naming.spring_paths(&validated, &intent, &naming_vars));
 
                 let warnings = validated.warnings.clone();
                 let status = if warnings.iter().any(|w| w.starts_with("Warning:")) {
@@ -94,26 +220,69 @@ impl SpringGenerationService {
             }
             Err(e) => {
                 // Validation failed - try retry once
-                tracing::warn!("First Spring generation failed validation: {}", e);
-
-                // Retry with more explicit instructions
-                let retry_prompt = format!(
-                    "{}\n\nIMPORTANT: Your previous response could not be parsed. \
-                    Please ensure you output exactly 6 sections with these markers:\n\
-                    --- CONTROLLER ---\n--- SERVICE ---\n--- SERVICE_IMPL ---\n\
-                    --- DTO ---\n--- MAPPER ---\n--- MAPPER_XML ---",
-                    prompt.full()
-                );
-
-                match llm.generate(&retry_prompt).await {
-                    Ok(retry_output) => {
-                        match SpringValidator::parse_and_validate(&retry_output, &intent) {
+                let refused = e.downcast_ref::<Refusal>().is_some();
+                let retry_prompt = if refused {
+                    // Refused - retry without the request notes
+                    tracing::warn!("First Spring generation was refused: {}", e);
+                    hooks.prompt_compiled(&hook_ctx, &mut sanitized.system, &mut sanitized.user);
+                    Refusal::retry_prompt(&sanitized.full())
+                } else {
+                    tracing::warn!("First Spring generation failed validation: {}", e);
+
+                    // Retry with more explicit instructions
+                    format!(
+                        "{}\n\nIMPORTANT: Your previous response could not be parsed. \
+                        Please ensure you output exactly 6 sections with these markers:\n\
+                        --- CONTROLLER ---\n--- SERVICE ---\n--- SERVICE_IMPL ---\n\
+                        --- DTO ---\n--- MAPPER ---\n--- MAPPER_XML ---",
+                        prompt.full()
+                    )
+                };
+
+                let retry_span = Span::child("llm.generate", SpanKind::Client)
+                    .with_attr("llm.provider", llm_provider.as_str())
+                    .with_attr("attempt", 2i64);
+                let retry = traced(retry_span, llm.generate(&retry_prompt)).await;
+                sla::mark(Stage::LlmEnd);
+                match retry {
+                    Ok(output) => {
+                        llm_usage.add(&output);
+                        let mut retry_output = output.text;
+                        hooks.llm_completed(&hook_ctx, 2, &mut retry_output);
+                        let retry_result = match Refusal::detect(&retry_output) {
+                            Some(refusal) => Err(refusal.into()),
+                            None => SpringValidator::parse_and_validate(&retry_output, &intent),
+                        };
+                        match retry_result {
                             Ok(mut validated) => {
                                 SpringValidator::post_process(&mut validated, &intent);
+                                JavaFormatter::format_artifacts(&mut validated, &java_style);
+                                SpringValidator::check_forbidden_apis(&mut validated, &forbidden_apis);
+                                SpringValidator::check_error_codes(&mut validated, &prompt.error_codes);
+                                if options.deep_validation {
+                                    let issues = JavaSyntaxChecker::check_artifacts(&validated);
+                                    validated.warnings.extend(issues);
+                                }
+                                if let Some(compile) = &compile_check {
+                                    validated.warnings.extend(JavaCompiler::check_artifacts(compile, &validated).await);
+                                }
+                                if let Some(plan) = &plan_check {
+                                    validated.warnings.extend(QueryPlanAdvisor::check(plan, &validated.mapper_xml).await);
+                                }
+                                validated.paths = Some(naming.spring_paths(&validated, &intent, &naming_vars));
                                 let mut warnings = validated.warnings.clone();
-                                warnings.push("Note: Generation required retry".to_string());
+                                warnings.push(if refused {
+                                    Refusal::retried_note()
+                                } else {
+                                    "Note: Generation required retry".to_string()
+                                });
                                 (Some(validated), warnings, GenerateStatus::PartialSuccess, None)
                             }
+                            Err(retry_err) if retry_err.is::<Refusal>() => {
+                                tracing::warn!("Spring retry was refused too: {}", retry_err);
+                                error_code = Some(REFUSAL_ERROR_CODE.to_string());
+                                (None, vec![retry_err.to_string()], GenerateStatus::Error, Some(Refusal::error_message()))
+                            }
                             Err(retry_err) => {
                                 (None, vec![], GenerateStatus::Error, Some(format!("Validation failed after retry: {}", retry_err)))
                             }
@@ -125,21 +294,48 @@ impl SpringGenerationService {
                 }
             }
         };
+        sla::mark(Stage::PipelineEnd);
+
+        // Sample rows for the source table
+        if let (GenerateInput::DbSchema(schema), Some(rows), Some(generated)) =
+            (input, options.test_data_rows.filter(|r| *r > 0), artifacts.as_mut())
+        {
+            generated.test_data = Some(TestDataGenerator::insert_script(schema, rows));
+            generated.test_data_filename = Some(TestDataGenerator::filename(schema));
+        }
+
+        warnings.extend(naming_note);
+
+        hooks.pipeline_finished(
+            &hook_ctx,
+            PipelineOutcome {
+                status: &mut status,
+                artifacts: artifacts.as_mut().map(FinishedArtifacts::Spring),
+                warnings: &mut warnings,
+                error: error_message.as_deref(),
+            },
+        );
 
         let generation_time_ms = start.elapsed().as_millis() as u64;
+        telemetry::telemetry().record_generation("spring-backend", status.as_str(), generation_time_ms);
 
         // 6. Log to audit trail (NO input data stored)
+        sla::llm_usage(llm_usage);
         let log_result = Self::log_generation(
             db,
-            &input,
+            input,
             &intent,
             template_version,
             &status,
             &artifacts,
             &warnings,
             error_message.as_deref(),
+            error_code.as_deref(),
             generation_time_ms as i32,
             user_id,
+            &llm_usage,
+            tenant.company(),
+            context.issue_key.as_deref(),
         )
         .await;
 
@@ -153,6 +349,7 @@ impl SpringGenerationService {
             artifacts,
             warnings,
             error: error_message,
+            error_code,
             meta: ResponseMeta {
                 generator: "spring-backend-v1".to_string(),
                 timestamp: Utc::now(),
@@ -179,11 +376,14 @@ impl SpringGenerationService {
         let llm = create_backend_from_env();
         llm.health_check().await?;
 
-        let raw_output = llm.generate(&prompt.full()).await?;
+        let raw_output = llm.generate(&prompt.full()).await?.text;
 
         // 4. Parse and validate
         let mut validated = SpringValidator::parse_and_validate(&raw_output, &intent)?;
         SpringValidator::post_process(&mut validated, &intent);
+        JavaFormatter::format_artifacts(&mut validated, &JavaFormatStyle::default());
+        let naming_vars = NamingVars::for_spring(&intent, Local::now().naive_local());
+        validated.paths = Some(NamingProfile::default().spring_paths(&validated, &intent, &naming_vars));
 
         let generation_time_ms = start.elapsed().as_millis() as u64;
 
@@ -200,6 +400,7 @@ impl SpringGenerationService {
             artifacts: Some(validated),
             warnings,
             error: None,
+            error_code: None,
             meta: ResponseMeta {
                 generator: "spring-backend-v1".to_string(),
                 timestamp: Utc::now(),
@@ -212,33 +413,42 @@ impl SpringGenerationService {
     async fn log_generation(
         db: &DatabaseConnection,
         input: &GenerateInput,
-        intent: &crate::domain::SpringIntent,
+        intent: &SpringIntent,
         template_version: i32,
         status: &GenerateStatus,
         artifacts: &Option<SpringArtifacts>,
         warnings: &[String],
         error_message: Option<&str>,
+        error_code: Option<&str>,
         generation_time_ms: i32,
         user_id: Option<i32>,
+        llm_usage: &LlmUsage,
+        company: Option<&str>,
+        issue_key: Option<&str>,
     ) -> Result<()> {
         // Determine input type (without storing actual input data)
         let input_type = match input {
             GenerateInput::DbSchema(_) => "db-schema",
             GenerateInput::QuerySample(_) => "query-sample",
             GenerateInput::NaturalLanguage(_) => "natural-language",
+            GenerateInput::Dashboard(_) => "dashboard",
+            GenerateInput::Intent(_) => "intent",
+            GenerateInput::SavedIntent(_) => "saved-intent",
+            GenerateInput::Dsl(_) => "dsl",
         };
 
-        let status_str = match status {
-            GenerateStatus::Success => "success",
-            GenerateStatus::PartialSuccess => "partial_success",
-            GenerateStatus::Error => "error",
-        };
+        let status_str = status.as_str();
 
         // Store Spring intent (meta model) instead of raw input
         let spring_intent_json = serde_json::to_string(intent)?;
 
         // Store artifacts
         let artifacts_json = artifacts.as_ref().map(|a| serde_json::to_string(a).ok()).flatten();
+        let lineage = LineageService::build_json("spring-backend", &spring_intent_json, artifacts_json.as_deref());
+        let artifacts_expire_at = match &artifacts_json {
+            Some(_) => RetentionService::expiry(db, user_id, "spring-backend", Utc::now().into()).await?,
+            None => None,
+        };
 
         // Store warnings
         let warnings_json = if warnings.is_empty() {
@@ -247,7 +457,11 @@ impl SpringGenerationService {
             Some(serde_json::to_string(warnings)?)
         };
 
-        let log = generation_logs::ActiveModel {
+        let quality_score = artifacts
+            .as_ref()
+            .map(|a| ArtifactScorer::score_spring(a, intent, warnings).total as i32);
+
+        let mut log = generation_logs::ActiveModel {
             product: Set("spring-backend".to_string()),
             input_type: Set(input_type.to_string()),
             ui_intent: Set(spring_intent_json), // Reuse column for SpringIntent
@@ -256,12 +470,21 @@ impl SpringGenerationService {
             artifacts: Set(artifacts_json),
             warnings: Set(warnings_json),
             error_message: Set(error_message.map(|s| s.to_string())),
+            error_code: Set(error_code.map(|s| s.to_string())),
             generation_time_ms: Set(Some(generation_time_ms)),
-            user_id: Set(user_id.unwrap_or(1)),
+            user_id: Set(user_id.unwrap_or(SYSTEM_USER_ID)),
+            quality_score: Set(quality_score),
+            project: Set(Some(intent.package_base.clone())),
+            company: Set(company.map(|s| s.to_string())),
+            issue_key: Set(issue_key.map(|s| s.to_string())),
+            lineage: Set(lineage),
+            artifacts_expire_at: Set(artifacts_expire_at),
             ..Default::default()
         };
+        llm_usage.apply(&mut log);
 
-        log.insert(db).await?;
+        let log = log.insert(db).await?;
+        IssueTrackerService::report(db, &log);
 
         Ok(())
     }
@@ -279,6 +502,10 @@ mod tests {
             GenerateInput::DbSchema(_) => "db-schema",
             GenerateInput::QuerySample(_) => "query-sample",
             GenerateInput::NaturalLanguage(_) => "natural-language",
+            GenerateInput::Dashboard(_) => "dashboard",
+            GenerateInput::Intent(_) => "intent",
+            GenerateInput::SavedIntent(_) => "saved-intent",
+            GenerateInput::Dsl(_) => "dsl",
         };
         assert_eq!(input_type, "db-schema");
     }
@@ -0,0 +1,282 @@
+//! Spring Module Generation
+//!
+//! Generates several entities as one module: the shared classes are rendered
+//! once, each entity slice is generated with a prompt that references them,
+//! and the slices are then checked against each other and the module package.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use regex::Regex;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use crate::domain::{
+    GenerateInput, GenerateOptions, GenerateStatus, RequestContext, ResponseMeta, SharedSpringClass,
+    SpringArtifacts, SpringIntent, SpringModuleContext,
+};
+use crate::models::naming_profiles;
+use crate::services::{SpringCommonClasses, SpringGenerateResponse, SpringGenerationService, TenantScope};
+
+/// Response for multi-entity Spring generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpringModuleResponse {
+    /// Overall status (partial if any entity failed or cross-entity checks warn)
+    pub status: GenerateStatus,
+
+    /// Classes generated once for the whole module
+    pub shared: Vec<SharedSpringClass>,
+
+    /// One result per entity, in request order
+    pub entities: Vec<SpringGenerateResponse>,
+
+    /// Cross-entity warnings
+    #[serde(default)]
+    pub warnings: Vec<String>,
+
+    /// Response metadata
+    pub meta: ResponseMeta,
+}
+
+/// Service for generating multi-entity Spring modules
+pub struct SpringModuleService;
+
+impl SpringModuleService {
+    pub async fn generate(
+        db: &DatabaseConnection,
+        inputs: Vec<GenerateInput>,
+        options: &GenerateOptions,
+        context: &RequestContext,
+        user_id: Option<i32>,
+    ) -> Result<SpringModuleResponse> {
+        let start = Instant::now();
+        if inputs.is_empty() {
+            return Err(anyhow!("A module needs at least one entity"));
+        }
+        let package_base = context.project.as_deref().unwrap_or("com.company.project");
+
+        // 1. Normalize every entity, then build the shared classes once
+        let tenant = TenantScope::for_user_id(db, user_id).await;
+        let mut intents = Vec::with_capacity(inputs.len());
+        for input in &inputs {
+            intents.push(SpringGenerationService::normalize(db, input, package_base, &tenant).await?);
+        }
+        let (naming, _) =
+            naming_profiles::Model::profile_or_default(db, options.naming_profile.as_deref(), &tenant).await;
+        let wrapper = intents[0]
+            .options
+            .response_wrapper
+            .clone()
+            .unwrap_or_else(|| "ApiResponse".to_string());
+        let module = SpringCommonClasses::build(package_base, &wrapper, &naming);
+        Self::check_intents(&intents, &module)?;
+
+        // 2. Generate each slice against the shared classes
+        let mut entities = Vec::with_capacity(intents.len());
+        let mut warnings = Vec::new();
+        for (input, intent) in inputs.iter().zip(intents) {
+            let entity_name = intent.entity_name.clone();
+            let response =
+                SpringGenerationService::generate_for_intent(db, input, intent, options, context, Some(&module), user_id)
+                    .await
+                    .unwrap_or_else(|e| Self::failed(&entity_name, e));
+            if let Some(artifacts) = &response.artifacts {
+                warnings.extend(Self::check_slice(&entity_name, artifacts, &module));
+            }
+            entities.push(response);
+        }
+
+        let status = if entities.iter().all(|e| e.status == GenerateStatus::Error) {
+            GenerateStatus::Error
+        } else if !warnings.is_empty() || entities.iter().any(|e| e.status != GenerateStatus::Success) {
+            GenerateStatus::PartialSuccess
+        } else {
+            GenerateStatus::Success
+        };
+
+        Ok(SpringModuleResponse {
+            status,
+            shared: module.shared,
+            entities,
+            warnings,
+            meta: ResponseMeta {
+                generator: "spring-backend-v1".to_string(),
+                timestamp: Utc::now(),
+                generation_time_ms: start.elapsed().as_millis() as u64,
+            },
+        })
+    }
+
+    fn failed(entity_name: &str, error: anyhow::Error) -> SpringGenerateResponse {
+        tracing::error!("Spring module entity {} failed: {}", entity_name, error);
+        SpringGenerateResponse {
+            status: GenerateStatus::Error,
+            artifacts: None,
+            warnings: vec![],
+            error: Some(format!("{}: Generation failed: {}", entity_name, error)),
+            error_code: None,
+            meta: ResponseMeta {
+                generator: "spring-backend-v1".to_string(),
+                timestamp: Utc::now(),
+                generation_time_ms: 0,
+            },
+        }
+    }
+
+    /// Reject modules whose entities would produce clashing classes
+    pub fn check_intents(intents: &[SpringIntent], module: &SpringModuleContext) -> Result<()> {
+        let mut seen_entities: Vec<String> = Vec::new();
+        let mut seen_tables: Vec<String> = Vec::new();
+
+        for intent in intents {
+            if intent.package_base != module.package_base {
+                return Err(anyhow!(
+                    "Entity {} uses package {} but the module package is {}",
+                    intent.entity_name,
+                    intent.package_base,
+                    module.package_base
+                ));
+            }
+
+            let entity = intent.entity_name.to_lowercase();
+            if seen_entities.contains(&entity) {
+                return Err(anyhow!("Entity {} appears more than once", intent.entity_name));
+            }
+            seen_entities.push(entity);
+
+            let table = intent.table_name.to_uppercase();
+            if seen_tables.contains(&table) {
+                return Err(anyhow!("Table {} is used by more than one entity", intent.table_name));
+            }
+            seen_tables.push(table);
+
+            let classes = [
+                intent.controller_name(),
+                intent.service_name(),
+                intent.service_impl_name(),
+                intent.dto_name(),
+                intent.mapper_name(),
+            ];
+            if let Some(clash) = classes.iter().find(|c| module.find(c).is_some()) {
+                return Err(anyhow!(
+                    "Entity {} would generate {}, which clashes with a shared class",
+                    intent.entity_name,
+                    clash
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Cross-check one generated slice against the module
+    pub fn check_slice(entity_name: &str, artifacts: &SpringArtifacts, module: &SpringModuleContext) -> Vec<String> {
+        let package_re = Regex::new(r"(?m)^\s*package\s+([\w.]+)\s*;").unwrap();
+        let decl_re = Regex::new(r"\b(?:class|interface|enum)\s+(\w+)").unwrap();
+        let import_re = Regex::new(r"(?m)^\s*import\s+([\w.]+)\.(\w+)\s*;").unwrap();
+        let in_module = |package: &str| {
+            package == module.package_base || package.starts_with(&format!("{}.", module.package_base))
+        };
+
+        let mut files = vec![
+            ("controller", &artifacts.controller),
+            ("service", &artifacts.service_interface),
+            ("service_impl", &artifacts.service_impl),
+            ("dto", &artifacts.dto),
+            ("mapper", &artifacts.mapper_interface),
+        ];
+        if let Some(search_dto) = &artifacts.search_dto {
+            files.push(("search_dto", search_dto));
+        }
+
+        let mut warnings = Vec::new();
+        for (label, source) in files {
+            if let Some(cap) = package_re.captures(source) {
+                if !in_module(&cap[1]) {
+                    warnings.push(format!(
+                        "Warning: [{}] {} package '{}' is outside module package '{}'",
+                        entity_name, label, &cap[1], module.package_base
+                    ));
+                }
+            }
+            for cap in decl_re.captures_iter(source) {
+                if module.find(&cap[1]).is_some() {
+                    warnings.push(format!("Warning: [{}] {} redefines shared class {}", entity_name, label, &cap[1]));
+                }
+            }
+            for cap in import_re.captures_iter(source) {
+                if let Some(shared) = module.find(&cap[2]).filter(|s| s.package != cap[1]) {
+                    warnings.push(format!(
+                        "Warning: [{}] {} imports {} from '{}' instead of '{}'",
+                        entity_name, label, &cap[2], &cap[1], shared.package
+                    ));
+                }
+            }
+        }
+
+        if !artifacts.controller.contains("extends BaseController") {
+            warnings.push(format!("Warning: [{}] controller does not extend BaseController", entity_name));
+        }
+
+        let namespace = Regex::new(r#"namespace\s*=\s*"([\w.]+)""#)
+            .unwrap()
+            .captures(&artifacts.mapper_xml)
+            .map(|cap| cap[1].to_string());
+        if let Some(namespace) = namespace.filter(|ns| !in_module(ns)) {
+            warnings.push(format!(
+                "Warning: [{}] mapper XML namespace '{}' is outside module package '{}'",
+                entity_name, namespace, module.package_base
+            ));
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::NamingProfile;
+
+    fn module() -> SpringModuleContext {
+        SpringCommonClasses::build("com.company.erp", "ApiResponse", &NamingProfile::default())
+    }
+
+    #[test]
+    fn test_check_intents() {
+        let member = SpringIntent::new("Member", "TB_MEMBER", "com.company.erp");
+        let order = SpringIntent::new("Order", "TB_ORDER", "com.company.erp");
+        assert!(SpringModuleService::check_intents(&[member.clone(), order], &module()).is_ok());
+
+        let dup = SpringIntent::new("Member", "TB_MEMBER_HIST", "com.company.erp");
+        assert!(SpringModuleService::check_intents(&[member.clone(), dup], &module()).is_err());
+
+        let base = SpringIntent::new("Base", "TB_BASE", "com.company.erp");
+        let err = SpringModuleService::check_intents(&[member, base], &module()).unwrap_err();
+        assert!(err.to_string().contains("BaseController"));
+
+        let other = SpringIntent::new("Order", "TB_ORDER", "com.company.other");
+        assert!(SpringModuleService::check_intents(&[other], &module()).is_err());
+    }
+
+    #[test]
+    fn test_check_slice() {
+        let mut artifacts = SpringArtifacts::new();
+        artifacts.controller = "package com.company.erp.controller;\n\
+            import com.company.erp.common.response.ApiResponse;\n\
+            public class MemberController extends BaseController {}"
+            .to_string();
+        artifacts.dto = "package com.company.erp.dto;\npublic class MemberDTO {}".to_string();
+        assert!(SpringModuleService::check_slice("Member", &artifacts, &module()).is_empty());
+
+        artifacts.controller = "package com.company.erp.controller;\n\
+            import com.company.erp.dto.ApiResponse;\n\
+            public class MemberController {}"
+            .to_string();
+        artifacts.service_impl = "package com.other.service;\nclass BusinessException extends RuntimeException {}".to_string();
+        artifacts.mapper_xml = r#"<mapper namespace="com.other.mapper.MemberMapper">"#.to_string();
+        let warnings = SpringModuleService::check_slice("Member", &artifacts, &module());
+        assert_eq!(warnings.len(), 5);
+        assert!(warnings[0].contains("imports ApiResponse from 'com.company.erp.dto'"));
+        assert!(warnings.iter().any(|w| w.contains("redefines shared class BusinessException")));
+    }
+}
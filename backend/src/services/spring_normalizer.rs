@@ -1,7 +1,8 @@
 use crate::domain::{
-    ColumnIntent, CrudOperation, DataType, GenerateInput, SchemaColumn, SchemaInput, SpringIntent,
-    SpringOptions, UiType, to_pascal_case,
+    ColumnIntent, ColumnType, DbDialect, CrudOperation, DataType, GenerateInput, SchemaColumn, SchemaInput, SpringIntent,
+    SpringOptions, UiIntent, UiType, to_pascal_case,
 };
+use crate::services::ScreenDsl;
 use anyhow::{anyhow, Result};
 
 /// Service for normalizing input to SpringIntent DSL
@@ -23,6 +24,8 @@ impl SpringNormalizerService {
                     columns,
                     primary_keys: vec![],
                     foreign_keys: vec![],
+                    row_count: None,
+                    dialect: None,
                 };
                 Self::normalize_schema(&schema, package_base)
             }
@@ -34,9 +37,32 @@ impl SpringNormalizerService {
 
                 Ok(SpringIntent::new(entity_name, table_name, package_base))
             }
+            GenerateInput::Dashboard(_) => {
+                Err(anyhow!("Dashboard input generates screens only; send its queries as query samples"))
+            }
+            GenerateInput::Intent(intent) => Self::normalize_ui_intent(intent, package_base),
+            GenerateInput::Dsl(dsl) => Self::normalize_ui_intent(&ScreenDsl::parse(&dsl.yaml)?, package_base),
+            GenerateInput::SavedIntent(saved) => {
+                Err(anyhow!("Saved intent '{}' must be loaded before normalization", saved.name))
+            }
         }
     }
 
+    /// Backend for a UI intent: entity and columns of its first dataset
+    pub fn normalize_ui_intent(intent: &UiIntent, package_base: &str) -> Result<SpringIntent> {
+        let dataset = intent
+            .datasets
+            .first()
+            .ok_or_else(|| anyhow!("Intent '{}' has no dataset", intent.screen_name))?;
+        let table_name = dataset
+            .table_name
+            .clone()
+            .unwrap_or_else(|| dataset.id.trim_start_matches("ds_").to_uppercase());
+        let entity_name = Self::table_to_entity_name(&table_name);
+
+        Ok(SpringIntent::new(entity_name, table_name, package_base).with_columns(dataset.columns.clone()))
+    }
+
     /// Normalize database schema to SpringIntent
     pub fn normalize_schema(input: &SchemaInput, package_base: &str) -> Result<SpringIntent> {
         // Derive entity name from table name
@@ -44,10 +70,11 @@ impl SpringNormalizerService {
         let table_name = input.table.clone();
 
         // Convert schema columns to column intents
+        let dialect = input.effective_dialect();
         let columns: Vec<ColumnIntent> = input
             .columns
             .iter()
-            .map(|c| Self::schema_column_to_intent(c, &input.primary_keys))
+            .map(|c| Self::schema_column_to_intent(c, &input.primary_keys, dialect))
             .collect();
 
         // Determine CRUD operations (default: all)
@@ -66,6 +93,8 @@ impl SpringNormalizerService {
             columns,
             crud_operations,
             options: SpringOptions::default(),
+            dialect,
+            notes: None,
         })
     }
 
@@ -81,10 +110,14 @@ impl SpringNormalizerService {
     }
 
     /// Convert a schema column to column intent
-    fn schema_column_to_intent(col: &SchemaColumn, primary_keys: &[String]) -> ColumnIntent {
+    pub fn schema_column_to_intent(
+        col: &SchemaColumn,
+        primary_keys: &[String],
+        dialect: Option<DbDialect>,
+    ) -> ColumnIntent {
         let is_pk = col.pk || primary_keys.contains(&col.name);
         let label = Self::infer_label(&col.name, col.comment.as_deref());
-        let (ui_type, data_type) = Self::infer_types(&col.column_type, is_pk);
+        let (ui_type, data_type) = Self::infer_types(&col.column_type, is_pk, dialect);
 
         let mut intent = ColumnIntent::new(&col.name, label)
             .with_ui_type(ui_type)
@@ -105,22 +138,27 @@ impl SpringNormalizerService {
     }
 
     /// Infer UI type and data type from database column type
-    fn infer_types(db_type: &str, is_pk: bool) -> (UiType, DataType) {
+    fn infer_types(db_type: &str, is_pk: bool, dialect: Option<DbDialect>) -> (UiType, DataType) {
         if is_pk {
             return (UiType::Hidden, DataType::Integer);
         }
 
-        let upper = db_type.to_uppercase();
+        let parsed = ColumnType::parse(db_type);
+        let upper = parsed.base.as_str();
 
-        if upper.starts_with("VARCHAR") || upper.starts_with("CHAR") || upper == "NVARCHAR" {
+        if upper.starts_with("VARCHAR") || upper.starts_with("NVARCHAR") || upper.starts_with("CHAR") || upper.starts_with("NCHAR") {
             return (UiType::Input, DataType::String);
         }
 
-        if upper.starts_with("TEXT") || upper.starts_with("CLOB") || upper == "LONGTEXT" {
+        if upper.starts_with("TEXT") || upper.ends_with("CLOB") || upper == "LONGTEXT" || upper == "LONG" {
             return (UiType::TextArea, DataType::Text);
         }
 
+        // Oracle DATE carries a time of day
         if upper == "DATE" {
+            if dialect == Some(DbDialect::Oracle) {
+                return (UiType::DateTimePicker, DataType::DateTime);
+            }
             return (UiType::DatePicker, DataType::Date);
         }
 
@@ -132,11 +170,19 @@ impl SpringNormalizerService {
             return (UiType::Checkbox, DataType::Boolean);
         }
 
+        // Oracle NUMBER(p) / NUMBER(p,0) are integers; bare NUMBER or NUMBER(p,s) are decimals
+        if parsed.is_number() {
+            if parsed.is_integral_number() {
+                return (UiType::Number, DataType::Integer);
+            }
+            return (UiType::Number, DataType::Decimal);
+        }
+
         if upper.starts_with("INT") || upper == "BIGINT" || upper == "SMALLINT" || upper == "TINYINT" {
             return (UiType::Number, DataType::Integer);
         }
 
-        if upper.starts_with("DECIMAL") || upper.starts_with("NUMERIC") || upper == "FLOAT" || upper == "DOUBLE" {
+        if upper.starts_with("DECIMAL") || upper.starts_with("NUMERIC") || upper == "FLOAT" || upper == "DOUBLE" || upper.starts_with("BINARY_") {
             return (UiType::Number, DataType::Decimal);
         }
 
@@ -145,15 +191,7 @@ impl SpringNormalizerService {
 
     /// Extract length from VARCHAR(n)
     fn extract_varchar_length(db_type: &str) -> Option<u32> {
-        let upper = db_type.to_uppercase();
-        if let Some(start) = upper.find('(') {
-            if let Some(end) = upper.find(')') {
-                if let Ok(len) = upper[start + 1..end].trim().parse() {
-                    return Some(len);
-                }
-            }
-        }
-        None
+        ColumnType::parse(db_type).length()
     }
 
     /// Infer display label from column name
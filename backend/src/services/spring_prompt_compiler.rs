@@ -1,5 +1,7 @@
-use crate::domain::{CrudOperation, SpringIntent, to_camel_case};
-use crate::models::_entities::{company_rules, prompt_templates};
+use crate::domain::{CrudOperation, DataType, DatePolicy, ErrorCodeCatalog, SpringIntent, to_camel_case};
+use crate::models::_entities::prompt_templates;
+use crate::models::{company_rules, error_code_catalogs};
+use crate::services::TenantScope;
 use anyhow::Result;
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 
@@ -8,12 +10,30 @@ use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 pub struct SpringCompiledPrompt {
     pub system: String,
     pub user: String,
+    /// User prompt rendered without the request notes (retry of a refusal)
+    pub user_without_notes: String,
+    /// Error codes listed in the system prompt (checked by `SpringValidator`)
+    pub error_codes: ErrorCodeCatalog,
 }
 
 impl SpringCompiledPrompt {
     pub fn full(&self) -> String {
         format!("{}\n\n{}", self.system, self.user)
     }
+
+    /// The prompt with the notes-free user prompt
+    pub fn without_notes(&self) -> Self {
+        Self {
+            user: self.user_without_notes.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Append a section to both user prompts
+    pub fn add_user_section(&mut self, section: &str) {
+        self.user.push_str(section);
+        self.user_without_notes.push_str(section);
+    }
 }
 
 /// Service for compiling SpringIntent into LLM prompts
@@ -25,30 +45,44 @@ impl SpringPromptCompiler {
         db: &DatabaseConnection,
         intent: &SpringIntent,
         company_id: Option<&str>,
+        tenant: &TenantScope,
     ) -> Result<SpringCompiledPrompt> {
         // 1. Load template from DB (or use defaults)
-        let template = Self::load_template(db, "spring-backend", "crud").await;
+        let template = Self::load_template(db, "spring-backend", "crud", tenant).await;
 
-        // 2. Load company rules if provided
+        // 2. Load company rules if provided (only sections relevant to Spring)
         let rules = if let Some(cid) = company_id {
-            Self::load_company_rules(db, cid).await.ok()
+            Self::load_company_rules(db, cid, tenant)
+                .await
+                .ok()
+                .map(|r| r.render_for("spring-backend", None))
+                .unwrap_or_default()
         } else {
-            None
+            String::new()
         };
 
-        // 3. Build prompts
-        let system = Self::build_system_prompt(&template, &rules, intent);
+        // 3. Load the error codes exceptions must use
+        let error_codes = error_code_catalogs::Model::active(db, tenant).await;
+
+        // 4. Build prompts
+        let mut system = Self::build_system_prompt(&template, &rules, intent);
+        if !error_codes.is_empty() {
+            system.push_str("\n\nERROR CODES:\n");
+            system.push_str(&error_codes.render("spring-backend"));
+        }
         let user = Self::build_user_prompt(&template, intent, &rules);
+        let user_without_notes = Self::build_user_prompt(&template, &intent.without_notes(), &rules);
 
-        Ok(SpringCompiledPrompt { system, user })
+        Ok(SpringCompiledPrompt { system, user, user_without_notes, error_codes })
     }
 
     /// Compile using default templates (no database)
     pub fn compile_with_defaults(intent: &SpringIntent, company_rules: Option<&str>) -> SpringCompiledPrompt {
         let system = Self::get_default_system_prompt(intent);
         let user = Self::build_user_prompt_from_intent(intent, company_rules);
+        let user_without_notes = Self::build_user_prompt_from_intent(&intent.without_notes(), company_rules);
 
-        SpringCompiledPrompt { system, user }
+        SpringCompiledPrompt { system, user, user_without_notes, error_codes: ErrorCodeCatalog::default() }
     }
 
     /// Load template from database
@@ -56,11 +90,14 @@ impl SpringPromptCompiler {
         db: &DatabaseConnection,
         product: &str,
         screen_type: &str,
+        tenant: &TenantScope,
     ) -> Option<prompt_templates::Model> {
-        prompt_templates::Entity::find()
+        let query = prompt_templates::Entity::find()
             .filter(prompt_templates::Column::Product.eq(product))
             .filter(prompt_templates::Column::ScreenType.eq(Some(screen_type.to_string())))
-            .filter(prompt_templates::Column::IsActive.eq(Some(true)))
+            .filter(prompt_templates::Column::IsActive.eq(Some(true)));
+        tenant
+            .prefer_own(query, prompt_templates::Column::Company)
             .one(db)
             .await
             .ok()
@@ -72,12 +109,11 @@ impl SpringPromptCompiler {
     async fn load_company_rules(
         db: &DatabaseConnection,
         rule_name: &str,
+        tenant: &TenantScope,
     ) -> Result<company_rules::Model> {
-        company_rules::Entity::find()
-            .filter(company_rules::Column::Name.eq(rule_name))
-            .one(db)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("Company rules not found for: {}", rule_name))
+        company_rules::Model::find_by_name(db, rule_name, tenant)
+            .await
+            .map_err(|_| anyhow::anyhow!("Company rules not found for: {}", rule_name))
     }
 
     /// Get default system prompt for Spring code generation
@@ -144,7 +180,7 @@ IMPORTANT:
     /// Build system prompt from template and rules
     fn build_system_prompt(
         template: &Option<prompt_templates::Model>,
-        rules: &Option<company_rules::Model>,
+        rules: &str,
         intent: &SpringIntent,
     ) -> String {
         let base_prompt = template
@@ -153,12 +189,8 @@ IMPORTANT:
             .unwrap_or_else(|| Self::get_default_system_prompt(intent));
 
         // Append company rules if available
-        if let Some(r) = rules {
-            if let Some(ref additional) = r.additional_rules {
-                if !additional.is_empty() {
-                    return format!("{}\n\nCOMPANY-SPECIFIC RULES:\n{}", base_prompt, additional);
-                }
-            }
+        if !rules.is_empty() {
+            return format!("{}\n\nCOMPANY-SPECIFIC RULES:\n{}", base_prompt, rules);
         }
 
         base_prompt
@@ -168,34 +200,45 @@ IMPORTANT:
     fn build_user_prompt(
         template: &Option<prompt_templates::Model>,
         intent: &SpringIntent,
-        rules: &Option<company_rules::Model>,
+        rules: &str,
     ) -> String {
-        let company_rules_str = rules
-            .as_ref()
-            .and_then(|r| r.additional_rules.clone())
-            .unwrap_or_default();
-
         if let Some(t) = template {
-            Self::render_template(&t.user_prompt_template, intent, &company_rules_str)
+            // Templates predate the date policy, so it follows the rendered prompt
+            let mut prompt = Self::render_template(&t.user_prompt_template, intent, rules);
+            prompt.push_str(&intent.options.date_policy.prompt_section(&intent.columns));
+            prompt
         } else {
-            let rules_ref = if company_rules_str.is_empty() {
-                None
-            } else {
-                Some(company_rules_str.as_str())
-            };
+            let rules_ref = if rules.is_empty() { None } else { Some(rules) };
             Self::build_user_prompt_from_intent(intent, rules_ref)
         }
     }
 
+    /// Values of the template variables (see `TemplateVariables`)
+    pub fn template_variables(intent: &SpringIntent, company_rules: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("entity_name", intent.entity_name.clone()),
+            ("table_name", intent.table_name.clone()),
+            ("package_base", intent.package_base.clone()),
+            ("path_name", intent.path_name()),
+            ("columns", Self::describe_columns(intent)),
+            ("crud_operations", Self::describe_operations(intent)),
+            ("notes", intent.notes.clone().unwrap_or_default()),
+            ("company_rules", company_rules.to_string()),
+        ]
+    }
+
     /// Render a template with intent data
-    fn render_template(template: &str, intent: &SpringIntent, company_rules: &str) -> String {
-        template
-            .replace("{{entity_name}}", &intent.entity_name)
-            .replace("{{table_name}}", &intent.table_name)
-            .replace("{{package_base}}", &intent.package_base)
-            .replace("{{columns}}", &Self::describe_columns(intent))
-            .replace("{{crud_operations}}", &Self::describe_operations(intent))
-            .replace("{{company_rules}}", company_rules)
+    pub fn render_template(template: &str, intent: &SpringIntent, company_rules: &str) -> String {
+        Self::template_variables(intent, company_rules)
+            .iter()
+            .fold(template.to_string(), |rendered, (name, value)| {
+                rendered.replace(&format!("{{{{{}}}}}", name), value)
+            })
+            // Handle conditional blocks (simple version)
+            .lines()
+            .filter(|line| !line.contains("{{#if") && !line.contains("{{/if}}"))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     /// Build user prompt directly from intent
@@ -223,7 +266,7 @@ IMPORTANT:
         // Column definitions
         prompt.push_str("\nCOLUMN DEFINITIONS:\n");
         for col in &intent.columns {
-            let java_type = Self::infer_java_type(&col.data_type);
+            let java_type = Self::infer_java_type(&col.data_type, &intent.options.date_policy);
             let field_name = to_camel_case(&col.name);
             prompt.push_str(&format!(
                 "- {} {} ({}){}{}",
@@ -250,6 +293,12 @@ IMPORTANT:
             prompt.push_str(&format!("\nRESPONSE WRAPPER: Use {} for all responses\n", wrapper));
         }
 
+        prompt.push_str(&intent.options.date_policy.prompt_section(&intent.columns));
+
+        if let Some(notes) = &intent.notes {
+            prompt.push_str(&format!("\nADDITIONAL NOTES:\n{}\n", notes));
+        }
+
         // Company rules
         if let Some(rules) = company_rules {
             if !rules.is_empty() {
@@ -266,7 +315,7 @@ IMPORTANT:
     fn describe_columns(intent: &SpringIntent) -> String {
         intent.columns.iter()
             .map(|col| {
-                let java_type = Self::infer_java_type(&col.data_type);
+                let java_type = Self::infer_java_type(&col.data_type, &intent.options.date_policy);
                 format!("{} {} ({})", java_type, to_camel_case(&col.name), col.label)
             })
             .collect::<Vec<_>>()
@@ -292,17 +341,16 @@ IMPORTANT:
         }
     }
 
-    /// Infer Java type from DataType
-    fn infer_java_type(data_type: &crate::domain::DataType) -> &'static str {
+    /// Infer Java type from DataType (date types follow the date policy)
+    fn infer_java_type(data_type: &DataType, date_policy: &DatePolicy) -> &'static str {
         match data_type {
-            crate::domain::DataType::String => "String",
-            crate::domain::DataType::Integer => "Long",
-            crate::domain::DataType::Decimal => "BigDecimal",
-            crate::domain::DataType::Boolean => "Boolean",
-            crate::domain::DataType::Date => "LocalDate",
-            crate::domain::DataType::DateTime => "LocalDateTime",
-            crate::domain::DataType::Text => "String",
-            crate::domain::DataType::Binary => "byte[]",
+            DataType::String => "String",
+            DataType::Integer => "Long",
+            DataType::Decimal => "BigDecimal",
+            DataType::Boolean => "Boolean",
+            DataType::Date | DataType::DateTime => date_policy.java_type(data_type).unwrap_or("LocalDateTime"),
+            DataType::Text => "String",
+            DataType::Binary => "byte[]",
         }
     }
 }
@@ -310,7 +358,7 @@ IMPORTANT:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{ColumnIntent, DataType, UiType};
+    use crate::domain::{ColumnIntent, SpringOptions, StorageZone, UiType};
 
     fn create_test_intent() -> SpringIntent {
         SpringIntent::new("Member", "TB_MEMBER", "com.company.project")
@@ -380,4 +428,21 @@ mod tests {
         assert!(full.contains("MyBatis"));
         assert!(full.contains("Member"));
     }
+
+    #[test]
+    fn test_user_prompt_follows_date_policy() {
+        let options = SpringOptions {
+            date_policy: DatePolicy { storage_zone: StorageZone::Utc, explicit_zone: true, ..Default::default() },
+            ..Default::default()
+        };
+        let intent = create_test_intent()
+            .with_column(ColumnIntent::new("reg_dt", "등록일시").with_data_type(DataType::DateTime))
+            .with_options(options);
+        let prompt = SpringPromptCompiler::compile_with_defaults(&intent, None);
+
+        assert!(prompt.user.contains("- OffsetDateTime regDt (등록일시)"));
+        assert!(prompt.user.contains("DATE/TIME POLICY:\n- Timestamps are stored in UTC (UTC)"));
+        assert!(prompt.user.contains("@JsonFormat(pattern = \"yyyy-MM-dd HH:mm:ss\", timezone = \"UTC\")"));
+        assert!(prompt.user.contains("typeHandler=org.apache.ibatis.type.OffsetDateTimeTypeHandler"));
+    }
 }
@@ -1,4 +1,5 @@
-use crate::domain::{CrudOperation, SpringArtifacts, SpringIntent, to_camel_case};
+use crate::domain::{CrudOperation, ErrorCodeCatalog, ForbiddenApi, SpringArtifacts, SpringIntent, LEGACY_DATE_APIS, to_camel_case};
+use crate::services::{MyBatisSqlChecker, MyBatisValidator};
 use anyhow::{anyhow, Result};
 use regex::Regex;
 
@@ -20,8 +21,9 @@ impl SpringValidator {
         warnings.extend(Self::validate_dto(&sections.dto, intent)?);
         warnings.extend(Self::validate_mapper(&sections.mapper_interface, intent)?);
         warnings.extend(Self::validate_mapper_xml(&sections.mapper_xml, intent)?);
+        warnings.extend(Self::validate_dates(&sections, intent));
 
-        Ok(SpringArtifacts {
+        let mut artifacts = SpringArtifacts {
             controller: sections.controller,
             service_interface: sections.service_interface,
             service_impl: sections.service_impl,
@@ -30,7 +32,20 @@ impl SpringValidator {
             mapper_interface: sections.mapper_interface,
             mapper_xml: sections.mapper_xml,
             warnings,
-        })
+            paths: None,
+            test_data: None,
+            test_data_filename: None,
+        };
+
+        // 3. Cross-check the mapper XML against the Java classes
+        let mapper_warnings = MyBatisValidator::check(&artifacts);
+        artifacts.warnings.extend(mapper_warnings);
+
+        // 4. Parse the statements' SQL in the source dialect
+        let sql_warnings = MyBatisSqlChecker::check(&artifacts.mapper_xml, intent);
+        artifacts.warnings.extend(sql_warnings);
+
+        Ok(artifacts)
     }
 
     /// Split LLM output into separate code sections
@@ -324,6 +339,102 @@ impl SpringValidator {
         Ok(warnings)
     }
 
+    /// Check date handling against the intent's date policy: no legacy
+    /// `java.util.Date` APIs, java.time fields with the policy's `@JsonFormat`,
+    /// and the policy's typeHandlers in the mapper XML
+    fn validate_dates(sections: &ParsedSections, intent: &SpringIntent) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let policy = &intent.options.date_policy;
+
+        let files = [
+            ("Controller", Some(&sections.controller)),
+            ("Service", Some(&sections.service_interface)),
+            ("ServiceImpl", Some(&sections.service_impl)),
+            ("DTO", Some(&sections.dto)),
+            ("SearchDTO", sections.search_dto.as_ref()),
+            ("Mapper", Some(&sections.mapper_interface)),
+        ];
+        // A bare `Date` field or `new Date()` is java.util.Date too
+        let bare_date = Regex::new(r"\bnew Date\(|(?:private|protected|public)\s+Date\s+\w+").unwrap();
+        for (label, code) in files {
+            let Some(code) = code else { continue };
+            for (api, replacement) in LEGACY_DATE_APIS {
+                if code.contains(api) {
+                    warnings.push(format!("Warning: [{}] Uses {}; use {} instead", label, api, replacement));
+                }
+            }
+            if !code.contains("java.util.Date") && bare_date.is_match(code) {
+                warnings.push(format!("Warning: [{}] Uses java.util.Date; use LocalDate / LocalDateTime instead", label));
+            }
+        }
+
+        let mut missing_handlers = Vec::new();
+        for col in &intent.columns {
+            let (Some(java_type), Some(pattern), Some(handler)) = (
+                policy.java_type(&col.data_type),
+                policy.pattern(&col.data_type),
+                policy.type_handler(&col.data_type),
+            ) else {
+                continue;
+            };
+            let field = to_camel_case(&col.name);
+            if !sections.mapper_xml.contains(handler) {
+                missing_handlers.push(col.name.clone());
+            }
+
+            // Field not found is reported by validate_dto
+            let declaration = Regex::new(&format!(
+                r"(?m)^[ \t]*(?:private|protected|public)\s+([\w.]+)\s+{}\s*[;=]",
+                regex::escape(&field)
+            ))
+            .unwrap();
+            let Some(found) = declaration.captures(&sections.dto) else { continue };
+            let declared = &found[1];
+            if declared != java_type {
+                warnings.push(format!(
+                    "Warning: DTO field '{}' is {}; the date policy expects {}",
+                    field, declared, java_type
+                ));
+            }
+
+            // Annotations directly above the declaration
+            let before = &sections.dto[..found.get(0).map_or(0, |m| m.start())];
+            let annotations: Vec<&str> = before
+                .lines()
+                .rev()
+                .map(str::trim)
+                .skip_while(|line| line.is_empty())
+                .take_while(|line| line.starts_with('@') || line.starts_with("//"))
+                .collect();
+            let Some(json_format) = annotations.iter().find(|a| a.starts_with("@JsonFormat")) else {
+                warnings.push(format!(
+                    "Warning: DTO field '{}' has no {}",
+                    field,
+                    policy.json_format(&col.data_type).unwrap_or_default()
+                ));
+                continue;
+            };
+            if !json_format.contains(&format!("\"{}\"", pattern)) {
+                warnings.push(format!(
+                    "Warning: @JsonFormat of '{}' does not use the pattern \"{}\"",
+                    field, pattern
+                ));
+            }
+            let zone = policy.storage_zone.zone_id();
+            if java_type != "LocalDate" && !json_format.contains(&format!("\"{}\"", zone)) {
+                warnings.push(format!("Warning: @JsonFormat of '{}' does not set timezone = \"{}\"", field, zone));
+            }
+        }
+        if !missing_handlers.is_empty() {
+            warnings.push(format!(
+                "Note: Mapper XML sets no date policy typeHandler for {}",
+                missing_handlers.join(", ")
+            ));
+        }
+
+        warnings
+    }
+
     /// Get expected method name for a CRUD operation
     fn expected_method_name(op: &CrudOperation, entity_name: &str) -> String {
         match op {
@@ -346,6 +457,55 @@ impl SpringValidator {
         }
     }
 
+    /// Flag company-forbidden APIs in every generated file
+    pub fn check_forbidden_apis(artifacts: &mut SpringArtifacts, apis: &[ForbiddenApi]) {
+        if apis.is_empty() {
+            return;
+        }
+
+        let mut warnings = Vec::new();
+        for (label, code) in Self::labelled_files(artifacts) {
+            for hit in ForbiddenApi::scan(code, apis) {
+                warnings.push(format!("Warning: [{}] {}", label, hit.message()));
+            }
+        }
+        artifacts.warnings.extend(warnings);
+    }
+
+    /// Flag error codes that are not in the project's error-code catalog
+    pub fn check_error_codes(artifacts: &mut SpringArtifacts, catalog: &ErrorCodeCatalog) {
+        if catalog.is_empty() {
+            return;
+        }
+
+        let mut warnings = Vec::new();
+        for (label, code) in Self::labelled_files(artifacts) {
+            for (line, unknown) in catalog.unknown_codes(code) {
+                warnings.push(format!(
+                    "Warning: [{}] Error code '{}' at line {} is not in the error-code catalog",
+                    label, unknown, line
+                ));
+            }
+        }
+        artifacts.warnings.extend(warnings);
+    }
+
+    /// Generated files with the label their warnings carry
+    fn labelled_files(artifacts: &SpringArtifacts) -> Vec<(&'static str, &String)> {
+        [
+            ("Controller", Some(&artifacts.controller)),
+            ("Service", Some(&artifacts.service_interface)),
+            ("ServiceImpl", Some(&artifacts.service_impl)),
+            ("DTO", Some(&artifacts.dto)),
+            ("SearchDTO", artifacts.search_dto.as_ref()),
+            ("Mapper", Some(&artifacts.mapper_interface)),
+            ("Mapper XML", Some(&artifacts.mapper_xml)),
+        ]
+        .into_iter()
+        .filter_map(|(label, code)| code.map(|code| (label, code)))
+        .collect()
+    }
+
     /// Post-process the output to fix common issues
     pub fn post_process(artifacts: &mut SpringArtifacts, intent: &SpringIntent) {
         // Add missing imports if detected
@@ -370,6 +530,8 @@ impl SpringValidator {
             ("@Valid", "javax.validation.Valid"),
             ("LocalDate", "java.time.LocalDate"),
             ("LocalDateTime", "java.time.LocalDateTime"),
+            ("OffsetDateTime", "java.time.OffsetDateTime"),
+            ("@JsonFormat", "com.fasterxml.jackson.annotation.JsonFormat"),
             ("BigDecimal", "java.math.BigDecimal"),
         ];
 
@@ -544,6 +706,73 @@ public class MemberDTO {
         assert!(warnings.iter().any(|w| w.contains("SQL injection")));
     }
 
+    #[test]
+    fn test_validate_dates_against_policy() {
+        let intent = create_test_intent()
+            .with_column(ColumnIntent::new("birth_date", "생년월일").with_data_type(DataType::Date))
+            .with_column(ColumnIntent::new("reg_dt", "등록일시").with_data_type(DataType::DateTime));
+        let sections = |dto: &str, service_impl: &str| ParsedSections {
+            controller: String::new(),
+            service_interface: String::new(),
+            service_impl: service_impl.to_string(),
+            dto: dto.to_string(),
+            search_dto: None,
+            mapper_interface: String::new(),
+            mapper_xml: r#"<result property="regDt" column="REG_DT" typeHandler="org.apache.ibatis.type.LocalDateTimeTypeHandler"/>"#
+                .to_string(),
+        };
+
+        let dto = r#"
+public class MemberDTO {
+    @JsonFormat(pattern = "yyyy-MM-dd")
+    private LocalDate birthDate;
+
+    @JsonFormat(pattern = "yyyy-MM-dd HH:mm:ss", timezone = "Asia/Seoul")
+    private LocalDateTime regDt;
+}
+"#;
+        let warnings = SpringValidator::validate_dates(&sections(dto, "dto.setRegDt(LocalDateTime.now(ZoneId.of(\"Asia/Seoul\")));"), &intent);
+        assert_eq!(warnings, vec!["Note: Mapper XML sets no date policy typeHandler for birth_date".to_string()]);
+
+        let legacy = r#"
+import java.util.Date;
+public class MemberDTO {
+    private LocalDate birthDate;
+
+    @JsonFormat(pattern = "yyyy/MM/dd HH:mm")
+    private Date regDt;
+}
+"#;
+        let warnings = SpringValidator::validate_dates(&sections(legacy, "new SimpleDateFormat(\"yyyyMMdd\")"), &intent);
+        assert!(warnings.contains(&"Warning: [DTO] Uses java.util.Date; use LocalDate / LocalDateTime instead".to_string()));
+        assert!(warnings.contains(&"Warning: [ServiceImpl] Uses SimpleDateFormat; use DateTimeFormatter instead".to_string()));
+        assert!(warnings.contains(&"Warning: DTO field 'birthDate' has no @JsonFormat(pattern = \"yyyy-MM-dd\")".to_string()));
+        assert!(warnings.contains(&"Warning: DTO field 'regDt' is Date; the date policy expects LocalDateTime".to_string()));
+        assert!(warnings.contains(&"Warning: @JsonFormat of 'regDt' does not use the pattern \"yyyy-MM-dd HH:mm:ss\"".to_string()));
+        assert!(warnings.contains(&"Warning: @JsonFormat of 'regDt' does not set timezone = \"Asia/Seoul\"".to_string()));
+    }
+
+    #[test]
+    fn test_check_error_codes() {
+        let catalog = ErrorCodeCatalog::from_csv("code,message,http_status\nE1001,회원 없음,404\n").unwrap();
+        let mut artifacts = SpringArtifacts {
+            service_impl: "public MemberDTO getMemberById(Long id) {\n    MemberDTO member = memberMapper.selectById(id);\n    \
+                           if (member == null) {\n        throw new BusinessException(\"E1001\");\n    }\n    \
+                           if (member.isLocked()) {\n        throw new BusinessException(\"E1403\");\n    }\n    return member;\n}"
+                .to_string(),
+            ..Default::default()
+        };
+
+        SpringValidator::check_error_codes(&mut artifacts, &ErrorCodeCatalog::default());
+        assert!(artifacts.warnings.is_empty());
+
+        SpringValidator::check_error_codes(&mut artifacts, &catalog);
+        assert_eq!(
+            artifacts.warnings,
+            vec!["Warning: [ServiceImpl] Error code 'E1403' at line 7 is not in the error-code catalog".to_string()]
+        );
+    }
+
     #[test]
     fn test_expected_method_names() {
         assert_eq!(
@@ -0,0 +1,729 @@
+//! Symbol Rename
+//!
+//! Renames a symbol or an entity consistently across the files of a
+//! generation (xFrame5 XML/JS, Java, MyBatis XML). Files are split into
+//! code, string and comment regions first, and only occurrences the symbol
+//! graph accounts for are touched:
+//!
+//! - Symbols (`fn_search`, `ds_task`, `grid_list`) are looked up with the
+//!   symbol linker and graph validator extractors. JS identifiers, XML
+//!   attribute values (`eventfunc:fn_search`, `link_data="ds_task:COL"`) and
+//!   JS strings consisting of exactly the name (transaction `input`/`output`,
+//!   popup callbacks) are renamed.
+//! - Entities (`task` → `job`) are renamed word by word inside identifiers,
+//!   keeping their case style: `ds_task` → `ds_job`, `TaskController` →
+//!   `JobController`, `selectTaskList` → `selectJobList`, file names included.
+//!   Upper-case names other than screen ids (`TASK_ID`, `TB_TASK`) are
+//!   database names and stay, as does SQL text outside `#{...}`.
+//!
+//! Occurrences left alone (comments, free text in strings, database names)
+//! are listed in the report so they can be checked by hand.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, bail, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::services::pipeline::passes::{GraphValidator, SymbolLinker};
+use crate::services::PackagedFile;
+
+/// What is renamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenameKind {
+    /// Event handler or other JS function
+    Function,
+    /// Dataset id
+    Dataset,
+    /// Grid or other XML component
+    Component,
+    /// Entity words inside identifiers
+    Entity,
+}
+
+/// Lines changed in one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenameChange {
+    pub file: String,
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// Occurrence that was not renamed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedOccurrence {
+    pub file: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Renamed files and the change report
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameResult {
+    pub kind: RenameKind,
+    pub files: Vec<PackagedFile>,
+    /// `(old name, new name)` of renamed files
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub renamed_files: Vec<(String, String)>,
+    pub changes: Vec<RenameChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedOccurrence>,
+    /// Handlers or bindings that no longer resolve after the rename
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    /// JavaScript and Java
+    Script,
+    /// xFrame5 screen or MyBatis mapper
+    Xml,
+    Other,
+}
+
+impl Lang {
+    fn of(name: &str) -> Self {
+        let lower = name.to_lowercase();
+        if lower.ends_with(".js") || lower.ends_with(".java") {
+            Self::Script
+        } else if lower.ends_with(".xml") {
+            Self::Xml
+        } else {
+            Self::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    /// Code, or tag and attribute names in XML
+    Code,
+    Comment,
+    /// String literal, or attribute value in XML
+    Str,
+    /// Text between XML tags (SQL in mappers)
+    Text,
+}
+
+/// Split JS/Java source into regions
+fn script_regions(text: &str) -> Vec<(Region, Range<usize>)> {
+    let b = text.as_bytes();
+    let mut regions = Vec::new();
+    let (mut i, mut start) = (0, 0);
+    let push = |region, range: Range<usize>, regions: &mut Vec<(Region, Range<usize>)>| {
+        if !range.is_empty() {
+            regions.push((region, range));
+        }
+    };
+
+    while i < b.len() {
+        let rest = &b[i..];
+        if rest.starts_with(b"//") || rest.starts_with(b"/*") {
+            push(Region::Code, start..i, &mut regions);
+            let end = if rest[1] == b'/' {
+                text[i..].find('\n').map_or(b.len(), |n| i + n)
+            } else {
+                text[i + 2..].find("*/").map_or(b.len(), |n| i + 2 + n + 2)
+            };
+            push(Region::Comment, i..end, &mut regions);
+            (i, start) = (end, end);
+        } else if matches!(b[i], b'"' | b'\'' | b'`') {
+            push(Region::Code, start..i, &mut regions);
+            let quote = b[i];
+            let mut j = i + 1;
+            while j < b.len() && b[j] != quote && (b[j] != b'\n' || quote == b'`') {
+                j += if b[j] == b'\\' { 2 } else { 1 };
+            }
+            let end = j.min(b.len());
+            // Region covers the content only
+            push(Region::Str, i + 1..end, &mut regions);
+            (i, start) = (end + 1, end + 1);
+        } else {
+            i += 1;
+        }
+    }
+    push(Region::Code, start.min(b.len())..b.len(), &mut regions);
+    regions
+}
+
+/// Split XML into regions
+fn xml_regions(text: &str) -> Vec<(Region, Range<usize>)> {
+    let b = text.as_bytes();
+    let mut regions = Vec::new();
+    let mut i = 0;
+    let mut push = |region, range: Range<usize>| {
+        if !range.is_empty() {
+            regions.push((region, range));
+        }
+    };
+
+    while i < b.len() {
+        let rest = &text[i..];
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(b.len(), |n| i + n + 3);
+            push(Region::Comment, i..end);
+            i = end;
+        } else if rest.starts_with("<![CDATA[") {
+            let end = rest.find("]]>").map_or(b.len(), |n| i + n + 3);
+            push(Region::Text, i..end);
+            i = end;
+        } else if b[i] == b'<' {
+            // Tag: names are code, quoted values are strings
+            let mut j = i;
+            let mut code_start = i;
+            while j < b.len() && b[j] != b'>' {
+                if matches!(b[j], b'"' | b'\'') {
+                    push(Region::Code, code_start..j);
+                    let quote = b[j];
+                    let end = text[j + 1..].find(quote as char).map_or(b.len(), |n| j + 1 + n);
+                    push(Region::Str, j + 1..end);
+                    j = end + 1;
+                    code_start = j;
+                } else {
+                    j += 1;
+                }
+            }
+            let end = (j + 1).min(b.len());
+            push(Region::Code, code_start.min(end)..end);
+            i = end;
+        } else {
+            let end = rest.find('<').map_or(b.len(), |n| i + n);
+            push(Region::Text, i..end);
+            i = end;
+        }
+    }
+    regions
+}
+
+fn identifier() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[A-Za-z_$][\w$]*").expect("Failed to compile identifier regex"))
+}
+
+/// MyBatis parameter references inside SQL text
+fn mybatis_param() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[#$]\{[^}]*\}").expect("Failed to compile MyBatis parameter regex"))
+}
+
+/// XML components with a name or id (grids, buttons, fields)
+fn component() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"<(\w+)\b[^>]*?\b(?:name|id)\s*=\s*"([^"]+)""#).expect("Failed to compile component regex")
+    })
+}
+
+/// Entity words inside identifiers
+#[derive(Debug, Clone)]
+struct EntityWords {
+    from: Vec<String>,
+    to: Vec<String>,
+}
+
+/// Words of an identifier with the separator before each (`_` or none)
+fn split_words(ident: &str) -> Vec<(bool, String)> {
+    let mut words: Vec<(bool, String)> = Vec::new();
+    for (n, part) in ident.split('_').enumerate() {
+        let chars: Vec<char> = part.chars().collect();
+        let mut current = String::new();
+        let mut first = true;
+        for (k, &c) in chars.iter().enumerate() {
+            let boundary = k > 0
+                && c.is_uppercase()
+                && (chars[k - 1].is_lowercase()
+                    || chars[k - 1].is_ascii_digit()
+                    || chars.get(k + 1).is_some_and(|next| next.is_lowercase()) && chars[k - 1].is_uppercase());
+            if boundary {
+                words.push((first && n > 0, std::mem::take(&mut current)));
+                first = false;
+            }
+            current.push(c);
+        }
+        words.push((first && n > 0, current));
+    }
+    words
+}
+
+impl EntityWords {
+    fn new(from: &str, to: &str) -> Self {
+        let words = |s: &str| s.split('_').map(str::to_string).collect();
+        Self { from: words(from), to: words(to) }
+    }
+
+    /// Identifier with the entity words replaced, keeping case style;
+    /// `None` when it doesn't contain them
+    fn apply(&self, ident: &str) -> Option<String> {
+        let words = split_words(ident);
+        let lower: Vec<String> = words.iter().map(|(_, w)| w.to_lowercase()).collect();
+        let n = self.from.len();
+        let mut out: Vec<(bool, String)> = Vec::new();
+        let mut i = 0;
+        let mut changed = false;
+        while i < words.len() {
+            if i + n <= words.len() && lower[i..i + n] == self.from[..] {
+                let (sep, first) = &words[i];
+                let snake = ident.contains('_');
+                let upper = first.len() > 1 && first.chars().all(|c| !c.is_lowercase());
+                let capital = first.chars().next().is_some_and(char::is_uppercase);
+                for (k, word) in self.to.iter().enumerate() {
+                    let styled = if upper {
+                        word.to_uppercase()
+                    } else if capital || (k > 0 && !snake) {
+                        capitalize(word)
+                    } else {
+                        word.clone()
+                    };
+                    out.push((if k == 0 { *sep } else { snake }, styled));
+                }
+                i += n;
+                changed = true;
+            } else {
+                out.push(words[i].clone());
+                i += 1;
+            }
+        }
+        changed.then(|| out.into_iter().map(|(sep, w)| if sep { format!("_{}", w) } else { w }).collect())
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Upper-case database name (column, table); screen ids are not
+fn is_database_name(ident: &str) -> bool {
+    ident.chars().any(char::is_alphabetic)
+        && !ident.chars().any(char::is_lowercase)
+        && !ident.starts_with("SCREEN_")
+}
+
+/// What the rename replaces
+enum Target {
+    Symbol(String),
+    Entity(EntityWords),
+}
+
+struct FileRename<'a> {
+    name: &'a str,
+    text: &'a str,
+    edits: Vec<(Range<usize>, String)>,
+    skipped: Vec<(usize, &'static str)>,
+}
+
+impl FileRename<'_> {
+    fn line_of(&self, offset: usize) -> usize {
+        self.text[..offset].matches('\n').count() + 1
+    }
+
+    fn skip(&mut self, offset: usize, reason: &'static str) {
+        let line = self.line_of(offset);
+        if !self.skipped.iter().any(|(l, r)| *l == line && *r == reason) {
+            self.skipped.push((line, reason));
+        }
+    }
+
+    /// Rename identifiers in `range`; a JS string is renamed only when it is exactly the symbol
+    fn identifiers(&mut self, target: &Target, range: Range<usize>, region: Region) {
+        let slice = &self.text[range.clone()];
+        if let (Target::Symbol(name), Region::Str, Lang::Script) = (target, region, Lang::of(self.name)) {
+            if slice == name {
+                self.edits.push((range, String::new()));
+            } else if identifier().find_iter(slice).any(|m| m.as_str() == name) {
+                self.skip(range.start, "text inside a string");
+            }
+            return;
+        }
+
+        let matches: Vec<(Range<usize>, String)> = identifier()
+            .find_iter(slice)
+            .map(|m| (range.start + m.start()..range.start + m.end(), m.as_str().to_string()))
+            .collect();
+        for (at, ident) in matches {
+            match target {
+                Target::Symbol(name) if ident == *name => self.edits.push((at, String::new())),
+                Target::Entity(words) => {
+                    if let Some(renamed) = words.apply(&ident) {
+                        if is_database_name(&ident) {
+                            self.skip(at.start, "database name");
+                        } else {
+                            self.edits.push((at, renamed));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn mentions(target: &Target, text: &str) -> bool {
+        identifier().find_iter(text).any(|m| match target {
+            Target::Symbol(name) => m.as_str() == name,
+            Target::Entity(words) => words.apply(m.as_str()).is_some(),
+        })
+    }
+
+    fn run(&mut self, target: &Target, to: &str) {
+        let lang = Lang::of(self.name);
+        let regions = match lang {
+            Lang::Script => script_regions(self.text),
+            Lang::Xml => xml_regions(self.text),
+            Lang::Other => return,
+        };
+        let is_mapper = lang == Lang::Xml && self.text.contains("<mapper");
+
+        for (region, range) in regions {
+            let slice = &self.text[range.clone()];
+            match region {
+                Region::Comment => {
+                    if Self::mentions(target, slice) {
+                        self.skip(range.start, "comment");
+                    }
+                }
+                Region::Code if lang == Lang::Xml => {}
+                Region::Code | Region::Str => {
+                    // Symbols live in screens only; Java and mappers are reported
+                    if matches!(target, Target::Symbol(_)) && (is_mapper || self.name.ends_with(".java")) {
+                        if Self::mentions(target, slice) {
+                            self.skip(range.start, "not part of the screen");
+                        }
+                        continue;
+                    }
+                    self.identifiers(target, range, region);
+                }
+                Region::Text => {
+                    if is_mapper && matches!(target, Target::Entity(_)) {
+                        let params: Vec<Range<usize>> = mybatis_param()
+                            .find_iter(slice)
+                            .map(|m| range.start + m.start()..range.start + m.end())
+                            .collect();
+                        for param in params {
+                            self.identifiers(target, param, Region::Code);
+                        }
+                    }
+                    let sql = mybatis_param().replace_all(slice, "");
+                    if Self::mentions(target, &sql) {
+                        self.skip(range.start, if is_mapper { "SQL text" } else { "element text" });
+                    }
+                }
+            }
+        }
+
+        // Symbol edits carry the new name
+        for (_, replacement) in self.edits.iter_mut() {
+            if replacement.is_empty() {
+                *replacement = to.to_string();
+            }
+        }
+    }
+
+    fn apply(&self) -> String {
+        let mut edits = self.edits.clone();
+        edits.sort_by_key(|(range, _)| range.start);
+        let mut out = String::with_capacity(self.text.len());
+        let mut pos = 0;
+        for (range, replacement) in edits {
+            if range.start < pos {
+                continue;
+            }
+            out.push_str(&self.text[pos..range.start]);
+            out.push_str(&replacement);
+            pos = range.end;
+        }
+        out.push_str(&self.text[pos..]);
+        out
+    }
+}
+
+/// Symbols of the screens in a file set
+#[derive(Debug, Default)]
+struct SymbolGraph {
+    functions: BTreeSet<String>,
+    handlers: BTreeSet<String>,
+    datasets: BTreeSet<String>,
+    bindings: Vec<(String, String)>,
+    components: BTreeSet<String>,
+}
+
+impl SymbolGraph {
+    fn build(files: &[PackagedFile]) -> Self {
+        let linker = SymbolLinker::new();
+        let graph = GraphValidator::new();
+        let mut symbols = Self::default();
+        for file in files {
+            match Lang::of(&file.name) {
+                Lang::Xml if !file.content.contains("<mapper") => {
+                    symbols.handlers.extend(linker.extract_xml_handlers(&file.content));
+                    symbols.datasets.extend(graph.extract_datasets(&file.content));
+                    symbols.bindings.extend(graph.extract_link_data_refs(&file.content));
+                    symbols.components.extend(
+                        component()
+                            .captures_iter(&file.content)
+                            .filter(|c| !c[1].to_lowercase().contains("dataset") && &c[1] != "screen")
+                            .map(|c| c[2].to_string()),
+                    );
+                }
+                Lang::Script if file.name.ends_with(".js") => {
+                    symbols.functions.extend(linker.extract_js_functions(&file.content));
+                }
+                _ => {}
+            }
+        }
+        symbols
+    }
+
+    fn kind_of(&self, name: &str) -> Option<RenameKind> {
+        if self.datasets.contains(name) {
+            Some(RenameKind::Dataset)
+        } else if self.functions.contains(name) || self.handlers.contains(name) {
+            Some(RenameKind::Function)
+        } else if self.components.contains(name) {
+            Some(RenameKind::Component)
+        } else {
+            None
+        }
+    }
+
+    /// Handlers without a function and bindings to unknown datasets
+    fn dangling(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = self
+            .handlers
+            .iter()
+            .filter(|h| !self.functions.contains(*h))
+            .map(|h| format!("Handler '{}' has no JS function", h))
+            .collect();
+        warnings.extend(
+            self.bindings
+                .iter()
+                .filter(|(_, ds)| !self.datasets.contains(ds))
+                .map(|(element, ds)| format!("<{}> binds unknown dataset '{}'", element, ds)),
+        );
+        warnings
+    }
+}
+
+/// Renames symbols and entities across generated files
+pub struct SymbolRenamer;
+
+impl SymbolRenamer {
+    /// Rename `from` to `to`; the kind is looked up in the symbol graph
+    /// unless given, and names not in the graph are treated as entities
+    pub fn rename(files: &[PackagedFile], from: &str, to: &str, kind: Option<RenameKind>) -> Result<RenameResult> {
+        let (from, to) = (from.trim(), to.trim());
+        if from == to {
+            bail!("'{}' and '{}' are the same name", from, to);
+        }
+        let graph = SymbolGraph::build(files);
+        let kind = match kind.or_else(|| graph.kind_of(from)) {
+            Some(kind) => kind,
+            None if entity_name().is_match(from) => RenameKind::Entity,
+            None => bail!("Symbol '{}' not found in the files", from),
+        };
+
+        let target = if kind == RenameKind::Entity {
+            if !entity_name().is_match(from) || !entity_name().is_match(to) {
+                bail!("Entity names must be lower snake_case words (e.g. order_detail)");
+            }
+            Target::Entity(EntityWords::new(from, to))
+        } else {
+            if graph.kind_of(from) != Some(kind) {
+                bail!("'{}' is not a {:?} in these files", from, kind);
+            }
+            if identifier().find(to).is_none_or(|m| m.as_str() != to) {
+                bail!("'{}' is not a valid identifier", to);
+            }
+            if let Some(existing) = graph.kind_of(to) {
+                bail!("'{}' already exists ({:?})", to, existing);
+            }
+            Target::Symbol(from.to_string())
+        };
+
+        let mut result = RenameResult {
+            kind,
+            files: Vec::new(),
+            renamed_files: Vec::new(),
+            changes: Vec::new(),
+            skipped: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for file in files {
+            let mut rename = FileRename {
+                name: &file.name,
+                text: &file.content,
+                edits: Vec::new(),
+                skipped: Vec::new(),
+            };
+            rename.run(&target, to);
+            let content = rename.apply();
+
+            let name = match &target {
+                Target::Entity(words) => Self::rename_path(&file.name, words),
+                Target::Symbol(_) => file.name.clone(),
+            };
+            if name != file.name {
+                result.renamed_files.push((file.name.clone(), name.clone()));
+            }
+            for (line, (before, after)) in file.content.lines().zip(content.lines()).enumerate() {
+                if before != after {
+                    result.changes.push(RenameChange {
+                        file: name.clone(),
+                        line: line + 1,
+                        before: before.to_string(),
+                        after: after.to_string(),
+                    });
+                }
+            }
+            result.skipped.extend(rename.skipped.into_iter().map(|(line, reason)| SkippedOccurrence {
+                file: name.clone(),
+                line,
+                reason: reason.to_string(),
+            }));
+            result.files.push(PackagedFile { name, content });
+        }
+
+        if result.changes.is_empty() && result.renamed_files.is_empty() {
+            return Err(anyhow!("No occurrence of '{}' could be renamed", from));
+        }
+        let before = graph.dangling();
+        result.warnings = SymbolGraph::build(&result.files)
+            .dangling()
+            .into_iter()
+            .filter(|w| !before.contains(w))
+            .collect();
+        Ok(result)
+    }
+
+    /// Path with the entity renamed in every directory and file stem
+    fn rename_path(path: &str, words: &EntityWords) -> String {
+        identifier()
+            .replace_all(path, |caps: &regex::Captures| {
+                let ident = &caps[0];
+                words.apply(ident).unwrap_or_else(|| ident.to_string())
+            })
+            .into_owned()
+    }
+}
+
+fn entity_name() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^[a-z][a-z0-9]*(_[a-z0-9]+)*$").expect("Failed to compile entity name regex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, content: &str) -> PackagedFile {
+        PackagedFile { name: name.to_string(), content: content.to_string() }
+    }
+
+    fn screen() -> Vec<PackagedFile> {
+        vec![
+            file(
+                "task_list.xml",
+                r#"<screen id="SCREEN_TASK_LIST">
+  <xlinkdataset id="ds_task" columns="TASK_ID:STRING"/>
+  <!-- ds_task holds the search result -->
+  <grid name="grid_task" link_data="ds_task" on_itemdblclick="eventfunc:fn_search"/>
+  <pushbutton name="btn_search" on_click="eventfunc:fn_search"/>
+</screen>"#,
+            ),
+            file(
+                "task_list.js",
+                r#"// fn_search loads ds_task
+this.fn_search = function() {
+    ds_task.deleteall();
+    xcomm.execute({ url: "/task/selectTaskList.do", output: "ds_task", callback: "fn_search_callback" });
+    console.log("fn_search done");
+};
+this.fn_search_callback = function() { fn_search(); };
+"#,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_split_and_apply_entity_words() {
+        let words = EntityWords::new("task", "work_item");
+        assert_eq!(words.apply("ds_task").as_deref(), Some("ds_work_item"));
+        assert_eq!(words.apply("TaskController").as_deref(), Some("WorkItemController"));
+        assert_eq!(words.apply("selectTaskList").as_deref(), Some("selectWorkItemList"));
+        assert_eq!(words.apply("taskService").as_deref(), Some("workItemService"));
+        assert_eq!(words.apply("TaskDTO").as_deref(), Some("WorkItemDTO"));
+        assert_eq!(words.apply("SCREEN_TASK_LIST").as_deref(), Some("SCREEN_WORK_ITEM_LIST"));
+        assert_eq!(words.apply("tasks"), None);
+    }
+
+    #[test]
+    fn test_function_rename_follows_graph() {
+        let result = SymbolRenamer::rename(&screen(), "fn_search", "fn_query", None).unwrap();
+        assert_eq!(result.kind, RenameKind::Function);
+        let xml = &result.files[0].content;
+        let js = &result.files[1].content;
+        assert_eq!(xml.matches("eventfunc:fn_query").count(), 2);
+        assert!(js.contains("this.fn_query = function"));
+        assert!(js.contains("fn_query();"));
+        // Other symbols and free text stay
+        assert!(js.contains("\"fn_search_callback\""));
+        assert!(js.contains("\"fn_search done\""));
+        assert!(js.starts_with("// fn_search loads"));
+        assert_eq!(result.skipped.len(), 2);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_dataset_rename_covers_bindings_and_strings() {
+        let result = SymbolRenamer::rename(&screen(), "ds_task", "ds_job", None).unwrap();
+        assert_eq!(result.kind, RenameKind::Dataset);
+        let xml = &result.files[0].content;
+        let js = &result.files[1].content;
+        assert!(xml.contains(r#"<xlinkdataset id="ds_job""#));
+        assert!(xml.contains(r#"link_data="ds_job""#));
+        assert!(xml.contains("<!-- ds_task holds"));
+        assert!(js.contains("ds_job.deleteall()"));
+        assert!(js.contains(r#"output: "ds_job""#));
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_conflicting_symbols() {
+        assert!(SymbolRenamer::rename(&screen(), "fn_missing", "fn_x", None).is_err());
+        assert!(SymbolRenamer::rename(&screen(), "fn_search", "fn_search_callback", None).is_err());
+        assert!(SymbolRenamer::rename(&screen(), "ds_task", "ds job", None).is_err());
+    }
+
+    #[test]
+    fn test_entity_rename_across_screen_and_spring() {
+        let mut files = screen();
+        files.push(file(
+            "src/main/java/com/acme/task/TaskMapper.java",
+            "package com.acme.task;\n\npublic interface TaskMapper {\n    List<TaskDTO> selectTaskList(TaskSearchDTO search);\n}\n",
+        ));
+        files.push(file(
+            "src/main/resources/mapper/TaskMapper.xml",
+            "<mapper namespace=\"com.acme.task.TaskMapper\">\n  <select id=\"selectTaskList\" resultType=\"TaskDTO\">\n    SELECT TASK_ID FROM TB_TASK WHERE TASK_ID = #{taskId}\n  </select>\n</mapper>\n",
+        ));
+
+        let result = SymbolRenamer::rename(&files, "task", "job", None).unwrap();
+        assert_eq!(result.kind, RenameKind::Entity);
+        assert!(result
+            .renamed_files
+            .contains(&("src/main/java/com/acme/task/TaskMapper.java".to_string(), "src/main/java/com/acme/job/JobMapper.java".to_string())));
+        assert!(result.files[0].content.contains("SCREEN_JOB_LIST"));
+        assert!(result.files[0].content.contains("TASK_ID:STRING"));
+        assert!(result.files[1].content.contains("/job/selectJobList.do"));
+        assert!(result.files[2].content.contains("List<JobDTO> selectJobList(JobSearchDTO search);"));
+        let mapper = &result.files[3].content;
+        assert!(mapper.contains("namespace=\"com.acme.job.JobMapper\""));
+        assert!(mapper.contains("FROM TB_TASK WHERE TASK_ID = #{jobId}"));
+        assert!(result.skipped.iter().any(|s| s.reason == "SQL text"));
+    }
+}
@@ -0,0 +1,368 @@
+//! Telemetry (OpenTelemetry export)
+//!
+//! Lightweight span and counter recording that is pushed to an OTLP/HTTP
+//! collector (Tempo, Grafana Agent, OTel Collector, ...) in the background.
+//! Disabled unless `OTEL_EXPORTER_OTLP_ENDPOINT` is set, in which case every
+//! call here is a cheap no-op.
+//!
+//! Trace shape: `http.request` (controller) → `generation.job` (worker) →
+//! `llm.generate` / `pipeline.run` (service). The active span travels with
+//! the request future via a task-local, and across the job queue as a W3C
+//! `traceparent` string stored in the job payload.
+//!
+//! As with audit logs, spans carry meta data only (product, screen type,
+//! status, sizes) - never prompts, inputs or generated code.
+
+mod otlp;
+
+pub use otlp::start_exporter;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Finished spans kept while the collector is unreachable (oldest are dropped)
+const MAX_BUFFERED_SPANS: usize = 2048;
+
+/// Export settings, read from the standard OTel environment variables
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    /// Collector base URL (`OTEL_EXPORTER_OTLP_ENDPOINT`), e.g. `http://otel-collector:4318`
+    pub endpoint: String,
+
+    /// Extra request headers (`OTEL_EXPORTER_OTLP_HEADERS`, `k1=v1,k2=v2`)
+    pub headers: Vec<(String, String)>,
+
+    /// Reported `service.name` (`OTEL_SERVICE_NAME`, default `coder`)
+    pub service_name: String,
+
+    /// Push interval (`OTEL_EXPORT_INTERVAL_SECS`, default 10)
+    pub export_interval: Duration,
+}
+
+impl TelemetryConfig {
+    /// Load from environment; `None` when no endpoint is configured
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+        let endpoint = endpoint.trim().trim_end_matches('/').to_string();
+        if endpoint.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            endpoint,
+            headers: Self::parse_headers(
+                &std::env::var("OTEL_EXPORTER_OTLP_HEADERS").unwrap_or_default(),
+            ),
+            service_name: std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "coder".to_string()),
+            export_interval: Duration::from_secs(
+                std::env::var("OTEL_EXPORT_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10)
+                    .max(1),
+            ),
+        })
+    }
+
+    /// Parse `k1=v1,k2=v2` (values may contain `=`)
+    pub fn parse_headers(raw: &str) -> Vec<(String, String)> {
+        raw.split(',')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .filter(|(k, _)| !k.is_empty())
+            .collect()
+    }
+}
+
+/// Span attribute value
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl From<&str> for AttrValue {
+    fn from(v: &str) -> Self {
+        Self::Str(v.to_string())
+    }
+}
+
+impl From<String> for AttrValue {
+    fn from(v: String) -> Self {
+        Self::Str(v)
+    }
+}
+
+impl From<i64> for AttrValue {
+    fn from(v: i64) -> Self {
+        Self::Int(v)
+    }
+}
+
+impl From<usize> for AttrValue {
+    fn from(v: usize) -> Self {
+        Self::Int(v as i64)
+    }
+}
+
+impl From<bool> for AttrValue {
+    fn from(v: bool) -> Self {
+        Self::Bool(v)
+    }
+}
+
+/// OTLP span kind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpanKind {
+    Internal = 1,
+    Server = 2,
+    Client = 3,
+}
+
+/// Trace/span identifiers (hex), propagated between components
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanContext {
+    pub trace_id: String,
+    pub span_id: String,
+}
+
+impl SpanContext {
+    /// W3C `traceparent` header value
+    pub fn to_traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id, self.span_id)
+    }
+
+    /// Parse a W3C `traceparent` value
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.trim().split('-').collect();
+        let is_hex = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit());
+        match parts.as_slice() {
+            [_, trace_id, span_id, _] if is_hex(trace_id, 32) && is_hex(span_id, 16) => Some(Self {
+                trace_id: trace_id.to_lowercase(),
+                span_id: span_id.to_lowercase(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A timed operation; recorded for export when ended
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub context: SpanContext,
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub kind: SpanKind,
+    pub start_unix_nano: u64,
+    pub end_unix_nano: u64,
+    pub attributes: Vec<(String, AttrValue)>,
+    pub error: Option<String>,
+}
+
+tokio::task_local! {
+    static CURRENT_SPAN: SpanContext;
+}
+
+impl Span {
+    /// Start a span under `parent`, or a new trace when there is none
+    pub fn start(name: &str, kind: SpanKind, parent: Option<&SpanContext>) -> Self {
+        let trace_id = parent
+            .map(|p| p.trace_id.clone())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+        Self {
+            context: SpanContext {
+                trace_id,
+                span_id: uuid::Uuid::new_v4().simple().to_string()[..16].to_string(),
+            },
+            parent_span_id: parent.map(|p| p.span_id.clone()),
+            name: name.to_string(),
+            kind,
+            start_unix_nano: now_unix_nano(),
+            end_unix_nano: 0,
+            attributes: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Start a span under the span active in the current task (if any)
+    pub fn child(name: &str, kind: SpanKind) -> Self {
+        Self::start(name, kind, current_context().as_ref())
+    }
+
+    pub fn set_attr(&mut self, key: &str, value: impl Into<AttrValue>) {
+        self.attributes.push((key.to_string(), value.into()));
+    }
+
+    /// Builder form of `set_attr`
+    pub fn with_attr(mut self, key: &str, value: impl Into<AttrValue>) -> Self {
+        self.set_attr(key, value);
+        self
+    }
+
+    /// Mark the span as failed
+    pub fn set_error(&mut self, message: impl ToString) {
+        self.error = Some(message.to_string());
+    }
+
+    /// Run `fut` with this span as the parent of any `Span::child` inside it
+    pub async fn in_scope<F: Future>(&self, fut: F) -> F::Output {
+        CURRENT_SPAN.scope(self.context.clone(), fut).await
+    }
+
+    /// Finish the span and queue it for export
+    pub fn end(mut self) {
+        let telemetry = telemetry();
+        if !telemetry.is_enabled() {
+            return;
+        }
+        self.end_unix_nano = now_unix_nano();
+        let mut spans = telemetry.spans.lock().unwrap();
+        if spans.len() >= MAX_BUFFERED_SPANS {
+            spans.remove(0);
+        }
+        spans.push(self);
+    }
+}
+
+/// Context of the span active in the current task, if any
+pub fn current_context() -> Option<SpanContext> {
+    CURRENT_SPAN.try_with(|ctx| ctx.clone()).ok()
+}
+
+/// Await `fut` inside `span`, marking the span failed on `Err`
+pub async fn traced<T, E, F>(mut span: Span, fut: F) -> Result<T, E>
+where
+    E: std::fmt::Display,
+    F: Future<Output = Result<T, E>>,
+{
+    let result = span.in_scope(fut).await;
+    if let Err(e) = &result {
+        span.set_error(e);
+    }
+    span.end();
+    result
+}
+
+/// Cumulative generation counters, keyed by (product, status)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenerationStats {
+    pub count: u64,
+    pub total_duration_ms: u64,
+}
+
+/// Global telemetry state
+pub struct Telemetry {
+    config: Option<TelemetryConfig>,
+    spans: Arc<Mutex<Vec<Span>>>,
+    generations: Arc<Mutex<HashMap<(String, String), GenerationStats>>>,
+    start_unix_nano: u64,
+}
+
+impl Telemetry {
+    fn new(config: Option<TelemetryConfig>) -> Self {
+        Self {
+            config,
+            spans: Arc::new(Mutex::new(Vec::new())),
+            generations: Arc::new(Mutex::new(HashMap::new())),
+            start_unix_nano: now_unix_nano(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    pub fn config(&self) -> Option<&TelemetryConfig> {
+        self.config.as_ref()
+    }
+
+    /// Count a finished generation (exported as `coder.generations` / `coder.generation.duration`)
+    pub fn record_generation(&self, product: &str, status: &str, duration_ms: u64) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut generations = self.generations.lock().unwrap();
+        let stats = generations
+            .entry((product.to_string(), status.to_string()))
+            .or_default();
+        stats.count += 1;
+        stats.total_duration_ms += duration_ms;
+    }
+
+    /// Take all finished spans
+    fn drain_spans(&self) -> Vec<Span> {
+        std::mem::take(&mut *self.spans.lock().unwrap())
+    }
+
+    /// Put spans back after a failed export (bounded)
+    fn requeue_spans(&self, mut failed: Vec<Span>) {
+        let mut spans = self.spans.lock().unwrap();
+        failed.append(&mut spans);
+        let excess = failed.len().saturating_sub(MAX_BUFFERED_SPANS);
+        failed.drain(..excess);
+        *spans = failed;
+    }
+
+    fn generation_stats(&self) -> HashMap<(String, String), GenerationStats> {
+        self.generations.lock().unwrap().clone()
+    }
+}
+
+/// Global telemetry instance
+static TELEMETRY: std::sync::OnceLock<Telemetry> = std::sync::OnceLock::new();
+
+/// Get the global telemetry instance (configured from env on first use)
+pub fn telemetry() -> &'static Telemetry {
+    TELEMETRY.get_or_init(|| Telemetry::new(TelemetryConfig::from_env()))
+}
+
+fn now_unix_nano() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_headers() {
+        let headers = TelemetryConfig::parse_headers("Authorization=Basic abc==, X-Scope-OrgID=tenant1,bad");
+        assert_eq!(
+            headers,
+            vec![
+                ("Authorization".to_string(), "Basic abc==".to_string()),
+                ("X-Scope-OrgID".to_string(), "tenant1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_traceparent_roundtrip() {
+        let span = Span::start("http.request", SpanKind::Server, None);
+        let parsed = SpanContext::from_traceparent(&span.context.to_traceparent()).unwrap();
+        assert_eq!(parsed, span.context);
+        assert_eq!(parsed.trace_id.len(), 32);
+        assert_eq!(parsed.span_id.len(), 16);
+        assert!(SpanContext::from_traceparent("00-xyz-123-01").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_child_inherits_scoped_parent() {
+        let root = Span::start("generation.job", SpanKind::Internal, None);
+        let child = root
+            .in_scope(async { Span::child("llm.generate", SpanKind::Client) })
+            .await;
+
+        assert_eq!(child.context.trace_id, root.context.trace_id);
+        assert_eq!(child.parent_span_id.as_deref(), Some(root.context.span_id.as_str()));
+
+        let orphan = Span::child("llm.generate", SpanKind::Client);
+        assert!(orphan.parent_span_id.is_none());
+    }
+}
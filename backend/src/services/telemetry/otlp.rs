@@ -0,0 +1,216 @@
+//! OTLP/HTTP JSON encoding and the background exporter
+//!
+//! Uses the JSON flavour of OTLP (`/v1/traces`, `/v1/metrics`) so no
+//! protobuf/gRPC stack is needed; every OTLP/HTTP receiver accepts it.
+
+use serde_json::{json, Value};
+use tokio::time::interval;
+
+use super::{now_unix_nano, telemetry, AttrValue, Span, Telemetry, TelemetryConfig};
+use crate::services::metrics_history::get_metrics_store;
+
+const SCOPE_NAME: &str = "coder";
+
+/// OTLP `AGGREGATION_TEMPORALITY_CUMULATIVE`
+const CUMULATIVE: u8 = 2;
+
+/// OTLP `STATUS_CODE_ERROR`
+const STATUS_ERROR: u8 = 2;
+
+fn attr(key: &str, value: &AttrValue) -> Value {
+    let value = match value {
+        AttrValue::Str(s) => json!({ "stringValue": s }),
+        // OTLP JSON encodes 64-bit integers as strings
+        AttrValue::Int(i) => json!({ "intValue": i.to_string() }),
+        AttrValue::Bool(b) => json!({ "boolValue": b }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn resource(config: &TelemetryConfig) -> Value {
+    json!({
+        "attributes": [
+            attr("service.name", &AttrValue::from(config.service_name.as_str())),
+            attr("service.version", &AttrValue::from(env!("CARGO_PKG_VERSION"))),
+        ]
+    })
+}
+
+/// Encode finished spans as an OTLP `ExportTraceServiceRequest`
+pub(super) fn encode_spans(config: &TelemetryConfig, spans: &[Span]) -> Value {
+    let spans: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let mut encoded = json!({
+                "traceId": span.context.trace_id,
+                "spanId": span.context.span_id,
+                "parentSpanId": span.parent_span_id.clone().unwrap_or_default(),
+                "name": span.name,
+                "kind": span.kind as u8,
+                "startTimeUnixNano": span.start_unix_nano.to_string(),
+                "endTimeUnixNano": span.end_unix_nano.to_string(),
+                "attributes": span.attributes.iter().map(|(k, v)| attr(k, v)).collect::<Vec<_>>(),
+            });
+            if let Some(message) = &span.error {
+                encoded["status"] = json!({ "code": STATUS_ERROR, "message": message });
+            }
+            encoded
+        })
+        .collect();
+
+    json!({
+        "resourceSpans": [{
+            "resource": resource(config),
+            "scopeSpans": [{ "scope": { "name": SCOPE_NAME }, "spans": spans }]
+        }]
+    })
+}
+
+/// Encode generation counters and the latest system sample as an OTLP `ExportMetricsServiceRequest`
+pub(super) fn encode_metrics(config: &TelemetryConfig, telemetry: &Telemetry) -> Value {
+    let now = now_unix_nano().to_string();
+    let start = telemetry.start_unix_nano.to_string();
+
+    let mut counts = Vec::new();
+    let mut durations = Vec::new();
+    for ((product, status), stats) in telemetry.generation_stats() {
+        let attributes = json!([
+            attr("product", &AttrValue::from(product)),
+            attr("status", &AttrValue::from(status)),
+        ]);
+        let point = |value: u64| {
+            json!({
+                "attributes": attributes,
+                "startTimeUnixNano": start,
+                "timeUnixNano": now,
+                "asInt": value.to_string(),
+            })
+        };
+        counts.push(point(stats.count));
+        durations.push(point(stats.total_duration_ms));
+    }
+
+    let sum = |name: &str, unit: &str, points: Vec<Value>| {
+        json!({
+            "name": name,
+            "unit": unit,
+            "sum": { "aggregationTemporality": CUMULATIVE, "isMonotonic": true, "dataPoints": points }
+        })
+    };
+    let mut metrics = vec![
+        sum("coder.generations", "1", counts),
+        sum("coder.generation.duration", "ms", durations),
+    ];
+
+    if let Some(sample) = get_metrics_store().get_latest() {
+        let gauge = |name: &str, value: f32| {
+            json!({
+                "name": name,
+                "unit": "%",
+                "gauge": { "dataPoints": [{ "timeUnixNano": now, "asDouble": value }] }
+            })
+        };
+        metrics.push(gauge("system.cpu.utilization", sample.cpu_usage));
+        metrics.push(gauge("system.memory.utilization", sample.memory_usage));
+        metrics.push(gauge("system.disk.utilization", sample.disk_usage));
+    }
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource(config),
+            "scopeMetrics": [{ "scope": { "name": SCOPE_NAME }, "metrics": metrics }]
+        }]
+    })
+}
+
+async fn post(client: &reqwest::Client, config: &TelemetryConfig, path: &str, body: &Value) -> anyhow::Result<()> {
+    let mut request = client.post(format!("{}{}", config.endpoint, path)).json(body);
+    for (key, value) in &config.headers {
+        request = request.header(key, value);
+    }
+    request.send().await?.error_for_status()?;
+    Ok(())
+}
+
+/// Start the background OTLP push loop (no-op when telemetry is disabled)
+pub fn start_exporter() {
+    let Some(config) = telemetry().config().cloned() else {
+        tracing::debug!("OTLP export disabled (OTEL_EXPORTER_OTLP_ENDPOINT not set)");
+        return;
+    };
+
+    tracing::info!(
+        "OTLP export enabled: {} (interval: {}s)",
+        config.endpoint,
+        config.export_interval.as_secs()
+    );
+
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let mut ticker = interval(config.export_interval);
+
+        loop {
+            ticker.tick().await;
+            let telemetry = telemetry();
+
+            let spans = telemetry.drain_spans();
+            if !spans.is_empty() {
+                if let Err(e) = post(&client, &config, "/v1/traces", &encode_spans(&config, &spans)).await {
+                    tracing::warn!("OTLP trace export failed ({} spans kept): {}", spans.len(), e);
+                    telemetry.requeue_spans(spans);
+                }
+            }
+
+            if let Err(e) = post(&client, &config, "/v1/metrics", &encode_metrics(&config, telemetry)).await {
+                tracing::warn!("OTLP metrics export failed: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::telemetry::SpanKind;
+    use std::time::Duration;
+
+    fn config() -> TelemetryConfig {
+        TelemetryConfig {
+            endpoint: "http://collector:4318".to_string(),
+            headers: vec![],
+            service_name: "coder-test".to_string(),
+            export_interval: Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn test_encode_spans() {
+        let root = Span::start("generation.job", SpanKind::Internal, None);
+        let mut child = Span::start("llm.generate", SpanKind::Client, Some(&root.context));
+        child.set_attr("prompt_chars", 1200usize);
+        child.set_error("timeout");
+
+        let body = encode_spans(&config(), &[child]);
+        let resource = &body["resourceSpans"][0];
+        assert_eq!(resource["resource"]["attributes"][0]["value"]["stringValue"], "coder-test");
+
+        let span = &resource["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["parentSpanId"], root.context.span_id.as_str());
+        assert_eq!(span["kind"], 3);
+        assert_eq!(span["attributes"][0]["value"]["intValue"], "1200");
+        assert_eq!(span["status"]["code"], 2);
+    }
+
+    #[test]
+    fn test_encode_metrics() {
+        let telemetry = Telemetry::new(Some(config()));
+        telemetry.record_generation("xframe5-ui", "success", 1500);
+        telemetry.record_generation("xframe5-ui", "success", 500);
+
+        let body = encode_metrics(&config(), &telemetry);
+        let metrics = &body["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics[0]["name"], "coder.generations");
+        assert_eq!(metrics[0]["sum"]["dataPoints"][0]["asInt"], "2");
+        assert_eq!(metrics[1]["sum"]["dataPoints"][0]["asInt"], "2000");
+    }
+}
@@ -1,4 +1,6 @@
 use crate::models::_entities::prompt_templates;
+use crate::services::lookup_cache::{LookupCache, Namespace};
+use crate::services::TenantScope;
 use anyhow::{anyhow, Result};
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
 
@@ -7,24 +9,34 @@ pub struct TemplateService;
 
 impl TemplateService {
     /// Get the active template for a product and screen type
+    ///
+    /// A tenant's own template wins over the shared one.
     pub async fn get_active(
         db: &DatabaseConnection,
         product: &str,
         screen_type: Option<&str>,
+        tenant: &TenantScope,
     ) -> Result<prompt_templates::Model> {
-        let mut query = prompt_templates::Entity::find()
-            .filter(prompt_templates::Column::Product.eq(product))
-            .filter(prompt_templates::Column::IsActive.eq(Some(true)));
-
-        if let Some(st) = screen_type {
-            query = query.filter(prompt_templates::Column::ScreenType.eq(Some(st.to_string())));
-        }
-
-        // Get the highest version
-        let template = query
-            .order_by_desc(prompt_templates::Column::Version)
-            .one(db)
-            .await?;
+        let key = format!("{}:{}:{}", product, screen_type.unwrap_or(""), tenant.company().unwrap_or(""));
+        let template = LookupCache::get_or_load(Namespace::Templates, &key, || async {
+            let mut query = tenant.prefer_own(
+                prompt_templates::Entity::find()
+                    .filter(prompt_templates::Column::Product.eq(product))
+                    .filter(prompt_templates::Column::IsActive.eq(Some(true))),
+                prompt_templates::Column::Company,
+            );
+
+            if let Some(st) = screen_type {
+                query = query.filter(prompt_templates::Column::ScreenType.eq(Some(st.to_string())));
+            }
+
+            // Get the highest version
+            query
+                .order_by_desc(prompt_templates::Column::Version)
+                .one(db)
+                .await
+        })
+        .await?;
 
         template.ok_or_else(|| {
             anyhow!(
@@ -138,8 +150,60 @@ RULES:
 2. Use proper field bindings with Attribute Map properties
 3. Generate JavaScript with standard functions (fn_init, fn_save, fn_delete, fn_validate)
 4. Handle form validation properly
-5. Add TODO comments for any information you need but don't have
-6. NEVER make up API endpoints - use TODO placeholders instead
+5. When tabs are specified, put the fields in one tab control with a tab_item per tab, each holding its panel (pnl_tab_<tab id>)
+6. When address fields are specified, the postcode search button opens the address popup and its callback fills the postcode and base address
+7. Add TODO comments for any information you need but don't have
+8. NEVER make up API endpoints - use TODO placeholders instead
+
+OUTPUT FORMAT:
+Respond with exactly two sections:
+
+--- XML ---
+<your XML content here>
+
+--- JS ---
+<your JavaScript content here>
+
+Do not include any explanation outside these sections."#
+    }
+
+    /// System prompt for dashboard screen
+    pub fn xframe5_dashboard_system_prompt() -> &'static str {
+        r#"You are an expert xFrame5 frontend code generator. Your task is to generate XML view files and JavaScript event handlers for xFrame5 dashboard (statistics) screens.
+
+RULES:
+1. Generate valid xFrame5 XML with one Dataset per aggregate query
+2. Render each KPI as a panel with a label text and a value text bound to its dataset column
+3. Render each chart as a chart component bound to its dataset with link_data
+4. Place every panel and chart exactly at the given x, y, width and height
+5. Generate JavaScript with fn_refresh, which reloads every dataset and redraws the charts
+6. Add TODO comments for any information you need but don't have
+7. NEVER make up API endpoints - use TODO placeholders instead
+
+OUTPUT FORMAT:
+Respond with exactly two sections:
+
+--- XML ---
+<your XML content here>
+
+--- JS ---
+<your JavaScript content here>
+
+Do not include any explanation outside these sections."#
+    }
+
+    /// System prompt for wizard (multi-step) screen
+    pub fn xframe5_wizard_system_prompt() -> &'static str {
+        r#"You are an expert xFrame5 frontend code generator. Your task is to generate XML view files and JavaScript event handlers for xFrame5 multi-step wizard screens.
+
+RULES:
+1. Generate valid xFrame5 XML with one Dataset per step and one panel per step (pnl_step_<step id>)
+2. Show only the current step panel; hide the others
+3. Generate a validation function per step (fn_validate_<step id>) that checks the step's required fields
+4. Generate fn_next (validate the current step, then show the next panel) and fn_prev (show the previous panel)
+5. Generate fn_submit that validates every step and sends all step datasets in one transaction
+6. Add TODO comments for any information you need but don't have
+7. NEVER make up API endpoints - use TODO placeholders instead
 
 OUTPUT FORMAT:
 Respond with exactly two sections:
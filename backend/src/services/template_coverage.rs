@@ -0,0 +1,281 @@
+//! Template Coverage Analyzer
+//!
+//! Cross-references the rules a system prompt asks for (checklist items,
+//! numbered rules, MUST/NEVER/ALWAYS lines) against the checks the validators
+//! and pipeline passes actually run. Rules nobody checks are reported as
+//! `uncovered`; checks the prompt never mentions as `unprompted_checks`.
+//!
+//! Matching is keyword-based, so the check table below must be kept in sync
+//! when a validator gains or loses a check.
+
+use serde::Serialize;
+
+use crate::models::_entities::prompt_templates;
+use RuleStatus::{Enforced, Validated};
+
+/// How a check covers a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleStatus {
+    /// Violations are reported as warnings
+    Validated,
+    /// Violations are fixed automatically
+    Enforced,
+    /// Neither validated nor enforced
+    Uncovered,
+}
+
+/// A check run on generated output
+#[derive(Debug)]
+pub struct ValidatorCheck {
+    pub id: &'static str,
+    pub product: &'static str,
+    pub status: RuleStatus,
+    /// Validator or pass that runs the check
+    pub source: &'static str,
+    /// A rule matches when all keywords of any group occur in it (lowercase)
+    keywords: &'static [&'static [&'static str]],
+}
+
+const fn check(
+    id: &'static str,
+    product: &'static str,
+    status: RuleStatus,
+    source: &'static str,
+    keywords: &'static [&'static [&'static str]],
+) -> ValidatorCheck {
+    ValidatorCheck { id, product, status, source, keywords }
+}
+
+/// Checks in match order (more specific first)
+const CHECKS: &[ValidatorCheck] = &[
+    // xFrame5
+    check("output_sections", "xframe5-ui", Validated, "OutputParser", &[&["both files"], &["skip javascript"]]),
+    check("xml_parse", "xframe5-ui", Validated, "XFrame5Validator", &[&["xml", "parse"], &["unclosed"]]),
+    check("event_syntax", "xframe5-ui", Enforced, "Canonicalizer", &[&["on_click"], &["onclick"], &["eventfunc"]]),
+    check("grid_version", "xframe5-ui", Enforced, "Canonicalizer", &[&["grid", "version"]]),
+    check("chart_binding", "xframe5-ui", Validated, "ChartBindingValidator", &[&["chart", "link_data"], &["chart", "bound"], &["fn_refresh"]]),
+    check("wizard_steps", "xframe5-ui", Validated, "WizardStepValidator", &[&["step", "panel"], &["fn_validate_"], &["fn_next"], &["fn_prev"], &["fn_submit"]]),
+    check("tab_panels", "xframe5-ui", Validated, "TabPanelValidator", &[&["tab_item"], &["pnl_tab_"], &["fn_load_tab_"], &["fn_tab_changed"]]),
+    check("address_popup", "xframe5-ui", Validated, "AddressFieldValidator", &[&["address", "popup"], &["postcode"], &["우편번호"]]),
+    check("print_button", "xframe5-ui", Validated, "PrintValidator", &[&["fn_print"], &["print", "button"], &["인쇄"]]),
+    check("search_state", "xframe5-ui", Validated, "SearchStateValidator", &[&["search", "restore"], &["search", "keep"], &["search condition"], &["검색조건"]]),
+    check("dataset_binding", "xframe5-ui", Validated, "GraphValidator", &[&["link_data"], &["binding"]]),
+    check("dataset_present", "xframe5-ui", Validated, "XFrame5Validator", &[&["dataset", "grid data"], &["dataset", "form data"]]),
+    check("action_functions", "xframe5-ui", Validated, "XFrame5Validator", &[&["function", "button"]]),
+    check("error_handling", "xframe5-ui", Validated, "ErrorHandlingValidator", &[&["callback"], &["catch"], &["error", "alert"], &["error", "console"]]),
+    check("label_language", "xframe5-ui", Validated, "LabelLanguageValidator", &[&["korean", "label"], &["korean", "caption"], &["korean", "message"], &["한글"]]),
+    check("ux_behaviors", "xframe5-ui", Enforced, "UxBehaviorPass", &[&["enter", "search"], &["f5"], &["double"]]),
+    check("event_handlers", "xframe5-ui", Enforced, "SymbolLinker", &[&["handler"]]),
+    check("hardcoded_endpoints", "xframe5-ui", Validated, "XFrame5Validator", &[&["api endpoint"], &["hardcode"]]),
+    check("api_allowlist", "xframe5-ui", Validated, "ApiAllowlistFilter", &[&["allowlist"], &["hallucinat"], &["non-existent"]]),
+    check("api_denylist", "xframe5-ui", Validated, "ApiDenylistFilter", &[&["deprecated"], &["forbidden"], &["eval("]]),
+    check("paging", "xframe5-ui", Validated, "PagingValidator", &[&["paging"], &["page_no"], &["total_count"]]),
+    check("editable_grid", "xframe5-ui", Validated, "EditableGridValidator", &[&["getchangeddata"], &["changed row"]]),
+    check("sensitive_masking", "xframe5-ui", Enforced, "SensitiveDataPass", &[&["mask"]]),
+    check("unused_code", "xframe5-ui", Enforced, "MinimalismPass", &[&["unused"], &["unrequested"]]),
+    // Spring
+    check("output_sections", "spring-backend", Validated, "SpringValidator", &[&["sections"], &["marker"]]),
+    check("sql_injection", "spring-backend", Validated, "SpringValidator", &[&["${}"], &["sql injection"]]),
+    check("controller_annotations", "spring-backend", Validated, "SpringValidator", &[&["@restcontroller"], &["@requestmapping"]]),
+    check("service_annotation", "spring-backend", Validated, "SpringValidator", &[&["@service"]]),
+    check("mapper_annotation", "spring-backend", Validated, "SpringValidator", &[&["@mapper"]]),
+    check("dto_lombok", "spring-backend", Validated, "SpringValidator", &[&["lombok"], &["@data"]]),
+    check("dto_validation", "spring-backend", Validated, "SpringValidator", &[&["@notnull"], &["validation annotation"]]),
+    check("mapper_namespace", "spring-backend", Validated, "MyBatisValidator", &[&["namespace"]]),
+    check("result_map", "spring-backend", Validated, "MyBatisValidator", &[&["resultmap"]]),
+    check("crud_statements", "spring-backend", Validated, "SpringValidator", &[&["crud statement"], &["crud", "operation"]]),
+    check("imports", "spring-backend", Validated, "JavaSyntaxChecker", &[&["import"]]),
+    check("compilable", "spring-backend", Validated, "JavaSyntaxChecker", &[&["compilable"], &["syntax"]]),
+    check("forbidden_apis", "spring-backend", Validated, "SpringValidator", &[&["forbidden"]]),
+];
+
+/// A rule found in a system prompt
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleCoverage {
+    /// 1-based line in the system prompt
+    pub line: usize,
+    pub text: String,
+    pub status: RuleStatus,
+    /// Matching check (`source:id`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check: Option<String>,
+}
+
+/// Coverage of one template's system prompt
+#[derive(Debug, Serialize)]
+pub struct CoverageReport {
+    pub template_id: i32,
+    pub template_name: String,
+    pub product: String,
+    pub screen_type: Option<String>,
+    pub version: i32,
+    pub rules: Vec<RuleCoverage>,
+    pub uncovered: usize,
+    /// Share of rules that are validated or enforced (0-100)
+    pub coverage_percent: f32,
+    /// Checks the prompt never asks for (`source:id`)
+    pub unprompted_checks: Vec<String>,
+}
+
+/// Analyzes prompt/validator coverage
+pub struct TemplateCoverageAnalyzer;
+
+impl TemplateCoverageAnalyzer {
+    /// Analyze a template's system prompt
+    pub fn analyze(template: &prompt_templates::Model) -> CoverageReport {
+        let checks: Vec<&ValidatorCheck> =
+            CHECKS.iter().filter(|c| c.product == template.product).collect();
+
+        let mut matched = vec![false; checks.len()];
+        let rules: Vec<RuleCoverage> = Self::extract_rules(&template.system_prompt)
+            .into_iter()
+            .map(|(line, text)| {
+                let lower = text.to_lowercase();
+                let found = checks.iter().position(|c| {
+                    c.keywords.iter().any(|group| group.iter().all(|k| lower.contains(k)))
+                });
+                match found {
+                    Some(i) => {
+                        matched[i] = true;
+                        RuleCoverage {
+                            line,
+                            text,
+                            status: checks[i].status,
+                            check: Some(Self::label(checks[i])),
+                        }
+                    }
+                    None => RuleCoverage { line, text, status: RuleStatus::Uncovered, check: None },
+                }
+            })
+            .collect();
+
+        let uncovered = rules.iter().filter(|r| r.status == RuleStatus::Uncovered).count();
+        let coverage_percent = if rules.is_empty() {
+            100.0
+        } else {
+            (rules.len() - uncovered) as f32 * 100.0 / rules.len() as f32
+        };
+        let unprompted_checks = checks
+            .iter()
+            .zip(&matched)
+            .filter(|(_, m)| !**m)
+            .map(|(c, _)| Self::label(c))
+            .collect();
+
+        CoverageReport {
+            template_id: template.id,
+            template_name: template.name.clone(),
+            product: template.product.clone(),
+            screen_type: template.screen_type.clone(),
+            version: template.version,
+            rules,
+            uncovered,
+            coverage_percent,
+            unprompted_checks,
+        }
+    }
+
+    /// Rules as `(line, text)`: checklist items (`[ ]`), numbered items and
+    /// lines with an upper-case MUST/NEVER/ALWAYS
+    pub fn extract_rules(system_prompt: &str) -> Vec<(usize, String)> {
+        system_prompt
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let trimmed = line.trim();
+                let text = if let Some(item) = trimmed.strip_prefix("[ ]") {
+                    item.trim()
+                } else if let Some(item) = Self::strip_number(trimmed) {
+                    item
+                } else if trimmed
+                    .split(|c: char| !c.is_ascii_alphabetic())
+                    .any(|w| matches!(w, "MUST" | "NEVER" | "ALWAYS"))
+                {
+                    trimmed
+                } else {
+                    return None;
+                };
+                Some((i + 1, text.to_string()))
+            })
+            .collect()
+    }
+
+    /// `12. text` → `text`
+    fn strip_number(line: &str) -> Option<&str> {
+        let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        line[digits..].strip_prefix(". ").map(str::trim)
+    }
+
+    fn label(check: &ValidatorCheck) -> String {
+        format!("{}:{}", check.source, check.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(product: &str, system_prompt: &str) -> prompt_templates::Model {
+        prompt_templates::Model {
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+            id: 1,
+            name: "test".to_string(),
+            product: product.to_string(),
+            screen_type: Some("list".to_string()),
+            system_prompt: system_prompt.to_string(),
+            user_prompt_template: String::new(),
+            version: 1,
+            is_active: Some(true),
+            company: None,
+        }
+    }
+
+    const XFRAME5_PROMPT: &str = "You are an xFrame5 generator.
+For LIST screens, you MUST include:
+[ ] pnl_header panel with title
+[ ] grid_list with link_data binding to ds_list
+[ ] fn_search function (for Query button)
+
+1. EVERY button MUST use: on_click=\"eventfunc:fn_name()\" (NOT onclick)
+2. Panel names MUST start with: pnl_";
+
+    #[test]
+    fn test_extract_rules() {
+        let rules = TemplateCoverageAnalyzer::extract_rules(XFRAME5_PROMPT);
+        let lines: Vec<usize> = rules.iter().map(|(l, _)| *l).collect();
+        assert_eq!(lines, vec![2, 3, 4, 5, 7, 8]);
+        assert_eq!(rules[1].1, "pnl_header panel with title");
+        assert_eq!(rules[5].1, "Panel names MUST start with: pnl_");
+    }
+
+    #[test]
+    fn test_reports_uncovered_rules() {
+        let report = TemplateCoverageAnalyzer::analyze(&template("xframe5-ui", XFRAME5_PROMPT));
+        let status = |line: usize| report.rules.iter().find(|r| r.line == line).unwrap().status;
+
+        assert_eq!(status(3), RuleStatus::Uncovered);
+        assert_eq!(status(4), RuleStatus::Validated);
+        assert_eq!(status(5), RuleStatus::Validated);
+        assert_eq!(status(7), RuleStatus::Enforced);
+        assert_eq!(status(8), RuleStatus::Uncovered);
+        // "For LIST screens, you MUST include:" is a rule heading nobody checks
+        assert_eq!(report.uncovered, 3);
+        assert_eq!(report.coverage_percent, 50.0);
+        assert!(report.unprompted_checks.contains(&"PagingValidator:paging".to_string()));
+        assert!(!report.unprompted_checks.contains(&"GraphValidator:dataset_binding".to_string()));
+    }
+
+    #[test]
+    fn test_checks_are_per_product() {
+        let prompt = "1. Use @Service annotation for service implementations";
+        let spring = TemplateCoverageAnalyzer::analyze(&template("spring-backend", prompt));
+        assert_eq!(spring.rules[0].status, RuleStatus::Validated);
+        let xframe5 = TemplateCoverageAnalyzer::analyze(&template("xframe5-ui", prompt));
+        assert_eq!(xframe5.rules[0].status, RuleStatus::Uncovered);
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::models::_entities::prompt_templates;
 use crate::services::template::TemplateService;
+use crate::services::TemplateVariables;
 
 /// Template file format for import
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +68,9 @@ pub struct ImportOptions {
 
     /// Make this version active
     pub set_active: bool,
+
+    /// Tenant company of the imported template (None = shared)
+    pub company: Option<String>,
 }
 
 impl Default for ImportOptions {
@@ -75,6 +79,7 @@ impl Default for ImportOptions {
             deactivate_old: true,
             force_version: None,
             set_active: true,
+            company: None,
         }
     }
 }
@@ -141,6 +146,12 @@ impl TemplateImporter {
         if template.user_prompt_template.is_empty() {
             return Err(anyhow!("User prompt template is required"));
         }
+        TemplateVariables::validate(
+            &template.metadata.product,
+            template.metadata.screen_type.as_deref(),
+            &template.system_prompt,
+            &template.user_prompt_template,
+        )?;
 
         Ok(())
     }
@@ -174,7 +185,7 @@ impl TemplateImporter {
 
         // 2. Deactivate old versions if requested
         if options.deactivate_old && latest_version > 0 {
-            Self::deactivate_versions(db, &meta.product, &meta.name).await?;
+            Self::deactivate_versions(db, &meta.product, &meta.name, options.company.as_deref()).await?;
         }
 
         // 3. Insert new template
@@ -186,35 +197,51 @@ impl TemplateImporter {
             user_prompt_template: Set(template.user_prompt_template.clone()),
             version: Set(new_version),
             is_active: Set(Some(options.set_active)),
+            company: Set(options.company.clone()),
             ..Default::default()
         };
 
         let inserted = new_template.insert(db).await?;
+        let notes = TemplateVariables::check(
+            &meta.product,
+            meta.screen_type.as_deref(),
+            &template.system_prompt,
+            &template.user_prompt_template,
+        )
+        .notes();
 
         Ok(ImportResult {
             success: true,
             template_id: Some(inserted.id),
             version: new_version,
             message: format!(
-                "Successfully imported {} v{} (previous: v{})",
+                "Successfully imported {} v{} (previous: v{}){}",
                 meta.name,
                 new_version,
-                if latest_version > 0 { latest_version.to_string() } else { "none".to_string() }
+                if latest_version > 0 { latest_version.to_string() } else { "none".to_string() },
+                notes.iter().map(|n| format!(". {}", n)).collect::<String>()
             ),
             previous_version: if latest_version > 0 { Some(latest_version) } else { None },
         })
     }
 
-    /// Deactivate all versions of a template
+    /// Deactivate all versions of a template (of the same company)
     async fn deactivate_versions(
         db: &DatabaseConnection,
         product: &str,
         name: &str,
+        company: Option<&str>,
     ) -> Result<()> {
+        let same_company = match company {
+            Some(company) => prompt_templates::Column::Company.eq(company),
+            None => prompt_templates::Column::Company.is_null(),
+        };
+
         // Find all templates with this product/name
         let templates = prompt_templates::Entity::find()
             .filter(prompt_templates::Column::Product.eq(product))
             .filter(prompt_templates::Column::Name.eq(name))
+            .filter(same_company)
             .all(db)
             .await?;
 
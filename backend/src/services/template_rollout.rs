@@ -0,0 +1,374 @@
+//! Template Rollout
+//!
+//! Puts approved template activations into effect and watches them:
+//! - An approved activation whose window has opened replaces the active
+//!   template of its slot (same product, screen type and company); proposals
+//!   still waiting when their window closes expire.
+//! - For `TEMPLATE_ROLLBACK_MONITOR_HOURS` after activation the warning rate
+//!   of the new version is compared with the rate the previous version had
+//!   before it. A spike rolls the slot back to the previous version and is
+//!   posted to the chat channel.
+//!
+//! The scheduler runs on every instance; each state change is claimed with a
+//! conditional update, so an activation is applied and rolled back once.
+
+use chrono::{Duration, Utc};
+use loco_rs::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{query::*, DatabaseConnection, PaginatorTrait};
+use serde::Serialize;
+
+use crate::models::_entities::template_activations::{self, Column, Entity};
+use crate::models::_entities::{generation_logs, prompt_templates};
+use crate::services::admin::audit_log::{AuditAction, AuditLogService};
+use crate::services::Notifier;
+
+/// Seconds between scheduler runs
+const TICK_SECS: u64 = 60;
+
+/// Actor recorded for changes made by the scheduler itself
+pub const ROLLOUT_ACTOR: &str = "template-rollout";
+
+/// Generation statuses that never finished
+const UNFINISHED_STATUSES: [&str; 2] = ["queued", "processing"];
+
+/// Statuses counted as a bad generation even without warnings
+const FAILED_STATUSES: [&str; 2] = ["error", "failed"];
+
+/// Rollback thresholds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RolloutConfig {
+    /// How long a new version is watched (also the baseline lookback)
+    pub monitor_hours: i64,
+    /// Generations needed before a rate is trusted
+    pub min_samples: u64,
+    /// Spike = rate at least this multiple of the baseline ...
+    pub spike_ratio: f32,
+    /// ... and at least this much above it (0.10 = 10 points)
+    pub spike_margin: f32,
+}
+
+impl Default for RolloutConfig {
+    fn default() -> Self {
+        Self {
+            monitor_hours: 24,
+            min_samples: 20,
+            spike_ratio: 2.0,
+            spike_margin: 0.10,
+        }
+    }
+}
+
+impl RolloutConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_string());
+        Self {
+            monitor_hours: var("TEMPLATE_ROLLBACK_MONITOR_HOURS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.monitor_hours),
+            min_samples: var("TEMPLATE_ROLLBACK_MIN_SAMPLES")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.min_samples),
+            spike_ratio: var("TEMPLATE_ROLLBACK_SPIKE_RATIO")
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f32| *v >= 1.0)
+                .unwrap_or(defaults.spike_ratio),
+            spike_margin: var("TEMPLATE_ROLLBACK_SPIKE_MARGIN")
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f32| (0.0..=1.0).contains(v))
+                .unwrap_or(defaults.spike_margin),
+        }
+    }
+
+    /// Whether `current` is a spike over `baseline` (no baseline = never)
+    pub fn is_spike(&self, baseline: Option<f32>, current: &WarningRate) -> bool {
+        let Some(baseline) = baseline else {
+            return false;
+        };
+        if current.generations < self.min_samples {
+            return false;
+        }
+        let rate = current.rate();
+        rate >= baseline * self.spike_ratio && rate - baseline >= self.spike_margin
+    }
+}
+
+/// Share of a template's generations with warnings or errors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WarningRate {
+    pub generations: u64,
+    pub with_warnings: u64,
+}
+
+impl WarningRate {
+    pub fn rate(&self) -> f32 {
+        if self.generations == 0 {
+            return 0.0;
+        }
+        self.with_warnings as f32 / self.generations as f32
+    }
+}
+
+pub struct TemplateRollout;
+
+impl TemplateRollout {
+    /// Warning rate of `template`'s generations between `from` and `to`
+    pub async fn warning_rate(
+        db: &DatabaseConnection,
+        template: &prompt_templates::Model,
+        from: DateTimeWithTimeZone,
+        to: DateTimeWithTimeZone,
+    ) -> Result<WarningRate> {
+        use generation_logs::Column as Log;
+
+        let same_company = match &template.company {
+            Some(company) => Log::Company.eq(company.as_str()),
+            None => Log::Company.is_null(),
+        };
+        let logs = generation_logs::Entity::find()
+            .filter(Log::Product.eq(template.product.as_str()))
+            .filter(Log::TemplateVersion.eq(template.version))
+            .filter(same_company)
+            .filter(Log::Status.is_not_in(UNFINISHED_STATUSES))
+            .filter(Log::CreatedAt.gte(from))
+            .filter(Log::CreatedAt.lt(to));
+
+        let generations = logs.clone().count(db).await?;
+        let with_warnings = logs
+            .filter(
+                Condition::any()
+                    .add(Log::Warnings.contains("Warning:"))
+                    .add(Log::Status.is_in(FAILED_STATUSES)),
+            )
+            .count(db)
+            .await?;
+        Ok(WarningRate { generations, with_warnings })
+    }
+
+    /// Active templates in the slot `template` is activated into
+    pub async fn slot(db: &DatabaseConnection, template: &prompt_templates::Model) -> Result<Vec<prompt_templates::Model>> {
+        use prompt_templates::Column as T;
+
+        let same_company = match &template.company {
+            Some(company) => T::Company.eq(company.as_str()),
+            None => T::Company.is_null(),
+        };
+        let same_screen_type = match &template.screen_type {
+            Some(screen_type) => T::ScreenType.eq(screen_type.as_str()),
+            None => T::ScreenType.is_null(),
+        };
+        Ok(prompt_templates::Entity::find()
+            .filter(T::Product.eq(template.product.as_str()))
+            .filter(same_screen_type)
+            .filter(same_company)
+            .filter(T::IsActive.eq(Some(true)))
+            .filter(T::Id.ne(template.id))
+            .order_by_desc(T::Version)
+            .all(db)
+            .await?)
+    }
+
+    /// Move an activation from `from` to `to`; false if another instance (or
+    /// request) got there first
+    pub async fn claim(db: &DatabaseConnection, id: i32, from: &str, to: &str) -> Result<bool> {
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(to))
+            .col_expr(Column::UpdatedAt, Expr::value(DateTimeWithTimeZone::from(Utc::now())))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(from))
+            .exec(db)
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn set_active(db: &DatabaseConnection, actor: &str, template: &prompt_templates::Model, active: bool) -> Result<()> {
+        let mut item: prompt_templates::ActiveModel = template.clone().into();
+        item.is_active = Set(Some(active));
+        let updated = item.update(db).await?;
+        let action = if active { AuditAction::Activate } else { AuditAction::Update };
+        AuditLogService::record(db, actor, action, Some(template), Some(&updated)).await
+    }
+
+    /// Put a `scheduled` activation into effect (`actor` = approving administrator)
+    pub async fn activate(
+        db: &DatabaseConnection,
+        activation: template_activations::Model,
+        actor: &str,
+    ) -> Result<template_activations::Model> {
+        if !Self::claim(db, activation.id, "scheduled", "active").await? {
+            return Ok(Entity::find_by_id(activation.id).one(db).await?.unwrap_or(activation));
+        }
+
+        let Some(template) = prompt_templates::Entity::find_by_id(activation.prompt_template_id).one(db).await? else {
+            let mut item: template_activations::ActiveModel = activation.into();
+            item.status = Set("cancelled".to_string());
+            return Ok(item.update(db).await?);
+        };
+
+        let config = RolloutConfig::from_env();
+        let now: DateTimeWithTimeZone = Utc::now().into();
+        let slot = Self::slot(db, &template).await?;
+        let previous = slot.first().cloned();
+        let baseline = match &previous {
+            Some(previous) => {
+                let rate = Self::warning_rate(db, previous, now - Duration::hours(config.monitor_hours), now).await?;
+                (rate.generations >= config.min_samples).then(|| rate.rate())
+            }
+            None => None,
+        };
+
+        for other in &slot {
+            Self::set_active(db, actor, other, false).await?;
+        }
+        Self::set_active(db, actor, &template, true).await?;
+
+        let mut item: template_activations::ActiveModel = activation.into();
+        item.status = Set("active".to_string());
+        item.activated_at = Set(Some(now));
+        item.previous_template_id = Set(previous.map(|p| p.id));
+        item.baseline_warning_rate = Set(baseline);
+        Ok(item.update(db).await?)
+    }
+
+    /// Restore the previous version of an `active` activation's slot
+    pub async fn rollback(
+        db: &DatabaseConnection,
+        activation: template_activations::Model,
+        actor: &str,
+        reason: &str,
+    ) -> Result<template_activations::Model> {
+        if !Self::claim(db, activation.id, "active", "rolled_back").await? {
+            return Ok(Entity::find_by_id(activation.id).one(db).await?.unwrap_or(activation));
+        }
+
+        let template = prompt_templates::Entity::find_by_id(activation.prompt_template_id).one(db).await?;
+        if let Some(template) = template.as_ref().filter(|t| t.is_active == Some(true)) {
+            Self::set_active(db, actor, template, false).await?;
+        }
+        if let Some(previous_id) = activation.previous_template_id {
+            if let Some(previous) = prompt_templates::Entity::find_by_id(previous_id).one(db).await? {
+                Self::set_active(db, actor, &previous, true).await?;
+            }
+        }
+
+        if actor == ROLLOUT_ACTOR {
+            if let Some(template) = &template {
+                Notifier::template_rolled_back(db, &template.name, template.version, reason);
+            }
+        }
+
+        let mut item: template_activations::ActiveModel = activation.into();
+        item.status = Set("rolled_back".to_string());
+        item.rolled_back_at = Set(Some(Utc::now().into()));
+        item.rollback_reason = Set(Some(reason.to_string()));
+        Ok(item.update(db).await?)
+    }
+
+    /// One scheduler run: expire, activate due proposals, watch active ones
+    pub async fn tick(db: &DatabaseConnection, config: &RolloutConfig) -> Result<()> {
+        let now: DateTimeWithTimeZone = Utc::now().into();
+
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value("expired"))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Status.is_in(["pending", "scheduled"]))
+            .filter(Column::WindowEnd.lt(now))
+            .exec(db)
+            .await?;
+
+        let due = Entity::find()
+            .filter(Column::Status.eq("scheduled"))
+            .filter(Condition::any().add(Column::WindowStart.is_null()).add(Column::WindowStart.lte(now)))
+            .all(db)
+            .await?;
+        for activation in due {
+            let actor = activation.reviewed_by.clone().unwrap_or_else(|| ROLLOUT_ACTOR.to_string());
+            Self::activate(db, activation, &actor).await?;
+        }
+
+        let watched = Entity::find().filter(Column::Status.eq("active")).all(db).await?;
+        for activation in watched {
+            let Some(activated_at) = activation.activated_at else {
+                continue;
+            };
+            if now - activated_at > Duration::hours(config.monitor_hours) {
+                Self::claim(db, activation.id, "active", "completed").await?;
+                continue;
+            }
+            if activation.previous_template_id.is_none() {
+                continue;
+            }
+            let Some(template) = prompt_templates::Entity::find_by_id(activation.prompt_template_id).one(db).await? else {
+                continue;
+            };
+            let current = Self::warning_rate(db, &template, activated_at, now).await?;
+            if config.is_spike(activation.baseline_warning_rate, &current) {
+                let reason = format!(
+                    "Warning rate {:.0}% over {} generations vs. {:.0}% before activation",
+                    current.rate() * 100.0,
+                    current.generations,
+                    activation.baseline_warning_rate.unwrap_or_default() * 100.0
+                );
+                tracing::warn!("Rolling back template {} v{}: {}", template.name, template.version, reason);
+                Self::rollback(db, activation, ROLLOUT_ACTOR, &reason).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Start the background rollout scheduler
+pub fn start_scheduler(db: DatabaseConnection) {
+    let config = RolloutConfig::from_env();
+    tracing::info!(
+        "Template rollout scheduler started (monitoring {}h after activation)",
+        config.monitor_hours
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(TICK_SECS));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = TemplateRollout::tick(&db, &config).await {
+                tracing::warn!("Template rollout run failed: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(generations: u64, with_warnings: u64) -> WarningRate {
+        WarningRate { generations, with_warnings }
+    }
+
+    #[test]
+    fn test_spike_needs_ratio_margin_and_samples() {
+        let config = RolloutConfig::default();
+
+        // 50% vs 10%: ratio 5x, margin 40 points
+        assert!(config.is_spike(Some(0.10), &rate(40, 20)));
+        // Too few generations to trust
+        assert!(!config.is_spike(Some(0.10), &rate(10, 5)));
+        // 3% vs 1%: ratio 3x but only 2 points
+        assert!(!config.is_spike(Some(0.01), &rate(100, 3)));
+        // 45% vs 30%: 15 points but only 1.5x
+        assert!(!config.is_spike(Some(0.30), &rate(100, 45)));
+        // Nothing to compare with
+        assert!(!config.is_spike(None, &rate(100, 90)));
+    }
+
+    #[test]
+    fn test_warning_rate_of_no_generations() {
+        assert_eq!(WarningRate::default().rate(), 0.0);
+        assert_eq!(rate(8, 2).rate(), 0.25);
+    }
+}
@@ -0,0 +1,341 @@
+//! Template Variables
+//!
+//! Registry of the `{{variables}}` each kind of prompt template gets
+//! substituted: generation templates per product, review templates
+//! (`screen_type: review`) and Q&A templates (`screen_type: qa`). Templates
+//! are checked against it on save: unknown variables (including
+//! `{{#if name}}` conditions) are rejected, supported variables the user
+//! prompt never uses are noted.
+//!
+//! The system prompt of generation templates is not rendered; placeholders
+//! there reach the model verbatim and are noted instead of rejected.
+//!
+//! The generation tables must list exactly what
+//! `PromptCompiler::template_variables` and
+//! `SpringPromptCompiler::template_variables` supply (checked by tests); the
+//! review and Q&A tables follow `ReviewService` and `QAService`.
+
+use anyhow::{anyhow, bail, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::GenerateInput;
+use crate::models::_entities::prompt_templates;
+use crate::services::{NormalizerService, PromptCompiler, SpringNormalizerService, SpringPromptCompiler};
+
+/// A variable the renderer supplies
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TemplateVariable {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const fn variable(name: &'static str, description: &'static str) -> TemplateVariable {
+    TemplateVariable { name, description }
+}
+
+/// Variables of one kind of template
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct VariableSet {
+    pub user: &'static [TemplateVariable],
+    /// Variables of the system prompt (empty = not rendered)
+    pub system: &'static [TemplateVariable],
+}
+
+const XFRAME5_VARIABLES: &[TemplateVariable] = &[
+    variable("dsl_description", "Full screen specification (datasets, grids, actions, options)"),
+    variable("screen_type", "Screen type (`list`, `detail`, `popup`, ...)"),
+    variable("screen_name", "Screen name"),
+    variable("datasets", "Datasets and their columns"),
+    variable("grid_columns", "Grids and their columns"),
+    variable("form_fields", "Form fields (currently the grid columns)"),
+    variable("actions", "Buttons and their functions"),
+    variable("notes", "Notes of the request"),
+    variable("company_rules", "Company rules for the product and screen type"),
+];
+
+const SPRING_VARIABLES: &[TemplateVariable] = &[
+    variable("entity_name", "Entity class name (`Member`)"),
+    variable("table_name", "Source table"),
+    variable("package_base", "Base package (`com.company.erp`)"),
+    variable("path_name", "API path segment (`member`)"),
+    variable("columns", "Columns with their Java types"),
+    variable("crud_operations", "CRUD operations and their endpoints"),
+    variable("notes", "Notes of the request"),
+    variable("company_rules", "Company rules for Spring"),
+];
+
+const REVIEW_VARIABLES: &[TemplateVariable] = &[
+    variable("code", "Code under review"),
+    variable("file_type", "`xml`, `javascript` or `java`"),
+    variable("file_name", "File name (block removed when missing)"),
+    variable("context", "Description of the code (block removed when missing)"),
+    variable("review_focus", "Requested focus areas (block removed when none)"),
+];
+
+const REVIEW_SYSTEM_VARIABLES: &[TemplateVariable] = &[
+    variable("knowledge", "Knowledge base entries for the product"),
+    variable("company_rules", "Company rules for the product"),
+];
+
+const QA_VARIABLES: &[TemplateVariable] = &[
+    variable("question", "The question"),
+    variable("context", "Context of the question (block removed when missing)"),
+];
+
+const QA_SYSTEM_VARIABLES: &[TemplateVariable] = &[variable("knowledge", "Knowledge base entries for the question")];
+
+/// Package base of playground Spring intents (as for generations without a project)
+const PLAYGROUND_PACKAGE_BASE: &str = "com.company.project";
+
+/// Result of checking a template against the registry
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VariableCheck {
+    /// Variables the renderer does not supply
+    pub unknown: Vec<String>,
+    /// Supported variables the user prompt template does not use
+    pub unused: Vec<String>,
+    /// Placeholders in a system prompt that is not rendered (sent verbatim)
+    pub system_placeholders: Vec<String>,
+}
+
+impl VariableCheck {
+    /// Notes for a template that passed the check
+    pub fn notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+        if !self.unused.is_empty() {
+            notes.push(format!("Unused variables: {}", Self::list(&self.unused)));
+        }
+        if !self.system_placeholders.is_empty() {
+            notes.push(format!(
+                "The system prompt is not rendered; {} reach the model verbatim",
+                Self::list(&self.system_placeholders)
+            ));
+        }
+        notes
+    }
+
+    fn list(names: &[String]) -> String {
+        names.iter().map(|n| format!("{{{{{}}}}}", n)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Compiled value of a variable (template playground)
+#[derive(Debug, Clone, Serialize)]
+pub struct VariableValue {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub value: String,
+    /// Whether the template uses the variable
+    pub used: bool,
+}
+
+/// Playground request: a sample input compiled through a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaygroundRequest {
+    pub input: GenerateInput,
+    /// Rendered company rules to substitute (default: none)
+    #[serde(default)]
+    pub company_rules: Option<String>,
+}
+
+/// Variables and user prompt compiled for a sample input
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaygroundResult {
+    pub variables: Vec<VariableValue>,
+    pub check: VariableCheck,
+    pub notes: Vec<String>,
+    pub user_prompt: String,
+}
+
+pub struct TemplateVariables;
+
+impl TemplateVariables {
+    /// Supported variables of a template kind (`None` for products without a renderer)
+    pub fn for_template(product: &str, screen_type: Option<&str>) -> Option<VariableSet> {
+        match (screen_type, product) {
+            (Some("review"), _) => Some(VariableSet {
+                user: REVIEW_VARIABLES,
+                system: REVIEW_SYSTEM_VARIABLES,
+            }),
+            (Some("qa"), _) => Some(VariableSet {
+                user: QA_VARIABLES,
+                system: QA_SYSTEM_VARIABLES,
+            }),
+            (_, "xframe5-ui") => Some(VariableSet {
+                user: XFRAME5_VARIABLES,
+                system: &[],
+            }),
+            (_, "spring-backend") => Some(VariableSet {
+                user: SPRING_VARIABLES,
+                system: &[],
+            }),
+            _ => None,
+        }
+    }
+
+    /// Variables referenced by a template, in order of first use
+    ///
+    /// `{{#if name}}` counts as a reference; `{{/if}}` does not.
+    pub fn referenced(template: &str) -> Vec<String> {
+        let re = Regex::new(r"\{\{\s*(?:#if\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap();
+        let mut names: Vec<String> = Vec::new();
+        for caps in re.captures_iter(template) {
+            let name = &caps[1];
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// Check a template against the registry
+    pub fn check(
+        product: &str,
+        screen_type: Option<&str>,
+        system_prompt: &str,
+        user_prompt_template: &str,
+    ) -> VariableCheck {
+        let Some(set) = Self::for_template(product, screen_type) else {
+            return VariableCheck::default();
+        };
+        let supports = |vars: &[TemplateVariable], name: &str| vars.iter().any(|v| v.name == name);
+
+        let used = Self::referenced(user_prompt_template);
+        let mut unknown: Vec<String> = used.iter().filter(|name| !supports(set.user, name.as_str())).cloned().collect();
+        let mut system_placeholders = Vec::new();
+        for name in Self::referenced(system_prompt) {
+            if set.system.is_empty() {
+                system_placeholders.push(name);
+            } else if !supports(set.system, &name) && !unknown.contains(&name) {
+                unknown.push(name);
+            }
+        }
+
+        VariableCheck {
+            unknown,
+            unused: set
+                .user
+                .iter()
+                .filter(|v| !used.iter().any(|name| name == v.name))
+                .map(|v| v.name.to_string())
+                .collect(),
+            system_placeholders,
+        }
+    }
+
+    /// Check a template being saved; unknown variables are an error
+    pub fn validate(
+        product: &str,
+        screen_type: Option<&str>,
+        system_prompt: &str,
+        user_prompt_template: &str,
+    ) -> Result<VariableCheck> {
+        let check = Self::check(product, screen_type, system_prompt, user_prompt_template);
+        if check.unknown.is_empty() {
+            return Ok(check);
+        }
+        let supported = Self::for_template(product, screen_type)
+            .map(|set| set.user.iter().chain(set.system).map(|v| v.name).collect::<Vec<_>>().join(", "))
+            .unwrap_or_default();
+        Err(anyhow!(
+            "Unknown template variables: {} (supported: {})",
+            VariableCheck::list(&check.unknown),
+            supported
+        ))
+    }
+
+    /// Compile a sample input through a generation template and show every variable's value
+    pub fn playground(template: &prompt_templates::Model, request: &PlaygroundRequest) -> Result<PlaygroundResult> {
+        let screen_type = template.screen_type.as_deref();
+        if matches!(screen_type, Some("review" | "qa")) {
+            bail!("The playground compiles generation templates only");
+        }
+        let set = Self::for_template(&template.product, screen_type)
+            .ok_or_else(|| anyhow!("No template variables are defined for {}", template.product))?;
+        let rules = request.company_rules.as_deref().unwrap_or_default();
+
+        let (values, user_prompt) = if template.product == "spring-backend" {
+            let intent = SpringNormalizerService::normalize(&request.input, PLAYGROUND_PACKAGE_BASE)?;
+            (
+                SpringPromptCompiler::template_variables(&intent, rules),
+                SpringPromptCompiler::render_template(&template.user_prompt_template, &intent, rules),
+            )
+        } else {
+            let intent = NormalizerService::normalize(&request.input)?;
+            (
+                PromptCompiler::template_variables(&intent, rules),
+                PromptCompiler::render_template(&template.user_prompt_template, &intent, rules),
+            )
+        };
+
+        let check = Self::check(&template.product, screen_type, &template.system_prompt, &template.user_prompt_template);
+        let variables = set
+            .user
+            .iter()
+            .map(|v| VariableValue {
+                name: v.name,
+                description: v.description,
+                value: values
+                    .iter()
+                    .find(|(name, _)| *name == v.name)
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default(),
+                used: !check.unused.iter().any(|name| name == v.name),
+            })
+            .collect();
+
+        Ok(PlaygroundResult {
+            variables,
+            notes: check.notes(),
+            check,
+            user_prompt,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ScreenType, SpringIntent, UiIntent};
+    use crate::services::template::DefaultTemplates;
+
+    fn names(variables: &[(&'static str, String)]) -> Vec<&'static str> {
+        variables.iter().map(|(name, _)| *name).collect()
+    }
+
+    fn registry(product: &str) -> Vec<&'static str> {
+        TemplateVariables::for_template(product, None).unwrap().user.iter().map(|v| v.name).collect()
+    }
+
+    #[test]
+    fn test_registry_matches_compilers() {
+        let intent = UiIntent::new("member_list", ScreenType::List);
+        assert_eq!(names(&PromptCompiler::template_variables(&intent, "")), registry("xframe5-ui"));
+
+        let intent = SpringIntent::new("Member", "TB_MEMBER", "com.company.erp");
+        assert_eq!(names(&SpringPromptCompiler::template_variables(&intent, "")), registry("spring-backend"));
+    }
+
+    #[test]
+    fn test_check_unknown_unused_and_system_placeholders() {
+        let user = "Screen {{screen_name}} ({{ screen_type }})\n{{#if popup_screens}}\n{{popup_screens}}\n{{/if}}";
+        let check = TemplateVariables::check("xframe5-ui", Some("list"), "Rules:\n{{company_rules}}", user);
+        assert_eq!(check.unknown, vec!["popup_screens"]);
+        assert!(check.unused.contains(&"dsl_description".to_string()));
+        assert!(!check.unused.contains(&"screen_type".to_string()));
+        assert_eq!(check.system_placeholders, vec!["company_rules"]);
+
+        let err = TemplateVariables::validate("xframe5-ui", Some("list"), "", user).unwrap_err().to_string();
+        assert!(err.contains("{{popup_screens}}"));
+        let check =
+            TemplateVariables::validate("xframe5-ui", Some("list"), "", DefaultTemplates::xframe5_list_user_template())
+                .unwrap();
+        assert_eq!(check.unused, vec!["form_fields"]);
+
+        // Review system prompts are rendered
+        let check = TemplateVariables::check("xframe5-ui", Some("review"), "{{knowledge}} {{rules}}", "{{code}}");
+        assert_eq!(check.unknown, vec!["rules"]);
+        assert!(check.system_placeholders.is_empty());
+    }
+}
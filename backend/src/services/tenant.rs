@@ -0,0 +1,169 @@
+//! Tenant Scope
+//!
+//! Multi-company deployments tag rows with a `company` code. Rows without a
+//! company are shared by every tenant (templates, rules, knowledge, LLM
+//! configs); users without a company are platform administrators and see
+//! every row. Admin services take a scope and filter with it, so one tenant
+//! never reads or changes another tenant's rows.
+
+use loco_rs::prelude::*;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder, Select};
+
+use crate::middleware::cookie_auth::AuthUser;
+use crate::models::_entities::users;
+
+/// Rows visible to the current user
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TenantScope {
+    /// Platform administrator: every company and the shared rows
+    #[default]
+    Platform,
+    /// A single company plus the shared rows
+    Company(String),
+}
+
+impl TenantScope {
+    /// Scope for a company code (blank or missing = platform)
+    pub fn new(company: Option<&str>) -> Self {
+        match company.map(str::trim) {
+            Some(code) if !code.is_empty() => Self::Company(code.to_string()),
+            _ => Self::Platform,
+        }
+    }
+
+    /// Scope of an admin panel user
+    pub fn for_user(user: &AuthUser) -> Self {
+        Self::new(user.company.as_deref())
+    }
+
+    /// Scope of the user who made an API request (platform when unknown)
+    pub async fn for_user_id(db: &DatabaseConnection, user_id: Option<i32>) -> Self {
+        let Some(id) = user_id else {
+            return Self::Platform;
+        };
+        let user = users::Entity::find_by_id(id).one(db).await.ok().flatten();
+        Self::new(user.and_then(|u| u.company).as_deref())
+    }
+
+    /// Whether any user belongs to a company, i.e. the deployment is multi-company
+    ///
+    /// Plugin calls must then say who they are: an anonymous call cannot be
+    /// given a tenant.
+    pub async fn is_multi_company(db: &DatabaseConnection) -> bool {
+        users::Entity::find()
+            .filter(users::Column::Company.is_not_null())
+            .filter(users::Column::Company.ne(""))
+            .one(db)
+            .await
+            .map(|user| user.is_some())
+            .unwrap_or(true)
+    }
+
+    /// Company code, `None` for the platform
+    pub fn company(&self) -> Option<&str> {
+        match self {
+            Self::Platform => None,
+            Self::Company(code) => Some(code),
+        }
+    }
+
+    /// Rows the scope may read: its own company's rows and the shared ones
+    pub fn readable<C: ColumnTrait>(&self, column: C) -> Condition {
+        match self {
+            Self::Platform => Condition::all(),
+            Self::Company(code) => Condition::any()
+                .add(column.is_null())
+                .add(column.eq(code.as_str())),
+        }
+    }
+
+    /// Rows owned by the scope (tenants: their company only, never shared rows)
+    ///
+    /// Also used for data that is never shared, such as users and logs.
+    pub fn owned<C: ColumnTrait>(&self, column: C) -> Condition {
+        match self {
+            Self::Platform => Condition::all(),
+            Self::Company(code) => Condition::all().add(column.eq(code.as_str())),
+        }
+    }
+
+    /// Rows a generation may use: shared rows plus the company's own
+    ///
+    /// Unlike `readable`, platform users only get shared rows here, so one
+    /// tenant's templates or knowledge never end up in another's prompt.
+    pub fn usable<C: ColumnTrait>(&self, column: C) -> Condition {
+        match self {
+            Self::Platform => Condition::all().add(column.is_null()),
+            Self::Company(_) => self.readable(column),
+        }
+    }
+
+    /// Restrict a query to usable rows, the company's own before shared ones
+    pub fn prefer_own<E: EntityTrait, C: ColumnTrait>(&self, query: Select<E>, column: C) -> Select<E> {
+        query
+            .filter(self.usable(column))
+            .order_by(column.is_null(), Order::Asc)
+    }
+
+    /// Whether the scope may change a row of `company`
+    pub fn can_write(&self, company: Option<&str>) -> bool {
+        match self {
+            Self::Platform => true,
+            Self::Company(code) => company == Some(code.as_str()),
+        }
+    }
+
+    /// Reject changes to rows the scope does not own
+    pub fn ensure_writable(&self, company: Option<&str>) -> Result<()> {
+        if self.can_write(company) {
+            Ok(())
+        } else {
+            Err(Error::Unauthorized(
+                "Shared items can only be changed by platform administrators".to_string(),
+            ))
+        }
+    }
+
+    /// Company of a new row: tenants always create in their own company,
+    /// platform administrators pick one (blank = shared)
+    pub fn company_for_new(&self, requested: Option<String>) -> Option<String> {
+        match self {
+            Self::Platform => Self::new(requested.as_deref()).company().map(str::to_string),
+            Self::Company(code) => Some(code.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blank_company_is_platform() {
+        assert_eq!(TenantScope::new(None), TenantScope::Platform);
+        assert_eq!(TenantScope::new(Some("  ")), TenantScope::Platform);
+        assert_eq!(TenantScope::new(Some(" acme ")).company(), Some("acme"));
+    }
+
+    #[test]
+    fn test_tenants_write_only_their_own_rows() {
+        let acme = TenantScope::new(Some("acme"));
+        assert!(acme.can_write(Some("acme")));
+        assert!(!acme.can_write(Some("globex")));
+        assert!(!acme.can_write(None));
+        assert!(TenantScope::Platform.can_write(None));
+        assert!(acme.ensure_writable(None).is_err());
+    }
+
+    #[test]
+    fn test_company_for_new_rows() {
+        let acme = TenantScope::new(Some("acme"));
+        assert_eq!(acme.company_for_new(Some("globex".to_string())).as_deref(), Some("acme"));
+        assert_eq!(acme.company_for_new(None).as_deref(), Some("acme"));
+        assert_eq!(TenantScope::Platform.company_for_new(Some("".to_string())), None);
+        assert_eq!(
+            TenantScope::Platform.company_for_new(Some("globex".to_string())).as_deref(),
+            Some("globex")
+        );
+    }
+}
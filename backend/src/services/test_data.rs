@@ -0,0 +1,423 @@
+//! Test Data Scripts
+//!
+//! Builds an INSERT script of sample rows for a schema input, packaged with
+//! the artifacts so a generated screen can be tried end to end right away.
+//! Values follow each column's type and name: Korean person, company and
+//! department names, road addresses, mobile numbers, dates in 2024, codes and
+//! amounts. The same schema and row count always give the same script, and
+//! adding rows doesn't change the earlier ones.
+//!
+//! Constraints are respected where the schema input describes them:
+//! - Primary keys, and id/number/code/email columns, are unique per row
+//! - Strings fit the declared length in bytes (safe for Oracle `BYTE` semantics),
+//!   numbers fit their precision
+//! - Foreign keys read an existing parent row; self-references point at an
+//!   earlier row of the script
+//! - Auto-increment / identity / `SERIAL` columns are left to the database
+
+use chrono::{Duration, NaiveDate};
+
+use crate::domain::{ColumnType, DbDialect, SchemaColumn, SchemaInput};
+
+/// Upper limit for `test_data_rows`
+pub const MAX_TEST_DATA_ROWS: u32 = 1000;
+
+const SURNAMES: &[&str] = &["김", "이", "박", "최", "정", "강", "조", "윤", "장", "임", "한", "오", "서", "신", "권", "황"];
+const GIVEN_NAMES: &[&str] = &[
+    "민준", "서연", "도윤", "서윤", "시우", "지우", "하준", "하윤", "지호", "지유", "준서", "수아", "예준", "지민",
+    "현우", "채원", "건우", "다은", "우진", "예린",
+];
+const CITIES: &[(&str, &[&str])] = &[
+    ("서울특별시", &["강남구", "마포구", "송파구", "종로구", "영등포구"]),
+    ("부산광역시", &["해운대구", "수영구", "부산진구"]),
+    ("경기도 성남시", &["분당구", "수정구"]),
+    ("인천광역시", &["연수구", "남동구"]),
+    ("대전광역시", &["유성구", "서구"]),
+    ("대구광역시", &["수성구", "달서구"]),
+    ("광주광역시", &["북구", "서구"]),
+];
+const ROADS: &[&str] = &["테헤란로", "세종대로", "올림픽로", "해운대로", "판교역로", "중앙로", "월드컵로", "한강대로"];
+const COMPANIES: &[&str] = &[
+    "(주)한빛소프트", "(주)새솔정보", "누리시스템(주)", "(주)다온테크", "미래로(주)", "(주)푸른데이터", "가온솔루션(주)",
+];
+const DEPARTMENTS: &[&str] = &["경영지원팀", "인사팀", "재무팀", "영업1팀", "영업2팀", "개발팀", "품질관리팀", "고객지원팀"];
+const PRODUCTS: &[&str] = &["무선 키보드", "노트북 거치대", "USB-C 허브", "모니터 암", "사무용 의자", "A4 복사용지", "레이저 프린터"];
+const SENTENCES: &[&str] = &[
+    "테스트 데이터입니다.",
+    "정기 점검 결과를 공유합니다.",
+    "고객 요청에 따라 일정을 조정했습니다.",
+    "다음 주까지 검토 부탁드립니다.",
+    "처리 완료 후 결과를 등록했습니다.",
+];
+
+/// Deterministic value source (splitmix64 seeded with FNV-1a)
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: &str) -> Self {
+        let hash = seed
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+        Self(hash)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// In `low..=high`
+    fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next() % (high - low + 1).max(1) as u64) as i64
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[(self.next() % items.len() as u64) as usize]
+    }
+}
+
+/// Generates INSERT scripts of sample rows
+pub struct TestDataGenerator;
+
+impl TestDataGenerator {
+    /// `member_test_data.sql` for table `MEMBER`
+    pub fn filename(schema: &SchemaInput) -> String {
+        format!("{}_test_data.sql", schema.table.to_lowercase())
+    }
+
+    /// INSERT script with `rows` rows (at most `MAX_TEST_DATA_ROWS`)
+    pub fn insert_script(schema: &SchemaInput, rows: u32) -> String {
+        let rows = rows.min(MAX_TEST_DATA_ROWS);
+        let dialect = schema.effective_dialect();
+        let table = match &schema.schema {
+            Some(owner) => format!("{}.{}", owner, schema.table),
+            None => schema.table.clone(),
+        };
+        let columns: Vec<&SchemaColumn> = schema.columns.iter().filter(|c| !Self::is_generated(c)).collect();
+
+        let mut script = format!(
+            "-- Test data: {} ({} rows, {})\n-- Deterministic: the same schema and row count always give these rows.\n",
+            table,
+            rows,
+            dialect.map_or("generic SQL", |d| d.as_str())
+        );
+        let parents: Vec<&str> = schema
+            .foreign_keys
+            .iter()
+            .filter(|fk| !fk.ref_table.eq_ignore_ascii_case(&schema.table))
+            .map(|fk| fk.ref_table.as_str())
+            .collect();
+        if !parents.is_empty() {
+            script.push_str(&format!("-- Load rows into {} first (foreign keys).\n", parents.join(", ")));
+        }
+        script.push('\n');
+
+        let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        for row in 1..=rows as i64 {
+            let values: Vec<String> = columns.iter().map(|c| Self::value(schema, c, row, dialect)).collect();
+            script.push_str(&format!(
+                "INSERT INTO {} ({}) VALUES ({});\n",
+                table,
+                names.join(", "),
+                values.join(", ")
+            ));
+        }
+        script.push_str("\nCOMMIT;\n");
+        script
+    }
+
+    /// Filled in by the database (identity, sequence default, auto-increment)
+    fn is_generated(column: &SchemaColumn) -> bool {
+        let ty = column.column_type.to_uppercase();
+        let default = column.default.as_deref().unwrap_or_default().to_uppercase();
+        ty.contains("SERIAL") || ty.contains("AUTO_INCREMENT") || ty.contains("IDENTITY") || default.contains("NEXTVAL")
+    }
+
+    fn is_pk(schema: &SchemaInput, column: &SchemaColumn) -> bool {
+        column.pk || schema.primary_keys.iter().any(|pk| pk.eq_ignore_ascii_case(&column.name))
+    }
+
+    /// SQL literal for one cell
+    fn value(schema: &SchemaInput, column: &SchemaColumn, row: i64, dialect: Option<DbDialect>) -> String {
+        if let Some(fk) = schema.foreign_keys.iter().find(|fk| fk.column.eq_ignore_ascii_case(&column.name)) {
+            if !fk.ref_table.eq_ignore_ascii_case(&schema.table) {
+                return format!("(SELECT MIN({}) FROM {})", fk.ref_column, fk.ref_table);
+            }
+            // Self-reference: the first row is the root, later rows hang below earlier ones
+            let target = schema.columns.iter().find(|c| c.name.eq_ignore_ascii_case(&fk.ref_column));
+            return match target {
+                Some(_) if row == 1 && column.nullable => "NULL".to_string(),
+                Some(target) if !Self::is_generated(target) => Self::value(schema, target, (row / 2).max(1), dialect),
+                _ => "NULL".to_string(),
+            };
+        }
+
+        let mut rng = Rng::new(&format!("{}.{}.{}", schema.table, column.name, row));
+        let parsed = ColumnType::parse(&column.column_type);
+        let base = parsed.base.as_str();
+        let name = column.name.to_lowercase();
+        let has = |keys: &[&str]| keys.iter().any(|k| name.contains(k));
+        let unique = Self::is_pk(schema, column);
+
+        if base == "BOOLEAN" || base == "BOOL" || base == "BIT" {
+            let value = rng.range(0, 3) > 0;
+            return match dialect {
+                Some(DbDialect::Mysql) | Some(DbDialect::Oracle) => (value as u8).to_string(),
+                _ => value.to_string().to_uppercase(),
+            };
+        }
+
+        if base == "DATE" || base.starts_with("DATETIME") || base.starts_with("TIMESTAMP") {
+            let (start, days) = if has(&["birth", "brth"]) {
+                (NaiveDate::from_ymd_opt(1960, 1, 1), 45 * 365)
+            } else {
+                (NaiveDate::from_ymd_opt(2024, 1, 1), 365)
+            };
+            let offset = if unique { row - 1 } else { rng.range(0, days) };
+            let date = start.unwrap_or_default() + Duration::days(offset);
+            return if base == "DATE" && dialect != Some(DbDialect::Oracle) {
+                format!("DATE '{}'", date.format("%Y-%m-%d"))
+            } else {
+                let time = date.and_hms_opt(rng.range(8, 18) as u32, rng.range(0, 59) as u32, 0).unwrap_or_default();
+                match base {
+                    "DATE" => format!("TO_DATE('{}', 'YYYY-MM-DD HH24:MI:SS')", time.format("%Y-%m-%d %H:%M:%S")),
+                    _ => format!("TIMESTAMP '{}'", time.format("%Y-%m-%d %H:%M:%S")),
+                }
+            };
+        }
+
+        let integer = (base.starts_with("INT") && !base.starts_with("INTERVAL"))
+            || matches!(base, "BIGINT" | "SMALLINT" | "TINYINT" | "MEDIUMINT")
+            || parsed.is_integral_number();
+        let decimal = !integer
+            && (parsed.is_number()
+                || base.starts_with("DECIMAL")
+                || base.starts_with("NUMERIC")
+                || base.starts_with("BINARY_")
+                || matches!(base, "FLOAT" | "DOUBLE" | "DOUBLE PRECISION" | "REAL" | "MONEY"));
+        if integer || decimal {
+            return Self::number(&name, &parsed, integer, unique, row, &mut rng);
+        }
+
+        if base.starts_with("BLOB") || base.ends_with("RAW") || matches!(base, "BYTEA" | "BINARY" | "VARBINARY") {
+            return match (column.nullable, dialect) {
+                (true, _) | (false, None) => "NULL".to_string(),
+                (false, Some(DbDialect::Postgres)) => "decode('', 'hex')".to_string(),
+                (false, Some(DbDialect::Mysql)) => "''".to_string(),
+                (false, Some(DbDialect::Oracle)) => "EMPTY_BLOB()".to_string(),
+            };
+        }
+
+        let long_text = base.starts_with("TEXT") || base.ends_with("CLOB") || base == "LONGTEXT" || base == "LONG";
+        let length = if long_text { None } else { parsed.length() };
+        let text = Self::text(&name, length, unique, row, &mut rng);
+        format!("'{}'", Self::fit(&text, length).replace('\'', "''"))
+    }
+
+    fn number(name: &str, parsed: &ColumnType, integer: bool, unique: bool, row: i64, rng: &mut Rng) -> String {
+        let has = |keys: &[&str]| keys.iter().any(|k| name.contains(k));
+        let scale = if integer { 0 } else { parsed.scale.unwrap_or(2).min(4) };
+        let max = match (parsed.base.as_str(), parsed.precision) {
+            ("TINYINT", _) => 127,
+            ("SMALLINT", _) => 32_767,
+            (_, Some(p)) if p > scale => 10i64.saturating_pow(p - scale).saturating_sub(1),
+            _ => i64::MAX,
+        };
+
+        let whole = if unique || has(&["seq", "sort", "ord", "rank"]) {
+            row
+        } else if has(&["age"]) {
+            rng.range(20, 69)
+        } else if has(&["year", "yyyy"]) {
+            rng.range(2020, 2025)
+        } else if has(&["month"]) {
+            rng.range(1, 12)
+        } else if has(&["cnt", "count", "qty", "quantity"]) {
+            rng.range(1, 100)
+        } else if has(&["amount", "amt", "price", "cost", "salary", "pay", "fee"]) {
+            rng.range(10, 10_000) * 100
+        } else if has(&["rate", "ratio", "pct", "percent"]) {
+            rng.range(0, 100)
+        } else {
+            rng.range(1, 1000)
+        };
+        let whole = whole.min(max);
+
+        if scale == 0 || unique {
+            return whole.to_string();
+        }
+        let fraction = rng.range(0, 10i64.pow(scale) - 1);
+        format!("{}.{:0width$}", whole, fraction, width = scale as usize)
+    }
+
+    fn text(name: &str, length: Option<u32>, unique: bool, row: i64, rng: &mut Rng) -> String {
+        let has = |keys: &[&str]| keys.iter().any(|k| name.contains(k));
+        let code = |prefix: &str| {
+            let digits = format!("{:04}", row);
+            match length {
+                Some(len) if (prefix.len() + digits.len()) as u32 > len => digits,
+                _ => format!("{}{}", prefix, digits),
+            }
+        };
+
+        if name.ends_with("_yn") || name.ends_with("_flag") || length == Some(1) {
+            return if rng.range(0, 4) > 0 { "Y" } else { "N" }.to_string();
+        }
+        if has(&["email", "mail"]) {
+            return format!("user{:04}@example.com", row);
+        }
+        if has(&["phone", "tel", "mobile", "hp", "fax"]) {
+            return format!("010-{:04}-{:04}", rng.range(1000, 9999), rng.range(0, 9999));
+        }
+        if has(&["zip", "post"]) {
+            return format!("{:05}", rng.range(1000, 63_999));
+        }
+        if has(&["addr", "address"]) {
+            if has(&["detail", "dtl", "2"]) {
+                return format!("{}동 {}호", rng.range(101, 112), rng.range(1, 25) * 100 + rng.range(1, 4));
+            }
+            let (city, districts) = rng.pick(CITIES);
+            return format!("{} {} {} {}", city, rng.pick(districts), rng.pick(ROADS), rng.range(1, 500));
+        }
+        if unique {
+            return code("T");
+        }
+        if has(&["status", "stat", "state", "type", "gb", "div", "kind", "grade", "level", "_cd", "code"])
+            && !has(&["_id", "_no"])
+        {
+            return format!("{:02}", rng.range(1, 3));
+        }
+        if name.ends_with("id") || name.ends_with("_no") || name.ends_with("num") || has(&["login", "account"]) {
+            return code("user");
+        }
+        if has(&["company", "corp", "cust_nm", "vendor", "client"]) {
+            return rng.pick(COMPANIES).to_string();
+        }
+        if has(&["dept"]) {
+            return rng.pick(DEPARTMENTS).to_string();
+        }
+        if has(&["product", "prod", "item", "goods"]) {
+            return rng.pick(PRODUCTS).to_string();
+        }
+        if has(&["name", "nm"]) {
+            return format!("{}{}", rng.pick(SURNAMES), rng.pick(GIVEN_NAMES));
+        }
+        if has(&["title", "subject"]) {
+            return format!("테스트 제목 {}", row);
+        }
+        if has(&["url", "homepage", "link"]) {
+            return format!("https://example.com/{}", row);
+        }
+        if has(&["content", "desc", "remark", "note", "memo", "comment", "reason", "etc"]) || length.is_none_or(|l| l > 100) {
+            return rng.pick(SENTENCES).to_string();
+        }
+        format!("테스트{}", row)
+    }
+
+    /// Cut to `length` bytes on a character boundary
+    fn fit(text: &str, length: Option<u32>) -> &str {
+        let Some(length) = length.map(|l| l as usize) else { return text };
+        if text.len() <= length {
+            return text;
+        }
+        let end = (0..=length).rev().find(|i| text.is_char_boundary(*i)).unwrap_or(0);
+        &text[..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ForeignKey;
+
+    fn schema() -> SchemaInput {
+        let mut schema = SchemaInput::new("TB_MEMBER")
+            .with_column(SchemaColumn::new("MEMBER_ID", "NUMBER(10)").primary_key())
+            .with_column(SchemaColumn::new("LOGIN_ID", "VARCHAR2(20)").not_null())
+            .with_column(SchemaColumn::new("MEMBER_NM", "VARCHAR2(50)").not_null())
+            .with_column(SchemaColumn::new("EMAIL", "VARCHAR2(100)"))
+            .with_column(SchemaColumn::new("MOBILE_NO", "VARCHAR2(13)"))
+            .with_column(SchemaColumn::new("ADDR", "VARCHAR2(10)"))
+            .with_column(SchemaColumn::new("DEPT_ID", "NUMBER(10)"))
+            .with_column(SchemaColumn::new("MANAGER_ID", "NUMBER(10)"))
+            .with_column(SchemaColumn::new("SALARY", "NUMBER(12,2)"))
+            .with_column(SchemaColumn::new("USE_YN", "CHAR(1)").not_null())
+            .with_column(SchemaColumn::new("JOIN_DT", "DATE"))
+            .with_dialect(DbDialect::Oracle);
+        schema.foreign_keys = vec![
+            ForeignKey {
+                column: "DEPT_ID".to_string(),
+                ref_table: "TB_DEPT".to_string(),
+                ref_column: "DEPT_ID".to_string(),
+            },
+            ForeignKey {
+                column: "MANAGER_ID".to_string(),
+                ref_table: "TB_MEMBER".to_string(),
+                ref_column: "MEMBER_ID".to_string(),
+            },
+        ];
+        schema
+    }
+
+    #[test]
+    fn test_script_is_deterministic_and_stable() {
+        let script = TestDataGenerator::insert_script(&schema(), 5);
+        assert_eq!(script, TestDataGenerator::insert_script(&schema(), 5));
+        assert!(script.contains("-- Load rows into TB_DEPT first (foreign keys)."));
+        assert_eq!(script.matches("INSERT INTO TB_MEMBER (MEMBER_ID, LOGIN_ID, MEMBER_NM,").count(), 5);
+        assert!(script.ends_with("COMMIT;\n"));
+
+        // More rows keep the earlier ones
+        let longer = TestDataGenerator::insert_script(&schema(), 8);
+        let first_rows: Vec<&str> = script.lines().filter(|l| l.starts_with("INSERT")).collect();
+        assert!(first_rows.iter().all(|row| longer.contains(row)));
+        assert_eq!(TestDataGenerator::filename(&schema()), "tb_member_test_data.sql");
+    }
+
+    #[test]
+    fn test_values_follow_columns_and_constraints() {
+        let script = TestDataGenerator::insert_script(&schema(), 4);
+        let rows: Vec<&str> = script.lines().filter(|l| l.starts_with("INSERT")).collect();
+
+        assert!(rows[0].contains("VALUES (1, 'user0001', '"), "{}", rows[0]);
+        assert!(rows[1].contains("VALUES (2, 'user0002', '"), "{}", rows[1]);
+        assert!(rows[2].contains("'user0003@example.com', '010-"), "{}", rows[2]);
+        assert!(rows[0].contains("(SELECT MIN(DEPT_ID) FROM TB_DEPT)"));
+        // Self-reference: root row, then earlier rows
+        assert!(rows[0].contains("FROM TB_DEPT), NULL, "), "{}", rows[0]);
+        assert!(rows[3].contains("FROM TB_DEPT), 2, "), "{}", rows[3]);
+        assert!(rows[0].contains("TO_DATE('2024-"), "{}", rows[0]);
+
+        // VARCHAR2(10) address cut to 10 bytes without splitting a character
+        let cell = |row: &str, index: usize| -> String {
+            let values = &row[row.find("VALUES (").unwrap() + 8..row.len() - 2];
+            let cells: Vec<&str> = values.split(", ").collect();
+            cells[index].trim_matches('\'').to_string()
+        };
+        for row in &rows {
+            assert!(cell(row, 5).len() <= 10, "{}", row);
+            assert!(cell(row, 2).chars().count() >= 2);
+            assert!(["Y", "N"].contains(&cell(row, 9).as_str()), "{}", row);
+        }
+    }
+
+    #[test]
+    fn test_dialect_literals_and_generated_columns() {
+        let schema = SchemaInput::new("orders")
+            .with_column(SchemaColumn::new("order_id", "BIGSERIAL").primary_key())
+            .with_column(SchemaColumn::new("paid", "BOOLEAN").not_null())
+            .with_column(SchemaColumn::new("ordered_at", "TIMESTAMP"))
+            .with_column(SchemaColumn::new("order_date", "DATE"))
+            .with_column(SchemaColumn::new("qty", "SMALLINT"))
+            .with_dialect(DbDialect::Postgres);
+        let script = TestDataGenerator::insert_script(&schema, 2);
+        assert!(script.contains("INSERT INTO orders (paid, ordered_at, order_date, qty) VALUES ("));
+        assert!(script.contains("TRUE") || script.contains("FALSE"));
+        assert!(script.contains("TIMESTAMP '2024-") && script.contains("DATE '2024-"));
+
+        assert_eq!(TestDataGenerator::insert_script(&schema, 5000).matches("INSERT").count(), MAX_TEST_DATA_ROWS as usize);
+    }
+}
@@ -0,0 +1,502 @@
+//! TODO Resolution
+//!
+//! Generated screens leave TODO placeholders wherever the model could not
+//! know a value: transaction endpoints, popup screen paths, unverified APIs.
+//! The resolver lists them with their surrounding code and substitutes
+//! values supplied by the developer. Substitution is purely textual: the
+//! placeholder literal next to the TODO is replaced and the TODO comment is
+//! dropped, so the same values always yield the same file.
+//!
+//! TODOs are identified as `{file}:{line}` (line of the TODO comment in the
+//! unmodified file); a resolution whose id no longer matches is rejected.
+
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{GeneratedArtifacts, UiIntent};
+use crate::models::_entities::generation_logs;
+use crate::services::pipeline::{ExecutionMode, PostProcessingPipeline};
+
+/// Lines after a TODO searched for its placeholder
+const LOOKAHEAD_LINES: usize = 8;
+
+/// Lines of code shown around a TODO
+const CONTEXT_BEFORE: usize = 2;
+const CONTEXT_AFTER: usize = 4;
+
+/// What a TODO asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoKind {
+    /// Transaction / API endpoint URL
+    Endpoint,
+    /// Screen file opened as a popup
+    PopupPath,
+    /// API call the allowlist could not verify
+    Api,
+    /// Anything else (implementation left to the developer)
+    Other,
+}
+
+impl TodoKind {
+    fn of(comment: &str) -> Self {
+        let lower = comment.to_lowercase();
+        if lower.contains("verify api") {
+            Self::Api
+        } else if lower.contains("popup") || lower.contains("screen path") {
+            Self::PopupPath
+        } else if lower.contains("endpoint") || lower.contains("url") {
+            Self::Endpoint
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Whether `value` can replace a placeholder of this kind
+    fn validate(self, value: &str) -> std::result::Result<(), String> {
+        if value.is_empty() {
+            return Err("value is empty".to_string());
+        }
+        if value.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '\\' | '<' | '>')) {
+            return Err(format!("'{}' contains whitespace, quotes or markup characters", value));
+        }
+        match self {
+            Self::Endpoint if !(value.starts_with('/') || value.starts_with("http://") || value.starts_with("https://")) => {
+                Err(format!("endpoint '{}' must start with / or http(s)://", value))
+            }
+            Self::PopupPath if !value.ends_with(".xml") => Err(format!("popup path '{}' must end with .xml", value)),
+            Self::Api if !api_name().is_match(value) => Err(format!("'{}' is not an API name", value)),
+            Self::Other => Err("this TODO has no placeholder to substitute".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A TODO found in a generated file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TodoItem {
+    /// `{file}:{line}`, used to resolve it
+    pub id: String,
+    pub file: String,
+    /// 1-based line of the TODO comment
+    pub line: usize,
+    pub kind: TodoKind,
+    /// Comment text after `TODO:`
+    pub comment: String,
+    /// Current placeholder value (`None` when there is nothing to substitute)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// Surrounding lines
+    pub context: String,
+}
+
+/// Value supplied for one TODO
+#[derive(Debug, Clone, Deserialize)]
+pub struct TodoResolution {
+    pub id: String,
+    pub value: String,
+}
+
+/// Text replaced by the value: line index and byte range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placeholder {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+/// A TODO plus where its placeholder sits
+#[derive(Debug, Clone)]
+struct FoundTodo {
+    item: TodoItem,
+    placeholder: Option<Placeholder>,
+    /// Whether the comment is the whole line (removed on resolution)
+    own_line: bool,
+}
+
+/// TODO comments in JS (`//`, `/* */`) and XML (`<!-- -->`)
+fn todo_comment() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?://|/\*|<!--)\s*TODO\b:?\s*(.*?)\s*(?:\*/|-->|$)").expect("Failed to compile TODO regex")
+    })
+}
+
+/// Quoted string literals, optionally after `url:`
+fn string_literal() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(url\s*:\s*)?(?:"([^"\n]*)"|'([^'\n]*)')"#).expect("Failed to compile literal regex")
+    })
+}
+
+/// `/* TODO: verify API 'name' */ name` inserted by the allowlist pass
+fn unverified_api() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"/\*\s*TODO: verify API '([^']+)'\s*\*/\s*").expect("Failed to compile API TODO regex")
+    })
+}
+
+fn api_name() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^[A-Za-z_$][\w$]*(\.[A-Za-z_$][\w$]*)*$").expect("Failed to compile API name regex"))
+}
+
+/// Lists TODO placeholders and substitutes supplied values
+pub struct TodoResolver;
+
+impl TodoResolver {
+    /// TODOs of one file, in line order
+    pub fn find(file: &str, code: &str) -> Vec<TodoItem> {
+        Self::scan(file, code).into_iter().map(|t| t.item).collect()
+    }
+
+    fn scan(file: &str, code: &str) -> Vec<FoundTodo> {
+        let lines: Vec<&str> = code.lines().collect();
+        let mut found = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            let Some(caps) = todo_comment().captures(line) else {
+                continue;
+            };
+            let comment_start = caps.get(0).map(|m| m.start()).unwrap_or_default();
+            let comment = caps[1].trim().to_string();
+            let kind = TodoKind::of(&comment);
+
+            let (placeholder, current) = match kind {
+                // The marker comment and the call name after it are replaced together
+                TodoKind::Api => match unverified_api().captures(line) {
+                    Some(c) if line[c.get(0).map_or(0, |m| m.end())..].starts_with(&c[1]) => {
+                        let marker = c.get(0).expect("whole match");
+                        let placeholder = Placeholder { line: idx, start: marker.start(), end: marker.end() + c[1].len() };
+                        (Some(placeholder), Some(c[1].to_string()))
+                    }
+                    _ => (None, None),
+                },
+                TodoKind::Endpoint | TodoKind::PopupPath => {
+                    let placeholder = Self::placeholder_after(&lines, idx, comment_start, kind);
+                    (placeholder, placeholder.map(|p| lines[p.line][p.start..p.end].to_string()))
+                }
+                TodoKind::Other => (None, None),
+            };
+
+            let from = idx.saturating_sub(CONTEXT_BEFORE);
+            let to = (idx + CONTEXT_AFTER + 1).min(lines.len());
+            found.push(FoundTodo {
+                item: TodoItem {
+                    id: format!("{}:{}", file, idx + 1),
+                    file: file.to_string(),
+                    line: idx + 1,
+                    kind,
+                    comment,
+                    placeholder: current,
+                    context: lines[from..to].join("\n"),
+                },
+                placeholder,
+                own_line: line[..comment_start].trim().is_empty() && kind != TodoKind::Api,
+            });
+        }
+        found
+    }
+
+    /// First literal that looks like the wanted value: on the TODO line before
+    /// the comment, then on the following lines up to the next TODO
+    fn placeholder_after(lines: &[&str], todo_line: usize, comment_start: usize, kind: TodoKind) -> Option<Placeholder> {
+        let last = (todo_line + LOOKAHEAD_LINES).min(lines.len().saturating_sub(1));
+        for (idx, &line) in lines.iter().enumerate().take(last + 1).skip(todo_line) {
+            let line = if idx == todo_line { &line[..comment_start] } else { line };
+            if idx > todo_line && todo_comment().is_match(line) {
+                break;
+            }
+            for caps in string_literal().captures_iter(line) {
+                let Some(content) = caps.get(2).or_else(|| caps.get(3)) else {
+                    continue;
+                };
+                let value = content.as_str();
+                let matches = match kind {
+                    TodoKind::PopupPath => caps.get(1).is_some() || value.ends_with(".xml"),
+                    _ => value.starts_with('/') || value.starts_with("http://") || value.starts_with("https://"),
+                };
+                if matches {
+                    return Some(Placeholder { line: idx, start: content.start(), end: content.end() });
+                }
+            }
+        }
+        None
+    }
+
+    /// Substitute `resolutions` belonging to `file`; returns the new code,
+    /// the ids applied and an error per rejected resolution
+    pub fn resolve(file: &str, code: &str, resolutions: &[TodoResolution]) -> (String, Vec<String>, Vec<String>) {
+        let todos = Self::scan(file, code);
+        let mut lines: Vec<String> = code.lines().map(str::to_string).collect();
+        let mut applied = Vec::new();
+        let mut errors = Vec::new();
+        let mut remove = Vec::new();
+
+        let prefix = format!("{}:", file);
+        let mut wanted: Vec<(&FoundTodo, &TodoResolution)> = Vec::new();
+        for resolution in resolutions.iter().filter(|r| r.id.starts_with(&prefix)) {
+            match todos.iter().find(|t| t.item.id == resolution.id) {
+                Some(todo) => wanted.push((todo, resolution)),
+                None => errors.push(format!("{}: no TODO at this line", resolution.id)),
+            }
+        }
+        // Right-to-left within a line keeps the byte ranges of earlier placeholders valid
+        wanted.sort_by_key(|(todo, _)| std::cmp::Reverse(todo.placeholder.map(|p| (p.line, p.start))));
+
+        for (todo, resolution) in wanted {
+            let value = resolution.value.trim();
+            if let Err(e) = todo.item.kind.validate(value) {
+                errors.push(format!("{}: {}", resolution.id, e));
+                continue;
+            }
+            let Some(p) = todo.placeholder else {
+                errors.push(format!("{}: no placeholder found next to the TODO", resolution.id));
+                continue;
+            };
+
+            lines[p.line].replace_range(p.start..p.end, value);
+            if todo.item.kind != TodoKind::Api {
+                if todo.own_line {
+                    remove.push(todo.item.line - 1);
+                } else if let Some(m) = todo_comment().find(&lines[todo.item.line - 1]) {
+                    let start = m.start();
+                    let todo_line = &mut lines[todo.item.line - 1];
+                    todo_line.truncate(start);
+                    todo_line.truncate(todo_line.trim_end().len());
+                }
+            }
+            applied.push(resolution.id.clone());
+        }
+
+        remove.sort_unstable();
+        for idx in remove.into_iter().rev() {
+            lines.remove(idx);
+        }
+        let mut resolved = lines.join("\n");
+        if code.ends_with('\n') {
+            resolved.push('\n');
+        }
+        applied.reverse();
+        (resolved, applied, errors)
+    }
+
+    /// Artifacts and intent of a logged screen generation
+    pub fn generation(log: &generation_logs::Model) -> Result<(GeneratedArtifacts, UiIntent)> {
+        if log.product == "spring-backend" {
+            return Err(anyhow!("TODO resolution is only available for screen generations"));
+        }
+        let artifacts: Option<GeneratedArtifacts> = match log.artifacts.as_deref() {
+            Some(json) => serde_json::from_str(json)?,
+            None => None,
+        };
+        let artifacts = artifacts.ok_or_else(|| anyhow!("Generation {} has no artifacts", log.id))?;
+        let intent: UiIntent = serde_json::from_str(&log.ui_intent)?;
+        Ok((artifacts, intent))
+    }
+
+    /// Files of a screen generation as `(name, code)`
+    fn files(artifacts: &GeneratedArtifacts) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        if let Some(xml) = &artifacts.xml {
+            files.push((artifacts.xml_filename.clone().unwrap_or_else(|| "screen.xml".to_string()), xml.clone()));
+        }
+        if let Some(js) = &artifacts.javascript {
+            files.push((artifacts.js_filename.clone().unwrap_or_else(|| "screen.js".to_string()), js.clone()));
+        }
+        for screen in &artifacts.additional_screens {
+            files.push((screen.xml_filename.clone(), screen.xml.clone()));
+            files.push((screen.js_filename.clone(), screen.javascript.clone()));
+        }
+        files
+    }
+
+    /// TODOs across all files of a screen generation
+    pub fn find_in_artifacts(artifacts: &GeneratedArtifacts) -> Vec<TodoItem> {
+        Self::files(artifacts)
+            .iter()
+            .flat_map(|(name, code)| Self::find(name, code))
+            .collect()
+    }
+
+    /// Substitute values across a screen generation and re-validate the result
+    pub fn resolve_artifacts(
+        artifacts: &GeneratedArtifacts,
+        intent: &UiIntent,
+        resolutions: &[TodoResolution],
+    ) -> Result<TodoResolveResult> {
+        let known: Vec<String> = Self::files(artifacts).into_iter().map(|(name, _)| name).collect();
+        let mut errors: Vec<String> = resolutions
+            .iter()
+            .filter(|r| !known.iter().any(|name| r.id.starts_with(&format!("{}:", name))))
+            .map(|r| format!("{}: unknown file", r.id))
+            .collect();
+        let mut applied = Vec::new();
+
+        let mut resolve = |name: &str, code: &mut String| {
+            let (resolved, ids, errs) = Self::resolve(name, code, resolutions);
+            *code = resolved;
+            applied.extend(ids);
+            errors.extend(errs);
+        };
+        let mut resolved = artifacts.clone();
+        if let Some(xml) = resolved.xml.as_mut() {
+            resolve(resolved.xml_filename.as_deref().unwrap_or("screen.xml"), xml);
+        }
+        if let Some(js) = resolved.javascript.as_mut() {
+            resolve(resolved.js_filename.as_deref().unwrap_or("screen.js"), js);
+        }
+        for screen in resolved.additional_screens.iter_mut() {
+            resolve(&screen.xml_filename, &mut screen.xml);
+            resolve(&screen.js_filename, &mut screen.javascript);
+        }
+
+        let warnings = Self::validate(&resolved, intent)?;
+        let remaining = Self::find_in_artifacts(&resolved);
+        Ok(TodoResolveResult {
+            artifacts: resolved,
+            applied,
+            errors,
+            warnings,
+            remaining,
+        })
+    }
+
+    /// Post-processing findings for the resolved files (Relaxed mode; the
+    /// pipeline's rewrites are discarded, only its warnings are reported)
+    fn validate(artifacts: &GeneratedArtifacts, intent: &UiIntent) -> Result<Vec<String>> {
+        let section = |xml: &str, js: &str| format!("--- XML ---\n{}\n\n--- JS ---\n{}\n", xml, js);
+        let mut raw = section(
+            artifacts.xml.as_deref().unwrap_or_default(),
+            artifacts.javascript.as_deref().unwrap_or_default(),
+        );
+        for screen in &artifacts.additional_screens {
+            raw.push('\n');
+            raw.push_str(&section(&screen.xml, &screen.javascript));
+        }
+        Ok(PostProcessingPipeline::run(raw, intent, ExecutionMode::Relaxed)?.warnings)
+    }
+}
+
+/// Outcome of resolving TODOs in a screen generation
+#[derive(Debug, Clone, Serialize)]
+pub struct TodoResolveResult {
+    /// Files with the values substituted
+    pub artifacts: GeneratedArtifacts,
+    /// Ids of the TODOs resolved
+    pub applied: Vec<String>,
+    /// Resolutions that were rejected
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// Post-processing findings for the resolved files
+    pub warnings: Vec<String>,
+    /// TODOs still open
+    pub remaining: Vec<TodoItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JS: &str = r#"this.fn_search = function() {
+    // TODO: Replace with actual API endpoint
+    var tranUrl = "/api/tasks/search";
+
+    // TODO: Implement transaction call
+    fn_updateTotalCount();
+};
+
+this.fn_create = function() {
+    // TODO: Replace with actual popup screen path
+    loadpopup({
+        url: "/screens/task_editor.xml",
+        width: 600
+    });
+    /* TODO: verify API 'grid_list.getSelRow' */ grid_list.getSelRow();
+};
+"#;
+
+    fn resolution(id: &str, value: &str) -> TodoResolution {
+        TodoResolution { id: id.to_string(), value: value.to_string() }
+    }
+
+    #[test]
+    fn test_find_classifies_todos() {
+        let todos = TodoResolver::find("task_list.js", JS);
+        let summary: Vec<(usize, TodoKind, Option<&str>)> =
+            todos.iter().map(|t| (t.line, t.kind, t.placeholder.as_deref())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, TodoKind::Endpoint, Some("/api/tasks/search")),
+                (5, TodoKind::Other, None),
+                (10, TodoKind::PopupPath, Some("/screens/task_editor.xml")),
+                (15, TodoKind::Api, Some("grid_list.getSelRow")),
+            ]
+        );
+        assert_eq!(todos[0].id, "task_list.js:2");
+        assert!(todos[0].context.contains("this.fn_search"));
+    }
+
+    #[test]
+    fn test_resolve_substitutes_and_drops_comments() {
+        let (code, applied, errors) = TodoResolver::resolve(
+            "task_list.js",
+            JS,
+            &[
+                resolution("task_list.js:2", "/task/selectTaskList.do"),
+                resolution("task_list.js:10", "/screens/task/TASK_EDIT_POP.xml"),
+                resolution("task_list.js:15", "grid_list.getselectrow"),
+            ],
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(applied.len(), 3);
+        assert!(code.contains(r#"var tranUrl = "/task/selectTaskList.do";"#));
+        assert!(code.contains(r#"url: "/screens/task/TASK_EDIT_POP.xml","#));
+        assert!(code.contains("    grid_list.getselectrow();"));
+        assert!(!code.contains("Replace with actual"));
+        assert!(code.contains("TODO: Implement transaction call"));
+
+        // Same input, same output
+        let (again, _, _) = TodoResolver::resolve(
+            "task_list.js",
+            JS,
+            &[
+                resolution("task_list.js:15", "grid_list.getselectrow"),
+                resolution("task_list.js:10", "/screens/task/TASK_EDIT_POP.xml"),
+                resolution("task_list.js:2", "/task/selectTaskList.do"),
+            ],
+        );
+        assert_eq!(code, again);
+    }
+
+    #[test]
+    fn test_resolve_rejects_invalid_values() {
+        let (code, applied, errors) = TodoResolver::resolve(
+            "task_list.js",
+            JS,
+            &[
+                resolution("task_list.js:2", "task/search\"); alert(1"),
+                resolution("task_list.js:5", "/api/x"),
+                resolution("task_list.js:10", "/screens/editor"),
+                resolution("task_list.js:3", "/api/x"),
+            ],
+        );
+        assert_eq!(code, JS);
+        assert!(applied.is_empty());
+        assert_eq!(errors.len(), 4);
+    }
+
+    #[test]
+    fn test_inline_todo_keeps_code() {
+        let js = "var url = \"/api/TODO\"; // TODO: set endpoint\n";
+        let (code, applied, _) = TodoResolver::resolve("a.js", js, &[resolution("a.js:1", "/member/list.do")]);
+        assert_eq!(applied, vec!["a.js:1"]);
+        assert_eq!(code, "var url = \"/member/list.do\";\n");
+    }
+}
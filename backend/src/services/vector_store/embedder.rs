@@ -0,0 +1,120 @@
+//! Text embedders for the vector store
+//!
+//! - `gguf`: a local GGUF sentence encoder run through llama.cpp
+//!   (`local-llm` feature)
+//! - `hashing`: feature hashing of words and character trigrams - no model
+//!   file, weaker semantics, useful when no embedding model is approved
+
+use std::path::PathBuf;
+
+use crate::llm::LocalEmbedder;
+
+/// Turns text into a fixed-size vector
+pub trait TextEmbedder: Send + Sync {
+    /// Identifies the model; a stored index built by another model is discarded
+    fn model(&self) -> String;
+
+    fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>>;
+}
+
+/// Dimensions of the hashing embedder
+const HASHING_DIMENSIONS: usize = 512;
+
+/// Signed feature hashing over lowercase words and character trigrams
+///
+/// Trigrams keep Korean text (no whitespace between particles) and
+/// identifiers such as `fn_search` / `fnSearch` comparable.
+pub struct HashingEmbedder;
+
+impl HashingEmbedder {
+    fn fnv1a(feature: &str) -> u64 {
+        feature.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    fn add(vector: &mut [f32], feature: &str, weight: f32) {
+        let hash = Self::fnv1a(feature);
+        let slot = (hash % HASHING_DIMENSIONS as u64) as usize;
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[slot] += sign * weight;
+    }
+}
+
+impl TextEmbedder for HashingEmbedder {
+    fn model(&self) -> String {
+        format!("hashing-{}", HASHING_DIMENSIONS)
+    }
+
+    fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let mut vector = vec![0.0f32; HASHING_DIMENSIONS];
+        let lower = text.to_lowercase();
+
+        for word in lower
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| !w.is_empty())
+        {
+            Self::add(&mut vector, word, 1.0);
+
+            let chars: Vec<char> = format!(" {} ", word).chars().collect();
+            for trigram in chars.windows(3) {
+                Self::add(&mut vector, &trigram.iter().collect::<String>(), 0.5);
+            }
+        }
+        Ok(vector)
+    }
+}
+
+/// GGUF embedding model through llama.cpp
+pub struct GgufEmbedder {
+    inner: LocalEmbedder,
+}
+
+impl GgufEmbedder {
+    /// Token window per text; longer entries are truncated
+    const CONTEXT_SIZE: u32 = 512;
+
+    pub fn new(model_path: PathBuf, threads: u32) -> Self {
+        Self {
+            inner: LocalEmbedder::new(model_path, Self::CONTEXT_SIZE, threads),
+        }
+    }
+}
+
+impl TextEmbedder for GgufEmbedder {
+    fn model(&self) -> String {
+        self.inner.model_name().to_string()
+    }
+
+    fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        self.inner.embed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::vector_store::hnsw::normalize;
+
+    fn similarity(a: &str, b: &str) -> f32 {
+        let mut a = HashingEmbedder.embed(a).unwrap();
+        let mut b = HashingEmbedder.embed(b).unwrap();
+        normalize(&mut a);
+        normalize(&mut b);
+        a.iter().zip(&b).map(|(x, y)| x * y).sum()
+    }
+
+    #[test]
+    fn test_hashing_embedder_is_deterministic() {
+        let a = HashingEmbedder.embed("Grid column header").unwrap();
+        assert_eq!(a.len(), HASHING_DIMENSIONS);
+        assert_eq!(a, HashingEmbedder.embed("grid COLUMN header").unwrap());
+    }
+
+    #[test]
+    fn test_hashing_embedder_ranks_related_text_higher() {
+        let related = similarity("그리드 컬럼 헤더 설정", "그리드의 컬럼 헤더를 설정하는 방법");
+        let unrelated = similarity("그리드 컬럼 헤더 설정", "transaction timeout for save");
+        assert!(related > unrelated + 0.2, "{} vs {}", related, unrelated);
+    }
+}
@@ -0,0 +1,327 @@
+//! HNSW index (hierarchical navigable small world graph)
+//!
+//! Approximate nearest-neighbour search over unit vectors by cosine
+//! similarity. Each node lives on levels `0..=level`; upper levels are sparse
+//! and route the search towards the query, level 0 holds every node. Nodes
+//! are never removed - the store rebuilds the index once stale entries pile up.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Neighbours kept per node on upper levels (twice as many on level 0)
+const DEFAULT_M: usize = 12;
+
+/// Candidate list size while inserting
+const DEFAULT_EF_CONSTRUCTION: usize = 64;
+
+/// Distance with a total order, for the heaps
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Dist(f32);
+
+impl Eq for Dist {}
+
+impl PartialOrd for Dist {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Dist {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Node {
+    key: String,
+    vector: Vec<f32>,
+    /// Neighbour node indexes per level
+    neighbors: Vec<Vec<usize>>,
+}
+
+/// Scale to unit length (zero vectors stay zero)
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+}
+
+/// Nearest-neighbour index over string keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HnswIndex {
+    dimensions: usize,
+    m: usize,
+    ef_construction: usize,
+    nodes: Vec<Node>,
+    entry_point: Option<usize>,
+    /// xorshift state for level assignment (deterministic builds)
+    rng: u64,
+}
+
+impl HnswIndex {
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            m: DEFAULT_M,
+            ef_construction: DEFAULT_EF_CONSTRUCTION,
+            nodes: Vec::new(),
+            entry_point: None,
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Key and (normalized) vector of every node, in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[f32])> {
+        self.nodes.iter().map(|n| (n.key.as_str(), n.vector.as_slice()))
+    }
+
+    fn max_neighbors(&self, level: usize) -> usize {
+        if level == 0 {
+            self.m * 2
+        } else {
+            self.m
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let uniform = ((self.rng >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level = (-uniform.ln() / (self.m as f64).ln()).floor() as usize;
+        level.min(16)
+    }
+
+    fn top_level(&self) -> usize {
+        self.entry_point.map_or(0, |ep| self.nodes[ep].neighbors.len() - 1)
+    }
+
+    /// Closest `ef` nodes to `query` on `level`, nearest first
+    fn search_level(&self, query: &[f32], entry_points: &[usize], ef: usize, level: usize) -> Vec<(f32, usize)> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<(Dist, usize)>> = BinaryHeap::new();
+        let mut found: BinaryHeap<(Dist, usize)> = BinaryHeap::new();
+
+        for &ep in entry_points {
+            let d = Dist(distance(query, &self.nodes[ep].vector));
+            candidates.push(Reverse((d, ep)));
+            found.push((d, ep));
+        }
+
+        while let Some(Reverse((d, current))) = candidates.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|(worst, _)| d > *worst) {
+                break;
+            }
+            let Some(neighbors) = self.nodes[current].neighbors.get(level) else {
+                continue;
+            };
+            for &next in neighbors {
+                if !visited.insert(next) {
+                    continue;
+                }
+                let d = Dist(distance(query, &self.nodes[next].vector));
+                if found.len() < ef || found.peek().is_some_and(|(worst, _)| d < *worst) {
+                    candidates.push(Reverse((d, next)));
+                    found.push((d, next));
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        let mut result: Vec<(f32, usize)> = found.into_iter().map(|(d, i)| (d.0, i)).collect();
+        result.sort_by(|a, b| a.0.total_cmp(&b.0));
+        result
+    }
+
+    /// Walk down from the top level to `level + 1`, one closest node at a time
+    fn descend(&self, query: &[f32], level: usize) -> Option<usize> {
+        let mut current = self.entry_point?;
+        for l in (level + 1..=self.top_level()).rev() {
+            current = self.search_level(query, &[current], 1, l)[0].1;
+        }
+        Some(current)
+    }
+
+    /// Add a vector (normalized here) under `key`
+    pub fn insert(&mut self, key: impl Into<String>, mut vector: Vec<f32>) -> anyhow::Result<()> {
+        if vector.len() != self.dimensions {
+            anyhow::bail!(
+                "Vector has {} dimensions, index expects {}",
+                vector.len(),
+                self.dimensions
+            );
+        }
+        normalize(&mut vector);
+
+        let level = self.random_level();
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            key: key.into(),
+            vector,
+            neighbors: vec![Vec::new(); level + 1],
+        });
+
+        let query = self.nodes[id].vector.clone();
+        let Some(mut current) = self.descend(&query, level) else {
+            self.entry_point = Some(id);
+            return Ok(());
+        };
+
+        for l in (0..=level.min(self.top_level())).rev() {
+            let nearest = self.search_level(&query, &[current], self.ef_construction, l);
+            let selected: Vec<usize> = nearest.iter().take(self.max_neighbors(l)).map(|(_, i)| *i).collect();
+            current = nearest[0].1;
+
+            for &neighbor in &selected {
+                self.nodes[neighbor].neighbors[l].push(id);
+                self.prune(neighbor, l);
+            }
+            self.nodes[id].neighbors[l] = selected;
+        }
+
+        if level > self.top_level() {
+            self.entry_point = Some(id);
+        }
+        Ok(())
+    }
+
+    /// Keep only the closest neighbours of `node` on `level`
+    fn prune(&mut self, node: usize, level: usize) {
+        let max = self.max_neighbors(level);
+        if self.nodes[node].neighbors[level].len() <= max {
+            return;
+        }
+        let base = &self.nodes[node].vector;
+        let mut scored: Vec<(f32, usize)> = self.nodes[node].neighbors[level]
+            .iter()
+            .map(|&n| (distance(base, &self.nodes[n].vector), n))
+            .collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self.nodes[node].neighbors[level] = scored.into_iter().take(max).map(|(_, n)| n).collect();
+    }
+
+    /// Up to `k` keys closest to `query` with their cosine similarity, best first
+    ///
+    /// `ef` (at least `k`) trades speed for recall.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(String, f32)> {
+        if query.len() != self.dimensions || k == 0 {
+            return Vec::new();
+        }
+        let mut query = query.to_vec();
+        normalize(&mut query);
+
+        let Some(entry) = self.descend(&query, 0) else {
+            return Vec::new();
+        };
+        self.search_level(&query, &[entry], ef.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|(d, i)| (self.nodes[i].key.clone(), 1.0 - d))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random vectors
+    fn vectors(count: usize, dims: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                (0..dims)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                        ((state >> 33) as f32 / u32::MAX as f32) - 0.25
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn brute_force(data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+        let mut q = query.to_vec();
+        normalize(&mut q);
+        let mut scored: Vec<(f32, usize)> = data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let mut v = v.clone();
+                normalize(&mut v);
+                (distance(&q, &v), i)
+            })
+            .collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        scored.into_iter().take(k).map(|(_, i)| format!("kb:{}", i)).collect()
+    }
+
+    #[test]
+    fn test_search_finds_exact_match() {
+        let data = vectors(300, 24);
+        let mut index = HnswIndex::new(24);
+        for (i, v) in data.iter().enumerate() {
+            index.insert(format!("kb:{}", i), v.clone()).unwrap();
+        }
+        assert_eq!(index.len(), 300);
+
+        for i in [0, 57, 299] {
+            let hits = index.search(&data[i], 1, 32);
+            assert_eq!(hits[0].0, format!("kb:{}", i));
+            assert!((hits[0].1 - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_recall_against_brute_force() {
+        let data = vectors(400, 16);
+        let queries = vectors(420, 16).split_off(400);
+        let mut index = HnswIndex::new(16);
+        for (i, v) in data.iter().enumerate() {
+            index.insert(format!("kb:{}", i), v.clone()).unwrap();
+        }
+
+        let mut hits = 0;
+        for query in &queries {
+            let expected = brute_force(&data, query, 5);
+            let found: Vec<String> = index.search(query, 5, 64).into_iter().map(|(k, _)| k).collect();
+            hits += expected.iter().filter(|k| found.contains(k)).count();
+        }
+        let recall = hits as f32 / (queries.len() * 5) as f32;
+        assert!(recall >= 0.9, "recall {}", recall);
+    }
+
+    #[test]
+    fn test_round_trip_and_dimension_check() {
+        let mut index = HnswIndex::new(3);
+        index.insert("a", vec![1.0, 0.0, 0.0]).unwrap();
+        index.insert("b", vec![0.0, 1.0, 0.0]).unwrap();
+        assert!(index.insert("c", vec![1.0, 0.0]).is_err());
+
+        let restored: HnswIndex = serde_json::from_str(&serde_json::to_string(&index).unwrap()).unwrap();
+        assert_eq!(restored.search(&[0.1, 0.9, 0.0], 1, 8)[0].0, "b");
+        assert!(HnswIndex::new(3).search(&[1.0, 0.0, 0.0], 3, 8).is_empty());
+    }
+}
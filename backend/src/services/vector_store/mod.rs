@@ -0,0 +1,330 @@
+//! Embedded Vector Store
+//!
+//! Semantic retrieval over the knowledge base without any external service:
+//! entries are embedded in-process (GGUF model through llama.cpp, or feature
+//! hashing) and kept in an HNSW index persisted to a single file, so
+//! air-gapped installs get RAG with nothing but a model file on disk.
+//!
+//! Configuration (env):
+//! - `KB_RETRIEVAL`: `keyword` (default) or `vector`
+//! - `KB_EMBEDDER`: `gguf` (default) or `hashing`
+//! - `KB_EMBEDDING_MODEL_PATH`: GGUF embedding model (default `llm-models/embedding.gguf`)
+//! - `KB_VECTOR_INDEX_PATH`: index file (default `data/kb-index.json`)
+//! - `KB_EMBEDDING_THREADS`: CPU threads for the embedding model (default 4)
+//!
+//! Entries are indexed lazily: every search embeds entries whose content is
+//! not in the index yet, so edits are picked up on the next question.
+
+pub mod embedder;
+pub mod hnsw;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+pub use embedder::{GgufEmbedder, HashingEmbedder, TextEmbedder};
+pub use hnsw::HnswIndex;
+
+/// How knowledge entries are retrieved for Q&A
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetrievalMode {
+    #[default]
+    Keyword,
+    Vector,
+}
+
+/// Which embedder builds the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmbedderKind {
+    #[default]
+    Gguf,
+    Hashing,
+}
+
+#[derive(Debug, Clone)]
+pub struct VectorStoreConfig {
+    pub mode: RetrievalMode,
+    pub embedder: EmbedderKind,
+    pub model_path: PathBuf,
+    pub index_path: PathBuf,
+    pub threads: u32,
+}
+
+impl VectorStoreConfig {
+    pub fn from_env() -> Self {
+        let var = |key: &str| std::env::var(key).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let mode = match var("KB_RETRIEVAL").map(|v| v.to_lowercase()).as_deref() {
+            Some("vector") | Some("hybrid") => RetrievalMode::Vector,
+            _ => RetrievalMode::Keyword,
+        };
+        let embedder = match var("KB_EMBEDDER").map(|v| v.to_lowercase()).as_deref() {
+            Some("hashing") => EmbedderKind::Hashing,
+            _ => EmbedderKind::Gguf,
+        };
+
+        Self {
+            mode,
+            embedder,
+            model_path: var("KB_EMBEDDING_MODEL_PATH")
+                .unwrap_or_else(|| "llm-models/embedding.gguf".to_string())
+                .into(),
+            index_path: var("KB_VECTOR_INDEX_PATH")
+                .unwrap_or_else(|| "data/kb-index.json".to_string())
+                .into(),
+            threads: var("KB_EMBEDDING_THREADS")
+                .and_then(|v| v.parse().ok())
+                .filter(|t| (1..=64).contains(t))
+                .unwrap_or(4),
+        }
+    }
+}
+
+/// Text of one knowledge entry to index
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub id: i32,
+    pub text: String,
+}
+
+impl Document {
+    /// Index key: entry id plus a content hash, so an edited entry is re-embedded
+    fn key(&self) -> String {
+        let hash = self.text.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        format!("{}:{:016x}", self.id, hash)
+    }
+}
+
+/// Index file contents
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredIndex {
+    model: String,
+    index: HnswIndex,
+}
+
+/// Embedder plus the on-disk index
+pub struct VectorStore {
+    embedder: Box<dyn TextEmbedder>,
+    index_path: PathBuf,
+    index: Mutex<Option<HnswIndex>>,
+}
+
+impl VectorStore {
+    /// Open the store, reusing the index file when it was built by the same model
+    pub fn open(embedder: Box<dyn TextEmbedder>, index_path: PathBuf) -> Self {
+        let index = match Self::load(&index_path) {
+            Ok(Some(stored)) if stored.model == embedder.model() => Some(stored.index),
+            Ok(Some(stored)) => {
+                tracing::info!(
+                    "Vector index {:?} was built by {}, rebuilding for {}",
+                    index_path,
+                    stored.model,
+                    embedder.model()
+                );
+                None
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable vector index {:?}: {}", index_path, e);
+                None
+            }
+        };
+
+        Self {
+            embedder,
+            index_path,
+            index: Mutex::new(index),
+        }
+    }
+
+    fn load(path: &Path) -> anyhow::Result<Option<StoredIndex>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = std::fs::read(path)?;
+        Ok(Some(serde_json::from_slice(&data)?))
+    }
+
+    /// Write the index atomically (temporary file, then rename)
+    fn save(&self, index: &HnswIndex) -> anyhow::Result<()> {
+        if let Some(dir) = self.index_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let stored = StoredIndex {
+            model: self.embedder.model(),
+            index: index.clone(),
+        };
+        let tmp = self.index_path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&stored)?)?;
+        std::fs::rename(&tmp, &self.index_path)
+            .with_context(|| format!("Failed to write vector index {:?}", self.index_path))?;
+        Ok(())
+    }
+
+    /// Add missing documents to `index`; returns whether it changed
+    ///
+    /// Old revisions of the given documents are dropped by rebuilding once
+    /// they outnumber the current ones.
+    fn sync(&self, slot: &mut Option<HnswIndex>, documents: &[Document]) -> anyhow::Result<bool> {
+        let current: HashSet<String> = documents.iter().map(Document::key).collect();
+        let ids: HashSet<String> = documents.iter().map(|d| d.id.to_string()).collect();
+        let mut changed = false;
+
+        if let Some(index) = slot.as_ref() {
+            let stale = index
+                .iter()
+                .filter(|(key, _)| {
+                    !current.contains(*key) && key.split(':').next().is_some_and(|id| ids.contains(id))
+                })
+                .count();
+            if stale > current.len() {
+                let mut rebuilt = HnswIndex::new(index.dimensions());
+                for (key, vector) in index.iter() {
+                    let id = key.split(':').next().unwrap_or_default();
+                    if current.contains(key) || !ids.contains(id) {
+                        rebuilt.insert(key, vector.to_vec())?;
+                    }
+                }
+                *slot = Some(rebuilt);
+                changed = true;
+            }
+        }
+
+        let indexed: HashSet<String> = slot
+            .as_ref()
+            .map(|index| index.iter().map(|(key, _)| key.to_string()).collect())
+            .unwrap_or_default();
+        for document in documents {
+            let key = document.key();
+            if indexed.contains(&key) {
+                continue;
+            }
+            let vector = self.embedder.embed(&document.text)?;
+            slot.get_or_insert_with(|| HnswIndex::new(vector.len())).insert(key, vector)?;
+            changed = true;
+        }
+        Ok(changed)
+    }
+
+    /// Cosine similarity of `query` to the closest `documents` (blocking)
+    ///
+    /// Only the given documents are returned, so callers pass exactly the
+    /// entries the requesting tenant may see.
+    pub fn similarities(&self, documents: &[Document], query: &str, k: usize) -> anyhow::Result<HashMap<i32, f32>> {
+        let mut slot = self
+            .index
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Vector index lock poisoned: {}", e))?;
+        if self.sync(&mut slot, documents)? {
+            if let Some(index) = slot.as_ref() {
+                if let Err(e) = self.save(index) {
+                    tracing::warn!("Failed to persist vector index: {}", e);
+                }
+            }
+        }
+        let Some(index) = slot.as_ref() else {
+            return Ok(HashMap::new());
+        };
+
+        let query = self.embedder.embed(query)?;
+        let wanted: HashMap<String, i32> = documents.iter().map(|d| (d.key(), d.id)).collect();
+        // Other tenants' entries share the index; over-fetch so filtering keeps k hits
+        let fetch = (k * 4).max(32).min(index.len());
+        Ok(index
+            .search(&query, fetch, fetch * 2)
+            .into_iter()
+            .filter_map(|(key, similarity)| wanted.get(&key).map(|id| (*id, similarity)))
+            .take(k)
+            .collect())
+    }
+}
+
+/// Global vector store (`None` in keyword mode)
+static VECTOR_STORE: OnceLock<Option<Arc<VectorStore>>> = OnceLock::new();
+
+/// Get the vector store when vector retrieval is configured
+pub fn vector_store() -> Option<Arc<VectorStore>> {
+    VECTOR_STORE
+        .get_or_init(|| {
+            let config = VectorStoreConfig::from_env();
+            if config.mode == RetrievalMode::Keyword {
+                return None;
+            }
+            let embedder: Box<dyn TextEmbedder> = match config.embedder {
+                EmbedderKind::Gguf => Box::new(GgufEmbedder::new(config.model_path, config.threads)),
+                EmbedderKind::Hashing => Box::new(HashingEmbedder),
+            };
+            tracing::info!(
+                "Knowledge retrieval: vector ({}), index {:?}",
+                embedder.model(),
+                config.index_path
+            );
+            Some(Arc::new(VectorStore::open(embedder, config.index_path)))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents() -> Vec<Document> {
+        [
+            (1, "Grid column header: set the header text of a grid column"),
+            (2, "Transaction timeout: configure the save transaction timeout"),
+            (3, "Popup window: open a detail popup and return a value"),
+        ]
+        .into_iter()
+        .map(|(id, text)| Document { id, text: text.to_string() })
+        .collect()
+    }
+
+    fn index_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("kb-index-{}-{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_similarities_rank_and_persist() {
+        let path = index_path("persist");
+        let store = VectorStore::open(Box::new(HashingEmbedder), path.clone());
+        let hits = store.similarities(&documents(), "how do I change the grid header", 3).unwrap();
+        let best = hits.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        assert_eq!(*best.0, 1);
+
+        let reopened = VectorStore::open(Box::new(HashingEmbedder), path.clone());
+        assert_eq!(reopened.index.lock().unwrap().as_ref().unwrap().len(), 3);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_only_given_documents_are_returned() {
+        let path = index_path("scope");
+        let store = VectorStore::open(Box::new(HashingEmbedder), path.clone());
+        store.similarities(&documents(), "grid", 3).unwrap();
+
+        let visible: Vec<Document> = documents().into_iter().filter(|d| d.id != 1).collect();
+        let hits = store.similarities(&visible, "grid column header", 3).unwrap();
+        assert!(!hits.contains_key(&1));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_edited_entries_are_reindexed() {
+        let path = index_path("edit");
+        let store = VectorStore::open(Box::new(HashingEmbedder), path.clone());
+        let mut docs = documents();
+        store.similarities(&docs, "grid", 3).unwrap();
+
+        docs[1].text = "Calendar control: pick a date range".to_string();
+        let hits = store.similarities(&docs, "date range calendar", 1).unwrap();
+        assert_eq!(hits.keys().copied().collect::<Vec<_>>(), vec![2]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,321 @@
+//! WASM Validation Plugins
+//!
+//! Customer checks (in-house security scanner rules, naming conventions)
+//! uploaded by administrators as WebAssembly modules, so a customer can add
+//! proprietary rules without handing us the code. The modules run in the
+//! wasmi interpreter as pipeline pass `WasmPluginPass`.
+//!
+//! Plugin interface:
+//! - `memory`: the exported linear memory
+//! - `alloc(len: i32) -> i32`: a buffer of `len` bytes for the host to write into
+//! - `validate(xml_ptr, xml_len, js_ptr, js_len, intent_ptr, intent_len: i32) -> i64`:
+//!   checks the screen (the intent is JSON) and returns `(ptr << 32) | len` of its report
+//!
+//! The report is UTF-8 text with one finding per line, `error: ...`,
+//! `warning: ...` or `note: ...` (no prefix = warning); an empty report passes.
+//!
+//! Sandbox: a module may not import anything (no WASI, no host functions, so
+//! no files, network, clock or randomness). Every call runs in a fresh
+//! instance with an instruction budget (`WASM_PLUGIN_FUEL`) and a memory cap
+//! (`WASM_PLUGIN_MEMORY_MB`).
+
+use std::sync::{Arc, OnceLock, RwLock};
+
+use sea_orm::{DatabaseConnection, EntityTrait};
+use sha2::{Digest, Sha256};
+use wasmi::{Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+
+use crate::models::validation_plugins;
+
+/// Default instruction budget per call
+const DEFAULT_FUEL: u64 = 200_000_000;
+/// Default memory cap per instance
+const DEFAULT_MEMORY_MB: usize = 64;
+/// Longest report read back from a plugin
+const MAX_REPORT_BYTES: usize = 1024 * 1024;
+
+/// Severity of a plugin finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginSeverity {
+    Error,
+    Warning,
+    Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginFinding {
+    pub severity: PluginSeverity,
+    pub message: String,
+}
+
+/// A compiled plugin
+#[derive(Clone)]
+pub struct WasmPlugin {
+    pub name: String,
+    pub sha256: String,
+    module: Arc<Module>,
+}
+
+/// Resource limits of one call
+struct Sandbox {
+    limits: StoreLimits,
+}
+
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(|| {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        Engine::new(&config)
+    })
+}
+
+fn fuel() -> u64 {
+    std::env::var("WASM_PLUGIN_FUEL")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_FUEL)
+}
+
+fn memory_limit() -> usize {
+    std::env::var("WASM_PLUGIN_MEMORY_MB")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MEMORY_MB)
+        * 1024
+        * 1024
+}
+
+/// Hex SHA-256 of a module
+pub fn sha256_hex(wasm: &[u8]) -> String {
+    Sha256::digest(wasm).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl WasmPlugin {
+    /// Compile a module and check it implements the plugin interface
+    pub fn compile(name: impl Into<String>, wasm: &[u8]) -> Result<Self, String> {
+        let module = Module::new(engine(), wasm).map_err(|e| format!("Not a valid WebAssembly module: {}", e))?;
+        if let Some(import) = module.imports().next() {
+            return Err(format!(
+                "Plugins can't import host functions (imports {}::{})",
+                import.module(),
+                import.name()
+            ));
+        }
+
+        let plugin = Self {
+            name: name.into(),
+            sha256: sha256_hex(wasm),
+            module: Arc::new(module),
+        };
+        plugin.instantiate()?;
+        Ok(plugin)
+    }
+
+    /// Fresh instance with its memory, `alloc` and `validate`
+    #[allow(clippy::type_complexity)]
+    fn instantiate(
+        &self,
+    ) -> Result<(Store<Sandbox>, Memory, TypedFunc<i32, i32>, TypedFunc<(i32, i32, i32, i32, i32, i32), i64>), String> {
+        let sandbox = Sandbox {
+            limits: StoreLimitsBuilder::new().memory_size(memory_limit()).instances(1).build(),
+        };
+        let mut store = Store::new(engine(), sandbox);
+        store.limiter(|sandbox| &mut sandbox.limits);
+        store.set_fuel(fuel()).map_err(|e| e.to_string())?;
+
+        let instance = Linker::<Sandbox>::new(engine())
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| format!("Instantiation failed: {}", e))?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or("Missing export `memory`")?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&store, "alloc")
+            .map_err(|_| "Missing export `alloc(i32) -> i32`")?;
+        let validate = instance
+            .get_typed_func::<(i32, i32, i32, i32, i32, i32), i64>(&store, "validate")
+            .map_err(|_| "Missing export `validate(i32, i32, i32, i32, i32, i32) -> i64`")?;
+        Ok((store, memory, alloc, validate))
+    }
+
+    /// Run the plugin on a screen
+    pub fn validate(&self, xml: &str, js: &str, intent_json: &str) -> Result<Vec<PluginFinding>, String> {
+        let (mut store, memory, alloc, validate) = self.instantiate()?;
+        let trap = |e: wasmi::Error| match e.as_trap_code() {
+            Some(wasmi::core::TrapCode::OutOfFuel) => "instruction budget exhausted (endless loop?)".to_string(),
+            _ => e.to_string(),
+        };
+
+        let mut args = Vec::with_capacity(6);
+        for input in [xml, js, intent_json] {
+            let len = i32::try_from(input.len()).map_err(|_| "input too large".to_string())?;
+            let ptr = alloc.call(&mut store, len).map_err(trap)?;
+            memory
+                .write(&mut store, ptr as u32 as usize, input.as_bytes())
+                .map_err(|e| format!("alloc returned an invalid buffer: {}", e))?;
+            args.extend([ptr, len]);
+        }
+
+        let packed = validate
+            .call(&mut store, (args[0], args[1], args[2], args[3], args[4], args[5]))
+            .map_err(trap)?;
+        let (ptr, len) = ((packed as u64 >> 32) as usize, (packed as u64 & 0xffff_ffff) as usize);
+        if len > MAX_REPORT_BYTES {
+            return Err(format!("report of {} bytes exceeds the {} byte limit", len, MAX_REPORT_BYTES));
+        }
+        let mut report = vec![0u8; len];
+        memory
+            .read(&store, ptr, &mut report)
+            .map_err(|e| format!("report outside memory: {}", e))?;
+
+        Ok(Self::parse_report(&String::from_utf8_lossy(&report)))
+    }
+
+    fn parse_report(report: &str) -> Vec<PluginFinding> {
+        report
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (severity, message) = match line.split_once(':') {
+                    Some((prefix, rest)) => match prefix.trim().to_ascii_lowercase().as_str() {
+                        "error" => (PluginSeverity::Error, rest.trim()),
+                        "warning" | "warn" => (PluginSeverity::Warning, rest.trim()),
+                        "note" | "info" => (PluginSeverity::Note, rest.trim()),
+                        _ => (PluginSeverity::Warning, line),
+                    },
+                    None => (PluginSeverity::Warning, line),
+                };
+                PluginFinding {
+                    severity,
+                    message: message.to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Compiled enabled plugins, kept in step with `validation_plugins`
+pub struct WasmPlugins;
+
+fn loaded_slot() -> &'static RwLock<Arc<Vec<WasmPlugin>>> {
+    static LOADED: OnceLock<RwLock<Arc<Vec<WasmPlugin>>>> = OnceLock::new();
+    LOADED.get_or_init(Default::default)
+}
+
+impl WasmPlugins {
+    /// Enabled plugins as of the last `sync`
+    pub fn loaded() -> Arc<Vec<WasmPlugin>> {
+        loaded_slot().read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Reload when plugins were uploaded, changed or disabled (on any
+    /// instance); unchanged modules are not recompiled
+    pub async fn sync(db: &DatabaseConnection) {
+        let digests = match validation_plugins::Model::enabled_digests(db).await {
+            Ok(digests) => digests,
+            Err(e) => {
+                tracing::warn!("Could not read validation plugins: {}", e);
+                return;
+            }
+        };
+
+        let current = Self::loaded();
+        let unchanged = current.len() == digests.len()
+            && current
+                .iter()
+                .zip(&digests)
+                .all(|(plugin, (_, name, sha256))| plugin.name == *name && plugin.sha256 == *sha256);
+        if unchanged {
+            return;
+        }
+
+        let mut plugins = Vec::with_capacity(digests.len());
+        for (id, name, sha256) in digests {
+            if let Some(plugin) = current.iter().find(|p| p.sha256 == sha256) {
+                plugins.push(WasmPlugin { name, ..plugin.clone() });
+                continue;
+            }
+            let row = match validation_plugins::Entity::find_by_id(id).one(db).await {
+                Ok(Some(row)) => row,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("Could not load validation plugin '{}': {}", name, e);
+                    continue;
+                }
+            };
+            match WasmPlugin::compile(name, &row.wasm) {
+                Ok(plugin) => plugins.push(plugin),
+                Err(e) => tracing::error!("Validation plugin '{}' not loaded: {}", row.name, e),
+            }
+        }
+
+        tracing::info!("Loaded {} validation plugin(s)", plugins.len());
+        *loaded_slot().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(plugins);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Reports `error: password in log` when the script logs a password,
+    /// `note: checked` otherwise
+    pub(crate) const PASSWORD_CHECK: &str = r#"(module
+  (memory (export "memory") 2)
+  (global $next (mut i32) (i32.const 1024))
+  (data (i32.const 0) "error: password in log")
+  (data (i32.const 64) "note: checked")
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (local.get $ptr))
+  ;; naive search for "password" in the script
+  (func (export "validate") (param i32 i32 i32 i32 i32 i32) (result i64)
+    (local $i i32)
+    (block $done
+      (loop $scan
+        (br_if $done (i32.gt_s (i32.add (local.get $i) (i32.const 8)) (local.get 3)))
+        (if (i64.eq (i64.load (i32.add (local.get 2) (local.get $i))) (i64.const 0x64726f7773736170))
+          (then (return (i64.const 22))))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $scan)))
+    (i64.or (i64.shl (i64.const 64) (i64.const 32)) (i64.const 13))))"#;
+
+    #[test]
+    fn test_plugin_reports_findings() {
+        let plugin = WasmPlugin::compile("acme-security", &wat::parse_str(PASSWORD_CHECK).unwrap()).unwrap();
+
+        let findings = plugin.validate("<screen/>", "console.log(user.password);", "{}").unwrap();
+        assert_eq!(
+            findings,
+            vec![PluginFinding { severity: PluginSeverity::Error, message: "password in log".to_string() }]
+        );
+        let findings = plugin.validate("<screen/>", "fn_search();", "{}").unwrap();
+        assert_eq!(findings[0].severity, PluginSeverity::Note);
+        assert_eq!(plugin.sha256.len(), 64);
+    }
+
+    #[test]
+    fn test_sandbox_rejects_imports_and_endless_loops() {
+        let wasi = r#"(module (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32))))"#;
+        let err = WasmPlugin::compile("io", &wat::parse_str(wasi).unwrap()).err().unwrap();
+        assert_eq!(err, "Plugins can't import host functions (imports wasi_snapshot_preview1::fd_write)");
+
+        let err = WasmPlugin::compile("empty", &wat::parse_str("(module)").unwrap()).err().unwrap();
+        assert_eq!(err, "Missing export `memory`");
+
+        let spin = r#"(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) (i32.const 0))
+  (func (export "validate") (param i32 i32 i32 i32 i32 i32) (result i64)
+    (loop $forever (br $forever))
+    (i64.const 0)))"#;
+        let plugin = WasmPlugin::compile("spin", &wat::parse_str(spin).unwrap()).unwrap();
+        let err = plugin.validate("", "", "{}").unwrap_err();
+        assert_eq!(err, "instruction budget exhausted (endless loop?)");
+    }
+}
@@ -1,4 +1,5 @@
-use crate::domain::{GeneratedArtifacts, UiIntent};
+use crate::domain::{GeneratedArtifacts, NamingProfile, NamingVars, UiIntent};
+use crate::services::pipeline::{passes::OutputParser, screen_artifacts, ScreenSection};
 use anyhow::{anyhow, Result};
 use regex::Regex;
 
@@ -16,37 +17,45 @@ pub struct ValidatedArtifacts {
 
     /// Screen name for filename generation
     pub screen_name: Option<String>,
+
+    /// Further screens of a multi-screen response, validated separately
+    pub additional_screens: Vec<ScreenSection>,
 }
 
 impl ValidatedArtifacts {
-    /// Convert to GeneratedArtifacts with filenames based on screen_name
-    pub fn into_artifacts(self) -> GeneratedArtifacts {
-        let (xml_filename, js_filename) = if let Some(ref name) = self.screen_name {
-            // Convert screen_name to snake_case filename
-            let base_name = name
-                .to_lowercase()
-                .replace(' ', "_")
-                .replace('-', "_");
-            (
-                Some(format!("{}.xml", base_name)),
-                Some(format!("{}.js", base_name)),
-            )
-        } else {
-            (None, None)
+    /// Convert to GeneratedArtifacts with filenames from the naming profile
+    pub fn into_artifacts(self, naming: &NamingProfile, vars: &NamingVars) -> GeneratedArtifacts {
+        let (xml_filename, js_filename) = match self.screen_name {
+            Some(_) => {
+                let (xml, js) = naming.screen_files(vars);
+                (Some(xml), Some(js))
+            }
+            None => (None, None),
         };
 
+        let taken: Vec<&str> = xml_filename.iter().chain(js_filename.iter()).map(String::as_str).collect();
+        let additional_screens = screen_artifacts(self.additional_screens, naming, vars, &taken);
+
         GeneratedArtifacts {
             xml: Some(self.xml),
             javascript: Some(self.javascript),
             xml_filename,
             js_filename,
+            additional_screens,
+            print_layout: None,
+            print_layout_filename: None,
+            accessibility_report: None,
+            test_data: None,
+            test_data_filename: None,
         }
     }
 }
 
 impl From<ValidatedArtifacts> for GeneratedArtifacts {
     fn from(v: ValidatedArtifacts) -> Self {
-        v.into_artifacts()
+        let vars = NamingVars::new(chrono::Local::now().naive_local())
+            .with("screen_name", v.screen_name.clone().unwrap_or_default());
+        v.into_artifacts(&NamingProfile::default(), &vars)
     }
 }
 
@@ -56,6 +65,40 @@ pub struct XFrame5Validator;
 impl XFrame5Validator {
     /// Parse and validate LLM output
     pub fn parse_and_validate(raw: &str, intent: &UiIntent) -> Result<ValidatedArtifacts> {
+        // Several <screen> documents: validate each XML/JS pair
+        let mut screens = OutputParser::split_screens(raw);
+        if screens.len() > 1 {
+            let primary = screens
+                .iter()
+                .position(|screen| screen.matches_intent(intent))
+                .unwrap_or(0);
+            let main = screens.remove(primary);
+
+            let mut warnings = Self::validate_xml(&main.xml)?;
+            warnings.extend(Self::validate_js(&main.javascript, intent)?);
+
+            // Additional screens aren't described by the intent: structural checks only
+            for (i, screen) in screens.iter().enumerate() {
+                let label = screen.label(i);
+                let screen_intent = UiIntent::new(label.as_str(), intent.screen_type);
+                let mut screen_warnings = Self::validate_xml(&screen.xml)?;
+                if screen.javascript.is_empty() {
+                    screen_warnings.push("Warning: No JavaScript found for this screen".to_string());
+                } else {
+                    screen_warnings.extend(Self::validate_js(&screen.javascript, &screen_intent)?);
+                }
+                warnings.extend(screen_warnings.into_iter().map(|w| format!("[{}] {}", label, w)));
+            }
+
+            return Ok(ValidatedArtifacts {
+                xml: main.xml,
+                javascript: main.javascript,
+                warnings,
+                screen_name: Some(intent.screen_name.clone()),
+                additional_screens: screens,
+            });
+        }
+
         // 1. Split XML and JS sections
         let (xml, js) = Self::split_output(raw)?;
 
@@ -74,6 +117,7 @@ impl XFrame5Validator {
             javascript: js,
             warnings,
             screen_name: Some(intent.screen_name.clone()),
+            additional_screens: Vec::new(),
         })
     }
 
@@ -477,6 +521,7 @@ this.fn_save = function() {
             javascript: "// existing code".to_string(),
             warnings: vec![],
             screen_name: Some("test_screen".to_string()),
+            additional_screens: Vec::new(),
         };
 
         XFrame5Validator::post_process(&mut artifacts, &intent);
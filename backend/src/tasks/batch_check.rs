@@ -0,0 +1,42 @@
+//! Batch check task.
+//!
+//! Asks whether a batch job may run now, for cron entries starting nightly
+//! jobs. Fails (non-zero exit) on days off and during maintenance windows:
+//!
+//! ```sh
+//! cargo loco task batch_check job:nightly_regeneration company:acme && ./regenerate.sh
+//! ```
+
+use loco_rs::prelude::*;
+
+use crate::services::business_calendar::korea_now;
+use crate::services::{BatchDecision, BatchJob, BatchScheduleService};
+
+pub struct BatchCheckTask;
+
+#[async_trait]
+impl Task for BatchCheckTask {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "batch_check".to_string(),
+            detail: "Fail when a batch job must not run now (job:<nightly_regeneration|retention_cleanup|drift_detection>, optional company:<name>)".to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, vars: &task::Vars) -> Result<()> {
+        let raw = vars.cli_arg("job")?;
+        let job = BatchJob::parse(raw).ok_or_else(|| {
+            let jobs: Vec<&str> = BatchJob::ALL.iter().map(BatchJob::key).collect();
+            Error::string(&format!("Unknown job '{}', expected one of: {}", raw, jobs.join(", ")))
+        })?;
+        let company = vars.cli_arg("company").ok().map(String::as_str);
+
+        match BatchScheduleService::check(&ctx.db, job, company, korea_now()).await? {
+            BatchDecision::Run => {
+                tracing::info!("{} may run", job.key());
+                Ok(())
+            }
+            BatchDecision::Skip(reason) => Err(Error::string(&format!("{} skipped: {}", job.key(), reason))),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Client SDK task.
+//!
+//! Writes the OpenAPI document and the TypeScript client generated from it,
+//! for the IDE plugin and web console builds:
+//!
+//! ```sh
+//! cargo loco task client_sdk out:target/client-sdk
+//! ```
+
+use loco_rs::prelude::*;
+use std::path::Path;
+
+use crate::controllers::api_docs;
+use crate::services::client_sdk::TypeScriptClient;
+
+/// Default output directory
+const DEFAULT_OUT: &str = "target/client-sdk";
+
+pub struct ClientSdkTask;
+
+#[async_trait]
+impl Task for ClientSdkTask {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "client_sdk".to_string(),
+            detail: "Write openapi.json and the TypeScript client (out:<dir>, default target/client-sdk)".to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, vars: &task::Vars) -> Result<()> {
+        let out = Path::new(vars.cli_arg("out").map_or(DEFAULT_OUT, String::as_str));
+        let doc = api_docs::document(ctx)?;
+        let spec = serde_json::to_string_pretty(&doc)?;
+
+        let write = |name: &str, content: &str| {
+            std::fs::create_dir_all(out)
+                .and_then(|()| std::fs::write(out.join(name), content))
+                .map_err(|e| Error::string(&format!("Failed to write {}: {}", out.join(name).display(), e)))
+        };
+        write("openapi.json", &spec)?;
+        write("coder-client.ts", &TypeScriptClient::render(&doc))?;
+
+        tracing::info!("Client SDK written to {}", out.display());
+        Ok(())
+    }
+}
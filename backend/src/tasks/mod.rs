@@ -1,3 +1,9 @@
+pub mod batch_check;
+pub mod client_sdk;
 pub mod queue_processor;
+pub mod retention_cleanup;
 
+pub use batch_check::BatchCheckTask;
+pub use client_sdk::ClientSdkTask;
 pub use queue_processor::QueueProcessorTask;
+pub use retention_cleanup::RetentionCleanupTask;
@@ -0,0 +1,31 @@
+//! Retention cleanup task.
+//!
+//! Purges the artifacts of generations past their retention expiry. Started
+//! by cron behind the batch schedule check:
+//!
+//! ```sh
+//! cargo loco task batch_check job:retention_cleanup && cargo loco task retention_cleanup
+//! ```
+
+use chrono::Utc;
+use loco_rs::prelude::*;
+
+use crate::services::RetentionService;
+
+pub struct RetentionCleanupTask;
+
+#[async_trait]
+impl Task for RetentionCleanupTask {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "retention_cleanup".to_string(),
+            detail: "Purge generation artifacts past their retention expiry".to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, _vars: &task::Vars) -> Result<()> {
+        let purged = RetentionService::purge_expired(&ctx.db, Utc::now().into()).await?;
+        tracing::info!("Purged artifacts of {} expired generations", purged);
+        Ok(())
+    }
+}
@@ -5,14 +5,17 @@
 
 use loco_rs::prelude::*;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, Set,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::domain::{GenerateInput, GenerateOptions, GenerateStatus, RequestContext};
 use crate::models::_entities::generation_logs;
-use crate::services::{GenerationService, SpringGenerationService};
+use crate::models::users::known_user;
+use crate::services::sla;
+use crate::services::telemetry::{traced, Span, SpanContext, SpanKind};
+use crate::services::{GenerationService, GitPublisher, JobStatusService, Notifier, RetentionService, SpringGenerationService};
+use super::job_queue::job_queue;
 
 /// Worker arguments containing the job ID to process
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,12 +62,13 @@ impl BackgroundWorker<GenerationWorkerArgs> for GenerationWorker {
         active_job.status = Set("processing".to_string());
         active_job.started_at = Set(Some(chrono::Utc::now().into()));
         let job = active_job.update(&self.ctx.db).await?;
+        JobStatusService::notify();
 
         // Parse the request payload
         let payload = match &job.request_payload {
             Some(p) => p,
             None => {
-                let _ = update_job_failed(&self.ctx.db, &args.job_id, "No request payload").await;
+                let _ = update_job_failed(&self.ctx.db, &args.job_id, "No request payload", None).await;
                 return Ok(());
             }
         };
@@ -72,7 +76,7 @@ impl BackgroundWorker<GenerationWorkerArgs> for GenerationWorker {
         let request: GenerateJobRequest = match serde_json::from_str(payload) {
             Ok(r) => r,
             Err(e) => {
-                let _ = update_job_failed(&self.ctx.db, &args.job_id, &format!("Invalid payload: {}", e))
+                let _ = update_job_failed(&self.ctx.db, &args.job_id, &format!("Invalid payload: {}", e), None)
                     .await;
                 return Ok(());
             }
@@ -81,29 +85,32 @@ impl BackgroundWorker<GenerationWorkerArgs> for GenerationWorker {
         let start_time = std::time::Instant::now();
 
         // Process based on product type
-        let result = match request.product.as_str() {
-            "spring-backend" => {
-                process_spring_generation(&self.ctx.db, &request, job.user_id).await
-            }
-            _ => process_xframe5_generation(&self.ctx.db, &request, job.user_id).await,
-        };
+        let (result, stage_times) =
+            sla::record(process_traced(&self.ctx.db, &request, &args.job_id, job.user_id)).await;
 
         let generation_time_ms = start_time.elapsed().as_millis() as i32;
 
         // Update job with result
         match result {
             Ok((artifacts, warnings)) => {
+                let completed_at: sea_orm::prelude::DateTimeWithTimeZone = chrono::Utc::now().into();
+                let expire_at = RetentionService::expiry(&self.ctx.db, known_user(job.user_id), &job.product, completed_at).await?;
                 let mut active_job: generation_logs::ActiveModel = job.into();
                 active_job.status = Set("completed".to_string());
                 active_job.artifacts = Set(Some(artifacts));
                 active_job.warnings = Set(Some(serde_json::to_string(&warnings).unwrap_or_default()));
                 active_job.generation_time_ms = Set(Some(generation_time_ms));
-                active_job.completed_at = Set(Some(chrono::Utc::now().into()));
-                active_job.update(&self.ctx.db).await?;
+                active_job.completed_at = Set(Some(completed_at));
+                active_job.artifacts_expire_at = Set(expire_at);
+                stage_times.apply(&mut active_job);
+                let job = active_job.update(&self.ctx.db).await?;
+                JobStatusService::notify();
                 tracing::info!("Job {} completed in {}ms", args.job_id, generation_time_ms);
+                Notifier::job_finished(&self.ctx.db, &job);
+                GitPublisher::auto_push(&self.ctx.db, &job).await;
             }
             Err(e) => {
-                let _ = update_job_failed(&self.ctx.db, &args.job_id, &e.to_string()).await;
+                let _ = update_job_failed(&self.ctx.db, &args.job_id, &e.to_string(), error_code(&e)).await;
                 tracing::error!("Job {} failed: {}", args.job_id, e);
             }
         }
@@ -119,6 +126,34 @@ pub struct GenerateJobRequest {
     pub input: GenerateInput,
     pub options: GenerateOptions,
     pub context: RequestContext,
+
+    /// W3C traceparent of the enqueuing request (links the job span to it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_parent: Option<String>,
+}
+
+/// Process a job inside a `generation.job` span linked to the enqueuing request
+async fn process_traced(
+    db: &DatabaseConnection,
+    request: &GenerateJobRequest,
+    job_id: &str,
+    user_id: i32,
+) -> anyhow::Result<(String, Vec<String>)> {
+    let parent = request
+        .trace_parent
+        .as_deref()
+        .and_then(SpanContext::from_traceparent);
+    let span = Span::start("generation.job", SpanKind::Internal, parent.as_ref())
+        .with_attr("job_id", job_id)
+        .with_attr("product", request.product.as_str());
+
+    traced(span, async {
+        match request.product.as_str() {
+            "spring-backend" => process_spring_generation(db, request, user_id).await,
+            _ => process_xframe5_generation(db, request, user_id).await,
+        }
+    })
+    .await
 }
 
 /// Process xFrame5 UI generation
@@ -138,9 +173,7 @@ async fn process_xframe5_generation(
     .await?;
 
     if response.status == GenerateStatus::Error {
-        return Err(anyhow::anyhow!(
-            response.error.unwrap_or_else(|| "Unknown error".to_string())
-        ));
+        return Err(FailedGeneration::new(response.error, response.error_code).into());
     }
 
     let artifacts = serde_json::to_string(&response.artifacts)?;
@@ -163,20 +196,49 @@ async fn process_spring_generation(
     .await?;
 
     if response.status == GenerateStatus::Error {
-        return Err(anyhow::anyhow!(
-            response.error.unwrap_or_else(|| "Unknown error".to_string())
-        ));
+        return Err(FailedGeneration::new(response.error, response.error_code).into());
     }
 
     let artifacts = serde_json::to_string(&response.artifacts)?;
     Ok((artifacts, response.warnings))
 }
 
+/// Generation that finished with an error status
+#[derive(Debug)]
+struct FailedGeneration {
+    message: String,
+    /// Error code of the response (e.g. `provider_refusal`)
+    code: Option<String>,
+}
+
+impl FailedGeneration {
+    fn new(error: Option<String>, code: Option<String>) -> Self {
+        Self {
+            message: error.unwrap_or_else(|| "Unknown error".to_string()),
+            code,
+        }
+    }
+}
+
+impl std::fmt::Display for FailedGeneration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FailedGeneration {}
+
+/// Error code of a failed job, if the generation set one
+fn error_code(error: &anyhow::Error) -> Option<&str> {
+    error.downcast_ref::<FailedGeneration>().and_then(|f| f.code.as_deref())
+}
+
 /// Update job as failed
 async fn update_job_failed(
     db: &DatabaseConnection,
     job_id: &str,
     error: &str,
+    error_code: Option<&str>,
 ) -> anyhow::Result<()> {
     let job = generation_logs::Entity::find()
         .filter(generation_logs::Column::JobId.eq(job_id))
@@ -187,8 +249,12 @@ async fn update_job_failed(
         let mut active_job: generation_logs::ActiveModel = job.into();
         active_job.status = Set("failed".to_string());
         active_job.error_message = Set(Some(error.to_string()));
+        active_job.error_code = Set(error_code.map(|c| c.to_string()));
         active_job.completed_at = Set(Some(chrono::Utc::now().into()));
-        active_job.update(db).await?;
+        active_job.lease_expires_at = Set(None);
+        let job = active_job.update(db).await?;
+        JobStatusService::notify();
+        Notifier::job_finished(db, &job);
     }
 
     Ok(())
@@ -196,85 +262,98 @@ async fn update_job_failed(
 
 /// Job queue processor - runs continuously to process queued jobs
 ///
-/// This is an alternative to the Loco worker queue that uses the database directly
-/// for simpler on-premise deployment without Redis.
+/// Jobs are handed out by the configured [`JobQueue`] backend, so any number
+/// of instances can run the processor against the same database.
 pub struct JobQueueProcessor;
 
 impl JobQueueProcessor {
     /// Process the next queued job (returns true if a job was processed)
     pub async fn process_next(db: &DatabaseConnection) -> anyhow::Result<bool> {
-        // Find the next queued job (by priority then queued_at)
-        let job = generation_logs::Entity::find()
-            .filter(generation_logs::Column::Status.eq("queued"))
-            .order_by_asc(generation_logs::Column::Priority)
-            .order_by_asc(generation_logs::Column::QueuedAt)
-            .one(db)
-            .await?;
-
-        let job = match job {
-            Some(j) => j,
-            None => return Ok(false), // No jobs to process
+        let queue = job_queue();
+        let Some(job) = queue.claim(db).await? else {
+            return Ok(false); // No jobs to process
         };
+        JobStatusService::notify();
 
         let job_id = match &job.job_id {
             Some(id) => id.clone(),
             None => return Ok(false),
         };
 
-        tracing::info!("Dequeued job: {}", job_id);
+        tracing::info!("Dequeued job: {} (attempt {})", job_id, job.attempts);
+
+        // Keep the lease alive while the job runs
+        let heartbeat = {
+            let db = db.clone();
+            let job_id = job_id.clone();
+            let interval = queue.config().visibility / 3;
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    if let Err(e) = queue.extend(&db, &job_id).await {
+                        tracing::warn!("Could not renew lease of job {}: {}", job_id, e);
+                    }
+                }
+            })
+        };
 
-        // Mark as processing
-        let mut active_job: generation_logs::ActiveModel = job.clone().into();
-        active_job.status = Set("processing".to_string());
-        active_job.started_at = Set(Some(chrono::Utc::now().into()));
-        let job = active_job.update(db).await?;
+        let result = Self::run_claimed(db, job, &job_id).await;
+
+        heartbeat.abort();
+        if let Err(e) = queue.ack(&job_id).await {
+            tracing::warn!("Could not acknowledge job {}: {}", job_id, e);
+        }
 
+        result.map(|()| true)
+    }
+
+    /// Run a claimed job and store its result
+    async fn run_claimed(db: &DatabaseConnection, job: generation_logs::Model, job_id: &str) -> anyhow::Result<()> {
         // Parse request
         let payload = match &job.request_payload {
             Some(p) => p.clone(),
-            None => {
-                update_job_failed(db, &job_id, "No request payload").await?;
-                return Ok(true);
-            }
+            None => return update_job_failed(db, job_id, "No request payload", None).await,
         };
 
         let request: GenerateJobRequest = match serde_json::from_str(&payload) {
             Ok(r) => r,
-            Err(e) => {
-                update_job_failed(db, &job_id, &format!("Invalid payload: {}", e)).await?;
-                return Ok(true);
-            }
+            Err(e) => return update_job_failed(db, job_id, &format!("Invalid payload: {}", e), None).await,
         };
 
         let start_time = std::time::Instant::now();
 
         // Process
-        let result = match request.product.as_str() {
-            "spring-backend" => process_spring_generation(db, &request, job.user_id).await,
-            _ => process_xframe5_generation(db, &request, job.user_id).await,
-        };
+        let (result, stage_times) = sla::record(process_traced(db, &request, job_id, job.user_id)).await;
 
         let generation_time_ms = start_time.elapsed().as_millis() as i32;
 
         // Update result
         match result {
             Ok((artifacts, warnings)) => {
+                let completed_at: sea_orm::prelude::DateTimeWithTimeZone = chrono::Utc::now().into();
+                let expire_at = RetentionService::expiry(db, known_user(job.user_id), &job.product, completed_at).await?;
                 let mut active_job: generation_logs::ActiveModel = job.into();
                 active_job.status = Set("completed".to_string());
                 active_job.artifacts = Set(Some(artifacts));
                 active_job.warnings = Set(Some(serde_json::to_string(&warnings).unwrap_or_default()));
                 active_job.generation_time_ms = Set(Some(generation_time_ms));
-                active_job.completed_at = Set(Some(chrono::Utc::now().into()));
-                active_job.update(db).await?;
+                active_job.completed_at = Set(Some(completed_at));
+                active_job.artifacts_expire_at = Set(expire_at);
+                stage_times.apply(&mut active_job);
+                active_job.lease_expires_at = Set(None);
+                let job = active_job.update(db).await?;
+                JobStatusService::notify();
                 tracing::info!("Job {} completed in {}ms", job_id, generation_time_ms);
+                Notifier::job_finished(db, &job);
+                GitPublisher::auto_push(db, &job).await;
             }
             Err(e) => {
-                update_job_failed(db, &job_id, &e.to_string()).await?;
+                update_job_failed(db, job_id, &e.to_string(), error_code(&e)).await?;
                 tracing::error!("Job {} failed: {}", job_id, e);
             }
         }
 
-        Ok(true)
+        Ok(())
     }
 
     /// Get queue statistics
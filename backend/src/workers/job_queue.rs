@@ -0,0 +1,831 @@
+//! Job queue backends
+//!
+//! The `generation_logs` row stays the source of truth for a job (status,
+//! payload, result); a queue backend only decides which worker gets which
+//! job. Selected with `JOB_QUEUE_BACKEND`:
+//! - `db` (default): workers claim rows straight from `generation_logs`
+//! - `redis`: job IDs wait in a Redis sorted set (`REDIS_URL`), so idle
+//!   instances poll Redis instead of the database
+//!
+//! Either way a claim is an atomic conditional update of the row, so two
+//! instances never run the same job. A claim is a lease: the worker renews
+//! it while running, and a job whose lease expired (crashed or partitioned
+//! worker) becomes visible again after `JOB_QUEUE_VISIBILITY_SECS`. Jobs
+//! claimed `JOB_QUEUE_MAX_ATTEMPTS` times without finishing are failed.
+//!
+//! Jobs are handed out in queue order (priority, then age) unless the admin
+//! queue settings ask for fairness (see [`FairnessPolicy`]): users or
+//! projects then take turns, and an owner with `max_in_flight` running jobs
+//! waits until one of them finishes.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Select,
+};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::models::_entities::generation_logs::{self, Column, Entity};
+use crate::models::queue_settings;
+use crate::models::users::SYSTEM_USER_ID;
+use crate::services::redis_client::{RedisClient, Reply};
+
+/// Rows looked at per claim attempt (others may win the race for some)
+const CLAIM_CANDIDATES: u64 = 5;
+
+/// Claimable rows looked at when owners take turns
+const FAIR_CANDIDATES: u64 = 200;
+
+/// Latest job starts that decide whose turn it is
+const RECENT_STARTS: u64 = 100;
+
+/// How often the Redis queue re-adds claimable rows it may have missed
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
+
+const PENDING_KEY: &str = "coder:jobs:pending";
+const INFLIGHT_KEY: &str = "coder:jobs:inflight";
+
+/// Queue settings, read from the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// `db` or `redis` (`JOB_QUEUE_BACKEND`)
+    pub backend: String,
+
+    /// Redis URL (`REDIS_URL`, default `redis://127.0.0.1:6379`)
+    pub redis_url: String,
+
+    /// How long a claimed job stays hidden from other workers
+    /// (`JOB_QUEUE_VISIBILITY_SECS`, default 600)
+    pub visibility: Duration,
+
+    /// Claims before a job that never finishes is failed (`JOB_QUEUE_MAX_ATTEMPTS`, default 3)
+    pub max_attempts: i32,
+}
+
+impl QueueConfig {
+    pub fn from_env() -> Self {
+        let env_number = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+
+        Self {
+            backend: std::env::var("JOB_QUEUE_BACKEND")
+                .map(|b| b.trim().to_lowercase())
+                .unwrap_or_else(|_| "db".to_string()),
+            redis_url: std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+            visibility: Duration::from_secs(env_number("JOB_QUEUE_VISIBILITY_SECS", 600)),
+            max_attempts: env_number("JOB_QUEUE_MAX_ATTEMPTS", 3).min(100) as i32,
+        }
+    }
+}
+
+/// Distributes queued generation jobs between workers
+#[async_trait]
+pub trait JobQueue: Send + Sync {
+    /// Backend name for logging
+    fn name(&self) -> &'static str;
+
+    /// Settings the queue runs with
+    fn config(&self) -> &QueueConfig;
+
+    /// Announce a job whose row was just stored as `queued`
+    async fn push(&self, job: &generation_logs::Model) -> anyhow::Result<()>;
+
+    /// Claim the next job (already marked `processing`), `None` when idle
+    async fn claim(&self, db: &DatabaseConnection) -> anyhow::Result<Option<generation_logs::Model>>;
+
+    /// Renew the lease of a job that is still running
+    async fn extend(&self, db: &DatabaseConnection, job_id: &str) -> anyhow::Result<()>;
+
+    /// Forget a finished job
+    async fn ack(&self, job_id: &str) -> anyhow::Result<()>;
+}
+
+/// Queue backend of this process (configured from the environment on first use)
+pub fn job_queue() -> &'static dyn JobQueue {
+    static QUEUE: OnceLock<Box<dyn JobQueue>> = OnceLock::new();
+    QUEUE
+        .get_or_init(|| {
+            let config = QueueConfig::from_env();
+            let queue: Box<dyn JobQueue> = match config.backend.as_str() {
+                "redis" => match RedisClient::from_url(&config.redis_url) {
+                    Ok(client) => Box::new(RedisJobQueue::new(client, config)),
+                    Err(e) => {
+                        tracing::warn!("Invalid REDIS_URL ({}), using the database job queue", e);
+                        Box::new(DbJobQueue::new(config))
+                    }
+                },
+                "db" => Box::new(DbJobQueue::new(config)),
+                other => {
+                    tracing::warn!("Unknown JOB_QUEUE_BACKEND '{}', using the database job queue", other);
+                    Box::new(DbJobQueue::new(config))
+                }
+            };
+            tracing::info!(
+                "Job queue: {} (visibility {}s)",
+                queue.name(),
+                queue.config().visibility.as_secs()
+            );
+            queue
+        })
+        .as_ref()
+}
+
+/// Rows a worker may claim: queued, or processing with an expired lease
+fn claimable(now: chrono::DateTime<Utc>) -> Condition {
+    Condition::any().add(Column::Status.eq("queued")).add(
+        Condition::all()
+            .add(Column::Status.eq("processing"))
+            .add(Column::LeaseExpiresAt.lt(now)),
+    )
+}
+
+fn lease_until(visibility: Duration) -> chrono::DateTime<Utc> {
+    Utc::now() + chrono::Duration::from_std(visibility).unwrap_or_else(|_| chrono::Duration::minutes(10))
+}
+
+/// Atomically claim one row; `None` when another worker got it first
+async fn claim_row(
+    db: &DatabaseConnection,
+    id: i32,
+    config: &QueueConfig,
+) -> anyhow::Result<Option<generation_logs::Model>> {
+    let now = Utc::now();
+    let result = Entity::update_many()
+        .col_expr(Column::Status, Expr::value("processing"))
+        .col_expr(Column::StartedAt, Expr::value(now))
+        .col_expr(Column::LeaseExpiresAt, Expr::value(lease_until(config.visibility)))
+        .col_expr(Column::Attempts, Expr::col(Column::Attempts).add(1))
+        .filter(Column::Id.eq(id))
+        .filter(claimable(now))
+        .filter(Column::Attempts.lt(config.max_attempts))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Ok(None);
+    }
+    Ok(Entity::find_by_id(id).one(db).await?)
+}
+
+/// Fail jobs whose lease ran out after the last allowed attempt
+async fn fail_exhausted(db: &DatabaseConnection, config: &QueueConfig) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let result = Entity::update_many()
+        .col_expr(Column::Status, Expr::value("failed"))
+        .col_expr(
+            Column::ErrorMessage,
+            Expr::value(format!(
+                "Job did not finish after {} attempts (worker stopped responding)",
+                config.max_attempts
+            )),
+        )
+        .col_expr(Column::CompletedAt, Expr::value(now))
+        .filter(claimable(now))
+        .filter(Column::Attempts.gte(config.max_attempts))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected > 0 {
+        tracing::warn!("Failed {} job(s) that exhausted their attempts", result.rows_affected);
+        crate::services::JobStatusService::notify();
+        if let Err(e) = crate::services::Notifier::check_failure_spike(db).await {
+            tracing::warn!("Could not post failure spike to chat: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Renew the lease of a running job
+async fn renew_lease(db: &DatabaseConnection, job_id: &str, config: &QueueConfig) -> anyhow::Result<()> {
+    Entity::update_many()
+        .col_expr(Column::LeaseExpiresAt, Expr::value(lease_until(config.visibility)))
+        .filter(Column::JobId.eq(job_id))
+        .filter(Column::Status.eq("processing"))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Claimable rows in queue order
+fn claimable_query() -> Select<Entity> {
+    Entity::find()
+        .filter(Column::JobId.is_not_null())
+        .filter(claimable(Utc::now()))
+        .order_by_asc(Column::Priority)
+        .order_by_asc(Column::QueuedAt)
+}
+
+/// Claimable rows in queue order, in full
+async fn claim_candidates(db: &DatabaseConnection, limit: u64) -> anyhow::Result<Vec<generation_logs::Model>> {
+    Ok(claimable_query().limit(limit).all(db).await?)
+}
+
+/// A job as the scheduler sees it (no payload or artifacts)
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedJob {
+    pub id: i32,
+    pub job_id: Option<String>,
+    pub user_id: i32,
+    pub project: Option<String>,
+    pub priority: i32,
+    pub queued_at: Option<DateTimeWithTimeZone>,
+    pub started_at: Option<DateTimeWithTimeZone>,
+}
+
+type QueuedJobRow = (
+    i32,
+    Option<String>,
+    i32,
+    Option<String>,
+    i32,
+    Option<DateTimeWithTimeZone>,
+    Option<DateTimeWithTimeZone>,
+);
+
+impl From<QueuedJobRow> for QueuedJob {
+    fn from((id, job_id, user_id, project, priority, queued_at, started_at): QueuedJobRow) -> Self {
+        Self {
+            id,
+            job_id,
+            user_id,
+            project,
+            priority,
+            queued_at,
+            started_at,
+        }
+    }
+}
+
+/// Rows of `query` with the columns the scheduler needs
+async fn queued_jobs(db: &DatabaseConnection, query: Select<Entity>) -> anyhow::Result<Vec<QueuedJob>> {
+    let rows = query
+        .select_only()
+        .column(Column::Id)
+        .column(Column::JobId)
+        .column(Column::UserId)
+        .column(Column::Project)
+        .column(Column::Priority)
+        .column(Column::QueuedAt)
+        .column(Column::StartedAt)
+        .into_tuple::<QueuedJobRow>()
+        .all(db)
+        .await?;
+    Ok(rows.into_iter().map(QueuedJob::from).collect())
+}
+
+/// Whose jobs take turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fairness {
+    /// Queue order (priority, then age)
+    Off,
+    /// Users take turns
+    User,
+    /// Projects take turns (jobs without a project by their user)
+    Project,
+}
+
+/// How workers share the queue between users (admin queue settings)
+///
+/// With fairness on, claimable jobs are ordered by priority, then by how long
+/// ago their owner's latest job started (owners not served recently first),
+/// then by age: one user's 50 queued jobs alternate with everyone else's
+/// instead of running first. The turn order comes from the start times in
+/// `generation_logs`, so all instances share it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FairnessPolicy {
+    pub fairness: Fairness,
+    /// Running jobs per owner before its other jobs wait (0 = no cap)
+    pub max_in_flight: usize,
+}
+
+impl FairnessPolicy {
+    pub fn from_settings(settings: &queue_settings::Model) -> Self {
+        let fairness = match settings.fairness.as_str() {
+            "user" => Fairness::User,
+            "project" => Fairness::Project,
+            _ => Fairness::Off,
+        };
+        Self {
+            fairness,
+            max_in_flight: usize::try_from(settings.max_in_flight).unwrap_or(0),
+        }
+    }
+
+    /// Policy of the stored queue settings
+    pub async fn current(db: &DatabaseConnection) -> Self {
+        Self::from_settings(&queue_settings::Model::current(db).await)
+    }
+
+    /// Plain queue order: no turns, no cap
+    pub fn is_fifo(&self) -> bool {
+        self.fairness == Fairness::Off && self.max_in_flight == 0
+    }
+
+    /// Owner a job takes turns (and counts against the cap) as
+    ///
+    /// Anonymous jobs (stored under the system user) are owned by their
+    /// project, so unrelated anonymous callers do not share one turn.
+    fn owner(&self, job: &QueuedJob) -> String {
+        let project = job.project.as_deref().map(str::trim).filter(|project| !project.is_empty());
+        match project {
+            Some(project) if self.fairness == Fairness::Project || job.user_id == SYSTEM_USER_ID => {
+                format!("project:{}", project)
+            }
+            _ => format!("user:{}", job.user_id),
+        }
+    }
+
+    /// Candidates in claim order: owners at their cap are left out, the rest
+    /// take turns (`running` are jobs with a live lease, `recent` the latest
+    /// started jobs)
+    pub fn order(&self, candidates: Vec<QueuedJob>, running: &[QueuedJob], recent: &[QueuedJob]) -> Vec<QueuedJob> {
+        let mut in_flight: HashMap<String, usize> = HashMap::new();
+        for job in running {
+            *in_flight.entry(self.owner(job)).or_default() += 1;
+        }
+        let mut last_start: HashMap<String, DateTimeWithTimeZone> = HashMap::new();
+        for job in recent.iter().chain(running) {
+            if let Some(started_at) = job.started_at {
+                let latest = last_start.entry(self.owner(job)).or_insert(started_at);
+                *latest = (*latest).max(started_at);
+            }
+        }
+
+        let mut jobs: Vec<QueuedJob> = candidates
+            .into_iter()
+            .filter(|job| {
+                self.max_in_flight == 0 || in_flight.get(&self.owner(job)).copied().unwrap_or(0) < self.max_in_flight
+            })
+            .collect();
+        if self.fairness != Fairness::Off {
+            // Stable: an owner's own jobs keep their queue order
+            jobs.sort_by_cached_key(|job| (job.priority, last_start.get(&self.owner(job)).copied(), job.queued_at));
+        }
+        jobs
+    }
+
+    /// Claimable jobs in claim order (at most `CLAIM_CANDIDATES`)
+    async fn claim_order(&self, db: &DatabaseConnection) -> anyhow::Result<Vec<QueuedJob>> {
+        let limit = if self.is_fifo() { CLAIM_CANDIDATES } else { FAIR_CANDIDATES };
+        let candidates = queued_jobs(db, claimable_query().limit(limit)).await?;
+        let mut jobs = self.arrange(db, candidates).await?;
+        jobs.truncate(CLAIM_CANDIDATES as usize);
+        Ok(jobs)
+    }
+
+    /// Apply the policy to candidates in queue order
+    async fn arrange(&self, db: &DatabaseConnection, candidates: Vec<QueuedJob>) -> anyhow::Result<Vec<QueuedJob>> {
+        if self.is_fifo() || candidates.is_empty() {
+            return Ok(candidates);
+        }
+        let running = queued_jobs(
+            db,
+            Entity::find()
+                .filter(Column::Status.eq("processing"))
+                .filter(Column::LeaseExpiresAt.gt(Utc::now())),
+        )
+        .await?;
+        let recent = if self.fairness == Fairness::Off {
+            Vec::new()
+        } else {
+            queued_jobs(
+                db,
+                Entity::find()
+                    .filter(Column::StartedAt.is_not_null())
+                    .order_by_desc(Column::StartedAt)
+                    .limit(RECENT_STARTS),
+            )
+            .await?
+        };
+        Ok(self.order(candidates, &running, &recent))
+    }
+}
+
+/// Queue that claims rows straight from `generation_logs`
+pub struct DbJobQueue {
+    config: QueueConfig,
+}
+
+impl DbJobQueue {
+    pub fn new(config: QueueConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl JobQueue for DbJobQueue {
+    fn name(&self) -> &'static str {
+        "db"
+    }
+
+    fn config(&self) -> &QueueConfig {
+        &self.config
+    }
+
+    async fn push(&self, _job: &generation_logs::Model) -> anyhow::Result<()> {
+        // The stored row is the queue entry
+        Ok(())
+    }
+
+    async fn claim(&self, db: &DatabaseConnection) -> anyhow::Result<Option<generation_logs::Model>> {
+        fail_exhausted(db, &self.config).await?;
+
+        let policy = FairnessPolicy::current(db).await;
+        for candidate in policy.claim_order(db).await? {
+            if let Some(job) = claim_row(db, candidate.id, &self.config).await? {
+                return Ok(Some(job));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn extend(&self, db: &DatabaseConnection, job_id: &str) -> anyhow::Result<()> {
+        renew_lease(db, job_id, &self.config).await
+    }
+
+    async fn ack(&self, _job_id: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Queue that hands out job IDs from Redis
+///
+/// `coder:jobs:pending` is a sorted set scored by priority, then queue time;
+/// `coder:jobs:inflight` holds claimed IDs scored by their lease deadline.
+/// Rows the sorted sets lost (Redis restart, failed push) are re-added from
+/// the database whenever the queue runs dry, at most every 30 seconds.
+pub struct RedisJobQueue {
+    client: RedisClient,
+    config: QueueConfig,
+    last_reconcile: Mutex<Option<Instant>>,
+}
+
+impl RedisJobQueue {
+    pub fn new(client: RedisClient, config: QueueConfig) -> Self {
+        Self {
+            client,
+            config,
+            last_reconcile: Mutex::new(None),
+        }
+    }
+
+    /// Pending score: priority first, then queue time (both ascending)
+    fn score(job: &generation_logs::Model) -> String {
+        let queued_ms = job
+            .queued_at
+            .map(|t| t.timestamp_millis())
+            .unwrap_or_else(|| Utc::now().timestamp_millis());
+        (i64::from(job.priority) * 10_000_000_000_000 + queued_ms).to_string()
+    }
+
+    fn deadline_ms(&self) -> String {
+        lease_until(self.config.visibility).timestamp_millis().to_string()
+    }
+
+    /// Move in-flight IDs whose lease ran out back to pending
+    async fn requeue_expired(&self) -> anyhow::Result<()> {
+        let now = Utc::now().timestamp_millis().to_string();
+        let expired = self
+            .client
+            .command(&["ZRANGEBYSCORE", INFLIGHT_KEY, "-inf", &now, "LIMIT", "0", "20"])
+            .await?
+            .into_array();
+
+        for job_id in expired.into_iter().filter_map(Reply::into_string) {
+            // Only the instance that removes the entry requeues it
+            if self.client.command(&["ZREM", INFLIGHT_KEY, &job_id]).await? == Reply::Integer(1) {
+                self.client.command(&["ZADD", PENDING_KEY, "NX", "0", &job_id]).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-add claimable rows missing from Redis (throttled)
+    async fn reconcile(&self, db: &DatabaseConnection) -> anyhow::Result<bool> {
+        {
+            let mut last = self.last_reconcile.lock().await;
+            if last.is_some_and(|at| at.elapsed() < RECONCILE_INTERVAL) {
+                return Ok(false);
+            }
+            *last = Some(Instant::now());
+        }
+
+        let rows = claim_candidates(db, 100).await?;
+        for job in &rows {
+            if let Some(job_id) = &job.job_id {
+                self.client
+                    .command(&["ZADD", PENDING_KEY, "NX", &Self::score(job), job_id])
+                    .await?;
+            }
+        }
+        Ok(!rows.is_empty())
+    }
+
+    async fn pop(&self) -> anyhow::Result<Option<String>> {
+        let popped = self.client.command(&["ZPOPMIN", PENDING_KEY]).await?.into_array();
+        Ok(popped.into_iter().next().and_then(Reply::into_string))
+    }
+
+    /// Remove an ID from pending; true for the one instance that removed it
+    async fn take(&self, job_id: &str) -> anyhow::Result<bool> {
+        Ok(self.client.command(&["ZREM", PENDING_KEY, job_id]).await? == Reply::Integer(1))
+    }
+
+    /// Claim a job and track its lease in Redis
+    async fn claim_id(
+        &self,
+        db: &DatabaseConnection,
+        id: i32,
+        job_id: &str,
+    ) -> anyhow::Result<Option<generation_logs::Model>> {
+        let Some(job) = claim_row(db, id, &self.config).await? else {
+            return Ok(None);
+        };
+        self.client
+            .command(&["ZADD", INFLIGHT_KEY, &self.deadline_ms(), job_id])
+            .await?;
+        Ok(Some(job))
+    }
+
+    /// Claim in queue order: pop IDs from the front of pending
+    async fn claim_next(&self, db: &DatabaseConnection) -> anyhow::Result<Option<generation_logs::Model>> {
+        let mut reconciled = false;
+        for _ in 0..CLAIM_CANDIDATES {
+            let job_id = match self.pop().await? {
+                Some(job_id) => job_id,
+                None if !reconciled && self.reconcile(db).await? => {
+                    reconciled = true;
+                    continue;
+                }
+                None => return Ok(None),
+            };
+
+            // Cancelled, finished or already running jobs are simply dropped
+            let Some(row) = Entity::find().filter(Column::JobId.eq(&job_id)).one(db).await? else {
+                continue;
+            };
+            if let Some(job) = self.claim_id(db, row.id, &job_id).await? {
+                return Ok(Some(job));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Claim by the fairness policy: look at the front of pending, take the
+    /// ID whose owner's turn it is
+    async fn claim_fair(
+        &self,
+        db: &DatabaseConnection,
+        policy: &FairnessPolicy,
+    ) -> anyhow::Result<Option<generation_logs::Model>> {
+        let last = (FAIR_CANDIDATES - 1).to_string();
+        let mut ids = Vec::new();
+        for attempt in 0..2 {
+            ids = self
+                .client
+                .command(&["ZRANGE", PENDING_KEY, "0", &last])
+                .await?
+                .into_array()
+                .into_iter()
+                .filter_map(Reply::into_string)
+                .collect::<Vec<_>>();
+            if !ids.is_empty() || attempt == 1 || !self.reconcile(db).await? {
+                break;
+            }
+        }
+        if ids.is_empty() {
+            return Ok(None);
+        }
+
+        let candidates = queued_jobs(
+            db,
+            claimable_query().filter(Column::JobId.is_in(ids.iter().map(String::as_str))),
+        )
+        .await?;
+
+        // Cancelled, finished or already running jobs are simply dropped
+        let claimable_ids: HashSet<&str> = candidates.iter().filter_map(|job| job.job_id.as_deref()).collect();
+        for job_id in ids.iter().filter(|id| !claimable_ids.contains(id.as_str())) {
+            self.take(job_id).await?;
+        }
+
+        for candidate in policy.arrange(db, candidates).await?.into_iter().take(CLAIM_CANDIDATES as usize) {
+            let Some(job_id) = candidate.job_id.as_deref() else {
+                continue;
+            };
+            // Only the instance that removes the ID claims the job
+            if !self.take(job_id).await? {
+                continue;
+            }
+            if let Some(job) = self.claim_id(db, candidate.id, job_id).await? {
+                return Ok(Some(job));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl JobQueue for RedisJobQueue {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn config(&self) -> &QueueConfig {
+        &self.config
+    }
+
+    async fn push(&self, job: &generation_logs::Model) -> anyhow::Result<()> {
+        let Some(job_id) = &job.job_id else {
+            return Ok(());
+        };
+        self.client
+            .command(&["ZADD", PENDING_KEY, "NX", &Self::score(job), job_id])
+            .await?;
+        Ok(())
+    }
+
+    async fn claim(&self, db: &DatabaseConnection) -> anyhow::Result<Option<generation_logs::Model>> {
+        fail_exhausted(db, &self.config).await?;
+        self.requeue_expired().await?;
+
+        let policy = FairnessPolicy::current(db).await;
+        if policy.is_fifo() {
+            self.claim_next(db).await
+        } else {
+            self.claim_fair(db, &policy).await
+        }
+    }
+
+    async fn extend(&self, db: &DatabaseConnection, job_id: &str) -> anyhow::Result<()> {
+        renew_lease(db, job_id, &self.config).await?;
+        self.client
+            .command(&["ZADD", INFLIGHT_KEY, "XX", &self.deadline_ms(), job_id])
+            .await?;
+        Ok(())
+    }
+
+    async fn ack(&self, job_id: &str) -> anyhow::Result<()> {
+        self.client.command(&["ZREM", INFLIGHT_KEY, job_id]).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(priority: i32, queued_ms: i64) -> generation_logs::Model {
+        let at = chrono::DateTime::from_timestamp_millis(queued_ms).unwrap().fixed_offset();
+        generation_logs::Model {
+            created_at: at,
+            updated_at: at,
+            id: 1,
+            product: "xframe5-ui".to_string(),
+            input_type: "db_schema".to_string(),
+            ui_intent: "pending".to_string(),
+            template_version: 1,
+            status: "queued".to_string(),
+            artifacts: None,
+            warnings: None,
+            error_message: None,
+            error_code: None,
+            generation_time_ms: None,
+            user_id: 1,
+            job_id: Some("job".to_string()),
+            request_payload: None,
+            queued_at: Some(at),
+            started_at: None,
+            completed_at: None,
+            priority,
+            model_name: None,
+            provider: None,
+            quality_score: None,
+            knowledge_revisions: None,
+            company: None,
+            lease_expires_at: None,
+            attempts: 0,
+            llm_started_at: None,
+            llm_finished_at: None,
+            pipeline_finished_at: None,
+            project: None,
+            pipeline_profile: None,
+            pipeline_overrides: None,
+            replay_of: None,
+            issue_key: None,
+            canary_id: None,
+            canary_arm: None,
+            lineage: None,
+            artifacts_expire_at: None,
+            artifacts_purged_at: None,
+            prompt_tokens: None,
+            completion_tokens: None,
+            llm_latency_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_pending_score_orders_by_priority_then_age() {
+        let score = |p, ms| RedisJobQueue::score(&job(p, ms)).parse::<f64>().unwrap();
+        let now = 1_800_000_000_000;
+
+        assert!(score(1, now) < score(2, now - 60_000));
+        assert!(score(3, now - 1) < score(3, now));
+        // Scores stay exact as Redis doubles
+        assert_eq!(score(5, now), 5.0e13 + now as f64);
+    }
+
+    fn queued(id: i32, user_id: i32, project: Option<&str>, queued_min: i64) -> QueuedJob {
+        let at = |min: i64| chrono::DateTime::from_timestamp(1_800_000_000 + min * 60, 0).unwrap().fixed_offset();
+        QueuedJob {
+            id,
+            job_id: Some(format!("job-{}", id)),
+            user_id,
+            project: project.map(str::to_string),
+            priority: 5,
+            queued_at: Some(at(queued_min)),
+            started_at: None,
+        }
+    }
+
+    fn started(mut job: QueuedJob, started_min: i64) -> QueuedJob {
+        job.started_at = Some(chrono::DateTime::from_timestamp(1_800_000_000 + started_min * 60, 0).unwrap().fixed_offset());
+        job
+    }
+
+    fn ids(jobs: &[QueuedJob]) -> Vec<i32> {
+        jobs.iter().map(|job| job.id).collect()
+    }
+
+    #[test]
+    fn test_users_take_turns() {
+        // User 1 queued a batch before users 2 and 3
+        let candidates = vec![
+            queued(1, 1, None, 0),
+            queued(2, 1, None, 1),
+            queued(3, 2, None, 2),
+            queued(4, 3, None, 3),
+        ];
+        let recent = vec![started(queued(10, 1, None, -5), 4), started(queued(11, 3, None, -5), 2)];
+        let policy = FairnessPolicy { fairness: Fairness::User, max_in_flight: 0 };
+
+        // User 2 was never served, user 3 before user 1
+        assert_eq!(ids(&policy.order(candidates.clone(), &[], &recent)), vec![3, 4, 1, 2]);
+
+        // Priority still comes first
+        let mut urgent = candidates.clone();
+        urgent[1].priority = 1;
+        assert_eq!(ids(&policy.order(urgent, &[], &recent))[0], 2);
+
+        // Queue order when fairness is off
+        let fifo = FairnessPolicy { fairness: Fairness::Off, max_in_flight: 0 };
+        assert!(fifo.is_fifo());
+        assert_eq!(ids(&fifo.order(candidates, &[], &recent)), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_in_flight_cap_per_owner() {
+        let candidates = vec![
+            queued(1, 1, Some("billing"), 0),
+            queued(2, 2, Some("billing"), 1),
+            queued(3, 2, None, 2),
+        ];
+        let running = vec![started(queued(10, 1, Some("billing"), -5), 0)];
+
+        let by_user = FairnessPolicy { fairness: Fairness::Off, max_in_flight: 1 };
+        assert_eq!(ids(&by_user.order(candidates.clone(), &running, &[])), vec![2, 3]);
+
+        // Jobs without a project count as their user's
+        let by_project = FairnessPolicy { fairness: Fairness::Project, max_in_flight: 1 };
+        assert_eq!(ids(&by_project.order(candidates, &running, &[])), vec![3]);
+    }
+
+    #[test]
+    fn test_anonymous_jobs_take_turns_by_project() {
+        let anonymous = SYSTEM_USER_ID;
+        let candidates = vec![
+            queued(1, anonymous, Some("billing"), 0),
+            queued(2, anonymous, Some("billing"), 1),
+            queued(3, anonymous, Some("hr"), 2),
+            queued(4, 7, Some("billing"), 3),
+        ];
+        let running = vec![started(queued(10, anonymous, Some("billing"), -5), 0)];
+
+        // Billing's anonymous jobs wait for their running one, HR's do not
+        let policy = FairnessPolicy { fairness: Fairness::User, max_in_flight: 1 };
+        assert_eq!(ids(&policy.order(candidates, &running, &[])), vec![3, 4]);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod downloader;
 pub mod generation;
+pub mod job_queue;
 
 pub use generation::{GenerationWorker, GenerationWorkerArgs, JobQueueProcessor, QueueStats};
+pub use job_queue::{job_queue, JobQueue, QueueConfig};
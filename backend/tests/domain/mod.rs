@@ -72,6 +72,8 @@ fn test_screen_type_as_str() {
     assert_eq!(ScreenType::Detail.as_str(), "detail");
     assert_eq!(ScreenType::Popup.as_str(), "popup");
     assert_eq!(ScreenType::ListWithPopup.as_str(), "list_with_popup");
+    assert_eq!(ScreenType::Dashboard.as_str(), "dashboard");
+    assert_eq!(ScreenType::Wizard.as_str(), "wizard");
 }
 
 #[test]
@@ -284,7 +284,7 @@ async fn test_mock_llm_generates_valid_output() {
 
     // Generate should return valid xFrame5 output
     let prompt = "Generate member list screen";
-    let result = mock.generate(prompt).await.unwrap();
+    let result = mock.generate(prompt).await.unwrap().text;
 
     assert!(result.contains("--- XML ---"));
     assert!(result.contains("--- JS ---"));
@@ -317,7 +317,7 @@ async fn test_mock_llm_retry_simulation() {
     // Retry succeeds
     let second = mock.generate("test").await;
     assert!(second.is_ok());
-    assert!(second.unwrap().contains("--- XML ---"));
+    assert!(second.unwrap().text.contains("--- XML ---"));
 
     // Call count should be 2
     assert_eq!(mock.call_count(), 2);
@@ -596,9 +596,16 @@ fn test_generate_response_serialization() {
             javascript: Some("fn_search".to_string()),
             xml_filename: Some("test.xml".to_string()),
             js_filename: Some("test.js".to_string()),
+            additional_screens: vec![],
+            print_layout: None,
+            print_layout_filename: None,
+            accessibility_report: None,
+            test_data: None,
+            test_data_filename: None,
         }),
         warnings: vec!["Warning: TODO found".to_string()],
         error: None,
+        error_code: None,
         meta: ResponseMeta {
             generator: "xframe5-ui-v1".to_string(),
             timestamp: chrono::Utc::now(),
@@ -218,7 +218,7 @@ fn test_mock_backend_name_and_model() {
 #[tokio::test]
 async fn test_mock_backend_default_output() {
     let mock = MockLlmBackend::new();
-    let result = mock.generate("test prompt").await.unwrap();
+    let result = mock.generate("test prompt").await.unwrap().text;
 
     assert!(result.contains("--- XML ---"));
     assert!(result.contains("--- JS ---"));
@@ -233,9 +233,9 @@ async fn test_mock_backend_custom_responses() {
         MockResponse::Success("Response 2".to_string()),
     ]);
 
-    assert_eq!(mock.generate("").await.unwrap(), "Response 1");
-    assert_eq!(mock.generate("").await.unwrap(), "Response 2");
-    assert_eq!(mock.generate("").await.unwrap(), "Response 1"); // Cycles
+    assert_eq!(mock.generate("").await.unwrap().text, "Response 1");
+    assert_eq!(mock.generate("").await.unwrap().text, "Response 2");
+    assert_eq!(mock.generate("").await.unwrap().text, "Response 1"); // Cycles
 }
 
 #[tokio::test]
@@ -282,5 +282,5 @@ async fn test_mock_backend_fail_then_succeed() {
     // Second call succeeds
     let second = mock.generate("attempt 2").await;
     assert!(second.is_ok());
-    assert!(second.unwrap().contains("--- XML ---"));
+    assert!(second.unwrap().text.contains("--- XML ---"));
 }
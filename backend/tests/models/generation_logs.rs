@@ -1,5 +1,6 @@
 use coder::app::App;
-use coder::models::_entities::generation_logs;
+use coder::models::_entities::{generation_logs, users};
+use coder::workers::job_queue::{DbJobQueue, JobQueue, QueueConfig};
 use loco_rs::testing::prelude::*;
 use sea_orm::{ActiveModelTrait, EntityTrait, Set};
 use serial_test::serial;
@@ -161,3 +162,73 @@ async fn test_audit_log_no_input_data() {
     // The raw input (table name, column definitions, etc.) is NOT stored
     // This satisfies the privacy requirement from CLAUDE.md
 }
+
+#[tokio::test]
+#[serial]
+async fn test_generation_log_inherits_user_company() {
+    configure_insta!();
+
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let user = users::Entity::find_by_id(1).one(db).await.unwrap().unwrap();
+    let mut user: users::ActiveModel = user.into();
+    user.company = Set(Some("acme".to_string()));
+    user.update(db).await.unwrap();
+
+    let saved = generation_logs::ActiveModel {
+        product: Set("xframe5-ui".to_string()),
+        input_type: Set("db-schema".to_string()),
+        ui_intent: Set(r#"{"screen_name":"member_list"}"#.to_string()),
+        template_version: Set(1),
+        status: Set("success".to_string()),
+        user_id: Set(1),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    assert_eq!(saved.company.as_deref(), Some("acme"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_queued_job_is_claimed_once() {
+    configure_insta!();
+
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    generation_logs::ActiveModel {
+        product: Set("xframe5-ui".to_string()),
+        input_type: Set("db_schema".to_string()),
+        ui_intent: Set("pending".to_string()),
+        template_version: Set(1),
+        status: Set("queued".to_string()),
+        job_id: Set(Some("job-claim".to_string())),
+        queued_at: Set(Some(chrono::Utc::now().into())),
+        user_id: Set(1),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    let queue = DbJobQueue::new(QueueConfig {
+        backend: "db".to_string(),
+        redis_url: String::new(),
+        visibility: std::time::Duration::from_secs(600),
+        max_attempts: 3,
+    });
+
+    let claimed = queue.claim(db).await.unwrap().expect("job should be claimed");
+    assert_eq!(claimed.status, "processing");
+    assert_eq!(claimed.attempts, 1);
+    assert!(claimed.lease_expires_at.is_some());
+
+    // A second worker finds nothing while the lease is held
+    assert!(queue.claim(db).await.unwrap().is_none());
+}
@@ -0,0 +1,48 @@
+use coder::{
+    app::App,
+    models::{knowledge_base_revisions, knowledge_bases},
+};
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn saves_record_revisions() {
+    let boot = boot_test::<App>().await.unwrap();
+    let db = &boot.app_context.db;
+
+    let entry = knowledge_bases::ActiveModel {
+        name: Set("revision-test".to_string()),
+        category: Set("component".to_string()),
+        content: Set("Grid needs version=\"1.1\"".to_string()),
+        version: Set(Some(1)),
+        is_active: Set(Some(true)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+    assert_eq!(entry.version, Some(1));
+
+    // Content edit → new revision, version follows
+    let mut active = entry.into_active_model();
+    active.content = Set("Grid needs version=\"1.1\" and link_data".to_string());
+    let entry = active.update(db).await.unwrap();
+    assert_eq!(entry.version, Some(2));
+
+    // Status-only change is not a revision
+    let mut active = entry.into_active_model();
+    active.is_active = Set(Some(false));
+    let entry = active.update(db).await.unwrap();
+    assert_eq!(entry.version, Some(2));
+
+    let history = knowledge_base_revisions::Model::history(db, entry.id).await.unwrap();
+    assert_eq!(history.iter().map(|r| r.revision).collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(history[1].content, "Grid needs version=\"1.1\"");
+
+    let resolved = knowledge_base_revisions::Model::resolve(db, &[(entry.id, 1), (entry.id, 9)])
+        .await
+        .unwrap();
+    assert_eq!(resolved.len(), 1);
+}
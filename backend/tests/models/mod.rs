@@ -4,4 +4,6 @@ mod prompt_templates;
 mod company_rules;
 mod generation_logs;
 mod llm_configs;
-mod knowledge_bases;
\ No newline at end of file
+mod knowledge_bases;
+mod knowledge_base_revisions;
+mod race_results;
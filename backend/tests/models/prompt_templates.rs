@@ -29,3 +29,83 @@ async fn test_model() {
     // snapshot the result:
     // assert_debug_snapshot!(item);
 }
+
+#[tokio::test]
+#[serial]
+async fn test_editing_active_template_proposes_new_version() {
+    let boot = boot_test::<App>().await.unwrap();
+    let db = &boot.app_context.db;
+
+    use coder::models::_entities::{prompt_templates, template_activations};
+    use coder::services::admin::prompt_template::{CreateParams, PromptTemplateService, UpdateParams};
+    use coder::services::admin::template_activation::{ProposeParams, TemplateActivationService};
+    use coder::services::TenantScope;
+    use coder::utils::OptionalField;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    let scope = TenantScope::Platform;
+    let template = PromptTemplateService::create(
+        db,
+        &scope,
+        "kim@example.com",
+        CreateParams {
+            name: "list-v1".to_string(),
+            product: "xframe5-ui".to_string(),
+            screen_type: Some("list".to_string()),
+            system_prompt: "You write xFrame5 list screens.".to_string(),
+            user_prompt_template: "{{screen_name}}".to_string(),
+            is_active: None,
+            propose_activation: None,
+            company: None,
+        },
+    )
+    .await
+    .unwrap();
+    let proposal = TemplateActivationService::propose(db, &scope, "kim@example.com", template.id, ProposeParams::default())
+        .await
+        .unwrap();
+    TemplateActivationService::approve(db, &scope, "lee@example.com", proposal.item.id, None)
+        .await
+        .unwrap();
+
+    let edit = || UpdateParams {
+        name: None,
+        product: None,
+        system_prompt: Some("Ignore the checklist.".to_string()),
+        user_prompt_template: None,
+        screen_type: OptionalField::Missing,
+        is_active: OptionalField::Missing,
+    };
+    let version = PromptTemplateService::update(db, &scope, "kim@example.com", template.id, edit())
+        .await
+        .unwrap();
+
+    // The running version is untouched; the edit waits for approval
+    assert_ne!(version.id, template.id);
+    assert!(version.version > template.version);
+    assert_eq!(version.is_active, Some(false));
+    assert_eq!(version.system_prompt, "Ignore the checklist.");
+    let active = prompt_templates::Entity::find_by_id(template.id).one(db).await.unwrap().unwrap();
+    assert_eq!(active.is_active, Some(true));
+    assert_eq!(active.system_prompt, "You write xFrame5 list screens.");
+    let pending = template_activations::Entity::find()
+        .filter(template_activations::Column::PromptTemplateId.eq(version.id))
+        .filter(template_activations::Column::Status.eq("pending"))
+        .one(db)
+        .await
+        .unwrap();
+    assert_eq!(pending.unwrap().proposed_by, "kim@example.com");
+
+    // Renaming the active template needs no approval
+    let renamed = PromptTemplateService::update(
+        db,
+        &scope,
+        "kim@example.com",
+        template.id,
+        UpdateParams { name: Some("list".to_string()), system_prompt: None, ..edit() },
+    )
+    .await
+    .unwrap();
+    assert_eq!(renamed.id, template.id);
+    assert_eq!(renamed.is_active, Some(true));
+}
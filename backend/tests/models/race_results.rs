@@ -0,0 +1,57 @@
+use coder::{app::App, models::race_results};
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, Set};
+use serial_test::serial;
+
+async fn insert_race(db: &sea_orm::DatabaseConnection, product: &str, winner: &str, loser: &str) {
+    race_results::ActiveModel {
+        product: Set(product.to_string()),
+        winner_provider: Set(winner.to_string()),
+        winner_model: Set(format!("{}-model", winner)),
+        loser_provider: Set(loser.to_string()),
+        loser_model: Set(format!("{}-model", loser)),
+        loser_outcome: Set("cancelled".to_string()),
+        winner_ms: Set(1200),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn can_compute_win_rates() {
+    let boot = boot_test::<App>().await.unwrap();
+    let db = &boot.app_context.db;
+
+    insert_race(db, "race-rates", "local-llama-cpp", "groq").await;
+    insert_race(db, "race-rates", "groq", "local-llama-cpp").await;
+    insert_race(db, "race-rates", "groq", "local-llama-cpp").await;
+
+    let races = race_results::Model::recent(db, "race-rates", 20).await.unwrap();
+    let rates = race_results::Model::win_rates(&races);
+    assert_eq!(rates[0].provider, "groq");
+    assert_eq!((rates[0].races, rates[0].wins), (3, 2));
+    assert_eq!((rates[1].races, rates[1].wins), (3, 1));
+
+    // Too few races to route on
+    assert!(race_results::Model::dominant(db, "race-rates").await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn can_find_dominant_provider() {
+    let boot = boot_test::<App>().await.unwrap();
+    let db = &boot.app_context.db;
+
+    insert_race(db, "race-dominant", "local-llama-cpp", "groq").await;
+    for _ in 0..19 {
+        insert_race(db, "race-dominant", "groq", "local-llama-cpp").await;
+    }
+
+    let dominant = race_results::Model::dominant(db, "race-dominant").await.unwrap().unwrap();
+    assert_eq!(dominant.provider, "groq");
+    assert_eq!(dominant.model, "groq-model");
+    assert!(race_results::Model::dominant(db, "spring-backend").await.unwrap().is_none());
+}
@@ -0,0 +1,20 @@
+use coder::app::App;
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn openapi_documents_public_endpoints_only() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/api/openapi.json").await;
+        assert_eq!(res.status_code(), 200);
+        let doc: serde_json::Value = res.json();
+
+        let paths = doc["paths"].as_object().expect("paths");
+        assert!(paths["/agent/generate"]["post"]["requestBody"].is_object());
+        assert!(paths.contains_key("/agent/jobs/{job_id}"));
+        assert!(paths.contains_key("/api/company_rules/{id}"));
+        assert!(!paths.keys().any(|p| p.starts_with("/api/llm_configs") || p.starts_with("/admin")));
+    })
+    .await;
+}
@@ -1,16 +1,66 @@
 use coder::app::App;
+use coder::models::_entities::admin_audit_logs;
 use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
 use serial_test::serial;
+use serde_json::json;
+
+use super::prepare_data;
 
 #[tokio::test]
 #[serial]
 async fn can_get_company_rules() {
-    request::<App, _, _>(|request, _ctx| async move {
-        let res = request.get("/api/company_rules/").await;
+    request::<App, _, _>(|request, ctx| async move {
+        let res = request.get("/api/company_rules").await;
+        assert_eq!(res.status_code(), 401);
+
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.get("/api/company_rules").add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn company_rule_writes_are_tenant_scoped_and_audited() {
+    request::<App, _, _>(|request, ctx| async move {
+        let kim = prepare_data::init_user_login_as(&request, &ctx, "kim", "kim@acme.com").await;
+        let mut user = kim.user.clone().into_active_model();
+        user.company = Set(Some("acme".to_string()));
+        user.update(&ctx.db).await.unwrap();
+        let lee = prepare_data::init_user_login_as(&request, &ctx, "lee", "lee@globex.com").await;
+        let mut user = lee.user.clone().into_active_model();
+        user.company = Set(Some("globex".to_string()));
+        user.update(&ctx.db).await.unwrap();
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&kim.token);
+        let res = request
+            .post("/api/company_rules")
+            .add_header(auth_key, auth_value)
+            .json(&json!({ "name": "acme-rules", "naming_convention": "camelCase" }))
+            .await;
         assert_eq!(res.status_code(), 200);
+        let created: serde_json::Value = res.json();
+        assert_eq!(created["company"], "acme");
+        let id = created["id"].as_i64().unwrap();
+
+        let audit = admin_audit_logs::Entity::find().all(&ctx.db).await.unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].actor, "kim@acme.com");
+        assert_eq!(audit[0].action, "create");
+        assert_eq!(audit[0].company.as_deref(), Some("acme"));
+
+        // Another tenant neither sees nor changes the rule
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request.get("/api/company_rules").add_header(auth_key, auth_value).await;
+        let listed: serde_json::Value = res.json();
+        assert!(listed.as_array().unwrap().iter().all(|rule| rule["id"].as_i64() != Some(id)));
 
-        // you can assert content like this:
-        // assert_eq!(res.text(), "content");
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request.delete(&format!("/api/company_rules/{}", id)).add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 404);
     })
     .await;
 }
@@ -1,8 +1,11 @@
 use coder::app::App;
 use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
 use serial_test::serial;
 use serde_json::json;
 
+use super::prepare_data;
+
 #[tokio::test]
 #[serial]
 async fn can_get_health() {
@@ -150,3 +153,64 @@ async fn generate_accepts_query_sample_input() {
     })
     .await;
 }
+
+#[tokio::test]
+#[serial]
+async fn spring_module_rejects_duplicate_entities() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let entity = json!({
+            "type": "db_schema",
+            "table": "TB_MEMBER",
+            "columns": [{"name": "MEMBER_ID", "column_type": "VARCHAR(20)", "nullable": false, "pk": true}]
+        });
+        let payload = json!({
+            "entities": [entity.clone(), entity],
+            "context": {"project": "com.company.erp"}
+        });
+
+        let res = request
+            .post("/agent/generate/spring-module")
+            .json(&payload)
+            .await;
+
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn generate_requires_caller_once_users_belong_to_companies() {
+    request::<App, _, _>(|request, ctx| async move {
+        let payload = json!({
+            "product": "",
+            "input": {"type": "db_schema", "table": "member", "columns": []}
+        });
+
+        // Single-company deployment: anonymous calls are accepted
+        let res = request.post("/agent/generate").json(&payload).await;
+        assert_eq!(res.status_code(), 200);
+
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let mut user = logged_in.user.clone().into_active_model();
+        user.company = Set(Some("acme".to_string()));
+        user.update(&ctx.db).await.unwrap();
+
+        let res = request.post("/agent/generate").json(&payload).await;
+        assert_eq!(res.status_code(), 401);
+
+        let (auth_key, auth_value) = prepare_data::auth_header("not-a-token");
+        let res = request.post("/agent/generate").add_header(auth_key, auth_value).json(&payload).await;
+        assert_eq!(res.status_code(), 401);
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.post("/agent/generate").add_header(auth_key, auth_value).json(&payload).await;
+        assert_eq!(res.status_code(), 200);
+
+        // The API key works as well
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.user.api_key);
+        let res = request.post("/agent/generate").add_header(auth_key, auth_value).json(&payload).await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
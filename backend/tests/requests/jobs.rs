@@ -0,0 +1,109 @@
+use axum::http::{HeaderName, HeaderValue};
+use coder::app::App;
+use coder::models::_entities::generation_logs;
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, Set};
+use serial_test::serial;
+
+use super::prepare_data;
+
+async fn insert_job(ctx: &loco_rs::app::AppContext, job_id: &str, status: &str) {
+    generation_logs::ActiveModel {
+        product: Set("xframe5-ui".to_string()),
+        input_type: Set("db-schema".to_string()),
+        ui_intent: Set("{}".to_string()),
+        template_version: Set(1),
+        status: Set(status.to_string()),
+        job_id: Set(Some(job_id.to_string())),
+        user_id: Set(1),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn job_status_answers_not_modified_for_matching_etag() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        insert_job(&ctx, "job-etag", "completed").await;
+
+        let res = request.get("/agent/jobs/job-etag").await;
+        assert_eq!(res.status_code(), 200);
+        let etag = res.headers().get("etag").expect("ETag header").clone();
+
+        // A finished job returns at once even when long-polling
+        let res = request
+            .get("/agent/jobs/job-etag?wait=30s")
+            .add_header(HeaderName::from_static("if-none-match"), etag.clone())
+            .await;
+        assert_eq!(res.status_code(), 304);
+
+        let res = request
+            .get("/agent/jobs/job-etag")
+            .add_header(HeaderName::from_static("if-none-match"), HeaderValue::from_static("\"stale\""))
+            .await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn batch_status_lists_known_and_missing_jobs() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        insert_job(&ctx, "job-a", "queued").await;
+        insert_job(&ctx, "job-b", "failed").await;
+
+        let res = request.get("/agent/jobs?ids=job-b,job-a,job-x").await;
+        assert_eq!(res.status_code(), 200);
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["jobs"][0]["job_id"], "job-b");
+        assert_eq!(body["jobs"][1]["status"], "queued");
+        assert_eq!(body["missing"], serde_json::json!(["job-x"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn scaling_hints_report_queue_depth() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        insert_job(&ctx, "job-q1", "queued").await;
+        insert_job(&ctx, "job-q2", "queued").await;
+
+        let res = request.get("/agent/queue/scaling").await;
+        assert_eq!(res.status_code(), 200);
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["queue_depth"], 2);
+        assert_eq!(body["desired_replicas"], 2);
+
+        let res = request.get("/agent/queue/scaling?format=prometheus").await;
+        assert_eq!(res.status_code(), 200);
+        assert!(res.text().contains("coder_queue_depth 2\n"));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn push_needs_the_user_who_queued_the_job() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        insert_job(&ctx, "job-push", "completed").await;
+
+        let res = request.post("/agent/jobs/job-push/push").await;
+        assert_eq!(res.status_code(), 401);
+
+        // The job belongs to the system user, not to the caller
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.post("/agent/jobs/job-push/push").add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
@@ -2,15 +2,19 @@ use coder::app::App;
 use loco_rs::testing::prelude::*;
 use serial_test::serial;
 
+use super::prepare_data;
+
 #[tokio::test]
 #[serial]
 async fn can_get_knowledge_bases() {
-    request::<App, _, _>(|request, _ctx| async move {
-        let res = request.get("/api/knowledge_bases/").await;
-        assert_eq!(res.status_code(), 200);
+    request::<App, _, _>(|request, ctx| async move {
+        let res = request.get("/api/knowledge_bases").await;
+        assert_eq!(res.status_code(), 401);
 
-        // you can assert content like this:
-        // assert_eq!(res.text(), "content");
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.get("/api/knowledge_bases").add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 200);
     })
     .await;
 }
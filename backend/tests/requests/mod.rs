@@ -3,9 +3,17 @@ mod prepare_data;
 
 pub mod prompt_template;
 pub mod company_rule;
+pub mod project_output_setting;
+pub mod naming_profile;
+pub mod pipeline_profile;
+pub mod saved_intent;
 pub mod generate;
 pub mod generation_log;
+pub mod my_generation;
 pub mod llm_config;
 pub mod knowledge_base;
 pub mod review;
-pub mod qa;
\ No newline at end of file
+pub mod qa;
+pub mod jobs;
+pub mod screen_registry;
+pub mod api_docs;
@@ -0,0 +1,58 @@
+use coder::app::App;
+use loco_rs::testing::prelude::*;
+use loco_rs::TestServer;
+use serial_test::serial;
+use serde_json::json;
+
+use super::prepare_data::{self, LoggedInUser};
+
+async fn queue_generation(request: &TestServer, caller: &LoggedInUser, table: &str) -> String {
+    let payload = json!({
+        "product": "xframe5-ui",
+        "input": {
+            "type": "db_schema",
+            "table": table,
+            "columns": [{"name": "id", "column_type": "INTEGER", "nullable": false, "pk": true}],
+            "primary_keys": ["id"],
+            "foreign_keys": []
+        }
+    });
+    let (auth_key, auth_value) = prepare_data::auth_header(&caller.token);
+    let res = request
+        .post("/agent/generate?mode=async")
+        .add_header(auth_key, auth_value)
+        .json(&payload)
+        .await;
+    assert_eq!(res.status_code(), 200);
+    let body: serde_json::Value = res.json();
+    body["job_id"].as_str().unwrap().to_string()
+}
+
+async fn my_job_ids(request: &TestServer, caller: &LoggedInUser) -> Vec<String> {
+    let (auth_key, auth_value) = prepare_data::auth_header(&caller.token);
+    let res = request.get("/api/my/generations").add_header(auth_key, auth_value).await;
+    assert_eq!(res.status_code(), 200);
+    let body: serde_json::Value = res.json();
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|item| item["job_id"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn each_user_sees_their_own_generations() {
+    request::<App, _, _>(|request, ctx| async move {
+        let kim = prepare_data::init_user_login_as(&request, &ctx, "kim", "kim@example.com").await;
+        let lee = prepare_data::init_user_login_as(&request, &ctx, "lee", "lee@example.com").await;
+
+        let kim_job = queue_generation(&request, &kim, "member").await;
+        let lee_job = queue_generation(&request, &lee, "orders").await;
+
+        assert_eq!(my_job_ids(&request, &kim).await, vec![kim_job]);
+        assert_eq!(my_job_ids(&request, &lee).await, vec![lee_job]);
+    })
+    .await;
+}
@@ -0,0 +1,84 @@
+use coder::app::App;
+use coder::models::_entities::admin_audit_logs;
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use serial_test::serial;
+use serde_json::json;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn can_get_naming_profiles() {
+    request::<App, _, _>(|request, ctx| async move {
+        let res = request.get("/api/naming_profiles").await;
+        assert_eq!(res.status_code(), 401);
+
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.get("/api/naming_profiles").add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_unknown_placeholder() {
+    request::<App, _, _>(|request, ctx| async move {
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request
+            .post("/api/naming_profiles")
+            .add_header(auth_key, auth_value)
+            .json(&json!({
+                "name": "legacy",
+                "xml_template": "SCR_{ENTITY}_{SCREEN_ID}.xml"
+            }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn naming_profile_writes_are_tenant_scoped_and_audited() {
+    request::<App, _, _>(|request, ctx| async move {
+        let kim = prepare_data::init_user_login_as(&request, &ctx, "kim", "kim@acme.com").await;
+        let mut user = kim.user.clone().into_active_model();
+        user.company = Set(Some("acme".to_string()));
+        user.update(&ctx.db).await.unwrap();
+        let lee = prepare_data::init_user_login_as(&request, &ctx, "lee", "lee@globex.com").await;
+        let mut user = lee.user.clone().into_active_model();
+        user.company = Set(Some("globex".to_string()));
+        user.update(&ctx.db).await.unwrap();
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&kim.token);
+        let res = request
+            .post("/api/naming_profiles")
+            .add_header(auth_key, auth_value)
+            .json(&json!({ "name": "acme-legacy", "xml_template": "SCR_{ENTITY}_{TYPE}.xml" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let created: serde_json::Value = res.json();
+        assert_eq!(created["company"], "acme");
+        let id = created["id"].as_i64().unwrap();
+
+        let audit = admin_audit_logs::Entity::find().all(&ctx.db).await.unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].entity_type, "naming_profiles");
+        assert_eq!(audit[0].company.as_deref(), Some("acme"));
+
+        // Another tenant neither sees nor changes the profile
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request.get("/api/naming_profiles").add_header(auth_key, auth_value).await;
+        let listed: serde_json::Value = res.json();
+        assert!(listed.as_array().unwrap().iter().all(|p| p["id"].as_i64() != Some(id)));
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request.delete(&format!("/api/naming_profiles/{}", id)).add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
@@ -11,9 +11,14 @@ pub struct LoggedInUser {
 }
 
 pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedInUser {
+    init_user_login_as(request, ctx, "loco", USER_EMAIL).await
+}
+
+/// Register, verify and log in a user of the given name and email
+pub async fn init_user_login_as(request: &TestServer, ctx: &AppContext, name: &str, email: &str) -> LoggedInUser {
     let register_payload = serde_json::json!({
-        "name": "loco",
-        "email": USER_EMAIL,
+        "name": name,
+        "email": email,
         "password": USER_PASSWORD
     });
 
@@ -22,7 +27,7 @@ pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedIn
         .post("/api/auth/register")
         .json(&register_payload)
         .await;
-    let user = users::Model::find_by_email(&ctx.db, USER_EMAIL)
+    let user = users::Model::find_by_email(&ctx.db, email)
         .await
         .unwrap();
 
@@ -36,7 +41,7 @@ pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedIn
     let response = request
         .post("/api/auth/login")
         .json(&serde_json::json!({
-            "email": USER_EMAIL,
+            "email": email,
             "password": USER_PASSWORD
         }))
         .await;
@@ -58,7 +63,7 @@ pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedIn
         .to_string();
 
     LoggedInUser {
-        user: users::Model::find_by_email(&ctx.db, USER_EMAIL)
+        user: users::Model::find_by_email(&ctx.db, email)
             .await
             .unwrap(),
         token,
@@ -2,15 +2,52 @@ use coder::app::App;
 use loco_rs::testing::prelude::*;
 use serial_test::serial;
 
+use super::prepare_data;
+
 #[tokio::test]
 #[serial]
 async fn can_get_prompt_templates() {
-    request::<App, _, _>(|request, _ctx| async move {
-        let res = request.get("/api/prompt_templates/").await;
+    request::<App, _, _>(|request, ctx| async move {
+        let res = request.get("/api/prompt_templates").await;
+        assert_eq!(res.status_code(), 401);
+
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.get("/api/prompt_templates").add_header(auth_key, auth_value).await;
         assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
 
-        // you can assert content like this:
-        // assert_eq!(res.text(), "content");
+#[tokio::test]
+#[serial]
+async fn can_get_prompt_template_coverage() {
+    request::<App, _, _>(|request, ctx| async move {
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request
+            .post("/api/prompt_templates")
+            .add_header(auth_key, auth_value)
+            .json(&serde_json::json!({
+                "name": "coverage-test",
+                "product": "xframe5-ui",
+                "screen_type": "list",
+                "system_prompt": "[ ] pnl_header panel with title\n1. EVERY grid MUST have: version=\"1.1\"",
+                "user_prompt_template": "{{screen_name}}"
+            }))
+            .await;
+        let created: serde_json::Value = res.json();
+        let id = created["id"].as_i64().unwrap();
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request
+            .get(&format!("/api/prompt_templates/{}/coverage", id))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["uncovered"], 1);
+        assert_eq!(report["rules"][1]["status"], "enforced");
     })
     .await;
 }
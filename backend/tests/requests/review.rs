@@ -157,3 +157,61 @@ public class MemberController {
     })
     .await;
 }
+
+#[tokio::test]
+#[serial]
+async fn review_apply_returns_patched_code() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let payload = json!({
+            "code": "<screen>\n  <pushbutton onclick=\"fn_search\"/>\n</screen>\n",
+            "patches": [
+                "--- a/a.xml\n+++ b/a.xml\n@@ -2,1 +2,1 @@\n-  <pushbutton onclick=\"fn_search\"/>\n+  <pushbutton on_click=\"eventfunc:fn_search()\"/>\n",
+                "@@ -1,1 +1,1 @@\n-<stale>\n+<screen>\n"
+            ]
+        });
+
+        let res = request
+            .post("/agent/review/apply")
+            .json(&payload)
+            .await;
+
+        assert_eq!(res.status_code(), 200);
+
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["status"], "success");
+        assert_eq!(body["applied"], 1);
+        assert!(body["code"].as_str().unwrap().contains("eventfunc:fn_search()"));
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn review_gate_reports_error_exit_code_for_empty_code() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let payload = json!({
+            "product": "xframe5-ui",
+            "input": {
+                "code": ""
+            },
+            "policy": {
+                "max_errors": 0,
+                "categories": { "security": { "max_warnings": 0 } }
+            }
+        });
+
+        let res = request
+            .post("/agent/review/gate")
+            .json(&payload)
+            .await;
+
+        assert_eq!(res.status_code(), 200);
+
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["status"], "error");
+        assert_eq!(body["exit_code"], 2);
+        assert!(body["gate"].is_null());
+    })
+    .await;
+}
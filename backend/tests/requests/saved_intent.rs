@@ -0,0 +1,134 @@
+use coder::app::App;
+use coder::models::_entities::{admin_audit_logs, generation_logs};
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use serial_test::serial;
+
+use super::prepare_data;
+
+fn member_list(name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "name": name,
+        "tags": ["member"],
+        "intent": {
+            "screen_name": "member_list",
+            "screen_type": "list",
+            "datasets": [],
+            "grids": [],
+            "actions": [],
+            "notes": null
+        }
+    })
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_saved_intents() {
+    request::<App, _, _>(|request, ctx| async move {
+        let res = request.get("/api/saved_intents?tag=member").await;
+        assert_eq!(res.status_code(), 401);
+
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request.get("/api/saved_intents?tag=member").add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_duplicate_intent() {
+    request::<App, _, _>(|request, ctx| async move {
+        let logged_in = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request
+            .post("/api/saved_intents")
+            .add_header(auth_key, auth_value)
+            .json(&member_list("member_list"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&logged_in.token);
+        let res = request
+            .post("/api/saved_intents")
+            .add_header(auth_key, auth_value)
+            .json(&member_list("member_list_copy"))
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn saved_intents_and_diff_logs_are_tenant_scoped() {
+    request::<App, _, _>(|request, ctx| async move {
+        let kim = prepare_data::init_user_login_as(&request, &ctx, "kim", "kim@acme.com").await;
+        let mut user = kim.user.clone().into_active_model();
+        user.company = Set(Some("acme".to_string()));
+        user.update(&ctx.db).await.unwrap();
+        let lee = prepare_data::init_user_login_as(&request, &ctx, "lee", "lee@globex.com").await;
+        let mut user = lee.user.clone().into_active_model();
+        user.company = Set(Some("globex".to_string()));
+        user.update(&ctx.db).await.unwrap();
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&kim.token);
+        let res = request
+            .post("/api/saved_intents")
+            .add_header(auth_key, auth_value)
+            .json(&member_list("member_list"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let created: serde_json::Value = res.json();
+        assert_eq!(created["company"], "acme");
+        let id = created["id"].as_i64().unwrap();
+
+        let audit = admin_audit_logs::Entity::find().all(&ctx.db).await.unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].entity_type, "saved_intents");
+        assert_eq!(audit[0].company.as_deref(), Some("acme"));
+
+        // Another tenant may save the same structure and cannot see kim's entry
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request
+            .post("/api/saved_intents")
+            .add_header(auth_key, auth_value)
+            .json(&member_list("globex_member_list"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request.get(&format!("/api/saved_intents/{}", id)).add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 404);
+
+        // Only the caller's own generation logs can be diffed against
+        let log = |user_id: i32, company: &str| generation_logs::ActiveModel {
+            product: Set("xframe5-ui".to_string()),
+            input_type: Set("db-schema".to_string()),
+            ui_intent: Set(r#"{"screen_name":"member_list"}"#.to_string()),
+            template_version: Set(1),
+            status: Set("success".to_string()),
+            user_id: Set(user_id),
+            company: Set(Some(company.to_string())),
+            ..Default::default()
+        };
+        let own = log(kim.user.id, "acme").insert(&ctx.db).await.unwrap();
+        let foreign = log(lee.user.id, "globex").insert(&ctx.db).await.unwrap();
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&kim.token);
+        let res = request
+            .get(&format!("/api/saved_intents/{}/diff/{}", id, own.id))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&kim.token);
+        let res = request
+            .get(&format!("/api/saved_intents/{}/diff/{}", id, foreign.id))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
@@ -0,0 +1,45 @@
+use coder::app::App;
+use coder::models::_entities::screen_registrations;
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, Set};
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn teammates_screen_is_taken_for_other_callers() {
+    request::<App, _, _>(|request, ctx| async move {
+        let kim = prepare_data::init_user_login_as(&request, &ctx, "kim", "kim@example.com").await;
+        let lee = prepare_data::init_user_login_as(&request, &ctx, "lee", "lee@example.com").await;
+
+        // Kim delivered SCREEN_MEMBER_LIST in the project
+        screen_registrations::ActiveModel {
+            project: Set("erp".to_string()),
+            screen_id: Set("SCREEN_MEMBER_LIST".to_string()),
+            xml_filename: Set(Some("member_list.xml".to_string())),
+            js_filename: Set(Some("member_list.js".to_string())),
+            user_id: Set(kim.user.id),
+            ..Default::default()
+        }
+        .insert(&ctx.db)
+        .await
+        .unwrap();
+
+        let url = "/agent/screens/check?project=erp&screen_id=SCREEN_MEMBER_LIST";
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&lee.token);
+        let res = request.get(url).add_header(auth_key, auth_value).await;
+        assert_eq!(res.status_code(), 200);
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["available"], false);
+        assert_eq!(body["taken_by"]["owner"], "kim");
+        assert_eq!(body["suggestion"], "SCREEN_MEMBER_LIST_2");
+
+        let (auth_key, auth_value) = prepare_data::auth_header(&kim.token);
+        let res = request.get(url).add_header(auth_key, auth_value).await;
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["available"], true);
+    })
+    .await;
+}
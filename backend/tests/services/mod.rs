@@ -307,6 +307,7 @@ fn test_xframe5_post_process_adds_stubs() {
         javascript: "// existing".to_string(),
         warnings: vec![],
         screen_name: Some("test_list".to_string()),
+        additional_screens: Vec::new(),
     };
 
     XFrame5Validator::post_process(&mut artifacts, &intent);
@@ -0,0 +1,22 @@
+#!/bin/bash
+# Enterprise Code Generator - Download Swagger UI for /api/docs
+#
+# Run on a machine with internet access; the files land in
+# backend/assets/static/swagger-ui/ and are served locally (air-gapped sites
+# copy that directory to the server).
+
+set -e
+
+VERSION="${1:-5.17.14}"
+SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
+TARGET="$SCRIPT_DIR/../backend/assets/static/swagger-ui"
+TMP="$(mktemp -d)"
+trap 'rm -rf "$TMP"' EXIT
+
+echo "Downloading swagger-ui-dist $VERSION..."
+curl -fsSL "https://registry.npmjs.org/swagger-ui-dist/-/swagger-ui-dist-$VERSION.tgz" -o "$TMP/swagger-ui.tgz"
+tar -xzf "$TMP/swagger-ui.tgz" -C "$TMP"
+
+mkdir -p "$TARGET"
+cp "$TMP/package/swagger-ui.css" "$TMP/package/swagger-ui-bundle.js" "$TARGET/"
+echo "Installed to $TARGET"